@@ -602,4 +602,8 @@ impl StatusItemView for ActivityIndicator {
         _: &mut Context<Self>,
     ) {
     }
+
+    fn segment_name(&self, _: &App) -> Option<SharedString> {
+        Some("activity".into())
+    }
 }
@@ -7,6 +7,7 @@ use crate::thread_store::ThreadStore;
 use crate::tool_use::{PendingToolUseStatus, ToolUse, ToolUseStatus};
 use crate::ui::{AgentNotification, AgentNotificationEvent, ContextPill};
 use crate::AssistantPanel;
+use assistant_context_editor::humanize_token_count;
 use assistant_settings::AssistantSettings;
 use collections::HashMap;
 use editor::{Editor, MultiBuffer};
@@ -27,7 +28,10 @@ use std::sync::Arc;
 use std::time::Duration;
 use text::ToPoint;
 use theme::ThemeSettings;
-use ui::{prelude::*, Disclosure, IconButton, KeyBinding, Scrollbar, ScrollbarState, Tooltip};
+use ui::{
+    prelude::*, ContextMenu, Disclosure, IconButton, KeyBinding, PopoverMenu, Scrollbar,
+    ScrollbarState, Tooltip,
+};
 use util::ResultExt as _;
 use workspace::{OpenOptions, Workspace};
 
@@ -48,6 +52,7 @@ pub struct ActiveThread {
     editing_message: Option<(MessageId, EditMessageState)>,
     expanded_tool_uses: HashMap<LanguageModelToolUseId, bool>,
     expanded_thinking_segments: HashMap<(MessageId, usize), bool>,
+    compaction_summary_expanded: bool,
     last_error: Option<ThreadError>,
     notifications: Vec<WindowHandle<AgentNotification>>,
     _subscriptions: Vec<Subscription>,
@@ -252,6 +257,7 @@ impl ActiveThread {
             rendered_tool_use_labels: HashMap::default(),
             expanded_tool_uses: HashMap::default(),
             expanded_thinking_segments: HashMap::default(),
+            compaction_summary_expanded: false,
             list_state: list_state.clone(),
             scrollbar_state: ScrollbarState::new(list_state),
             editing_message: None,
@@ -503,8 +509,7 @@ impl ActiveThread {
                         Task::ready(anyhow::Ok(Vec::new()))
                     };
 
-                    let model_registry = LanguageModelRegistry::read_global(cx);
-                    if let Some(model) = model_registry.active_model() {
+                    if let Some(model) = self.thread.read(cx).model(cx) {
                         cx.spawn(async move |this, cx| {
                             let updated_context = context_update_task.await?;
 
@@ -522,6 +527,10 @@ impl ActiveThread {
                 }
             }
             ThreadEvent::CheckpointChanged => cx.notify(),
+            ThreadEvent::Compacted => {
+                self.save_thread(cx);
+                cx.notify();
+            }
         }
     }
 
@@ -709,8 +718,7 @@ impl ActiveThread {
             cx.notify();
             return;
         }
-        let model_registry = LanguageModelRegistry::read_global(cx);
-        let Some(model) = model_registry.active_model() else {
+        let Some(model) = self.thread.read(cx).model(cx) else {
             return;
         };
 
@@ -790,6 +798,25 @@ impl ActiveThread {
         let checkpoint = thread.checkpoint_for_message(message_id);
         let context = thread.context_for_message(message_id);
         let tool_uses = thread.tool_uses_for_message(message_id, cx);
+        // Label each response with the model that generated it, since requests can fall back
+        // to other models when the thread's model is unavailable.
+        let token_usage_label = thread.message_token_usage(message_id).map(|message_usage| {
+            let mut label = message_usage.model_id.clone();
+            let total_tokens = message_usage.usage.total_tokens();
+            if total_tokens > 0 {
+                label.push_str(&format!(
+                    " · {} tokens",
+                    humanize_token_count(total_tokens as usize)
+                ));
+            }
+            if let Some(pricing) = AssistantSettings::get_global(cx)
+                .model_pricing
+                .get(&message_usage.model_id)
+            {
+                label.push_str(&format!(" · ${:.2}", pricing.cost(&message_usage.usage)));
+            }
+            label
+        });
 
         // Don't render user messages that are just there for returning tool results.
         if message.role == Role::User && thread.message_has_tool_results(message_id) {
@@ -805,8 +832,15 @@ impl ActiveThread {
             .filter(|(id, _)| *id == message_id)
             .map(|(_, state)| state.editor.clone());
 
+        let is_assistant_message = message.role == Role::Assistant;
         let first_message = ix == 0;
         let is_last_message = ix == self.messages.len() - 1;
+        let is_branch_point = thread
+            .branched_from()
+            .map_or(false, |branch_point| branch_point.message_id == message_id);
+        let is_compaction_point = thread
+            .compaction()
+            .map_or(false, |compaction| compaction.up_to == message_id);
 
         let colors = cx.theme().colors();
         let active_color = colors.element_active;
@@ -1002,73 +1036,86 @@ impl ActiveThread {
                                 )
                                 .child(
                                     h_flex()
-                                        // DL: To double-check whether we want to fully remove
-                                        // the editing feature from meassages. Checkpoint sort of
-                                        // solve the same problem.
-                                        .invisible()
                                         .gap_1()
-                                        .when_some(
-                                            edit_message_editor.clone(),
-                                            |this, edit_message_editor| {
-                                                let focus_handle =
-                                                    edit_message_editor.focus_handle(cx);
-                                                this.child(
-                                                    Button::new("cancel-edit-message", "Cancel")
-                                                        .label_size(LabelSize::Small)
-                                                        .key_binding(
-                                                            KeyBinding::for_action_in(
-                                                                &menu::Cancel,
-                                                                &focus_handle,
-                                                                window,
-                                                                cx,
+                                        .child(self.render_branch_button(ix, message_id, cx))
+                                        .child(
+                                            h_flex()
+                                                // DL: To double-check whether we want to fully remove
+                                                // the editing feature from meassages. Checkpoint sort of
+                                                // solve the same problem.
+                                                .invisible()
+                                                .gap_1()
+                                                .when_some(
+                                                    edit_message_editor.clone(),
+                                                    |this, edit_message_editor| {
+                                                        let focus_handle =
+                                                            edit_message_editor.focus_handle(cx);
+                                                        this.child(
+                                                            Button::new(
+                                                                "cancel-edit-message",
+                                                                "Cancel",
                                                             )
-                                                            .map(|kb| kb.size(rems_from_px(12.))),
-                                                        )
-                                                        .on_click(
-                                                            cx.listener(Self::handle_cancel_click),
-                                                        ),
-                                                )
-                                                .child(
-                                                    Button::new(
-                                                        "confirm-edit-message",
-                                                        "Regenerate",
-                                                    )
-                                                    .label_size(LabelSize::Small)
-                                                    .key_binding(
-                                                        KeyBinding::for_action_in(
-                                                            &menu::Confirm,
-                                                            &focus_handle,
-                                                            window,
-                                                            cx,
+                                                            .label_size(LabelSize::Small)
+                                                            .key_binding(
+                                                                KeyBinding::for_action_in(
+                                                                    &menu::Cancel,
+                                                                    &focus_handle,
+                                                                    window,
+                                                                    cx,
+                                                                )
+                                                                .map(|kb| {
+                                                                    kb.size(rems_from_px(12.))
+                                                                }),
+                                                            )
+                                                            .on_click(cx.listener(
+                                                                Self::handle_cancel_click,
+                                                            )),
                                                         )
-                                                        .map(|kb| kb.size(rems_from_px(12.))),
-                                                    )
-                                                    .on_click(
-                                                        cx.listener(Self::handle_regenerate_click),
-                                                    ),
-                                                )
-                                            },
-                                        )
-                                        .when(
-                                            edit_message_editor.is_none() && allow_editing_message,
-                                            |this| {
-                                                this.child(
-                                                    Button::new("edit-message", "Edit")
-                                                        .label_size(LabelSize::Small)
-                                                        .on_click(cx.listener({
-                                                            let message_segments =
-                                                                message.segments.clone();
-                                                            move |this, _, window, cx| {
-                                                                this.start_editing_message(
-                                                                    message_id,
-                                                                    &message_segments,
+                                                        .child(
+                                                            Button::new(
+                                                                "confirm-edit-message",
+                                                                "Regenerate",
+                                                            )
+                                                            .label_size(LabelSize::Small)
+                                                            .key_binding(
+                                                                KeyBinding::for_action_in(
+                                                                    &menu::Confirm,
+                                                                    &focus_handle,
                                                                     window,
                                                                     cx,
-                                                                );
-                                                            }
-                                                        })),
+                                                                )
+                                                                .map(|kb| {
+                                                                    kb.size(rems_from_px(12.))
+                                                                }),
+                                                            )
+                                                            .on_click(cx.listener(
+                                                                Self::handle_regenerate_click,
+                                                            )),
+                                                        )
+                                                    },
                                                 )
-                                            },
+                                                .when(
+                                                    edit_message_editor.is_none()
+                                                        && allow_editing_message,
+                                                    |this| {
+                                                        this.child(
+                                                            Button::new("edit-message", "Edit")
+                                                                .label_size(LabelSize::Small)
+                                                                .on_click(cx.listener({
+                                                                    let message_segments =
+                                                                        message.segments.clone();
+                                                                    move |this, _, window, cx| {
+                                                                        this.start_editing_message(
+                                                                            message_id,
+                                                                            &message_segments,
+                                                                            window,
+                                                                            cx,
+                                                                        );
+                                                                    }
+                                                                })),
+                                                        )
+                                                    },
+                                                ),
                                         ),
                                 ),
                         )
@@ -1083,14 +1130,48 @@ impl ActiveThread {
                 .border_color(cx.theme().colors().border_variant)
                 .child(message_content)
                 .when(!tool_uses.is_empty(), |parent| {
+                    let in_progress_count = tool_uses
+                        .iter()
+                        .filter(|tool_use| {
+                            matches!(
+                                tool_use.status,
+                                ToolUseStatus::Running | ToolUseStatus::Queued
+                            )
+                        })
+                        .count();
+
                     parent.child(
-                        v_flex().children(
-                            tool_uses
-                                .into_iter()
-                                .map(|tool_use| self.render_tool_use(tool_use, cx)),
-                        ),
+                        v_flex()
+                            .when(in_progress_count > 1, |parent| {
+                                parent.child(
+                                    Label::new(format!(
+                                        "Running {in_progress_count} tools in parallel"
+                                    ))
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted),
+                                )
+                            })
+                            .children(
+                                tool_uses
+                                    .into_iter()
+                                    .map(|tool_use| self.render_tool_use(tool_use, cx)),
+                            ),
                     )
-                }),
+                })
+                .child(
+                    h_flex()
+                        .pb_2()
+                        .gap_1()
+                        .justify_between()
+                        .child(div().when_some(token_usage_label, |parent, label| {
+                            parent.child(
+                                Label::new(label)
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted),
+                            )
+                        }))
+                        .child(self.render_branch_button(ix, message_id, cx)),
+                ),
             Role::System => div().id(("message-container", ix)).py_1().px_2().child(
                 v_flex()
                     .bg(colors.editor_background)
@@ -1105,6 +1186,12 @@ impl ActiveThread {
                 parent.child(self.render_rules_item(cx))
             })
             .when_some(checkpoint, |parent, checkpoint| {
+                // Assistant messages only have checkpoints when their tools modified files.
+                let restore_label = if is_assistant_message {
+                    "Restore to Before This Step"
+                } else {
+                    "Restore Checkpoint"
+                };
                 let mut is_pending = false;
                 let mut error = None;
                 if let Some(last_restore_checkpoint) =
@@ -1121,7 +1208,7 @@ impl ActiveThread {
                 }
 
                 let restore_checkpoint_button =
-                    Button::new(("restore-checkpoint", ix), "Restore Checkpoint")
+                    Button::new(("restore-checkpoint", ix), restore_label)
                         .icon(if error.is_some() {
                             IconName::XCircle
                         } else {
@@ -1174,6 +1261,12 @@ impl ActiveThread {
                 )
             })
             .child(styled_message)
+            .when(is_branch_point, |parent| {
+                parent.child(self.render_branch_point(cx))
+            })
+            .when(is_compaction_point, |parent| {
+                parent.children(self.render_compaction_point(cx))
+            })
             .when(
                 is_last_message && !self.thread.read(cx).is_generating(),
                 |parent| parent.child(feedback_items),
@@ -1181,6 +1274,155 @@ impl ActiveThread {
             .into_any()
     }
 
+    fn render_branch_button(
+        &self,
+        ix: usize,
+        message_id: MessageId,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        IconButton::new(("branch-from-message", ix), IconName::GitBranch)
+            .icon_size(IconSize::XSmall)
+            .icon_color(Color::Ignored)
+            .shape(ui::IconButtonShape::Square)
+            .disabled(self.thread.read(cx).is_generating())
+            .tooltip(Tooltip::text("Branch from Here"))
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.branch_from_message(message_id, window, cx);
+            }))
+    }
+
+    fn branch_from_message(
+        &mut self,
+        message_id: MessageId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                if let Some(panel) = workspace.panel::<AssistantPanel>(cx) {
+                    panel.update(cx, |panel, cx| panel.branch_thread(message_id, window, cx));
+                }
+            })
+            .log_err();
+    }
+
+    /// Renders the divider between the messages this thread shares with the thread it was
+    /// branched from, and the ones it doesn't.
+    fn render_branch_point(&self, cx: &Context<Self>) -> impl IntoElement {
+        let parent_id = self
+            .thread
+            .read(cx)
+            .branched_from()
+            .map(|branch_point| branch_point.thread_id.clone());
+        let parent_summary = parent_id.as_ref().and_then(|parent_id| {
+            self.thread_store
+                .read(cx)
+                .thread_metadata(parent_id)
+                .map(|thread| thread.summary.clone())
+        });
+
+        let branch_button = match parent_summary {
+            Some(summary) => Button::new("branched-from", format!("Branched from “{summary}”"))
+                .icon(IconName::GitBranch)
+                .icon_size(IconSize::XSmall)
+                .icon_position(IconPosition::Start)
+                .label_size(LabelSize::XSmall)
+                .tooltip(Tooltip::text("Messages above are shared with this thread"))
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    let Some(parent_id) = parent_id.clone() else {
+                        return;
+                    };
+                    this.workspace
+                        .update(cx, |workspace, cx| {
+                            if let Some(panel) = workspace.panel::<AssistantPanel>(cx) {
+                                panel.update(cx, |panel, cx| {
+                                    panel
+                                        .open_thread(&parent_id, window, cx)
+                                        .detach_and_log_err(cx)
+                                });
+                            }
+                        })
+                        .log_err();
+                })),
+            None => Button::new("branched-from", "Branched from a deleted thread")
+                .icon(IconName::GitBranch)
+                .icon_size(IconSize::XSmall)
+                .icon_position(IconPosition::Start)
+                .label_size(LabelSize::XSmall)
+                .disabled(true),
+        };
+
+        h_flex()
+            .pt_2p5()
+            .px_2p5()
+            .w_full()
+            .gap_1()
+            .child(ui::Divider::horizontal())
+            .child(branch_button)
+            .child(ui::Divider::horizontal())
+    }
+
+    /// Renders the divider below the last message that the thread's compaction summary covers.
+    fn render_compaction_point(&self, cx: &Context<Self>) -> Option<impl IntoElement> {
+        let compaction = self.thread.read(cx).compaction()?;
+        let label = match compaction.tokens_saved {
+            Some(tokens_saved) => format!(
+                "Summarized above, saving {} tokens",
+                humanize_token_count(tokens_saved)
+            ),
+            None => "Summarized above".to_string(),
+        };
+        let expanded = self.compaction_summary_expanded;
+
+        Some(
+            v_flex()
+                .pt_2p5()
+                .px_2p5()
+                .w_full()
+                .gap_1()
+                .child(
+                    h_flex()
+                        .w_full()
+                        .gap_1()
+                        .child(ui::Divider::horizontal())
+                        .child(
+                            Button::new("compaction-summary", label)
+                                .icon(if expanded {
+                                    IconName::ChevronDown
+                                } else {
+                                    IconName::ChevronRight
+                                })
+                                .icon_size(IconSize::XSmall)
+                                .icon_position(IconPosition::Start)
+                                .label_size(LabelSize::XSmall)
+                                .tooltip(Tooltip::text(
+                                    "Messages above are sent to the model as a summary",
+                                ))
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.compaction_summary_expanded =
+                                        !this.compaction_summary_expanded;
+                                    cx.notify();
+                                })),
+                        )
+                        .child(ui::Divider::horizontal()),
+                )
+                .when(expanded, |parent| {
+                    parent.child(
+                        div()
+                            .p_2()
+                            .rounded_md()
+                            .border_1()
+                            .border_color(cx.theme().colors().border_variant)
+                            .child(
+                                Label::new(compaction.summary.clone())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                }),
+        )
+    }
+
     fn render_message_content(
         &self,
         message_id: MessageId,
@@ -1406,9 +1648,11 @@ impl ActiveThread {
                 ToolUseStatus::Pending | ToolUseStatus::NeedsConfirmation => {
                     (IconName::Warning, Color::Warning, false)
                 }
+                ToolUseStatus::Queued => (IconName::CountdownTimer, Color::Muted, false),
                 ToolUseStatus::Running => (IconName::ArrowCircle, Color::Accent, true),
                 ToolUseStatus::Finished(_) => (IconName::Check, Color::Success, false),
                 ToolUseStatus::Error(_) => (IconName::Close, Color::Error, false),
+                ToolUseStatus::Blocked(_) => (IconName::LockOutlined, Color::Warning, false),
             };
 
             let icon = Icon::new(icon_name).color(color).size(IconSize::Small);
@@ -1498,6 +1742,26 @@ impl ActiveThread {
                         )
                         .child(Label::new(err).size(LabelSize::Small).buffer_font(cx)),
                 ),
+                ToolUseStatus::Queued => container.child(
+                    content_container().child(
+                        h_flex()
+                            .gap_1()
+                            .pb_1()
+                            .border_t_1()
+                            .border_color(self.tool_card_border_color(cx))
+                            .child(
+                                Icon::new(IconName::CountdownTimer)
+                                    .size(IconSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .child(
+                                Label::new("Waiting for earlier edits…")
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted)
+                                    .buffer_font(cx),
+                            ),
+                    ),
+                ),
                 ToolUseStatus::Pending => container,
                 ToolUseStatus::NeedsConfirmation => container.child(
                     content_container()
@@ -1510,6 +1774,18 @@ impl ActiveThread {
                                 .buffer_font(cx),
                         ),
                 ),
+                ToolUseStatus::Blocked(reason) => container.child(
+                    content_container()
+                        .border_t_1()
+                        .border_color(self.tool_card_border_color(cx))
+                        .child(
+                            Label::new("Blocked by Path Policy")
+                                .size(LabelSize::XSmall)
+                                .color(Color::Muted)
+                                .buffer_font(cx),
+                        )
+                        .child(Label::new(reason).size(LabelSize::Small).buffer_font(cx)),
+                ),
             });
 
         fn gradient_overlay(color: Hsla) -> impl IntoElement {
@@ -1693,18 +1969,51 @@ impl ActiveThread {
         let rules_files = system_prompt_context
             .worktrees
             .iter()
-            .filter_map(|worktree| worktree.rules_file.as_ref())
+            .flat_map(|worktree| {
+                worktree
+                    .rules_files
+                    .iter()
+                    .map(move |rules_file| (worktree, rules_file))
+            })
+            .collect::<Vec<_>>();
+        let enabled_rules_files = rules_files
+            .iter()
+            .filter(|(_, rules_file)| rules_file.enabled)
             .collect::<Vec<_>>();
 
-        let label_text = match rules_files.as_slice() {
-            &[] => return div().into_any(),
-            &[rules_file] => {
+        let label_text = match (rules_files.as_slice(), enabled_rules_files.as_slice()) {
+            (&[], _) => return div().into_any(),
+            (&[(_, rules_file)], &[_]) => {
                 format!("Using {:?} file", rules_file.rel_path)
             }
-            rules_files => {
+            (rules_files, enabled_rules_files)
+                if rules_files.len() == enabled_rules_files.len() =>
+            {
                 format!("Using {} rules files", rules_files.len())
             }
+            (rules_files, enabled_rules_files) => {
+                format!(
+                    "Using {} of {} rules files",
+                    enabled_rules_files.len(),
+                    rules_files.len()
+                )
+            }
         };
+        let rules_file_toggles = rules_files
+            .iter()
+            .map(|(worktree, rules_file)| {
+                (
+                    SharedString::from(format!(
+                        "{}/{}",
+                        worktree.root_name,
+                        rules_file.rel_path.display()
+                    )),
+                    rules_file.abs_path.clone(),
+                    rules_file.enabled,
+                )
+            })
+            .collect::<Vec<_>>();
+        let this = cx.entity().downgrade();
 
         div()
             .pt_1()
@@ -1735,6 +2044,43 @@ impl ActiveThread {
                             .icon_color(Color::Ignored)
                             .on_click(cx.listener(Self::handle_open_rules))
                             .tooltip(Tooltip::text("View Rules")),
+                    )
+                    .child(
+                        PopoverMenu::new("rules-files-menu")
+                            .trigger_with_tooltip(
+                                IconButton::new("toggle-rules-files", IconName::ListTree)
+                                    .shape(ui::IconButtonShape::Square)
+                                    .icon_size(IconSize::XSmall)
+                                    .icon_color(Color::Ignored),
+                                Tooltip::text("Choose Rules Files"),
+                            )
+                            .anchor(gpui::Corner::TopLeft)
+                            .menu(move |window, cx| {
+                                Some(ContextMenu::build(window, cx, |mut menu, _window, _cx| {
+                                    for (label, abs_path, enabled) in &rules_file_toggles {
+                                        let this = this.clone();
+                                        let abs_path = abs_path.clone();
+                                        let enabled = *enabled;
+                                        menu = menu.toggleable_entry(
+                                            label.clone(),
+                                            enabled,
+                                            IconPosition::Start,
+                                            None,
+                                            move |_window, cx| {
+                                                this.update(cx, |this, cx| {
+                                                    this.thread.update(cx, |thread, cx| {
+                                                        thread.set_rules_file_enabled(
+                                                            &abs_path, !enabled, cx,
+                                                        )
+                                                    });
+                                                })
+                                                .log_err();
+                                            },
+                                        );
+                                    }
+                                    menu
+                                }))
+                            }),
                     ),
             )
             .into_any()
@@ -1787,7 +2133,8 @@ impl ActiveThread {
         let abs_paths = system_prompt_context
             .worktrees
             .iter()
-            .flat_map(|worktree| worktree.rules_file.as_ref())
+            .flat_map(|worktree| &worktree.rules_files)
+            .filter(|rules_file| rules_file.enabled)
             .map(|rules_file| rules_file.abs_path.to_path_buf())
             .collect::<Vec<_>>();
 
@@ -1810,6 +2157,20 @@ impl ActiveThread {
         thread
             .tools_needing_confirmation()
             .map(|tool| {
+                let blocked_reason = tool.status.blocked_reason().cloned();
+                let (prompt, allow_label, note) = match blocked_reason {
+                    Some(_) => (
+                        "The project's tool path policy blocked this action:",
+                        "Allow Once",
+                        "Allowing this action doesn't change the policy. To allow these paths permanently, add them to \"assistant\": { \"tool_path_policy\": { \"allow\": [] } } in your settings.json.",
+                    ),
+                    None => (
+                        "The agent wants to run this action:",
+                        "Allow",
+                        "Note: A future release will introduce a way to remember your answers to these. In the meantime, you can avoid these prompts by adding \"assistant\": { \"always_allow_tool_actions\": true } to your settings.json.",
+                    ),
+                };
+
                 div()
                     .m_3()
                     .p_2()
@@ -1823,18 +2184,22 @@ impl ActiveThread {
                             .child(
                                 v_flex()
                                     .gap_0p5()
-                                    .child(
-                                        Label::new("The agent wants to run this action:")
-                                            .color(Color::Muted),
-                                    )
-                                    .child(div().p_3().child(Label::new(&tool.ui_text))),
+                                    .child(Label::new(prompt).color(Color::Muted))
+                                    .child(div().p_3().child(Label::new(&tool.ui_text)))
+                                    .children(blocked_reason.map(|reason| {
+                                        div().px_3().child(
+                                            Label::new(reason.to_string())
+                                                .size(LabelSize::Small)
+                                                .color(Color::Warning),
+                                        )
+                                    })),
                             )
                             .child(
                                 h_flex()
                                     .gap_1()
                                     .child({
                                         let tool_id = tool.id.clone();
-                                        Button::new("allow-tool-action", "Allow").on_click(
+                                        Button::new("allow-tool-action", allow_label).on_click(
                                             cx.listener(move |this, event, window, cx| {
                                                 this.handle_allow_tool(
                                                     tool_id.clone(),
@@ -1860,7 +2225,7 @@ impl ActiveThread {
                                     }),
                             )
                             .child(
-                                Label::new("Note: A future release will introduce a way to remember your answers to these. In the meantime, you can avoid these prompts by adding \"assistant\": { \"always_allow_tool_actions\": true } to your settings.json.")
+                                Label::new(note)
                                     .color(Color::Muted)
                                     .size(LabelSize::Small),
                             ),
@@ -2013,5 +2378,12 @@ pub(crate) fn open_context(
                 }
             })
         }
+        // Project-wide context is regenerated on every send, so there's nothing to open.
+        AssistantContext::Diagnostics(_)
+        | AssistantContext::ProjectSearch(_)
+        | AssistantContext::GitDiff(_)
+        | AssistantContext::DebugSession(_) => {}
+        // Images aren't backed by an editor, so there's nothing to open.
+        AssistantContext::Image(_) => {}
     }
 }
@@ -3,6 +3,7 @@ mod assistant_configuration;
 mod assistant_diff;
 mod assistant_model_selector;
 mod assistant_panel;
+mod background_threads;
 mod buffer_codegen;
 mod context;
 mod context_picker;
@@ -13,11 +14,13 @@ mod inline_assistant;
 mod inline_prompt_editor;
 mod message_editor;
 mod profile_selector;
+mod screenshot;
 mod terminal_codegen;
 mod terminal_inline_assistant;
 mod thread;
 mod thread_history;
 mod thread_store;
+mod thread_template_picker;
 mod tool_use;
 mod ui;
 
@@ -35,7 +38,9 @@ use serde::Deserialize;
 use settings::Settings as _;
 
 pub use crate::active_thread::ActiveThread;
-use crate::assistant_configuration::{AddContextServerModal, ManageProfilesModal};
+use crate::assistant_configuration::{
+    AddContextServerModal, ContextServerRegistryModal, ManageProfilesModal,
+};
 pub use crate::assistant_panel::{AssistantPanel, ConcreteAssistantPanelDelegate};
 pub use crate::inline_assistant::InlineAssistant;
 pub use crate::thread::{Message, RequestKind, Thread, ThreadEvent};
@@ -46,6 +51,9 @@ actions!(
     assistant2,
     [
         NewThread,
+        NewThreadFromTemplate,
+        RunInBackground,
+        OpenBackgroundThreads,
         NewPromptEditor,
         ToggleContextPicker,
         ToggleProfileSelector,
@@ -53,6 +61,7 @@ actions!(
         OpenHistory,
         OpenConfiguration,
         AddContextServer,
+        BrowseContextServers,
         RemoveSelectedThread,
         Chat,
         ChatMode,
@@ -86,7 +95,33 @@ impl ManageProfiles {
     }
 }
 
-impl_actions!(assistant, [ManageProfiles]);
+/// The format in which a thread is exported.
+#[derive(PartialEq, Clone, Copy, Default, Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ThreadExportFormat {
+    #[default]
+    Markdown,
+    Json,
+}
+
+impl ThreadExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Json => "json",
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema)]
+pub struct ExportThread {
+    #[serde(default)]
+    pub format: ThreadExportFormat,
+}
+
+impl_actions!(assistant, [ManageProfiles, ExportThread]);
+
+actions!(assistant, [ImportThread, EditSelection]);
 
 const NAMESPACE: &str = "assistant2";
 
@@ -114,6 +149,8 @@ pub fn init(
         cx,
     );
     cx.observe_new(AddContextServerModal::register).detach();
+    cx.observe_new(ContextServerRegistryModal::register)
+        .detach();
     cx.observe_new(ManageProfilesModal::register).detach();
 
     feature_gate_assistant2_actions(cx);
@@ -1,4 +1,5 @@
 mod add_context_server_modal;
+mod context_server_registry_modal;
 mod manage_profiles_modal;
 mod tool_picker;
 
@@ -14,9 +15,10 @@ use util::ResultExt as _;
 use zed_actions::ExtensionCategoryFilter;
 
 pub(crate) use add_context_server_modal::AddContextServerModal;
+pub(crate) use context_server_registry_modal::ContextServerRegistryModal;
 pub(crate) use manage_profiles_modal::ManageProfilesModal;
 
-use crate::AddContextServer;
+use crate::{AddContextServer, BrowseContextServers};
 
 pub struct AssistantConfiguration {
     focus_handle: FocusHandle,
@@ -305,6 +307,20 @@ impl AssistantConfiguration {
                 h_flex()
                     .justify_between()
                     .gap_2()
+                    .child(
+                        h_flex().w_full().child(
+                            Button::new("browse-context-servers", "Browse Context Servers")
+                                .style(ButtonStyle::Filled)
+                                .layer(ElevationIndex::ModalSurface)
+                                .full_width()
+                                .icon(IconName::MagnifyingGlass)
+                                .icon_size(IconSize::Small)
+                                .icon_position(IconPosition::Start)
+                                .on_click(|_event, window, cx| {
+                                    window.dispatch_action(BrowseContextServers.boxed_clone(), cx)
+                                }),
+                        ),
+                    )
                     .child(
                         h_flex().w_full().child(
                             Button::new("add-context-server", "Add Context Server")
@@ -337,6 +353,7 @@ impl AssistantConfiguration {
                                         category_filter: Some(
                                             ExtensionCategoryFilter::ContextServers,
                                         ),
+                                        id: None,
                                     }
                                     .boxed_clone(),
                                     cx,
@@ -0,0 +1,534 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use collections::{HashMap, HashSet};
+use context_server::catalog::{catalog, CatalogEntry, CatalogInput};
+use context_server::manager::ContextServer;
+use context_server::ContextServerSettings;
+use credentials_provider::CredentialsProvider;
+use editor::Editor;
+use futures::FutureExt as _;
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    prelude::*, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity,
+};
+use picker::{Picker, PickerDelegate};
+use settings::{update_settings_file, Settings as _};
+use ui::{
+    prelude::*, HighlightedLabel, ListItem, ListItemSpacing, Modal, ModalFooter, ModalHeader,
+    Section, Tooltip,
+};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+use crate::BrowseContextServers;
+
+/// How long to wait for a newly configured server to complete the MCP handshake.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A modal for installing context servers from the curated [`catalog`].
+///
+/// Choosing a server asks for the values of its inputs, then starts it to
+/// check that it connects before writing its configuration to the settings.
+pub struct ContextServerRegistryModal {
+    workspace: WeakEntity<Workspace>,
+    picker: Entity<Picker<ContextServerCatalogDelegate>>,
+    configure: Option<ConfigureServer>,
+    focus_handle: FocusHandle,
+}
+
+struct ConfigureServer {
+    entry: CatalogEntry,
+    inputs: Vec<(CatalogInput, Entity<Editor>)>,
+    validation: Validation,
+}
+
+enum Validation {
+    Idle,
+    Connecting(Task<()>),
+    Failed(SharedString),
+    Saving(Task<()>),
+    SaveFailed(SharedString),
+}
+
+impl ContextServerRegistryModal {
+    pub fn register(
+        workspace: &mut Workspace,
+        _window: Option<&mut Window>,
+        _cx: &mut Context<Workspace>,
+    ) {
+        workspace.register_action(|workspace, _: &BrowseContextServers, window, cx| {
+            let workspace_handle = cx.entity().downgrade();
+            workspace.toggle_modal(window, cx, |window, cx| {
+                Self::new(workspace_handle, window, cx)
+            })
+        });
+    }
+
+    pub fn new(
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let installed = ContextServerSettings::get_global(cx)
+            .context_servers
+            .keys()
+            .map(|id| id.to_string())
+            .collect();
+        let delegate = ContextServerCatalogDelegate {
+            modal: cx.entity().downgrade(),
+            installed,
+            candidates: catalog()
+                .iter()
+                .enumerate()
+                .map(|(id, entry)| StringMatchCandidate::new(id, &entry.name))
+                .collect(),
+            matches: Vec::new(),
+            selected_index: 0,
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+
+        Self {
+            workspace,
+            picker,
+            configure: None,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn configure(&mut self, entry: CatalogEntry, window: &mut Window, cx: &mut Context<Self>) {
+        let inputs = entry
+            .inputs
+            .iter()
+            .map(|input| {
+                let editor = cx.new(|cx| {
+                    let mut editor = Editor::single_line(window, cx);
+                    editor.set_placeholder_text(input.description.clone(), cx);
+                    if let Some(default) = &input.default {
+                        editor.set_text(default.clone(), window, cx);
+                    }
+                    editor.set_masked(input.secret, cx);
+                    editor
+                });
+                cx.observe(&editor, |_, _, cx| cx.notify()).detach();
+                (input.clone(), editor)
+            })
+            .collect::<Vec<_>>();
+
+        match inputs.first() {
+            Some((_, editor)) => editor.focus_handle(cx).focus(window),
+            None => self.focus_handle.focus(window),
+        }
+
+        self.configure = Some(ConfigureServer {
+            entry,
+            inputs,
+            validation: Validation::Idle,
+        });
+        cx.notify();
+    }
+
+    fn back(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.configure.take().is_some() {
+            self.picker.focus_handle(cx).focus(window);
+            cx.notify();
+        } else {
+            cx.emit(DismissEvent);
+        }
+    }
+
+    /// Returns the values entered for the server's inputs, keyed by [`CatalogInput::key`], or
+    /// `None` if a required input is empty.
+    fn input_values(&self, cx: &App) -> Option<HashMap<String, String>> {
+        let configure = self.configure.as_ref()?;
+        let mut values = HashMap::default();
+        for (input, editor) in &configure.inputs {
+            let value = editor.read(cx).text(cx).trim().to_string();
+            if value.is_empty() && input.is_required() {
+                return None;
+            }
+            values.insert(input.key.clone(), value);
+        }
+        Some(values)
+    }
+
+    fn install(&mut self, cx: &mut Context<Self>) {
+        let (Some(configure), Some(values)) = (self.configure.as_ref(), self.input_values(cx))
+        else {
+            return;
+        };
+
+        let id: Arc<str> = configure.entry.id.clone().into();
+        let config = configure.entry.server_config(&values);
+        let server = Arc::new(ContextServer::new(id, Arc::new(config)));
+        let task = cx.spawn(async move |this, cx| {
+            let timeout = cx.background_executor().timer(CONNECTION_TIMEOUT);
+            let result = futures::select_biased! {
+                result = server.clone().start(cx).fuse() => result,
+                _ = timeout.fuse() => Err(anyhow!(
+                    "the server didn't respond within {} seconds",
+                    CONNECTION_TIMEOUT.as_secs()
+                )),
+            };
+            server.stop().log_err();
+
+            this.update(cx, |this, cx| match result {
+                Ok(()) => this.save(values, cx),
+                Err(error) => {
+                    if let Some(configure) = this.configure.as_mut() {
+                        configure.validation = Validation::Failed(error.to_string().into());
+                        cx.notify();
+                    }
+                }
+            })
+            .log_err();
+        });
+
+        if let Some(configure) = self.configure.as_mut() {
+            configure.validation = Validation::Connecting(task);
+        }
+        cx.notify();
+    }
+
+    fn install_without_validation(&mut self, cx: &mut Context<Self>) {
+        if let Some(values) = self.input_values(cx) {
+            self.save(values, cx);
+        }
+    }
+
+    /// Writes the server's configuration to the settings, and the values of its secret inputs
+    /// to the keychain.
+    fn save(&mut self, values: HashMap<String, String>, cx: &mut Context<Self>) {
+        let Some(configure) = self.configure.as_ref() else {
+            return;
+        };
+        let entry = configure.entry.clone();
+        let credentials_provider = <dyn CredentialsProvider>::global(cx);
+
+        let task = cx.spawn(async move |this, cx| {
+            let result = entry
+                .write_secrets(&values, &*credentials_provider, &cx)
+                .await;
+
+            this.update(cx, |this, cx| match result {
+                Ok(()) => {
+                    let id: Arc<str> = entry.id.clone().into();
+                    let config = entry.settings_config(&values);
+                    if let Some(workspace) = this.workspace.upgrade() {
+                        workspace.update(cx, |workspace, cx| {
+                            let fs = workspace.app_state().fs.clone();
+                            update_settings_file::<ContextServerSettings>(
+                                fs,
+                                cx,
+                                move |settings, _| {
+                                    settings.context_servers.insert(id, config);
+                                },
+                            );
+                        });
+                    }
+                    cx.emit(DismissEvent);
+                }
+                Err(error) => {
+                    if let Some(configure) = this.configure.as_mut() {
+                        configure.validation = Validation::SaveFailed(error.to_string().into());
+                        cx.notify();
+                    }
+                }
+            })
+            .log_err();
+        });
+
+        if let Some(configure) = self.configure.as_mut() {
+            configure.validation = Validation::Saving(task);
+        }
+        cx.notify();
+    }
+
+    fn render_configure(&self, configure: &ConfigureServer, cx: &mut Context<Self>) -> AnyElement {
+        let entry = &configure.entry;
+        let is_missing_input = self.input_values(cx).is_none();
+        let is_connecting = matches!(
+            configure.validation,
+            Validation::Connecting(_) | Validation::Saving(_)
+        );
+        let command = std::iter::once(entry.command.as_str())
+            .chain(entry.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        div()
+            .elevation_3(cx)
+            .w(rems(34.))
+            .key_context("ContextServerRegistryModal")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(|this, _: &menu::Cancel, window, cx| this.back(window, cx)))
+            .on_action(cx.listener(|this, _: &menu::Confirm, _window, cx| this.install(cx)))
+            .child(
+                Modal::new("configure-context-server", None)
+                    .header(
+                        ModalHeader::new()
+                            .headline(format!("Install {}", entry.name))
+                            .show_back_button(true),
+                    )
+                    .section(
+                        Section::new()
+                            .child(Label::new(entry.description.clone()).color(Color::Muted))
+                            .child(
+                                div()
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_sm()
+                                    .bg(cx.theme().colors().editor_background)
+                                    .child(
+                                        Label::new(command).size(LabelSize::Small).buffer_font(cx),
+                                    ),
+                            )
+                            .children(configure.inputs.iter().map(|(input, editor)| {
+                                v_flex()
+                                    .gap_1()
+                                    .child(Label::new(input.label.clone()))
+                                    .child(editor.clone())
+                                    .when(input.secret, |this| {
+                                        this.child(
+                                            Label::new(
+                                                "Stored in the system keychain, not in your settings.",
+                                            )
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted),
+                                        )
+                                    })
+                            }))
+                            .map(|section| match &configure.validation {
+                                Validation::Idle => section,
+                                Validation::Connecting(_) => section.child(
+                                    Label::new("Connecting to the server…")
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                ),
+                                Validation::Failed(error) => section.child(
+                                    Label::new(format!("Couldn't connect to the server: {error}"))
+                                        .size(LabelSize::Small)
+                                        .color(Color::Error),
+                                ),
+                                Validation::Saving(_) => section,
+                                Validation::SaveFailed(error) => section.child(
+                                    Label::new(format!(
+                                        "Couldn't store the server's secrets in the keychain: {error}"
+                                    ))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Error),
+                                ),
+                            }),
+                    )
+                    .footer(
+                        ModalFooter::new()
+                            .start_slot(h_flex().gap_1().when_some(
+                                entry.homepage.clone(),
+                                |this, homepage| {
+                                    this.child(
+                                        Button::new("context-server-docs", "Docs")
+                                            .icon(IconName::ArrowUpRight)
+                                            .icon_size(IconSize::XSmall)
+                                            .icon_color(Color::Muted)
+                                            .on_click(move |_, _, cx| cx.open_url(&homepage)),
+                                    )
+                                },
+                            ))
+                            .end_slot(
+                                h_flex()
+                                    .gap_1()
+                                    .when(
+                                        matches!(configure.validation, Validation::Failed(_)),
+                                        |this| {
+                                            this.child(
+                                                Button::new("install-anyway", "Install Anyway")
+                                                    .on_click(cx.listener(|this, _, _, cx| {
+                                                        this.install_without_validation(cx)
+                                                    })),
+                                            )
+                                        },
+                                    )
+                                    .child(
+                                        Button::new("install-context-server", "Install")
+                                            .disabled(is_missing_input || is_connecting)
+                                            .when(is_missing_input, |button| {
+                                                button.tooltip(Tooltip::text(
+                                                    "Fill in every field without a default",
+                                                ))
+                                            })
+                                            .on_click(
+                                                cx.listener(|this, _, _, cx| this.install(cx)),
+                                            ),
+                                    ),
+                            ),
+                    ),
+            )
+            .into_any_element()
+    }
+}
+
+impl ModalView for ContextServerRegistryModal {}
+
+impl Focusable for ContextServerRegistryModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        match &self.configure {
+            Some(configure) => configure
+                .inputs
+                .first()
+                .map(|(_, editor)| editor.focus_handle(cx))
+                .unwrap_or_else(|| self.focus_handle.clone()),
+            None => self.picker.focus_handle(cx),
+        }
+    }
+}
+
+impl EventEmitter<DismissEvent> for ContextServerRegistryModal {}
+
+impl Render for ContextServerRegistryModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        match &self.configure {
+            Some(configure) => self.render_configure(configure, cx),
+            None => v_flex()
+                .w(rems(34.))
+                .child(self.picker.clone())
+                .into_any_element(),
+        }
+    }
+}
+
+pub struct ContextServerCatalogDelegate {
+    modal: WeakEntity<ContextServerRegistryModal>,
+    /// The IDs of the context servers already configured in the settings.
+    installed: HashSet<String>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl PickerDelegate for ContextServerCatalogDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search context servers…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, candidate)| StringMatch {
+                        candidate_id: index,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(mat) = self.matches.get(self.selected_index) else {
+            return;
+        };
+        let entry = catalog()[mat.candidate_id].clone();
+        self.modal
+            .update(cx, |modal, cx| modal.configure(entry, window, cx))
+            .log_err();
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.modal
+            .update(cx, |modal, cx| {
+                if modal.configure.is_none() {
+                    cx.emit(DismissEvent);
+                }
+            })
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let entry = &catalog()[mat.candidate_id];
+        let is_installed = self.installed.contains(&entry.id);
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    v_flex()
+                        .child(HighlightedLabel::new(
+                            mat.string.clone(),
+                            mat.positions.clone(),
+                        ))
+                        .child(
+                            Label::new(entry.description.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                )
+                .when(is_installed, |item| {
+                    item.end_slot(
+                        Label::new("Installed")
+                            .size(LabelSize::Small)
+                            .color(Color::Success),
+                    )
+                }),
+        )
+    }
+}
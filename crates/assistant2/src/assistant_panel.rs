@@ -1,12 +1,13 @@
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Result};
 use assistant_context_editor::{
-    make_lsp_adapter_delegate, render_remaining_tokens, AssistantPanelDelegate, ConfigurationError,
-    ContextEditor, SlashCommandCompletionProvider,
+    humanize_token_count, make_lsp_adapter_delegate, render_remaining_tokens,
+    AssistantPanelDelegate, ConfigurationError, ContextEditor, SlashCommandCompletionProvider,
 };
-use assistant_settings::{AssistantDockPosition, AssistantSettings};
+use assistant_settings::{AssistantDockPosition, AssistantSettings, BudgetStatus, ThreadTemplate};
 use assistant_slash_command::SlashCommandWorkingSet;
 use assistant_tool::ToolWorkingSet;
 
@@ -15,32 +16,38 @@ use editor::{Editor, MultiBuffer};
 use fs::Fs;
 use gpui::{
     action_with_deprecated_aliases, prelude::*, Action, AnyElement, App, AsyncWindowContext,
-    Corner, Entity, EventEmitter, FocusHandle, Focusable, FontWeight, KeyContext, Pixels,
-    Subscription, Task, UpdateGlobal, WeakEntity,
+    Corner, Entity, EventEmitter, FocusHandle, Focusable, FontWeight, KeyContext,
+    PathPromptOptions, Pixels, Subscription, Task, UpdateGlobal, WeakEntity,
 };
 use language::LanguageRegistry;
-use language_model::{LanguageModelProviderTosView, LanguageModelRegistry};
+use language_model::{
+    LanguageModelId, LanguageModelProviderId, LanguageModelProviderTosView, LanguageModelRegistry,
+};
 use project::Project;
 use prompt_library::{open_prompt_library, PromptLibrary};
 use prompt_store::PromptBuilder;
 use settings::{update_settings_file, Settings};
-use time::UtcOffset;
+use time::{OffsetDateTime, UtcOffset};
 use ui::{prelude::*, ContextMenu, KeyBinding, PopoverMenu, PopoverMenuHandle, Tab, Tooltip};
 use util::ResultExt as _;
 use workspace::dock::{DockPosition, Panel, PanelEvent};
-use workspace::Workspace;
+use workspace::notifications::NotificationId;
+use workspace::{Toast, Workspace};
 use zed_actions::assistant::ToggleFocus;
 
 use crate::active_thread::ActiveThread;
 use crate::assistant_configuration::{AssistantConfiguration, AssistantConfigurationEvent};
+use crate::background_threads::{BackgroundThread, BackgroundThreadStatus};
 use crate::history_store::{HistoryEntry, HistoryStore};
 use crate::message_editor::MessageEditor;
-use crate::thread::{Thread, ThreadError, ThreadId};
+use crate::thread::{MessageId, Thread, ThreadError, ThreadEvent, ThreadId};
 use crate::thread_history::{PastContext, PastThread, ThreadHistory};
-use crate::thread_store::ThreadStore;
+use crate::thread_store::{SerializedThreadMetadata, ThreadStore};
+use crate::thread_template_picker::ThreadTemplatePicker;
 use crate::{
-    InlineAssistant, NewPromptEditor, NewThread, OpenActiveThreadAsMarkdown, OpenConfiguration,
-    OpenHistory,
+    Chat, ExportThread, ImportThread, InlineAssistant, NewPromptEditor, NewThread,
+    NewThreadFromTemplate, OpenActiveThreadAsMarkdown, OpenBackgroundThreads, OpenConfiguration,
+    OpenHistory, RunInBackground, ThreadExportFormat,
 };
 
 action_with_deprecated_aliases!(
@@ -59,6 +66,28 @@ pub fn init(cx: &mut App) {
                         workspace.focus_panel::<AssistantPanel>(window, cx);
                     }
                 })
+                .register_action(|workspace, _: &NewThreadFromTemplate, window, cx| {
+                    ThreadTemplatePicker::toggle(workspace, window, cx);
+                })
+                .register_action(|workspace, action: &ExportThread, window, cx| {
+                    if let Some(panel) = workspace.panel::<AssistantPanel>(cx) {
+                        panel.update(cx, |panel, cx| {
+                            panel.export_thread(action.format, window, cx)
+                        });
+                    }
+                })
+                .register_action(|workspace, _: &ImportThread, window, cx| {
+                    if let Some(panel) = workspace.panel::<AssistantPanel>(cx) {
+                        workspace.focus_panel::<AssistantPanel>(window, cx);
+                        panel.update(cx, |panel, cx| panel.import_thread(window, cx));
+                    }
+                })
+                .register_action(|workspace, _: &OpenBackgroundThreads, window, cx| {
+                    if let Some(panel) = workspace.panel::<AssistantPanel>(cx) {
+                        workspace.focus_panel::<AssistantPanel>(window, cx);
+                        panel.update(cx, |panel, cx| panel.open_background_threads(window, cx));
+                    }
+                })
                 .register_action(|workspace, _: &OpenHistory, window, cx| {
                     if let Some(panel) = workspace.panel::<AssistantPanel>(cx) {
                         workspace.focus_panel::<AssistantPanel>(window, cx);
@@ -100,6 +129,7 @@ enum ActiveView {
     Thread,
     PromptEditor,
     History,
+    BackgroundThreads,
     Configuration,
 }
 
@@ -120,6 +150,9 @@ pub struct AssistantPanel {
     history_store: Entity<HistoryStore>,
     history: Entity<ThreadHistory>,
     assistant_dropdown_menu_handle: PopoverMenuHandle<ContextMenu>,
+    background_threads: Vec<BackgroundThread>,
+    background_threads_focus_handle: FocusHandle,
+    _refresh_background_threads: Option<Task<()>>,
     width: Option<Pixels>,
     height: Option<Pixels>,
 }
@@ -220,6 +253,9 @@ impl AssistantPanel {
             history_store: history_store.clone(),
             history: cx.new(|cx| ThreadHistory::new(weak_self, history_store, cx)),
             assistant_dropdown_menu_handle: PopoverMenuHandle::default(),
+            background_threads: Vec::new(),
+            background_threads_focus_handle: cx.focus_handle(),
+            _refresh_background_threads: None,
             width: None,
             height: None,
         }
@@ -258,6 +294,8 @@ impl AssistantPanel {
     }
 
     fn new_thread(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.move_generating_thread_to_background(window, cx);
+
         let thread = self
             .thread_store
             .update(cx, |this, cx| this.create_thread(cx));
@@ -292,6 +330,55 @@ impl AssistantPanel {
         self.message_editor.focus_handle(cx).focus(window);
     }
 
+    /// Starts a new thread with the given template's profile and model, and sends the
+    /// rendered prompt as its first message.
+    ///
+    /// The profile and model only apply to the new thread, leaving the selected ones as they are.
+    pub(crate) fn new_thread_from_template(
+        &mut self,
+        template: &ThreadTemplate,
+        prompt: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let profile = template.profile.as_ref().and_then(|profile_id| {
+            let profile = AssistantSettings::get_global(cx).profiles.get(profile_id);
+            if profile.is_none() {
+                log::warn!("thread template profile {profile_id} does not exist");
+            }
+            profile.cloned()
+        });
+
+        let model = template.model.as_ref().and_then(|selection| {
+            let provider_id = LanguageModelProviderId::from(selection.provider.clone());
+            let model_id = LanguageModelId::from(selection.model.clone());
+            let model = LanguageModelRegistry::read_global(cx)
+                .available_models(cx)
+                .find(|model| model.provider_id() == provider_id && model.id() == model_id);
+            if model.is_none() {
+                log::warn!(
+                    "thread template model {}/{} is not available",
+                    selection.provider,
+                    selection.model
+                );
+            }
+            model
+        });
+
+        self.new_thread(window, cx);
+        self.thread.read(cx).thread().update(cx, |thread, _cx| {
+            if let Some(profile) = profile {
+                thread.set_profile(profile);
+            }
+            if let Some(model) = model {
+                thread.set_model(model);
+            }
+        });
+        self.message_editor.update(cx, |message_editor, cx| {
+            message_editor.send_message(prompt, window, cx)
+        });
+    }
+
     fn new_prompt_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.active_view = ActiveView::PromptEditor;
 
@@ -395,6 +482,23 @@ impl AssistantPanel {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
+        if let Some(ix) = self
+            .background_threads
+            .iter()
+            .position(|background_thread| {
+                background_thread
+                    .active_thread
+                    .read(cx)
+                    .thread()
+                    .read(cx)
+                    .id()
+                    == thread_id
+            })
+        {
+            self.open_background_thread(ix, window, cx);
+            return Task::ready(Ok(()));
+        }
+
         let open_thread_task = self
             .thread_store
             .update(cx, |this, cx| this.open_thread(thread_id, cx));
@@ -402,6 +506,7 @@ impl AssistantPanel {
         cx.spawn_in(window, async move |this, cx| {
             let thread = open_thread_task.await?;
             this.update_in(cx, |this, window, cx| {
+                this.move_generating_thread_to_background(window, cx);
                 this.active_view = ActiveView::Thread;
                 let message_editor_context_store =
                     cx.new(|_cx| crate::context_store::ContextStore::new(this.workspace.clone()));
@@ -432,6 +537,259 @@ impl AssistantPanel {
         })
     }
 
+    /// Branches the active thread after the given message, then opens the branch.
+    pub(crate) fn branch_thread(
+        &mut self,
+        message_id: MessageId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let thread = self.active_thread(cx);
+        let branch_thread = self.thread_store.update(cx, |thread_store, cx| {
+            thread_store.branch_thread(&thread, message_id, cx)
+        });
+
+        cx.spawn_in(window, async move |this, cx| {
+            let thread_id = branch_thread.await?;
+            this.update_in(cx, |this, window, cx| {
+                this.open_thread(&thread_id, window, cx)
+            })?
+            .await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Sends the message being composed, then keeps the thread running in the background
+    /// while a new thread is started.
+    fn run_in_background(
+        &mut self,
+        _: &RunInBackground,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.message_editor.read(cx).is_editor_empty(cx) {
+            return;
+        }
+
+        self.message_editor.update(cx, |message_editor, cx| {
+            message_editor.chat(&Chat, window, cx)
+        });
+        self.move_thread_to_background(window, cx);
+        self.new_thread(window, cx);
+    }
+
+    fn move_generating_thread_to_background(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.thread.read(cx).thread().read(cx).is_generating() {
+            self.move_thread_to_background(window, cx);
+        }
+    }
+
+    fn move_thread_to_background(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self
+            .background_threads
+            .iter()
+            .any(|background_thread| background_thread.active_thread == self.thread)
+        {
+            return;
+        }
+
+        let thread = self.thread.read(cx).thread().clone();
+        let subscription = cx.subscribe_in(&thread, window, Self::handle_background_thread_event);
+        self.background_threads.push(BackgroundThread {
+            active_thread: self.thread.clone(),
+            message_editor: self.message_editor.clone(),
+            started_at: Instant::now(),
+            finished_at: None,
+            _subscription: subscription,
+        });
+
+        // Keep the elapsed time of the running threads up to date.
+        if self._refresh_background_threads.is_none() {
+            self._refresh_background_threads = Some(cx.spawn(async move |this, cx| loop {
+                cx.background_executor().timer(Duration::from_secs(1)).await;
+                let Ok(is_running) = this.update(cx, |this, cx| {
+                    let is_running = this
+                        .background_threads
+                        .iter()
+                        .any(|background_thread| background_thread.is_running(cx));
+                    if !is_running {
+                        this._refresh_background_threads = None;
+                    }
+                    cx.notify();
+                    is_running
+                }) else {
+                    break;
+                };
+                if !is_running {
+                    break;
+                }
+            }));
+        }
+        cx.notify();
+    }
+
+    fn handle_background_thread_event(
+        &mut self,
+        thread: &Entity<Thread>,
+        event: &ThreadEvent,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(background_thread) = self
+            .background_threads
+            .iter_mut()
+            .find(|background_thread| background_thread.active_thread.read(cx).thread() == thread)
+        else {
+            return;
+        };
+
+        let message = match event {
+            ThreadEvent::DoneStreaming if !thread.read(cx).is_generating() => {
+                background_thread.finished_at = Some(Instant::now());
+                "finished"
+            }
+            ThreadEvent::ToolConfirmationNeeded => "needs tool confirmation",
+            _ => return,
+        };
+        cx.notify();
+
+        let thread_id = thread.read(cx).id().clone();
+        let summary = thread.read(cx).summary_or_default();
+        let panel = cx.entity().downgrade();
+        let workspace = self.workspace.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(
+                        NotificationId::composite::<BackgroundThread>(thread_id.to_string()),
+                        format!("Background thread \"{summary}\" {message}"),
+                    )
+                    .on_click("Open Thread", move |window, cx| {
+                        panel
+                            .update(cx, |panel, cx| {
+                                panel
+                                    .open_thread(&thread_id, window, cx)
+                                    .detach_and_log_err(cx);
+                            })
+                            .ok();
+                        workspace
+                            .update(cx, |workspace, cx| {
+                                workspace.focus_panel::<AssistantPanel>(window, cx);
+                            })
+                            .ok();
+                    }),
+                    cx,
+                );
+            })
+            .log_err();
+    }
+
+    pub(crate) fn open_background_threads(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.active_view = ActiveView::BackgroundThreads;
+        self.background_threads_focus_handle.focus(window);
+        cx.notify();
+    }
+
+    fn open_background_thread(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if ix >= self.background_threads.len() {
+            return;
+        }
+        let background_thread = self.background_threads.remove(ix);
+        self.move_generating_thread_to_background(window, cx);
+
+        self.thread = background_thread.active_thread;
+        self.message_editor = background_thread.message_editor;
+        self.active_view = ActiveView::Thread;
+        self.message_editor.focus_handle(cx).focus(window);
+        cx.notify();
+    }
+
+    fn clear_finished_background_threads(&mut self, cx: &mut Context<Self>) {
+        self.background_threads
+            .retain(|background_thread| background_thread.is_running(cx));
+        cx.notify();
+    }
+
+    fn render_background_threads(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let has_finished = self
+            .background_threads
+            .iter()
+            .any(|background_thread| !background_thread.is_running(cx));
+
+        v_flex()
+            .id("background-threads")
+            .track_focus(&self.background_threads_focus_handle)
+            .size_full()
+            .p_1()
+            .overflow_y_scroll()
+            .when(self.background_threads.is_empty(), |parent| {
+                parent.child(h_flex().p_4().justify_center().child(
+                    Label::new("No threads are running in the background").color(Color::Muted),
+                ))
+            })
+            .children(
+                self.background_threads
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, background_thread)| {
+                        background_thread.render(ix, cx).on_click(cx.listener(
+                            move |this, _, window, cx| {
+                                this.open_background_thread(ix, window, cx);
+                            },
+                        ))
+                    }),
+            )
+            .when(has_finished, |parent| {
+                parent.child(
+                    h_flex().p_2().justify_end().child(
+                        Button::new("clear-finished", "Clear Finished")
+                            .label_size(LabelSize::Small)
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.clear_finished_background_threads(cx);
+                            })),
+                    ),
+                )
+            })
+    }
+
+    fn render_background_threads_indicator(&self, cx: &App) -> Option<impl IntoElement> {
+        let running_count = self
+            .background_threads
+            .iter()
+            .filter(|background_thread| background_thread.is_running(cx))
+            .count();
+        let needs_attention = self.background_threads.iter().any(|background_thread| {
+            background_thread.status(cx) == BackgroundThreadStatus::WaitingForConfirmation
+        });
+        if self.background_threads.is_empty() {
+            return None;
+        }
+
+        Some(
+            Button::new("background-threads", running_count.to_string())
+                .icon(IconName::ArrowCircle)
+                .icon_position(IconPosition::Start)
+                .icon_size(IconSize::Small)
+                .icon_color(if needs_attention {
+                    Color::Warning
+                } else {
+                    Color::Muted
+                })
+                .label_size(LabelSize::Small)
+                .tooltip(Tooltip::text(format!(
+                    "{running_count} of {} background threads running",
+                    self.background_threads.len()
+                )))
+                .on_click(|_, window, cx| {
+                    window.dispatch_action(OpenBackgroundThreads.boxed_clone(), cx);
+                }),
+        )
+    }
+
     pub(crate) fn open_configuration(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let context_server_manager = self.thread_store.read(cx).context_server_manager();
         let tools = self.thread_store.read(cx).tools();
@@ -511,6 +869,69 @@ impl AssistantPanel {
         .detach_and_log_err(cx);
     }
 
+    /// Writes the active thread, including its tool calls and token usage, to a file
+    /// chosen by the user.
+    pub(crate) fn export_thread(
+        &mut self,
+        format: ThreadExportFormat,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let thread = self.active_thread(cx);
+        let contents = match format {
+            ThreadExportFormat::Markdown => Task::ready(thread.read(cx).to_markdown(cx)),
+            ThreadExportFormat::Json => {
+                let serialized_thread = thread.update(cx, |thread, cx| thread.serialize(cx));
+                cx.background_spawn(async move {
+                    Ok(serde_json::to_string_pretty(&serialized_thread.await?)?)
+                })
+            }
+        };
+        let path = cx.prompt_for_new_path(paths::home_dir());
+        let fs = self.fs.clone();
+
+        cx.spawn_in(window, async move |_this, _cx| {
+            let contents = contents.await?;
+            let Some(mut path) = path.await?? else {
+                return Ok(());
+            };
+            if path.extension().is_none() {
+                path.set_extension(format.extension());
+            }
+            fs.atomic_write(path, contents).await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Adds a thread that was exported as JSON to the thread store and opens it.
+    pub(crate) fn import_thread(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let paths = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+        });
+        let fs = self.fs.clone();
+        let thread_store = self.thread_store.downgrade();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let Some(path) = paths.await??.and_then(|paths| paths.into_iter().next()) else {
+                return Ok(());
+            };
+            let json = fs.load(&path).await?;
+            let thread_id = thread_store
+                .update(cx, |thread_store, cx| {
+                    thread_store.import_thread(json.as_bytes(), cx)
+                })?
+                .await?;
+
+            this.update_in(cx, |this, window, cx| {
+                this.open_thread(&thread_id, window, cx)
+            })?
+            .await
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn handle_assistant_configuration_event(
         &mut self,
         _entity: &Entity<AssistantConfiguration>,
@@ -566,6 +987,7 @@ impl Focusable for AssistantPanel {
         match self.active_view {
             ActiveView::Thread => self.message_editor.focus_handle(cx),
             ActiveView::History => self.history.focus_handle(cx),
+            ActiveView::BackgroundThreads => self.background_threads_focus_handle.clone(),
             ActiveView::PromptEditor => {
                 if let Some(context_editor) = self.context_editor.as_ref() {
                     context_editor.focus_handle(cx)
@@ -687,6 +1109,7 @@ impl AssistantPanel {
                 })
                 .unwrap_or_else(|| SharedString::from("Loading Summary…")),
             ActiveView::History => "History".into(),
+            ActiveView::BackgroundThreads => "Background Threads".into(),
             ActiveView::Configuration => "Settings".into(),
         };
 
@@ -719,6 +1142,17 @@ impl AssistantPanel {
                     } else {
                         None
                     })
+                    .children(if matches!(self.active_view, ActiveView::Thread) {
+                        self.render_thread_token_usage(cx)
+                    } else {
+                        None
+                    })
+                    .children(if matches!(self.active_view, ActiveView::Thread) {
+                        self.render_thread_branch_switcher(cx)
+                    } else {
+                        None
+                    })
+                    .children(self.render_background_threads_indicator(cx))
                     .child(
                         h_flex()
                             .h_full()
@@ -764,6 +1198,10 @@ impl AssistantPanel {
                                                 )
                                                 .separator()
                                                 .action("History", OpenHistory.boxed_clone())
+                                                .action(
+                                                    "Background Threads",
+                                                    OpenBackgroundThreads.boxed_clone(),
+                                                )
                                                 .action("Settings", OpenConfiguration.boxed_clone())
                                             },
                                         ))
@@ -773,6 +1211,147 @@ impl AssistantPanel {
             )
     }
 
+    fn render_thread_token_usage(&self, cx: &App) -> Option<impl IntoElement> {
+        let thread = self.thread.read(cx).thread().read(cx);
+        let usage = thread.cumulative_token_usage();
+        let total_tokens = usage.total_tokens();
+        if total_tokens == 0 {
+            return None;
+        }
+
+        let (color, status) = match thread.budget_status(cx) {
+            BudgetStatus::WithinBudget => (Color::Muted, None),
+            BudgetStatus::SoftLimitExceeded => (Color::Warning, Some("Soft token limit exceeded")),
+            BudgetStatus::HardLimitExceeded => (Color::Error, Some("Token budget exhausted")),
+        };
+        let mut label = format!("{} tokens", humanize_token_count(total_tokens as usize));
+        if let Some(cost) = thread.estimated_cost(cx) {
+            label.push_str(&format!(" · ${cost:.2}"));
+        }
+
+        let mut tooltip = format!(
+            "Input: {}\nOutput: {}\nCache writes: {}\nCache reads: {}",
+            usage.input_tokens,
+            usage.output_tokens,
+            usage.cache_creation_input_tokens,
+            usage.cache_read_input_tokens,
+        );
+        if let Some(status) = status {
+            tooltip = format!("{status}\n\n{tooltip}");
+        }
+
+        Some(
+            div()
+                .id("thread-token-usage")
+                .child(Label::new(label).size(LabelSize::Small).color(color))
+                .tooltip(Tooltip::text(tooltip)),
+        )
+    }
+
+    /// Renders a menu for switching between the threads that the active thread was branched
+    /// from, its sibling branches, and the branches of it.
+    fn render_thread_branch_switcher(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let thread = self.thread.read(cx).thread().read(cx);
+        let thread_id = thread.id().clone();
+        let thread_store = self.thread_store.read(cx);
+        let ancestors = thread_store.thread_ancestors(&thread_id);
+        let siblings = thread
+            .branched_from()
+            .map(|branch_point| thread_store.thread_branches(&branch_point.thread_id))
+            .unwrap_or_default();
+        let branches = thread_store.thread_branches(&thread_id);
+        if ancestors.is_empty() && branches.is_empty() {
+            return None;
+        }
+
+        let to_entry = |thread: SerializedThreadMetadata| {
+            let label = self.thread_branch_label(&thread);
+            (thread.id, label)
+        };
+        let ancestors = ancestors
+            .into_iter()
+            .rev()
+            .map(to_entry)
+            .collect::<Vec<_>>();
+        let siblings = siblings.into_iter().map(to_entry).collect::<Vec<_>>();
+        let branches = branches.into_iter().map(to_entry).collect::<Vec<_>>();
+        let current_label = thread.summary_or_default();
+        let this = cx.entity().downgrade();
+
+        Some(
+            PopoverMenu::new("thread-branches")
+                .trigger_with_tooltip(
+                    IconButton::new("thread-branches", IconName::GitBranch)
+                        .icon_size(IconSize::Small)
+                        .style(ButtonStyle::Subtle),
+                    Tooltip::text("Switch Branch"),
+                )
+                .anchor(Corner::TopRight)
+                .menu(move |window, cx| {
+                    let open_thread = |thread_id: &ThreadId| {
+                        let this = this.clone();
+                        let thread_id = thread_id.clone();
+                        move |window: &mut Window, cx: &mut App| {
+                            this.update(cx, |this, cx| {
+                                this.open_thread(&thread_id, window, cx)
+                                    .detach_and_log_err(cx)
+                            })
+                            .log_err();
+                        }
+                    };
+
+                    Some(ContextMenu::build(window, cx, |mut menu, _window, _cx| {
+                        if !ancestors.is_empty() {
+                            menu = menu.header("Branched From");
+                            for (id, label) in &ancestors {
+                                menu = menu.entry(label.clone(), None, open_thread(id));
+                            }
+                        }
+
+                        menu = menu.header("Branches");
+                        if siblings.is_empty() {
+                            menu = menu.toggleable_entry(
+                                current_label.clone(),
+                                true,
+                                IconPosition::Start,
+                                None,
+                                |_, _| {},
+                            );
+                        }
+                        for (id, label) in &siblings {
+                            menu = menu.toggleable_entry(
+                                label.clone(),
+                                id == &thread_id,
+                                IconPosition::Start,
+                                None,
+                                open_thread(id),
+                            );
+                        }
+
+                        if !branches.is_empty() {
+                            menu = menu.header("Branches of This Thread");
+                            for (id, label) in &branches {
+                                menu = menu.entry(label.clone(), None, open_thread(id));
+                            }
+                        }
+                        menu
+                    }))
+                }),
+        )
+    }
+
+    /// Labels a thread with when it was last updated as well as its summary, since a branch
+    /// starts out with the summary of the thread it was branched from.
+    fn thread_branch_label(&self, thread: &SerializedThreadMetadata) -> SharedString {
+        let timestamp = time_format::format_localized_timestamp(
+            OffsetDateTime::from_unix_timestamp(thread.updated_at.timestamp()).unwrap(),
+            OffsetDateTime::now_utc(),
+            self.local_timezone,
+            time_format::TimestampFormat::EnhancedAbsolute,
+        );
+        format!("{} · {}", thread.summary, timestamp).into()
+    }
+
     fn render_active_thread_or_empty_state(
         &self,
         window: &mut Window,
@@ -1103,6 +1682,10 @@ impl Render for AssistantPanel {
             .on_action(cx.listener(|this, _: &OpenConfiguration, window, cx| {
                 this.open_configuration(window, cx);
             }))
+            .on_action(cx.listener(|this, _: &OpenBackgroundThreads, window, cx| {
+                this.open_background_threads(window, cx);
+            }))
+            .on_action(cx.listener(Self::run_in_background))
             .on_action(cx.listener(Self::open_active_thread_as_markdown))
             .on_action(cx.listener(Self::deploy_prompt_library))
             .child(self.render_toolbar(window, cx))
@@ -1112,6 +1695,7 @@ impl Render for AssistantPanel {
                     .child(h_flex().child(self.message_editor.clone()))
                     .children(self.render_last_error(cx)),
                 ActiveView::History => parent.child(self.history.clone()),
+                ActiveView::BackgroundThreads => parent.child(self.render_background_threads(cx)),
                 ActiveView::PromptEditor => parent.children(self.context_editor.clone()),
                 ActiveView::Configuration => parent.children(self.configuration.clone()),
             })
@@ -0,0 +1,109 @@
+use std::time::{Duration, Instant};
+
+use gpui::{App, Entity, SharedString, Subscription};
+use ui::{prelude::*, ListItem, Tooltip};
+
+use crate::active_thread::ActiveThread;
+use crate::message_editor::MessageEditor;
+
+/// A thread that keeps running while it isn't shown in the assistant panel.
+///
+/// The [`ActiveThread`] drives the thread's tool use loop, so it is kept alive along with
+/// its [`MessageEditor`] until the thread is brought back to the foreground.
+pub(crate) struct BackgroundThread {
+    pub active_thread: Entity<ActiveThread>,
+    pub message_editor: Entity<MessageEditor>,
+    pub started_at: Instant,
+    pub finished_at: Option<Instant>,
+    pub _subscription: Subscription,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum BackgroundThreadStatus {
+    Generating,
+    RunningTool(SharedString),
+    WaitingForConfirmation,
+    Finished,
+}
+
+impl BackgroundThread {
+    pub fn status(&self, cx: &App) -> BackgroundThreadStatus {
+        let thread = self.active_thread.read(cx).thread().read(cx);
+        if thread.tools_needing_confirmation().next().is_some() {
+            BackgroundThreadStatus::WaitingForConfirmation
+        } else if let Some(tool_label) = thread.running_tool_label() {
+            BackgroundThreadStatus::RunningTool(tool_label)
+        } else if thread.is_generating() {
+            BackgroundThreadStatus::Generating
+        } else {
+            BackgroundThreadStatus::Finished
+        }
+    }
+
+    pub fn is_running(&self, cx: &App) -> bool {
+        self.status(cx) != BackgroundThreadStatus::Finished
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.finished_at
+            .unwrap_or_else(Instant::now)
+            .duration_since(self.started_at)
+    }
+
+    pub fn render(&self, ix: usize, cx: &App) -> ListItem {
+        let thread = self.active_thread.read(cx).thread().read(cx);
+        let summary = thread.summary_or_default();
+
+        let (icon, color, status) = match self.status(cx) {
+            BackgroundThreadStatus::Generating => {
+                (IconName::ArrowCircle, Color::Accent, "Generating…".into())
+            }
+            BackgroundThreadStatus::RunningTool(tool_label) => (
+                IconName::ArrowCircle,
+                Color::Accent,
+                SharedString::from(format!("Running {tool_label}")),
+            ),
+            BackgroundThreadStatus::WaitingForConfirmation => (
+                IconName::Warning,
+                Color::Warning,
+                "Waiting for tool confirmation".into(),
+            ),
+            BackgroundThreadStatus::Finished => {
+                (IconName::Check, Color::Success, "Finished".into())
+            }
+        };
+
+        ListItem::new(("background-thread", ix))
+            .inset(true)
+            .start_slot(Icon::new(icon).size(IconSize::Small).color(color))
+            .child(
+                v_flex().child(Label::new(summary).truncate()).child(
+                    Label::new(status.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .truncate(),
+                ),
+            )
+            .end_slot(
+                div()
+                    .id(("background-thread-elapsed", ix))
+                    .child(
+                        Label::new(format_elapsed(self.elapsed()))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .tooltip(Tooltip::text(status)),
+            )
+    }
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    if seconds < 60 {
+        format!("{seconds}s")
+    } else if seconds < 60 * 60 {
+        format!("{}m {}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}h {}m", seconds / 3600, (seconds % 3600) / 60)
+    }
+}
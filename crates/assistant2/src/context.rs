@@ -1,9 +1,12 @@
 use std::ops::Range;
+use std::sync::Arc;
 
 use file_icons::FileIcons;
-use gpui::{App, Entity, SharedString};
+use futures::future::Shared;
+use futures::FutureExt as _;
+use gpui::{App, Entity, Image, SharedString, Task};
 use language::Buffer;
-use language_model::{LanguageModelRequestMessage, MessageContent};
+use language_model::{LanguageModelImage, LanguageModelRequestMessage, MessageContent};
 use project::ProjectPath;
 use serde::{Deserialize, Serialize};
 use text::{Anchor, BufferId};
@@ -32,6 +35,17 @@ pub struct ContextSnapshot {
     pub kind: ContextKind,
     /// Joining these strings separated by \n yields text for model. Not refreshed by `snapshot`.
     pub text: Box<[SharedString]>,
+    /// The image sent to the model alongside the text, for image context.
+    pub image: Option<ContextImage>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContextImage {
+    /// The image as it was added, which is rendered as the context's thumbnail.
+    pub original: Arc<Image>,
+    /// The scaled-down image sent to the model, or `None` while it's still being
+    /// processed or if it couldn't be decoded.
+    pub image: Option<LanguageModelImage>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -41,6 +55,11 @@ pub enum ContextKind {
     Symbol,
     FetchedUrl,
     Thread,
+    Diagnostics,
+    ProjectSearch,
+    GitDiff,
+    DebugSession,
+    Image,
 }
 
 impl ContextKind {
@@ -51,6 +70,11 @@ impl ContextKind {
             ContextKind::Symbol => IconName::Code,
             ContextKind::FetchedUrl => IconName::Globe,
             ContextKind::Thread => IconName::MessageBubbles,
+            ContextKind::Diagnostics => IconName::Warning,
+            ContextKind::ProjectSearch => IconName::MagnifyingGlass,
+            ContextKind::GitDiff => IconName::GitBranch,
+            ContextKind::DebugSession => IconName::Debug,
+            ContextKind::Image => IconName::Image,
         }
     }
 }
@@ -62,6 +86,11 @@ pub enum AssistantContext {
     Symbol(SymbolContext),
     FetchedUrl(FetchedUrlContext),
     Thread(ThreadContext),
+    Diagnostics(DiagnosticsContext),
+    ProjectSearch(ProjectSearchContext),
+    GitDiff(GitDiffContext),
+    DebugSession(DebugSessionContext),
+    Image(ImageContext),
 }
 
 impl AssistantContext {
@@ -72,6 +101,11 @@ impl AssistantContext {
             Self::Symbol(symbol) => symbol.id,
             Self::FetchedUrl(url) => url.id,
             Self::Thread(thread) => thread.id,
+            Self::Diagnostics(diagnostics) => diagnostics.id,
+            Self::ProjectSearch(search) => search.id,
+            Self::GitDiff(diff) => diff.id,
+            Self::DebugSession(session) => session.id,
+            Self::Image(image) => image.id,
         }
     }
 }
@@ -112,6 +146,47 @@ pub struct ThreadContext {
     pub text: SharedString,
 }
 
+/// The errors and warnings currently reported in the project. Recomputed whenever a message is sent.
+#[derive(Debug)]
+pub struct DiagnosticsContext {
+    pub id: ContextId,
+    pub text: SharedString,
+}
+
+/// The results of a project-wide text search. Recomputed whenever a message is sent.
+#[derive(Debug)]
+pub struct ProjectSearchContext {
+    pub id: ContextId,
+    pub query: SharedString,
+    pub text: SharedString,
+}
+
+/// The uncommitted changes in the project's repositories. Recomputed whenever a message is sent.
+#[derive(Debug)]
+pub struct GitDiffContext {
+    pub id: ContextId,
+    pub text: SharedString,
+}
+
+/// The stack trace, locals, and recent console output of a paused debug session. Recomputed
+/// whenever a message is sent.
+#[derive(Debug)]
+pub struct DebugSessionContext {
+    pub id: ContextId,
+    pub text: SharedString,
+}
+
+/// An image pasted into the message editor or picked from the project's files.
+#[derive(Debug)]
+pub struct ImageContext {
+    pub id: ContextId,
+    pub name: SharedString,
+    pub original_image: Arc<Image>,
+    /// The largest width or height the image is scaled down to before it's sent.
+    pub max_size: u32,
+    pub image_task: Shared<Task<Option<LanguageModelImage>>>,
+}
+
 // TODO: Model<Buffer> holds onto the buffer even if the file is deleted and closed. Should remove
 // the context from the message editor in this case.
 
@@ -149,6 +224,11 @@ impl AssistantContext {
             Self::Symbol(symbol_context) => symbol_context.snapshot(cx),
             Self::FetchedUrl(fetched_url_context) => Some(fetched_url_context.snapshot()),
             Self::Thread(thread_context) => Some(thread_context.snapshot(cx)),
+            Self::Diagnostics(diagnostics_context) => Some(diagnostics_context.snapshot()),
+            Self::ProjectSearch(search_context) => Some(search_context.snapshot()),
+            Self::GitDiff(diff_context) => Some(diff_context.snapshot()),
+            Self::DebugSession(session_context) => Some(session_context.snapshot()),
+            Self::Image(image_context) => Some(image_context.snapshot()),
         }
     }
 }
@@ -177,6 +257,7 @@ impl FileContext {
             icon_path,
             kind: ContextKind::File,
             text: Box::new([self.context_buffer.text.clone()]),
+            image: None,
         })
     }
 }
@@ -218,6 +299,7 @@ impl DirectoryContext {
                 icon_path: None,
                 kind: ContextKind::Directory,
                 text,
+                image: None,
             },
         }
     }
@@ -244,6 +326,7 @@ impl SymbolContext {
             icon_path: None,
             kind: ContextKind::Symbol,
             text: Box::new([self.context_symbol.text.clone()]),
+            image: None,
         })
     }
 }
@@ -258,6 +341,7 @@ impl FetchedUrlContext {
             icon_path: None,
             kind: ContextKind::FetchedUrl,
             text: Box::new([self.text.clone()]),
+            image: None,
         }
     }
 }
@@ -273,6 +357,99 @@ impl ThreadContext {
             icon_path: None,
             kind: ContextKind::Thread,
             text: Box::new([self.text.clone()]),
+            image: None,
+        }
+    }
+}
+
+impl DiagnosticsContext {
+    pub fn snapshot(&self) -> ContextSnapshot {
+        ContextSnapshot {
+            id: self.id,
+            name: "Diagnostics".into(),
+            parent: None,
+            tooltip: Some("All errors and warnings in the project".into()),
+            icon_path: None,
+            kind: ContextKind::Diagnostics,
+            text: Box::new([self.text.clone()]),
+            image: None,
+        }
+    }
+}
+
+impl ProjectSearchContext {
+    pub fn snapshot(&self) -> ContextSnapshot {
+        ContextSnapshot {
+            id: self.id,
+            name: self.query.clone(),
+            parent: None,
+            tooltip: Some(format!("Project search results for \"{}\"", self.query).into()),
+            icon_path: None,
+            kind: ContextKind::ProjectSearch,
+            text: Box::new([self.text.clone()]),
+            image: None,
+        }
+    }
+}
+
+impl GitDiffContext {
+    pub fn snapshot(&self) -> ContextSnapshot {
+        ContextSnapshot {
+            id: self.id,
+            name: "Uncommitted Changes".into(),
+            parent: None,
+            tooltip: Some("The diff between HEAD and the working tree".into()),
+            icon_path: None,
+            kind: ContextKind::GitDiff,
+            text: Box::new([self.text.clone()]),
+            image: None,
+        }
+    }
+}
+
+impl DebugSessionContext {
+    pub fn snapshot(&self) -> ContextSnapshot {
+        ContextSnapshot {
+            id: self.id,
+            name: "Debug Session".into(),
+            parent: None,
+            tooltip: Some(
+                "The stack trace, locals, and console output of the paused debugger".into(),
+            ),
+            icon_path: None,
+            kind: ContextKind::DebugSession,
+            text: Box::new([self.text.clone()]),
+            image: None,
+        }
+    }
+}
+
+impl ImageContext {
+    pub fn snapshot(&self) -> ContextSnapshot {
+        let image = self.image_task.clone().now_or_never().flatten();
+        let tooltip = image.as_ref().map(|image| {
+            let size = image.size();
+            format!(
+                "{}×{} px, about {} tokens",
+                size.width.0,
+                size.height.0,
+                image.estimate_tokens()
+            )
+            .into()
+        });
+
+        ContextSnapshot {
+            id: self.id,
+            name: self.name.clone(),
+            parent: None,
+            tooltip,
+            icon_path: None,
+            kind: ContextKind::Image,
+            text: Box::new([]),
+            image: Some(ContextImage {
+                original: self.original_image.clone(),
+                image,
+            }),
         }
     }
 }
@@ -286,6 +463,11 @@ pub fn attach_context_to_message(
     let mut symbol_context = Vec::new();
     let mut fetch_context = Vec::new();
     let mut thread_context = Vec::new();
+    let mut diagnostics_context = Vec::new();
+    let mut search_context = Vec::new();
+    let mut git_diff_context = Vec::new();
+    let mut debug_session_context = Vec::new();
+    let mut images = Vec::new();
 
     let mut capacity = 0;
     for context in contexts {
@@ -296,6 +478,11 @@ pub fn attach_context_to_message(
             ContextKind::Symbol => symbol_context.push(context),
             ContextKind::FetchedUrl => fetch_context.push(context),
             ContextKind::Thread => thread_context.push(context),
+            ContextKind::Diagnostics => diagnostics_context.push(context),
+            ContextKind::ProjectSearch => search_context.push(context),
+            ContextKind::GitDiff => git_diff_context.push(context),
+            ContextKind::DebugSession => debug_session_context.push(context),
+            ContextKind::Image => images.extend(context.image.and_then(|image| image.image)),
         }
     }
     if !file_context.is_empty() {
@@ -313,7 +500,19 @@ pub fn attach_context_to_message(
     if !thread_context.is_empty() {
         capacity += 1 + thread_context.len();
     }
-    if capacity == 0 {
+    if !diagnostics_context.is_empty() {
+        capacity += 1;
+    }
+    if !search_context.is_empty() {
+        capacity += 1;
+    }
+    if !git_diff_context.is_empty() {
+        capacity += 1;
+    }
+    if !debug_session_context.is_empty() {
+        capacity += 1;
+    }
+    if capacity == 0 && images.is_empty() {
         return;
     }
 
@@ -366,6 +565,42 @@ pub fn attach_context_to_message(
         }
     }
 
+    if !diagnostics_context.is_empty() {
+        context_chunks.push("The following diagnostics are currently reported in the project:\n");
+        for context in &diagnostics_context {
+            for chunk in &context.text {
+                context_chunks.push(&chunk);
+            }
+        }
+    }
+
+    if !search_context.is_empty() {
+        context_chunks.push("The following project search results are available:\n");
+        for context in &search_context {
+            for chunk in &context.text {
+                context_chunks.push(&chunk);
+            }
+        }
+    }
+
+    if !git_diff_context.is_empty() {
+        context_chunks.push("The project has the following uncommitted changes:\n");
+        for context in &git_diff_context {
+            for chunk in &context.text {
+                context_chunks.push(&chunk);
+            }
+        }
+    }
+
+    if !debug_session_context.is_empty() {
+        context_chunks.push("The project's debugger is paused in the following state:\n");
+        for context in &debug_session_context {
+            for chunk in &context.text {
+                context_chunks.push(&chunk);
+            }
+        }
+    }
+
     debug_assert!(
         context_chunks.len() == capacity,
         "attach_context_message calculated capacity of {}, but length was {}",
@@ -378,4 +613,8 @@ pub fn attach_context_to_message(
             .content
             .push(MessageContent::Text(context_chunks.join("\n")));
     }
+
+    message
+        .content
+        .extend(images.into_iter().map(MessageContent::Image));
 }
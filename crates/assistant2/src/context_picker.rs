@@ -1,6 +1,7 @@
 mod completion_provider;
 mod fetch_context_picker;
 mod file_context_picker;
+mod search_context_picker;
 mod symbol_context_picker;
 mod thread_context_picker;
 
@@ -27,8 +28,10 @@ use workspace::{notifications::NotifyResultExt, Workspace};
 pub use crate::context_picker::completion_provider::ContextPickerCompletionProvider;
 use crate::context_picker::fetch_context_picker::FetchContextPicker;
 use crate::context_picker::file_context_picker::FileContextPicker;
+use crate::context_picker::search_context_picker::SearchContextPicker;
 use crate::context_picker::thread_context_picker::ThreadContextPicker;
 use crate::context_store::ContextStore;
+use crate::screenshot;
 use crate::thread_store::ThreadStore;
 use crate::AssistantPanel;
 
@@ -44,6 +47,11 @@ enum ContextPickerMode {
     Symbol,
     Fetch,
     Thread,
+    Diagnostics,
+    Search,
+    GitDiff,
+    DebugSession,
+    Screenshot,
 }
 
 impl TryFrom<&str> for ContextPickerMode {
@@ -55,6 +63,11 @@ impl TryFrom<&str> for ContextPickerMode {
             "symbol" => Ok(Self::Symbol),
             "fetch" => Ok(Self::Fetch),
             "thread" => Ok(Self::Thread),
+            "diagnostics" => Ok(Self::Diagnostics),
+            "search" => Ok(Self::Search),
+            "diff" => Ok(Self::GitDiff),
+            "debug" => Ok(Self::DebugSession),
+            "screenshot" => Ok(Self::Screenshot),
             _ => Err(format!("Invalid context picker mode: {}", value)),
         }
     }
@@ -67,6 +80,11 @@ impl ContextPickerMode {
             Self::Symbol => "symbol",
             Self::Fetch => "fetch",
             Self::Thread => "thread",
+            Self::Diagnostics => "diagnostics",
+            Self::Search => "search",
+            Self::GitDiff => "diff",
+            Self::DebugSession => "debug",
+            Self::Screenshot => "screenshot",
         }
     }
 
@@ -76,6 +94,11 @@ impl ContextPickerMode {
             Self::Symbol => "Symbols",
             Self::Fetch => "Fetch",
             Self::Thread => "Thread",
+            Self::Diagnostics => "Diagnostics",
+            Self::Search => "Project Search",
+            Self::GitDiff => "Git Diff",
+            Self::DebugSession => "Debug Session",
+            Self::Screenshot => "Screenshot",
         }
     }
 
@@ -85,6 +108,11 @@ impl ContextPickerMode {
             Self::Symbol => IconName::Code,
             Self::Fetch => IconName::Globe,
             Self::Thread => IconName::MessageBubbles,
+            Self::Diagnostics => IconName::Warning,
+            Self::Search => IconName::MagnifyingGlass,
+            Self::GitDiff => IconName::GitBranch,
+            Self::DebugSession => IconName::Debug,
+            Self::Screenshot => IconName::Image,
         }
     }
 }
@@ -96,6 +124,7 @@ enum ContextPickerState {
     Symbol(Entity<SymbolContextPicker>),
     Fetch(Entity<FetchContextPicker>),
     Thread(Entity<ThreadContextPicker>),
+    Search(Entity<SearchContextPicker>),
 }
 
 pub(super) struct ContextPicker {
@@ -144,7 +173,7 @@ impl ContextPicker {
                 .enumerate()
                 .map(|(ix, entry)| self.recent_menu_item(context_picker.clone(), ix, entry));
 
-            let modes = supported_context_picker_modes(&self.thread_store);
+            let modes = supported_context_picker_modes(&self.thread_store, &self.context_store, cx);
 
             let menu = menu
                 .when(has_recent, |menu| {
@@ -251,12 +280,61 @@ impl ContextPicker {
                     }));
                 }
             }
+            ContextPickerMode::Search => {
+                self.mode = ContextPickerState::Search(cx.new(|cx| {
+                    SearchContextPicker::new(
+                        context_picker.clone(),
+                        self.context_store.clone(),
+                        self.confirm_behavior,
+                        window,
+                        cx,
+                    )
+                }));
+            }
+            ContextPickerMode::Diagnostics
+            | ContextPickerMode::GitDiff
+            | ContextPickerMode::DebugSession
+            | ContextPickerMode::Screenshot => {
+                self.add_project_state(mode, window, cx);
+                return;
+            }
         }
 
         cx.notify();
         cx.focus_self(window);
     }
 
+    /// Adds context that doesn't require any further input in the picker, such as the current
+    /// diagnostics or a screenshot.
+    fn add_project_state(
+        &mut self,
+        mode: ContextPickerMode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(context_store) = self.context_store.upgrade() else {
+            return;
+        };
+
+        let task = context_store.update(cx, |context_store, cx| match mode {
+            ContextPickerMode::GitDiff => context_store.add_git_diff(true, cx),
+            ContextPickerMode::DebugSession => context_store.add_debug_session(true, cx),
+            ContextPickerMode::Screenshot => context_store.add_screenshot(cx),
+            _ => context_store.add_diagnostics(true, cx),
+        });
+
+        cx.spawn_in(window, async move |_, cx| task.await.notify_async_err(cx))
+            .detach();
+
+        match self.confirm_behavior {
+            ConfirmBehavior::KeepOpen => {
+                self.mode = ContextPickerState::Default(self.build_menu(window, cx));
+                cx.notify();
+            }
+            ConfirmBehavior::Close => cx.emit(DismissEvent),
+        }
+    }
+
     fn recent_menu_item(
         &self,
         context_picker: Entity<ContextPicker>,
@@ -439,6 +517,7 @@ impl Focusable for ContextPicker {
             ContextPickerState::Symbol(symbol_picker) => symbol_picker.focus_handle(cx),
             ContextPickerState::Fetch(fetch_picker) => fetch_picker.focus_handle(cx),
             ContextPickerState::Thread(thread_picker) => thread_picker.focus_handle(cx),
+            ContextPickerState::Search(search_picker) => search_picker.focus_handle(cx),
         }
     }
 }
@@ -454,6 +533,7 @@ impl Render for ContextPicker {
                 ContextPickerState::Symbol(symbol_picker) => parent.child(symbol_picker.clone()),
                 ContextPickerState::Fetch(fetch_picker) => parent.child(fetch_picker.clone()),
                 ContextPickerState::Thread(thread_picker) => parent.child(thread_picker.clone()),
+                ContextPickerState::Search(search_picker) => parent.child(search_picker.clone()),
             })
     }
 }
@@ -467,15 +547,29 @@ enum RecentEntry {
 
 fn supported_context_picker_modes(
     thread_store: &Option<WeakEntity<ThreadStore>>,
+    context_store: &WeakEntity<ContextStore>,
+    cx: &App,
 ) -> Vec<ContextPickerMode> {
     let mut modes = vec![
         ContextPickerMode::File,
         ContextPickerMode::Symbol,
         ContextPickerMode::Fetch,
+        ContextPickerMode::Diagnostics,
+        ContextPickerMode::Search,
+        ContextPickerMode::GitDiff,
     ];
+    // The debug session can only be captured while it's paused.
+    if context_store.upgrade().map_or(false, |context_store| {
+        context_store.read(cx).has_paused_debug_session(cx)
+    }) {
+        modes.push(ContextPickerMode::DebugSession);
+    }
     if thread_store.is_some() {
         modes.push(ContextPickerMode::Thread);
     }
+    if screenshot::is_supported() {
+        modes.push(ContextPickerMode::Screenshot);
+    }
     modes
 }
 
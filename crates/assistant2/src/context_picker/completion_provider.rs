@@ -100,7 +100,7 @@ impl ContextPickerCompletionProvider {
         );
 
         completions.extend(
-            supported_context_picker_modes(&thread_store)
+            supported_context_picker_modes(&thread_store, &context_store.downgrade(), cx)
                 .iter()
                 .map(|mode| {
                     Completion {
@@ -240,6 +240,82 @@ impl ContextPickerCompletionProvider {
         }
     }
 
+    fn completion_for_project_search(
+        source_range: Range<Anchor>,
+        query: SharedString,
+        excerpt_id: ExcerptId,
+        editor: Entity<Editor>,
+        context_store: Entity<ContextStore>,
+    ) -> Completion {
+        let new_text = format!("@search {}", query);
+        let new_text_len = new_text.len();
+        Completion {
+            old_range: source_range.clone(),
+            new_text,
+            label: CodeLabel::plain(query.to_string(), None),
+            documentation: None,
+            source: project::CompletionSource::Custom,
+            icon_path: Some(IconName::MagnifyingGlass.path().into()),
+            confirm: Some(confirm_completion_callback(
+                IconName::MagnifyingGlass.path().into(),
+                query.clone(),
+                excerpt_id,
+                source_range.start,
+                new_text_len,
+                editor.clone(),
+                move |cx| {
+                    context_store.update(cx, |context_store, cx| {
+                        context_store
+                            .add_project_search(query.clone(), false, cx)
+                            .detach_and_log_err(cx);
+                    })
+                },
+            )),
+        }
+    }
+
+    /// Returns a completion for context that is captured from the project as a whole, such as
+    /// its diagnostics, uncommitted changes, or paused debug session, or from the screen.
+    fn completion_for_project_state(
+        mode: ContextPickerMode,
+        source_range: Range<Anchor>,
+        excerpt_id: ExcerptId,
+        editor: Entity<Editor>,
+        context_store: Entity<ContextStore>,
+    ) -> Completion {
+        let new_text = format!("@{}", mode.mention_prefix());
+        let new_text_len = new_text.len();
+        Completion {
+            old_range: source_range.clone(),
+            new_text,
+            label: CodeLabel::plain(mode.label().to_string(), None),
+            documentation: None,
+            source: project::CompletionSource::Custom,
+            icon_path: Some(mode.icon().path().into()),
+            confirm: Some(confirm_completion_callback(
+                mode.icon().path().into(),
+                mode.label().into(),
+                excerpt_id,
+                source_range.start,
+                new_text_len,
+                editor.clone(),
+                move |cx| {
+                    context_store.update(cx, |context_store, cx| {
+                        let task = match mode {
+                            ContextPickerMode::GitDiff => context_store.add_git_diff(false, cx),
+                            ContextPickerMode::DebugSession => {
+                                context_store.add_debug_session(false, cx)
+                            }
+                            ContextPickerMode::Screenshot => context_store.add_screenshot(cx),
+                            _ => context_store.add_diagnostics(false, cx),
+                        };
+                        task.detach_and_log_err(cx);
+                    })
+                },
+            )),
+        }
+    }
+
     fn completion_for_path(
         project_path: ProjectPath,
         path_prefix: &str,
@@ -538,6 +614,35 @@ impl CompletionProvider for ContextPickerCompletionProvider {
                         }
                     }
                 }
+                Some(ContextPickerMode::Search) => {
+                    if let Some(editor) = editor.upgrade() {
+                        if !query.is_empty() {
+                            completions.push(Self::completion_for_project_search(
+                                source_range.clone(),
+                                query.into(),
+                                excerpt_id,
+                                editor.clone(),
+                                context_store.clone(),
+                            ));
+                        }
+                    }
+                }
+                Some(
+                    mode @ (ContextPickerMode::Diagnostics
+                    | ContextPickerMode::GitDiff
+                    | ContextPickerMode::DebugSession
+                    | ContextPickerMode::Screenshot),
+                ) => {
+                    if let Some(editor) = editor.upgrade() {
+                        completions.push(Self::completion_for_project_state(
+                            mode,
+                            source_range.clone(),
+                            excerpt_id,
+                            editor,
+                            context_store.clone(),
+                        ));
+                    }
+                }
                 None => {
                     cx.update(|cx| {
                         if let Some(editor) = editor.upgrade() {
@@ -869,18 +974,22 @@ mod tests {
         editor.update(&mut cx, |editor, cx| {
             assert_eq!(editor.text(cx), "Lorem @");
             assert!(editor.has_visible_completions_menu());
-            assert_eq!(
-                current_completion_labels(editor),
-                &[
-                    "seven.txt dir/b/",
-                    "six.txt dir/b/",
-                    "five.txt dir/b/",
-                    "four.txt dir/a/",
-                    "Files & Directories",
-                    "Symbols",
-                    "Fetch"
-                ]
-            );
+            let mut expected_labels = vec![
+                "seven.txt dir/b/",
+                "six.txt dir/b/",
+                "five.txt dir/b/",
+                "four.txt dir/a/",
+                "Files & Directories",
+                "Symbols",
+                "Fetch",
+                "Diagnostics",
+                "Project Search",
+                "Git Diff",
+            ];
+            if crate::screenshot::is_supported() {
+                expected_labels.push("Screenshot");
+            }
+            assert_eq!(current_completion_labels(editor), expected_labels);
         });
 
         // Select and confirm "File"
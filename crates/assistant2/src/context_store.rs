@@ -1,23 +1,37 @@
+use std::fmt::Write as _;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{anyhow, bail, Result};
+use assistant_settings::AssistantSettings;
 use collections::{BTreeMap, HashMap, HashSet};
-use futures::{self, future, Future, FutureExt};
-use gpui::{App, AppContext as _, AsyncApp, Context, Entity, SharedString, Task, WeakEntity};
-use language::Buffer;
-use project::{ProjectItem, ProjectPath, Worktree};
+use futures::future::Shared;
+use futures::{self, future, Future, FutureExt, StreamExt as _};
+use git::repository::DiffType;
+use gpui::{
+    App, AppContext as _, AsyncApp, Context, Entity, Image, SharedString, Task, WeakEntity,
+};
+use language::{Buffer, DiagnosticSeverity};
+use language_model::LanguageModelImage;
+use project::debugger::session::{OutputToken, Session, ThreadId as DebugThreadId, ThreadStatus};
+use project::search::{SearchQuery, SearchResult};
+use project::{Project, ProjectItem, ProjectPath, Worktree};
 use rope::Rope;
-use text::{Anchor, BufferId, OffsetRangeExt};
-use util::maybe;
+use settings::Settings as _;
+use text::{Anchor, BufferId, OffsetRangeExt, ToPoint as _};
+use util::paths::PathMatcher;
+use util::{maybe, ResultExt as _};
 use workspace::Workspace;
 
 use crate::context::{
     AssistantContext, ContextBuffer, ContextId, ContextSnapshot, ContextSymbol, ContextSymbolId,
-    DirectoryContext, FetchedUrlContext, FileContext, SymbolContext, ThreadContext,
+    DebugSessionContext, DiagnosticsContext, DirectoryContext, FetchedUrlContext, FileContext,
+    GitDiffContext, ImageContext, ProjectSearchContext, SymbolContext, ThreadContext,
 };
 use crate::context_strip::SuggestedContext;
+use crate::screenshot;
 use crate::thread::{Thread, ThreadId};
 
 pub struct ContextStore {
@@ -32,8 +46,39 @@ pub struct ContextStore {
     symbols_by_path: HashMap<ProjectPath, Vec<ContextSymbolId>>,
     threads: HashMap<ThreadId, ContextId>,
     fetched_urls: HashMap<String, ContextId>,
+    diagnostics: Option<ContextId>,
+    project_searches: HashMap<SharedString, ContextId>,
+    git_diff: Option<ContextId>,
+    debug_session: Option<ContextId>,
+    images: HashMap<u64, ContextId>,
 }
 
+/// The maximum number of matching lines included in project search context.
+const MAX_SEARCH_CONTEXT_MATCHES: usize = 100;
+
+/// The maximum number of stack frames included in debug session context.
+const MAX_DEBUG_STACK_FRAMES: usize = 50;
+
+/// The maximum number of variables included per scope in debug session context.
+const MAX_DEBUG_VARIABLES_PER_SCOPE: usize = 100;
+
+/// The maximum number of characters of a variable's value included in debug session context.
+const MAX_DEBUG_VALUE_LEN: usize = 200;
+
+/// The maximum number of console output events included in debug session context.
+const MAX_DEBUG_OUTPUT_EVENTS: usize = 50;
+
+/// How often, and how many times, to check whether the debug adapter has reported the paused
+/// session's state before giving up on waiting for it.
+const DEBUG_SESSION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const DEBUG_SESSION_POLL_ATTEMPTS: usize = 20;
+
+/// The extensions of files that are added as image context rather than as text.
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp", "gif", "bmp", "tif", "tiff"];
+
+/// The smallest width or height an image can be downscaled to.
+pub const MIN_IMAGE_SIZE: u32 = 128;
+
 impl ContextStore {
     pub fn new(workspace: WeakEntity<Workspace>) -> Self {
         Self {
@@ -47,9 +92,20 @@ impl ContextStore {
             symbols_by_path: HashMap::default(),
             threads: HashMap::default(),
             fetched_urls: HashMap::default(),
+            diagnostics: None,
+            project_searches: HashMap::default(),
+            git_diff: None,
+            debug_session: None,
+            images: HashMap::default(),
         }
     }
 
+    fn project(&self, cx: &App) -> Option<Entity<Project>> {
+        self.workspace
+            .upgrade()
+            .map(|workspace| workspace.read(cx).project().clone())
+    }
+
     pub fn snapshot<'a>(&'a self, cx: &'a App) -> impl Iterator<Item = ContextSnapshot> + 'a {
         self.context()
             .iter()
@@ -70,6 +126,11 @@ impl ContextStore {
         self.directories.clear();
         self.threads.clear();
         self.fetched_urls.clear();
+        self.diagnostics = None;
+        self.project_searches.clear();
+        self.git_diff = None;
+        self.debug_session = None;
+        self.images.clear();
     }
 
     pub fn add_file_from_path(
@@ -78,6 +139,10 @@ impl ContextStore {
         remove_if_exists: bool,
         cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
+        if is_image_path(&project_path.path) {
+            return self.add_image_from_path(project_path, remove_if_exists, cx);
+        }
+
         let workspace = self.workspace.clone();
 
         let Some(project) = workspace
@@ -396,6 +461,242 @@ impl ContextStore {
             }));
     }
 
+    pub fn add_diagnostics(
+        &mut self,
+        remove_if_exists: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if let Some(context_id) = self.diagnostics {
+            if remove_if_exists {
+                self.remove_context(context_id);
+            }
+            return Task::ready(Ok(()));
+        }
+        let Some(project) = self.project(cx) else {
+            return Task::ready(Err(anyhow!("failed to read project")));
+        };
+
+        let text_task = collect_diagnostics_text(project, cx);
+        cx.spawn(async move |this, cx| {
+            let text = text_task.await?;
+            this.update(cx, |this, _| {
+                if this.diagnostics.is_none() {
+                    let id = this.next_context_id.post_inc();
+                    this.diagnostics = Some(id);
+                    this.context
+                        .push(AssistantContext::Diagnostics(DiagnosticsContext {
+                            id,
+                            text,
+                        }));
+                }
+            })
+        })
+    }
+
+    pub fn add_project_search(
+        &mut self,
+        query: SharedString,
+        remove_if_exists: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if let Some(context_id) = self.includes_project_search(&query) {
+            if remove_if_exists {
+                self.remove_context(context_id);
+            }
+            return Task::ready(Ok(()));
+        }
+        let Some(project) = self.project(cx) else {
+            return Task::ready(Err(anyhow!("failed to read project")));
+        };
+
+        let text_task = collect_project_search_text(project, query.clone(), cx);
+        cx.spawn(async move |this, cx| {
+            let text = text_task.await?;
+            this.update(cx, |this, _| {
+                if this.includes_project_search(&query).is_none() {
+                    let id = this.next_context_id.post_inc();
+                    this.project_searches.insert(query.clone(), id);
+                    this.context
+                        .push(AssistantContext::ProjectSearch(ProjectSearchContext {
+                            id,
+                            query,
+                            text,
+                        }));
+                }
+            })
+        })
+    }
+
+    pub fn add_git_diff(
+        &mut self,
+        remove_if_exists: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if let Some(context_id) = self.git_diff {
+            if remove_if_exists {
+                self.remove_context(context_id);
+            }
+            return Task::ready(Ok(()));
+        }
+        let Some(project) = self.project(cx) else {
+            return Task::ready(Err(anyhow!("failed to read project")));
+        };
+
+        let text_task = collect_git_diff_text(project, cx);
+        cx.spawn(async move |this, cx| {
+            let text = text_task.await?;
+            this.update(cx, |this, _| {
+                if this.git_diff.is_none() {
+                    let id = this.next_context_id.post_inc();
+                    this.git_diff = Some(id);
+                    this.context
+                        .push(AssistantContext::GitDiff(GitDiffContext { id, text }));
+                }
+            })
+        })
+    }
+
+    pub fn add_debug_session(
+        &mut self,
+        remove_if_exists: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if let Some(context_id) = self.debug_session {
+            if remove_if_exists {
+                self.remove_context(context_id);
+            }
+            return Task::ready(Ok(()));
+        }
+        let Some(project) = self.project(cx) else {
+            return Task::ready(Err(anyhow!("failed to read project")));
+        };
+
+        let text_task = collect_debug_session_text(project, cx);
+        cx.spawn(async move |this, cx| {
+            let text = text_task.await?;
+            this.update(cx, |this, _| {
+                if this.debug_session.is_none() {
+                    let id = this.next_context_id.post_inc();
+                    this.debug_session = Some(id);
+                    this.context
+                        .push(AssistantContext::DebugSession(DebugSessionContext {
+                            id,
+                            text,
+                        }));
+                }
+            })
+        })
+    }
+
+    fn add_image_from_path(
+        &mut self,
+        project_path: ProjectPath,
+        remove_if_exists: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let Some(project) = self.project(cx) else {
+            return Task::ready(Err(anyhow!("failed to read project")));
+        };
+        let name: SharedString = project_path
+            .path
+            .file_name()
+            .unwrap_or(project_path.path.as_os_str())
+            .to_string_lossy()
+            .into_owned()
+            .into();
+
+        cx.spawn(async move |this, cx| {
+            let image_item = project
+                .update(cx, |project, cx| project.open_image(project_path, cx))?
+                .await?;
+            let image = image_item.read_with(cx, |image_item, _| image_item.image.clone())?;
+
+            this.update(cx, |this, cx| match this.includes_image(&image) {
+                Some(context_id) => {
+                    if remove_if_exists {
+                        this.remove_context(context_id);
+                    }
+                }
+                None => this.add_image(image, name, cx),
+            })
+        })
+    }
+
+    /// Adds an image, such as one pasted from the clipboard, scaling it down to the
+    /// `max_image_size` setting before it's sent.
+    pub fn add_image(
+        &mut self,
+        image: Arc<Image>,
+        name: impl Into<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.includes_image(&image).is_some() {
+            return;
+        }
+
+        let id = self.next_context_id.post_inc();
+        let max_size = AssistantSettings::get_global(cx).max_image_size;
+        let image_task = process_image(image.clone(), max_size, cx);
+        self.images.insert(image.id(), id);
+        self.context.push(AssistantContext::Image(ImageContext {
+            id,
+            name: name.into(),
+            original_image: image,
+            max_size,
+            image_task,
+        }));
+        cx.notify();
+    }
+
+    /// Lets the user select a region of the screen, such as part of a Zed window, and adds
+    /// it as image context. Nothing is added if the selection is cancelled.
+    pub fn add_screenshot(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let capture = screenshot::capture_screen_region(cx);
+        cx.spawn(async move |this, cx| {
+            let Some(image) = capture.await? else {
+                return Ok(());
+            };
+            this.update(cx, |this, cx| {
+                this.add_image(Arc::new(image), "Screenshot", cx)
+            })
+        })
+    }
+
+    /// Returns the size the image context can be downscaled to, or `None` if it's still being
+    /// processed or is already as small as it can be.
+    pub fn downscaled_image_size(&self, id: ContextId) -> Option<u32> {
+        let Some(AssistantContext::Image(image_context)) = self.context_for_id(id) else {
+            return None;
+        };
+        let image = image_context.image_task.clone().now_or_never().flatten()?;
+        let size = image.size();
+        downscaled_size(size.width.0.max(size.height.0) as u32)
+    }
+
+    /// Halves the size of an image context, so that it uses fewer tokens.
+    pub fn downscale_image(&mut self, id: ContextId, cx: &mut Context<Self>) {
+        let Some(max_size) = self.downscaled_image_size(id) else {
+            return;
+        };
+        let Some(AssistantContext::Image(image_context)) =
+            self.context.iter_mut().find(|context| context.id() == id)
+        else {
+            return;
+        };
+
+        image_context.max_size = max_size;
+        image_context.image_task =
+            process_image(image_context.original_image.clone(), max_size, cx);
+        cx.notify();
+    }
+
+    /// Returns whether the project has a debug session that is paused, and can be added as context.
+    pub fn has_paused_debug_session(&self, cx: &App) -> bool {
+        self.project(cx).map_or(false, |project| {
+            paused_debug_session(&project, cx).is_some()
+        })
+    }
+
     pub fn accept_suggested_context(
         &mut self,
         suggested: &SuggestedContext,
@@ -451,6 +752,22 @@ impl ContextStore {
             AssistantContext::Thread(_) => {
                 self.threads.retain(|_, context_id| *context_id != id);
             }
+            AssistantContext::Diagnostics(_) => {
+                self.diagnostics = None;
+            }
+            AssistantContext::ProjectSearch(_) => {
+                self.project_searches
+                    .retain(|_, context_id| *context_id != id);
+            }
+            AssistantContext::GitDiff(_) => {
+                self.git_diff = None;
+            }
+            AssistantContext::DebugSession(_) => {
+                self.debug_session = None;
+            }
+            AssistantContext::Image(_) => {
+                self.images.retain(|_, context_id| *context_id != id);
+            }
         }
     }
 
@@ -528,6 +845,26 @@ impl ContextStore {
         self.fetched_urls.get(url).copied()
     }
 
+    pub fn includes_diagnostics(&self) -> Option<ContextId> {
+        self.diagnostics
+    }
+
+    pub fn includes_project_search(&self, query: &str) -> Option<ContextId> {
+        self.project_searches.get(query).copied()
+    }
+
+    pub fn includes_git_diff(&self) -> Option<ContextId> {
+        self.git_diff
+    }
+
+    pub fn includes_image(&self, image: &Image) -> Option<ContextId> {
+        self.images.get(&image.id()).copied()
+    }
+
+    pub fn includes_debug_session(&self) -> Option<ContextId> {
+        self.debug_session
+    }
+
     /// Replaces the context that matches the ID of the new context, if any match.
     fn replace_context(&mut self, new_context: AssistantContext) {
         let id = new_context.id();
@@ -550,7 +887,12 @@ impl ContextStore {
                 AssistantContext::Directory(_)
                 | AssistantContext::Symbol(_)
                 | AssistantContext::FetchedUrl(_)
-                | AssistantContext::Thread(_) => None,
+                | AssistantContext::Thread(_)
+                | AssistantContext::Diagnostics(_)
+                | AssistantContext::ProjectSearch(_)
+                | AssistantContext::GitDiff(_)
+                | AssistantContext::DebugSession(_)
+                | AssistantContext::Image(_) => None,
             })
             .collect()
     }
@@ -683,6 +1025,37 @@ fn collect_files_in_path(worktree: &Worktree, path: &Path) -> Vec<Arc<Path>> {
     files
 }
 
+/// Returns half of an image's largest dimension, but no less than [`MIN_IMAGE_SIZE`], or `None`
+/// if the image is already that small.
+fn downscaled_size(current_size: u32) -> Option<u32> {
+    let downscaled_size = (current_size / 2).max(MIN_IMAGE_SIZE);
+    (downscaled_size < current_size).then_some(downscaled_size)
+}
+
+fn is_image_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map_or(false, |extension| {
+            IMAGE_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+        })
+}
+
+/// Scales down and encodes the image for the model, re-rendering the context store once
+/// it's done so that its size shows up.
+fn process_image(
+    image: Arc<Image>,
+    max_size: u32,
+    cx: &mut Context<ContextStore>,
+) -> Shared<Task<Option<LanguageModelImage>>> {
+    let image_task = LanguageModelImage::from_image_with_max_size(image, max_size, cx);
+    cx.spawn(async move |this, cx| {
+        let image = image_task.await;
+        this.update(cx, |_, cx| cx.notify()).ok();
+        image
+    })
+    .shared()
+}
+
 pub fn refresh_context_store_text(
     context_store: Entity<ContextStore>,
     changed_buffers: &HashSet<Entity<Buffer>>,
@@ -732,6 +1105,24 @@ pub fn refresh_context_store_text(
                         return Some(refresh_thread_text(context_store, thread_context, cx));
                     }
                 }
+                AssistantContext::Diagnostics(_)
+                | AssistantContext::ProjectSearch(_)
+                | AssistantContext::GitDiff(_)
+                | AssistantContext::DebugSession(_) => {
+                    if changed_buffers.is_empty() {
+                        let context_store = context_store.clone();
+                        return refresh_project_state_text(context_store, context, cx);
+                    }
+                }
+                // Images don't change, but sending has to wait until they've been processed.
+                AssistantContext::Image(image_context) => {
+                    if changed_buffers.is_empty() {
+                        let image_task = image_context.image_task.clone();
+                        return Some(cx.background_spawn(async move {
+                            image_task.await;
+                        }));
+                    }
+                }
                 // Intentionally omit refreshing fetched URLs as it doesn't seem all that useful,
                 // and doing the caching properly could be tricky (unless it's already handled by
                 // the HttpClient?).
@@ -852,6 +1243,356 @@ fn refresh_thread_text(
     })
 }
 
+/// Recomputes context that reflects the current state of the project, such as its diagnostics.
+fn refresh_project_state_text(
+    context_store: Entity<ContextStore>,
+    context: &AssistantContext,
+    cx: &App,
+) -> Option<Task<()>> {
+    let project = context_store.read(cx).project(cx)?;
+    let id = context.id();
+    let (text_task, make_context): (_, Box<dyn FnOnce(SharedString) -> AssistantContext>) =
+        match context {
+            AssistantContext::Diagnostics(_) => (
+                collect_diagnostics_text(project, cx),
+                Box::new(move |text| {
+                    AssistantContext::Diagnostics(DiagnosticsContext { id, text })
+                }),
+            ),
+            AssistantContext::ProjectSearch(search_context) => {
+                let query = search_context.query.clone();
+                (
+                    collect_project_search_text(project, query.clone(), cx),
+                    Box::new(move |text| {
+                        AssistantContext::ProjectSearch(ProjectSearchContext { id, query, text })
+                    }),
+                )
+            }
+            AssistantContext::GitDiff(_) => (
+                collect_git_diff_text(project, cx),
+                Box::new(move |text| AssistantContext::GitDiff(GitDiffContext { id, text })),
+            ),
+            AssistantContext::DebugSession(_) => (
+                collect_debug_session_text(project, cx),
+                Box::new(move |text| {
+                    AssistantContext::DebugSession(DebugSessionContext { id, text })
+                }),
+            ),
+            _ => return None,
+        };
+
+    Some(cx.spawn(async move |cx| {
+        let Some(text) = text_task.await.log_err() else {
+            return;
+        };
+        context_store
+            .update(cx, |context_store, _| {
+                context_store.replace_context(make_context(text));
+            })
+            .ok();
+    }))
+}
+
+fn collect_diagnostics_text(project: Entity<Project>, cx: &App) -> Task<Result<SharedString>> {
+    let mut seen_paths = HashSet::default();
+    let paths = project
+        .read(cx)
+        .diagnostic_summaries(false, cx)
+        .filter(|(_, _, summary)| summary.error_count > 0 || summary.warning_count > 0)
+        .filter_map(|(project_path, _, _)| {
+            seen_paths
+                .insert(project_path.clone())
+                .then_some(project_path)
+        })
+        .collect::<Vec<_>>();
+
+    cx.spawn(async move |cx| {
+        let mut text = String::new();
+        for project_path in paths {
+            let buffer = project
+                .update(cx, |project, cx| project.open_buffer(project_path, cx))?
+                .await?;
+            buffer.read_with(cx, |buffer, cx| -> Result<()> {
+                let Some(file) = buffer.file() else {
+                    return Ok(());
+                };
+                let snapshot = buffer.snapshot();
+                writeln!(text, "## {}", file.full_path(cx).display())?;
+                for (_, group) in snapshot.diagnostic_groups(None) {
+                    let entry = &group.entries[group.primary_ix];
+                    let severity = match entry.diagnostic.severity {
+                        DiagnosticSeverity::ERROR => "error",
+                        DiagnosticSeverity::WARNING => "warning",
+                        _ => continue,
+                    };
+                    writeln!(
+                        text,
+                        "- {severity} at line {}: {}",
+                        entry.range.start.to_point(&snapshot).row + 1,
+                        entry.diagnostic.message
+                    )?;
+                }
+                Ok(())
+            })??;
+        }
+
+        if text.is_empty() {
+            text.push_str("No errors or warnings are reported in the project.\n");
+        }
+        Ok(text.into())
+    })
+}
+
+fn collect_project_search_text(
+    project: Entity<Project>,
+    query: SharedString,
+    cx: &App,
+) -> Task<Result<SharedString>> {
+    let search_query = match SearchQuery::text(
+        query.as_ref(),
+        false,
+        false,
+        false,
+        PathMatcher::default(),
+        PathMatcher::default(),
+        None,
+    ) {
+        Ok(search_query) => search_query,
+        Err(error) => return Task::ready(Err(error)),
+    };
+
+    cx.spawn(async move |cx| {
+        let results = project.update(cx, |project, cx| project.search(search_query, cx))?;
+        futures::pin_mut!(results);
+
+        let mut text = String::new();
+        let mut match_count = 0;
+        let mut truncated = false;
+        while let Some(result) = results.next().await {
+            let SearchResult::Buffer { buffer, ranges } = result else {
+                truncated = true;
+                break;
+            };
+            buffer.read_with(cx, |buffer, cx| -> Result<()> {
+                let Some(file) = buffer.file() else {
+                    return Ok(());
+                };
+                let path = file.full_path(cx);
+                let mut last_row = None;
+                for range in ranges {
+                    let row = range.start.to_point(buffer).row;
+                    if last_row == Some(row) {
+                        continue;
+                    }
+                    last_row = Some(row);
+                    if match_count == MAX_SEARCH_CONTEXT_MATCHES {
+                        truncated = true;
+                        break;
+                    }
+                    match_count += 1;
+                    let line = buffer
+                        .text_for_range(
+                            text::Point::new(row, 0)..text::Point::new(row, buffer.line_len(row)),
+                        )
+                        .collect::<String>();
+                    writeln!(text, "{}:{}: {}", path.display(), row + 1, line.trim())?;
+                }
+                Ok(())
+            })??;
+            if truncated {
+                break;
+            }
+        }
+
+        let header = if match_count == 0 {
+            format!("No results for `{query}`.\n")
+        } else if truncated {
+            format!("First {match_count} matching lines for `{query}`:\n```\n")
+        } else {
+            format!("{match_count} matching lines for `{query}`:\n```\n")
+        };
+        if match_count > 0 {
+            text.push_str("```\n");
+        }
+        Ok(format!("{header}{text}").into())
+    })
+}
+
+fn collect_git_diff_text(project: Entity<Project>, cx: &App) -> Task<Result<SharedString>> {
+    let diffs = project
+        .read(cx)
+        .git_store()
+        .read(cx)
+        .repositories()
+        .values()
+        .map(|repository| repository.read(cx).diff(DiffType::HeadToWorktree, cx))
+        .collect::<Vec<_>>();
+
+    cx.background_spawn(async move {
+        let mut text = String::new();
+        for diff in diffs {
+            let diff = diff.await??;
+            if diff.is_empty() {
+                continue;
+            }
+            text.push_str("```diff\n");
+            text.push_str(&diff);
+            if !diff.ends_with('\n') {
+                text.push('\n');
+            }
+            text.push_str("```\n");
+        }
+
+        if text.is_empty() {
+            text.push_str("There are no uncommitted changes.\n");
+        }
+        Ok(text.into())
+    })
+}
+
+fn paused_debug_session(project: &Entity<Project>, cx: &App) -> Option<Entity<Session>> {
+    project
+        .read(cx)
+        .dap_store()
+        .read(cx)
+        .sessions()
+        .find(|session| {
+            let session = session.read(cx);
+            !session.is_terminated() && session.any_stopped_thread()
+        })
+        .cloned()
+}
+
+fn collect_debug_session_text(project: Entity<Project>, cx: &App) -> Task<Result<SharedString>> {
+    let Some(session) = paused_debug_session(&project, cx) else {
+        return Task::ready(Ok("The debugger is not paused.\n".into()));
+    };
+
+    cx.spawn(async move |cx| {
+        // The session fetches its threads, stack frames and variables lazily, so keep asking for
+        // them until the debug adapter has responded.
+        let mut text = None;
+        for _ in 0..DEBUG_SESSION_POLL_ATTEMPTS {
+            let (latest_text, is_complete) =
+                session.update(cx, |session, cx| debug_session_text(session, cx))?;
+            text = latest_text;
+            if is_complete {
+                break;
+            }
+            cx.background_executor()
+                .timer(DEBUG_SESSION_POLL_INTERVAL)
+                .await;
+        }
+
+        let text = text.ok_or_else(|| anyhow!("the debugger did not report a stack trace"))?;
+        Ok(text.into())
+    })
+}
+
+/// Describes the first stopped thread of the session, along with whether all of the state it
+/// describes has been fetched from the debug adapter.
+fn debug_session_text(session: &mut Session, cx: &mut Context<Session>) -> (Option<String>, bool) {
+    if session.is_terminated() || !session.any_stopped_thread() {
+        return (Some("The debugger is not paused.\n".into()), true);
+    }
+
+    let Some((thread, _)) = session
+        .threads(cx)
+        .into_iter()
+        .find(|(_, status)| *status == ThreadStatus::Stopped)
+    else {
+        return (None, false);
+    };
+    let stack_frames = session.stack_frames(DebugThreadId(thread.id), cx);
+    let Some(top_frame) = stack_frames.first() else {
+        return (None, false);
+    };
+
+    let mut text = String::new();
+    if let Some(configuration) = session.configuration() {
+        writeln!(text, "Session: {}", configuration.label).ok();
+    }
+    writeln!(text, "Stopped thread: {} (id {})", thread.name, thread.id).ok();
+
+    text.push_str("\nStack trace:\n");
+    for (index, frame) in stack_frames.iter().take(MAX_DEBUG_STACK_FRAMES).enumerate() {
+        let frame = &frame.dap;
+        match frame
+            .source
+            .as_ref()
+            .and_then(|source| source.path.as_ref())
+        {
+            Some(path) => writeln!(text, "#{index} {} at {path}:{}", frame.name, frame.line),
+            None => writeln!(text, "#{index} {}", frame.name),
+        }
+        .ok();
+    }
+    if stack_frames.len() > MAX_DEBUG_STACK_FRAMES {
+        writeln!(
+            text,
+            "({} more frames omitted)",
+            stack_frames.len() - MAX_DEBUG_STACK_FRAMES
+        )
+        .ok();
+    }
+
+    let scopes = session.scopes(top_frame.dap.id, cx).to_vec();
+    let mut is_complete = !scopes.is_empty();
+    for scope in scopes {
+        writeln!(text, "\n{} in `{}`:", scope.name, top_frame.dap.name).ok();
+        // Expensive scopes, such as globals, are only listed by name.
+        if scope.expensive {
+            text.push_str("(not fetched)\n");
+            continue;
+        }
+
+        let variables = session.variables(scope.variables_reference, cx);
+        if variables.is_empty() {
+            is_complete = false;
+            text.push_str("(none)\n");
+            continue;
+        }
+        for variable in variables.iter().take(MAX_DEBUG_VARIABLES_PER_SCOPE) {
+            let mut value = variable.value.as_str();
+            let mut ellipsis = "";
+            if value.len() > MAX_DEBUG_VALUE_LEN {
+                let mut end = MAX_DEBUG_VALUE_LEN;
+                while !value.is_char_boundary(end) {
+                    end -= 1;
+                }
+                value = &value[..end];
+                ellipsis = "…";
+            }
+            match &variable.type_ {
+                Some(type_) => writeln!(text, "- {}: {type_} = {value}{ellipsis}", variable.name),
+                None => writeln!(text, "- {} = {value}{ellipsis}", variable.name),
+            }
+            .ok();
+        }
+        if variables.len() > MAX_DEBUG_VARIABLES_PER_SCOPE {
+            writeln!(
+                text,
+                "({} more variables omitted)",
+                variables.len() - MAX_DEBUG_VARIABLES_PER_SCOPE
+            )
+            .ok();
+        }
+    }
+
+    let (output, _) = session.output(OutputToken(0));
+    let output = output.collect::<Vec<_>>();
+    if !output.is_empty() {
+        text.push_str("\nRecent debug console output:\n```\n");
+        for event in &output[output.len().saturating_sub(MAX_DEBUG_OUTPUT_EVENTS)..] {
+            text.push_str(event.output.trim_end());
+            text.push('\n');
+        }
+        text.push_str("```\n");
+    }
+
+    (Some(text), is_complete)
+}
+
 fn refresh_context_buffer(
     context_buffer: &ContextBuffer,
     cx: &App,
@@ -904,3 +1645,123 @@ fn refresh_context_symbol(
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assistant_settings::AssistantSettings;
+    use assistant_tool::ToolWorkingSet;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use prompt_store::{AssistantSystemPromptContext, PromptBuilder};
+    use serde_json::json;
+    use settings::{Settings as _, SettingsStore};
+    use util::path;
+
+    use crate::thread::RequestKind;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            client::init_settings(cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            editor::init_settings(cx);
+            AssistantSettings::register(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_context_is_refreshed_before_sending(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "file.txt": "original text\n",
+                "other.txt": "no match here\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        let project_path = ProjectPath {
+            worktree_id,
+            path: Path::new("file.txt").into(),
+        };
+
+        let context_store = cx.new(|_| ContextStore::new(workspace.downgrade()));
+        context_store
+            .update(cx, |context_store, cx| {
+                context_store.add_file_from_path(project_path.clone(), false, cx)
+            })
+            .await
+            .unwrap();
+        context_store
+            .update(cx, |context_store, cx| {
+                context_store.add_project_search("edited".into(), false, cx)
+            })
+            .await
+            .unwrap();
+
+        // Edit the file after it was added as context, without saving it.
+        let buffer = project
+            .update(cx, |project, cx| project.open_buffer(project_path, cx))
+            .await
+            .unwrap();
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(0..buffer.len(), "edited text\n")], None, cx)
+        });
+        cx.run_until_parked();
+
+        // Send a message the same way the message editor does.
+        cx.update(|cx| refresh_context_store_text(context_store.clone(), &HashSet::default(), cx))
+            .await;
+        cx.run_until_parked();
+        let context = context_store.read_with(cx, |context_store, cx| {
+            context_store.snapshot(cx).collect::<Vec<_>>()
+        });
+        let thread = cx.new(|cx| {
+            Thread::new(
+                project.clone(),
+                Arc::new(ToolWorkingSet::default()),
+                Arc::new(PromptBuilder::new(None).unwrap()),
+                cx,
+            )
+        });
+        let request = thread.update(cx, |thread, cx| {
+            thread.set_system_prompt_context(AssistantSystemPromptContext::new(Vec::new()));
+            thread.insert_user_message("What changed?", context, None, cx);
+            thread.to_completion_request(RequestKind::Chat, cx)
+        });
+
+        let sent_context = request.messages.last().unwrap().string_contents();
+        assert!(sent_context.contains("edited text"), "{sent_context}");
+        assert!(!sent_context.contains("original text"), "{sent_context}");
+        // Project search context is run again, so it finds the edit too.
+        assert!(
+            sent_context.contains(&format!(
+                "{}:1: edited text",
+                Path::new("dir").join("file.txt").display()
+            )),
+            "{sent_context}"
+        );
+    }
+
+    #[test]
+    fn test_downscaled_size() {
+        assert_eq!(downscaled_size(1568), Some(784));
+        assert_eq!(downscaled_size(1001), Some(500));
+        // Images are never downscaled below the minimum size.
+        assert_eq!(downscaled_size(200), Some(MIN_IMAGE_SIZE));
+        assert_eq!(downscaled_size(MIN_IMAGE_SIZE), None);
+        assert_eq!(downscaled_size(64), None);
+    }
+}
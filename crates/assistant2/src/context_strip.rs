@@ -59,6 +59,7 @@ impl ContextStrip {
         let focus_handle = cx.focus_handle();
 
         let subscriptions = vec![
+            cx.observe(&context_store, |_, _, cx| cx.notify()),
             cx.subscribe_in(&context_picker, window, Self::handle_context_picker_event),
             cx.on_focus(&focus_handle, window, Self::handle_focus),
             cx.on_blur(&focus_handle, window, Self::handle_blur),
@@ -372,6 +373,11 @@ impl Render for ContextStrip {
             .iter()
             .flat_map(|context| context.snapshot(cx))
             .collect::<Vec<_>>();
+        let downscalable_images = context
+            .iter()
+            .filter(|context| context_store.downscaled_image_size(context.id).is_some())
+            .map(|context| context.id)
+            .collect::<HashSet<_>>();
         let context_picker = self.context_picker.clone();
         let focus_handle = self.focus_handle.clone();
 
@@ -492,6 +498,14 @@ impl Render for ContextStrip {
                         cx.notify();
                     },
                 )))
+                .when(downscalable_images.contains(&id), |pill| {
+                    let context_store = self.context_store.clone();
+                    pill.on_downscale(Rc::new(move |_event, _window, cx| {
+                        context_store.update(cx, |context_store, cx| {
+                            context_store.downscale_image(id, cx);
+                        });
+                    }))
+                })
             }))
             .when_some(suggested_context, |el, suggested| {
                 el.child(
@@ -45,7 +45,7 @@ use crate::context_store::ContextStore;
 use crate::inline_prompt_editor::{CodegenStatus, InlineAssistId, PromptEditor, PromptEditorEvent};
 use crate::terminal_inline_assistant::TerminalInlineAssistant;
 use crate::thread_store::ThreadStore;
-use crate::AssistantPanel;
+use crate::{AssistantPanel, EditSelection};
 
 pub fn init(
     fs: Arc<dyn Fs>,
@@ -237,12 +237,6 @@ impl InlineAssistant {
             return;
         };
 
-        let is_authenticated = || {
-            LanguageModelRegistry::read_global(cx)
-                .active_provider()
-                .map_or(false, |provider| provider.is_authenticated(cx))
-        };
-
         let thread_store = workspace
             .panel::<AssistantPanel>(cx)
             .map(|assistant_panel| assistant_panel.read(cx).thread_store().downgrade());
@@ -273,8 +267,93 @@ impl InlineAssistant {
                 }
             };
 
-        if is_authenticated() {
-            handle_assist(window, cx);
+        Self::assist_when_authenticated(window, cx, handle_assist);
+    }
+
+    /// Rewrites the newest selection in the active editor, streaming the model's changes as an
+    /// inline diff that can be accepted, rejected, or refined with a follow-up prompt.
+    ///
+    /// Unlike [`Self::inline_assist`], the selection is edited exactly as selected, rather than
+    /// being expanded to whole lines.
+    pub fn edit_selection(
+        workspace: &mut Workspace,
+        _: &EditSelection,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        if !AssistantSettings::get_global(cx).enabled {
+            return;
+        }
+        let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+            return;
+        };
+
+        let range = editor.update(cx, |editor, cx| {
+            let selection = editor.selections.newest::<Point>(cx);
+            if selection.is_empty() {
+                return Err("Select the text you want the assistant to edit");
+            }
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let range =
+                snapshot.anchor_before(selection.start)..snapshot.anchor_after(selection.end);
+            if snapshot
+                .ranges_to_buffer_ranges(std::iter::once(range.clone()))
+                .count()
+                > 1
+            {
+                return Err("The selection must be within a single excerpt");
+            }
+            Ok(range)
+        });
+        let range = match range {
+            Ok(range) => range,
+            Err(message) => {
+                struct EditSelectionToast;
+                workspace.show_toast(
+                    Toast::new(NotificationId::unique::<EditSelectionToast>(), message).autohide(),
+                    cx,
+                );
+                return;
+            }
+        };
+
+        let thread_store = workspace
+            .panel::<AssistantPanel>(cx)
+            .map(|assistant_panel| assistant_panel.read(cx).thread_store().downgrade());
+
+        Self::assist_when_authenticated(window, cx, move |window, cx| {
+            let workspace = cx.entity().downgrade();
+            InlineAssistant::update_global(cx, |assistant, cx| {
+                assistant.suggest_assist(
+                    &editor,
+                    range,
+                    String::new(),
+                    None,
+                    true,
+                    workspace,
+                    thread_store,
+                    window,
+                    cx,
+                );
+            })
+        });
+    }
+
+    /// Runs `assist` if the active language model provider is authenticated, and otherwise
+    /// authenticates it, prompting to configure a provider if there isn't one.
+    fn assist_when_authenticated(
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+        assist: impl FnOnce(&mut Window, &mut Context<Workspace>),
+    ) {
+        let is_authenticated = |cx: &App| {
+            LanguageModelRegistry::read_global(cx)
+                .active_provider()
+                .map_or(false, |provider| provider.is_authenticated(cx))
+        };
+
+        if is_authenticated(cx) {
+            assist(window, cx);
         } else {
             cx.spawn_in(window, async move |_workspace, cx| {
                 let Some(task) = cx.update(|_, cx| {
@@ -308,8 +387,8 @@ impl InlineAssistant {
             })
             .detach_and_log_err(cx);
 
-            if is_authenticated() {
-                handle_assist(window, cx);
+            if is_authenticated(cx) {
+                assist(window, cx);
             }
         }
     }
@@ -1,13 +1,13 @@
 use std::sync::Arc;
 
 use collections::HashSet;
-use editor::actions::MoveUp;
+use editor::actions::{MoveUp, Paste};
 use editor::{ContextMenuOptions, ContextMenuPlacement, Editor, EditorElement, EditorStyle};
 use file_icons::FileIcons;
 use fs::Fs;
 use gpui::{
-    Animation, AnimationExt, App, DismissEvent, Entity, Focusable, Subscription, TextStyle,
-    WeakEntity,
+    Animation, AnimationExt, App, ClipboardEntry, ClipboardItem, DismissEvent, Entity, Focusable,
+    Image, Subscription, TextStyle, WeakEntity,
 };
 use language_model::LanguageModelRegistry;
 use language_model_selector::ToggleModelSelector;
@@ -35,6 +35,9 @@ use crate::{
     ToggleProfileSelector,
 };
 
+/// Replaces the thread's earlier messages with a summary in requests to the model.
+const COMPACT_COMMAND: &str = "/compact";
+
 pub struct MessageEditor {
     thread: Entity<Thread>,
     editor: Entity<Editor>,
@@ -169,26 +172,32 @@ impl MessageEditor {
         cx.notify();
     }
 
-    fn chat(&mut self, _: &Chat, window: &mut Window, cx: &mut Context<Self>) {
+    pub(crate) fn chat(&mut self, _: &Chat, window: &mut Window, cx: &mut Context<Self>) {
         if self.is_editor_empty(cx) {
             return;
         }
 
-        if self.thread.read(cx).is_generating() {
+        let thread = self.thread.read(cx);
+        if thread.is_generating() || thread.is_compacting() {
             return;
         }
 
         self.send_to_model(RequestKind::Chat, window, cx);
     }
 
-    fn is_editor_empty(&self, cx: &App) -> bool {
+    /// Replaces the contents of the editor with the given message and sends it.
+    pub fn send_message(&mut self, message: String, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.set_text(message, window, cx));
+        self.chat(&Chat, window, cx);
+    }
+
+    pub(crate) fn is_editor_empty(&self, cx: &App) -> bool {
         self.editor.read(cx).text(cx).is_empty()
     }
 
     fn is_model_selected(&self, cx: &App) -> bool {
-        LanguageModelRegistry::read_global(cx)
-            .active_model()
-            .is_some()
+        self.thread.read(cx).model(cx).is_some()
     }
 
     fn send_to_model(
@@ -206,8 +215,7 @@ impl MessageEditor {
             return;
         }
 
-        let model_registry = LanguageModelRegistry::read_global(cx);
-        let Some(model) = model_registry.active_model() else {
+        let Some(model) = self.thread.read(cx).model(cx) else {
             return;
         };
 
@@ -217,6 +225,12 @@ impl MessageEditor {
             text
         });
 
+        if user_message.trim() == COMPACT_COMMAND {
+            self.thread
+                .update(cx, |thread, cx| thread.compact(model, cx));
+            return;
+        }
+
         let refresh_task =
             refresh_context_store_text(self.context_store.clone(), &HashSet::default(), cx);
 
@@ -251,6 +265,24 @@ impl MessageEditor {
         .detach();
     }
 
+    /// Adds pasted images as context, leaving everything else to be pasted into the editor.
+    fn paste(&mut self, _: &Paste, _window: &mut Window, cx: &mut Context<Self>) {
+        let images = cx
+            .read_from_clipboard()
+            .map(clipboard_images)
+            .unwrap_or_default();
+        if images.is_empty() {
+            cx.propagate();
+            return;
+        }
+
+        self.context_store.update(cx, |context_store, cx| {
+            for image in images {
+                context_store.add_image(Arc::new(image), "Pasted Image", cx);
+            }
+        });
+    }
+
     fn handle_inline_context_picker_event(
         &mut self,
         _inline_context_picker: &Entity<ContextPicker>,
@@ -310,6 +342,7 @@ impl Render for MessageEditor {
         let inline_context_picker = self.inline_context_picker.clone();
 
         let is_generating = self.thread.read(cx).is_generating();
+        let is_compacting = self.thread.read(cx).is_compacting();
         let is_model_selected = self.is_model_selected(cx);
         let is_editor_empty = self.is_editor_empty(cx);
         let submit_label_color = if is_editor_empty {
@@ -335,7 +368,7 @@ impl Render for MessageEditor {
 
         v_flex()
             .size_full()
-            .when(is_generating, |parent| {
+            .when(is_generating || is_compacting, |parent| {
                 let focus_handle = self.editor.focus_handle(cx).clone();
                 parent.child(
                     h_flex().py_3().w_full().justify_center().child(
@@ -365,31 +398,36 @@ impl Render for MessageEditor {
                                     ),
                             )
                             .child(
-                                Label::new("Generating…")
-                                    .size(LabelSize::XSmall)
-                                    .color(Color::Muted),
+                                Label::new(if is_generating {
+                                    "Generating…"
+                                } else {
+                                    "Compacting…"
+                                })
+                                .size(LabelSize::XSmall)
+                                .color(Color::Muted),
                             )
-                            .child(ui::Divider::vertical())
-                            .child(
-                                Button::new("cancel-generation", "Cancel")
-                                    .label_size(LabelSize::XSmall)
-                                    .key_binding(
-                                        KeyBinding::for_action_in(
-                                            &editor::actions::Cancel,
-                                            &focus_handle,
-                                            window,
-                                            cx,
+                            .when(is_generating, |parent| {
+                                parent.child(ui::Divider::vertical()).child(
+                                    Button::new("cancel-generation", "Cancel")
+                                        .label_size(LabelSize::XSmall)
+                                        .key_binding(
+                                            KeyBinding::for_action_in(
+                                                &editor::actions::Cancel,
+                                                &focus_handle,
+                                                window,
+                                                cx,
+                                            )
+                                            .map(|kb| kb.size(rems_from_px(10.))),
                                         )
-                                        .map(|kb| kb.size(rems_from_px(10.))),
-                                    )
-                                    .on_click(move |_event, window, cx| {
-                                        focus_handle.dispatch_action(
-                                            &editor::actions::Cancel,
-                                            window,
-                                            cx,
-                                        );
-                                    }),
-                            ),
+                                        .on_click(move |_event, window, cx| {
+                                            focus_handle.dispatch_action(
+                                                &editor::actions::Cancel,
+                                                window,
+                                                cx,
+                                            );
+                                        }),
+                                )
+                            }),
                     ),
                 )
             })
@@ -531,6 +569,7 @@ impl Render for MessageEditor {
             .child(
                 v_flex()
                     .key_context("MessageEditor")
+                    .capture_action(cx.listener(Self::paste))
                     .on_action(cx.listener(Self::chat))
                     .on_action(cx.listener(|this, _: &ToggleProfileSelector, window, cx| {
                         this.profile_selector
@@ -661,3 +700,39 @@ impl Render for MessageEditor {
             )
     }
 }
+
+/// Returns the images on the clipboard, which are added as context rather than pasted.
+fn clipboard_images(item: ClipboardItem) -> Vec<Image> {
+    item.into_entries()
+        .filter_map(|entry| match entry {
+            ClipboardEntry::Image(image) => Some(image),
+            ClipboardEntry::String(_) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{hash, ImageFormat};
+
+    #[test]
+    fn test_clipboard_images() {
+        let bytes = vec![0x89, b'P', b'N', b'G'];
+        let image = Image {
+            id: hash(&bytes),
+            format: ImageFormat::Png,
+            bytes,
+        };
+        assert_eq!(
+            clipboard_images(ClipboardItem::new_image(&image)),
+            vec![image]
+        );
+
+        // Text is left for the editor to paste.
+        assert_eq!(
+            clipboard_images(ClipboardItem::new_string("Lorem ipsum".into())),
+            Vec::new()
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Captures a region of the screen, such as part of a Zed window, to attach as image context.
+
+use std::path::Path;
+
+use anyhow::{anyhow, Context as _, Result};
+use gpui::{hash, App, Image, ImageFormat, Task};
+use util::ResultExt as _;
+
+/// Whether the platform has a screenshot tool that regions can be captured with.
+pub fn is_supported() -> bool {
+    cfg!(any(
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "freebsd"
+    ))
+}
+
+/// Lets the user select a region of the screen with the platform's screenshot tool, and
+/// returns it as a PNG, or `None` if the selection was cancelled.
+pub fn capture_screen_region(cx: &App) -> Task<Result<Option<Image>>> {
+    let path = paths::temp_dir().join(format!("screenshot-{}.png", uuid::Uuid::new_v4()));
+    cx.background_spawn(async move {
+        let mut command = screenshot_command(&path)?;
+        let status = command
+            .status()
+            .await
+            .with_context(|| format!("failed to run {command:?}"))?;
+        // The shell reports a missing screenshot tool with this exit code.
+        if status.code() == Some(127) {
+            return Err(anyhow!(
+                "failed to run {command:?}: the screenshot tool isn't installed"
+            ));
+        }
+        // Nothing is written when the selection is cancelled.
+        if !status.success() || !path.exists() {
+            return Ok(None);
+        }
+
+        let bytes = smol::fs::read(&path).await?;
+        smol::fs::remove_file(&path).await.log_err();
+        Ok(Some(Image {
+            id: hash(&bytes),
+            format: ImageFormat::Png,
+            bytes,
+        }))
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn screenshot_command(path: &Path) -> Result<smol::process::Command> {
+    // `-i` selects a region, or a window after pressing space, and `-x` mutes the sound.
+    let mut command = util::command::new_smol_command("screencapture");
+    command.arg("-i").arg("-x").arg(path);
+    Ok(command)
+}
+
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+fn screenshot_command(path: &Path) -> Result<smol::process::Command> {
+    let mut command = util::command::new_smol_command("sh");
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        command.arg("-c").arg(r#"grim -g "$(slurp)" "$0""#);
+    } else {
+        // ImageMagick's `import` captures the region dragged out with the mouse.
+        command.arg("-c").arg(r#"import "$0""#);
+    }
+    command.arg(path);
+    Ok(command)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "freebsd")))]
+fn screenshot_command(_path: &Path) -> Result<smol::process::Command> {
+    Err(anyhow!(
+        "capturing a screen region isn't supported on this platform"
+    ))
+}
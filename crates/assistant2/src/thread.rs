@@ -1,11 +1,14 @@
 use std::fmt::Write as _;
 use std::io::Write;
 use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{Context as _, Result};
-use assistant_settings::AssistantSettings;
-use assistant_tool::{ActionLog, Tool, ToolWorkingSet};
+use assistant_settings::{AgentProfile, AssistantSettings, BudgetStatus, ModelFallbackSettings};
+use assistant_tool::{
+    check_tool_path_policy, worktree_tool_path_policy, ActionLog, Tool, ToolSource, ToolWorkingSet,
+};
 use chrono::{DateTime, Utc};
 use collections::{BTreeMap, HashMap, HashSet};
 use fs::Fs;
@@ -14,10 +17,10 @@ use futures::{FutureExt, StreamExt as _};
 use git;
 use gpui::{App, AppContext, Context, Entity, EventEmitter, SharedString, Task, WeakEntity};
 use language_model::{
-    LanguageModel, LanguageModelCompletionEvent, LanguageModelRegistry, LanguageModelRequest,
-    LanguageModelRequestMessage, LanguageModelRequestTool, LanguageModelToolResult,
-    LanguageModelToolUseId, MaxMonthlySpendReachedError, MessageContent, PaymentRequiredError,
-    Role, StopReason, TokenUsage,
+    LanguageModel, LanguageModelCompletionEvent, LanguageModelProviderId, LanguageModelRegistry,
+    LanguageModelRequest, LanguageModelRequestMessage, LanguageModelRequestTool,
+    LanguageModelToolResult, LanguageModelToolUseId, MaxMonthlySpendReachedError, MessageContent,
+    PaymentRequiredError, Role, StopReason, TokenUsage,
 };
 use project::git_store::{GitStore, GitStoreCheckpoint};
 use project::{Project, Worktree};
@@ -26,7 +29,7 @@ use prompt_store::{
 };
 use serde::{Deserialize, Serialize};
 use settings::Settings;
-use util::{maybe, post_inc, ResultExt as _, TryFutureExt as _};
+use util::{post_inc, ResultExt as _, TryFutureExt as _};
 use uuid::Uuid;
 
 use crate::context::{attach_context_to_message, ContextId, ContextSnapshot};
@@ -34,7 +37,19 @@ use crate::thread_store::{
     SerializedMessage, SerializedMessageSegment, SerializedThread, SerializedToolResult,
     SerializedToolUse,
 };
-use crate::tool_use::{PendingToolUse, ToolUse, ToolUseState};
+use crate::tool_use::{PendingToolUse, PendingToolUseStatus, ToolUse, ToolUseState};
+
+const DISABLED_RULES_FILES_KEY: &str = "assistant-disabled-rules-files";
+
+/// Returns the rules files that the user excluded from the system prompt.
+fn disabled_rules_files() -> HashSet<PathBuf> {
+    db::kvp::KEY_VALUE_STORE
+        .read_kvp(DISABLED_RULES_FILES_KEY)
+        .log_err()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).log_err())
+        .unwrap_or_default()
+}
 
 #[derive(Debug, Clone, Copy)]
 pub enum RequestKind {
@@ -67,6 +82,27 @@ impl MessageId {
     }
 }
 
+/// The point in another thread that a thread was branched from.
+///
+/// A branch shares its messages up to and including `message_id` with the thread it was
+/// branched from, and diverges after it.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct ThreadBranchPoint {
+    pub thread_id: ThreadId,
+    pub message_id: MessageId,
+}
+
+/// A summary that stands in for a thread's earlier messages in requests to the model, created
+/// with `/compact`. The summarized messages are still shown in the thread.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct ThreadCompaction {
+    /// The last message the summary covers.
+    pub up_to: MessageId,
+    pub summary: String,
+    /// How many fewer tokens the thread's requests use, if the model could count them.
+    pub tokens_saved: Option<usize>,
+}
+
 /// A message in a [`Thread`].
 #[derive(Debug, Clone)]
 pub struct Message {
@@ -75,6 +111,13 @@ pub struct Message {
     pub segments: Vec<MessageSegment>,
 }
 
+/// The tokens spent generating an assistant message, and the model that generated it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageTokenUsage {
+    pub model_id: String,
+    pub usage: TokenUsage,
+}
+
 impl Message {
     pub fn push_thinking(&mut self, text: &str) {
         if let Some(MessageSegment::Thinking(segment)) = self.segments.last_mut() {
@@ -172,24 +215,43 @@ pub struct Thread {
     id: ThreadId,
     updated_at: DateTime<Utc>,
     summary: Option<SharedString>,
+    branched_from: Option<ThreadBranchPoint>,
     pending_summary: Task<Option<()>>,
+    compaction: Option<ThreadCompaction>,
+    pending_compaction: Option<Task<()>>,
     messages: Vec<Message>,
     next_message_id: MessageId,
     context: BTreeMap<ContextId, ContextSnapshot>,
     context_by_message: HashMap<MessageId, Vec<ContextId>>,
     system_prompt_context: Option<AssistantSystemPromptContext>,
     checkpoints_by_message: HashMap<MessageId, ThreadCheckpoint>,
+    /// Checkpoints being taken before running the file-modifying tools requested
+    /// by an assistant message, keyed by that message.
+    pending_step_checkpoints: HashMap<MessageId, Shared<Task<()>>>,
+    /// The most recently started tool use that modifies files.
+    ///
+    /// Tools that only read run concurrently with each other, but every tool waits for the
+    /// file-modifying tool requested before it to finish, so that edits don't interleave
+    /// and reads see the edits that were requested before them.
+    last_file_modifying_tool: Option<Shared<Task<()>>>,
     completion_count: usize,
     pending_completions: Vec<PendingCompletion>,
     project: Entity<Project>,
     prompt_builder: Arc<PromptBuilder>,
     tools: Arc<ToolWorkingSet>,
+    /// The profile whose tools this thread uses, instead of the selected profile's.
+    profile: Option<AgentProfile>,
+    /// The model this thread's requests are sent to, instead of the active model.
+    model: Option<Arc<dyn LanguageModel>>,
     tool_use: ToolUseState,
     action_log: Entity<ActionLog>,
     last_restore_checkpoint: Option<LastRestoreCheckpoint>,
     pending_checkpoint: Option<ThreadCheckpoint>,
     initial_project_snapshot: Shared<Task<Option<Arc<ProjectSnapshot>>>>,
     cumulative_token_usage: TokenUsage,
+    token_usage_by_message: HashMap<MessageId, MessageTokenUsage>,
+    /// Whether the user has been warned that the thread exceeded its soft token limit.
+    budget_warning_shown: bool,
     feedback: Option<ThreadFeedback>,
 }
 
@@ -204,18 +266,25 @@ impl Thread {
             id: ThreadId::new(),
             updated_at: Utc::now(),
             summary: None,
+            branched_from: None,
             pending_summary: Task::ready(None),
+            compaction: None,
+            pending_compaction: None,
             messages: Vec::new(),
             next_message_id: MessageId(0),
             context: BTreeMap::default(),
             context_by_message: HashMap::default(),
             system_prompt_context: None,
             checkpoints_by_message: HashMap::default(),
+            pending_step_checkpoints: HashMap::default(),
+            last_file_modifying_tool: None,
             completion_count: 0,
             pending_completions: Vec::new(),
             project: project.clone(),
             prompt_builder,
             tools: tools.clone(),
+            profile: None,
+            model: None,
             last_restore_checkpoint: None,
             pending_checkpoint: None,
             tool_use: ToolUseState::new(tools.clone()),
@@ -227,6 +296,8 @@ impl Thread {
                     .shared()
             },
             cumulative_token_usage: TokenUsage::default(),
+            token_usage_by_message: HashMap::default(),
+            budget_warning_shown: false,
             feedback: None,
         }
     }
@@ -248,12 +319,20 @@ impl Thread {
         );
         let tool_use =
             ToolUseState::from_serialized_messages(tools.clone(), &serialized.messages, |_| true);
+        let token_usage_by_message = serialized
+            .messages
+            .iter()
+            .filter_map(|message| Some((message.id, message.token_usage.clone()?)))
+            .collect();
 
         Self {
             id,
             updated_at: serialized.updated_at,
             summary: Some(serialized.summary),
+            branched_from: serialized.branched_from,
             pending_summary: Task::ready(None),
+            compaction: serialized.compaction,
+            pending_compaction: None,
             messages: serialized
                 .messages
                 .into_iter()
@@ -277,6 +356,8 @@ impl Thread {
             context_by_message: HashMap::default(),
             system_prompt_context: None,
             checkpoints_by_message: HashMap::default(),
+            pending_step_checkpoints: HashMap::default(),
+            last_file_modifying_tool: None,
             completion_count: 0,
             pending_completions: Vec::new(),
             last_restore_checkpoint: None,
@@ -284,10 +365,14 @@ impl Thread {
             project,
             prompt_builder,
             tools,
+            profile: None,
+            model: None,
             tool_use,
             action_log: cx.new(|_| ActionLog::new()),
             initial_project_snapshot: Task::ready(serialized.initial_project_snapshot).shared(),
             cumulative_token_usage: serialized.cumulative_token_usage,
+            token_usage_by_message,
+            budget_warning_shown: false,
             feedback: None,
         }
     }
@@ -300,6 +385,21 @@ impl Thread {
         self.messages.is_empty()
     }
 
+    /// Returns where this thread was branched from, if it's a branch of another thread.
+    pub fn branched_from(&self) -> Option<&ThreadBranchPoint> {
+        self.branched_from.as_ref()
+    }
+
+    /// Returns the summary that replaces this thread's earlier messages in requests, if it
+    /// has been compacted.
+    pub fn compaction(&self) -> Option<&ThreadCompaction> {
+        self.compaction.as_ref()
+    }
+
+    pub fn is_compacting(&self) -> bool {
+        self.pending_compaction.is_some()
+    }
+
     pub fn updated_at(&self) -> DateTime<Utc> {
         self.updated_at
     }
@@ -338,6 +438,48 @@ impl Thread {
         &self.tools
     }
 
+    /// Makes this thread use the given profile's tools, regardless of which profile is selected.
+    pub fn set_profile(&mut self, profile: AgentProfile) {
+        self.profile = Some(profile);
+    }
+
+    /// Makes this thread send its requests to the given model, regardless of which model is active.
+    pub fn set_model(&mut self, model: Arc<dyn LanguageModel>) {
+        self.model = Some(model);
+    }
+
+    /// Returns the model this thread's requests are sent to.
+    pub fn model(&self, cx: &App) -> Option<Arc<dyn LanguageModel>> {
+        self.model
+            .clone()
+            .or_else(|| LanguageModelRegistry::read_global(cx).active_model())
+    }
+
+    /// Returns the tools offered to the model in this thread's requests.
+    fn enabled_tools(&self, cx: &App) -> Vec<Arc<dyn Tool>> {
+        let Some(profile) = self.profile.as_ref() else {
+            return self.tools.enabled_tools(cx);
+        };
+
+        self.tools
+            .tools(cx)
+            .into_iter()
+            .filter(|tool| {
+                let name = tool.name();
+                let tools = match tool.source() {
+                    ToolSource::Native => &profile.tools,
+                    ToolSource::ContextServer { id } => {
+                        match profile.context_servers.get(id.as_ref()) {
+                            Some(preset) => &preset.tools,
+                            None => return false,
+                        }
+                    }
+                };
+                tools.get(name.as_str()).copied().unwrap_or(false)
+            })
+            .collect()
+    }
+
     pub fn pending_tool(&self, id: &LanguageModelToolUseId) -> Option<&PendingToolUse> {
         self.tool_use
             .pending_tool_uses()
@@ -352,6 +494,20 @@ impl Thread {
             .filter(|tool_use| tool_use.status.needs_confirmation())
     }
 
+    /// Returns the label of the first tool use that is currently running, if any.
+    pub fn running_tool_label(&self) -> Option<SharedString> {
+        self.tool_use
+            .pending_tool_uses()
+            .into_iter()
+            .find(|tool_use| {
+                matches!(
+                    tool_use.status,
+                    PendingToolUseStatus::Running { queued: false, .. }
+                )
+            })
+            .map(|tool_use| SharedString::from(tool_use.ui_text.clone()))
+    }
+
     pub fn has_pending_tool_uses(&self) -> bool {
         !self.tool_use.pending_tool_uses().is_empty()
     }
@@ -469,6 +625,7 @@ impl Thread {
         for deleted_message in self.messages.drain(message_ix..) {
             self.context_by_message.remove(&deleted_message.id);
             self.checkpoints_by_message.remove(&deleted_message.id);
+            self.pending_step_checkpoints.remove(&deleted_message.id);
         }
         cx.notify();
     }
@@ -645,10 +802,13 @@ impl Thread {
                                 content: tool_result.content.clone(),
                             })
                             .collect(),
+                        token_usage: this.token_usage_by_message.get(&message.id).cloned(),
                     })
                     .collect(),
                 initial_project_snapshot,
                 cumulative_token_usage: this.cumulative_token_usage.clone(),
+                branched_from: this.branched_from.clone(),
+                compaction: this.compaction.clone(),
             })
         })
     }
@@ -666,12 +826,14 @@ impl Thread {
         cx: &App,
     ) -> Task<(AssistantSystemPromptContext, Option<ThreadError>)> {
         let project = self.project.read(cx);
+        let disabled_rules_files = disabled_rules_files();
         let tasks = project
             .visible_worktrees(cx)
             .map(|worktree| {
                 Self::load_worktree_info_for_system_prompt(
                     project.fs().clone(),
                     worktree.read(cx),
+                    &disabled_rules_files,
                     cx,
                 )
             })
@@ -696,6 +858,7 @@ impl Thread {
     fn load_worktree_info_for_system_prompt(
         fs: Arc<dyn Fs>,
         worktree: &Worktree,
+        disabled_rules_files: &HashSet<PathBuf>,
         cx: &App,
     ) -> Task<(WorktreeInfoForSystemPrompt, Option<ThreadError>)> {
         let root_name = worktree.root_name().into();
@@ -703,65 +866,141 @@ impl Thread {
 
         // Note that Cline supports `.clinerules` being a directory, but that is not currently
         // supported. This doesn't seem to occur often in GitHub repositories.
-        const RULES_FILE_NAMES: [&'static str; 6] = [
+        const RULES_FILE_NAMES: [&'static str; 7] = [
             ".rules",
             ".cursorrules",
             ".windsurfrules",
             ".clinerules",
             ".github/copilot-instructions.md",
+            "AGENTS.md",
             "CLAUDE.md",
         ];
-        let selected_rules_file = RULES_FILE_NAMES
-            .into_iter()
-            .filter_map(|name| {
-                worktree
-                    .entry_for_path(name)
-                    .filter(|entry| entry.is_file())
-                    .map(|entry| (entry.path.clone(), worktree.absolutize(&entry.path)))
-            })
-            .next();
-
-        if let Some((rel_rules_path, abs_rules_path)) = selected_rules_file {
-            cx.spawn(async move |_| {
-                let rules_file_result = maybe!(async move {
-                    let abs_rules_path = abs_rules_path?;
-                    let text = fs.load(&abs_rules_path).await.with_context(|| {
-                        format!("Failed to load assistant rules file {:?}", abs_rules_path)
-                    })?;
-                    anyhow::Ok(RulesFile {
-                        rel_path: rel_rules_path,
-                        abs_path: abs_rules_path.into(),
-                        text: text.trim().to_string(),
-                    })
+        // Monorepos can have a rules file per package, so only load a limited number of them.
+        const MAX_NESTED_RULES_FILES: usize = 32;
+
+        let root_rules_file = RULES_FILE_NAMES.into_iter().find_map(|name| {
+            worktree
+                .entry_for_path(name)
+                .filter(|entry| entry.is_file())
+                .map(|entry| entry.path.clone())
+        });
+
+        // Rules files in subdirectories apply alongside the one at the root. Like at the root,
+        // only the first of the rules file names that a directory contains is used.
+        let mut nested_rules_files = HashMap::<&Path, (usize, &Arc<Path>)>::default();
+        for entry in worktree.files(false, 0) {
+            let Some(parent) = entry.path.parent() else {
+                continue;
+            };
+            if parent.as_os_str().is_empty() {
+                continue;
+            }
+            let Some(priority) = entry
+                .path
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .and_then(|file_name| RULES_FILE_NAMES.iter().position(|name| *name == file_name))
+            else {
+                continue;
+            };
+            nested_rules_files
+                .entry(parent)
+                .and_modify(|rules_file| {
+                    if priority < rules_file.0 {
+                        *rules_file = (priority, &entry.path);
+                    }
                 })
-                .await;
-                let (rules_file, rules_file_error) = match rules_file_result {
-                    Ok(rules_file) => (Some(rules_file), None),
-                    Err(err) => (
-                        None,
-                        Some(ThreadError::Message {
-                            header: "Error loading rules file".into(),
-                            message: format!("{err}").into(),
-                        }),
-                    ),
-                };
-                let worktree_info = WorktreeInfoForSystemPrompt {
-                    root_name,
-                    abs_path,
-                    rules_file,
-                };
-                (worktree_info, rules_file_error)
+                .or_insert((priority, &entry.path));
+        }
+        let mut nested_rules_files = nested_rules_files
+            .into_values()
+            .map(|(_, path)| path.clone())
+            .collect::<Vec<_>>();
+        nested_rules_files.sort();
+        nested_rules_files.truncate(MAX_NESTED_RULES_FILES);
+
+        let rules_file_paths = root_rules_file
+            .into_iter()
+            .chain(nested_rules_files)
+            .map(|rel_path| {
+                let abs_path = worktree.absolutize(&rel_path);
+                let enabled = abs_path
+                    .as_ref()
+                    .map_or(true, |abs_path| !disabled_rules_files.contains(abs_path));
+                (rel_path, abs_path, enabled)
             })
-        } else {
-            Task::ready((
-                WorktreeInfoForSystemPrompt {
-                    root_name,
-                    abs_path,
-                    rules_file: None,
+            .collect::<Vec<_>>();
+
+        cx.spawn(async move |_| {
+            let results = futures::future::join_all(rules_file_paths.into_iter().map(
+                |(rel_path, abs_path, enabled)| {
+                    let fs = fs.clone();
+                    async move {
+                        let abs_path = abs_path?;
+                        let text = fs.load(&abs_path).await.with_context(|| {
+                            format!("Failed to load assistant rules file {:?}", abs_path)
+                        })?;
+                        anyhow::Ok(RulesFile {
+                            rel_path,
+                            abs_path: abs_path.into(),
+                            text: text.trim().to_string(),
+                            enabled,
+                        })
+                    }
                 },
-                None,
             ))
+            .await;
+
+            let mut rules_files = Vec::new();
+            let mut rules_file_error = None;
+            for result in results {
+                match result {
+                    Ok(rules_file) => rules_files.push(rules_file),
+                    Err(err) => {
+                        rules_file_error.get_or_insert(ThreadError::Message {
+                            header: "Error loading rules file".into(),
+                            message: format!("{err}").into(),
+                        });
+                    }
+                }
+            }
+            let worktree_info = WorktreeInfoForSystemPrompt {
+                root_name,
+                abs_path,
+                rules_files,
+            };
+            (worktree_info, rules_file_error)
+        })
+    }
+
+    /// Includes or excludes the rules file at the given path from the system prompt, both in
+    /// this thread and in future ones.
+    pub fn set_rules_file_enabled(
+        &mut self,
+        abs_path: &Path,
+        enabled: bool,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(system_prompt_context) = self.system_prompt_context.as_mut() {
+            system_prompt_context.set_rules_file_enabled(abs_path, enabled);
         }
+
+        let abs_path = abs_path.to_path_buf();
+        db::write_and_log(cx, move || async move {
+            let mut disabled_rules_files = disabled_rules_files();
+            if enabled {
+                disabled_rules_files.remove(&abs_path);
+            } else {
+                disabled_rules_files.insert(abs_path);
+            }
+            db::kvp::KEY_VALUE_STORE
+                .write_kvp(
+                    DISABLED_RULES_FILES_KEY.into(),
+                    serde_json::to_string(&disabled_rules_files)?,
+                )
+                .await
+        });
+        cx.notify();
     }
 
     pub fn send_to_model(
@@ -770,19 +1009,33 @@ impl Thread {
         request_kind: RequestKind,
         cx: &mut Context<Self>,
     ) {
-        let mut request = self.to_completion_request(request_kind, cx);
-        request.tools = {
-            let mut tools = Vec::new();
-            tools.extend(self.tools().enabled_tools(cx).into_iter().map(|tool| {
-                LanguageModelRequestTool {
-                    name: tool.name(),
-                    description: tool.description(),
-                    input_schema: tool.input_schema(),
-                }
+        if self.budget_status(cx) == BudgetStatus::HardLimitExceeded {
+            let hard_limit = AssistantSettings::get_global(cx)
+                .thread_budget
+                .hard_limit_tokens
+                .unwrap_or_default();
+            cx.emit(ThreadEvent::ShowError(ThreadError::Message {
+                header: "Token budget exhausted".into(),
+                message: format!(
+                    "This thread has used {} tokens, exceeding its limit of {hard_limit}. \
+                     Raise `assistant.thread_budget.hard_limit_tokens` to continue.",
+                    self.cumulative_token_usage.total_tokens()
+                )
+                .into(),
             }));
+            return;
+        }
 
-            tools
-        };
+        let mut request = self.to_completion_request(request_kind, cx);
+        request.tools = self
+            .enabled_tools(cx)
+            .into_iter()
+            .map(|tool| LanguageModelRequestTool {
+                name: tool.name(),
+                description: tool.description(),
+                input_schema: tool.input_schema(),
+            })
+            .collect();
 
         self.stream_completion(request, model, cx);
     }
@@ -828,9 +1081,29 @@ impl Thread {
             log::error!("system_prompt_context not set.")
         }
 
+        if let Some(compaction) = self.compaction.as_ref() {
+            request.messages.push(LanguageModelRequestMessage {
+                role: Role::User,
+                content: vec![MessageContent::Text(format!(
+                    "Here is a summary of our conversation so far:\n\n{}",
+                    compaction.summary
+                ))],
+                cache: false,
+            });
+        }
+
         let mut referenced_context_ids = HashSet::default();
 
         for message in &self.messages {
+            // Messages covered by the compaction summary aren't sent, along with their context.
+            if self
+                .compaction
+                .as_ref()
+                .map_or(false, |compaction| message.id <= compaction.up_to)
+            {
+                continue;
+            }
+
             if let Some(context_ids) = self.context_by_message.get(&message.id) {
                 referenced_context_ids.extend(context_ids);
             }
@@ -954,13 +1227,46 @@ impl Thread {
         cx: &mut Context<Self>,
     ) {
         let pending_completion_id = post_inc(&mut self.completion_count);
+        let fallback_settings = AssistantSettings::get_global(cx).model_fallback.clone();
+        let mut fallback_models = Self::fallback_models(&fallback_settings, &model, cx).into_iter();
 
         let task = cx.spawn(async move |thread, cx| {
-            let stream = model.stream_completion(request, &cx);
+            let mut model = model;
             let initial_token_usage =
                 thread.read_with(cx, |thread, _cx| thread.cumulative_token_usage.clone());
             let stream_completion = async {
-                let mut events = stream.await?;
+                // Nothing has been added to the thread until the stream is open, so requests
+                // that fail to open it can be sent again, or sent to another model.
+                let mut retries = 0;
+                let mut events = loop {
+                    let error = match model.stream_completion(request.clone(), &cx).await {
+                        Ok(events) => break events,
+                        Err(error) => error,
+                    };
+                    if !is_retryable_completion_error(&error) {
+                        return Err(error);
+                    }
+
+                    if retries < fallback_settings.max_retries {
+                        let backoff = fallback_settings.backoff(retries);
+                        log::warn!(
+                            "retrying completion with {} in {backoff:?}: {error:#}",
+                            model.id().0
+                        );
+                        cx.background_executor().timer(backoff).await;
+                        retries += 1;
+                    } else if let Some(fallback_model) = fallback_models.next() {
+                        log::warn!(
+                            "falling back from {} to {}: {error:#}",
+                            model.id().0,
+                            fallback_model.id().0
+                        );
+                        model = fallback_model;
+                        retries = 0;
+                    } else {
+                        return Err(error);
+                    }
+                };
                 let mut stop_reason = StopReason::EndTurn;
                 let mut current_token_usage = TokenUsage::default();
 
@@ -1057,6 +1363,22 @@ impl Thread {
                         .pending_completions
                         .retain(|completion| completion.id != pending_completion_id);
 
+                    if let Some(message) = thread
+                        .messages
+                        .iter()
+                        .rfind(|message| message.role == Role::Assistant)
+                    {
+                        let message_usage = thread
+                            .token_usage_by_message
+                            .entry(message.id)
+                            .or_insert_with(|| MessageTokenUsage {
+                                model_id: model.id().0.to_string(),
+                                usage: TokenUsage::default(),
+                            });
+                        message_usage.usage =
+                            message_usage.usage.clone() + current_token_usage.clone();
+                    }
+
                     if thread.summary.is_none() && thread.messages.len() >= 2 {
                         thread.summarize(cx);
                     }
@@ -1102,6 +1424,20 @@ impl Thread {
                     }
                     cx.emit(ThreadEvent::DoneStreaming);
 
+                    if !thread.budget_warning_shown
+                        && thread.budget_status(cx) == BudgetStatus::SoftLimitExceeded
+                    {
+                        thread.budget_warning_shown = true;
+                        cx.emit(ThreadEvent::ShowError(ThreadError::Message {
+                            header: "Token budget warning".into(),
+                            message: format!(
+                                "This thread has used {} tokens, exceeding its soft limit.",
+                                thread.cumulative_token_usage.total_tokens()
+                            )
+                            .into(),
+                        }));
+                    }
+
                     if let Ok(initial_usage) = initial_token_usage {
                         let usage = thread.cumulative_token_usage.clone() - initial_usage;
 
@@ -1126,6 +1462,34 @@ impl Thread {
         });
     }
 
+    /// Returns the models to send requests to, in order, when requests to the given model
+    /// keep failing.
+    fn fallback_models(
+        settings: &ModelFallbackSettings,
+        model: &Arc<dyn LanguageModel>,
+        cx: &App,
+    ) -> Vec<Arc<dyn LanguageModel>> {
+        let registry = LanguageModelRegistry::read_global(cx);
+        settings
+            .models
+            .iter()
+            .filter_map(|selection| {
+                let provider = registry
+                    .provider(&LanguageModelProviderId::from(selection.provider.clone()))?;
+                if !provider.is_authenticated(cx) {
+                    return None;
+                }
+                provider
+                    .provided_models(cx)
+                    .into_iter()
+                    .find(|candidate| candidate.id().0.as_ref() == selection.model)
+            })
+            .filter(|candidate| {
+                candidate.provider_id() != model.provider_id() || candidate.id() != model.id()
+            })
+            .collect()
+    }
+
     pub fn summarize(&mut self, cx: &mut Context<Self>) {
         let Some(provider) = LanguageModelRegistry::read_global(cx).active_provider() else {
             return;
@@ -1183,6 +1547,85 @@ impl Thread {
         });
     }
 
+    /// Asks the model to summarize the thread so far, and sends that summary instead of the
+    /// summarized messages from then on.
+    pub fn compact(&mut self, model: Arc<dyn LanguageModel>, cx: &mut Context<Self>) {
+        let Some(up_to) = self.messages.last().map(|message| message.id) else {
+            return;
+        };
+        if self.is_generating()
+            || self.is_compacting()
+            || self
+                .compaction
+                .as_ref()
+                .map_or(false, |compaction| compaction.up_to == up_to)
+        {
+            return;
+        }
+
+        let tokens_before =
+            model.count_tokens(self.to_completion_request(RequestKind::Chat, cx), cx);
+        let mut request = self.to_completion_request(RequestKind::Summarize, cx);
+        request.messages.push(LanguageModelRequestMessage {
+            role: Role::User,
+            content: vec![
+                "Summarize our conversation so far, so that it can be continued from the summary alone. \
+                 Include the goals, the decisions made and why, the files and code that were discussed or changed, \
+                 and anything that is still unresolved. Go straight to the summary, without any preamble."
+                    .into(),
+            ],
+            cache: false,
+        });
+
+        self.pending_compaction = Some(cx.spawn(async move |this, cx| {
+            let result = async {
+                let mut messages = model.stream_completion_text(request, &cx).await?;
+                let mut summary = String::new();
+                while let Some(text) = messages.stream.next().await {
+                    summary.push_str(&text?);
+                }
+                let summary = summary.trim();
+                if summary.is_empty() {
+                    anyhow::bail!("The model didn't respond with a summary.");
+                }
+
+                let tokens_after = this.update(cx, |this, cx| {
+                    this.compaction = Some(ThreadCompaction {
+                        up_to,
+                        summary: summary.to_string(),
+                        tokens_saved: None,
+                    });
+                    model.count_tokens(this.to_completion_request(RequestKind::Chat, cx), cx)
+                })?;
+
+                let tokens_saved = match (tokens_before.await, tokens_after.await) {
+                    (Ok(before), Ok(after)) => Some(before.saturating_sub(after)),
+                    _ => None,
+                };
+                this.update(cx, |this, _| {
+                    if let Some(compaction) = this.compaction.as_mut() {
+                        compaction.tokens_saved = tokens_saved;
+                    }
+                })
+            }
+            .await;
+
+            this.update(cx, |this, cx| {
+                this.pending_compaction = None;
+                match result {
+                    Ok(()) => cx.emit(ThreadEvent::Compacted),
+                    Err(error) => cx.emit(ThreadEvent::ShowError(ThreadError::Message {
+                        header: "Failed to compact thread".into(),
+                        message: error.to_string().into(),
+                    })),
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
     pub fn use_pending_tools(
         &mut self,
         cx: &mut Context<Self>,
@@ -1199,15 +1642,21 @@ impl Thread {
 
         for tool_use in pending_tool_uses.iter() {
             if let Some(tool) = self.tools.tool(&tool_use.name, cx) {
-                if tool.needs_confirmation()
-                    && !AssistantSettings::get_global(cx).always_allow_tool_actions
-                {
+                let blocked_reason = self.path_policy_violation(&tool, &tool_use.input, cx);
+                let needs_confirmation = tool
+                    .confirmation_override(&tool_use.input, cx)
+                    .unwrap_or_else(|| {
+                        tool.needs_confirmation()
+                            && !AssistantSettings::get_global(cx).always_allow_tool_actions
+                    });
+                if blocked_reason.is_some() || needs_confirmation {
                     self.tool_use.confirm_tool_use(
                         tool_use.id.clone(),
                         tool_use.ui_text.clone(),
                         tool_use.input.clone(),
                         messages.clone(),
                         tool,
+                        blocked_reason.map(Into::into),
                     );
                     cx.emit(ThreadEvent::ToolConfirmationNeeded);
                 } else {
@@ -1235,6 +1684,32 @@ impl Thread {
         pending_tool_uses
     }
 
+    /// Checks the paths the tool would access against the tool path policy of
+    /// the worktree containing each path, returning why the tool is blocked.
+    fn path_policy_violation(
+        &self,
+        tool: &Arc<dyn Tool>,
+        input: &serde_json::Value,
+        cx: &App,
+    ) -> Option<String> {
+        let project = self.project.read(cx);
+        if tool.accesses_unlisted_paths(input, cx) {
+            let restricted_worktree = project.visible_worktrees(cx).find(|worktree| {
+                worktree_tool_path_policy(worktree.read(cx).id(), cx).restricts_worktree_paths()
+            });
+            if let Some(worktree) = restricted_worktree {
+                return Some(format!(
+                    "{} may access any path, so the tool path policy of {} can't be checked",
+                    tool.name(),
+                    worktree.read(cx).root_name()
+                ));
+            }
+        }
+        tool.accessed_paths(input, cx)
+            .into_iter()
+            .find_map(|path| check_tool_path_policy(&path, project, cx).err())
+    }
+
     pub fn run_tool(
         &mut self,
         tool_use_id: LanguageModelToolUseId,
@@ -1244,9 +1719,33 @@ impl Thread {
         tool: Arc<dyn Tool>,
         cx: &mut Context<Thread>,
     ) {
-        let task = self.spawn_tool_use(tool_use_id.clone(), messages, input, tool, cx);
+        let modifies_files = tool.modifies_files();
+        let preceding_tool = self
+            .last_file_modifying_tool
+            .clone()
+            .filter(|task| task.peek().is_none());
+        // While edits are held for review, tools that read files from disk wait for
+        // them to be reviewed and saved first.
+        let reads_from_disk = tool.reads_from_disk(&input, cx);
+        let queued = preceding_tool.is_some()
+            || (reads_from_disk && self.action_log.read(cx).has_deferred_saves());
+
+        let task = self
+            .spawn_tool_use(
+                tool_use_id.clone(),
+                messages,
+                input,
+                tool,
+                preceding_tool,
+                reads_from_disk,
+                cx,
+            )
+            .shared();
+        if modifies_files {
+            self.last_file_modifying_tool = Some(task.clone());
+        }
         self.tool_use
-            .run_pending_tool(tool_use_id, ui_text.into(), task);
+            .run_pending_tool(tool_use_id, ui_text.into(), task, queued);
     }
 
     fn spawn_tool_use(
@@ -1255,18 +1754,55 @@ impl Thread {
         messages: &[LanguageModelRequestMessage],
         input: serde_json::Value,
         tool: Arc<dyn Tool>,
+        preceding_tool: Option<Shared<Task<()>>>,
+        reads_from_disk: bool,
         cx: &mut Context<Thread>,
     ) -> Task<()> {
-        let run_tool = tool.run(
-            input,
-            messages,
-            self.project.clone(),
-            self.action_log.clone(),
-            cx,
-        );
+        let step_checkpoint = if tool.modifies_files() {
+            self.pending_tool(&tool_use_id)
+                .map(|tool_use| tool_use.assistant_message_id)
+                .map(|message_id| self.checkpoint_step(message_id, cx))
+        } else {
+            None
+        };
+        let messages = messages.to_vec();
 
         cx.spawn({
             async move |thread: WeakEntity<Thread>, cx| {
+                if let Some(preceding_tool) = preceding_tool {
+                    preceding_tool.await;
+                }
+                if reads_from_disk {
+                    let Ok(deferred_saves) = thread.update(cx, |thread, cx| {
+                        thread
+                            .action_log
+                            .update(cx, |action_log, _| action_log.wait_for_deferred_saves())
+                    }) else {
+                        return;
+                    };
+                    deferred_saves.await;
+                }
+                thread
+                    .update(cx, |thread, cx| {
+                        thread.tool_use.start_queued_tool(&tool_use_id);
+                        cx.notify();
+                    })
+                    .ok();
+                if let Some(step_checkpoint) = step_checkpoint {
+                    step_checkpoint.await;
+                }
+
+                let Ok(run_tool) = thread.update(cx, |thread, cx| {
+                    tool.run(
+                        input,
+                        &messages,
+                        thread.project.clone(),
+                        thread.action_log.clone(),
+                        cx,
+                    )
+                }) else {
+                    return;
+                };
                 let output = run_tool.await;
 
                 thread
@@ -1286,6 +1822,42 @@ impl Thread {
         })
     }
 
+    /// Records a checkpoint of the project before the tools requested by the given
+    /// assistant message modify any files, so that the step can be rolled back.
+    ///
+    /// All tools of a step share the same checkpoint, which is only taken once.
+    fn checkpoint_step(
+        &mut self,
+        message_id: MessageId,
+        cx: &mut Context<Self>,
+    ) -> Shared<Task<()>> {
+        if let Some(checkpoint) = self.pending_step_checkpoints.get(&message_id) {
+            return checkpoint.clone();
+        }
+
+        let checkpoint = self.project.read(cx).git_store().read(cx).checkpoint(cx);
+        let task = cx
+            .spawn(async move |this, cx| {
+                let Some(git_checkpoint) = checkpoint.await.log_err() else {
+                    return;
+                };
+                this.update(cx, |this, cx| {
+                    this.insert_checkpoint(
+                        ThreadCheckpoint {
+                            message_id,
+                            git_checkpoint,
+                        },
+                        cx,
+                    )
+                })
+                .ok();
+            })
+            .shared();
+        self.pending_step_checkpoints
+            .insert(message_id, task.clone());
+        task
+    }
+
     pub fn attach_tool_results(
         &mut self,
         updated_context: Vec<ContextSnapshot>,
@@ -1317,6 +1889,7 @@ impl Thread {
             true
         } else {
             let mut canceled = false;
+            self.last_file_modifying_tool = None;
             for pending_tool_use in self.tool_use.cancel_pending() {
                 canceled = true;
                 cx.emit(ThreadEvent::ToolFinished {
@@ -1525,6 +2098,36 @@ impl Thread {
                 writeln!(markdown, "**\n")?;
                 writeln!(markdown, "{}", tool_result.content)?;
             }
+
+            if let Some(message_usage) = self.message_token_usage(message.id) {
+                writeln!(
+                    markdown,
+                    "_{} input and {} output tokens ({})_\n",
+                    message_usage.usage.input_tokens,
+                    message_usage.usage.output_tokens,
+                    message_usage.model_id
+                )?;
+            }
+        }
+
+        let token_usage = self.cumulative_token_usage();
+        if token_usage.total_tokens() > 0 {
+            writeln!(markdown, "## Token Usage\n")?;
+            writeln!(markdown, "- Input: {}", token_usage.input_tokens)?;
+            writeln!(markdown, "- Output: {}", token_usage.output_tokens)?;
+            writeln!(
+                markdown,
+                "- Cache creation input: {}",
+                token_usage.cache_creation_input_tokens
+            )?;
+            writeln!(
+                markdown,
+                "- Cache read input: {}",
+                token_usage.cache_read_input_tokens
+            )?;
+            if let Some(cost) = self.estimated_cost(cx) {
+                writeln!(markdown, "- Estimated cost: ${cost:.2}")?;
+            }
         }
 
         Ok(String::from_utf8_lossy(&markdown).to_string())
@@ -1561,10 +2164,41 @@ impl Thread {
         self.cumulative_token_usage.clone()
     }
 
+    pub fn message_token_usage(&self, id: MessageId) -> Option<&MessageTokenUsage> {
+        self.token_usage_by_message.get(&id)
+    }
+
+    pub fn budget_status(&self, cx: &App) -> BudgetStatus {
+        AssistantSettings::get_global(cx)
+            .thread_budget
+            .status(self.cumulative_token_usage.total_tokens())
+    }
+
+    /// Returns the estimated cost of the thread in US dollars, if the price of any of
+    /// the models used to generate it is known.
+    pub fn estimated_cost(&self, cx: &App) -> Option<f64> {
+        let model_pricing = &AssistantSettings::get_global(cx).model_pricing;
+        self.token_usage_by_message
+            .values()
+            .filter_map(|message_usage| {
+                let pricing = model_pricing.get(&message_usage.model_id)?;
+                Some(pricing.cost(&message_usage.usage))
+            })
+            .reduce(|total, cost| total + cost)
+    }
+
     pub fn deny_tool_use(&mut self, tool_use_id: LanguageModelToolUseId, cx: &mut Context<Self>) {
-        let err = Err(anyhow::anyhow!(
-            "Permission to run tool action denied by user"
-        ));
+        let blocked_reason = self
+            .pending_tool(&tool_use_id)
+            .and_then(|tool_use| tool_use.status.blocked_reason().cloned());
+        let err = match blocked_reason {
+            Some(reason) => Err(anyhow::anyhow!(
+                "Blocked by the project's tool path policy: {reason}"
+            )),
+            None => Err(anyhow::anyhow!(
+                "Permission to run tool action denied by user"
+            )),
+        };
 
         self.tool_use.insert_tool_output(tool_use_id.clone(), err);
 
@@ -1576,6 +2210,33 @@ impl Thread {
     }
 }
 
+/// Returns whether a request failed in a way that's likely to succeed if it's sent again
+/// later, or to another model, such as when the model is rate limited or overloaded.
+fn is_retryable_completion_error(error: &anyhow::Error) -> bool {
+    if error.is::<PaymentRequiredError>() || error.is::<MaxMonthlySpendReachedError>() {
+        return false;
+    }
+
+    const RETRYABLE_ERROR_PATTERNS: [&str; 10] = [
+        "too many requests",
+        "rate limit",
+        "rate_limit",
+        "overloaded",
+        "timed out",
+        "timeout",
+        "internal server error",
+        "bad gateway",
+        "service unavailable",
+        "gateway timeout",
+    ];
+    error.chain().any(|error| {
+        let message = error.to_string().to_lowercase();
+        RETRYABLE_ERROR_PATTERNS
+            .iter()
+            .any(|pattern| message.contains(pattern))
+    })
+}
+
 #[derive(Debug, Clone)]
 pub enum ThreadError {
     PaymentRequired,
@@ -1608,6 +2269,7 @@ pub enum ThreadEvent {
     },
     CheckpointChanged,
     ToolConfirmationNeeded,
+    Compacted,
 }
 
 impl EventEmitter<ThreadEvent> for Thread {}
@@ -1616,3 +2278,722 @@ struct PendingCompletion {
     id: usize,
     _task: Task<()>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool_use::ToolUseStatus;
+    use gpui::TestAppContext;
+    use language_model::LanguageModelToolUse;
+    use parking_lot::Mutex;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::time::Duration;
+    use util::path;
+
+    /// A tool that writes the `content` of its input to the file at its `path`.
+    struct WriteFileTool;
+
+    impl Tool for WriteFileTool {
+        fn name(&self) -> String {
+            "write_file".into()
+        }
+
+        fn description(&self) -> String {
+            "Writes a file".into()
+        }
+
+        fn icon(&self) -> ui::IconName {
+            ui::IconName::Pencil
+        }
+
+        fn needs_confirmation(&self) -> bool {
+            false
+        }
+
+        fn modifies_files(&self) -> bool {
+            true
+        }
+
+        fn ui_text(&self, _input: &serde_json::Value) -> String {
+            "Write file".into()
+        }
+
+        fn run(
+            self: Arc<Self>,
+            input: serde_json::Value,
+            _messages: &[LanguageModelRequestMessage],
+            project: Entity<Project>,
+            _action_log: Entity<ActionLog>,
+            cx: &mut App,
+        ) -> Task<Result<String>> {
+            let fs = project.read(cx).fs().clone();
+            cx.background_spawn(async move {
+                let path = PathBuf::from(input["path"].as_str().unwrap());
+                let content = input["content"].as_str().unwrap().to_string();
+                fs.save(&path, &content.into(), Default::default()).await?;
+                Ok("Wrote the file".into())
+            })
+        }
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+            AssistantSettings::register(cx);
+        });
+    }
+
+    fn create_thread(
+        project: &Entity<Project>,
+        tools: Vec<Arc<dyn Tool>>,
+        cx: &mut TestAppContext,
+    ) -> Entity<Thread> {
+        let tool_working_set = Arc::new(ToolWorkingSet::default());
+        for tool in tools {
+            tool_working_set.insert(tool);
+        }
+        let prompt_builder = Arc::new(PromptBuilder::new(None).unwrap());
+        cx.new(|cx| Thread::new(project.clone(), tool_working_set, prompt_builder, cx))
+    }
+
+    /// Adds a user message, and an assistant message that requests the given tool uses.
+    fn request_tool_uses(
+        thread: &mut Thread,
+        tool_uses: Vec<(&str, serde_json::Value)>,
+        cx: &mut Context<Thread>,
+    ) -> MessageId {
+        thread.insert_user_message("Go ahead", Vec::new(), None, cx);
+        let message_id = thread.insert_message(
+            Role::Assistant,
+            vec![MessageSegment::Text("Using tools".into())],
+            cx,
+        );
+        for (ix, (name, input)) in tool_uses.into_iter().enumerate() {
+            thread.tool_use.request_tool_use(
+                message_id,
+                LanguageModelToolUse {
+                    id: format!("tool-{ix}").into(),
+                    name: name.into(),
+                    input,
+                },
+                cx,
+            );
+        }
+        message_id
+    }
+
+    #[gpui::test]
+    async fn test_step_checkpoints(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "file.txt": "original",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let thread = create_thread(&project, vec![Arc::new(WriteFileTool)], cx);
+
+        let message_id = thread.update(cx, |thread, cx| {
+            let message_id = request_tool_uses(
+                thread,
+                vec![(
+                    "write_file",
+                    json!({ "path": path!("/project/file.txt"), "content": "edited" }),
+                )],
+                cx,
+            );
+            thread.use_pending_tools(cx);
+            message_id
+        });
+        cx.run_until_parked();
+
+        // The checkpoint was taken before the tool ran, and is attached to the message that
+        // requested it.
+        assert_eq!(
+            fs.load(path!("/project/file.txt").as_ref()).await.unwrap(),
+            "edited"
+        );
+        let checkpoint = thread
+            .read_with(cx, |thread, _| thread.checkpoint_for_message(message_id))
+            .expect("a checkpoint was taken before the tool ran");
+
+        // Restoring it undoes the tool's edits, and removes the step from the thread.
+        thread
+            .update(cx, |thread, cx| thread.restore_checkpoint(checkpoint, cx))
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            fs.load(path!("/project/file.txt").as_ref()).await.unwrap(),
+            "original"
+        );
+        thread.read_with(cx, |thread, _| {
+            assert!(thread.message(message_id).is_none());
+            assert_eq!(thread.messages().count(), 1);
+        });
+    }
+
+    /// A tool that takes a second to run, and records when it starts and finishes.
+    struct SlowTool {
+        name: &'static str,
+        modifies_files: bool,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Tool for SlowTool {
+        fn name(&self) -> String {
+            self.name.into()
+        }
+
+        fn description(&self) -> String {
+            "Takes a while".into()
+        }
+
+        fn icon(&self) -> ui::IconName {
+            ui::IconName::Cog
+        }
+
+        fn needs_confirmation(&self) -> bool {
+            false
+        }
+
+        fn modifies_files(&self) -> bool {
+            self.modifies_files
+        }
+
+        fn accessed_paths(&self, input: &serde_json::Value, _cx: &App) -> Vec<PathBuf> {
+            input["path"]
+                .as_str()
+                .map(PathBuf::from)
+                .into_iter()
+                .collect()
+        }
+
+        fn accesses_unlisted_paths(&self, input: &serde_json::Value, _cx: &App) -> bool {
+            input["any_path"].as_bool().unwrap_or(false)
+        }
+
+        fn ui_text(&self, input: &serde_json::Value) -> String {
+            input["label"].as_str().unwrap().into()
+        }
+
+        fn run(
+            self: Arc<Self>,
+            input: serde_json::Value,
+            _messages: &[LanguageModelRequestMessage],
+            _project: Entity<Project>,
+            _action_log: Entity<ActionLog>,
+            cx: &mut App,
+        ) -> Task<Result<String>> {
+            let label = input["label"].as_str().unwrap().to_string();
+            let timer = cx.background_executor().timer(Duration::from_secs(1));
+            self.log.lock().push(format!("start {label}"));
+            cx.background_spawn(async move {
+                timer.await;
+                self.log.lock().push(format!("end {label}"));
+                Ok(label)
+            })
+        }
+    }
+
+    fn tool_statuses(
+        thread: &Entity<Thread>,
+        message_id: MessageId,
+        cx: &mut TestAppContext,
+    ) -> Vec<(String, &'static str)> {
+        thread.read_with(cx, |thread, cx| {
+            thread
+                .tool_uses_for_message(message_id, cx)
+                .into_iter()
+                .map(|tool_use| {
+                    let status = match tool_use.status {
+                        ToolUseStatus::NeedsConfirmation => "needs confirmation",
+                        ToolUseStatus::Blocked(_) => "blocked",
+                        ToolUseStatus::Pending => "pending",
+                        ToolUseStatus::Queued => "queued",
+                        ToolUseStatus::Running => "running",
+                        ToolUseStatus::Finished(_) => "finished",
+                        ToolUseStatus::Error(_) => "error",
+                    };
+                    (tool_use.ui_text.to_string(), status)
+                })
+                .collect()
+        })
+    }
+
+    #[gpui::test]
+    async fn test_file_modifying_tools_run_serially(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/project"), json!({ ".git": {}, "file.txt": "" }))
+            .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let thread = create_thread(
+            &project,
+            vec![
+                Arc::new(SlowTool {
+                    name: "edit",
+                    modifies_files: true,
+                    log: log.clone(),
+                }),
+                Arc::new(SlowTool {
+                    name: "read",
+                    modifies_files: false,
+                    log: log.clone(),
+                }),
+            ],
+            cx,
+        );
+
+        let message_id = thread.update(cx, |thread, cx| {
+            let message_id = request_tool_uses(
+                thread,
+                vec![
+                    ("read", json!({ "label": "first read" })),
+                    ("edit", json!({ "label": "first edit" })),
+                    ("read", json!({ "label": "second read" })),
+                    ("edit", json!({ "label": "second edit" })),
+                ],
+                cx,
+            );
+            thread.use_pending_tools(cx);
+            message_id
+        });
+        cx.run_until_parked();
+
+        // The first read runs alongside the first edit, while the tools requested after the
+        // edit, whether they read or edit, wait for it.
+        let mut started = log.lock().clone();
+        started.sort();
+        assert_eq!(started, ["start first edit", "start first read"]);
+        assert_eq!(
+            tool_statuses(&thread, message_id, cx),
+            [
+                ("first read".to_string(), "running"),
+                ("first edit".to_string(), "running"),
+                ("second read".to_string(), "queued"),
+                ("second edit".to_string(), "queued"),
+            ]
+        );
+
+        cx.executor().advance_clock(Duration::from_secs(1));
+        cx.run_until_parked();
+        let log_after_first_edit = log.lock().clone();
+        let position = |entry: &str| {
+            log_after_first_edit
+                .iter()
+                .position(|logged| logged == entry)
+                .unwrap()
+        };
+        assert!(position("end first edit") < position("start second read"));
+        assert!(position("end first edit") < position("start second edit"));
+        assert_eq!(
+            tool_statuses(&thread, message_id, cx),
+            [
+                ("first read".to_string(), "finished"),
+                ("first edit".to_string(), "finished"),
+                ("second read".to_string(), "running"),
+                ("second edit".to_string(), "running"),
+            ]
+        );
+
+        cx.executor().advance_clock(Duration::from_secs(1));
+        cx.run_until_parked();
+        assert!(log.lock().contains(&"end second read".to_string()));
+        assert!(log.lock().contains(&"end second edit".to_string()));
+        assert_eq!(
+            tool_statuses(&thread, message_id, cx)[2..],
+            [
+                ("second read".to_string(), "finished"),
+                ("second edit".to_string(), "finished"),
+            ]
+        );
+    }
+
+    /// A tool that replaces the contents of the buffer at its `path`, holding the edit
+    /// for review before saving it.
+    struct ReviewedEditTool;
+
+    impl Tool for ReviewedEditTool {
+        fn name(&self) -> String {
+            "edit_for_review".into()
+        }
+
+        fn description(&self) -> String {
+            "Edits a buffer".into()
+        }
+
+        fn icon(&self) -> ui::IconName {
+            ui::IconName::Pencil
+        }
+
+        fn needs_confirmation(&self) -> bool {
+            false
+        }
+
+        fn modifies_files(&self) -> bool {
+            true
+        }
+
+        fn ui_text(&self, _input: &serde_json::Value) -> String {
+            "Edit for review".into()
+        }
+
+        fn run(
+            self: Arc<Self>,
+            input: serde_json::Value,
+            _messages: &[LanguageModelRequestMessage],
+            project: Entity<Project>,
+            action_log: Entity<ActionLog>,
+            cx: &mut App,
+        ) -> Task<Result<String>> {
+            let project_path = project
+                .read(cx)
+                .find_project_path(input["path"].as_str().unwrap(), cx)
+                .unwrap();
+            let content = input["content"].as_str().unwrap().to_string();
+            cx.spawn(async move |cx| {
+                let buffer = project
+                    .update(cx, |project, cx| project.open_buffer(project_path, cx))?
+                    .await?;
+                let edit_id = buffer.update(cx, |buffer, cx| buffer.set_text(content, cx))?;
+                action_log.update(cx, |action_log, cx| {
+                    action_log.buffer_edited(buffer.clone(), edit_id.into_iter().collect(), cx);
+                    action_log.defer_save_until_reviewed(buffer, &project);
+                })?;
+                Ok("Edited the buffer".into())
+            })
+        }
+    }
+
+    /// A tool that reads the file at its `path` straight from disk.
+    struct ReadFromDiskTool;
+
+    impl Tool for ReadFromDiskTool {
+        fn name(&self) -> String {
+            "cat".into()
+        }
+
+        fn description(&self) -> String {
+            "Reads a file from disk".into()
+        }
+
+        fn icon(&self) -> ui::IconName {
+            ui::IconName::Terminal
+        }
+
+        fn needs_confirmation(&self) -> bool {
+            false
+        }
+
+        fn reads_from_disk(&self, _input: &serde_json::Value, _cx: &App) -> bool {
+            true
+        }
+
+        fn ui_text(&self, _input: &serde_json::Value) -> String {
+            "Read from disk".into()
+        }
+
+        fn run(
+            self: Arc<Self>,
+            input: serde_json::Value,
+            _messages: &[LanguageModelRequestMessage],
+            project: Entity<Project>,
+            _action_log: Entity<ActionLog>,
+            cx: &mut App,
+        ) -> Task<Result<String>> {
+            let fs = project.read(cx).fs().clone();
+            cx.background_spawn(async move {
+                let path = PathBuf::from(input["abs_path"].as_str().unwrap());
+                fs.load(&path).await
+            })
+        }
+    }
+
+    #[gpui::test]
+    async fn test_disk_reading_tools_wait_for_reviewed_edits(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/project"), json!({ "file.txt": "original" }))
+            .await;
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let thread = create_thread(
+            &project,
+            vec![Arc::new(ReviewedEditTool), Arc::new(ReadFromDiskTool)],
+            cx,
+        );
+
+        thread.update(cx, |thread, cx| {
+            request_tool_uses(
+                thread,
+                vec![(
+                    "edit_for_review",
+                    json!({ "path": "project/file.txt", "content": "edited" }),
+                )],
+                cx,
+            );
+            thread.use_pending_tools(cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            fs.load(path!("/project/file.txt").as_ref()).await.unwrap(),
+            "original"
+        );
+
+        // Reading the file from disk waits until the edit has been reviewed and saved.
+        let message_id = thread.update(cx, |thread, cx| {
+            let message_id = request_tool_uses(
+                thread,
+                vec![("cat", json!({ "abs_path": path!("/project/file.txt") }))],
+                cx,
+            );
+            thread.use_pending_tools(cx);
+            message_id
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            tool_statuses(&thread, message_id, cx),
+            [("Read from disk".to_string(), "queued")]
+        );
+
+        thread.update(cx, |thread, cx| thread.keep_all_edits(cx));
+        cx.run_until_parked();
+        assert_eq!(
+            fs.load(path!("/project/file.txt").as_ref()).await.unwrap(),
+            "edited"
+        );
+        thread.read_with(cx, |thread, cx| {
+            let tool_uses = thread.tool_uses_for_message(message_id, cx);
+            let ToolUseStatus::Finished(output) = &tool_uses[0].status else {
+                panic!("unexpected status: {:?}", tool_uses[0].status);
+            };
+            assert_eq!(&**output, "edited");
+        });
+    }
+
+    #[gpui::test]
+    async fn test_path_policy_blocks_paths_outside_of_worktrees(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/project"), json!({ "file.txt": "" }))
+            .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let thread = create_thread(
+            &project,
+            vec![Arc::new(SlowTool {
+                name: "read",
+                modifies_files: false,
+                log: log.clone(),
+            })],
+            cx,
+        );
+
+        let message_id = thread.update(cx, |thread, cx| {
+            let message_id = request_tool_uses(
+                thread,
+                vec![
+                    (
+                        "read",
+                        json!({ "label": "in worktree", "path": "project/file.txt" }),
+                    ),
+                    (
+                        "read",
+                        json!({ "label": "relative", "path": "elsewhere/file.txt" }),
+                    ),
+                    (
+                        "read",
+                        json!({ "label": "absolute", "path": path!("/etc/passwd") }),
+                    ),
+                ],
+                cx,
+            );
+            thread.use_pending_tools(cx);
+            message_id
+        });
+        cx.run_until_parked();
+
+        assert_eq!(
+            tool_statuses(&thread, message_id, cx),
+            [
+                ("in worktree".to_string(), "running"),
+                ("relative".to_string(), "blocked"),
+                ("absolute".to_string(), "blocked"),
+            ]
+        );
+        assert_eq!(*log.lock(), ["start in worktree"]);
+    }
+
+    #[gpui::test]
+    async fn test_path_policy_blocks_tools_accessing_unlisted_paths(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".zed": {
+                    "settings.json": r#"{
+                        "assistant": {
+                            "version": "2",
+                            "tool_path_policy": { "deny": ["**/.env"] }
+                        }
+                    }"#
+                },
+                "file.txt": "",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let thread = create_thread(
+            &project,
+            vec![Arc::new(SlowTool {
+                name: "read",
+                modifies_files: false,
+                log: log.clone(),
+            })],
+            cx,
+        );
+
+        // A tool whose paths can't all be checked, like a shell command, is blocked as soon
+        // as the policy restricts any path.
+        let message_id = thread.update(cx, |thread, cx| {
+            let message_id = request_tool_uses(
+                thread,
+                vec![
+                    (
+                        "read",
+                        json!({ "label": "listed", "path": "project/file.txt" }),
+                    ),
+                    ("read", json!({ "label": "unlisted", "any_path": true })),
+                ],
+                cx,
+            );
+            thread.use_pending_tools(cx);
+            message_id
+        });
+        cx.run_until_parked();
+
+        assert_eq!(
+            tool_statuses(&thread, message_id, cx),
+            [
+                ("listed".to_string(), "running"),
+                ("unlisted".to_string(), "blocked"),
+            ]
+        );
+        assert_eq!(*log.lock(), ["start listed"]);
+    }
+
+    #[gpui::test]
+    async fn test_profile_overrides_enabled_tools(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            assistant_tool::ToolRegistry::default_global(cx);
+        });
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let thread = create_thread(
+            &project,
+            vec![
+                Arc::new(WriteFileTool),
+                Arc::new(SlowTool {
+                    name: "read",
+                    modifies_files: false,
+                    log,
+                }),
+            ],
+            cx,
+        );
+        thread.read_with(cx, |thread, _| {
+            thread
+                .tools()
+                .enable(ToolSource::Native, &["write_file".into()])
+        });
+
+        let enabled_tools = |cx: &mut TestAppContext| {
+            thread.read_with(cx, |thread, cx| {
+                thread
+                    .enabled_tools(cx)
+                    .iter()
+                    .map(|tool| tool.name())
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(enabled_tools(cx), ["write_file"]);
+
+        thread.update(cx, |thread, _| {
+            thread.set_profile(AgentProfile {
+                name: "Read".into(),
+                tools: [("read".into(), true), ("write_file".into(), false)]
+                    .into_iter()
+                    .collect(),
+                context_servers: Default::default(),
+            })
+        });
+        assert_eq!(enabled_tools(cx), ["read"]);
+        thread.read_with(cx, |thread, cx| {
+            let enabled_in_working_set = thread
+                .tools()
+                .enabled_tools(cx)
+                .iter()
+                .map(|tool| tool.name())
+                .collect::<Vec<_>>();
+            assert_eq!(enabled_in_working_set, ["write_file"]);
+        });
+    }
+
+    #[test]
+    fn test_is_retryable_completion_error() {
+        for message in [
+            "Too Many Requests",
+            "rate limit exceeded",
+            "error: rate_limit_error",
+            "Overloaded",
+            "the request timed out",
+            "502 Bad Gateway",
+            "503 Service Unavailable",
+        ] {
+            assert!(
+                is_retryable_completion_error(&anyhow::anyhow!(message)),
+                "{message:?} should be retryable"
+            );
+        }
+        // The cause is found anywhere in the error's chain.
+        assert!(is_retryable_completion_error(
+            &anyhow::anyhow!("server is overloaded").context("failed to stream completion")
+        ));
+
+        for message in [
+            "401 Unauthorized",
+            "invalid request: prompt is too long",
+            "model not found",
+        ] {
+            assert!(
+                !is_retryable_completion_error(&anyhow::anyhow!(message)),
+                "{message:?} shouldn't be retryable"
+            );
+        }
+        assert!(!is_retryable_completion_error(&anyhow::Error::new(
+            PaymentRequiredError
+        )));
+        assert!(!is_retryable_completion_error(&anyhow::Error::new(
+            MaxMonthlySpendReachedError
+        )));
+    }
+}
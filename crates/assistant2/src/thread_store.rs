@@ -23,7 +23,10 @@ use serde::{Deserialize, Serialize};
 use settings::Settings as _;
 use util::ResultExt as _;
 
-use crate::thread::{MessageId, ProjectSnapshot, Thread, ThreadEvent, ThreadId};
+use crate::thread::{
+    MessageId, MessageTokenUsage, ProjectSnapshot, Thread, ThreadBranchPoint, ThreadCompaction,
+    ThreadEvent, ThreadId,
+};
 
 pub fn init(cx: &mut App) {
     ThreadsDatabase::init(cx);
@@ -92,6 +95,44 @@ impl ThreadStore {
         self.threads().into_iter().take(limit).collect()
     }
 
+    pub fn thread_metadata(&self, id: &ThreadId) -> Option<&SerializedThreadMetadata> {
+        self.threads.iter().find(|thread| &thread.id == id)
+    }
+
+    /// Returns the threads that the given thread descends from, starting with the one it
+    /// was branched from.
+    pub fn thread_ancestors(&self, id: &ThreadId) -> Vec<SerializedThreadMetadata> {
+        let mut ancestors = Vec::new();
+        let mut branched_from = self
+            .thread_metadata(id)
+            .and_then(|thread| thread.branched_from.as_ref());
+        while let Some(branch_point) = branched_from {
+            // Guard against cycles, in case the database was edited by hand.
+            if ancestors.len() >= self.threads.len() || &branch_point.thread_id == id {
+                break;
+            }
+            let Some(parent) = self.thread_metadata(&branch_point.thread_id) else {
+                break;
+            };
+            ancestors.push(parent.clone());
+            branched_from = parent.branched_from.as_ref();
+        }
+        ancestors
+    }
+
+    /// Returns the threads that were branched from the given thread, most recent first.
+    pub fn thread_branches(&self, id: &ThreadId) -> Vec<SerializedThreadMetadata> {
+        self.threads()
+            .into_iter()
+            .filter(|thread| {
+                thread
+                    .branched_from
+                    .as_ref()
+                    .map_or(false, |branch_point| &branch_point.thread_id == id)
+            })
+            .collect()
+    }
+
     pub fn create_thread(&mut self, cx: &mut Context<Self>) -> Entity<Thread> {
         cx.new(|cx| {
             Thread::new(
@@ -158,6 +199,50 @@ impl ThreadStore {
         })
     }
 
+    /// Saves the thread, then creates a branch of it that shares its messages up to and
+    /// including the given one, so that the conversation can take another direction
+    /// without losing the original.
+    pub fn branch_thread(
+        &self,
+        thread: &Entity<Thread>,
+        message_id: MessageId,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<ThreadId>> {
+        let (parent_id, serialized_thread) =
+            thread.update(cx, |thread, cx| (thread.id().clone(), thread.serialize(cx)));
+
+        let id = ThreadId::new();
+        let database_future = ThreadsDatabase::global_future(cx);
+        cx.spawn(async move |this, cx| {
+            let serialized_thread = serialized_thread.await?;
+            let branch = serialized_thread.branch(parent_id.clone(), message_id)?;
+            let database = database_future.await.map_err(|err| anyhow!(err))?;
+            database.save_thread(parent_id, serialized_thread).await?;
+            database.save_thread(id.clone(), branch).await?;
+
+            this.update(cx, |this, cx| this.reload(cx))?.await?;
+            Ok(id)
+        })
+    }
+
+    /// Adds a thread that was previously exported as JSON to the store, under a new ID.
+    pub fn import_thread(&self, json: &[u8], cx: &mut Context<Self>) -> Task<Result<ThreadId>> {
+        let serialized_thread = match SerializedThread::from_json(json) {
+            Ok(serialized_thread) => serialized_thread,
+            Err(error) => return Task::ready(Err(error)),
+        };
+
+        let id = ThreadId::new();
+        let database_future = ThreadsDatabase::global_future(cx);
+        cx.spawn(async move |this, cx| {
+            let database = database_future.await.map_err(|err| anyhow!(err))?;
+            database.save_thread(id.clone(), serialized_thread).await?;
+
+            this.update(cx, |this, cx| this.reload(cx))?.await?;
+            Ok(id)
+        })
+    }
+
     pub fn delete_thread(&mut self, id: &ThreadId, cx: &mut Context<Self>) -> Task<Result<()>> {
         let id = id.clone();
         let database_future = ThreadsDatabase::global_future(cx);
@@ -298,6 +383,8 @@ pub struct SerializedThreadMetadata {
     pub id: ThreadId,
     pub summary: SharedString,
     pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub branched_from: Option<ThreadBranchPoint>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -310,11 +397,60 @@ pub struct SerializedThread {
     pub initial_project_snapshot: Option<Arc<ProjectSnapshot>>,
     #[serde(default)]
     pub cumulative_token_usage: TokenUsage,
+    #[serde(default)]
+    pub branched_from: Option<ThreadBranchPoint>,
+    #[serde(default)]
+    pub compaction: Option<ThreadCompaction>,
 }
 
 impl SerializedThread {
     pub const VERSION: &'static str = "0.1.0";
 
+    /// Returns a copy of this thread that ends at the given message, recorded as a branch of
+    /// the thread with the given ID.
+    pub fn branch(&self, thread_id: ThreadId, message_id: MessageId) -> Result<Self> {
+        let message_ix = self
+            .messages
+            .iter()
+            .position(|message| message.id == message_id)
+            .ok_or_else(|| anyhow!("no message found with ID: {message_id:?}"))?;
+        let mut end_ix = message_ix + 1;
+        // Tool results are sent in the user message that follows the tool uses, so keep it
+        // rather than leaving the branch with tool uses the model never got results for.
+        if !self.messages[message_ix].tool_uses.is_empty() {
+            if let Some(next_message) = self.messages.get(end_ix) {
+                if next_message.role == Role::User && !next_message.tool_results.is_empty() {
+                    end_ix += 1;
+                }
+            }
+        }
+
+        let messages = self.messages[..end_ix].to_vec();
+        let cumulative_token_usage = messages
+            .iter()
+            .filter_map(|message| message.token_usage.as_ref())
+            .fold(TokenUsage::default(), |total, message_usage| {
+                total + message_usage.usage.clone()
+            });
+        Ok(Self {
+            version: Self::VERSION.to_string(),
+            summary: self.summary.clone(),
+            updated_at: Utc::now(),
+            branched_from: Some(ThreadBranchPoint {
+                thread_id,
+                message_id,
+            }),
+            messages,
+            initial_project_snapshot: self.initial_project_snapshot.clone(),
+            cumulative_token_usage,
+            // Keep the summary only if the branch has every message it covers.
+            compaction: self
+                .compaction
+                .clone()
+                .filter(|compaction| compaction.up_to <= message_id),
+        })
+    }
+
     pub fn from_json(json: &[u8]) -> Result<Self> {
         let saved_thread_json = serde_json::from_slice::<serde_json::Value>(json)?;
         match saved_thread_json.get("version") {
@@ -340,7 +476,7 @@ impl SerializedThread {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SerializedMessage {
     pub id: MessageId,
     pub role: Role,
@@ -350,9 +486,11 @@ pub struct SerializedMessage {
     pub tool_uses: Vec<SerializedToolUse>,
     #[serde(default)]
     pub tool_results: Vec<SerializedToolResult>,
+    #[serde(default)]
+    pub token_usage: Option<MessageTokenUsage>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum SerializedMessageSegment {
     #[serde(rename = "text")]
@@ -361,14 +499,14 @@ pub enum SerializedMessageSegment {
     Thinking { text: String },
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SerializedToolUse {
     pub id: LanguageModelToolUseId,
     pub name: SharedString,
     pub input: serde_json::Value,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SerializedToolResult {
     pub tool_use_id: LanguageModelToolUseId,
     pub is_error: bool,
@@ -393,6 +531,8 @@ impl LegacySerializedThread {
             messages: self.messages.into_iter().map(|msg| msg.upgrade()).collect(),
             initial_project_snapshot: self.initial_project_snapshot,
             cumulative_token_usage: TokenUsage::default(),
+            branched_from: None,
+            compaction: None,
         }
     }
 }
@@ -416,6 +556,7 @@ impl LegacySerializedMessage {
             segments: vec![SerializedMessageSegment::Text { text: self.text }],
             tool_uses: self.tool_uses,
             tool_results: self.tool_results,
+            token_usage: None,
         }
     }
 }
@@ -507,6 +648,7 @@ impl ThreadsDatabase {
                     id: key,
                     summary: value.summary,
                     updated_at: value.updated_at,
+                    branched_from: value.branched_from,
                 });
             }
 
@@ -549,3 +691,204 @@ impl ThreadsDatabase {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt as _;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use settings::{Settings as _, SettingsStore};
+    use util::path;
+
+    fn message(
+        id: usize,
+        role: Role,
+        text: &str,
+        tool_uses: Vec<SerializedToolUse>,
+        tool_results: Vec<SerializedToolResult>,
+    ) -> SerializedMessage {
+        SerializedMessage {
+            id: MessageId(id),
+            role,
+            segments: vec![SerializedMessageSegment::Text { text: text.into() }],
+            tool_uses,
+            tool_results,
+            token_usage: (role == Role::Assistant).then(|| MessageTokenUsage {
+                model_id: "model".into(),
+                usage: TokenUsage {
+                    input_tokens: 10,
+                    output_tokens: 1,
+                    ..Default::default()
+                },
+            }),
+        }
+    }
+
+    fn thread_with_tool_use() -> SerializedThread {
+        let tool_use_id = LanguageModelToolUseId::from("tool-1");
+        SerializedThread {
+            version: SerializedThread::VERSION.to_string(),
+            summary: "Reading a file".into(),
+            updated_at: Utc::now(),
+            messages: vec![
+                message(0, Role::User, "What's in the file?", Vec::new(), Vec::new()),
+                message(
+                    1,
+                    Role::Assistant,
+                    "Let me read it.",
+                    vec![SerializedToolUse {
+                        id: tool_use_id.clone(),
+                        name: "read_file".into(),
+                        input: serde_json::json!({ "path": "file.txt" }),
+                    }],
+                    Vec::new(),
+                ),
+                message(
+                    2,
+                    Role::User,
+                    "",
+                    Vec::new(),
+                    vec![SerializedToolResult {
+                        tool_use_id,
+                        is_error: false,
+                        content: "hello".into(),
+                    }],
+                ),
+                message(3, Role::Assistant, "It says hello.", Vec::new(), Vec::new()),
+                message(4, Role::User, "Thanks!", Vec::new(), Vec::new()),
+            ],
+            initial_project_snapshot: None,
+            cumulative_token_usage: TokenUsage::default(),
+            branched_from: None,
+            compaction: None,
+        }
+    }
+
+    fn message_ids(thread: &SerializedThread) -> Vec<usize> {
+        thread.messages.iter().map(|message| message.id.0).collect()
+    }
+
+    #[test]
+    fn test_branch() {
+        let thread = thread_with_tool_use();
+        let thread_id = ThreadId::new();
+
+        // Branching at a message with tool uses keeps the message with their results.
+        let branch = thread.branch(thread_id.clone(), MessageId(1)).unwrap();
+        assert_eq!(message_ids(&branch), [0, 1, 2]);
+        assert_eq!(branch.messages[1].tool_uses[0].name, "read_file");
+        assert_eq!(&*branch.messages[2].tool_results[0].content, "hello");
+        assert_eq!(
+            branch.branched_from,
+            Some(ThreadBranchPoint {
+                thread_id: thread_id.clone(),
+                message_id: MessageId(1),
+            })
+        );
+        assert_eq!(branch.cumulative_token_usage.input_tokens, 10);
+
+        // Everything after the branch point is dropped.
+        let branch = thread.branch(thread_id.clone(), MessageId(3)).unwrap();
+        assert_eq!(message_ids(&branch), [0, 1, 2, 3]);
+        assert_eq!(branch.cumulative_token_usage.input_tokens, 20);
+
+        let branch = thread.branch(thread_id.clone(), MessageId(0)).unwrap();
+        assert_eq!(message_ids(&branch), [0]);
+        assert_eq!(branch.cumulative_token_usage, TokenUsage::default());
+
+        assert!(thread.branch(thread_id, MessageId(5)).is_err());
+    }
+
+    #[gpui::test]
+    async fn test_import_exported_thread(cx: &mut TestAppContext) {
+        let database_dir = tempfile::tempdir().unwrap();
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+            AssistantSettings::register(cx);
+
+            let database = ThreadsDatabase::new(
+                database_dir.path().join("threads"),
+                cx.background_executor().clone(),
+            )
+            .map(Arc::new)
+            .map_err(Arc::new);
+            cx.set_global(GlobalThreadsDatabase(
+                future::ready(database).boxed().shared(),
+            ));
+        });
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            serde_json::json!({ "file.txt": "hello" }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let thread_store = cx
+            .update(|cx| {
+                ThreadStore::new(
+                    project.clone(),
+                    Arc::new(ToolWorkingSet::default()),
+                    Arc::new(PromptBuilder::new(None).unwrap()),
+                    cx,
+                )
+            })
+            .unwrap();
+
+        // Export a thread with a tool use the same way the assistant panel does.
+        let thread_id = ThreadId::new();
+        let thread = thread_store.update(cx, |thread_store, cx| {
+            cx.new(|cx| {
+                Thread::deserialize(
+                    thread_id.clone(),
+                    thread_with_tool_use(),
+                    project.clone(),
+                    thread_store.tools(),
+                    thread_store.prompt_builder.clone(),
+                    cx,
+                )
+            })
+        });
+        let exported = thread
+            .update(cx, |thread, cx| thread.serialize(cx))
+            .await
+            .unwrap();
+        let json = serde_json::to_string_pretty(&exported).unwrap();
+
+        let imported_id = thread_store
+            .update(cx, |thread_store, cx| {
+                thread_store.import_thread(json.as_bytes(), cx)
+            })
+            .await
+            .unwrap();
+        assert_ne!(imported_id, thread_id);
+        thread_store.read_with(cx, |thread_store, _| {
+            let metadata = thread_store.thread_metadata(&imported_id).unwrap();
+            assert_eq!(metadata.summary, "Reading a file");
+        });
+
+        let imported_thread = thread_store
+            .update(cx, |thread_store, cx| {
+                thread_store.open_thread(&imported_id, cx)
+            })
+            .await
+            .unwrap();
+        let imported = imported_thread
+            .update(cx, |thread, cx| thread.serialize(cx))
+            .await
+            .unwrap();
+        assert_eq!(
+            imported_thread.read_with(cx, |thread, _| thread.id().clone()),
+            imported_id
+        );
+        assert_eq!(imported.summary, exported.summary);
+        assert_eq!(message_ids(&imported), [0, 1, 2, 3, 4]);
+        assert_eq!(
+            serde_json::to_value(&imported.messages).unwrap(),
+            serde_json::to_value(&exported.messages).unwrap()
+        );
+    }
+}
@@ -0,0 +1,360 @@
+use std::sync::Arc;
+
+use assistant_settings::{AssistantSettings, TemplateVariable, ThreadTemplate};
+use collections::HashMap;
+use editor::Editor;
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity,
+};
+use language::Point;
+use picker::{Picker, PickerDelegate};
+use settings::Settings as _;
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+use crate::AssistantPanel;
+
+/// A modal for starting a new thread from one of the configured [`ThreadTemplate`]s.
+///
+/// Once a template is chosen, the picker asks for the value of each of its
+/// `{{input:…}}` variables in turn.
+pub struct ThreadTemplatePicker {
+    picker: Entity<Picker<ThreadTemplatePickerDelegate>>,
+}
+
+impl ThreadTemplatePicker {
+    pub fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let (selection, file) = active_editor_state(workspace, cx);
+        let workspace_handle = workspace.weak_handle();
+
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            Self::new(workspace_handle, selection, file, window, cx)
+        });
+    }
+
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        selection: String,
+        file: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let templates = AssistantSettings::get_global(cx)
+            .thread_templates
+            .iter()
+            .map(|(name, template)| (name.clone(), template.clone()))
+            .collect::<Vec<_>>();
+        let delegate = ThreadTemplatePickerDelegate {
+            template_picker: cx.entity().downgrade(),
+            workspace,
+            selection,
+            file,
+            candidates: templates
+                .iter()
+                .enumerate()
+                .map(|(id, (name, _))| StringMatchCandidate::new(id, name))
+                .collect(),
+            templates,
+            matches: Vec::new(),
+            selected_index: 0,
+            step: Step::SelectTemplate,
+            query: String::new(),
+        };
+
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for ThreadTemplatePicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for ThreadTemplatePicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for ThreadTemplatePicker {}
+impl ModalView for ThreadTemplatePicker {}
+
+enum Step {
+    SelectTemplate,
+    Input {
+        template: ThreadTemplate,
+        /// The descriptions of the template's input variables.
+        inputs: Vec<Arc<str>>,
+        values: HashMap<Arc<str>, String>,
+    },
+}
+
+pub struct ThreadTemplatePickerDelegate {
+    template_picker: WeakEntity<ThreadTemplatePicker>,
+    workspace: WeakEntity<Workspace>,
+    selection: String,
+    file: String,
+    templates: Vec<(Arc<str>, ThreadTemplate)>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    step: Step,
+    query: String,
+}
+
+impl ThreadTemplatePickerDelegate {
+    fn current_input(&self) -> Option<&Arc<str>> {
+        match &self.step {
+            Step::SelectTemplate => None,
+            Step::Input { inputs, values, .. } => inputs.get(values.len()),
+        }
+    }
+
+    fn launch(
+        &mut self,
+        template: ThreadTemplate,
+        values: HashMap<Arc<str>, String>,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let prompt = template.render(|variable| match variable {
+            TemplateVariable::Selection => self.selection.clone(),
+            TemplateVariable::File => self.file.clone(),
+            TemplateVariable::Input { description } => {
+                values.get(description).cloned().unwrap_or_default()
+            }
+        });
+
+        self.workspace
+            .update(cx, |workspace, cx| {
+                if let Some(panel) = workspace.panel::<AssistantPanel>(cx) {
+                    workspace.focus_panel::<AssistantPanel>(window, cx);
+                    panel.update(cx, |panel, cx| {
+                        panel.new_thread_from_template(&template, prompt, window, cx)
+                    });
+                }
+            })
+            .log_err();
+        self.dismissed(window, cx);
+    }
+}
+
+impl PickerDelegate for ThreadTemplatePickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        match self.current_input() {
+            Some(description) => format!("{description}…").into(),
+            None => "Select a thread template…".into(),
+        }
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        match self.step {
+            Step::SelectTemplate if self.templates.is_empty() => {
+                Some("No thread templates are configured in the assistant settings".into())
+            }
+            _ => None,
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        match self.step {
+            Step::SelectTemplate => self.matches.len(),
+            Step::Input { .. } => 1,
+        }
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        if let Step::Input { .. } = self.step {
+            self.query = query;
+            return Task::ready(());
+        }
+
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, candidate)| StringMatch {
+                        candidate_id: index,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        match &mut self.step {
+            Step::SelectTemplate => {
+                let Some(mat) = self.matches.get(self.selected_index) else {
+                    return;
+                };
+                let template = self.templates[mat.candidate_id].1.clone();
+                let inputs = template
+                    .variables()
+                    .into_iter()
+                    .filter_map(|variable| match variable {
+                        TemplateVariable::Input { description } => Some(description),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+
+                if inputs.is_empty() {
+                    self.launch(template, HashMap::default(), window, cx);
+                    return;
+                }
+
+                self.step = Step::Input {
+                    template,
+                    inputs,
+                    values: HashMap::default(),
+                };
+            }
+            Step::Input {
+                template,
+                inputs,
+                values,
+            } => {
+                if let Some(description) = inputs.get(values.len()) {
+                    values.insert(description.clone(), std::mem::take(&mut self.query));
+                }
+
+                if values.len() == inputs.len() {
+                    let template = template.clone();
+                    let values = std::mem::take(values);
+                    self.launch(template, values, window, cx);
+                    return;
+                }
+            }
+        }
+
+        self.selected_index = 0;
+        cx.defer_in(window, |picker, window, cx| {
+            picker.set_query("", window, cx);
+            picker.refresh_placeholder(window, cx);
+            picker.refresh(window, cx);
+        });
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.template_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let item = ListItem::new(ix)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+
+        match self.current_input() {
+            Some(description) => Some(
+                item.child(
+                    h_flex()
+                        .gap_2()
+                        .child(Label::new(description.clone()).color(Color::Muted))
+                        .child(Label::new(self.query.clone())),
+                ),
+            ),
+            None => {
+                let mat = self.matches.get(ix)?;
+                let (_, template) = &self.templates[mat.candidate_id];
+                Some(
+                    item.child(HighlightedLabel::new(
+                        mat.string.clone(),
+                        mat.positions.clone(),
+                    ))
+                    .when_some(template.profile.clone(), |item, profile| {
+                        item.end_slot(
+                            Label::new(profile)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    }),
+                )
+            }
+        }
+    }
+}
+
+/// Returns the selected text and the path of the file in the active editor, if any.
+fn active_editor_state(workspace: &Workspace, cx: &mut App) -> (String, String) {
+    let Some(editor) = workspace
+        .active_item(cx)
+        .and_then(|item| item.act_as::<Editor>(cx))
+    else {
+        return (String::new(), String::new());
+    };
+
+    editor.update(cx, |editor, cx| {
+        let selection = editor.selections.newest::<Point>(cx);
+        let buffer = editor.buffer().read(cx);
+        let snapshot = buffer.snapshot(cx);
+        let selected_text = snapshot
+            .text_for_range(selection.range())
+            .collect::<String>();
+        let file = buffer
+            .as_singleton()
+            .and_then(|buffer| {
+                let file = buffer.read(cx).file()?;
+                Some(file.full_path(cx).to_string_lossy().into_owned())
+            })
+            .unwrap_or_default();
+        (selected_text, file)
+    })
+}
@@ -2,9 +2,8 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use assistant_tool::{Tool, ToolWorkingSet};
-use collections::HashMap;
+use collections::{HashMap, IndexMap};
 use futures::future::Shared;
-use futures::FutureExt as _;
 use gpui::{App, SharedString, Task};
 use language_model::{
     LanguageModelRequestMessage, LanguageModelToolResult, LanguageModelToolUse,
@@ -29,7 +28,10 @@ pub struct ToolUse {
 #[derive(Debug, Clone)]
 pub enum ToolUseStatus {
     NeedsConfirmation,
+    /// The tool would access a path that the project's tool path policy doesn't allow.
+    Blocked(SharedString),
     Pending,
+    Queued,
     Running,
     Finished(SharedString),
     Error(SharedString),
@@ -40,7 +42,8 @@ pub struct ToolUseState {
     tool_uses_by_assistant_message: HashMap<MessageId, Vec<LanguageModelToolUse>>,
     tool_uses_by_user_message: HashMap<MessageId, Vec<LanguageModelToolUseId>>,
     tool_results: HashMap<LanguageModelToolUseId, LanguageModelToolResult>,
-    pending_tool_uses_by_id: HashMap<LanguageModelToolUseId, PendingToolUse>,
+    /// The pending tool uses, in the order in which they were requested.
+    pending_tool_uses_by_id: IndexMap<LanguageModelToolUseId, PendingToolUse>,
 }
 
 impl ToolUseState {
@@ -50,7 +53,7 @@ impl ToolUseState {
             tool_uses_by_assistant_message: HashMap::default(),
             tool_uses_by_user_message: HashMap::default(),
             tool_results: HashMap::default(),
-            pending_tool_uses_by_id: HashMap::default(),
+            pending_tool_uses_by_id: IndexMap::default(),
         }
     }
 
@@ -129,7 +132,7 @@ impl ToolUseState {
 
     pub fn cancel_pending(&mut self) -> Vec<PendingToolUse> {
         let mut pending_tools = Vec::new();
-        for (tool_use_id, tool_use) in self.pending_tool_uses_by_id.drain() {
+        for (tool_use_id, tool_use) in self.pending_tool_uses_by_id.drain(..) {
             self.tool_results.insert(
                 tool_use_id.clone(),
                 LanguageModelToolResult {
@@ -169,10 +172,16 @@ impl ToolUseState {
                 if let Some(pending_tool_use) = self.pending_tool_uses_by_id.get(&tool_use.id) {
                     match pending_tool_use.status {
                         PendingToolUseStatus::Idle => ToolUseStatus::Pending,
-                        PendingToolUseStatus::NeedsConfirmation { .. } => {
-                            ToolUseStatus::NeedsConfirmation
+                        PendingToolUseStatus::NeedsConfirmation(ref confirmation) => {
+                            match confirmation.blocked_reason {
+                                Some(ref reason) => ToolUseStatus::Blocked(reason.clone().into()),
+                                None => ToolUseStatus::NeedsConfirmation,
+                            }
+                        }
+                        PendingToolUseStatus::Running { queued: true, .. } => ToolUseStatus::Queued,
+                        PendingToolUseStatus::Running { queued: false, .. } => {
+                            ToolUseStatus::Running
                         }
-                        PendingToolUseStatus::Running { .. } => ToolUseStatus::Running,
                         PendingToolUseStatus::Error(ref err) => {
                             ToolUseStatus::Error(err.clone().into())
                         }
@@ -278,16 +287,29 @@ impl ToolUseState {
         &mut self,
         tool_use_id: LanguageModelToolUseId,
         ui_text: SharedString,
-        task: Task<()>,
+        task: Shared<Task<()>>,
+        queued: bool,
     ) {
         if let Some(tool_use) = self.pending_tool_uses_by_id.get_mut(&tool_use_id) {
             tool_use.ui_text = ui_text.into();
             tool_use.status = PendingToolUseStatus::Running {
-                _task: task.shared(),
+                _task: task,
+                queued,
             };
         }
     }
 
+    /// Marks a queued tool use as running, once the tool uses it was waiting on have finished.
+    pub fn start_queued_tool(&mut self, tool_use_id: &LanguageModelToolUseId) {
+        if let Some(PendingToolUse {
+            status: PendingToolUseStatus::Running { queued, .. },
+            ..
+        }) = self.pending_tool_uses_by_id.get_mut(tool_use_id)
+        {
+            *queued = false;
+        }
+    }
+
     pub fn confirm_tool_use(
         &mut self,
         tool_use_id: LanguageModelToolUseId,
@@ -295,6 +317,7 @@ impl ToolUseState {
         input: serde_json::Value,
         messages: Arc<Vec<LanguageModelRequestMessage>>,
         tool: Arc<dyn Tool>,
+        blocked_reason: Option<Arc<str>>,
     ) {
         if let Some(tool_use) = self.pending_tool_uses_by_id.get_mut(&tool_use_id) {
             let ui_text = ui_text.into();
@@ -305,6 +328,7 @@ impl ToolUseState {
                 messages,
                 tool,
                 ui_text,
+                blocked_reason,
             };
             tool_use.status = PendingToolUseStatus::NeedsConfirmation(Arc::new(confirmation));
         }
@@ -325,7 +349,7 @@ impl ToolUseState {
                         is_error: false,
                     },
                 );
-                self.pending_tool_uses_by_id.remove(&tool_use_id)
+                self.pending_tool_uses_by_id.shift_remove(&tool_use_id)
             }
             Err(err) => {
                 self.tool_results.insert(
@@ -399,7 +423,6 @@ impl ToolUseState {
 pub struct PendingToolUse {
     pub id: LanguageModelToolUseId,
     /// The ID of the Assistant message in which the tool use was requested.
-    #[allow(unused)]
     pub assistant_message_id: MessageId,
     pub name: Arc<str>,
     pub ui_text: Arc<str>,
@@ -414,13 +437,21 @@ pub struct Confirmation {
     pub ui_text: Arc<str>,
     pub messages: Arc<Vec<LanguageModelRequestMessage>>,
     pub tool: Arc<dyn Tool>,
+    /// Why the project's tool path policy blocked the tool, if it did.
+    ///
+    /// Allowing a blocked tool only overrides the policy for this tool use.
+    pub blocked_reason: Option<Arc<str>>,
 }
 
 #[derive(Debug, Clone)]
 pub enum PendingToolUseStatus {
     Idle,
     NeedsConfirmation(Arc<Confirmation>),
-    Running { _task: Shared<Task<()>> },
+    Running {
+        _task: Shared<Task<()>>,
+        /// Whether the tool is waiting for earlier tool uses that modify files to finish.
+        queued: bool,
+    },
     Error(#[allow(unused)] Arc<str>),
 }
 
@@ -436,4 +467,13 @@ impl PendingToolUseStatus {
     pub fn needs_confirmation(&self) -> bool {
         matches!(self, PendingToolUseStatus::NeedsConfirmation { .. })
     }
+
+    pub fn blocked_reason(&self) -> Option<&Arc<str>> {
+        match self {
+            PendingToolUseStatus::NeedsConfirmation(confirmation) => {
+                confirmation.blocked_reason.as_ref()
+            }
+            _ => None,
+        }
+    }
 }
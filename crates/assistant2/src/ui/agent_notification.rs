@@ -59,6 +59,7 @@ impl AgentNotification {
             app_id: Some(app_id.to_owned()),
             window_min_size: None,
             window_decorations: Some(WindowDecorations::Client),
+            tabbing_identifier: None,
         }
     }
 }
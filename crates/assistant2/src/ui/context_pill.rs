@@ -1,6 +1,7 @@
 use std::rc::Rc;
+use std::sync::Arc;
 
-use gpui::ClickEvent;
+use gpui::{img, ClickEvent, Image, ImageSource, ObjectFit};
 use ui::{prelude::*, IconButtonShape, Tooltip};
 
 use crate::context::{ContextKind, ContextSnapshot};
@@ -13,6 +14,7 @@ pub enum ContextPill {
         focused: bool,
         on_click: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
         on_remove: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
+        on_downscale: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
     },
     Suggested {
         name: SharedString,
@@ -36,6 +38,7 @@ impl ContextPill {
             on_remove,
             focused,
             on_click: None,
+            on_downscale: None,
         }
     }
 
@@ -66,6 +69,17 @@ impl ContextPill {
         self
     }
 
+    /// Shows a button that scales down the pill's image context.
+    pub fn on_downscale(
+        mut self,
+        listener: Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>,
+    ) -> Self {
+        if let ContextPill::Added { on_downscale, .. } = &mut self {
+            *on_downscale = Some(listener);
+        }
+        self
+    }
+
     pub fn id(&self) -> ElementId {
         match self {
             Self::Added { context, .. } => {
@@ -75,6 +89,13 @@ impl ContextPill {
         }
     }
 
+    fn image(&self) -> Option<Arc<Image>> {
+        match self {
+            Self::Added { context, .. } => Some(context.image.as_ref()?.original.clone()),
+            Self::Suggested { .. } => None,
+        }
+    }
+
     pub fn icon(&self) -> Icon {
         match self {
             Self::Added { context, .. } => match &context.icon_path {
@@ -105,7 +126,15 @@ impl RenderOnce for ContextPill {
             .border_1()
             .rounded_sm()
             .gap_1()
-            .child(self.icon().size(IconSize::XSmall).color(Color::Muted));
+            .map(|pill| match self.image() {
+                Some(image) => pill.child(
+                    img(ImageSource::Image(image))
+                        .size(rems_from_px(14.))
+                        .rounded_xs()
+                        .object_fit(ObjectFit::Cover),
+                ),
+                None => pill.child(self.icon().size(IconSize::XSmall).color(Color::Muted)),
+            });
 
         match &self {
             ContextPill::Added {
@@ -114,6 +143,7 @@ impl RenderOnce for ContextPill {
                 on_remove,
                 focused,
                 on_click,
+                on_downscale,
             } => base_pill
                 .bg(color.element_background)
                 .border_color(if *focused {
@@ -144,10 +174,35 @@ impl RenderOnce for ContextPill {
                                 element
                             }
                         })
-                        .when_some(context.tooltip.clone(), |element, tooltip| {
-                            element.tooltip(Tooltip::text(tooltip.clone()))
+                        .map(|element| match context.image.clone() {
+                            Some(image) => {
+                                let tooltip = context.tooltip.clone();
+                                element.tooltip(move |_window, cx| {
+                                    cx.new(|_| ImagePreviewTooltip {
+                                        image: image.original.clone(),
+                                        caption: tooltip.clone(),
+                                    })
+                                    .into()
+                                })
+                            }
+                            None => element
+                                .when_some(context.tooltip.clone(), |element, tooltip| {
+                                    element.tooltip(Tooltip::text(tooltip.clone()))
+                                }),
                         }),
                 )
+                .when_some(on_downscale.as_ref(), |element, on_downscale| {
+                    element.child(
+                        IconButton::new(("downscale", context.id.0), IconName::Minimize)
+                            .shape(IconButtonShape::Square)
+                            .icon_size(IconSize::XSmall)
+                            .tooltip(Tooltip::text("Downscale Image"))
+                            .on_click({
+                                let on_downscale = on_downscale.clone();
+                                move |event, window, cx| on_downscale(event, window, cx)
+                            }),
+                    )
+                })
                 .when_some(on_remove.as_ref(), |element, on_remove| {
                     element.child(
                         IconButton::new(("remove", context.id.0), IconName::Close)
@@ -199,7 +254,12 @@ impl RenderOnce for ContextPill {
                         ContextKind::Thread
                         | ContextKind::Directory
                         | ContextKind::FetchedUrl
-                        | ContextKind::Symbol => "Active",
+                        | ContextKind::Symbol
+                        | ContextKind::Diagnostics
+                        | ContextKind::ProjectSearch
+                        | ContextKind::GitDiff
+                        | ContextKind::DebugSession
+                        | ContextKind::Image => "Active",
                     })
                     .size(LabelSize::XSmall)
                     .color(Color::Muted),
@@ -219,3 +279,29 @@ impl RenderOnce for ContextPill {
         }
     }
 }
+
+/// A larger preview of an image context, shown when hovering its pill.
+struct ImagePreviewTooltip {
+    image: Arc<Image>,
+    caption: Option<SharedString>,
+}
+
+impl Render for ImagePreviewTooltip {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        ui::tooltip_container(window, cx, |this, _window, _cx| {
+            this.child(
+                img(ImageSource::Image(self.image.clone()))
+                    .max_w(px(256.))
+                    .max_h(px(256.))
+                    .object_fit(ObjectFit::Contain),
+            )
+            .when_some(self.caption.clone(), |this, caption| {
+                this.child(
+                    Label::new(caption)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+        })
+    }
+}
@@ -1,4 +1,9 @@
 mod agent_profile;
+mod model_fallback;
+mod terminal_tool_settings;
+mod thread_budget;
+mod thread_template;
+mod tool_path_policy;
 
 use std::sync::Arc;
 
@@ -16,6 +21,11 @@ use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources};
 
 pub use crate::agent_profile::*;
+pub use crate::model_fallback::*;
+pub use crate::terminal_tool_settings::*;
+pub use crate::thread_budget::*;
+pub use crate::thread_template::*;
+pub use crate::tool_path_policy::*;
 
 #[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -75,6 +85,14 @@ pub struct AssistantSettings {
     pub profiles: IndexMap<Arc<str>, AgentProfile>,
     pub always_allow_tool_actions: bool,
     pub notify_when_agent_waiting: bool,
+    pub review_edits_before_saving: bool,
+    pub thread_budget: ThreadBudget,
+    pub model_pricing: IndexMap<String, ModelPricing>,
+    pub thread_templates: IndexMap<Arc<str>, ThreadTemplate>,
+    pub tool_path_policy: ToolPathPolicy,
+    pub terminal_tool: TerminalToolSettings,
+    pub model_fallback: ModelFallbackSettings,
+    pub max_image_size: u32,
 }
 
 impl AssistantSettings {
@@ -179,6 +197,14 @@ impl AssistantSettingsContent {
                     profiles: None,
                     always_allow_tool_actions: None,
                     notify_when_agent_waiting: None,
+                    review_edits_before_saving: None,
+                    thread_budget: None,
+                    model_pricing: None,
+                    thread_templates: None,
+                    tool_path_policy: None,
+                    terminal_tool: None,
+                    model_fallback: None,
+                    max_image_size: None,
                 },
                 VersionedAssistantSettingsContent::V2(settings) => settings.clone(),
             },
@@ -204,6 +230,14 @@ impl AssistantSettingsContent {
                 profiles: None,
                 always_allow_tool_actions: None,
                 notify_when_agent_waiting: None,
+                review_edits_before_saving: None,
+                thread_budget: None,
+                model_pricing: None,
+                thread_templates: None,
+                tool_path_policy: None,
+                terminal_tool: None,
+                model_fallback: None,
+                max_image_size: None,
             },
         }
     }
@@ -349,6 +383,14 @@ impl Default for VersionedAssistantSettingsContent {
             profiles: None,
             always_allow_tool_actions: None,
             notify_when_agent_waiting: None,
+            review_edits_before_saving: None,
+            thread_budget: None,
+            model_pricing: None,
+            thread_templates: None,
+            tool_path_policy: None,
+            terminal_tool: None,
+            model_fallback: None,
+            max_image_size: None,
         })
     }
 }
@@ -398,6 +440,46 @@ pub struct AssistantSettingsContentV2 {
     ///
     /// Default: true
     notify_when_agent_waiting: Option<bool>,
+    /// Whether file edits made by the agent are held in the review diff and only
+    /// written to disk once every change to the file has been kept or rejected.
+    /// Until then, tools that read from disk wait for the review to finish, and
+    /// tools that copy, move or delete files always ask for confirmation.
+    ///
+    /// Default: false
+    review_edits_before_saving: Option<bool>,
+    /// Token limits applied to each agent thread.
+    thread_budget: Option<ThreadBudget>,
+    /// The prices of models, keyed by model ID, used to estimate the cost of threads.
+    ///
+    /// Default: {}
+    model_pricing: Option<IndexMap<String, ModelPricing>>,
+    /// Reusable prompts, keyed by name, that can be launched from the command palette
+    /// to start a new thread.
+    ///
+    /// Default: {}
+    thread_templates: Option<IndexMap<Arc<str>, ThreadTemplate>>,
+    /// Which paths the agent's filesystem tools may access. Set this in a
+    /// project's `.zed/settings.json` to apply it to that project only.
+    ///
+    /// Default: every path inside the project is allowed, and every path outside of it is denied.
+    tool_path_policy: Option<ToolPathPolicy>,
+    /// When the agent's terminal tool asks for approval, how long its commands
+    /// may run, and how much of their output is shown to the model.
+    ///
+    /// Default: commands need approval unless `always_allow_tool_actions` is enabled,
+    /// time out after 300 seconds, and show at most 16384 bytes of output.
+    terminal_tool: Option<TerminalToolSettings>,
+    /// How requests that fail because a model is rate limited, overloaded, or
+    /// unresponsive are retried, and which models to fall back to.
+    ///
+    /// Default: each model is retried twice, waiting 1 second and then 2 seconds,
+    /// with no fallback models.
+    model_fallback: Option<ModelFallbackSettings>,
+    /// The largest width or height, in pixels, of images attached as context.
+    /// Larger images are scaled down to fit before they're sent to the model.
+    ///
+    /// Default: 1568
+    max_image_size: Option<u32>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
@@ -549,6 +631,21 @@ impl Settings for AssistantSettings {
                 &mut settings.notify_when_agent_waiting,
                 value.notify_when_agent_waiting,
             );
+            merge(
+                &mut settings.review_edits_before_saving,
+                value.review_edits_before_saving,
+            );
+            merge(&mut settings.thread_budget, value.thread_budget);
+            if let Some(model_pricing) = value.model_pricing {
+                settings.model_pricing.extend(model_pricing);
+            }
+            if let Some(thread_templates) = value.thread_templates {
+                settings.thread_templates.extend(thread_templates);
+            }
+            merge(&mut settings.tool_path_policy, value.tool_path_policy);
+            merge(&mut settings.terminal_tool, value.terminal_tool);
+            merge(&mut settings.model_fallback, value.model_fallback);
+            merge(&mut settings.max_image_size, value.max_image_size);
             merge(&mut settings.default_profile, value.default_profile);
 
             if let Some(profiles) = value.profiles {
@@ -592,9 +689,43 @@ fn merge<T>(target: &mut T, value: Option<T>) {
 mod tests {
     use fs::Fs;
     use gpui::{ReadGlobal, TestAppContext};
+    use strum::IntoEnumIterator as _;
 
     use super::*;
 
+    #[gpui::test]
+    fn test_default_model_pricing(cx: &mut App) {
+        let test_settings = settings::SettingsStore::test(cx);
+        cx.set_global(test_settings);
+        AssistantSettings::register(cx);
+
+        // The models of the built-in providers are priced out of the box.
+        let model_pricing = &AssistantSettings::get_global(cx).model_pricing;
+        let model_ids = AnthropicModel::iter()
+            .filter(|model| !matches!(model, AnthropicModel::Custom { .. }))
+            .map(|model| model.id().to_string())
+            .chain(
+                OpenAiModel::iter()
+                    .filter(|model| !matches!(model, OpenAiModel::Custom { .. }))
+                    .map(|model| model.id().to_string()),
+            );
+        for model_id in model_ids {
+            assert!(
+                model_pricing.contains_key(&model_id),
+                "no pricing for {model_id}"
+            );
+        }
+        assert_eq!(
+            model_pricing.get("claude-3-7-sonnet-latest"),
+            Some(&ModelPricing {
+                input: 3.0,
+                output: 15.0,
+                cache_creation_input: 3.75,
+                cache_read_input: 0.3,
+            })
+        );
+    }
+
     #[gpui::test]
     async fn test_deserialize_assistant_settings_with_version(cx: &mut TestAppContext) {
         let fs = fs::FakeFs::new(cx.executor().clone());
@@ -644,6 +775,14 @@ mod tests {
                             profiles: None,
                             always_allow_tool_actions: None,
                             notify_when_agent_waiting: None,
+                            review_edits_before_saving: None,
+                            thread_budget: None,
+                            model_pricing: None,
+                            thread_templates: None,
+                            tool_path_policy: None,
+                            terminal_tool: None,
+                            model_fallback: None,
+                            max_image_size: None,
                         }),
                     )
                 },
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::LanguageModelSelection;
+
+/// How the agent retries requests that a model rejected because it was rate limited,
+/// overloaded, or didn't respond in time, and which models it falls back to.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ModelFallbackSettings {
+    /// The models to try, in order, once the thread's model has used up its retries.
+    ///
+    /// Default: []
+    #[serde(default)]
+    pub models: Vec<LanguageModelSelection>,
+    /// How many times to retry a model before falling back to the next one. Set to 0
+    /// to fail right away instead of retrying.
+    ///
+    /// Default: 2
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// How long to wait before the first retry, in milliseconds. The wait doubles
+    /// with each retry of the same model.
+    ///
+    /// Default: 1000
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    /// The longest to wait before a retry, in milliseconds.
+    ///
+    /// Default: 30000
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+fn default_initial_backoff_ms() -> u64 {
+    1000
+}
+
+fn default_max_backoff_ms() -> u64 {
+    30_000
+}
+
+impl Default for ModelFallbackSettings {
+    fn default() -> Self {
+        Self {
+            models: Vec::new(),
+            max_retries: default_max_retries(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+        }
+    }
+}
+
+impl ModelFallbackSettings {
+    /// Returns how long to wait before the given retry of a model, counting from zero.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let backoff_ms = self
+            .initial_backoff_ms
+            .saturating_mul(1u64.checked_shl(retry).unwrap_or(u64::MAX))
+            .min(self.max_backoff_ms);
+        Duration::from_millis(backoff_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let settings = ModelFallbackSettings {
+            initial_backoff_ms: 500,
+            max_backoff_ms: 3000,
+            ..Default::default()
+        };
+        assert_eq!(settings.backoff(0), Duration::from_millis(500));
+        assert_eq!(settings.backoff(1), Duration::from_millis(1000));
+        assert_eq!(settings.backoff(2), Duration::from_millis(2000));
+        assert_eq!(settings.backoff(3), Duration::from_millis(3000));
+        assert_eq!(settings.backoff(100), Duration::from_millis(3000));
+    }
+}
@@ -0,0 +1,132 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Settings for the agent's `terminal` tool.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TerminalToolSettings {
+    /// When to ask for approval before running a command.
+    ///
+    /// Default: allowlist
+    #[serde(default)]
+    pub approval: TerminalToolApproval,
+    /// Commands that run without asking for approval when `approval` is `allowlist`,
+    /// matched as prefixes of whole words, such as `cargo check` or `git status`.
+    /// Commands that chain, pipe, or redirect to other commands always need approval.
+    ///
+    /// Default: []
+    #[serde(default)]
+    pub allowed_commands: Vec<String>,
+    /// How long a command may run before it is killed, in seconds.
+    ///
+    /// Default: 300
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// The maximum size of a command's output shown to the model, in bytes. Longer
+    /// output keeps its beginning and end, omitting the lines in between.
+    ///
+    /// Default: 16384
+    #[serde(default = "default_max_output_bytes")]
+    pub max_output_bytes: usize,
+}
+
+fn default_timeout_secs() -> u64 {
+    300
+}
+
+fn default_max_output_bytes() -> usize {
+    16 * 1024
+}
+
+impl Default for TerminalToolSettings {
+    fn default() -> Self {
+        Self {
+            approval: TerminalToolApproval::default(),
+            allowed_commands: Vec::new(),
+            timeout_secs: default_timeout_secs(),
+            max_output_bytes: default_max_output_bytes(),
+        }
+    }
+}
+
+/// When the agent's `terminal` tool asks for approval before running a command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TerminalToolApproval {
+    /// Ask before running every command, even when `always_allow_tool_actions` is enabled.
+    AlwaysAsk,
+    /// Run the `allowed_commands` without asking, and ask for the rest unless
+    /// `always_allow_tool_actions` is enabled.
+    #[default]
+    Allowlist,
+    /// Never ask before running a command.
+    Never,
+}
+
+impl TerminalToolSettings {
+    /// Returns whether running the command needs approval, or `None` if that's up to
+    /// the `always_allow_tool_actions` setting.
+    pub fn needs_approval(&self, command: &str) -> Option<bool> {
+        match self.approval {
+            TerminalToolApproval::AlwaysAsk => Some(true),
+            TerminalToolApproval::Never => Some(false),
+            TerminalToolApproval::Allowlist => self.is_allowed(command).then_some(false),
+        }
+    }
+
+    /// Returns whether the command starts with one of the `allowed_commands`, and
+    /// doesn't run any other command alongside it.
+    pub fn is_allowed(&self, command: &str) -> bool {
+        let command = command.trim();
+        if command.contains(['\n', ';', '&', '|', '`', '<', '>']) || command.contains("$(") {
+            return false;
+        }
+
+        self.allowed_commands.iter().any(|prefix| {
+            let prefix = prefix.trim();
+            !prefix.is_empty()
+                && command
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowed_commands() {
+        let settings = TerminalToolSettings {
+            allowed_commands: vec!["cargo check".into(), "git status".into()],
+            ..Default::default()
+        };
+
+        assert!(settings.is_allowed("cargo check"));
+        assert!(settings.is_allowed("cargo check --workspace"));
+        assert!(settings.is_allowed("  git status  "));
+        assert!(!settings.is_allowed("cargo checkout"));
+        assert!(!settings.is_allowed("cargo test"));
+        assert!(!settings.is_allowed("cargo check && rm -rf target"));
+        assert!(!settings.is_allowed("cargo check; rm -rf target"));
+        assert!(!settings.is_allowed("git status | sh"));
+        assert!(!settings.is_allowed("git status > out.txt"));
+        assert!(!settings.is_allowed("cargo check $(rm -rf target)"));
+    }
+
+    #[test]
+    fn test_needs_approval() {
+        let mut settings = TerminalToolSettings {
+            allowed_commands: vec!["ls".into()],
+            ..Default::default()
+        };
+        assert_eq!(settings.needs_approval("ls -la"), Some(false));
+        assert_eq!(settings.needs_approval("rm file"), None);
+
+        settings.approval = TerminalToolApproval::AlwaysAsk;
+        assert_eq!(settings.needs_approval("ls -la"), Some(true));
+
+        settings.approval = TerminalToolApproval::Never;
+        assert_eq!(settings.needs_approval("rm file"), Some(false));
+    }
+}
@@ -0,0 +1,101 @@
+use language_model::TokenUsage;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Limits on how many tokens a single agent thread may consume.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ThreadBudget {
+    /// Warn once the thread has used this many tokens in total.
+    ///
+    /// Default: null
+    pub soft_limit_tokens: Option<u64>,
+    /// Stop sending requests for the thread once it has used this many tokens in total.
+    ///
+    /// Default: null
+    pub hard_limit_tokens: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BudgetStatus {
+    WithinBudget,
+    SoftLimitExceeded,
+    HardLimitExceeded,
+}
+
+impl ThreadBudget {
+    pub fn status(&self, total_tokens: u64) -> BudgetStatus {
+        if self
+            .hard_limit_tokens
+            .map_or(false, |limit| total_tokens >= limit)
+        {
+            BudgetStatus::HardLimitExceeded
+        } else if self
+            .soft_limit_tokens
+            .map_or(false, |limit| total_tokens >= limit)
+        {
+            BudgetStatus::SoftLimitExceeded
+        } else {
+            BudgetStatus::WithinBudget
+        }
+    }
+}
+
+/// The price of a model's tokens, in US dollars per million tokens.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ModelPricing {
+    pub input: f64,
+    pub output: f64,
+    #[serde(default)]
+    pub cache_creation_input: f64,
+    #[serde(default)]
+    pub cache_read_input: f64,
+}
+
+impl ModelPricing {
+    /// Returns the estimated cost of the given usage, in US dollars.
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        const TOKENS_PER_UNIT: f64 = 1_000_000.;
+        (usage.input_tokens as f64 * self.input
+            + usage.output_tokens as f64 * self.output
+            + usage.cache_creation_input_tokens as f64 * self.cache_creation_input
+            + usage.cache_read_input_tokens as f64 * self.cache_read_input)
+            / TOKENS_PER_UNIT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_status() {
+        let budget = ThreadBudget {
+            soft_limit_tokens: Some(100),
+            hard_limit_tokens: Some(200),
+        };
+        assert_eq!(budget.status(99), BudgetStatus::WithinBudget);
+        assert_eq!(budget.status(100), BudgetStatus::SoftLimitExceeded);
+        assert_eq!(budget.status(200), BudgetStatus::HardLimitExceeded);
+        assert_eq!(
+            ThreadBudget::default().status(u64::MAX),
+            BudgetStatus::WithinBudget
+        );
+    }
+
+    #[test]
+    fn test_model_pricing_cost() {
+        let pricing = ModelPricing {
+            input: 3.,
+            output: 15.,
+            cache_creation_input: 3.75,
+            cache_read_input: 0.3,
+        };
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            cache_creation_input_tokens: 0,
+            cache_read_input_tokens: 1_000_000,
+        };
+        assert!((pricing.cost(&usage) - 4.8).abs() < f64::EPSILON);
+    }
+}
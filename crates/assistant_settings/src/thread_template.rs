@@ -0,0 +1,146 @@
+use std::iter;
+use std::ops::Range;
+use std::sync::Arc;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::LanguageModelSelection;
+
+/// A reusable prompt that starts a new agent thread.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ThreadTemplate {
+    /// The text of the first message, which may reference variables such as
+    /// `{{selection}}`, `{{file}}` or `{{input:description}}`.
+    pub prompt: String,
+    /// The profile whose tools the thread uses, instead of the selected profile's.
+    ///
+    /// Default: null
+    #[serde(default)]
+    pub profile: Option<Arc<str>>,
+    /// The model the thread's requests are sent to, instead of the active model.
+    ///
+    /// Default: null
+    #[serde(default)]
+    pub model: Option<LanguageModelSelection>,
+}
+
+/// A variable referenced by a [`ThreadTemplate`] prompt.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TemplateVariable {
+    /// The text selected in the active editor.
+    Selection,
+    /// The path of the file open in the active editor.
+    File,
+    /// A value that is asked of the user when the template is launched.
+    Input { description: Arc<str> },
+}
+
+impl TemplateVariable {
+    fn parse(name: &str) -> Option<Self> {
+        match name.trim() {
+            "selection" => Some(Self::Selection),
+            "file" => Some(Self::File),
+            name => {
+                let description = name.strip_prefix("input:")?.trim();
+                Some(Self::Input {
+                    description: description.into(),
+                })
+            }
+        }
+    }
+}
+
+impl ThreadTemplate {
+    /// Returns the distinct variables referenced by the prompt, in the order they first appear.
+    pub fn variables(&self) -> Vec<TemplateVariable> {
+        let mut variables = Vec::new();
+        for (_, variable) in placeholders(&self.prompt) {
+            if let Some(variable) = variable {
+                if !variables.contains(&variable) {
+                    variables.push(variable);
+                }
+            }
+        }
+        variables
+    }
+
+    /// Returns the prompt with every variable replaced by the value returned by `value_for`.
+    ///
+    /// Placeholders that don't name a known variable are left untouched.
+    pub fn render(&self, mut value_for: impl FnMut(&TemplateVariable) -> String) -> String {
+        let mut rendered = String::with_capacity(self.prompt.len());
+        let mut rendered_up_to = 0;
+        for (range, variable) in placeholders(&self.prompt) {
+            if let Some(variable) = variable {
+                rendered.push_str(&self.prompt[rendered_up_to..range.start]);
+                rendered.push_str(&value_for(&variable));
+                rendered_up_to = range.end;
+            }
+        }
+        rendered.push_str(&self.prompt[rendered_up_to..]);
+        rendered
+    }
+}
+
+/// Returns the byte range of each `{{...}}` placeholder in the prompt, along with the variable
+/// it names, if it names one.
+fn placeholders(
+    prompt: &str,
+) -> impl Iterator<Item = (Range<usize>, Option<TemplateVariable>)> + '_ {
+    let mut offset = 0;
+    iter::from_fn(move || {
+        let start = offset + prompt[offset..].find("{{")?;
+        let end = start + prompt[start..].find("}}")? + 2;
+        offset = end;
+        Some((
+            start..end,
+            TemplateVariable::parse(&prompt[start + 2..end - 2]),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(prompt: &str) -> ThreadTemplate {
+        ThreadTemplate {
+            prompt: prompt.to_string(),
+            profile: None,
+            model: None,
+        }
+    }
+
+    #[test]
+    fn test_template_variables() {
+        let template = template(
+            "Review {{file}}:\n{{ selection }}\nFocus on {{input:Area to focus on}}, not {{unknown}}. {{file}}",
+        );
+        assert_eq!(
+            template.variables(),
+            vec![
+                TemplateVariable::File,
+                TemplateVariable::Selection,
+                TemplateVariable::Input {
+                    description: "Area to focus on".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_template() {
+        let template =
+            template("Explain {{selection}} from {{file}} in {{input:Style}}{{unknown}} {{");
+        let rendered = template.render(|variable| match variable {
+            TemplateVariable::Selection => "fn main() {}".to_string(),
+            TemplateVariable::File => "src/main.rs".to_string(),
+            TemplateVariable::Input { description } => format!("<{description}>"),
+        });
+        assert_eq!(
+            rendered,
+            "Explain fn main() {} from src/main.rs in <Style>{{unknown}} {{"
+        );
+    }
+}
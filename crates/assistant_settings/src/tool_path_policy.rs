@@ -0,0 +1,132 @@
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use util::paths::PathMatcher;
+
+/// Which paths the agent's filesystem tools may access.
+///
+/// Paths inside the project are matched relative to their worktree's root,
+/// and paths outside of the project are matched as absolute paths.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ToolPathPolicy {
+    /// Globs that tools may access. When empty, every path inside the
+    /// project is allowed. Paths outside of the project are only allowed
+    /// when they match one of these globs.
+    ///
+    /// Default: []
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Globs that tools may never access, even when they also match `allow`.
+    ///
+    /// Default: []
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl ToolPathPolicy {
+    /// Returns true if the policy blocks any path inside the project.
+    pub fn restricts_worktree_paths(&self) -> bool {
+        !self.allow.is_empty() || !self.deny.is_empty()
+    }
+
+    /// Checks a path inside a worktree, relative to the worktree's root.
+    ///
+    /// Returns the reason the path is blocked, if it is.
+    pub fn check_worktree_path(&self, path: &Path) -> Result<(), String> {
+        if let Some(glob) = Self::matching_glob(&self.deny, path) {
+            return Err(format!(
+                "{} matches the denied pattern `{glob}`",
+                path.display()
+            ));
+        }
+        if !self.allow.is_empty() && Self::matching_glob(&self.allow, path).is_none() {
+            return Err(format!(
+                "{} does not match any allowed pattern",
+                path.display()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks an absolute path outside of every worktree.
+    ///
+    /// Returns the reason the path is blocked, if it is.
+    pub fn check_external_path(&self, abs_path: &Path) -> Result<(), String> {
+        if let Some(glob) = Self::matching_glob(&self.deny, abs_path) {
+            return Err(format!(
+                "{} matches the denied pattern `{glob}`",
+                abs_path.display()
+            ));
+        }
+        if Self::matching_glob(&self.allow, abs_path).is_none() {
+            return Err(format!("{} is outside of the project", abs_path.display()));
+        }
+        Ok(())
+    }
+
+    fn matching_glob<'a>(globs: &'a [String], path: &Path) -> Option<&'a str> {
+        globs
+            .iter()
+            .map(String::as_str)
+            .find(|glob| match PathMatcher::new([glob]) {
+                Ok(matcher) => matcher.is_match(path),
+                Err(error) => {
+                    log::error!("invalid tool path policy glob {glob:?}: {error}");
+                    false
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allow: &[&str], deny: &[&str]) -> ToolPathPolicy {
+        ToolPathPolicy {
+            allow: allow.iter().map(|glob| glob.to_string()).collect(),
+            deny: deny.iter().map(|glob| glob.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_worktree_paths() {
+        let default = ToolPathPolicy::default();
+        assert!(!default.restricts_worktree_paths());
+        assert!(default
+            .check_worktree_path(Path::new("src/main.rs"))
+            .is_ok());
+
+        assert!(policy(&[], &["**/.env"]).restricts_worktree_paths());
+        let policy = policy(&["src/**", "Cargo.toml"], &["**/secrets/**"]);
+        assert!(policy.restricts_worktree_paths());
+        assert!(policy.check_worktree_path(Path::new("src/main.rs")).is_ok());
+        assert!(policy.check_worktree_path(Path::new("Cargo.toml")).is_ok());
+        assert!(policy
+            .check_worktree_path(Path::new("target/debug"))
+            .is_err());
+        assert!(policy
+            .check_worktree_path(Path::new("src/secrets/key.pem"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_external_paths() {
+        let default = ToolPathPolicy::default();
+        assert!(default
+            .check_external_path(Path::new("/etc/passwd"))
+            .is_err());
+
+        let policy = policy(&["/tmp/**"], &["/tmp/private/**"]);
+        assert!(policy
+            .check_external_path(Path::new("/tmp/out.txt"))
+            .is_ok());
+        assert!(policy
+            .check_external_path(Path::new("/tmp/private/out.txt"))
+            .is_err());
+        assert!(policy
+            .check_external_path(Path::new("/etc/passwd"))
+            .is_err());
+    }
+}
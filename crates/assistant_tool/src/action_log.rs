@@ -1,10 +1,12 @@
 use anyhow::{Context as _, Result};
 use buffer_diff::BufferDiff;
 use collections::{BTreeMap, HashMap, HashSet};
+use futures::{channel::oneshot, Future};
 use gpui::{App, AppContext, AsyncApp, Context, Entity, Subscription, Task, WeakEntity};
 use language::{
     Buffer, BufferEvent, DiskState, OffsetRangeExt, Operation, TextBufferSnapshot, ToOffset,
 };
+use project::Project;
 use std::{ops::Range, sync::Arc};
 
 /// Tracks actions performed by tools in a thread
@@ -16,6 +18,10 @@ pub struct ActionLog {
     tracked_buffers: BTreeMap<Entity<Buffer>, TrackedBuffer>,
     /// Has the model edited a file since it last checked diagnostics?
     edited_since_project_diagnostics_check: bool,
+    /// Reviewed buffers that are being written to disk.
+    saving_buffers: HashSet<Entity<Buffer>>,
+    /// Notified once no deferred save is waiting for review or being written.
+    deferred_save_waiters: Vec<oneshot::Sender<()>>,
 }
 
 impl ActionLog {
@@ -25,6 +31,8 @@ impl ActionLog {
             stale_buffers_in_context: HashSet::default(),
             tracked_buffers: BTreeMap::default(),
             edited_since_project_diagnostics_check: false,
+            saving_buffers: HashSet::default(),
+            deferred_save_waiters: Vec::new(),
         }
     }
 
@@ -40,6 +48,7 @@ impl ActionLog {
                 }
                 Change::Deleted { reviewed, .. } => !*reviewed,
             });
+        self.notify_deferred_save_waiters();
         cx.notify();
     }
 
@@ -83,6 +92,7 @@ impl ActionLog {
                         },
                     },
                     version: buffer.read(cx).version(),
+                    deferred_save: None,
                     diff,
                     secondary_diff: unreviewed_diff,
                     diff_update: diff_update_tx,
@@ -208,6 +218,7 @@ impl ActionLog {
                     // If the buffer had been edited by a tool, but it got
                     // deleted externally, we want to stop tracking it.
                     self.tracked_buffers.remove(&buffer);
+                    self.notify_deferred_save_waiters();
                 } else {
                     tracked_buffer.schedule_diff_update();
                 }
@@ -230,7 +241,24 @@ impl ActionLog {
                 anyhow::Ok(tracked_buffer.update_diff(cx))
             })??;
             update.await;
-            this.update(cx, |_this, cx| cx.notify())?;
+            this.update(cx, |this, cx| {
+                if let Some(tracked_buffer) = this.tracked_buffers.get_mut(&buffer) {
+                    if tracked_buffer.deferred_save.is_some()
+                        && !tracked_buffer.has_unreviewed_changes(cx)
+                    {
+                        let project = tracked_buffer.deferred_save.take();
+                        // A file created by a tool whose contents were all rejected is never
+                        // written, whereas one that was created empty still is.
+                        if !tracked_buffer.is_rejected_creation() {
+                            if let Some(project) = project.and_then(|project| project.upgrade()) {
+                                this.save_reviewed_buffer(buffer.clone(), project, cx);
+                            }
+                        }
+                        this.notify_deferred_save_waiters();
+                    }
+                }
+                cx.notify()
+            })?;
         }
 
         Ok(())
@@ -288,6 +316,74 @@ impl ActionLog {
         tracked_buffer.schedule_diff_update();
     }
 
+    /// Holds off writing a buffer edited by a tool to disk until the user has
+    /// reviewed all of its changes, at which point it is saved in the given project.
+    pub fn defer_save_until_reviewed(&mut self, buffer: Entity<Buffer>, project: &Entity<Project>) {
+        if let Some(tracked_buffer) = self.tracked_buffers.get_mut(&buffer) {
+            tracked_buffer.deferred_save = Some(project.downgrade());
+            tracked_buffer.schedule_diff_update();
+        }
+    }
+
+    /// Returns true if some edits are waiting to be reviewed before being
+    /// written to disk, or are being written.
+    pub fn has_deferred_saves(&self) -> bool {
+        !self.saving_buffers.is_empty()
+            || self
+                .tracked_buffers
+                .values()
+                .any(|tracked_buffer| tracked_buffer.deferred_save.is_some())
+    }
+
+    /// Returns a future that resolves once every edit whose save was deferred
+    /// has been reviewed and written to disk.
+    ///
+    /// Tools that read files from disk rather than from buffers await this
+    /// before running, so that they don't observe stale contents.
+    pub fn wait_for_deferred_saves(&mut self) -> impl Future<Output = ()> {
+        let waiter = self.has_deferred_saves().then(|| {
+            let (tx, rx) = oneshot::channel();
+            self.deferred_save_waiters.push(tx);
+            rx
+        });
+        async move {
+            if let Some(waiter) = waiter {
+                waiter.await.ok();
+            }
+        }
+    }
+
+    fn save_reviewed_buffer(
+        &mut self,
+        buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        cx: &mut Context<Self>,
+    ) {
+        if !buffer.read(cx).is_dirty() {
+            return;
+        }
+
+        let save = project.update(cx, |project, cx| project.save_buffer(buffer.clone(), cx));
+        self.saving_buffers.insert(buffer.clone());
+        cx.spawn(async move |this, cx| {
+            let result = save.await;
+            this.update(cx, |this, _| {
+                this.saving_buffers.remove(&buffer);
+                this.notify_deferred_save_waiters();
+            })?;
+            result
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn notify_deferred_save_waiters(&mut self) {
+        if !self.has_deferred_saves() {
+            for waiter in self.deferred_save_waiters.drain(..) {
+                waiter.send(()).ok();
+            }
+        }
+    }
+
     pub fn will_delete_buffer(&mut self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
         let tracked_buffer = self.track_buffer(buffer.clone(), false, cx);
         if let Change::Edited {
@@ -304,6 +400,7 @@ impl ActionLog {
                 tracked_buffer.schedule_diff_update();
             } else {
                 self.tracked_buffers.remove(&buffer);
+                self.notify_deferred_save_waiters();
                 cx.notify();
             }
         }
@@ -434,6 +531,8 @@ struct TrackedBuffer {
     buffer: Entity<Buffer>,
     change: Change,
     version: clock::Global,
+    /// The project to save the buffer in once its changes have been reviewed.
+    deferred_save: Option<WeakEntity<Project>>,
     diff: Entity<BufferDiff>,
     secondary_diff: Entity<BufferDiff>,
     diff_update: async_watch::Sender<()>,
@@ -463,6 +562,26 @@ impl TrackedBuffer {
             .is_some()
     }
 
+    fn has_unreviewed_changes(&self, cx: &App) -> bool {
+        self.secondary_diff
+            .read(cx)
+            .hunks(&self.buffer.read(cx), cx)
+            .next()
+            .is_some()
+    }
+
+    /// Whether the buffer was created by a tool and the user rejected its contents.
+    fn is_rejected_creation(&self) -> bool {
+        match &self.change {
+            Change::Edited {
+                unreviewed_edit_ids,
+                accepted_edit_ids,
+                initial_content: None,
+            } => accepted_edit_ids.is_empty() && !unreviewed_edit_ids.is_empty(),
+            _ => false,
+        }
+    }
+
     fn schedule_diff_update(&self) {
         self.diff_update.send(()).ok();
     }
@@ -598,6 +717,7 @@ pub struct ChangedBuffer {
 mod tests {
     use super::*;
     use buffer_diff::DiffHunkStatusKind;
+    use futures::FutureExt as _;
     use gpui::TestAppContext;
     use language::Point;
     use project::{FakeFs, Fs, Project, RemoveOptions};
@@ -817,6 +937,124 @@ mod tests {
         );
     }
 
+    #[gpui::test(iterations = 10)]
+    async fn test_deferred_save(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({"file": "abc\ndef\nghi\njkl\nmno"}))
+            .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let file_path = project
+            .read_with(cx, |project, cx| project.find_project_path("dir/file", cx))
+            .unwrap();
+        let buffer = project
+            .update(cx, |project, cx| project.open_buffer(file_path, cx))
+            .await
+            .unwrap();
+
+        let action_log = cx.new(|_| ActionLog::new());
+        let edit1 = buffer.update(cx, |buffer, cx| {
+            buffer
+                .edit([(Point::new(1, 1)..Point::new(1, 2), "E")], None, cx)
+                .unwrap()
+        });
+        let edit2 = buffer.update(cx, |buffer, cx| {
+            buffer
+                .edit([(Point::new(4, 2)..Point::new(4, 3), "O")], None, cx)
+                .unwrap()
+        });
+        action_log.update(cx, |log, cx| {
+            log.buffer_edited(buffer.clone(), vec![edit1, edit2], cx);
+            log.defer_save_until_reviewed(buffer.clone(), &project);
+        });
+        let mut saved = action_log.update(cx, |log, _| Box::pin(log.wait_for_deferred_saves()));
+        cx.run_until_parked();
+        assert!((&mut saved).now_or_never().is_none());
+        assert_eq!(
+            fs.load(path!("/dir/file").as_ref()).await.unwrap(),
+            "abc\ndef\nghi\njkl\nmno"
+        );
+
+        action_log.update(cx, |log, cx| {
+            log.review_edits_in_range(buffer.clone(), Point::new(4, 0)..Point::new(4, 3), true, cx)
+        });
+        cx.run_until_parked();
+        assert!((&mut saved).now_or_never().is_none());
+
+        // Rejecting the remaining hunk completes the review, and only the kept
+        // edit is written to disk.
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(Point::new(1, 1)..Point::new(1, 2), "e")], None, cx)
+        });
+        cx.run_until_parked();
+        assert!((&mut saved).now_or_never().is_some());
+        assert!(!action_log.read_with(cx, |log, _| log.has_deferred_saves()));
+        assert_eq!(
+            fs.load(path!("/dir/file").as_ref()).await.unwrap(),
+            "abc\ndef\nghi\njkl\nmnO"
+        );
+    }
+
+    #[gpui::test(iterations = 10)]
+    async fn test_deferred_save_of_created_files(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({})).await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let action_log = cx.new(|_| ActionLog::new());
+
+        // A created file whose contents are rejected is never written.
+        let rejected_path = project
+            .read_with(cx, |project, cx| {
+                project.find_project_path("dir/rejected", cx)
+            })
+            .unwrap();
+        let rejected = project
+            .update(cx, |project, cx| project.open_buffer(rejected_path, cx))
+            .await
+            .unwrap();
+        let edit_id = rejected.update(cx, |buffer, cx| buffer.set_text("lorem\n", cx));
+        action_log.update(cx, |log, cx| {
+            log.will_create_buffer(rejected.clone(), edit_id, cx);
+            log.defer_save_until_reviewed(rejected.clone(), &project);
+        });
+        cx.run_until_parked();
+        assert!(action_log.read_with(cx, |log, _| log.has_deferred_saves()));
+
+        rejected.update(cx, |buffer, cx| buffer.set_text("", cx));
+        cx.run_until_parked();
+        assert!(!action_log.read_with(cx, |log, _| log.has_deferred_saves()));
+        assert!(!fs.is_file(path!("/dir/rejected").as_ref()).await);
+
+        // A file that was created empty is written as soon as it's created.
+        let empty_path = project
+            .read_with(cx, |project, cx| project.find_project_path("dir/empty", cx))
+            .unwrap();
+        let empty = project
+            .update(cx, |project, cx| project.open_buffer(empty_path, cx))
+            .await
+            .unwrap();
+        action_log.update(cx, |log, cx| {
+            log.will_create_buffer(empty.clone(), None, cx);
+            log.defer_save_until_reviewed(empty.clone(), &project);
+        });
+        cx.run_until_parked();
+        assert!(!action_log.read_with(cx, |log, _| log.has_deferred_saves()));
+        assert_eq!(fs.load(path!("/dir/empty").as_ref()).await.unwrap(), "");
+    }
+
     #[gpui::test(iterations = 10)]
     async fn test_deletion(cx: &mut TestAppContext) {
         cx.update(|cx| {
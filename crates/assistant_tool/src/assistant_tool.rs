@@ -1,10 +1,12 @@
 mod action_log;
+mod path_policy;
 mod tool_registry;
 mod tool_working_set;
 
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::Result;
@@ -14,6 +16,7 @@ use language_model::LanguageModelRequestMessage;
 use project::Project;
 
 pub use crate::action_log::*;
+pub use crate::path_policy::*;
 pub use crate::tool_registry::*;
 pub use crate::tool_working_set::*;
 
@@ -49,6 +52,46 @@ pub trait Tool: 'static + Send + Sync {
     /// before having permission to run.
     fn needs_confirmation(&self) -> bool;
 
+    /// Returns whether running the tool with the given input needs the user's
+    /// confirmation, overriding [`Tool::needs_confirmation`] and the
+    /// `always_allow_tool_actions` setting, or `None` to defer to them.
+    fn confirmation_override(&self, _input: &serde_json::Value, _cx: &App) -> Option<bool> {
+        None
+    }
+
+    /// Returns true if running the tool may create, modify or delete files in the project.
+    ///
+    /// A checkpoint of the project is taken before such tools run, so that their
+    /// changes can be rolled back.
+    fn modifies_files(&self) -> bool {
+        false
+    }
+
+    /// Returns true if running the tool with the given input may access paths other
+    /// than those returned by [`Tool::accessed_paths`], such as a shell command.
+    ///
+    /// As these paths can't be checked, such tools are blocked until the user allows
+    /// them whenever a worktree's tool path policy restricts any paths.
+    fn accesses_unlisted_paths(&self, _input: &serde_json::Value, _cx: &App) -> bool {
+        false
+    }
+
+    /// Returns true if running the tool with the given input reads files from disk
+    /// rather than from the project's buffers.
+    ///
+    /// When edits are held for review before being saved, such tools wait until
+    /// the edits have been reviewed and written, so that they don't see stale files.
+    fn reads_from_disk(&self, _input: &serde_json::Value, _cx: &App) -> bool {
+        false
+    }
+
+    /// Returns the paths that running the tool with the given input would access.
+    ///
+    /// These are checked against the project's tool path policy before the tool runs.
+    fn accessed_paths(&self, _input: &serde_json::Value, _cx: &App) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
     /// Returns the JSON schema that describes the tool's input.
     fn input_schema(&self) -> serde_json::Value {
         serde_json::Value::Object(serde_json::Map::default())
@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use assistant_settings::{AssistantSettings, ToolPathPolicy};
+use gpui::App;
+use project::{Project, WorktreeId};
+use settings::{Settings as _, SettingsLocation};
+
+/// Checks a path that a tool would access against the tool path policy of the
+/// worktree containing it, or against the global policy for paths outside of the
+/// project.
+///
+/// Relative paths are resolved against the project's worktrees, and are blocked
+/// when they don't resolve to any of them.
+///
+/// Returns the reason the path is blocked, if it is.
+pub fn check_tool_path_policy(path: &Path, project: &Project, cx: &App) -> Result<(), String> {
+    let project_path = project.find_project_path(path, cx).or_else(|| {
+        path.is_absolute()
+            .then(|| project.project_path_for_absolute_path(path, cx))
+            .flatten()
+    });
+    if let Some(project_path) = project_path {
+        AssistantSettings::get(
+            Some(SettingsLocation {
+                worktree_id: project_path.worktree_id,
+                path: project_path.path.as_ref(),
+            }),
+            cx,
+        )
+        .tool_path_policy
+        .check_worktree_path(&project_path.path)
+    } else if path.is_absolute() {
+        AssistantSettings::get_global(cx)
+            .tool_path_policy
+            .check_external_path(path)
+    } else {
+        Err(format!(
+            "{} is not in any of the project's worktrees",
+            path.display()
+        ))
+    }
+}
+
+/// Returns the tool path policy for the paths in the given worktree, for tools
+/// that filter what they find in the project rather than accessing given paths.
+pub fn worktree_tool_path_policy(worktree_id: WorktreeId, cx: &App) -> ToolPathPolicy {
+    AssistantSettings::get(
+        Some(SettingsLocation {
+            worktree_id,
+            path: Path::new(""),
+        }),
+        cx,
+    )
+    .tool_path_policy
+    .clone()
+}
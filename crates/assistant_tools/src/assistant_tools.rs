@@ -1,4 +1,3 @@
-mod bash_tool;
 mod batch_tool;
 mod copy_path_tool;
 mod create_directory_tool;
@@ -16,6 +15,8 @@ mod path_search_tool;
 mod read_file_tool;
 mod regex_search_tool;
 mod replace;
+mod semantic_search_tool;
+mod terminal_tool;
 mod thinking_tool;
 
 use std::sync::Arc;
@@ -25,8 +26,9 @@ use copy_path_tool::CopyPathTool;
 use gpui::App;
 use http_client::HttpClientWithUrl;
 use move_path_tool::MovePathTool;
+use semantic_index::SemanticIndexSettings;
+use settings::{Settings as _, SettingsStore};
 
-use crate::bash_tool::BashTool;
 use crate::batch_tool::BatchTool;
 use crate::create_directory_tool::CreateDirectoryTool;
 use crate::create_file_tool::CreateFileTool;
@@ -41,6 +43,8 @@ use crate::open_tool::OpenTool;
 use crate::path_search_tool::PathSearchTool;
 use crate::read_file_tool::ReadFileTool;
 use crate::regex_search_tool::RegexSearchTool;
+use crate::semantic_search_tool::SemanticSearchTool;
+use crate::terminal_tool::TerminalTool;
 use crate::thinking_tool::ThinkingTool;
 
 pub fn init(http_client: Arc<HttpClientWithUrl>, cx: &mut App) {
@@ -48,7 +52,6 @@ pub fn init(http_client: Arc<HttpClientWithUrl>, cx: &mut App) {
     crate::edit_files_tool::log::init(cx);
 
     let registry = ToolRegistry::global(cx);
-    registry.register_tool(BashTool);
     registry.register_tool(BatchTool);
     registry.register_tool(CreateDirectoryTool);
     registry.register_tool(CreateFileTool);
@@ -64,6 +67,25 @@ pub fn init(http_client: Arc<HttpClientWithUrl>, cx: &mut App) {
     registry.register_tool(PathSearchTool);
     registry.register_tool(ReadFileTool);
     registry.register_tool(RegexSearchTool);
+    registry.register_tool(TerminalTool);
     registry.register_tool(ThinkingTool);
     registry.register_tool(FetchTool::new(http_client));
+
+    // Semantic search is only offered to the agent while projects are being indexed.
+    let mut semantic_index_enabled = None;
+    cx.observe_global::<SettingsStore>(move |cx| {
+        let enabled = SemanticIndexSettings::get_global(cx).enabled;
+        if semantic_index_enabled == Some(enabled) {
+            return;
+        }
+        semantic_index_enabled = Some(enabled);
+
+        let registry = ToolRegistry::global(cx);
+        if enabled {
+            registry.register_tool(SemanticSearchTool);
+        } else {
+            registry.unregister_tool(SemanticSearchTool);
+        }
+    })
+    .detach();
 }
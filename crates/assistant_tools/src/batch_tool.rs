@@ -6,6 +6,8 @@ use language_model::LanguageModelRequestMessage;
 use project::Project;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::mem;
+use std::path::PathBuf;
 use std::sync::Arc;
 use ui::IconName;
 
@@ -154,6 +156,10 @@ impl Tool for BatchTool {
         true
     }
 
+    fn modifies_files(&self) -> bool {
+        true
+    }
+
     fn description(&self) -> String {
         include_str!("./batch_tool/description.md").into()
     }
@@ -162,6 +168,50 @@ impl Tool for BatchTool {
         IconName::Cog
     }
 
+    fn accessed_paths(&self, input: &serde_json::Value, cx: &App) -> Vec<PathBuf> {
+        let Ok(input) = serde_json::from_value::<BatchToolInput>(input.clone()) else {
+            return Vec::new();
+        };
+        let working_set = ToolWorkingSet::default();
+        let mut paths = Vec::new();
+        for invocation in input.invocations {
+            if let Some(tool) = working_set.tool(&invocation.name, cx) {
+                for path in tool.accessed_paths(&invocation.input, cx) {
+                    if !paths.contains(&path) {
+                        paths.push(path);
+                    }
+                }
+            }
+        }
+        paths
+    }
+
+    fn accesses_unlisted_paths(&self, input: &serde_json::Value, cx: &App) -> bool {
+        let Ok(input) = serde_json::from_value::<BatchToolInput>(input.clone()) else {
+            return false;
+        };
+        let working_set = ToolWorkingSet::default();
+        input.invocations.iter().any(|invocation| {
+            working_set
+                .tool(&invocation.name, cx)
+                .map_or(false, |tool| {
+                    tool.accesses_unlisted_paths(&invocation.input, cx)
+                })
+        })
+    }
+
+    fn reads_from_disk(&self, input: &serde_json::Value, cx: &App) -> bool {
+        let Ok(input) = serde_json::from_value::<BatchToolInput>(input.clone()) else {
+            return false;
+        };
+        let working_set = ToolWorkingSet::default();
+        input.invocations.iter().any(|invocation| {
+            working_set
+                .tool(&invocation.name, cx)
+                .map_or(false, |tool| tool.reads_from_disk(&invocation.input, cx))
+        })
+    }
+
     fn input_schema(&self) -> serde_json::Value {
         let schema = schemars::schema_for!(BatchToolInput);
         serde_json::to_value(&schema).unwrap()
@@ -245,6 +295,20 @@ impl Tool for BatchTool {
                         return Err(anyhow!("Tool '{}' not found", tool_name));
                     };
 
+                    if !run_tools_concurrently
+                        && cx.update(|cx| tool.reads_from_disk(&invocation.input, cx))?
+                    {
+                        // Let the earlier tools finish, and any edits they held for review be
+                        // saved, before this one reads from disk.
+                        for task in &mut tasks {
+                            let result = mem::replace(task, Task::ready(Ok(String::new()))).await;
+                            *task = Task::ready(result);
+                        }
+                        action_log
+                            .update(cx, |action_log, _| action_log.wait_for_deferred_saves())?
+                            .await;
+                    }
+
                     let project = project.clone();
                     let action_log = action_log.clone();
                     let messages = messages.clone();
@@ -5,7 +5,7 @@ use language_model::LanguageModelRequestMessage;
 use project::Project;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use ui::IconName;
 use util::markdown::MarkdownString;
 
@@ -35,6 +35,10 @@ impl Tool for CreateDirectoryTool {
         true
     }
 
+    fn modifies_files(&self) -> bool {
+        true
+    }
+
     fn description(&self) -> String {
         include_str!("./create_directory_tool/description.md").into()
     }
@@ -43,6 +47,13 @@ impl Tool for CreateDirectoryTool {
         IconName::Folder
     }
 
+    fn accessed_paths(&self, input: &serde_json::Value, _cx: &App) -> Vec<PathBuf> {
+        match serde_json::from_value::<CreateDirectoryToolInput>(input.clone()) {
+            Ok(input) => vec![PathBuf::from(input.path)],
+            Err(_) => Vec::new(),
+        }
+    }
+
     fn input_schema(&self) -> serde_json::Value {
         let schema = schemars::schema_for!(CreateDirectoryToolInput);
         serde_json::to_value(&schema).unwrap()
@@ -1,10 +1,13 @@
 use anyhow::{anyhow, Result};
+use assistant_settings::AssistantSettings;
 use assistant_tool::{ActionLog, Tool};
 use gpui::{App, Entity, Task};
 use language_model::LanguageModelRequestMessage;
 use project::Project;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use settings::Settings;
+use std::path::PathBuf;
 use std::sync::Arc;
 use ui::IconName;
 use util::markdown::MarkdownString;
@@ -42,6 +45,10 @@ impl Tool for CreateFileTool {
         true
     }
 
+    fn modifies_files(&self) -> bool {
+        true
+    }
+
     fn description(&self) -> String {
         include_str!("./create_file_tool/description.md").into()
     }
@@ -50,6 +57,13 @@ impl Tool for CreateFileTool {
         IconName::FileCreate
     }
 
+    fn accessed_paths(&self, input: &serde_json::Value, _cx: &App) -> Vec<PathBuf> {
+        match serde_json::from_value::<CreateFileToolInput>(input.clone()) {
+            Ok(input) => vec![PathBuf::from(input.path)],
+            Err(_) => Vec::new(),
+        }
+    }
+
     fn input_schema(&self) -> serde_json::Value {
         let schema = schemars::schema_for!(CreateFileToolInput);
         serde_json::to_value(&schema).unwrap()
@@ -97,10 +111,18 @@ impl Tool for CreateFileTool {
                 action_log.will_create_buffer(buffer.clone(), edit_id, cx)
             })?;
 
-            project
-                .update(cx, |project, cx| project.save_buffer(buffer, cx))?
-                .await
-                .map_err(|err| anyhow!("Unable to save buffer for {destination_path}: {err}"))?;
+            if cx.update(|cx| AssistantSettings::get_global(cx).review_edits_before_saving)? {
+                action_log.update(cx, |action_log, _| {
+                    action_log.defer_save_until_reviewed(buffer, &project)
+                })?;
+            } else {
+                project
+                    .update(cx, |project, cx| project.save_buffer(buffer, cx))?
+                    .await
+                    .map_err(|err| {
+                        anyhow!("Unable to save buffer for {destination_path}: {err}")
+                    })?;
+            }
 
             Ok(format!("Created file {destination_path}"))
         })
@@ -1,4 +1,5 @@
 use anyhow::{anyhow, Result};
+use assistant_settings::AssistantSettings;
 use assistant_tool::{ActionLog, Tool};
 use futures::{channel::mpsc, SinkExt, StreamExt};
 use gpui::{App, AppContext, Entity, Task};
@@ -6,7 +7,8 @@ use language_model::LanguageModelRequestMessage;
 use project::{Project, ProjectPath};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use settings::Settings;
+use std::{path::PathBuf, sync::Arc};
 use ui::IconName;
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -36,6 +38,17 @@ impl Tool for DeletePathTool {
         true
     }
 
+    fn confirmation_override(&self, _input: &serde_json::Value, cx: &App) -> Option<bool> {
+        // While edits are held for review, changes to files on disk need approval too.
+        AssistantSettings::get_global(cx)
+            .review_edits_before_saving
+            .then_some(true)
+    }
+
+    fn modifies_files(&self) -> bool {
+        true
+    }
+
     fn description(&self) -> String {
         include_str!("./delete_path_tool/description.md").into()
     }
@@ -44,6 +57,17 @@ impl Tool for DeletePathTool {
         IconName::FileDelete
     }
 
+    fn accessed_paths(&self, input: &serde_json::Value, _cx: &App) -> Vec<PathBuf> {
+        match serde_json::from_value::<DeletePathToolInput>(input.clone()) {
+            Ok(input) => vec![PathBuf::from(input.path)],
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn reads_from_disk(&self, _input: &serde_json::Value, _cx: &App) -> bool {
+        true
+    }
+
     fn input_schema(&self) -> serde_json::Value {
         let schema = schemars::schema_for!(DeletePathToolInput);
         serde_json::to_value(&schema).unwrap()
@@ -6,7 +6,11 @@ use language_model::LanguageModelRequestMessage;
 use project::Project;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::{fmt::Write, path::Path, sync::Arc};
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use ui::IconName;
 use util::markdown::MarkdownString;
 
@@ -57,6 +61,13 @@ impl Tool for DiagnosticsTool {
         IconName::Warning
     }
 
+    fn accessed_paths(&self, input: &serde_json::Value, _cx: &App) -> Vec<PathBuf> {
+        match serde_json::from_value::<DiagnosticsToolInput>(input.clone()) {
+            Ok(input) => input.path.map(PathBuf::from).into_iter().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
     fn input_schema(&self) -> serde_json::Value {
         let schema = schemars::schema_for!(DiagnosticsToolInput);
         serde_json::to_value(&schema).unwrap()
@@ -3,7 +3,8 @@ pub mod log;
 
 use crate::replace::{replace_exact, replace_with_flexible_indent};
 use anyhow::{anyhow, Context, Result};
-use assistant_tool::{ActionLog, Tool};
+use assistant_settings::AssistantSettings;
+use assistant_tool::{check_tool_path_policy, ActionLog, Tool};
 use collections::HashSet;
 use edit_action::{EditAction, EditActionParser};
 use futures::{channel::mpsc, SinkExt, StreamExt};
@@ -15,7 +16,9 @@ use log::{EditToolLog, EditToolRequestId};
 use project::Project;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use settings::Settings;
 use std::fmt::Write;
+use std::path::PathBuf;
 use std::sync::Arc;
 use ui::IconName;
 use util::ResultExt;
@@ -83,6 +86,10 @@ impl Tool for EditFilesTool {
         true
     }
 
+    fn modifies_files(&self) -> bool {
+        true
+    }
+
     fn description(&self) -> String {
         include_str!("./edit_files_tool/description.md").into()
     }
@@ -91,6 +98,25 @@ impl Tool for EditFilesTool {
         IconName::Pencil
     }
 
+    /// Returns the paths named in the edit instructions, which start with a root directory
+    /// and are quoted in backticks. The paths of the edits the editor model makes are
+    /// checked again before they're applied.
+    fn accessed_paths(&self, input: &serde_json::Value, _cx: &App) -> Vec<PathBuf> {
+        let Ok(input) = serde_json::from_value::<EditFilesToolInput>(input.clone()) else {
+            return Vec::new();
+        };
+        let mut paths = Vec::new();
+        for quoted in input.edit_instructions.split('`').skip(1).step_by(2) {
+            if quoted.contains('/') && !quoted.contains(char::is_whitespace) {
+                let path = PathBuf::from(quoted);
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        paths
+    }
+
     fn input_schema(&self) -> serde_json::Value {
         let schema = schemars::schema_for!(EditFilesToolInput);
         serde_json::to_value(&schema).unwrap()
@@ -307,6 +333,8 @@ impl EditToolRequest {
         cx: &mut AsyncApp,
     ) -> Result<()> {
         let project_path = self.project.read_with(cx, |project, cx| {
+            check_tool_path_policy(action.file_path(), project, cx)
+                .map_err(|reason| anyhow!("The tool path policy blocks this edit: {reason}"))?;
             project
                 .find_project_path(action.file_path(), cx)
                 .context("Path not found in project")
@@ -481,10 +509,18 @@ impl EditToolRequest {
                     write!(&mut output, "\n\n{}", action.source)?;
                 }
 
+                let review_edits_before_saving =
+                    cx.update(|cx| AssistantSettings::get_global(cx).review_edits_before_saving)?;
                 for buffer in &changed_buffers {
-                    self.project
-                        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))?
-                        .await?;
+                    if review_edits_before_saving {
+                        self.action_log.update(cx, |log, _| {
+                            log.defer_save_until_reviewed(buffer.clone(), &self.project)
+                        })?;
+                    } else {
+                        self.project
+                            .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))?
+                            .await?;
+                    }
                 }
 
                 if !search_errors.is_empty() {
@@ -1,10 +1,12 @@
 use anyhow::{anyhow, Context as _, Result};
+use assistant_settings::AssistantSettings;
 use assistant_tool::{ActionLog, Tool};
 use gpui::{App, AppContext, Entity, Task};
 use language_model::LanguageModelRequestMessage;
 use project::Project;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use settings::Settings;
 use std::{path::PathBuf, sync::Arc};
 use ui::IconName;
 
@@ -132,6 +134,10 @@ impl Tool for FindReplaceFileTool {
         true
     }
 
+    fn modifies_files(&self) -> bool {
+        true
+    }
+
     fn description(&self) -> String {
         include_str!("find_replace_tool/description.md").to_string()
     }
@@ -140,6 +146,13 @@ impl Tool for FindReplaceFileTool {
         IconName::Pencil
     }
 
+    fn accessed_paths(&self, input: &serde_json::Value, _cx: &App) -> Vec<PathBuf> {
+        match serde_json::from_value::<FindReplaceFileToolInput>(input.clone()) {
+            Ok(input) => vec![input.path],
+            Err(_) => Vec::new(),
+        }
+    }
+
     fn input_schema(&self) -> serde_json::Value {
         let schema = schemars::schema_for!(FindReplaceFileToolInput);
         serde_json::to_value(&schema).unwrap()
@@ -200,9 +213,13 @@ impl Tool for FindReplaceFileTool {
                     log.buffer_edited(buffer.clone(), edit_ids, cx)
                 })?;
 
-                project.update(cx, |project, cx| {
-                    project.save_buffer(buffer, cx)
-                })?.await?;
+                if cx.update(|cx| AssistantSettings::get_global(cx).review_edits_before_saving)? {
+                    action_log.update(cx, |log, _| log.defer_save_until_reviewed(buffer, &project))?;
+                } else {
+                    project.update(cx, |project, cx| {
+                        project.save_buffer(buffer, cx)
+                    })?.await?;
+                }
 
                 Ok(format!("Edited {}", input.path.display()))
             } else {
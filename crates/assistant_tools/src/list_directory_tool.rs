@@ -5,7 +5,11 @@ use language_model::LanguageModelRequestMessage;
 use project::Project;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::{fmt::Write, path::Path, sync::Arc};
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use ui::IconName;
 use util::markdown::MarkdownString;
 
@@ -55,6 +59,17 @@ impl Tool for ListDirectoryTool {
         IconName::Folder
     }
 
+    fn accessed_paths(&self, input: &serde_json::Value, _cx: &App) -> Vec<PathBuf> {
+        match serde_json::from_value::<ListDirectoryToolInput>(input.clone()) {
+            Ok(input) => vec![PathBuf::from(input.path)],
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn reads_from_disk(&self, _input: &serde_json::Value, _cx: &App) -> bool {
+        true
+    }
+
     fn input_schema(&self) -> serde_json::Value {
         let schema = schemars::schema_for!(ListDirectoryToolInput);
         serde_json::to_value(&schema).unwrap()
@@ -1,11 +1,16 @@
 use anyhow::{anyhow, Result};
+use assistant_settings::AssistantSettings;
 use assistant_tool::{ActionLog, Tool};
 use gpui::{App, AppContext, Entity, Task};
 use language_model::LanguageModelRequestMessage;
 use project::Project;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::{path::Path, sync::Arc};
+use settings::Settings;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use ui::IconName;
 use util::markdown::MarkdownString;
 
@@ -45,6 +50,17 @@ impl Tool for MovePathTool {
         true
     }
 
+    fn confirmation_override(&self, _input: &serde_json::Value, cx: &App) -> Option<bool> {
+        // While edits are held for review, changes to files on disk need approval too.
+        AssistantSettings::get_global(cx)
+            .review_edits_before_saving
+            .then_some(true)
+    }
+
+    fn modifies_files(&self) -> bool {
+        true
+    }
+
     fn description(&self) -> String {
         include_str!("./move_path_tool/description.md").into()
     }
@@ -53,6 +69,20 @@ impl Tool for MovePathTool {
         IconName::ArrowRightLeft
     }
 
+    fn accessed_paths(&self, input: &serde_json::Value, _cx: &App) -> Vec<PathBuf> {
+        match serde_json::from_value::<MovePathToolInput>(input.clone()) {
+            Ok(input) => vec![
+                PathBuf::from(input.source_path),
+                PathBuf::from(input.destination_path),
+            ],
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn reads_from_disk(&self, _input: &serde_json::Value, _cx: &App) -> bool {
+        true
+    }
+
     fn input_schema(&self) -> serde_json::Value {
         let schema = schemars::schema_for!(MovePathToolInput);
         serde_json::to_value(&schema).unwrap()
@@ -5,6 +5,7 @@ use language_model::LanguageModelRequestMessage;
 use project::Project;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::sync::Arc;
 use ui::IconName;
 use util::markdown::MarkdownString;
@@ -34,6 +35,20 @@ impl Tool for OpenTool {
         IconName::ExternalLink
     }
 
+    fn accessed_paths(&self, input: &serde_json::Value, _cx: &App) -> Vec<PathBuf> {
+        match serde_json::from_value::<OpenToolInput>(input.clone()) {
+            // URLs are opened in the browser, and don't access the filesystem.
+            Ok(input) if !input.path_or_url.contains("://") => {
+                vec![PathBuf::from(input.path_or_url)]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn reads_from_disk(&self, _input: &serde_json::Value, _cx: &App) -> bool {
+        true
+    }
+
     fn input_schema(&self) -> serde_json::Value {
         let schema = schemars::schema_for!(OpenToolInput);
         serde_json::to_value(&schema).unwrap()
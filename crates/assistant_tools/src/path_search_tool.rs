@@ -1,5 +1,5 @@
 use anyhow::{anyhow, Result};
-use assistant_tool::{ActionLog, Tool};
+use assistant_tool::{worktree_tool_path_policy, ActionLog, Tool};
 use gpui::{App, AppContext, Entity, Task};
 use language_model::LanguageModelRequestMessage;
 use project::Project;
@@ -8,7 +8,6 @@ use serde::{Deserialize, Serialize};
 use std::{path::PathBuf, sync::Arc};
 use ui::IconName;
 use util::paths::PathMatcher;
-use worktree::Snapshot;
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PathSearchToolInput {
@@ -52,6 +51,10 @@ impl Tool for PathSearchTool {
         IconName::SearchCode
     }
 
+    fn reads_from_disk(&self, _input: &serde_json::Value, _cx: &App) -> bool {
+        true
+    }
+
     fn input_schema(&self) -> serde_json::Value {
         let schema = schemars::schema_for!(PathSearchToolInput);
         serde_json::to_value(&schema).unwrap()
@@ -84,21 +87,27 @@ impl Tool for PathSearchTool {
             Ok(matcher) => matcher,
             Err(err) => return Task::ready(Err(anyhow!("Invalid glob: {err}"))),
         };
-        let snapshots: Vec<Snapshot> = project
+        let snapshots: Vec<_> = project
             .read(cx)
             .worktrees(cx)
-            .map(|worktree| worktree.read(cx).snapshot())
+            .map(|worktree| {
+                let snapshot = worktree.read(cx).snapshot();
+                let policy = worktree_tool_path_policy(snapshot.id(), cx);
+                (snapshot, policy)
+            })
             .collect();
 
         cx.background_spawn(async move {
             let mut matches = Vec::new();
 
-            for worktree in snapshots {
+            for (worktree, policy) in snapshots {
                 let root_name = worktree.root_name();
 
-                // Don't consider ignored entries.
+                // Don't consider ignored entries, or entries the agent may not access.
                 for entry in worktree.entries(false, 0) {
-                    if path_matcher.is_match(&entry.path) {
+                    if path_matcher.is_match(&entry.path)
+                        && policy.check_worktree_path(&entry.path).is_ok()
+                    {
                         matches.push(
                             PathBuf::from(root_name)
                                 .join(&entry.path)
@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
@@ -58,6 +58,13 @@ impl Tool for ReadFileTool {
         IconName::Eye
     }
 
+    fn accessed_paths(&self, input: &serde_json::Value, _cx: &App) -> Vec<PathBuf> {
+        match serde_json::from_value::<ReadFileToolInput>(input.clone()) {
+            Ok(input) => vec![input.path.to_path_buf()],
+            Err(_) => Vec::new(),
+        }
+    }
+
     fn input_schema(&self) -> serde_json::Value {
         let schema = schemars::schema_for!(ReadFileToolInput);
         serde_json::to_value(&schema).unwrap()
@@ -1,5 +1,5 @@
 use anyhow::{anyhow, Result};
-use assistant_tool::{ActionLog, Tool};
+use assistant_tool::{check_tool_path_policy, ActionLog, Tool};
 use futures::StreamExt;
 use gpui::{App, Entity, Task};
 use language::OffsetRangeExt;
@@ -120,7 +120,11 @@ impl Tool for RegexSearchTool {
                 }
 
                 buffer.read_with(cx, |buffer, cx| -> Result<(), anyhow::Error> {
-                    if let Some(path) = buffer.file().map(|file| file.full_path(cx)) {
+                    if let Some(path) = buffer
+                        .file()
+                        .map(|file| file.full_path(cx))
+                        .filter(|path| check_tool_path_policy(path, project.read(cx), cx).is_ok())
+                    {
                         let mut file_header_written = false;
                         let mut ranges = ranges
                             .into_iter()
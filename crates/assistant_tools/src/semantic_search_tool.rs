@@ -0,0 +1,129 @@
+use anyhow::{anyhow, Result};
+use assistant_tool::{check_tool_path_policy, ActionLog, Tool};
+use gpui::{App, Entity, Task};
+use language_model::LanguageModelRequestMessage;
+use project::Project;
+use schemars::JsonSchema;
+use semantic_index::{SemanticDb, Status};
+use serde::{Deserialize, Serialize};
+use std::{fmt::Write, sync::Arc};
+use ui::IconName;
+use util::markdown::MarkdownString;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SemanticSearchToolInput {
+    /// One or more natural language descriptions of what to look for.
+    pub queries: Vec<String>,
+
+    /// The maximum number of excerpts to return. Defaults to 10.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 50;
+
+pub struct SemanticSearchTool;
+
+impl Tool for SemanticSearchTool {
+    fn name(&self) -> String {
+        "semantic-search".into()
+    }
+
+    fn needs_confirmation(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> String {
+        include_str!("./semantic_search_tool/description.md").into()
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::MagnifyingGlass
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        let schema = schemars::schema_for!(SemanticSearchToolInput);
+        serde_json::to_value(&schema).unwrap()
+    }
+
+    fn ui_text(&self, input: &serde_json::Value) -> String {
+        match serde_json::from_value::<SemanticSearchToolInput>(input.clone()) {
+            Ok(input) => match input.queries.as_slice() {
+                [query] => format!("Semantic search for {}", MarkdownString::inline_code(query)),
+                queries => format!("Semantic search for {} queries", queries.len()),
+            },
+            Err(_) => "Semantic search".to_string(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        _messages: &[LanguageModelRequestMessage],
+        project: Entity<Project>,
+        _action_log: Entity<ActionLog>,
+        cx: &mut App,
+    ) -> Task<Result<String>> {
+        let input = match serde_json::from_value::<SemanticSearchToolInput>(input) {
+            Ok(input) => input,
+            Err(err) => return Task::ready(Err(anyhow!(err))),
+        };
+        if input.queries.is_empty() {
+            return Task::ready(Err(anyhow!("At least one query is required")));
+        }
+        if !cx.has_global::<SemanticDb>() {
+            return Task::ready(Err(anyhow!(
+                "Semantic search is disabled. The user can enable it with the `semantic_index.enabled` setting."
+            )));
+        }
+
+        let project_index = cx.update_global::<SemanticDb, _>(|semantic_db, cx| {
+            semantic_db
+                .project_index(project.clone(), cx)
+                .unwrap_or_else(|| semantic_db.create_project_index(project.clone(), cx))
+        });
+        let limit = input.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let queries = input.queries;
+        let status = project_index.read(cx).status();
+        let fs = project_index.read(cx).fs();
+        let search = project_index.read(cx).search(queries.clone(), limit, cx);
+
+        cx.spawn(async move |cx| {
+            let results = SemanticDb::load_results(search.await?, &fs, cx).await?;
+            let results = project.read_with(cx, |project, cx| {
+                results
+                    .into_iter()
+                    .filter(|result| {
+                        check_tool_path_policy(&result.full_path, project, cx).is_ok()
+                    })
+                    .collect::<Vec<_>>()
+            })?;
+
+            let mut output = String::new();
+            if let Status::Loading | Status::Scanning { .. } = status {
+                writeln!(
+                    output,
+                    "Note: The project is still being indexed, so these results may be incomplete.\n"
+                )?;
+            }
+            if results.is_empty() {
+                output.push_str("No matches found");
+                return Ok(output);
+            }
+
+            for result in results {
+                let start_line = result.row_range.start() + 1;
+                let end_line = result.row_range.end() + 1;
+                writeln!(
+                    output,
+                    "## {} (lines {start_line}-{end_line}, matching {:?})\n```\n{}\n```\n",
+                    result.full_path.display(),
+                    queries[result.query_index],
+                    result.excerpt_content.trim_end()
+                )?;
+            }
+            Ok(output)
+        })
+    }
+}
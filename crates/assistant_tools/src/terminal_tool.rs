@@ -0,0 +1,315 @@
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context as _, Result};
+use assistant_settings::AssistantSettings;
+use assistant_tool::{ActionLog, Tool};
+use collections::HashMap;
+use futures::FutureExt as _;
+use gpui::{App, Entity, Task};
+use language_model::LanguageModelRequestMessage;
+use project::terminals::TerminalKind;
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use task::{HideStrategy, RevealStrategy, RevealTarget, ShellBuilder, SpawnInTerminal, TaskId};
+use terminal::TaskStatus;
+use ui::IconName;
+use util::markdown::MarkdownString;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TerminalToolInput {
+    /// The shell command to run.
+    command: String,
+    /// Working directory for the command. This must be one of the root directories of the project.
+    cd: String,
+}
+
+pub struct TerminalTool;
+
+impl Tool for TerminalTool {
+    fn name(&self) -> String {
+        "terminal".to_string()
+    }
+
+    fn needs_confirmation(&self) -> bool {
+        true
+    }
+
+    fn confirmation_override(&self, input: &serde_json::Value, cx: &App) -> Option<bool> {
+        let input = serde_json::from_value::<TerminalToolInput>(input.clone()).ok()?;
+        AssistantSettings::get_global(cx)
+            .terminal_tool
+            .needs_approval(&input.command)
+    }
+
+    fn modifies_files(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> String {
+        include_str!("./terminal_tool/description.md").to_string()
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::Terminal
+    }
+
+    fn accessed_paths(&self, input: &serde_json::Value, _cx: &App) -> Vec<PathBuf> {
+        match serde_json::from_value::<TerminalToolInput>(input.clone()) {
+            Ok(input) if input.cd != "." => vec![PathBuf::from(input.cd)],
+            _ => Vec::new(),
+        }
+    }
+
+    fn accesses_unlisted_paths(&self, _input: &serde_json::Value, _cx: &App) -> bool {
+        true
+    }
+
+    fn reads_from_disk(&self, _input: &serde_json::Value, _cx: &App) -> bool {
+        true
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        let schema = schemars::schema_for!(TerminalToolInput);
+        serde_json::to_value(&schema).unwrap()
+    }
+
+    fn ui_text(&self, input: &serde_json::Value) -> String {
+        match serde_json::from_value::<TerminalToolInput>(input.clone()) {
+            Ok(input) => {
+                if input.command.contains('\n') {
+                    MarkdownString::code_block("sh", &input.command).0
+                } else {
+                    MarkdownString::inline_code(&input.command).0
+                }
+            }
+            Err(_) => "Run terminal command".to_string(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        _messages: &[LanguageModelRequestMessage],
+        project: Entity<Project>,
+        _action_log: Entity<ActionLog>,
+        cx: &mut App,
+    ) -> Task<Result<String>> {
+        let input: TerminalToolInput = match serde_json::from_value(input) {
+            Ok(input) => input,
+            Err(err) => return Task::ready(Err(anyhow!(err))),
+        };
+        let working_dir = match working_dir(project.read(cx), &input.cd, cx) {
+            Ok(working_dir) => working_dir,
+            Err(err) => return Task::ready(Err(err)),
+        };
+        let settings = AssistantSettings::get_global(cx).terminal_tool.clone();
+        // The command runs in the shell configured for the project's terminals, which is the
+        // remote user's shell when the project is remote.
+        let (shell, command, args) = {
+            let project = project.read(cx);
+            let shell = project
+                .terminal_settings(&Some(working_dir.to_path_buf()), cx)
+                .shell
+                .clone();
+            let is_local = project.ssh_details(cx).is_none();
+            let (command, args) =
+                ShellBuilder::new(is_local, &shell).build(input.command.clone(), &Vec::new());
+            (shell, command, args)
+        };
+
+        // The command runs as a hidden task in a terminal of the project, so that it gets the
+        // same environment, shell and remote connection as the tasks that the user runs.
+        let terminal = project.update(cx, |project, cx| {
+            project.create_terminal(
+                TerminalKind::Task(SpawnInTerminal {
+                    id: TaskId("terminal-tool".to_string()),
+                    full_label: input.command.clone(),
+                    label: input.command.clone(),
+                    command,
+                    args,
+                    command_label: input.command.clone(),
+                    cwd: Some(working_dir.to_path_buf()),
+                    env: HashMap::default(),
+                    use_new_terminal: true,
+                    allow_concurrent_runs: true,
+                    reveal: RevealStrategy::Never,
+                    reveal_target: RevealTarget::Dock,
+                    hide: HideStrategy::Always,
+                    shell,
+                    show_summary: false,
+                    show_command: false,
+                    show_rerun: false,
+                }),
+                None,
+                cx,
+            )
+        });
+
+        cx.spawn(async move |cx| {
+            let terminal = terminal.await.context("Failed to run command")?;
+            let completion = terminal
+                .update(cx, |terminal, cx| terminal.wait_for_completed_task(cx))?
+                .fuse();
+            let timeout = cx
+                .background_executor()
+                .timer(Duration::from_secs(settings.timeout_secs))
+                .fuse();
+            futures::pin_mut!(completion, timeout);
+            let timed_out = futures::select_biased! {
+                _ = completion => false,
+                _ = timeout => true,
+            };
+
+            // Whatever a command printed before timing out is still returned. Dropping the
+            // terminal afterwards shuts down its process.
+            terminal.read_with(cx, |terminal, _| {
+                let status = if timed_out {
+                    None
+                } else {
+                    terminal.task().map(|task| task.status)
+                };
+                let output = terminal
+                    .last_n_non_empty_lines(terminal.total_lines())
+                    .join("\n");
+                format_output(
+                    status,
+                    &output,
+                    settings.timeout_secs,
+                    settings.max_output_bytes,
+                )
+            })
+        })
+    }
+}
+
+/// Resolves the `cd` input of a command-running tool to the absolute path of
+/// the directory the command should run in.
+fn working_dir(project: &Project, cd: &str, cx: &App) -> Result<Arc<Path>> {
+    let input_path = Path::new(cd);
+    if cd == "." {
+        // Accept "." as meaning "the one worktree" if we only have one worktree.
+        let mut worktrees = project.worktrees(cx);
+
+        let only_worktree = match worktrees.next() {
+            Some(worktree) => worktree,
+            None => return Err(anyhow!("No worktrees found in the project")),
+        };
+
+        if worktrees.next().is_some() {
+            return Err(anyhow!("'.' is ambiguous in multi-root workspaces. Please specify a root directory explicitly."));
+        }
+
+        Ok(only_worktree.read(cx).abs_path())
+    } else if input_path.is_absolute() {
+        // Absolute paths are allowed, but only if they're in one of the project's worktrees.
+        if !project
+            .worktrees(cx)
+            .any(|worktree| input_path.starts_with(&worktree.read(cx).abs_path()))
+        {
+            return Err(anyhow!(
+                "The absolute path must be within one of the project's worktrees"
+            ));
+        }
+
+        Ok(input_path.into())
+    } else {
+        let Some(worktree) = project.worktree_for_root_name(cd, cx) else {
+            return Err(anyhow!("`cd` directory {} not found in the project", cd));
+        };
+
+        Ok(worktree.read(cx).abs_path())
+    }
+}
+
+fn format_output(
+    status: Option<TaskStatus>,
+    output: &str,
+    timeout_secs: u64,
+    max_output_bytes: usize,
+) -> String {
+    let mut result = match status {
+        Some(TaskStatus::Completed { success: true }) => {
+            "Command executed successfully.".to_string()
+        }
+        Some(TaskStatus::Completed { success: false }) => "Command failed.".to_string(),
+        Some(TaskStatus::Running | TaskStatus::Unknown) => {
+            "Command exited without reporting its exit status.".to_string()
+        }
+        None => format!("Command timed out after {timeout_secs} seconds and was killed."),
+    };
+
+    let output = output.trim_end();
+    if !output.is_empty() {
+        result.push_str(&format!(
+            "\n\noutput:\n```\n{}\n```",
+            truncate_output(output, max_output_bytes)
+        ));
+    }
+
+    result
+}
+
+/// Shortens the output to roughly `max_bytes`, keeping whole lines from its beginning and its
+/// end, which is where commands usually report what they're doing and how it went.
+fn truncate_output(output: &str, max_bytes: usize) -> Cow<'_, str> {
+    if output.len() <= max_bytes {
+        return Cow::Borrowed(output);
+    }
+
+    let head_bytes = max_bytes / 4;
+    let tail_bytes = max_bytes - head_bytes;
+
+    let mut head_end = head_bytes;
+    while !output.is_char_boundary(head_end) {
+        head_end -= 1;
+    }
+    if let Some(newline_ix) = output[..head_end].rfind('\n') {
+        head_end = newline_ix + 1;
+    }
+
+    let mut tail_start = output.len() - tail_bytes;
+    while !output.is_char_boundary(tail_start) {
+        tail_start += 1;
+    }
+    if let Some(newline_ix) = output[tail_start..].find('\n') {
+        if tail_start + newline_ix + 1 < output.len() {
+            tail_start += newline_ix + 1;
+        }
+    }
+
+    let omitted_lines = output[head_end..tail_start].lines().count();
+    Cow::Owned(format!(
+        "{}[... {omitted_lines} lines omitted ...]\n{}",
+        &output[..head_end],
+        &output[tail_start..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_output() {
+        assert_eq!(truncate_output("short", 100), "short");
+
+        let output = (0..100)
+            .map(|ix| format!("line {ix}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let truncated = truncate_output(&output, 200);
+        assert!(truncated.len() < 250);
+        assert!(truncated.starts_with("line 0\nline 1\n"));
+        assert!(truncated.ends_with("line 98\nline 99"));
+        assert!(truncated.contains("lines omitted ..."));
+
+        let output = "é".repeat(100);
+        let truncated = truncate_output(&output, 51);
+        assert!(truncated.contains("[... 1 lines omitted ...]"));
+    }
+}
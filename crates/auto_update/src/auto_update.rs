@@ -3,7 +3,8 @@ use client::{Client, TelemetrySettings};
 use db::kvp::KEY_VALUE_STORE;
 use db::RELEASE_CHANNEL;
 use gpui::{
-    actions, App, AppContext as _, AsyncApp, Context, Entity, Global, SemanticVersion, Task, Window,
+    actions, App, AppContext as _, AsyncApp, Context, Entity, Global, PromptLevel, SemanticVersion,
+    Task, Window,
 };
 use http_client::{AsyncBody, HttpClient, HttpClientWithUrl};
 use paths::remote_servers_dir;
@@ -12,7 +13,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources, SettingsStore};
 use smol::{fs, io::AsyncReadExt};
-use smol::{fs::File, process::Command};
+use smol::{fs::File, process::Command, stream::StreamExt as _};
 use std::{
     env::{
         self,
@@ -21,15 +22,30 @@ use std::{
     ffi::OsString,
     path::{Path, PathBuf},
     sync::Arc,
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
+use util::ResultExt as _;
 use which::which;
 use workspace::Workspace;
 
 const SHOULD_SHOW_UPDATE_NOTIFICATION_KEY: &str = "auto-updater-should-show-updated-notification";
+const DEFERRED_UNTIL_KEY: &str = "auto-updater-deferred-until";
+const PREVIOUS_VERSION_KEY: &str = "auto-updater-previous-version";
+const INSTALLED_VERSION_KEY: &str = "auto-updater-installed-version";
+const SKIPPED_VERSION_KEY: &str = "auto-updater-skipped-version";
 const POLL_INTERVAL: Duration = Duration::from_secs(60 * 60);
-
-actions!(auto_update, [Check, DismissErrorMessage, ViewReleaseNotes,]);
+const DEFER_INTERVAL: Duration = Duration::from_secs(60 * 60 * 24);
+
+actions!(
+    auto_update,
+    [
+        Check,
+        DismissErrorMessage,
+        ViewReleaseNotes,
+        DeferUpdates,
+        RollBackUpdate,
+    ]
+);
 
 #[derive(Serialize)]
 struct UpdateRequestBody {
@@ -61,6 +77,8 @@ pub struct AutoUpdater {
     current_version: SemanticVersion,
     http_client: Arc<HttpClientWithUrl>,
     pending_poll: Option<Task<Option<()>>>,
+    /// Updates aren't checked for automatically until this time.
+    deferred_until: Option<SystemTime>,
 }
 
 #[derive(Deserialize)]
@@ -121,6 +139,45 @@ impl Settings for AutoUpdateSetting {
     }
 }
 
+/// The release channel that Zed updates to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, JsonSchema, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    Stable,
+    Preview,
+    Nightly,
+}
+
+impl From<UpdateChannel> for ReleaseChannel {
+    fn from(channel: UpdateChannel) -> Self {
+        match channel {
+            UpdateChannel::Stable => ReleaseChannel::Stable,
+            UpdateChannel::Preview => ReleaseChannel::Preview,
+            UpdateChannel::Nightly => ReleaseChannel::Nightly,
+        }
+    }
+}
+
+/// The release channel to update to, or null to stay on the installed one.
+///
+/// Default: null
+struct AutoUpdateChannelSetting(Option<UpdateChannel>);
+
+impl Settings for AutoUpdateChannelSetting {
+    const KEY: Option<&'static str> = Some("auto_update_channel");
+
+    type FileContent = Option<UpdateChannel>;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        let channel = [sources.server, sources.release_channel, sources.user]
+            .into_iter()
+            .find_map(|value| value.copied().flatten())
+            .or(*sources.default);
+
+        Ok(Self(channel))
+    }
+}
+
 #[derive(Default)]
 struct GlobalAutoUpdate(Option<Entity<AutoUpdater>>);
 
@@ -128,10 +185,19 @@ impl Global for GlobalAutoUpdate {}
 
 pub fn init(http_client: Arc<HttpClientWithUrl>, cx: &mut App) {
     AutoUpdateSetting::register(cx);
+    AutoUpdateChannelSetting::register(cx);
 
     cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
         workspace.register_action(|_, action: &Check, window, cx| check(action, window, cx));
 
+        workspace.register_action(|_, action: &DeferUpdates, _, cx| {
+            defer_updates(action, cx);
+        });
+
+        workspace.register_action(|_, action: &RollBackUpdate, window, cx| {
+            roll_back_update(action, window, cx);
+        });
+
         workspace.register_action(|_, action, _, cx| {
             view_release_notes(action, cx);
         });
@@ -214,6 +280,51 @@ pub fn check(_: &Check, window: &mut Window, cx: &mut App) {
     }
 }
 
+pub fn defer_updates(_: &DeferUpdates, cx: &mut App) {
+    if let Some(updater) = AutoUpdater::get(cx) {
+        updater.update(cx, |updater, cx| updater.defer(cx).detach_and_log_err(cx));
+    }
+}
+
+pub fn roll_back_update(_: &RollBackUpdate, window: &mut Window, cx: &mut App) {
+    let Some(updater) = AutoUpdater::get(cx) else {
+        return;
+    };
+    let previous_version = updater.read(cx).previous_version(cx);
+    window
+        .spawn(cx, async move |cx| {
+            let Some(previous_version) = previous_version.await? else {
+                cx.update(|window, cx| {
+                    window.prompt(
+                        PromptLevel::Info,
+                        "There's no previous version to roll back to.",
+                        Some("Zed keeps the version it replaced the last time it installed an update."),
+                        &["Ok"],
+                        cx,
+                    )
+                })?
+                .await
+                .ok();
+                return Ok(());
+            };
+
+            let answer = cx.update(|window, cx| {
+                window.prompt(
+                    PromptLevel::Warning,
+                    &format!("Roll back to Zed {previous_version}?"),
+                    Some("The version that's installed now won't be installed by automatic updates again."),
+                    &["Roll Back", "Cancel"],
+                    cx,
+                )
+            })?;
+            if answer.await? == 0 {
+                updater.update(cx, |updater, cx| updater.roll_back(cx))?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+}
+
 pub fn view_release_notes(_: &ViewReleaseNotes, cx: &mut App) -> Option<()> {
     let auto_updater = AutoUpdater::get(cx)?;
     let release_channel = ReleaseChannel::try_global(cx)?;
@@ -248,16 +359,91 @@ impl AutoUpdater {
             current_version,
             http_client,
             pending_poll: None,
+            deferred_until: None,
         }
     }
 
     pub fn start_polling(&self, cx: &mut Context<Self>) -> Task<Result<()>> {
-        cx.spawn(async move |this, cx| loop {
-            this.update(cx, |this, cx| this.poll(cx))?;
-            cx.background_executor().timer(POLL_INTERVAL).await;
+        cx.spawn(async move |this, cx| {
+            let deferred_until = cx
+                .background_spawn(async { KEY_VALUE_STORE.read_kvp(DEFERRED_UNTIL_KEY) })
+                .await?
+                .and_then(|secs| secs.parse().ok())
+                .map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+            this.update(cx, |this, _| this.deferred_until = deferred_until)?;
+
+            loop {
+                this.update(cx, |this, cx| {
+                    if !this.is_deferred() {
+                        this.poll(cx)
+                    }
+                })?;
+                cx.background_executor().timer(POLL_INTERVAL).await;
+            }
         })
     }
 
+    fn is_deferred(&self) -> bool {
+        self.deferred_until
+            .map_or(false, |deferred_until| SystemTime::now() < deferred_until)
+    }
+
+    /// Stops checking for updates automatically for a day. Updates can still be checked for
+    /// manually in the meantime.
+    pub fn defer(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let deferred_until = SystemTime::now() + DEFER_INTERVAL;
+        self.deferred_until = Some(deferred_until);
+        cx.notify();
+
+        let secs = deferred_until
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        cx.background_spawn(async move {
+            KEY_VALUE_STORE
+                .write_kvp(DEFERRED_UNTIL_KEY.to_string(), secs.to_string())
+                .await
+        })
+    }
+
+    /// Returns the version that the last installed update replaced, if it can be rolled back to.
+    pub fn previous_version(&self, cx: &App) -> Task<Result<Option<String>>> {
+        cx.background_spawn(async move {
+            let previous_version = KEY_VALUE_STORE.read_kvp(PREVIOUS_VERSION_KEY)?;
+            let backup_exists = fs::metadata(previous_release_dir()).await.is_ok();
+            Ok(previous_version.filter(|_| backup_exists))
+        })
+    }
+
+    /// Restores the version that the last installed update replaced, and skips the version that
+    /// it installed when checking for updates from then on.
+    pub fn roll_back(&mut self, cx: &mut Context<Self>) {
+        if self.pending_poll.is_some() {
+            return;
+        }
+
+        self.status = AutoUpdateStatus::Installing;
+        cx.notify();
+
+        self.pending_poll = Some(cx.spawn(async move |this, cx| {
+            let result = restore_previous_release(cx).await;
+            this.update(cx, |this, cx| {
+                this.pending_poll = None;
+                match result {
+                    Ok(binary_path) => {
+                        this.status = AutoUpdateStatus::Updated { binary_path };
+                    }
+                    Err(error) => {
+                        log::error!("rolling back update failed: error:{:?}", error);
+                        this.status = AutoUpdateStatus::Errored;
+                    }
+                }
+                cx.notify();
+            })
+            .ok()
+        }));
+    }
+
     pub fn poll(&mut self, cx: &mut Context<Self>) {
         if self.pending_poll.is_some() || self.status.is_updated() {
             return;
@@ -438,20 +624,33 @@ impl AutoUpdater {
             (
                 this.http_client.clone(),
                 this.current_version,
-                ReleaseChannel::try_global(cx),
+                AutoUpdateChannelSetting::get_global(cx)
+                    .0
+                    .map(ReleaseChannel::from)
+                    .or(ReleaseChannel::try_global(cx)),
             )
         })?;
 
         let release =
             Self::get_latest_release(&this, "zed", OS, ARCH, release_channel, &mut cx).await?;
 
-        let should_download = match *RELEASE_CHANNEL {
-            ReleaseChannel::Nightly => cx
-                .update(|cx| AppCommitSha::try_global(cx).map(|sha| release.version != sha.0))
-                .ok()
-                .flatten()
-                .unwrap_or(true),
-            _ => release.version.parse::<SemanticVersion>()? > current_version,
+        let skipped_version = cx
+            .background_spawn(async { KEY_VALUE_STORE.read_kvp(SKIPPED_VERSION_KEY) })
+            .await?;
+
+        let should_download = if skipped_version.as_ref() == Some(&release.version) {
+            false
+        } else if release_channel.is_some_and(|channel| channel != *RELEASE_CHANNEL) {
+            true
+        } else {
+            match *RELEASE_CHANNEL {
+                ReleaseChannel::Nightly => cx
+                    .update(|cx| AppCommitSha::try_global(cx).map(|sha| release.version != sha.0))
+                    .ok()
+                    .flatten()
+                    .unwrap_or(true),
+                _ => release.version.parse::<SemanticVersion>()? > current_version,
+            }
         };
 
         if !should_download {
@@ -482,6 +681,7 @@ impl AutoUpdater {
             "Aborting. Could not find rsync which is required for auto-updates."
         );
 
+        let release_version = release.version.clone();
         let downloaded_asset = temp_dir.path().join(filename);
         download_release(&downloaded_asset, release, client, &cx).await?;
 
@@ -490,12 +690,36 @@ impl AutoUpdater {
             cx.notify();
         })?;
 
+        let running_app_path = cx.update(|cx| cx.app_path())??;
+        let app_dir = installed_app_dir(&running_app_path)?;
+        // The update can still be installed when the installed version can't be kept, it just
+        // can't be rolled back.
+        let backed_up = back_up_installed_release(&app_dir)
+            .await
+            .log_err()
+            .is_some();
+
+        let target_channel = release_channel.unwrap_or(*RELEASE_CHANNEL);
         let binary_path = match OS {
-            "macos" => install_release_macos(&temp_dir, downloaded_asset, &cx).await,
-            "linux" => install_release_linux(&temp_dir, downloaded_asset, &cx).await,
+            "macos" => install_release_macos(&temp_dir, downloaded_asset, &app_dir).await,
+            "linux" => {
+                install_release_linux(&temp_dir, downloaded_asset, target_channel, &app_dir).await
+            }
             _ => Err(anyhow!("not supported: {:?}", OS)),
         }?;
 
+        if backed_up {
+            KEY_VALUE_STORE
+                .write_kvp(
+                    PREVIOUS_VERSION_KEY.to_string(),
+                    current_version.to_string(),
+                )
+                .await?;
+            KEY_VALUE_STORE
+                .write_kvp(INSTALLED_VERSION_KEY.to_string(), release_version)
+                .await?;
+        }
+
         this.update(&mut cx, |this, cx| {
             this.set_should_show_update_notification(true, cx)
                 .detach_and_log_err(cx);
@@ -626,15 +850,107 @@ async fn download_release(
     Ok(())
 }
 
+/// The directory that Zed's kept in the last time an update was installed, which rolling back
+/// restores.
+fn previous_release_dir() -> PathBuf {
+    paths::data_dir()
+        .join("previous_release")
+        .join(RELEASE_CHANNEL.dev_name())
+}
+
+/// Returns the directory of the installed app, which updates replace.
+fn installed_app_dir(running_app_path: &Path) -> Result<PathBuf> {
+    match OS {
+        "macos" => Ok(running_app_path.to_path_buf()),
+        "linux" => {
+            if running_app_path.ends_with("libexec/zed-editor") {
+                if let Some(app_dir) = running_app_path.parent().and_then(Path::parent) {
+                    return Ok(app_dir.to_path_buf());
+                }
+            }
+            let home_dir = PathBuf::from(env::var("HOME").context("no HOME env var set")?);
+            Ok(home_dir
+                .join(".local")
+                .join(linux_app_folder_name(*RELEASE_CHANNEL)))
+        }
+        _ => Err(anyhow!("not supported: {:?}", OS)),
+    }
+}
+
+fn linux_app_folder_name(channel: ReleaseChannel) -> String {
+    match channel {
+        ReleaseChannel::Stable => "zed.app".to_string(),
+        channel => format!("zed-{}.app", channel.dev_name()),
+    }
+}
+
+async fn back_up_installed_release(app_dir: &Path) -> Result<()> {
+    sync_dir(app_dir, &previous_release_dir()).await
+}
+
+/// Restores the backup of the version that the last installed update replaced, returning the
+/// path of the binary to restart into.
+async fn restore_previous_release(cx: &mut AsyncApp) -> Result<PathBuf> {
+    let running_app_path = cx.update(|cx| cx.app_path())??;
+    let app_dir = installed_app_dir(&running_app_path)?;
+    let backup_dir = previous_release_dir();
+    anyhow::ensure!(
+        fs::metadata(&backup_dir).await.is_ok(),
+        "no previous release to roll back to"
+    );
+
+    sync_dir(&backup_dir, &app_dir).await?;
+
+    if let Some(installed_version) = KEY_VALUE_STORE.read_kvp(INSTALLED_VERSION_KEY)? {
+        KEY_VALUE_STORE
+            .write_kvp(SKIPPED_VERSION_KEY.to_string(), installed_version)
+            .await?;
+    }
+    KEY_VALUE_STORE
+        .delete_kvp(INSTALLED_VERSION_KEY.to_string())
+        .await?;
+    KEY_VALUE_STORE
+        .delete_kvp(PREVIOUS_VERSION_KEY.to_string())
+        .await?;
+    fs::remove_dir_all(&backup_dir).await.log_err();
+
+    match OS {
+        "linux" => Ok(app_dir.join("libexec/zed-editor")),
+        _ => Ok(app_dir),
+    }
+}
+
+/// Makes the contents of one directory the same as the contents of another.
+async fn sync_dir(from: &Path, to: &Path) -> Result<()> {
+    let mut from_contents: OsString = from.into();
+    from_contents.push("/");
+    fs::create_dir_all(to)
+        .await
+        .with_context(|| format!("failed to create directory {to:?}"))?;
+
+    let output = Command::new("rsync")
+        .args(["-a", "--delete"])
+        .arg(&from_contents)
+        .arg(to)
+        .output()
+        .await?;
+
+    anyhow::ensure!(
+        output.status.success(),
+        "failed to copy {:?} to {:?}: {:?}",
+        from,
+        to,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(())
+}
+
 async fn install_release_linux(
     temp_dir: &tempfile::TempDir,
     downloaded_tar_gz: PathBuf,
-    cx: &AsyncApp,
+    channel: ReleaseChannel,
+    app_dir: &Path,
 ) -> Result<PathBuf> {
-    let channel = cx.update(|cx| ReleaseChannel::global(cx).dev_name())?;
-    let home_dir = PathBuf::from(env::var("HOME").context("no HOME env var set")?);
-    let running_app_path = cx.update(|cx| cx.app_path())??;
-
     let extracted = temp_dir.path().join("zed");
     fs::create_dir_all(&extracted)
         .await
@@ -656,57 +972,26 @@ async fn install_release_linux(
         String::from_utf8_lossy(&output.stderr)
     );
 
-    let suffix = if channel != "stable" {
-        format!("-{}", channel)
-    } else {
-        String::default()
-    };
-    let app_folder_name = format!("zed{}.app", suffix);
-
-    let from = extracted.join(&app_folder_name);
-    let mut to = home_dir.join(".local");
-
-    let expected_suffix = format!("{}/libexec/zed-editor", app_folder_name);
-
-    if let Some(prefix) = running_app_path
-        .to_str()
-        .and_then(|str| str.strip_suffix(&expected_suffix))
-    {
-        to = PathBuf::from(prefix);
-    }
-
-    let output = Command::new("rsync")
-        .args(["-av", "--delete"])
-        .arg(&from)
-        .arg(&to)
-        .output()
-        .await?;
-
-    anyhow::ensure!(
-        output.status.success(),
-        "failed to copy Zed update from {:?} to {:?}: {:?}",
-        from,
-        to,
-        String::from_utf8_lossy(&output.stderr)
-    );
+    // The update is installed in place of the installed app, even when it's from another
+    // channel, whose app folder is named differently.
+    let from = extracted.join(linux_app_folder_name(channel));
+    sync_dir(&from, app_dir)
+        .await
+        .context("failed to copy Zed update")?;
 
-    Ok(to.join(expected_suffix))
+    Ok(app_dir.join("libexec/zed-editor"))
 }
 
 async fn install_release_macos(
     temp_dir: &tempfile::TempDir,
     downloaded_dmg: PathBuf,
-    cx: &AsyncApp,
+    running_app_path: &Path,
 ) -> Result<PathBuf> {
-    let running_app_path = cx.update(|cx| cx.app_path())??;
     let running_app_filename = running_app_path
         .file_name()
         .ok_or_else(|| anyhow!("invalid running app path"))?;
 
     let mount_path = temp_dir.path().join("Zed");
-    let mut mounted_app_path: OsString = mount_path.join(running_app_filename).into();
-
-    mounted_app_path.push("/");
     let output = Command::new("hdiutil")
         .args(["attach", "-nobrowse"])
         .arg(&downloaded_dmg)
@@ -726,18 +1011,25 @@ async fn install_release_macos(
         mount_path: mount_path.clone(),
     };
 
-    let output = Command::new("rsync")
-        .args(["-av", "--delete"])
-        .arg(&mounted_app_path)
-        .arg(&running_app_path)
-        .output()
-        .await?;
+    // Updates from another channel have a differently named app, like "Zed Preview.app".
+    let mut mounted_app_path = mount_path.join(running_app_filename);
+    if fs::metadata(&mounted_app_path).await.is_err() {
+        let mut entries = fs::read_dir(&mount_path).await?;
+        while let Some(entry) = entries.next().await {
+            let path = entry?.path();
+            if path
+                .extension()
+                .map_or(false, |extension| extension == "app")
+            {
+                mounted_app_path = path;
+                break;
+            }
+        }
+    }
 
-    anyhow::ensure!(
-        output.status.success(),
-        "failed to copy app: {:?}",
-        String::from_utf8_lossy(&output.stderr)
-    );
+    sync_dir(&mounted_app_path, running_app_path)
+        .await
+        .context("failed to copy app")?;
 
-    Ok(running_app_path)
+    Ok(running_app_path.to_path_buf())
 }
@@ -1,4 +1,4 @@
-use auto_update::AutoUpdater;
+use auto_update::{AutoUpdater, RollBackUpdate};
 use client::proto::UpdateNotification;
 use editor::{Editor, MultiBuffer};
 use gpui::{actions, prelude::*, App, Context, DismissEvent, Entity, SharedString, Window};
@@ -155,6 +155,11 @@ pub fn notify_if_app_was_updated(cx: &mut App) {
                                 }
                                 cx.emit(DismissEvent);
                             })
+                            .secondary_message("Roll Back")
+                            .secondary_on_click(|window, cx| {
+                                window.dispatch_action(Box::new(RollBackUpdate), cx);
+                                cx.emit(DismissEvent);
+                            })
                         })
                     },
                 );
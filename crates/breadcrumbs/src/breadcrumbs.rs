@@ -1,12 +1,15 @@
-use editor::Editor;
+use editor::{scroll::Autoscroll, Editor};
 use gpui::{
-    Context, Element, EventEmitter, Focusable, IntoElement, ParentElement, Render, StyledText,
-    Subscription, Window,
+    AnyElement, App, Context, Element, Entity, EventEmitter, Focusable, IntoElement, ParentElement,
+    Render, StyledText, Subscription, WeakEntity, Window,
 };
 use itertools::Itertools;
+use project::ProjectPath;
 use std::cmp;
 use theme::ActiveTheme;
-use ui::{prelude::*, ButtonLike, ButtonStyle, Label, Tooltip};
+use ui::{
+    prelude::*, ButtonLike, ButtonStyle, ContextMenu, IconPosition, Label, PopoverMenu, Tooltip,
+};
 use workspace::{
     item::{BreadcrumbText, ItemEvent, ItemHandle},
     ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView,
@@ -50,9 +53,15 @@ impl Render for Breadcrumbs {
             return element;
         };
 
-        let Some(mut segments) = active_item.breadcrumbs(cx.theme(), cx) else {
+        let Some(segments) = active_item.breadcrumbs(cx.theme(), cx) else {
             return element;
         };
+        // Segments are paired with their indices, which the ellipsis for the hidden ones lacks.
+        let mut segments = segments
+            .into_iter()
+            .enumerate()
+            .map(|(ix, segment)| (Some(ix), segment))
+            .collect::<Vec<_>>();
 
         let prefix_end_ix = cmp::min(segments.len(), MAX_SEGMENTS / 2);
         let suffix_start_ix = cmp::max(
@@ -63,15 +72,20 @@ impl Render for Breadcrumbs {
         if suffix_start_ix > prefix_end_ix {
             segments.splice(
                 prefix_end_ix..suffix_start_ix,
-                Some(BreadcrumbText {
-                    text: "⋯".into(),
-                    highlights: None,
-                    font: None,
-                }),
+                Some((
+                    None,
+                    BreadcrumbText {
+                        text: "⋯".into(),
+                        highlights: None,
+                        font: None,
+                    },
+                )),
             );
         }
 
-        let highlighted_segments = segments.into_iter().map(|segment| {
+        let editor = active_item.downcast::<Editor>();
+        let project_path = active_item.project_path(cx);
+        let highlighted_segments = segments.into_iter().map(|(segment_ix, segment)| {
             let mut text_style = window.text_style();
             if let Some(font) = segment.font {
                 text_style.font_family = font.family;
@@ -81,9 +95,21 @@ impl Render for Breadcrumbs {
             }
             text_style.color = Color::Muted.color(cx);
 
-            StyledText::new(segment.text.replace('\n', "⏎"))
-                .with_default_highlights(&text_style, segment.highlights.unwrap_or_default())
-                .into_any()
+            let text = StyledText::new(segment.text.replace('\n', "⏎"))
+                .with_default_highlights(&text_style, segment.highlights.unwrap_or_default());
+            match (segment_ix, editor.as_ref()) {
+                // The first segment is the file, whose siblings are the files in its directory.
+                (Some(0), Some(editor)) => match project_path.clone() {
+                    Some(project_path) => {
+                        render_file_segment(text, project_path, editor.downgrade())
+                    }
+                    None => text.into_any(),
+                },
+                (Some(segment_ix), Some(editor)) => {
+                    render_symbol_segment(text, segment_ix - 1, editor.downgrade())
+                }
+                _ => text.into_any(),
+            }
         });
         let breadcrumbs = Itertools::intersperse_with(highlighted_segments, || {
             Label::new("›").color(Color::Placeholder).into_any_element()
@@ -91,53 +117,157 @@ impl Render for Breadcrumbs {
 
         let breadcrumbs_stack = h_flex().gap_1().children(breadcrumbs);
 
-        match active_item
-            .downcast::<Editor>()
-            .map(|editor| editor.downgrade())
-        {
-            Some(editor) => element.child(
-                ButtonLike::new("toggle outline view")
-                    .child(breadcrumbs_stack)
-                    .style(ButtonStyle::Transparent)
-                    .on_click({
-                        let editor = editor.clone();
-                        move |_, window, cx| {
-                            if let Some((editor, callback)) = editor
-                                .upgrade()
-                                .zip(zed_actions::outline::TOGGLE_OUTLINE.get())
-                            {
-                                callback(editor.to_any(), window, cx);
-                            }
-                        }
-                    })
-                    .tooltip(move |window, cx| {
-                        if let Some(editor) = editor.upgrade() {
-                            let focus_handle = editor.read(cx).focus_handle(cx);
-                            Tooltip::for_action_in(
-                                "Show Symbol Outline",
-                                &zed_actions::outline::ToggleOutline,
-                                &focus_handle,
-                                window,
-                                cx,
-                            )
-                        } else {
-                            Tooltip::for_action(
-                                "Show Symbol Outline",
-                                &zed_actions::outline::ToggleOutline,
-                                window,
-                                cx,
-                            )
-                        }
-                    }),
-            ),
-            None => element
-                // Match the height of the `ButtonLike` in the other arm.
-                .h(rems_from_px(22.))
-                .child(breadcrumbs_stack),
-        }
+        element
+            // Match the height of the `ButtonLike`s of the segments.
+            .h(rems_from_px(22.))
+            .child(breadcrumbs_stack)
     }
 }
 
+/// Renders a segment that opens a menu of the symbols next to the one at the given index of the
+/// symbols containing the cursor.
+fn render_symbol_segment(
+    text: StyledText,
+    symbol_ix: usize,
+    editor: WeakEntity<Editor>,
+) -> AnyElement {
+    PopoverMenu::new(("breadcrumb-symbols", symbol_ix))
+        .menu({
+            let editor = editor.clone();
+            move |window, cx| {
+                let editor = editor.upgrade()?;
+                Some(sibling_symbols_menu(symbol_ix, editor, window, cx))
+            }
+        })
+        .trigger_with_tooltip(
+            ButtonLike::new(("breadcrumb-symbol", symbol_ix))
+                .child(text)
+                .style(ButtonStyle::Transparent),
+            Tooltip::text("Show Sibling Symbols"),
+        )
+        .into_any_element()
+}
+
+fn render_file_segment(
+    text: StyledText,
+    project_path: ProjectPath,
+    editor: WeakEntity<Editor>,
+) -> AnyElement {
+    PopoverMenu::new("breadcrumb-files")
+        .menu(move |window, cx| {
+            let editor = editor.upgrade()?;
+            sibling_files_menu(project_path.clone(), editor, window, cx)
+        })
+        .trigger_with_tooltip(
+            ButtonLike::new("breadcrumb-file")
+                .child(text)
+                .style(ButtonStyle::Transparent),
+            Tooltip::text("Show Sibling Files"),
+        )
+        .into_any_element()
+}
+
+fn sibling_symbols_menu(
+    symbol_ix: usize,
+    editor: Entity<Editor>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Entity<ContextMenu> {
+    let cursor = editor.read(cx).selections.newest_anchor().head();
+    let snapshot = editor.read(cx).buffer().read(cx).snapshot(cx);
+    let siblings = snapshot
+        .sibling_symbols(cursor, symbol_ix, None)
+        .unwrap_or_default();
+    ContextMenu::build(window, cx, |mut menu, _, _| {
+        for sibling in siblings {
+            let is_current = sibling.range.start.cmp(&cursor, &snapshot).is_le()
+                && sibling.range.end.cmp(&cursor, &snapshot).is_ge();
+            let position = sibling.range.start;
+            let editor = editor.clone();
+            menu = menu.toggleable_entry(
+                sibling.text,
+                is_current,
+                IconPosition::Start,
+                None,
+                move |window, cx| {
+                    editor.update(cx, |editor, cx| {
+                        editor.change_selections(
+                            Some(Autoscroll::center()),
+                            window,
+                            cx,
+                            |selections| selections.select_anchor_ranges([position..position]),
+                        );
+                    });
+                    window.focus(&editor.focus_handle(cx));
+                },
+            );
+        }
+        with_outline_entry(menu, editor)
+    })
+}
+
+fn sibling_files_menu(
+    project_path: ProjectPath,
+    editor: Entity<Editor>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Option<Entity<ContextMenu>> {
+    let workspace = editor.read(cx).workspace()?;
+    let worktree = workspace
+        .read(cx)
+        .project()
+        .read(cx)
+        .worktree_for_id(project_path.worktree_id, cx)?;
+    let parent_path = project_path.path.parent()?;
+    let siblings = worktree
+        .read(cx)
+        .child_entries(parent_path)
+        .filter(|entry| entry.is_file())
+        .map(|entry| entry.path.clone())
+        .collect::<Vec<_>>();
+    Some(ContextMenu::build(window, cx, |mut menu, _, _| {
+        for path in siblings {
+            let Some(file_name) = path.file_name() else {
+                continue;
+            };
+            let is_current = path == project_path.path;
+            let project_path = ProjectPath {
+                worktree_id: project_path.worktree_id,
+                path: path.clone(),
+            };
+            let workspace = workspace.downgrade();
+            menu = menu.toggleable_entry(
+                file_name.to_string_lossy().into_owned(),
+                is_current,
+                IconPosition::Start,
+                None,
+                move |window, cx| {
+                    workspace
+                        .update(cx, |workspace, cx| {
+                            workspace
+                                .open_path(project_path.clone(), None, true, window, cx)
+                                .detach_and_log_err(cx);
+                        })
+                        .ok();
+                },
+            );
+        }
+        with_outline_entry(menu, editor)
+    }))
+}
+
+fn with_outline_entry(menu: ContextMenu, editor: Entity<Editor>) -> ContextMenu {
+    menu.separator().entry(
+        "Show Symbol Outline",
+        Some(Box::new(zed_actions::outline::ToggleOutline)),
+        move |window, cx| {
+            if let Some(callback) = zed_actions::outline::TOGGLE_OUTLINE.get() {
+                callback(editor.to_any(), window, cx);
+            }
+        },
+    )
+}
+
 impl ToolbarItemView for Breadcrumbs {
     fn set_active_pane_item(
         &mut self,
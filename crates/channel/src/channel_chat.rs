@@ -26,6 +26,7 @@ pub struct ChannelChat {
     loaded_all_messages: bool,
     last_acknowledged_id: Option<u64>,
     next_pending_message_id: usize,
+    failed_message_ids: HashSet<usize>,
     first_loaded_message_id: Option<u64>,
     user_store: Entity<UserStore>,
     rpc: Arc<Client>,
@@ -129,6 +130,7 @@ impl ChannelChat {
                 acknowledged_message_ids: Default::default(),
                 loaded_all_messages: false,
                 next_pending_message_id: 0,
+                failed_message_ids: Default::default(),
                 last_acknowledged_id: None,
                 rng: StdRng::from_entropy(),
                 first_loaded_message_id: None,
@@ -181,52 +183,139 @@ impl ChannelChat {
             .current_user()
             .ok_or_else(|| anyhow!("current_user is not present"))?;
 
-        let channel_id = self.channel_id;
         let pending_id = ChannelMessageId::Pending(post_inc(&mut self.next_pending_message_id));
-        let nonce = self.rng.gen();
-        self.insert_messages(
-            SumTree::from_item(
-                ChannelMessage {
-                    id: pending_id,
-                    body: message.text.clone(),
-                    sender: current_user,
-                    timestamp: OffsetDateTime::now_utc(),
-                    mentions: message.mentions.clone(),
-                    nonce,
-                    reply_to_message_id: message.reply_to_message_id,
-                    edited_at: None,
-                },
-                &(),
-            ),
-            cx,
-        );
+        let message = ChannelMessage {
+            id: pending_id,
+            body: message.text,
+            sender: current_user,
+            timestamp: OffsetDateTime::now_utc(),
+            mentions: message.mentions,
+            nonce: self.rng.gen(),
+            reply_to_message_id: message.reply_to_message_id,
+            edited_at: None,
+        };
+        self.insert_messages(SumTree::from_item(message.clone(), &()), cx);
+        Ok(self.send_pending_message(message, cx))
+    }
+
+    /// Sends a message that previously failed to send.
+    pub fn retry_message(
+        &mut self,
+        pending_id: usize,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<u64>> {
+        let message = self
+            .pending_messages()
+            .find(|message| message.id == ChannelMessageId::Pending(pending_id))
+            .cloned();
+        match message {
+            Some(message) => self.send_pending_message(message, cx),
+            None => Task::ready(Err(anyhow!("message {pending_id} is not pending"))),
+        }
+    }
+
+    /// Removes a message that hasn't been sent yet.
+    pub fn discard_message(&mut self, pending_id: usize, cx: &mut Context<Self>) {
+        let id = ChannelMessageId::Pending(pending_id);
+        let mut cursor = self.messages.cursor::<(ChannelMessageId, Count)>(&());
+        let mut messages = cursor.slice(&id, Bias::Left, &());
+        if cursor.item().map_or(false, |message| message.id == id) {
+            let ix = cursor.start().1 .0;
+            cursor.next(&());
+            messages.append(cursor.suffix(&()), &());
+            drop(cursor);
+            self.messages = messages;
+            self.failed_message_ids.remove(&pending_id);
+            cx.emit(ChannelChatEvent::MessagesUpdated {
+                old_range: ix..ix + 1,
+                new_count: 0,
+            });
+            cx.notify();
+        }
+    }
+
+    /// Whether the given message was rejected by the server, as opposed to
+    /// still waiting to be sent.
+    pub fn is_message_failed(&self, id: ChannelMessageId) -> bool {
+        match id {
+            ChannelMessageId::Pending(pending_id) => self.failed_message_ids.contains(&pending_id),
+            ChannelMessageId::Saved(_) => false,
+        }
+    }
+
+    fn send_pending_message(
+        &mut self,
+        message: ChannelMessage,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<u64>> {
+        let ChannelMessageId::Pending(pending_id) = message.id else {
+            return Task::ready(Err(anyhow!("message was already sent")));
+        };
+        if self.failed_message_ids.remove(&pending_id) {
+            self.emit_message_updated(message.id, cx);
+        }
+
+        let channel_id = self.channel_id;
         let user_store = self.user_store.clone();
         let rpc = self.rpc.clone();
         let outgoing_messages_lock = self.outgoing_messages_lock.clone();
+        cx.spawn(async move |this, cx| {
+            let result = async {
+                let outgoing_message_guard = outgoing_messages_lock.lock().await;
+                let response = rpc
+                    .request(proto::SendChannelMessage {
+                        channel_id: channel_id.0,
+                        body: message.body,
+                        nonce: Some(message.nonce.into()),
+                        mentions: mentions_to_proto(&message.mentions),
+                        reply_to_message_id: message.reply_to_message_id,
+                    })
+                    .await?;
+                drop(outgoing_message_guard);
+                let response = response.message.ok_or_else(|| anyhow!("invalid message"))?;
+                ChannelMessage::from_proto(response, &user_store, cx).await
+            }
+            .await;
 
-        // todo - handle messages that fail to send (e.g. >1024 chars)
-        Ok(cx.spawn(async move |this, cx| {
-            let outgoing_message_guard = outgoing_messages_lock.lock().await;
-            let request = rpc.request(proto::SendChannelMessage {
-                channel_id: channel_id.0,
-                body: message.text,
-                nonce: Some(nonce.into()),
-                mentions: mentions_to_proto(&message.mentions),
-                reply_to_message_id: message.reply_to_message_id,
-            });
-            let response = request.await?;
-            drop(outgoing_message_guard);
-            let response = response.message.ok_or_else(|| anyhow!("invalid message"))?;
-            let id = response.id;
-            let message = ChannelMessage::from_proto(response, &user_store, cx).await?;
-            this.update(cx, |this, cx| {
-                this.insert_messages(SumTree::from_item(message, &()), cx);
-                if this.first_loaded_message_id.is_none() {
-                    this.first_loaded_message_id = Some(id);
+            match result {
+                Ok(message) => {
+                    let ChannelMessageId::Saved(id) = message.id else {
+                        return Err(anyhow!("invalid message"));
+                    };
+                    this.update(cx, |this, cx| {
+                        this.insert_messages(SumTree::from_item(message, &()), cx);
+                        if this.first_loaded_message_id.is_none() {
+                            this.first_loaded_message_id = Some(id);
+                        }
+                    })?;
+                    Ok(id)
                 }
-            })?;
-            Ok(id)
-        }))
+                Err(error) => {
+                    // Messages that couldn't be sent because we're offline stay
+                    // queued and are sent again when we reconnect.
+                    if rpc.status().borrow().is_connected() {
+                        this.update(cx, |this, cx| {
+                            this.failed_message_ids.insert(pending_id);
+                            this.emit_message_updated(ChannelMessageId::Pending(pending_id), cx);
+                        })?;
+                    }
+                    Err(error)
+                }
+            }
+        })
+    }
+
+    fn emit_message_updated(&mut self, id: ChannelMessageId, cx: &mut Context<Self>) {
+        let mut cursor = self.messages.cursor::<(ChannelMessageId, Count)>(&());
+        cursor.seek(&id, Bias::Left, &());
+        if cursor.item().map_or(false, |message| message.id == id) {
+            let message_ix = cursor.start().1 .0;
+            cx.emit(ChannelChatEvent::UpdateMessage {
+                message_id: id,
+                message_ix,
+            });
+            cx.notify();
+        }
     }
 
     pub fn remove_message(&mut self, id: u64, cx: &mut Context<Self>) -> Task<Result<()>> {
@@ -436,10 +525,15 @@ impl ChannelChat {
         Ok(())
     }
 
+    /// Re-joins the chat after reconnecting, loading any messages that were
+    /// sent while we were away and sending the messages queued in the meantime.
     pub fn rejoin(&mut self, cx: &mut Context<Self>) {
         let user_store = self.user_store.clone();
         let rpc = self.rpc.clone();
         let channel_id = self.channel_id;
+        let last_saved_message_id = self.last_saved_message_id();
+        let first_loaded_message_id = self.first_loaded_message_id;
+        let loaded_all_messages = self.loaded_all_messages;
         cx.spawn(async move |this, cx| {
             async move {
                 let response = rpc
@@ -447,38 +541,62 @@ impl ChannelChat {
                         channel_id: channel_id.0,
                     })
                     .await?;
+                let mut messages = response.messages;
+                let mut done = response.done;
+
+                // More messages may have been sent while we were away than fit in
+                // the first page, so keep paging back until we close the gap.
+                if let Some(last_saved_message_id) = last_saved_message_id {
+                    while !done {
+                        let Some(oldest_message_id) = messages.first().map(|message| message.id)
+                        else {
+                            break;
+                        };
+                        if oldest_message_id <= last_saved_message_id {
+                            break;
+                        }
+                        let response = rpc
+                            .request(proto::GetChannelMessages {
+                                channel_id: channel_id.0,
+                                before_message_id: oldest_message_id,
+                            })
+                            .await?;
+                        done = response.done;
+                        let mut older_messages = response.messages;
+                        older_messages.append(&mut messages);
+                        messages = older_messages;
+                    }
+                }
+
                 Self::handle_loaded_messages(
                     this.clone(),
                     user_store.clone(),
                     rpc.clone(),
-                    response.messages,
-                    response.done,
+                    messages,
+                    done,
                     cx,
                 )
                 .await?;
 
-                let pending_messages = this.update(cx, |this, _| {
-                    this.pending_messages().cloned().collect::<Vec<_>>()
+                let queued_messages = this.update(cx, |this, _| {
+                    if last_saved_message_id.is_some() {
+                        this.loaded_all_messages |= loaded_all_messages;
+                        this.first_loaded_message_id = this
+                            .first_loaded_message_id
+                            .into_iter()
+                            .chain(first_loaded_message_id)
+                            .min();
+                    }
+                    this.pending_messages()
+                        .filter(|message| !this.is_message_failed(message.id))
+                        .cloned()
+                        .collect::<Vec<_>>()
                 })?;
 
-                for pending_message in pending_messages {
-                    let request = rpc.request(proto::SendChannelMessage {
-                        channel_id: channel_id.0,
-                        body: pending_message.body,
-                        mentions: mentions_to_proto(&pending_message.mentions),
-                        nonce: Some(pending_message.nonce.into()),
-                        reply_to_message_id: pending_message.reply_to_message_id,
-                    });
-                    let response = request.await?;
-                    let message = ChannelMessage::from_proto(
-                        response.message.ok_or_else(|| anyhow!("invalid message"))?,
-                        &user_store,
-                        cx,
-                    )
-                    .await?;
-                    this.update(cx, |this, cx| {
-                        this.insert_messages(SumTree::from_item(message, &()), cx);
-                    })?;
+                for message in queued_messages {
+                    this.update(cx, |this, cx| this.send_pending_message(message, cx))?
+                        .await
+                        .log_err();
                 }
 
                 anyhow::Ok(())
@@ -489,6 +607,13 @@ impl ChannelChat {
         .detach();
     }
 
+    fn last_saved_message_id(&self) -> Option<u64> {
+        self.messages
+            .iter()
+            .filter_map(|message| Option::<u64>::from(message.id))
+            .last()
+    }
+
     pub fn message_count(&self) -> usize {
         self.messages.summary().count
     }
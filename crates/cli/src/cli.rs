@@ -13,10 +13,25 @@ pub enum CliRequest {
     Open {
         paths: Vec<String>,
         urls: Vec<String>,
+        /// Pairs of old and new files to open diffs between.
+        diff_paths: Vec<[String; 2]>,
         wait: bool,
         open_new_workspace: Option<bool>,
         env: Option<HashMap<String, String>>,
     },
+    /// Formats the given files and directories without opening them in a window.
+    Format {
+        paths: Vec<String>,
+        /// Report the files that aren't formatted instead of formatting them.
+        check: bool,
+        env: Option<HashMap<String, String>>,
+    },
+    /// Reports the diagnostics for the given files and directories without opening them in a
+    /// window.
+    Check {
+        paths: Vec<String>,
+        env: Option<HashMap<String, String>>,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
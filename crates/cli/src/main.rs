@@ -4,7 +4,7 @@
 )]
 
 use anyhow::{Context as _, Result};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use cli::{ipc::IpcOneShotServer, CliRequest, CliResponse, IpcHandshake};
 use collections::HashMap;
 use parking_lot::Mutex;
@@ -45,7 +45,14 @@ Examples:
     `zed path-to-your-project`
           Open your project in Zed
     `zed -n path-to-file `
-          Open file/folder in a new window",
+          Open file/folder in a new window
+    `zed --wait --diff old-file new-file`
+          Open a diff between two files, and wait for it to be closed
+    `zed format src`
+          Format the files in a folder without opening them
+    `zed check src`
+          Print the diagnostics for the files in a folder",
+    args_conflicts_with_subcommands = true,
     after_help = "To read from stdin, append '-', e.g. 'ps axf | zed -'"
 )]
 struct Args {
@@ -65,6 +72,14 @@ struct Args {
     /// Print Zed's version and the app path.
     #[arg(short, long)]
     version: bool,
+    /// Open a diff between two files, with the changes from the first to the second.
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["OLD_PATH", "NEW_PATH"],
+        conflicts_with = "paths_with_position"
+    )]
+    diff: Vec<String>,
     /// Run zed in the foreground (useful for debugging)
     #[arg(long)]
     foreground: bool,
@@ -85,6 +100,29 @@ struct Args {
     ))]
     #[arg(long)]
     uninstall: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Format files with the formatters configured in Zed, without opening them in a window.
+    Format {
+        /// Print the files that aren't formatted and exit with a non-zero status if there are
+        /// any, instead of formatting them.
+        #[arg(long)]
+        check: bool,
+        /// The files and folders to format.
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    /// Print the diagnostics that language servers report for files, without opening them in a
+    /// window. Exits with a non-zero status if there are any errors.
+    Check {
+        /// The files and folders to check.
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
 }
 
 fn parse_path_with_position(argument_str: &str) -> anyhow::Result<String> {
@@ -244,19 +282,36 @@ fn main() -> Result<()> {
         ))?;
     }
 
+    let request = match args.command {
+        Some(Command::Format { check, paths }) => CliRequest::Format {
+            paths: canonicalize_paths(&paths)?,
+            check,
+            env,
+        },
+        Some(Command::Check { paths }) => CliRequest::Check {
+            paths: canonicalize_paths(&paths)?,
+            env,
+        },
+        None => CliRequest::Open {
+            paths,
+            urls,
+            diff_paths: canonicalize_paths(&args.diff)?
+                .chunks_exact(2)
+                .map(|pair| [pair[0].clone(), pair[1].clone()])
+                .collect(),
+            wait: args.wait,
+            open_new_workspace,
+            env,
+        },
+    };
+
     let sender: JoinHandle<anyhow::Result<()>> = thread::spawn({
         let exit_status = exit_status.clone();
         move || {
             let (_, handshake) = server.accept().context("Handshake after Zed spawn")?;
             let (tx, rx) = (handshake.requests, handshake.responses);
 
-            tx.send(CliRequest::Open {
-                paths,
-                urls,
-                wait: args.wait,
-                open_new_workspace,
-                env,
-            })?;
+            tx.send(request)?;
 
             while let Ok(response) = rx.recv() {
                 match response {
@@ -309,6 +364,16 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+fn canonicalize_paths(paths: &[String]) -> Result<Vec<String>> {
+    paths
+        .iter()
+        .map(|path| {
+            let path = fs::canonicalize(path).with_context(|| format!("resolving {path}"))?;
+            Ok(path.to_string_lossy().to_string())
+        })
+        .collect()
+}
+
 fn pipe_to_tmp(mut src: impl io::Read, mut dest: fs::File) -> Result<()> {
     let mut buffer = [0; 8 * 1024];
     loop {
@@ -1,6 +1,7 @@
 #[cfg(any(test, feature = "test-support"))]
 pub mod test;
 
+mod proxy;
 mod socks;
 pub mod telemetry;
 pub mod user;
@@ -21,16 +22,16 @@ use futures::{
     TryFutureExt as _, TryStreamExt,
 };
 use gpui::{actions, App, AppContext as _, AsyncApp, Entity, Global, Task, WeakEntity};
-use http_client::{AsyncBody, HttpClient, HttpClientWithUrl};
+use http_client::{AsyncBody, HttpClient, HttpClientWithUrl, ProxyConfig};
 use parking_lot::RwLock;
 use postage::watch;
+use proxy::connect_proxy_stream;
 use rand::prelude::*;
 use release_channel::{AppVersion, ReleaseChannel};
 use rpc::proto::{AnyTypedEnvelope, EnvelopedMessage, PeerId, RequestMessage};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources};
-use socks::connect_socks_proxy_stream;
 use std::pin::Pin;
 use std::{
     any::TypeId,
@@ -109,11 +110,23 @@ impl Settings for ClientSettings {
 #[derive(Default, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProxySettingsContent {
     proxy: Option<String>,
+    no_proxy: Option<String>,
+    ca_certificates: Option<Vec<PathBuf>>,
 }
 
 #[derive(Deserialize, Default)]
 pub struct ProxySettings {
     pub proxy: Option<String>,
+    pub no_proxy: Option<String>,
+    pub ca_certificates: Vec<PathBuf>,
+}
+
+impl ProxySettings {
+    /// The proxy that network clients connect through, falling back to the environment
+    /// variables that configure it when it isn't set.
+    pub fn proxy_config(&self) -> ProxyConfig {
+        ProxyConfig::resolve(self.proxy.as_deref(), self.no_proxy.as_deref())
+    }
 }
 
 impl Settings for ProxySettings {
@@ -122,12 +135,22 @@ impl Settings for ProxySettings {
     type FileContent = ProxySettingsContent;
 
     fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
-        Ok(Self {
-            proxy: sources
+        fn setting<T>(
+            sources: &SettingsSources<ProxySettingsContent>,
+            get: impl Fn(&ProxySettingsContent) -> Option<T>,
+        ) -> Option<T> {
+            sources
                 .user
                 .or(sources.server)
-                .and_then(|value| value.proxy.clone())
-                .or(sources.default.proxy.clone()),
+                .and_then(&get)
+                .or_else(|| get(sources.default))
+        }
+
+        Ok(Self {
+            proxy: setting(&sources, |value| value.proxy.clone()),
+            no_proxy: setting(&sources, |value| value.no_proxy.clone()),
+            ca_certificates: setting(&sources, |value| value.ca_certificates.clone())
+                .unwrap_or_default(),
         })
     }
 }
@@ -546,10 +569,10 @@ impl Client {
 
     pub fn production(cx: &mut App) -> Arc<Self> {
         let clock = Arc::new(clock::RealSystemClock);
-        let http = Arc::new(HttpClientWithUrl::new_uri(
+        let http = Arc::new(HttpClientWithUrl::new_proxy_config(
             cx.http_client(),
             &ClientSettings::get_global(cx).server_url,
-            cx.http_client().proxy().cloned(),
+            cx.http_client().proxy_config(),
         ));
         Self::new(clock, http, cx)
     }
@@ -1081,7 +1104,7 @@ impl Client {
             .unwrap_or_default();
 
         let http = self.http.clone();
-        let proxy = http.proxy().cloned();
+        let proxy_config = http.proxy_config();
         let credentials = credentials.clone();
         let rpc_url = self.rpc_url(http, release_channel);
         let system_id = self.telemetry.system_id();
@@ -1105,7 +1128,8 @@ impl Client {
                 .host_str()
                 .zip(rpc_url.port_or_known_default())
                 .ok_or_else(|| anyhow!("missing host in rpc url"))?;
-            let stream = connect_socks_proxy_stream(proxy.as_ref(), rpc_host).await?;
+            let stream =
+                connect_proxy_stream(proxy_config.proxy_for_host(rpc_host.0), rpc_host).await?;
 
             log::info!("connected to rpc endpoint {}", rpc_url);
 
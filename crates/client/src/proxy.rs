@@ -0,0 +1,149 @@
+//! Connections to the collab server through the configured proxy
+use anyhow::{anyhow, Context as _, Result};
+use base64::Engine as _;
+use futures::{
+    io::{AsyncRead, AsyncWrite},
+    AsyncReadExt as _, AsyncWriteExt as _,
+};
+use http_client::Uri;
+
+use crate::socks::connect_socks_proxy_stream;
+
+/// The most bytes that are read of a proxy's response to a `CONNECT` request.
+const MAX_CONNECT_RESPONSE_LEN: usize = 8 * 1024;
+
+pub(crate) async fn connect_proxy_stream(
+    proxy: Option<&Uri>,
+    rpc_host: (&str, u16),
+) -> Result<Box<dyn AsyncReadWrite>> {
+    let Some(proxy) = proxy else {
+        return connect_directly(rpc_host).await;
+    };
+    if let Some(stream) = connect_socks_proxy_stream(proxy, rpc_host).await? {
+        return Ok(stream);
+    }
+    match proxy.scheme_str() {
+        Some("http") => connect_http_proxy_stream(proxy, rpc_host).await,
+        _ => {
+            log::warn!(
+                "proxy {} isn't supported for collaboration, connecting directly",
+                http_client::redact_proxy_uri(proxy)
+            );
+            connect_directly(rpc_host).await
+        }
+    }
+}
+
+async fn connect_directly(rpc_host: (&str, u16)) -> Result<Box<dyn AsyncReadWrite>> {
+    Ok(Box::new(smol::net::TcpStream::connect(rpc_host).await?))
+}
+
+/// Tunnels a connection through an HTTP proxy with a `CONNECT` request.
+async fn connect_http_proxy_stream(
+    proxy: &Uri,
+    rpc_host: (&str, u16),
+) -> Result<Box<dyn AsyncReadWrite>> {
+    let proxy_host = proxy
+        .host()
+        .ok_or_else(|| anyhow!("missing host in proxy url"))?;
+    let proxy_port = proxy.port_u16().unwrap_or(80);
+    let mut stream = smol::net::TcpStream::connect((proxy_host, proxy_port))
+        .await
+        .context("error connecting to http proxy")?;
+    tunnel(&mut stream, http_client::proxy_credentials(proxy), rpc_host).await?;
+    Ok(Box::new(stream))
+}
+
+async fn tunnel(
+    stream: &mut (impl AsyncRead + AsyncWrite + Unpin),
+    credentials: Option<(&str, &str)>,
+    (host, port): (&str, u16),
+) -> Result<()> {
+    let target = if host.contains(':') {
+        format!("[{host}]:{port}")
+    } else {
+        format!("{host}:{port}")
+    };
+    let mut request = format!("CONNECT {target} HTTP/1.1\r\nHost: {target}\r\n");
+    if let Some((username, password)) = credentials {
+        let credentials =
+            base64::engine::general_purpose::STANDARD.encode(format!("{username}:{password}"));
+        request.push_str(&format!("Proxy-Authorization: Basic {credentials}\r\n"));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await?;
+    stream.flush().await?;
+
+    // Read the response a byte at a time, so that none of the tunneled connection is consumed.
+    let mut response = Vec::new();
+    let mut byte = [0];
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() >= MAX_CONNECT_RESPONSE_LEN {
+            return Err(anyhow!("http proxy response is too long"));
+        }
+        if stream.read(&mut byte).await? == 0 {
+            return Err(anyhow!("http proxy closed the connection"));
+        }
+        response.push(byte[0]);
+    }
+
+    let response = String::from_utf8_lossy(&response);
+    let status_line = response.lines().next().unwrap_or_default();
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(|| anyhow!("invalid http proxy response: {status_line}"))?;
+    if !(200..300).contains(&status) {
+        return Err(anyhow!("http proxy refused to connect: {status_line}"));
+    }
+    Ok(())
+}
+
+pub(crate) trait AsyncReadWrite: AsyncRead + AsyncWrite + Unpin + Send + 'static {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send + 'static> AsyncReadWrite for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_proxy_tunnel() {
+        smol::block_on(async {
+            let listener = smol::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let proxy_address = listener.local_addr().unwrap();
+            let proxy = smol::spawn(async move {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut byte = [0];
+                while !request.ends_with(b"\r\n\r\n") {
+                    stream.read_exact(&mut byte).await.unwrap();
+                    request.push(byte[0]);
+                }
+                stream
+                    .write_all(b"HTTP/1.1 200 Connection established\r\n\r\ntunneled")
+                    .await
+                    .unwrap();
+                String::from_utf8(request).unwrap()
+            });
+
+            let mut stream = smol::net::TcpStream::connect(proxy_address).await.unwrap();
+            tunnel(
+                &mut stream,
+                Some(("user", "secret")),
+                ("collab.zed.dev", 443),
+            )
+            .await
+            .unwrap();
+            let mut tunneled = String::new();
+            stream.read_to_string(&mut tunneled).await.unwrap();
+            assert_eq!(tunneled, "tunneled");
+            assert_eq!(
+                proxy.await,
+                "CONNECT collab.zed.dev:443 HTTP/1.1\r\n\
+                 Host: collab.zed.dev:443\r\n\
+                 Proxy-Authorization: Basic dXNlcjpzZWNyZXQ=\r\n\r\n"
+            );
+        });
+    }
+}
@@ -1,41 +1,48 @@
 //! socks proxy
 use anyhow::{anyhow, Result};
-use futures::io::{AsyncRead, AsyncWrite};
 use http_client::Uri;
 use tokio_socks::{
     io::Compat,
     tcp::{Socks4Stream, Socks5Stream},
 };
 
+use crate::proxy::AsyncReadWrite;
+
 pub(crate) async fn connect_socks_proxy_stream(
-    proxy: Option<&Uri>,
+    proxy: &Uri,
     rpc_host: (&str, u16),
-) -> Result<Box<dyn AsyncReadWrite>> {
-    let stream = match parse_socks_proxy(proxy) {
-        Some((socks_proxy, SocksVersion::V4)) => {
-            let stream = Socks4Stream::connect_with_socket(
-                Compat::new(smol::net::TcpStream::connect(socks_proxy).await?),
-                rpc_host,
-            )
-            .await
-            .map_err(|err| anyhow!("error connecting to socks {}", err))?;
-            Box::new(stream) as Box<dyn AsyncReadWrite>
-        }
-        Some((socks_proxy, SocksVersion::V5)) => Box::new(
-            Socks5Stream::connect_with_socket(
-                Compat::new(smol::net::TcpStream::connect(socks_proxy).await?),
-                rpc_host,
-            )
-            .await
-            .map_err(|err| anyhow!("error connecting to socks {}", err))?,
+) -> Result<Option<Box<dyn AsyncReadWrite>>> {
+    let Some((socks_proxy, socks_version)) = parse_socks_proxy(proxy) else {
+        return Ok(None);
+    };
+    let socket = Compat::new(smol::net::TcpStream::connect(socks_proxy).await?);
+    let credentials = http_client::proxy_credentials(proxy);
+    let stream = match (socks_version, credentials) {
+        (SocksVersion::V4, None) => Box::new(
+            Socks4Stream::connect_with_socket(socket, rpc_host)
+                .await
+                .map_err(|err| anyhow!("error connecting to socks {}", err))?,
+        ) as Box<dyn AsyncReadWrite>,
+        (SocksVersion::V4, Some((user_id, _))) => Box::new(
+            Socks4Stream::connect_with_userid_and_socket(socket, rpc_host, user_id)
+                .await
+                .map_err(|err| anyhow!("error connecting to socks {}", err))?,
+        ) as Box<dyn AsyncReadWrite>,
+        (SocksVersion::V5, None) => Box::new(
+            Socks5Stream::connect_with_socket(socket, rpc_host)
+                .await
+                .map_err(|err| anyhow!("error connecting to socks {}", err))?,
+        ) as Box<dyn AsyncReadWrite>,
+        (SocksVersion::V5, Some((username, password))) => Box::new(
+            Socks5Stream::connect_with_password_and_socket(socket, rpc_host, username, password)
+                .await
+                .map_err(|err| anyhow!("error connecting to socks {}", err))?,
         ) as Box<dyn AsyncReadWrite>,
-        None => Box::new(smol::net::TcpStream::connect(rpc_host).await?) as Box<dyn AsyncReadWrite>,
     };
-    Ok(stream)
+    Ok(Some(stream))
 }
 
-fn parse_socks_proxy(proxy: Option<&Uri>) -> Option<((String, u16), SocksVersion)> {
-    let proxy_uri = proxy?;
+fn parse_socks_proxy(proxy_uri: &Uri) -> Option<((String, u16), SocksVersion)> {
     let scheme = proxy_uri.scheme_str()?;
     let socks_version = if scheme.starts_with("socks4") {
         // socks4
@@ -59,6 +66,3 @@ enum SocksVersion {
     V4,
     V5,
 }
-
-pub(crate) trait AsyncReadWrite: AsyncRead + AsyncWrite + Unpin + Send + 'static {}
-impl<T: AsyncRead + AsyncWrite + Unpin + Send + 'static> AsyncReadWrite for T {}
@@ -58,6 +58,8 @@ pub struct Collaborator {
     pub replica_id: ReplicaId,
     pub user_id: UserId,
     pub is_host: bool,
+    /// What the host of the project allows this collaborator to do.
+    pub permissions: proto::ProjectPermissions,
 }
 
 impl PartialOrd for User {
@@ -839,6 +841,7 @@ impl Collaborator {
             replica_id: message.replica_id as ReplicaId,
             user_id: message.user_id as UserId,
             is_host: message.is_host,
+            permissions: message.permissions.unwrap_or_default(),
         })
     }
 }
@@ -0,0 +1,376 @@
+//! The picker that `editor: paste from history` opens, which lists the text that was copied and
+//! cut, previews the selected entry and pastes it into the editor that opened it.
+
+use editor::{
+    clipboard_history::{ClipboardHistory, ClipboardHistoryEntry},
+    Editor,
+};
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{
+    actions, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    SharedString, Subscription, Task, WeakEntity, Window,
+};
+use picker::{Picker, PickerDelegate};
+use settings::Settings;
+use std::sync::Arc;
+use theme::ThemeSettings;
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing, Tooltip};
+use util::ResultExt;
+use workspace::{ModalView, Workspace};
+
+actions!(clipboard_history_ui, [TogglePin]);
+
+/// The number of characters of an entry that are shown in the list.
+const MAX_LABEL_LEN: usize = 100;
+/// The number of lines of the selected entry that are previewed.
+const MAX_PREVIEW_LINES: usize = 12;
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|editor: &mut Editor, _window, cx: &mut Context<Editor>| {
+        let editor_handle = cx.entity().downgrade();
+        editor
+            .register_action(move |_: &editor::actions::PasteFromHistory, window, cx| {
+                let Some(editor) = editor_handle.upgrade() else {
+                    return;
+                };
+                let Some(workspace) = editor.read(cx).workspace() else {
+                    return;
+                };
+                let editor = editor.downgrade();
+                workspace.update(cx, |workspace, cx| {
+                    ClipboardHistoryModal::toggle(workspace, editor, window, cx)
+                });
+            })
+            .detach();
+    })
+    .detach();
+}
+
+pub struct ClipboardHistoryModal {
+    picker: Entity<Picker<ClipboardHistoryDelegate>>,
+    _subscription: Subscription,
+}
+
+impl ModalView for ClipboardHistoryModal {}
+
+impl ClipboardHistoryModal {
+    fn toggle(
+        workspace: &mut Workspace,
+        editor: WeakEntity<Editor>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        workspace.toggle_modal(window, cx, |window, cx| {
+            let delegate = ClipboardHistoryDelegate::new(editor, cx);
+            let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+            let _subscription = cx.subscribe(&picker, |_, _, _, cx| cx.emit(DismissEvent));
+            Self {
+                picker,
+                _subscription,
+            }
+        });
+    }
+}
+
+impl EventEmitter<DismissEvent> for ClipboardHistoryModal {}
+
+impl Focusable for ClipboardHistoryModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for ClipboardHistoryModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("ClipboardHistory")
+            .w(rems(34.))
+            .on_action(cx.listener(|this, _: &TogglePin, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    let ix = picker.delegate.selected_index();
+                    picker.delegate.toggle_pin(ix, window, cx);
+                })
+            }))
+            .child(self.picker.clone())
+    }
+}
+
+pub struct ClipboardHistoryDelegate {
+    editor: WeakEntity<Editor>,
+    entries: Vec<ClipboardHistoryEntry>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    /// The text of the entry to select when the matches are next updated, after it was pinned or
+    /// unpinned and moved.
+    entry_to_select: Option<SharedString>,
+}
+
+impl ClipboardHistoryDelegate {
+    fn new(editor: WeakEntity<Editor>, cx: &App) -> Self {
+        Self {
+            editor,
+            entries: history_entries(cx),
+            matches: Vec::new(),
+            selected_index: 0,
+            entry_to_select: None,
+        }
+    }
+
+    fn selected_entry(&self) -> Option<&ClipboardHistoryEntry> {
+        let hit = self.matches.get(self.selected_index)?;
+        self.entries.get(hit.candidate_id)
+    }
+
+    fn toggle_pin(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(text) = self
+            .matches
+            .get(ix)
+            .and_then(|hit| self.entries.get(hit.candidate_id))
+            .map(|entry| entry.text.clone())
+        else {
+            return;
+        };
+        cx.default_global::<ClipboardHistory>().toggle_pin(&text);
+        self.entry_to_select = Some(text);
+        cx.defer_in(window, |picker, window, cx| picker.refresh(window, cx));
+    }
+}
+
+fn history_entries(cx: &App) -> Vec<ClipboardHistoryEntry> {
+    cx.try_global::<ClipboardHistory>()
+        .map(|history| history.entries().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Returns the text of an entry on a single line, shortened to fit in the list.
+fn entry_label(text: &str) -> String {
+    let mut label = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if let Some((ix, _)) = label.char_indices().nth(MAX_LABEL_LEN) {
+        label.truncate(ix);
+        label.push('…');
+    }
+    label
+}
+
+impl EventEmitter<DismissEvent> for ClipboardHistoryDelegate {}
+
+impl PickerDelegate for ClipboardHistoryDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Paste from clipboard history...".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some(if self.entries.is_empty() {
+            "Copied and cut text appears here".into()
+        } else {
+            "No matches".into()
+        })
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        self.entries = history_entries(cx);
+        let candidates = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(id, entry)| StringMatchCandidate::new(id, &entry_label(&entry.text)))
+            .collect::<Vec<_>>();
+        let background = cx.background_executor().clone();
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            picker
+                .update_in(cx, |picker, window, cx| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.selected_index = 0;
+                    if let Some(text) = delegate.entry_to_select.take() {
+                        // Keep the entry selected where it moved to.
+                        if let Some(ix) = delegate
+                            .matches
+                            .iter()
+                            .position(|hit| delegate.entries[hit.candidate_id].text == text)
+                        {
+                            picker.set_selected_index(ix, true, window, cx);
+                        }
+                    }
+                    cx.notify();
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.selected_entry().cloned() else {
+            return;
+        };
+        // Put the entry on the clipboard too, so that it's pasted again by the next paste, and by
+        // vim's registers that read the clipboard.
+        let item = entry.to_clipboard_item();
+        cx.write_to_clipboard(item.clone());
+        ClipboardHistory::record(&item, cx);
+        self.editor
+            .update(cx, |editor, cx| {
+                editor.do_paste(
+                    &entry.text.to_string(),
+                    entry.clipboard_selections,
+                    true,
+                    window,
+                    cx,
+                )
+            })
+            .log_err();
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, _cx: &mut Context<Picker<Self>>) {}
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let hit = self.matches.get(ix)?;
+        let entry = self.entries.get(hit.candidate_id)?;
+        let is_pinned = entry.pinned;
+        let line_count = entry.text.lines().count();
+
+        let pin_button = IconButton::new(
+            "pin",
+            if is_pinned {
+                IconName::Unpin
+            } else {
+                IconName::Pin
+            },
+        )
+        .icon_size(IconSize::Small)
+        .on_click(cx.listener(move |this, _event, window, cx| {
+            cx.stop_propagation();
+            window.prevent_default();
+
+            this.delegate.toggle_pin(ix, window, cx)
+        }))
+        .tooltip(move |window, cx| {
+            Tooltip::for_action(
+                if is_pinned { "Unpin" } else { "Pin" },
+                &TogglePin,
+                window,
+                cx,
+            )
+        });
+        let end_slot = h_flex()
+            .gap_1()
+            .when(line_count > 1, |this| {
+                this.child(
+                    Label::new(format!("{line_count} lines"))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .child(pin_button)
+            .into_any_element();
+
+        let list_item = ListItem::new(ix)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected)
+            .child(HighlightedLabel::new(hit.string.clone(), hit.positions.clone()).truncate());
+        Some(if selected || is_pinned {
+            list_item.end_slot::<AnyElement>(end_slot)
+        } else {
+            list_item.end_hover_slot::<AnyElement>(end_slot)
+        })
+    }
+
+    fn render_footer(
+        &self,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        let entry = self.selected_entry()?;
+        let theme_settings = ThemeSettings::get_global(cx);
+        let line_count = entry.text.lines().count();
+        let preview = entry
+            .text
+            .lines()
+            .take(MAX_PREVIEW_LINES)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Some(
+            v_flex()
+                .w_full()
+                .p_2()
+                .gap_1()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    div()
+                        .font(theme_settings.buffer_font.clone())
+                        .text_size(theme_settings.buffer_font_size(cx))
+                        .text_color(cx.theme().colors().text)
+                        .overflow_hidden()
+                        .child(preview),
+                )
+                .when(line_count > MAX_PREVIEW_LINES, |this| {
+                    this.child(
+                        Label::new(format!("+{} more lines", line_count - MAX_PREVIEW_LINES))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                })
+                .into_any(),
+        )
+    }
+}
@@ -184,7 +184,10 @@ CREATE TABLE "project_collaborators" (
     "connection_server_id" INTEGER NOT NULL REFERENCES servers (id) ON DELETE CASCADE,
     "user_id" INTEGER NOT NULL,
     "replica_id" INTEGER NOT NULL,
-    "is_host" BOOLEAN NOT NULL
+    "is_host" BOOLEAN NOT NULL,
+    "can_edit" BOOLEAN NOT NULL DEFAULT TRUE,
+    "can_use_terminal" BOOLEAN NOT NULL DEFAULT FALSE,
+    "can_run_tasks" BOOLEAN NOT NULL DEFAULT FALSE
 );
 
 CREATE INDEX "index_project_collaborators_on_project_id" ON "project_collaborators" ("project_id");
@@ -532,3 +535,19 @@ CREATE TABLE IF NOT EXISTS "breakpoints" (
 );
 
 CREATE INDEX "index_breakpoints_on_project_id" ON "breakpoints" ("project_id");
+
+CREATE TABLE IF NOT EXISTS "review_comments" (
+    "id" INTEGER PRIMARY KEY AUTOINCREMENT,
+    "project_owner_id" INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "worktree_root_name" TEXT NOT NULL,
+    "worktree_abs_path" TEXT NOT NULL,
+    "path" TEXT NOT NULL,
+    "row" INTEGER NOT NULL,
+    "line_text" TEXT NOT NULL,
+    "author_id" INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "body" TEXT NOT NULL,
+    "created_at" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "resolved" BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX "index_review_comments_on_project_owner_id_and_worktree_abs_path" ON "review_comments" ("project_owner_id", "worktree_abs_path");
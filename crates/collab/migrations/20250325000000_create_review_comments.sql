@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS "review_comments" (
+    "id" SERIAL PRIMARY KEY,
+    "project_owner_id" INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "worktree_root_name" VARCHAR NOT NULL,
+    "worktree_abs_path" VARCHAR NOT NULL,
+    "path" VARCHAR NOT NULL,
+    "row" INTEGER NOT NULL,
+    "line_text" VARCHAR NOT NULL,
+    "author_id" INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "body" TEXT NOT NULL,
+    "created_at" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    "resolved" BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX "index_review_comments_on_project_owner_id_and_worktree_abs_path" ON "review_comments" ("project_owner_id", "worktree_abs_path");
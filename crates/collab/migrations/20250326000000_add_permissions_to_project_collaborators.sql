@@ -0,0 +1,4 @@
+ALTER TABLE "project_collaborators"
+    ADD COLUMN "can_edit" BOOLEAN NOT NULL DEFAULT TRUE,
+    ADD COLUMN "can_use_terminal" BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN "can_run_tasks" BOOLEAN NOT NULL DEFAULT FALSE;
@@ -479,6 +479,12 @@ pub struct CreatedChannelMessage {
     pub notifications: NotificationBatch,
 }
 
+pub struct CreatedReviewComment {
+    pub comment: review_comment::Model,
+    pub connection_ids: HashSet<ConnectionId>,
+    pub notifications: NotificationBatch,
+}
+
 pub struct UpdatedChannelMessage {
     pub message_id: MessageId,
     pub participant_connection_ids: Vec<ConnectionId>,
@@ -737,6 +743,7 @@ pub struct ProjectCollaborator {
     pub user_id: UserId,
     pub replica_id: ReplicaId,
     pub is_host: bool,
+    pub permissions: proto::ProjectPermissions,
 }
 
 impl ProjectCollaborator {
@@ -746,6 +753,7 @@ impl ProjectCollaborator {
             replica_id: self.replica_id.0 as u32,
             user_id: self.user_id.to_proto(),
             is_host: self.is_host,
+            permissions: Some(self.permissions),
         }
     }
 }
@@ -89,6 +89,7 @@ id_type!(NotificationKindId);
 id_type!(ProjectCollaboratorId);
 id_type!(ProjectId);
 id_type!(ReplicaId);
+id_type!(ReviewCommentId);
 id_type!(RoomId);
 id_type!(RoomParticipantId);
 id_type!(ServerId);
@@ -283,3 +284,22 @@ pub enum Capability {
     /// The buffer is a read-only replica.
     ReadOnly,
 }
+
+/// A permission that the host of a project can grant to its collaborators, in
+/// addition to editing.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ProjectPermission {
+    /// Opening terminals in, or typing into terminals shared from, the host's environment.
+    UseTerminal,
+    /// Running tasks in the host's environment.
+    RunTasks,
+}
+
+impl ProjectPermission {
+    pub fn description(&self) -> &'static str {
+        match self {
+            ProjectPermission::UseTerminal => "use terminals",
+            ProjectPermission::RunTasks => "run tasks",
+        }
+    }
+}
@@ -98,6 +98,9 @@ impl Database {
                 user_id: ActiveValue::set(participant.user_id),
                 replica_id: ActiveValue::set(ReplicaId(replica_id)),
                 is_host: ActiveValue::set(true),
+                can_edit: ActiveValue::set(true),
+                can_use_terminal: ActiveValue::set(true),
+                can_run_tasks: ActiveValue::set(true),
                 ..Default::default()
             }
             .insert(&*tx)
@@ -820,6 +823,9 @@ impl Database {
             user_id: ActiveValue::set(user_id),
             replica_id: ActiveValue::set(replica_id),
             is_host: ActiveValue::set(false),
+            can_edit: ActiveValue::set(true),
+            can_use_terminal: ActiveValue::set(false),
+            can_run_tasks: ActiveValue::set(false),
             ..Default::default()
         }
         .insert(tx)
@@ -1015,6 +1021,7 @@ impl Database {
                     user_id: collaborator.user_id,
                     replica_id: collaborator.replica_id,
                     is_host: collaborator.is_host,
+                    permissions: collaborator.permissions(),
                 })
                 .collect(),
             worktrees,
@@ -1165,6 +1172,13 @@ impl Database {
                 if !role.can_edit_projects() {
                     return Err(anyhow!("not authorized to edit projects"))?;
                 }
+                if !self
+                    .project_permissions(&project, connection_id, tx)
+                    .await?
+                    .can_edit
+                {
+                    return Err(anyhow!("the host has made this project read-only"))?;
+                }
             }
             Capability::ReadOnly => {
                 if !role.can_read_projects() {
@@ -1176,6 +1190,109 @@ impl Database {
         Ok((project, role))
     }
 
+    /// Returns the permissions that the host of the project has granted to the
+    /// given connection. The host itself is allowed to do anything.
+    async fn project_permissions(
+        &self,
+        project: &project::Model,
+        connection_id: ConnectionId,
+        tx: &DatabaseTransaction,
+    ) -> Result<proto::ProjectPermissions> {
+        if project.host_connection().ok() == Some(connection_id) {
+            return Ok(proto::ProjectPermissions {
+                can_edit: true,
+                can_use_terminal: true,
+                can_run_tasks: true,
+            });
+        }
+
+        let collaborator = project_collaborator::Entity::find()
+            .filter(
+                Condition::all()
+                    .add(project_collaborator::Column::ProjectId.eq(project.id))
+                    .add(project_collaborator::Column::ConnectionId.eq(connection_id.id as i32))
+                    .add(
+                        project_collaborator::Column::ConnectionServerId
+                            .eq(connection_id.owner_id as i32),
+                    ),
+            )
+            .one(tx)
+            .await?
+            .ok_or_else(|| anyhow!("not a collaborator on this project"))?;
+        Ok(collaborator.permissions())
+    }
+
+    /// Returns the host connection for a request that needs the given
+    /// permission, such as running a task in the host's environment.
+    pub async fn host_for_project_request_with_permission(
+        &self,
+        project_id: ProjectId,
+        connection_id: ConnectionId,
+        permission: ProjectPermission,
+    ) -> Result<ConnectionId> {
+        self.project_transaction(project_id, |tx| async move {
+            let (project, _) = self
+                .access_project(project_id, connection_id, Capability::ReadOnly, &tx)
+                .await?;
+            let permissions = self
+                .project_permissions(&project, connection_id, &tx)
+                .await?;
+            let allowed = match permission {
+                ProjectPermission::UseTerminal => permissions.can_use_terminal,
+                ProjectPermission::RunTasks => permissions.can_run_tasks,
+            };
+            if !allowed {
+                Err(anyhow!(
+                    "the host hasn't allowed you to {}",
+                    permission.description()
+                ))?;
+            }
+            project.host_connection()
+        })
+        .await
+        .map(|guard| guard.into_inner())
+    }
+
+    /// Changes the permissions of every connection that the given user has
+    /// in the project. Only the host of the project may do this.
+    pub async fn update_project_permissions(
+        &self,
+        project_id: ProjectId,
+        connection_id: ConnectionId,
+        user_id: UserId,
+        permissions: proto::ProjectPermissions,
+    ) -> Result<TransactionGuard<HashSet<ConnectionId>>> {
+        self.project_transaction(project_id, |tx| async move {
+            let project = project::Entity::find_by_id(project_id)
+                .one(&*tx)
+                .await?
+                .ok_or_else(|| anyhow!("no such project"))?;
+            if project.host_connection()? != connection_id {
+                Err(anyhow!("only the host can change project permissions"))?;
+            }
+
+            project_collaborator::Entity::update_many()
+                .filter(
+                    Condition::all()
+                        .add(project_collaborator::Column::ProjectId.eq(project_id))
+                        .add(project_collaborator::Column::UserId.eq(user_id))
+                        .add(project_collaborator::Column::IsHost.eq(false)),
+                )
+                .set(project_collaborator::ActiveModel {
+                    can_edit: ActiveValue::set(permissions.can_edit),
+                    can_use_terminal: ActiveValue::set(permissions.can_use_terminal),
+                    can_run_tasks: ActiveValue::set(permissions.can_run_tasks),
+                    ..Default::default()
+                })
+                .exec(&*tx)
+                .await?;
+
+            self.internal_project_connection_ids(project_id, connection_id, true, &tx)
+                .await
+        })
+        .await
+    }
+
     /// Returns the host connection for a read-only request to join a shared project.
     pub async fn host_for_read_only_project_request(
         &self,
@@ -1260,7 +1377,7 @@ impl Database {
         .await
     }
 
-    async fn internal_project_connection_ids(
+    pub(in crate::db) async fn internal_project_connection_ids(
         &self,
         project_id: ProjectId,
         connection_id: ConnectionId,
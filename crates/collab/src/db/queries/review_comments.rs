@@ -0,0 +1,157 @@
+use super::*;
+use rpc::Notification;
+use time::OffsetDateTime;
+
+impl Database {
+    /// Leaves a comment on a line of a shared project, notifying the project's owner.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_review_comment(
+        &self,
+        project_id: ProjectId,
+        connection_id: ConnectionId,
+        author_id: UserId,
+        worktree_id: u64,
+        path: &str,
+        row: u32,
+        line_text: &str,
+        body: &str,
+    ) -> Result<TransactionGuard<CreatedReviewComment>> {
+        self.project_transaction(project_id, |tx| async move {
+            let (project, _) = self
+                .access_project(project_id, connection_id, Capability::ReadOnly, &tx)
+                .await?;
+            let project_owner_id = project
+                .host_user_id
+                .ok_or_else(|| anyhow!("project has no owner"))?;
+            let worktree = worktree::Entity::find_by_id((worktree_id as i64, project_id))
+                .one(&*tx)
+                .await?
+                .ok_or_else(|| anyhow!("no such worktree"))?;
+
+            let now = OffsetDateTime::now_utc();
+            let comment = review_comment::ActiveModel {
+                project_owner_id: ActiveValue::Set(project_owner_id),
+                worktree_root_name: ActiveValue::Set(worktree.root_name),
+                worktree_abs_path: ActiveValue::Set(worktree.abs_path),
+                path: ActiveValue::Set(path.to_string()),
+                row: ActiveValue::Set(row as i32),
+                line_text: ActiveValue::Set(line_text.to_string()),
+                author_id: ActiveValue::Set(author_id),
+                body: ActiveValue::Set(body.to_string()),
+                created_at: ActiveValue::Set(PrimitiveDateTime::new(now.date(), now.time())),
+                resolved: ActiveValue::Set(false),
+                ..Default::default()
+            }
+            .insert(&*tx)
+            .await?;
+
+            let mut notifications = Vec::new();
+            if project_owner_id != author_id {
+                notifications.extend(
+                    self.create_notification(
+                        project_owner_id,
+                        Notification::ReviewComment {
+                            comment_id: comment.id.to_proto(),
+                            author_id: author_id.to_proto(),
+                            worktree_root_name: comment.worktree_root_name.clone(),
+                            path: comment.path.clone(),
+                        },
+                        false,
+                        &tx,
+                    )
+                    .await?,
+                );
+            }
+
+            let connection_ids = self
+                .internal_project_connection_ids(project_id, connection_id, false, &tx)
+                .await?;
+
+            Ok(CreatedReviewComment {
+                comment,
+                connection_ids,
+                notifications,
+            })
+        })
+        .await
+    }
+
+    /// Returns the unresolved comments on the worktrees of a shared project,
+    /// including those left while the project was shared in earlier calls.
+    pub async fn get_review_comments(
+        &self,
+        project_id: ProjectId,
+        connection_id: ConnectionId,
+    ) -> Result<TransactionGuard<Vec<review_comment::Model>>> {
+        self.project_transaction(project_id, |tx| async move {
+            let (project, _) = self
+                .access_project(project_id, connection_id, Capability::ReadOnly, &tx)
+                .await?;
+            let Some(project_owner_id) = project.host_user_id else {
+                return Ok(Vec::new());
+            };
+            let abs_paths = project
+                .find_related(worktree::Entity)
+                .all(&*tx)
+                .await?
+                .into_iter()
+                .map(|worktree| worktree.abs_path)
+                .collect::<Vec<_>>();
+
+            Ok(review_comment::Entity::find()
+                .filter(
+                    review_comment::Column::ProjectOwnerId
+                        .eq(project_owner_id)
+                        .and(review_comment::Column::WorktreeAbsPath.is_in(abs_paths))
+                        .and(review_comment::Column::Resolved.eq(false)),
+                )
+                .order_by_asc(review_comment::Column::Id)
+                .all(&*tx)
+                .await?)
+        })
+        .await
+    }
+
+    /// Marks a comment on a shared project as resolved or unresolved.
+    pub async fn resolve_review_comment(
+        &self,
+        project_id: ProjectId,
+        connection_id: ConnectionId,
+        comment_id: ReviewCommentId,
+        resolved: bool,
+    ) -> Result<TransactionGuard<(review_comment::Model, HashSet<ConnectionId>)>> {
+        self.project_transaction(project_id, |tx| async move {
+            let (project, _) = self
+                .access_project(project_id, connection_id, Capability::ReadOnly, &tx)
+                .await?;
+            let comment = review_comment::Entity::find_by_id(comment_id)
+                .one(&*tx)
+                .await?
+                .ok_or_else(|| anyhow!("no such review comment"))?;
+            let is_project_comment = Some(comment.project_owner_id) == project.host_user_id
+                && project
+                    .find_related(worktree::Entity)
+                    .filter(worktree::Column::AbsPath.eq(comment.worktree_abs_path.as_str()))
+                    .one(&*tx)
+                    .await?
+                    .is_some();
+            if !is_project_comment {
+                Err(anyhow!("review comment does not belong to this project"))?;
+            }
+
+            let comment = review_comment::ActiveModel {
+                id: ActiveValue::Unchanged(comment.id),
+                resolved: ActiveValue::Set(resolved),
+                ..Default::default()
+            }
+            .update(&*tx)
+            .await?;
+
+            let connection_ids = self
+                .internal_project_connection_ids(project_id, connection_id, false, &tx)
+                .await?;
+            Ok((comment, connection_ids))
+        })
+        .await
+    }
+}
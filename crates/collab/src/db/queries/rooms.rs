@@ -553,6 +553,7 @@ impl Database {
                             user_id: collaborator.user_id,
                             replica_id: collaborator.replica_id,
                             is_host: collaborator.is_host,
+                            permissions: collaborator.permissions(),
                         })
                         .collect(),
                     worktrees: reshared_project.worktrees.clone(),
@@ -848,6 +849,7 @@ impl Database {
                 user_id: collaborator.user_id,
                 replica_id: collaborator.replica_id,
                 is_host: collaborator.is_host,
+                permissions: collaborator.permissions(),
             })
             .collect::<Vec<_>>();
 
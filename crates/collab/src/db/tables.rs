@@ -29,6 +29,7 @@ pub mod project_collaborator;
 pub mod project_repository;
 pub mod project_repository_statuses;
 pub mod rate_buckets;
+pub mod review_comment;
 pub mod room;
 pub mod room_participant;
 pub mod server;
@@ -1,5 +1,5 @@
 use crate::db::{ProjectCollaboratorId, ProjectId, ReplicaId, ServerId, UserId};
-use rpc::ConnectionId;
+use rpc::{proto, ConnectionId};
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
@@ -13,6 +13,11 @@ pub struct Model {
     pub user_id: UserId,
     pub replica_id: ReplicaId,
     pub is_host: bool,
+    /// Whether the host allows this collaborator to edit the project. This is
+    /// in addition to the collaborator's role in the room.
+    pub can_edit: bool,
+    pub can_use_terminal: bool,
+    pub can_run_tasks: bool,
 }
 
 impl Model {
@@ -22,6 +27,14 @@ impl Model {
             id: self.connection_id as u32,
         }
     }
+
+    pub fn permissions(&self) -> proto::ProjectPermissions {
+        proto::ProjectPermissions {
+            can_edit: self.can_edit,
+            can_use_terminal: self.can_use_terminal,
+            can_run_tasks: self.can_run_tasks,
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
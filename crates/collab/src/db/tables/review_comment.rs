@@ -0,0 +1,60 @@
+use crate::db::{ReviewCommentId, UserId};
+use rpc::proto;
+use sea_orm::entity::prelude::*;
+use time::PrimitiveDateTime;
+
+/// A comment left on a line of a shared project. Comments are keyed by the
+/// project's owner and the absolute path of the worktree on the owner's machine
+/// rather than by the project id, so they outlive the call in which the project
+/// was shared, without mixing up different worktrees that share a root name.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "review_comments")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: ReviewCommentId,
+    pub project_owner_id: UserId,
+    pub worktree_root_name: String,
+    pub worktree_abs_path: String,
+    pub path: String,
+    pub row: i32,
+    pub line_text: String,
+    pub author_id: UserId,
+    pub body: String,
+    pub created_at: PrimitiveDateTime,
+    pub resolved: bool,
+}
+
+impl Model {
+    pub fn to_proto(&self) -> proto::ReviewComment {
+        proto::ReviewComment {
+            id: self.id.to_proto(),
+            author_id: self.author_id.to_proto(),
+            worktree_root_name: self.worktree_root_name.clone(),
+            path: self.path.clone(),
+            row: self.row as u32,
+            line_text: self.line_text.clone(),
+            body: self.body.clone(),
+            created_at: self.created_at.assume_utc().unix_timestamp() as u64,
+            resolved: self.resolved,
+            worktree_abs_path: self.worktree_abs_path.clone(),
+        }
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::AuthorId",
+        to = "super::user::Column::Id"
+    )]
+    Author,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Author.def()
+    }
+}
@@ -6,9 +6,10 @@ use crate::{
     auth,
     db::{
         self, BufferId, Capability, Channel, ChannelId, ChannelRole, ChannelsForUser,
-        CreatedChannelMessage, Database, InviteMemberResult, MembershipUpdated, MessageId,
-        NotificationId, Project, ProjectId, RejoinedProject, RemoveChannelMemberResult, ReplicaId,
-        RespondToChannelInvite, RoomId, ServerId, UpdatedChannelMessage, User, UserId,
+        CreatedChannelMessage, CreatedReviewComment, Database, InviteMemberResult,
+        MembershipUpdated, MessageId, NotificationId, Project, ProjectId, ProjectPermission,
+        RejoinedProject, RemoveChannelMemberResult, ReplicaId, RespondToChannelInvite,
+        ReviewCommentId, RoomId, ServerId, UpdatedChannelMessage, User, UserId,
     },
     executor::Executor,
     AppState, Config, Error, RateLimit, Result,
@@ -348,7 +349,16 @@ impl Server {
             .add_request_handler(forward_mutating_project_request::<proto::BlameBuffer>)
             .add_request_handler(forward_mutating_project_request::<proto::MultiLspQuery>)
             .add_request_handler(forward_mutating_project_request::<proto::RestartLanguageServers>)
+            .add_request_handler(forward_mutating_project_request::<proto::StopLanguageServers>)
             .add_request_handler(forward_mutating_project_request::<proto::LinkedEditingRange>)
+            .add_request_handler(add_review_comment)
+            .add_request_handler(get_review_comments)
+            .add_request_handler(resolve_review_comment)
+            .add_request_handler(update_project_permissions)
+            .add_request_handler(task_context_for_location)
+            .add_message_handler(broadcast_project_message_from_host::<proto::UpdateSharedTerminal>)
+            .add_message_handler(broadcast_project_message_from_host::<proto::UnshareTerminal>)
+            .add_request_handler(shared_terminal_input)
             .add_message_handler(create_buffer_for_peer)
             .add_request_handler(update_buffer)
             .add_message_handler(broadcast_project_message_from_host::<proto::RefreshInlayHints>)
@@ -1846,6 +1856,11 @@ fn join_project_internal(
         .collect::<Vec<_>>();
     let project_id = project.id;
     let guest_user_id = session.user_id();
+    let permissions = project
+        .collaborators
+        .iter()
+        .find(|collaborator| collaborator.connection_id == session.connection_id)
+        .map(|collaborator| collaborator.permissions);
 
     let worktrees = project
         .worktrees
@@ -1865,6 +1880,7 @@ fn join_project_internal(
             replica_id: replica_id.0 as u32,
             user_id: guest_user_id.to_proto(),
             is_host: false,
+            permissions,
         }),
     };
 
@@ -1886,6 +1902,7 @@ fn join_project_internal(
         collaborators: collaborators.clone(),
         language_servers: project.language_servers.clone(),
         role: project.role.into(),
+        permissions,
     })?;
 
     for (worktree_id, worktree) in mem::take(&mut project.worktrees) {
@@ -2078,6 +2095,122 @@ async fn remove_repository(
     Ok(())
 }
 
+/// Leaves a comment on a line of a shared project.
+async fn add_review_comment(
+    request: proto::AddReviewComment,
+    response: Response<proto::AddReviewComment>,
+    session: Session,
+) -> Result<()> {
+    let body = request.body.trim();
+    if body.len() > MAX_MESSAGE_LEN {
+        return Err(anyhow!("comment is too long"))?;
+    }
+    if body.is_empty() {
+        return Err(anyhow!("comment can't be blank"))?;
+    }
+
+    let project_id = ProjectId::from_proto(request.project_id);
+    let CreatedReviewComment {
+        comment,
+        connection_ids,
+        notifications,
+    } = session
+        .db()
+        .await
+        .create_review_comment(
+            project_id,
+            session.connection_id,
+            session.user_id(),
+            request.worktree_id,
+            &request.path,
+            request.row,
+            &request.line_text,
+            body,
+        )
+        .await?
+        .into_inner();
+
+    let comment = comment.to_proto();
+    broadcast(
+        Some(session.connection_id),
+        connection_ids.iter().copied(),
+        |connection_id| {
+            session.peer.send(
+                connection_id,
+                proto::UpdateReviewComment {
+                    project_id: project_id.to_proto(),
+                    comment: Some(comment.clone()),
+                },
+            )
+        },
+    );
+    response.send(proto::AddReviewCommentResponse {
+        comment: Some(comment),
+    })?;
+
+    let pool = &*session.connection_pool().await;
+    send_notifications(pool, &session.peer, notifications);
+
+    Ok(())
+}
+
+/// Returns the unresolved review comments on a shared project.
+async fn get_review_comments(
+    request: proto::GetReviewComments,
+    response: Response<proto::GetReviewComments>,
+    session: Session,
+) -> Result<()> {
+    let comments = session
+        .db()
+        .await
+        .get_review_comments(
+            ProjectId::from_proto(request.project_id),
+            session.connection_id,
+        )
+        .await?;
+    response.send(proto::GetReviewCommentsResponse {
+        comments: comments.iter().map(|comment| comment.to_proto()).collect(),
+    })?;
+    Ok(())
+}
+
+/// Marks a review comment on a shared project as resolved or unresolved.
+async fn resolve_review_comment(
+    request: proto::ResolveReviewComment,
+    response: Response<proto::ResolveReviewComment>,
+    session: Session,
+) -> Result<()> {
+    let project_id = ProjectId::from_proto(request.project_id);
+    let (comment, connection_ids) = session
+        .db()
+        .await
+        .resolve_review_comment(
+            project_id,
+            session.connection_id,
+            ReviewCommentId::from_proto(request.comment_id),
+            request.resolved,
+        )
+        .await?
+        .into_inner();
+
+    let comment = comment.to_proto();
+    broadcast(
+        Some(session.connection_id),
+        connection_ids.iter().copied(),
+        |connection_id| {
+            session.peer.send(
+                connection_id,
+                proto::UpdateReviewComment {
+                    project_id: project_id.to_proto(),
+                    comment: Some(comment.clone()),
+                },
+            )
+        },
+    );
+    response.send(proto::Ack {})?;
+    Ok(())
+}
+
 /// Updates other participants with changes to the diagnostics
 async fn update_diagnostic_summary(
     message: proto::UpdateDiagnosticSummary,
@@ -2240,6 +2373,90 @@ where
     Ok(())
 }
 
+/// Resolves a task's context in the host's environment, which guests may only
+/// do if the host has allowed them to run tasks.
+async fn task_context_for_location(
+    request: proto::TaskContextForLocation,
+    response: Response<proto::TaskContextForLocation>,
+    session: Session,
+) -> Result<()> {
+    let project_id = ProjectId::from_proto(request.project_id);
+    let host_connection_id = session
+        .db()
+        .await
+        .host_for_project_request_with_permission(
+            project_id,
+            session.connection_id,
+            ProjectPermission::RunTasks,
+        )
+        .await?;
+    let payload = session
+        .peer
+        .forward_request(session.connection_id, host_connection_id, request)
+        .await?;
+    response.send(payload)?;
+    Ok(())
+}
+
+/// Forwards a guest's keystrokes to a terminal that the host is sharing. The
+/// host decides whether the guest currently has input control.
+async fn shared_terminal_input(
+    request: proto::SharedTerminalInput,
+    response: Response<proto::SharedTerminalInput>,
+    session: Session,
+) -> Result<()> {
+    let project_id = ProjectId::from_proto(request.project_id);
+    let host_connection_id = session
+        .db()
+        .await
+        .host_for_project_request_with_permission(
+            project_id,
+            session.connection_id,
+            ProjectPermission::UseTerminal,
+        )
+        .await?;
+    let payload = session
+        .peer
+        .forward_request(session.connection_id, host_connection_id, request)
+        .await?;
+    response.send(payload)?;
+    Ok(())
+}
+
+/// Changes what a collaborator is allowed to do in the host's project.
+async fn update_project_permissions(
+    request: proto::UpdateProjectPermissions,
+    response: Response<proto::UpdateProjectPermissions>,
+    session: Session,
+) -> Result<()> {
+    let project_id = ProjectId::from_proto(request.project_id);
+    let permissions = request
+        .permissions
+        .ok_or_else(|| anyhow!("missing permissions"))?;
+    let guest_connection_ids = session
+        .db()
+        .await
+        .update_project_permissions(
+            project_id,
+            session.connection_id,
+            UserId::from_proto(request.user_id),
+            permissions,
+        )
+        .await?;
+
+    broadcast(
+        Some(session.connection_id),
+        guest_connection_ids.iter().copied(),
+        |connection_id| {
+            session
+                .peer
+                .forward_send(session.connection_id, connection_id, request.clone())
+        },
+    );
+    response.send(proto::Ack {})?;
+    Ok(())
+}
+
 /// Notify other participants that a new buffer has been created
 async fn create_buffer_for_peer(
     request: proto::CreateBufferForPeer,
@@ -185,6 +185,82 @@ async fn test_rejoin_channel_chat(
     assert_messages(&channel_chat_b, expected_messages, cx_b);
 }
 
+#[gpui::test]
+async fn test_failed_channel_messages(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    let channel_chat_a = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    // The server rejects messages that are too long, so they're marked as failed
+    // rather than being dropped.
+    channel_chat_a
+        .update(cx_a, |c, cx| {
+            c.send_message("x".repeat(2048).as_str().into(), cx)
+                .unwrap()
+        })
+        .await
+        .unwrap_err();
+    let failed_id = channel_chat_a.read_with(cx_a, |chat, _| {
+        let message = chat.pending_messages().next().unwrap();
+        assert!(chat.is_message_failed(message.id));
+        message.id
+    });
+
+    // Messages sent while offline are queued instead of failing.
+    server.forbid_connections();
+    server.disconnect_client(client_a.peer_id().unwrap());
+    channel_chat_a
+        .update(cx_a, |c, cx| c.send_message("one".into(), cx).unwrap())
+        .await
+        .unwrap_err();
+    channel_chat_a.read_with(cx_a, |chat, _| {
+        let queued = chat.pending_messages().last().unwrap();
+        assert_eq!(queued.body, "one");
+        assert!(!chat.is_message_failed(queued.id));
+    });
+
+    // On reconnect, queued messages are sent but failed ones are left alone.
+    server.allow_connections();
+    executor.advance_clock(RECONNECT_TIMEOUT);
+    executor.run_until_parked();
+    channel_chat_a.read_with(cx_a, |chat, _| {
+        assert_eq!(chat.pending_messages().count(), 1);
+        assert!(chat.is_message_failed(failed_id));
+    });
+    assert_messages(&channel_chat_b, &["one"], cx_b);
+
+    // Discarding a failed message removes it.
+    let ChannelMessageId::Pending(failed_id) = failed_id else {
+        panic!("expected a pending message");
+    };
+    channel_chat_a.update(cx_a, |chat, cx| chat.discard_message(failed_id, cx));
+    assert_messages(&channel_chat_a, &["one"], cx_a);
+}
+
 #[gpui::test]
 async fn test_remove_channel_message(
     executor: BackgroundExecutor,
@@ -9,7 +9,7 @@ use editor::{
         ToggleCodeActions, Undo,
     },
     test::editor_test_context::{AssertionContextManager, EditorTestContext},
-    Editor, RowInfo,
+    Editor, EditorSettings, RowInfo,
 };
 use fs::Fs;
 use futures::StreamExt;
@@ -761,6 +761,14 @@ async fn test_collaborating_with_renames(cx_a: &mut TestAppContext, cx_b: &mut T
     let active_call_a = cx_a.read(ActiveCall::global);
 
     cx_b.update(editor::init);
+    // Apply renames across files right away, rather than in a preview.
+    cx_b.update(|cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings::<EditorSettings>(cx, |settings| {
+                settings.rename_preview = Some(false);
+            });
+        });
+    });
 
     // Set up a fake language server.
     client_a.language_registry().add(rust_lang());
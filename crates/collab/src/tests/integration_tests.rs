@@ -32,6 +32,7 @@ use pretty_assertions::assert_eq;
 use project::{
     lsp_store::{FormatTrigger, LspFormatTarget},
     search::{SearchQuery, SearchResult},
+    terminals::TerminalKind,
     DiagnosticSummary, HoverBlockKind, Project, ProjectPath,
 };
 use prompt_store::PromptBuilder;
@@ -1438,6 +1439,252 @@ async fn test_unshare_project(
     });
 }
 
+#[gpui::test]
+async fn test_project_permissions(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree("/a", json!({ "a.txt": "a-contents" }))
+        .await;
+    let (project_a, worktree_id) = client_a.build_local_project("/a", cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+    executor.run_until_parked();
+
+    // Guests can edit, but can't use terminals or run tasks, by default.
+    project_b.read_with(cx_b, |project, cx| {
+        assert!(!project.is_read_only(cx));
+        assert_eq!(
+            project.permissions(),
+            rpc::proto::ProjectPermissions {
+                can_edit: true,
+                can_use_terminal: false,
+                can_run_tasks: false,
+            }
+        );
+    });
+
+    // The host makes the project read-only for client B.
+    let user_b = client_b.user_id().unwrap();
+    project_a
+        .update(cx_a, |project, cx| {
+            project.set_collaborator_permissions(
+                user_b,
+                rpc::proto::ProjectPermissions {
+                    can_edit: false,
+                    can_use_terminal: false,
+                    can_run_tasks: true,
+                },
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    project_a.read_with(cx_a, |project, _| {
+        let collaborator = project
+            .collaborators()
+            .values()
+            .find(|collaborator| collaborator.user_id == user_b)
+            .unwrap();
+        assert!(!collaborator.permissions.can_edit);
+        assert!(collaborator.permissions.can_run_tasks);
+    });
+    project_b.read_with(cx_b, |project, cx| {
+        assert!(project.is_read_only(cx));
+        assert!(project.permissions().can_run_tasks);
+    });
+
+    // The server rejects edits from client B.
+    let buffer_b = project_b
+        .update(cx_b, |project, cx| {
+            project.open_buffer((worktree_id, "a.txt"), cx)
+        })
+        .await
+        .unwrap();
+    assert!(buffer_b.read_with(cx_b, |buffer, _| buffer.read_only()));
+    assert!(project_b
+        .update(cx_b, |project, cx| {
+            project.create_entry((worktree_id, "b.txt"), false, cx)
+        })
+        .await
+        .is_err());
+
+    // Only the host can change permissions.
+    assert!(project_b
+        .update(cx_b, |project, cx| {
+            project.set_collaborator_permissions(
+                user_b,
+                rpc::proto::ProjectPermissions {
+                    can_edit: true,
+                    can_use_terminal: true,
+                    can_run_tasks: true,
+                },
+                cx,
+            )
+        })
+        .await
+        .is_err());
+}
+
+#[gpui::test]
+async fn test_shared_terminal_input_control(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+    cx_c: &mut TestAppContext,
+) {
+    // The host's terminal runs a real shell.
+    executor.allow_parking();
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let client_c = server.create_client(cx_c, "user_c").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b), (&client_c, cx_c)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+    cx_a.update(terminal::terminal_settings::TerminalSettings::register);
+
+    client_a
+        .fs()
+        .insert_tree("/a", json!({ "a.txt": "a-contents" }))
+        .await;
+    let (project_a, _) = client_a.build_local_project("/a", cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+    let project_c = client_c.join_remote_project(project_id, cx_c).await;
+    executor.run_until_parked();
+
+    for client in [&client_b, &client_c] {
+        let user_id = client.user_id().unwrap();
+        project_a
+            .update(cx_a, |project, cx| {
+                project.set_collaborator_permissions(
+                    user_id,
+                    rpc::proto::ProjectPermissions {
+                        can_edit: true,
+                        can_use_terminal: true,
+                        can_run_tasks: false,
+                    },
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+    }
+
+    let terminal_a = project_a
+        .update(cx_a, |project, cx| {
+            project.create_terminal_with_venv(
+                TerminalKind::Shell(Some(env::temp_dir())),
+                None,
+                None,
+                cx,
+            )
+        })
+        .unwrap();
+    project_a
+        .update(cx_a, |project, cx| project.share_terminal(&terminal_a, cx))
+        .unwrap();
+    executor.run_until_parked();
+
+    let terminal_id = terminal_a.entity_id().as_u64();
+    let terminal_b = project_b.read_with(cx_b, |project, _| {
+        project.remote_terminals().next().unwrap().clone()
+    });
+    let terminal_c = project_c.read_with(cx_c, |project, _| {
+        project.remote_terminals().next().unwrap().clone()
+    });
+    let send_input = |client: &TestClient| {
+        client.client().request(rpc::proto::SharedTerminalInput {
+            project_id,
+            terminal_id,
+            input: b"ls\n".to_vec(),
+        })
+    };
+
+    // Guests can't type into the terminal until the host hands them control,
+    // even when they bypass their own check and message the host directly.
+    terminal_b.read_with(cx_b, |terminal, _| {
+        assert_eq!(terminal.id(), terminal_id);
+        assert_eq!(terminal.input_controller(), None);
+        assert!(!terminal.has_input_control());
+    });
+    assert!(terminal_b
+        .read_with(cx_b, |terminal, cx| terminal.input(b"ls\n".to_vec(), cx))
+        .await
+        .is_err());
+    assert!(send_input(&client_b).await.is_err());
+
+    // The host hands control to client B.
+    let peer_id_b = client_b.peer_id().unwrap();
+    let peer_id_c = client_c.peer_id().unwrap();
+    project_a.update(cx_a, |project, cx| {
+        project.set_shared_terminal_input_controller(&terminal_a, Some(peer_id_b), cx)
+    });
+    executor.run_until_parked();
+
+    assert!(terminal_b.read_with(cx_b, |terminal, _| terminal.has_input_control()));
+    terminal_c.read_with(cx_c, |terminal, _| {
+        assert_eq!(terminal.input_controller(), Some(peer_id_b));
+        assert!(!terminal.has_input_control());
+    });
+    terminal_b
+        .read_with(cx_b, |terminal, cx| terminal.input(b"ls\n".to_vec(), cx))
+        .await
+        .unwrap();
+    assert!(send_input(&client_c).await.is_err());
+
+    // Handing control to client C revokes it from client B.
+    project_a.update(cx_a, |project, cx| {
+        project.set_shared_terminal_input_controller(&terminal_a, Some(peer_id_c), cx)
+    });
+    executor.run_until_parked();
+
+    terminal_b.read_with(cx_b, |terminal, _| {
+        assert_eq!(terminal.input_controller(), Some(peer_id_c));
+        assert!(!terminal.has_input_control());
+    });
+    assert!(terminal_c.read_with(cx_c, |terminal, _| terminal.has_input_control()));
+    assert!(terminal_b
+        .read_with(cx_b, |terminal, cx| terminal.input(b"ls\n".to_vec(), cx))
+        .await
+        .is_err());
+    assert!(send_input(&client_b).await.is_err());
+    terminal_c
+        .read_with(cx_c, |terminal, cx| terminal.input(b"ls\n".to_vec(), cx))
+        .await
+        .unwrap();
+
+    // Taking control back leaves no guest able to type.
+    project_a.update(cx_a, |project, cx| {
+        project.set_shared_terminal_input_controller(&terminal_a, None, cx)
+    });
+    executor.run_until_parked();
+
+    assert!(!terminal_c.read_with(cx_c, |terminal, _| terminal.has_input_control()));
+    assert!(send_input(&client_c).await.is_err());
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_project_reconnect(
     executor: BackgroundExecutor,
@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
+use call::ActiveCall;
 use gpui::{BackgroundExecutor, TestAppContext};
 use notifications::NotificationEvent;
 use parking_lot::Mutex;
 use rpc::{proto, Notification};
+use serde_json::json;
 
 use crate::tests::TestServer;
 
@@ -158,3 +160,119 @@ async fn test_notifications(
         assert_eq!(entry.response, Some(true));
     });
 }
+
+#[gpui::test]
+async fn test_review_comment_notifications(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree("/a", json!({ "a.txt": "one\ntwo\nthree" }))
+        .await;
+    let (project_a, worktree_id) = client_a.build_local_project("/a", cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    client_b.join_remote_project(project_id, cx_b).await;
+
+    // Client B leaves a comment on the shared project.
+    let comment = client_b
+        .client()
+        .request(proto::AddReviewComment {
+            project_id,
+            worktree_id: worktree_id.to_proto(),
+            path: "a.txt".into(),
+            row: 1,
+            line_text: "two".into(),
+            body: "Should this be 2?".into(),
+        })
+        .await
+        .unwrap()
+        .comment
+        .unwrap();
+    assert_eq!(comment.worktree_root_name, "a");
+    assert_eq!(comment.worktree_abs_path, "/a");
+    assert!(!comment.resolved);
+
+    // Client A, who owns the project, is notified about the comment.
+    executor.run_until_parked();
+    client_a.notification_store().read_with(cx_a, |store, _| {
+        assert_eq!(store.notification_count(), 1);
+        assert_eq!(
+            store.notification_at(0).unwrap().notification,
+            Notification::ReviewComment {
+                comment_id: comment.id,
+                author_id: client_b.id(),
+                worktree_root_name: "a".into(),
+                path: "a.txt".into(),
+            }
+        );
+    });
+
+    // The comment persists after the project is unshared and shared again.
+    project_a
+        .update(cx_a, |project, cx| project.unshare(cx))
+        .unwrap();
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let comments = client_a
+        .client()
+        .request(proto::GetReviewComments { project_id })
+        .await
+        .unwrap()
+        .comments;
+    assert_eq!(comments, vec![comment.clone()]);
+
+    // They aren't listed on another worktree with the same root name.
+    client_a
+        .fs()
+        .insert_tree("/other/a", json!({ "a.txt": "one\ntwo\nthree" }))
+        .await;
+    let (other_project, _) = client_a.build_local_project("/other/a", cx_a).await;
+    let other_project_id = active_call_a
+        .update(cx_a, |call, cx| {
+            call.share_project(other_project.clone(), cx)
+        })
+        .await
+        .unwrap();
+    let comments = client_a
+        .client()
+        .request(proto::GetReviewComments {
+            project_id: other_project_id,
+        })
+        .await
+        .unwrap()
+        .comments;
+    assert!(comments.is_empty());
+
+    // Resolved comments are no longer listed.
+    client_a
+        .client()
+        .request(proto::ResolveReviewComment {
+            project_id,
+            comment_id: comment.id,
+            resolved: true,
+        })
+        .await
+        .unwrap();
+    let comments = client_a
+        .client()
+        .request(proto::GetReviewComments { project_id })
+        .await
+        .unwrap()
+        .comments;
+    assert!(comments.is_empty());
+}
@@ -14,6 +14,7 @@ use gpui::{
     actions, AnyView, App, ClipboardItem, Context, Entity, EventEmitter, Focusable, Pixels, Point,
     Render, Subscription, Task, VisualContext as _, WeakEntity, Window,
 };
+use markdown_preview::markdown_preview_view::{MarkdownPreviewMode, MarkdownPreviewView};
 use project::Project;
 use rpc::proto::ChannelVisibility;
 use std::{
@@ -30,7 +31,7 @@ use workspace::{
     ItemNavHistory, Pane, SaveIntent, Toast, ViewId, Workspace, WorkspaceId,
 };
 
-actions!(collab, [CopyLink]);
+actions!(collab, [CopyLink, ToggleNotesPreview]);
 
 pub fn init(cx: &mut App) {
     workspace::FollowableViewRegistry::register::<ChannelView>(cx)
@@ -43,6 +44,8 @@ pub struct ChannelView {
     channel_store: Entity<ChannelStore>,
     channel_buffer: Entity<ChannelBuffer>,
     remote_id: Option<ViewId>,
+    /// A rendered preview of the notes, shown beside the editor.
+    preview: Option<Entity<MarkdownPreviewView>>,
     _editor_event_subscription: Subscription,
     _reparse_subscription: Option<Subscription>,
 }
@@ -213,9 +216,18 @@ impl ChannelView {
             editor.set_custom_context_menu(move |_, position, window, cx| {
                 let this = this.clone();
                 Some(ui::ContextMenu::build(window, cx, move |menu, _, _| {
-                    menu.entry("Copy link to section", None, move |window, cx| {
+                    menu.entry("Copy link to section", None, {
+                        let this = this.clone();
+                        move |window, cx| {
+                            this.update(cx, |this, cx| {
+                                this.copy_link_for_position(position, window, cx)
+                            })
+                            .ok();
+                        }
+                    })
+                    .entry("Toggle Preview", None, move |window, cx| {
                         this.update(cx, |this, cx| {
-                            this.copy_link_for_position(position, window, cx)
+                            this.toggle_preview(&ToggleNotesPreview, window, cx)
                         })
                         .ok();
                     })
@@ -236,6 +248,7 @@ impl ChannelView {
             channel_store,
             channel_buffer,
             remote_id: None,
+            preview: None,
             _editor_event_subscription,
             _reparse_subscription: None,
         }
@@ -338,6 +351,30 @@ impl ChannelView {
             .ok();
     }
 
+    fn toggle_preview(
+        &mut self,
+        _: &ToggleNotesPreview,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.preview.take().is_none() {
+            let language_registry = self.project.read(cx).languages().clone();
+            let fallback_description = self
+                .channel(cx)
+                .map(|channel| format!("Preview {}", channel.name).into());
+            self.preview = Some(MarkdownPreviewView::new(
+                MarkdownPreviewMode::Default,
+                self.editor.clone(),
+                self.workspace.clone(),
+                language_registry,
+                fallback_description,
+                window,
+                cx,
+            ));
+        }
+        cx.notify();
+    }
+
     pub fn channel(&self, cx: &App) -> Option<Arc<Channel>> {
         self.channel_buffer.read(cx).channel(cx)
     }
@@ -399,10 +436,21 @@ impl EventEmitter<EditorEvent> for ChannelView {}
 
 impl Render for ChannelView {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        div()
+        h_flex()
             .size_full()
             .on_action(cx.listener(Self::copy_link))
-            .child(self.editor.clone())
+            .on_action(cx.listener(Self::toggle_preview))
+            .child(div().h_full().flex_1().child(self.editor.clone()))
+            .when_some(self.preview.clone(), |this, preview| {
+                this.child(
+                    div()
+                        .h_full()
+                        .flex_1()
+                        .border_l_1()
+                        .border_color(cx.theme().colors().border)
+                        .child(preview),
+                )
+            })
     }
 }
 
@@ -424,7 +424,7 @@ impl ChatPanel {
                 (this_message, is_continuation_from_previous, is_admin)
             });
 
-        let _is_pending = message.is_pending();
+        let is_failed = active_chat.read(cx).is_message_failed(message.id);
 
         let belongs_to_user = Some(message.sender.id) == self.client.user_id();
         let can_delete_message = belongs_to_user || is_admin;
@@ -444,6 +444,10 @@ impl ChatPanel {
             ChannelMessageId::Saved(id) => Some(id),
             ChannelMessageId::Pending(_) => None,
         };
+        let pending_id = match message.id {
+            ChannelMessageId::Saved(_) => None,
+            ChannelMessageId::Pending(id) => Some(id),
+        };
 
         let reply_to_message = message
             .reply_to_message_id
@@ -548,6 +552,9 @@ impl ChatPanel {
                         .when(self.has_open_menu(message_id), |el| {
                             el.bg(cx.theme().colors().element_selected)
                         })
+                    })
+                    .when_some(pending_id, |el, pending_id| {
+                        el.child(self.render_pending_message_status(pending_id, is_failed, cx))
                     }),
             )
             .when(
@@ -578,6 +585,40 @@ impl ChatPanel {
             )
     }
 
+    fn render_pending_message_status(
+        &self,
+        pending_id: usize,
+        is_failed: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let (icon, color, label) = if is_failed {
+            (IconName::XCircle, Color::Error, "Failed to send")
+        } else {
+            (IconName::CountdownTimer, Color::Muted, "Pending")
+        };
+
+        h_flex()
+            .gap_1()
+            .child(Icon::new(icon).size(IconSize::XSmall).color(color))
+            .child(Label::new(label).size(LabelSize::XSmall).color(color))
+            .when(is_failed, |el| {
+                el.child(
+                    Button::new(("retry-message", pending_id), "Retry")
+                        .label_size(LabelSize::XSmall)
+                        .on_click(
+                            cx.listener(move |this, _, _, cx| this.retry_message(pending_id, cx)),
+                        ),
+                )
+                .child(
+                    Button::new(("discard-message", pending_id), "Discard")
+                        .label_size(LabelSize::XSmall)
+                        .on_click(
+                            cx.listener(move |this, _, _, cx| this.discard_message(pending_id, cx)),
+                        ),
+                )
+            })
+    }
+
     fn has_open_menu(&self, message_id: Option<u64>) -> bool {
         match self.open_context_menu.as_ref() {
             Some((id, _)) => Some(*id) == message_id,
@@ -838,6 +879,18 @@ impl ChatPanel {
         }
     }
 
+    fn retry_message(&mut self, pending_id: usize, cx: &mut Context<Self>) {
+        if let Some((chat, _)) = self.active_chat.as_ref() {
+            chat.update(cx, |chat, cx| chat.retry_message(pending_id, cx).detach())
+        }
+    }
+
+    fn discard_message(&mut self, pending_id: usize, cx: &mut Context<Self>) {
+        if let Some((chat, _)) = self.active_chat.as_ref() {
+            chat.update(cx, |chat, cx| chat.discard_message(pending_id, cx))
+        }
+    }
+
     fn load_more_messages(&mut self, cx: &mut Context<Self>) {
         if let Some((chat, _)) = self.active_chat.as_ref() {
             chat.update(cx, |channel, cx| {
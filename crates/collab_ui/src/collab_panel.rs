@@ -821,6 +821,7 @@ impl CollabPanel {
         let is_call_admin = ActiveCall::global(cx).read(cx).room().is_some_and(|room| {
             room.read(cx).local_participant().role == proto::ChannelRole::Admin
         });
+        let is_sharing_project = !is_current_user && self.shared_project(cx).is_some();
 
         ListItem::new(SharedString::from(user.github_login.clone()))
             .start_slot(Avatar::new(user.avatar_uri.clone()))
@@ -854,7 +855,7 @@ impl CollabPanel {
                             .ok();
                     }))
             })
-            .when(is_call_admin, |el| {
+            .when(is_call_admin || is_sharing_project, |el| {
                 el.on_secondary_mouse_down(cx.listener(
                     move |this, event: &MouseDownEvent, window, cx| {
                         this.deploy_participant_context_menu(
@@ -1030,6 +1031,16 @@ impl CollabPanel {
         })
     }
 
+    /// Returns the project in this workspace if the local user is sharing it.
+    fn shared_project(&self, cx: &App) -> Option<Entity<Project>> {
+        let project = self.workspace.upgrade()?.read(cx).project().clone();
+        let is_host = {
+            let project = project.read(cx);
+            project.is_shared() && !project.is_via_collab()
+        };
+        is_host.then_some(project)
+    }
+
     fn deploy_participant_context_menu(
         &mut self,
         position: Point<Pixels>,
@@ -1039,15 +1050,28 @@ impl CollabPanel {
         cx: &mut Context<Self>,
     ) {
         let this = cx.entity().clone();
-        if !(role == proto::ChannelRole::Guest
-            || role == proto::ChannelRole::Talker
-            || role == proto::ChannelRole::Member)
-        {
+        let is_call_admin = ActiveCall::global(cx).read(cx).room().is_some_and(|room| {
+            room.read(cx).local_participant().role == proto::ChannelRole::Admin
+        });
+        let can_change_role = is_call_admin
+            && (role == proto::ChannelRole::Guest
+                || role == proto::ChannelRole::Talker
+                || role == proto::ChannelRole::Member);
+        let shared_project = self.shared_project(cx).and_then(|project| {
+            let permissions = project
+                .read(cx)
+                .collaborators()
+                .values()
+                .find(|collaborator| collaborator.user_id == user_id && !collaborator.is_host)?
+                .permissions;
+            Some((project, permissions))
+        });
+        if !can_change_role && shared_project.is_none() {
             return;
         }
 
         let context_menu = ContextMenu::build(window, cx, |mut context_menu, window, _| {
-            if role == proto::ChannelRole::Guest {
+            if can_change_role && role == proto::ChannelRole::Guest {
                 context_menu = context_menu.entry(
                     "Grant Mic Access",
                     None,
@@ -1074,7 +1098,9 @@ impl CollabPanel {
                     }),
                 );
             }
-            if role == proto::ChannelRole::Guest || role == proto::ChannelRole::Talker {
+            if can_change_role
+                && (role == proto::ChannelRole::Guest || role == proto::ChannelRole::Talker)
+            {
                 context_menu = context_menu.entry(
                     "Grant Write Access",
                     None,
@@ -1101,7 +1127,9 @@ impl CollabPanel {
                     }),
                 );
             }
-            if role == proto::ChannelRole::Member || role == proto::ChannelRole::Talker {
+            if can_change_role
+                && (role == proto::ChannelRole::Member || role == proto::ChannelRole::Talker)
+            {
                 let label = if role == proto::ChannelRole::Talker {
                     "Mute"
                 } else {
@@ -1133,6 +1161,48 @@ impl CollabPanel {
                     }),
                 );
             }
+            if let Some((project, permissions)) = shared_project {
+                if can_change_role {
+                    context_menu = context_menu.separator();
+                }
+                context_menu = context_menu.header("Project Permissions");
+                let toggles: [(&str, bool, fn(&mut proto::ProjectPermissions) -> &mut bool); 3] = [
+                    ("Edit Files", permissions.can_edit, |permissions| {
+                        &mut permissions.can_edit
+                    }),
+                    (
+                        "Use Terminals",
+                        permissions.can_use_terminal,
+                        |permissions| &mut permissions.can_use_terminal,
+                    ),
+                    ("Run Tasks", permissions.can_run_tasks, |permissions| {
+                        &mut permissions.can_run_tasks
+                    }),
+                ];
+                for (label, toggled, permission) in toggles {
+                    let project = project.clone();
+                    context_menu = context_menu.toggleable_entry(
+                        label,
+                        toggled,
+                        IconPosition::Start,
+                        None,
+                        window.handler_for(&this, move |_, window, cx| {
+                            let mut permissions = permissions;
+                            *permission(&mut permissions) = !toggled;
+                            project
+                                .update(cx, |project, cx| {
+                                    project.set_collaborator_permissions(user_id, permissions, cx)
+                                })
+                                .detach_and_prompt_err(
+                                    "Failed to change project permissions",
+                                    window,
+                                    cx,
+                                    |_, _, _| None,
+                                )
+                        }),
+                    );
+                }
+            }
 
             context_menu
         });
@@ -4,6 +4,7 @@ pub mod collab_panel;
 pub mod notification_panel;
 pub mod notifications;
 mod panel_settings;
+pub mod review_panel;
 
 use std::{rc::Rc, sync::Arc};
 
@@ -15,6 +16,7 @@ use gpui::{
 use panel_settings::MessageEditorSettings;
 pub use panel_settings::{
     ChatPanelButton, ChatPanelSettings, CollaborationPanelSettings, NotificationPanelSettings,
+    ReviewPanelSettings,
 };
 use release_channel::ReleaseChannel;
 use settings::Settings;
@@ -25,6 +27,7 @@ pub fn init(app_state: &Arc<AppState>, cx: &mut App) {
     CollaborationPanelSettings::register(cx);
     ChatPanelSettings::register(cx);
     NotificationPanelSettings::register(cx);
+    ReviewPanelSettings::register(cx);
     MessageEditorSettings::register(cx);
 
     channel_view::init(cx);
@@ -32,6 +35,7 @@ pub fn init(app_state: &Arc<AppState>, cx: &mut App) {
     collab_panel::init(cx);
     notification_panel::init(cx);
     notifications::init(app_state, cx);
+    review_panel::init(&app_state.client, cx);
     title_bar::init(cx);
 }
 
@@ -66,5 +70,6 @@ fn notification_window_options(
         app_id: Some(app_id.to_owned()),
         window_min_size: None,
         window_decorations: Some(WindowDecorations::Client),
+        tabbing_identifier: None,
     }
 }
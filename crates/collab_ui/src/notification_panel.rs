@@ -1,4 +1,4 @@
-use crate::{chat_panel::ChatPanel, NotificationPanelSettings};
+use crate::{chat_panel::ChatPanel, review_panel::ReviewPanel, NotificationPanelSettings};
 use anyhow::Result;
 use channel::ChannelStore;
 use client::{ChannelId, Client, Notification, User, UserStore};
@@ -8,7 +8,7 @@ use futures::StreamExt;
 use gpui::{
     actions, div, img, list, px, AnyElement, App, AsyncWindowContext, Context, CursorStyle,
     DismissEvent, Element, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement,
-    IntoElement, ListAlignment, ListScrollEvent, ListState, ParentElement, Render,
+    IntoElement, ListAlignment, ListScrollEvent, ListState, ParentElement, PromptLevel, Render,
     StatefulInteractiveElement, Styled, Task, WeakEntity, Window,
 };
 use notifications::{NotificationEntry, NotificationEvent, NotificationStore};
@@ -22,7 +22,9 @@ use ui::{
     h_flex, prelude::*, v_flex, Avatar, Button, Icon, IconButton, IconName, Label, Tab, Tooltip,
 };
 use util::{ResultExt, TryFutureExt};
-use workspace::notifications::{Notification as WorkspaceNotification, NotificationId};
+use workspace::notifications::{
+    record_notification, Notification as WorkspaceNotification, NotificationId, NotificationSource,
+};
 use workspace::{
     dock::{DockPosition, Panel, PanelEvent},
     Workspace,
@@ -423,6 +425,24 @@ impl NotificationPanel {
                     can_navigate: true,
                 })
             }
+            Notification::ReviewComment {
+                author_id,
+                ref worktree_root_name,
+                ref path,
+                ..
+            } => {
+                let author = user_store.get_cached_user(author_id)?;
+                Some(NotificationPresenter {
+                    icon: "icons/message_bubbles.svg",
+                    text: format!(
+                        "{} commented on {worktree_root_name}/{path}",
+                        author.github_login
+                    ),
+                    needs_response: false,
+                    actor: Some(author),
+                    can_navigate: true,
+                })
+            }
         }
     }
 
@@ -434,7 +454,9 @@ impl NotificationPanel {
         cx: &mut Context<Self>,
     ) {
         let should_mark_as_read = match notification {
-            Notification::ContactRequestAccepted { .. } => true,
+            Notification::ContactRequestAccepted { .. } | Notification::ReviewComment { .. } => {
+                true
+            }
             Notification::ContactRequest { .. }
             | Notification::ChannelInvitation { .. }
             | Notification::ChannelMessageMention { .. } => false,
@@ -465,25 +487,36 @@ impl NotificationPanel {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if let Notification::ChannelMessageMention {
-            message_id,
-            channel_id,
-            ..
-        } = notification.clone()
-        {
-            if let Some(workspace) = self.workspace.upgrade() {
-                window.defer(cx, move |window, cx| {
-                    workspace.update(cx, |workspace, cx| {
-                        if let Some(panel) = workspace.focus_panel::<ChatPanel>(window, cx) {
-                            panel.update(cx, |panel, cx| {
-                                panel
-                                    .select_channel(ChannelId(channel_id), Some(message_id), cx)
-                                    .detach_and_log_err(cx);
-                            });
-                        }
+        match notification.clone() {
+            Notification::ChannelMessageMention {
+                message_id,
+                channel_id,
+                ..
+            } => {
+                if let Some(workspace) = self.workspace.upgrade() {
+                    window.defer(cx, move |window, cx| {
+                        workspace.update(cx, |workspace, cx| {
+                            if let Some(panel) = workspace.focus_panel::<ChatPanel>(window, cx) {
+                                panel.update(cx, |panel, cx| {
+                                    panel
+                                        .select_channel(ChannelId(channel_id), Some(message_id), cx)
+                                        .detach_and_log_err(cx);
+                                });
+                            }
+                        });
                     });
-                });
+                }
+            }
+            Notification::ReviewComment { .. } => {
+                if let Some(workspace) = self.workspace.upgrade() {
+                    window.defer(cx, move |window, cx| {
+                        workspace.update(cx, |workspace, cx| {
+                            workspace.focus_panel::<ReviewPanel>(window, cx);
+                        });
+                    });
+                }
             }
+            _ => {}
         }
     }
 
@@ -553,6 +586,16 @@ impl NotificationPanel {
             return;
         };
 
+        if !record_notification(
+            NotificationSource::Collab,
+            PromptLevel::Info,
+            text.clone(),
+            Some(Box::new(ToggleFocus)),
+            cx,
+        ) {
+            return;
+        }
+
         let notification_id = entry.id;
         self.current_notification_toast = Some((
             notification_id,
@@ -1,4 +1,4 @@
-use gpui::{img, prelude::*, AnyElement, SharedUri};
+use gpui::{img, prelude::*, Accessibility, AccessibilityRole, AnyElement, SharedUri};
 use smallvec::SmallVec;
 use ui::prelude::*;
 
@@ -7,6 +7,7 @@ pub struct CollabNotification {
     avatar_uri: SharedUri,
     accept_button: Button,
     dismiss_button: Button,
+    announcement: Option<SharedString>,
     children: SmallVec<[AnyElement; 2]>,
 }
 
@@ -20,9 +21,16 @@ impl CollabNotification {
             avatar_uri: avatar_uri.into(),
             accept_button,
             dismiss_button,
+            announcement: None,
             children: SmallVec::new(),
         }
     }
+
+    /// Sets what screen readers announce when the notification appears.
+    pub fn announcement(mut self, announcement: impl Into<SharedString>) -> Self {
+        self.announcement = Some(announcement.into());
+        self
+    }
 }
 
 impl ParentElement for CollabNotification {
@@ -33,7 +41,11 @@ impl ParentElement for CollabNotification {
 
 impl RenderOnce for CollabNotification {
     fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let mut accessibility = Accessibility::new(AccessibilityRole::Alert);
+        accessibility.label = self.announcement;
+
         h_flex()
+            .accessibility(accessibility)
             .text_ui(cx)
             .justify_between()
             .size_full()
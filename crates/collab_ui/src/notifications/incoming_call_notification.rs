@@ -1,13 +1,16 @@
-use crate::notification_window_options;
 use crate::notifications::collab_notification::CollabNotification;
+use crate::{collab_panel, notification_window_options};
 use call::{ActiveCall, IncomingCall};
 use futures::StreamExt;
-use gpui::{prelude::*, App, WindowHandle};
-
+use gpui::{prelude::*, App, PromptLevel, WindowHandle};
+use localization::{localize, localize_with};
 use std::sync::{Arc, Weak};
 use ui::{prelude::*, Button, Label};
 use util::ResultExt;
-use workspace::AppState;
+use workspace::{
+    notifications::{record_notification, NotificationSource},
+    AppState,
+};
 
 pub fn init(app_state: &Arc<AppState>, cx: &mut App) {
     let app_state = Arc::downgrade(app_state);
@@ -24,6 +27,21 @@ pub fn init(app_state: &Arc<AppState>, cx: &mut App) {
             }
 
             if let Some(incoming_call) = incoming_call {
+                let should_show = cx
+                    .update(|cx| {
+                        record_notification(
+                            NotificationSource::Call,
+                            PromptLevel::Info,
+                            format!("{} called you", incoming_call.calling_user.github_login),
+                            Some(Box::new(collab_panel::ToggleFocus)),
+                            cx,
+                        )
+                    })
+                    .unwrap_or(false);
+                if !should_show {
+                    continue;
+                }
+
                 let unique_screens = cx.update(|cx| cx.displays()).unwrap();
                 let window_size = gpui::Size {
                     width: px(400.),
@@ -113,22 +131,29 @@ impl IncomingCallNotification {
 impl Render for IncomingCallNotification {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let ui_font = theme::setup_ui_font(window, cx);
+        let login = self.state.call.calling_user.github_login.as_str();
 
         div().size_full().font(ui_font).child(
             CollabNotification::new(
                 self.state.call.calling_user.avatar_uri.clone(),
-                Button::new("accept", "Accept").on_click({
+                Button::new("accept", localize("Accept", cx)).on_click({
                     let state = self.state.clone();
                     move |_, _, cx| state.respond(true, cx)
                 }),
-                Button::new("decline", "Decline").on_click({
+                Button::new("decline", localize("Decline", cx)).on_click({
                     let state = self.state.clone();
                     move |_, _, cx| state.respond(false, cx)
                 }),
             )
-            .child(v_flex().overflow_hidden().child(Label::new(format!(
-                "{} is sharing a project in Zed",
-                self.state.call.calling_user.github_login
+            .announcement(localize_with(
+                "Incoming call from {login}",
+                &[("login", login)],
+                cx,
+            ))
+            .child(v_flex().overflow_hidden().child(Label::new(localize_with(
+                "{login} is sharing a project in Zed",
+                &[("login", login)],
+                cx,
             )))),
         )
     }
@@ -4,6 +4,7 @@ use call::{room, ActiveCall};
 use client::User;
 use collections::HashMap;
 use gpui::{App, Size};
+use localization::{localize, localize_with};
 use std::sync::{Arc, Weak};
 
 use ui::{prelude::*, Button, Label};
@@ -128,24 +129,28 @@ impl Render for ProjectSharedNotification {
         div().size_full().font(ui_font).child(
             CollabNotification::new(
                 self.owner.avatar_uri.clone(),
-                Button::new("open", "Open").on_click(cx.listener(move |this, _event, _, cx| {
-                    this.join(cx);
-                })),
-                Button::new("dismiss", "Dismiss").on_click(cx.listener(
+                Button::new("open", localize("Open", cx)).on_click(cx.listener(
+                    move |this, _event, _, cx| {
+                        this.join(cx);
+                    },
+                )),
+                Button::new("dismiss", localize("Dismiss", cx)).on_click(cx.listener(
                     move |this, _event, _, cx| {
                         this.dismiss(cx);
                     },
                 )),
             )
+            .announcement(localize_with(
+                "{login} is sharing a project in Zed",
+                &[("login", self.owner.github_login.as_str())],
+                cx,
+            ))
             .child(Label::new(self.owner.github_login.clone()))
-            .child(Label::new(format!(
-                "is sharing a project in Zed{}",
-                if self.worktree_root_names.is_empty() {
-                    ""
-                } else {
-                    ":"
-                }
-            )))
+            .child(Label::new(if self.worktree_root_names.is_empty() {
+                localize("is sharing a project in Zed", cx)
+            } else {
+                localize("is sharing a project in Zed:", cx)
+            }))
             .children(if self.worktree_root_names.is_empty() {
                 None
             } else {
@@ -0,0 +1,683 @@
+use crate::ReviewPanelSettings;
+use anyhow::{anyhow, Result};
+use client::{Client, UserStore};
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, MultiBufferRow};
+use gpui::{
+    actions, img, AnyElement, App, AsyncApp, AsyncWindowContext, Context, DismissEvent, Entity,
+    EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement, ParentElement, Render,
+    StatefulInteractiveElement, Styled, Task, WeakEntity, Window,
+};
+use language::{BufferSnapshot, Point};
+use project::{Fs, Project, ProjectPath};
+use rpc::{proto, AnyProtoClient, TypedEnvelope};
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use std::{future::Future, path::Path, sync::Arc};
+use ui::{h_flex, prelude::*, v_flex, Icon, IconButton, IconName, Label, Tab, Tooltip};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    notifications::NotificationId,
+    ModalView, Toast, Workspace,
+};
+
+const REVIEW_PANEL_KEY: &str = "ReviewPanel";
+
+actions!(review_panel, [ToggleFocus, AddComment]);
+
+pub fn init(client: &Arc<Client>, cx: &mut App) {
+    let client: AnyProtoClient = client.clone().into();
+    client.add_entity_message_handler(ReviewPanel::handle_update_review_comment);
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace
+            .register_action(|workspace, _: &ToggleFocus, window, cx| {
+                workspace.toggle_panel_focus::<ReviewPanel>(window, cx);
+            })
+            .register_action(ReviewPanel::add_comment);
+    })
+    .detach();
+}
+
+/// Lists the unresolved review comments that collaborators have left on the
+/// lines of a shared project. Comments are stored on the collab server, so
+/// they remain visible after the call in which they were written has ended.
+pub struct ReviewPanel {
+    client: Arc<Client>,
+    user_store: Entity<UserStore>,
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    project_id: Option<u64>,
+    comments: Vec<proto::ReviewComment>,
+    pending_serialization: Task<Option<()>>,
+    pending_load: Task<()>,
+    remote_subscription: Option<client::Subscription>,
+    _subscriptions: Vec<gpui::Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedReviewPanel {
+    width: Option<Pixels>,
+}
+
+/// A line of a buffer that a review comment is being written about.
+#[derive(Clone)]
+struct CommentLocation {
+    project_id: u64,
+    worktree_id: u64,
+    path: String,
+    row: u32,
+    line_text: String,
+}
+
+enum ReviewCommentHighlight {}
+
+impl ReviewPanel {
+    pub fn new(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let client = workspace.app_state().client.clone();
+        let user_store = workspace.app_state().user_store.clone();
+        let project = workspace.project().clone();
+        let workspace_handle = workspace.weak_handle();
+
+        cx.new(|cx| {
+            let subscriptions = vec![
+                cx.subscribe(&project, |this, project, event, cx| match event {
+                    project::Event::RemoteIdChanged(_) => {
+                        let project_id = project.read(cx).remote_id();
+                        this.set_project_id(project_id, cx);
+                    }
+                    project::Event::DisconnectedFromHost => this.set_project_id(None, cx),
+                    _ => {}
+                }),
+                cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+            ];
+
+            let mut this = Self {
+                client,
+                user_store,
+                project: project.clone(),
+                workspace: workspace_handle,
+                fs,
+                focus_handle: cx.focus_handle(),
+                width: None,
+                project_id: None,
+                comments: Vec::new(),
+                pending_serialization: Task::ready(None),
+                pending_load: Task::ready(()),
+                remote_subscription: None,
+                _subscriptions: subscriptions,
+            };
+            this.set_project_id(project.read(cx).remote_id(), cx);
+            this
+        })
+    }
+
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let serialized_panel = if let Some(panel) = cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(REVIEW_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedReviewPanel>(&panel)?)
+            } else {
+                None
+            };
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let panel = Self::new(workspace, window, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|w| w.round());
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        REVIEW_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedReviewPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    /// Starts listening for comments on the project with the given id, which
+    /// is `None` while the project isn't shared in a call.
+    fn set_project_id(&mut self, project_id: Option<u64>, cx: &mut Context<Self>) {
+        if self.project_id == project_id {
+            return;
+        }
+
+        self.project_id = project_id;
+        self.comments.clear();
+        self.remote_subscription.take();
+        self.pending_load = Task::ready(());
+        cx.notify();
+
+        let Some(project_id) = project_id else {
+            return;
+        };
+        self.remote_subscription = self
+            .client
+            .subscribe_to_entity::<Self>(project_id)
+            .log_err()
+            .map(|subscription| subscription.set_entity(&cx.entity(), &cx.to_async()));
+
+        let request = self.client.request(proto::GetReviewComments { project_id });
+        self.pending_load = cx.spawn(async move |this, cx| {
+            Self::load_comments(this, request, cx).await.log_err();
+        });
+    }
+
+    async fn load_comments(
+        this: WeakEntity<Self>,
+        request: impl Future<Output = Result<proto::GetReviewCommentsResponse>>,
+        cx: &mut AsyncApp,
+    ) -> Result<()> {
+        let response = request.await?;
+        let user_store = this.update(cx, |this, _| this.user_store.clone())?;
+        let author_ids = response
+            .comments
+            .iter()
+            .map(|comment| comment.author_id)
+            .collect();
+        user_store
+            .update(cx, |user_store, cx| user_store.get_users(author_ids, cx))?
+            .await?;
+        this.update(cx, |this, cx| {
+            for comment in response.comments {
+                this.apply_comment(comment, cx);
+            }
+        })
+    }
+
+    fn apply_comment(&mut self, comment: proto::ReviewComment, cx: &mut Context<Self>) {
+        self.comments.retain(|existing| existing.id != comment.id);
+        if !comment.resolved {
+            let ix = self
+                .comments
+                .partition_point(|existing| existing.id < comment.id);
+            self.comments.insert(ix, comment);
+        }
+        cx.notify();
+    }
+
+    async fn handle_update_review_comment(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UpdateReviewComment>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let comment = envelope
+            .payload
+            .comment
+            .ok_or_else(|| anyhow!("missing review comment"))?;
+        let user_store = this.read_with(&cx, |this, _| this.user_store.clone())?;
+        user_store
+            .update(&mut cx, |user_store, cx| {
+                user_store.get_user(comment.author_id, cx)
+            })?
+            .await?;
+        this.update(&mut cx, |this, cx| this.apply_comment(comment, cx))
+    }
+
+    fn add_comment(
+        workspace: &mut Workspace,
+        _: &AddComment,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(panel) = workspace.panel::<ReviewPanel>(cx) else {
+            return;
+        };
+        let Some(project_id) = panel.read(cx).project_id else {
+            workspace.show_toast(
+                Toast::new(
+                    NotificationId::unique::<ReviewPanel>(),
+                    "Share this project in a call to leave review comments",
+                )
+                .autohide(),
+                cx,
+            );
+            return;
+        };
+        let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+            return;
+        };
+        let Some(location) = CommentLocation::for_editor(project_id, &editor, cx) else {
+            return;
+        };
+
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            AddCommentModal::new(panel, location, window, cx)
+        });
+    }
+
+    fn submit_comment(
+        &mut self,
+        location: CommentLocation,
+        body: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let request = self.client.request(proto::AddReviewComment {
+            project_id: location.project_id,
+            worktree_id: location.worktree_id,
+            path: location.path,
+            row: location.row,
+            line_text: location.line_text,
+            body,
+        });
+        cx.spawn(async move |this, cx| {
+            let comment = request
+                .await?
+                .comment
+                .ok_or_else(|| anyhow!("missing review comment"))?;
+            this.update(cx, |this, cx| this.apply_comment(comment, cx))
+        })
+    }
+
+    fn resolve_comment(&mut self, comment_id: u64, cx: &mut Context<Self>) {
+        let Some(project_id) = self.project_id else {
+            return;
+        };
+        let request = self.client.request(proto::ResolveReviewComment {
+            project_id,
+            comment_id,
+            resolved: true,
+        });
+        cx.spawn(async move |this, cx| {
+            request.await?;
+            this.update(cx, |this, cx| {
+                this.comments.retain(|comment| comment.id != comment_id);
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn open_comment(
+        &mut self,
+        comment: &proto::ReviewComment,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(worktree_id) = self
+            .project
+            .read(cx)
+            .visible_worktrees(cx)
+            .find(|worktree| {
+                worktree.read(cx).abs_path().to_string_lossy() == comment.worktree_abs_path
+            })
+            .map(|worktree| worktree.read(cx).id())
+        else {
+            return;
+        };
+        let project_path = ProjectPath {
+            worktree_id,
+            path: Path::new(&comment.path).into(),
+        };
+        let Some(open_task) = self
+            .workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_path(project_path, None, true, window, cx)
+            })
+            .log_err()
+        else {
+            return;
+        };
+
+        let row = comment.row;
+        let line_text = comment.line_text.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            let Some(editor) = open_task.await?.downcast::<Editor>() else {
+                return Ok(());
+            };
+            editor.update_in(cx, |editor, window, cx| {
+                let Some(buffer) = editor.buffer().read(cx).as_singleton() else {
+                    return;
+                };
+                let row = anchored_row(&buffer.read(cx).snapshot(), row, &line_text);
+                editor.go_to_singleton_buffer_point(Point::new(row, 0), window, cx);
+
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let start = snapshot.anchor_before(Point::new(row, 0));
+                let end =
+                    snapshot.anchor_after(Point::new(row, snapshot.line_len(MultiBufferRow(row))));
+                editor.clear_row_highlights::<ReviewCommentHighlight>();
+                editor.highlight_rows::<ReviewCommentHighlight>(
+                    start..end,
+                    cx.theme().colors().editor_highlighted_line_background,
+                    true,
+                    cx,
+                );
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn render_comment(
+        &self,
+        comment: &proto::ReviewComment,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let author = self.user_store.read(cx).get_cached_user(comment.author_id);
+        let comment_id = comment.id;
+        let location = format!(
+            "{}/{}:{}",
+            comment.worktree_root_name,
+            comment.path,
+            comment.row + 1
+        );
+
+        h_flex()
+            .id(("review-comment", comment_id))
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .items_start()
+            .hover(|style| style.bg(cx.theme().colors().element_hover))
+            .cursor_pointer()
+            .on_click({
+                let comment = comment.clone();
+                cx.listener(move |this, _, window, cx| this.open_comment(&comment, window, cx))
+            })
+            .children(author.as_ref().map(|author| {
+                img(author.avatar_uri.clone())
+                    .flex_none()
+                    .w_6()
+                    .h_6()
+                    .rounded_full()
+            }))
+            .child(
+                v_flex()
+                    .flex_1()
+                    .gap_0p5()
+                    .overflow_hidden()
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .children(author.map(|author| Label::new(author.github_login.clone())))
+                            .child(
+                                Label::new(location)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .truncate(),
+                            ),
+                    )
+                    .child(
+                        Label::new(comment.line_text.trim().to_string())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx)
+                            .truncate(),
+                    )
+                    .child(Label::new(comment.body.clone())),
+            )
+            .child(
+                IconButton::new(("resolve-review-comment", comment_id), IconName::Check)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Resolve"))
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.resolve_comment(comment_id, cx);
+                    })),
+            )
+    }
+}
+
+/// Returns the row that a comment left on `row` now refers to. If the line has
+/// moved since the comment was written, the closest line with the same text is
+/// used instead.
+fn anchored_row(snapshot: &BufferSnapshot, row: u32, line_text: &str) -> u32 {
+    let line_at = |row: u32| {
+        snapshot
+            .text_for_range(Point::new(row, 0)..Point::new(row, snapshot.line_len(row)))
+            .collect::<String>()
+    };
+
+    let max_row = snapshot.max_point().row;
+    if row <= max_row && line_at(row) == line_text {
+        return row;
+    }
+    (0..=max_row)
+        .filter(|candidate| line_at(*candidate) == line_text)
+        .min_by_key(|candidate| candidate.abs_diff(row))
+        .unwrap_or(row.min(max_row))
+}
+
+impl CommentLocation {
+    fn for_editor(project_id: u64, editor: &Entity<Editor>, cx: &mut App) -> Option<Self> {
+        let editor = editor.read(cx);
+        let buffer = editor.buffer().read(cx).as_singleton()?;
+        let row = editor.selections.newest::<Point>(cx).head().row;
+        let buffer = buffer.read(cx);
+        let file = project::File::from_dyn(buffer.file())?;
+        let snapshot = buffer.snapshot();
+        let line_text = snapshot
+            .text_for_range(Point::new(row, 0)..Point::new(row, snapshot.line_len(row)))
+            .collect();
+        Some(Self {
+            project_id,
+            worktree_id: file.worktree_id(cx).to_proto(),
+            path: file.path().to_string_lossy().into_owned(),
+            row,
+            line_text,
+        })
+    }
+}
+
+impl Render for ReviewPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .px_2()
+                    .py_1()
+                    // Match the height of the tab bar so they line up.
+                    .h(Tab::container_height(cx))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Label::new("Review"))
+                    .child(Icon::new(IconName::MessageBubbles)),
+            )
+            .map(|this| {
+                let message = if self.project_id.is_none() {
+                    Some("Share this project in a call to see its review comments.")
+                } else if self.comments.is_empty() {
+                    Some("There are no unresolved comments.")
+                } else {
+                    None
+                };
+                if let Some(message) = message {
+                    this.child(
+                        v_flex().p_4().child(
+                            div().flex().w_full().items_center().child(
+                                Label::new(message)
+                                    .color(Color::Muted)
+                                    .size(LabelSize::Small),
+                            ),
+                        ),
+                    )
+                } else {
+                    let comments = self
+                        .comments
+                        .iter()
+                        .map(|comment| self.render_comment(comment, cx).into_any_element())
+                        .collect::<Vec<AnyElement>>();
+                    this.child(
+                        v_flex()
+                            .id("review-comments")
+                            .size_full()
+                            .overflow_y_scroll()
+                            .children(comments),
+                    )
+                }
+            })
+    }
+}
+
+impl Focusable for ReviewPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for ReviewPanel {}
+
+impl Panel for ReviewPanel {
+    fn persistent_name() -> &'static str {
+        "ReviewPanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        ReviewPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<ReviewPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| ReviewPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        let settings = ReviewPanelSettings::get_global(cx);
+        (settings.button && self.project_id.is_some()).then_some(IconName::MessageBubbles)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Review Panel")
+    }
+
+    fn icon_label(&self, _window: &Window, _cx: &App) -> Option<String> {
+        (!self.comments.is_empty()).then(|| self.comments.len().to_string())
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        12
+    }
+}
+
+struct AddCommentModal {
+    panel: Entity<ReviewPanel>,
+    location: CommentLocation,
+    editor: Entity<Editor>,
+}
+
+impl AddCommentModal {
+    fn new(
+        panel: Entity<ReviewPanel>,
+        location: CommentLocation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Leave a comment on this line…", cx);
+            editor
+        });
+        Self {
+            panel,
+            location,
+            editor,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        let body = self.editor.read(cx).text(cx).trim().to_string();
+        if body.is_empty() {
+            return;
+        }
+        let location = self.location.clone();
+        self.panel
+            .update(cx, |panel, cx| panel.submit_comment(location, body, cx))
+            .detach_and_log_err(cx);
+        cx.emit(DismissEvent);
+    }
+}
+
+impl ModalView for AddCommentModal {}
+
+impl EventEmitter<DismissEvent> for AddCommentModal {}
+
+impl Focusable for AddCommentModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Render for AddCommentModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(rems(34.))
+            .elevation_2(cx)
+            .key_context("AddReviewComment")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .child(
+                div()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .px_2()
+                    .py_1()
+                    .child(self.editor.clone()),
+            )
+            .child(
+                h_flex().px_2().py_1().child(
+                    Label::new(format!("{}:{}", self.location.path, self.location.row + 1))
+                        .color(Color::Muted)
+                        .size(LabelSize::Small),
+                ),
+            )
+    }
+}
@@ -7,7 +7,7 @@ use std::{
 use client::parse_zed_link;
 use collections::HashMap;
 use command_palette_hooks::{
-    CommandInterceptResult, CommandPaletteFilter, CommandPaletteInterceptor,
+    CommandInterceptResult, CommandPaletteCommands, CommandPaletteFilter, CommandPaletteInterceptor,
 };
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{
@@ -91,7 +91,7 @@ impl CommandPalette {
     ) -> Self {
         let filter = CommandPaletteFilter::try_global(cx);
 
-        let commands = window
+        let mut commands = window
             .available_actions(cx)
             .into_iter()
             .filter_map(|action| {
@@ -104,7 +104,13 @@ impl CommandPalette {
                     action,
                 })
             })
-            .collect();
+            .collect::<Vec<_>>();
+        if let Some(additional_commands) = CommandPaletteCommands::try_global(cx) {
+            commands.extend(additional_commands.commands().map(|command| Command {
+                name: command.name.clone(),
+                action: command.action.boxed_clone(),
+            }));
+        }
 
         let delegate =
             CommandPaletteDelegate::new(cx.entity().downgrade(), commands, previous_focus_handle);
@@ -468,6 +474,7 @@ mod tests {
     use std::sync::Arc;
 
     use super::*;
+    use command_palette_hooks::CommandPaletteCommand;
     use editor::Editor;
     use go_to_line::GoToLine;
     use gpui::TestAppContext;
@@ -582,6 +589,59 @@ mod tests {
             assert!(palette.delegate.matches.is_empty())
         });
     }
+    #[gpui::test]
+    async fn test_additional_commands(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        let project = Project::test(app_state.fs.clone(), [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        let editor = cx.new_window_entity(|window, cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_text("abc", window, cx);
+            editor
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(editor.clone()), None, true, window, cx);
+            editor.update(cx, |editor, cx| window.focus(&editor.focus_handle(cx)))
+        });
+
+        cx.update(|_window, cx| {
+            CommandPaletteCommands::update_global(cx, |commands, _| {
+                commands.add(CommandPaletteCommand {
+                    name: "my extension: trim last character".to_string(),
+                    action: editor::actions::Backspace.boxed_clone(),
+                });
+            });
+        });
+
+        cx.simulate_keystrokes("cmd-shift-p");
+        cx.simulate_input("trim last");
+
+        let palette = workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_modal::<CommandPalette>(cx)
+                .unwrap()
+                .read(cx)
+                .picker
+                .clone()
+        });
+        palette.update(cx, |palette, _| {
+            assert_eq!(
+                palette.delegate.matches[0].string,
+                "my extension: trim last character"
+            );
+        });
+
+        cx.simulate_keystrokes("enter");
+
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<CommandPalette>(cx).is_none());
+            assert_eq!(editor.read(cx).text(cx), "ab")
+        });
+    }
+
     #[gpui::test]
     async fn test_normalized_matches(cx: &mut TestAppContext) {
         let app_state = init_test(cx);
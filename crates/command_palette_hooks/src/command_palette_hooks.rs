@@ -12,6 +12,7 @@ use gpui::{Action, App, BorrowAppContext, Global};
 pub fn init(cx: &mut App) {
     cx.set_global(GlobalCommandPaletteFilter::default());
     cx.set_global(GlobalCommandPaletteInterceptor::default());
+    cx.set_global(GlobalCommandPaletteCommands::default());
 }
 
 /// A filter for the command palette.
@@ -152,3 +153,61 @@ impl CommandPaletteInterceptor {
         self.0 = Some(handler);
     }
 }
+
+/// A command added to the command palette, in addition to the available actions.
+#[derive(Debug)]
+pub struct CommandPaletteCommand {
+    /// The name of the command, as displayed in the command palette.
+    pub name: String,
+    /// The action dispatched when the command is confirmed.
+    pub action: Box<dyn Action>,
+}
+
+/// The commands added to the command palette, in addition to the available actions.
+///
+/// This is used for commands that are only known at runtime, like the ones contributed by
+/// extensions, which all dispatch the same action type with different arguments.
+#[derive(Default)]
+pub struct CommandPaletteCommands {
+    commands: Vec<CommandPaletteCommand>,
+}
+
+#[derive(Deref, DerefMut, Default)]
+struct GlobalCommandPaletteCommands(CommandPaletteCommands);
+
+impl Global for GlobalCommandPaletteCommands {}
+
+impl CommandPaletteCommands {
+    /// Returns the global [`CommandPaletteCommands`], if set.
+    pub fn try_global(cx: &App) -> Option<&CommandPaletteCommands> {
+        cx.try_global::<GlobalCommandPaletteCommands>()
+            .map(|commands| &commands.0)
+    }
+
+    /// Updates the global [`CommandPaletteCommands`] using the given closure.
+    pub fn update_global<F>(cx: &mut App, update: F)
+    where
+        F: FnOnce(&mut Self, &mut App),
+    {
+        if cx.has_global::<GlobalCommandPaletteCommands>() {
+            cx.update_global(|this: &mut GlobalCommandPaletteCommands, cx| update(&mut this.0, cx))
+        }
+    }
+
+    /// Returns the commands to add to the command palette.
+    pub fn commands(&self) -> impl Iterator<Item = &CommandPaletteCommand> {
+        self.commands.iter()
+    }
+
+    /// Adds a command to the command palette, replacing any command with an equal action.
+    pub fn add(&mut self, command: CommandPaletteCommand) {
+        self.commands
+            .retain(|existing| !existing.action.partial_eq(&*command.action));
+        self.commands.push(command);
+    }
+
+    /// Removes the commands for which the given predicate returns `false`.
+    pub fn retain(&mut self, predicate: impl FnMut(&CommandPaletteCommand) -> bool) {
+        self.commands.retain(predicate);
+    }
+}
@@ -0,0 +1,340 @@
+//! A curated list of well-known context servers that can be installed
+//! without writing their configuration by hand.
+
+use std::sync::LazyLock;
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use credentials_provider::CredentialsProvider;
+use gpui::AsyncApp;
+use serde::Deserialize;
+
+use crate::{ServerCommand, ServerConfig};
+
+static CATALOG: LazyLock<Vec<CatalogEntry>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("catalog.json")).expect("invalid context server catalog")
+});
+
+/// Returns the known context servers.
+pub fn catalog() -> &'static [CatalogEntry] {
+    &CATALOG
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CatalogEntry {
+    /// The ID used for the server in the `context_servers` setting.
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub homepage: Option<String>,
+    pub command: String,
+    /// The command's arguments, which may reference inputs as `{{key}}`.
+    pub args: Vec<String>,
+    /// The values the user needs to provide to run the server.
+    #[serde(default)]
+    pub inputs: Vec<CatalogInput>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CatalogInput {
+    /// The environment variable name or argument placeholder this input fills in.
+    pub key: String,
+    pub label: String,
+    pub description: String,
+    pub kind: CatalogInputKind,
+    /// Whether the value is a credential, which shouldn't be displayed.
+    #[serde(default)]
+    pub secret: bool,
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatalogInputKind {
+    /// The value is substituted into the command's arguments.
+    Arg,
+    /// The value is passed to the server as an environment variable.
+    Env,
+}
+
+impl CatalogInput {
+    /// Whether the user has to provide a value, as there's no default to fall back to.
+    pub fn is_required(&self) -> bool {
+        self.default.is_none()
+    }
+
+    fn value<'a>(&'a self, values: &'a HashMap<String, String>) -> Option<&'a str> {
+        values
+            .get(&self.key)
+            .map(String::as_str)
+            .filter(|value| !value.is_empty())
+            .or(self.default.as_deref())
+    }
+
+    fn placeholder(&self) -> String {
+        format!("{{{{{}}}}}", self.key)
+    }
+}
+
+impl CatalogEntry {
+    /// Builds the server's configuration from the values of its inputs, keyed by [`CatalogInput::key`].
+    ///
+    /// Inputs without a value fall back to their default.
+    pub fn server_config(&self, values: &HashMap<String, String>) -> ServerConfig {
+        self.build_server_config(values, |_| true)
+    }
+
+    /// Builds the configuration that is written to the settings, which leaves out the values of
+    /// secret inputs. They're stored with [`CatalogEntry::write_secrets`] instead, and filled back
+    /// in by [`CatalogEntry::fill_in_secrets`] when the server is started.
+    pub fn settings_config(&self, values: &HashMap<String, String>) -> ServerConfig {
+        self.build_server_config(values, |input| !input.secret)
+    }
+
+    fn build_server_config(
+        &self,
+        values: &HashMap<String, String>,
+        include: impl Fn(&CatalogInput) -> bool,
+    ) -> ServerConfig {
+        let mut command = ServerCommand {
+            path: self.command.clone(),
+            args: self.args.clone(),
+            env: None,
+        };
+        let values = self
+            .inputs
+            .iter()
+            .filter(|input| include(input))
+            .filter_map(|input| Some((input.key.clone(), input.value(values)?.to_string())))
+            .collect::<HashMap<_, _>>();
+        self.fill_in_inputs(&mut command, &values, |input| include(input));
+
+        // Arguments whose inputs have no value are passed as empty strings, rather than as
+        // their placeholder.
+        for arg in &mut command.args {
+            for input in self.inputs.iter().filter(|input| include(input)) {
+                *arg = arg.replace(&input.placeholder(), "");
+            }
+        }
+
+        ServerConfig {
+            command: Some(command),
+            settings: Some(serde_json::json!({})),
+        }
+    }
+
+    /// Whether any of the server's inputs are secret.
+    pub fn has_secrets(&self) -> bool {
+        self.inputs.iter().any(|input| input.secret)
+    }
+
+    /// Fills the values of secret inputs into a command built by [`CatalogEntry::settings_config`].
+    ///
+    /// Environment variables that are already set in the command are kept as they are.
+    pub fn fill_in_secrets(&self, command: &mut ServerCommand, secrets: &HashMap<String, String>) {
+        self.fill_in_inputs(command, secrets, |input| input.secret);
+    }
+
+    fn fill_in_inputs(
+        &self,
+        command: &mut ServerCommand,
+        values: &HashMap<String, String>,
+        include: impl Fn(&CatalogInput) -> bool,
+    ) {
+        for input in self.inputs.iter().filter(|input| include(input)) {
+            let Some(value) = values.get(&input.key).filter(|value| !value.is_empty()) else {
+                continue;
+            };
+            match input.kind {
+                CatalogInputKind::Arg => {
+                    for arg in &mut command.args {
+                        *arg = arg.replace(&input.placeholder(), value);
+                    }
+                }
+                CatalogInputKind::Env => {
+                    command
+                        .env
+                        .get_or_insert_with(HashMap::default)
+                        .entry(input.key.clone())
+                        .or_insert_with(|| value.clone());
+                }
+            }
+        }
+    }
+
+    /// Stores the values of the server's secret inputs in the system keychain.
+    pub async fn write_secrets(
+        &self,
+        values: &HashMap<String, String>,
+        credentials_provider: &dyn CredentialsProvider,
+        cx: &AsyncApp,
+    ) -> Result<()> {
+        let secrets = self
+            .inputs
+            .iter()
+            .filter(|input| input.secret)
+            .filter_map(|input| Some((input.key.clone(), input.value(values)?.to_string())))
+            .collect::<HashMap<_, _>>();
+        if secrets.is_empty() {
+            return Ok(());
+        }
+
+        credentials_provider
+            .write_credentials(
+                &self.credentials_url(),
+                "inputs",
+                &serde_json::to_vec(&secrets)?,
+                cx,
+            )
+            .await
+    }
+
+    /// Reads the values of the server's secret inputs from the system keychain.
+    pub async fn read_secrets(
+        &self,
+        credentials_provider: &dyn CredentialsProvider,
+        cx: &AsyncApp,
+    ) -> Result<HashMap<String, String>> {
+        let Some((_, secrets)) = credentials_provider
+            .read_credentials(&self.credentials_url(), cx)
+            .await?
+        else {
+            return Ok(HashMap::default());
+        };
+        serde_json::from_slice(&secrets).context("invalid context server secrets")
+    }
+
+    fn credentials_url(&self) -> String {
+        format!("zed://context-servers/{}", self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_catalog_is_valid() {
+        let catalog = catalog();
+        assert!(!catalog.is_empty());
+
+        let mut ids = HashSet::default();
+        for entry in catalog {
+            assert!(
+                ids.insert(&entry.id),
+                "duplicate catalog entry {}",
+                entry.id
+            );
+            for input in &entry.inputs {
+                if input.kind == CatalogInputKind::Arg {
+                    assert!(
+                        entry
+                            .args
+                            .iter()
+                            .any(|arg| arg.contains(&input.placeholder())),
+                        "input {} of {} isn't referenced by its arguments",
+                        input.key,
+                        entry.id
+                    );
+                }
+            }
+
+            let config = entry.server_config(&HashMap::default());
+            for arg in &config.command.unwrap().args {
+                assert!(
+                    !arg.contains("{{"),
+                    "{} references an unknown input in {arg}",
+                    entry.id
+                );
+            }
+        }
+    }
+
+    fn entry() -> CatalogEntry {
+        serde_json::from_value(serde_json::json!({
+            "id": "example",
+            "name": "Example",
+            "description": "An example server.",
+            "command": "example-server",
+            "args": ["--root", "{{root}}", "--token={{token}}"],
+            "inputs": [
+                { "key": "root", "label": "Root", "description": "", "kind": "arg" },
+                { "key": "token", "label": "Token", "description": "", "kind": "arg", "secret": true },
+                { "key": "API_KEY", "label": "API Key", "description": "", "kind": "env", "secret": true },
+                {
+                    "key": "API_URL",
+                    "label": "API URL",
+                    "description": "",
+                    "kind": "env",
+                    "default": "https://example.com"
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn values(values: &[(&str, &str)]) -> HashMap<String, String> {
+        values
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_server_config() {
+        let entry = entry();
+        assert!(entry.inputs[0].is_required());
+        assert!(!entry.inputs[3].is_required());
+
+        let config = entry.server_config(&values(&[
+            ("root", "/src"),
+            ("token", "secret-token"),
+            ("API_KEY", "secret-key"),
+            ("API_URL", ""),
+        ]));
+        let command = config.command.unwrap();
+        assert_eq!(command.path, "example-server");
+        assert_eq!(command.args, ["--root", "/src", "--token=secret-token"]);
+        assert_eq!(
+            command.env,
+            Some(values(&[
+                ("API_KEY", "secret-key"),
+                ("API_URL", "https://example.com"),
+            ]))
+        );
+
+        let config = entry.server_config(&values(&[
+            ("root", "/src"),
+            ("API_URL", "https://example.org"),
+        ]));
+        let command = config.command.unwrap();
+        assert_eq!(command.args, ["--root", "/src", "--token="]);
+        assert_eq!(
+            command.env,
+            Some(values(&[("API_URL", "https://example.org")]))
+        );
+    }
+
+    #[test]
+    fn test_secrets_are_left_out_of_settings() {
+        let entry = entry();
+        let input_values = values(&[
+            ("root", "/src"),
+            ("token", "secret-token"),
+            ("API_KEY", "secret-key"),
+        ]);
+
+        let mut command = entry.settings_config(&input_values).command.unwrap();
+        assert_eq!(command.args, ["--root", "/src", "--token={{token}}"]);
+        assert_eq!(
+            command.env,
+            Some(values(&[("API_URL", "https://example.com")]))
+        );
+
+        entry.fill_in_secrets(&mut command, &input_values);
+        assert_eq!(command, entry.server_config(&input_values).command.unwrap());
+    }
+}
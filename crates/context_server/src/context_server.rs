@@ -1,3 +1,4 @@
+pub mod catalog;
 pub mod client;
 mod context_server_tool;
 mod extension_context_server;
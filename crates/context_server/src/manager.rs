@@ -20,6 +20,7 @@ use std::sync::Arc;
 use anyhow::{bail, Result};
 use collections::HashMap;
 use command_palette_hooks::CommandPaletteFilter;
+use credentials_provider::CredentialsProvider;
 use gpui::{AsyncApp, Context, Entity, EventEmitter, Subscription, Task, WeakEntity};
 use log;
 use parking_lot::RwLock;
@@ -27,6 +28,7 @@ use project::Project;
 use settings::{Settings, SettingsStore};
 use util::ResultExt as _;
 
+use crate::catalog::catalog;
 use crate::{ContextServerSettings, ServerConfig};
 
 use crate::{
@@ -63,15 +65,29 @@ impl ContextServer {
 
     pub async fn start(self: Arc<Self>, cx: &AsyncApp) -> Result<()> {
         log::info!("starting context server {}", self.id);
-        let Some(command) = &self.config.command else {
+        let Some(mut command) = self.config.command.clone() else {
             bail!("no command specified for server {}", self.id);
         };
+        // Servers installed from the catalog keep the values of their secret inputs in the
+        // keychain, rather than in the settings.
+        if let Some(entry) = catalog()
+            .iter()
+            .find(|entry| *entry.id == *self.id && entry.has_secrets())
+        {
+            let credentials_provider = cx.update(|cx| <dyn CredentialsProvider>::global(cx))?;
+            let secrets = entry
+                .read_secrets(&*credentials_provider, cx)
+                .await
+                .log_err()
+                .unwrap_or_default();
+            entry.fill_in_secrets(&mut command, &secrets);
+        }
         let client = Client::new(
             client::ContextServerId(self.id.clone()),
             client::ModelContextServerBinary {
                 executable: Path::new(&command.path).to_path_buf(),
-                args: command.args.clone(),
-                env: command.env.clone(),
+                args: command.args,
+                env: command.env,
             },
             cx.clone(),
         )?;
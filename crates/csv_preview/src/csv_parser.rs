@@ -0,0 +1,185 @@
+use std::{cmp::Ordering, path::Path};
+
+/// Returns the delimiter of the delimiter-separated values in the file at `path`, if it is a
+/// CSV or TSV file.
+pub fn delimiter_for_path(path: &Path) -> Option<char> {
+    let extension = path.extension()?.to_str()?;
+    if extension.eq_ignore_ascii_case("csv") {
+        Some(',')
+    } else if extension.eq_ignore_ascii_case("tsv") || extension.eq_ignore_ascii_case("tab") {
+        Some('\t')
+    } else {
+        None
+    }
+}
+
+/// A row of delimiter-separated values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    /// The zero-based line on which the record starts.
+    pub line: u32,
+    pub fields: Vec<String>,
+}
+
+/// An iterator over the records of delimiter-separated text, following RFC 4180: fields may be
+/// quoted with `"`, in which case they can contain delimiters, line breaks and escaped `""`
+/// quotes. Malformed quoting is accepted as-is rather than failing the whole file.
+pub struct Records<'a> {
+    text: &'a str,
+    offset: usize,
+    line: u32,
+    delimiter: char,
+}
+
+impl<'a> Records<'a> {
+    pub fn new(text: &'a str, delimiter: char) -> Self {
+        let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+        Self {
+            text,
+            offset: 0,
+            line: 0,
+            delimiter,
+        }
+    }
+}
+
+impl Iterator for Records<'_> {
+    type Item = Record;
+
+    fn next(&mut self) -> Option<Record> {
+        if self.offset >= self.text.len() {
+            return None;
+        }
+
+        let record_line = self.line;
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        let mut field_started = false;
+        let mut chars = self.text[self.offset..].char_indices().peekable();
+
+        while let Some((ix, character)) = chars.next() {
+            if in_quotes {
+                match character {
+                    '"' if chars.peek().is_some_and(|(_, next)| *next == '"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    '"' => in_quotes = false,
+                    '\n' => {
+                        self.line += 1;
+                        field.push('\n');
+                    }
+                    _ => field.push(character),
+                }
+                continue;
+            }
+
+            match character {
+                '"' if !field_started => {
+                    in_quotes = true;
+                    field_started = true;
+                }
+                '\r' if chars.peek().is_some_and(|(_, next)| *next == '\n') => {}
+                '\n' => {
+                    self.offset += ix + 1;
+                    self.line += 1;
+                    fields.push(field);
+                    return Some(Record {
+                        line: record_line,
+                        fields,
+                    });
+                }
+                character if character == self.delimiter => {
+                    fields.push(std::mem::take(&mut field));
+                    field_started = false;
+                }
+                _ => {
+                    field.push(character);
+                    field_started = true;
+                }
+            }
+        }
+
+        self.offset = self.text.len();
+        fields.push(field);
+        Some(Record {
+            line: record_line,
+            fields,
+        })
+    }
+}
+
+/// Compares two fields, ordering numbers by their value and placing them before text.
+pub fn compare_fields(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a
+            .to_lowercase()
+            .cmp(&b.to_lowercase())
+            .then_with(|| a.cmp(b)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(text: &str, delimiter: char) -> Vec<(u32, Vec<String>)> {
+        Records::new(text, delimiter)
+            .map(|record| (record.line, record.fields))
+            .collect()
+    }
+
+    fn row(line: u32, fields: &[&str]) -> (u32, Vec<String>) {
+        (line, fields.iter().map(|field| field.to_string()).collect())
+    }
+
+    #[test]
+    fn test_records() {
+        assert_eq!(
+            records("name,age\nAda,36\r\nGrace,85\n", ','),
+            vec![
+                row(0, &["name", "age"]),
+                row(1, &["Ada", "36"]),
+                row(2, &["Grace", "85"]),
+            ]
+        );
+        assert_eq!(
+            records("\u{feff}a\tb\n1\t\n", '\t'),
+            vec![row(0, &["a", "b"]), row(1, &["1", ""])]
+        );
+    }
+
+    #[test]
+    fn test_quoted_records() {
+        assert_eq!(
+            records(
+                "id,quote\n1,\"Hello, \"\"world\"\"\"\n2,\"two\nlines\"\n3,unterminated \"quote\n",
+                ','
+            ),
+            vec![
+                row(0, &["id", "quote"]),
+                row(1, &["1", "Hello, \"world\""]),
+                row(2, &["2", "two\nlines"]),
+                row(4, &["3", "unterminated \"quote"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_fields() {
+        let mut fields = vec!["b", "10", "A", "9", "", "-1.5"];
+        fields.sort_by(|a, b| compare_fields(a, b));
+        assert_eq!(fields, vec!["-1.5", "9", "10", "", "A", "b"]);
+    }
+
+    #[test]
+    fn test_delimiter_for_path() {
+        assert_eq!(delimiter_for_path(Path::new("data/people.CSV")), Some(','));
+        assert_eq!(delimiter_for_path(Path::new("export.tsv")), Some('\t'));
+        assert_eq!(delimiter_for_path(Path::new("README.md")), None);
+    }
+}
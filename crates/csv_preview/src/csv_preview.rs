@@ -0,0 +1,19 @@
+use gpui::{actions, App};
+use workspace::Workspace;
+
+pub mod csv_parser;
+pub mod csv_table_view;
+pub mod parquet_file;
+
+actions!(csv, [ToggleTableView]);
+
+pub fn init(cx: &mut App) {
+    workspace::register_project_item::<csv_table_view::CsvTableView>(cx);
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+        csv_table_view::CsvTableView::register(workspace, window, cx);
+    })
+    .detach();
+}
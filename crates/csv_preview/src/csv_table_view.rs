@@ -0,0 +1,696 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::{Context as _, Result};
+use editor::scroll::Autoscroll;
+use editor::{Editor, EditorEvent};
+use futures::{channel::mpsc, SinkExt as _, StreamExt as _};
+use gpui::{
+    px, uniform_list, App, ClickEvent, Context, Entity, EntityId, EventEmitter, FocusHandle,
+    Focusable, InteractiveElement, IntoElement, ParentElement, Pixels, Render, Styled,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, Window,
+};
+use language::Point as BufferPoint;
+use project::Project;
+use ui::{prelude::*, Tooltip};
+use util::paths::PathExt;
+use workspace::item::{Item, ProjectItem};
+use workspace::{Pane, Workspace};
+
+use crate::csv_parser::{compare_fields, delimiter_for_path, Record, Records};
+use crate::parquet_file::{parquet_records, ParquetFile};
+use crate::ToggleTableView;
+
+const REPARSE_DEBOUNCE: Duration = Duration::from_millis(200);
+/// The number of records parsed before they are shown, so that large files appear
+/// progressively instead of all at once.
+const RECORDS_PER_BATCH: usize = 5_000;
+/// The estimated width of a character in a cell, used to size the columns.
+const CHARACTER_WIDTH: Pixels = px(7.5);
+const CELL_PADDING: Pixels = px(16.);
+const MIN_COLUMN_CHARACTERS: usize = 4;
+const MAX_COLUMN_CHARACTERS: usize = 40;
+
+/// A table view of a CSV or TSV file open in an editor, with the first row as its header, or
+/// of a Parquet file.
+pub struct CsvTableView {
+    source: TableSource,
+    focus_handle: FocusHandle,
+    filter_editor: Entity<Editor>,
+    headers: Vec<SharedString>,
+    rows: Vec<TableRow>,
+    /// The indices of the rows matching the filter, in the order they are shown.
+    visible_rows: Vec<usize>,
+    /// The width of each column, in characters.
+    column_widths: Vec<usize>,
+    sort: Option<ColumnSort>,
+    is_loading: bool,
+    error: Option<SharedString>,
+    scroll_handle: UniformListScrollHandle,
+    _parse_task: Option<Task<Result<()>>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+/// Where the rows of a table view come from.
+enum TableSource {
+    /// A CSV or TSV file open in an editor, which the table view can be toggled with.
+    Editor {
+        editor: Entity<Editor>,
+        workspace: WeakEntity<Workspace>,
+    },
+    /// A Parquet file, which is only shown as a table since it isn't text.
+    Parquet(Entity<ParquetFile>),
+}
+
+/// What the records of a table view are parsed from, in the background.
+enum RecordsInput {
+    Text { text: String, delimiter: char },
+    Parquet(PathBuf),
+}
+
+struct TableRow {
+    /// The line in the file on which the row starts.
+    line: u32,
+    cells: Vec<SharedString>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct ColumnSort {
+    column: usize,
+    descending: bool,
+}
+
+impl CsvTableView {
+    pub fn register(workspace: &mut Workspace, _window: &mut Window, _cx: &mut Context<Workspace>) {
+        workspace.register_action(|workspace, _: &ToggleTableView, window, cx| {
+            Self::toggle(workspace, window, cx);
+        });
+    }
+
+    /// Switches between a CSV editor and its table view, within the active pane.
+    fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let Some(active_item) = workspace.active_item(cx) else {
+            return;
+        };
+
+        if let Some(table_view) = active_item.downcast::<CsvTableView>() {
+            let Some(editor) = table_view.read(cx).editor().cloned() else {
+                return;
+            };
+            workspace
+                .active_pane()
+                .update(cx, |pane, cx| match pane.index_for_item(&editor) {
+                    Some(ix) => pane.activate_item(ix, true, true, window, cx),
+                    None => pane.add_item(Box::new(editor), true, true, None, window, cx),
+                });
+        } else if let Some(editor) = Self::resolve_active_item_as_csv_editor(workspace, cx) {
+            let existing_view = workspace
+                .active_pane()
+                .read(cx)
+                .items_of_type::<CsvTableView>()
+                .find(|view| view.read(cx).editor() == Some(&editor));
+            let view = existing_view.unwrap_or_else(|| {
+                let source = TableSource::Editor {
+                    editor,
+                    workspace: workspace.weak_handle(),
+                };
+                cx.new(|cx| Self::new(source, window, cx))
+            });
+            workspace
+                .active_pane()
+                .update(cx, |pane, cx| match pane.index_for_item(&view) {
+                    Some(ix) => pane.activate_item(ix, true, true, window, cx),
+                    None => pane.add_item(Box::new(view), true, true, None, window, cx),
+                });
+        }
+    }
+
+    pub fn resolve_active_item_as_csv_editor(
+        workspace: &Workspace,
+        cx: &mut Context<Workspace>,
+    ) -> Option<Entity<Editor>> {
+        let editor = workspace
+            .active_item(cx)
+            .and_then(|item| item.act_as::<Editor>(cx))?;
+        Self::delimiter_for_editor(&editor, cx).map(|_| editor)
+    }
+
+    /// Returns the editor that the table view can be toggled with, unless it shows a Parquet
+    /// file.
+    fn editor(&self) -> Option<&Entity<Editor>> {
+        match &self.source {
+            TableSource::Editor { editor, .. } => Some(editor),
+            TableSource::Parquet(_) => None,
+        }
+    }
+
+    fn delimiter_for_editor(editor: &Entity<Editor>, cx: &App) -> Option<char> {
+        let buffer = editor.read(cx).buffer().read(cx).as_singleton()?;
+        let file = buffer.read(cx).file()?;
+        delimiter_for_path(file.path())
+    }
+
+    fn records_input(&self, cx: &App) -> Option<RecordsInput> {
+        match &self.source {
+            TableSource::Editor { editor, .. } => Some(RecordsInput::Text {
+                delimiter: Self::delimiter_for_editor(editor, cx)?,
+                text: editor.read(cx).buffer().read(cx).snapshot(cx).text(),
+            }),
+            TableSource::Parquet(file) => Some(RecordsInput::Parquet(
+                file.read(cx).abs_path().to_path_buf(),
+            )),
+        }
+    }
+
+    fn new(source: TableSource, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let filter_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Filter rows…", cx);
+            editor
+        });
+
+        let filter_subscription =
+            cx.subscribe(&filter_editor, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.update_visible_rows(cx);
+                }
+            });
+        let mut subscriptions = vec![filter_subscription];
+        if let TableSource::Editor { editor, .. } = &source {
+            subscriptions.push(cx.subscribe_in(
+                editor,
+                window,
+                |this, _, event: &EditorEvent, window, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        this.parse(true, window, cx);
+                    }
+                },
+            ));
+        }
+
+        let mut this = Self {
+            source,
+            focus_handle: cx.focus_handle(),
+            filter_editor,
+            headers: Vec::new(),
+            rows: Vec::new(),
+            visible_rows: Vec::new(),
+            column_widths: Vec::new(),
+            sort: None,
+            is_loading: true,
+            error: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            _parse_task: None,
+            _subscriptions: subscriptions,
+        };
+        this.parse(false, window, cx);
+        this
+    }
+
+    /// Parses the editor's text, or reads the Parquet file, in the background, showing its rows
+    /// in batches as they are parsed.
+    fn parse(&mut self, wait_for_debounce: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self._parse_task = Some(cx.spawn_in(window, async move |this, cx| {
+            if wait_for_debounce {
+                // Wait for the user to stop typing
+                cx.background_executor().timer(REPARSE_DEBOUNCE).await;
+            }
+
+            let Some(input) = this.update(cx, |this, cx| {
+                let input = this.records_input(cx);
+                this.is_loading = input.is_some();
+                this.error = None;
+                cx.notify();
+                input
+            })?
+            else {
+                return Ok(());
+            };
+
+            // Parsing stops once the receiver is dropped, i.e. when parsing starts over.
+            let (batches_tx, mut batches_rx) = mpsc::channel(1);
+            cx.background_spawn(async move {
+                match input {
+                    RecordsInput::Text { text, delimiter } => {
+                        send_batches(Records::new(&text, delimiter).map(Ok), batches_tx).await
+                    }
+                    RecordsInput::Parquet(abs_path) => {
+                        let records = std::fs::File::open(&abs_path)
+                            .map_err(anyhow::Error::from)
+                            .and_then(parquet_records)
+                            .with_context(|| format!("failed to read {abs_path:?}"));
+                        match records {
+                            Ok(records) => send_batches(records, batches_tx).await,
+                            Err(error) => {
+                                send_batches(std::iter::once(Err(error)), batches_tx).await
+                            }
+                        }
+                    }
+                }
+            })
+            .detach();
+
+            let mut is_first_batch = true;
+            while let Some(batch) = batches_rx.next().await {
+                this.update(cx, |this, cx| match batch {
+                    Ok(batch) => this.append_records(batch, is_first_batch, cx),
+                    Err(error) => {
+                        this.error = Some(format!("{error:#}").into());
+                        cx.notify();
+                    }
+                })?;
+                is_first_batch = false;
+            }
+
+            this.update(cx, |this, cx| {
+                this.is_loading = false;
+                this.update_visible_rows(cx);
+            })
+        }));
+    }
+
+    fn append_records(&mut self, records: Vec<Record>, replace: bool, cx: &mut Context<Self>) {
+        let mut records = records.into_iter();
+        if replace {
+            self.headers = records
+                .next()
+                .map(|record| record.fields.into_iter().map(cell_text).collect())
+                .unwrap_or_default();
+            self.rows.clear();
+            self.visible_rows.clear();
+            self.column_widths = self
+                .headers
+                .iter()
+                // Leave room for the sort indicator.
+                .map(|header| header.chars().count() + 2)
+                .collect();
+        }
+
+        let first_new_row = self.rows.len();
+        for record in records {
+            if record.fields.len() > self.column_widths.len() {
+                self.column_widths.resize(record.fields.len(), 0);
+            }
+            for (width, field) in self.column_widths.iter_mut().zip(&record.fields) {
+                *width = (*width).max(field.chars().count());
+            }
+            self.rows.push(TableRow {
+                line: record.line,
+                cells: record.fields.into_iter().map(cell_text).collect(),
+            });
+        }
+
+        // Rows are only sorted once the whole file has been parsed.
+        let query = self.filter_query(cx);
+        let new_rows = (first_new_row..self.rows.len())
+            .filter(|ix| row_matches(&self.rows[*ix], &query))
+            .collect::<Vec<_>>();
+        self.visible_rows.extend(new_rows);
+        cx.notify();
+    }
+
+    fn filter_query(&self, cx: &App) -> String {
+        self.filter_editor.read(cx).text(cx).trim().to_lowercase()
+    }
+
+    fn update_visible_rows(&mut self, cx: &mut Context<Self>) {
+        let query = self.filter_query(cx);
+        let mut visible_rows = (0..self.rows.len())
+            .filter(|ix| row_matches(&self.rows[*ix], &query))
+            .collect::<Vec<_>>();
+
+        if let Some(sort) = self.sort {
+            let cell = |ix: usize| {
+                self.rows[ix]
+                    .cells
+                    .get(sort.column)
+                    .map_or("", |cell| cell.as_ref())
+            };
+            visible_rows.sort_by(|a, b| {
+                let ordering = compare_fields(cell(*a), cell(*b));
+                if sort.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+
+        self.visible_rows = visible_rows;
+        cx.notify();
+    }
+
+    /// Cycles the sorting of a column from ascending to descending to unsorted.
+    fn toggle_sort(&mut self, column: usize, cx: &mut Context<Self>) {
+        self.sort = match self.sort {
+            Some(sort) if sort.column == column && !sort.descending => Some(ColumnSort {
+                column,
+                descending: true,
+            }),
+            Some(sort) if sort.column == column => None,
+            _ => Some(ColumnSort {
+                column,
+                descending: false,
+            }),
+        };
+        self.update_visible_rows(cx);
+    }
+
+    /// Moves the cursor of the editor to the line of the row and switches to the editor.
+    fn reveal_row_in_editor(&mut self, line: u32, window: &mut Window, cx: &mut Context<Self>) {
+        let TableSource::Editor { editor, workspace } = &self.source else {
+            return;
+        };
+        editor.update(cx, |editor, cx| {
+            let point = BufferPoint::new(line, 0);
+            editor.change_selections(Some(Autoscroll::center()), window, cx, |selections| {
+                selections.select_ranges(vec![point..point])
+            });
+        });
+        if let Some(workspace) = workspace.upgrade() {
+            workspace.update(cx, |workspace, cx| Self::toggle(workspace, window, cx));
+        }
+    }
+
+    fn column_width(&self, column: usize) -> Pixels {
+        let characters =
+            self.column_widths[column].clamp(MIN_COLUMN_CHARACTERS, MAX_COLUMN_CHARACTERS);
+        CHARACTER_WIDTH * characters as f32 + CELL_PADDING
+    }
+
+    fn line_number_width(&self) -> Pixels {
+        let max_line = self.rows.last().map_or(0, |row| row.line + 1);
+        CHARACTER_WIDTH * max_line.to_string().len() as f32 + CELL_PADDING
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        let row_count = if self.visible_rows.len() == self.rows.len() {
+            format!("{} rows", self.rows.len())
+        } else {
+            format!("{} of {} rows", self.visible_rows.len(), self.rows.len())
+        };
+
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                h_flex()
+                    .flex_1()
+                    .gap_1()
+                    .child(
+                        Icon::new(IconName::MagnifyingGlass)
+                            .size(IconSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(self.filter_editor.clone()),
+            )
+            .when(self.is_loading, |this| {
+                this.child(
+                    Label::new("Loading…")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .children(self.error.clone().map(|error| {
+                Label::new(error)
+                    .size(LabelSize::Small)
+                    .color(Color::Error)
+                    .single_line()
+            }))
+            .child(
+                Label::new(row_count)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .when(self.editor().is_some(), |this| {
+                this.child(
+                    IconButton::new("show-source", IconName::Code)
+                        .icon_size(IconSize::Small)
+                        .tooltip(move |window, cx| {
+                            Tooltip::for_action_in(
+                                "Show Source",
+                                &ToggleTableView,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                        })
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(Box::new(ToggleTableView), cx)
+                        }),
+                )
+            })
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().title_bar_background)
+            .child(div().flex_none().w(self.line_number_width()))
+            .children((0..self.column_widths.len()).map(|column| {
+                let header = self.headers.get(column).cloned().unwrap_or_default();
+                let sort_icon = self.sort.filter(|sort| sort.column == column).map(|sort| {
+                    if sort.descending {
+                        IconName::ChevronDown
+                    } else {
+                        IconName::ChevronUp
+                    }
+                });
+
+                h_flex()
+                    .id(("column-header", column))
+                    .flex_none()
+                    .w(self.column_width(column))
+                    .px_2()
+                    .py_1()
+                    .gap_1()
+                    .overflow_hidden()
+                    .cursor_pointer()
+                    .hover(|style| style.bg(cx.theme().colors().element_hover))
+                    .child(
+                        Label::new(header)
+                            .size(LabelSize::Small)
+                            .weight(FontWeight::SEMIBOLD)
+                            .single_line(),
+                    )
+                    .children(sort_icon.map(|icon| Icon::new(icon).size(IconSize::XSmall)))
+                    .tooltip(Tooltip::text("Sort"))
+                    .on_click(cx.listener(move |this, _, _, cx| this.toggle_sort(column, cx)))
+            }))
+    }
+
+    fn render_rows(
+        &mut self,
+        range: std::ops::Range<usize>,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        let line_number_width = self.line_number_width();
+        let column_widths = (0..self.column_widths.len())
+            .map(|column| self.column_width(column))
+            .collect::<Vec<_>>();
+
+        self.visible_rows[range]
+            .iter()
+            .map(|row_ix| {
+                let row = &self.rows[*row_ix];
+                let line = row.line;
+                h_flex()
+                    .id(("row", *row_ix))
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .hover(|style| style.bg(cx.theme().colors().element_hover))
+                    .child(
+                        div().flex_none().w(line_number_width).px_2().child(
+                            Label::new((line + 1).to_string())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                    )
+                    .children(column_widths.iter().enumerate().map(|(column, width)| {
+                        div().flex_none().w(*width).px_2().overflow_hidden().child(
+                            Label::new(row.cells.get(column).cloned().unwrap_or_default())
+                                .size(LabelSize::Small)
+                                .single_line(),
+                        )
+                    }))
+                    .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                        if event.down.click_count == 2 {
+                            this.reveal_row_in_editor(line, window, cx);
+                        }
+                    }))
+                    .into_any_element()
+            })
+            .collect()
+    }
+}
+
+/// Returns the text shown for a field, which is kept on a single line.
+fn cell_text(field: String) -> SharedString {
+    if field.contains('\n') {
+        field.replace(['\r', '\n'], " ").into()
+    } else {
+        field.into()
+    }
+}
+
+/// Sends the records to be shown in batches, stopping at the first error.
+async fn send_batches(
+    records: impl Iterator<Item = Result<Record>>,
+    mut batches_tx: mpsc::Sender<Result<Vec<Record>>>,
+) {
+    let mut batch = Vec::with_capacity(RECORDS_PER_BATCH);
+    for record in records {
+        let record = match record {
+            Ok(record) => record,
+            Err(error) => {
+                batches_tx.send(Ok(batch)).await.ok();
+                batches_tx.send(Err(error)).await.ok();
+                return;
+            }
+        };
+        batch.push(record);
+        if batch.len() == RECORDS_PER_BATCH
+            && batches_tx
+                .send(Ok(std::mem::take(&mut batch)))
+                .await
+                .is_err()
+        {
+            return;
+        }
+    }
+    batches_tx.send(Ok(batch)).await.ok();
+}
+
+fn row_matches(row: &TableRow, query: &str) -> bool {
+    query.is_empty()
+        || row
+            .cells
+            .iter()
+            .any(|cell| cell.to_lowercase().contains(query))
+}
+
+impl Focusable for CsvTableView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+pub enum TableViewEvent {}
+
+impl EventEmitter<TableViewEvent> for CsvTableView {}
+
+impl Item for CsvTableView {
+    type Event = TableViewEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Blocks))
+    }
+
+    fn tab_content_text(&self, _window: &Window, cx: &App) -> Option<SharedString> {
+        let description = match &self.source {
+            TableSource::Editor { editor, .. } => editor.read(cx).tab_description(0, cx),
+            TableSource::Parquet(file) => file
+                .read(cx)
+                .project_path()
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string().into()),
+        };
+        Some(
+            description
+                .map(|description| format!("Table {description}").into())
+                .unwrap_or_else(|| "Table".into()),
+        )
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        let TableSource::Parquet(file) = &self.source else {
+            return None;
+        };
+        let abs_path = file.read(cx).abs_path();
+        Some(abs_path.compact().to_string_lossy().to_string().into())
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(EntityId, &dyn project::ProjectItem),
+    ) {
+        if let TableSource::Parquet(file) = &self.source {
+            f(file.entity_id(), file.read(cx))
+        }
+    }
+
+    fn is_singleton(&self, _cx: &App) -> bool {
+        matches!(self.source, TableSource::Parquet(_))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("CSV Table View Opened")
+    }
+
+    fn to_item_events(_event: &Self::Event, _f: impl FnMut(workspace::item::ItemEvent)) {}
+}
+
+impl Render for CsvTableView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let table_width = self.line_number_width()
+            + (0..self.column_widths.len())
+                .map(|column| self.column_width(column))
+                .fold(px(0.), |total, width| total + width);
+
+        v_flex()
+            .id("CsvTableView")
+            .key_context("CsvTableView")
+            .track_focus(&self.focus_handle(cx))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_toolbar(cx))
+            .child(
+                div()
+                    .id("csv-table")
+                    .flex_1()
+                    .min_h_0()
+                    .overflow_x_scroll()
+                    .child(
+                        // The header stays in place while the rows below it scroll.
+                        v_flex()
+                            .h_full()
+                            .min_w_full()
+                            .w(table_width)
+                            .child(self.render_header(cx))
+                            .child(
+                                uniform_list(
+                                    cx.entity(),
+                                    "csv-rows",
+                                    self.visible_rows.len(),
+                                    |this, range, _, cx| this.render_rows(range, cx),
+                                )
+                                .flex_1()
+                                .track_scroll(self.scroll_handle.clone()),
+                            ),
+                    ),
+            )
+    }
+}
+
+impl ProjectItem for CsvTableView {
+    type Item = ParquetFile;
+
+    fn for_project_item(
+        _: Entity<Project>,
+        _: &Pane,
+        item: Entity<Self::Item>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(TableSource::Parquet(item), window, cx)
+    }
+}
@@ -0,0 +1,201 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use gpui::{App, AppContext as _, Entity, Task};
+use parquet::{
+    file::reader::{ChunkReader, FileReader, SerializedFileReader},
+    record::{reader::RowIter, Field},
+};
+use project::{Project, ProjectEntryId, ProjectItem, ProjectPath};
+
+use crate::csv_parser::Record;
+
+/// Returns whether the file at `path` is an Apache Parquet file.
+pub fn is_parquet_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("parquet"))
+}
+
+/// A Parquet file in a local worktree. Its rows are read straight from disk when it is shown,
+/// since it can't be opened as text.
+pub struct ParquetFile {
+    project_path: ProjectPath,
+    entry_id: Option<ProjectEntryId>,
+    abs_path: PathBuf,
+}
+
+impl ParquetFile {
+    pub fn abs_path(&self) -> &Path {
+        &self.abs_path
+    }
+
+    pub fn project_path(&self) -> &ProjectPath {
+        &self.project_path
+    }
+}
+
+impl ProjectItem for ParquetFile {
+    fn try_open(
+        project: &Entity<Project>,
+        path: &ProjectPath,
+        cx: &mut App,
+    ) -> Option<Task<Result<Entity<Self>>>> {
+        if !is_parquet_path(&path.path) {
+            return None;
+        }
+        let worktree = project.read(cx).worktree_for_id(path.worktree_id, cx)?;
+        let worktree = worktree.read(cx);
+        // Rows are read straight from disk, which isn't possible for remote projects.
+        if !worktree.is_local() {
+            return None;
+        }
+        let abs_path = worktree.abs_path().join(&path.path);
+        let entry_id = worktree.entry_for_path(&path.path).map(|entry| entry.id);
+        let project_path = path.clone();
+
+        Some(cx.spawn(async move |cx| {
+            let is_file = cx
+                .background_spawn({
+                    let abs_path = abs_path.clone();
+                    async move { abs_path.is_file() }
+                })
+                .await;
+            if !is_file {
+                return Err(anyhow!("{abs_path:?} is not a file"));
+            }
+            cx.new(|_| ParquetFile {
+                project_path,
+                entry_id,
+                abs_path,
+            })
+        }))
+    }
+
+    fn entry_id(&self, _: &App) -> Option<ProjectEntryId> {
+        self.entry_id
+    }
+
+    fn project_path(&self, _: &App) -> Option<ProjectPath> {
+        Some(self.project_path.clone())
+    }
+
+    fn is_dirty(&self) -> bool {
+        false
+    }
+}
+
+/// Returns the records of a Parquet file: a header with the names of its columns, followed by
+/// its rows, which are decoded one row group at a time as the iterator advances.
+pub fn parquet_records<R: ChunkReader + 'static>(
+    reader: R,
+) -> Result<impl Iterator<Item = Result<Record>> + Send> {
+    let reader = SerializedFileReader::new(reader)?;
+    let header = Record {
+        line: 0,
+        fields: reader
+            .metadata()
+            .file_metadata()
+            .schema()
+            .get_fields()
+            .iter()
+            .map(|field| field.name().to_string())
+            .collect(),
+    };
+    let rows =
+        RowIter::from_file_into(Box::new(reader))
+            .enumerate()
+            .map(|(ix, row)| -> Result<Record> {
+                Ok(Record {
+                    line: ix as u32,
+                    fields: row?
+                        .get_column_iter()
+                        .map(|(_, field)| field_text(field))
+                        .collect(),
+                })
+            });
+    Ok(std::iter::once(Ok(header)).chain(rows))
+}
+
+fn field_text(field: &Field) -> String {
+    match field {
+        Field::Null => String::new(),
+        // Strings are quoted when displayed.
+        Field::Str(text) => text.clone(),
+        field => field.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use parquet::{
+        column::writer::ColumnWriter, data_type::ByteArray, file::writer::SerializedFileWriter,
+        schema::parser::parse_message_type,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_parquet_records() {
+        let schema = parse_message_type(
+            "message schema {
+                REQUIRED INT32 id;
+                OPTIONAL BYTE_ARRAY name (UTF8);
+                REQUIRED DOUBLE score;
+            }",
+        )
+        .unwrap();
+        let mut buffer = Vec::new();
+        let mut writer =
+            SerializedFileWriter::new(&mut buffer, Arc::new(schema), Default::default()).unwrap();
+        let mut row_group = writer.next_row_group().unwrap();
+        while let Some(mut column) = row_group.next_column().unwrap() {
+            match column.untyped() {
+                ColumnWriter::Int32ColumnWriter(writer) => {
+                    writer.write_batch(&[1, 2, 3], None, None).unwrap();
+                }
+                ColumnWriter::ByteArrayColumnWriter(writer) => {
+                    let names = [ByteArray::from("Ada"), ByteArray::from("Grace, Hopper")];
+                    writer.write_batch(&names, Some(&[1, 1, 0]), None).unwrap();
+                }
+                ColumnWriter::DoubleColumnWriter(writer) => {
+                    writer.write_batch(&[36.5, 2.25, 0.5], None, None).unwrap();
+                }
+                _ => unreachable!(),
+            }
+            column.close().unwrap();
+        }
+        row_group.close().unwrap();
+        writer.close().unwrap();
+
+        let records = parquet_records(Bytes::from(buffer))
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let record = |line: u32, fields: &[&str]| Record {
+            line,
+            fields: fields.iter().map(|field| field.to_string()).collect(),
+        };
+        assert_eq!(
+            records,
+            vec![
+                record(0, &["id", "name", "score"]),
+                record(0, &["1", "Ada", "36.5"]),
+                record(1, &["2", "Grace, Hopper", "2.25"]),
+                record(2, &["3", "", "0.5"]),
+            ]
+        );
+
+        assert!(parquet_records(Bytes::from_static(b"id,name\n1,Ada\n")).is_err());
+    }
+
+    #[test]
+    fn test_is_parquet_path() {
+        assert!(is_parquet_path(Path::new("data/events.parquet")));
+        assert!(is_parquet_path(Path::new("EXPORT.PARQUET")));
+        assert!(!is_parquet_path(Path::new("people.csv")));
+    }
+}
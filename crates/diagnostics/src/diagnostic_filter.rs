@@ -0,0 +1,164 @@
+use collections::{BTreeMap, BTreeSet};
+use language::{Diagnostic, DiagnosticSeverity};
+use lsp::NumberOrString;
+use std::path::Path;
+use util::paths::PathMatcher;
+
+/// Identifies the rule that produced a diagnostic, e.g. `clippy(needless_return)`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct DiagnosticRule {
+    pub source: Option<String>,
+    pub code: String,
+}
+
+impl DiagnosticRule {
+    pub fn for_diagnostic(diagnostic: &Diagnostic) -> Option<Self> {
+        let code = match diagnostic.code.as_ref()? {
+            NumberOrString::Number(code) => code.to_string(),
+            NumberOrString::String(code) => code.clone(),
+        };
+        Some(Self {
+            source: diagnostic.source.clone(),
+            code,
+        })
+    }
+
+    pub fn label(&self) -> String {
+        match &self.source {
+            Some(source) => format!("{source}({})", self.code),
+            None => self.code.clone(),
+        }
+    }
+}
+
+/// The severities that diagnostics can be filtered by, along with their labels.
+pub(crate) const SEVERITIES: [(DiagnosticSeverity, &str); 4] = [
+    (DiagnosticSeverity::ERROR, "Errors"),
+    (DiagnosticSeverity::WARNING, "Warnings"),
+    (DiagnosticSeverity::INFORMATION, "Information"),
+    (DiagnosticSeverity::HINT, "Hints"),
+];
+
+/// Narrows down the diagnostics displayed in the project diagnostics view.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DiagnosticFilter {
+    /// Sources (e.g. `rustc`, `clippy`) whose diagnostics are hidden.
+    pub excluded_sources: BTreeSet<String>,
+    /// When set, only diagnostics produced by this rule are shown.
+    pub rule: Option<DiagnosticRule>,
+    /// When set, only diagnostics in paths matching these globs are shown.
+    pub paths: Option<PathMatcher>,
+    /// When set, only diagnostics of these severities are shown. Otherwise errors are shown,
+    /// along with warnings when they're included.
+    pub severities: Option<BTreeSet<DiagnosticSeverity>>,
+}
+
+impl DiagnosticFilter {
+    pub fn is_empty(&self) -> bool {
+        self.excluded_sources.is_empty()
+            && self.rule.is_none()
+            && self.paths.is_none()
+            && self.severities.is_none()
+    }
+
+    pub fn matches_severity(&self, severity: DiagnosticSeverity, include_warnings: bool) -> bool {
+        match &self.severities {
+            Some(severities) => severities.contains(&severity),
+            None => {
+                severity == DiagnosticSeverity::ERROR
+                    || (include_warnings && severity == DiagnosticSeverity::WARNING)
+            }
+        }
+    }
+
+    /// Shows or hides the diagnostics of a severity, starting from the severities that are
+    /// currently shown.
+    pub fn toggle_severity(&mut self, severity: DiagnosticSeverity, include_warnings: bool) {
+        let mut severities = self.severities.take().unwrap_or_else(|| {
+            SEVERITIES
+                .iter()
+                .map(|(severity, _)| *severity)
+                .filter(|severity| self.matches_severity(*severity, include_warnings))
+                .collect()
+        });
+        if !severities.remove(&severity) {
+            severities.insert(severity);
+        }
+        self.severities = Some(severities);
+    }
+
+    pub fn matches_path(&self, path: &Path) -> bool {
+        self.paths
+            .as_ref()
+            .map_or(true, |matcher| matcher.is_match(path))
+    }
+
+    pub fn matches(&self, diagnostic: &Diagnostic) -> bool {
+        if let Some(source) = &diagnostic.source {
+            if self.excluded_sources.contains(source) {
+                return false;
+            }
+        }
+        match &self.rule {
+            Some(rule) => DiagnosticRule::for_diagnostic(diagnostic).as_ref() == Some(rule),
+            None => true,
+        }
+    }
+
+    /// Parses a comma-separated list of path globs, as typed into the filter editor.
+    pub fn set_path_globs(&mut self, text: &str) -> anyhow::Result<()> {
+        let globs = text
+            .split(',')
+            .map(str::trim)
+            .filter(|glob| !glob.is_empty())
+            .collect::<Vec<_>>();
+        self.paths = if globs.is_empty() {
+            None
+        } else {
+            Some(PathMatcher::new(globs)?)
+        };
+        Ok(())
+    }
+}
+
+/// The severities, sources and rules of the diagnostics in a single path, used to populate the
+/// filter menu.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DiagnosticKinds {
+    pub severities: BTreeMap<DiagnosticSeverity, usize>,
+    pub sources: BTreeMap<String, usize>,
+    pub rules: BTreeMap<DiagnosticRule, usize>,
+}
+
+impl DiagnosticKinds {
+    pub fn is_empty(&self) -> bool {
+        self.severities.is_empty()
+    }
+
+    /// Counts the severity of a diagnostic, which isn't yet known to be shown.
+    pub fn insert_severity(&mut self, severity: DiagnosticSeverity) {
+        *self.severities.entry(severity).or_default() += 1;
+    }
+
+    /// Counts the source and rule of a diagnostic of a severity that is shown.
+    pub fn insert(&mut self, diagnostic: &Diagnostic) {
+        if let Some(source) = &diagnostic.source {
+            *self.sources.entry(source.clone()).or_default() += 1;
+        }
+        if let Some(rule) = DiagnosticRule::for_diagnostic(diagnostic) {
+            *self.rules.entry(rule).or_default() += 1;
+        }
+    }
+
+    pub fn extend(&mut self, other: &Self) {
+        for (severity, count) in &other.severities {
+            *self.severities.entry(*severity).or_default() += count;
+        }
+        for (source, count) in &other.sources {
+            *self.sources.entry(source.clone()).or_default() += count;
+        }
+        for (rule, count) in &other.rules {
+            *self.rules.entry(rule.clone()).or_default() += count;
+        }
+    }
+}
@@ -1,3 +1,4 @@
+mod diagnostic_filter;
 pub mod items;
 mod toolbar_controls;
 
@@ -5,7 +6,8 @@ mod toolbar_controls;
 mod diagnostics_tests;
 
 use anyhow::Result;
-use collections::{BTreeSet, HashSet};
+use collections::{BTreeMap, BTreeSet, HashSet};
+use diagnostic_filter::{DiagnosticFilter, DiagnosticKinds, DiagnosticRule};
 use editor::{
     diagnostic_block_renderer,
     display_map::{BlockPlacement, BlockProperties, BlockStyle, CustomBlockId, RenderBlock},
@@ -14,16 +16,20 @@ use editor::{
     Editor, EditorEvent, ExcerptId, ExcerptRange, MultiBuffer, ToOffset,
 };
 use gpui::{
-    actions, div, svg, AnyElement, AnyView, App, AsyncApp, Context, Entity, EventEmitter,
-    FocusHandle, Focusable, Global, HighlightStyle, InteractiveElement, IntoElement, ParentElement,
-    Render, SharedString, Styled, StyledText, Subscription, Task, WeakEntity, Window,
+    actions, div, svg, AnyElement, AnyView, App, AsyncApp, AsyncWindowContext, Context, Entity,
+    EventEmitter, FocusHandle, Focusable, Global, HighlightStyle, InteractiveElement, IntoElement,
+    ParentElement, Render, SharedString, Styled, StyledText, Subscription, Task, WeakEntity,
+    Window,
 };
 use language::{
     Bias, Buffer, BufferRow, BufferSnapshot, Diagnostic, DiagnosticEntry, DiagnosticSeverity,
     Point, Selection, SelectionGoal, ToTreeSitterPoint,
 };
-use lsp::LanguageServerId;
-use project::{project_settings::ProjectSettings, DiagnosticSummary, Project, ProjectPath};
+use lsp::{CodeActionKind, LanguageServerId};
+use project::{
+    project_settings::ProjectSettings, CodeAction, DiagnosticSummary, LspAction, Project,
+    ProjectPath,
+};
 use settings::Settings;
 use std::{
     any::{Any, TypeId},
@@ -40,11 +46,22 @@ use ui::{h_flex, prelude::*, Icon, IconName, Label};
 use util::ResultExt;
 use workspace::{
     item::{BreadcrumbText, Item, ItemEvent, ItemHandle, TabContentParams},
+    notifications::NotificationId,
     searchable::SearchableItemHandle,
-    ItemNavHistory, ToolbarItemLocation, Workspace,
+    ItemNavHistory, Toast, ToolbarItemLocation, Workspace,
 };
 
-actions!(diagnostics, [Deploy, ToggleWarnings]);
+actions!(
+    diagnostics,
+    [
+        Deploy,
+        ToggleWarnings,
+        ToggleFilters,
+        ClearFilters,
+        ToggleGroupByRule,
+        FixAllOccurrences
+    ]
+);
 
 struct IncludeWarnings(bool);
 impl Global for IncludeWarnings {}
@@ -63,12 +80,22 @@ struct ProjectDiagnosticsEditor {
     path_states: Vec<PathState>,
     paths_to_update: BTreeSet<(ProjectPath, Option<LanguageServerId>)>,
     include_warnings: bool,
+    group_by_rule: bool,
+    filter: DiagnosticFilter,
+    diagnostic_kinds: BTreeMap<ProjectPath, DiagnosticKinds>,
+    show_filters: bool,
+    path_filter_editor: Entity<Editor>,
+    path_filter_error: bool,
     context: u32,
     update_excerpts_task: Option<Task<Result<()>>>,
+    fix_all_task: Option<Task<Result<()>>>,
     _subscription: Subscription,
 }
 
+/// The diagnostics of a path, or when grouping by rule, the diagnostics of a path that were
+/// produced by a rule. Groups without a rule, or all groups when grouping by file, have no rule.
 struct PathState {
+    rule: Option<DiagnosticRule>,
     path: ProjectPath,
     diagnostic_groups: Vec<DiagnosticGroupState>,
 }
@@ -94,49 +121,76 @@ impl Render for ProjectDiagnosticsEditor {
             0
         };
 
-        let child = if warning_count + self.summary.error_count == 0 {
-            let label = if self.summary.warning_count == 0 {
-                SharedString::new_static("No problems in workspace")
+        let child =
+            if self.filter.severities.is_none() && warning_count + self.summary.error_count == 0 {
+                let label = if self.summary.warning_count == 0 {
+                    SharedString::new_static("No problems in workspace")
+                } else {
+                    SharedString::new_static("No errors in workspace")
+                };
+                v_flex()
+                    .key_context("EmptyPane")
+                    .size_full()
+                    .gap_1()
+                    .justify_center()
+                    .items_center()
+                    .text_center()
+                    .bg(cx.theme().colors().editor_background)
+                    .child(Label::new(label).color(Color::Muted))
+                    .when(self.summary.warning_count > 0, |this| {
+                        let plural_suffix = if self.summary.warning_count > 1 {
+                            "s"
+                        } else {
+                            ""
+                        };
+                        let label = format!(
+                            "Show {} warning{}",
+                            self.summary.warning_count, plural_suffix
+                        );
+                        this.child(
+                            Button::new("diagnostics-show-warning-label", label).on_click(
+                                cx.listener(|this, _, window, cx| {
+                                    this.toggle_warnings(&Default::default(), window, cx);
+                                    cx.notify();
+                                }),
+                            ),
+                        )
+                    })
+            } else if self.path_states.is_empty()
+                && !self.filter.is_empty()
+                && self.update_excerpts_task.is_none()
+            {
+                v_flex()
+                    .size_full()
+                    .gap_1()
+                    .justify_center()
+                    .items_center()
+                    .text_center()
+                    .bg(cx.theme().colors().editor_background)
+                    .child(Label::new("No problems match the current filters").color(Color::Muted))
+                    .child(
+                        Button::new("diagnostics-clear-filters", "Clear Filters").on_click(
+                            cx.listener(|this, _, window, cx| {
+                                this.clear_filters(&ClearFilters, window, cx);
+                            }),
+                        ),
+                    )
             } else {
-                SharedString::new_static("No errors in workspace")
+                div().size_full().child(self.editor.clone())
             };
-            v_flex()
-                .key_context("EmptyPane")
-                .size_full()
-                .gap_1()
-                .justify_center()
-                .items_center()
-                .text_center()
-                .bg(cx.theme().colors().editor_background)
-                .child(Label::new(label).color(Color::Muted))
-                .when(self.summary.warning_count > 0, |this| {
-                    let plural_suffix = if self.summary.warning_count > 1 {
-                        "s"
-                    } else {
-                        ""
-                    };
-                    let label = format!(
-                        "Show {} warning{}",
-                        self.summary.warning_count, plural_suffix
-                    );
-                    this.child(
-                        Button::new("diagnostics-show-warning-label", label).on_click(cx.listener(
-                            |this, _, window, cx| {
-                                this.toggle_warnings(&Default::default(), window, cx);
-                                cx.notify();
-                            },
-                        )),
-                    )
-                })
-        } else {
-            div().size_full().child(self.editor.clone())
-        };
 
-        div()
+        v_flex()
             .key_context("Diagnostics")
             .track_focus(&self.focus_handle(cx))
             .size_full()
             .on_action(cx.listener(Self::toggle_warnings))
+            .on_action(cx.listener(Self::toggle_filters))
+            .on_action(cx.listener(Self::clear_filters))
+            .on_action(cx.listener(Self::toggle_group_by_rule))
+            .on_action(cx.listener(Self::fix_all_occurrences))
+            .when(self.show_filters, |this| {
+                this.child(self.render_filter_bar(cx))
+            })
             .child(child)
     }
 }
@@ -227,12 +281,45 @@ impl ProjectDiagnosticsEditor {
         })
         .detach();
 
+        let path_filter_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Include paths, e.g. src/**/*.rs, crates/*/tests", cx);
+            editor
+        });
+        cx.subscribe_in(
+            &path_filter_editor,
+            window,
+            |this, editor, event: &EditorEvent, window, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    let text = editor.read(cx).text(cx);
+                    let mut filter = this.filter.clone();
+                    match filter.set_path_globs(&text) {
+                        Ok(()) => {
+                            this.path_filter_error = false;
+                            this.set_filter(filter, window, cx);
+                        }
+                        Err(_) => {
+                            this.path_filter_error = true;
+                            cx.notify();
+                        }
+                    }
+                }
+            },
+        )
+        .detach();
+
         let project = project_handle.read(cx);
         let mut this = Self {
             project: project_handle.clone(),
             context,
             summary: project.diagnostic_summary(false, cx),
             include_warnings,
+            group_by_rule: false,
+            filter: DiagnosticFilter::default(),
+            diagnostic_kinds: BTreeMap::default(),
+            show_filters: false,
+            path_filter_editor,
+            path_filter_error: false,
             workspace,
             excerpts,
             focus_handle,
@@ -240,6 +327,7 @@ impl ProjectDiagnosticsEditor {
             path_states: Default::default(),
             paths_to_update: Default::default(),
             update_excerpts_task: None,
+            fix_all_task: None,
             _subscription: project_event_subscription,
         };
         this.update_all_excerpts(window, cx);
@@ -333,11 +421,321 @@ impl ProjectDiagnosticsEditor {
 
     fn toggle_warnings(&mut self, _: &ToggleWarnings, window: &mut Window, cx: &mut Context<Self>) {
         self.include_warnings = !self.include_warnings;
+        if let Some(severities) = self.filter.severities.as_mut() {
+            if self.include_warnings {
+                severities.insert(DiagnosticSeverity::WARNING);
+            } else {
+                severities.remove(&DiagnosticSeverity::WARNING);
+            }
+        }
         cx.set_global(IncludeWarnings(self.include_warnings));
         self.update_all_excerpts(window, cx);
         cx.notify();
     }
 
+    fn toggle_filters(&mut self, _: &ToggleFilters, window: &mut Window, cx: &mut Context<Self>) {
+        self.show_filters = !self.show_filters;
+        if self.show_filters {
+            self.path_filter_editor.focus_handle(cx).focus(window);
+        } else {
+            window.focus(&self.focus_handle);
+        }
+        cx.notify();
+    }
+
+    fn clear_filters(&mut self, _: &ClearFilters, window: &mut Window, cx: &mut Context<Self>) {
+        self.path_filter_error = false;
+        self.set_filter(DiagnosticFilter::default(), window, cx);
+        self.path_filter_editor.update(cx, |editor, cx| {
+            if !editor.text(cx).is_empty() {
+                editor.set_text("", window, cx);
+            }
+        });
+    }
+
+    /// Switches between grouping the diagnostics by file and by the rule that produced them.
+    /// The sections of the previous grouping are replaced as their paths are updated.
+    fn toggle_group_by_rule(
+        &mut self,
+        _: &ToggleGroupByRule,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.group_by_rule = !self.group_by_rule;
+        self.update_all_excerpts(window, cx);
+        cx.notify();
+    }
+
+    fn set_filter(
+        &mut self,
+        filter: DiagnosticFilter,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.filter = filter;
+        self.update_all_excerpts(window, cx);
+        cx.notify();
+    }
+
+    fn toggle_source(&mut self, source: String, window: &mut Window, cx: &mut Context<Self>) {
+        let mut filter = self.filter.clone();
+        if !filter.excluded_sources.remove(&source) {
+            filter.excluded_sources.insert(source);
+        }
+        self.set_filter(filter, window, cx);
+    }
+
+    fn toggle_severity(
+        &mut self,
+        severity: DiagnosticSeverity,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut filter = self.filter.clone();
+        filter.toggle_severity(severity, self.include_warnings);
+        self.set_filter(filter, window, cx);
+    }
+
+    fn set_rule(
+        &mut self,
+        rule: Option<DiagnosticRule>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut filter = self.filter.clone();
+        filter.rule = rule;
+        self.set_filter(filter, window, cx);
+    }
+
+    /// The sources and rules of all diagnostics in the project, ignoring the current filter.
+    fn diagnostic_kinds(&self) -> DiagnosticKinds {
+        let mut kinds = DiagnosticKinds::default();
+        for path_kinds in self.diagnostic_kinds.values() {
+            kinds.extend(path_kinds);
+        }
+        kinds
+    }
+
+    fn render_filter_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let border_color = if self.path_filter_error {
+            Color::Error.color(cx)
+        } else {
+            cx.theme().colors().border
+        };
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .bg(cx.theme().colors().toolbar_background)
+            .child(
+                h_flex()
+                    .flex_1()
+                    .px_2()
+                    .py_1()
+                    .rounded_md()
+                    .border_1()
+                    .border_color(border_color)
+                    .child(self.path_filter_editor.clone()),
+            )
+            .when_some(self.filter.rule.as_ref(), |this, rule| {
+                this.child(
+                    Button::new("diagnostics-clear-rule", format!("Rule: {}", rule.label()))
+                        .icon(IconName::Close)
+                        .icon_position(IconPosition::End)
+                        .icon_size(IconSize::XSmall)
+                        .style(ButtonStyle::Filled)
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.set_rule(None, window, cx);
+                        })),
+                )
+            })
+    }
+
+    /// Returns the group containing the newest selection, along with the rule that produced it.
+    fn group_at_cursor(
+        &self,
+        cx: &App,
+    ) -> Option<(
+        ProjectPath,
+        DiagnosticEntry<language::Anchor>,
+        DiagnosticRule,
+    )> {
+        let excerpt_id = self
+            .editor
+            .read(cx)
+            .selections
+            .newest_anchor()
+            .head()
+            .excerpt_id;
+        self.path_states.iter().find_map(|path_state| {
+            let group = path_state
+                .diagnostic_groups
+                .iter()
+                .find(|group| group.excerpts.contains(&excerpt_id))?;
+            let rule = DiagnosticRule::for_diagnostic(&group.primary_diagnostic.diagnostic)?;
+            Some((
+                path_state.path.clone(),
+                group.primary_diagnostic.clone(),
+                rule,
+            ))
+        })
+    }
+
+    /// Applies the quick fix offered for the diagnostic under the cursor to every
+    /// diagnostic in this view that was produced by the same rule.
+    fn fix_all_occurrences(
+        &mut self,
+        _: &FixAllOccurrences,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.fix_all_task.is_some() {
+            return;
+        }
+        let Some((path, primary, rule)) = self.group_at_cursor(cx) else {
+            return;
+        };
+        let occurrences = self
+            .path_states
+            .iter()
+            .flat_map(|path_state| {
+                path_state.diagnostic_groups.iter().filter_map(|group| {
+                    let group_rule =
+                        DiagnosticRule::for_diagnostic(&group.primary_diagnostic.diagnostic)?;
+                    (group_rule == rule).then(|| {
+                        (
+                            path_state.path.clone(),
+                            group.primary_diagnostic.range.clone(),
+                        )
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let project = self.project.clone();
+        let workspace = self.workspace.clone();
+        self.fix_all_task = Some(cx.spawn_in(window, async move |this, cx| {
+            let result = Self::apply_quick_fix_to_all(
+                project,
+                workspace,
+                path,
+                primary,
+                rule,
+                occurrences,
+                cx,
+            )
+            .await;
+            this.update(cx, |this, _| this.fix_all_task.take())?;
+            result
+        }));
+    }
+
+    async fn apply_quick_fix_to_all(
+        project: Entity<Project>,
+        workspace: WeakEntity<Workspace>,
+        path: ProjectPath,
+        primary: DiagnosticEntry<language::Anchor>,
+        rule: DiagnosticRule,
+        occurrences: Vec<(ProjectPath, Range<language::Anchor>)>,
+        cx: &mut AsyncWindowContext,
+    ) -> Result<()> {
+        let quick_fix = async {
+            let buffer = project
+                .update(cx, |project, cx| project.open_buffer(path, cx))?
+                .await?;
+            let actions = project
+                .update(cx, |project, cx| {
+                    project.code_actions(
+                        &buffer,
+                        primary.range.clone(),
+                        Some(vec![CodeActionKind::QUICKFIX]),
+                        cx,
+                    )
+                })?
+                .await?;
+            anyhow::Ok(select_quick_fix(actions, &rule, None))
+        };
+        let title = match quick_fix.await {
+            Ok(Some(action)) => Some(action.lsp_action.title().to_string()),
+            Ok(None) => None,
+            Err(error) => {
+                log::error!(
+                    "failed to fetch quick fixes for {}: {error:#}",
+                    rule.label()
+                );
+                None
+            }
+        };
+
+        // Occurrences that don't offer the same quick fix get the one they prefer, so the
+        // titles of all applied quick fixes are reported.
+        let mut applied = 0;
+        let mut applied_titles = BTreeSet::new();
+        if let Some(title) = title.as_ref() {
+            for (path, range) in occurrences {
+                let result = async {
+                    let buffer = project
+                        .update(cx, |project, cx| project.open_buffer(path, cx))?
+                        .await?;
+                    let actions = project
+                        .update(cx, |project, cx| {
+                            project.code_actions(
+                                &buffer,
+                                range,
+                                Some(vec![CodeActionKind::QUICKFIX]),
+                                cx,
+                            )
+                        })?
+                        .await?;
+                    let Some(action) = select_quick_fix(actions, &rule, Some(title.as_str()))
+                    else {
+                        return anyhow::Ok(None);
+                    };
+                    let action_title = action.lsp_action.title().to_string();
+                    project
+                        .update(cx, |project, cx| {
+                            project.apply_code_action(buffer, action, true, cx)
+                        })?
+                        .await?;
+                    anyhow::Ok(Some(action_title))
+                };
+                if let Some(Some(action_title)) = result.await.log_err() {
+                    applied += 1;
+                    applied_titles.insert(action_title);
+                }
+            }
+        }
+
+        let message = match title {
+            Some(title) => {
+                if applied_titles.is_empty() {
+                    applied_titles.insert(title);
+                }
+                let titles = applied_titles
+                    .iter()
+                    .map(|title| format!("\"{title}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "Applied {titles} to {applied} occurrence{} of {}",
+                    if applied == 1 { "" } else { "s" },
+                    rule.label()
+                )
+            }
+            None => format!("No quick fix is available for {}", rule.label()),
+        };
+        workspace.update(cx, |workspace, cx| {
+            workspace.show_toast(
+                Toast::new(NotificationId::unique::<FixAllOccurrences>(), message).autohide(),
+                cx,
+            );
+        })?;
+        Ok(())
+    }
+
     fn focus_in(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if self.focus_handle.is_focused(window) && !self.path_states.is_empty() {
             self.editor.focus_handle(cx).focus(window)
@@ -378,18 +776,82 @@ impl ProjectDiagnosticsEditor {
         buffer: Entity<Buffer>,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let snapshot = buffer.read(cx).snapshot();
+        let mut kinds = DiagnosticKinds::default();
+        // The path's sections are those of its shown diagnostics, along with the ones it has
+        // now, which are removed if they no longer have diagnostics.
+        let mut sections = self
+            .path_states
+            .iter()
+            .filter(|state| state.path == path_to_update)
+            .map(|state| state.rule.clone())
+            .collect::<BTreeSet<_>>();
+        for (_, group) in snapshot.diagnostic_groups(None) {
+            let primary = &group.entries[group.primary_ix].diagnostic;
+            kinds.insert_severity(primary.severity);
+            if self
+                .filter
+                .matches_severity(primary.severity, self.include_warnings)
+            {
+                kinds.insert(primary);
+                sections.insert(self.section_rule(primary));
+            }
+        }
+        if kinds.is_empty() {
+            self.diagnostic_kinds.remove(&path_to_update);
+        } else {
+            self.diagnostic_kinds.insert(path_to_update.clone(), kinds);
+        }
+
+        cx.spawn_in(window, async move |this, cx| {
+            for rule in sections {
+                this.update_in(cx, |this, window, cx| {
+                    this.update_section(
+                        rule,
+                        path_to_update.clone(),
+                        server_to_update,
+                        buffer.clone(),
+                        window,
+                        cx,
+                    )
+                })?
+                .await?;
+            }
+            Ok(())
+        })
+    }
+
+    /// The rule of the section that a diagnostic group with this primary diagnostic is shown in.
+    fn section_rule(&self, primary: &Diagnostic) -> Option<DiagnosticRule> {
+        if self.group_by_rule {
+            DiagnosticRule::for_diagnostic(primary)
+        } else {
+            None
+        }
+    }
+
+    fn update_section(
+        &mut self,
+        rule: Option<DiagnosticRule>,
+        path_to_update: ProjectPath,
+        server_to_update: Option<LanguageServerId>,
+        buffer: Entity<Buffer>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
         let was_empty = self.path_states.is_empty();
         let snapshot = buffer.read(cx).snapshot();
         let path_ix = match self
             .path_states
-            .binary_search_by_key(&&path_to_update, |e| &e.path)
+            .binary_search_by(|state| (&state.rule, &state.path).cmp(&(&rule, &path_to_update)))
         {
             Ok(ix) => ix,
             Err(ix) => {
                 self.path_states.insert(
                     ix,
                     PathState {
+                        rule: rule.clone(),
                         path: path_to_update.clone(),
                         diagnostic_groups: Default::default(),
                     },
@@ -411,11 +873,11 @@ impl ProjectDiagnosticsEditor {
         let mut blocks_to_add = Vec::new();
         let mut blocks_to_remove = HashSet::default();
         let mut first_excerpt_id = None;
-        let max_severity = if self.include_warnings {
-            DiagnosticSeverity::WARNING
-        } else {
-            DiagnosticSeverity::ERROR
-        };
+        let include_warnings = self.include_warnings;
+        let group_by_rule = self.group_by_rule;
+
+        let filter = self.filter.clone();
+        let path_matches_filter = filter.matches_path(&path_to_update.path);
         let excerpts = self.excerpts.clone().downgrade();
         let context = self.context;
         let editor = self.editor.clone().downgrade();
@@ -431,7 +893,16 @@ impl ProjectDiagnosticsEditor {
                 .diagnostic_groups(server_to_update)
                 .into_iter()
                 .filter(|(_, group)| {
-                    group.entries[group.primary_ix].diagnostic.severity <= max_severity
+                    let primary = &group.entries[group.primary_ix].diagnostic;
+                    let section_rule = if group_by_rule {
+                        DiagnosticRule::for_diagnostic(primary)
+                    } else {
+                        None
+                    };
+                    filter.matches_severity(primary.severity, include_warnings)
+                        && path_matches_filter
+                        && filter.matches(primary)
+                        && section_rule == rule
                 })
                 .peekable();
             loop {
@@ -728,6 +1199,17 @@ impl ProjectDiagnosticsEditor {
 
     #[cfg(test)]
     fn check_invariants(&self, cx: &mut Context<Self>) {
+        for states in self.path_states.windows(2) {
+            if (&states[0].rule, &states[0].path) >= (&states[1].rule, &states[1].path) {
+                panic!("sections are not sorted by rule and path");
+            }
+        }
+        // Excerpts are only sorted by path when grouping by file, and until switching to it has
+        // replaced the sections of rules.
+        if self.path_states.iter().any(|state| state.rule.is_some()) {
+            return;
+        }
+
         let mut excerpts = Vec::new();
         for (id, buffer, _) in self.excerpts.read(cx).snapshot(cx).excerpts() {
             if let Some(file) = buffer.file() {
@@ -949,6 +1431,47 @@ impl Item for ProjectDiagnosticsEditor {
     }
 }
 
+/// Picks the quick fix addressing `rule` out of the actions offered for one of its diagnostics.
+/// When `title` is given, the action with that title wins over the server's preferred fix.
+fn select_quick_fix(
+    actions: Vec<CodeAction>,
+    rule: &DiagnosticRule,
+    title: Option<&str>,
+) -> Option<CodeAction> {
+    let mut candidates = actions
+        .into_iter()
+        .filter(|action| match &action.lsp_action {
+            LspAction::Action(lsp_action) => {
+                lsp_action.diagnostics.as_ref().map_or(true, |diagnostics| {
+                    diagnostics.iter().any(|diagnostic| {
+                        let code = match diagnostic.code.as_ref() {
+                            Some(lsp::NumberOrString::Number(code)) => code.to_string(),
+                            Some(lsp::NumberOrString::String(code)) => code.clone(),
+                            None => return false,
+                        };
+                        code == rule.code
+                    })
+                })
+            }
+            LspAction::Command(_) | LspAction::CodeLens(_) => false,
+        })
+        .collect::<Vec<_>>();
+
+    let is_preferred = |action: &CodeAction| match &action.lsp_action {
+        LspAction::Action(lsp_action) => lsp_action.is_preferred == Some(true),
+        _ => false,
+    };
+    let ix = title
+        .and_then(|title| {
+            candidates
+                .iter()
+                .position(|action| action.lsp_action.title() == title)
+        })
+        .or_else(|| candidates.iter().position(is_preferred))
+        .or_else(|| (title.is_none() && !candidates.is_empty()).then_some(0))?;
+    Some(candidates.swap_remove(ix))
+}
+
 const DIAGNOSTIC_HEADER: &str = "diagnostic header";
 
 fn diagnostic_header_renderer(diagnostic: Diagnostic) -> RenderBlock {
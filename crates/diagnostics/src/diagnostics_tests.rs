@@ -4,6 +4,7 @@ use editor::{
     display_map::{Block, BlockContext, DisplayRow},
     DisplayPoint, GutterDimensions,
 };
+use futures::StreamExt as _;
 use gpui::{px, AvailableSpace, Stateful, TestAppContext, VisualTestContext};
 use language::{
     Diagnostic, DiagnosticEntry, DiagnosticSeverity, OffsetRangeExt, PointUtf16, Rope, Unclipped,
@@ -719,6 +720,569 @@ async fn test_diagnostics_multiple_servers(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_diagnostics_filter(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/test"),
+        json!({
+            "main.js": "
+                a();
+                b();
+            ".unindent(),
+            "other.js": "
+                c();
+                d();
+            ".unindent(),
+        }),
+    )
+    .await;
+
+    let server_id = LanguageServerId(0);
+    let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+    let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*window, cx);
+    let workspace = window.root(cx).unwrap();
+
+    lsp_store.update(cx, |lsp_store, cx| {
+        for (path, source, code) in [
+            (path!("/test/main.js"), "eslint", "no-undef"),
+            (path!("/test/other.js"), "tsc", "2304"),
+        ] {
+            lsp_store
+                .update_diagnostic_entries(
+                    server_id,
+                    PathBuf::from(path),
+                    None,
+                    vec![DiagnosticEntry {
+                        range: Unclipped(PointUtf16::new(0, 0))..Unclipped(PointUtf16::new(0, 1)),
+                        diagnostic: Diagnostic {
+                            message: "cannot find name".to_string(),
+                            severity: DiagnosticSeverity::ERROR,
+                            source: Some(source.to_string()),
+                            code: Some(lsp::NumberOrString::String(code.to_string())),
+                            is_primary: true,
+                            group_id: 0,
+                            ..Default::default()
+                        },
+                    }],
+                    cx,
+                )
+                .unwrap();
+        }
+    });
+
+    let diagnostics = window.build_entity(cx, |window, cx| {
+        ProjectDiagnosticsEditor::new_with_context(
+            1,
+            true,
+            project.clone(),
+            workspace.downgrade(),
+            window,
+            cx,
+        )
+    });
+    let displayed_paths = |cx: &mut VisualTestContext| {
+        diagnostics.update(cx, |diagnostics, _| {
+            diagnostics
+                .path_states
+                .iter()
+                .map(|state| state.path.path.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        })
+    };
+    let wait_for_update = |cx: &mut VisualTestContext| {
+        cx.executor()
+            .advance_clock(DIAGNOSTICS_UPDATE_DEBOUNCE + Duration::from_millis(10));
+        cx.executor().run_until_parked();
+    };
+
+    wait_for_update(cx);
+    assert_eq!(displayed_paths(cx), ["main.js", "other.js"]);
+    diagnostics.update(cx, |diagnostics, _| {
+        let kinds = diagnostics.diagnostic_kinds();
+        assert_eq!(
+            kinds.sources.into_iter().collect::<Vec<_>>(),
+            [("eslint".to_string(), 1), ("tsc".to_string(), 1)]
+        );
+        assert_eq!(
+            kinds
+                .rules
+                .keys()
+                .map(|rule| rule.label())
+                .collect::<Vec<_>>(),
+            ["eslint(no-undef)", "tsc(2304)"]
+        );
+    });
+
+    // Excluding a source hides its diagnostics.
+    diagnostics.update_in(cx, |diagnostics, window, cx| {
+        diagnostics.toggle_source("eslint".to_string(), window, cx);
+    });
+    wait_for_update(cx);
+    assert_eq!(displayed_paths(cx), ["other.js"]);
+
+    // Including it again restores them.
+    diagnostics.update_in(cx, |diagnostics, window, cx| {
+        diagnostics.toggle_source("eslint".to_string(), window, cx);
+    });
+    wait_for_update(cx);
+    assert_eq!(displayed_paths(cx), ["main.js", "other.js"]);
+
+    // Only diagnostics produced by the selected rule are shown.
+    diagnostics.update_in(cx, |diagnostics, window, cx| {
+        let rule = DiagnosticRule {
+            source: Some("eslint".to_string()),
+            code: "no-undef".to_string(),
+        };
+        diagnostics.set_rule(Some(rule), window, cx);
+    });
+    wait_for_update(cx);
+    assert_eq!(displayed_paths(cx), ["main.js"]);
+
+    // Path globs are typed into the filter editor.
+    diagnostics.update_in(cx, |diagnostics, window, cx| {
+        diagnostics.set_rule(None, window, cx);
+        diagnostics.toggle_filters(&ToggleFilters, window, cx);
+        diagnostics.path_filter_editor.update(cx, |editor, cx| {
+            editor.set_text("other.*", window, cx);
+        });
+    });
+    wait_for_update(cx);
+    assert_eq!(displayed_paths(cx), ["other.js"]);
+
+    diagnostics.update_in(cx, |diagnostics, window, cx| {
+        diagnostics.clear_filters(&ClearFilters, window, cx);
+    });
+    wait_for_update(cx);
+    assert_eq!(displayed_paths(cx), ["main.js", "other.js"]);
+}
+
+#[gpui::test]
+async fn test_diagnostics_severity_filter_and_grouping_by_rule(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/test"),
+        json!({
+            "main.js": "
+                a();
+                let b;
+                let c;
+            ".unindent(),
+            "other.js": "
+                d();
+                e();
+            ".unindent(),
+        }),
+    )
+    .await;
+
+    let server_id = LanguageServerId(0);
+    let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+    let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*window, cx);
+    let workspace = window.root(cx).unwrap();
+
+    lsp_store.update(cx, |lsp_store, cx| {
+        for (path, diagnostics) in [
+            (
+                path!("/test/main.js"),
+                vec![
+                    (0, DiagnosticSeverity::ERROR, "eslint", "no-undef"),
+                    (1, DiagnosticSeverity::WARNING, "eslint", "no-unused-vars"),
+                    (2, DiagnosticSeverity::HINT, "tsc", "6133"),
+                ],
+            ),
+            (
+                path!("/test/other.js"),
+                vec![
+                    (0, DiagnosticSeverity::ERROR, "tsc", "2304"),
+                    (1, DiagnosticSeverity::ERROR, "eslint", "no-undef"),
+                ],
+            ),
+        ] {
+            let entries = diagnostics
+                .into_iter()
+                .enumerate()
+                .map(
+                    |(group_id, (row, severity, source, code))| DiagnosticEntry {
+                        range: Unclipped(PointUtf16::new(row, 0))
+                            ..Unclipped(PointUtf16::new(row, 1)),
+                        diagnostic: Diagnostic {
+                            message: format!("{source} {code}"),
+                            severity,
+                            source: Some(source.to_string()),
+                            code: Some(lsp::NumberOrString::String(code.to_string())),
+                            is_primary: true,
+                            group_id,
+                            ..Default::default()
+                        },
+                    },
+                )
+                .collect();
+            lsp_store
+                .update_diagnostic_entries(server_id, PathBuf::from(path), None, entries, cx)
+                .unwrap();
+        }
+    });
+
+    let diagnostics = window.build_entity(cx, |window, cx| {
+        ProjectDiagnosticsEditor::new_with_context(
+            1,
+            true,
+            project.clone(),
+            workspace.downgrade(),
+            window,
+            cx,
+        )
+    });
+    let displayed_sections = |cx: &mut VisualTestContext| {
+        diagnostics.update(cx, |diagnostics, _| {
+            diagnostics
+                .path_states
+                .iter()
+                .map(|state| {
+                    (
+                        state.rule.as_ref().map(|rule| rule.label()),
+                        state.path.path.to_string_lossy().to_string(),
+                        state.diagnostic_groups.len(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+    let excerpt_paths = |cx: &mut VisualTestContext| {
+        diagnostics.update(cx, |diagnostics, cx| {
+            diagnostics
+                .excerpts
+                .read(cx)
+                .snapshot(cx)
+                .excerpts()
+                .map(|(_, buffer, _)| buffer.file().unwrap().path().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        })
+    };
+    let wait_for_update = |cx: &mut VisualTestContext| {
+        cx.executor()
+            .advance_clock(DIAGNOSTICS_UPDATE_DEBOUNCE + Duration::from_millis(10));
+        cx.executor().run_until_parked();
+    };
+
+    wait_for_update(cx);
+    assert_eq!(
+        displayed_sections(cx),
+        [
+            (None, "main.js".to_string(), 2),
+            (None, "other.js".to_string(), 2)
+        ]
+    );
+    diagnostics.update(cx, |diagnostics, _| {
+        assert_eq!(
+            diagnostics
+                .diagnostic_kinds()
+                .severities
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [
+                (DiagnosticSeverity::ERROR, 3),
+                (DiagnosticSeverity::WARNING, 1),
+                (DiagnosticSeverity::HINT, 1)
+            ]
+        );
+    });
+
+    // Toggling a severity starts from the severities that the warnings toggle shows.
+    diagnostics.update_in(cx, |diagnostics, window, cx| {
+        diagnostics.toggle_severity(DiagnosticSeverity::WARNING, window, cx);
+    });
+    wait_for_update(cx);
+    assert_eq!(
+        displayed_sections(cx),
+        [
+            (None, "main.js".to_string(), 1),
+            (None, "other.js".to_string(), 2)
+        ]
+    );
+
+    // Severities below warnings can be shown too.
+    diagnostics.update_in(cx, |diagnostics, window, cx| {
+        diagnostics.toggle_severity(DiagnosticSeverity::HINT, window, cx);
+    });
+    wait_for_update(cx);
+    assert_eq!(
+        displayed_sections(cx),
+        [
+            (None, "main.js".to_string(), 2),
+            (None, "other.js".to_string(), 2)
+        ]
+    );
+    diagnostics.update(cx, |diagnostics, _| {
+        assert_eq!(
+            diagnostics.filter.severities,
+            Some([DiagnosticSeverity::ERROR, DiagnosticSeverity::HINT].into())
+        );
+    });
+
+    // Grouping by rule puts the diagnostics of each rule together, across files.
+    diagnostics.update_in(cx, |diagnostics, window, cx| {
+        diagnostics.clear_filters(&ClearFilters, window, cx);
+        diagnostics.toggle_group_by_rule(&ToggleGroupByRule, window, cx);
+    });
+    wait_for_update(cx);
+    assert_eq!(
+        displayed_sections(cx),
+        [
+            (
+                Some("eslint(no-undef)".to_string()),
+                "main.js".to_string(),
+                1
+            ),
+            (
+                Some("eslint(no-undef)".to_string()),
+                "other.js".to_string(),
+                1
+            ),
+            (
+                Some("eslint(no-unused-vars)".to_string()),
+                "main.js".to_string(),
+                1
+            ),
+            (Some("tsc(2304)".to_string()), "other.js".to_string(), 1),
+        ]
+    );
+    assert_eq!(
+        excerpt_paths(cx),
+        ["main.js", "other.js", "main.js", "other.js"]
+    );
+
+    diagnostics.update_in(cx, |diagnostics, window, cx| {
+        diagnostics.toggle_group_by_rule(&ToggleGroupByRule, window, cx);
+    });
+    wait_for_update(cx);
+    assert_eq!(
+        displayed_sections(cx),
+        [
+            (None, "main.js".to_string(), 2),
+            (None, "other.js".to_string(), 2)
+        ]
+    );
+    assert_eq!(
+        excerpt_paths(cx),
+        ["main.js", "main.js", "other.js", "other.js"]
+    );
+}
+
+#[gpui::test]
+async fn test_fix_all_occurrences(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/test"),
+        json!({
+            "a.rs": "let unused_a = 1;\n",
+            "b.rs": "let unused_b = 2;\nlet c = 3;\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(Arc::new(language::Language::new(
+        language::LanguageConfig {
+            name: "Rust".into(),
+            matcher: language::LanguageMatcher {
+                path_suffixes: vec!["rs".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        None,
+    )));
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        language::FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                code_action_provider: Some(lsp::CodeActionProviderCapability::Simple(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    let buffer_a = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/test/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let buffer_b = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/test/b.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+
+    let diagnostic = |row, start, end, code: &str| lsp::Diagnostic {
+        range: lsp::Range::new(lsp::Position::new(row, start), lsp::Position::new(row, end)),
+        severity: Some(lsp::DiagnosticSeverity::WARNING),
+        code: Some(lsp::NumberOrString::String(code.to_string())),
+        source: Some("rustc".to_string()),
+        message: "unused variable".to_string(),
+        ..Default::default()
+    };
+    fake_server.notify::<lsp::notification::PublishDiagnostics>(&lsp::PublishDiagnosticsParams {
+        uri: lsp::Url::from_file_path(path!("/test/a.rs")).unwrap(),
+        diagnostics: vec![diagnostic(0, 4, 12, "unused_variables")],
+        version: None,
+    });
+    fake_server.notify::<lsp::notification::PublishDiagnostics>(&lsp::PublishDiagnosticsParams {
+        uri: lsp::Url::from_file_path(path!("/test/b.rs")).unwrap(),
+        diagnostics: vec![
+            diagnostic(0, 4, 12, "unused_variables"),
+            diagnostic(1, 4, 5, "non_snake_case"),
+        ],
+        version: None,
+    });
+    // The server offers a fix for each diagnostic under the requested range, preferring to
+    // prefix unused variables with an underscore.
+    fake_server.set_request_handler::<lsp::request::CodeActionRequest, _, _>(
+        move |params, _| async move {
+            let fix = |title: &str, code: &str, new_text: &str, is_preferred| {
+                lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+                    title: title.to_string(),
+                    kind: Some(lsp::CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic(0, 0, 0, code)]),
+                    is_preferred: Some(is_preferred),
+                    edit: Some(lsp::WorkspaceEdit {
+                        changes: Some(
+                            [(
+                                params.text_document.uri.clone(),
+                                vec![lsp::TextEdit::new(
+                                    lsp::Range::new(params.range.start, params.range.start),
+                                    new_text.to_string(),
+                                )],
+                            )]
+                            .into_iter()
+                            .collect(),
+                        ),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+            };
+            Ok(Some(if params.range.start.line == 0 {
+                vec![
+                    fix("Remove the variable", "unused_variables", "//", false),
+                    fix("Prefix with an underscore", "unused_variables", "_", true),
+                ]
+            } else {
+                vec![fix("Convert to snake case", "non_snake_case", "x", true)]
+            }))
+        },
+    );
+
+    let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*window, cx);
+    let workspace = window.root(cx).unwrap();
+    let diagnostics = window.build_entity(cx, |window, cx| {
+        ProjectDiagnosticsEditor::new_with_context(
+            1,
+            true,
+            project.clone(),
+            workspace.downgrade(),
+            window,
+            cx,
+        )
+    });
+    cx.executor()
+        .advance_clock(DIAGNOSTICS_UPDATE_DEBOUNCE + Duration::from_millis(10));
+    cx.executor().run_until_parked();
+
+    // The fix for the diagnostic under the cursor is applied to every diagnostic produced by the
+    // same rule, but not to the other diagnostics.
+    diagnostics.update_in(cx, |diagnostics, window, cx| {
+        diagnostics.fix_all_occurrences(&FixAllOccurrences, window, cx);
+    });
+    cx.executor().run_until_parked();
+    assert_eq!(
+        buffer_a.read_with(cx, |buffer, _| buffer.text()),
+        "let _unused_a = 1;\n"
+    );
+    assert_eq!(
+        buffer_b.read_with(cx, |buffer, _| buffer.text()),
+        "let _unused_b = 2;\nlet c = 3;\n"
+    );
+    diagnostics.update(cx, |diagnostics, _| {
+        assert!(diagnostics.fix_all_task.is_none());
+    });
+}
+
+#[test]
+fn test_select_quick_fix() {
+    let code_action = |title: &str, code: Option<&str>, is_preferred| CodeAction {
+        server_id: LanguageServerId(0),
+        range: language::Anchor::MIN..language::Anchor::MIN,
+        lsp_action: LspAction::Action(Box::new(lsp::CodeAction {
+            title: title.to_string(),
+            diagnostics: code.map(|code| {
+                vec![lsp::Diagnostic {
+                    code: Some(lsp::NumberOrString::String(code.to_string())),
+                    ..Default::default()
+                }]
+            }),
+            is_preferred: Some(is_preferred),
+            ..Default::default()
+        })),
+        resolved: true,
+    };
+    let actions = || {
+        vec![
+            code_action("Remove the variable", Some("unused_variables"), false),
+            code_action("Prefix with an underscore", Some("unused_variables"), true),
+            code_action("Allow dead code", Some("dead_code"), true),
+            code_action("Disable the lint", None, false),
+        ]
+    };
+    let rule = |code: &str| DiagnosticRule {
+        source: Some("rustc".to_string()),
+        code: code.to_string(),
+    };
+    let select = |code, title| {
+        select_quick_fix(actions(), &rule(code), title)
+            .map(|action| action.lsp_action.title().to_string())
+    };
+
+    // The server's preferred fix for the rule is picked, ignoring fixes for other rules.
+    assert_eq!(
+        select("unused_variables", None).as_deref(),
+        Some("Prefix with an underscore")
+    );
+    assert_eq!(
+        select("dead_code", None).as_deref(),
+        Some("Allow dead code")
+    );
+    // An action with the given title wins over the preferred fix.
+    assert_eq!(
+        select("unused_variables", Some("Remove the variable")).as_deref(),
+        Some("Remove the variable")
+    );
+    // Actions that don't name the diagnostics they fix are assumed to fix the rule.
+    assert_eq!(
+        select("unused_variables", Some("Disable the lint")).as_deref(),
+        Some("Disable the lint")
+    );
+    // Without a preferred fix, the first fix is picked, unless a title was given.
+    assert_eq!(select("unknown", None).as_deref(), Some("Disable the lint"));
+    assert_eq!(select("unknown", Some("Remove the variable")), None);
+}
+
 #[gpui::test(iterations = 20)]
 async fn test_random_diagnostics(cx: &mut TestAppContext, mut rng: StdRng) {
     init_test(cx);
@@ -198,4 +198,8 @@ impl StatusItemView for DiagnosticIndicator {
         }
         cx.notify();
     }
+
+    fn segment_name(&self, _: &App) -> Option<SharedString> {
+        Some("diagnostics".into())
+    }
 }
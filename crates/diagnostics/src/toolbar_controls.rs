@@ -1,11 +1,19 @@
-use crate::ProjectDiagnosticsEditor;
-use gpui::{Context, Entity, EventEmitter, ParentElement, Render, WeakEntity, Window};
+use crate::diagnostic_filter::SEVERITIES;
+use crate::{
+    ClearFilters, FixAllOccurrences, ProjectDiagnosticsEditor, ToggleFilters, ToggleGroupByRule,
+};
+use gpui::{
+    Action, Context, Corner, Entity, EventEmitter, ParentElement, Render, WeakEntity, Window,
+};
 use ui::prelude::*;
-use ui::{IconButton, IconButtonShape, IconName, Tooltip};
+use ui::{
+    ContextMenu, IconButton, IconButtonShape, IconName, PopoverMenu, PopoverMenuHandle, Tooltip,
+};
 use workspace::{item::ItemHandle, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView};
 
 pub struct ToolbarControls {
     editor: Option<WeakEntity<ProjectDiagnosticsEditor>>,
+    filter_menu_handle: PopoverMenuHandle<ContextMenu>,
 }
 
 impl Render for ToolbarControls {
@@ -13,10 +21,12 @@ impl Render for ToolbarControls {
         let mut include_warnings = false;
         let mut has_stale_excerpts = false;
         let mut is_updating = false;
+        let mut is_filtered = false;
 
         if let Some(editor) = self.diagnostics() {
             let diagnostics = editor.read(cx);
             include_warnings = diagnostics.include_warnings;
+            is_filtered = !diagnostics.filter.is_empty();
             has_stale_excerpts = !diagnostics.paths_to_update.is_empty();
             is_updating = diagnostics.update_excerpts_task.is_some()
                 || diagnostics
@@ -70,9 +80,135 @@ impl Render for ToolbarControls {
                         }
                     })),
             )
+            .child(
+                PopoverMenu::new("diagnostics-filter-menu")
+                    .trigger_with_tooltip(
+                        IconButton::new("toggle-filter-menu", IconName::Filter)
+                            .icon_color(if is_filtered {
+                                Color::Accent
+                            } else {
+                                Color::Muted
+                            })
+                            .shape(IconButtonShape::Square)
+                            .toggle_state(self.filter_menu_handle.is_deployed()),
+                        Tooltip::text("Filter Diagnostics"),
+                    )
+                    .with_handle(self.filter_menu_handle.clone())
+                    .anchor(Corner::TopRight)
+                    .menu({
+                        let diagnostics = self.editor.clone();
+                        move |window, cx| {
+                            let diagnostics = diagnostics.as_ref()?.upgrade()?;
+                            Some(build_filter_menu(diagnostics, window, cx))
+                        }
+                    }),
+            )
     }
 }
 
+fn build_filter_menu(
+    diagnostics: Entity<ProjectDiagnosticsEditor>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Entity<ContextMenu> {
+    let (filter, kinds, include_warnings, group_by_rule, focus_handle) = {
+        let diagnostics = diagnostics.read(cx);
+        (
+            diagnostics.filter.clone(),
+            diagnostics.diagnostic_kinds(),
+            diagnostics.include_warnings,
+            diagnostics.group_by_rule,
+            diagnostics.focus_handle.clone(),
+        )
+    };
+    ContextMenu::build(window, cx, move |mut menu, _, _| {
+        menu = menu
+            .context(focus_handle)
+            .action("Filter by Path…", ToggleFilters.boxed_clone())
+            .toggleable_entry(
+                "Group by Rule",
+                group_by_rule,
+                IconPosition::Start,
+                Some(ToggleGroupByRule.boxed_clone()),
+                {
+                    let diagnostics = diagnostics.clone();
+                    move |window, cx| {
+                        diagnostics.update(cx, |diagnostics, cx| {
+                            diagnostics.toggle_group_by_rule(&ToggleGroupByRule, window, cx);
+                        });
+                    }
+                },
+            )
+            .action("Fix All Occurrences", FixAllOccurrences.boxed_clone());
+
+        if !kinds.severities.is_empty() {
+            menu = menu.separator().header("Severities");
+            for (severity, label) in SEVERITIES {
+                let count = kinds.severities.get(&severity).copied().unwrap_or(0);
+                let diagnostics = diagnostics.clone();
+                menu = menu.toggleable_entry(
+                    format!("{label} ({count})"),
+                    filter.matches_severity(severity, include_warnings),
+                    IconPosition::Start,
+                    None,
+                    move |window, cx| {
+                        diagnostics.update(cx, |diagnostics, cx| {
+                            diagnostics.toggle_severity(severity, window, cx);
+                        });
+                    },
+                );
+            }
+        }
+
+        if !kinds.sources.is_empty() {
+            menu = menu.separator().header("Sources");
+            for (source, count) in &kinds.sources {
+                let source = source.clone();
+                let diagnostics = diagnostics.clone();
+                menu = menu.toggleable_entry(
+                    format!("{source} ({count})"),
+                    !filter.excluded_sources.contains(&source),
+                    IconPosition::Start,
+                    None,
+                    move |window, cx| {
+                        diagnostics.update(cx, |diagnostics, cx| {
+                            diagnostics.toggle_source(source.clone(), window, cx);
+                        });
+                    },
+                );
+            }
+        }
+
+        if !kinds.rules.is_empty() {
+            menu = menu.separator().header("Rules");
+            for (rule, count) in &kinds.rules {
+                let is_selected = filter.rule.as_ref() == Some(rule);
+                let rule = rule.clone();
+                let diagnostics = diagnostics.clone();
+                menu = menu.toggleable_entry(
+                    format!("{} ({count})", rule.label()),
+                    is_selected,
+                    IconPosition::Start,
+                    None,
+                    move |window, cx| {
+                        let rule = (!is_selected).then(|| rule.clone());
+                        diagnostics.update(cx, |diagnostics, cx| {
+                            diagnostics.set_rule(rule, window, cx);
+                        });
+                    },
+                );
+            }
+        }
+
+        if !filter.is_empty() {
+            menu = menu
+                .separator()
+                .action("Clear Filters", ClearFilters.boxed_clone());
+        }
+        menu
+    })
+}
+
 impl EventEmitter<ToolbarItemEvent> for ToolbarControls {}
 
 impl ToolbarItemView for ToolbarControls {
@@ -103,7 +239,10 @@ impl Default for ToolbarControls {
 
 impl ToolbarControls {
     pub fn new() -> Self {
-        ToolbarControls { editor: None }
+        ToolbarControls {
+            editor: None,
+            filter_menu_handle: PopoverMenuHandle::default(),
+        }
     }
 
     fn diagnostics(&self) -> Option<Entity<ProjectDiagnosticsEditor>> {
@@ -266,6 +266,8 @@ actions!(
         ContextMenuLast,
         ContextMenuNext,
         ContextMenuPrevious,
+        ConvertIndentationToSpaces,
+        ConvertIndentationToTabs,
         ConvertToKebabCase,
         ConvertToLowerCamelCase,
         ConvertToLowerCase,
@@ -364,9 +366,13 @@ actions!(
         PageDown,
         PageUp,
         Paste,
+        PasteFromHistory,
+        PeekDefinition,
+        PeekReferences,
         PreviousEditPrediction,
         Redo,
         RedoSelection,
+        Refactor,
         Rename,
         RestartLanguageServer,
         RevealInFileManager,
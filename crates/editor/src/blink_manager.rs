@@ -55,7 +55,7 @@ impl BlinkManager {
     }
 
     fn blink_cursors(&mut self, epoch: usize, cx: &mut Context<Self>) {
-        if EditorSettings::get_global(cx).cursor_blink {
+        if EditorSettings::get_global(cx).cursor_blink && !cx.reduce_motion() {
             if epoch == self.blink_epoch && self.enabled && !self.blinking_paused {
                 self.visible = !self.visible;
                 cx.notify();
@@ -0,0 +1,243 @@
+//! Journals the contents of dirty buffers that aren't restored with their workspace, such as
+//! untitled buffers in a window without worktrees, so that they can be recovered if Zed crashes
+//! or is forced to quit.
+
+use std::fmt::Write as _;
+
+use anyhow::{anyhow, Result};
+use gpui::{App, AppContext as _, Context, Entity, Global, PromptLevel, Task, Window};
+use language::Buffer;
+use util::ResultExt as _;
+use workspace::{local_workspace_windows, AppState, ItemId, OpenOptions, OpenVisible, Workspace};
+
+use crate::{
+    persistence::{UnsavedBuffer, DB},
+    Editor,
+};
+
+/// Set once the buffers left behind by previous sessions were offered for restoring, so that
+/// only the first window asks.
+struct RecoveryOffered;
+
+impl Global for RecoveryOffered {}
+
+pub(crate) fn init(cx: &mut App) {
+    // A session that quits cleanly leaves nothing behind, so the buffers journaled by other
+    // sessions are from ones that crashed or were forced to quit.
+    cx.on_app_quit(|cx| {
+        let session_id = AppState::try_global(cx)
+            .and_then(|app_state| app_state.upgrade())
+            .map(|app_state| app_state.session.read(cx).id().to_owned());
+        async move {
+            if let Some(session_id) = session_id {
+                DB.delete_unsaved_buffers_of_session(session_id)
+                    .await
+                    .log_err();
+            }
+        }
+    })
+    .detach();
+
+    cx.observe_new(
+        |workspace: &mut Workspace, window: Option<&mut Window>, cx: &mut Context<Workspace>| {
+            let Some(window) = window else {
+                return;
+            };
+            if cx.has_global::<RecoveryOffered>() {
+                return;
+            }
+            let Some(session_id) = workspace.session_id() else {
+                return;
+            };
+            cx.set_global(RecoveryOffered);
+            cx.spawn_in(window, async move |workspace, cx| {
+                let buffers = cx
+                    .background_spawn({
+                        let session_id = session_id.clone();
+                        async move { DB.unsaved_buffers_of_other_sessions(session_id) }
+                    })
+                    .await?;
+                if buffers.is_empty() {
+                    return anyhow::Ok(());
+                }
+
+                let answer = workspace.update_in(cx, |_, window, cx| {
+                    window.prompt(
+                        PromptLevel::Warning,
+                        "Zed quit unexpectedly",
+                        Some(&recovery_detail(&buffers)),
+                        &["Restore", "Discard"],
+                        cx,
+                    )
+                })?;
+                // Keep the buffers around if the prompt was dismissed without an answer.
+                let Ok(answer) = answer.await else {
+                    return Ok(());
+                };
+                if answer == 0 {
+                    for buffer in buffers {
+                        let restore = workspace.update_in(cx, |workspace, window, cx| {
+                            restore_buffer(workspace, buffer, window, cx)
+                        })?;
+                        restore.await.log_err();
+                    }
+                }
+                DB.delete_unsaved_buffers_of_other_sessions(session_id)
+                    .await
+            })
+            .detach_and_log_err(cx);
+        },
+    )
+    .detach();
+}
+
+impl Editor {
+    /// Records the buffer's contents if it's dirty, or forgets them once it's saved or closed.
+    pub(crate) fn journal_unsaved_buffer(
+        &self,
+        workspace: &Workspace,
+        item_id: ItemId,
+        closing: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let (Some(session_id), Some(workspace_id)) =
+            (workspace.session_id(), workspace.database_id())
+        else {
+            return;
+        };
+        let Some(buffer) = self.buffer().read(cx).as_singleton() else {
+            return;
+        };
+        let buffer = buffer.read(cx);
+        if closing || !buffer.is_dirty() {
+            cx.background_spawn(async move {
+                DB.delete_unsaved_buffer(session_id, workspace_id, item_id)
+                    .await
+                    .log_err();
+            })
+            .detach();
+            return;
+        }
+
+        let abs_path = buffer
+            .file()
+            .and_then(|file| file.as_local())
+            .map(|file| file.abs_path(cx));
+        let snapshot = buffer.snapshot();
+        cx.background_spawn(async move {
+            let contents = snapshot.text();
+            let language = snapshot
+                .language()
+                .map(|language| language.name().to_string());
+            DB.save_unsaved_buffer(
+                session_id,
+                workspace_id,
+                item_id,
+                abs_path,
+                contents,
+                language,
+            )
+            .await
+            .log_err();
+        })
+        .detach();
+    }
+}
+
+fn recovery_detail(buffers: &[UnsavedBuffer]) -> String {
+    let mut detail = String::from("Unsaved changes were found in:\n");
+    for buffer in buffers {
+        match &buffer.abs_path {
+            Some(abs_path) => writeln!(detail, "• {}", abs_path.display()).ok(),
+            None => writeln!(detail, "• Untitled").ok(),
+        };
+    }
+    detail
+}
+
+/// Opens the buffer in the window of the workspace it was edited in, if that's open, and in the
+/// given workspace otherwise.
+fn restore_buffer(
+    workspace: &mut Workspace,
+    buffer: UnsavedBuffer,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) -> Task<Result<()>> {
+    if workspace.database_id() != Some(buffer.workspace_id) {
+        let target = local_workspace_windows(cx).into_iter().find(|target| {
+            target
+                .read(cx)
+                .is_ok_and(|target| target.database_id() == Some(buffer.workspace_id))
+        });
+        if let Some(target) = target {
+            return target
+                .update(cx, |workspace, window, cx| {
+                    restore_buffer_in(workspace, buffer, window, cx)
+                })
+                .unwrap_or_else(|error| Task::ready(Err(error)));
+        }
+    }
+    restore_buffer_in(workspace, buffer, window, cx)
+}
+
+fn restore_buffer_in(
+    workspace: &mut Workspace,
+    buffer: UnsavedBuffer,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) -> Task<Result<()>> {
+    let UnsavedBuffer {
+        abs_path,
+        contents,
+        language,
+        ..
+    } = buffer;
+
+    if let Some(abs_path) = abs_path {
+        let open = workspace.open_abs_path(
+            abs_path,
+            OpenOptions {
+                visible: Some(OpenVisible::None),
+                ..Default::default()
+            },
+            window,
+            cx,
+        );
+        return cx.spawn_in(window, async move |_, cx| {
+            let editor = open
+                .await?
+                .downcast::<Editor>()
+                .ok_or_else(|| anyhow!("restored buffer wasn't opened in an editor"))?;
+            editor.update(cx, |editor, cx| {
+                if let Some(buffer) = editor.buffer().read(cx).as_singleton() {
+                    buffer.update(cx, |buffer, cx| buffer.set_text(contents, cx));
+                }
+            })
+        });
+    }
+
+    let project = workspace.project().clone();
+    let language_registry = project.read(cx).languages().clone();
+    cx.spawn_in(window, async move |workspace, cx| {
+        let language = match language {
+            Some(language) => language_registry
+                .language_for_name(&language)
+                .await
+                .log_err(),
+            None => None,
+        };
+        let buffer: Entity<Buffer> = project
+            .update(cx, |project, cx| project.create_buffer(cx))?
+            .await?;
+        buffer.update(cx, |buffer, cx| {
+            if let Some(language) = language {
+                buffer.set_language(Some(language), cx);
+            }
+            buffer.set_text(contents, cx);
+        })?;
+        workspace.update_in(cx, |workspace, window, cx| {
+            let editor = cx.new(|cx| Editor::for_buffer(buffer, Some(project.clone()), window, cx));
+            workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+        })
+    })
+}
@@ -0,0 +1,138 @@
+//! A bounded history of the text that's copied and cut, most recent first, which
+//! `editor: paste from history` pastes from. Pinned entries stay in the history when it's full.
+
+use std::collections::VecDeque;
+
+use gpui::{App, ClipboardEntry, ClipboardItem, Global, SharedString};
+use settings::Settings;
+
+use crate::{ClipboardSelection, EditorSettings};
+
+#[derive(Clone, Debug)]
+pub struct ClipboardHistoryEntry {
+    pub text: SharedString,
+    pub clipboard_selections: Option<Vec<ClipboardSelection>>,
+    pub pinned: bool,
+}
+
+impl ClipboardHistoryEntry {
+    pub fn to_clipboard_item(&self) -> ClipboardItem {
+        match &self.clipboard_selections {
+            Some(clipboard_selections) => ClipboardItem::new_string_with_json_metadata(
+                self.text.to_string(),
+                clipboard_selections.clone(),
+            ),
+            None => ClipboardItem::new_string(self.text.to_string()),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ClipboardHistory {
+    entries: VecDeque<ClipboardHistoryEntry>,
+}
+
+impl Global for ClipboardHistory {}
+
+impl ClipboardHistory {
+    /// Adds the text of a clipboard item to the history, unless the history is turned off.
+    pub fn record(item: &ClipboardItem, cx: &mut App) {
+        let max_entries = EditorSettings::get_global(cx).clipboard_history_size;
+        if max_entries == 0 {
+            return;
+        }
+        let Some(ClipboardEntry::String(string)) = item.entries().first() else {
+            return;
+        };
+        let text = SharedString::from(string.text().clone());
+        let clipboard_selections = string.metadata_json::<Vec<ClipboardSelection>>();
+        cx.default_global::<Self>()
+            .push(text, clipboard_selections, max_entries);
+    }
+
+    /// Returns the entries, pinned ones first and then the rest, most recent first.
+    pub fn entries(&self) -> impl Iterator<Item = &ClipboardHistoryEntry> {
+        let pinned = self.entries.iter().filter(|entry| entry.pinned);
+        pinned.chain(self.entries.iter().filter(|entry| !entry.pinned))
+    }
+
+    /// Adds text to the front of the history, moving it there if it's already in the history,
+    /// and forgets the oldest unpinned entries past `max_entries`.
+    fn push(
+        &mut self,
+        text: SharedString,
+        clipboard_selections: Option<Vec<ClipboardSelection>>,
+        max_entries: usize,
+    ) {
+        if text.trim().is_empty() {
+            return;
+        }
+        let pinned = self
+            .entries
+            .iter()
+            .position(|entry| entry.text == text)
+            .and_then(|ix| self.entries.remove(ix))
+            .is_some_and(|entry| entry.pinned);
+        self.entries.push_front(ClipboardHistoryEntry {
+            text,
+            clipboard_selections,
+            pinned,
+        });
+
+        let mut unpinned_entries = 0;
+        self.entries.retain(|entry| {
+            if !entry.pinned {
+                unpinned_entries += 1;
+            }
+            entry.pinned || unpinned_entries <= max_entries
+        });
+    }
+
+    /// Pins the entry with the given text, or unpins it if it's pinned.
+    pub fn toggle_pin(&mut self, text: &str) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.text == text) {
+            entry.pinned = !entry.pinned;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(history: &ClipboardHistory) -> Vec<&str> {
+        history.entries().map(|entry| entry.text.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_clipboard_history() {
+        let mut history = ClipboardHistory::default();
+        for text in ["one", "two", "three"] {
+            history.push(text.into(), None, 2);
+        }
+        assert_eq!(texts(&history), ["three", "two"]);
+
+        // Copying text that's in the history moves it to the front.
+        history.push("two".into(), None, 2);
+        assert_eq!(texts(&history), ["two", "three"]);
+
+        // Whitespace isn't recorded.
+        history.push("  \n".into(), None, 2);
+        assert_eq!(texts(&history), ["two", "three"]);
+
+        // Pinned entries come first, and aren't forgotten when the history is full.
+        history.toggle_pin("three");
+        history.push("four".into(), None, 2);
+        history.push("five".into(), None, 2);
+        assert_eq!(texts(&history), ["three", "five", "four"]);
+
+        // Copying a pinned entry again keeps it pinned.
+        history.push("three".into(), None, 2);
+        assert_eq!(texts(&history), ["three", "five", "four"]);
+
+        // Unpinned entries are forgotten again when the history is full.
+        history.toggle_pin("three");
+        history.push("six".into(), None, 2);
+        assert_eq!(texts(&history), ["six", "three"]);
+    }
+}
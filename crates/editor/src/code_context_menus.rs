@@ -1,3 +1,4 @@
+use collections::HashMap;
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{
     div, px, uniform_list, AnyElement, BackgroundExecutor, Entity, Focusable, FontWeight,
@@ -6,6 +7,7 @@ use gpui::{
 };
 use language::Buffer;
 use language::CodeLabel;
+use language::HighlightedText;
 use markdown::Markdown;
 use multi_buffer::{Anchor, ExcerptId};
 use ordered_float::OrderedFloat;
@@ -147,7 +149,7 @@ impl CodeContextMenu {
     ) -> Option<AnyElement> {
         match self {
             CodeContextMenu::Completions(menu) => menu.render_aside(editor, max_size, window, cx),
-            CodeContextMenu::CodeActions(_) => None,
+            CodeContextMenu::CodeActions(menu) => menu.render_aside(editor, max_size),
         }
     }
 
@@ -897,6 +899,18 @@ pub struct CodeActionsMenu {
     pub selected_item: usize,
     pub scroll_handle: UniformListScrollHandle,
     pub deployed_from_indicator: Option<DisplayRow>,
+    /// Previews of the edits that the actions make, by the indices of the actions, which are
+    /// shown beside the menu when this is set.
+    pub previews: Option<HashMap<usize, CodeActionPreview>>,
+}
+
+/// The edits that a code action makes, for showing them before it's confirmed.
+pub struct CodeActionPreview {
+    /// The action's edits to the menu's buffer, highlighted in the lines around them, or `None`
+    /// when the action runs a command instead of returning its edits.
+    pub edits: Option<HighlightedText>,
+    /// The number of other files that the action edits.
+    pub other_files: usize,
 }
 
 impl CodeActionsMenu {
@@ -1072,4 +1086,53 @@ impl CodeActionsMenu {
 
         Popover::new().child(list).into_any_element()
     }
+
+    fn render_aside(&self, editor: &Editor, max_size: Size<Pixels>) -> Option<AnyElement> {
+        let previews = self.previews.as_ref()?;
+        let preview = match previews.get(&self.selected_item) {
+            None => div().child(Label::new("Loading preview…").color(Color::Muted)),
+            Some(CodeActionPreview {
+                edits: None,
+                other_files,
+            }) => div().child(
+                Label::new(if *other_files > 0 {
+                    format!("Only edits other files ({other_files})")
+                } else {
+                    "Runs a command whose edits can't be previewed".to_string()
+                })
+                .color(Color::Muted),
+            ),
+            Some(CodeActionPreview {
+                edits: Some(edits),
+                other_files,
+            }) => v_flex()
+                .gap_1()
+                .child(edits.to_styled_text(&editor.style()?.text))
+                .when(*other_files > 0, |this| {
+                    this.child(
+                        Label::new(if *other_files == 1 {
+                            "Also edits 1 other file".to_string()
+                        } else {
+                            format!("Also edits {other_files} other files")
+                        })
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    )
+                }),
+        };
+
+        Some(
+            Popover::new()
+                .child(
+                    preview
+                        .id("code_action_preview")
+                        .px(MENU_ASIDE_X_PADDING / 2.)
+                        .max_w(max_size.width)
+                        .max_h(max_size.height)
+                        .overflow_y_scroll()
+                        .occlude(),
+                )
+                .into_any_element(),
+        )
+    }
 }
@@ -0,0 +1,100 @@
+use gpui::{Pixels, Point};
+use std::time::{Duration, Instant};
+
+const DURATION: Duration = Duration::from_millis(80);
+
+/// Moves the newest local cursor from where it was painted to where it moved to over a few
+/// frames, instead of jumping there.
+///
+/// Positions are in the editor's content, before it's scrolled, so that scrolling doesn't
+/// animate the cursor.
+#[derive(Default)]
+pub struct CursorAnimation {
+    from: Point<Pixels>,
+    to: Option<Point<Pixels>>,
+    started_at: Option<Instant>,
+    painted: Point<Pixels>,
+}
+
+impl CursorAnimation {
+    /// Returns where to paint the cursor, whose position is `target`, on this frame, and whether
+    /// the animation has frames left. Cursors that move further than `max_distance` jump there.
+    pub fn position(
+        &mut self,
+        target: Point<Pixels>,
+        max_distance: Pixels,
+        now: Instant,
+    ) -> (Point<Pixels>, bool) {
+        match self.to {
+            Some(to) if to == target => {}
+            Some(_)
+                if (target.x - self.painted.x).abs() <= max_distance
+                    && (target.y - self.painted.y).abs() <= max_distance =>
+            {
+                self.from = self.painted;
+                self.to = Some(target);
+                self.started_at = Some(now);
+            }
+            _ => {
+                self.to = Some(target);
+                self.started_at = None;
+            }
+        }
+
+        let Some(started_at) = self.started_at else {
+            self.painted = target;
+            return (target, false);
+        };
+        let progress = (now - started_at).as_secs_f32() / DURATION.as_secs_f32();
+        if progress >= 1. {
+            self.started_at = None;
+            self.painted = target;
+            return (target, false);
+        }
+
+        let eased = 1. - (1. - progress).powi(3);
+        self.painted = self.from + (target - self.from) * eased;
+        (self.painted, true)
+    }
+
+    /// Forgets where the cursor was painted, so that it doesn't animate from there when it's
+    /// painted again.
+    pub fn reset(&mut self) {
+        self.to = None;
+        self.started_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{point, px};
+
+    #[test]
+    fn test_cursor_animation() {
+        let start = Instant::now();
+        let mut animation = CursorAnimation::default();
+        assert_eq!(
+            animation.position(point(px(0.), px(0.)), px(100.), start),
+            (point(px(0.), px(0.)), false)
+        );
+
+        let target = point(px(40.), px(0.));
+        let (position, animating) = animation.position(target, px(100.), start);
+        assert_eq!((position, animating), (point(px(0.), px(0.)), true));
+        let (position, animating) = animation.position(target, px(100.), start + DURATION / 2);
+        assert!(animating);
+        assert!(position.x > px(20.) && position.x < px(40.));
+        assert_eq!(
+            animation.position(target, px(100.), start + DURATION),
+            (target, false)
+        );
+
+        // Cursors that move too far jump there.
+        let target = point(px(40.), px(400.));
+        assert_eq!(
+            animation.position(target, px(100.), start + DURATION),
+            (target, false)
+        );
+    }
+}
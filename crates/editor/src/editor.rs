@@ -14,9 +14,12 @@
 //! If you're looking to improve Vim mode, you should check out Vim crate that wraps Editor and overrides its behavior.
 pub mod actions;
 mod blink_manager;
+mod buffer_recovery;
 mod clangd_ext;
+pub mod clipboard_history;
 mod code_context_menus;
 pub mod commit_tooltip;
+mod cursor_animation;
 pub mod display_map;
 mod editor_settings;
 mod editor_settings_controls;
@@ -30,15 +33,18 @@ mod inlay_hint_cache;
 pub mod items;
 mod jsx_tag_auto_close;
 mod linked_editing_ranges;
+mod linked_transactions;
 mod lsp_ext;
 mod mouse_context_menu;
 pub mod movement;
+mod peek_view;
 mod persistence;
 mod proposed_changes_editor;
 mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
 pub mod tasks;
+mod text_decorations;
 
 #[cfg(test)]
 mod editor_tests;
@@ -55,15 +61,17 @@ use anyhow::{anyhow, Context as _, Result};
 use blink_manager::BlinkManager;
 use buffer_diff::DiffHunkStatus;
 use client::{Collaborator, ParticipantIndex};
+use clipboard_history::ClipboardHistory;
 use clock::ReplicaId;
 use collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use convert_case::{Case, Casing};
+use cursor_animation::CursorAnimation;
 use display_map::*;
 pub use display_map::{DisplayPoint, FoldPlaceholder};
 use editor_settings::GoToDefinitionFallback;
 pub use editor_settings::{
-    CurrentLineHighlight, EditorSettings, HideMouseMode, ScrollBeyondLastLine, SearchSettings,
-    ShowScrollbar,
+    CurrentLineHighlight, EditorSettings, HideMouseMode, ScrollBeyondLastLine,
+    ScrollbarMarkCategory, SearchSettings, ShowScrollbar,
 };
 pub use editor_settings_controls::*;
 use element::{layout_line, AcceptEditPredictionBinding, LineWithInvisibles, PositionMap};
@@ -79,19 +87,19 @@ use fuzzy::StringMatchCandidate;
 
 use ::git::Restore;
 use code_context_menus::{
-    AvailableCodeAction, CodeActionContents, CodeActionsItem, CodeActionsMenu, CodeContextMenu,
-    CompletionsMenu, ContextMenuOrigin,
+    AvailableCodeAction, CodeActionContents, CodeActionPreview, CodeActionsItem, CodeActionsMenu,
+    CodeContextMenu, CompletionsMenu, ContextMenuOrigin,
 };
 use git::blame::GitBlame;
 use gpui::{
     div, impl_actions, point, prelude::*, pulsating_between, px, relative, size, Action, Animation,
     AnimationExt, AnyElement, App, AppContext, AsyncWindowContext, AvailableSpace, Background,
     Bounds, ClickEvent, ClipboardEntry, ClipboardItem, Context, DispatchPhase, Edges, Entity,
-    EntityInputHandler, EventEmitter, FocusHandle, FocusOutEvent, Focusable, FontId, FontWeight,
-    Global, HighlightStyle, Hsla, KeyContext, Modifiers, MouseButton, MouseDownEvent, PaintQuad,
-    ParentElement, Pixels, Render, SharedString, Size, Stateful, Styled, StyledText, Subscription,
-    Task, TextStyle, TextStyleRefinement, UTF16Selection, UnderlineStyle, UniformListScrollHandle,
-    WeakEntity, WeakFocusHandle, Window,
+    EntityInputHandler, EventEmitter, FocusHandle, FocusOutEvent, Focusable, FontFeatures, FontId,
+    FontWeight, Global, HighlightStyle, Hsla, KeyContext, Modifiers, MouseButton, MouseDownEvent,
+    PaintQuad, ParentElement, Pixels, Render, SharedString, Size, Stateful, Styled, StyledText,
+    Subscription, Task, TextStyle, TextStyleRefinement, UTF16Selection, UnderlineStyle,
+    UniformListScrollHandle, WeakEntity, WeakFocusHandle, Window, WrapOptions,
 };
 use highlight_matching_bracket::refresh_matching_bracket_highlights;
 use hover_links::{find_file, HoverLink, HoveredLinkState, InlayHighlight};
@@ -123,6 +131,10 @@ use project::{
     ProjectPath,
 };
 
+use text_decorations::TextDecorations;
+pub use text_decorations::{TextDecoration, TextDecorationKind};
+
+pub use linked_transactions::link_transactions;
 pub use proposed_changes_editor::{
     ProposedChangeLocation, ProposedChangesEditor, ProposedChangesEditorToolbar,
 };
@@ -149,11 +161,12 @@ use multi_buffer::{
 use parking_lot::Mutex;
 use project::{
     debugger::breakpoint_store::Breakpoint,
+    lsp_command::code_action_kind_contains,
     lsp_store::{CompletionDocumentation, FormatTrigger, LspFormatTarget, OpenLspBufferHandle},
     project_settings::{GitGutterSetting, ProjectSettings},
     CodeAction, Completion, CompletionIntent, CompletionSource, DocumentHighlight, InlayHint,
-    Location, LocationLink, PrepareRenameResponse, Project, ProjectItem, ProjectTransaction,
-    TaskSourceKind,
+    Location, LocationLink, LspAction, PrepareRenameResponse, Project, ProjectItem,
+    ProjectTransaction, TaskSourceKind,
 };
 use rand::prelude::*;
 use rpc::{proto::*, ErrorExt};
@@ -167,7 +180,7 @@ use smallvec::SmallVec;
 use snippet::Snippet;
 use std::sync::Arc;
 use std::{
-    any::TypeId,
+    any::{Any, TypeId},
     borrow::Cow,
     cell::RefCell,
     cmp::{self, Ordering, Reverse},
@@ -246,6 +259,7 @@ const COLUMNAR_SELECTION_MODIFIERS: Modifiers = Modifiers {
 pub enum InlayId {
     InlineCompletion(usize),
     Hint(usize),
+    Decoration(usize),
 }
 
 impl InlayId {
@@ -253,6 +267,7 @@ impl InlayId {
         match self {
             Self::InlineCompletion(id) => *id,
             Self::Hint(id) => *id,
+            Self::Decoration(id) => *id,
         }
     }
 }
@@ -308,6 +323,7 @@ pub fn init(cx: &mut App) {
     workspace::register_project_item::<Editor>(cx);
     workspace::FollowableViewRegistry::register::<Editor>(cx);
     workspace::register_serializable_item::<Editor>(cx);
+    buffer_recovery::init(cx);
 
     cx.observe_new(
         |workspace: &mut Workspace, _: Option<&mut Window>, _cx: &mut Context<Workspace>| {
@@ -380,6 +396,14 @@ pub enum SelectPhase {
     End,
 }
 
+/// A mouse down inside a selection, which drags the selected text out of the window if the mouse
+/// moves, or places the cursor where it went down if it's released first.
+struct PendingTextDrag {
+    mouse_down_position: gpui::Point<Pixels>,
+    position: DisplayPoint,
+    text: String,
+}
+
 #[derive(Clone, Debug)]
 pub enum SelectMode {
     Character,
@@ -583,6 +607,11 @@ impl EditorActionId {
 
 type BackgroundHighlight = (fn(&ThemeColors) -> Hsla, Arc<[Range<Anchor>]>);
 type GutterHighlight = (fn(&App) -> Hsla, Arc<[Range<Anchor>]>);
+type ScrollbarMarks = (
+    ScrollbarMarkCategory,
+    fn(&ThemeColors) -> Hsla,
+    Arc<[Range<Anchor>]>,
+);
 
 #[derive(Default)]
 struct ScrollbarMarkerState {
@@ -608,6 +637,8 @@ struct RunnableTasks {
     extra_variables: HashMap<String, String>,
     // Full range of the tagged region. We use it to determine which `extra_variables` to grab for context resolution in e.g. a modal.
     context_range: Range<BufferOffset>,
+    // The file the runnable is in and the text of its `@run` capture, such as a test's name.
+    key: Option<RunnableKey>,
 }
 
 impl RunnableTasks {
@@ -623,6 +654,22 @@ impl RunnableTasks {
     }
 }
 
+/// Identifies a runnable, such as a test, in a project by the file it's in and the text of its
+/// `@run` capture, so that same-named runnables in different files are told apart.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RunnableKey {
+    pub path: ProjectPath,
+    pub name: SharedString,
+}
+
+/// The outcome of the last run of a runnable, such as a test, which colors its run indicator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunnableStatus {
+    Running,
+    Passed,
+    Failed,
+}
+
 #[derive(Clone)]
 struct ResolvedTasks {
     templates: SmallVec<[(TaskSourceKind, ResolvedTask); 1]>,
@@ -687,6 +734,7 @@ pub struct Editor {
     completion_provider: Option<Box<dyn CompletionProvider>>,
     collaboration_hub: Option<Box<dyn CollaborationHub>>,
     blink_manager: Entity<BlinkManager>,
+    cursor_animation: CursorAnimation,
     show_cursor_names: bool,
     hovered_cursors: HashMap<HoveredCursor, Task<()>>,
     pub show_local_selections: bool,
@@ -707,7 +755,10 @@ pub struct Editor {
     highlighted_rows: HashMap<TypeId, Vec<RowHighlight>>,
     background_highlights: TreeMap<TypeId, BackgroundHighlight>,
     gutter_highlights: TreeMap<TypeId, GutterHighlight>,
+    scrollbar_marks: TreeMap<TypeId, ScrollbarMarks>,
+    text_decorations: BTreeMap<SharedString, TextDecorations>,
     scrollbar_marker_state: ScrollbarMarkerState,
+    minimap_marker_state: ScrollbarMarkerState,
     active_indent_guides_state: ActiveIndentGuidesState,
     nav_history: Option<ItemNavHistory>,
     context_menu: RefCell<Option<CodeContextMenu>>,
@@ -725,6 +776,9 @@ pub struct Editor {
     linked_editing_range_task: Option<Task<Option<()>>>,
     linked_edit_ranges: linked_editing_ranges::LinkedEditingRanges,
     pending_rename: Option<RenameState>,
+    peek_view: Option<peek_view::PeekViewState>,
+    /// Whether the editor's pane is in zen mode, which hides the gutter.
+    zen_mode: bool,
     searchable: bool,
     cursor_shape: CursorShape,
     current_line_highlight: Option<CurrentLineHighlight>,
@@ -738,6 +792,7 @@ pub struct Editor {
     remote_id: Option<ViewId>,
     hover_state: HoverState,
     pending_mouse_down: Option<Rc<RefCell<Option<MouseDownEvent>>>>,
+    pending_text_drag: Option<PendingTextDrag>,
     gutter_hovered: bool,
     hovered_link_state: Option<HoveredLinkState>,
     edit_prediction_provider: Option<RegisteredInlineCompletionProvider>,
@@ -792,6 +847,7 @@ pub struct Editor {
     expect_bounds_change: Option<Bounds<Pixels>>,
     tasks: BTreeMap<(BufferId, BufferRow), RunnableTasks>,
     tasks_update_task: Option<Task<()>>,
+    runnable_statuses: Arc<HashMap<RunnableKey, RunnableStatus>>,
     breakpoint_store: Option<Entity<BreakpointStore>>,
     /// Allow's a user to create a breakpoint by selecting this indicator
     /// It should be None while a user is not hovering over the gutter
@@ -1342,17 +1398,27 @@ impl Editor {
                                 let focus_handle = editor.focus_handle(cx);
                                 if focus_handle.is_focused(window) {
                                     let snapshot = buffer.read(cx).snapshot();
-                                    for (range, snippet) in snippet_edits {
-                                        let editor_range =
-                                            language::range_from_lsp(*range).to_offset(&snapshot);
-                                        editor
-                                            .insert_snippet(
-                                                &[editor_range],
-                                                snippet.clone(),
-                                                window,
-                                                cx,
-                                            )
-                                            .ok();
+                                    let snippet_edits = snippet_edits
+                                        .iter()
+                                        .map(|(range, snippet)| {
+                                            let editor_range = language::range_from_lsp(*range)
+                                                .to_offset(&snapshot);
+                                            (editor_range, snippet.clone())
+                                        })
+                                        .collect::<Vec<_>>();
+                                    if editor.buffer.read(cx).is_singleton() {
+                                        editor.insert_snippet_edits(snippet_edits, window, cx);
+                                    } else {
+                                        for (editor_range, snippet) in snippet_edits {
+                                            editor
+                                                .insert_snippet(
+                                                    &[editor_range],
+                                                    snippet,
+                                                    window,
+                                                    cx,
+                                                )
+                                                .ok();
+                                        }
                                     }
                                 }
                             }
@@ -1457,6 +1523,7 @@ impl Editor {
             collaboration_hub: project.clone().map(|project| Box::new(project) as _),
             project,
             blink_manager: blink_manager.clone(),
+            cursor_animation: CursorAnimation::default(),
             show_local_selections: true,
             show_scrollbars: true,
             mode,
@@ -1475,7 +1542,10 @@ impl Editor {
             highlighted_rows: HashMap::default(),
             background_highlights: Default::default(),
             gutter_highlights: TreeMap::default(),
+            scrollbar_marks: TreeMap::default(),
+            text_decorations: BTreeMap::default(),
             scrollbar_marker_state: ScrollbarMarkerState::default(),
+            minimap_marker_state: ScrollbarMarkerState::default(),
             active_indent_guides_state: ActiveIndentGuidesState::default(),
             nav_history: None,
             context_menu: RefCell::new(None),
@@ -1494,6 +1564,8 @@ impl Editor {
             document_highlights_task: Default::default(),
             linked_editing_range_task: Default::default(),
             pending_rename: Default::default(),
+            peek_view: None,
+            zen_mode: false,
             searchable: true,
             cursor_shape: EditorSettings::get_global(cx)
                 .cursor_shape
@@ -1513,6 +1585,7 @@ impl Editor {
             remote_id: None,
             hover_state: Default::default(),
             pending_mouse_down: None,
+            pending_text_drag: None,
             hovered_link_state: Default::default(),
             edit_prediction_provider: None,
             active_inline_completion: None,
@@ -1554,6 +1627,7 @@ impl Editor {
             blame: None,
             blame_subscription: None,
             tasks: Default::default(),
+            runnable_statuses: Default::default(),
 
             breakpoint_store,
             gutter_breakpoint_indicator: (None, None),
@@ -1914,7 +1988,7 @@ impl Editor {
 
         EditorSnapshot {
             mode: self.mode,
-            show_gutter: self.show_gutter,
+            show_gutter: self.show_gutter && !self.zen_mode,
             show_line_numbers: self.show_line_numbers,
             show_git_diff_gutter: self.show_git_diff_gutter,
             show_code_actions: self.show_code_actions,
@@ -2943,6 +3017,28 @@ impl Editor {
         self.selections.pending_anchor().is_some() || self.columnar_selection_tail.is_some()
     }
 
+    /// Returns the text of the selections, to drag it out of the window, if the position is
+    /// inside one of them.
+    fn text_to_drag(&self, position: DisplayPoint, cx: &mut Context<Self>) -> Option<String> {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let point = position.to_point(&display_map);
+        let selections = self.selections.all::<Point>(cx);
+        if !selections
+            .iter()
+            .any(|selection| selection.start < point && point < selection.end)
+        {
+            return None;
+        }
+        let buffer = self.buffer.read(cx).read(cx);
+        Some(
+            selections
+                .iter()
+                .filter(|selection| !selection.is_empty())
+                .map(|selection| buffer.text_for_range(selection.range()).collect::<String>())
+                .join("\n"),
+        )
+    }
+
     pub fn cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
         self.selection_mark_mode = false;
 
@@ -3002,6 +3098,10 @@ impl Editor {
             return true;
         }
 
+        if is_user_requested && self.close_peek_view(window, cx) {
+            return true;
+        }
+
         false
     }
 
@@ -4147,6 +4247,35 @@ impl Editor {
             .collect()
     }
 
+    /// Lets large buffers that haven't been parsed yet parse their visible ranges first, so that
+    /// those are highlighted before the whole buffer has been parsed.
+    pub(crate) fn parse_visible_ranges(&self, cx: &mut Context<Editor>) {
+        let Some(visible_line_count) = self.visible_line_count() else {
+            return;
+        };
+        let multi_buffer = self.buffer().read(cx);
+        let multi_buffer_snapshot = multi_buffer.snapshot(cx);
+        let visible_start = self
+            .scroll_manager
+            .anchor()
+            .anchor
+            .to_point(&multi_buffer_snapshot);
+        let visible_end = multi_buffer_snapshot.clip_point(
+            visible_start + Point::new(visible_line_count.ceil() as u32, 0),
+            Bias::Left,
+        );
+        let visible_ranges = multi_buffer_snapshot
+            .range_to_buffer_ranges(visible_start..visible_end)
+            .into_iter()
+            .filter_map(|(buffer, range, _)| {
+                Some((multi_buffer.buffer(buffer.remote_id())?, range))
+            })
+            .collect::<Vec<_>>();
+        for (buffer, range) in visible_ranges {
+            buffer.update(cx, |buffer, cx| buffer.parse_visible_range(range, cx));
+        }
+    }
+
     pub fn text_layout_details(&self, window: &mut Window) -> TextLayoutDetails {
         TextLayoutDetails {
             text_system: window.text_system().clone(),
@@ -4829,6 +4958,7 @@ impl Editor {
                                     selected_item: Default::default(),
                                     scroll_handle: UniformListScrollHandle::default(),
                                     deployed_from_indicator,
+                                    previews: None,
                                 }));
                             if spawn_straight_away {
                                 if let Some(task) = editor.confirm_code_action(
@@ -4861,6 +4991,126 @@ impl Editor {
         .detach_and_log_err(cx);
     }
 
+    /// Shows the refactorings that language servers offer for the newest selection, along with
+    /// previews of the edits that each of them makes.
+    pub fn refactor(&mut self, _: &Refactor, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let selection = self.selections.newest_anchor().clone();
+        let multibuffer = self.buffer.read(cx);
+        let Some((buffer, start)) = multibuffer.text_anchor_for_position(selection.start, cx)
+        else {
+            return;
+        };
+        let Some((end_buffer, end)) = multibuffer.text_anchor_for_position(selection.end, cx)
+        else {
+            return;
+        };
+        if buffer != end_buffer {
+            return;
+        }
+        let excerpt_id = selection.start.excerpt_id;
+        let code_actions = project.update(cx, |project, cx| {
+            project.code_actions(
+                &buffer,
+                start..end,
+                Some(vec![CodeActionKind::REFACTOR]),
+                cx,
+            )
+        });
+
+        cx.spawn_in(window, async move |editor, cx| {
+            let code_actions = code_actions
+                .await?
+                .into_iter()
+                .filter(|action| match &action.lsp_action {
+                    LspAction::Action(lsp_action) => lsp_action.kind.as_ref().is_some_and(|kind| {
+                        code_action_kind_contains(&CodeActionKind::REFACTOR, kind)
+                    }),
+                    LspAction::Command(_) | LspAction::CodeLens(_) => false,
+                })
+                .collect::<Vec<_>>();
+            if code_actions.is_empty() {
+                return Ok(());
+            }
+
+            let provider: Rc<dyn CodeActionProvider> = Rc::new(project.clone());
+            let actions = code_actions
+                .iter()
+                .map(|action| AvailableCodeAction {
+                    excerpt_id,
+                    action: action.clone(),
+                    provider: provider.clone(),
+                })
+                .collect::<Rc<[_]>>();
+            editor.update_in(cx, |editor, window, cx| {
+                if !editor.focus_handle.is_focused(window) {
+                    return;
+                }
+                editor.completion_tasks.clear();
+                editor.discard_inline_completion(false, cx);
+                *editor.context_menu.borrow_mut() =
+                    Some(CodeContextMenu::CodeActions(CodeActionsMenu {
+                        buffer: buffer.clone(),
+                        actions: CodeActionContents {
+                            tasks: None,
+                            actions: Some(actions.clone()),
+                        },
+                        selected_item: 0,
+                        scroll_handle: UniformListScrollHandle::default(),
+                        deployed_from_indicator: None,
+                        previews: Some(HashMap::default()),
+                    }));
+                cx.notify();
+            })?;
+
+            for (ix, action) in code_actions.into_iter().enumerate() {
+                let action = project
+                    .update(cx, |project, cx| {
+                        project.resolve_code_action(&buffer, action, cx)
+                    })?
+                    .await;
+                let preview = match action {
+                    Ok(action) => code_action_preview(&buffer, &action, cx).await?,
+                    Err(error) => {
+                        log::error!("failed to resolve code action: {error:#}");
+                        CodeActionPreview {
+                            edits: None,
+                            other_files: 0,
+                        }
+                    }
+                };
+                let menu_shown = editor.update(cx, |editor, cx| {
+                    let mut context_menu = editor.context_menu.borrow_mut();
+                    let Some(CodeContextMenu::CodeActions(CodeActionsMenu {
+                        actions:
+                            CodeActionContents {
+                                actions: Some(shown_actions),
+                                ..
+                            },
+                        previews: Some(previews),
+                        ..
+                    })) = context_menu.as_mut()
+                    else {
+                        return false;
+                    };
+                    if !Rc::ptr_eq(shown_actions, &actions) {
+                        return false;
+                    }
+                    previews.insert(ix, preview);
+                    cx.notify();
+                    true
+                })?;
+                if !menu_shown {
+                    break;
+                }
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn confirm_code_action(
         &mut self,
         action: &ConfirmCodeAction,
@@ -6494,6 +6744,43 @@ impl Editor {
         .detach();
     }
 
+    /// Resolves the first task matching the predicate of the runnable that encloses the cursor,
+    /// or of the closest one, such as the test to run from a test explorer.
+    pub fn resolve_nearest_runnable_task(
+        &mut self,
+        predicate: impl Fn(&TaskTemplate) -> bool + 'static,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<(TaskSourceKind, ResolvedTask)>> {
+        let Some(project) = self.project.clone() else {
+            return Task::ready(None);
+        };
+        let Some((buffer, buffer_row, tasks)) = self
+            .find_enclosing_node_task(cx)
+            .or_else(|| self.find_closest_task(cx))
+        else {
+            return Task::ready(None);
+        };
+
+        let task_context = Self::build_tasks_context(&project, &buffer, buffer_row, &tasks, cx);
+        cx.spawn(async move |_, _| {
+            let context = task_context.await?;
+            tasks
+                .resolve(&context)
+                .find(|(_, task)| predicate(task.original_task()))
+        })
+    }
+
+    /// Sets the outcomes of the last runs of runnables in the editor's project, which color
+    /// their run indicators in the gutter.
+    pub fn set_runnable_statuses(
+        &mut self,
+        statuses: Arc<HashMap<RunnableKey, RunnableStatus>>,
+        cx: &mut Context<Self>,
+    ) {
+        self.runnable_statuses = statuses;
+        cx.notify();
+    }
+
     fn find_closest_task(
         &mut self,
         cx: &mut Context<Self>,
@@ -6555,10 +6842,16 @@ impl Editor {
         _style: &EditorStyle,
         is_active: bool,
         row: DisplayRow,
+        status: Option<RunnableStatus>,
         breakpoint: Option<(Anchor, Breakpoint)>,
         cx: &mut Context<Self>,
     ) -> IconButton {
-        let color = Color::Muted;
+        let color = match status {
+            None => Color::Muted,
+            Some(RunnableStatus::Running) => Color::Accent,
+            Some(RunnableStatus::Passed) => Color::Success,
+            Some(RunnableStatus::Failed) => Color::Error,
+        };
         let position = breakpoint.as_ref().map(|(anchor, _)| *anchor);
 
         IconButton::new(("run_indicator", row.0 as usize), ui::IconName::Play)
@@ -7795,6 +8088,107 @@ impl Editor {
         Ok(())
     }
 
+    /// Inserts snippets at several ranges of a singleton buffer in one edit, like those that a
+    /// code action returns, and links their tabstops by their order, so that a placeholder that
+    /// appears in several of them, like the name of an extracted function, is only typed once.
+    fn insert_snippet_edits(
+        &mut self,
+        mut snippet_edits: Vec<(Range<usize>, Snippet)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        snippet_edits.sort_by_key(|(range, _)| range.start);
+        // Each snippet's last tabstop is its final one, which goes last when they're linked.
+        let final_tabstop_ix = snippet_edits
+            .iter()
+            .map(|(_, snippet)| snippet.tabstops.len().saturating_sub(1))
+            .max()
+            .unwrap_or(0);
+
+        let (ranges, choices): (Vec<_>, Vec<_>) = self.buffer.update(cx, |buffer, cx| {
+            let edits = snippet_edits
+                .iter()
+                .map(|(range, snippet)| (range.clone(), snippet.text.clone()));
+            buffer.edit(edits, None, cx);
+
+            let snapshot = &*buffer.read(cx);
+            let mut tabstops: Vec<(Vec<Range<Anchor>>, Option<Vec<String>>)> =
+                vec![(Vec::new(), None); final_tabstop_ix + 1];
+            let mut implicit_final_tabstop = None;
+            let mut delta = 0_isize;
+            for (insertion_range, snippet) in &snippet_edits {
+                let insertion_start = insertion_range.start as isize + delta;
+                delta += snippet.text.len() as isize - insertion_range.len() as isize;
+                let snippet_final_tabstop_ix = snippet.tabstops.len().saturating_sub(1);
+                for (ix, tabstop) in snippet.tabstops.iter().enumerate() {
+                    let tabstop_ranges = tabstop.ranges.iter().map(|tabstop_range| {
+                        let start =
+                            ((insertion_start + tabstop_range.start) as usize).min(snapshot.len());
+                        let end =
+                            ((insertion_start + tabstop_range.end) as usize).min(snapshot.len());
+                        snapshot.anchor_before(start)..snapshot.anchor_after(end)
+                    });
+                    if ix == snippet_final_tabstop_ix {
+                        // Snippets without a `$0` end with an empty tabstop, which is only kept
+                        // for the last one, when none of them has a `$0`.
+                        let is_implicit = tabstop.choices.is_none()
+                            && tabstop.ranges.iter().all(|range| {
+                                range.is_empty() && range.start == snippet.text.len() as isize
+                            });
+                        if is_implicit {
+                            implicit_final_tabstop = Some(tabstop_ranges.collect::<Vec<_>>());
+                            continue;
+                        }
+                    }
+
+                    let linked_ix = if ix == snippet_final_tabstop_ix {
+                        final_tabstop_ix
+                    } else {
+                        ix
+                    };
+                    let (ranges, choices) = &mut tabstops[linked_ix];
+                    ranges.extend(tabstop_ranges);
+                    if choices.is_none() {
+                        *choices = tabstop.choices.clone();
+                    }
+                }
+            }
+            if tabstops[final_tabstop_ix].0.is_empty() {
+                if let Some(implicit_final_tabstop) = implicit_final_tabstop {
+                    tabstops[final_tabstop_ix].0 = implicit_final_tabstop;
+                }
+            }
+
+            tabstops
+                .into_iter()
+                .filter(|(ranges, _)| !ranges.is_empty())
+                .map(|(mut ranges, choices)| {
+                    ranges.sort_unstable_by(|a, b| a.start.cmp(&b.start, snapshot));
+                    (ranges, choices)
+                })
+                .unzip()
+        });
+
+        let Some(first_ranges) = ranges.first() else {
+            return;
+        };
+        self.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+            s.select_ranges(first_ranges.iter().cloned());
+        });
+        if let (Some(Some(first_choices)), Some(selection)) =
+            (choices.first(), first_ranges.first())
+        {
+            self.show_snippet_choices(first_choices, selection.clone(), cx);
+        }
+        if ranges.len() > 1 {
+            self.snippet_stack.push(SnippetState {
+                active_index: 0,
+                ranges,
+                choices,
+            });
+        }
+    }
+
     pub fn move_to_next_snippet_tabstop(
         &mut self,
         window: &mut Window,
@@ -9067,6 +9461,41 @@ impl Editor {
         })
     }
 
+    pub fn convert_indentation_to_spaces(
+        &mut self,
+        _: &ConvertIndentationToSpaces,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.convert_indentation(IndentKind::Space, window, cx)
+    }
+
+    pub fn convert_indentation_to_tabs(
+        &mut self,
+        _: &ConvertIndentationToTabs,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.convert_indentation(IndentKind::Tab, window, cx)
+    }
+
+    fn convert_indentation(
+        &mut self,
+        kind: IndentKind,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.read_only(cx) {
+            return;
+        }
+        let buffers = self.buffer.read(cx).all_buffers();
+        self.transact(window, cx, |_, _, cx| {
+            for buffer in buffers {
+                buffer.update(cx, |buffer, cx| buffer.convert_indentation(kind, cx));
+            }
+        });
+    }
+
     fn manipulate_text<Fn>(&mut self, window: &mut Window, cx: &mut Context<Self>, mut callback: Fn)
     where
         Fn: FnMut(&str) -> String,
@@ -9691,6 +10120,7 @@ impl Editor {
     pub fn cut(&mut self, _: &Cut, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
         let item = self.cut_common(window, cx);
+        ClipboardHistory::record(&item, cx);
         cx.write_to_clipboard(item);
     }
 
@@ -9704,6 +10134,7 @@ impl Editor {
             });
         });
         let item = self.cut_common(window, cx);
+        ClipboardHistory::record(&item, cx);
         cx.set_global(KillRing(item))
     }
 
@@ -9803,10 +10234,9 @@ impl Editor {
             }
         }
 
-        cx.write_to_clipboard(ClipboardItem::new_string_with_json_metadata(
-            text,
-            clipboard_selections,
-        ));
+        let item = ClipboardItem::new_string_with_json_metadata(text, clipboard_selections);
+        ClipboardHistory::record(&item, cx);
+        cx.write_to_clipboard(item);
     }
 
     pub fn do_paste(
@@ -9904,6 +10334,9 @@ impl Editor {
     pub fn paste(&mut self, _: &Paste, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
         if let Some(item) = cx.read_from_clipboard() {
+            // Text that was copied in other applications is added to the history when it's
+            // pasted.
+            ClipboardHistory::record(&item, cx);
             let entries = item.entries();
 
             match entries.first() {
@@ -9930,6 +10363,9 @@ impl Editor {
         self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
 
         if let Some(transaction_id) = self.buffer.update(cx, |buffer, cx| buffer.undo(cx)) {
+            if let Some(buffer) = self.buffer.read(cx).as_singleton() {
+                linked_transactions::undo_linked_transactions(&buffer, transaction_id, cx);
+            }
             if let Some((selections, _)) =
                 self.selection_history.transaction(transaction_id).cloned()
             {
@@ -9960,6 +10396,9 @@ impl Editor {
         self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
 
         if let Some(transaction_id) = self.buffer.update(cx, |buffer, cx| buffer.redo(cx)) {
+            if let Some(buffer) = self.buffer.read(cx).as_singleton() {
+                linked_transactions::redo_linked_transactions(&buffer, transaction_id, cx);
+            }
             if let Some((_, Some(selections))) =
                 self.selection_history.transaction(transaction_id).cloned()
             {
@@ -11156,6 +11595,14 @@ impl Editor {
         }
     }
 
+    /// Returns the cursor position of a navigation history entry for an editor, such as to
+    /// persist it. Navigating to a [`Point`] moves the cursor there.
+    pub fn navigation_position(data: &(dyn Any + Send)) -> Option<Point> {
+        data.downcast_ref::<NavigationData>()
+            .map(|data| data.cursor_position)
+            .or_else(|| data.downcast_ref::<Point>().copied())
+    }
+
     pub fn select_to_end(&mut self, _: &SelectToEnd, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(&HideMouseCursorOrigin::MovementAction);
         let buffer = self.buffer.read(cx).snapshot(cx);
@@ -12371,6 +12818,21 @@ impl Editor {
 
                 let context_range =
                     BufferOffset(runnable.full_range.start)..BufferOffset(runnable.full_range.end);
+                let name = snapshot
+                    .buffer_snapshot
+                    .text_for_range(runnable.run_range.clone())
+                    .collect::<String>();
+                let key = cx
+                    .update(|_, cx| {
+                        let buffer = project.read(cx).buffer_for_id(runnable.buffer_id, cx)?;
+                        let path = buffer.read(cx).project_path(cx)?;
+                        Some(RunnableKey {
+                            path,
+                            name: name.into(),
+                        })
+                    })
+                    .ok()
+                    .flatten();
                 Some((
                     (runnable.buffer_id, row),
                     RunnableTasks {
@@ -12381,6 +12843,7 @@ impl Editor {
                         context_range,
                         column: point.column,
                         extra_variables: runnable.extra_captures,
+                        key,
                     },
                 ))
             })
@@ -13764,14 +14227,26 @@ impl Editor {
 
         Some(cx.spawn_in(window, async move |editor, cx| {
             let project_transaction = rename.await?;
-            Self::open_project_transaction(
-                &editor,
-                workspace,
-                project_transaction,
-                format!("Rename: {} → {}", old_name, new_name),
-                cx,
-            )
-            .await?;
+            let title = format!("Rename: {} → {}", old_name, new_name);
+            let preview = project_transaction.0.len() > 1
+                && cx.update(|_, cx| EditorSettings::get_global(cx).rename_preview)?;
+            if preview {
+                workspace.update_in(cx, |workspace, window, cx| {
+                    Self::open_rename_preview(workspace, project_transaction, title, window, cx)
+                })?;
+            } else {
+                cx.update(|_, cx| {
+                    link_transactions(
+                        project_transaction
+                            .0
+                            .iter()
+                            .map(|(buffer, transaction)| (buffer.clone(), transaction.id)),
+                        cx,
+                    )
+                })?;
+                Self::open_project_transaction(&editor, workspace, project_transaction, title, cx)
+                    .await?;
+            }
 
             editor.update(cx, |editor, cx| {
                 editor.refresh_document_highlights(cx);
@@ -13780,6 +14255,76 @@ impl Editor {
         }))
     }
 
+    /// Reverts a rename that edited several files and proposes its edits in a preview instead,
+    /// where edits or whole files can be restored to leave them out before the rest is applied.
+    fn open_rename_preview(
+        workspace: &mut Workspace,
+        transaction: ProjectTransaction,
+        title: String,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let mut entries = transaction.0.into_iter().collect::<Vec<_>>();
+        entries
+            .sort_unstable_by_key(|(buffer, _)| buffer.read(cx).file().map(|f| f.path().clone()));
+
+        let mut locations = Vec::new();
+        let mut edits_by_buffer = Vec::new();
+        for (buffer, transaction) in entries {
+            let (edits, ranges) = buffer.update(cx, |buffer, cx| {
+                let edits = buffer
+                    .edits_since::<usize>(&transaction.start)
+                    .map(|edit| {
+                        let new_text = buffer.text_for_range(edit.new).collect::<String>();
+                        (edit.old, new_text)
+                    })
+                    .collect::<Vec<_>>();
+                buffer.undo_transaction(transaction.id, cx);
+                buffer.forget_transaction(transaction.id);
+
+                let max_row = buffer.max_point().row;
+                let mut ranges = Vec::<Range<Point>>::new();
+                for (range, _) in &edits {
+                    let start_row = buffer
+                        .offset_to_point(range.start)
+                        .row
+                        .saturating_sub(DEFAULT_MULTIBUFFER_CONTEXT);
+                    let end_row = (buffer.offset_to_point(range.end).row
+                        + DEFAULT_MULTIBUFFER_CONTEXT)
+                        .min(max_row);
+                    let range =
+                        Point::new(start_row, 0)..Point::new(end_row, buffer.line_len(end_row));
+                    match ranges.last_mut() {
+                        Some(last_range) if last_range.end.row + 1 >= range.start.row => {
+                            last_range.end = range.end;
+                        }
+                        _ => ranges.push(range),
+                    }
+                }
+                (edits, ranges)
+            });
+            locations.push(ProposedChangeLocation {
+                buffer: buffer.clone(),
+                ranges,
+            });
+            edits_by_buffer.push((buffer, edits));
+        }
+
+        let project = workspace.project().clone();
+        let preview =
+            cx.new(|cx| ProposedChangesEditor::new(title, locations, Some(project), window, cx));
+        preview.update(cx, |preview, cx| {
+            for (buffer, edits) in edits_by_buffer {
+                if let Some(branch_buffer) = preview.branch_buffer_for_base(&buffer) {
+                    branch_buffer.update(cx, |branch_buffer, cx| {
+                        branch_buffer.edit(edits, None, cx);
+                    });
+                }
+            }
+        });
+        workspace.add_item_to_active_pane(Box::new(preview), None, true, window, cx);
+    }
+
     fn take_rename(
         &mut self,
         moving_cursor: bool,
@@ -14824,7 +15369,7 @@ impl Editor {
             }
         }
 
-        self.scrollbar_marker_state.dirty = true;
+        self.invalidate_scrollbar_markers();
         self.folds_did_change(cx);
     }
 
@@ -14920,7 +15465,7 @@ impl Editor {
         }
 
         cx.notify();
-        self.scrollbar_marker_state.dirty = true;
+        self.invalidate_scrollbar_markers();
         self.active_indent_guides_state.dirty = true;
     }
 
@@ -15202,11 +15747,15 @@ impl Editor {
         self.hide_mouse_cursor(&HideMouseCursorOrigin::TypingAction);
 
         let buffers = self.buffer.read(cx).all_buffers();
+        let mut transactions = Vec::new();
         for branch_buffer in buffers {
-            branch_buffer.update(cx, |branch_buffer, cx| {
-                branch_buffer.merge_into_base(Vec::new(), cx);
+            let base_buffer = branch_buffer.read(cx).base_buffer();
+            let transaction_id = branch_buffer.update(cx, |branch_buffer, cx| {
+                branch_buffer.merge_into_base(Vec::new(), cx)
             });
+            transactions.extend(base_buffer.zip(transaction_id));
         }
+        link_transactions(transactions, cx);
 
         if let Some(project) = self.project.clone() {
             self.save(true, project, window, cx).detach_and_log_err(cx);
@@ -15223,6 +15772,7 @@ impl Editor {
         let snapshot = self.snapshot(window, cx);
         let hunks = snapshot.hunks_for_ranges(self.selections.ranges(cx));
         let mut ranges_by_buffer = HashMap::default();
+        let mut transactions = Vec::new();
         self.transact(window, cx, |editor, _window, cx| {
             for hunk in hunks {
                 if let Some(buffer) = editor.buffer.read(cx).buffer(hunk.buffer_id) {
@@ -15234,11 +15784,13 @@ impl Editor {
             }
 
             for (buffer, ranges) in ranges_by_buffer {
-                buffer.update(cx, |buffer, cx| {
-                    buffer.merge_into_base(ranges, cx);
-                });
+                let base_buffer = buffer.read(cx).base_buffer();
+                let transaction_id =
+                    buffer.update(cx, |buffer, cx| buffer.merge_into_base(ranges, cx));
+                transactions.extend(base_buffer.zip(transaction_id));
             }
         });
+        link_transactions(transactions, cx);
 
         if let Some(project) = self.project.clone() {
             self.save(true, project, window, cx).detach_and_log_err(cx);
@@ -15398,6 +15950,12 @@ impl Editor {
             .text()
     }
 
+    /// Recomputes the markers of the scrollbar and the minimap the next time they're painted.
+    fn invalidate_scrollbar_markers(&mut self) {
+        self.scrollbar_marker_state.dirty = true;
+        self.minimap_marker_state.dirty = true;
+    }
+
     pub fn wrap_guides(&self, cx: &App) -> SmallVec<[(usize, bool); 2]> {
         let mut wrap_guides = smallvec::smallvec![];
 
@@ -15436,6 +15994,16 @@ impl Editor {
             language_settings::SoftWrap::Bounded => {
                 SoftWrap::Bounded(settings.preferred_line_length)
             }
+            language_settings::SoftWrap::Fixed => SoftWrap::Column(settings.soft_wrap_column),
+        }
+    }
+
+    /// Returns how lines are soft-wrapped, according to the settings of the editor's buffer.
+    pub fn wrap_options(&self, cx: &App) -> WrapOptions {
+        let settings = self.buffer.read(cx).language_settings(cx);
+        WrapOptions {
+            at_word_boundaries: settings.soft_wrap_at_word_boundaries,
+            hanging_indent: settings.soft_wrap_hanging_indent,
         }
     }
 
@@ -15480,11 +16048,22 @@ impl Editor {
         self.style.as_ref()
     }
 
+    /// Returns the font features configured for the language of the editor's buffer, which
+    /// replace the buffer font's features.
+    fn language_font_features(&self, cx: &App) -> Option<FontFeatures> {
+        let language_settings = self.buffer.read(cx).language_settings(cx);
+        ThemeSettings::get_global(cx)
+            .resolve_font_features(language_settings.font_features.as_ref()?)
+    }
+
     // Called by the element. This method is not designed to be called outside of the editor
     // element's layout code because it does not notify when rewrapping is computed synchronously.
     pub(crate) fn set_wrap_width(&self, width: Option<Pixels>, cx: &mut App) -> bool {
-        self.display_map
-            .update(cx, |map, cx| map.set_wrap_width(width, cx))
+        let options = self.wrap_options(cx);
+        self.display_map.update(cx, |map, cx| {
+            let options_changed = map.set_wrap_options(options, cx);
+            map.set_wrap_width(width, cx) || options_changed
+        })
     }
 
     pub fn set_soft_wrap(&mut self) {
@@ -16362,7 +16941,7 @@ impl Editor {
     ) {
         self.background_highlights
             .insert(TypeId::of::<T>(), (color_fetcher, Arc::from(ranges)));
-        self.scrollbar_marker_state.dirty = true;
+        self.invalidate_scrollbar_markers();
         cx.notify();
     }
 
@@ -16372,7 +16951,7 @@ impl Editor {
     ) -> Option<BackgroundHighlight> {
         let text_highlights = self.background_highlights.remove(&TypeId::of::<T>())?;
         if !text_highlights.1.is_empty() {
-            self.scrollbar_marker_state.dirty = true;
+            self.invalidate_scrollbar_markers();
             cx.notify();
         }
         Some(text_highlights)
@@ -16465,6 +17044,38 @@ impl Editor {
             })
     }
 
+    /// Marks the given ranges in the scrollbar, replacing the marks that were added before with
+    /// the same type. The marks are only shown when the scrollbar settings show their category.
+    pub fn set_scrollbar_marks<T: 'static>(
+        &mut self,
+        category: ScrollbarMarkCategory,
+        ranges: &[Range<Anchor>],
+        color_fetcher: fn(&ThemeColors) -> Hsla,
+        cx: &mut Context<Self>,
+    ) {
+        self.scrollbar_marks.insert(
+            TypeId::of::<T>(),
+            (category, color_fetcher, Arc::from(ranges)),
+        );
+        self.invalidate_scrollbar_markers();
+        cx.notify();
+    }
+
+    pub fn clear_scrollbar_marks<T: 'static>(&mut self, cx: &mut Context<Self>) {
+        if self.scrollbar_marks.remove(&TypeId::of::<T>()).is_some() {
+            self.invalidate_scrollbar_markers();
+            cx.notify();
+        }
+    }
+
+    /// Returns whether any marks were added to the scrollbar in a category that the given
+    /// settings show.
+    pub fn has_scrollbar_marks(&self, settings: &editor_settings::Scrollbar) -> bool {
+        self.scrollbar_marks
+            .values()
+            .any(|(category, _, ranges)| settings.shows(*category) && !ranges.is_empty())
+    }
+
     pub fn has_background_highlights<T: 'static>(&self) -> bool {
         self.background_highlights
             .get(&TypeId::of::<T>())
@@ -16719,7 +17330,7 @@ impl Editor {
                 singleton_buffer_edited,
                 edited_buffer: buffer_edited,
             } => {
-                self.scrollbar_marker_state.dirty = true;
+                self.invalidate_scrollbar_markers();
                 self.active_indent_guides_state.dirty = true;
                 self.refresh_active_diagnostics(cx);
                 self.refresh_code_actions(window, cx);
@@ -16837,6 +17448,7 @@ impl Editor {
                 self.tasks_update_task = Some(self.refresh_runnables(window, cx));
             }
             multi_buffer::Event::LanguageChanged(buffer_id) => {
+                self.parse_visible_ranges(cx);
                 linked_editing_ranges::refresh_linked_ranges(self, window, cx);
                 jsx_tag_auto_close::refresh_enabled_in_any_buffer(self, multibuffer, cx);
                 cx.emit(EditorEvent::Reparsed(*buffer_id));
@@ -16851,7 +17463,7 @@ impl Editor {
             multi_buffer::Event::DiagnosticsUpdated => {
                 self.refresh_active_diagnostics(cx);
                 self.refresh_inline_diagnostics(true, window, cx);
-                self.scrollbar_marker_state.dirty = true;
+                self.invalidate_scrollbar_markers();
                 cx.notify();
             }
             _ => {}
@@ -19100,7 +19712,9 @@ impl Render for Editor {
             EditorMode::Full => TextStyle {
                 color: cx.theme().colors().editor_foreground,
                 font_family: settings.buffer_font.family.clone(),
-                font_features: settings.buffer_font.features.clone(),
+                font_features: self
+                    .language_font_features(cx)
+                    .unwrap_or_else(|| settings.buffer_font.features.clone()),
                 font_fallbacks: settings.buffer_font.fallbacks.clone(),
                 font_size: settings.buffer_font_size(cx).into(),
                 font_weight: settings.buffer_font.weight,
@@ -19637,6 +20251,113 @@ fn inline_completion_edit_text(
     edit_preview.highlight_edits(current_snapshot, &edits, include_deletions, cx)
 }
 
+/// Previews the edits that a resolved code action makes to the given buffer.
+async fn code_action_preview(
+    buffer: &Entity<Buffer>,
+    action: &CodeAction,
+    cx: &mut AsyncWindowContext,
+) -> Result<CodeActionPreview> {
+    let workspace_edit = match &action.lsp_action {
+        LspAction::Action(lsp_action) => lsp_action.edit.as_ref(),
+        LspAction::Command(_) | LspAction::CodeLens(_) => None,
+    };
+    let Some(workspace_edit) = workspace_edit else {
+        return Ok(CodeActionPreview {
+            edits: None,
+            other_files: 0,
+        });
+    };
+
+    let buffer_uri = buffer.read_with(cx, |buffer, cx| {
+        let abs_path = buffer.file()?.as_local()?.abs_path(cx);
+        lsp::Url::from_file_path(abs_path).ok()
+    })?;
+    let mut buffer_edits = Vec::new();
+    let mut other_files = HashSet::default();
+    for (uri, range, new_text) in workspace_edit_text_edits(workspace_edit) {
+        if Some(uri) == buffer_uri.as_ref() {
+            buffer_edits.push((range, new_text));
+        } else {
+            other_files.insert(uri);
+        }
+    }
+    if buffer_edits.is_empty() {
+        return Ok(CodeActionPreview {
+            edits: None,
+            other_files: other_files.len(),
+        });
+    }
+
+    let (edits, edit_preview) = buffer.read_with(cx, |buffer, cx| {
+        let snapshot = buffer.snapshot();
+        let mut edits = buffer_edits
+            .into_iter()
+            .map(|(range, new_text)| {
+                let start = snapshot.clip_point_utf16(point_from_lsp(range.start), Bias::Left);
+                let end = snapshot.clip_point_utf16(point_from_lsp(range.end), Bias::Left);
+                (
+                    snapshot.anchor_before(start)..snapshot.anchor_after(end),
+                    new_text,
+                )
+            })
+            .collect::<Vec<_>>();
+        edits.sort_by(|(a, _), (b, _)| a.start.cmp(&b.start, &snapshot));
+        let edits: Arc<[_]> = edits.into();
+        (edits.clone(), buffer.preview_edits(edits, cx))
+    })?;
+    let edit_preview = edit_preview.await;
+    let edits = buffer.read_with(cx, |buffer, cx| {
+        edit_preview.highlight_edits(&buffer.snapshot(), &edits, true, cx)
+    })?;
+    Ok(CodeActionPreview {
+        edits: Some(edits),
+        other_files: other_files.len(),
+    })
+}
+
+/// The text edits of a workspace edit with the files that they're in, with snippets' text in
+/// place of the snippets.
+fn workspace_edit_text_edits(
+    workspace_edit: &lsp::WorkspaceEdit,
+) -> Vec<(&lsp::Url, lsp::Range, String)> {
+    let mut text_edits = Vec::new();
+    for (uri, edits) in workspace_edit.changes.iter().flatten() {
+        for edit in edits {
+            text_edits.push((uri, edit.range, edit.new_text.clone()));
+        }
+    }
+
+    let document_edits = match &workspace_edit.document_changes {
+        Some(lsp::DocumentChanges::Edits(edits)) => edits.iter().collect(),
+        Some(lsp::DocumentChanges::Operations(operations)) => operations
+            .iter()
+            .filter_map(|operation| match operation {
+                lsp::DocumentChangeOperation::Edit(edit) => Some(edit),
+                lsp::DocumentChangeOperation::Op(_) => None,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    for document_edit in document_edits {
+        let uri = &document_edit.text_document.uri;
+        for edit in &document_edit.edits {
+            let (range, new_text) = match edit {
+                lsp::Edit::Plain(edit) => (edit.range, edit.new_text.clone()),
+                lsp::Edit::Annotated(edit) => {
+                    (edit.text_edit.range, edit.text_edit.new_text.clone())
+                }
+                lsp::Edit::Snippet(edit) => {
+                    let text = Snippet::parse(&edit.snippet.value)
+                        .map_or_else(|_| edit.snippet.value.clone(), |snippet| snippet.text);
+                    (edit.range, text)
+                }
+            };
+            text_edits.push((uri, range, new_text));
+        }
+    }
+    text_edits
+}
+
 pub fn highlight_diagnostic_message(
     diagnostic: &Diagnostic,
     mut max_message_rows: Option<u8>,
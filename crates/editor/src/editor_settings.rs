@@ -8,6 +8,7 @@ use settings::{Settings, SettingsSources};
 pub struct EditorSettings {
     pub cursor_blink: bool,
     pub cursor_shape: Option<CursorShape>,
+    pub cursor_animation: bool,
     pub current_line_highlight: CurrentLineHighlight,
     pub selection_highlight: bool,
     pub selection_highlight_debounce: u64,
@@ -16,12 +17,14 @@ pub struct EditorSettings {
     pub hover_popover_delay: u64,
     pub toolbar: Toolbar,
     pub scrollbar: Scrollbar,
+    pub minimap: Minimap,
     pub gutter: Gutter,
     pub scroll_beyond_last_line: ScrollBeyondLastLine,
     pub vertical_scroll_margin: f32,
     pub autoscroll_on_clicks: bool,
     pub horizontal_scroll_margin: f32,
     pub scroll_sensitivity: f32,
+    pub smooth_scroll: bool,
     pub relative_line_numbers: bool,
     pub seed_search_query_from_cursor: SeedQuerySetting,
     pub use_smartcase_search: bool,
@@ -29,6 +32,8 @@ pub struct EditorSettings {
     pub redact_private_values: bool,
     pub expand_excerpt_lines: u32,
     pub middle_click_paste: bool,
+    pub drag_and_drop_selection: bool,
+    pub clipboard_history_size: usize,
     #[serde(default)]
     pub double_click_in_multibuffer: DoubleClickInMultibuffer,
     pub search_wrap: bool,
@@ -38,6 +43,7 @@ pub struct EditorSettings {
     pub show_signature_help_after_edits: bool,
     #[serde(default)]
     pub go_to_definition_fallback: GoToDefinitionFallback,
+    pub rename_preview: bool,
     pub jupyter: Jupyter,
     pub hide_mouse: Option<HideMouseMode>,
 }
@@ -112,9 +118,43 @@ pub struct Scrollbar {
     pub search_results: bool,
     pub diagnostics: ScrollbarDiagnostics,
     pub cursors: bool,
+    pub vim_marks: bool,
     pub axes: ScrollbarAxes,
 }
 
+/// A category of marks in the scrollbar, which can be hidden with its setting.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ScrollbarMarkCategory {
+    GitDiff,
+    SearchResults,
+    SelectedText,
+    SelectedSymbol,
+    Diagnostics,
+    Cursors,
+    VimMarks,
+}
+
+impl Scrollbar {
+    /// Returns whether marks of the given category are shown in the scrollbar.
+    pub fn shows(&self, category: ScrollbarMarkCategory) -> bool {
+        match category {
+            ScrollbarMarkCategory::GitDiff => self.git_diff,
+            ScrollbarMarkCategory::SearchResults => self.search_results,
+            ScrollbarMarkCategory::SelectedText => self.selected_text,
+            ScrollbarMarkCategory::SelectedSymbol => self.selected_symbol,
+            ScrollbarMarkCategory::Diagnostics => self.diagnostics != ScrollbarDiagnostics::None,
+            ScrollbarMarkCategory::Cursors => self.cursors,
+            ScrollbarMarkCategory::VimMarks => self.vim_marks,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct Minimap {
+    pub enabled: bool,
+    pub width: f32,
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct Gutter {
     pub line_numbers: bool,
@@ -251,6 +291,10 @@ pub struct EditorSettingsContent {
     ///
     /// Default: None
     pub cursor_shape: Option<CursorShape>,
+    /// Whether the cursor glides to where it moves to, instead of jumping there.
+    ///
+    /// Default: true
+    pub cursor_animation: Option<bool>,
     /// Determines when the mouse cursor should be hidden in an editor or input box.
     ///
     /// Default: on_typing_and_movement
@@ -285,6 +329,8 @@ pub struct EditorSettingsContent {
     pub toolbar: Option<ToolbarContent>,
     /// Scrollbar related settings
     pub scrollbar: Option<ScrollbarContent>,
+    /// Minimap related settings
+    pub minimap: Option<MinimapContent>,
     /// Gutter related settings
     pub gutter: Option<GutterContent>,
     /// Whether the editor will scroll beyond the last line.
@@ -308,6 +354,11 @@ pub struct EditorSettingsContent {
     ///
     /// Default: 1.0
     pub scroll_sensitivity: Option<f32>,
+    /// Whether scrolling with a mouse wheel glides to where it scrolls to, and scrolling with a
+    /// touchpad keeps going for a moment after the fingers are lifted.
+    ///
+    /// Default: true
+    pub smooth_scroll: Option<bool>,
     /// Whether the line numbers on editors gutter are relative or not.
     ///
     /// Default: false
@@ -338,6 +389,18 @@ pub struct EditorSettingsContent {
     /// Default: true
     pub middle_click_paste: Option<bool>,
 
+    /// Whether selected text can be dragged out of the editor, to drop it in other applications,
+    /// on the platforms that support it (macOS and Wayland).
+    ///
+    /// Default: true
+    pub drag_and_drop_selection: Option<bool>,
+
+    /// How many of the most recently copied and cut texts `editor: paste from history` keeps,
+    /// besides the pinned ones. 0 turns the clipboard history off.
+    ///
+    /// Default: 20
+    pub clipboard_history_size: Option<usize>,
+
     /// What to do when multibuffer is double clicked in some of its excerpts
     /// (parts of singleton buffers).
     ///
@@ -370,6 +433,12 @@ pub struct EditorSettingsContent {
     /// Default: FindAllReferences
     pub go_to_definition_fallback: Option<GoToDefinitionFallback>,
 
+    /// Whether to review renames that edit several files in a preview, where edits and files can
+    /// be left out, before applying them.
+    ///
+    /// Default: true
+    pub rename_preview: Option<bool>,
+
     /// Jupyter REPL settings.
     pub jupyter: Option<JupyterContent>,
 }
@@ -423,6 +492,10 @@ pub struct ScrollbarContent {
     ///
     /// Default: true
     pub cursors: Option<bool>,
+    /// Whether to show vim marks, like the ones set with `ma`, in the scrollbar.
+    ///
+    /// Default: true
+    pub vim_marks: Option<bool>,
     /// Forcefully enable or disable the scrollbar for each axis
     pub axes: Option<ScrollbarAxesContent>,
 }
@@ -441,6 +514,20 @@ pub struct ScrollbarAxesContent {
     vertical: Option<bool>,
 }
 
+/// Minimap related settings
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct MinimapContent {
+    /// Whether to show a minimap, a scaled overview of the buffer with the
+    /// scrollbar's markers, next to the scrollbar.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// The width of the minimap, in pixels.
+    ///
+    /// Default: 80
+    pub width: Option<f32>,
+}
+
 /// Gutter related settings
 #[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct GutterContent {
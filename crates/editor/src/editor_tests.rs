@@ -12,8 +12,8 @@ use crate::{
 use buffer_diff::{BufferDiff, DiffHunkSecondaryStatus, DiffHunkStatus, DiffHunkStatusKind};
 use futures::StreamExt;
 use gpui::{
-    div, BackgroundExecutor, SemanticVersion, TestAppContext, UpdateGlobal, VisualTestContext,
-    WindowBounds, WindowOptions,
+    div, BackgroundExecutor, Modifiers, MouseButton, SemanticVersion, TestAppContext, UpdateGlobal,
+    VisualTestContext, WindowBounds, WindowOptions,
 };
 use indoc::indoc;
 use language::{
@@ -257,6 +257,79 @@ fn test_undo_redo_with_selection_restoration(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+fn test_undo_and_redo_linked_transactions(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let new_buffer = |text: &str, cx: &mut TestAppContext| {
+        cx.new(|cx| {
+            let mut buffer = language::Buffer::local(text, cx);
+            // Ensure automatic grouping doesn't occur.
+            buffer.set_group_interval(Duration::ZERO);
+            buffer
+        })
+    };
+    let buffer_a = new_buffer("const ONE: usize = 1;", cx);
+    let buffer_b = new_buffer("const TWO: usize = ONE + ONE;", cx);
+
+    // Rename `ONE` in both buffers, as a rename across files does.
+    let transaction_a = buffer_a.update(cx, |buffer, cx| {
+        buffer.start_transaction();
+        buffer.edit([(6..9, "THREE")], None, cx);
+        buffer.end_transaction(cx).unwrap()
+    });
+    let transaction_b = buffer_b.update(cx, |buffer, cx| {
+        buffer.start_transaction();
+        buffer.edit([(19..22, "THREE"), (25..28, "THREE")], None, cx);
+        buffer.end_transaction(cx).unwrap()
+    });
+    cx.update(|cx| {
+        link_transactions(
+            [
+                (buffer_a.clone(), transaction_a),
+                (buffer_b.clone(), transaction_b),
+            ],
+            cx,
+        )
+    });
+
+    let multibuffer = cx.new(|cx| MultiBuffer::singleton(buffer_a.clone(), cx));
+    let editor = cx.add_window(|window, cx| build_editor(multibuffer, window, cx));
+
+    // Undoing the rename in one buffer undoes it in the other as well.
+    _ = editor.update(cx, |editor, window, cx| {
+        editor.undo(&Undo, window, cx);
+        assert_eq!(editor.text(cx), "const ONE: usize = 1;");
+    });
+    assert_eq!(
+        buffer_b.read_with(cx, |buffer, _| buffer.text()),
+        "const TWO: usize = ONE + ONE;"
+    );
+
+    _ = editor.update(cx, |editor, window, cx| {
+        editor.redo(&Redo, window, cx);
+        assert_eq!(editor.text(cx), "const THREE: usize = 1;");
+    });
+    assert_eq!(
+        buffer_b.read_with(cx, |buffer, _| buffer.text()),
+        "const TWO: usize = THREE + THREE;"
+    );
+
+    // The other buffer's own edits are undone on their own.
+    buffer_b.update(cx, |buffer, cx| {
+        buffer.edit([(0..5, "static")], None, cx);
+        buffer.undo(cx);
+    });
+    assert_eq!(
+        buffer_a.read_with(cx, |buffer, _| buffer.text()),
+        "const THREE: usize = 1;"
+    );
+    assert_eq!(
+        buffer_b.read_with(cx, |buffer, _| buffer.text()),
+        "const TWO: usize = THREE + THREE;"
+    );
+}
+
 #[gpui::test]
 fn test_ime_composition(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -3903,6 +3976,49 @@ async fn test_manipulate_lines_with_multi_selection(cx: &mut TestAppContext) {
     "});
 }
 
+#[gpui::test]
+async fn test_convert_indentation(cx: &mut TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.tab_size = NonZeroU32::new(4)
+    });
+
+    let mut cx = EditorTestContext::new(cx).await;
+    let rust_language = Arc::new(
+        Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                ..Default::default()
+            },
+            Some(tree_sitter_rust::LANGUAGE.into()),
+        )
+        .with_override_query("(string_literal) @string")
+        .unwrap(),
+    );
+    cx.language_registry().add(rust_language.clone());
+    cx.update_buffer(|buffer, cx| {
+        buffer.set_language(Some(rust_language), cx);
+    });
+
+    // The whitespace at the start of lines in strings is left alone.
+    cx.set_state("fn a() {\n\tif b {\n\t\tc(ˇ\"d\n\t\t  e\");\n\t   }\n}\n");
+    cx.executor().run_until_parked();
+    cx.update_editor(|e, window, cx| {
+        e.convert_indentation_to_spaces(&ConvertIndentationToSpaces, window, cx)
+    });
+    cx.assert_editor_state("fn a() {\n    if b {\n        c(ˇ\"d\n\t\t  e\");\n       }\n}\n");
+    cx.buffer(|buffer, cx| {
+        let settings = buffer.settings(cx);
+        assert!(!settings.hard_tabs);
+        assert_eq!(settings.tab_size.get(), 4);
+    });
+
+    cx.update_editor(|e, window, cx| {
+        e.convert_indentation_to_tabs(&ConvertIndentationToTabs, window, cx)
+    });
+    cx.assert_editor_state("fn a() {\n\tif b {\n\t\tc(ˇ\"d\n\t\t  e\");\n\t   }\n}\n");
+    cx.buffer(|buffer, cx| assert!(buffer.settings(cx).hard_tabs));
+}
+
 #[gpui::test]
 async fn test_manipulate_text(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -8304,6 +8420,84 @@ async fn test_document_format_manual_trigger(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_document_format_scoped_formatters(cx: &mut TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.formatter =
+            Some(language_settings::SelectedFormatter::List(FormatterList(
+                vec![Formatter::Manual(Box::new(Formatter::LanguageServer {
+                    name: None,
+                }))]
+                .into(),
+            )))
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_file(path!("/file.rs"), Default::default()).await;
+
+    let project = Project::test(fs, [path!("/").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                document_formatting_provider: Some(lsp::OneOf::Left(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/file.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        build_editor_with_project(project.clone(), buffer, window, cx)
+    });
+    editor.update_in(cx, |editor, window, cx| {
+        editor.set_text("one\ntwo\nthree\n", window, cx)
+    });
+
+    cx.executor().start_waiting();
+    let fake_server = fake_servers.next().await.unwrap();
+    let format_requests = Arc::new(AtomicUsize::new(0));
+    fake_server.set_request_handler::<lsp::request::Formatting, _, _>({
+        let format_requests = format_requests.clone();
+        move |_, _| {
+            format_requests.fetch_add(1, atomic::Ordering::Release);
+            async move {
+                Ok(Some(vec![lsp::TextEdit::new(
+                    lsp::Range::new(lsp::Position::new(0, 3), lsp::Position::new(1, 0)),
+                    ", ".to_string(),
+                )]))
+            }
+        }
+    });
+
+    // The language server is only used to format manually.
+    for trigger in [FormatTrigger::Save, FormatTrigger::Manual] {
+        let format = editor
+            .update_in(cx, |editor, window, cx| {
+                editor.perform_format(project.clone(), trigger, FormatTarget::Buffers, window, cx)
+            })
+            .unwrap();
+        cx.executor().start_waiting();
+        format.await;
+    }
+    assert_eq!(format_requests.load(atomic::Ordering::Acquire), 1);
+    assert_eq!(
+        editor.update(cx, |editor, cx| editor.text(cx)),
+        "one, two\nthree\n"
+    );
+}
+
 #[gpui::test]
 async fn test_organize_imports_manual_trigger(cx: &mut TestAppContext) {
     init_test(cx, |settings| {
@@ -14048,6 +14242,76 @@ async fn test_mutlibuffer_in_navigation_history(cx: &mut TestAppContext) {
         .unwrap();
 }
 
+#[gpui::test]
+async fn test_restored_navigation_history(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "one.rs": "a\nb\nc\nd\n",
+            "two.rs": "e\nf\ng\n",
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let (workspace, cx) =
+        cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+    workspace
+        .update_in(cx, |workspace, window, cx| {
+            workspace.open_path((worktree_id, "two.rs"), None, true, window, cx)
+        })
+        .await
+        .unwrap();
+
+    // A jump into a file that isn't open, such as one from a previous session.
+    let one_path = ProjectPath::from((worktree_id, "one.rs"));
+    pane.update(cx, |pane, cx| {
+        pane.nav_history_mut().restore_backward_entries(
+            vec![(
+                one_path.clone(),
+                None,
+                Box::new(Point::new(2, 0)) as Box<dyn Any + Send>,
+            )],
+            cx,
+        );
+
+        let mut entries = Vec::new();
+        pane.nav_history()
+            .for_each_backward_entry(cx, |entry, (project_path, _)| {
+                let position = entry.data.as_deref().and_then(Editor::navigation_position);
+                entries.push((project_path, position));
+            });
+        assert_eq!(entries, vec![(one_path, Some(Point::new(2, 0)))]);
+    });
+
+    workspace
+        .update_in(cx, |workspace, window, cx| {
+            workspace.go_back(pane.downgrade(), window, cx)
+        })
+        .await
+        .unwrap();
+    let editor = workspace.update(cx, |workspace, cx| {
+        workspace.active_item_as::<Editor>(cx).unwrap()
+    });
+    editor.update(cx, |editor, cx| {
+        assert_eq!(editor.text(cx), "a\nb\nc\nd\n");
+        assert_eq!(
+            editor.selections.newest::<Point>(cx).head(),
+            Point::new(2, 0)
+        );
+    });
+    pane.update(cx, |pane, _| {
+        assert!(!pane.can_navigate_backward());
+        assert!(pane.can_navigate_forward());
+    });
+}
+
 #[gpui::test]
 async fn test_toggle_selected_diff_hunks(executor: BackgroundExecutor, cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -16497,6 +16761,84 @@ async fn test_goto_definition_no_fallback(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_peek_references(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            references_provider: Some(lsp::OneOf::Left(true)),
+            ..lsp::ServerCapabilities::default()
+        },
+        cx,
+    )
+    .await;
+    cx.set_state(
+        &r#"fn one() {
+            let mut a = ˇtwo();
+            let mut b = two();
+        }
+
+        fn two() {}"#
+            .unindent(),
+    );
+
+    let mut references = cx
+        .lsp
+        .set_request_handler::<lsp::request::References, _, _>(move |params, _| async move {
+            let uri = params.text_document_position.text_document.uri;
+            Ok(Some(vec![
+                lsp::Location {
+                    uri: uri.clone(),
+                    range: lsp::Range::new(lsp::Position::new(1, 16), lsp::Position::new(1, 19)),
+                },
+                lsp::Location {
+                    uri,
+                    range: lsp::Range::new(lsp::Position::new(2, 16), lsp::Position::new(2, 19)),
+                },
+            ]))
+        });
+    cx.update_editor(|editor, window, cx| editor.peek_references(&PeekReferences, window, cx));
+    references
+        .next()
+        .await
+        .expect("Should have requested the references");
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, _| {
+        assert!(editor.peek_view.is_some(), "Should show the references");
+        assert!(
+            !editor.focus_handle.is_focused(window),
+            "Should focus the peek view"
+        );
+    });
+
+    cx.update_editor(|editor, window, cx| editor.cancel(&Cancel, window, cx));
+    cx.update_editor(|editor, window, _| {
+        assert!(editor.peek_view.is_none(), "Should close the peek view");
+        assert!(
+            editor.focus_handle.is_focused(window),
+            "Should focus the editor again"
+        );
+    });
+
+    let mut references = cx
+        .lsp
+        .set_request_handler::<lsp::request::References, _, _>(move |_, _| async move {
+            Ok(Some(Vec::new()))
+        });
+    cx.update_editor(|editor, window, cx| editor.peek_references(&PeekReferences, window, cx));
+    references
+        .next()
+        .await
+        .expect("Should have requested the references");
+    cx.run_until_parked();
+    cx.update_editor(|editor, _, _| {
+        assert!(
+            editor.peek_view.is_none(),
+            "Shouldn't show a peek view without references"
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_find_enclosing_node_with_task(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -16552,6 +16894,7 @@ async fn test_find_enclosing_node_with_task(cx: &mut TestAppContext) {
                 column: 0,
                 extra_variables: HashMap::default(),
                 context_range: BufferOffset(43)..BufferOffset(85),
+                key: None,
             },
         );
         editor.tasks.insert(
@@ -16562,6 +16905,7 @@ async fn test_find_enclosing_node_with_task(cx: &mut TestAppContext) {
                 column: 0,
                 extra_variables: HashMap::default(),
                 context_range: BufferOffset(86)..BufferOffset(191),
+                key: None,
             },
         );
 
@@ -18282,6 +18626,30 @@ async fn test_apply_code_lens_actions_with_commands(cx: &mut gpui::TestAppContex
     });
 }
 
+#[gpui::test]
+async fn test_mouse_down_inside_selection(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+
+    // Clicking inside a selection places the cursor there when the mouse is released.
+    cx.set_state("one «two threeˇ» four");
+    let position = cx.pixel_position("one two thˇree four");
+    cx.simulate_mouse_down(position, MouseButton::Left, Modifiers::none());
+    cx.assert_editor_state("one «two threeˇ» four");
+    cx.simulate_mouse_up(position, MouseButton::Left, Modifiers::none());
+    cx.assert_editor_state("one two thˇree four");
+
+    // Dragging from inside a selection selects from there instead, when the platform can't
+    // drag the text out of the window.
+    cx.set_state("one «two threeˇ» four");
+    let start = cx.pixel_position("one twˇo three four");
+    let end = cx.pixel_position("one two three foˇur");
+    cx.simulate_mouse_down(start, MouseButton::Left, Modifiers::none());
+    cx.simulate_mouse_move(end, MouseButton::Left, Modifiers::none());
+    cx.simulate_mouse_up(end, MouseButton::Left, Modifiers::none());
+    cx.assert_editor_state("one tw«o three foˇ»ur");
+}
+
 #[gpui::test]
 async fn test_editor_restore_data_different_in_panes(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -18677,6 +19045,71 @@ fn empty_range(row: usize, column: usize) -> Range<DisplayPoint> {
     point..point
 }
 
+#[gpui::test]
+fn test_text_decorations(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let editor = cx.add_window(|window, cx| {
+        let buffer = MultiBuffer::build_simple("let a = 1;\nlet b = 2;\n", cx);
+        build_editor(buffer, window, cx)
+    });
+
+    _ = editor.update(cx, |editor, window, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let range =
+            snapshot.anchor_before(Point::new(0, 4))..snapshot.anchor_after(Point::new(0, 5));
+        editor.set_text_decorations(
+            "linter",
+            vec![
+                TextDecoration {
+                    range: range.clone(),
+                    kind: TextDecorationKind::Underline,
+                    color: ui::Color::Warning,
+                },
+                TextDecoration {
+                    range: range.clone(),
+                    kind: TextDecorationKind::InlineHint("unused".into()),
+                    color: ui::Color::Warning,
+                },
+            ],
+            cx,
+        );
+        assert_eq!(editor.display_text(cx), "let a unused = 1;\nlet b = 2;\n");
+
+        let display_snapshot = editor.snapshot(window, cx).display_snapshot;
+        let underlines = editor.text_decoration_underlines_in_range(
+            Anchor::min()..Anchor::max(),
+            &display_snapshot,
+            cx,
+        );
+        assert_eq!(
+            underlines
+                .into_iter()
+                .map(|(range, _)| range)
+                .collect::<Vec<_>>(),
+            vec![DisplayPoint::new(DisplayRow(0), 4)..DisplayPoint::new(DisplayRow(0), 5)]
+        );
+
+        // Replacing the decorations from a source removes the previous inline hints.
+        editor.set_text_decorations(
+            "linter",
+            vec![TextDecoration {
+                range,
+                kind: TextDecorationKind::InlineHint("unused variable".into()),
+                color: ui::Color::Warning,
+            }],
+            cx,
+        );
+        assert_eq!(
+            editor.display_text(cx),
+            "let a unused variable = 1;\nlet b = 2;\n"
+        );
+
+        editor.clear_text_decorations("linter", cx);
+        assert_eq!(editor.display_text(cx), "let a = 1;\nlet b = 2;\n");
+    });
+}
+
 fn assert_selection_ranges(marked_text: &str, editor: &mut Editor, cx: &mut Context<Editor>) {
     let (text, ranges) = marked_text_ranges(marked_text, true);
     assert_eq!(editor.text(cx), text);
@@ -6,7 +6,7 @@ use crate::{
     },
     editor_settings::{
         CurrentLineHighlight, DoubleClickInMultibuffer, MultiCursorModifier, ScrollBeyondLastLine,
-        ScrollbarAxes, ScrollbarDiagnostics, ShowScrollbar,
+        ScrollbarAxes, ScrollbarDiagnostics, ScrollbarMarkCategory, ShowScrollbar,
     },
     git::blame::GitBlame,
     hover_popover::{
@@ -21,9 +21,10 @@ use crate::{
     Editor, EditorMode, EditorSettings, EditorSnapshot, EditorStyle, FocusedBlock,
     GutterDimensions, HalfPageDown, HalfPageUp, HandleInput, HoveredCursor, InlayHintRefreshReason,
     InlineCompletion, JumpData, LineDown, LineHighlight, LineUp, OpenExcerpts, PageDown, PageUp,
-    Point, RowExt, RowRangeExt, SelectPhase, SelectedTextHighlight, Selection, SoftWrap,
-    StickyHeaderExcerpt, ToPoint, ToggleFold, COLUMNAR_SELECTION_MODIFIERS, CURSORS_VISIBLE_FOR,
-    FILE_HEADER_HEIGHT, GIT_BLAME_MAX_AUTHOR_CHARS_DISPLAYED, MAX_LINE_LEN, MIN_LINE_NUMBER_DIGITS,
+    PendingTextDrag, Point, RowExt, RowRangeExt, ScrollbarMarkerState, SelectPhase,
+    SelectedTextHighlight, Selection, SoftWrap, StickyHeaderExcerpt, ToPoint, ToggleFold,
+    COLUMNAR_SELECTION_MODIFIERS, CURSORS_VISIBLE_FOR, FILE_HEADER_HEIGHT,
+    GIT_BLAME_MAX_AUTHOR_CHARS_DISPLAYED, MAX_LINE_LEN, MIN_LINE_NUMBER_DIGITS,
     MULTI_BUFFER_EXCERPT_HEADER_HEIGHT,
 };
 use buffer_diff::{DiffHunkStatus, DiffHunkStatusKind};
@@ -34,14 +35,15 @@ use file_icons::FileIcons;
 use git::{blame::BlameEntry, status::FileStatus, Oid};
 use gpui::{
     anchored, deferred, div, fill, linear_color_stop, linear_gradient, outline, point, px, quad,
-    relative, size, solid_background, transparent_black, Action, Along, AnyElement, App,
-    AvailableSpace, Axis as ScrollbarAxis, BorderStyle, Bounds, ClickEvent, ClipboardItem,
-    ContentMask, Context, Corner, Corners, CursorStyle, DispatchPhase, Edges, Element,
-    ElementInputHandler, Entity, Focusable as _, FontId, GlobalElementId, Hitbox, Hsla,
-    InteractiveElement, IntoElement, Keystroke, Length, ModifiersChangedEvent, MouseButton,
-    MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad, ParentElement, Pixels, ScrollDelta,
-    ScrollWheelEvent, ShapedLine, SharedString, Size, StatefulInteractiveElement, Style, Styled,
-    Subscription, TextRun, TextStyleRefinement, Window,
+    relative, size, solid_background, transparent_black, Accessibility, AccessibilityRole, Action,
+    Along, AnyElement, App, AvailableSpace, Axis as ScrollbarAxis, BorderStyle, Bounds, ClickEvent,
+    ClipboardItem, ContentMask, Context, Corner, Corners, CursorStyle, DispatchPhase, Edges,
+    Element, ElementInputHandler, Entity, ExternalDragItem, Focusable as _, FontId,
+    GlobalElementId, Hitbox, Hsla, InteractiveElement, IntoElement, Keystroke, Length, Modifiers,
+    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad,
+    ParentElement, Pixels, ScrollDelta, ScrollWheelEvent, ShapedLine, SharedString, Size,
+    StatefulInteractiveElement, Style, Styled, Subscription, TextRun, TextStyleRefinement,
+    UnderlineStyle, Window,
 };
 use itertools::Itertools;
 use language::{
@@ -71,7 +73,7 @@ use std::{
     ops::{Deref, Range},
     rc::Rc,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use sum_tree::Bias;
 use text::BufferId;
@@ -82,6 +84,8 @@ use util::{debug_panic, RangeExt, ResultExt};
 use workspace::{item::Item, notifications::NotifyTaskExt};
 
 const INLINE_BLAME_PADDING_EM_WIDTHS: f32 = 7.;
+/// How far the mouse has to move after going down inside a selection to drag its text.
+const TEXT_DRAG_THRESHOLD: f64 = 4.;
 
 /// Determines what kinds of highlights should be applied to a lines background.
 #[derive(Clone, Copy, Default)]
@@ -223,6 +227,8 @@ impl EditorElement {
         register_action(editor, window, Editor::convert_to_upper_camel_case);
         register_action(editor, window, Editor::convert_to_lower_camel_case);
         register_action(editor, window, Editor::convert_to_opposite_case);
+        register_action(editor, window, Editor::convert_indentation_to_spaces);
+        register_action(editor, window, Editor::convert_indentation_to_tabs);
         register_action(editor, window, Editor::delete_to_previous_word_start);
         register_action(editor, window, Editor::delete_to_previous_subword_start);
         register_action(editor, window, Editor::delete_to_next_word_end);
@@ -402,6 +408,7 @@ impl EditorElement {
         register_action(editor, window, Editor::show_completions);
         register_action(editor, window, Editor::show_word_completions);
         register_action(editor, window, Editor::toggle_code_actions);
+        register_action(editor, window, Editor::refactor);
         register_action(editor, window, Editor::open_excerpts);
         register_action(editor, window, Editor::open_excerpts_in_split);
         register_action(editor, window, Editor::open_proposed_changes_editor);
@@ -496,6 +503,8 @@ impl EditorElement {
                 cx.propagate();
             }
         });
+        register_action(editor, window, Editor::peek_definition);
+        register_action(editor, window, Editor::peek_references);
         register_action(editor, window, Editor::show_signature_help);
         register_action(editor, window, Editor::next_edit_prediction);
         register_action(editor, window, Editor::previous_edit_prediction);
@@ -658,6 +667,19 @@ impl EditorElement {
                 window,
                 cx,
             );
+        } else if let Some(text) = (click_count == 1
+            && modifiers == Modifiers::none()
+            && EditorSettings::get_global(cx).drag_and_drop_selection)
+            .then(|| editor.text_to_drag(position, cx))
+            .flatten()
+        {
+            // Wait for the mouse to move before deciding whether to drag the selected text, or to
+            // place the cursor.
+            editor.pending_text_drag = Some(PendingTextDrag {
+                mouse_down_position: event.position,
+                position,
+                text,
+            });
         } else {
             let multi_cursor_setting = EditorSettings::get_global(cx).multi_cursor_modifier;
             let multi_cursor_modifier = match multi_cursor_setting {
@@ -778,6 +800,21 @@ impl EditorElement {
         window: &mut Window,
         cx: &mut Context<Editor>,
     ) {
+        if let Some(text_drag) = editor.pending_text_drag.take() {
+            editor.select(
+                SelectPhase::Begin {
+                    position: text_drag.position,
+                    add: false,
+                    click_count: 1,
+                },
+                window,
+                cx,
+            );
+            editor.select(SelectPhase::End, window, cx);
+            cx.stop_propagation();
+            return;
+        }
+
         let text_hitbox = &position_map.text_hitbox;
         let end_selection = editor.has_pending_selection();
         let pending_nonempty_selections = editor.has_pending_nonempty_selection();
@@ -848,6 +885,27 @@ impl EditorElement {
         window: &mut Window,
         cx: &mut Context<Editor>,
     ) {
+        if let Some(text_drag) = editor.pending_text_drag.as_ref() {
+            if (event.position - text_drag.mouse_down_position).magnitude() < TEXT_DRAG_THRESHOLD {
+                return;
+            }
+            let text_drag = editor.pending_text_drag.take().unwrap();
+            if window.start_external_drag(ExternalDragItem::Text(text_drag.text)) {
+                return;
+            }
+            // Select from where the mouse went down instead, when the platform can't drag out of
+            // the window.
+            editor.select(
+                SelectPhase::Begin {
+                    position: text_drag.position,
+                    add: false,
+                    click_count: 1,
+                },
+                window,
+                cx,
+            );
+        }
+
         if !editor.has_pending_selection() {
             return;
         }
@@ -1238,6 +1296,9 @@ impl EditorElement {
             let mut cursors = Vec::new();
 
             let show_local_cursors = editor.show_local_cursors(window, cx);
+            let animate_cursor =
+                EditorSettings::get_global(cx).cursor_animation && !cx.reduce_motion();
+            let mut newest_cursor_painted = false;
 
             for (player_color, selections) in selections {
                 for selection in selections {
@@ -1350,10 +1411,27 @@ impl EditorElement {
                         }
                     }
 
+                    let mut origin = point(x, y);
+                    if selection.is_local && selection.is_newest && animate_cursor {
+                        newest_cursor_painted = true;
+                        let (position, animating) = editor.cursor_animation.position(
+                            point(
+                                cursor_character_x,
+                                cursor_position.row().as_f32() * line_height,
+                            ),
+                            text_hitbox.size.height,
+                            Instant::now(),
+                        );
+                        origin = position - scroll_pixel_position;
+                        if animating {
+                            window.request_animation_frame();
+                        }
+                    }
+
                     let mut cursor = CursorLayout {
                         color: player_color.cursor,
                         block_width,
-                        origin: point(x, y),
+                        origin,
                         line_height,
                         shape: selection.cursor_shape,
                         block_text,
@@ -1368,6 +1446,9 @@ impl EditorElement {
                     cursors.push(cursor);
                 }
             }
+            if !newest_cursor_painted {
+                editor.cursor_animation.reset();
+            }
 
             cursors
         });
@@ -1422,6 +1503,9 @@ impl EditorElement {
                     // Diagnostics
                     (is_singleton && scrollbar_settings.diagnostics != ScrollbarDiagnostics::None && snapshot.buffer_snapshot.has_diagnostics())
                     ||
+                    // Marks added by other features
+                    (is_singleton && editor.has_scrollbar_marks(&scrollbar_settings))
+                    ||
                     // Cursors out of sight
                     non_visible_cursors
                     ||
@@ -1444,6 +1528,85 @@ impl EditorElement {
         ))
     }
 
+    fn minimap_width(snapshot: &EditorSnapshot, cx: &App) -> Pixels {
+        let minimap_settings = EditorSettings::get_global(cx).minimap;
+        if snapshot.mode == EditorMode::Full && minimap_settings.enabled {
+            px(minimap_settings.width)
+        } else {
+            Pixels::ZERO
+        }
+    }
+
+    fn layout_minimap(
+        &self,
+        snapshot: &EditorSnapshot,
+        text_bounds: Bounds<Pixels>,
+        line_height: Pixels,
+        scroll_position: gpui::Point<f32>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<MinimapLayout> {
+        let minimap_width = Self::minimap_width(snapshot, cx);
+        if minimap_width <= Pixels::ZERO {
+            return None;
+        }
+
+        // If a drag took place after we started dragging the minimap,
+        // cancel the minimap drag.
+        if cx.has_active_drag() {
+            self.editor.update(cx, |editor, cx| {
+                editor.scroll_manager.set_dragging_minimap(false, cx)
+            });
+        }
+
+        let bounds = Bounds::from_corner_and_size(
+            Corner::TopRight,
+            text_bounds.top_right() - point(self.style.scrollbar_width, Pixels::ZERO),
+            size(minimap_width, text_bounds.size.height),
+        );
+        let row_count = snapshot.max_point().row().next_row().as_f32();
+        let scrollbar_layout = ScrollbarLayout::for_minimap(
+            window.insert_hitbox(bounds, true),
+            row_count,
+            text_bounds.size.height / line_height,
+            scroll_position.y,
+        );
+
+        // Only one row is laid out for every few pixels, so that the minimap of a large buffer
+        // doesn't cost more than the one of a small buffer.
+        let row_height = scrollbar_layout.text_unit_size;
+        let rows_per_line = (MinimapLayout::MIN_LINE_HEIGHT / row_height).ceil().max(1.) as u32;
+        let line_height = row_height * rows_per_line as f32;
+        let column_width = minimap_width / MinimapLayout::COLUMNS as f32;
+        let color = cx.theme().colors().editor_foreground.opacity(0.4);
+
+        let mut line_quads = Vec::new();
+        for row in (0..row_count as u32).step_by(rows_per_line as usize) {
+            let line = snapshot.line(DisplayRow(row));
+            let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+            let len = line.chars().count().min(MinimapLayout::COLUMNS);
+            if indent >= len {
+                continue;
+            }
+            let top = bounds.origin.y + row as f32 * row_height;
+            line_quads.push(fill(
+                Bounds::from_corners(
+                    point(bounds.origin.x + column_width * indent as f32, top),
+                    point(
+                        bounds.origin.x + column_width * len as f32,
+                        top + line_height * MinimapLayout::LINE_FILL,
+                    ),
+                ),
+                color,
+            ));
+        }
+
+        Some(MinimapLayout {
+            scrollbar_layout,
+            line_quads,
+        })
+    }
+
     fn prepaint_crease_toggles(
         &self,
         crease_toggles: &mut [Option<AnyElement>],
@@ -2040,6 +2203,50 @@ impl EditorElement {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn layout_decoration_gutter_icons(
+        &self,
+        line_height: Pixels,
+        range: Range<Anchor>,
+        scroll_pixel_position: gpui::Point<Pixels>,
+        gutter_dimensions: &GutterDimensions,
+        gutter_hitbox: &Hitbox,
+        display_hunks: &[(DisplayDiffHunk, Option<Hitbox>)],
+        snapshot: &EditorSnapshot,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<AnyElement> {
+        let icons = self
+            .editor
+            .read(cx)
+            .text_decoration_gutter_icons_in_range(range, &snapshot.display_snapshot);
+        icons
+            .into_iter()
+            .filter(|(display_row, _)| {
+                let row = MultiBufferRow(DisplayPoint::new(*display_row, 0).to_point(snapshot).row);
+                !snapshot.is_line_folded(row)
+            })
+            .map(|(display_row, (icon, color))| {
+                let button =
+                    IconButton::new(("decoration_gutter_icon", display_row.0 as usize), icon)
+                        .icon_size(IconSize::XSmall)
+                        .icon_color(color)
+                        .size(ui::ButtonSize::None);
+                prepaint_gutter_button(
+                    button,
+                    display_row,
+                    line_height,
+                    gutter_dimensions,
+                    scroll_pixel_position,
+                    gutter_hitbox,
+                    display_hunks,
+                    window,
+                    cx,
+                )
+            })
+            .collect()
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn layout_run_indicators(
         &self,
@@ -2124,6 +2331,11 @@ impl EditorElement {
                         &self.style,
                         Some(display_row) == active_task_indicator_row,
                         display_row,
+                        tasks
+                            .key
+                            .as_ref()
+                            .and_then(|key| editor.runnable_statuses.get(key))
+                            .copied(),
                         breakpoints.remove(&display_row),
                         cx,
                     );
@@ -2334,6 +2546,66 @@ impl EditorElement {
         relative_rows
     }
 
+    /// Lays out the `soft_wrap_indicator` next to the rows that continue soft-wrapped lines, which
+    /// are the rows without a buffer row that aren't blocks.
+    fn layout_soft_wrap_indicators(
+        &self,
+        gutter_hitbox: &Hitbox,
+        gutter_dimensions: GutterDimensions,
+        line_height: Pixels,
+        scroll_position: gpui::Point<f32>,
+        rows: Range<DisplayRow>,
+        row_infos: &[RowInfo],
+        snapshot: &EditorSnapshot,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<(gpui::Point<Pixels>, ShapedLine)> {
+        if snapshot.mode != EditorMode::Full {
+            return Vec::new();
+        }
+        let editor = self.editor.read(cx);
+        let Some(indicator) = editor
+            .buffer
+            .read(cx)
+            .language_settings(cx)
+            .soft_wrap_indicator
+            .clone()
+            .filter(|indicator| !indicator.is_empty())
+        else {
+            return Vec::new();
+        };
+        let Some(indicator) = self
+            .shape_line_number(
+                indicator.into(),
+                cx.theme().colors().editor_line_number,
+                window,
+            )
+            .log_err()
+        else {
+            return Vec::new();
+        };
+
+        let scroll_top = scroll_position.y * line_height;
+        row_infos
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, row_info)| {
+                let display_row = DisplayRow(rows.start.0 + ix as u32);
+                if row_info.buffer_row.is_some() || snapshot.is_block_line(display_row) {
+                    return None;
+                }
+                let origin = gutter_hitbox.origin
+                    + point(
+                        gutter_hitbox.size.width
+                            - indicator.width
+                            - gutter_dimensions.right_padding,
+                        ix as f32 * line_height - (scroll_top % line_height),
+                    );
+                Some((origin, indicator.clone()))
+            })
+            .collect()
+    }
+
     fn layout_line_numbers(
         &self,
         gutter_hitbox: Option<&Hitbox>,
@@ -4399,6 +4671,10 @@ impl EditorElement {
                 window.set_cursor_style(CursorStyle::PointingHand, Some(&hitbox));
             }
         }
+
+        for (origin, indicator) in &layout.soft_wrap_indicators {
+            indicator.paint(*origin, line_height, window, cx).log_err();
+        }
     }
 
     fn paint_gutter_diff_hunks(layout: &mut EditorLayout, window: &mut Window, cx: &mut App) {
@@ -4593,6 +4869,10 @@ impl EditorElement {
                 breakpoint.paint(window, cx);
             }
 
+            for icon in layout.decoration_gutter_icons.iter_mut() {
+                icon.paint(window, cx);
+            }
+
             for test_indicator in layout.test_indicators.iter_mut() {
                 test_indicator.paint(window, cx);
             }
@@ -4712,6 +4992,7 @@ impl EditorElement {
                 self.paint_lines_background(layout, window, cx);
                 let invisible_display_ranges = self.paint_highlights(layout, window);
                 self.paint_lines(&invisible_display_ranges, layout, window, cx);
+                self.paint_decoration_underlines(layout, window);
                 self.paint_redactions(layout, window);
                 self.paint_cursors(layout, window, cx);
                 self.paint_inline_diagnostics(layout, window, cx);
@@ -4767,6 +5048,48 @@ impl EditorElement {
         })
     }
 
+    fn paint_decoration_underlines(&mut self, layout: &mut EditorLayout, window: &mut Window) {
+        let start_row = layout.visible_display_row_range.start;
+        let end_row = layout.visible_display_row_range.end;
+        let line_height = layout.position_map.line_height;
+        let scroll_pixel_position = layout.position_map.scroll_pixel_position;
+        for (range, color) in &layout.decoration_underlines {
+            let row_range = cmp::max(range.start.row(), start_row)
+                ..cmp::min(range.end.row().next_row(), end_row);
+            for row in row_range.iter_rows() {
+                let line_layout = &layout.position_map.line_layouts[row.minus(start_row) as usize];
+                let start_x = if row == range.start.row() {
+                    line_layout.x_for_index(range.start.column() as usize)
+                } else {
+                    Pixels::ZERO
+                };
+                let end_x = if row == range.end.row() {
+                    line_layout.x_for_index(range.end.column() as usize)
+                } else {
+                    line_layout.width
+                };
+                if end_x <= start_x {
+                    continue;
+                }
+
+                let origin = layout.content_origin
+                    + point(
+                        start_x - scroll_pixel_position.x,
+                        (row.as_f32() + 0.85) * line_height - scroll_pixel_position.y,
+                    );
+                window.paint_underline(
+                    origin,
+                    end_x - start_x,
+                    &UnderlineStyle {
+                        color: Some(*color),
+                        thickness: px(1.),
+                        wavy: true,
+                    },
+                );
+            }
+        }
+    }
+
     fn paint_lines(
         &mut self,
         invisible_display_ranges: &[Range<DisplayPoint>],
@@ -4842,6 +5165,127 @@ impl EditorElement {
         }
     }
 
+    fn paint_minimap(&mut self, layout: &mut EditorLayout, window: &mut Window, cx: &mut App) {
+        let Some(minimap) = &layout.minimap else {
+            return;
+        };
+        let scrollbar_layout = &minimap.scrollbar_layout;
+        let hitbox = &scrollbar_layout.hitbox;
+
+        // Refresh the slow markers in the background, and paint whatever markers have already
+        // been computed behind the lines, faded so that they don't drown the lines out.
+        self.refresh_slow_scrollbar_markers(
+            layout,
+            scrollbar_layout,
+            MarkerTarget::Minimap,
+            window,
+            cx,
+        );
+        let selection_ranges = layout
+            .selections
+            .iter()
+            .flat_map(|(player_color, selections)| {
+                selections.iter().map(move |selection| ColoredRange {
+                    start: selection.range.start.row(),
+                    end: selection.range.end.row(),
+                    color: player_color.cursor,
+                })
+            });
+        let selection_markers = scrollbar_layout.marker_quads_for_ranges(selection_ranges, None);
+        let markers = self.editor.read(cx).minimap_marker_state.markers.clone();
+
+        window.paint_layer(hitbox.bounds, |window| {
+            window.paint_quad(fill(hitbox.bounds, cx.theme().colors().editor_background));
+            for marker in markers.iter().chain(&selection_markers) {
+                let mut marker = marker.clone();
+                marker.bounds.origin += hitbox.origin;
+                marker.background = marker.background.opacity(MinimapLayout::MARKER_OPACITY);
+                window.paint_quad(marker);
+            }
+            window.paint_quad(fill(
+                scrollbar_layout.thumb_bounds(),
+                cx.theme().colors().scrollbar_thumb_background,
+            ));
+            for line_quad in &minimap.line_quads {
+                window.paint_quad(line_quad.clone());
+            }
+        });
+        window.set_cursor_style(CursorStyle::Arrow, Some(hitbox));
+
+        window.on_mouse_event({
+            let editor = self.editor.clone();
+            let scrollbar_layout = scrollbar_layout.clone();
+            let mut mouse_position = window.mouse_position();
+            move |event: &MouseMoveEvent, phase, window, cx| {
+                if phase == DispatchPhase::Capture {
+                    return;
+                }
+
+                editor.update(cx, |editor, cx| {
+                    if editor.scroll_manager.is_dragging_minimap() {
+                        if event.pressed_button == Some(MouseButton::Left) {
+                            let delta = (event.position.y - mouse_position.y)
+                                / scrollbar_layout.text_unit_size;
+                            let mut position = editor.scroll_position(cx);
+                            position.y = (position.y + delta).max(0.);
+                            editor.set_scroll_position(position, window, cx);
+                            cx.stop_propagation();
+                        } else {
+                            editor.scroll_manager.set_dragging_minimap(false, cx);
+                        }
+                    }
+                    mouse_position = event.position;
+                });
+            }
+        });
+
+        if self.editor.read(cx).scroll_manager.is_dragging_minimap() {
+            window.on_mouse_event({
+                let editor = self.editor.clone();
+                move |_: &MouseUpEvent, phase, _, cx| {
+                    if phase == DispatchPhase::Capture {
+                        return;
+                    }
+
+                    editor.update(cx, |editor, cx| {
+                        editor.scroll_manager.set_dragging_minimap(false, cx);
+                        cx.stop_propagation();
+                    });
+                }
+            });
+        } else {
+            window.on_mouse_event({
+                let editor = self.editor.clone();
+                let scrollbar_layout = scrollbar_layout.clone();
+                move |event: &MouseDownEvent, phase, window, cx| {
+                    if phase == DispatchPhase::Capture
+                        || event.button != MouseButton::Left
+                        || !scrollbar_layout.hitbox.is_hovered(window)
+                    {
+                        return;
+                    }
+
+                    editor.update(cx, |editor, cx| {
+                        editor.scroll_manager.set_dragging_minimap(true, cx);
+
+                        // Clicking outside of the visible rows centers them on the clicked row.
+                        let thumb_bounds = scrollbar_layout.thumb_bounds();
+                        if !thumb_bounds.contains(&event.position) {
+                            let visible_rows = scrollbar_layout.visible_range.end
+                                - scrollbar_layout.visible_range.start;
+                            let clicked_row = (event.position.y - scrollbar_layout.hitbox.origin.y)
+                                / scrollbar_layout.text_unit_size;
+                            let mut position = editor.scroll_position(cx);
+                            position.y = (clicked_row - visible_rows / 2.).max(0.);
+                            editor.set_scroll_position(position, window, cx);
+                        }
+                        cx.stop_propagation();
+                    });
+                }
+            });
+        }
+    }
+
     fn paint_scrollbars(&mut self, layout: &mut EditorLayout, window: &mut Window, cx: &mut App) {
         let Some(scrollbars_layout) = &layout.scrollbars_layout else {
             return;
@@ -4882,7 +5326,13 @@ impl EditorElement {
                             self.collect_fast_scrollbar_markers(layout, &scrollbar_layout, cx);
                         // Refresh slow scrollbar markers in the background. Below, we
                         // paint whatever markers have already been computed.
-                        self.refresh_slow_scrollbar_markers(layout, &scrollbar_layout, window, cx);
+                        self.refresh_slow_scrollbar_markers(
+                            layout,
+                            &scrollbar_layout,
+                            MarkerTarget::Scrollbar,
+                            window,
+                            cx,
+                        );
 
                         let markers = self.editor.read(cx).scrollbar_marker_state.markers.clone();
                         for marker in markers.iter().chain(&fast_markers) {
@@ -5050,13 +5500,14 @@ impl EditorElement {
         &self,
         layout: &EditorLayout,
         scrollbar_layout: &ScrollbarLayout,
+        target: MarkerTarget,
         window: &mut Window,
         cx: &mut App,
     ) {
         self.editor.update(cx, |editor, cx| {
             if !editor.is_singleton(cx)
-                || !editor
-                    .scrollbar_marker_state
+                || !target
+                    .state(editor)
                     .should_refresh(scrollbar_layout.hitbox.size)
             {
                 return;
@@ -5064,19 +5515,20 @@ impl EditorElement {
 
             let scrollbar_layout = scrollbar_layout.clone();
             let background_highlights = editor.background_highlights.clone();
+            let scrollbar_marks = editor.scrollbar_marks.clone();
             let snapshot = layout.position_map.snapshot.clone();
             let theme = cx.theme().clone();
             let scrollbar_settings = EditorSettings::get_global(cx).scrollbar;
 
-            editor.scrollbar_marker_state.dirty = false;
-            editor.scrollbar_marker_state.pending_refresh =
+            target.state(editor).dirty = false;
+            target.state(editor).pending_refresh =
                 Some(cx.spawn_in(window, async move |editor, cx| {
                     let scrollbar_size = scrollbar_layout.hitbox.size;
                     let scrollbar_markers = cx
                         .background_spawn(async move {
                             let max_point = snapshot.display_snapshot.buffer_snapshot.max_point();
                             let mut marker_quads = Vec::new();
-                            if scrollbar_settings.git_diff {
+                            if scrollbar_settings.shows(ScrollbarMarkCategory::GitDiff) {
                                 let marker_row_ranges =
                                     snapshot.buffer_snapshot.diff_hunks().map(|hunk| {
                                         let start_display_row =
@@ -5109,8 +5561,10 @@ impl EditorElement {
                                     });
 
                                 marker_quads.extend(
-                                    scrollbar_layout
-                                        .marker_quads_for_ranges(marker_row_ranges, Some(0)),
+                                    scrollbar_layout.marker_quads_for_ranges(
+                                        marker_row_ranges,
+                                        target.column(0),
+                                    ),
                                 );
                             }
 
@@ -5125,9 +5579,15 @@ impl EditorElement {
                                     == TypeId::of::<DocumentHighlightRead>()
                                     || *background_highlight_id
                                         == TypeId::of::<DocumentHighlightWrite>();
-                                if (is_search_highlights && scrollbar_settings.search_results)
-                                    || (is_text_highlights && scrollbar_settings.selected_text)
-                                    || (is_symbol_occurrences && scrollbar_settings.selected_symbol)
+                                if (is_search_highlights
+                                    && scrollbar_settings
+                                        .shows(ScrollbarMarkCategory::SearchResults))
+                                    || (is_text_highlights
+                                        && scrollbar_settings
+                                            .shows(ScrollbarMarkCategory::SelectedText))
+                                    || (is_symbol_occurrences
+                                        && scrollbar_settings
+                                            .shows(ScrollbarMarkCategory::SelectedSymbol))
                                 {
                                     let mut color = theme.status().info;
                                     if is_symbol_occurrences {
@@ -5145,14 +5605,41 @@ impl EditorElement {
                                             color,
                                         }
                                     });
-                                    marker_quads.extend(
-                                        scrollbar_layout
-                                            .marker_quads_for_ranges(marker_row_ranges, Some(1)),
-                                    );
+                                    marker_quads.extend(scrollbar_layout.marker_quads_for_ranges(
+                                        marker_row_ranges,
+                                        target.column(1),
+                                    ));
                                 }
                             }
 
-                            if scrollbar_settings.diagnostics != ScrollbarDiagnostics::None {
+                            for (category, color_fetcher, ranges) in scrollbar_marks.values() {
+                                if !scrollbar_settings.shows(*category) {
+                                    continue;
+                                }
+                                let color = color_fetcher(theme.colors());
+                                let marker_row_ranges = ranges.iter().map(|range| ColoredRange {
+                                    start: range
+                                        .start
+                                        .to_display_point(&snapshot.display_snapshot)
+                                        .row(),
+                                    end: range
+                                        .end
+                                        .to_display_point(&snapshot.display_snapshot)
+                                        .row(),
+                                    color,
+                                });
+                                let column = match category {
+                                    ScrollbarMarkCategory::GitDiff => 0,
+                                    ScrollbarMarkCategory::Diagnostics => 2,
+                                    _ => 1,
+                                };
+                                marker_quads.extend(scrollbar_layout.marker_quads_for_ranges(
+                                    marker_row_ranges,
+                                    target.column(column),
+                                ));
+                            }
+
+                            if scrollbar_settings.shows(ScrollbarMarkCategory::Diagnostics) {
                                 let diagnostics = snapshot
                                     .buffer_snapshot
                                     .diagnostics_in_range::<Point>(Point::zero()..max_point)
@@ -5208,8 +5695,10 @@ impl EditorElement {
                                     }
                                 });
                                 marker_quads.extend(
-                                    scrollbar_layout
-                                        .marker_quads_for_ranges(marker_row_ranges, Some(2)),
+                                    scrollbar_layout.marker_quads_for_ranges(
+                                        marker_row_ranges,
+                                        target.column(2),
+                                    ),
                                 );
                             }
 
@@ -5218,9 +5707,10 @@ impl EditorElement {
                         .await;
 
                     editor.update(cx, |editor, cx| {
-                        editor.scrollbar_marker_state.markers = scrollbar_markers;
-                        editor.scrollbar_marker_state.scrollbar_size = scrollbar_size;
-                        editor.scrollbar_marker_state.pending_refresh = None;
+                        let marker_state = target.state(editor);
+                        marker_state.markers = scrollbar_markers;
+                        marker_state.scrollbar_size = scrollbar_size;
+                        marker_state.pending_refresh = None;
                         cx.notify();
                     })?;
 
@@ -5333,6 +5823,43 @@ impl EditorElement {
         }
     }
 
+    /// Describes the editor to screen readers as an input whose value is the text that's visible
+    /// in it, named after its buffer or placeholder.
+    fn accessibility(&self, layout: &EditorLayout, cx: &App) -> Accessibility {
+        let editor = self.editor.read(cx);
+        let (role, label) = match editor.mode() {
+            EditorMode::Full => (
+                AccessibilityRole::MultilineTextInput,
+                Some(editor.buffer().read(cx).title(cx).to_string()),
+            ),
+            EditorMode::AutoHeight { .. } => (
+                AccessibilityRole::MultilineTextInput,
+                editor.placeholder_text().map(ToString::to_string),
+            ),
+            EditorMode::SingleLine { .. } => (
+                AccessibilityRole::TextInput,
+                editor.placeholder_text().map(ToString::to_string),
+            ),
+        };
+
+        let snapshot = &layout.position_map.snapshot;
+        let rows = &layout.visible_display_row_range;
+        let start = snapshot.display_point_to_point(DisplayPoint::new(rows.start, 0), Bias::Left);
+        let end = if rows.end > snapshot.max_point().row() {
+            snapshot.buffer_snapshot.max_point()
+        } else {
+            snapshot.display_point_to_point(DisplayPoint::new(rows.end, 0), Bias::Left)
+        };
+        let value = snapshot
+            .buffer_snapshot
+            .text_for_range(start..end)
+            .collect::<String>();
+
+        let mut accessibility = Accessibility::new(role).value(value);
+        accessibility.label = label.map(Into::into);
+        accessibility
+    }
+
     fn paint_mouse_context_menu(
         &mut self,
         layout: &mut EditorLayout,
@@ -5362,10 +5889,25 @@ impl EditorElement {
 
             move |event: &ScrollWheelEvent, phase, window, cx| {
                 if phase == DispatchPhase::Bubble && hitbox.is_hovered(window) {
-                    delta = delta.coalesce(event.delta);
                     editor.update(cx, |editor, cx| {
                         let position_map: &PositionMap = &position_map;
 
+                        // Wheel scrolls that are still gliding continue from where they're
+                        // gliding to.
+                        let is_wheel = matches!(event.delta, ScrollDelta::Lines(_));
+                        let wheel_scroll_target =
+                            is_wheel.then(|| editor.wheel_scroll_target()).flatten();
+                        let current_scroll_position = match wheel_scroll_target {
+                            Some(target) => {
+                                delta = event.delta;
+                                target
+                            }
+                            None => {
+                                delta = delta.coalesce(event.delta);
+                                position_map.snapshot.scroll_position()
+                            }
+                        };
+
                         let line_height = position_map.line_height;
                         let max_glyph_width = position_map.em_width;
                         let (delta, axis) = match delta {
@@ -5383,7 +5925,6 @@ impl EditorElement {
                             }
                         };
 
-                        let current_scroll_position = position_map.snapshot.scroll_position();
                         let x = (current_scroll_position.x * max_glyph_width
                             - (delta.x * scroll_sensitivity))
                             / max_glyph_width;
@@ -5398,7 +5939,16 @@ impl EditorElement {
                         }
 
                         if scroll_position != current_scroll_position {
-                            editor.scroll(scroll_position, axis, window, cx);
+                            if is_wheel {
+                                editor.scroll_by_wheel(
+                                    scroll_position,
+                                    position_map.scroll_max,
+                                    window,
+                                    cx,
+                                );
+                            } else {
+                                editor.scroll(scroll_position, axis, window, cx);
+                            }
                             cx.stop_propagation();
                         } else if y < 0. {
                             // Due to clamping, we may fail to detect cases of overscroll to the top;
@@ -5406,6 +5956,16 @@ impl EditorElement {
                             // on the next frame.
                             cx.notify();
                         }
+
+                        if !is_wheel {
+                            editor.track_scroll_gesture(
+                                scroll_position,
+                                event.touch_phase,
+                                position_map.scroll_max,
+                                window,
+                                cx,
+                            );
+                        }
                     });
                 }
             }
@@ -6611,8 +7171,11 @@ impl Element for EditorElement {
                         .unwrap_or_default();
                     let text_width = bounds.size.width - gutter_dimensions.width;
 
-                    let editor_width =
-                        text_width - gutter_dimensions.margin - em_width - style.scrollbar_width;
+                    let editor_width = text_width
+                        - gutter_dimensions.margin
+                        - em_width
+                        - style.scrollbar_width
+                        - Self::minimap_width(&snapshot, cx);
 
                     snapshot = self.editor.update(cx, |editor, cx| {
                         editor.last_bounds = Some(bounds);
@@ -6793,11 +7356,25 @@ impl Element for EditorElement {
                             .or_insert(background);
                     }
 
-                    let highlighted_ranges = self.editor.read(cx).background_highlights_in_range(
-                        start_anchor..end_anchor,
-                        &snapshot.display_snapshot,
-                        cx.theme().colors(),
+                    let mut highlighted_ranges =
+                        self.editor.read(cx).background_highlights_in_range(
+                            start_anchor..end_anchor,
+                            &snapshot.display_snapshot,
+                            cx.theme().colors(),
+                        );
+                    highlighted_ranges.extend(
+                        self.editor.read(cx).text_decoration_backgrounds_in_range(
+                            start_anchor..end_anchor,
+                            &snapshot.display_snapshot,
+                            cx,
+                        ),
                     );
+                    let decoration_underlines =
+                        self.editor.read(cx).text_decoration_underlines_in_range(
+                            start_anchor..end_anchor,
+                            &snapshot.display_snapshot,
+                            cx,
+                        );
                     let highlighted_gutter_ranges =
                         self.editor.read(cx).gutter_highlights_in_range(
                             start_anchor..end_anchor,
@@ -6876,6 +7453,17 @@ impl Element for EditorElement {
                         window,
                         cx,
                     );
+                    let soft_wrap_indicators = self.layout_soft_wrap_indicators(
+                        &gutter_hitbox,
+                        gutter_dimensions,
+                        line_height,
+                        scroll_position,
+                        start_row..end_row,
+                        &row_infos,
+                        &snapshot,
+                        window,
+                        cx,
+                    );
 
                     // We add the gutter breakpoint indicator to breakpoint_rows after painting
                     // line numbers so we don't paint a line number debug accent color if a user
@@ -7281,6 +7869,14 @@ impl Element for EditorElement {
                         window,
                         cx,
                     );
+                    let minimap = self.layout_minimap(
+                        &snapshot,
+                        text_hitbox.bounds,
+                        line_height,
+                        scroll_position,
+                        window,
+                        cx,
+                    );
 
                     let gutter_settings = EditorSettings::get_global(cx).gutter;
 
@@ -7401,6 +7997,18 @@ impl Element for EditorElement {
                         vec![]
                     };
 
+                    let decoration_gutter_icons = self.layout_decoration_gutter_icons(
+                        line_height,
+                        start_anchor..end_anchor,
+                        scroll_pixel_position,
+                        &gutter_dimensions,
+                        &gutter_hitbox,
+                        &display_hunks,
+                        &snapshot,
+                        window,
+                        cx,
+                    );
+
                     self.layout_signature_help(
                         &hitbox,
                         content_origin,
@@ -7532,13 +8140,16 @@ impl Element for EditorElement {
                         display_hunks,
                         content_origin,
                         scrollbars_layout,
+                        minimap,
                         active_rows,
                         highlighted_rows,
                         highlighted_ranges,
                         highlighted_gutter_ranges,
+                        decoration_underlines,
                         redacted_ranges,
                         line_elements,
                         line_numbers,
+                        soft_wrap_indicators,
                         blamed_display_rows,
                         inline_diagnostics,
                         inline_blame,
@@ -7551,6 +8162,7 @@ impl Element for EditorElement {
                         mouse_context_menu,
                         test_indicators,
                         breakpoints,
+                        decoration_gutter_icons,
                         code_actions_indicator,
                         crease_toggles,
                         crease_trailers,
@@ -7593,41 +8205,45 @@ impl Element for EditorElement {
             ..Default::default()
         };
         let rem_size = self.rem_size(cx);
-        window.with_rem_size(rem_size, |window| {
-            window.with_text_style(Some(text_style), |window| {
-                window.with_content_mask(Some(ContentMask { bounds }), |window| {
-                    self.paint_mouse_listeners(layout, window, cx);
-                    self.paint_background(layout, window, cx);
-                    self.paint_indent_guides(layout, window, cx);
+        let accessibility = self.accessibility(layout, cx);
+        window.with_accessibility_node(Some(accessibility), bounds, Some(&focus_handle), |window| {
+            window.with_rem_size(rem_size, |window| {
+                window.with_text_style(Some(text_style), |window| {
+                    window.with_content_mask(Some(ContentMask { bounds }), |window| {
+                        self.paint_mouse_listeners(layout, window, cx);
+                        self.paint_background(layout, window, cx);
+                        self.paint_indent_guides(layout, window, cx);
+
+                        if layout.gutter_hitbox.size.width > Pixels::ZERO {
+                            self.paint_blamed_display_rows(layout, window, cx);
+                            self.paint_line_numbers(layout, window, cx);
+                        }
 
-                    if layout.gutter_hitbox.size.width > Pixels::ZERO {
-                        self.paint_blamed_display_rows(layout, window, cx);
-                        self.paint_line_numbers(layout, window, cx);
-                    }
+                        self.paint_text(layout, window, cx);
 
-                    self.paint_text(layout, window, cx);
+                        if layout.gutter_hitbox.size.width > Pixels::ZERO {
+                            self.paint_gutter_highlights(layout, window, cx);
+                            self.paint_gutter_indicators(layout, window, cx);
+                        }
 
-                    if layout.gutter_hitbox.size.width > Pixels::ZERO {
-                        self.paint_gutter_highlights(layout, window, cx);
-                        self.paint_gutter_indicators(layout, window, cx);
-                    }
+                        if !layout.blocks.is_empty() {
+                            window.with_element_namespace("blocks", |window| {
+                                self.paint_blocks(layout, window, cx);
+                            });
+                        }
 
-                    if !layout.blocks.is_empty() {
                         window.with_element_namespace("blocks", |window| {
-                            self.paint_blocks(layout, window, cx);
+                            if let Some(mut sticky_header) = layout.sticky_buffer_header.take() {
+                                sticky_header.paint(window, cx)
+                            }
                         });
-                    }
 
-                    window.with_element_namespace("blocks", |window| {
-                        if let Some(mut sticky_header) = layout.sticky_buffer_header.take() {
-                            sticky_header.paint(window, cx)
-                        }
+                        self.paint_minimap(layout, window, cx);
+                        self.paint_scrollbars(layout, window, cx);
+                        self.paint_inline_completion_popover(layout, window, cx);
+                        self.paint_mouse_context_menu(layout, window, cx);
                     });
-
-                    self.paint_scrollbars(layout, window, cx);
-                    self.paint_inline_completion_popover(layout, window, cx);
-                    self.paint_mouse_context_menu(layout, window, cx);
-                });
+                })
             })
         })
     }
@@ -7703,6 +8319,7 @@ pub struct EditorLayout {
     gutter_hitbox: Hitbox,
     content_origin: gpui::Point<Pixels>,
     scrollbars_layout: Option<EditorScrollbars>,
+    minimap: Option<MinimapLayout>,
     mode: EditorMode,
     wrap_guides: SmallVec<[(Pixels, bool); 2]>,
     indent_guides: Option<Vec<IndentGuideLayout>>,
@@ -7711,6 +8328,7 @@ pub struct EditorLayout {
     highlighted_rows: BTreeMap<DisplayRow, LineHighlight>,
     line_elements: SmallVec<[AnyElement; 1]>,
     line_numbers: Arc<HashMap<MultiBufferRow, LineNumberLayout>>,
+    soft_wrap_indicators: Vec<(gpui::Point<Pixels>, ShapedLine)>,
     display_hunks: Vec<(DisplayDiffHunk, Option<Hitbox>)>,
     blamed_display_rows: Option<Vec<AnyElement>>,
     inline_diagnostics: HashMap<DisplayRow, AnyElement>,
@@ -7718,6 +8336,7 @@ pub struct EditorLayout {
     blocks: Vec<BlockLayout>,
     highlighted_ranges: Vec<(Range<DisplayPoint>, Hsla)>,
     highlighted_gutter_ranges: Vec<(Range<DisplayPoint>, Hsla)>,
+    decoration_underlines: Vec<(Range<DisplayPoint>, Hsla)>,
     redacted_ranges: Vec<Range<DisplayPoint>>,
     cursors: Vec<(DisplayPoint, Hsla)>,
     visible_cursors: Vec<CursorLayout>,
@@ -7725,6 +8344,7 @@ pub struct EditorLayout {
     code_actions_indicator: Option<AnyElement>,
     test_indicators: Vec<AnyElement>,
     breakpoints: Vec<AnyElement>,
+    decoration_gutter_icons: Vec<AnyElement>,
     crease_toggles: Vec<Option<AnyElement>>,
     expand_toggles: Vec<Option<(AnyElement, gpui::Point<Pixels>)>>,
     diff_hunk_controls: Vec<AnyElement>,
@@ -7742,6 +8362,48 @@ impl EditorLayout {
     }
 }
 
+/// A scaled overview of the buffer, where lines are drawn as bars.
+struct MinimapLayout {
+    /// Maps rows to the minimap like a scrollbar does, with a thumb that covers the visible rows.
+    scrollbar_layout: ScrollbarLayout,
+    line_quads: Vec<PaintQuad>,
+}
+
+impl MinimapLayout {
+    /// The number of columns that fit across the minimap.
+    const COLUMNS: usize = 120;
+    const MAX_ROW_HEIGHT: Pixels = px(3.0);
+    const MIN_LINE_HEIGHT: Pixels = px(3.0);
+    /// How much of a line's height its bar fills, leaving a gap between lines.
+    const LINE_FILL: f32 = 0.67;
+    const MARKER_OPACITY: f32 = 0.5;
+}
+
+/// Where markers of the editor's diff hunks, highlights and diagnostics are shown.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MarkerTarget {
+    Scrollbar,
+    Minimap,
+}
+
+impl MarkerTarget {
+    fn state(self, editor: &mut Editor) -> &mut ScrollbarMarkerState {
+        match self {
+            MarkerTarget::Scrollbar => &mut editor.scrollbar_marker_state,
+            MarkerTarget::Minimap => &mut editor.minimap_marker_state,
+        }
+    }
+
+    /// The scrollbar shows each kind of marker in its own column, while the minimap shows
+    /// markers across its whole width, behind the lines.
+    fn column(self, column: usize) -> Option<usize> {
+        match self {
+            MarkerTarget::Scrollbar => Some(column),
+            MarkerTarget::Minimap => None,
+        }
+    }
+}
+
 struct LineNumberLayout {
     shaped_line: ShapedLine,
     hitbox: Option<Hitbox>,
@@ -7923,6 +8585,26 @@ impl ScrollbarLayout {
         }
     }
 
+    /// Lays out a minimap, which fits all rows into its height, up to a maximum row height.
+    fn for_minimap(
+        hitbox: Hitbox,
+        row_count: f32,
+        visible_row_count: f32,
+        scroll_position: f32,
+    ) -> Self {
+        // Leave room for a page of overscroll, so that the thumb always fits.
+        let text_unit_size = (hitbox.size.height / (row_count + visible_row_count))
+            .min(MinimapLayout::MAX_ROW_HEIGHT);
+        ScrollbarLayout {
+            hitbox,
+            visible_range: scroll_position..scroll_position + visible_row_count,
+            text_unit_size,
+            content_offset: Pixels::ZERO,
+            thumb_size: text_unit_size * visible_row_count,
+            axis: ScrollbarAxis::Vertical,
+        }
+    }
+
     fn thumb_bounds(&self) -> Bounds<Pixels> {
         let scrollbar_track = &self.hitbox.bounds;
         Bounds::new(
@@ -8718,6 +9400,51 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_minimap_layout(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        cx.update(|cx| {
+            cx.update_global::<settings::SettingsStore, _>(|settings, cx| {
+                settings.update_user_settings::<EditorSettings>(cx, |settings| {
+                    settings.minimap = Some(crate::editor_settings::MinimapContent {
+                        enabled: Some(true),
+                        width: Some(120.),
+                    });
+                });
+            });
+        });
+
+        let window = cx.add_window(|window, cx| {
+            let buffer = MultiBuffer::build_simple("fn main() {\n    1\n\n}", cx);
+            Editor::new(EditorMode::Full, buffer, None, window, cx)
+        });
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let editor = window.root(cx).unwrap();
+        let style = cx.update(|_, cx| editor.read(cx).style().unwrap().clone());
+
+        let (_, state) = cx.draw(
+            point(px(500.), px(500.)),
+            size(px(500.), px(500.)),
+            |_, _| EditorElement::new(&editor, style),
+        );
+        let minimap = state.minimap.as_ref().unwrap();
+        let bounds = minimap.scrollbar_layout.hitbox.bounds;
+        assert_eq!(bounds.size.width, px(120.));
+
+        // The empty line doesn't have a bar, and the bars start at the lines' indentation,
+        // with a column for each pixel.
+        let line_starts = minimap
+            .line_quads
+            .iter()
+            .map(|quad| quad.bounds.origin.x - bounds.origin.x)
+            .collect::<Vec<_>>();
+        assert_eq!(line_starts, [px(0.), px(4.), px(0.)]);
+        assert_eq!(
+            minimap.scrollbar_layout.thumb_bounds().origin,
+            bounds.origin
+        );
+    }
+
     #[gpui::test]
     fn test_all_invisibles_drawing(cx: &mut TestAppContext) {
         const TAB_SIZE: u32 = 4;
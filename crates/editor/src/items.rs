@@ -309,6 +309,7 @@ impl FollowableItem for Editor {
                 }
                 _ => false,
             },
+            _ => false,
         }
     }
 
@@ -319,7 +320,9 @@ impl FollowableItem for Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        let update_view::Variant::Editor(message) = message;
+        let update_view::Variant::Editor(message) = message else {
+            return Task::ready(Err(anyhow!("received a non-editor update for an editor")));
+        };
         let project = project.clone();
         cx.spawn_in(window, async move |this, cx| {
             update_editor_from_message(this, project, message, cx).await
@@ -557,6 +560,23 @@ impl Item for Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> bool {
+        let data = match data.downcast::<Point>() {
+            Ok(point) => {
+                let buffer = self.buffer.read(cx).read(cx);
+                let offset = buffer.clip_point(*point, Bias::Left);
+                drop(buffer);
+                if self.selections.newest::<Point>(cx).head() == offset {
+                    return false;
+                }
+                let nav_history = self.nav_history.take();
+                self.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                    s.select_ranges([offset..offset])
+                });
+                self.nav_history = nav_history;
+                return true;
+            }
+            Err(data) => data,
+        };
         if let Ok(data) = data.downcast::<NavigationData>() {
             let newest_selection = self.selections.newest::<Point>(cx);
             let buffer = self.buffer.read(cx).read(cx);
@@ -746,6 +766,13 @@ impl Item for Editor {
         self.hide_hovered_link(cx);
     }
 
+    fn set_zen_mode(&mut self, enabled: bool, _: &mut Window, cx: &mut Context<Self>) {
+        if self.zen_mode != enabled {
+            self.zen_mode = enabled;
+            cx.notify();
+        }
+    }
+
     fn is_dirty(&self, cx: &App) -> bool {
         self.buffer().read(cx).read(cx).is_dirty()
     }
@@ -1191,6 +1218,12 @@ impl SerializableItem for Editor {
             serialize_dirty_buffers = false;
         }
 
+        // Dirty buffers that aren't restored with the workspace are journaled instead, so that
+        // they can be recovered after a crash.
+        if !serialize_dirty_buffers {
+            self.journal_unsaved_buffer(workspace, item_id, closing, cx);
+        }
+
         if closing && !serialize_dirty_buffers {
             return None;
         }
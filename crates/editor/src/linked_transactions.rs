@@ -0,0 +1,96 @@
+//! Transactions in different buffers that were made together, such as the edits of a rename
+//! across files, so that undoing or redoing any of them in an editor undoes or redoes them all.
+
+use std::sync::Arc;
+
+use collections::HashMap;
+use gpui::{App, Entity, EntityId, Global, WeakEntity};
+use language::Buffer;
+use text::TransactionId;
+
+#[derive(Default)]
+struct LinkedTransactions {
+    groups: HashMap<(EntityId, TransactionId), Arc<[(WeakEntity<Buffer>, TransactionId)]>>,
+}
+
+impl Global for LinkedTransactions {}
+
+/// Links the given transactions, each of which is in a different buffer, into one undo step.
+pub fn link_transactions(
+    transactions: impl IntoIterator<Item = (Entity<Buffer>, TransactionId)>,
+    cx: &mut App,
+) {
+    let transactions = transactions
+        .into_iter()
+        .map(|(buffer, transaction_id)| (buffer.downgrade(), transaction_id))
+        .collect::<Arc<[_]>>();
+    if transactions.len() < 2 {
+        return;
+    }
+
+    let linked_transactions = cx.default_global::<LinkedTransactions>();
+    linked_transactions
+        .groups
+        .retain(|_, group| group.iter().all(|(buffer, _)| buffer.upgrade().is_some()));
+    for (buffer, transaction_id) in transactions.iter() {
+        linked_transactions
+            .groups
+            .insert((buffer.entity_id(), *transaction_id), transactions.clone());
+    }
+}
+
+/// Undoes the transactions linked to one that was just undone in the given buffer.
+pub(crate) fn undo_linked_transactions(
+    buffer: &Entity<Buffer>,
+    transaction_id: TransactionId,
+    cx: &mut App,
+) {
+    for (linked_buffer, linked_transaction_id) in linked_transactions(buffer, transaction_id, cx) {
+        linked_buffer.update(cx, |linked_buffer, cx| {
+            linked_buffer.undo_transaction(linked_transaction_id, cx);
+        });
+    }
+}
+
+/// Redoes the transactions linked to one that was just redone in the given buffer, as long as
+/// they're the last ones that were undone in their buffers.
+pub(crate) fn redo_linked_transactions(
+    buffer: &Entity<Buffer>,
+    transaction_id: TransactionId,
+    cx: &mut App,
+) {
+    for (linked_buffer, linked_transaction_id) in linked_transactions(buffer, transaction_id, cx) {
+        linked_buffer.update(cx, |linked_buffer, cx| {
+            let is_last_undone = linked_buffer
+                .peek_redo_stack()
+                .is_some_and(|entry| entry.transaction_id() == linked_transaction_id);
+            if is_last_undone {
+                linked_buffer.redo_to_transaction(linked_transaction_id, cx);
+            }
+        });
+    }
+}
+
+fn linked_transactions(
+    buffer: &Entity<Buffer>,
+    transaction_id: TransactionId,
+    cx: &App,
+) -> Vec<(Entity<Buffer>, TransactionId)> {
+    let Some(linked_transactions) = cx.try_global::<LinkedTransactions>() else {
+        return Vec::new();
+    };
+    let key = (buffer.entity_id(), transaction_id);
+    let Some(group) = linked_transactions.groups.get(&key) else {
+        return Vec::new();
+    };
+    group
+        .iter()
+        .filter(|(linked_buffer, linked_transaction_id)| {
+            (linked_buffer.entity_id(), *linked_transaction_id)
+                != (buffer.entity_id(), transaction_id)
+        })
+        .filter_map(|(linked_buffer, linked_transaction_id)| {
+            Some((linked_buffer.upgrade()?, *linked_transaction_id))
+        })
+        .collect()
+}
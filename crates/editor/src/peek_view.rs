@@ -0,0 +1,509 @@
+//! Peek views, which show the definitions or references of a symbol in an editor embedded below
+//! the line that it's on, so that they can be read and edited without leaving the current editor.
+
+use std::sync::Arc;
+
+use collections::{HashMap, HashSet};
+use gpui::{
+    actions, uniform_list, App, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    ScrollStrategy, Subscription, UniformListScrollHandle, Window,
+};
+use language::Buffer;
+use project::{Location, LocationLink, Project};
+use text::ToPoint as _;
+use ui::{prelude::*, IconButton, ListItem, Tooltip};
+
+use crate::{
+    actions::{Cancel, PeekDefinition, PeekReferences},
+    display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
+    hover_links::HoverLink,
+    scroll::Autoscroll,
+    Anchor, Editor, GotoDefinitionKind,
+};
+
+actions!(
+    peek_view,
+    [
+        Close,
+        SelectNextLocation,
+        SelectPreviousLocation,
+        OpenLocation,
+        Grow,
+        Shrink
+    ]
+);
+
+const DEFAULT_HEIGHT_IN_LINES: u32 = 14;
+const MIN_HEIGHT_IN_LINES: u32 = 6;
+const MAX_HEIGHT_IN_LINES: u32 = 48;
+const HEIGHT_STEP_IN_LINES: u32 = 4;
+
+pub(crate) struct PeekViewState {
+    block_id: CustomBlockId,
+    view: Entity<PeekView>,
+    _subscription: Subscription,
+}
+
+enum PeekViewEvent {
+    Close,
+    Resized,
+    OpenLocation(Location),
+}
+
+/// Definitions or references, one of which is shown in an embedded editor, with a list to pick
+/// the others from.
+struct PeekView {
+    title: SharedString,
+    locations: Vec<Location>,
+    labels: Vec<SharedString>,
+    selected_ix: usize,
+    editor: Entity<Editor>,
+    project: Entity<Project>,
+    height_in_lines: u32,
+    scroll_handle: UniformListScrollHandle,
+}
+
+impl EventEmitter<PeekViewEvent> for PeekView {}
+
+impl Editor {
+    /// Shows the definition of the symbol under the newest cursor below its line.
+    pub fn peek_definition(
+        &mut self,
+        _: &PeekDefinition,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(provider) = self.semantics_provider.clone() else {
+            return;
+        };
+        let head = self.selections.newest_anchor().head();
+        let Some((buffer, position)) = self.buffer.read(cx).text_anchor_for_position(head, cx)
+        else {
+            return;
+        };
+        let Some(definitions) =
+            provider.definitions(&buffer, position, GotoDefinitionKind::Symbol, cx)
+        else {
+            return;
+        };
+
+        cx.spawn_in(window, async move |editor, cx| {
+            let definitions = definitions.await?;
+            editor.update_in(cx, |editor, window, cx| {
+                let title = match definitions.first().and_then(|link| link.origin.as_ref()) {
+                    Some(origin) => {
+                        let name = origin
+                            .buffer
+                            .read(cx)
+                            .text_for_range(origin.range.clone())
+                            .collect::<String>();
+                        format!("Definition of `{name}`")
+                    }
+                    None => "Definition".to_string(),
+                };
+                let locations = definitions.into_iter().map(|link| link.target).collect();
+                editor.show_peek_view(title, head, locations, window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Shows the references to the symbol under the newest cursor below its line.
+    pub fn peek_references(
+        &mut self,
+        _: &PeekReferences,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let head = self.selections.newest_anchor().head();
+        let Some((buffer, position)) = self.buffer.read(cx).text_anchor_for_position(head, cx)
+        else {
+            return;
+        };
+        let references =
+            project.update(cx, |project, cx| project.references(&buffer, position, cx));
+
+        cx.spawn_in(window, async move |editor, cx| {
+            let locations = references.await?;
+            editor.update_in(cx, |editor, window, cx| {
+                let title = match locations.len() {
+                    1 => "1 reference".to_string(),
+                    count => format!("{count} references"),
+                };
+                editor.show_peek_view(title, head, locations, window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn show_peek_view(
+        &mut self,
+        title: String,
+        position: Anchor,
+        locations: Vec<Location>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.close_peek_view(window, cx);
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        if locations.is_empty() {
+            return;
+        }
+
+        let view = cx.new(|cx| PeekView::new(title.into(), locations, project, window, cx));
+        let block_id = self.insert_blocks(
+            [BlockProperties {
+                placement: BlockPlacement::Below(position),
+                height: view.read(cx).height_in_lines,
+                style: BlockStyle::Sticky,
+                render: Arc::new({
+                    let view = view.clone();
+                    move |cx: &mut BlockContext| {
+                        div()
+                            .occlude()
+                            .size_full()
+                            .pl(cx.gutter_dimensions.full_width())
+                            .child(view.clone())
+                            .into_any_element()
+                    }
+                }),
+                priority: 0,
+            }],
+            Some(Autoscroll::fit()),
+            cx,
+        )[0];
+        let subscription =
+            cx.subscribe_in(
+                &view,
+                window,
+                |editor, view, event, window, cx| match event {
+                    PeekViewEvent::Close => {
+                        editor.close_peek_view(window, cx);
+                    }
+                    PeekViewEvent::Resized => {
+                        if let Some(block_id) =
+                            editor.peek_view.as_ref().map(|state| state.block_id)
+                        {
+                            let height = view.read(cx).height_in_lines;
+                            editor.resize_blocks(
+                                HashMap::from_iter([(block_id, height)]),
+                                None,
+                                cx,
+                            );
+                        }
+                    }
+                    PeekViewEvent::OpenLocation(location) => {
+                        let link = HoverLink::Text(LocationLink {
+                            origin: None,
+                            target: location.clone(),
+                        });
+                        editor.close_peek_view(window, cx);
+                        editor
+                            .navigate_to_hover_links(None, vec![link], false, window, cx)
+                            .detach_and_log_err(cx);
+                    }
+                },
+            );
+        window.focus(&view.focus_handle(cx));
+        self.peek_view = Some(PeekViewState {
+            block_id,
+            view,
+            _subscription: subscription,
+        });
+    }
+
+    /// Closes the peek view, returning whether one was shown.
+    pub(crate) fn close_peek_view(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let Some(state) = self.peek_view.take() else {
+            return false;
+        };
+        let was_focused = state.view.focus_handle(cx).contains_focused(window, cx);
+        self.remove_blocks(HashSet::from_iter([state.block_id]), None, cx);
+        if was_focused {
+            window.focus(&self.focus_handle);
+        }
+        true
+    }
+}
+
+impl PeekView {
+    fn new(
+        title: SharedString,
+        locations: Vec<Location>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let labels = locations
+            .iter()
+            .map(|location| {
+                let buffer = location.buffer.read(cx);
+                let row = location.range.start.to_point(buffer).row;
+                let path = buffer.file().map_or_else(
+                    || "untitled".to_string(),
+                    |file| file.path().to_string_lossy().into_owned(),
+                );
+                format!("{path}:{}", row + 1).into()
+            })
+            .collect();
+        let editor = Self::build_editor(&locations[0].buffer, &project, window, cx);
+        Self::reveal_location(&editor, &locations[0], window, cx);
+        Self {
+            title,
+            locations,
+            labels,
+            selected_ix: 0,
+            editor,
+            project,
+            height_in_lines: DEFAULT_HEIGHT_IN_LINES,
+            scroll_handle: UniformListScrollHandle::default(),
+        }
+    }
+
+    fn build_editor(
+        buffer: &Entity<Buffer>,
+        project: &Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Editor> {
+        cx.new(|cx| Editor::for_buffer(buffer.clone(), Some(project.clone()), window, cx))
+    }
+
+    fn reveal_location(
+        editor: &Entity<Editor>,
+        location: &Location,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        editor.update(cx, |editor, cx| {
+            let buffer = location.buffer.read(cx);
+            let range = location.range.start.to_point(buffer)..location.range.end.to_point(buffer);
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let range = snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end);
+            editor.change_selections(Some(Autoscroll::center()), window, cx, |selections| {
+                selections.select_anchor_ranges([range.start..range.start]);
+            });
+            editor.highlight_background::<PeekView>(
+                &[range],
+                |theme| theme.editor_highlighted_line_background,
+                cx,
+            );
+        });
+    }
+
+    fn select_location(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(location) = self.locations.get(ix).cloned() else {
+            return;
+        };
+        self.selected_ix = ix;
+        self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+
+        let is_same_buffer = self
+            .editor
+            .read(cx)
+            .buffer()
+            .read(cx)
+            .as_singleton()
+            .is_some_and(|buffer| buffer == location.buffer);
+        if !is_same_buffer {
+            let was_focused = self.editor.focus_handle(cx).contains_focused(window, cx);
+            self.editor = Self::build_editor(&location.buffer, &self.project, window, cx);
+            if was_focused {
+                window.focus(&self.editor.focus_handle(cx));
+            }
+        }
+        Self::reveal_location(&self.editor, &location, window, cx);
+        cx.notify();
+    }
+
+    fn select_next_location(
+        &mut self,
+        _: &SelectNextLocation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ix = (self.selected_ix + 1) % self.locations.len();
+        self.select_location(ix, window, cx);
+    }
+
+    fn select_previous_location(
+        &mut self,
+        _: &SelectPreviousLocation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ix = self
+            .selected_ix
+            .checked_sub(1)
+            .unwrap_or(self.locations.len() - 1);
+        self.select_location(ix, window, cx);
+    }
+
+    fn open_location(&mut self, _: &OpenLocation, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(location) = self.locations.get(self.selected_ix).cloned() {
+            cx.emit(PeekViewEvent::OpenLocation(location));
+        }
+    }
+
+    fn close(&mut self, _: &Close, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(PeekViewEvent::Close);
+    }
+
+    fn cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(PeekViewEvent::Close);
+    }
+
+    fn grow(&mut self, _: &Grow, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_height(self.height_in_lines + HEIGHT_STEP_IN_LINES, cx);
+    }
+
+    fn shrink(&mut self, _: &Shrink, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_height(
+            self.height_in_lines.saturating_sub(HEIGHT_STEP_IN_LINES),
+            cx,
+        );
+    }
+
+    fn set_height(&mut self, height_in_lines: u32, cx: &mut Context<Self>) {
+        let height_in_lines = height_in_lines.clamp(MIN_HEIGHT_IN_LINES, MAX_HEIGHT_IN_LINES);
+        if height_in_lines != self.height_in_lines {
+            self.height_in_lines = height_in_lines;
+            cx.emit(PeekViewEvent::Resized);
+            cx.notify();
+        }
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors();
+        h_flex()
+            .px_2()
+            .py_0p5()
+            .gap_2()
+            .justify_between()
+            .border_b_1()
+            .border_color(colors.border_variant)
+            .bg(colors.editor_subheader_background)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(self.title.clone()).size(LabelSize::Small))
+                    .child(
+                        Label::new(self.labels[self.selected_ix].clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new("shrink", IconName::ChevronUp)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.height_in_lines <= MIN_HEIGHT_IN_LINES)
+                            .tooltip(Tooltip::for_action_title("Shrink", &Shrink))
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.shrink(&Shrink, window, cx)),
+                            ),
+                    )
+                    .child(
+                        IconButton::new("grow", IconName::ChevronDown)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.height_in_lines >= MAX_HEIGHT_IN_LINES)
+                            .tooltip(Tooltip::for_action_title("Grow", &Grow))
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.grow(&Grow, window, cx)),
+                            ),
+                    )
+                    .child(
+                        IconButton::new("open", IconName::ArrowUpRight)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::for_action_title("Open Location", &OpenLocation))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.open_location(&OpenLocation, window, cx)
+                            })),
+                    )
+                    .child(
+                        IconButton::new("close", IconName::Close)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::for_action_title("Close", &Close))
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.close(&Close, window, cx)),
+                            ),
+                    ),
+            )
+    }
+
+    fn render_locations(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .w(px(280.))
+            .h_full()
+            .border_l_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                uniform_list(
+                    cx.entity(),
+                    "peek_view_locations",
+                    self.locations.len(),
+                    |this, range, _, cx| {
+                        range
+                            .map(|ix| {
+                                ListItem::new(ix)
+                                    .inset(true)
+                                    .toggle_state(ix == this.selected_ix)
+                                    .child(
+                                        Label::new(this.labels[ix].clone())
+                                            .size(LabelSize::Small)
+                                            .truncate(),
+                                    )
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.select_location(ix, window, cx)
+                                    }))
+                            })
+                            .collect()
+                    },
+                )
+                .size_full()
+                .track_scroll(self.scroll_handle.clone()),
+            )
+    }
+}
+
+impl Focusable for PeekView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Render for PeekView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors();
+        v_flex()
+            .key_context("PeekView")
+            .on_action(cx.listener(Self::select_next_location))
+            .on_action(cx.listener(Self::select_previous_location))
+            .on_action(cx.listener(Self::open_location))
+            .on_action(cx.listener(Self::close))
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::grow))
+            .on_action(cx.listener(Self::shrink))
+            .size_full()
+            .border_y_1()
+            .border_color(colors.border_focused)
+            .bg(colors.editor_background)
+            .child(self.render_header(cx))
+            .child(
+                h_flex()
+                    .flex_1()
+                    .min_h_0()
+                    .child(div().flex_1().h_full().child(self.editor.clone()))
+                    .when(self.locations.len() > 1, |this| {
+                        this.child(self.render_locations(cx))
+                    }),
+            )
+    }
+}
@@ -83,6 +83,38 @@ impl Column for SerializedEditor {
     }
 }
 
+/// The contents of a dirty buffer that isn't restored with its workspace, journaled so that it
+/// can be recovered if Zed doesn't quit cleanly.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct UnsavedBuffer {
+    pub(crate) workspace_id: WorkspaceId,
+    pub(crate) abs_path: Option<PathBuf>,
+    pub(crate) contents: String,
+    pub(crate) language: Option<String>,
+}
+
+impl StaticColumnCount for UnsavedBuffer {
+    fn column_count() -> usize {
+        4
+    }
+}
+
+impl Column for UnsavedBuffer {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (workspace_id, start_index) = Column::column(statement, start_index)?;
+        let (abs_path, start_index) = Column::column(statement, start_index)?;
+        let (contents, start_index) = Column::column(statement, start_index)?;
+        let (language, start_index) = Column::column(statement, start_index)?;
+        let buffer = Self {
+            workspace_id,
+            abs_path,
+            contents,
+            language,
+        };
+        Ok((buffer, start_index))
+    }
+}
+
 define_connection!(
     // Current schema shape using pseudo-rust syntax:
     // editors(
@@ -113,6 +145,15 @@ define_connection!(
     //   start: usize,
     //   end: usize,
     // )
+    //
+    // unsaved_buffers(
+    //   session_id: String,
+    //   workspace_id: usize,
+    //   item_id: usize,
+    //   path: Option<PathBuf>,
+    //   contents: String,
+    //   language: Option<String>,
+    // )
     pub static ref DB: EditorDb<WorkspaceDb> = &[
         sql! (
             CREATE TABLE editors(
@@ -188,6 +229,19 @@ define_connection!(
                 ON DELETE CASCADE
             ) STRICT;
         ),
+        // Not tied to the workspaces table, so that the contents outlive workspaces that aren't
+        // restored.
+        sql! (
+            CREATE TABLE unsaved_buffers (
+                session_id TEXT NOT NULL,
+                workspace_id INTEGER NOT NULL,
+                item_id INTEGER NOT NULL,
+                path BLOB,
+                contents TEXT NOT NULL,
+                language TEXT,
+                PRIMARY KEY(session_id, workspace_id, item_id)
+            ) STRICT;
+        ),
     ];
 );
 
@@ -372,6 +426,55 @@ VALUES {placeholders};
         Ok(())
     }
 
+    query! {
+        pub async fn save_unsaved_buffer(
+            session_id: String,
+            workspace_id: WorkspaceId,
+            item_id: ItemId,
+            abs_path: Option<PathBuf>,
+            contents: String,
+            language: Option<String>
+        ) -> Result<()> {
+            INSERT OR REPLACE INTO unsaved_buffers
+                (session_id, workspace_id, item_id, path, contents, language)
+            VALUES
+                (?1, ?2, ?3, ?4, ?5, ?6)
+        }
+    }
+
+    query! {
+        pub async fn delete_unsaved_buffer(
+            session_id: String,
+            workspace_id: WorkspaceId,
+            item_id: ItemId
+        ) -> Result<()> {
+            DELETE FROM unsaved_buffers
+            WHERE session_id = ?1 AND workspace_id = ?2 AND item_id = ?3
+        }
+    }
+
+    query! {
+        pub async fn delete_unsaved_buffers_of_session(session_id: String) -> Result<()> {
+            DELETE FROM unsaved_buffers WHERE session_id = ?
+        }
+    }
+
+    // The unsaved buffers left behind by sessions that didn't quit cleanly.
+    query! {
+        pub fn unsaved_buffers_of_other_sessions(session_id: String) -> Result<Vec<UnsavedBuffer>> {
+            SELECT workspace_id, path, contents, language
+            FROM unsaved_buffers
+            WHERE session_id != ?
+            ORDER BY workspace_id, item_id
+        }
+    }
+
+    query! {
+        pub async fn delete_unsaved_buffers_of_other_sessions(session_id: String) -> Result<()> {
+            DELETE FROM unsaved_buffers WHERE session_id != ?
+        }
+    }
+
     pub async fn delete_unloaded_items(
         &self,
         workspace: WorkspaceId,
@@ -478,4 +581,80 @@ mod tests {
             .unwrap();
         assert_eq!(have, serialized_editor);
     }
+
+    #[gpui::test]
+    async fn test_unsaved_buffers_of_other_sessions() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        let crashed_session = "crashed-session".to_string();
+        let current_session = "current-session".to_string();
+
+        DB.save_unsaved_buffer(
+            crashed_session.clone(),
+            workspace_id,
+            1,
+            None,
+            "untitled".to_string(),
+            Some("Rust".to_string()),
+        )
+        .await
+        .unwrap();
+        DB.save_unsaved_buffer(
+            crashed_session.clone(),
+            workspace_id,
+            2,
+            Some(PathBuf::from("/root/file.txt")),
+            "edited".to_string(),
+            None,
+        )
+        .await
+        .unwrap();
+        DB.save_unsaved_buffer(
+            current_session.clone(),
+            workspace_id,
+            3,
+            None,
+            "current".to_string(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        // Saving a buffer removes it from the journal.
+        DB.delete_unsaved_buffer(current_session.clone(), workspace_id, 3)
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.unsaved_buffers_of_other_sessions(crashed_session.clone())
+                .unwrap(),
+            Vec::new()
+        );
+
+        assert_eq!(
+            DB.unsaved_buffers_of_other_sessions(current_session.clone())
+                .unwrap(),
+            vec![
+                UnsavedBuffer {
+                    workspace_id,
+                    abs_path: None,
+                    contents: "untitled".to_string(),
+                    language: Some("Rust".to_string()),
+                },
+                UnsavedBuffer {
+                    workspace_id,
+                    abs_path: Some(PathBuf::from("/root/file.txt")),
+                    contents: "edited".to_string(),
+                    language: None,
+                },
+            ]
+        );
+
+        DB.delete_unsaved_buffers_of_other_sessions(current_session.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.unsaved_buffers_of_other_sessions(current_session)
+                .unwrap(),
+            Vec::new()
+        );
+    }
 }
@@ -1,6 +1,7 @@
 mod actions;
 pub(crate) mod autoscroll;
 pub(crate) mod scroll_amount;
+mod smooth_scroll;
 
 use crate::editor_settings::ScrollBeyondLastLine;
 use crate::{
@@ -16,6 +17,7 @@ use gpui::{point, px, App, Axis, Context, Global, Pixels, Task, Window};
 use language::{Bias, Point};
 pub use scroll_amount::ScrollAmount;
 use settings::Settings;
+use smooth_scroll::{ScrollGesture, SmoothScroll};
 use std::{
     cmp::Ordering,
     time::{Duration, Instant},
@@ -132,6 +134,9 @@ pub struct ScrollManager {
     show_scrollbars: bool,
     hide_scrollbar_task: Option<Task<()>>,
     dragging_scrollbar: Option<Axis>,
+    dragging_minimap: bool,
+    smooth_scroll: Option<SmoothScroll>,
+    scroll_gesture: ScrollGesture,
     visible_line_count: Option<f32>,
     forbid_vertical_scroll: bool,
 }
@@ -146,6 +151,9 @@ impl ScrollManager {
             show_scrollbars: true,
             hide_scrollbar_task: None,
             dragging_scrollbar: None,
+            dragging_minimap: false,
+            smooth_scroll: None,
+            scroll_gesture: ScrollGesture::default(),
             last_autoscroll: None,
             visible_line_count: None,
             forbid_vertical_scroll: false,
@@ -262,6 +270,7 @@ impl ScrollManager {
             return;
         }
         self.anchor = anchor;
+        self.smooth_scroll = None;
         cx.emit(EditorEvent::ScrollPositionChanged { local, autoscroll });
         self.show_scrollbars(window, cx);
         self.autoscroll_request.take();
@@ -338,6 +347,17 @@ impl ScrollManager {
         }
     }
 
+    pub fn is_dragging_minimap(&self) -> bool {
+        self.dragging_minimap
+    }
+
+    pub fn set_dragging_minimap(&mut self, dragging: bool, cx: &mut Context<Editor>) {
+        if self.dragging_minimap != dragging {
+            self.dragging_minimap = dragging;
+            cx.notify();
+        }
+    }
+
     pub fn clamp_scroll_left(&mut self, max: f32) -> bool {
         if max < self.anchor.offset.x {
             self.anchor.offset.x = max;
@@ -387,6 +407,7 @@ impl Editor {
             cx.spawn_in(window, async move |editor, cx| {
                 editor
                     .update(cx, |editor, cx| {
+                        editor.parse_visible_ranges(cx);
                         editor.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx)
                     })
                     .ok()
@@ -487,6 +508,7 @@ impl Editor {
             cx,
         );
 
+        self.parse_visible_ranges(cx);
         self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
     }
 
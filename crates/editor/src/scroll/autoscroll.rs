@@ -4,7 +4,9 @@ use crate::{
 use gpui::{px, Bounds, Context, Pixels, Window};
 use language::Point;
 use multi_buffer::Anchor;
+use settings::Settings;
 use std::{cmp, f32};
+use workspace::WorkspaceSettings;
 
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub enum Autoscroll {
@@ -123,9 +125,23 @@ impl Editor {
             self.set_scroll_position(scroll_position, window, cx);
         }
 
-        let Some((autoscroll, local)) = self.scroll_manager.autoscroll_request.take() else {
+        let Some((mut autoscroll, local)) = self.scroll_manager.autoscroll_request.take() else {
             return false;
         };
+        // Typewriter scrolling keeps the cursor's line in the middle of the viewport.
+        if self.zen_mode
+            && WorkspaceSettings::get_global(cx)
+                .zen_mode
+                .typewriter_scrolling
+        {
+            if let Autoscroll::Strategy(
+                AutoscrollStrategy::Fit | AutoscrollStrategy::Newest,
+                anchor,
+            ) = autoscroll
+            {
+                autoscroll = Autoscroll::Strategy(AutoscrollStrategy::Center, anchor);
+            }
+        }
 
         let mut target_top;
         let mut target_bottom;
@@ -0,0 +1,265 @@
+//! Animates scrolling. Mouse wheels scroll by whole lines, so the editor glides to where they
+//! scroll to instead of jumping there. Touchpad gestures keep scrolling with a decaying velocity
+//! after the fingers are lifted, on the platforms that don't do that themselves.
+
+use std::time::{Duration, Instant};
+
+use gpui::{App, Context, Task, TouchPhase, Window};
+use settings::Settings;
+
+use crate::{Editor, EditorSettings};
+
+const FRAME_INTERVAL: Duration = Duration::from_millis(8);
+/// How long a wheel scroll takes to cover about two thirds of the distance left to go.
+const WHEEL_TIME_CONSTANT: f32 = 0.04;
+/// How close, in lines and columns, a wheel scroll has to get to where it's going to stop.
+const WHEEL_PRECISION: f32 = 0.01;
+/// The fraction of its velocity that momentum keeps after a second.
+const MOMENTUM_RETAINED_PER_SECOND: f32 = 0.03;
+/// The speed, in lines and columns per second, below which momentum stops.
+const MIN_MOMENTUM_SPEED: f32 = 1.;
+/// How long a touchpad gesture goes without events before it's considered to have ended, on the
+/// platforms that don't report when the fingers are lifted.
+const GESTURE_END_DELAY: Duration = Duration::from_millis(50);
+/// Whether the platform keeps sending scroll events after touchpad gestures end, for momentum.
+const PLATFORM_HAS_MOMENTUM: bool = cfg!(target_os = "macos");
+
+pub(crate) struct SmoothScroll {
+    motion: SmoothScrollMotion,
+    scroll_max: gpui::Point<f32>,
+    last_frame: Instant,
+    _task: Task<()>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SmoothScrollMotion {
+    /// Glides to a scroll position.
+    Target(gpui::Point<f32>),
+    /// Keeps scrolling with a velocity, in lines and columns per second, that decays over time.
+    Momentum(gpui::Point<f32>),
+}
+
+/// How fast a touchpad gesture is scrolling, to keep scrolling at that velocity after it ends.
+#[derive(Default)]
+pub(crate) struct ScrollGesture {
+    velocity: gpui::Point<f32>,
+    last_event: Option<(Instant, gpui::Point<f32>)>,
+    /// Whether the platform reports when the fingers are lifted, with [`TouchPhase::Ended`].
+    reports_end: bool,
+    end_task: Option<Task<()>>,
+}
+
+impl ScrollGesture {
+    fn record(&mut self, scroll_position: gpui::Point<f32>, now: Instant) {
+        match self.last_event {
+            Some((last_event, last_position)) if now - last_event < GESTURE_END_DELAY => {
+                let elapsed = (now - last_event).as_secs_f32().max(0.001);
+                let velocity = (scroll_position - last_position) / elapsed;
+                // Average with the previous velocity, since events don't arrive evenly.
+                self.velocity = (self.velocity + velocity) / 2.;
+            }
+            _ => self.velocity = gpui::Point::default(),
+        }
+        self.last_event = Some((now, scroll_position));
+    }
+
+    /// Returns the velocity to keep scrolling at when the gesture ends, which is zero if the
+    /// fingers were held still before they were lifted.
+    fn end(&mut self, now: Instant) -> gpui::Point<f32> {
+        self.end_task = None;
+        match self.last_event.take() {
+            Some((last_event, _)) if now - last_event < GESTURE_END_DELAY => self.velocity,
+            _ => gpui::Point::default(),
+        }
+    }
+}
+
+/// Returns whether scrolling is animated, which it isn't when the system asks for reduced motion.
+fn smooth_scroll_enabled(cx: &App) -> bool {
+    EditorSettings::get_global(cx).smooth_scroll && !cx.reduce_motion()
+}
+
+impl Editor {
+    /// Returns the scroll position that a mouse wheel scroll is gliding to.
+    pub(crate) fn wheel_scroll_target(&self) -> Option<gpui::Point<f32>> {
+        match self.scroll_manager.smooth_scroll.as_ref()?.motion {
+            SmoothScrollMotion::Target(target) => Some(target),
+            SmoothScrollMotion::Momentum(_) => None,
+        }
+    }
+
+    /// Scrolls to where a mouse wheel scrolled to, gliding there when smooth scrolling is enabled.
+    pub(crate) fn scroll_by_wheel(
+        &mut self,
+        scroll_position: gpui::Point<f32>,
+        scroll_max: gpui::Point<f32>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if smooth_scroll_enabled(cx) {
+            self.start_smooth_scroll(
+                SmoothScrollMotion::Target(scroll_position),
+                scroll_max,
+                window,
+                cx,
+            );
+        } else {
+            self.scroll(scroll_position, None, window, cx);
+        }
+    }
+
+    /// Keeps track of a touchpad gesture that scrolled to the given position, to keep scrolling
+    /// with momentum when it ends.
+    pub(crate) fn track_scroll_gesture(
+        &mut self,
+        scroll_position: gpui::Point<f32>,
+        touch_phase: TouchPhase,
+        scroll_max: gpui::Point<f32>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if PLATFORM_HAS_MOMENTUM || !smooth_scroll_enabled(cx) {
+            return;
+        }
+
+        let now = Instant::now();
+        let gesture = &mut self.scroll_manager.scroll_gesture;
+        match touch_phase {
+            TouchPhase::Started => {
+                *gesture = ScrollGesture {
+                    reports_end: gesture.reports_end,
+                    ..Default::default()
+                }
+            }
+            TouchPhase::Moved => {
+                gesture.record(scroll_position, now);
+                if !gesture.reports_end {
+                    gesture.end_task = Some(cx.spawn_in(window, async move |editor, cx| {
+                        cx.background_executor().timer(GESTURE_END_DELAY).await;
+                        editor
+                            .update_in(cx, |editor, window, cx| {
+                                editor.end_scroll_gesture(scroll_max, window, cx)
+                            })
+                            .ok();
+                    }));
+                }
+            }
+            TouchPhase::Ended => {
+                gesture.reports_end = true;
+                self.end_scroll_gesture(scroll_max, window, cx);
+            }
+        }
+    }
+
+    fn end_scroll_gesture(
+        &mut self,
+        scroll_max: gpui::Point<f32>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let velocity = self.scroll_manager.scroll_gesture.end(Instant::now());
+        if velocity.x.abs().max(velocity.y.abs()) >= MIN_MOMENTUM_SPEED {
+            self.start_smooth_scroll(
+                SmoothScrollMotion::Momentum(velocity),
+                scroll_max,
+                window,
+                cx,
+            );
+        }
+    }
+
+    fn start_smooth_scroll(
+        &mut self,
+        motion: SmoothScrollMotion,
+        scroll_max: gpui::Point<f32>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let task = cx.spawn_in(window, async move |editor, cx| loop {
+            cx.background_executor().timer(FRAME_INTERVAL).await;
+            let animating = editor
+                .update_in(cx, |editor, window, cx| {
+                    editor.step_smooth_scroll(window, cx)
+                })
+                .unwrap_or(false);
+            if !animating {
+                break;
+            }
+        });
+        self.scroll_manager.smooth_scroll = Some(SmoothScroll {
+            motion,
+            scroll_max,
+            last_frame: Instant::now(),
+            _task: task,
+        });
+    }
+
+    /// Moves a frame further along the smooth scroll, returning whether it has frames left.
+    fn step_smooth_scroll(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        // Scrolling stops smooth scrolls, so take it while it scrolls and put it back after.
+        let Some(mut smooth_scroll) = self.scroll_manager.smooth_scroll.take() else {
+            return false;
+        };
+        let now = Instant::now();
+        let elapsed = (now - smooth_scroll.last_frame).as_secs_f32();
+        smooth_scroll.last_frame = now;
+
+        let current_position = self.scroll_position(cx);
+        let (scroll_position, animating) = match &mut smooth_scroll.motion {
+            SmoothScrollMotion::Target(target) => {
+                let remaining = *target - current_position;
+                if remaining.x.abs().max(remaining.y.abs()) <= WHEEL_PRECISION {
+                    (*target, false)
+                } else {
+                    let progress = 1. - (-elapsed / WHEEL_TIME_CONSTANT).exp();
+                    (current_position + remaining * progress, true)
+                }
+            }
+            SmoothScrollMotion::Momentum(velocity) => {
+                let scroll_position = (current_position + *velocity * elapsed)
+                    .clamp(&gpui::Point::default(), &smooth_scroll.scroll_max);
+                // Stop at the edges instead of pushing against them.
+                if scroll_position.x != current_position.x + velocity.x * elapsed {
+                    velocity.x = 0.;
+                }
+                if scroll_position.y != current_position.y + velocity.y * elapsed {
+                    velocity.y = 0.;
+                }
+                *velocity = *velocity * MOMENTUM_RETAINED_PER_SECOND.powf(elapsed);
+                let animating = velocity.x.abs().max(velocity.y.abs()) >= MIN_MOMENTUM_SPEED;
+                (scroll_position, animating)
+            }
+        };
+
+        if scroll_position != current_position {
+            self.set_scroll_position(scroll_position, window, cx);
+        }
+        if animating {
+            self.scroll_manager.smooth_scroll = Some(smooth_scroll);
+        }
+        animating
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::point;
+
+    #[test]
+    fn test_scroll_gesture_velocity() {
+        let start = Instant::now();
+        let mut gesture = ScrollGesture::default();
+        gesture.record(point(0., 0.), start);
+        gesture.record(point(0., 1.), start + Duration::from_millis(10));
+        gesture.record(point(0., 2.), start + Duration::from_millis(20));
+        let velocity = gesture.end(start + Duration::from_millis(30));
+        assert!((velocity.y - 100.).abs() < 1., "{velocity:?}");
+
+        // Holding the fingers still before lifting them doesn't leave any momentum.
+        gesture.record(point(0., 2.), start + Duration::from_secs(1));
+        gesture.record(point(0., 3.), start + Duration::from_millis(1010));
+        let velocity = gesture.end(start + Duration::from_millis(1200));
+        assert_eq!(velocity, point(0., 0.));
+    }
+}
@@ -0,0 +1,181 @@
+use std::{cmp::Ordering, ops::Range};
+
+use collections::BTreeMap;
+use gpui::{App, Context, Hsla, SharedString};
+use multi_buffer::{Anchor, MultiBufferSnapshot, ToOffset as _};
+use ui::{Color, IconName};
+use util::post_inc;
+
+use crate::{
+    display_map::{Inlay, ToDisplayPoint as _},
+    DisplayPoint, DisplayRow, DisplaySnapshot, Editor, InlayId,
+};
+
+/// A decoration displayed over a range of an editor's text, on behalf of a source other
+/// than a language server, like an extension.
+#[derive(Clone, Debug)]
+pub struct TextDecoration {
+    pub range: Range<Anchor>,
+    pub kind: TextDecorationKind,
+    pub color: Color,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TextDecorationKind {
+    /// An icon in the gutter, on the first row of the range.
+    GutterIcon(IconName),
+    /// A wavy underline below the range.
+    Underline,
+    /// A background highlight behind the range.
+    Background,
+    /// A hint displayed inline at the end of the range, styled like an inlay hint.
+    InlineHint(SharedString),
+}
+
+#[derive(Default)]
+pub(crate) struct TextDecorations {
+    decorations: Vec<TextDecoration>,
+    inlay_ids: Vec<InlayId>,
+}
+
+/// The opacity of the background highlights, so that the text remains readable.
+const BACKGROUND_OPACITY: f32 = 0.2;
+
+impl Editor {
+    /// Replaces the decorations from the given source.
+    pub fn set_text_decorations(
+        &mut self,
+        source: impl Into<SharedString>,
+        decorations: Vec<TextDecoration>,
+        cx: &mut Context<Self>,
+    ) {
+        let source = source.into();
+        let previous_inlay_ids = self
+            .text_decorations
+            .remove(&source)
+            .map(|previous| previous.inlay_ids)
+            .unwrap_or_default();
+
+        let mut inlay_ids = Vec::new();
+        let mut inlays = Vec::new();
+        for decoration in &decorations {
+            if let TextDecorationKind::InlineHint(text) = &decoration.kind {
+                let inlay = Inlay::decoration(
+                    post_inc(&mut self.next_inlay_id),
+                    decoration.range.end,
+                    format!(" {text}"),
+                );
+                inlay_ids.push(inlay.id);
+                inlays.push(inlay);
+            }
+        }
+        if !previous_inlay_ids.is_empty() || !inlays.is_empty() {
+            self.splice_inlays(&previous_inlay_ids, inlays, cx);
+        }
+
+        if !decorations.is_empty() {
+            self.text_decorations.insert(
+                source,
+                TextDecorations {
+                    decorations,
+                    inlay_ids,
+                },
+            );
+        }
+        cx.notify();
+    }
+
+    /// Removes the decorations from the given source.
+    pub fn clear_text_decorations(&mut self, source: &str, cx: &mut Context<Self>) {
+        if self.text_decorations.contains_key(source) {
+            self.set_text_decorations(SharedString::from(source.to_string()), Vec::new(), cx);
+        }
+    }
+
+    fn text_decorations_in_range<'a>(
+        &'a self,
+        search_range: Range<Anchor>,
+        buffer: &'a MultiBufferSnapshot,
+    ) -> impl Iterator<Item = &'a TextDecoration> {
+        self.text_decorations
+            .values()
+            .flat_map(|decorations| decorations.decorations.iter())
+            .filter(move |decoration| {
+                decoration.range.end.cmp(&search_range.start, buffer) != Ordering::Less
+                    && decoration.range.start.cmp(&search_range.end, buffer) != Ordering::Greater
+            })
+    }
+
+    pub(crate) fn text_decoration_backgrounds_in_range(
+        &self,
+        search_range: Range<Anchor>,
+        display_snapshot: &DisplaySnapshot,
+        cx: &App,
+    ) -> Vec<(Range<DisplayPoint>, Hsla)> {
+        self.text_decoration_ranges_in_range(
+            search_range,
+            TextDecorationKind::Background,
+            display_snapshot,
+        )
+        .into_iter()
+        .map(|(range, color)| (range, color.color(cx).opacity(BACKGROUND_OPACITY)))
+        .collect()
+    }
+
+    pub(crate) fn text_decoration_underlines_in_range(
+        &self,
+        search_range: Range<Anchor>,
+        display_snapshot: &DisplaySnapshot,
+        cx: &App,
+    ) -> Vec<(Range<DisplayPoint>, Hsla)> {
+        self.text_decoration_ranges_in_range(
+            search_range,
+            TextDecorationKind::Underline,
+            display_snapshot,
+        )
+        .into_iter()
+        .map(|(range, color)| (range, color.color(cx)))
+        .collect()
+    }
+
+    fn text_decoration_ranges_in_range(
+        &self,
+        search_range: Range<Anchor>,
+        kind: TextDecorationKind,
+        display_snapshot: &DisplaySnapshot,
+    ) -> Vec<(Range<DisplayPoint>, Color)> {
+        let buffer = &display_snapshot.buffer_snapshot;
+        self.text_decorations_in_range(search_range, buffer)
+            .filter(|decoration| decoration.kind == kind)
+            .filter(|decoration| {
+                decoration.range.start.to_offset(buffer) < decoration.range.end.to_offset(buffer)
+            })
+            .map(|decoration| {
+                let start = decoration.range.start.to_display_point(display_snapshot);
+                let end = decoration.range.end.to_display_point(display_snapshot);
+                (start..end, decoration.color)
+            })
+            .collect()
+    }
+
+    /// Returns the gutter icon to display on each row, where the first decoration wins.
+    pub(crate) fn text_decoration_gutter_icons_in_range(
+        &self,
+        search_range: Range<Anchor>,
+        display_snapshot: &DisplaySnapshot,
+    ) -> BTreeMap<DisplayRow, (IconName, Color)> {
+        let buffer = &display_snapshot.buffer_snapshot;
+        let mut icons = BTreeMap::default();
+        for decoration in self.text_decorations_in_range(search_range, buffer) {
+            if let TextDecorationKind::GutterIcon(icon) = decoration.kind {
+                let row = decoration
+                    .range
+                    .start
+                    .to_display_point(display_snapshot)
+                    .row();
+                icons.entry(row).or_insert((icon, decoration.color));
+            }
+        }
+        icons
+    }
+}
@@ -0,0 +1,96 @@
+use editor::Editor;
+use gpui::{
+    div, App, Context, Entity, FocusHandle, Focusable as _, IntoElement, ParentElement, Render,
+    SharedString, Subscription, Window,
+};
+use language::Encoding;
+use ui::{
+    Button, ButtonCommon, Clickable, ContextMenu, FluentBuilder, LabelSize, PopoverMenu, Tooltip,
+};
+use workspace::{item::ItemHandle, StatusItemView, Workspace};
+
+use crate::{ReopenWithEncoding, SaveWithEncoding};
+
+pub struct ActiveBufferEncoding {
+    active_encoding: Option<Encoding>,
+    /// Focused when an action is picked from the menu, so that it applies to the active editor.
+    editor_focus_handle: Option<FocusHandle>,
+    _observe_active_editor: Option<Subscription>,
+}
+
+impl ActiveBufferEncoding {
+    pub fn new(_workspace: &Workspace) -> Self {
+        Self {
+            active_encoding: None,
+            editor_focus_handle: None,
+            _observe_active_editor: None,
+        }
+    }
+
+    fn update_encoding(&mut self, editor: Entity<Editor>, _: &mut Window, cx: &mut Context<Self>) {
+        self.active_encoding = None;
+
+        let editor = editor.read(cx);
+        if let Some((_, buffer, _)) = editor.active_excerpt(cx) {
+            let buffer = buffer.read(cx);
+            if buffer.file().is_some_and(|file| file.as_local().is_some()) {
+                self.active_encoding = Some(buffer.encoding());
+            }
+        }
+
+        cx.notify();
+    }
+}
+
+impl Render for ActiveBufferEncoding {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.editor_focus_handle.clone();
+        div().when_some(self.active_encoding, |el, active_encoding| {
+            el.child(
+                PopoverMenu::new("encoding-menu")
+                    .trigger(
+                        Button::new("change-encoding", active_encoding.name())
+                            .label_size(LabelSize::Small)
+                            .tooltip(Tooltip::text("Select Encoding")),
+                    )
+                    .anchor(gpui::Corner::BottomRight)
+                    .menu(move |window, cx| {
+                        let focus_handle = focus_handle.clone();
+                        Some(ContextMenu::build(window, cx, |mut menu, _, _| {
+                            if let Some(focus_handle) = focus_handle {
+                                menu = menu.context(focus_handle);
+                            }
+                            menu.action("Reopen with Encoding", Box::new(ReopenWithEncoding))
+                                .action("Save with Encoding", Box::new(SaveWithEncoding))
+                        }))
+                    }),
+            )
+        })
+    }
+}
+
+impl StatusItemView for ActiveBufferEncoding {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(editor) = active_pane_item.and_then(|item| item.downcast::<Editor>()) {
+            self.editor_focus_handle = Some(editor.focus_handle(cx));
+            self._observe_active_editor =
+                Some(cx.observe_in(&editor, window, Self::update_encoding));
+            self.update_encoding(editor, window, cx);
+        } else {
+            self.active_encoding = None;
+            self.editor_focus_handle = None;
+            self._observe_active_editor = None;
+        }
+
+        cx.notify();
+    }
+
+    fn segment_name(&self, _: &App) -> Option<SharedString> {
+        Some("encoding".into())
+    }
+}
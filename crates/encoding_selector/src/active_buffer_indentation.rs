@@ -0,0 +1,185 @@
+use editor::{
+    actions::{ConvertIndentationToSpaces, ConvertIndentationToTabs},
+    Editor,
+};
+use gpui::{
+    div, App, Context, Entity, FocusHandle, Focusable as _, IntoElement, ParentElement, Render,
+    SharedString, Subscription, WeakEntity, Window,
+};
+use language::{Buffer, IndentKind, IndentSize};
+use ui::{
+    Button, ButtonCommon, Clickable, ContextMenu, FluentBuilder, IconPosition, LabelSize,
+    PopoverMenu, Tooltip,
+};
+use workspace::{item::ItemHandle, StatusItemView, Workspace};
+
+/// The space indentation widths offered in the menu.
+const SPACE_WIDTHS: [u32; 3] = [2, 4, 8];
+
+pub struct ActiveBufferIndentation {
+    active_buffer: Option<ActiveBuffer>,
+    /// Focused when a conversion is picked from the menu, so that it applies to the active editor.
+    editor_focus_handle: Option<FocusHandle>,
+    _observe_active_editor: Option<Subscription>,
+}
+
+#[derive(Clone)]
+struct ActiveBuffer {
+    buffer: WeakEntity<Buffer>,
+    indent: IndentSize,
+    tab_size: u32,
+    is_overridden: bool,
+    is_detected: bool,
+}
+
+impl ActiveBufferIndentation {
+    pub fn new(_workspace: &Workspace) -> Self {
+        Self {
+            active_buffer: None,
+            editor_focus_handle: None,
+            _observe_active_editor: None,
+        }
+    }
+
+    fn update_indentation(
+        &mut self,
+        editor: Entity<Editor>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.active_buffer = None;
+
+        let editor = editor.read(cx);
+        if let Some((_, buffer, _)) = editor.active_excerpt(cx) {
+            let handle = buffer.downgrade();
+            let buffer = buffer.read(cx);
+            let settings = buffer.settings(cx);
+            let indentation = buffer.indentation();
+            self.active_buffer = Some(ActiveBuffer {
+                buffer: handle,
+                indent: if settings.hard_tabs {
+                    IndentSize::tab()
+                } else {
+                    IndentSize::spaces(settings.tab_size.get())
+                },
+                tab_size: settings.tab_size.get(),
+                is_overridden: indentation.overridden.is_some(),
+                is_detected: indentation.detected.is_some() && settings.detect_indentation,
+            });
+        }
+
+        cx.notify();
+    }
+}
+
+impl Render for ActiveBufferIndentation {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.editor_focus_handle.clone();
+        div().when_some(self.active_buffer.clone(), |el, active_buffer| {
+            let label = match active_buffer.indent.kind {
+                IndentKind::Space => format!("Spaces: {}", active_buffer.indent.len),
+                IndentKind::Tab => format!("Tab Size: {}", active_buffer.tab_size),
+            };
+
+            el.child(
+                PopoverMenu::new("indentation-menu")
+                    .trigger(
+                        Button::new("change-indentation", label)
+                            .label_size(LabelSize::Small)
+                            .tooltip(Tooltip::text("Select Indentation")),
+                    )
+                    .anchor(gpui::Corner::BottomRight)
+                    .menu(move |window, cx| {
+                        let active_buffer = active_buffer.clone();
+                        let focus_handle = focus_handle.clone();
+                        Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                            if let Some(focus_handle) = focus_handle {
+                                menu = menu.context(focus_handle);
+                            }
+                            menu = menu.header("Indent Using");
+                            for width in SPACE_WIDTHS {
+                                menu = menu.toggleable_entry(
+                                    format!("Spaces: {width}"),
+                                    active_buffer.indent == IndentSize::spaces(width),
+                                    IconPosition::Start,
+                                    None,
+                                    override_indentation(
+                                        active_buffer.buffer.clone(),
+                                        Some(IndentSize::spaces(width)),
+                                    ),
+                                );
+                            }
+                            menu = menu.toggleable_entry(
+                                "Tabs",
+                                active_buffer.indent.kind == IndentKind::Tab,
+                                IconPosition::Start,
+                                None,
+                                override_indentation(
+                                    active_buffer.buffer.clone(),
+                                    Some(IndentSize::tab()),
+                                ),
+                            );
+                            if active_buffer.is_overridden {
+                                let label = if active_buffer.is_detected {
+                                    "Use Detected Indentation"
+                                } else {
+                                    "Use Indentation from Settings"
+                                };
+                                menu = menu.separator().entry(
+                                    label,
+                                    None,
+                                    override_indentation(active_buffer.buffer.clone(), None),
+                                );
+                            }
+                            menu.separator()
+                                .action(
+                                    "Convert Indentation to Spaces",
+                                    Box::new(ConvertIndentationToSpaces),
+                                )
+                                .action(
+                                    "Convert Indentation to Tabs",
+                                    Box::new(ConvertIndentationToTabs),
+                                )
+                        }))
+                    }),
+            )
+        })
+    }
+}
+
+fn override_indentation(
+    buffer: WeakEntity<Buffer>,
+    indent: Option<IndentSize>,
+) -> impl Fn(&mut Window, &mut App) + 'static {
+    move |_, cx| {
+        buffer
+            .update(cx, |buffer, cx| buffer.set_indentation_override(indent, cx))
+            .ok();
+    }
+}
+
+impl StatusItemView for ActiveBufferIndentation {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(editor) = active_pane_item.and_then(|item| item.downcast::<Editor>()) {
+            self.editor_focus_handle = Some(editor.focus_handle(cx));
+            self._observe_active_editor =
+                Some(cx.observe_in(&editor, window, Self::update_indentation));
+            self.update_indentation(editor, window, cx);
+        } else {
+            self.active_buffer = None;
+            self.editor_focus_handle = None;
+            self._observe_active_editor = None;
+        }
+
+        cx.notify();
+    }
+
+    fn segment_name(&self, _: &App) -> Option<SharedString> {
+        Some("indentation".into())
+    }
+}
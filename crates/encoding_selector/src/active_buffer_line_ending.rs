@@ -0,0 +1,152 @@
+use editor::Editor;
+use gpui::{
+    div, App, Context, Entity, IntoElement, ParentElement, Render, SharedString, Subscription,
+    WeakEntity, Window,
+};
+use language::{Buffer, LineEnding};
+use ui::{
+    Button, ButtonCommon, Clickable, ContextMenu, FluentBuilder, IconPosition, LabelSize,
+    PopoverMenu, Tooltip,
+};
+use workspace::{item::ItemHandle, StatusItemView, Workspace};
+
+pub struct ActiveBufferLineEnding {
+    active_buffer: Option<ActiveBuffer>,
+    _observe_active_editor: Option<Subscription>,
+}
+
+#[derive(Clone)]
+struct ActiveBuffer {
+    buffer: WeakEntity<Buffer>,
+    line_ending: LineEnding,
+    has_bom: bool,
+}
+
+impl ActiveBufferLineEnding {
+    pub fn new(_workspace: &Workspace) -> Self {
+        Self {
+            active_buffer: None,
+            _observe_active_editor: None,
+        }
+    }
+
+    fn update_line_ending(
+        &mut self,
+        editor: Entity<Editor>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.active_buffer = None;
+
+        let editor = editor.read(cx);
+        if let Some((_, buffer, _)) = editor.active_excerpt(cx) {
+            let handle = buffer.downgrade();
+            let buffer = buffer.read(cx);
+            if buffer.file().is_some_and(|file| file.as_local().is_some()) {
+                self.active_buffer = Some(ActiveBuffer {
+                    buffer: handle,
+                    line_ending: buffer.line_ending(),
+                    has_bom: buffer.has_bom(),
+                });
+            }
+        }
+
+        cx.notify();
+    }
+}
+
+impl Render for ActiveBufferLineEnding {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        div().when_some(self.active_buffer.clone(), |el, active_buffer| {
+            let mut label = match active_buffer.line_ending {
+                LineEnding::Unix => "LF",
+                LineEnding::Windows => "CRLF",
+            }
+            .to_string();
+            if active_buffer.has_bom {
+                label.push_str(" with BOM");
+            }
+
+            el.child(
+                PopoverMenu::new("line-ending-menu")
+                    .trigger(
+                        Button::new("change-line-ending", label)
+                            .label_size(LabelSize::Small)
+                            .tooltip(Tooltip::text("Select Line Ending")),
+                    )
+                    .anchor(gpui::Corner::BottomRight)
+                    .menu(move |window, cx| {
+                        let active_buffer = active_buffer.clone();
+                        Some(ContextMenu::build(window, cx, move |menu, _, _| {
+                            let ActiveBuffer {
+                                buffer,
+                                line_ending,
+                                has_bom,
+                            } = active_buffer;
+                            menu.toggleable_entry(
+                                "LF",
+                                line_ending == LineEnding::Unix,
+                                IconPosition::Start,
+                                None,
+                                set_line_ending(buffer.clone(), LineEnding::Unix),
+                            )
+                            .toggleable_entry(
+                                "CRLF",
+                                line_ending == LineEnding::Windows,
+                                IconPosition::Start,
+                                None,
+                                set_line_ending(buffer.clone(), LineEnding::Windows),
+                            )
+                            .separator()
+                            .toggleable_entry(
+                                "Byte Order Mark",
+                                has_bom,
+                                IconPosition::Start,
+                                None,
+                                move |_, cx| {
+                                    buffer
+                                        .update(cx, |buffer, cx| buffer.set_has_bom(!has_bom, cx))
+                                        .ok();
+                                },
+                            )
+                        }))
+                    }),
+            )
+        })
+    }
+}
+
+fn set_line_ending(
+    buffer: WeakEntity<Buffer>,
+    line_ending: LineEnding,
+) -> impl Fn(&mut Window, &mut App) + 'static {
+    move |_, cx| {
+        buffer
+            .update(cx, |buffer, cx| buffer.set_line_ending(line_ending, cx))
+            .ok();
+    }
+}
+
+impl StatusItemView for ActiveBufferLineEnding {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(editor) = active_pane_item.and_then(|item| item.downcast::<Editor>()) {
+            self._observe_active_editor =
+                Some(cx.observe_in(&editor, window, Self::update_line_ending));
+            self.update_line_ending(editor, window, cx);
+        } else {
+            self.active_buffer = None;
+            self._observe_active_editor = None;
+        }
+
+        cx.notify();
+    }
+
+    fn segment_name(&self, _: &App) -> Option<SharedString> {
+        Some("line_ending".into())
+    }
+}
@@ -129,6 +129,18 @@ pub trait Extension: Send + Sync + 'static {
         package_name: Arc<str>,
         kv_store: Arc<dyn KeyValueStoreDelegate>,
     ) -> Result<()>;
+
+    async fn render_panel(&self, panel_id: Arc<str>) -> Result<Vec<PanelElement>>;
+
+    async fn handle_panel_event(&self, panel_id: Arc<str>, event: PanelEvent) -> Result<()>;
+
+    async fn run_command(&self, command_id: Arc<str>, context: EditorContext) -> Result<()>;
+
+    async fn buffer_decorations(
+        &self,
+        provider_id: Arc<str>,
+        buffer: DecoratedBuffer,
+    ) -> Result<Vec<TextDecoration>>;
 }
 
 pub fn parse_wasm_extension_version(
@@ -5,12 +5,15 @@ use anyhow::{anyhow, bail, Context as _, Result};
 use async_compression::futures::bufread::GzipDecoder;
 use async_tar::Archive;
 use convert_case::{Case, Casing as _};
+use futures::channel::mpsc::UnboundedSender;
 use futures::io::BufReader;
 use futures::AsyncReadExt;
 use http_client::{self, AsyncBody, HttpClient};
 use serde::Deserialize;
 use std::{
-    env, fs, mem,
+    env, fs,
+    io::{BufRead as _, BufReader as StdBufReader},
+    mem,
     path::{Path, PathBuf},
     process::Stdio,
     sync::Arc,
@@ -52,6 +55,16 @@ pub struct ExtensionBuilder {
 
 pub struct CompileExtensionOptions {
     pub release: bool,
+    /// Receives the output of the build as it happens, line by line.
+    pub output: Option<UnboundedSender<String>>,
+}
+
+impl CompileExtensionOptions {
+    fn write_output(&self, line: impl Into<String>) {
+        if let Some(output) = &self.output {
+            output.unbounded_send(line.into()).ok();
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -91,7 +104,11 @@ impl ExtensionBuilder {
 
         if extension_manifest.lib.kind == Some(ExtensionLibraryKind::Rust) {
             log::info!("compiling Rust extension {}", extension_dir.display());
-            self.compile_rust_extension(extension_dir, extension_manifest, options)
+            options.write_output(format!(
+                "Compiling Rust extension {}",
+                extension_dir.display()
+            ));
+            self.compile_rust_extension(extension_dir, extension_manifest, &options)
                 .await
                 .context("failed to compile Rust extension")?;
             log::info!("compiled Rust extension {}", extension_dir.display());
@@ -107,6 +124,7 @@ impl ExtensionBuilder {
                 "compiling grammar {grammar_name} for extension {}",
                 extension_dir.display()
             );
+            options.write_output(format!("Compiling grammar {grammar_name}"));
             self.compile_grammar(extension_dir, grammar_name.as_ref(), grammar_metadata)
                 .await
                 .with_context(|| format!("failed to compile grammar '{grammar_name}'"))?;
@@ -117,6 +135,10 @@ impl ExtensionBuilder {
         }
 
         log::info!("finished compiling extension {}", extension_dir.display());
+        options.write_output(format!(
+            "Finished compiling extension {}",
+            extension_dir.display()
+        ));
         Ok(())
     }
 
@@ -124,7 +146,7 @@ impl ExtensionBuilder {
         &self,
         extension_dir: &Path,
         manifest: &mut ExtensionManifest,
-        options: CompileExtensionOptions,
+        options: &CompileExtensionOptions,
     ) -> Result<(), anyhow::Error> {
         self.install_rust_wasm_target_if_needed()?;
         let adapter_bytes = self.install_wasi_preview1_adapter_if_needed().await?;
@@ -136,7 +158,7 @@ impl ExtensionBuilder {
             "compiling Rust crate for extension {}",
             extension_dir.display()
         );
-        let output = util::command::new_std_command("cargo")
+        let mut cargo = util::command::new_std_command("cargo")
             .args(["build", "--target", RUST_TARGET])
             .args(options.release.then_some("--release"))
             .arg("--target-dir")
@@ -144,13 +166,28 @@ impl ExtensionBuilder {
             // WASI builds do not work with sccache and just stuck, so disable it.
             .env("RUSTC_WRAPPER", "")
             .current_dir(extension_dir)
-            .output()
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
             .context("failed to run `cargo`")?;
-        if !output.status.success() {
-            bail!(
-                "failed to build extension {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
+
+        // Cargo reports its progress and any errors on stderr.
+        let mut stderr = String::new();
+        if let Some(cargo_stderr) = cargo.stderr.take() {
+            for line in StdBufReader::new(cargo_stderr).lines() {
+                let line = line.context("failed to read `cargo` output")?;
+                stderr.push_str(&line);
+                stderr.push('\n');
+                options.write_output(line);
+            }
+        }
+        let status = cargo.wait().context("failed to run `cargo`")?;
+        if !status.success() {
+            // The errors have already been written to the output, if there is one.
+            if options.output.is_some() {
+                bail!("failed to build extension");
+            }
+            bail!("failed to build extension {stderr}");
         }
 
         log::info!(
@@ -8,7 +8,7 @@ use language::{BinaryStatus, LanguageMatcher, LanguageName, LoadedLanguage};
 use lsp::LanguageServerName;
 use parking_lot::RwLock;
 
-use crate::{Extension, SlashCommand};
+use crate::{DecorationProvider, Extension, ExtensionCommand, ExtensionPanel, SlashCommand};
 
 #[derive(Default)]
 struct GlobalExtensionHostProxy(Arc<ExtensionHostProxy>);
@@ -26,9 +26,13 @@ pub struct ExtensionHostProxy {
     language_proxy: RwLock<Option<Arc<dyn ExtensionLanguageProxy>>>,
     language_server_proxy: RwLock<Option<Arc<dyn ExtensionLanguageServerProxy>>>,
     snippet_proxy: RwLock<Option<Arc<dyn ExtensionSnippetProxy>>>,
+    locale_proxy: RwLock<Option<Arc<dyn ExtensionLocaleProxy>>>,
     slash_command_proxy: RwLock<Option<Arc<dyn ExtensionSlashCommandProxy>>>,
     context_server_proxy: RwLock<Option<Arc<dyn ExtensionContextServerProxy>>>,
     indexed_docs_provider_proxy: RwLock<Option<Arc<dyn ExtensionIndexedDocsProviderProxy>>>,
+    panel_proxy: RwLock<Option<Arc<dyn ExtensionPanelProxy>>>,
+    command_proxy: RwLock<Option<Arc<dyn ExtensionCommandProxy>>>,
+    decoration_proxy: RwLock<Option<Arc<dyn ExtensionDecorationProxy>>>,
 }
 
 impl ExtensionHostProxy {
@@ -51,9 +55,13 @@ impl ExtensionHostProxy {
             language_proxy: RwLock::default(),
             language_server_proxy: RwLock::default(),
             snippet_proxy: RwLock::default(),
+            locale_proxy: RwLock::default(),
             slash_command_proxy: RwLock::default(),
             context_server_proxy: RwLock::default(),
             indexed_docs_provider_proxy: RwLock::default(),
+            panel_proxy: RwLock::default(),
+            command_proxy: RwLock::default(),
+            decoration_proxy: RwLock::default(),
         }
     }
 
@@ -77,6 +85,10 @@ impl ExtensionHostProxy {
         self.snippet_proxy.write().replace(Arc::new(proxy));
     }
 
+    pub fn register_locale_proxy(&self, proxy: impl ExtensionLocaleProxy) {
+        self.locale_proxy.write().replace(Arc::new(proxy));
+    }
+
     pub fn register_slash_command_proxy(&self, proxy: impl ExtensionSlashCommandProxy) {
         self.slash_command_proxy.write().replace(Arc::new(proxy));
     }
@@ -93,6 +105,18 @@ impl ExtensionHostProxy {
             .write()
             .replace(Arc::new(proxy));
     }
+
+    pub fn register_panel_proxy(&self, proxy: impl ExtensionPanelProxy) {
+        self.panel_proxy.write().replace(Arc::new(proxy));
+    }
+
+    pub fn register_command_proxy(&self, proxy: impl ExtensionCommandProxy) {
+        self.command_proxy.write().replace(Arc::new(proxy));
+    }
+
+    pub fn register_decoration_proxy(&self, proxy: impl ExtensionDecorationProxy) {
+        self.decoration_proxy.write().replace(Arc::new(proxy));
+    }
 }
 
 pub trait ExtensionThemeProxy: Send + Sync + 'static {
@@ -341,6 +365,20 @@ impl ExtensionSnippetProxy for ExtensionHostProxy {
     }
 }
 
+pub trait ExtensionLocaleProxy: Send + Sync + 'static {
+    fn register_locale(&self, locale: &str, locale_contents: &str) -> Result<()>;
+}
+
+impl ExtensionLocaleProxy for ExtensionHostProxy {
+    fn register_locale(&self, locale: &str, locale_contents: &str) -> Result<()> {
+        let Some(proxy) = self.locale_proxy.read().clone() else {
+            return Ok(());
+        };
+
+        proxy.register_locale(locale, locale_contents)
+    }
+}
+
 pub trait ExtensionSlashCommandProxy: Send + Sync + 'static {
     fn register_slash_command(&self, extension: Arc<dyn Extension>, command: SlashCommand);
 }
@@ -392,3 +430,95 @@ impl ExtensionIndexedDocsProviderProxy for ExtensionHostProxy {
         proxy.register_indexed_docs_provider(extension, provider_id)
     }
 }
+
+pub trait ExtensionPanelProxy: Send + Sync + 'static {
+    fn register_panel(&self, extension: Arc<dyn Extension>, panel: ExtensionPanel, cx: &mut App);
+
+    fn remove_panels(&self, extension_id: &Arc<str>, cx: &mut App);
+}
+
+impl ExtensionPanelProxy for ExtensionHostProxy {
+    fn register_panel(&self, extension: Arc<dyn Extension>, panel: ExtensionPanel, cx: &mut App) {
+        let Some(proxy) = self.panel_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_panel(extension, panel, cx)
+    }
+
+    fn remove_panels(&self, extension_id: &Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.panel_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.remove_panels(extension_id, cx)
+    }
+}
+
+pub trait ExtensionCommandProxy: Send + Sync + 'static {
+    fn register_command(
+        &self,
+        extension: Arc<dyn Extension>,
+        command: ExtensionCommand,
+        cx: &mut App,
+    );
+
+    fn remove_commands(&self, extension_id: &Arc<str>, cx: &mut App);
+}
+
+impl ExtensionCommandProxy for ExtensionHostProxy {
+    fn register_command(
+        &self,
+        extension: Arc<dyn Extension>,
+        command: ExtensionCommand,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.command_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_command(extension, command, cx)
+    }
+
+    fn remove_commands(&self, extension_id: &Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.command_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.remove_commands(extension_id, cx)
+    }
+}
+
+pub trait ExtensionDecorationProxy: Send + Sync + 'static {
+    fn register_decoration_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider: DecorationProvider,
+        cx: &mut App,
+    );
+
+    fn remove_decoration_providers(&self, extension_id: &Arc<str>, cx: &mut App);
+}
+
+impl ExtensionDecorationProxy for ExtensionHostProxy {
+    fn register_decoration_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider: DecorationProvider,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.decoration_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_decoration_provider(extension, provider, cx)
+    }
+
+    fn remove_decoration_providers(&self, extension_id: &Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.decoration_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.remove_decoration_providers(extension_id, cx)
+    }
+}
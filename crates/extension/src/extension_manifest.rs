@@ -84,7 +84,16 @@ pub struct ExtensionManifest {
     #[serde(default)]
     pub indexed_docs_providers: BTreeMap<Arc<str>, IndexedDocsProviderEntry>,
     #[serde(default)]
+    pub panels: BTreeMap<Arc<str>, PanelManifestEntry>,
+    #[serde(default)]
+    pub commands: BTreeMap<Arc<str>, CommandManifestEntry>,
+    #[serde(default)]
+    pub decoration_providers: BTreeMap<Arc<str>, DecorationProviderManifestEntry>,
+    #[serde(default)]
     pub snippets: Option<PathBuf>,
+    /// Locale files that translate UI strings, named after their locales, like `de.json`.
+    #[serde(default)]
+    pub locales: Vec<PathBuf>,
     #[serde(default)]
     pub capabilities: Vec<ExtensionCapability>,
 }
@@ -95,28 +104,10 @@ impl ExtensionManifest {
         desired_command: &str,
         desired_args: &[impl AsRef<str> + std::fmt::Debug],
     ) -> Result<()> {
-        let is_allowed = self.capabilities.iter().any(|capability| match capability {
-            ExtensionCapability::ProcessExec { command, args } if command == desired_command => {
-                for (ix, arg) in args.iter().enumerate() {
-                    if arg == "**" {
-                        return true;
-                    }
-
-                    if ix >= desired_args.len() {
-                        return false;
-                    }
-
-                    if arg != "*" && arg != desired_args[ix].as_ref() {
-                        return false;
-                    }
-                }
-                if args.len() < desired_args.len() {
-                    return false;
-                }
-                true
-            }
-            _ => false,
-        });
+        let is_allowed = self
+            .capabilities
+            .iter()
+            .any(|capability| capability.allows_exec(desired_command, desired_args));
 
         if !is_allowed {
             bail!(
@@ -126,9 +117,27 @@ impl ExtensionManifest {
 
         Ok(())
     }
+
+    pub fn allow_fetch(&self, desired_host: &str) -> Result<()> {
+        let is_allowed = self
+            .capabilities
+            .iter()
+            .any(|capability| capability.allows_fetch(desired_host));
+
+        if !is_allowed {
+            bail!(
+                "capability for network:fetch {desired_host} was not listed in the extension manifest",
+            );
+        }
+
+        Ok(())
+    }
 }
 
 /// A capability for an extension.
+///
+/// Capabilities are declared in the extension manifest, and must be granted by the user
+/// before the extension can make use of them.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(tag = "kind")]
 pub enum ExtensionCapability {
@@ -140,6 +149,102 @@ pub enum ExtensionCapability {
         /// If the last element is `**`, then any trailing arguments are allowed.
         args: Vec<String>,
     },
+    #[serde(rename = "network:fetch")]
+    NetworkFetch {
+        /// The host to fetch from. Use `*.example.com` to allow any subdomain of
+        /// `example.com`, or `*` to allow any host.
+        host: String,
+    },
+    #[serde(rename = "fs:read")]
+    FileSystemRead {
+        /// The directory to read from, which may start with `~` for the home directory.
+        path: String,
+    },
+    #[serde(rename = "fs:write")]
+    FileSystemWrite {
+        /// The directory to read from and write to, which may start with `~` for the home
+        /// directory.
+        path: String,
+    },
+}
+
+impl ExtensionCapability {
+    pub fn allows_exec(
+        &self,
+        desired_command: &str,
+        desired_args: &[impl AsRef<str> + std::fmt::Debug],
+    ) -> bool {
+        let Self::ProcessExec { command, args } = self else {
+            return false;
+        };
+        if command != desired_command {
+            return false;
+        }
+
+        for (ix, arg) in args.iter().enumerate() {
+            if arg == "**" {
+                return true;
+            }
+
+            if ix >= desired_args.len() {
+                return false;
+            }
+
+            if arg != "*" && arg != desired_args[ix].as_ref() {
+                return false;
+            }
+        }
+        args.len() >= desired_args.len()
+    }
+
+    pub fn allows_fetch(&self, desired_host: &str) -> bool {
+        let Self::NetworkFetch { host } = self else {
+            return false;
+        };
+        let desired_host = desired_host.to_ascii_lowercase();
+        let host = host.to_ascii_lowercase();
+        if host == "*" {
+            return true;
+        }
+        match host.strip_prefix("*.") {
+            Some(domain) => desired_host
+                .strip_suffix(domain)
+                .is_some_and(|subdomain| subdomain.ends_with('.') && subdomain.len() > 1),
+            None => desired_host == host,
+        }
+    }
+
+    /// Returns the directory that this capability gives access to, and whether it can be
+    /// written to.
+    pub fn directory(&self, home_dir: &Path) -> Option<(PathBuf, bool)> {
+        let (path, writable) = match self {
+            Self::FileSystemRead { path } => (path, false),
+            Self::FileSystemWrite { path } => (path, true),
+            Self::ProcessExec { .. } | Self::NetworkFetch { .. } => return None,
+        };
+        let path = match path.strip_prefix('~') {
+            Some(relative_path) => home_dir.join(relative_path.trim_start_matches('/')),
+            None => PathBuf::from(path),
+        };
+        path.is_absolute().then_some((path, writable))
+    }
+}
+
+impl fmt::Display for ExtensionCapability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ProcessExec { command, args } => {
+                write!(f, "process:exec {command}")?;
+                for arg in args {
+                    write!(f, " {arg}")?;
+                }
+                Ok(())
+            }
+            Self::NetworkFetch { host } => write!(f, "network:fetch {host}"),
+            Self::FileSystemRead { path } => write!(f, "fs:read {path}"),
+            Self::FileSystemWrite { path } => write!(f, "fs:write {path}"),
+        }
+    }
 }
 
 #[derive(Clone, Default, PartialEq, Eq, Debug, Deserialize, Serialize)]
@@ -206,6 +311,23 @@ pub struct SlashCommandManifestEntry {
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
 pub struct IndexedDocsProviderEntry {}
 
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct PanelManifestEntry {
+    pub title: String,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct CommandManifestEntry {
+    pub title: String,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct DecorationProviderManifestEntry {
+    /// The languages of the buffers to decorate, or all buffers if empty.
+    #[serde(default)]
+    pub languages: Vec<LanguageName>,
+}
+
 impl ExtensionManifest {
     pub async fn load(fs: Arc<dyn Fs>, extension_dir: &Path) -> Result<Self> {
         let extension_name = extension_dir
@@ -272,7 +394,11 @@ fn manifest_from_old_manifest(
         context_servers: BTreeMap::default(),
         slash_commands: BTreeMap::default(),
         indexed_docs_providers: BTreeMap::default(),
+        panels: BTreeMap::default(),
+        commands: BTreeMap::default(),
+        decoration_providers: BTreeMap::default(),
         snippets: None,
+        locales: Vec::new(),
         capabilities: Vec::new(),
     }
 }
@@ -299,7 +425,11 @@ mod tests {
             context_servers: BTreeMap::default(),
             slash_commands: BTreeMap::default(),
             indexed_docs_providers: BTreeMap::default(),
+            panels: BTreeMap::default(),
+            commands: BTreeMap::default(),
+            decoration_providers: BTreeMap::default(),
             snippets: None,
+            locales: vec![],
             capabilities: vec![],
         }
     }
@@ -373,4 +503,63 @@ mod tests {
             .is_ok());
         assert!(manifest.allow_exec("docker", &["ps"]).is_err()); // wrong first arg
     }
+
+    #[test]
+    fn test_allow_fetch() {
+        let manifest = ExtensionManifest {
+            capabilities: vec![
+                ExtensionCapability::NetworkFetch {
+                    host: "api.github.com".to_string(),
+                },
+                ExtensionCapability::NetworkFetch {
+                    host: "*.example.com".to_string(),
+                },
+            ],
+            ..extension_manifest()
+        };
+
+        assert!(manifest.allow_fetch("api.github.com").is_ok());
+        assert!(manifest.allow_fetch("API.GitHub.com").is_ok());
+        assert!(manifest.allow_fetch("github.com").is_err());
+        assert!(manifest.allow_fetch("docs.example.com").is_ok());
+        assert!(manifest.allow_fetch("a.b.example.com").is_ok());
+        assert!(manifest.allow_fetch("example.com").is_err()); // wildcard requires a subdomain
+        assert!(manifest.allow_fetch("notexample.com").is_err());
+
+        let manifest = ExtensionManifest {
+            capabilities: vec![ExtensionCapability::NetworkFetch {
+                host: "*".to_string(),
+            }],
+            ..extension_manifest()
+        };
+        assert!(manifest.allow_fetch("zed.dev").is_ok());
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_capability_directory() {
+        let home_dir = Path::new("/home/user");
+
+        assert_eq!(
+            ExtensionCapability::FileSystemRead {
+                path: "~/.config/tool".to_string()
+            }
+            .directory(home_dir),
+            Some((PathBuf::from("/home/user/.config/tool"), false))
+        );
+        assert_eq!(
+            ExtensionCapability::FileSystemWrite {
+                path: "/tmp/cache".to_string()
+            }
+            .directory(home_dir),
+            Some((PathBuf::from("/tmp/cache"), true))
+        );
+        assert_eq!(
+            ExtensionCapability::FileSystemRead {
+                path: "relative/path".to_string()
+            }
+            .directory(home_dir),
+            None
+        );
+    }
 }
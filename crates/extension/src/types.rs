@@ -1,9 +1,15 @@
+mod command;
+mod decoration;
 mod lsp;
+mod panel;
 mod slash_command;
 
 use std::ops::Range;
 
+pub use command::*;
+pub use decoration::*;
 pub use lsp::*;
+pub use panel::*;
 pub use slash_command::*;
 
 /// A list of environment variables.
@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+/// A command contributed by an extension.
+#[derive(Debug, Clone)]
+pub struct ExtensionCommand {
+    /// The ID of the command, unique within its extension.
+    pub id: String,
+    /// The title of the command, as displayed in the command palette.
+    pub title: String,
+}
+
+/// A position in a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextPosition {
+    /// The zero-based row.
+    pub row: u32,
+    /// The zero-based column, as a byte offset within the row.
+    pub column: u32,
+}
+
+/// A selection in an editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorSelection {
+    /// The selected text, which is empty when only a cursor is placed.
+    pub text: String,
+    /// The start of the selection.
+    pub start: TextPosition,
+    /// The end of the selection.
+    pub end: TextPosition,
+}
+
+/// The state of the active editor when a command was run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditorContext {
+    /// The absolute path of the file open in the active editor, if any.
+    pub file_path: Option<PathBuf>,
+    /// The newest selection in the active editor, if there is an active editor.
+    pub selection: Option<EditorSelection>,
+}
@@ -0,0 +1,73 @@
+use std::path::PathBuf;
+
+use language::LanguageName;
+
+use crate::TextPosition;
+
+/// A decoration provider contributed by an extension.
+#[derive(Debug, Clone)]
+pub struct DecorationProvider {
+    /// The ID of the decoration provider, unique within its extension.
+    pub id: String,
+    /// The languages of the buffers to decorate, or all buffers if empty.
+    pub languages: Vec<LanguageName>,
+}
+
+/// A buffer for which an extension provides decorations.
+#[derive(Debug, Clone)]
+pub struct DecoratedBuffer {
+    /// The absolute path of the file.
+    pub file_path: PathBuf,
+    /// The name of the buffer's language, if known.
+    pub language: Option<LanguageName>,
+    /// The text of the buffer.
+    pub text: String,
+}
+
+/// A decoration displayed over a range of a buffer's text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextDecoration {
+    /// The start of the range.
+    pub start: TextPosition,
+    /// The end of the range.
+    pub end: TextPosition,
+    /// How the range is decorated.
+    pub kind: TextDecorationKind,
+    /// The color of the decoration.
+    pub color: DecorationColor,
+}
+
+/// How a range of text is decorated.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextDecorationKind {
+    /// An icon in the gutter, on the first row of the range.
+    GutterIcon(GutterIcon),
+    /// A wavy underline below the range.
+    Underline,
+    /// A background highlight behind the range.
+    Background,
+    /// A hint displayed inline at the end of the range.
+    InlineHint(String),
+}
+
+/// An icon displayed in the gutter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GutterIcon {
+    Circle,
+    Check,
+    XCircle,
+    Warning,
+    Info,
+}
+
+/// The color of a decoration, which follows the current theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecorationColor {
+    Error,
+    Warning,
+    Info,
+    Hint,
+    Success,
+    Accent,
+    Muted,
+}
@@ -0,0 +1,123 @@
+/// A panel contributed by an extension.
+#[derive(Debug, Clone)]
+pub struct ExtensionPanel {
+    /// The ID of the panel, unique within its extension.
+    pub id: String,
+    /// The title of the panel.
+    pub title: String,
+}
+
+/// A UI element displayed in a panel contributed by an extension.
+///
+/// A panel's UI is a vertical stack of elements.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PanelElement {
+    /// A line of text.
+    Label(String),
+    /// A button.
+    Button(PanelButton),
+    /// A single-line text input.
+    TextInput(PanelTextInput),
+    /// A list of selectable items.
+    List(PanelList),
+    /// A tree of selectable items, which can be expanded and collapsed.
+    Tree(PanelTree),
+    /// A horizontal separator.
+    Separator,
+}
+
+/// A button in a panel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PanelButton {
+    /// The ID of the button, which is passed back in events.
+    pub id: String,
+    /// The label displayed on the button.
+    pub label: String,
+}
+
+/// A single-line text input in a panel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PanelTextInput {
+    /// The ID of the text input, which is passed back in events.
+    pub id: String,
+    /// The text displayed when the input is empty.
+    pub placeholder: String,
+    /// The text in the input.
+    pub value: String,
+}
+
+/// A list of selectable items in a panel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PanelList {
+    /// The ID of the list, which is passed back in events.
+    pub id: String,
+    /// The items in the list.
+    pub items: Vec<PanelListItem>,
+}
+
+/// An item in a list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PanelListItem {
+    /// The ID of the item, which is passed back in events.
+    pub id: String,
+    /// The label of the item.
+    pub label: String,
+    /// Secondary text displayed after the label.
+    pub detail: Option<String>,
+    /// Whether the item is selected.
+    pub selected: bool,
+}
+
+/// A tree of selectable items in a panel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PanelTree {
+    /// The ID of the tree, which is passed back in events.
+    pub id: String,
+    /// The nodes of the tree, in depth-first order.
+    pub nodes: Vec<PanelTreeNode>,
+}
+
+/// A node in a tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PanelTreeNode {
+    /// The ID of the node, which is passed back in events.
+    pub id: String,
+    /// The label of the node.
+    pub label: String,
+    /// The depth of the node, where root nodes have a depth of zero.
+    pub depth: u32,
+    /// Whether the node can be expanded, even if its children aren't known yet.
+    pub expandable: bool,
+    /// Whether the node is expanded.
+    pub expanded: bool,
+    /// Whether the node is selected.
+    pub selected: bool,
+}
+
+/// An event triggered by the user in a panel.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PanelEvent {
+    /// The button with the given ID was clicked.
+    ButtonClicked(String),
+    /// A text input was submitted.
+    TextInputSubmitted {
+        /// The ID of the text input.
+        input_id: String,
+        /// The text in the input.
+        value: String,
+    },
+    /// An item in a list or tree was selected.
+    ItemSelected {
+        /// The ID of the list or tree.
+        element_id: String,
+        /// The ID of the item.
+        item_id: String,
+    },
+    /// A node in a tree was expanded or collapsed.
+    TreeNodeToggled {
+        /// The ID of the tree.
+        element_id: String,
+        /// The ID of the node.
+        item_id: String,
+    },
+}
@@ -16,6 +16,10 @@ pub use serde_json;
 // that we may want to shadow to provide a cleaner Rust API.
 pub use wit::{
     download_file, make_file_executable,
+    zed::extension::decoration::{
+        DecoratedBuffer, DecorationColor, GutterIcon, TextDecoration, TextDecorationKind,
+    },
+    zed::extension::editor::{EditorContext, EditorSelection, TextPosition},
     zed::extension::github::{
         github_release_by_tag_name, latest_github_release, GithubRelease, GithubReleaseAsset,
         GithubReleaseOptions,
@@ -24,6 +28,10 @@ pub use wit::{
         node_binary_path, npm_install_package, npm_package_installed_version,
         npm_package_latest_version,
     },
+    zed::extension::panel::{
+        PanelButton, PanelElement, PanelEvent, PanelItemEvent, PanelList, PanelListItem,
+        PanelTextInput, PanelTextInputSubmission, PanelTree, PanelTreeNode,
+    },
     zed::extension::platform::{current_platform, Architecture, Os},
     zed::extension::slash_command::{
         SlashCommand, SlashCommandArgumentCompletion, SlashCommandOutput, SlashCommandOutputSection,
@@ -177,6 +185,33 @@ pub trait Extension: Send + Sync {
     ) -> Result<(), String> {
         Err("`index_docs` not implemented".to_string())
     }
+
+    /// Returns the UI elements to display in the specified panel.
+    fn render_panel(&mut self, _panel_id: &str) -> Result<Vec<PanelElement>, String> {
+        Err("`render_panel` not implemented".to_string())
+    }
+
+    /// Handles an event triggered by the user in the specified panel.
+    ///
+    /// The panel is rendered again after the event has been handled.
+    fn handle_panel_event(&mut self, _panel_id: &str, _event: PanelEvent) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Runs the specified command, which was invoked from the command palette or a key binding.
+    fn run_command(&mut self, _command_id: &str, _context: EditorContext) -> Result<(), String> {
+        Err("`run_command` not implemented".to_string())
+    }
+
+    /// Returns the decorations to display in the given buffer, on behalf of the specified
+    /// decoration provider.
+    fn buffer_decorations(
+        &mut self,
+        _provider_id: &str,
+        _buffer: DecoratedBuffer,
+    ) -> Result<Vec<TextDecoration>, String> {
+        Ok(Vec::new())
+    }
 }
 
 /// Registers the provided type as a Zed extension.
@@ -353,6 +388,25 @@ impl wit::Guest for Component {
     ) -> Result<(), String> {
         extension().index_docs(provider, package, database)
     }
+
+    fn render_panel(panel_id: String) -> Result<Vec<PanelElement>, String> {
+        extension().render_panel(&panel_id)
+    }
+
+    fn handle_panel_event(panel_id: String, event: PanelEvent) -> Result<(), String> {
+        extension().handle_panel_event(&panel_id, event)
+    }
+
+    fn run_command(command_id: String, context: EditorContext) -> Result<(), String> {
+        extension().run_command(&command_id, context)
+    }
+
+    fn buffer_decorations(
+        provider_id: String,
+        buffer: DecoratedBuffer,
+    ) -> Result<Vec<TextDecoration>, String> {
+        extension().buffer_decorations(&provider_id, buffer)
+    }
 }
 
 /// The ID of a language server.
@@ -70,7 +70,10 @@ async fn main() -> Result<()> {
         .compile_extension(
             &extension_path,
             &mut manifest,
-            CompileExtensionOptions { release: true },
+            CompileExtensionOptions {
+                release: true,
+                output: None,
+            },
         )
         .await
         .context("failed to compile extension")?;
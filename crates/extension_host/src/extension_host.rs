@@ -1,3 +1,4 @@
+pub mod extension_permissions;
 pub mod extension_settings;
 pub mod headless_host;
 pub mod wasm_host;
@@ -14,9 +15,11 @@ use collections::{btree_map, BTreeMap, BTreeSet, HashMap, HashSet};
 use extension::extension_builder::{CompileExtensionOptions, ExtensionBuilder};
 pub use extension::ExtensionManifest;
 use extension::{
-    ExtensionContextServerProxy, ExtensionEvents, ExtensionGrammarProxy, ExtensionHostProxy,
+    ExtensionCapability, ExtensionCommandProxy, ExtensionContextServerProxy,
+    ExtensionDecorationProxy, ExtensionEvents, ExtensionGrammarProxy, ExtensionHostProxy,
     ExtensionIndexedDocsProviderProxy, ExtensionLanguageProxy, ExtensionLanguageServerProxy,
-    ExtensionSlashCommandProxy, ExtensionSnippetProxy, ExtensionThemeProxy,
+    ExtensionLocaleProxy, ExtensionPanelProxy, ExtensionSlashCommandProxy, ExtensionSnippetProxy,
+    ExtensionThemeProxy,
 };
 use fs::{Fs, RemoveOptions};
 use futures::{
@@ -33,7 +36,7 @@ use gpui::{
 };
 use http_client::{AsyncBody, HttpClient, HttpClientWithUrl};
 use language::{
-    LanguageConfig, LanguageMatcher, LanguageName, LanguageQueries, LoadedLanguage, Rope,
+    Buffer, LanguageConfig, LanguageMatcher, LanguageName, LanguageQueries, LoadedLanguage, Rope,
     QUERY_FILENAME_PREFIXES,
 };
 use node_runtime::NodeRuntime;
@@ -61,6 +64,7 @@ use wasm_host::{
 pub use extension::{
     ExtensionLibraryKind, GrammarManifestEntry, OldExtensionManifest, SchemaVersion,
 };
+pub use extension_permissions::ExtensionPermissions;
 pub use extension_settings::ExtensionSettings;
 
 pub const RELOAD_DEBOUNCE_DURATION: Duration = Duration::from_millis(200);
@@ -110,12 +114,18 @@ pub struct ExtensionStore {
     pub installed_dir: PathBuf,
     pub outstanding_operations: BTreeMap<Arc<str>, ExtensionOperation>,
     pub index_path: PathBuf,
+    pub permissions: ExtensionPermissions,
+    pub permissions_path: PathBuf,
     pub modified_extensions: HashSet<Arc<str>>,
     pub wasm_host: Arc<WasmHost>,
     pub wasm_extensions: Vec<(Arc<ExtensionManifest>, WasmExtension)>,
     pub tasks: Vec<Task<()>>,
     pub ssh_clients: HashMap<String, WeakEntity<SshRemoteClient>>,
     pub ssh_registered_tx: UnboundedSender<()>,
+    /// The dev extensions that are rebuilt whenever their sources change.
+    pub dev_extension_watchers: HashMap<Arc<str>, Task<()>>,
+    /// The output of the most recent build of each dev extension.
+    pub dev_extension_build_logs: HashMap<Arc<str>, Entity<Buffer>>,
 }
 
 #[derive(Clone, Copy)]
@@ -131,6 +141,7 @@ pub enum Event {
     StartedReloading,
     ExtensionInstalled(Arc<str>),
     ExtensionFailedToLoad(Arc<str>),
+    DevExtensionBuildFailed(Arc<str>),
 }
 
 impl EventEmitter<Event> for ExtensionStore {}
@@ -233,6 +244,7 @@ impl ExtensionStore {
         let build_dir = build_dir.unwrap_or_else(|| extensions_dir.join("build"));
         let installed_dir = extensions_dir.join("installed");
         let index_path = extensions_dir.join("index.json");
+        let permissions_path = extensions_dir.join("permissions.json");
 
         let (reload_tx, mut reload_rx) = unbounded();
         let (connection_registered_tx, mut connection_registered_rx) = unbounded();
@@ -241,6 +253,8 @@ impl ExtensionStore {
             extension_index: Default::default(),
             installed_dir,
             index_path,
+            permissions: Default::default(),
+            permissions_path,
             builder: Arc::new(ExtensionBuilder::new(builder_client, build_dir)),
             outstanding_operations: Default::default(),
             modified_extensions: Default::default(),
@@ -262,17 +276,20 @@ impl ExtensionStore {
 
             ssh_clients: HashMap::default(),
             ssh_registered_tx: connection_registered_tx,
+            dev_extension_watchers: HashMap::default(),
+            dev_extension_build_logs: HashMap::default(),
         };
 
         // The extensions store maintains an index file, which contains a complete
         // list of the installed extensions and the resources that they provide.
         // This index is loaded synchronously on startup.
-        let (index_content, index_metadata, extensions_metadata) =
+        let (index_content, index_metadata, extensions_metadata, permissions_content) =
             cx.background_executor().block(async {
                 futures::join!(
                     this.fs.load(&this.index_path),
                     this.fs.metadata(&this.index_path),
                     this.fs.metadata(&this.installed_dir),
+                    this.fs.load(&this.permissions_path),
                 )
             });
 
@@ -297,6 +314,23 @@ impl ExtensionStore {
             }
         }
 
+        // The granted capabilities are also loaded synchronously, as they are needed to load
+        // the extensions. Extensions that were installed before capabilities had to be
+        // granted keep the capabilities they declare.
+        if let Ok(permissions_content) = permissions_content {
+            this.permissions = serde_json::from_str(&permissions_content)
+                .log_err()
+                .unwrap_or_default();
+        } else {
+            for (extension_id, entry) in &extension_index.extensions {
+                if !entry.dev {
+                    this.permissions
+                        .grant(extension_id, entry.manifest.capabilities.iter().cloned());
+                }
+            }
+            this.save_permissions(cx);
+        }
+
         // Immediately load all of the extensions in the initial manifest. If the
         // index needs to be rebuild, then enqueue
         let load_initial_extensions = this.extensions_updated(extension_index, cx);
@@ -415,6 +449,88 @@ impl ExtensionStore {
         self.installed_dir.clone()
     }
 
+    /// Returns the capabilities declared by the installed extension that have been granted
+    /// to it.
+    pub fn granted_capabilities(&self, extension_id: &str) -> Vec<ExtensionCapability> {
+        self.extension_index
+            .extensions
+            .get(extension_id)
+            .map(|entry| self.granted_capabilities_for_entry(entry))
+            .unwrap_or_default()
+    }
+
+    /// Returns the capabilities declared by the installed extension that are awaiting the
+    /// user's approval.
+    pub fn pending_capabilities(&self, extension_id: &str) -> Vec<ExtensionCapability> {
+        self.extension_index
+            .extensions
+            .get(extension_id)
+            .filter(|entry| !entry.dev)
+            .map(|entry| self.permissions.pending_capabilities(&entry.manifest))
+            .unwrap_or_default()
+    }
+
+    fn granted_capabilities_for_entry(
+        &self,
+        entry: &ExtensionIndexEntry,
+    ) -> Vec<ExtensionCapability> {
+        if entry.dev {
+            entry.manifest.capabilities.clone()
+        } else {
+            self.permissions.granted_capabilities(&entry.manifest)
+        }
+    }
+
+    /// Grants the given capabilities to the extension, and reloads it so that they take
+    /// effect.
+    pub fn grant_capabilities(
+        &mut self,
+        extension_id: Arc<str>,
+        capabilities: Vec<ExtensionCapability>,
+        cx: &mut Context<Self>,
+    ) {
+        self.permissions.grant(&extension_id, capabilities);
+        self.permissions_changed(extension_id, cx);
+    }
+
+    /// Revokes the given capability from the extension, and reloads it so that it can no
+    /// longer make use of it.
+    pub fn revoke_capability(
+        &mut self,
+        extension_id: Arc<str>,
+        capability: &ExtensionCapability,
+        cx: &mut Context<Self>,
+    ) {
+        self.permissions.revoke(&extension_id, capability);
+        self.permissions_changed(extension_id, cx);
+    }
+
+    fn permissions_changed(&mut self, extension_id: Arc<str>, cx: &mut Context<Self>) {
+        self.save_permissions(cx);
+        drop(self.reload(Some(extension_id), cx));
+        cx.notify();
+    }
+
+    fn save_permissions(&self, cx: &mut Context<Self>) {
+        let fs = self.fs.clone();
+        let permissions_path = self.permissions_path.clone();
+        let Some(permissions_json) = serde_json::to_string_pretty(&self.permissions).log_err()
+        else {
+            return;
+        };
+        cx.background_spawn(async move {
+            fs.save(
+                &permissions_path,
+                &permissions_json.as_str().into(),
+                Default::default(),
+            )
+            .await
+            .context("failed to save extension permissions")
+            .log_err();
+        })
+        .detach();
+    }
+
     pub fn outstanding_operations(&self) -> &BTreeMap<Arc<str>, ExtensionOperation> {
         &self.outstanding_operations
     }
@@ -819,6 +935,7 @@ impl ExtensionStore {
     }
 
     pub fn uninstall_extension(&mut self, extension_id: Arc<str>, cx: &mut Context<Self>) {
+        self.dev_extension_watchers.remove(&extension_id);
         let extension_dir = self.installed_dir.join(extension_id.as_ref());
         let work_dir = self.wasm_host.work_dir.join(extension_id.as_ref());
         let fs = self.fs.clone();
@@ -848,7 +965,12 @@ impl ExtensionStore {
 
             // todo(windows)
             // Stop the server here.
-            this.update(cx, |this, cx| this.reload(None, cx))?.await;
+            this.update(cx, |this, cx| {
+                this.permissions.remove(&extension_id);
+                this.save_permissions(cx);
+                this.reload(None, cx)
+            })?
+            .await;
 
             fs.remove_dir(
                 &work_dir,
@@ -864,11 +986,12 @@ impl ExtensionStore {
         .detach_and_log_err(cx)
     }
 
+    /// Builds and installs the extension in the given directory, returning its ID.
     pub fn install_dev_extension(
         &mut self,
         extension_source_path: PathBuf,
         cx: &mut Context<Self>,
-    ) -> Task<Result<()>> {
+    ) -> Task<Result<Arc<str>>> {
         let extensions_dir = self.extensions_dir();
         let fs = self.fs.clone();
         let builder = self.builder.clone();
@@ -897,22 +1020,35 @@ impl ExtensionStore {
                 }
             });
 
-            cx.background_spawn({
-                let extension_source_path = extension_source_path.clone();
-                async move {
-                    builder
-                        .compile_extension(
-                            &extension_source_path,
-                            &mut extension_manifest,
-                            CompileExtensionOptions { release: false },
-                        )
-                        .await
-                }
-            })
-            .await
-            .inspect_err(|error| {
-                util::log_err(error);
+            let output = this.update(cx, |this, cx| {
+                this.start_dev_extension_build_log(extension_id.clone(), cx)
             })?;
+            let compile_result = cx
+                .background_spawn({
+                    let extension_source_path = extension_source_path.clone();
+                    let output = output.clone();
+                    async move {
+                        builder
+                            .compile_extension(
+                                &extension_source_path,
+                                &mut extension_manifest,
+                                CompileExtensionOptions {
+                                    release: false,
+                                    output: Some(output),
+                                },
+                            )
+                            .await
+                    }
+                })
+                .await;
+            if let Err(error) = &compile_result {
+                util::log_err(error);
+                // Installation errors are reported by the caller, so we only log them here.
+                output
+                    .unbounded_send(format!("Build failed: {error:#}"))
+                    .ok();
+            }
+            compile_result?;
 
             let output_path = &extensions_dir.join(extension_id.as_ref());
             if let Some(metadata) = fs.metadata(output_path).await? {
@@ -934,30 +1070,46 @@ impl ExtensionStore {
                 .await?;
 
             this.update(cx, |this, cx| this.reload(None, cx))?.await;
-            Ok(())
+            Ok(extension_id)
         })
     }
 
     pub fn rebuild_dev_extension(&mut self, extension_id: Arc<str>, cx: &mut Context<Self>) {
+        self.compile_dev_extension(extension_id, cx)
+            .detach_and_log_err(cx)
+    }
+
+    fn compile_dev_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
         let path = self.installed_dir.join(extension_id.as_ref());
         let builder = self.builder.clone();
         let fs = self.fs.clone();
 
         match self.outstanding_operations.entry(extension_id.clone()) {
-            btree_map::Entry::Occupied(_) => return,
+            btree_map::Entry::Occupied(_) => return Task::ready(Ok(())),
             btree_map::Entry::Vacant(e) => e.insert(ExtensionOperation::Upgrade),
         };
 
         cx.notify();
-        let compile = cx.background_spawn(async move {
-            let mut manifest = ExtensionManifest::load(fs, &path).await?;
-            builder
-                .compile_extension(
-                    &path,
-                    &mut manifest,
-                    CompileExtensionOptions { release: true },
-                )
-                .await
+        let output = self.start_dev_extension_build_log(extension_id.clone(), cx);
+        let compile = cx.background_spawn({
+            let output = output.clone();
+            async move {
+                let mut manifest = ExtensionManifest::load(fs, &path).await?;
+                builder
+                    .compile_extension(
+                        &path,
+                        &mut manifest,
+                        CompileExtensionOptions {
+                            release: true,
+                            output: Some(output),
+                        },
+                    )
+                    .await
+            }
         });
 
         cx.spawn(async move |this, cx| {
@@ -965,6 +1117,12 @@ impl ExtensionStore {
 
             this.update(cx, |this, cx| {
                 this.outstanding_operations.remove(&extension_id);
+                if let Err(error) = &result {
+                    output
+                        .unbounded_send(format!("Build failed: {error:#}"))
+                        .ok();
+                    cx.emit(Event::DevExtensionBuildFailed(extension_id.clone()));
+                }
                 cx.notify();
             })?;
 
@@ -975,7 +1133,87 @@ impl ExtensionStore {
 
             result
         })
-        .detach_and_log_err(cx)
+    }
+
+    /// Clears the build log of the dev extension, returning a sender for the output of its
+    /// next build.
+    fn start_dev_extension_build_log(
+        &mut self,
+        extension_id: Arc<str>,
+        cx: &mut Context<Self>,
+    ) -> UnboundedSender<String> {
+        let buffer = self
+            .dev_extension_build_logs
+            .entry(extension_id)
+            .or_insert_with(|| cx.new(|cx| Buffer::local("", cx)))
+            .clone();
+        buffer.update(cx, |buffer, cx| buffer.set_text("", cx));
+
+        let (output_tx, mut output_rx) = unbounded::<String>();
+        cx.spawn(async move |_, cx| {
+            while let Some(line) = output_rx.next().await {
+                buffer
+                    .update(cx, |buffer, cx| {
+                        let end = buffer.len();
+                        buffer.edit([(end..end, format!("{line}\n"))], None, cx);
+                    })
+                    .ok();
+            }
+        })
+        .detach();
+        output_tx
+    }
+
+    pub fn dev_extension_build_log(&self, extension_id: &str) -> Option<Entity<Buffer>> {
+        self.dev_extension_build_logs.get(extension_id).cloned()
+    }
+
+    pub fn is_watching_dev_extension(&self, extension_id: &str) -> bool {
+        self.dev_extension_watchers.contains_key(extension_id)
+    }
+
+    /// Rebuilds and reloads the dev extension whenever its sources change, until it is
+    /// uninstalled or no longer watched.
+    pub fn watch_dev_extension(&mut self, extension_id: Arc<str>, cx: &mut Context<Self>) {
+        let path = self.installed_dir.join(extension_id.as_ref());
+        let fs = self.fs.clone();
+        let watcher = cx.spawn({
+            let extension_id = extension_id.clone();
+            async move |this, cx| {
+                // Dev extensions are symlinked to their sources, which are what we watch.
+                let Some(source_dir) = fs.canonicalize(&path).await.log_err() else {
+                    return;
+                };
+                let (mut events, _watcher) = fs.watch(&source_dir, FS_WATCH_LATENCY).await;
+                while let Some(events) = events.next().await {
+                    let sources_changed = events.iter().any(|event| {
+                        event
+                            .path
+                            .strip_prefix(&source_dir)
+                            .is_ok_and(is_dev_extension_source)
+                    });
+                    if !sources_changed {
+                        continue;
+                    }
+
+                    let Ok(rebuild) = this.update(cx, |this, cx| {
+                        this.compile_dev_extension(extension_id.clone(), cx)
+                    }) else {
+                        break;
+                    };
+                    // Build errors are reported in the extension's build log.
+                    rebuild.await.ok();
+                }
+            }
+        });
+        self.dev_extension_watchers.insert(extension_id, watcher);
+        cx.notify();
+    }
+
+    pub fn unwatch_dev_extension(&mut self, extension_id: &str, cx: &mut Context<Self>) {
+        if self.dev_extension_watchers.remove(extension_id).is_some() {
+            cx.notify();
+        }
     }
 
     /// Updates the set of installed extensions.
@@ -1101,6 +1339,34 @@ impl ExtensionStore {
                         .remove_language_server(&language, language_server_name);
                 }
             }
+            // When an extension is reloaded without changing its panels, commands, or
+            // decoration providers, they are registered again in place once it has loaded,
+            // so that their state is preserved, like which panel tab is active.
+            let new_manifest = new_index
+                .extensions
+                .get(extension_id)
+                .map(|entry| &entry.manifest);
+            if !extension.manifest.panels.is_empty()
+                && new_manifest.map_or(true, |manifest| {
+                    manifest.panels != extension.manifest.panels
+                })
+            {
+                self.proxy.remove_panels(extension_id, cx);
+            }
+            if !extension.manifest.commands.is_empty()
+                && new_manifest.map_or(true, |manifest| {
+                    manifest.commands != extension.manifest.commands
+                })
+            {
+                self.proxy.remove_commands(extension_id, cx);
+            }
+            if !extension.manifest.decoration_providers.is_empty()
+                && new_manifest.map_or(true, |manifest| {
+                    manifest.decoration_providers != extension.manifest.decoration_providers
+                })
+            {
+                self.proxy.remove_decoration_providers(extension_id, cx);
+            }
         }
 
         self.wasm_extensions
@@ -1119,6 +1385,7 @@ impl ExtensionStore {
         let mut themes_to_add = Vec::new();
         let mut icon_themes_to_add = Vec::new();
         let mut snippets_to_add = Vec::new();
+        let mut locales_to_add = Vec::new();
         for extension_id in &extensions_to_load {
             let Some(extension) = new_index.extensions.get(extension_id) else {
                 continue;
@@ -1152,6 +1419,11 @@ impl ExtensionStore {
                 path.extend([Path::new(extension_id.as_ref()), snippets_path.as_path()]);
                 path
             }));
+            locales_to_add.extend(extension.manifest.locales.iter().map(|locale_path| {
+                let mut path = self.installed_dir.clone();
+                path.extend([Path::new(extension_id.as_ref()), locale_path.as_path()]);
+                path
+            }));
         }
 
         self.proxy.register_grammars(grammars_to_add);
@@ -1196,7 +1468,11 @@ impl ExtensionStore {
         let proxy = self.proxy.clone();
         let extension_entries = extensions_to_load
             .iter()
-            .filter_map(|name| new_index.extensions.get(name).cloned())
+            .filter_map(|name| {
+                let entry = new_index.extensions.get(name)?;
+                let granted_capabilities = self.granted_capabilities_for_entry(entry);
+                Some((entry.clone(), granted_capabilities))
+            })
             .collect::<Vec<_>>();
 
         self.extension_index = new_index;
@@ -1228,12 +1504,22 @@ impl ExtensionStore {
                                 .log_err();
                         }
                     }
+
+                    for locale_path in &locales_to_add {
+                        let Some(locale) = locale_path.file_stem().and_then(|stem| stem.to_str())
+                        else {
+                            continue;
+                        };
+                        if let Some(locale_contents) = fs.load(locale_path).await.log_err() {
+                            proxy.register_locale(locale, &locale_contents).log_err();
+                        }
+                    }
                 }
             })
             .await;
 
             let mut wasm_extensions = Vec::new();
-            for extension in extension_entries {
+            for (extension, granted_capabilities) in extension_entries {
                 if extension.manifest.lib.kind.is_none() {
                     continue;
                 };
@@ -1242,6 +1528,7 @@ impl ExtensionStore {
                 let wasm_extension = WasmExtension::load(
                     extension_path,
                     &extension.manifest,
+                    granted_capabilities,
                     wasm_host.clone(),
                     &cx,
                 )
@@ -1250,8 +1537,14 @@ impl ExtensionStore {
                 if let Some(wasm_extension) = wasm_extension.log_err() {
                     wasm_extensions.push((extension.manifest.clone(), wasm_extension));
                 } else {
-                    this.update(cx, |_, cx| {
-                        cx.emit(Event::ExtensionFailedToLoad(extension.manifest.id.clone()))
+                    this.update(cx, |this, cx| {
+                        // Remove anything that was kept registered while the extension
+                        // reloaded, as it won't be registered again.
+                        let extension_id = &extension.manifest.id;
+                        this.proxy.remove_panels(extension_id, cx);
+                        this.proxy.remove_commands(extension_id, cx);
+                        this.proxy.remove_decoration_providers(extension_id, cx);
+                        cx.emit(Event::ExtensionFailedToLoad(extension_id.clone()))
                     })
                     .ok();
                 }
@@ -1297,6 +1590,39 @@ impl ExtensionStore {
                         this.proxy
                             .register_indexed_docs_provider(extension.clone(), provider_id.clone());
                     }
+
+                    for (panel_id, panel) in &manifest.panels {
+                        this.proxy.register_panel(
+                            extension.clone(),
+                            extension::ExtensionPanel {
+                                id: panel_id.to_string(),
+                                title: panel.title.clone(),
+                            },
+                            cx,
+                        );
+                    }
+
+                    for (command_id, command) in &manifest.commands {
+                        this.proxy.register_command(
+                            extension.clone(),
+                            extension::ExtensionCommand {
+                                id: command_id.to_string(),
+                                title: command.title.clone(),
+                            },
+                            cx,
+                        );
+                    }
+
+                    for (provider_id, provider) in &manifest.decoration_providers {
+                        this.proxy.register_decoration_provider(
+                            extension.clone(),
+                            extension::DecorationProvider {
+                                id: provider_id.to_string(),
+                                languages: provider.languages.clone(),
+                            },
+                            cx,
+                        );
+                    }
                 }
 
                 this.wasm_extensions.extend(wasm_extensions);
@@ -1655,6 +1981,18 @@ impl ExtensionStore {
     }
 }
 
+/// Returns whether a change to the given path, relative to the directory of a dev extension,
+/// requires the extension to be rebuilt, as opposed to being one of its build outputs.
+fn is_dev_extension_source(path: &Path) -> bool {
+    let Some(path::Component::Normal(first_component)) = path.components().next() else {
+        return false;
+    };
+    !matches!(
+        first_component.to_str(),
+        Some("target" | "grammars" | ".git" | "extension.wasm")
+    )
+}
+
 fn load_plugin_queries(root_path: &Path) -> LanguageQueries {
     let mut result = LanguageQueries::default();
     if let Some(entries) = std::fs::read_dir(root_path).log_err() {
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use collections::BTreeMap;
+use extension::{ExtensionCapability, ExtensionManifest};
+use serde::{Deserialize, Serialize};
+
+/// The capabilities that the user has granted to each installed extension.
+///
+/// An extension can only make use of the capabilities that it declares in its manifest and
+/// that have been granted to it. Dev extensions are implicitly granted every capability they
+/// declare.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ExtensionPermissions {
+    #[serde(default)]
+    pub granted: BTreeMap<Arc<str>, Vec<ExtensionCapability>>,
+}
+
+impl ExtensionPermissions {
+    /// Returns the capabilities declared by the extension that have been granted to it.
+    pub fn granted_capabilities(&self, manifest: &ExtensionManifest) -> Vec<ExtensionCapability> {
+        let granted = self.granted.get(&manifest.id);
+        manifest
+            .capabilities
+            .iter()
+            .filter(|capability| granted.is_some_and(|granted| granted.contains(capability)))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the capabilities declared by the extension that have not been granted to it,
+    /// either because it was never approved, or because it was revoked.
+    pub fn pending_capabilities(&self, manifest: &ExtensionManifest) -> Vec<ExtensionCapability> {
+        let granted = self.granted.get(&manifest.id);
+        manifest
+            .capabilities
+            .iter()
+            .filter(|capability| granted.map_or(true, |granted| !granted.contains(capability)))
+            .cloned()
+            .collect()
+    }
+
+    pub fn grant(
+        &mut self,
+        extension_id: &Arc<str>,
+        capabilities: impl IntoIterator<Item = ExtensionCapability>,
+    ) {
+        let granted = self.granted.entry(extension_id.clone()).or_default();
+        for capability in capabilities {
+            if !granted.contains(&capability) {
+                granted.push(capability);
+            }
+        }
+    }
+
+    pub fn revoke(&mut self, extension_id: &Arc<str>, capability: &ExtensionCapability) {
+        if let Some(granted) = self.granted.get_mut(extension_id) {
+            granted.retain(|granted| granted != capability);
+            if granted.is_empty() {
+                self.granted.remove(extension_id);
+            }
+        }
+    }
+
+    pub fn remove(&mut self, extension_id: &Arc<str>) {
+        self.granted.remove(extension_id);
+    }
+}
@@ -162,7 +162,11 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         context_servers: BTreeMap::default(),
                         slash_commands: BTreeMap::default(),
                         indexed_docs_providers: BTreeMap::default(),
+                        panels: BTreeMap::default(),
+                        commands: BTreeMap::default(),
+                        decoration_providers: BTreeMap::default(),
                         snippets: None,
+                        locales: Vec::new(),
                         capabilities: Vec::new(),
                     }),
                     dev: false,
@@ -191,7 +195,11 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         context_servers: BTreeMap::default(),
                         slash_commands: BTreeMap::default(),
                         indexed_docs_providers: BTreeMap::default(),
+                        panels: BTreeMap::default(),
+                        commands: BTreeMap::default(),
+                        decoration_providers: BTreeMap::default(),
                         snippets: None,
+                        locales: Vec::new(),
                         capabilities: Vec::new(),
                     }),
                     dev: false,
@@ -357,7 +365,11 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                 context_servers: BTreeMap::default(),
                 slash_commands: BTreeMap::default(),
                 indexed_docs_providers: BTreeMap::default(),
+                panels: BTreeMap::default(),
+                commands: BTreeMap::default(),
+                decoration_providers: BTreeMap::default(),
                 snippets: None,
+                locales: Vec::new(),
                 capabilities: Vec::new(),
             }),
             dev: false,
@@ -169,8 +169,18 @@ impl HeadlessExtensionStore {
             return Ok(());
         }
 
-        let wasm_extension: Arc<dyn Extension> =
-            Arc::new(WasmExtension::load(extension_dir, &manifest, wasm_host.clone(), &cx).await?);
+        // The capabilities of remote extensions are approved on the client that installs them.
+        let granted_capabilities = manifest.capabilities.clone();
+        let wasm_extension: Arc<dyn Extension> = Arc::new(
+            WasmExtension::load(
+                extension_dir,
+                &manifest,
+                granted_capabilities,
+                wasm_host.clone(),
+                &cx,
+            )
+            .await?,
+        );
 
         for (language_server_id, language_server_config) in &manifest.language_servers {
             for language in language_server_config.languages() {
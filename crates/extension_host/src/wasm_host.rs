@@ -4,7 +4,8 @@ use crate::ExtensionManifest;
 use anyhow::{anyhow, bail, Context as _, Result};
 use async_trait::async_trait;
 use extension::{
-    CodeLabel, Command, Completion, ExtensionHostProxy, KeyValueStoreDelegate, ProjectDelegate,
+    CodeLabel, Command, Completion, DecoratedBuffer, EditorContext, ExtensionCapability,
+    ExtensionHostProxy, KeyValueStoreDelegate, PanelElement, PanelEvent, ProjectDelegate,
     SlashCommand, SlashCommandArgumentCompletion, SlashCommandOutput, Symbol, WorktreeDelegate,
 };
 use fs::{normalize_path, Fs};
@@ -30,13 +31,12 @@ use std::{
 };
 use wasmtime::{
     component::{Component, ResourceTable},
-    Engine, Store,
+    Store,
 };
 use wasmtime_wasi::{self as wasi, WasiView};
 use wit::Extension;
 
 pub struct WasmHost {
-    engine: Engine,
     release_channel: ReleaseChannel,
     http_client: Arc<dyn HttpClient>,
     node_runtime: NodeRuntime,
@@ -346,10 +346,76 @@ impl extension::Extension for WasmExtension {
         })
         .await
     }
+
+    async fn render_panel(&self, panel_id: Arc<str>) -> Result<Vec<PanelElement>> {
+        self.call(|extension, store| {
+            async move {
+                let elements = extension
+                    .call_render_panel(store, panel_id.as_ref())
+                    .await?
+                    .map_err(|err| anyhow!("{err}"))?;
+
+                Ok(elements.into_iter().map(Into::into).collect())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn handle_panel_event(&self, panel_id: Arc<str>, event: PanelEvent) -> Result<()> {
+        self.call(|extension, store| {
+            async move {
+                extension
+                    .call_handle_panel_event(store, panel_id.as_ref(), &event.into())
+                    .await?
+                    .map_err(|err| anyhow!("{err}"))?;
+
+                anyhow::Ok(())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn run_command(&self, command_id: Arc<str>, context: EditorContext) -> Result<()> {
+        self.call(|extension, store| {
+            async move {
+                extension
+                    .call_run_command(store, command_id.as_ref(), &context.into())
+                    .await?
+                    .map_err(|err| anyhow!("{err}"))?;
+
+                anyhow::Ok(())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn buffer_decorations(
+        &self,
+        provider_id: Arc<str>,
+        buffer: DecoratedBuffer,
+    ) -> Result<Vec<extension::TextDecoration>> {
+        self.call(|extension, store| {
+            async move {
+                let decorations = extension
+                    .call_buffer_decorations(store, provider_id.as_ref(), &buffer.into())
+                    .await?
+                    .map_err(|err| anyhow!("{err}"))?;
+
+                Ok(decorations.into_iter().map(Into::into).collect())
+            }
+            .boxed()
+        })
+        .await
+    }
 }
 
 pub struct WasmState {
     manifest: Arc<ExtensionManifest>,
+    /// The capabilities declared in the manifest that the user has granted to the extension.
+    granted_capabilities: Vec<ExtensionCapability>,
     pub table: ResourceTable,
     ctx: wasi::WasiCtx,
     pub host: Arc<WasmHost>,
@@ -361,6 +427,8 @@ type ExtensionCall = Box<
     dyn Send + for<'a> FnOnce(&'a mut Extension, &'a mut Store<WasmState>) -> BoxFuture<'a, ()>,
 >;
 
+/// Returns the engine shared by all extensions, creating it the first time an extension is loaded
+/// rather than at startup.
 fn wasm_engine() -> wasmtime::Engine {
     static WASM_ENGINE: OnceLock<wasmtime::Engine> = OnceLock::new();
 
@@ -390,7 +458,6 @@ impl WasmHost {
             }
         });
         Arc::new(Self {
-            engine: wasm_engine(),
             fs,
             work_dir,
             http_client,
@@ -406,6 +473,7 @@ impl WasmHost {
         self: &Arc<Self>,
         wasm_bytes: Vec<u8>,
         manifest: &Arc<ExtensionManifest>,
+        granted_capabilities: Vec<ExtensionCapability>,
         executor: BackgroundExecutor,
     ) -> Task<Result<WasmExtension>> {
         let this = self.clone();
@@ -413,14 +481,18 @@ impl WasmHost {
         executor.clone().spawn(async move {
             let zed_api_version = parse_wasm_extension_version(&manifest.id, &wasm_bytes)?;
 
-            let component = Component::from_binary(&this.engine, &wasm_bytes)
+            let engine = wasm_engine();
+            let component = Component::from_binary(&engine, &wasm_bytes)
                 .context("failed to compile wasm component")?;
 
             let mut store = wasmtime::Store::new(
-                &this.engine,
+                &engine,
                 WasmState {
-                    ctx: this.build_wasi_ctx(&manifest).await?,
+                    ctx: this
+                        .build_wasi_ctx(&manifest, &granted_capabilities)
+                        .await?,
                     manifest: manifest.clone(),
+                    granted_capabilities,
                     table: ResourceTable::new(),
                     host: this.clone(),
                 },
@@ -457,7 +529,11 @@ impl WasmHost {
         })
     }
 
-    async fn build_wasi_ctx(&self, manifest: &Arc<ExtensionManifest>) -> Result<wasi::WasiCtx> {
+    async fn build_wasi_ctx(
+        &self,
+        manifest: &Arc<ExtensionManifest>,
+        granted_capabilities: &[ExtensionCapability],
+    ) -> Result<wasi::WasiCtx> {
         let extension_work_dir = self.work_dir.join(manifest.id.as_ref());
         self.fs
             .create_dir(&extension_work_dir)
@@ -467,7 +543,8 @@ impl WasmHost {
         let file_perms = wasi::FilePerms::all();
         let dir_perms = wasi::DirPerms::all();
 
-        Ok(wasi::WasiCtxBuilder::new()
+        let mut builder = wasi::WasiCtxBuilder::new();
+        builder
             .inherit_stdio()
             .preopened_dir(&extension_work_dir, ".", dir_perms, file_perms)?
             .preopened_dir(
@@ -477,8 +554,30 @@ impl WasmHost {
                 file_perms,
             )?
             .env("PWD", extension_work_dir.to_string_lossy())
-            .env("RUST_BACKTRACE", "full")
-            .build())
+            .env("RUST_BACKTRACE", "full");
+
+        // Granted filesystem scopes are mounted at their own paths, so that extensions can
+        // use the same paths as the rest of the system.
+        for capability in granted_capabilities {
+            let Some((path, writable)) = capability.directory(paths::home_dir()) else {
+                continue;
+            };
+            if !self.fs.is_dir(&path).await {
+                log::warn!(
+                    "extension {} was granted access to {path:?}, which is not a directory",
+                    manifest.id
+                );
+                continue;
+            }
+            let (dir_perms, file_perms) = if writable {
+                (wasi::DirPerms::all(), wasi::FilePerms::all())
+            } else {
+                (wasi::DirPerms::READ, wasi::FilePerms::READ)
+            };
+            builder.preopened_dir(&path, path.to_string_lossy(), dir_perms, file_perms)?;
+        }
+
+        Ok(builder.build())
     }
 
     pub fn writeable_path_from_extension(&self, id: &Arc<str>, path: &Path) -> Result<PathBuf> {
@@ -539,6 +638,7 @@ impl WasmExtension {
     pub async fn load(
         extension_dir: PathBuf,
         manifest: &Arc<ExtensionManifest>,
+        granted_capabilities: Vec<ExtensionCapability>,
         wasm_host: Arc<WasmHost>,
         cx: &AsyncApp,
     ) -> Result<Self> {
@@ -556,7 +656,12 @@ impl WasmExtension {
             .context("failed to read wasm")?;
 
         wasm_host
-            .load_extension(wasm_bytes, manifest, cx.background_executor().clone())
+            .load_extension(
+                wasm_bytes,
+                manifest,
+                granted_capabilities,
+                cx.background_executor().clone(),
+            )
             .await
             .with_context(|| format!("failed to load wasm extension {}", manifest.id))
     }
@@ -607,6 +712,45 @@ impl WasmState {
     fn work_dir(&self) -> PathBuf {
         self.host.work_dir.join(self.manifest.id.as_ref())
     }
+
+    /// Checks that the extension has declared, and been granted, the capability to run the
+    /// given command.
+    fn allow_exec(&self, command: &str, args: &[String]) -> Result<()> {
+        self.manifest.allow_exec(command, args)?;
+        let is_granted = self
+            .granted_capabilities
+            .iter()
+            .any(|capability| capability.allows_exec(command, args));
+        if !is_granted {
+            bail!("capability for process:exec {command} {args:?} has not been granted");
+        }
+        Ok(())
+    }
+
+    /// Checks that the extension has declared, and been granted, the capability to fetch
+    /// the given URL.
+    fn allow_fetch(&self, url: &str) -> Result<()> {
+        allow_fetch(&self.manifest, &self.granted_capabilities, url)
+    }
+}
+
+fn allow_fetch(
+    manifest: &ExtensionManifest,
+    granted_capabilities: &[ExtensionCapability],
+    url: &str,
+) -> Result<()> {
+    let url = url::Url::parse(url).with_context(|| format!("invalid URL '{url}'"))?;
+    let host = url
+        .host_str()
+        .with_context(|| format!("URL '{url}' has no host"))?;
+    manifest.allow_fetch(host)?;
+    let is_granted = granted_capabilities
+        .iter()
+        .any(|capability| capability.allows_fetch(host));
+    if !is_granted {
+        bail!("capability for network:fetch {host} has not been granted");
+    }
+    Ok(())
 }
 
 impl wasi::WasiView for WasmState {
@@ -618,3 +762,49 @@ impl wasi::WasiView for WasmState {
         &mut self.ctx
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allow_fetch() {
+        let manifest: ExtensionManifest = toml::from_str(
+            r#"
+            id = "test"
+            name = "Test"
+            version = "1.0.0"
+            schema_version = 1
+
+            [[capabilities]]
+            kind = "network:fetch"
+            host = "api.github.com"
+
+            [[capabilities]]
+            kind = "network:fetch"
+            host = "*.example.com"
+            "#,
+        )
+        .unwrap();
+        let granted_capabilities = vec![ExtensionCapability::NetworkFetch {
+            host: "api.github.com".to_string(),
+        }];
+
+        assert!(allow_fetch(
+            &manifest,
+            &granted_capabilities,
+            "https://api.github.com/repos"
+        )
+        .is_ok());
+        // Declared in the manifest, but not granted.
+        assert!(allow_fetch(
+            &manifest,
+            &granted_capabilities,
+            "https://docs.example.com/file.tar.gz"
+        )
+        .is_err());
+        // Neither declared nor granted.
+        assert!(allow_fetch(&manifest, &granted_capabilities, "https://evil.dev/").is_err());
+        assert!(allow_fetch(&manifest, &granted_capabilities, "not a url").is_err());
+    }
+}
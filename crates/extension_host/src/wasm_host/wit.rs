@@ -23,9 +23,12 @@ use wasmtime::{
 #[cfg(test)]
 pub use latest::CodeLabelSpanLiteral;
 pub use latest::{
+    zed::extension::decoration::{DecoratedBuffer, TextDecoration},
+    zed::extension::editor::EditorContext,
     zed::extension::lsp::{
         Completion, CompletionKind, CompletionLabelDetails, InsertTextFormat, Symbol, SymbolKind,
     },
+    zed::extension::panel::{PanelElement, PanelEvent},
     zed::extension::slash_command::{SlashCommandArgumentCompletion, SlashCommandOutput},
     CodeLabel, CodeLabelSpan, Command, ExtensionProject, Range, SlashCommand,
 };
@@ -683,6 +686,80 @@ impl Extension {
             }
         }
     }
+
+    pub async fn call_render_panel(
+        &self,
+        store: &mut Store<WasmState>,
+        panel_id: &str,
+    ) -> Result<Result<Vec<PanelElement>, String>> {
+        match self {
+            Extension::V0_4_0(ext) => ext.call_render_panel(store, panel_id).await,
+            Extension::V0_3_0(_)
+            | Extension::V0_2_0(_)
+            | Extension::V0_1_0(_)
+            | Extension::V0_0_6(_)
+            | Extension::V0_0_4(_)
+            | Extension::V0_0_1(_) => Err(anyhow!("`render_panel` not available prior to v0.4.0")),
+        }
+    }
+
+    pub async fn call_handle_panel_event(
+        &self,
+        store: &mut Store<WasmState>,
+        panel_id: &str,
+        event: &PanelEvent,
+    ) -> Result<Result<(), String>> {
+        match self {
+            Extension::V0_4_0(ext) => ext.call_handle_panel_event(store, panel_id, event).await,
+            Extension::V0_3_0(_)
+            | Extension::V0_2_0(_)
+            | Extension::V0_1_0(_)
+            | Extension::V0_0_6(_)
+            | Extension::V0_0_4(_)
+            | Extension::V0_0_1(_) => Err(anyhow!(
+                "`handle_panel_event` not available prior to v0.4.0"
+            )),
+        }
+    }
+
+    pub async fn call_run_command(
+        &self,
+        store: &mut Store<WasmState>,
+        command_id: &str,
+        context: &EditorContext,
+    ) -> Result<Result<(), String>> {
+        match self {
+            Extension::V0_4_0(ext) => ext.call_run_command(store, command_id, context).await,
+            Extension::V0_3_0(_)
+            | Extension::V0_2_0(_)
+            | Extension::V0_1_0(_)
+            | Extension::V0_0_6(_)
+            | Extension::V0_0_4(_)
+            | Extension::V0_0_1(_) => Err(anyhow!("`run_command` not available prior to v0.4.0")),
+        }
+    }
+
+    pub async fn call_buffer_decorations(
+        &self,
+        store: &mut Store<WasmState>,
+        provider_id: &str,
+        buffer: &DecoratedBuffer,
+    ) -> Result<Result<Vec<TextDecoration>, String>> {
+        match self {
+            Extension::V0_4_0(ext) => {
+                ext.call_buffer_decorations(store, provider_id, buffer)
+                    .await
+            }
+            Extension::V0_3_0(_)
+            | Extension::V0_2_0(_)
+            | Extension::V0_1_0(_)
+            | Extension::V0_0_6(_)
+            | Extension::V0_0_4(_)
+            | Extension::V0_0_1(_) => Err(anyhow!(
+                "`buffer_decorations` not available prior to v0.4.0"
+            )),
+        }
+    }
 }
 
 trait ToWasmtimeResult<T> {
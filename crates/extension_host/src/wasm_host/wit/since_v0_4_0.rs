@@ -247,6 +247,170 @@ impl From<SlashCommandArgumentCompletion> for extension::SlashCommandArgumentCom
     }
 }
 
+impl From<panel::PanelElement> for extension::PanelElement {
+    fn from(value: panel::PanelElement) -> Self {
+        match value {
+            panel::PanelElement::Label(text) => Self::Label(text),
+            panel::PanelElement::Button(button) => Self::Button(extension::PanelButton {
+                id: button.id,
+                label: button.label,
+            }),
+            panel::PanelElement::TextInput(input) => Self::TextInput(extension::PanelTextInput {
+                id: input.id,
+                placeholder: input.placeholder,
+                value: input.value,
+            }),
+            panel::PanelElement::List(list) => Self::List(extension::PanelList {
+                id: list.id,
+                items: list
+                    .items
+                    .into_iter()
+                    .map(|item| extension::PanelListItem {
+                        id: item.id,
+                        label: item.label,
+                        detail: item.detail,
+                        selected: item.selected,
+                    })
+                    .collect(),
+            }),
+            panel::PanelElement::Tree(tree) => Self::Tree(extension::PanelTree {
+                id: tree.id,
+                nodes: tree
+                    .nodes
+                    .into_iter()
+                    .map(|node| extension::PanelTreeNode {
+                        id: node.id,
+                        label: node.label,
+                        depth: node.depth,
+                        expandable: node.expandable,
+                        expanded: node.expanded,
+                        selected: node.selected,
+                    })
+                    .collect(),
+            }),
+            panel::PanelElement::Separator => Self::Separator,
+        }
+    }
+}
+
+impl From<extension::PanelEvent> for panel::PanelEvent {
+    fn from(value: extension::PanelEvent) -> Self {
+        match value {
+            extension::PanelEvent::ButtonClicked(id) => Self::ButtonClicked(id),
+            extension::PanelEvent::TextInputSubmitted { input_id, value } => {
+                Self::TextInputSubmitted(panel::PanelTextInputSubmission { input_id, value })
+            }
+            extension::PanelEvent::ItemSelected {
+                element_id,
+                item_id,
+            } => Self::ItemSelected(panel::PanelItemEvent {
+                element_id,
+                item_id,
+            }),
+            extension::PanelEvent::TreeNodeToggled {
+                element_id,
+                item_id,
+            } => Self::TreeNodeToggled(panel::PanelItemEvent {
+                element_id,
+                item_id,
+            }),
+        }
+    }
+}
+
+impl From<extension::TextPosition> for editor::TextPosition {
+    fn from(value: extension::TextPosition) -> Self {
+        Self {
+            row: value.row,
+            column: value.column,
+        }
+    }
+}
+
+impl From<extension::EditorContext> for editor::EditorContext {
+    fn from(value: extension::EditorContext) -> Self {
+        Self {
+            file_path: value
+                .file_path
+                .map(|path| path.to_string_lossy().into_owned()),
+            selection: value.selection.map(|selection| editor::EditorSelection {
+                text: selection.text,
+                start: selection.start.into(),
+                end: selection.end.into(),
+            }),
+        }
+    }
+}
+
+impl From<extension::DecoratedBuffer> for decoration::DecoratedBuffer {
+    fn from(value: extension::DecoratedBuffer) -> Self {
+        Self {
+            file_path: value.file_path.to_string_lossy().into_owned(),
+            language: value.language.map(|language| language.to_string()),
+            text: value.text,
+        }
+    }
+}
+
+impl From<editor::TextPosition> for extension::TextPosition {
+    fn from(value: editor::TextPosition) -> Self {
+        Self {
+            row: value.row,
+            column: value.column,
+        }
+    }
+}
+
+impl From<decoration::TextDecoration> for extension::TextDecoration {
+    fn from(value: decoration::TextDecoration) -> Self {
+        Self {
+            start: value.start.into(),
+            end: value.end.into(),
+            kind: match value.kind {
+                decoration::TextDecorationKind::GutterIcon(icon) => {
+                    extension::TextDecorationKind::GutterIcon(icon.into())
+                }
+                decoration::TextDecorationKind::Underline => {
+                    extension::TextDecorationKind::Underline
+                }
+                decoration::TextDecorationKind::Background => {
+                    extension::TextDecorationKind::Background
+                }
+                decoration::TextDecorationKind::InlineHint(text) => {
+                    extension::TextDecorationKind::InlineHint(text)
+                }
+            },
+            color: value.color.into(),
+        }
+    }
+}
+
+impl From<decoration::GutterIcon> for extension::GutterIcon {
+    fn from(value: decoration::GutterIcon) -> Self {
+        match value {
+            decoration::GutterIcon::Circle => Self::Circle,
+            decoration::GutterIcon::Check => Self::Check,
+            decoration::GutterIcon::XCircle => Self::XCircle,
+            decoration::GutterIcon::Warning => Self::Warning,
+            decoration::GutterIcon::Info => Self::Info,
+        }
+    }
+}
+
+impl From<decoration::DecorationColor> for extension::DecorationColor {
+    fn from(value: decoration::DecorationColor) -> Self {
+        match value {
+            decoration::DecorationColor::Error => Self::Error,
+            decoration::DecorationColor::Warning => Self::Warning,
+            decoration::DecorationColor::Info => Self::Info,
+            decoration::DecorationColor::Hint => Self::Hint,
+            decoration::DecorationColor::Success => Self::Success,
+            decoration::DecorationColor::Accent => Self::Accent,
+            decoration::DecorationColor::Muted => Self::Muted,
+        }
+    }
+}
+
 impl HostKeyValueStore for WasmState {
     async fn insert(
         &mut self,
@@ -337,6 +501,7 @@ impl http_client::Host for WasmState {
     ) -> wasmtime::Result<Result<http_client::HttpResponse, String>> {
         maybe!(async {
             let url = &request.url;
+            self.allow_fetch(url)?;
             let request = convert_request(&request)?;
             let mut response = self.host.http_client.send(request).await?;
 
@@ -353,10 +518,10 @@ impl http_client::Host for WasmState {
         &mut self,
         request: http_client::HttpRequest,
     ) -> wasmtime::Result<Result<Resource<ExtensionHttpResponseStream>, String>> {
-        let request = convert_request(&request)?;
-        let response = self.host.http_client.send(request);
         maybe!(async {
-            let response = response.await?;
+            self.allow_fetch(&request.url)?;
+            let request = convert_request(&request)?;
+            let response = self.host.http_client.send(request).await?;
             let stream = Arc::new(Mutex::new(response));
             let resource = self.table.push(stream)?;
             Ok(resource)
@@ -592,7 +757,7 @@ impl process::Host for WasmState {
         command: process::Command,
     ) -> wasmtime::Result<Result<process::Output, String>> {
         maybe!(async {
-            self.manifest.allow_exec(&command.command, &command.args)?;
+            self.allow_exec(&command.command, &command.args)?;
 
             let output = util::command::new_smol_command(command.command.as_str())
                 .args(&command.args)
@@ -610,6 +775,12 @@ impl process::Host for WasmState {
 #[async_trait]
 impl slash_command::Host for WasmState {}
 
+impl panel::Host for WasmState {}
+
+impl editor::Host for WasmState {}
+
+impl decoration::Host for WasmState {}
+
 impl ExtensionImports for WasmState {
     async fn get_settings(
         &mut self,
@@ -712,6 +883,7 @@ impl ExtensionImports for WasmState {
         file_type: DownloadedFileType,
     ) -> wasmtime::Result<Result<(), String>> {
         maybe!(async {
+            self.allow_fetch(&url)?;
             let path = PathBuf::from(path);
             let extension_work_dir = self.host.work_dir.join(self.manifest.id.as_ref());
 
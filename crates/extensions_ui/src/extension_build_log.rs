@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use editor::{actions::MoveToEnd, Editor, EditorEvent};
+use extension_host::ExtensionStore;
+use gpui::{
+    App, Context, Entity, EventEmitter, FocusHandle, Focusable, Render, Subscription, Window,
+};
+use language::{Buffer, BufferEvent};
+use ui::prelude::*;
+use workspace::{
+    item::Item,
+    notifications::{simple_message_notification::MessageNotification, NotificationId},
+    Workspace, WorkspaceId,
+};
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+        let Some(store) = ExtensionStore::try_global(cx) else {
+            return;
+        };
+        cx.subscribe_in(&store, window, |workspace, _, event, _window, cx| {
+            if let extension_host::Event::DevExtensionBuildFailed(extension_id) = event {
+                show_build_failed_notification(workspace, extension_id.clone(), cx);
+            }
+        })
+        .detach();
+    })
+    .detach();
+}
+
+fn show_build_failed_notification(
+    workspace: &mut Workspace,
+    extension_id: Arc<str>,
+    cx: &mut Context<Workspace>,
+) {
+    struct DevExtensionBuildFailed;
+
+    let workspace_handle = cx.entity().downgrade();
+    let notification_id = NotificationId::composite::<DevExtensionBuildFailed>(SharedString::from(
+        extension_id.clone(),
+    ));
+    workspace.show_notification(notification_id, cx, |cx| {
+        cx.new(move |cx| {
+            MessageNotification::new(
+                format!("Failed to build the '{extension_id}' dev extension."),
+                cx,
+            )
+            .primary_message("View Build Log")
+            .primary_on_click(move |window, cx| {
+                workspace_handle
+                    .update(cx, |workspace, cx| {
+                        open_build_log(workspace, extension_id.clone(), window, cx)
+                    })
+                    .ok();
+            })
+        })
+    });
+}
+
+/// Opens the output of the most recent build of the dev extension.
+pub(crate) fn open_build_log(
+    workspace: &mut Workspace,
+    extension_id: Arc<str>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(buffer) = ExtensionStore::global(cx)
+        .read(cx)
+        .dev_extension_build_log(&extension_id)
+    else {
+        return;
+    };
+
+    let existing = workspace
+        .active_pane()
+        .read(cx)
+        .items()
+        .filter_map(|item| item.downcast::<ExtensionBuildLog>())
+        .find(|build_log| build_log.read(cx).extension_id == extension_id);
+    if let Some(existing) = existing {
+        workspace.activate_item(&existing, true, true, window, cx);
+    } else {
+        let build_log = cx.new(|cx| ExtensionBuildLog::new(extension_id, buffer, window, cx));
+        workspace.add_item_to_active_pane(Box::new(build_log), None, true, window, cx);
+    }
+}
+
+/// Displays the output of the most recent build of a dev extension, as it is built.
+pub struct ExtensionBuildLog {
+    extension_id: Arc<str>,
+    editor: Entity<Editor>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ExtensionBuildLog {
+    fn new(
+        extension_id: Arc<str>,
+        buffer: Entity<Buffer>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::for_buffer(buffer.clone(), None, window, cx);
+            editor.set_read_only(true);
+            editor.set_show_edit_predictions(Some(false), window, cx);
+            editor.move_to_end(&MoveToEnd, window, cx);
+            editor
+        });
+        let subscriptions = vec![
+            cx.subscribe(&editor, |_, _, event: &EditorEvent, cx| {
+                cx.emit(event.clone())
+            }),
+            // Follow the output of the build.
+            cx.subscribe_in(&buffer, window, |this, _, event, window, cx| {
+                if matches!(event, BufferEvent::Edited) {
+                    this.editor.update(cx, |editor, cx| {
+                        editor.move_to_end(&MoveToEnd, window, cx);
+                    });
+                }
+            }),
+        ];
+
+        Self {
+            extension_id,
+            editor,
+            _subscriptions: subscriptions,
+        }
+    }
+}
+
+impl EventEmitter<EditorEvent> for ExtensionBuildLog {}
+
+impl Focusable for ExtensionBuildLog {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Render for ExtensionBuildLog {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        self.editor.clone()
+    }
+}
+
+impl Item for ExtensionBuildLog {
+    type Event = EditorEvent;
+
+    fn to_item_events(event: &Self::Event, f: impl FnMut(workspace::item::ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn tab_content_text(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some(format!("{} Build Log", self.extension_id).into())
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<WorkspaceId>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<Entity<Self>> {
+        None
+    }
+}
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use collections::HashMap;
+use command_palette_hooks::{CommandPaletteCommand, CommandPaletteCommands};
+use editor::Editor;
+use extension::{
+    EditorContext, EditorSelection, Extension, ExtensionCommand, ExtensionCommandProxy,
+    ExtensionHostProxy, TextPosition,
+};
+use gpui::{impl_actions, App, AppContext as _, Context, Global, Window};
+use language::Point;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use workspace::{notifications::NotifyTaskExt as _, Workspace};
+
+/// Runs a command contributed by an extension.
+#[derive(Clone, Debug, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RunExtensionCommand {
+    /// The ID of the extension that contributes the command.
+    pub extension: Arc<str>,
+    /// The ID of the command, as declared in the extension's manifest.
+    pub command: Arc<str>,
+}
+
+impl_actions!(extensions, [RunExtensionCommand]);
+
+pub fn init(cx: &mut App) {
+    cx.set_global(ExtensionCommandRegistry::default());
+
+    let proxy = ExtensionHostProxy::default_global(cx);
+    proxy.register_command_proxy(ExtensionCommandRegistryProxy);
+
+    cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
+        workspace.register_action(run_extension_command);
+    })
+    .detach();
+}
+
+/// The commands contributed by extensions, keyed by extension ID and command ID.
+#[derive(Default)]
+struct ExtensionCommandRegistry {
+    commands: HashMap<(Arc<str>, Arc<str>), Arc<dyn Extension>>,
+}
+
+impl Global for ExtensionCommandRegistry {}
+
+struct ExtensionCommandRegistryProxy;
+
+impl ExtensionCommandProxy for ExtensionCommandRegistryProxy {
+    fn register_command(
+        &self,
+        extension: Arc<dyn Extension>,
+        command: ExtensionCommand,
+        cx: &mut App,
+    ) {
+        let manifest = extension.manifest();
+        let action = RunExtensionCommand {
+            extension: manifest.id.clone(),
+            command: command.id.into(),
+        };
+        let name = format!("{}: {}", manifest.name.to_lowercase(), command.title);
+
+        CommandPaletteCommands::update_global(cx, |commands, _| {
+            commands.add(CommandPaletteCommand {
+                name,
+                action: Box::new(action.clone()),
+            });
+        });
+        cx.global_mut::<ExtensionCommandRegistry>()
+            .commands
+            .insert((action.extension, action.command), extension);
+    }
+
+    fn remove_commands(&self, extension_id: &Arc<str>, cx: &mut App) {
+        CommandPaletteCommands::update_global(cx, |commands, _| {
+            commands.retain(|command| {
+                command
+                    .action
+                    .as_any()
+                    .downcast_ref::<RunExtensionCommand>()
+                    .map_or(true, |action| &action.extension != extension_id)
+            });
+        });
+        cx.global_mut::<ExtensionCommandRegistry>()
+            .commands
+            .retain(|(command_extension_id, _), _| command_extension_id != extension_id);
+    }
+}
+
+fn run_extension_command(
+    workspace: &mut Workspace,
+    action: &RunExtensionCommand,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(extension) = cx
+        .global::<ExtensionCommandRegistry>()
+        .commands
+        .get(&(action.extension.clone(), action.command.clone()))
+        .cloned()
+    else {
+        workspace.show_error(
+            &anyhow::anyhow!(
+                "extension {:?} has no command {:?}",
+                action.extension,
+                action.command
+            ),
+            cx,
+        );
+        return;
+    };
+
+    let context = workspace
+        .active_item_as::<Editor>(cx)
+        .map(|editor| editor.update(cx, |editor, cx| editor_context(editor, cx)))
+        .unwrap_or_default();
+    let command_id = action.command.clone();
+    cx.background_spawn(async move { extension.run_command(command_id, context).await })
+        .detach_and_notify_err(window, cx);
+}
+
+fn editor_context(editor: &mut Editor, cx: &mut Context<Editor>) -> EditorContext {
+    let selection = editor.selections.newest::<Point>(cx);
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let text = snapshot.text_for_range(selection.range()).collect();
+    // Report positions within the file, rather than within the multibuffer.
+    let text_position = |point: Point| {
+        let point = snapshot
+            .point_to_buffer_point(point)
+            .map_or(point, |(_, buffer_point, _)| buffer_point);
+        TextPosition {
+            row: point.row,
+            column: point.column,
+        }
+    };
+
+    EditorContext {
+        file_path: editor.target_file_abs_path(cx),
+        selection: Some(EditorSelection {
+            text,
+            start: text_position(selection.start),
+            end: text_position(selection.end),
+        }),
+    }
+}
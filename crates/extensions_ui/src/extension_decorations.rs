@@ -0,0 +1,311 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use collections::{HashMap, HashSet};
+use editor::{Editor, EditorMode, ExcerptId, MultiBufferSnapshot};
+use extension::{
+    DecoratedBuffer, DecorationColor, DecorationProvider, Extension, ExtensionDecorationProxy,
+    ExtensionHostProxy, GutterIcon, TextDecorationKind, TextPosition,
+};
+use futures::future::join_all;
+use gpui::{App, AsyncApp, Context, EntityId, Global, SharedString, Task, WeakEntity};
+use language::{Bias, BufferEvent, Point, TextBufferSnapshot};
+use ui::{Color, IconName};
+use util::ResultExt as _;
+
+/// How long to wait after an edit before asking the extensions for new decorations.
+const EDIT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+pub fn init(cx: &mut App) {
+    cx.set_global(ExtensionDecorations::default());
+
+    let proxy = ExtensionHostProxy::default_global(cx);
+    proxy.register_decoration_proxy(ExtensionDecorationsProxy);
+
+    cx.observe_new(|editor: &mut Editor, _window, cx| {
+        if !matches!(editor.mode(), EditorMode::Full) {
+            return;
+        }
+        let Some(buffer) = editor.buffer().read(cx).as_singleton() else {
+            return;
+        };
+
+        let editor_id = cx.entity_id();
+        let buffer_subscription = cx.subscribe(&buffer, |_, _, event, cx| match event {
+            BufferEvent::Edited => schedule_refresh(cx.entity().downgrade(), EDIT_DEBOUNCE, cx),
+            BufferEvent::Saved
+            | BufferEvent::Reloaded
+            | BufferEvent::FileHandleChanged
+            | BufferEvent::LanguageChanged => {
+                schedule_refresh(cx.entity().downgrade(), Duration::ZERO, cx)
+            }
+            _ => {}
+        });
+        cx.on_release(move |_, cx| {
+            cx.global_mut::<ExtensionDecorations>()
+                .editors
+                .remove(&editor_id);
+        })
+        .detach();
+
+        cx.global_mut::<ExtensionDecorations>().editors.insert(
+            editor_id,
+            DecoratedEditor {
+                editor: cx.entity().downgrade(),
+                sources: HashSet::default(),
+                pending_refresh: None,
+                _buffer_subscription: buffer_subscription,
+            },
+        );
+        schedule_refresh(cx.entity().downgrade(), Duration::ZERO, cx);
+    })
+    .detach();
+}
+
+/// The decoration providers contributed by extensions, and the editors they decorate.
+#[derive(Default)]
+struct ExtensionDecorations {
+    providers: Vec<RegisteredProvider>,
+    editors: HashMap<EntityId, DecoratedEditor>,
+}
+
+impl Global for ExtensionDecorations {}
+
+#[derive(Clone)]
+struct RegisteredProvider {
+    extension: Arc<dyn Extension>,
+    provider: DecorationProvider,
+}
+
+impl RegisteredProvider {
+    /// The source of the provider's decorations in an editor.
+    fn source(&self) -> SharedString {
+        format!("{}/{}", self.extension.manifest().id, self.provider.id).into()
+    }
+}
+
+struct DecoratedEditor {
+    editor: WeakEntity<Editor>,
+    /// The sources of the decorations currently displayed in the editor.
+    sources: HashSet<SharedString>,
+    pending_refresh: Option<Task<()>>,
+    _buffer_subscription: gpui::Subscription,
+}
+
+impl ExtensionDecorations {
+    fn refresh_all(cx: &mut App) {
+        let editors = cx
+            .global::<Self>()
+            .editors
+            .values()
+            .map(|decorated| decorated.editor.clone())
+            .collect::<Vec<_>>();
+        for editor in editors {
+            schedule_refresh(editor, Duration::ZERO, cx);
+        }
+    }
+}
+
+struct ExtensionDecorationsProxy;
+
+impl ExtensionDecorationProxy for ExtensionDecorationsProxy {
+    fn register_decoration_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider: DecorationProvider,
+        cx: &mut App,
+    ) {
+        let registered = RegisteredProvider {
+            extension,
+            provider,
+        };
+        let source = registered.source();
+        let decorations = cx.global_mut::<ExtensionDecorations>();
+        // Reloading an extension registers its providers again.
+        decorations
+            .providers
+            .retain(|existing| existing.source() != source);
+        decorations.providers.push(registered);
+        ExtensionDecorations::refresh_all(cx);
+    }
+
+    fn remove_decoration_providers(&self, extension_id: &Arc<str>, cx: &mut App) {
+        cx.global_mut::<ExtensionDecorations>()
+            .providers
+            .retain(|registered| &registered.extension.manifest().id != extension_id);
+        ExtensionDecorations::refresh_all(cx);
+    }
+}
+
+fn schedule_refresh(editor: WeakEntity<Editor>, delay: Duration, cx: &mut App) {
+    let editor_id = editor.entity_id();
+    let task = cx.spawn({
+        let editor = editor.clone();
+        async move |cx| {
+            if !delay.is_zero() {
+                cx.background_executor().timer(delay).await;
+            }
+            refresh_decorations(editor, cx).await.log_err();
+        }
+    });
+    if let Some(decorated) = cx
+        .global_mut::<ExtensionDecorations>()
+        .editors
+        .get_mut(&editor_id)
+    {
+        decorated.pending_refresh = Some(task);
+    }
+}
+
+async fn refresh_decorations(editor: WeakEntity<Editor>, cx: &mut AsyncApp) -> Result<()> {
+    let request = editor.update(cx, |editor, cx| {
+        let buffer = editor.buffer().read(cx).as_singleton()?;
+        let buffer = buffer.read(cx);
+        let file_path = buffer.file()?.as_local()?.abs_path(cx);
+        let language = buffer.language().map(|language| language.name());
+        let providers = cx
+            .global::<ExtensionDecorations>()
+            .providers
+            .iter()
+            .filter(|registered| {
+                let languages = &registered.provider.languages;
+                languages.is_empty()
+                    || language
+                        .as_ref()
+                        .is_some_and(|language| languages.contains(language))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        let decorated_buffer = DecoratedBuffer {
+            file_path,
+            language,
+            text: buffer.text(),
+        };
+        Some((providers, decorated_buffer, buffer.text_snapshot()))
+    })?;
+    // Only local files can be decorated, as extensions are given their path.
+    let Some((providers, decorated_buffer, buffer_snapshot)) = request else {
+        return editor.update(cx, |editor, cx| {
+            retain_decoration_sources(editor, HashSet::default(), cx)
+        });
+    };
+
+    let results = join_all(providers.iter().map(|registered| {
+        let extension = registered.extension.clone();
+        let provider_id: Arc<str> = registered.provider.id.clone().into();
+        let decorated_buffer = decorated_buffer.clone();
+        async move {
+            extension
+                .buffer_decorations(provider_id, decorated_buffer)
+                .await
+        }
+    }))
+    .await;
+
+    editor.update(cx, |editor, cx| {
+        let multibuffer = editor.buffer().read(cx).snapshot(cx);
+        let excerpt_id = multibuffer
+            .as_singleton()
+            .map(|(excerpt_id, _, _)| *excerpt_id);
+
+        let mut sources = HashSet::default();
+        for (registered, result) in providers.iter().zip(results) {
+            let source = registered.source();
+            match result {
+                Ok(decorations) => {
+                    let decorations = excerpt_id
+                        .map(|excerpt_id| {
+                            decorations
+                                .into_iter()
+                                .filter_map(|decoration| {
+                                    editor_decoration(
+                                        decoration,
+                                        &buffer_snapshot,
+                                        &multibuffer,
+                                        excerpt_id,
+                                    )
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    editor.set_text_decorations(source.clone(), decorations, cx);
+                }
+                // Keep the previous decorations, which are likely still relevant.
+                Err(error) => log::error!("failed to compute decorations for {source}: {error:?}"),
+            }
+            sources.insert(source);
+        }
+        retain_decoration_sources(editor, sources, cx);
+    })
+}
+
+/// Removes the decorations from the providers that no longer apply to the editor, like the
+/// ones from uninstalled extensions.
+fn retain_decoration_sources(
+    editor: &mut Editor,
+    sources: HashSet<SharedString>,
+    cx: &mut Context<Editor>,
+) {
+    let editor_id = cx.entity_id();
+    let Some(decorated) = cx
+        .global_mut::<ExtensionDecorations>()
+        .editors
+        .get_mut(&editor_id)
+    else {
+        return;
+    };
+    let stale_sources = decorated
+        .sources
+        .difference(&sources)
+        .cloned()
+        .collect::<Vec<_>>();
+    decorated.sources = sources;
+    for source in stale_sources {
+        editor.clear_text_decorations(&source, cx);
+    }
+}
+
+fn editor_decoration(
+    decoration: extension::TextDecoration,
+    buffer: &TextBufferSnapshot,
+    multibuffer: &MultiBufferSnapshot,
+    excerpt_id: ExcerptId,
+) -> Option<editor::TextDecoration> {
+    let point = |position: TextPosition| {
+        buffer.clip_point(Point::new(position.row, position.column), Bias::Left)
+    };
+    let start = point(decoration.start);
+    let end = point(decoration.end).max(start);
+    let start = multibuffer.anchor_in_excerpt(excerpt_id, buffer.anchor_before(start))?;
+    let end = multibuffer.anchor_in_excerpt(excerpt_id, buffer.anchor_after(end))?;
+
+    let kind = match decoration.kind {
+        TextDecorationKind::GutterIcon(icon) => {
+            editor::TextDecorationKind::GutterIcon(match icon {
+                GutterIcon::Circle => IconName::Circle,
+                GutterIcon::Check => IconName::Check,
+                GutterIcon::XCircle => IconName::XCircle,
+                GutterIcon::Warning => IconName::Warning,
+                GutterIcon::Info => IconName::Info,
+            })
+        }
+        TextDecorationKind::Underline => editor::TextDecorationKind::Underline,
+        TextDecorationKind::Background => editor::TextDecorationKind::Background,
+        TextDecorationKind::InlineHint(text) => editor::TextDecorationKind::InlineHint(text.into()),
+    };
+    let color = match decoration.color {
+        DecorationColor::Error => Color::Error,
+        DecorationColor::Warning => Color::Warning,
+        DecorationColor::Info => Color::Info,
+        DecorationColor::Hint => Color::Hint,
+        DecorationColor::Success => Color::Success,
+        DecorationColor::Accent => Color::Accent,
+        DecorationColor::Muted => Color::Muted,
+    };
+
+    Some(editor::TextDecoration {
+        range: start..end,
+        kind,
+        color,
+    })
+}
@@ -0,0 +1,540 @@
+use std::sync::Arc;
+
+use collections::HashMap;
+use editor::Editor;
+use extension::{
+    Extension, ExtensionHostProxy, ExtensionPanel, ExtensionPanelProxy, PanelElement, PanelEvent,
+};
+use gpui::{
+    actions, px, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    Global, Pixels, Task, Window,
+};
+use ui::{prelude::*, Divider, ListItem, ListItemSpacing};
+use util::ResultExt as _;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent as DockPanelEvent},
+    Workspace,
+};
+
+actions!(extension_panels, [ToggleFocus]);
+
+const DEFAULT_WIDTH: Pixels = px(320.);
+
+pub fn init(cx: &mut App) {
+    let registry = cx.new(|_| ExtensionPanelRegistry::default());
+    cx.set_global(GlobalExtensionPanelRegistry(registry.clone()));
+
+    let proxy = ExtensionHostProxy::default_global(cx);
+    proxy.register_panel_proxy(ExtensionPanelRegistryProxy { registry });
+
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<ExtensionPanels>(window, cx);
+        });
+
+        let panel = cx.new(|cx| ExtensionPanels::new(window, cx));
+        workspace.add_panel(panel, window, cx);
+    })
+    .detach();
+}
+
+struct GlobalExtensionPanelRegistry(Entity<ExtensionPanelRegistry>);
+
+impl Global for GlobalExtensionPanelRegistry {}
+
+/// The panels contributed by extensions.
+#[derive(Default)]
+pub struct ExtensionPanelRegistry {
+    panels: Vec<RegisteredPanel>,
+}
+
+#[derive(Clone)]
+struct RegisteredPanel {
+    extension: Arc<dyn Extension>,
+    panel: ExtensionPanel,
+}
+
+impl RegisteredPanel {
+    fn key(&self) -> (Arc<str>, &str) {
+        (self.extension.manifest().id.clone(), self.panel.id.as_str())
+    }
+}
+
+impl ExtensionPanelRegistry {
+    pub fn global(cx: &App) -> Entity<Self> {
+        cx.global::<GlobalExtensionPanelRegistry>().0.clone()
+    }
+
+    fn register(&mut self, extension: Arc<dyn Extension>, panel: ExtensionPanel) {
+        let registered = RegisteredPanel { extension, panel };
+        // Reloading an extension registers its panels again, which keep their position.
+        if let Some(existing) = self
+            .panels
+            .iter_mut()
+            .find(|existing| existing.key() == registered.key())
+        {
+            *existing = registered;
+        } else {
+            self.panels.push(registered);
+        }
+    }
+
+    fn remove(&mut self, extension_id: &Arc<str>) {
+        self.panels
+            .retain(|registered| &registered.extension.manifest().id != extension_id);
+    }
+}
+
+struct ExtensionPanelRegistryProxy {
+    registry: Entity<ExtensionPanelRegistry>,
+}
+
+impl ExtensionPanelProxy for ExtensionPanelRegistryProxy {
+    fn register_panel(&self, extension: Arc<dyn Extension>, panel: ExtensionPanel, cx: &mut App) {
+        self.registry.update(cx, |registry, cx| {
+            registry.register(extension, panel);
+            cx.notify();
+        });
+    }
+
+    fn remove_panels(&self, extension_id: &Arc<str>, cx: &mut App) {
+        self.registry.update(cx, |registry, cx| {
+            registry.remove(extension_id);
+            cx.notify();
+        });
+    }
+}
+
+/// The dock panel that displays the panels contributed by extensions, one at a time.
+pub struct ExtensionPanels {
+    registry: Entity<ExtensionPanelRegistry>,
+    focus_handle: FocusHandle,
+    position: DockPosition,
+    width: Option<Pixels>,
+    active: bool,
+    active_panel_ix: usize,
+    panel_states: HashMap<(Arc<str>, String), PanelState>,
+    _registry_subscription: gpui::Subscription,
+}
+
+/// The most recently rendered contents of a panel contributed by an extension.
+#[derive(Default)]
+struct PanelState {
+    elements: Vec<PanelElement>,
+    error: Option<SharedString>,
+    loaded: bool,
+    text_inputs: HashMap<String, TextInputState>,
+    pending: Option<Task<()>>,
+}
+
+struct TextInputState {
+    editor: Entity<Editor>,
+    /// The value last reported by the extension, so that the user's edits are only
+    /// replaced when the extension changes the value.
+    value: String,
+}
+
+impl ExtensionPanels {
+    fn new(_window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let registry = ExtensionPanelRegistry::global(cx);
+        let registry_subscription = cx.observe(&registry, |this, _, cx| {
+            let panel_count = this.registry.read(cx).panels.len();
+            this.active_panel_ix = this.active_panel_ix.min(panel_count.saturating_sub(1));
+            // Render every panel from scratch, as the extension might have been reloaded.
+            this.panel_states.clear();
+            if this.active {
+                this.render_active_panel(cx);
+            }
+            cx.notify();
+        });
+
+        Self {
+            registry,
+            focus_handle: cx.focus_handle(),
+            position: DockPosition::Right,
+            width: None,
+            active: false,
+            active_panel_ix: 0,
+            panel_states: HashMap::default(),
+            _registry_subscription: registry_subscription,
+        }
+    }
+
+    fn active_panel(&self, cx: &App) -> Option<RegisteredPanel> {
+        self.registry
+            .read(cx)
+            .panels
+            .get(self.active_panel_ix)
+            .cloned()
+    }
+
+    fn activate_panel(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.active_panel_ix = ix;
+        if self.active {
+            self.render_active_panel(cx);
+        }
+        cx.notify();
+    }
+
+    /// Asks the extension for the contents of the active panel, unless they are already known.
+    fn render_active_panel(&mut self, cx: &mut Context<Self>) {
+        let Some(registered) = self.active_panel(cx) else {
+            return;
+        };
+        let key = state_key(&registered);
+        if self
+            .panel_states
+            .get(&key)
+            .map_or(false, |state| state.loaded || state.pending.is_some())
+        {
+            return;
+        }
+        self.update_panel(registered, None, cx);
+    }
+
+    /// Sends the event to the extension, if any, and renders the panel again.
+    fn update_panel(
+        &mut self,
+        registered: RegisteredPanel,
+        event: Option<PanelEvent>,
+        cx: &mut Context<Self>,
+    ) {
+        let key = state_key(&registered);
+        let panel_id: Arc<str> = registered.panel.id.clone().into();
+        let extension = registered.extension.clone();
+        let task = cx.spawn(async move |this, cx| {
+            let result = async {
+                if let Some(event) = event {
+                    extension
+                        .handle_panel_event(panel_id.clone(), event)
+                        .await?;
+                }
+                extension.render_panel(panel_id).await
+            }
+            .await;
+
+            this.update(cx, |this, cx| {
+                let state = this.panel_states.entry(key).or_default();
+                state.pending = None;
+                state.loaded = true;
+                match result {
+                    Ok(elements) => {
+                        state.elements = elements;
+                        state.error = None;
+                    }
+                    Err(error) => {
+                        log::error!("failed to render extension panel: {error:?}");
+                        state.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .log_err();
+        });
+        self.panel_states
+            .entry(state_key(&registered))
+            .or_default()
+            .pending = Some(task);
+    }
+
+    fn dispatch_event(&mut self, event: PanelEvent, cx: &mut Context<Self>) {
+        if let Some(registered) = self.active_panel(cx) {
+            self.update_panel(registered, Some(event), cx);
+        }
+    }
+
+    fn submit_text_input(&mut self, input_id: String, cx: &mut Context<Self>) {
+        let Some(registered) = self.active_panel(cx) else {
+            return;
+        };
+        let Some(input) = self
+            .panel_states
+            .get(&state_key(&registered))
+            .and_then(|state| state.text_inputs.get(&input_id))
+        else {
+            return;
+        };
+        let value = input.editor.read(cx).text(cx);
+        self.dispatch_event(PanelEvent::TextInputSubmitted { input_id, value }, cx);
+    }
+
+    fn render_tabs(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let panels = &self.registry.read(cx).panels;
+        h_flex()
+            .flex_wrap()
+            .gap_1()
+            .px_2()
+            .py_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .children(panels.iter().enumerate().map(|(ix, registered)| {
+                Button::new(("extension-panel-tab", ix), registered.panel.title.clone())
+                    .style(ButtonStyle::Subtle)
+                    .toggle_state(ix == self.active_panel_ix)
+                    .on_click(cx.listener(move |this, _, _, cx| this.activate_panel(ix, cx)))
+            }))
+    }
+
+    fn render_element(
+        &mut self,
+        key: &(Arc<str>, String),
+        ix: usize,
+        element: &PanelElement,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        match element {
+            PanelElement::Label(text) => Label::new(text.clone()).into_any_element(),
+            PanelElement::Separator => Divider::horizontal().into_any_element(),
+            PanelElement::Button(button) => {
+                let id = button.id.clone();
+                Button::new(("extension-panel-button", ix), button.label.clone())
+                    .style(ButtonStyle::Filled)
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.dispatch_event(PanelEvent::ButtonClicked(id.clone()), cx)
+                    }))
+                    .into_any_element()
+            }
+            PanelElement::TextInput(input) => {
+                let state = self.panel_states.entry(key.clone()).or_default();
+                let input_state = state
+                    .text_inputs
+                    .entry(input.id.clone())
+                    .or_insert_with(|| TextInputState {
+                        editor: cx.new(|cx| Editor::single_line(window, cx)),
+                        value: String::new(),
+                    });
+                let editor = input_state.editor.clone();
+                editor.update(cx, |editor, cx| {
+                    editor.set_placeholder_text(input.placeholder.clone(), cx);
+                    if input_state.value != input.value {
+                        editor.set_text(input.value.clone(), window, cx);
+                    }
+                });
+                input_state.value = input.value.clone();
+
+                let input_id = input.id.clone();
+                div()
+                    .px_2()
+                    .py_1()
+                    .border_1()
+                    .border_color(cx.theme().colors().border)
+                    .rounded_sm()
+                    .on_action(cx.listener(move |this, _: &menu::Confirm, _, cx| {
+                        this.submit_text_input(input_id.clone(), cx)
+                    }))
+                    .child(editor)
+                    .into_any_element()
+            }
+            PanelElement::List(list) => v_flex()
+                .children(list.items.iter().enumerate().map(|(item_ix, item)| {
+                    let element_id = list.id.clone();
+                    let item_id = item.id.clone();
+                    ListItem::new(("extension-panel-list-item", ix * 10_000 + item_ix))
+                        .spacing(ListItemSpacing::Sparse)
+                        .toggle_state(item.selected)
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(Label::new(item.label.clone()))
+                                .children(item.detail.clone().map(|detail| {
+                                    Label::new(detail)
+                                        .color(Color::Muted)
+                                        .size(LabelSize::Small)
+                                })),
+                        )
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.dispatch_event(
+                                PanelEvent::ItemSelected {
+                                    element_id: element_id.clone(),
+                                    item_id: item_id.clone(),
+                                },
+                                cx,
+                            )
+                        }))
+                }))
+                .into_any_element(),
+            PanelElement::Tree(tree) => {
+                let mut collapsed_depth = None;
+                let visible_nodes = tree.nodes.iter().enumerate().filter(|(_, node)| {
+                    if let Some(depth) = collapsed_depth {
+                        if node.depth > depth {
+                            return false;
+                        }
+                        collapsed_depth = None;
+                    }
+                    if node.expandable && !node.expanded {
+                        collapsed_depth = Some(node.depth);
+                    }
+                    true
+                });
+
+                v_flex()
+                    .children(
+                        visible_nodes
+                            .map(|(node_ix, node)| {
+                                let element_id = tree.id.clone();
+                                let item_id = node.id.clone();
+                                let toggle_element_id = tree.id.clone();
+                                let toggle_item_id = node.id.clone();
+                                ListItem::new(("extension-panel-tree-node", ix * 10_000 + node_ix))
+                                    .spacing(ListItemSpacing::Sparse)
+                                    .indent_level(node.depth as usize)
+                                    .indent_step_size(px(12.))
+                                    .toggle_state(node.selected)
+                                    .when(node.expandable, |item| {
+                                        item.toggle(node.expanded).on_toggle(cx.listener(
+                                            move |this, _, _, cx| {
+                                                this.dispatch_event(
+                                                    PanelEvent::TreeNodeToggled {
+                                                        element_id: toggle_element_id.clone(),
+                                                        item_id: toggle_item_id.clone(),
+                                                    },
+                                                    cx,
+                                                )
+                                            },
+                                        ))
+                                    })
+                                    .child(Label::new(node.label.clone()))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.dispatch_event(
+                                            PanelEvent::ItemSelected {
+                                                element_id: element_id.clone(),
+                                                item_id: item_id.clone(),
+                                            },
+                                            cx,
+                                        )
+                                    }))
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                    .into_any_element()
+            }
+        }
+    }
+}
+
+fn state_key(registered: &RegisteredPanel) -> (Arc<str>, String) {
+    (
+        registered.extension.manifest().id.clone(),
+        registered.panel.id.clone(),
+    )
+}
+
+impl Render for ExtensionPanels {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let panel_count = self.registry.read(cx).panels.len();
+        let active_panel = self.active_panel(cx);
+
+        let content = match active_panel {
+            None => Label::new("No extension panels installed")
+                .color(Color::Muted)
+                .into_any_element(),
+            Some(registered) => {
+                let key = state_key(&registered);
+                let (elements, error, loading) = self
+                    .panel_states
+                    .get(&key)
+                    .map(|state| (state.elements.clone(), state.error.clone(), !state.loaded))
+                    .unwrap_or((Vec::new(), None, true));
+
+                if let Some(error) = error {
+                    Label::new(error).color(Color::Error).into_any_element()
+                } else if loading {
+                    Label::new("Loading…")
+                        .color(Color::Muted)
+                        .into_any_element()
+                } else {
+                    v_flex()
+                        .gap_2()
+                        .children(elements.iter().enumerate().map(|(ix, element)| {
+                            self.render_element(&key, ix, element, window, cx)
+                        }))
+                        .into_any_element()
+                }
+            }
+        };
+
+        v_flex()
+            .key_context("ExtensionPanels")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().panel_background)
+            .when(panel_count > 1, |this| this.child(self.render_tabs(cx)))
+            .child(
+                v_flex()
+                    .id("extension-panel-content")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .p_2()
+                    .child(content),
+            )
+    }
+}
+
+impl Focusable for ExtensionPanels {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<DockPanelEvent> for ExtensionPanels {}
+
+impl Panel for ExtensionPanels {
+    fn persistent_name() -> &'static str {
+        "ExtensionPanels"
+    }
+
+    fn position(&self, _window: &Window, _cx: &App) -> DockPosition {
+        self.position
+    }
+
+    fn position_is_valid(&self, _position: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        self.position = position;
+        cx.notify();
+    }
+
+    fn size(&self, _window: &Window, _cx: &App) -> Pixels {
+        self.width.unwrap_or(DEFAULT_WIDTH)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        cx.notify();
+    }
+
+    fn set_active(&mut self, active: bool, _window: &mut Window, cx: &mut Context<Self>) {
+        self.active = active;
+        if active {
+            self.render_active_panel(cx);
+        }
+    }
+
+    fn icon(&self, _window: &Window, _cx: &App) -> Option<IconName> {
+        Some(IconName::Blocks)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Extension Panels")
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        10
+    }
+
+    fn enabled(&self, cx: &App) -> bool {
+        !self.registry.read(cx).panels.is_empty()
+    }
+}
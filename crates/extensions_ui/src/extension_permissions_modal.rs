@@ -0,0 +1,199 @@
+use std::sync::Arc;
+
+use extension::ExtensionCapability;
+use extension_host::ExtensionStore;
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, Subscription,
+};
+use ui::prelude::*;
+use workspace::{ModalView, Workspace};
+
+/// Returns a description of what the capability lets an extension do.
+pub(crate) fn capability_description(capability: &ExtensionCapability) -> String {
+    match capability {
+        ExtensionCapability::ProcessExec { command, args } => {
+            let mut command_line = command.clone();
+            for arg in args {
+                command_line.push(' ');
+                command_line.push_str(arg);
+            }
+            format!("Run `{command_line}`")
+        }
+        ExtensionCapability::NetworkFetch { host } if host == "*" => {
+            "Fetch from any host".to_string()
+        }
+        ExtensionCapability::NetworkFetch { host } => format!("Fetch from {host}"),
+        ExtensionCapability::FileSystemRead { path } => format!("Read files in {path}"),
+        ExtensionCapability::FileSystemWrite { path } => {
+            format!("Read and write files in {path}")
+        }
+    }
+}
+
+/// Lists the capabilities declared by an installed extension, so that they can be granted
+/// and revoked.
+pub struct ExtensionPermissionsModal {
+    focus_handle: FocusHandle,
+    extension_id: Arc<str>,
+    store: Entity<ExtensionStore>,
+    _store_subscription: Subscription,
+}
+
+impl ExtensionPermissionsModal {
+    pub fn toggle(
+        extension_id: Arc<str>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        workspace.toggle_modal(window, cx, |_window, cx| {
+            let store = ExtensionStore::global(cx);
+            Self {
+                focus_handle: cx.focus_handle(),
+                extension_id,
+                _store_subscription: cx.observe(&store, |_, _, cx| cx.notify()),
+                store,
+            }
+        });
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_capability(
+        &self,
+        ix: usize,
+        capability: &ExtensionCapability,
+        is_granted: bool,
+        is_dev: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let button = if is_dev {
+            None
+        } else if is_granted {
+            Some(Button::new(("revoke", ix), "Revoke").on_click(cx.listener({
+                let capability = capability.clone();
+                move |this, _, _, cx| {
+                    let extension_id = this.extension_id.clone();
+                    this.store.update(cx, |store, cx| {
+                        store.revoke_capability(extension_id, &capability, cx)
+                    });
+                }
+            })))
+        } else {
+            Some(
+                Button::new(("grant", ix), "Grant")
+                    .color(Color::Accent)
+                    .on_click(cx.listener({
+                        let capability = capability.clone();
+                        move |this, _, _, cx| {
+                            let extension_id = this.extension_id.clone();
+                            this.store.update(cx, |store, cx| {
+                                store.grant_capabilities(extension_id, vec![capability.clone()], cx)
+                            });
+                        }
+                    })),
+            )
+        };
+
+        h_flex()
+            .w_full()
+            .gap_2()
+            .justify_between()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .min_w_0()
+                    .child(
+                        Icon::new(if is_granted {
+                            IconName::Check
+                        } else {
+                            IconName::Warning
+                        })
+                        .size(IconSize::Small)
+                        .color(if is_granted {
+                            Color::Success
+                        } else {
+                            Color::Warning
+                        }),
+                    )
+                    .child(Label::new(capability_description(capability)).truncate()),
+            )
+            .children(button)
+    }
+}
+
+impl EventEmitter<DismissEvent> for ExtensionPermissionsModal {}
+
+impl Focusable for ExtensionPermissionsModal {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl ModalView for ExtensionPermissionsModal {}
+
+impl Render for ExtensionPermissionsModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let store = self.store.read(cx);
+        let entry = store
+            .extension_index
+            .extensions
+            .get(&self.extension_id)
+            .cloned();
+        let granted_capabilities = store.granted_capabilities(&self.extension_id);
+
+        let base = v_flex()
+            .key_context("ExtensionPermissionsModal")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(px(450.))
+            .p_4()
+            .gap_2();
+
+        let Some(entry) = entry else {
+            return base.child(
+                Label::new(format!("{} is not installed.", self.extension_id)).color(Color::Muted),
+            );
+        };
+
+        base.child(
+            h_flex()
+                .w_full()
+                .justify_between()
+                .child(Headline::new(format!(
+                    "{} Permissions",
+                    entry.manifest.name
+                )))
+                .child(
+                    IconButton::new("close", IconName::Close)
+                        .icon_color(Color::Muted)
+                        .on_click(cx.listener(|_, _, _, cx| cx.emit(DismissEvent))),
+                ),
+        )
+        .child(
+            Label::new(if entry.manifest.capabilities.is_empty() {
+                "This extension doesn't require any permissions."
+            } else if entry.dev {
+                "Dev extensions are granted the permissions they declare."
+            } else {
+                "This extension can only do the following once it has been granted permission."
+            })
+            .color(Color::Muted),
+        )
+        .children(
+            entry
+                .manifest
+                .capabilities
+                .iter()
+                .enumerate()
+                .map(|(ix, capability)| {
+                    let is_granted = granted_capabilities.contains(capability);
+                    self.render_capability(ix, capability, is_granted, entry.dev, cx)
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+}
@@ -1,4 +1,9 @@
 mod components;
+mod extension_build_log;
+mod extension_command;
+mod extension_decorations;
+mod extension_panel;
+mod extension_permissions_modal;
 mod extension_suggest;
 mod extension_version_selector;
 
@@ -8,17 +13,20 @@ use std::{ops::Range, sync::Arc};
 
 use client::{ExtensionMetadata, ExtensionProvides};
 use collections::{BTreeMap, BTreeSet};
+use command_palette_hooks::{CommandPaletteCommand, CommandPaletteCommands};
 use editor::{Editor, EditorElement, EditorStyle};
 use extension_host::{ExtensionManifest, ExtensionOperation, ExtensionStore};
 use fuzzy::{match_strings, StringMatchCandidate};
 use gpui::{
-    actions, point, uniform_list, Action, App, ClipboardItem, Context, Entity, EventEmitter,
-    Flatten, Focusable, InteractiveElement, KeyContext, ParentElement, Render, Styled, Task,
-    TextStyle, UniformListScrollHandle, WeakEntity, Window,
+    impl_actions, point, uniform_list, Action, App, ClipboardItem, Context, Entity, EventEmitter,
+    Flatten, Focusable, InteractiveElement, KeyContext, ParentElement, PromptLevel, Render, Styled,
+    Task, TextStyle, UniformListScrollHandle, WeakEntity, Window,
 };
 use num_format::{Locale, ToFormattedString};
 use project::DirectoryLister;
 use release_channel::ReleaseChannel;
+use schemars::JsonSchema;
+use serde::Deserialize;
 use settings::Settings;
 use strum::IntoEnumIterator as _;
 use theme::ThemeSettings;
@@ -34,13 +42,35 @@ use workspace::{
 use zed_actions::ExtensionCategoryFilter;
 
 use crate::components::{ExtensionCard, FeatureUpsell};
+use crate::extension_permissions_modal::{capability_description, ExtensionPermissionsModal};
 use crate::extension_version_selector::{
     ExtensionVersionSelector, ExtensionVersionSelectorDelegate,
 };
 
-actions!(zed, [InstallDevExtension]);
+/// Installs an extension from a local directory.
+#[derive(Clone, Default, Debug, PartialEq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InstallDevExtension {
+    /// Whether to rebuild and reload the extension whenever its sources change.
+    #[serde(default)]
+    pub watch: bool,
+}
+
+impl_actions!(zed, [InstallDevExtension]);
 
 pub fn init(cx: &mut App) {
+    extension_build_log::init(cx);
+    extension_command::init(cx);
+    extension_decorations::init(cx);
+    extension_panel::init(cx);
+
+    CommandPaletteCommands::update_global(cx, |commands, _| {
+        commands.add(CommandPaletteCommand {
+            name: "zed: install dev extension (watch)".to_string(),
+            action: Box::new(InstallDevExtension { watch: true }),
+        });
+    });
+
     cx.observe_new(move |workspace: &mut Workspace, window, cx| {
         let Some(window) = window else {
             return;
@@ -72,7 +102,7 @@ pub fn init(cx: &mut App) {
                         .items()
                         .find_map(|item| item.downcast::<ExtensionsPage>());
 
-                    if let Some(existing) = existing {
+                    let extensions_page = if let Some(existing) = existing {
                         if provides_filter.is_some() {
                             existing.update(cx, |extensions_page, cx| {
                                 extensions_page.change_provides_filter(provides_filter, cx);
@@ -80,21 +110,30 @@ pub fn init(cx: &mut App) {
                         }
 
                         workspace.activate_item(&existing, true, true, window, cx);
+                        existing
                     } else {
                         let extensions_page =
                             ExtensionsPage::new(workspace, provides_filter, window, cx);
                         workspace.add_item_to_active_pane(
-                            Box::new(extensions_page),
+                            Box::new(extensions_page.clone()),
                             None,
                             true,
                             window,
                             cx,
-                        )
+                        );
+                        extensions_page
+                    };
+
+                    if let Some(id) = &action.id {
+                        extensions_page.update(cx, |extensions_page, cx| {
+                            extensions_page.set_search_query(id, window, cx);
+                        });
                     }
                 },
             )
-            .register_action(move |workspace, _: &InstallDevExtension, window, cx| {
+            .register_action(move |workspace, action: &InstallDevExtension, window, cx| {
                 let store = ExtensionStore::global(cx);
+                let watch = action.watch;
                 let prompt = workspace.prompt_for_open_path(
                     gpui::PathPromptOptions {
                         files: false,
@@ -130,7 +169,15 @@ pub fn init(cx: &mut App) {
                             .ok()?;
 
                         match install_task.await {
-                            Ok(_) => {}
+                            Ok(extension_id) => {
+                                if watch {
+                                    store
+                                        .update(cx, |store, cx| {
+                                            store.watch_dev_extension(extension_id, cx)
+                                        })
+                                        .ok();
+                                }
+                            }
                             Err(err) => {
                                 workspace_handle
                                     .update(cx, |workspace, cx| {
@@ -331,6 +378,8 @@ impl ExtensionsPage {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        self.request_pending_capabilities(extension_id, window, cx);
+
         let extension_store = ExtensionStore::global(cx).read(cx);
         let themes = extension_store
             .extension_themes(extension_id)
@@ -370,6 +419,61 @@ impl ExtensionsPage {
         }
     }
 
+    /// Asks the user to grant the capabilities that the extension declares.
+    fn request_pending_capabilities(
+        &mut self,
+        extension_id: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let extension_store = ExtensionStore::global(cx);
+        let store = extension_store.read(cx);
+        let capabilities = store.pending_capabilities(extension_id);
+        let Some(entry) = store.extension_index.extensions.get(extension_id) else {
+            return;
+        };
+        if capabilities.is_empty() {
+            return;
+        }
+
+        let extension_id = entry.manifest.id.clone();
+        let message = format!("Allow {} to do the following?", entry.manifest.name);
+        let detail = capabilities
+            .iter()
+            .map(|capability| format!("• {}", capability_description(capability)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            &message,
+            Some(&detail),
+            &["Allow", "Don't Allow"],
+            cx,
+        );
+        cx.spawn(async move |_, cx| {
+            if answer.await? != 0 {
+                return anyhow::Ok(());
+            }
+            extension_store.update(cx, |store, cx| {
+                store.grant_capabilities(extension_id, capabilities, cx)
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn show_extension_permissions(
+        &mut self,
+        extension_id: Arc<str>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                ExtensionPermissionsModal::toggle(extension_id, workspace, window, cx)
+            })
+            .ok();
+    }
+
     /// Returns whether a dev extension currently exists for the extension with the given ID.
     fn dev_extension_exists(extension_id: &str, cx: &mut Context<Self>) -> bool {
         let extension_store = ExtensionStore::global(cx).read(cx);
@@ -508,6 +612,12 @@ impl ExtensionsPage {
         let status = Self::extension_status(&extension.id, cx);
 
         let repository_url = extension.repository.clone();
+        let store = ExtensionStore::global(cx);
+        let is_watching = store.read(cx).is_watching_dev_extension(&extension.id);
+        let has_build_log = store
+            .read(cx)
+            .dev_extension_build_log(&extension.id)
+            .is_some();
 
         ExtensionCard::new()
             .child(
@@ -527,6 +637,32 @@ impl ExtensionsPage {
                         h_flex()
                             .gap_2()
                             .justify_between()
+                            .child(
+                                Button::new(
+                                    SharedString::from(format!("watch-{}", extension.id)),
+                                    if is_watching {
+                                        "Stop Watching"
+                                    } else {
+                                        "Watch"
+                                    },
+                                )
+                                .on_click({
+                                    let extension_id = extension.id.clone();
+                                    move |_, _, cx| {
+                                        ExtensionStore::global(cx).update(cx, |store, cx| {
+                                            if store.is_watching_dev_extension(&extension_id) {
+                                                store.unwatch_dev_extension(&extension_id, cx)
+                                            } else {
+                                                store.watch_dev_extension(extension_id.clone(), cx)
+                                            }
+                                        });
+                                    }
+                                })
+                                .color(Color::Accent)
+                                .tooltip(Tooltip::text(
+                                    "Rebuild and reload the extension when its sources change",
+                                )),
+                            )
                             .child(
                                 Button::new(
                                     SharedString::from(format!("rebuild-{}", extension.id)),
@@ -602,7 +738,35 @@ impl ExtensionsPage {
                             }
                         }))
                         .tooltip(Tooltip::text(repository_url.clone()))
-                    })),
+                    }))
+                    .when(has_build_log, |this| {
+                        this.child(
+                            IconButton::new(
+                                SharedString::from(format!("build-log-{}", extension.id)),
+                                IconName::FileText,
+                            )
+                            .icon_color(Color::Accent)
+                            .icon_size(IconSize::Small)
+                            .style(ButtonStyle::Filled)
+                            .on_click(cx.listener({
+                                let extension_id = extension.id.clone();
+                                move |this, _, window, cx| {
+                                    let extension_id = extension_id.clone();
+                                    this.workspace
+                                        .update(cx, |workspace, cx| {
+                                            extension_build_log::open_build_log(
+                                                workspace,
+                                                extension_id,
+                                                window,
+                                                cx,
+                                            )
+                                        })
+                                        .ok();
+                                }
+                            }))
+                            .tooltip(Tooltip::text("View Build Log")),
+                        )
+                    }),
             )
     }
 
@@ -626,6 +790,11 @@ impl ExtensionsPage {
             ExtensionStatus::Installed(installed_version) => Some(installed_version),
             _ => None,
         };
+        let has_pending_capabilities = installed_version.is_some()
+            && !ExtensionStore::global(cx)
+                .read(cx)
+                .pending_capabilities(&extension.id)
+                .is_empty();
 
         ExtensionCard::new()
             .overridden_by_dev_extension(has_dev_extension)
@@ -682,6 +851,25 @@ impl ExtensionsPage {
                         h_flex()
                             .gap_2()
                             .justify_between()
+                            .when(has_pending_capabilities, |this| {
+                                this.child(
+                                    Button::new(
+                                        SharedString::from(format!("permissions-{}", extension.id)),
+                                        "Review Permissions",
+                                    )
+                                    .color(Color::Warning)
+                                    .on_click(cx.listener({
+                                        let extension_id = extension.id.clone();
+                                        move |this, _, window, cx| {
+                                            this.show_extension_permissions(
+                                                extension_id.clone(),
+                                                window,
+                                                cx,
+                                            )
+                                        }
+                                    })),
+                                )
+                            })
                             .children(upgrade_button)
                             .child(install_or_uninstall_button),
                     ),
@@ -775,8 +963,26 @@ impl ExtensionsPage {
         window: &mut Window,
         cx: &mut App,
     ) -> Entity<ContextMenu> {
+        let declares_capabilities = ExtensionStore::global(cx)
+            .read(cx)
+            .extension_index
+            .extensions
+            .get(&extension_id)
+            .is_some_and(|entry| !entry.manifest.capabilities.is_empty());
         let context_menu = ContextMenu::build(window, cx, |context_menu, window, _| {
             context_menu
+                .when(declares_capabilities, |context_menu| {
+                    context_menu.entry(
+                        "Manage Permissions...",
+                        None,
+                        window.handler_for(this, {
+                            let extension_id = extension_id.clone();
+                            move |this, window, cx| {
+                                this.show_extension_permissions(extension_id.clone(), window, cx)
+                            }
+                        }),
+                    )
+                })
                 .entry(
                     "Install Another Version...",
                     None,
@@ -1049,6 +1255,14 @@ impl ExtensionsPage {
         }));
     }
 
+    /// Replaces the search query, such as to show an extension that was linked to.
+    pub fn set_search_query(&mut self, query: &str, window: &mut Window, cx: &mut Context<Self>) {
+        self.query_editor.update(cx, |query_editor, cx| {
+            query_editor.set_text(query, window, cx);
+        });
+        self.refresh_search(cx);
+    }
+
     pub fn search_query(&self, cx: &mut App) -> Option<String> {
         let search = self.query_editor.read(cx).text(cx);
         if search.trim().is_empty() {
@@ -1264,7 +1478,10 @@ impl Render for ExtensionsPage {
                                     .style(ButtonStyle::Filled)
                                     .size(ButtonSize::Large)
                                     .on_click(|_event, window, cx| {
-                                        window.dispatch_action(Box::new(InstallDevExtension), cx)
+                                        window.dispatch_action(
+                                            Box::new(InstallDevExtension::default()),
+                                            cx,
+                                        )
                                     }),
                             ),
                     )
@@ -1,4 +1,4 @@
-use crate::FakeFs;
+use crate::{FakeFs, Fs as _, RemoveOptions};
 use anyhow::{anyhow, Context as _, Result};
 use collections::{HashMap, HashSet};
 use futures::future::{self, BoxFuture};
@@ -9,6 +9,7 @@ use git::{
         PushOptions, Remote, RepoPath, ResetMode,
     },
     status::{FileStatus, GitStatus, StatusCode, TrackedStatus, UnmergedStatus},
+    Oid,
 };
 use gpui::{AsyncApp, BackgroundExecutor};
 use ignore::gitignore::GitignoreBuilder;
@@ -34,6 +35,9 @@ pub struct FakeGitRepositoryState {
     pub current_branch_name: Option<String>,
     pub branches: HashSet<String>,
     pub simulated_index_write_error_message: Option<String>,
+    /// The contents of the files in the working directory when each checkpoint was taken.
+    pub checkpoints: HashMap<Oid, Vec<(PathBuf, Vec<u8>)>>,
+    next_checkpoint_id: u64,
 }
 
 impl FakeGitRepositoryState {
@@ -48,6 +52,8 @@ impl FakeGitRepositoryState {
             current_branch_name: Default::default(),
             branches: Default::default(),
             simulated_index_write_error_message: Default::default(),
+            checkpoints: Default::default(),
+            next_checkpoint_id: 0,
         }
     }
 }
@@ -76,6 +82,17 @@ impl FakeGitRepository {
         }
         .boxed()
     }
+
+    /// Returns the files in the working directory, leaving out the repository itself.
+    fn working_directory_files(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        let Some(work_directory) = self.dot_git_path.parent() else {
+            return Vec::new();
+        };
+        let mut files = self.fs.files_with_contents(work_directory);
+        files.retain(|(path, _)| !path.starts_with(&self.dot_git_path));
+        files.sort();
+        files
+    }
 }
 
 impl GitRepository for FakeGitRepository {
@@ -410,23 +427,74 @@ impl GitRepository for FakeGitRepository {
     }
 
     fn checkpoint(&self) -> BoxFuture<'static, Result<GitRepositoryCheckpoint>> {
-        unimplemented!()
+        let files = self.working_directory_files();
+        self.with_state_async(false, move |state| {
+            state.next_checkpoint_id += 1;
+            let mut bytes = [0; 20];
+            bytes[..8].copy_from_slice(&state.next_checkpoint_id.to_be_bytes());
+            let commit_sha = Oid::from_bytes(&bytes)?;
+            state.checkpoints.insert(commit_sha, files);
+            Ok(GitRepositoryCheckpoint::fake(commit_sha))
+        })
     }
 
-    fn restore_checkpoint(&self, _checkpoint: GitRepositoryCheckpoint) -> BoxFuture<Result<()>> {
-        unimplemented!()
+    fn restore_checkpoint(&self, checkpoint: GitRepositoryCheckpoint) -> BoxFuture<Result<()>> {
+        let fs = self.fs.clone();
+        let current_files = self.working_directory_files();
+        let checkpoint_files = self.with_state_async(false, move |state| {
+            state
+                .checkpoints
+                .get(&checkpoint.commit_sha())
+                .cloned()
+                .context("checkpoint not found")
+        });
+        async move {
+            let checkpoint_files = checkpoint_files.await?;
+            for (path, _) in &current_files {
+                if !checkpoint_files
+                    .iter()
+                    .any(|(checkpoint_path, _)| checkpoint_path == path)
+                {
+                    fs.remove_file(path, RemoveOptions::default()).await?;
+                }
+            }
+            for (path, content) in checkpoint_files {
+                if current_files.contains(&(path.clone(), content.clone())) {
+                    continue;
+                }
+                if let Some(parent) = path.parent() {
+                    fs.create_dir(parent).await?;
+                }
+                fs.write_file_internal(path, content)?;
+            }
+            Ok(())
+        }
+        .boxed()
     }
 
     fn compare_checkpoints(
         &self,
-        _left: GitRepositoryCheckpoint,
-        _right: GitRepositoryCheckpoint,
+        left: GitRepositoryCheckpoint,
+        right: GitRepositoryCheckpoint,
     ) -> BoxFuture<Result<bool>> {
-        unimplemented!()
+        self.with_state_async(false, move |state| {
+            let left = state
+                .checkpoints
+                .get(&left.commit_sha())
+                .context("checkpoint not found")?;
+            let right = state
+                .checkpoints
+                .get(&right.commit_sha())
+                .context("checkpoint not found")?;
+            Ok(left == right)
+        })
     }
 
-    fn delete_checkpoint(&self, _checkpoint: GitRepositoryCheckpoint) -> BoxFuture<Result<()>> {
-        unimplemented!()
+    fn delete_checkpoint(&self, checkpoint: GitRepositoryCheckpoint) -> BoxFuture<Result<()>> {
+        self.with_state_async(false, move |state| {
+            state.checkpoints.remove(&checkpoint.commit_sha());
+            Ok(())
+        })
     }
 
     fn diff_checkpoints(
@@ -103,6 +103,15 @@ pub trait Fs: Send + Sync {
     async fn trash_file(&self, path: &Path, options: RemoveOptions) -> Result<()> {
         self.remove_file(path, options).await
     }
+    /// Whether files and directories that were moved to the trash can be restored with
+    /// [`Fs::restore_from_trash`].
+    fn can_restore_from_trash(&self) -> bool {
+        false
+    }
+    /// Moves the file or directory that was moved to the trash from `path` back there.
+    async fn restore_from_trash(&self, path: &Path) -> Result<()> {
+        Err(anyhow!("cannot restore {path:?} from the trash"))
+    }
     async fn open_handle(&self, path: &Path) -> Result<Arc<dyn FileHandle>>;
     async fn open_sync(&self, path: &Path) -> Result<Box<dyn io::Read + Send + Sync>>;
     async fn load(&self, path: &Path) -> Result<String> {
@@ -111,6 +120,7 @@ pub trait Fs: Send + Sync {
     async fn load_bytes(&self, path: &Path) -> Result<Vec<u8>>;
     async fn atomic_write(&self, path: PathBuf, text: String) -> Result<()>;
     async fn save(&self, path: &Path, text: &Rope, line_ending: LineEnding) -> Result<()>;
+    async fn write(&self, path: &Path, content: &[u8]) -> Result<()>;
     async fn canonicalize(&self, path: &Path) -> Result<PathBuf>;
     async fn is_file(&self, path: &Path) -> bool;
     async fn is_dir(&self, path: &Path) -> bool;
@@ -244,6 +254,10 @@ impl From<MTime> for proto::Timestamp {
 pub struct RealFs {
     git_binary_path: Option<PathBuf>,
     executor: BackgroundExecutor,
+    /// Where the files and directories that were moved to the trash are in it, by their original
+    /// paths, since macOS doesn't record where they came from in a way that can be read.
+    #[cfg(target_os = "macos")]
+    trashed_paths: parking_lot::Mutex<collections::HashMap<PathBuf, PathBuf>>,
 }
 
 pub trait FileHandle: Send + Sync + std::fmt::Debug {
@@ -299,6 +313,8 @@ impl RealFs {
         Self {
             git_binary_path,
             executor,
+            #[cfg(target_os = "macos")]
+            trashed_paths: Default::default(),
         }
     }
 }
@@ -423,22 +439,40 @@ impl Fs for RealFs {
     #[cfg(target_os = "macos")]
     async fn trash_file(&self, path: &Path, _options: RemoveOptions) -> Result<()> {
         use cocoa::{
-            base::{id, nil},
+            base::{id, nil, NO},
             foundation::{NSAutoreleasePool, NSString},
         };
-        use objc::{class, msg_send, sel, sel_impl};
+        use objc::{class, msg_send, runtime::BOOL, sel, sel_impl};
+        use std::ffi::CStr;
 
-        unsafe {
+        let trashed_path = unsafe {
             unsafe fn ns_string(string: &str) -> id {
                 NSString::alloc(nil).init_str(string).autorelease()
             }
 
             let url: id = msg_send![class!(NSURL), fileURLWithPath: ns_string(path.to_string_lossy().as_ref())];
-            let array: id = msg_send![class!(NSArray), arrayWithObject: url];
-            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
-
-            let _: id = msg_send![workspace, recycleURLs: array completionHandler: nil];
-        }
+            let file_manager: id = msg_send![class!(NSFileManager), defaultManager];
+            let mut trashed_url: id = nil;
+            let mut error: id = nil;
+            let trashed: BOOL = msg_send![
+                file_manager,
+                trashItemAtURL: url
+                resultingItemURL: &mut trashed_url
+                error: &mut error
+            ];
+            if trashed == NO {
+                return Err(anyhow!("failed to move {path:?} to the trash"));
+            }
+            let trashed_path: id = msg_send![trashed_url, path];
+            PathBuf::from(
+                CStr::from_ptr(trashed_path.UTF8String())
+                    .to_string_lossy()
+                    .into_owned(),
+            )
+        };
+        self.trashed_paths
+            .lock()
+            .insert(path.to_path_buf(), trashed_path);
         Ok(())
     }
 
@@ -488,6 +522,76 @@ impl Fs for RealFs {
         self.trash_file(path, options).await
     }
 
+    fn can_restore_from_trash(&self) -> bool {
+        cfg!(any(
+            target_os = "macos",
+            target_os = "linux",
+            target_os = "freebsd"
+        ))
+    }
+
+    #[cfg(target_os = "macos")]
+    async fn restore_from_trash(&self, path: &Path) -> Result<()> {
+        let trashed_path = self
+            .trashed_paths
+            .lock()
+            .get(path)
+            .cloned()
+            .ok_or_else(|| anyhow!("{path:?} is not in the trash"))?;
+        if smol::fs::symlink_metadata(path).await.is_ok() {
+            return Err(anyhow!("{path:?} already exists"));
+        }
+        smol::fs::rename(&trashed_path, path).await?;
+        self.trashed_paths.lock().remove(path);
+        Ok(())
+    }
+
+    /// Restores the file or directory from the home trash, as described by the FreeDesktop.org
+    /// trash specification, which is where the trash portal moves them to.
+    #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+    async fn restore_from_trash(&self, path: &Path) -> Result<()> {
+        let trash_dir = std::env::var_os("XDG_DATA_HOME")
+            .filter(|data_home| !data_home.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| util::paths::home_dir().join(".local/share"))
+            .join("Trash");
+
+        // The same path can be trashed more than once, so restore the one trashed last.
+        let mut latest_trashed: Option<(String, PathBuf)> = None;
+        let mut info_files = smol::fs::read_dir(trash_dir.join("info")).await?;
+        while let Some(info_file) = info_files.next().await {
+            let info_path = info_file?.path();
+            if !info_path
+                .extension()
+                .is_some_and(|extension| extension == "trashinfo")
+            {
+                continue;
+            }
+            let Ok(content) = smol::fs::read_to_string(&info_path).await else {
+                continue;
+            };
+            if let Some((original_path, deletion_date)) = parse_trash_info(&content) {
+                if original_path == path
+                    && latest_trashed
+                        .as_ref()
+                        .map_or(true, |(latest_date, _)| deletion_date > *latest_date)
+                {
+                    latest_trashed = Some((deletion_date, info_path));
+                }
+            }
+        }
+
+        let (_, info_path) =
+            latest_trashed.ok_or_else(|| anyhow!("{path:?} is not in the trash"))?;
+        let file_name = info_path.file_stem().context("invalid trash info path")?;
+        if smol::fs::symlink_metadata(path).await.is_ok() {
+            return Err(anyhow!("{path:?} already exists"));
+        }
+        smol::fs::rename(trash_dir.join("files").join(file_name), path).await?;
+        util::ResultExt::log_err(smol::fs::remove_file(&info_path).await);
+        Ok(())
+    }
+
     #[cfg(target_os = "windows")]
     async fn trash_dir(&self, path: &Path, _options: RemoveOptions) -> Result<()> {
         use util::paths::SanitizedPath;
@@ -567,6 +671,14 @@ impl Fs for RealFs {
         Ok(())
     }
 
+    async fn write(&self, path: &Path, content: &[u8]) -> Result<()> {
+        if let Some(path) = path.parent() {
+            self.create_dir(path).await?;
+        }
+        smol::fs::write(path, content).await?;
+        Ok(())
+    }
+
     async fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
         Ok(smol::fs::canonicalize(path).await?)
     }
@@ -868,6 +980,8 @@ struct FakeFsState {
     read_dir_call_count: usize,
     moves: std::collections::HashMap<u64, PathBuf>,
     home_dir: Option<PathBuf>,
+    /// The entries that were moved to the trash, with the paths they were moved from.
+    trash: Vec<(PathBuf, Arc<Mutex<FakeFsEntry>>)>,
 }
 
 #[cfg(any(test, feature = "test-support"))]
@@ -1054,6 +1168,7 @@ impl FakeFs {
                 metadata_call_count: 0,
                 moves: Default::default(),
                 home_dir: None,
+                trash: Vec::new(),
             }),
         });
 
@@ -1536,6 +1651,16 @@ impl FakeFs {
         result
     }
 
+    /// Keeps the entry at the given path, which is about to be removed, so that it can be restored
+    /// from the trash.
+    fn move_to_trash(&self, path: &Path) {
+        let path = normalize_path(path);
+        let mut state = self.state.lock();
+        if let Ok(entry) = state.read_path(&path) {
+            state.trash.push((path, entry));
+        }
+    }
+
     pub fn files(&self) -> Vec<PathBuf> {
         let mut result = Vec::new();
         let mut queue = collections::VecDeque::new();
@@ -1946,6 +2071,43 @@ impl Fs for FakeFs {
         Ok(())
     }
 
+    async fn trash_file(&self, path: &Path, options: RemoveOptions) -> Result<()> {
+        self.move_to_trash(path);
+        self.remove_file(path, options).await
+    }
+
+    async fn trash_dir(&self, path: &Path, options: RemoveOptions) -> Result<()> {
+        self.move_to_trash(path);
+        self.remove_dir(path, options).await
+    }
+
+    fn can_restore_from_trash(&self) -> bool {
+        true
+    }
+
+    async fn restore_from_trash(&self, path: &Path) -> Result<()> {
+        self.simulate_random_delay().await;
+
+        let path = normalize_path(path);
+        let mut state = self.state.lock();
+        let ix = state
+            .trash
+            .iter()
+            .rposition(|(trashed_path, _)| *trashed_path == path)
+            .ok_or_else(|| anyhow!("{path:?} is not in the trash"))?;
+        let entry = state.trash[ix].1.clone();
+        state.write_path(&path, |e| match e {
+            btree_map::Entry::Occupied(_) => Err(anyhow!("{path:?} already exists")),
+            btree_map::Entry::Vacant(e) => {
+                e.insert(entry);
+                Ok(())
+            }
+        })?;
+        state.trash.remove(ix);
+        state.emit_event([(path, Some(PathEventKind::Created))]);
+        Ok(())
+    }
+
     async fn open_sync(&self, path: &Path) -> Result<Box<dyn io::Read + Send + Sync>> {
         let bytes = self.load_internal(path).await?;
         Ok(Box::new(io::Cursor::new(bytes)))
@@ -1991,6 +2153,16 @@ impl Fs for FakeFs {
         Ok(())
     }
 
+    async fn write(&self, path: &Path, content: &[u8]) -> Result<()> {
+        self.simulate_random_delay().await;
+        let path = normalize_path(path);
+        if let Some(path) = path.parent() {
+            self.create_dir(path).await?;
+        }
+        self.write_file_internal(path, content.to_vec())?;
+        Ok(())
+    }
+
     async fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
         let path = normalize_path(path);
         self.simulate_random_delay().await;
@@ -2254,6 +2426,36 @@ pub async fn copy_recursive<'a>(
     Ok(())
 }
 
+/// Returns the original path of a file in the trash, and the date that it was moved there, from
+/// the contents of its `.trashinfo` file.
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+fn parse_trash_info(content: &str) -> Option<(PathBuf, String)> {
+    use std::os::unix::ffi::OsStringExt;
+
+    let mut original_path = None;
+    let mut deletion_date = None;
+    for line in content.lines() {
+        if let Some(path) = line.strip_prefix("Path=") {
+            // Paths are percent-encoded, like in URLs.
+            let mut bytes = Vec::with_capacity(path.len());
+            let mut chars = path.bytes();
+            while let Some(byte) = chars.next() {
+                if byte == b'%' {
+                    let high = char::from(chars.next()?).to_digit(16)?;
+                    let low = char::from(chars.next()?).to_digit(16)?;
+                    bytes.push((high * 16 + low) as u8);
+                } else {
+                    bytes.push(byte);
+                }
+            }
+            original_path = Some(PathBuf::from(std::ffi::OsString::from_vec(bytes)));
+        } else if let Some(date) = line.strip_prefix("DeletionDate=") {
+            deletion_date = Some(date.to_string());
+        }
+    }
+    Some((original_path?, deletion_date?))
+}
+
 async fn read_dir_items<'a>(fs: &'a dyn Fs, source: &'a Path) -> Result<Vec<(bool, PathBuf)>> {
     let mut items = Vec::new();
     read_recursive(fs, source, &mut items).await?;
@@ -2328,6 +2530,66 @@ mod tests {
     use serde_json::json;
     use util::path;
 
+    #[gpui::test]
+    async fn test_fake_fs_trash(executor: BackgroundExecutor) {
+        let fs = FakeFs::new(executor.clone());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "a": "A",
+                "dir": { "b": "B" }
+            }),
+        )
+        .await;
+
+        fs.trash_file(path!("/root/a").as_ref(), Default::default())
+            .await
+            .unwrap();
+        fs.trash_dir(
+            path!("/root/dir").as_ref(),
+            RemoveOptions {
+                recursive: true,
+                ignore_if_not_exists: false,
+            },
+        )
+        .await
+        .unwrap();
+        assert!(fs.files().is_empty());
+
+        fs.restore_from_trash(path!("/root/dir").as_ref())
+            .await
+            .unwrap();
+        fs.restore_from_trash(path!("/root/a").as_ref())
+            .await
+            .unwrap();
+        assert_eq!(
+            fs.files(),
+            [
+                PathBuf::from(path!("/root/a")),
+                PathBuf::from(path!("/root/dir/b"))
+            ]
+        );
+        assert!(fs
+            .restore_from_trash(path!("/root/a").as_ref())
+            .await
+            .is_err());
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+    #[test]
+    fn test_parse_trash_info() {
+        assert_eq!(
+            parse_trash_info(
+                "[Trash Info]\nPath=/home/user/my%20project/a%25b.rs\nDeletionDate=2024-05-01T10:20:30\n"
+            ),
+            Some((
+                PathBuf::from("/home/user/my project/a%b.rs"),
+                "2024-05-01T10:20:30".to_string()
+            ))
+        );
+        assert_eq!(parse_trash_info("[Trash Info]\nPath=/a\n"), None);
+    }
+
     #[gpui::test]
     async fn test_fake_fs(executor: BackgroundExecutor) {
         let fs = FakeFs::new(executor.clone());
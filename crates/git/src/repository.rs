@@ -362,6 +362,22 @@ pub struct GitRepositoryCheckpoint {
     commit_sha: Oid,
 }
 
+#[cfg(any(test, feature = "test-support"))]
+impl GitRepositoryCheckpoint {
+    /// Creates a checkpoint of a fake repository, which identifies it by its commit.
+    pub fn fake(commit_sha: Oid) -> Self {
+        Self {
+            ref_name: String::new(),
+            head_sha: None,
+            commit_sha,
+        }
+    }
+
+    pub fn commit_sha(&self) -> Oid {
+        self.commit_sha
+    }
+}
+
 impl GitRepository for RealGitRepository {
     fn reload_index(&self) {
         if let Ok(mut index) = self.repository.lock().index() {
@@ -0,0 +1,419 @@
+use anyhow::{Context as _, Result};
+use buffer_diff::BufferDiff;
+use editor::{Editor, EditorEvent};
+use gpui::{
+    AnyElement, AnyView, App, AppContext as _, AsyncApp, Entity, EventEmitter, FocusHandle,
+    Focusable, Render, Task,
+};
+use language::{Buffer, BufferEvent};
+use multi_buffer::MultiBuffer;
+use project::{Project, ProjectPath};
+use std::{
+    any::{Any, TypeId},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use ui::prelude::*;
+use workspace::{
+    item::{BreadcrumbText, Item, ItemEvent, TabContentParams},
+    searchable::SearchableItemHandle,
+    ItemNavHistory, ToolbarItemLocation, Workspace,
+};
+
+/// How long to wait after either file is edited before the diff is recalculated.
+const RECALCULATE_DIFF_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Shows the differences between two files, such as when running `zed --diff old new`. The new
+/// file can be edited, and the diff is updated as either file changes.
+pub struct FileDiffView {
+    editor: Entity<Editor>,
+    old_buffer: Entity<Buffer>,
+    new_buffer: Entity<Buffer>,
+    diff: Entity<BufferDiff>,
+    old_path: PathBuf,
+    new_path: PathBuf,
+    _recalculate_diff_task: Task<()>,
+}
+
+impl FileDiffView {
+    /// Opens the diff between the files at the given paths in the workspace's active pane.
+    pub fn open(
+        old_path: PathBuf,
+        new_path: PathBuf,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<Entity<Self>>> {
+        let project = workspace.project().clone();
+        cx.spawn_in(window, async move |workspace, cx| {
+            let old_buffer = open_abs_path(&project, &old_path, cx).await?;
+            let new_buffer = open_abs_path(&project, &new_path, cx).await?;
+            let diff = cx.new(|cx| BufferDiff::new(&new_buffer.read(cx).text_snapshot(), cx))?;
+            diff.update(cx, |diff, cx| {
+                diff.set_base_text(old_buffer.clone(), new_buffer.read(cx).text_snapshot(), cx)
+            })?
+            .await
+            .ok();
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let diff_view = cx.new(|cx| {
+                    FileDiffView::new(
+                        old_buffer, new_buffer, diff, old_path, new_path, project, window, cx,
+                    )
+                });
+                workspace.add_item_to_active_pane(
+                    Box::new(diff_view.clone()),
+                    None,
+                    true,
+                    window,
+                    cx,
+                );
+                diff_view
+            })
+        })
+    }
+
+    fn new(
+        old_buffer: Entity<Buffer>,
+        new_buffer: Entity<Buffer>,
+        diff: Entity<BufferDiff>,
+        old_path: PathBuf,
+        new_path: PathBuf,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::singleton(new_buffer.clone(), cx);
+            multibuffer.add_diff(diff.clone(), cx);
+            multibuffer
+        });
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::for_multibuffer(multibuffer, Some(project), window, cx);
+            editor.disable_inline_diagnostics();
+            editor.set_expand_all_diff_hunks(cx);
+            editor
+        });
+        cx.subscribe(&editor, |_, _, event: &EditorEvent, cx| {
+            cx.emit(event.clone())
+        })
+        .detach();
+        for buffer in [&old_buffer, &new_buffer] {
+            cx.subscribe(buffer, |this, _, event, cx| match event {
+                BufferEvent::Edited | BufferEvent::Reloaded => this.recalculate_diff(cx),
+                _ => {}
+            })
+            .detach();
+        }
+
+        Self {
+            editor,
+            old_buffer,
+            new_buffer,
+            diff,
+            old_path,
+            new_path,
+            _recalculate_diff_task: Task::ready(()),
+        }
+    }
+
+    fn recalculate_diff(&mut self, cx: &mut Context<Self>) {
+        let old_buffer = self.old_buffer.clone();
+        let new_buffer = self.new_buffer.clone();
+        let diff = self.diff.clone();
+        self._recalculate_diff_task = cx.spawn(async move |_, cx| {
+            cx.background_executor()
+                .timer(RECALCULATE_DIFF_DEBOUNCE)
+                .await;
+            let Ok(recalculated) = diff.update(cx, |diff, cx| {
+                diff.set_base_text(old_buffer, new_buffer.read(cx).text_snapshot(), cx)
+            }) else {
+                return;
+            };
+            recalculated.await.ok();
+        });
+    }
+}
+
+async fn open_abs_path(
+    project: &Entity<Project>,
+    abs_path: &Path,
+    cx: &mut AsyncApp,
+) -> Result<Entity<Buffer>> {
+    let (worktree, relative_path) = project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(abs_path, false, cx)
+        })?
+        .await
+        .with_context(|| format!("opening {abs_path:?}"))?;
+    let project_path = ProjectPath {
+        worktree_id: worktree.read_with(cx, |worktree, _| worktree.id())?,
+        path: relative_path.into(),
+    };
+    project
+        .update(cx, |project, cx| project.open_buffer(project_path, cx))?
+        .await
+        .with_context(|| format!("opening {abs_path:?}"))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+impl EventEmitter<EditorEvent> for FileDiffView {}
+
+impl Focusable for FileDiffView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for FileDiffView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Diff).color(Color::Muted))
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn navigate(
+        &mut self,
+        data: Box<dyn Any>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some(format!("{} ↔ {}", self.old_path.display(), self.new_path.display()).into())
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, _: &App) -> AnyElement {
+        Label::new(format!(
+            "{} ↔ {}",
+            file_name(&self.old_path),
+            file_name(&self.new_path)
+        ))
+        .color(if params.selected {
+            Color::Default
+        } else {
+            Color::Muted
+        })
+        .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("File Diff Opened")
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.editor.for_each_project_item(cx, f)
+    }
+
+    fn is_singleton(&self, _: &App) -> bool {
+        false
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.new_buffer.read(cx).is_dirty()
+    }
+
+    fn has_conflict(&self, cx: &App) -> bool {
+        self.new_buffer.read(cx).has_conflict()
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        true
+    }
+
+    fn save(
+        &mut self,
+        format: bool,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.editor.save(format, project, window, cx)
+    }
+
+    fn save_as(
+        &mut self,
+        _: Entity<Project>,
+        _: ProjectPath,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        unreachable!()
+    }
+
+    fn reload(
+        &mut self,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.editor.reload(project, window, cx)
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, theme: &theme::Theme, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.editor.breadcrumbs(theme, cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+impl Render for FileDiffView {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        self.editor.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use editor::test::editor_test_context::assert_state_with_diff;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use unindent::Unindent as _;
+    use util::path;
+
+    use super::*;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            editor::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_file_diff_view(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            json!({
+                "old.txt": "one\ntwo\nthree\n",
+                "new.txt": "one\nTWO\nthree\nfour\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                FileDiffView::open(
+                    path!("/test/old.txt").into(),
+                    path!("/test/new.txt").into(),
+                    workspace,
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        let editor = diff_view.update(cx, |diff_view, _| diff_view.editor.clone());
+        assert_state_with_diff(
+            &editor,
+            cx,
+            &"
+                ˇone
+              - two
+              + TWO
+                three
+              + four
+            "
+            .unindent(),
+        );
+
+        // The diff is updated when the old file changes.
+        let old_buffer = diff_view.update(cx, |diff_view, _| diff_view.old_buffer.clone());
+        old_buffer.update(cx, |buffer, cx| {
+            buffer.edit([(4..7, "TWO")], None, cx);
+        });
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.run_until_parked();
+        assert_state_with_diff(
+            &editor,
+            cx,
+            &"
+                ˇone
+                TWO
+                three
+              + four
+            "
+            .unindent(),
+        );
+    }
+}
@@ -7,7 +7,7 @@ use std::{fmt::Write, num::NonZeroU32, time::Duration};
 use text::{Point, Selection};
 use ui::{
     div, Button, ButtonCommon, Clickable, Context, FluentBuilder, IntoElement, LabelSize,
-    ParentElement, Render, Tooltip, Window,
+    ParentElement, Render, SharedString, Tooltip, Window,
 };
 use util::paths::FILE_ROW_COLUMN_DELIMITER;
 use workspace::{item::ItemHandle, StatusItemView, Workspace};
@@ -270,6 +270,10 @@ impl StatusItemView for CursorPosition {
 
         cx.notify();
     }
+
+    fn segment_name(&self, _: &App) -> Option<SharedString> {
+        Some("cursor_position".into())
+    }
 }
 
 #[derive(Clone, Copy, Default, PartialEq, JsonSchema, Deserialize, Serialize)]
@@ -294,7 +298,7 @@ impl Settings for LineIndicatorFormat {
     type FileContent = Option<LineIndicatorFormatContent>;
 
     fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> anyhow::Result<Self> {
-        let format = [sources.release_channel, sources.user]
+        let format = [sources.profile, sources.release_channel, sources.user]
             .into_iter()
             .find_map(|value| value.copied().flatten())
             .unwrap_or(sources.default.ok_or_else(Self::missing_default)?);
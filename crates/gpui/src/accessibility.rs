@@ -0,0 +1,281 @@
+//! Describes what's in a window to assistive technologies, like screen readers. Elements add
+//! nodes to a window's accessibility tree while they're painted, in the order they're painted,
+//! which is also the order in which screen readers visit them.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use crate::{Bounds, FocusId, Pixels, SharedString};
+
+/// What an element is, which tells assistive technologies how to present it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessibilityRole {
+    /// The root of a window's accessibility tree.
+    Window,
+    /// A group of related elements.
+    Group,
+    /// A button.
+    Button,
+    /// A checkbox, whose state is given by [`Accessibility::toggled`].
+    CheckBox,
+    /// A switch, whose state is given by [`Accessibility::toggled`].
+    Switch,
+    /// A single line of editable text.
+    TextInput,
+    /// Several lines of editable text, like a code editor.
+    MultilineTextInput,
+    /// Text that isn't editable.
+    Label,
+    /// A link.
+    Link,
+    /// A list of items.
+    List,
+    /// An item in a [`AccessibilityRole::List`].
+    ListItem,
+    /// A menu, like a context menu.
+    Menu,
+    /// An item in a [`AccessibilityRole::Menu`].
+    MenuItem,
+    /// A list of tabs.
+    TabList,
+    /// A tab in a [`AccessibilityRole::TabList`].
+    Tab,
+    /// A toolbar.
+    Toolbar,
+    /// A dialog, like a modal.
+    Dialog,
+    /// A message that's announced as soon as it appears, like a notification.
+    Alert,
+    /// A tree of items, like a file tree.
+    Tree,
+    /// An item in a [`AccessibilityRole::Tree`].
+    TreeItem,
+}
+
+/// How an element is presented to assistive technologies.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Accessibility {
+    /// What the element is.
+    pub role: AccessibilityRole,
+    /// The name of the element, like the text of a button.
+    pub label: Option<SharedString>,
+    /// The value of the element, like the text in an input.
+    pub value: Option<SharedString>,
+    /// Whether the element is checked, for checkboxes and switches.
+    pub toggled: Option<bool>,
+    /// Whether the element is selected, for items of lists, menus, tab lists and trees.
+    pub selected: Option<bool>,
+}
+
+impl Accessibility {
+    /// Creates the accessibility of an element with the given role.
+    pub fn new(role: AccessibilityRole) -> Self {
+        Self {
+            role,
+            label: None,
+            value: None,
+            toggled: None,
+            selected: None,
+        }
+    }
+
+    /// Sets the label of the element.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Sets the value of the element.
+    pub fn value(mut self, value: impl Into<SharedString>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Sets whether the element is checked.
+    pub fn toggled(mut self, toggled: bool) -> Self {
+        self.toggled = Some(toggled);
+        self
+    }
+
+    /// Sets whether the element is selected.
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = Some(selected);
+        self
+    }
+}
+
+/// Identifies a node in a window's accessibility tree across frames, for as long as the nodes
+/// painted before it under the same parent don't change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AccessibilityNodeId(pub u64);
+
+/// An element in a window's accessibility tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityNode {
+    /// The ID of the node.
+    pub id: AccessibilityNodeId,
+    /// How the element is presented.
+    pub accessibility: Accessibility,
+    /// The bounds of the element, in the window.
+    pub bounds: Bounds<Pixels>,
+    /// Whether the element can be focused.
+    pub focusable: bool,
+    /// The IDs of the node's children, in the order screen readers visit them.
+    pub children: Vec<AccessibilityNodeId>,
+}
+
+/// The accessibility tree of a window, as of the last frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityTree {
+    /// The nodes of the tree, with the root, whose role is [`AccessibilityRole::Window`], first
+    /// and each node before its descendants.
+    pub nodes: Vec<AccessibilityNode>,
+    /// The ID of the focused node, which is the root when no element with a node is focused.
+    pub focus: AccessibilityNodeId,
+}
+
+/// A node that an element added while it was painted.
+#[derive(Clone, Debug)]
+pub(crate) struct AccessibilityNodeRequest {
+    pub(crate) accessibility: Accessibility,
+    pub(crate) bounds: Bounds<Pixels>,
+    pub(crate) focus_id: Option<FocusId>,
+    /// How many nodes were being painted around this one.
+    pub(crate) depth: usize,
+}
+
+impl AccessibilityTree {
+    pub(crate) const ROOT_ID: AccessibilityNodeId = AccessibilityNodeId(0);
+
+    pub(crate) fn new(
+        requests: &[AccessibilityNodeRequest],
+        focus: Option<FocusId>,
+        bounds: Bounds<Pixels>,
+    ) -> Self {
+        let mut nodes = vec![AccessibilityNode {
+            id: Self::ROOT_ID,
+            accessibility: Accessibility::new(AccessibilityRole::Window),
+            bounds,
+            focusable: false,
+            children: Vec::new(),
+        }];
+        let mut focused_id = Self::ROOT_ID;
+        // The indices in `nodes` of the ancestors of the next node, by depth.
+        let mut ancestors = vec![0];
+        for request in requests {
+            ancestors.truncate(request.depth + 1);
+            let parent_ix = *ancestors.last().unwrap();
+            let parent = &mut nodes[parent_ix];
+            let mut hasher = DefaultHasher::new();
+            (parent.id, parent.children.len()).hash(&mut hasher);
+            let id = AccessibilityNodeId(hasher.finish());
+            parent.children.push(id);
+
+            if request.focus_id.is_some() && request.focus_id == focus {
+                focused_id = id;
+            }
+            ancestors.push(nodes.len());
+            nodes.push(AccessibilityNode {
+                id,
+                accessibility: request.accessibility.clone(),
+                bounds: request.bounds,
+                focusable: request.focus_id.is_some(),
+                children: Vec::new(),
+            });
+        }
+
+        Self {
+            nodes,
+            focus: focused_id,
+        }
+    }
+
+    /// The root of the tree.
+    pub fn root(&self) -> &AccessibilityNode {
+        &self.nodes[0]
+    }
+
+    /// The node with the given ID.
+    pub fn node(&self, id: AccessibilityNodeId) -> Option<&AccessibilityNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+}
+
+impl Default for AccessibilityTree {
+    fn default() -> Self {
+        Self::new(&[], None, Bounds::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as gpui, div, px, size, Accessibility, AccessibilityRole, Context, FocusHandle,
+        InteractiveElement, IntoElement, ParentElement, Render, Styled, TestAppContext, Window,
+    };
+
+    struct TestView {
+        focus_handle: FocusHandle,
+    }
+
+    impl Render for TestView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            div()
+                .size_full()
+                .accessibility(Accessibility::new(AccessibilityRole::Menu).label("Actions"))
+                .child(
+                    div().h(px(10.)).accessibility(
+                        Accessibility::new(AccessibilityRole::MenuItem).label("Copy"),
+                    ),
+                )
+                .child(
+                    div().h(px(10.)).child(
+                        div()
+                            .h(px(10.))
+                            .track_focus(&self.focus_handle)
+                            .accessibility(
+                                Accessibility::new(AccessibilityRole::MenuItem)
+                                    .label("Paste")
+                                    .selected(true),
+                            ),
+                    ),
+                )
+        }
+    }
+
+    #[gpui::test]
+    fn test_accessibility_tree(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_, cx| TestView {
+            focus_handle: cx.focus_handle(),
+        });
+        cx.simulate_resize(size(px(100.), px(100.)));
+        cx.update(|window, cx| {
+            window.focus(&view.read(cx).focus_handle);
+            window.draw(cx);
+
+            let tree = window.accessibility_tree();
+            let root = tree.root();
+            assert_eq!(root.accessibility.role, AccessibilityRole::Window);
+            assert_eq!(root.children.len(), 1);
+
+            let menu = tree.node(root.children[0]).unwrap();
+            assert_eq!(menu.accessibility.role, AccessibilityRole::Menu);
+            assert_eq!(menu.accessibility.label.as_deref(), Some("Actions"));
+            // Elements without accessibility don't add nodes, so the items are both children
+            // of the menu.
+            let labels = menu
+                .children
+                .iter()
+                .map(|id| tree.node(*id).unwrap().accessibility.label.as_deref())
+                .collect::<Vec<_>>();
+            assert_eq!(labels, [Some("Copy"), Some("Paste")]);
+
+            let focused = tree.node(tree.focus).unwrap();
+            assert_eq!(focused.id, menu.children[1]);
+            assert!(focused.focusable);
+            assert_eq!(focused.accessibility.selected, Some(true));
+        });
+    }
+}
@@ -49,6 +49,9 @@ mod test_context;
 /// The duration for which futures returned from [Context::on_app_quit] can run before the application fully quits.
 pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_millis(100);
 
+/// The default duration to wait for the next keystroke of a multi-stroke key binding.
+pub const DEFAULT_KEY_SEQUENCE_TIMEOUT: Duration = Duration::from_secs(1);
+
 /// Temporary(?) wrapper around [`RefCell<App>`] to help us debug any double borrows.
 /// Strongly consider removing after stabilization.
 #[doc(hidden)]
@@ -247,6 +250,7 @@ pub struct App {
     pub(crate) window_handles: FxHashMap<WindowId, AnyWindowHandle>,
     pub(crate) focus_handles: Arc<FocusMap>,
     pub(crate) keymap: Rc<RefCell<Keymap>>,
+    pub(crate) key_sequence_timeout: Duration,
     pub(crate) keyboard_layout: SharedString,
     pub(crate) global_action_listeners:
         FxHashMap<TypeId, Vec<Rc<dyn Fn(&dyn Any, DispatchPhase, &mut Self)>>>,
@@ -257,6 +261,7 @@ pub struct App {
     // TypeId is the type of the event that the listener callback expects
     pub(crate) event_listeners: SubscriberSet<EntityId, (TypeId, Listener)>,
     pub(crate) keystroke_observers: SubscriberSet<(), KeystrokeObserver>,
+    pub(crate) keystroke_interceptors: SubscriberSet<(), KeystrokeObserver>,
     pub(crate) keyboard_layout_observers: SubscriberSet<(), Handler>,
     pub(crate) release_listeners: SubscriberSet<EntityId, ReleaseListener>,
     pub(crate) global_observers: SubscriberSet<TypeId, Handler>,
@@ -264,6 +269,7 @@ pub struct App {
     pub(crate) window_closed_observers: SubscriberSet<(), WindowClosedHandler>,
     pub(crate) layout_id_buffer: Vec<LayoutId>, // We recycle this memory across layout requests.
     pub(crate) propagate_event: bool,
+    reduce_motion: bool,
     pub(crate) prompt_builder: Option<PromptBuilder>,
     pub(crate) window_invalidators_by_entity:
         FxHashMap<EntityId, FxHashMap<WindowId, WindowInvalidator>>,
@@ -313,6 +319,7 @@ impl App {
                 window_handles: FxHashMap::default(),
                 focus_handles: Arc::new(RwLock::new(SlotMap::with_key())),
                 keymap: Rc::new(RefCell::new(Keymap::default())),
+                key_sequence_timeout: DEFAULT_KEY_SEQUENCE_TIMEOUT,
                 keyboard_layout,
                 global_action_listeners: FxHashMap::default(),
                 pending_effects: VecDeque::new(),
@@ -324,12 +331,14 @@ impl App {
                 event_listeners: SubscriberSet::new(),
                 release_listeners: SubscriberSet::new(),
                 keystroke_observers: SubscriberSet::new(),
+                keystroke_interceptors: SubscriberSet::new(),
                 keyboard_layout_observers: SubscriberSet::new(),
                 global_observers: SubscriberSet::new(),
                 quit_observers: SubscriberSet::new(),
                 window_closed_observers: SubscriberSet::new(),
                 layout_id_buffer: Default::default(),
                 propagate_event: true,
+                reduce_motion: false,
                 prompt_builder: Some(PromptBuilder::Default),
 
                 #[cfg(any(test, feature = "test-support", debug_assertions))]
@@ -788,6 +797,29 @@ impl App {
         self.platform.should_auto_hide_scrollbars()
     }
 
+    /// Returns whether the user has asked the platform to increase the contrast of the UI.
+    pub fn system_prefers_high_contrast(&self) -> bool {
+        self.platform.prefers_high_contrast()
+    }
+
+    /// Returns whether the user has asked the platform to reduce motion in the UI.
+    pub fn system_prefers_reduced_motion(&self) -> bool {
+        self.platform.prefers_reduced_motion()
+    }
+
+    /// Returns whether animations should be skipped, showing their final frames right away.
+    pub fn reduce_motion(&self) -> bool {
+        self.reduce_motion
+    }
+
+    /// Sets whether animations should be skipped, showing their final frames right away.
+    pub fn set_reduce_motion(&mut self, reduce_motion: bool) {
+        if self.reduce_motion != reduce_motion {
+            self.reduce_motion = reduce_motion;
+            self.refresh_windows();
+        }
+    }
+
     /// Restarts the application.
     pub fn restart(&self, binary_path: Option<PathBuf>) {
         self.platform.restart(binary_path)
@@ -1281,18 +1313,52 @@ impl App {
         )
     }
 
+    /// Register a callback to be invoked when a keystroke is received by the application in any
+    /// window, before it is matched against key bindings. Call [`App::stop_propagation`] from the
+    /// callback to consume the keystroke, so that it doesn't trigger any action or text input.
+    pub fn intercept_keystrokes(
+        &mut self,
+        mut f: impl FnMut(&KeystrokeEvent, &mut Window, &mut App) + 'static,
+    ) -> Subscription {
+        let (subscription, activate) = self.keystroke_interceptors.insert(
+            (),
+            Box::new(move |event, window, cx| {
+                f(event, window, cx);
+                true
+            }),
+        );
+        activate();
+        subscription
+    }
+
     /// Register key bindings.
     pub fn bind_keys(&mut self, bindings: impl IntoIterator<Item = KeyBinding>) {
         self.keymap.borrow_mut().add_bindings(bindings);
         self.pending_effects.push_back(Effect::RefreshWindows);
     }
 
+    /// Returns the application's keymap, which contains every registered key binding.
+    pub fn key_bindings(&self) -> Rc<RefCell<Keymap>> {
+        self.keymap.clone()
+    }
+
     /// Clear all key bindings in the app.
     pub fn clear_key_bindings(&mut self) {
         self.keymap.borrow_mut().clear();
         self.pending_effects.push_back(Effect::RefreshWindows);
     }
 
+    /// How long to wait for the next keystroke of a multi-stroke key binding before the pending
+    /// keystrokes are dispatched on their own.
+    pub fn key_sequence_timeout(&self) -> Duration {
+        self.key_sequence_timeout
+    }
+
+    /// Set how long to wait for the next keystroke of a multi-stroke key binding.
+    pub fn set_key_sequence_timeout(&mut self, timeout: Duration) {
+        self.key_sequence_timeout = timeout;
+    }
+
     /// Register a global listener for actions invoked via the keyboard.
     pub fn on_action<A: Action>(&mut self, listener: impl Fn(&A, &mut Self) + 'static) {
         self.global_action_listeners
@@ -1508,6 +1574,16 @@ impl App {
         self.active_drag.is_some()
     }
 
+    /// Stops the current drag without dropping it, returning whether there was one.
+    pub fn stop_active_drag(&mut self, window: &mut Window) -> bool {
+        if self.active_drag.take().is_some() {
+            window.refresh();
+            true
+        } else {
+            false
+        }
+    }
+
     /// Set the prompt renderer for GPUI. This will replace the default or platform specific
     /// prompts with this custom implementation.
     pub fn set_prompt_builder(
@@ -115,7 +115,11 @@ impl<E: IntoElement + 'static> Element for AnimationElement<E> {
                 state.start.elapsed().as_secs_f32() / self.animation.duration.as_secs_f32();
 
             let mut done = false;
-            if delta > 1.0 {
+            // When motion is reduced, animations show their final frame right away.
+            if cx.reduce_motion() {
+                done = true;
+                delta = 1.0;
+            } else if delta > 1.0 {
                 if self.animation.oneshot {
                     done = true;
                     delta = 1.0;
@@ -16,9 +16,9 @@
 //! constructed by combining these two systems into an all-in-one element.
 
 use crate::{
-    point, px, size, Action, AnyDrag, AnyElement, AnyTooltip, AnyView, App, Bounds, ClickEvent,
-    DispatchPhase, Element, ElementId, Entity, FocusHandle, Global, GlobalElementId, Hitbox,
-    HitboxId, IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent, LayoutId,
+    point, px, size, Accessibility, Action, AnyDrag, AnyElement, AnyTooltip, AnyView, App, Bounds,
+    ClickEvent, DispatchPhase, Element, ElementId, Entity, FocusHandle, Global, GlobalElementId,
+    Hitbox, HitboxId, IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent, LayoutId,
     ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
     ParentElement, Pixels, Point, Render, ScrollWheelEvent, SharedString, Size, Style,
     StyleRefinement, Styled, Task, TooltipId, Visibility, Window,
@@ -572,6 +572,13 @@ pub trait InteractiveElement: Sized {
         FocusableWrapper { element: self }
     }
 
+    /// Describe this element to assistive technologies, like screen readers, by adding a node
+    /// to the window's accessibility tree that the nodes of its children are nested in.
+    fn accessibility(mut self, accessibility: Accessibility) -> Self {
+        self.interactivity().accessibility = Some(accessibility);
+        self
+    }
+
     /// Set the keymap context for this element. This will be used to determine
     /// which action to dispatch from the keymap.
     fn key_context<C, E>(mut self, key_context: C) -> Self
@@ -1363,6 +1370,7 @@ pub struct Interactivity {
     pub(crate) hover_listener: Option<Box<dyn Fn(&bool, &mut Window, &mut App)>>,
     pub(crate) tooltip_builder: Option<TooltipBuilder>,
     pub(crate) occlude_mouse: bool,
+    pub(crate) accessibility: Option<Accessibility>,
 
     #[cfg(debug_assertions)]
     pub(crate) location: Option<core::panic::Location<'static>>,
@@ -1635,7 +1643,12 @@ impl Interactivity {
                                     }
 
                                     self.paint_keyboard_listeners(window, cx);
-                                    f(&style, window, cx);
+                                    window.with_accessibility_node(
+                                        self.accessibility.clone(),
+                                        bounds,
+                                        self.tracked_focus_handle.as_ref(),
+                                        |window| f(&style, window, cx),
+                                    );
 
                                     if hitbox.is_some() {
                                         if let Some(group) = self.group.as_ref() {
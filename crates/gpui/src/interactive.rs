@@ -392,6 +392,16 @@ impl Render for ExternalPaths {
     }
 }
 
+/// Something to drag out of the window with [`crate::Window::start_external_drag`], for other
+/// applications to drop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalDragItem {
+    /// Plain text.
+    Text(String),
+    /// Files and folders, by their absolute paths.
+    Paths(Vec<PathBuf>),
+}
+
 /// A file drop event from the platform, generated when files are dragged and dropped onto the window.
 #[derive(Debug, Clone)]
 pub enum FileDropEvent {
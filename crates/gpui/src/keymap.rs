@@ -177,6 +177,84 @@ impl Keymap {
         (bindings, is_pending.unwrap_or_default())
     }
 
+    /// Returns the bindings that could still match if more keystrokes were typed after the given
+    /// input, in precedence order. When several bindings use the same keystrokes, only the one that
+    /// would be dispatched is returned, and bindings disabled with `null` are omitted.
+    pub fn pending_bindings_for_input(
+        &self,
+        input: &[Keystroke],
+        context_stack: &[KeyContext],
+    ) -> Vec<KeyBinding> {
+        let mut bindings: Vec<(KeyBinding, usize)> = Vec::new();
+
+        for binding in self.bindings().rev() {
+            if binding.match_keystrokes(input) != Some(true) {
+                continue;
+            }
+            let Some(depth) = (0..=context_stack.len())
+                .rev()
+                .find(|depth| self.binding_enabled(binding, &context_stack[0..*depth]))
+            else {
+                continue;
+            };
+
+            if let Some(existing) = bindings
+                .iter_mut()
+                .find(|(existing, _)| existing.keystrokes == binding.keystrokes)
+            {
+                if depth > existing.1 {
+                    *existing = (binding.clone(), depth);
+                }
+            } else {
+                bindings.push((binding.clone(), depth));
+            }
+        }
+
+        bindings
+            .into_iter()
+            .map(|(binding, _)| binding)
+            .filter(|binding| !is_no_action(&*binding.action))
+            .collect()
+    }
+
+    /// For each binding, in the order they were added, returns the index of a later binding that
+    /// takes precedence over it everywhere it is enabled, so that it can never be triggered.
+    /// Disabling a binding with `null` counts as shadowing it.
+    pub fn shadowing_bindings(&self) -> Vec<Option<usize>> {
+        let mut indices_by_keystrokes: HashMap<&[Keystroke], Vec<usize>> = HashMap::default();
+        for (ix, binding) in self.bindings.iter().enumerate() {
+            indices_by_keystrokes
+                .entry(binding.keystrokes.as_slice())
+                .or_default()
+                .push(ix);
+        }
+
+        let mut shadowing = vec![None; self.bindings.len()];
+        for indices in indices_by_keystrokes.values() {
+            for (position, &ix) in indices.iter().enumerate() {
+                let binding = &self.bindings[ix];
+                shadowing[ix] = indices[position + 1..]
+                    .iter()
+                    .rev()
+                    .copied()
+                    .find(|&later_ix| {
+                        let later = &self.bindings[later_ix];
+                        if !is_no_action(&*later.action)
+                            && later.action.partial_eq(&*binding.action)
+                        {
+                            return false;
+                        }
+                        match (&later.context_predicate, &binding.context_predicate) {
+                            (None, _) => true,
+                            (Some(_), None) => false,
+                            (Some(later), Some(predicate)) => later.is_superset(predicate),
+                        }
+                    });
+            }
+        }
+        shadowing
+    }
+
     /// Check if the given binding is enabled, given a certain key context.
     fn binding_enabled(&self, binding: &KeyBinding, context: &[KeyContext]) -> bool {
         // If binding has a context predicate, it must match the current context,
@@ -299,6 +377,71 @@ mod tests {
             .is_empty());
     }
 
+    #[test]
+    fn test_pending_bindings_for_input() {
+        let bindings = [
+            KeyBinding::new("space f f", ActionAlpha {}, None),
+            KeyBinding::new("space f g", ActionBeta {}, None),
+            KeyBinding::new("space f f", ActionGamma {}, Some("editor")),
+            KeyBinding::new("space f g", NoAction {}, Some("editor")),
+            KeyBinding::new("space b", ActionDelta {}, Some("pane")),
+        ];
+
+        let mut keymap = Keymap::default();
+        keymap.add_bindings(bindings.clone());
+
+        let continuations = |input: &str, context: &str| {
+            let input = input
+                .split_whitespace()
+                .map(|keystroke| Keystroke::parse(keystroke).unwrap())
+                .collect::<Vec<_>>();
+            keymap
+                .pending_bindings_for_input(&input, &[KeyContext::parse(context).unwrap()])
+                .into_iter()
+                .map(|binding| binding.action.name())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            continuations("space", "workspace"),
+            ["keymap_test::ActionBeta", "keymap_test::ActionAlpha"]
+        );
+        assert_eq!(
+            continuations("space f", "editor"),
+            ["keymap_test::ActionGamma"]
+        );
+        assert_eq!(
+            continuations("space", "pane"),
+            [
+                "keymap_test::ActionDelta",
+                "keymap_test::ActionBeta",
+                "keymap_test::ActionAlpha"
+            ]
+        );
+        assert!(continuations("space f f", "editor").is_empty());
+    }
+
+    #[test]
+    fn test_shadowing_bindings() {
+        let bindings = [
+            KeyBinding::new("ctrl-a", ActionAlpha {}, Some("editor")),
+            KeyBinding::new("ctrl-b", ActionBeta {}, Some("editor && mode == full")),
+            KeyBinding::new("ctrl-c", ActionGamma {}, None),
+            KeyBinding::new("ctrl-a", ActionBeta {}, Some("pane")),
+            KeyBinding::new("ctrl-b", ActionDelta {}, Some("editor")),
+            KeyBinding::new("ctrl-c", NoAction {}, None),
+            KeyBinding::new("ctrl-a", ActionAlpha {}, Some("editor")),
+        ];
+
+        let mut keymap = Keymap::default();
+        keymap.add_bindings(bindings);
+
+        assert_eq!(
+            keymap.shadowing_bindings(),
+            [None, Some(4), Some(5), None, None, None, None]
+        );
+    }
+
     #[test]
     fn test_bindings_for_action() {
         let bindings = [
@@ -0,0 +1,181 @@
+//! Converts the accessibility trees of windows to those of AccessKit, whose platform adapters
+//! expose them to screen readers, like VoiceOver, Narrator, NVDA and Orca.
+
+use std::sync::Arc;
+
+use accesskit::{
+    Action, ActionHandler, ActionRequest, ActivationHandler, Live, Node, NodeId, Rect, Role,
+    Toggled, Tree, TreeUpdate,
+};
+use collections::HashSet;
+use parking_lot::Mutex;
+
+use crate::{AccessibilityNode, AccessibilityNodeId, AccessibilityRole, AccessibilityTree};
+
+/// The last accessibility tree of a window, which adapters ask for once a screen reader starts
+/// reading the window.
+#[derive(Clone, Default)]
+pub(crate) struct LatestAccessibilityTree(Arc<Mutex<Option<(AccessibilityTree, f32)>>>);
+
+impl LatestAccessibilityTree {
+    pub(crate) fn set(&self, tree: &AccessibilityTree, scale_factor: f32) {
+        *self.0.lock() = Some((tree.clone(), scale_factor));
+    }
+}
+
+impl ActivationHandler for LatestAccessibilityTree {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        let latest_tree = self.0.lock();
+        let (tree, scale_factor) = latest_tree.as_ref()?;
+        Some(tree_update(tree, *scale_factor))
+    }
+}
+
+/// Ignores the requests of assistive technologies to act on elements, as windows only expose
+/// what's in them to be read.
+pub(crate) struct IgnoredAccessibilityActions;
+
+impl ActionHandler for IgnoredAccessibilityActions {
+    fn do_action(&mut self, _: ActionRequest) {}
+}
+
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+impl accesskit::DeactivationHandler for IgnoredAccessibilityActions {
+    fn deactivate_accessibility(&mut self) {}
+}
+
+/// Exposes the accessibility tree of a window over AT-SPI, which Orca reads it from.
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+#[derive(Default)]
+pub(crate) struct UnixAccessibility {
+    latest_tree: LatestAccessibilityTree,
+    /// Created once the window has an accessibility tree.
+    adapter: Option<accesskit_unix::Adapter>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+impl UnixAccessibility {
+    pub(crate) fn update(
+        &mut self,
+        tree: &AccessibilityTree,
+        scale_factor: f32,
+        is_window_focused: bool,
+    ) {
+        self.latest_tree.set(tree, scale_factor);
+        let latest_tree = self.latest_tree.clone();
+        let adapter = self.adapter.get_or_insert_with(|| {
+            accesskit_unix::Adapter::new(
+                latest_tree,
+                IgnoredAccessibilityActions,
+                IgnoredAccessibilityActions,
+            )
+        });
+        adapter.update_window_focus_state(is_window_focused);
+        adapter.update_if_active(|| tree_update(tree, scale_factor));
+    }
+}
+
+/// Converts a window's accessibility tree, whose bounds are in logical pixels, to an AccessKit
+/// tree, whose bounds are in physical pixels.
+pub(crate) fn tree_update(tree: &AccessibilityTree, scale_factor: f32) -> TreeUpdate {
+    let selected_ids = tree
+        .nodes
+        .iter()
+        .filter(|node| node.accessibility.selected == Some(true))
+        .map(|node| node.id)
+        .collect::<HashSet<_>>();
+    TreeUpdate {
+        nodes: tree
+            .nodes
+            .iter()
+            .map(|node| {
+                (
+                    NodeId(node.id.0),
+                    accesskit_node(node, &selected_ids, scale_factor),
+                )
+            })
+            .collect(),
+        tree: Some(Tree::new(NodeId(tree.root().id.0))),
+        focus: NodeId(tree.focus.0),
+    }
+}
+
+fn accesskit_node(
+    node: &AccessibilityNode,
+    selected_ids: &HashSet<AccessibilityNodeId>,
+    scale_factor: f32,
+) -> Node {
+    let accessibility = &node.accessibility;
+    let mut accesskit_node = Node::new(accesskit_role(accessibility.role));
+    if let Some(label) = &accessibility.label {
+        accesskit_node.set_label(label.to_string());
+    }
+    if let Some(value) = &accessibility.value {
+        accesskit_node.set_value(value.to_string());
+    }
+    if let Some(toggled) = accessibility.toggled {
+        accesskit_node.set_toggled(if toggled {
+            Toggled::True
+        } else {
+            Toggled::False
+        });
+    }
+    if let Some(selected) = accessibility.selected {
+        accesskit_node.set_selected(selected);
+    }
+    if accessibility.role == AccessibilityRole::Alert {
+        accesskit_node.set_live(Live::Assertive);
+    }
+    if node.focusable {
+        accesskit_node.add_action(Action::Focus);
+    }
+    // Focus stays on lists and menus while their items are selected, so screen readers are
+    // told which of the items to read.
+    if let Some(selected_child) = node
+        .children
+        .iter()
+        .find(|child| selected_ids.contains(child))
+    {
+        accesskit_node.set_active_descendant(NodeId(selected_child.0));
+    }
+
+    let bounds = node.bounds.scale(scale_factor);
+    accesskit_node.set_bounds(Rect {
+        x0: bounds.left().0 as f64,
+        y0: bounds.top().0 as f64,
+        x1: bounds.right().0 as f64,
+        y1: bounds.bottom().0 as f64,
+    });
+    accesskit_node.set_children(
+        node.children
+            .iter()
+            .map(|id| NodeId(id.0))
+            .collect::<Vec<_>>(),
+    );
+    accesskit_node
+}
+
+fn accesskit_role(role: AccessibilityRole) -> Role {
+    match role {
+        AccessibilityRole::Window => Role::Window,
+        AccessibilityRole::Group => Role::Group,
+        AccessibilityRole::Button => Role::Button,
+        AccessibilityRole::CheckBox => Role::CheckBox,
+        AccessibilityRole::Switch => Role::Switch,
+        AccessibilityRole::TextInput => Role::TextInput,
+        AccessibilityRole::MultilineTextInput => Role::MultilineTextInput,
+        AccessibilityRole::Label => Role::Label,
+        AccessibilityRole::Link => Role::Link,
+        AccessibilityRole::List => Role::List,
+        AccessibilityRole::ListItem => Role::ListItem,
+        AccessibilityRole::Menu => Role::Menu,
+        AccessibilityRole::MenuItem => Role::MenuItem,
+        AccessibilityRole::TabList => Role::TabList,
+        AccessibilityRole::Tab => Role::Tab,
+        AccessibilityRole::Toolbar => Role::Toolbar,
+        AccessibilityRole::Dialog => Role::Dialog,
+        AccessibilityRole::Alert => Role::Alert,
+        AccessibilityRole::Tree => Role::Tree,
+        AccessibilityRole::TreeItem => Role::TreeItem,
+    }
+}
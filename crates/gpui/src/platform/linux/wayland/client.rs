@@ -1,6 +1,7 @@
 use std::{
     cell::{RefCell, RefMut},
     hash::Hash,
+    mem,
     os::fd::{AsRawFd, BorrowedFd},
     path::PathBuf,
     rc::{Rc, Weak},
@@ -73,7 +74,9 @@ use crate::platform::linux::{
     get_xkb_compose_state, is_within_click_distance, open_uri_internal, read_fd,
     reveal_path_internal,
     wayland::{
-        clipboard::{Clipboard, DataOffer, FILE_LIST_MIME_TYPE, TEXT_MIME_TYPE},
+        clipboard::{
+            Clipboard, DataOffer, ALLOWED_TEXT_MIME_TYPES, FILE_LIST_MIME_TYPE, TEXT_MIME_TYPE,
+        },
         cursor::Cursor,
         serial::{SerialKind, SerialTracker},
         window::WaylandWindow,
@@ -83,12 +86,12 @@ use crate::platform::linux::{
 };
 use crate::platform::{blade::BladeContext, PlatformWindow};
 use crate::{
-    point, px, size, AnyWindowHandle, Bounds, CursorStyle, DevicePixels, DisplayId, FileDropEvent,
-    ForegroundExecutor, KeyDownEvent, KeyUpEvent, Keystroke, LinuxCommon, Modifiers,
-    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseExitEvent, MouseMoveEvent,
-    MouseUpEvent, NavigationDirection, Pixels, PlatformDisplay, PlatformInput, Point, ScaledPixels,
-    ScrollDelta, ScrollWheelEvent, Size, TouchPhase, WindowParams, DOUBLE_CLICK_INTERVAL,
-    SCROLL_LINES,
+    point, px, size, AnyWindowHandle, Bounds, CursorStyle, DevicePixels, DisplayId,
+    ExternalDragItem, FileDropEvent, ForegroundExecutor, KeyDownEvent, KeyUpEvent, Keystroke,
+    LinuxCommon, Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseExitEvent,
+    MouseMoveEvent, MouseUpEvent, NavigationDirection, Pixels, PlatformDisplay, PlatformInput,
+    Point, ScaledPixels, ScrollDelta, ScrollWheelEvent, Size, TouchPhase, WindowParams,
+    DOUBLE_CLICK_INTERVAL, SCROLL_LINES,
 };
 
 /// Used to convert evdev scancode to xkb scancode
@@ -207,6 +210,12 @@ pub(crate) struct WaylandClientState {
     keymap_state: Option<xkb::State>,
     compose_state: Option<xkb::compose::State>,
     drag: DragState,
+    /// The data source of the drag out of a window, with what's dragged and the window.
+    drag_source: Option<(
+        wl_data_source::WlDataSource,
+        ExternalDragItem,
+        WaylandWindowStatePtr,
+    )>,
     click: ClickState,
     repeat: KeyRepeat,
     pub modifiers: Modifiers,
@@ -217,6 +226,8 @@ pub(crate) struct WaylandClientState {
     vertical_modifier: f32,
     horizontal_modifier: f32,
     scroll_event_received: bool,
+    /// Whether the fingers were lifted from the touchpad, which ends the scroll gesture.
+    scroll_stopped: bool,
     enter_token: Option<()>,
     button_pressed: Option<MouseButton>,
     mouse_focused_window: Option<WaylandWindowStatePtr>,
@@ -277,6 +288,34 @@ impl WaylandClientStatePtr {
         self.0.upgrade().unwrap().borrow().serial_tracker.get(kind)
     }
 
+    /// Starts dragging the item out of the window, returning false if the compositor doesn't
+    /// support drag and drop.
+    pub fn start_drag(&self, window: WaylandWindowStatePtr, item: ExternalDragItem) -> bool {
+        let client = self.get_client();
+        let mut state = client.borrow_mut();
+        let (Some(data_device_manager), Some(data_device)) = (
+            state.globals.data_device_manager.clone(),
+            state.data_device.clone(),
+        ) else {
+            return false;
+        };
+
+        let data_source = data_device_manager.create_data_source(&state.globals.qh, ());
+        match &item {
+            ExternalDragItem::Text(_) => {
+                for mime_type in ALLOWED_TEXT_MIME_TYPES {
+                    data_source.offer(mime_type.to_string());
+                }
+            }
+            ExternalDragItem::Paths(_) => data_source.offer(FILE_LIST_MIME_TYPE.to_string()),
+        }
+        data_source.set_actions(DndAction::Copy);
+        let serial = state.serial_tracker.get(SerialKind::MousePress);
+        data_device.start_drag(Some(&data_source), &window.surface(), None, serial);
+        state.drag_source = Some((data_source, item, window));
+        true
+    }
+
     pub fn set_pending_activation(&self, window: ObjectId) {
         self.0.upgrade().unwrap().borrow_mut().pending_activation =
             Some(PendingActivation::Window(window));
@@ -539,6 +578,7 @@ impl WaylandClient {
                 window: None,
                 position: Point::default(),
             },
+            drag_source: None,
             click: ClickState {
                 last_click: Instant::now(),
                 last_mouse_button: None,
@@ -559,6 +599,7 @@ impl WaylandClient {
                 platform: false,
             },
             scroll_event_received: false,
+            scroll_stopped: false,
             axis_source: AxisSource::Wheel,
             mouse_location: None,
             continuous_scroll_delta: None,
@@ -1718,21 +1759,37 @@ impl Dispatch<wl_pointer::WlPointer, ()> for WaylandClientStatePtr {
                     _ => unreachable!(),
                 }
             }
+            wl_pointer::Event::AxisStop { .. } => {
+                state.scroll_event_received = true;
+                state.scroll_stopped = true;
+            }
             wl_pointer::Event::Frame => {
                 if state.scroll_event_received {
                     state.scroll_event_received = false;
                     let continuous = state.continuous_scroll_delta.take();
                     let discrete = state.discrete_scroll_delta.take();
-                    if let Some(continuous) = continuous {
+                    let stopped = mem::take(&mut state.scroll_stopped);
+                    if continuous.is_some() || stopped {
                         if let Some(window) = state.mouse_focused_window.clone() {
-                            let input = PlatformInput::ScrollWheel(ScrollWheelEvent {
-                                position: state.mouse_location.unwrap(),
-                                delta: ScrollDelta::Pixels(continuous),
-                                modifiers: state.modifiers,
-                                touch_phase: TouchPhase::Moved,
-                            });
+                            let position = state.mouse_location.unwrap();
+                            let modifiers = state.modifiers;
                             drop(state);
-                            window.handle_input(input);
+                            if let Some(continuous) = continuous {
+                                window.handle_input(PlatformInput::ScrollWheel(ScrollWheelEvent {
+                                    position,
+                                    delta: ScrollDelta::Pixels(continuous),
+                                    modifiers,
+                                    touch_phase: TouchPhase::Moved,
+                                }));
+                            }
+                            if stopped {
+                                window.handle_input(PlatformInput::ScrollWheel(ScrollWheelEvent {
+                                    position,
+                                    delta: ScrollDelta::Pixels(Point::default()),
+                                    modifiers,
+                                    touch_phase: TouchPhase::Ended,
+                                }));
+                            }
                         }
                     } else if let Some(discrete) = discrete {
                         if let Some(window) = state.mouse_focused_window.clone() {
@@ -2001,12 +2058,33 @@ impl Dispatch<wl_data_source::WlDataSource, ()> for WaylandClientStatePtr {
         let client = this.get_client();
         let mut state = client.borrow_mut();
 
+        let is_drag_source = state
+            .drag_source
+            .as_ref()
+            .is_some_and(|(drag_source, _, _)| drag_source == data_source);
         match event {
             wl_data_source::Event::Send { mime_type, fd } => {
-                state.clipboard.send(mime_type, fd);
+                if let Some((_, item, _)) = state.drag_source.as_ref().filter(|_| is_drag_source) {
+                    state.clipboard.send_drag_item(item, fd);
+                } else {
+                    state.clipboard.send(mime_type, fd);
+                }
             }
-            wl_data_source::Event::Cancelled => {
+            wl_data_source::Event::Cancelled | wl_data_source::Event::DndFinished => {
                 data_source.destroy();
+                if is_drag_source {
+                    // The window doesn't receive the button release that ends a drag out of it,
+                    // so send one to end the drag in the window too.
+                    let (_, _, window) = state.drag_source.take().unwrap();
+                    let input = PlatformInput::MouseUp(MouseUpEvent {
+                        button: MouseButton::Left,
+                        position: state.mouse_location.unwrap_or_default(),
+                        modifiers: state.modifiers,
+                        click_count: 1,
+                    });
+                    drop(state);
+                    window.handle_input(input);
+                }
             }
             _ => {}
         }
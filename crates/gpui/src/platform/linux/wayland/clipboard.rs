@@ -7,12 +7,13 @@ use std::{
 use calloop::{LoopHandle, PostAction};
 use filedescriptor::Pipe;
 use strum::IntoEnumIterator;
+use url::Url;
 use wayland_client::{protocol::wl_data_offer::WlDataOffer, Connection};
 use wayland_protocols::wp::primary_selection::zv1::client::zwp_primary_selection_offer_v1::ZwpPrimarySelectionOfferV1;
 
 use crate::{
-    hash, platform::linux::platform::read_fd, ClipboardEntry, ClipboardItem, Image, ImageFormat,
-    WaylandClientStatePtr,
+    hash, platform::linux::platform::read_fd, ClipboardEntry, ClipboardItem, ExternalDragItem,
+    Image, ImageFormat, WaylandClientStatePtr,
 };
 
 pub(crate) const TEXT_MIME_TYPE: &str = "text/plain;charset=utf-8";
@@ -201,6 +202,20 @@ impl Clipboard {
         }
     }
 
+    /// Sends the data of a drag out of the window to the application that it's dropped in.
+    pub fn send_drag_item(&self, item: &ExternalDragItem, fd: OwnedFd) {
+        let bytes = match item {
+            ExternalDragItem::Text(text) => text.as_bytes().to_owned(),
+            ExternalDragItem::Paths(paths) => paths
+                .iter()
+                .filter_map(|path| Url::from_file_path(path).ok())
+                .map(|url| format!("{url}\r\n"))
+                .collect::<String>()
+                .into_bytes(),
+        };
+        self.send_internal(fd, bytes);
+    }
+
     pub fn read(&mut self) -> Option<ClipboardItem> {
         let offer = self.current_offer.as_ref()?;
         if let Some(cached) = self.cached_read.clone() {
@@ -22,16 +22,18 @@ use wayland_protocols::xdg::shell::client::xdg_toplevel::{self};
 use wayland_protocols_plasma::blur::client::org_kde_kwin_blur;
 
 use crate::platform::{
+    accessibility::UnixAccessibility,
     blade::{BladeContext, BladeRenderer, BladeSurfaceConfig},
     linux::wayland::{display::WaylandDisplay, serial::SerialKind},
     PlatformAtlas, PlatformInputHandler, PlatformWindow,
 };
 use crate::scene::Scene;
 use crate::{
-    px, size, AnyWindowHandle, Bounds, Decorations, Globals, GpuSpecs, Modifiers, Output, Pixels,
-    PlatformDisplay, PlatformInput, Point, PromptLevel, RequestFrameOptions, ResizeEdge,
-    ScaledPixels, Size, Tiling, WaylandClientStatePtr, WindowAppearance,
-    WindowBackgroundAppearance, WindowBounds, WindowControls, WindowDecorations, WindowParams,
+    px, size, AccessibilityTree, AnyWindowHandle, Bounds, Decorations, ExternalDragItem, Globals,
+    GpuSpecs, Modifiers, Output, Pixels, PlatformDisplay, PlatformInput, Point, PromptLevel,
+    RequestFrameOptions, ResizeEdge, ScaledPixels, Size, Tiling, WaylandClientStatePtr,
+    WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowControls, WindowDecorations,
+    WindowParams,
 };
 
 #[derive(Default)]
@@ -106,6 +108,7 @@ pub struct WaylandWindowState {
     in_progress_window_controls: Option<WindowControls>,
     window_controls: WindowControls,
     inset: Option<Pixels>,
+    accessibility: UnixAccessibility,
 }
 
 #[derive(Clone)]
@@ -180,6 +183,7 @@ impl WaylandWindowState {
             in_progress_window_controls: None,
             window_controls: WindowControls::default(),
             inset: None,
+            accessibility: UnixAccessibility::default(),
         })
     }
 
@@ -895,6 +899,13 @@ impl PlatformWindow for WaylandWindow {
         self.borrow().toplevel.set_title(title.to_string());
     }
 
+    fn update_accessibility_tree(&self, tree: &AccessibilityTree) {
+        let mut state = self.borrow_mut();
+        let scale_factor = state.scale;
+        let active = state.active;
+        state.accessibility.update(tree, scale_factor, active);
+    }
+
     fn set_app_id(&mut self, app_id: &str) {
         let mut state = self.borrow_mut();
         state.toplevel.set_app_id(app_id.to_owned());
@@ -1001,6 +1012,11 @@ impl PlatformWindow for WaylandWindow {
         state.toplevel._move(&state.globals.seat, serial);
     }
 
+    fn start_external_drag(&self, item: ExternalDragItem) -> bool {
+        let client = self.borrow().client.clone();
+        client.start_drag(self.0.clone(), item)
+    }
+
     fn start_window_resize(&self, edge: crate::ResizeEdge) {
         let state = self.borrow();
         state.toplevel.resize(
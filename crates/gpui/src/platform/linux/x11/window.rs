@@ -1,12 +1,13 @@
 use anyhow::{anyhow, Context as _};
 
+use crate::platform::accessibility::UnixAccessibility;
 use crate::platform::blade::{BladeContext, BladeRenderer, BladeSurfaceConfig};
 use crate::{
-    px, size, AnyWindowHandle, Bounds, Decorations, DevicePixels, ForegroundExecutor, GpuSpecs,
-    Modifiers, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler,
-    PlatformWindow, Point, PromptLevel, RequestFrameOptions, ResizeEdge, ScaledPixels, Scene, Size,
-    Tiling, WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowDecorations,
-    WindowKind, WindowParams, X11ClientStatePtr,
+    px, size, AccessibilityTree, AnyWindowHandle, Bounds, Decorations, DevicePixels,
+    ForegroundExecutor, GpuSpecs, Modifiers, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
+    PlatformInputHandler, PlatformWindow, Point, PromptLevel, RequestFrameOptions, ResizeEdge,
+    ScaledPixels, Scene, Size, Tiling, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
+    WindowDecorations, WindowKind, WindowParams, X11ClientStatePtr,
 };
 
 use blade_graphics as gpu;
@@ -266,6 +267,7 @@ pub struct X11WindowState {
     edge_constraints: Option<EdgeConstraints>,
     pub handle: AnyWindowHandle,
     last_insets: [u32; 4],
+    accessibility: UnixAccessibility,
 }
 
 impl X11WindowState {
@@ -614,6 +616,7 @@ impl X11WindowState {
                 client_side_decorations_supported,
                 decorations: WindowDecorations::Server,
                 last_insets: [0, 0, 0, 0],
+                accessibility: UnixAccessibility::default(),
                 edge_constraints: None,
                 counter_id: sync_request_counter,
                 last_sync_counter: None,
@@ -1322,6 +1325,13 @@ impl PlatformWindow for X11Window {
         log::info!("ignoring macOS specific show_character_palette");
     }
 
+    fn update_accessibility_tree(&self, tree: &AccessibilityTree) {
+        let mut state = self.0.state.borrow_mut();
+        let scale_factor = state.scale_factor;
+        let active = state.active;
+        state.accessibility.update(tree, scale_factor, active);
+    }
+
     fn minimize(&self) {
         let state = self.0.state.borrow();
         const WINDOW_ICONIC_STATE: u32 = 3;
@@ -1,12 +1,17 @@
 use super::{ns_string, renderer, MacDisplay, NSRange, NSStringExt};
 use crate::{
-    platform::PlatformInputHandler, point, px, size, AnyWindowHandle, Bounds, DisplayLink,
+    platform::{
+        accessibility::{tree_update, IgnoredAccessibilityActions, LatestAccessibilityTree},
+        PlatformInputHandler,
+    },
+    point, px, size, AccessibilityTree, AnyWindowHandle, Bounds, DisplayLink, ExternalDragItem,
     ExternalPaths, FileDropEvent, ForegroundExecutor, KeyDownEvent, Keystroke, Modifiers,
     ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels,
     PlatformAtlas, PlatformDisplay, PlatformInput, PlatformWindow, Point, PromptLevel,
     RequestFrameOptions, ScaledPixels, Size, Timer, WindowAppearance, WindowBackgroundAppearance,
     WindowBounds, WindowKind, WindowParams,
 };
+use accesskit_macos::SubclassingAdapter;
 use block::ConcreteBlock;
 use cocoa::{
     appkit::{
@@ -72,6 +77,8 @@ const NSTrackingInVisibleRect: NSUInteger = 0x200;
 const NSWindowAnimationBehaviorUtilityWindow: NSInteger = 4;
 #[allow(non_upper_case_globals)]
 const NSViewLayerContentsRedrawDuringViewResize: NSInteger = 2;
+#[allow(non_upper_case_globals)]
+const NSWindowTabbingModeDisallowed: NSInteger = 2;
 // https://developer.apple.com/documentation/appkit/nsdragoperation
 type NSDragOperation = NSUInteger;
 #[allow(non_upper_case_globals)]
@@ -236,6 +243,17 @@ unsafe fn build_classes() {
             character_index_for_point as extern "C" fn(&Object, Sel, NSPoint) -> u64,
         );
 
+        decl.add_protocol(Protocol::get("NSDraggingSource").unwrap());
+        decl.add_method(
+            sel!(draggingSession:sourceOperationMaskForDraggingContext:),
+            dragging_source_operation_mask
+                as extern "C" fn(&Object, Sel, id, NSInteger) -> NSDragOperation,
+        );
+        decl.add_method(
+            sel!(draggingSession:endedAtPoint:operation:),
+            dragging_session_ended as extern "C" fn(&Object, Sel, id, NSPoint, NSDragOperation),
+        );
+
         decl.register()
     };
 }
@@ -350,6 +368,9 @@ struct MacWindowState {
     // Whether the next left-mouse click is also the focusing click.
     first_mouse: bool,
     fullscreen_restore_bounds: Bounds<Pixels>,
+    latest_accessibility_tree: LatestAccessibilityTree,
+    /// Created once the window has an accessibility tree, as it subclasses the native view.
+    accessibility_adapter: Option<SubclassingAdapter>,
 }
 
 impl MacWindowState {
@@ -509,6 +530,7 @@ impl MacWindow {
             show,
             display_id,
             window_min_size,
+            tabbing_identifier,
         }: WindowParams,
         executor: ForegroundExecutor,
         renderer_context: renderer::Context,
@@ -633,6 +655,8 @@ impl MacWindow {
                 external_files_dragged: false,
                 first_mouse: false,
                 fullscreen_restore_bounds: Bounds::default(),
+                latest_accessibility_tree: LatestAccessibilityTree::default(),
+                accessibility_adapter: None,
             })));
 
             (*native_window).set_ivar(
@@ -654,6 +678,15 @@ impl MacWindow {
 
             native_window.setMovable_(is_movable as BOOL);
 
+            if let Some(tabbing_identifier) = tabbing_identifier {
+                let _: () = msg_send![
+                    native_window,
+                    setTabbingIdentifier: ns_string(&tabbing_identifier)
+                ];
+            } else {
+                let _: () = msg_send![native_window, setTabbingMode: NSWindowTabbingModeDisallowed];
+            }
+
             if let Some(window_min_size) = window_min_size {
                 native_window.setContentMinSize_(NSSize {
                     width: window_min_size.width.to_f64(),
@@ -1042,6 +1075,23 @@ impl PlatformWindow for MacWindow {
         self.0.lock().move_traffic_light();
     }
 
+    fn update_accessibility_tree(&self, tree: &AccessibilityTree) {
+        let mut lock = self.0.lock();
+        let scale_factor = lock.scale_factor();
+        lock.latest_accessibility_tree.set(tree, scale_factor);
+        let latest_tree = lock.latest_accessibility_tree.clone();
+        let native_view = lock.native_view.as_ptr() as *mut c_void;
+        let adapter = lock.accessibility_adapter.get_or_insert_with(|| unsafe {
+            SubclassingAdapter::new(native_view, latest_tree, IgnoredAccessibilityActions)
+        });
+        let events = adapter.update_if_active(|| tree_update(tree, scale_factor));
+        // Raising the events may call back into the window, so it mustn't be locked.
+        drop(lock);
+        if let Some(events) = events {
+            events.raise();
+        }
+    }
+
     fn show_character_palette(&self) {
         let this = self.0.lock();
         let window = this.native_window;
@@ -1055,6 +1105,30 @@ impl PlatformWindow for MacWindow {
             .detach();
     }
 
+    fn merge_all_windows(&self) {
+        let this = self.0.lock();
+        let window = this.native_window;
+        this.executor
+            .spawn(async move {
+                unsafe {
+                    let _: () = msg_send![window, mergeAllWindows: nil];
+                }
+            })
+            .detach();
+    }
+
+    fn move_tab_to_new_window(&self) {
+        let this = self.0.lock();
+        let window = this.native_window;
+        this.executor
+            .spawn(async move {
+                unsafe {
+                    let _: () = msg_send![window, moveTabToNewWindow: nil];
+                }
+            })
+            .detach();
+    }
+
     fn minimize(&self) {
         let window = self.0.lock().native_window;
         unsafe {
@@ -1140,6 +1214,69 @@ impl PlatformWindow for MacWindow {
         self.0.lock().renderer.sprite_atlas().clone()
     }
 
+    fn start_external_drag(&self, item: ExternalDragItem) -> bool {
+        let mut lock = self.0.lock();
+        // The view stops receiving mouse events until the drag ends, so stop the synthetic drag.
+        lock.synthetic_drag_counter += 1;
+        let view = lock.native_view.as_ptr();
+        drop(lock);
+
+        unsafe {
+            let app = NSApplication::sharedApplication(nil);
+            let event: id = msg_send![app, currentEvent];
+            if event == nil {
+                return false;
+            }
+            let location: NSPoint = msg_send![event, locationInWindow];
+            let location: NSPoint = msg_send![view, convertPoint: location fromView: nil];
+
+            let writers: Vec<(id, id)> = match item {
+                ExternalDragItem::Text(text) => vec![(ns_string(&text), nil)],
+                ExternalDragItem::Paths(paths) => {
+                    let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+                    paths
+                        .iter()
+                        .map(|path| {
+                            let path = ns_string(&path.to_string_lossy());
+                            let url: id = msg_send![class!(NSURL), fileURLWithPath: path];
+                            let icon: id = msg_send![workspace, iconForFile: path];
+                            (url, icon)
+                        })
+                        .collect()
+                }
+            };
+            if writers.is_empty() {
+                return false;
+            }
+
+            let dragging_items = writers
+                .into_iter()
+                .enumerate()
+                .map(|(ix, (writer, image))| {
+                    let dragging_item: id = msg_send![class!(NSDraggingItem), alloc];
+                    let dragging_item: id =
+                        msg_send![dragging_item, initWithPasteboardWriter: writer];
+                    let offset = 8. * ix as f64;
+                    let frame = NSRect::new(
+                        NSPoint::new(location.x - 16. + offset, location.y - 16. - offset),
+                        NSSize::new(32., 32.),
+                    );
+                    let _: () = msg_send![dragging_item, setDraggingFrame: frame contents: image];
+                    let _: id = msg_send![dragging_item, autorelease];
+                    dragging_item
+                })
+                .collect::<Vec<_>>();
+            let dragging_items = NSArray::arrayWithObjects(nil, &dragging_items);
+            let session: id = msg_send![
+                view,
+                beginDraggingSessionWithItems: dragging_items
+                event: event
+                source: view
+            ];
+            session != nil
+        }
+    }
+
     fn gpu_specs(&self) -> Option<crate::GpuSpecs> {
         None
     }
@@ -2014,6 +2151,35 @@ extern "C" fn conclude_drag_operation(this: &Object, _: Sel, _: id) {
     );
 }
 
+extern "C" fn dragging_source_operation_mask(
+    _: &Object,
+    _: Sel,
+    _: id,
+    _: NSInteger,
+) -> NSDragOperation {
+    NSDragOperationCopy
+}
+
+extern "C" fn dragging_session_ended(this: &Object, _: Sel, _: id, _: NSPoint, _: NSDragOperation) {
+    // The view doesn't receive the mouse up that ends a drag out of the window, so send one to
+    // end the drag in the window too.
+    let window_state = unsafe { get_window_state(this) };
+    let position = {
+        let lock = window_state.lock();
+        let position = unsafe { lock.native_window.mouseLocationOutsideOfEventStream() };
+        convert_mouse_position(position, lock.content_size().height)
+    };
+    send_new_event(
+        &window_state,
+        PlatformInput::MouseUp(MouseUpEvent {
+            button: MouseButton::Left,
+            position,
+            modifiers: Modifiers::default(),
+            click_count: 1,
+        }),
+    );
+}
+
 async fn synthetic_drag(
     window_state: Weak<Mutex<MacWindowState>>,
     drag_id: usize,
@@ -27,7 +27,7 @@ use windows::{
     },
     UI::{
         StartScreen::{JumpList, JumpListItem},
-        ViewManagement::UISettings,
+        ViewManagement::{AccessibilitySettings, UISettings},
     },
 };
 
@@ -572,6 +572,14 @@ impl Platform for WindowsPlatform {
         should_auto_hide_scrollbars().log_err().unwrap_or(false)
     }
 
+    fn prefers_high_contrast(&self) -> bool {
+        prefers_high_contrast().log_err().unwrap_or(false)
+    }
+
+    fn prefers_reduced_motion(&self) -> bool {
+        prefers_reduced_motion().log_err().unwrap_or(false)
+    }
+
     fn write_to_clipboard(&self, item: ClipboardItem) {
         write_to_clipboard(item);
     }
@@ -824,6 +832,18 @@ fn should_auto_hide_scrollbars() -> Result<bool> {
     Ok(ui_settings.AutoHideScrollBars()?)
 }
 
+#[inline]
+fn prefers_high_contrast() -> Result<bool> {
+    let accessibility_settings = AccessibilitySettings::new()?;
+    Ok(accessibility_settings.HighContrast()?)
+}
+
+#[inline]
+fn prefers_reduced_motion() -> Result<bool> {
+    let ui_settings = UISettings::new()?;
+    Ok(!ui_settings.AnimationsEnabled()?)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{read_from_clipboard, write_to_clipboard, ClipboardItem};
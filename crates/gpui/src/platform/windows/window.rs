@@ -11,6 +11,7 @@ use std::{
 };
 
 use ::util::ResultExt;
+use accesskit_windows::SubclassingAdapter;
 use anyhow::{Context as _, Result};
 use async_task::Runnable;
 use futures::channel::oneshot::{self, Receiver};
@@ -26,6 +27,9 @@ use windows::{
     },
 };
 
+use crate::platform::accessibility::{
+    tree_update, IgnoredAccessibilityActions, LatestAccessibilityTree,
+};
 use crate::platform::blade::{BladeContext, BladeRenderer};
 use crate::*;
 
@@ -69,6 +73,9 @@ pub(crate) struct WindowsWindowStatePtr {
     pub(crate) validation_number: usize,
     pub(crate) main_receiver: flume::Receiver<Runnable>,
     pub(crate) main_thread_id_win32: u32,
+    latest_accessibility_tree: LatestAccessibilityTree,
+    /// Created once the window has an accessibility tree, as it subclasses the window.
+    accessibility_adapter: RefCell<Option<SubclassingAdapter>>,
 }
 
 impl WindowsWindowState {
@@ -243,6 +250,8 @@ impl WindowsWindowStatePtr {
             validation_number: context.validation_number,
             main_receiver: context.main_receiver.clone(),
             main_thread_id_win32: context.main_thread_id_win32,
+            latest_accessibility_tree: LatestAccessibilityTree::default(),
+            accessibility_adapter: RefCell::new(None),
         }))
     }
 
@@ -816,6 +825,27 @@ impl PlatformWindow for WindowsWindow {
         self.0.hwnd
     }
 
+    fn update_accessibility_tree(&self, tree: &AccessibilityTree) {
+        let scale_factor = self.0.state.borrow().scale_factor;
+        self.0.latest_accessibility_tree.set(tree, scale_factor);
+        let events = self
+            .0
+            .accessibility_adapter
+            .borrow_mut()
+            .get_or_insert_with(|| {
+                SubclassingAdapter::new(
+                    accesskit_windows::HWND(self.0.hwnd.0),
+                    self.0.latest_accessibility_tree.clone(),
+                    IgnoredAccessibilityActions,
+                )
+            })
+            .update_if_active(|| tree_update(tree, scale_factor));
+        // Raising the events sends messages to the window, so the adapter mustn't be borrowed.
+        if let Some(events) = events {
+            events.raise();
+        }
+    }
+
     fn gpu_specs(&self) -> Option<GpuSpecs> {
         Some(self.0.state.borrow().renderer.gpu_specs())
     }
@@ -34,6 +34,17 @@ impl LineWrapper {
         &'a mut self,
         line: &'a str,
         wrap_width: Pixels,
+    ) -> impl Iterator<Item = Boundary> + 'a {
+        self.wrap_line_with_options(line, wrap_width, WrapOptions::default())
+    }
+
+    /// Wrap a line of text to the given width with this wrapper's font and font size, choosing
+    /// where to wrap and how to indent wrapped lines with the given options.
+    pub fn wrap_line_with_options<'a>(
+        &'a mut self,
+        line: &'a str,
+        wrap_width: Pixels,
+        options: WrapOptions,
     ) -> impl Iterator<Item = Boundary> + 'a {
         let mut width = px(0.);
         let mut first_non_whitespace_ix = None;
@@ -49,16 +60,19 @@ impl LineWrapper {
                     continue;
                 }
 
-                if Self::is_word_char(c) {
-                    if prev_c == ' ' && c != ' ' && first_non_whitespace_ix.is_some() {
-                        last_candidate_ix = ix;
-                        last_candidate_width = width;
-                    }
-                } else {
-                    // CJK may not be space separated, e.g.: `Hello world你好世界`
-                    if c != ' ' && first_non_whitespace_ix.is_some() {
-                        last_candidate_ix = ix;
-                        last_candidate_width = width;
+                // Without candidates, lines wrap at the first character that doesn't fit.
+                if options.at_word_boundaries {
+                    if Self::is_word_char(c) {
+                        if prev_c == ' ' && c != ' ' && first_non_whitespace_ix.is_some() {
+                            last_candidate_ix = ix;
+                            last_candidate_width = width;
+                        }
+                    } else {
+                        // CJK may not be space separated, e.g.: `Hello world你好世界`
+                        if c != ' ' && first_non_whitespace_ix.is_some() {
+                            last_candidate_ix = ix;
+                            last_candidate_width = width;
+                        }
                     }
                 }
 
@@ -69,7 +83,8 @@ impl LineWrapper {
                 let char_width = self.width_for_char(c);
                 width += char_width;
                 if width > wrap_width && ix > last_wrap_ix {
-                    if let (None, Some(first_non_whitespace_ix)) = (indent, first_non_whitespace_ix)
+                    if let (true, None, Some(first_non_whitespace_ix)) =
+                        (options.hanging_indent, indent, first_non_whitespace_ix)
                     {
                         indent = Some(
                             Self::MAX_INDENT.min((first_non_whitespace_ix - last_wrap_ix) as u32),
@@ -213,6 +228,25 @@ fn update_runs_after_truncation(result: &str, ellipsis: &str, runs: &mut Vec<Tex
     }
 }
 
+/// Options for how the [`LineWrapper`] wraps lines.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WrapOptions {
+    /// Whether lines are wrapped between words, rather than at the first character that doesn't
+    /// fit.
+    pub at_word_boundaries: bool,
+    /// Whether wrapped lines are indented to the first non-whitespace character of the line.
+    pub hanging_indent: bool,
+}
+
+impl Default for WrapOptions {
+    fn default() -> Self {
+        Self {
+            at_word_boundaries: true,
+            hanging_indent: true,
+        }
+    }
+}
+
 /// A boundary between two lines of text.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct Boundary {
@@ -329,6 +363,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_wrap_line_with_options() {
+        let mut wrapper = build_wrapper();
+
+        let options = WrapOptions {
+            at_word_boundaries: false,
+            hanging_indent: true,
+        };
+        assert_eq!(
+            wrapper
+                .wrap_line_with_options("aa bbb cccc ddddd eeee", px(72.), options)
+                .collect::<Vec<_>>(),
+            &[
+                Boundary::new(7, 0),
+                Boundary::new(14, 0),
+                Boundary::new(21, 0)
+            ],
+        );
+
+        let options = WrapOptions {
+            at_word_boundaries: true,
+            hanging_indent: false,
+        };
+        assert_eq!(
+            wrapper
+                .wrap_line_with_options("     aaaaaaa", px(72.), options)
+                .collect::<Vec<_>>(),
+            &[Boundary::new(7, 0)]
+        );
+    }
+
     #[test]
     fn test_truncate_line() {
         let mut wrapper = build_wrapper();
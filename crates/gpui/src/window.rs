@@ -1,18 +1,19 @@
 use crate::{
-    point, prelude::*, px, size, transparent_black, Action, AnyDrag, AnyElement, AnyTooltip,
-    AnyView, App, AppContext, Arena, Asset, AsyncWindowContext, AvailableSpace, Background,
-    BorderStyle, Bounds, BoxShadow, Context, Corners, CursorStyle, Decorations, DevicePixels,
-    DispatchActionListener, DispatchNodeId, DispatchTree, DisplayId, Edges, Effect, Entity,
-    EntityId, EventEmitter, FileDropEvent, FontId, Global, GlobalElementId, GlyphId, GpuSpecs,
-    Hsla, InputHandler, IsZero, KeyBinding, KeyContext, KeyDownEvent, KeyEvent, Keystroke,
-    KeystrokeEvent, LayoutId, LineLayoutIndex, Modifiers, ModifiersChangedEvent, MonochromeSprite,
-    MouseButton, MouseEvent, MouseMoveEvent, MouseUpEvent, Path, Pixels, PlatformAtlas,
-    PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow, Point, PolychromeSprite,
-    PromptLevel, Quad, Render, RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams,
-    Replay, ResizeEdge, ScaledPixels, Scene, Shadow, SharedString, Size, StrikethroughStyle, Style,
-    SubscriberSet, Subscription, TaffyLayoutEngine, Task, TextStyle, TextStyleRefinement,
-    TransformationMatrix, Underline, UnderlineStyle, WindowAppearance, WindowBackgroundAppearance,
-    WindowBounds, WindowControls, WindowDecorations, WindowOptions, WindowParams, WindowTextSystem,
+    point, prelude::*, px, size, transparent_black, Accessibility, AccessibilityNodeRequest,
+    AccessibilityTree, Action, AnyDrag, AnyElement, AnyTooltip, AnyView, App, AppContext, Arena,
+    Asset, AsyncWindowContext, AvailableSpace, Background, BorderStyle, Bounds, BoxShadow, Context,
+    Corners, CursorStyle, Decorations, DevicePixels, DispatchActionListener, DispatchNodeId,
+    DispatchTree, DisplayId, Edges, Effect, Entity, EntityId, EventEmitter, ExternalDragItem,
+    FileDropEvent, FontId, Global, GlobalElementId, GlyphId, GpuSpecs, Hsla, InputHandler, IsZero,
+    KeyBinding, KeyContext, KeyDownEvent, KeyEvent, Keystroke, KeystrokeEvent, LayoutId,
+    LineLayoutIndex, Modifiers, ModifiersChangedEvent, MonochromeSprite, MouseButton, MouseEvent,
+    MouseMoveEvent, MouseUpEvent, Path, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
+    PlatformInputHandler, PlatformWindow, Point, PolychromeSprite, PromptLevel, Quad, Render,
+    RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams, Replay, ResizeEdge,
+    ScaledPixels, Scene, Shadow, SharedString, Size, StrikethroughStyle, Style, SubscriberSet,
+    Subscription, TaffyLayoutEngine, Task, TextStyle, TextStyleRefinement, TransformationMatrix,
+    Underline, UnderlineStyle, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
+    WindowControls, WindowDecorations, WindowOptions, WindowParams, WindowTextSystem,
     SMOOTH_SVG_SCALE_FACTOR, SUBPIXEL_VARIANTS,
 };
 use anyhow::{anyhow, Context as _, Result};
@@ -48,8 +49,10 @@ use util::post_inc;
 use util::{measure, ResultExt};
 use uuid::Uuid;
 
+mod frame_timings;
 mod prompts;
 
+pub use frame_timings::*;
 pub use prompts::*;
 
 pub(crate) const DEFAULT_WINDOW_SIZE: Size<Pixels> = size(px(1024.), px(700.));
@@ -500,6 +503,7 @@ pub(crate) struct Frame {
     pub(crate) input_handlers: Vec<Option<PlatformInputHandler>>,
     pub(crate) tooltip_requests: Vec<Option<TooltipRequest>>,
     pub(crate) cursor_styles: Vec<CursorStyleRequest>,
+    pub(crate) accessibility_nodes: Vec<AccessibilityNodeRequest>,
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) debug_bounds: FxHashMap<String, Bounds<Pixels>>,
 }
@@ -520,6 +524,8 @@ pub(crate) struct PaintIndex {
     mouse_listeners_index: usize,
     input_handlers_index: usize,
     cursor_styles_index: usize,
+    accessibility_nodes_index: usize,
+    accessibility_depth: usize,
     accessed_element_states_index: usize,
     line_layout_index: LineLayoutIndex,
 }
@@ -539,6 +545,7 @@ impl Frame {
             input_handlers: Vec::new(),
             tooltip_requests: Vec::new(),
             cursor_styles: Vec::new(),
+            accessibility_nodes: Vec::new(),
 
             #[cfg(any(test, feature = "test-support"))]
             debug_bounds: FxHashMap::default(),
@@ -554,6 +561,7 @@ impl Frame {
         self.input_handlers.clear();
         self.tooltip_requests.clear();
         self.cursor_styles.clear();
+        self.accessibility_nodes.clear();
         self.hitboxes.clear();
         self.deferred_draws.clear();
         self.focus = None;
@@ -612,6 +620,8 @@ pub struct Window {
     pub(crate) root: Option<AnyView>,
     pub(crate) element_id_stack: SmallVec<[ElementId; 32]>,
     pub(crate) text_style_stack: Vec<TextStyleRefinement>,
+    accessibility_depth: usize,
+    accessibility_tree: AccessibilityTree,
     pub(crate) rendered_entity_stack: Vec<EntityId>,
     pub(crate) element_offset_stack: Vec<Point<Pixels>>,
     pub(crate) element_opacity: Option<f32>,
@@ -646,6 +656,7 @@ pub struct Window {
     pending_modifier: ModifierState,
     pub(crate) pending_input_observers: SubscriberSet<(), AnyObserver>,
     prompt: Option<RenderablePromptHandle>,
+    frame_timings: Option<FrameTimings>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -716,6 +727,7 @@ impl Window {
             app_id,
             window_min_size,
             window_decorations,
+            tabbing_identifier,
         } = options;
 
         let bounds = window_bounds
@@ -732,6 +744,7 @@ impl Window {
                 show,
                 display_id,
                 window_min_size,
+                tabbing_identifier,
             },
         )?;
         let display_id = platform_window.display().map(|display| display.id());
@@ -896,6 +909,8 @@ impl Window {
             root: None,
             element_id_stack: SmallVec::default(),
             text_style_stack: Vec::new(),
+            accessibility_depth: 0,
+            accessibility_tree: AccessibilityTree::default(),
             rendered_entity_stack: Vec::new(),
             element_offset_stack: Vec::new(),
             content_mask_stack: Vec::new(),
@@ -930,6 +945,7 @@ impl Window {
             pending_modifier: ModifierState::default(),
             pending_input_observers: SubscriberSet::new(),
             prompt: None,
+            frame_timings: None,
         })
     }
 
@@ -1373,6 +1389,18 @@ impl Window {
         self.platform_window.zoom();
     }
 
+    /// Merges all the windows that share this window's tabbing identifier into native tabs of
+    /// this window. macOS only
+    pub fn merge_all_windows(&self) {
+        self.platform_window.merge_all_windows();
+    }
+
+    /// Moves this window out of the native tabs it's shown in, into a window of its own. macOS
+    /// only
+    pub fn move_tab_to_new_window(&self) {
+        self.platform_window.move_tab_to_new_window();
+    }
+
     /// Opens the native title bar context menu, useful when implementing client side decorations (Wayland and X11)
     pub fn show_window_menu(&self, position: Point<Pixels>) {
         self.platform_window.show_window_menu(position)
@@ -1429,6 +1457,13 @@ impl Window {
             .find(|display| Some(display.id()) == self.display_id)
     }
 
+    /// Starts dragging the given item out of the window, so that it can be dropped in other
+    /// applications, while the left mouse button is held down. Returns false if the platform
+    /// doesn't support dragging out of windows, which only macOS and Wayland do.
+    pub fn start_external_drag(&self, item: ExternalDragItem) -> bool {
+        self.platform_window.start_external_drag(item)
+    }
+
     /// Show the platform character palette.
     pub fn show_character_palette(&self) {
         self.platform_window.show_character_palette();
@@ -1520,6 +1555,34 @@ impl Window {
         self.platform_window.completed_frame();
     }
 
+    /// Starts or stops measuring how long it takes to draw this window's frames.
+    pub fn set_frame_timings_enabled(&mut self, enabled: bool) {
+        if enabled {
+            self.frame_timings.get_or_insert_with(FrameTimings::default);
+        } else {
+            self.frame_timings = None;
+        }
+    }
+
+    /// The timings of the recently drawn frames, if they are being measured.
+    pub fn frame_timings(&self) -> Option<&FrameTimings> {
+        self.frame_timings.as_ref()
+    }
+
+    /// Records a piece of work to show among the slowest recent spans, if frame timings are
+    /// being measured.
+    pub fn record_span(&mut self, name: impl Into<SharedString>, duration: Duration) {
+        if let Some(frame_timings) = self.frame_timings.as_mut() {
+            frame_timings.record_span(name, duration);
+        }
+    }
+
+    pub(crate) fn record_view_name<V: 'static>(&mut self, view_id: EntityId) {
+        if let Some(frame_timings) = self.frame_timings.as_mut() {
+            frame_timings.set_view_name(view_id, std::any::type_name::<V>());
+        }
+    }
+
     /// Produces a new frame and assigns it to `rendered_frame`. To actually show
     /// the contents of the new [Scene], use [present].
     #[profiling::function]
@@ -1529,6 +1592,9 @@ impl Window {
         debug_assert!(self.rendered_entity_stack.is_empty());
         self.invalidator.set_dirty(false);
         self.requested_autoscroll = None;
+        if let Some(frame_timings) = self.frame_timings.as_mut() {
+            frame_timings.start_frame();
+        }
 
         // Restore the previously-used input handler.
         if let Some(input_handler) = self.platform_window.take_input_handler() {
@@ -1592,9 +1658,13 @@ impl Window {
         debug_assert!(self.rendered_entity_stack.is_empty());
         self.record_entities_accessed(cx);
         self.reset_cursor_style(cx);
+        self.update_accessibility_tree();
         self.refreshing = false;
         self.invalidator.set_phase(DrawPhase::None);
         self.needs_present.set(true);
+        if let Some(frame_timings) = self.frame_timings.as_mut() {
+            frame_timings.finish_frame();
+        }
     }
 
     fn record_entities_accessed(&mut self, cx: &mut App) {
@@ -1662,6 +1732,9 @@ impl Window {
 
         // Now actually paint the elements.
         self.invalidator.set_phase(DrawPhase::Paint);
+        if let Some(frame_timings) = self.frame_timings.as_mut() {
+            frame_timings.start_painting();
+        }
         root_element.paint(self, cx);
 
         self.paint_deferred_draws(&sorted_deferred_draws, cx);
@@ -1874,6 +1947,8 @@ impl Window {
             mouse_listeners_index: self.next_frame.mouse_listeners.len(),
             input_handlers_index: self.next_frame.input_handlers.len(),
             cursor_styles_index: self.next_frame.cursor_styles.len(),
+            accessibility_nodes_index: self.next_frame.accessibility_nodes.len(),
+            accessibility_depth: self.accessibility_depth,
             accessed_element_states_index: self.next_frame.accessed_element_states.len(),
             line_layout_index: self.text_system.layout_index(),
         }
@@ -1886,6 +1961,17 @@ impl Window {
                 .iter()
                 .cloned(),
         );
+        // The reused nodes may now be painted inside of more or fewer nodes than before.
+        let accessibility_depth = self.accessibility_depth;
+        self.next_frame.accessibility_nodes.extend(
+            self.rendered_frame.accessibility_nodes
+                [range.start.accessibility_nodes_index..range.end.accessibility_nodes_index]
+                .iter()
+                .map(|node| AccessibilityNodeRequest {
+                    depth: node.depth - range.start.accessibility_depth + accessibility_depth,
+                    ..node.clone()
+                }),
+        );
         self.next_frame.input_handlers.extend(
             self.rendered_frame.input_handlers
                 [range.start.input_handlers_index..range.end.input_handlers_index]
@@ -1931,6 +2017,40 @@ impl Window {
         }
     }
 
+    /// Adds a node with the given accessibility to the window's accessibility tree, and calls a
+    /// function in which the nodes that are added become its children. Does nothing but call the
+    /// function when there's no accessibility. This method should only be called as part of the
+    /// paint phase of element drawing.
+    pub fn with_accessibility_node<R>(
+        &mut self,
+        accessibility: Option<Accessibility>,
+        bounds: Bounds<Pixels>,
+        focus_handle: Option<&FocusHandle>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.invalidator.debug_assert_paint();
+        let Some(accessibility) = accessibility else {
+            return f(self);
+        };
+        self.next_frame
+            .accessibility_nodes
+            .push(AccessibilityNodeRequest {
+                accessibility,
+                bounds,
+                focus_id: focus_handle.map(|handle| handle.id),
+                depth: self.accessibility_depth,
+            });
+        self.accessibility_depth += 1;
+        let result = f(self);
+        self.accessibility_depth -= 1;
+        result
+    }
+
+    /// The window's accessibility tree, as of the last frame.
+    pub fn accessibility_tree(&self) -> &AccessibilityTree {
+        &self.accessibility_tree
+    }
+
     /// Updates the cursor style at the platform level. This method should only be called
     /// during the prepaint phase of element drawing.
     pub fn set_cursor_style(&mut self, style: CursorStyle, hitbox: Option<&Hitbox>) {
@@ -2840,9 +2960,16 @@ impl Window {
         id: EntityId,
         f: impl FnOnce(&mut Self) -> R,
     ) -> R {
+        let started_at = self.frame_timings.as_mut().map(|frame_timings| {
+            frame_timings.start_view();
+            Instant::now()
+        });
         self.rendered_entity_stack.push(id);
         let result = f(self);
         self.rendered_entity_stack.pop();
+        if let Some((frame_timings, started_at)) = self.frame_timings.as_mut().zip(started_at) {
+            frame_timings.end_view(id, started_at.elapsed());
+        }
         result
     }
 
@@ -2981,6 +3108,19 @@ impl Window {
         subscription
     }
 
+    fn update_accessibility_tree(&mut self) {
+        let tree = AccessibilityTree::new(
+            &self.rendered_frame.accessibility_nodes,
+            self.focus,
+            Bounds::new(Point::default(), self.viewport_size),
+        );
+        if tree != self.accessibility_tree {
+            self.accessibility_tree = tree;
+            self.platform_window
+                .update_accessibility_tree(&self.accessibility_tree);
+        }
+    }
+
     fn reset_cursor_style(&self, cx: &mut App) {
         // Set the cursor only if we're the active window.
         if self.is_window_hovered() {
@@ -3240,6 +3380,23 @@ impl Window {
             return;
         };
 
+        if let Some(key_down_event) = event.downcast_ref::<KeyDownEvent>() {
+            cx.propagate_event = true;
+            cx.keystroke_interceptors.clone().retain(&(), |callback| {
+                (callback)(
+                    &KeystrokeEvent {
+                        keystroke: key_down_event.keystroke.clone(),
+                        action: None,
+                    },
+                    self,
+                    cx,
+                )
+            });
+            if !cx.propagate_event {
+                return;
+            }
+        }
+
         let mut currently_pending = self.pending_input.take().unwrap_or_default();
         if currently_pending.focus.is_some() && currently_pending.focus != self.focus {
             currently_pending = PendingInput::default();
@@ -3258,8 +3415,9 @@ impl Window {
         if !match_result.pending.is_empty() {
             currently_pending.keystrokes = match_result.pending;
             currently_pending.focus = self.focus;
+            let timeout = cx.key_sequence_timeout();
             currently_pending.timer = Some(self.spawn(cx, async move |cx| {
-                cx.background_executor.timer(Duration::from_secs(1)).await;
+                cx.background_executor.timer(timeout).await;
                 cx.update(move |window, cx| {
                     let Some(currently_pending) = window
                         .pending_input
@@ -3276,7 +3434,8 @@ impl Window {
                         .dispatch_tree
                         .flush_dispatch(currently_pending.keystrokes, &dispatch_path);
 
-                    window.replay_pending_input(to_replay, cx)
+                    window.replay_pending_input(to_replay, cx);
+                    window.pending_input_changed(cx);
                 })
                 .log_err();
             }));
@@ -3392,6 +3551,18 @@ impl Window {
             .map(|pending_input| pending_input.keystrokes.as_slice())
     }
 
+    /// Returns the key bindings that could still be completed by typing more keystrokes after the
+    /// currently pending input, in the context of the focused element. Bindings that are shadowed
+    /// by another binding for the same keystrokes are omitted.
+    pub fn pending_input_continuations(&self, cx: &App) -> Vec<KeyBinding> {
+        let Some(pending_input) = self.pending_input.as_ref() else {
+            return Vec::new();
+        };
+        cx.keymap
+            .borrow()
+            .pending_bindings_for_input(&pending_input.keystrokes, &self.context_stack())
+    }
+
     fn replay_pending_input(&mut self, replays: SmallVec<[Replay; 1]>, cx: &mut App) {
         let node_id = self
             .focus
@@ -0,0 +1,265 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use collections::FxHashMap;
+
+use crate::{EntityId, SharedString};
+
+/// The number of recent frames whose timings are kept.
+const MAX_FRAMES: usize = 240;
+/// How long a span stays among the slowest recent ones.
+const SPAN_RETENTION: Duration = Duration::from_secs(30);
+/// Spans shorter than this aren't recorded, to keep recording cheap.
+const MIN_SPAN_DURATION: Duration = Duration::from_millis(1);
+const MAX_SPANS: usize = 64;
+
+/// How long it took to draw a frame.
+#[derive(Clone, Copy, Debug)]
+pub struct FrameTiming {
+    /// When the frame started being drawn.
+    pub started_at: Instant,
+    /// The time spent rendering the views, laying them out and prepainting them.
+    pub layout: Duration,
+    /// The time spent painting the views.
+    pub paint: Duration,
+    /// The time spent drawing the whole frame.
+    pub total: Duration,
+}
+
+/// The time spent drawing the views of one type in the last frame, excluding the time spent
+/// drawing the views they contain.
+#[derive(Clone, Debug)]
+pub struct ViewTiming {
+    /// The name of the view's type, without its module path.
+    pub name: &'static str,
+    /// The number of views of this type that were drawn.
+    pub count: usize,
+    /// The time spent rendering the views, laying them out and prepainting them.
+    pub layout: Duration,
+    /// The time spent painting the views.
+    pub paint: Duration,
+}
+
+impl ViewTiming {
+    /// The time spent drawing the views.
+    pub fn total(&self) -> Duration {
+        self.layout + self.paint
+    }
+}
+
+/// A recent piece of work, such as drawing a view, that took a noticeable amount of time.
+#[derive(Clone, Debug)]
+pub struct TimedSpan {
+    /// What was being done.
+    pub name: SharedString,
+    /// How long it took.
+    pub duration: Duration,
+    /// When it finished.
+    pub recorded_at: Instant,
+}
+
+/// Measures how long drawing a window's frames takes, and which views it was spent on.
+/// Measuring is off by default, see [`crate::Window::set_frame_timings_enabled`].
+#[derive(Default)]
+pub struct FrameTimings {
+    frames: VecDeque<FrameTiming>,
+    views: Vec<ViewTiming>,
+    spans: Vec<TimedSpan>,
+    frame_started_at: Option<Instant>,
+    paint_started_at: Option<Instant>,
+    view_names: FxHashMap<EntityId, &'static str>,
+    /// The layout and paint time of each view drawn in the current frame.
+    current_views: FxHashMap<EntityId, (Duration, Duration)>,
+    /// The time spent drawing the children of each view being drawn, innermost last.
+    child_durations: Vec<Duration>,
+}
+
+impl FrameTimings {
+    /// The timings of the recent frames, oldest first.
+    pub fn frames(&self) -> impl DoubleEndedIterator<Item = &FrameTiming> + ExactSizeIterator {
+        self.frames.iter()
+    }
+
+    /// The number of frames drawn in the last second.
+    pub fn frames_per_second(&self) -> usize {
+        let now = Instant::now();
+        self.frames
+            .iter()
+            .rev()
+            .take_while(|frame| now.duration_since(frame.started_at) < Duration::from_secs(1))
+            .count()
+    }
+
+    /// The time spent on each type of view in the last frame, slowest first.
+    pub fn views(&self) -> &[ViewTiming] {
+        &self.views
+    }
+
+    /// The slowest spans recorded recently, slowest first.
+    pub fn slowest_spans(&self) -> &[TimedSpan] {
+        &self.spans
+    }
+
+    /// Records a piece of work to show among the slowest recent spans.
+    pub fn record_span(&mut self, name: impl Into<SharedString>, duration: Duration) {
+        if duration < MIN_SPAN_DURATION {
+            return;
+        }
+        let ix = self.spans.partition_point(|span| span.duration >= duration);
+        if ix >= MAX_SPANS {
+            return;
+        }
+        self.spans.insert(
+            ix,
+            TimedSpan {
+                name: name.into(),
+                duration,
+                recorded_at: Instant::now(),
+            },
+        );
+        self.spans.truncate(MAX_SPANS);
+    }
+
+    pub(crate) fn set_view_name(&mut self, view_id: EntityId, type_name: &'static str) {
+        self.view_names.insert(view_id, short_type_name(type_name));
+    }
+
+    pub(crate) fn start_frame(&mut self) {
+        self.frame_started_at = Some(Instant::now());
+        self.paint_started_at = None;
+        self.current_views.clear();
+        self.child_durations.clear();
+    }
+
+    pub(crate) fn start_painting(&mut self) {
+        self.paint_started_at = Some(Instant::now());
+    }
+
+    pub(crate) fn start_view(&mut self) {
+        self.child_durations.push(Duration::ZERO);
+    }
+
+    /// Records the time spent drawing a view, `elapsed` including the views it contains.
+    pub(crate) fn end_view(&mut self, view_id: EntityId, elapsed: Duration) {
+        let child_duration = self.child_durations.pop().unwrap_or_default();
+        if let Some(parent_child_duration) = self.child_durations.last_mut() {
+            *parent_child_duration += elapsed;
+        }
+
+        let own_duration = elapsed.saturating_sub(child_duration);
+        let painting = self.paint_started_at.is_some();
+        let (layout, paint) = self.current_views.entry(view_id).or_default();
+        if painting {
+            *paint += own_duration;
+        } else {
+            *layout += own_duration;
+        }
+
+        if elapsed >= MIN_SPAN_DURATION {
+            let name = self.view_names.get(&view_id).copied().unwrap_or("View");
+            let phase = if painting { "paint" } else { "layout" };
+            self.record_span(format!("{name} {phase}"), elapsed);
+        }
+    }
+
+    pub(crate) fn finish_frame(&mut self) {
+        let Some(started_at) = self.frame_started_at.take() else {
+            return;
+        };
+        let now = Instant::now();
+        let paint_started_at = self.paint_started_at.take().unwrap_or(now);
+        let frame = FrameTiming {
+            started_at,
+            layout: paint_started_at.duration_since(started_at),
+            paint: now.duration_since(paint_started_at),
+            total: now.duration_since(started_at),
+        };
+        if self.frames.len() == MAX_FRAMES {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+        self.record_span("Frame", frame.total);
+
+        // Views that weren't drawn in this frame were released or are no longer visible.
+        self.view_names
+            .retain(|view_id, _| self.current_views.contains_key(view_id));
+        let mut views = FxHashMap::<&'static str, ViewTiming>::default();
+        for (view_id, (layout, paint)) in self.current_views.drain() {
+            let name = self.view_names.get(&view_id).copied().unwrap_or("View");
+            let timing = views.entry(name).or_insert(ViewTiming {
+                name,
+                count: 0,
+                layout: Duration::ZERO,
+                paint: Duration::ZERO,
+            });
+            timing.count += 1;
+            timing.layout += layout;
+            timing.paint += paint;
+        }
+        self.views = views.into_values().collect();
+        self.views
+            .sort_by(|a, b| b.total().cmp(&a.total()).then_with(|| a.name.cmp(b.name)));
+
+        self.spans
+            .retain(|span| now.duration_since(span.recorded_at) < SPAN_RETENTION);
+    }
+}
+
+/// Turns a type name like `editor::Editor` or `ui::Tooltip<ui::Label>` into `Editor`
+/// or `Tooltip`.
+fn short_type_name(type_name: &'static str) -> &'static str {
+    let name = type_name.split('<').next().unwrap_or(type_name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_timings_exclude_child_views() {
+        let mut timings = FrameTimings::default();
+        let workspace = EntityId::from(1);
+        let editor = EntityId::from(2);
+        timings.set_view_name(workspace, "workspace::Workspace");
+        timings.set_view_name(editor, "editor::Editor");
+
+        timings.start_frame();
+        timings.start_view();
+        timings.start_view();
+        timings.end_view(editor, Duration::from_millis(3));
+        timings.end_view(workspace, Duration::from_millis(5));
+        timings.start_painting();
+        timings.start_view();
+        timings.end_view(workspace, Duration::from_millis(2));
+        timings.finish_frame();
+
+        let views = timings.views();
+        assert_eq!(views.len(), 2);
+        assert_eq!(views[0].name, "Workspace");
+        assert_eq!(views[0].layout, Duration::from_millis(2));
+        assert_eq!(views[0].paint, Duration::from_millis(2));
+        assert_eq!(views[1].name, "Editor");
+        assert_eq!(views[1].layout, Duration::from_millis(3));
+
+        let spans = timings
+            .slowest_spans()
+            .iter()
+            .map(|span| span.name.as_ref())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            spans,
+            ["Workspace layout", "Editor layout", "Workspace paint"]
+        );
+        assert_eq!(timings.frames().len(), 1);
+    }
+
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(short_type_name("editor::Editor"), "Editor");
+        assert_eq!(short_type_name("ui::Tooltip<ui::Label>"), "Tooltip");
+        assert_eq!(short_type_name("EmptyView"), "EmptyView");
+    }
+}
@@ -0,0 +1,421 @@
+use std::{
+    collections::BTreeMap,
+    fs::OpenOptions,
+    io::{Read as _, Seek as _, SeekFrom, Write as _},
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{anyhow, Context as _, Result};
+use collections::{HashMap, HashSet};
+use gpui::{App, AppContext as _, Context, Entity, EventEmitter, Task};
+use project::{Project, ProjectEntryId, ProjectItem, ProjectPath};
+
+/// The number of bytes read from disk at once. Only the pages around what is being viewed are
+/// kept in memory, so that files of any size can be opened.
+pub const PAGE_SIZE: u64 = 64 * 1024;
+const MAX_CACHED_PAGES: usize = 64;
+/// The number of bytes searched at once.
+const SEARCH_CHUNK_SIZE: u64 = 1024 * 1024;
+
+pub enum BinaryFileEvent {
+    DirtyChanged,
+    Saved,
+}
+
+/// A file on disk whose bytes can be viewed and overwritten, read one page at a time.
+pub struct BinaryFile {
+    project_path: ProjectPath,
+    entry_id: Option<ProjectEntryId>,
+    abs_path: PathBuf,
+    len: u64,
+    pages: HashMap<u64, Arc<[u8]>>,
+    loading_pages: HashSet<u64>,
+    /// The bytes that were changed and not saved yet, by their offset.
+    edits: BTreeMap<u64, u8>,
+    /// The offsets of the edited bytes and the edits they replaced, most recent last.
+    undo_stack: Vec<(u64, Option<u8>)>,
+}
+
+impl EventEmitter<BinaryFileEvent> for BinaryFile {}
+
+impl BinaryFile {
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn abs_path(&self) -> &Path {
+        &self.abs_path
+    }
+
+    pub fn project_path(&self) -> &ProjectPath {
+        &self.project_path
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        !self.edits.is_empty()
+    }
+
+    pub fn is_edited(&self, offset: u64) -> bool {
+        self.edits.contains_key(&offset)
+    }
+
+    /// Returns the byte at the given offset, or `None` if its page isn't loaded yet.
+    pub fn byte(&self, offset: u64) -> Option<u8> {
+        if offset >= self.len {
+            return None;
+        }
+        if let Some(byte) = self.edits.get(&offset) {
+            return Some(*byte);
+        }
+        let page = self.pages.get(&(offset / PAGE_SIZE))?;
+        page.get((offset % PAGE_SIZE) as usize).copied()
+    }
+
+    /// Loads the pages that hold the given range of bytes, evicting the pages that are furthest
+    /// away from it once too many pages are loaded.
+    pub fn load_range(&mut self, range: Range<u64>, cx: &mut Context<Self>) {
+        if range.is_empty() {
+            return;
+        }
+        let first_page = range.start / PAGE_SIZE;
+        let last_page = (range.end - 1).min(self.len.saturating_sub(1)) / PAGE_SIZE;
+
+        if self.pages.len() > MAX_CACHED_PAGES {
+            let mut cached_pages = self.pages.keys().copied().collect::<Vec<_>>();
+            cached_pages.sort_by_key(|page| page.abs_diff(first_page));
+            for page in cached_pages.drain(MAX_CACHED_PAGES / 2..) {
+                self.pages.remove(&page);
+            }
+        }
+
+        for page in first_page..=last_page {
+            if self.pages.contains_key(&page) || !self.loading_pages.insert(page) {
+                continue;
+            }
+            let abs_path = self.abs_path.clone();
+            cx.spawn(async move |this, cx| {
+                let bytes = cx
+                    .background_spawn(async move {
+                        read_range(&abs_path, page * PAGE_SIZE, PAGE_SIZE as usize)
+                    })
+                    .await;
+                this.update(cx, |this, cx| {
+                    this.loading_pages.remove(&page);
+                    match bytes {
+                        Ok(bytes) => {
+                            this.pages.insert(page, bytes.into());
+                            cx.notify();
+                        }
+                        Err(error) => log::error!("failed to read {:?}: {error}", this.abs_path),
+                    }
+                })
+                .ok();
+            })
+            .detach();
+        }
+    }
+
+    /// Overwrites the byte at the given offset, which must be loaded.
+    pub fn set_byte(&mut self, offset: u64, value: u8, cx: &mut Context<Self>) {
+        let Some(current) = self.byte(offset) else {
+            return;
+        };
+        if current == value {
+            return;
+        }
+
+        let was_dirty = self.is_dirty();
+        let previous_edit = self.edits.insert(offset, value);
+        self.undo_stack.push((offset, previous_edit));
+        // Changing a byte back to what's on disk is no longer an edit.
+        if self.disk_byte(offset) == Some(value) {
+            self.edits.remove(&offset);
+        }
+        if was_dirty != self.is_dirty() {
+            cx.emit(BinaryFileEvent::DirtyChanged);
+        }
+        cx.notify();
+    }
+
+    /// Reverts the most recent edit, returning the offset of the reverted byte.
+    pub fn undo(&mut self, cx: &mut Context<Self>) -> Option<u64> {
+        let (offset, previous_edit) = self.undo_stack.pop()?;
+        let was_dirty = self.is_dirty();
+        match previous_edit {
+            Some(byte) => self.edits.insert(offset, byte),
+            None => self.edits.remove(&offset),
+        };
+        if was_dirty != self.is_dirty() {
+            cx.emit(BinaryFileEvent::DirtyChanged);
+        }
+        cx.notify();
+        Some(offset)
+    }
+
+    fn disk_byte(&self, offset: u64) -> Option<u8> {
+        let page = self.pages.get(&(offset / PAGE_SIZE))?;
+        page.get((offset % PAGE_SIZE) as usize).copied()
+    }
+
+    /// Writes the edited bytes to disk, in place.
+    pub fn save(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let edits = self.edits.clone();
+        let abs_path = self.abs_path.clone();
+        cx.spawn(async move |this, cx| {
+            cx.background_spawn({
+                let edits = edits.clone();
+                async move {
+                    let mut file = OpenOptions::new()
+                        .write(true)
+                        .open(&abs_path)
+                        .with_context(|| format!("opening {abs_path:?}"))?;
+                    for (offset, byte) in edits {
+                        file.seek(SeekFrom::Start(offset))?;
+                        file.write_all(&[byte])?;
+                    }
+                    file.flush()?;
+                    anyhow::Ok(())
+                }
+            })
+            .await?;
+
+            this.update(cx, |this, cx| {
+                // Keep the edits made while saving.
+                this.edits
+                    .retain(|offset, byte| edits.get(offset) != Some(byte));
+                this.pages.clear();
+                this.undo_stack.clear();
+                cx.emit(BinaryFileEvent::DirtyChanged);
+                cx.emit(BinaryFileEvent::Saved);
+                cx.notify();
+            })
+        })
+    }
+
+    /// Discards the edits and reads the file from disk again.
+    pub fn reload(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let abs_path = self.abs_path.clone();
+        cx.spawn(async move |this, cx| {
+            let len = cx
+                .background_spawn(async move { std::fs::metadata(&abs_path) })
+                .await?
+                .len();
+            this.update(cx, |this, cx| {
+                let was_dirty = this.is_dirty();
+                this.len = len;
+                this.pages.clear();
+                this.edits.clear();
+                this.undo_stack.clear();
+                if was_dirty {
+                    cx.emit(BinaryFileEvent::DirtyChanged);
+                }
+                cx.notify();
+            })
+        })
+    }
+
+    /// Searches for the next occurrence of the pattern after `start`, or before it if
+    /// `backwards` is set, wrapping around the end of the file.
+    pub fn search(
+        &self,
+        pattern: Vec<u8>,
+        start: u64,
+        backwards: bool,
+        cx: &App,
+    ) -> Task<Result<Option<u64>>> {
+        let abs_path = self.abs_path.clone();
+        let edits = self.edits.clone();
+        let len = self.len;
+        cx.background_spawn(async move {
+            let read = |offset: u64, len: usize| {
+                let mut bytes = read_range(&abs_path, offset, len)?;
+                for (edit_offset, byte) in edits.range(offset..offset + bytes.len() as u64) {
+                    bytes[(edit_offset - offset) as usize] = *byte;
+                }
+                Ok(bytes)
+            };
+            find_pattern(read, len, &pattern, start, backwards, SEARCH_CHUNK_SIZE)
+        })
+    }
+}
+
+fn read_range(path: &Path, offset: u64, len: usize) -> std::io::Result<Vec<u8>> {
+    let mut file = std::fs::File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut bytes = Vec::with_capacity(len);
+    file.take(len as u64).read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Finds the offset of the first occurrence of `pattern` after `start`, or the last one
+/// before it if `backwards` is set, wrapping around. The bytes are read in chunks, which
+/// overlap so that occurrences spanning two chunks are found.
+fn find_pattern(
+    mut read: impl FnMut(u64, usize) -> std::io::Result<Vec<u8>>,
+    len: u64,
+    pattern: &[u8],
+    start: u64,
+    backwards: bool,
+    chunk_size: u64,
+) -> Result<Option<u64>> {
+    if pattern.is_empty() || pattern.len() as u64 > len {
+        return Ok(None);
+    }
+    let overlap = pattern.len() as u64 - 1;
+    let chunk_starts = (0..len).step_by(chunk_size as usize).collect::<Vec<_>>();
+    let mut matches_in_chunk = |chunk_start: u64| -> Result<Vec<u64>> {
+        let bytes = read(chunk_start, (chunk_size + overlap) as usize)?;
+        Ok(bytes
+            .windows(pattern.len())
+            .enumerate()
+            .filter(|(_, window)| *window == pattern)
+            .map(|(ix, _)| chunk_start + ix as u64)
+            // Occurrences starting in the overlap are found in the next chunk.
+            .filter(|offset| *offset < chunk_start + chunk_size)
+            .collect())
+    };
+
+    let mut first_match = None;
+    let mut last_match = None;
+    if backwards {
+        for chunk_start in chunk_starts.into_iter().rev() {
+            let matches = matches_in_chunk(chunk_start)?;
+            if let Some(offset) = matches.iter().rev().find(|offset| **offset < start) {
+                return Ok(Some(*offset));
+            }
+            last_match = last_match.or(matches.last().copied());
+        }
+        Ok(last_match)
+    } else {
+        for chunk_start in chunk_starts {
+            if chunk_start + chunk_size <= start && first_match.is_some() {
+                continue;
+            }
+            let matches = matches_in_chunk(chunk_start)?;
+            if let Some(offset) = matches.iter().find(|offset| **offset > start) {
+                return Ok(Some(*offset));
+            }
+            first_match = first_match.or(matches.first().copied());
+        }
+        Ok(first_match)
+    }
+}
+
+/// Parses a search pattern of hexadecimal bytes, e.g. `DE AD be ef` or `0xdeadbeef`.
+pub fn parse_hex_pattern(pattern: &str) -> Option<Vec<u8>> {
+    let digits = pattern
+        .split_whitespace()
+        .map(|part| {
+            part.strip_prefix("0x")
+                .or_else(|| part.strip_prefix("0X"))
+                .unwrap_or(part)
+        })
+        .collect::<String>();
+    if digits.is_empty() || digits.len() % 2 != 0 {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|ix| u8::from_str_radix(digits.get(ix..ix + 2)?, 16).ok())
+        .collect()
+}
+
+impl ProjectItem for BinaryFile {
+    fn try_open(
+        project: &Entity<Project>,
+        path: &ProjectPath,
+        cx: &mut App,
+    ) -> Option<Task<Result<Entity<Self>>>> {
+        let worktree = project.read(cx).worktree_for_id(path.worktree_id, cx)?;
+        let worktree = worktree.read(cx);
+        // Pages are read straight from disk, which isn't possible for remote projects.
+        if !worktree.is_local() {
+            return None;
+        }
+        let abs_path = worktree.abs_path().join(&path.path);
+        let entry_id = worktree.entry_for_path(&path.path).map(|entry| entry.id);
+        let project_path = path.clone();
+
+        Some(cx.spawn(async move |cx| {
+            let metadata = cx
+                .background_spawn({
+                    let abs_path = abs_path.clone();
+                    async move { std::fs::metadata(&abs_path) }
+                })
+                .await
+                .with_context(|| format!("reading metadata of {abs_path:?}"))?;
+            if !metadata.is_file() {
+                return Err(anyhow!("{abs_path:?} is not a file"));
+            }
+            cx.new(|_| BinaryFile {
+                project_path,
+                entry_id,
+                abs_path,
+                len: metadata.len(),
+                pages: HashMap::default(),
+                loading_pages: HashSet::default(),
+                edits: BTreeMap::new(),
+                undo_stack: Vec::new(),
+            })
+        }))
+    }
+
+    fn entry_id(&self, _: &App) -> Option<ProjectEntryId> {
+        self.entry_id
+    }
+
+    fn project_path(&self, _: &App) -> Option<ProjectPath> {
+        Some(self.project_path.clone())
+    }
+
+    fn is_dirty(&self) -> bool {
+        BinaryFile::is_dirty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(haystack: &[u8], pattern: &[u8], start: u64, backwards: bool) -> Option<u64> {
+        let read = |offset: u64, len: usize| {
+            let start = (offset as usize).min(haystack.len());
+            let end = (start + len).min(haystack.len());
+            Ok(haystack[start..end].to_vec())
+        };
+        // Small chunks, to find occurrences spanning several of them.
+        find_pattern(read, haystack.len() as u64, pattern, start, backwards, 4).unwrap()
+    }
+
+    #[test]
+    fn test_find_pattern() {
+        let haystack = b"xxABCxxxxABCxxABC";
+        assert_eq!(find(haystack, b"ABC", 0, false), Some(2));
+        assert_eq!(find(haystack, b"ABC", 2, false), Some(9));
+        assert_eq!(find(haystack, b"ABC", 9, false), Some(14));
+        // Wraps around the end of the file.
+        assert_eq!(find(haystack, b"ABC", 14, false), Some(2));
+        assert_eq!(find(haystack, b"ABC", 14, true), Some(9));
+        assert_eq!(find(haystack, b"ABC", 2, true), Some(14));
+        assert_eq!(find(haystack, b"ABD", 0, false), None);
+        assert_eq!(find(haystack, b"", 0, false), None);
+    }
+
+    #[test]
+    fn test_parse_hex_pattern() {
+        assert_eq!(
+            parse_hex_pattern("DE AD be ef"),
+            Some(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(parse_hex_pattern("0x7f454c46"), Some(b"\x7fELF".to_vec()));
+        assert_eq!(parse_hex_pattern("abc"), None);
+        assert_eq!(parse_hex_pattern("zz"), None);
+        assert_eq!(parse_hex_pattern("  "), None);
+    }
+}
@@ -0,0 +1,727 @@
+mod binary_file;
+
+use std::ops::Range;
+
+use anyhow::Result;
+use editor::{actions::SelectAll, Editor, EditorEvent};
+use file_icons::FileIcons;
+use gpui::{
+    actions, div, uniform_list, App, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, KeyDownEvent, ParentElement, Render, ScrollStrategy, Styled,
+    Subscription, Task, UniformListScrollHandle, Window,
+};
+use project::Project;
+use settings::Settings;
+use ui::{prelude::*, Tooltip};
+use util::paths::PathExt;
+use workspace::{
+    item::{Item, ItemEvent, ProjectItem, TabContentParams},
+    ItemSettings, Pane, WorkspaceId,
+};
+
+pub use crate::binary_file::*;
+
+actions!(
+    hex_editor,
+    [
+        MoveLeft,
+        MoveRight,
+        MoveUp,
+        MoveDown,
+        PageUp,
+        PageDown,
+        MoveToBeginning,
+        MoveToEnd,
+        SwitchColumn,
+        Undo,
+        Find,
+        FindNext,
+        FindPrevious,
+        ToggleSearchMode
+    ]
+);
+
+const BYTES_PER_ROW: u64 = 16;
+const ROWS_PER_PAGE: u64 = 32;
+
+pub fn init(cx: &mut App) {
+    workspace::register_binary_project_item::<HexEditor>(cx);
+}
+
+/// The column in which bytes are edited.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Hex,
+    Text,
+}
+
+/// How the search query is interpreted.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SearchMode {
+    Hex,
+    Text,
+}
+
+/// A view of the bytes of a file that isn't text, as hexadecimal next to their ASCII
+/// characters.
+pub struct HexEditor {
+    file: Entity<BinaryFile>,
+    project: Entity<Project>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    cursor: u64,
+    /// Whether the high nibble of the byte under the cursor was typed, and the low one is next.
+    editing_low_nibble: bool,
+    column: Column,
+    search_editor: Entity<Editor>,
+    search_mode: SearchMode,
+    search_match: Option<Range<u64>>,
+    search_status: Option<SharedString>,
+    _search_task: Option<Task<Result<()>>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl HexEditor {
+    pub fn new(
+        file: Entity<BinaryFile>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let search_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Search hex bytes, e.g. DE AD BE EF…", cx);
+            editor
+        });
+        let subscriptions = vec![
+            cx.subscribe(&file, |_, _, event, cx| match event {
+                BinaryFileEvent::DirtyChanged | BinaryFileEvent::Saved => {
+                    cx.emit(HexEditorEvent::TitleChanged)
+                }
+            }),
+            cx.observe(&file, |_, _, cx| cx.notify()),
+            cx.subscribe(&search_editor, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.search_match = None;
+                    this.search_status = None;
+                    cx.notify();
+                }
+            }),
+        ];
+
+        Self {
+            file,
+            project,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            cursor: 0,
+            editing_low_nibble: false,
+            column: Column::Hex,
+            search_editor,
+            search_mode: SearchMode::Hex,
+            search_match: None,
+            search_status: None,
+            _search_task: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn move_cursor_to(&mut self, offset: u64, cx: &mut Context<Self>) {
+        let len = self.file.read(cx).len();
+        self.cursor = offset.min(len.saturating_sub(1));
+        self.editing_low_nibble = false;
+        self.scroll_handle
+            .scroll_to_item((self.cursor / BYTES_PER_ROW) as usize, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    fn move_left(&mut self, _: &MoveLeft, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor_to(self.cursor.saturating_sub(1), cx);
+    }
+
+    fn move_right(&mut self, _: &MoveRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor_to(self.cursor + 1, cx);
+    }
+
+    fn move_up(&mut self, _: &MoveUp, _: &mut Window, cx: &mut Context<Self>) {
+        if self.cursor >= BYTES_PER_ROW {
+            self.move_cursor_to(self.cursor - BYTES_PER_ROW, cx);
+        }
+    }
+
+    fn move_down(&mut self, _: &MoveDown, _: &mut Window, cx: &mut Context<Self>) {
+        if self.cursor + BYTES_PER_ROW < self.file.read(cx).len() {
+            self.move_cursor_to(self.cursor + BYTES_PER_ROW, cx);
+        }
+    }
+
+    fn page_up(&mut self, _: &PageUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor_to(
+            self.cursor.saturating_sub(BYTES_PER_ROW * ROWS_PER_PAGE),
+            cx,
+        );
+    }
+
+    fn page_down(&mut self, _: &PageDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor_to(self.cursor + BYTES_PER_ROW * ROWS_PER_PAGE, cx);
+    }
+
+    fn move_to_beginning(&mut self, _: &MoveToBeginning, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor_to(0, cx);
+    }
+
+    fn move_to_end(&mut self, _: &MoveToEnd, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor_to(u64::MAX, cx);
+    }
+
+    fn switch_column(&mut self, _: &SwitchColumn, _: &mut Window, cx: &mut Context<Self>) {
+        self.column = match self.column {
+            Column::Hex => Column::Text,
+            Column::Text => Column::Hex,
+        };
+        self.editing_low_nibble = false;
+        cx.notify();
+    }
+
+    fn undo(&mut self, _: &Undo, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(offset) = self.file.update(cx, |file, cx| file.undo(cx)) {
+            self.move_cursor_to(offset, cx);
+        }
+    }
+
+    fn select_byte(
+        &mut self,
+        offset: u64,
+        column: Column,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.column = column;
+        self.move_cursor_to(offset, cx);
+        window.focus(&self.focus_handle);
+    }
+
+    /// Overwrites the byte under the cursor with the typed hex digit or character.
+    fn key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.focus_handle.is_focused(window) {
+            return;
+        }
+        let keystroke = &event.keystroke;
+        if keystroke.modifiers.control || keystroke.modifiers.platform {
+            return;
+        }
+        let Some(character) = keystroke
+            .key_char
+            .as_deref()
+            .and_then(|key_char| key_char.chars().next())
+        else {
+            return;
+        };
+        let Some(byte) = self.file.read(cx).byte(self.cursor) else {
+            return;
+        };
+
+        let new_byte = match self.column {
+            Column::Hex => {
+                let Some(digit) = character.to_digit(16) else {
+                    return;
+                };
+                let digit = digit as u8;
+                if self.editing_low_nibble {
+                    (byte & 0xf0) | digit
+                } else {
+                    (byte & 0x0f) | (digit << 4)
+                }
+            }
+            Column::Text => {
+                if !character.is_ascii() || character.is_ascii_control() {
+                    return;
+                }
+                character as u8
+            }
+        };
+
+        let cursor = self.cursor;
+        self.file
+            .update(cx, |file, cx| file.set_byte(cursor, new_byte, cx));
+        if self.column == Column::Hex && !self.editing_low_nibble {
+            self.editing_low_nibble = true;
+            cx.notify();
+        } else {
+            self.move_cursor_to(cursor + 1, cx);
+        }
+        cx.stop_propagation();
+    }
+
+    fn find(&mut self, _: &Find, window: &mut Window, cx: &mut Context<Self>) {
+        self.search_editor.update(cx, |editor, cx| {
+            editor.select_all(&SelectAll, window, cx);
+        });
+        window.focus(&self.search_editor.focus_handle(cx));
+    }
+
+    fn toggle_search_mode(&mut self, _: &ToggleSearchMode, _: &mut Window, cx: &mut Context<Self>) {
+        self.search_mode = match self.search_mode {
+            SearchMode::Hex => SearchMode::Text,
+            SearchMode::Text => SearchMode::Hex,
+        };
+        let placeholder = match self.search_mode {
+            SearchMode::Hex => "Search hex bytes, e.g. DE AD BE EF…",
+            SearchMode::Text => "Search text…",
+        };
+        self.search_editor.update(cx, |editor, cx| {
+            editor.set_placeholder_text(placeholder, cx)
+        });
+        self.search_match = None;
+        self.search_status = None;
+        cx.notify();
+    }
+
+    fn find_next(&mut self, _: &FindNext, _: &mut Window, cx: &mut Context<Self>) {
+        self.search(false, cx);
+    }
+
+    fn find_previous(&mut self, _: &FindPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        self.search(true, cx);
+    }
+
+    fn search(&mut self, backwards: bool, cx: &mut Context<Self>) {
+        let query = self.search_editor.read(cx).text(cx);
+        let pattern = match self.search_mode {
+            SearchMode::Hex => parse_hex_pattern(&query),
+            SearchMode::Text => Some(query.into_bytes()).filter(|pattern| !pattern.is_empty()),
+        };
+        let Some(pattern) = pattern else {
+            self.search_status = Some("Invalid search".into());
+            cx.notify();
+            return;
+        };
+
+        let pattern_len = pattern.len() as u64;
+        let start = self
+            .search_match
+            .as_ref()
+            .map_or(self.cursor, |search_match| search_match.start);
+        let search = self.file.read(cx).search(pattern, start, backwards, cx);
+        self.search_status = Some("Searching…".into());
+        cx.notify();
+        self._search_task = Some(cx.spawn(async move |this, cx| {
+            let offset = search.await?;
+            this.update(cx, |this, cx| {
+                match offset {
+                    Some(offset) => {
+                        this.search_match = Some(offset..offset + pattern_len);
+                        this.search_status = None;
+                        this.move_cursor_to(offset, cx);
+                    }
+                    None => {
+                        this.search_match = None;
+                        this.search_status = Some("No matches".into());
+                    }
+                }
+                cx.notify();
+            })
+        }));
+    }
+
+    fn offset_digits(&self, cx: &App) -> usize {
+        let len = self.file.read(cx).len();
+        let digits = (u64::BITS - len.leading_zeros()).div_ceil(4) as usize;
+        digits.max(8)
+    }
+
+    fn render_search_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        let mode_label = match self.search_mode {
+            SearchMode::Hex => "Hex",
+            SearchMode::Text => "Text",
+        };
+
+        h_flex()
+            .key_context("HexEditorSearchBar")
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                h_flex()
+                    .flex_1()
+                    .gap_1()
+                    .child(
+                        Icon::new(IconName::MagnifyingGlass)
+                            .size(IconSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(self.search_editor.clone()),
+            )
+            .children(self.search_status.clone().map(|status| {
+                Label::new(status)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+            }))
+            .children(self.search_match.as_ref().map(|search_match| {
+                Label::new(format!(
+                    "{:#x}–{:#x}",
+                    search_match.start,
+                    search_match.end - 1
+                ))
+                .size(LabelSize::Small)
+                .color(Color::Muted)
+            }))
+            .child(
+                Button::new("search-mode", mode_label)
+                    .label_size(LabelSize::Small)
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |window, cx| {
+                            Tooltip::for_action_in(
+                                "Toggle Hex or Text Search",
+                                &ToggleSearchMode,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                        }
+                    })
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.toggle_search_mode(&ToggleSearchMode, window, cx)
+                    })),
+            )
+            .child(
+                IconButton::new("find-previous", IconName::ChevronLeft)
+                    .icon_size(IconSize::Small)
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |window, cx| {
+                            Tooltip::for_action_in(
+                                "Find Previous",
+                                &FindPrevious,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                        }
+                    })
+                    .on_click(cx.listener(|this, _, _, cx| this.search(true, cx))),
+            )
+            .child(
+                IconButton::new("find-next", IconName::ChevronRight)
+                    .icon_size(IconSize::Small)
+                    .tooltip(move |window, cx| {
+                        Tooltip::for_action_in("Find Next", &FindNext, &focus_handle, window, cx)
+                    })
+                    .on_click(cx.listener(|this, _, _, cx| this.search(false, cx))),
+            )
+    }
+
+    fn render_rows(
+        &mut self,
+        range: Range<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        let first_offset = range.start as u64 * BYTES_PER_ROW;
+        let last_offset = range.end as u64 * BYTES_PER_ROW;
+        self.file.update(cx, |file, cx| {
+            file.load_range(first_offset..last_offset, cx)
+        });
+
+        let offset_digits = self.offset_digits(cx);
+        let is_focused = self.focus_handle.is_focused(window);
+        let colors = cx.theme().colors();
+        let cursor_color = cx.theme().players().local().cursor;
+        let selection_color = cx.theme().players().local().selection;
+        let match_color = colors.search_match_background;
+        let modified_color = cx.theme().status().modified;
+        let file = self.file.read(cx);
+
+        range
+            .map(|row| {
+                let row_offset = row as u64 * BYTES_PER_ROW;
+                let offsets = row_offset..(row_offset + BYTES_PER_ROW).min(file.len());
+                let is_loaded = file.byte(row_offset).is_some();
+
+                let cell_background = |offset: u64, column: Column| {
+                    if offset == self.cursor {
+                        if column == self.column && is_focused {
+                            Some(cursor_color.opacity(0.4))
+                        } else {
+                            Some(selection_color)
+                        }
+                    } else if self
+                        .search_match
+                        .as_ref()
+                        .is_some_and(|search_match| search_match.contains(&offset))
+                    {
+                        Some(match_color)
+                    } else {
+                        None
+                    }
+                };
+
+                let hex_cells = offsets.clone().map(|offset| {
+                    let text = file
+                        .byte(offset)
+                        .map_or_else(|| "··".to_string(), |byte| format!("{byte:02X}"));
+                    div()
+                        .id(("hex", offset as usize))
+                        .px_0p5()
+                        // Leave a gap between the two halves of the row.
+                        .when(offset % BYTES_PER_ROW == BYTES_PER_ROW / 2, |this| {
+                            this.ml_2()
+                        })
+                        .when_some(cell_background(offset, Column::Hex), |this, color| {
+                            this.bg(color)
+                        })
+                        .when(file.is_edited(offset), |this| {
+                            this.text_color(modified_color)
+                        })
+                        .child(text)
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.select_byte(offset, Column::Hex, window, cx)
+                        }))
+                });
+                let text_cells = offsets.clone().map(|offset| {
+                    let character = match file.byte(offset) {
+                        Some(byte) if byte.is_ascii_graphic() || byte == b' ' => byte as char,
+                        Some(_) => '.',
+                        None => '·',
+                    };
+                    div()
+                        .id(("text", offset as usize))
+                        .when_some(cell_background(offset, Column::Text), |this, color| {
+                            this.bg(color)
+                        })
+                        .when(file.is_edited(offset), |this| {
+                            this.text_color(modified_color)
+                        })
+                        .child(character.to_string())
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.select_byte(offset, Column::Text, window, cx)
+                        }))
+                });
+
+                h_flex()
+                    .px_2()
+                    .gap_4()
+                    .when(!is_loaded, |this| this.text_color(colors.text_muted))
+                    .child(
+                        div()
+                            .text_color(colors.editor_line_number)
+                            .child(format!("{row_offset:0offset_digits$X}")),
+                    )
+                    .child(
+                        h_flex()
+                            .w(rems(BYTES_PER_ROW as f32 * 1.5 + 1.))
+                            .children(hex_cells),
+                    )
+                    .child(h_flex().children(text_cells))
+                    .into_any_element()
+            })
+            .collect()
+    }
+
+    fn render_status(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let file = self.file.read(cx);
+        let position = if file.is_empty() {
+            "Empty file".to_string()
+        } else {
+            let byte = file
+                .byte(self.cursor)
+                .map(|byte| format!(" · Value {byte} ({byte:#04x})"))
+                .unwrap_or_default();
+            format!("Offset {:#x} ({}){byte}", self.cursor, self.cursor)
+        };
+
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .justify_between()
+            .border_t_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                Label::new(position)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                Label::new(format!("{} bytes", file.len()))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+    }
+}
+
+pub enum HexEditorEvent {
+    TitleChanged,
+}
+
+impl EventEmitter<HexEditorEvent> for HexEditor {}
+
+impl Focusable for HexEditor {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for HexEditor {
+    type Event = HexEditorEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        match event {
+            HexEditorEvent::TitleChanged => f(ItemEvent::UpdateTab),
+        }
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        f(self.file.entity_id(), self.file.read(cx))
+    }
+
+    fn is_singleton(&self, _cx: &App) -> bool {
+        true
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        let abs_path = self.file.read(cx).abs_path();
+        Some(abs_path.compact().to_string_lossy().to_string().into())
+    }
+
+    fn tab_content(&self, params: TabContentParams, _: &Window, cx: &App) -> AnyElement {
+        let title = self
+            .file
+            .read(cx)
+            .project_path()
+            .path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        Label::new(title)
+            .single_line()
+            .color(params.text_color())
+            .when(params.preview, |this| this.italic())
+            .into_any_element()
+    }
+
+    fn tab_icon(&self, _: &Window, cx: &App) -> Option<Icon> {
+        let path = self.file.read(cx).project_path().path.clone();
+        ItemSettings::get_global(cx)
+            .file_icons
+            .then(|| FileIcons::get_icon(&path, cx))
+            .flatten()
+            .map(Icon::from_path)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Hex Editor Opened")
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<WorkspaceId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<Self>>
+    where
+        Self: Sized,
+    {
+        Some(cx.new(|cx| Self::new(self.file.clone(), self.project.clone(), window, cx)))
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.file.read(cx).is_dirty()
+    }
+
+    fn can_save(&self, _cx: &App) -> bool {
+        true
+    }
+
+    fn save(
+        &mut self,
+        _format: bool,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.file.update(cx, |file, cx| file.save(cx))
+    }
+
+    fn reload(
+        &mut self,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.file.update(cx, |file, cx| file.reload(cx))
+    }
+}
+
+impl Render for HexEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let row_count = self.file.read(cx).len().div_ceil(BYTES_PER_ROW) as usize;
+
+        v_flex()
+            .id("HexEditor")
+            .key_context("HexEditor")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .on_action(cx.listener(Self::move_left))
+            .on_action(cx.listener(Self::move_right))
+            .on_action(cx.listener(Self::move_up))
+            .on_action(cx.listener(Self::move_down))
+            .on_action(cx.listener(Self::page_up))
+            .on_action(cx.listener(Self::page_down))
+            .on_action(cx.listener(Self::move_to_beginning))
+            .on_action(cx.listener(Self::move_to_end))
+            .on_action(cx.listener(Self::switch_column))
+            .on_action(cx.listener(Self::undo))
+            .on_action(cx.listener(Self::find))
+            .on_action(cx.listener(Self::find_next))
+            .on_action(cx.listener(Self::find_previous))
+            .on_action(cx.listener(Self::toggle_search_mode))
+            .on_action(cx.listener(|this, _: &menu::Cancel, window, cx| {
+                window.focus(&this.focus_handle);
+                cx.notify();
+            }))
+            .on_key_down(cx.listener(Self::key_down))
+            .child(self.render_search_bar(cx))
+            .child(
+                uniform_list(
+                    cx.entity(),
+                    "hex-editor-rows",
+                    row_count,
+                    |this, range, window, cx| this.render_rows(range, window, cx),
+                )
+                .flex_1()
+                .py_1()
+                .font_buffer(cx)
+                .text_sm()
+                .track_scroll(self.scroll_handle.clone()),
+            )
+            .child(self.render_status(cx))
+    }
+}
+
+impl ProjectItem for HexEditor {
+    type Item = BinaryFile;
+
+    fn for_project_item(
+        project: Entity<Project>,
+        _: &Pane,
+        item: Entity<Self::Item>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(item, project, window, cx)
+    }
+}
@@ -1,10 +1,15 @@
 mod async_body;
 pub mod github;
+mod proxy;
 
 pub use anyhow::{anyhow, Result};
 pub use async_body::{AsyncBody, Inner};
 use derive_more::Deref;
 pub use http::{self, Method, Request, Response, StatusCode, Uri};
+pub use proxy::{
+    proxy_credentials, read_no_proxy_from_env, read_proxy_from_env, redact_proxy_uri, NoProxy,
+    ProxyConfig,
+};
 
 use futures::future::BoxFuture;
 use http::request::Builder;
@@ -83,6 +88,19 @@ pub trait HttpClient: 'static + Send + Sync {
     }
 
     fn proxy(&self) -> Option<&Uri>;
+
+    /// The hosts that requests are sent to without the proxy.
+    fn no_proxy(&self) -> Option<&NoProxy> {
+        None
+    }
+
+    /// The proxy that requests are sent through, with the hosts that bypass it.
+    fn proxy_config(&self) -> ProxyConfig {
+        ProxyConfig {
+            uri: self.proxy().cloned(),
+            no_proxy: self.no_proxy().cloned(),
+        }
+    }
 }
 
 /// An [`HttpClient`] that may have a proxy.
@@ -91,6 +109,7 @@ pub struct HttpClientWithProxy {
     #[deref]
     client: Arc<dyn HttpClient>,
     proxy: Option<Uri>,
+    no_proxy: Option<NoProxy>,
 }
 
 impl HttpClientWithProxy {
@@ -103,9 +122,21 @@ impl HttpClientWithProxy {
         Self::new_uri(client, proxy_uri)
     }
     pub fn new_uri(client: Arc<dyn HttpClient>, proxy_uri: Option<Uri>) -> Self {
+        Self::new_proxy_config(
+            client,
+            ProxyConfig {
+                uri: proxy_uri,
+                no_proxy: None,
+            },
+        )
+    }
+
+    /// Returns a new [`HttpClientWithProxy`] with the given proxy and the hosts that bypass it.
+    pub fn new_proxy_config(client: Arc<dyn HttpClient>, proxy_config: ProxyConfig) -> Self {
         Self {
             client,
-            proxy: proxy_uri,
+            proxy: proxy_config.uri,
+            no_proxy: proxy_config.no_proxy,
         }
     }
 }
@@ -122,6 +153,10 @@ impl HttpClient for HttpClientWithProxy {
         self.proxy.as_ref()
     }
 
+    fn no_proxy(&self) -> Option<&NoProxy> {
+        self.no_proxy.as_ref()
+    }
+
     fn type_name(&self) -> &'static str {
         self.client.type_name()
     }
@@ -139,6 +174,10 @@ impl HttpClient for Arc<HttpClientWithProxy> {
         self.proxy.as_ref()
     }
 
+    fn no_proxy(&self) -> Option<&NoProxy> {
+        self.no_proxy.as_ref()
+    }
+
     fn type_name(&self) -> &'static str {
         self.client.type_name()
     }
@@ -186,6 +225,19 @@ impl HttpClientWithUrl {
         }
     }
 
+    pub fn new_proxy_config(
+        client: Arc<dyn HttpClient>,
+        base_url: impl Into<String>,
+        proxy_config: ProxyConfig,
+    ) -> Self {
+        let client = HttpClientWithProxy::new_proxy_config(client, proxy_config);
+
+        Self {
+            base_url: Mutex::new(base_url.into()),
+            client,
+        }
+    }
+
     /// Returns the base URL.
     pub fn base_url(&self) -> String {
         self.base_url
@@ -254,6 +306,10 @@ impl HttpClient for Arc<HttpClientWithUrl> {
         self.client.proxy.as_ref()
     }
 
+    fn no_proxy(&self) -> Option<&NoProxy> {
+        self.client.no_proxy.as_ref()
+    }
+
     fn type_name(&self) -> &'static str {
         self.client.type_name()
     }
@@ -271,28 +327,13 @@ impl HttpClient for HttpClientWithUrl {
         self.client.proxy.as_ref()
     }
 
-    fn type_name(&self) -> &'static str {
-        self.client.type_name()
+    fn no_proxy(&self) -> Option<&NoProxy> {
+        self.client.no_proxy.as_ref()
     }
-}
 
-pub fn read_proxy_from_env() -> Option<Uri> {
-    const ENV_VARS: &[&str] = &[
-        "ALL_PROXY",
-        "all_proxy",
-        "HTTPS_PROXY",
-        "https_proxy",
-        "HTTP_PROXY",
-        "http_proxy",
-    ];
-
-    for var in ENV_VARS {
-        if let Ok(env) = std::env::var(var) {
-            return env.parse::<Uri>().ok();
-        }
+    fn type_name(&self) -> &'static str {
+        self.client.type_name()
     }
-
-    None
 }
 
 pub struct BlockedHttpClient;
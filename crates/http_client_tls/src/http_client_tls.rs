@@ -1,9 +1,54 @@
-use std::sync::OnceLock;
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+};
 
-use rustls::ClientConfig;
-use rustls_platform_verifier::ConfigVerifierExt;
+use anyhow::{Context as _, Result};
+use rustls::{
+    pki_types::{pem::PemObject, CertificateDer},
+    ClientConfig,
+};
+use rustls_platform_verifier::{ConfigVerifierExt, Verifier};
 
 static TLS_CONFIG: OnceLock<rustls::ClientConfig> = OnceLock::new();
+static CUSTOM_CA_CERTIFICATES: OnceLock<Vec<CertificateDer<'static>>> = OnceLock::new();
+
+/// Adds the certificates in the given PEM files to the ones that are trusted by the platform,
+/// for networks where connections are intercepted by a proxy with its own certificate
+/// authority. Must be called before the first connection is made, as the configuration can't
+/// change afterwards.
+pub fn set_custom_ca_certificates(paths: &[PathBuf]) -> Result<()> {
+    let certificates = paths
+        .iter()
+        .map(|path| read_certificates(path))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    if TLS_CONFIG.get().is_some() || CUSTOM_CA_CERTIFICATES.set(certificates).is_err() {
+        log::warn!("custom CA certificates are only applied when Zed starts");
+    }
+    Ok(())
+}
+
+/// The custom CA certificates that are trusted in addition to the platform's.
+pub fn custom_ca_certificates() -> &'static [CertificateDer<'static>] {
+    CUSTOM_CA_CERTIFICATES
+        .get()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn read_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let certificates = CertificateDer::pem_file_iter(path)
+        .and_then(|certificates| certificates.collect::<Result<Vec<_>, _>>())
+        .with_context(|| format!("reading CA certificates from {path:?}"))?;
+    anyhow::ensure!(
+        !certificates.is_empty(),
+        "no CA certificates found in {path:?}"
+    );
+    Ok(certificates)
+}
 
 pub fn tls_config() -> ClientConfig {
     TLS_CONFIG
@@ -15,7 +60,20 @@ pub fn tls_config() -> ClientConfig {
                 .install_default()
                 .ok();
 
-            ClientConfig::with_platform_verifier()
+            let custom_ca_certificates = custom_ca_certificates();
+            if custom_ca_certificates.is_empty() {
+                return ClientConfig::with_platform_verifier();
+            }
+            match Verifier::new_with_extra_roots(custom_ca_certificates.iter().cloned()) {
+                Ok(verifier) => ClientConfig::builder()
+                    .dangerous()
+                    .with_custom_certificate_verifier(Arc::new(verifier))
+                    .with_no_client_auth(),
+                Err(error) => {
+                    log::error!("failed to trust custom CA certificates: {error}");
+                    ClientConfig::with_platform_verifier()
+                }
+            }
         })
         .clone()
 }
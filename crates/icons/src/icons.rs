@@ -131,6 +131,7 @@ pub enum IconName {
     GitBranchSmall,
     Hash,
     HistoryRerun,
+    Image,
     Indicator,
     Info,
     InlayHint,
@@ -0,0 +1,396 @@
+//! A reader for the handful of EXIF fields that are worth showing next to an image.
+//!
+//! EXIF data is a TIFF structure: a byte order marker, followed by directories (IFDs) of tagged
+//! values. Only the primary directory and the Exif and GPS directories it points to are read.
+
+const EXIF_IFD_POINTER: u16 = 0x8769;
+const GPS_IFD_POINTER: u16 = 0x8825;
+
+/// A human readable EXIF field, e.g. `Camera: FUJIFILM X-T3`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExifField {
+    pub label: &'static str,
+    pub value: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Directory {
+    Primary,
+    Exif,
+    Gps,
+}
+
+struct Entry {
+    directory: Directory,
+    tag: u16,
+    value: Value,
+}
+
+enum Value {
+    Text(String),
+    Numbers(Vec<f64>),
+}
+
+impl Value {
+    fn first_number(&self) -> Option<f64> {
+        match self {
+            Value::Numbers(numbers) => numbers.first().copied(),
+            Value::Text(_) => None,
+        }
+    }
+}
+
+struct TiffReader<'a> {
+    data: &'a [u8],
+    big_endian: bool,
+}
+
+impl<'a> TiffReader<'a> {
+    fn new(data: &'a [u8]) -> Option<Self> {
+        let big_endian = match data.get(..2)? {
+            b"II" => false,
+            b"MM" => true,
+            _ => return None,
+        };
+        let reader = Self { data, big_endian };
+        (reader.u16(2)? == 42).then_some(reader)
+    }
+
+    fn u16(&self, offset: usize) -> Option<u16> {
+        let bytes: [u8; 2] = self.data.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    }
+
+    fn u32(&self, offset: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.data.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
+    fn read_directory(&self, offset: usize, directory: Directory, entries: &mut Vec<Entry>) {
+        let Some(count) = self.u16(offset) else {
+            return;
+        };
+        for ix in 0..count as usize {
+            let entry_offset = offset + 2 + ix * 12;
+            let (Some(tag), Some(kind), Some(count)) = (
+                self.u16(entry_offset),
+                self.u16(entry_offset + 2),
+                self.u32(entry_offset + 4),
+            ) else {
+                return;
+            };
+            if let Some(value) = self.read_value(kind, count as usize, entry_offset + 8) {
+                entries.push(Entry {
+                    directory,
+                    tag,
+                    value,
+                });
+            }
+        }
+    }
+
+    fn read_value(&self, kind: u16, count: usize, offset: usize) -> Option<Value> {
+        let size = match kind {
+            1 | 2 | 7 => 1,
+            3 => 2,
+            4 | 9 => 4,
+            5 | 10 => 8,
+            _ => return None,
+        };
+        let len = size * count;
+        // Values that fit into four bytes are stored in place of their offset.
+        let start = if len <= 4 {
+            offset
+        } else {
+            self.u32(offset)? as usize
+        };
+        let bytes = self.data.get(start..start.checked_add(len)?)?;
+
+        Some(match kind {
+            2 => Value::Text(
+                String::from_utf8_lossy(bytes)
+                    .trim_end_matches('\0')
+                    .trim()
+                    .to_string(),
+            ),
+            1 | 7 => Value::Numbers(bytes.iter().map(|byte| *byte as f64).collect()),
+            3 => Value::Numbers(
+                (0..count)
+                    .filter_map(|ix| Some(self.u16(start + ix * 2)? as f64))
+                    .collect(),
+            ),
+            4 => Value::Numbers(
+                (0..count)
+                    .filter_map(|ix| Some(self.u32(start + ix * 4)? as f64))
+                    .collect(),
+            ),
+            9 => Value::Numbers(
+                (0..count)
+                    .filter_map(|ix| Some(self.u32(start + ix * 4)? as i32 as f64))
+                    .collect(),
+            ),
+            _ => Value::Numbers(
+                (0..count)
+                    .filter_map(|ix| {
+                        let numerator = self.u32(start + ix * 8)?;
+                        let denominator = self.u32(start + ix * 8 + 4)?;
+                        let (numerator, denominator) = if kind == 10 {
+                            (numerator as i32 as f64, denominator as i32 as f64)
+                        } else {
+                            (numerator as f64, denominator as f64)
+                        };
+                        (denominator != 0.).then(|| numerator / denominator)
+                    })
+                    .collect(),
+            ),
+        })
+    }
+}
+
+/// Reads the EXIF fields of an image from its raw EXIF data, with or without the `Exif\0\0`
+/// prefix it has in JPEG files.
+pub fn parse_exif(data: &[u8]) -> Vec<ExifField> {
+    let data = data.strip_prefix(b"Exif\0\0").unwrap_or(data);
+    let Some(reader) = TiffReader::new(data) else {
+        return Vec::new();
+    };
+
+    let mut entries = Vec::new();
+    if let Some(offset) = reader.u32(4) {
+        reader.read_directory(offset as usize, Directory::Primary, &mut entries);
+    }
+    let pointers = entries
+        .iter()
+        .filter_map(|entry| match entry.tag {
+            EXIF_IFD_POINTER => Some((Directory::Exif, entry.value.first_number()?)),
+            GPS_IFD_POINTER => Some((Directory::Gps, entry.value.first_number()?)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    for (directory, offset) in pointers {
+        reader.read_directory(offset as usize, directory, &mut entries);
+    }
+
+    let find = |directory: Directory, tag: u16| {
+        entries
+            .iter()
+            .find(|entry| entry.directory == directory && entry.tag == tag)
+            .map(|entry| &entry.value)
+    };
+    let text = |directory: Directory, tag: u16| match find(directory, tag)? {
+        Value::Text(text) if !text.is_empty() => Some(text.clone()),
+        _ => None,
+    };
+    let number = |directory: Directory, tag: u16| find(directory, tag)?.first_number();
+
+    let mut fields = Vec::new();
+    let mut push = |label: &'static str, value: Option<String>| {
+        if let Some(value) = value {
+            fields.push(ExifField { label, value });
+        }
+    };
+
+    let make = text(Directory::Primary, 0x010F);
+    let model = text(Directory::Primary, 0x0110);
+    push(
+        "Camera",
+        match (make, model) {
+            // Models usually repeat the make, as in `Canon` and `Canon EOS R5`.
+            (Some(make), Some(model)) if model.starts_with(&make) => Some(model),
+            (Some(make), Some(model)) => Some(format!("{make} {model}")),
+            (make, model) => make.or(model),
+        },
+    );
+    push("Lens", text(Directory::Exif, 0xA434));
+    push(
+        "Taken",
+        text(Directory::Exif, 0x9003).or_else(|| text(Directory::Primary, 0x0132)),
+    );
+    push(
+        "Exposure",
+        number(Directory::Exif, 0x829A).map(|seconds| {
+            if seconds > 0. && seconds < 1. {
+                format!("1/{} s", (1. / seconds).round())
+            } else {
+                format!("{seconds} s")
+            }
+        }),
+    );
+    push(
+        "Aperture",
+        number(Directory::Exif, 0x829D).map(|f_number| format!("f/{f_number:.1}")),
+    );
+    push(
+        "ISO",
+        number(Directory::Exif, 0x8827).map(|iso| iso.to_string()),
+    );
+    push(
+        "Focal Length",
+        number(Directory::Exif, 0x920A).map(|length| format!("{length} mm")),
+    );
+    push(
+        "Orientation",
+        number(Directory::Primary, 0x0112).and_then(|orientation| {
+            Some(
+                match orientation as u32 {
+                    1 => "Normal",
+                    2 => "Mirrored",
+                    3 => "Rotated 180°",
+                    4 => "Mirrored vertically",
+                    5 => "Mirrored, rotated 90° counterclockwise",
+                    6 => "Rotated 90° clockwise",
+                    7 => "Mirrored, rotated 90° clockwise",
+                    8 => "Rotated 90° counterclockwise",
+                    _ => return None,
+                }
+                .to_string(),
+            )
+        }),
+    );
+    push("Software", text(Directory::Primary, 0x0131));
+    push("Artist", text(Directory::Primary, 0x013B));
+    push("Copyright", text(Directory::Primary, 0x8298));
+
+    let coordinate = |value_tag: u16, reference_tag: u16| {
+        let Value::Numbers(parts) = find(Directory::Gps, value_tag)? else {
+            return None;
+        };
+        let degrees = parts
+            .iter()
+            .zip([1., 60., 3600.])
+            .map(|(part, unit)| part / unit)
+            .sum::<f64>();
+        let reference = text(Directory::Gps, reference_tag)?;
+        Some(format!("{degrees:.5}° {reference}"))
+    };
+    push(
+        "Location",
+        coordinate(2, 1)
+            .zip(coordinate(4, 3))
+            .map(|(latitude, longitude)| format!("{latitude}, {longitude}")),
+    );
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds little endian EXIF data from directories of `(tag, kind, count, payload)` entries,
+    /// where the Exif directory follows the primary one.
+    fn build_exif(
+        primary: &[(u16, u16, u32, Vec<u8>)],
+        exif: &[(u16, u16, u32, Vec<u8>)],
+    ) -> Vec<u8> {
+        fn directory_len(entries: usize) -> usize {
+            2 + entries * 12 + 4
+        }
+
+        let primary_offset = 8;
+        let exif_offset = primary_offset + directory_len(primary.len() + 1);
+        let mut data_offset = exif_offset + directory_len(exif.len());
+        let mut directories = Vec::<u8>::new();
+        let mut data = Vec::<u8>::new();
+
+        let mut write_directory = |entries: &[(u16, u16, u32, Vec<u8>)]| {
+            directories.extend((entries.len() as u16).to_le_bytes());
+            for (tag, kind, count, payload) in entries {
+                directories.extend(tag.to_le_bytes());
+                directories.extend(kind.to_le_bytes());
+                directories.extend(count.to_le_bytes());
+                if payload.len() <= 4 {
+                    let mut value = payload.clone();
+                    value.resize(4, 0);
+                    directories.extend(value);
+                } else {
+                    directories.extend((data_offset as u32).to_le_bytes());
+                    data.extend(payload);
+                    data_offset += payload.len();
+                }
+            }
+            directories.extend(0u32.to_le_bytes());
+        };
+
+        let mut primary = primary.to_vec();
+        primary.push((
+            EXIF_IFD_POINTER,
+            4,
+            1,
+            (exif_offset as u32).to_le_bytes().to_vec(),
+        ));
+        write_directory(&primary);
+        write_directory(exif);
+
+        let mut result = b"Exif\0\0II".to_vec();
+        result.extend(42u16.to_le_bytes());
+        result.extend((primary_offset as u32).to_le_bytes());
+        result.extend(directories);
+        result.extend(data);
+        result
+    }
+
+    fn ascii(text: &str) -> (u32, Vec<u8>) {
+        let mut bytes = text.as_bytes().to_vec();
+        bytes.push(0);
+        (bytes.len() as u32, bytes)
+    }
+
+    fn rational(numerator: u32, denominator: u32) -> Vec<u8> {
+        let mut bytes = numerator.to_le_bytes().to_vec();
+        bytes.extend(denominator.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_parse_exif() {
+        let (make_len, make) = ascii("Canon");
+        let (model_len, model) = ascii("Canon EOS R5");
+        let (taken_len, taken) = ascii("2024:05:01 12:30:00");
+        let data = build_exif(
+            &[
+                (0x010F, 2, make_len, make),
+                (0x0110, 2, model_len, model),
+                (0x0112, 3, 1, 6u16.to_le_bytes().to_vec()),
+            ],
+            &[
+                (0x9003, 2, taken_len, taken),
+                (0x829A, 5, 1, rational(1, 250)),
+                (0x829D, 5, 1, rational(28, 10)),
+                (0x8827, 3, 1, 400u16.to_le_bytes().to_vec()),
+            ],
+        );
+
+        let fields = parse_exif(&data)
+            .into_iter()
+            .map(|field| (field.label, field.value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            [
+                ("Camera", "Canon EOS R5".to_string()),
+                ("Taken", "2024:05:01 12:30:00".to_string()),
+                ("Exposure", "1/250 s".to_string()),
+                ("Aperture", "f/2.8".to_string()),
+                ("ISO", "400".to_string()),
+                ("Orientation", "Rotated 90° clockwise".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_exif() {
+        assert_eq!(parse_exif(b""), Vec::new());
+        assert_eq!(parse_exif(b"Exif\0\0XX\x2a\0"), Vec::new());
+        // A directory offset pointing past the end of the data.
+        assert_eq!(parse_exif(b"II\x2a\0\xff\0\0\0"), Vec::new());
+    }
+}
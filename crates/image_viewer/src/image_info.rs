@@ -36,7 +36,7 @@ impl ImageInfo {
     }
 }
 
-fn format_file_size(size: u64, image_unit_type: ImageFileSizeUnit) -> String {
+pub(crate) fn format_file_size(size: u64, image_unit_type: ImageFileSizeUnit) -> String {
     match image_unit_type {
         ImageFileSizeUnit::Binary => {
             if size < 1024 {
@@ -59,6 +59,20 @@ fn format_file_size(size: u64, image_unit_type: ImageFileSizeUnit) -> String {
     }
 }
 
+pub(crate) fn format_name(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Png => "PNG",
+        ImageFormat::Jpeg => "JPEG",
+        ImageFormat::Gif => "GIF",
+        ImageFormat::WebP => "WebP",
+        ImageFormat::Tiff => "TIFF",
+        ImageFormat::Bmp => "BMP",
+        ImageFormat::Ico => "ICO",
+        ImageFormat::Avif => "Avif",
+        _ => "Unknown",
+    }
+}
+
 impl Render for ImageInfo {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let settings = ImageViewerSettings::get_global(cx);
@@ -79,20 +93,7 @@ impl Render for ImageInfo {
             ));
         }
 
-        components.push(
-            match metadata.format {
-                ImageFormat::Png => "PNG",
-                ImageFormat::Jpeg => "JPEG",
-                ImageFormat::Gif => "GIF",
-                ImageFormat::WebP => "WebP",
-                ImageFormat::Tiff => "TIFF",
-                ImageFormat::Bmp => "BMP",
-                ImageFormat::Ico => "ICO",
-                ImageFormat::Avif => "Avif",
-                _ => "Unknown",
-            }
-            .to_string(),
-        );
+        components.push(format_name(metadata.format).to_string());
 
         div().child(
             Button::new("image-metadata", components.join(" • ")).label_size(LabelSize::Small),
@@ -121,4 +122,8 @@ impl StatusItemView for ImageInfo {
         }
         cx.notify();
     }
+
+    fn segment_name(&self, _: &App) -> Option<SharedString> {
+        Some("image_info".into())
+    }
 }
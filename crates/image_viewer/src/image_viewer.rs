@@ -1,34 +1,103 @@
+mod exif;
 mod image_info;
 mod image_viewer_settings;
 
-use std::path::PathBuf;
+use std::{io::Cursor, path::PathBuf, pin::Pin, sync::Arc};
 
 use anyhow::Context as _;
 use editor::{items::entry_git_aware_label_color, EditorSettings};
 use file_icons::FileIcons;
+use fs::Fs as _;
 use gpui::{
-    canvas, div, fill, img, opaque_grey, point, size, AnyElement, App, Bounds, Context, Entity,
-    EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement, ObjectFit,
-    ParentElement, Render, Styled, Task, WeakEntity, Window,
+    actions, canvas, div, fill, hash, img, opaque_grey, point, rgba, size, AnyElement, App, Bounds,
+    Context, CursorStyle, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement,
+    IntoElement, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, ObjectFit,
+    ParentElement, Point, Render, ScrollWheelEvent, Size, Styled, Task, WeakEntity, Window,
 };
+use image::{DynamicImage, GenericImageView, ImageDecoder, ImageFormat, ImageReader};
 use persistence::IMAGE_VIEWER;
 use project::{image_store::ImageItemEvent, ImageItem, Project, ProjectPath};
 use settings::Settings;
 use theme::Theme;
-use ui::prelude::*;
+use ui::{prelude::*, ContextMenu, PopoverMenu, Tooltip};
 use util::paths::PathExt;
 use workspace::{
     item::{BreadcrumbText, Item, ProjectItem, SerializableItem, TabContentParams},
+    notifications::DetachAndPromptErr,
     ItemId, ItemSettings, Pane, ToolbarItemLocation, Workspace, WorkspaceId,
 };
 
+use crate::exif::{parse_exif, ExifField};
 pub use crate::image_info::*;
 pub use crate::image_viewer_settings::*;
 
+actions!(
+    image_viewer,
+    [
+        ZoomIn,
+        ZoomOut,
+        ZoomToFit,
+        ActualSize,
+        RotateClockwise,
+        RotateCounterclockwise,
+        ToggleCrop,
+        ToggleMetadata,
+        RevertEdits,
+        ExportAsPng,
+        ExportAsWebp
+    ]
+);
+
+const ZOOM_LEVELS: [f32; 14] = [
+    0.1, 0.25, 0.5, 0.75, 1., 1.5, 2., 3., 4., 6., 8., 12., 16., 32.,
+];
+const MIN_ZOOM: f32 = 0.01;
+const MAX_ZOOM: f32 = 64.;
+
 pub struct ImageView {
     image_item: Entity<ImageItem>,
     project: Entity<Project>,
     focus_handle: FocusHandle,
+    /// The decoded image, including the edits made in this view.
+    pixels: Option<Arc<DynamicImage>>,
+    /// The encoded `pixels`, shown in place of the image item's image once it has been edited.
+    edited_image: Option<Arc<gpui::Image>>,
+    exif: Vec<ExifField>,
+    /// The scale at which the image is shown, or `None` to fit it into the view.
+    zoom: Option<f32>,
+    /// The offset of the image from the center of the view.
+    pan: Point<Pixels>,
+    /// The bounds of the view in the window, as of the last frame.
+    viewport: Bounds<Pixels>,
+    pan_start: Option<Point<Pixels>>,
+    hovered_pixel: Option<Point<u32>>,
+    crop: Option<CropSelection>,
+    show_metadata: bool,
+    _decode_task: Task<()>,
+    edit_task: Option<Task<()>>,
+}
+
+/// The area being selected for cropping, in image pixels.
+#[derive(Default)]
+struct CropSelection {
+    start: Option<Point<u32>>,
+    end: Option<Point<u32>>,
+    is_selecting: bool,
+}
+
+impl CropSelection {
+    /// Returns the selected area, including the pixels at both corners.
+    fn bounds(&self) -> Option<Bounds<u32>> {
+        let (start, end) = (self.start?, self.end?);
+        let origin = point(start.x.min(end.x), start.y.min(end.y));
+        Some(Bounds::new(
+            origin,
+            size(
+                start.x.max(end.x) - origin.x + 1,
+                start.y.max(end.y) - origin.y + 1,
+            ),
+        ))
+    }
 }
 
 impl ImageView {
@@ -38,11 +107,25 @@ impl ImageView {
         cx: &mut Context<Self>,
     ) -> Self {
         cx.subscribe(&image_item, Self::on_image_event).detach();
-        Self {
+        let mut this = Self {
             image_item,
             project,
             focus_handle: cx.focus_handle(),
-        }
+            pixels: None,
+            edited_image: None,
+            exif: Vec::new(),
+            zoom: None,
+            pan: Point::default(),
+            viewport: Bounds::default(),
+            pan_start: None,
+            hovered_pixel: None,
+            crop: None,
+            show_metadata: false,
+            _decode_task: Task::ready(()),
+            edit_task: None,
+        };
+        this.decode_image(cx);
+        this
     }
 
     fn on_image_event(
@@ -52,15 +135,413 @@ impl ImageView {
         cx: &mut Context<Self>,
     ) {
         match event {
-            ImageItemEvent::MetadataUpdated
-            | ImageItemEvent::FileHandleChanged
-            | ImageItemEvent::Reloaded => {
+            ImageItemEvent::MetadataUpdated | ImageItemEvent::FileHandleChanged => {
+                cx.emit(ImageViewEvent::TitleChanged);
+                cx.notify();
+            }
+            ImageItemEvent::Reloaded => {
+                // Edits are discarded when the file changes on disk, e.g. after exporting over it.
+                self.decode_image(cx);
                 cx.emit(ImageViewEvent::TitleChanged);
                 cx.notify();
             }
             ImageItemEvent::ReloadNeeded => {}
         }
     }
+
+    /// Decodes the image item's image for inspecting and editing its pixels.
+    fn decode_image(&mut self, cx: &mut Context<Self>) {
+        let image = self.image_item.read(cx).image.clone();
+        self.edited_image = None;
+        self.crop = None;
+        self._decode_task = cx.spawn(async move |this, cx| {
+            let decoded = cx
+                .background_spawn(async move {
+                    let mut decoder = ImageReader::new(Cursor::new(image.bytes()))
+                        .with_guessed_format()?
+                        .into_decoder()?;
+                    let exif = decoder
+                        .exif_metadata()
+                        .ok()
+                        .flatten()
+                        .map(|exif| parse_exif(&exif))
+                        .unwrap_or_default();
+                    anyhow::Ok((DynamicImage::from_decoder(decoder)?, exif))
+                })
+                .await;
+
+            this.update(cx, |this, cx| {
+                match decoded {
+                    Ok((pixels, exif)) => {
+                        this.pixels = Some(Arc::new(pixels));
+                        this.exif = exif;
+                    }
+                    Err(error) => {
+                        log::error!("failed to decode image: {error:#}");
+                        this.pixels = None;
+                        this.exif = Vec::new();
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn image_size(&self, cx: &App) -> Option<Size<f32>> {
+        if let Some(pixels) = self.pixels.as_ref() {
+            return Some(size(pixels.width() as f32, pixels.height() as f32));
+        }
+        let metadata = self.image_item.read(cx).image_metadata?;
+        Some(size(metadata.width as f32, metadata.height as f32))
+    }
+
+    /// The scale at which the image is shown, which only shrinks images when fitting them.
+    fn scale(&self, image_size: Size<f32>) -> f32 {
+        self.zoom.unwrap_or_else(|| {
+            let viewport = self.viewport.size;
+            (viewport.width.0 / image_size.width)
+                .min(viewport.height.0 / image_size.height)
+                .min(1.)
+        })
+    }
+
+    /// The bounds of the image, relative to the view.
+    fn image_bounds(&self, image_size: Size<f32>, scale: f32) -> Bounds<Pixels> {
+        let image_size = size(px(image_size.width * scale), px(image_size.height * scale));
+        let centered = point(
+            (self.viewport.size.width - image_size.width) * 0.5,
+            (self.viewport.size.height - image_size.height) * 0.5,
+        );
+        Bounds::new(centered + self.pan, image_size)
+    }
+
+    /// Returns the pixel of the image under the given window position, clamping positions
+    /// outside of the image to its edges if `clamp` is set.
+    fn pixel_at(&self, position: Point<Pixels>, clamp: bool, cx: &App) -> Option<Point<u32>> {
+        let image_size = self.image_size(cx)?;
+        let scale = self.scale(image_size);
+        let bounds = self.image_bounds(image_size, scale);
+        let position = position - self.viewport.origin - bounds.origin;
+        let x = (position.x.0 / scale).floor();
+        let y = (position.y.0 / scale).floor();
+
+        if clamp {
+            Some(point(
+                x.clamp(0., image_size.width - 1.) as u32,
+                y.clamp(0., image_size.height - 1.) as u32,
+            ))
+        } else {
+            (x >= 0. && y >= 0. && x < image_size.width && y < image_size.height)
+                .then(|| point(x as u32, y as u32))
+        }
+    }
+
+    /// Zooms the image, keeping the point under `anchor` in place, or the center of the view
+    /// if there's no anchor.
+    fn set_zoom(&mut self, zoom: f32, anchor: Option<Point<Pixels>>, cx: &mut Context<Self>) {
+        let Some(image_size) = self.image_size(cx) else {
+            return;
+        };
+        let old_scale = self.scale(image_size);
+        let old_bounds = self.image_bounds(image_size, old_scale);
+        let anchor = anchor
+            .map(|anchor| anchor - self.viewport.origin)
+            .unwrap_or_else(|| {
+                point(
+                    self.viewport.size.width * 0.5,
+                    self.viewport.size.height * 0.5,
+                )
+            });
+
+        let new_scale = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+        let image_point = (anchor - old_bounds.origin).map(|coordinate| coordinate.0 / old_scale);
+        let new_origin = anchor - image_point.map(|coordinate| px(coordinate * new_scale));
+        let centered_origin = self.image_bounds(image_size, new_scale).origin - self.pan;
+        self.zoom = Some(new_scale);
+        self.pan = new_origin - centered_origin;
+        cx.notify();
+    }
+
+    fn zoom_in(&mut self, _: &ZoomIn, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(image_size) = self.image_size(cx) else {
+            return;
+        };
+        let scale = self.scale(image_size);
+        let zoom = ZOOM_LEVELS
+            .into_iter()
+            .find(|level| *level > scale * 1.01)
+            .unwrap_or(MAX_ZOOM);
+        self.set_zoom(zoom, None, cx);
+    }
+
+    fn zoom_out(&mut self, _: &ZoomOut, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(image_size) = self.image_size(cx) else {
+            return;
+        };
+        let scale = self.scale(image_size);
+        let zoom = ZOOM_LEVELS
+            .into_iter()
+            .rev()
+            .find(|level| *level < scale * 0.99)
+            .unwrap_or(MIN_ZOOM);
+        self.set_zoom(zoom, None, cx);
+    }
+
+    fn zoom_to_fit(&mut self, _: &ZoomToFit, _: &mut Window, cx: &mut Context<Self>) {
+        self.zoom = None;
+        self.pan = Point::default();
+        cx.notify();
+    }
+
+    fn actual_size(&mut self, _: &ActualSize, _: &mut Window, cx: &mut Context<Self>) {
+        self.zoom = Some(1.);
+        self.pan = Point::default();
+        cx.notify();
+    }
+
+    fn handle_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let delta = event.delta.pixel_delta(window.line_height());
+        if event.modifiers.secondary() {
+            let Some(image_size) = self.image_size(cx) else {
+                return;
+            };
+            let zoom = self.scale(image_size) * 1.005_f32.powf(delta.y.0);
+            self.set_zoom(zoom, Some(event.position), cx);
+        } else {
+            self.pan = self.pan + delta;
+            cx.notify();
+        }
+        cx.stop_propagation();
+    }
+
+    fn handle_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.focus_handle);
+        if self.crop.is_some() {
+            let pixel = self.pixel_at(event.position, true, cx);
+            if let Some(crop) = self.crop.as_mut() {
+                crop.start = pixel;
+                crop.end = pixel;
+                crop.is_selecting = true;
+            }
+        } else {
+            self.pan_start = Some(event.position - self.pan);
+        }
+        cx.notify();
+    }
+
+    fn handle_mouse_move(
+        &mut self,
+        event: &MouseMoveEvent,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let is_dragging = event.pressed_button == Some(MouseButton::Left);
+        self.hovered_pixel = self.pixel_at(event.position, false, cx);
+
+        if let Some(pan_start) = self.pan_start {
+            if is_dragging {
+                self.pan = event.position - pan_start;
+            } else {
+                self.pan_start = None;
+            }
+        }
+        if self.crop.as_ref().is_some_and(|crop| crop.is_selecting) {
+            let pixel = self.pixel_at(event.position, true, cx);
+            if let Some(crop) = self.crop.as_mut() {
+                crop.end = pixel;
+                crop.is_selecting = is_dragging;
+            }
+        }
+        cx.notify();
+    }
+
+    fn handle_mouse_up(&mut self, _: &MouseUpEvent, _: &mut Window, cx: &mut Context<Self>) {
+        self.pan_start = None;
+        if let Some(crop) = self.crop.as_mut() {
+            crop.is_selecting = false;
+        }
+        cx.notify();
+    }
+
+    /// Replaces the pixels of the image with the result of `edit`, leaving the file untouched
+    /// until the image is exported.
+    fn edit(
+        &mut self,
+        edit: impl FnOnce(&DynamicImage) -> DynamicImage + Send + 'static,
+        cx: &mut Context<Self>,
+    ) {
+        // Edits are made one at a time so that each builds on the result of the previous one.
+        if self.edit_task.is_some() {
+            return;
+        }
+        let Some(pixels) = self.pixels.clone() else {
+            return;
+        };
+
+        self.edit_task = Some(cx.spawn(async move |this, cx| {
+            let edited = cx
+                .background_spawn(async move {
+                    let edited = edit(&pixels);
+                    let bytes = encode_image(&edited, ImageFormat::Png)?;
+                    anyhow::Ok((edited, bytes))
+                })
+                .await;
+
+            this.update(cx, |this, cx| {
+                this.edit_task = None;
+                match edited {
+                    Ok((pixels, bytes)) => {
+                        this.pixels = Some(Arc::new(pixels));
+                        this.edited_image = Some(Arc::new(gpui::Image {
+                            id: hash(&bytes),
+                            format: gpui::ImageFormat::Png,
+                            bytes,
+                        }));
+                        this.zoom = None;
+                        this.pan = Point::default();
+                    }
+                    Err(error) => log::error!("failed to edit image: {error:#}"),
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn rotate_clockwise(&mut self, _: &RotateClockwise, _: &mut Window, cx: &mut Context<Self>) {
+        self.crop = None;
+        self.edit(|image| image.rotate90(), cx);
+    }
+
+    fn rotate_counterclockwise(
+        &mut self,
+        _: &RotateCounterclockwise,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.crop = None;
+        self.edit(|image| image.rotate270(), cx);
+    }
+
+    fn toggle_crop(&mut self, _: &ToggleCrop, _: &mut Window, cx: &mut Context<Self>) {
+        self.crop = match self.crop {
+            Some(_) => None,
+            None => Some(CropSelection::default()),
+        };
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(bounds) = self.crop.as_ref().and_then(CropSelection::bounds) else {
+            return;
+        };
+        self.crop = None;
+        self.edit(
+            move |image| {
+                image.crop_imm(
+                    bounds.origin.x,
+                    bounds.origin.y,
+                    bounds.size.width,
+                    bounds.size.height,
+                )
+            },
+            cx,
+        );
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        if self.crop.take().is_some() {
+            cx.notify();
+        } else {
+            cx.propagate();
+        }
+    }
+
+    fn toggle_metadata(&mut self, _: &ToggleMetadata, _: &mut Window, cx: &mut Context<Self>) {
+        self.show_metadata = !self.show_metadata;
+        cx.notify();
+    }
+
+    fn revert_edits(&mut self, _: &RevertEdits, _: &mut Window, cx: &mut Context<Self>) {
+        if self.edited_image.is_some() {
+            self.zoom = None;
+            self.pan = Point::default();
+            self.decode_image(cx);
+            cx.notify();
+        }
+    }
+
+    fn export_as_png(&mut self, _: &ExportAsPng, window: &mut Window, cx: &mut Context<Self>) {
+        self.export(ImageFormat::Png, window, cx);
+    }
+
+    fn export_as_webp(&mut self, _: &ExportAsWebp, window: &mut Window, cx: &mut Context<Self>) {
+        self.export(ImageFormat::WebP, window, cx);
+    }
+
+    /// Saves the image, including its edits, to a file chosen by the user.
+    fn export(&mut self, format: ImageFormat, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(pixels) = self.pixels.clone() else {
+            return;
+        };
+        let directory = self
+            .image_item
+            .read(cx)
+            .file
+            .as_local()
+            .and_then(|file| Some(file.abs_path(cx).parent()?.to_path_buf()))
+            .unwrap_or_else(|| PathBuf::from(""));
+        let fs = self.project.read(cx).fs().clone();
+        let path = cx.prompt_for_new_path(&directory);
+
+        cx.spawn_in(window, async move |_, cx| {
+            let Some(mut path) = path.await?? else {
+                return anyhow::Ok(());
+            };
+            if path.extension().is_none() {
+                path.set_extension(format.extensions_str()[0]);
+            }
+            let bytes = cx
+                .background_spawn(async move { encode_image(&pixels, format) })
+                .await?;
+            fs.create_file_with(&path, Pin::new(&mut futures::io::Cursor::new(bytes)))
+                .await
+                .with_context(|| format!("writing {path:?}"))
+        })
+        .detach_and_prompt_err("Failed to export image", window, cx, |_, _, _| None);
+    }
+}
+
+fn encode_image(image: &DynamicImage, format: ImageFormat) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    if format == ImageFormat::WebP {
+        // The WebP encoder only supports 8-bit images.
+        DynamicImage::ImageRgba8(image.to_rgba8())
+            .write_to(&mut Cursor::new(&mut bytes), format)?;
+    } else {
+        image.write_to(&mut Cursor::new(&mut bytes), format)?;
+    }
+    Ok(bytes)
+}
+
+fn format_color(color: image::Rgba<u8>) -> String {
+    let [red, green, blue, alpha] = color.0;
+    if alpha == u8::MAX {
+        format!("#{red:02X}{green:02X}{blue:02X}")
+    } else {
+        format!("#{red:02X}{green:02X}{blue:02X}{alpha:02X}")
+    }
 }
 
 pub enum ImageViewEvent {
@@ -171,11 +652,7 @@ impl Item for ImageView {
     where
         Self: Sized,
     {
-        Some(cx.new(|cx| Self {
-            image_item: self.image_item.clone(),
-            project: self.project.clone(),
-            focus_handle: cx.focus_handle(),
-        }))
+        Some(cx.new(|cx| Self::new(self.image_item.clone(), self.project.clone(), cx)))
     }
 }
 
@@ -280,9 +757,246 @@ impl Focusable for ImageView {
     }
 }
 
+impl ImageView {
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        let button = move |id: &'static str,
+                           icon: IconName,
+                           tooltip: &'static str,
+                           action: Box<dyn gpui::Action>| {
+            let focus_handle = focus_handle.clone();
+            let tooltip_action = action.boxed_clone();
+            IconButton::new(id, icon)
+                .icon_size(IconSize::Small)
+                .tooltip(move |window, cx| {
+                    Tooltip::for_action_in(tooltip, &*tooltip_action, &focus_handle, window, cx)
+                })
+                .on_click(move |_, window, cx| window.dispatch_action(action.boxed_clone(), cx))
+        };
+
+        let zoom = self
+            .image_size(cx)
+            .map(|image_size| format!("{:.0}%", self.scale(image_size) * 100.))
+            .unwrap_or_default();
+        let is_editable = self.pixels.is_some() && self.edit_task.is_none();
+        let separator = || div().h_4().w_px().bg(cx.theme().colors().border_variant);
+
+        h_flex()
+            .absolute()
+            .top_2()
+            .right_2()
+            .p_1()
+            .gap_1()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().elevated_surface_background)
+            .shadow_md()
+            .child(button(
+                "zoom-out",
+                IconName::Dash,
+                "Zoom Out",
+                Box::new(ZoomOut),
+            ))
+            .child(
+                Button::new("actual-size", zoom)
+                    .label_size(LabelSize::Small)
+                    .tooltip(Tooltip::for_action_title_in(
+                        "Actual Size",
+                        &ActualSize,
+                        &self.focus_handle,
+                    ))
+                    .on_click(|_, window, cx| window.dispatch_action(Box::new(ActualSize), cx)),
+            )
+            .child(button(
+                "zoom-in",
+                IconName::Plus,
+                "Zoom In",
+                Box::new(ZoomIn),
+            ))
+            .child(button(
+                "zoom-to-fit",
+                IconName::Maximize,
+                "Zoom to Fit",
+                Box::new(ZoomToFit),
+            ))
+            .child(separator())
+            .child(
+                button(
+                    "rotate-counterclockwise",
+                    IconName::RotateCcw,
+                    "Rotate Counterclockwise",
+                    Box::new(RotateCounterclockwise),
+                )
+                .disabled(!is_editable),
+            )
+            .child(
+                button(
+                    "rotate-clockwise",
+                    IconName::RotateCw,
+                    "Rotate Clockwise",
+                    Box::new(RotateClockwise),
+                )
+                .disabled(!is_editable),
+            )
+            .child(
+                button("crop", IconName::Snip, "Crop", Box::new(ToggleCrop))
+                    .toggle_state(self.crop.is_some())
+                    .disabled(!is_editable),
+            )
+            .when(self.edited_image.is_some(), |this| {
+                this.child(button(
+                    "revert-edits",
+                    IconName::Undo,
+                    "Revert Edits",
+                    Box::new(RevertEdits),
+                ))
+            })
+            .child(separator())
+            .child(
+                button(
+                    "metadata",
+                    IconName::Info,
+                    "Show Metadata",
+                    Box::new(ToggleMetadata),
+                )
+                .toggle_state(self.show_metadata),
+            )
+            .child(
+                PopoverMenu::new("export-menu")
+                    .trigger(
+                        IconButton::new("export", IconName::Download)
+                            .icon_size(IconSize::Small)
+                            .disabled(self.pixels.is_none())
+                            .tooltip(Tooltip::text("Export")),
+                    )
+                    .anchor(gpui::Corner::TopRight)
+                    .menu({
+                        let focus_handle = self.focus_handle.clone();
+                        move |window, cx| {
+                            Some(ContextMenu::build(window, cx, |menu, _, _| {
+                                menu.context(focus_handle.clone())
+                                    .action("Export as PNG", Box::new(ExportAsPng))
+                                    .action("Export as WebP", Box::new(ExportAsWebp))
+                            }))
+                        }
+                    }),
+            )
+    }
+
+    fn render_metadata(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let settings = ImageViewerSettings::get_global(cx);
+        let metadata = self.image_item.read(cx).image_metadata;
+
+        let mut fields = Vec::new();
+        if let Some(pixels) = self.pixels.as_ref() {
+            fields.push((
+                "Dimensions",
+                format!("{} × {}", pixels.width(), pixels.height()),
+            ));
+        }
+        if let Some(metadata) = metadata {
+            fields.push(("Format", format_name(metadata.format).to_string()));
+            fields.push((
+                "File Size",
+                format_file_size(metadata.file_size, settings.unit),
+            ));
+            if let Some(colors) = metadata.colors {
+                fields.push((
+                    "Color",
+                    format!(
+                        "{} channels, {} bits per channel",
+                        colors.channels, colors.bits_per_channel
+                    ),
+                ));
+            }
+        }
+        fields.extend(
+            self.exif
+                .iter()
+                .map(|field| (field.label, field.value.clone())),
+        );
+
+        v_flex()
+            .absolute()
+            .top_12()
+            .right_2()
+            .w_64()
+            .p_2()
+            .gap_1()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().elevated_surface_background)
+            .shadow_md()
+            .children(fields.into_iter().map(|(label, value)| {
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .justify_between()
+                    .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+                    .child(Label::new(value).size(LabelSize::Small).single_line())
+            }))
+    }
+
+    /// Renders the zoom level and the pixel under the mouse, or how to crop while cropping.
+    fn render_status(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let content = if let Some(crop) = self.crop.as_ref() {
+            let text = match crop.bounds() {
+                Some(bounds) => format!(
+                    "{} × {} at {}, {}. Press Enter to crop.",
+                    bounds.size.width, bounds.size.height, bounds.origin.x, bounds.origin.y
+                ),
+                None => "Drag to select the area to keep.".to_string(),
+            };
+            Label::new(text).size(LabelSize::Small).into_any_element()
+        } else {
+            let pixel = self.hovered_pixel?;
+            let color = self
+                .pixels
+                .as_ref()
+                .filter(|pixels| pixels.in_bounds(pixel.x, pixel.y))
+                .map(|pixels| pixels.get_pixel(pixel.x, pixel.y));
+            h_flex()
+                .gap_2()
+                .child(Label::new(format!("{}, {}", pixel.x, pixel.y)).size(LabelSize::Small))
+                .when_some(color, |this, color| {
+                    let [red, green, blue, alpha] = color.0;
+                    this.child(
+                        div()
+                            .size_3()
+                            .rounded_sm()
+                            .border_1()
+                            .border_color(cx.theme().colors().border)
+                            .bg(rgba(u32::from_be_bytes([red, green, blue, alpha]))),
+                    )
+                    .child(Label::new(format_color(color)).size(LabelSize::Small))
+                })
+                .into_any_element()
+        };
+
+        Some(
+            div()
+                .absolute()
+                .bottom_2()
+                .left_2()
+                .px_2()
+                .py_1()
+                .rounded_md()
+                .border_1()
+                .border_color(cx.theme().colors().border)
+                .bg(cx.theme().colors().elevated_surface_background)
+                .child(content),
+        )
+    }
+}
+
 impl Render for ImageView {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let image = self.image_item.read(cx).image.clone();
+        let image = self
+            .edited_image
+            .clone()
+            .unwrap_or_else(|| self.image_item.read(cx).image.clone());
         let checkered_background = |bounds: Bounds<Pixels>,
                                     _,
                                     window: &mut Window,
@@ -321,34 +1035,132 @@ impl Render for ImageView {
             }
         };
 
-        let checkered_background = canvas(|_, _, _| (), checkered_background)
-            .border_2()
-            .border_color(cx.theme().styles.colors.border)
-            .size_full()
-            .absolute()
-            .top_0()
-            .left_0();
+        let view = cx.entity().downgrade();
+        let checkered_background = canvas(
+            move |bounds, _, cx| {
+                // The viewport is needed to map mouse positions to pixels of the image.
+                view.update(cx, |this, cx| {
+                    if this.viewport != bounds {
+                        this.viewport = bounds;
+                        cx.notify();
+                    }
+                })
+                .ok();
+            },
+            checkered_background,
+        )
+        .border_2()
+        .border_color(cx.theme().styles.colors.border)
+        .size_full()
+        .absolute()
+        .top_0()
+        .left_0();
+
+        let image_size = self
+            .image_size(cx)
+            .filter(|_| self.viewport.size.width > px(0.));
+        let image = match image_size {
+            Some(image_size) => {
+                let scale = self.scale(image_size);
+                let bounds = self.image_bounds(image_size, scale);
+                let crop_bounds = self
+                    .crop
+                    .as_ref()
+                    .and_then(CropSelection::bounds)
+                    .map(|crop| {
+                        Bounds::new(
+                            bounds.origin
+                                + point(px(crop.origin.x as f32), px(crop.origin.y as f32)) * scale,
+                            size(px(crop.size.width as f32), px(crop.size.height as f32)) * scale,
+                        )
+                    });
 
-        div()
-            .track_focus(&self.focus_handle(cx))
-            .size_full()
-            .child(checkered_background)
-            .child(
                 div()
-                    .flex()
-                    .justify_center()
-                    .items_center()
-                    .w_full()
-                    // TODO: In browser based Tailwind & Flex this would be h-screen and we'd use w-full
-                    .h_full()
+                    .size_full()
+                    .relative()
+                    .overflow_hidden()
                     .child(
                         img(image)
-                            .object_fit(ObjectFit::ScaleDown)
-                            .max_w_full()
-                            .max_h_full()
+                            .absolute()
+                            .left(bounds.origin.x)
+                            .top(bounds.origin.y)
+                            .w(bounds.size.width)
+                            .h(bounds.size.height)
+                            .object_fit(ObjectFit::Fill)
                             .id("img"),
-                    ),
+                    )
+                    .when_some(crop_bounds, |this, crop_bounds| {
+                        this.child(
+                            div()
+                                .absolute()
+                                .left(crop_bounds.origin.x)
+                                .top(crop_bounds.origin.y)
+                                .w(crop_bounds.size.width)
+                                .h(crop_bounds.size.height)
+                                .border_1()
+                                .border_color(cx.theme().colors().text_accent)
+                                .bg(cx.theme().colors().text_accent.opacity(0.15)),
+                        )
+                    })
+            }
+            None => div()
+                .flex()
+                .justify_center()
+                .items_center()
+                .w_full()
+                // TODO: In browser based Tailwind & Flex this would be h-screen and we'd use w-full
+                .h_full()
+                .child(
+                    img(image)
+                        .object_fit(ObjectFit::ScaleDown)
+                        .max_w_full()
+                        .max_h_full()
+                        .id("img"),
+                ),
+        };
+
+        div()
+            .id("image-viewer")
+            .key_context("ImageViewer")
+            .track_focus(&self.focus_handle(cx))
+            .on_action(cx.listener(Self::zoom_in))
+            .on_action(cx.listener(Self::zoom_out))
+            .on_action(cx.listener(Self::zoom_to_fit))
+            .on_action(cx.listener(Self::actual_size))
+            .on_action(cx.listener(Self::rotate_clockwise))
+            .on_action(cx.listener(Self::rotate_counterclockwise))
+            .on_action(cx.listener(Self::toggle_crop))
+            .on_action(cx.listener(Self::toggle_metadata))
+            .on_action(cx.listener(Self::revert_edits))
+            .on_action(cx.listener(Self::export_as_png))
+            .on_action(cx.listener(Self::export_as_webp))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_scroll_wheel(cx.listener(Self::handle_scroll_wheel))
+            .on_mouse_down(MouseButton::Left, cx.listener(Self::handle_mouse_down))
+            .on_mouse_move(cx.listener(Self::handle_mouse_move))
+            .on_mouse_up(MouseButton::Left, cx.listener(Self::handle_mouse_up))
+            .on_hover(cx.listener(|this, hovered, _, cx| {
+                if !hovered && this.hovered_pixel.take().is_some() {
+                    cx.notify();
+                }
+            }))
+            .map(
+                |this| match (self.crop.is_some(), self.pan_start.is_some()) {
+                    (true, _) => this.cursor(CursorStyle::Crosshair),
+                    (false, true) => this.cursor(CursorStyle::ClosedHand),
+                    (false, false) => this.cursor(CursorStyle::OpenHand),
+                },
             )
+            .size_full()
+            .relative()
+            .child(checkered_background)
+            .child(image)
+            .child(self.render_toolbar(cx))
+            .when(self.show_metadata, |this| {
+                this.child(self.render_metadata(cx))
+            })
+            .children(self.render_status(cx))
     }
 }
 
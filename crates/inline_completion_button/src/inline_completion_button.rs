@@ -10,8 +10,8 @@ use feature_flags::{FeatureFlagAppExt, PredictEditsRateCompletionsFeatureFlag};
 use fs::Fs;
 use gpui::{
     actions, div, pulsating_between, Action, Animation, AnimationExt, App, AsyncWindowContext,
-    Corner, Entity, FocusHandle, Focusable, IntoElement, ParentElement, Render, Subscription,
-    WeakEntity,
+    Corner, Entity, FocusHandle, Focusable, IntoElement, ParentElement, Render, SharedString,
+    Subscription, WeakEntity,
 };
 use indoc::indoc;
 use language::{
@@ -229,6 +229,53 @@ impl Render for InlineCompletionButton {
                 );
             }
 
+            EditPredictionProvider::OpenAiCompatible => {
+                let is_configured = all_language_settings
+                    .edit_predictions
+                    .open_ai_compatible
+                    .api_url
+                    .is_some();
+                let enabled = is_configured && self.editor_enabled.unwrap_or(true);
+                let icon_color = if enabled {
+                    Color::Default
+                } else {
+                    Color::Muted
+                };
+                let tooltip_meta = if !is_configured {
+                    "No API URL Configured"
+                } else if !enabled {
+                    "Disabled For This File"
+                } else {
+                    "OpenAI-Compatible"
+                };
+
+                let this = cx.entity().clone();
+                div().child(
+                    PopoverMenu::new("open-ai-compatible")
+                        .menu(move |window, cx| {
+                            Some(this.update(cx, |this, cx| {
+                                this.build_open_ai_compatible_context_menu(window, cx)
+                            }))
+                        })
+                        .anchor(Corner::BottomRight)
+                        .trigger_with_tooltip(
+                            IconButton::new("open-ai-compatible-icon", IconName::AiOpenAi)
+                                .shape(IconButtonShape::Square)
+                                .icon_color(icon_color),
+                            move |window, cx| {
+                                Tooltip::with_meta(
+                                    "Edit Prediction",
+                                    Some(&ToggleMenu),
+                                    tooltip_meta,
+                                    window,
+                                    cx,
+                                )
+                            },
+                        )
+                        .with_handle(self.popover_menu_handle.clone()),
+                )
+            }
+
             EditPredictionProvider::Zed => {
                 let enabled = self.editor_enabled.unwrap_or(true);
 
@@ -618,6 +665,27 @@ impl InlineCompletionButton {
             );
         }
 
+        menu = menu.separator().header("Provider");
+        let current_provider = settings.edit_predictions.provider;
+        for (provider, label) in [
+            (EditPredictionProvider::Zed, "Zed"),
+            (EditPredictionProvider::Copilot, "GitHub Copilot"),
+            (EditPredictionProvider::Supermaven, "Supermaven"),
+            (
+                EditPredictionProvider::OpenAiCompatible,
+                "OpenAI-Compatible",
+            ),
+        ] {
+            let fs = fs.clone();
+            menu = menu.toggleable_entry(
+                label,
+                current_provider == provider,
+                IconPosition::Start,
+                None,
+                move |_, cx| set_completion_provider(fs.clone(), cx, provider),
+            );
+        }
+
         if let Some(editor_focus_handle) = self.editor_focus_handle.clone() {
             menu = menu
                 .separator()
@@ -668,6 +736,16 @@ impl InlineCompletionButton {
         })
     }
 
+    fn build_open_ai_compatible_context_menu(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<ContextMenu> {
+        ContextMenu::build(window, cx, |menu, window, cx| {
+            self.build_language_settings_menu(menu, window, cx)
+        })
+    }
+
     fn build_zeta_context_menu(
         &self,
         window: &mut Window,
@@ -731,6 +809,10 @@ impl StatusItemView for InlineCompletionButton {
         }
         cx.notify();
     }
+
+    fn segment_name(&self, _: &App) -> Option<SharedString> {
+        Some("edit_prediction".into())
+    }
 }
 
 impl SupermavenButtonStatus {
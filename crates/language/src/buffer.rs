@@ -5,8 +5,10 @@ pub use crate::{
 };
 use crate::{
     diagnostic_set::{DiagnosticEntry, DiagnosticGroup},
+    indentation::BufferIndentation,
     language_settings::{language_settings, LanguageSettings},
     outline::OutlineItem,
+    parse_queue::ParseQueue,
     syntax_map::{
         SyntaxLayer, SyntaxMap, SyntaxMapCapture, SyntaxMapCaptures, SyntaxMapMatch,
         SyntaxMapMatches, SyntaxSnapshot, ToTreeSitterPoint,
@@ -50,7 +52,10 @@ use std::{
     ops::{Deref, DerefMut, Range},
     path::{Path, PathBuf},
     rc, str,
-    sync::{Arc, LazyLock},
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc, LazyLock,
+    },
     time::{Duration, Instant},
     vec,
 };
@@ -77,6 +82,13 @@ pub use lsp::DiagnosticSeverity;
 /// a diff against the contents of its file.
 pub static BUFFER_DIFF_TASK: LazyLock<TaskLabel> = LazyLock::new(TaskLabel::new);
 
+/// Buffers at least this many bytes long are first parsed in the background without blocking
+/// the main thread, one after another, and have their visible ranges highlighted ahead of the
+/// rest of the buffer.
+pub const LARGE_BUFFER_PARSE_THRESHOLD: usize = 1024 * 1024;
+
+static LARGE_BUFFER_PARSES: ParseQueue = ParseQueue::new();
+
 /// Indicate whether a [`Buffer`] has permissions to edit.
 #[derive(PartialEq, Clone, Copy, Debug)]
 pub enum Capability {
@@ -101,6 +113,15 @@ pub struct Buffer {
     /// The version vector when this buffer was last loaded from
     /// or saved to disk.
     saved_version: clock::Global,
+    /// The encoding of the file's contents on disk, which they are converted from when loaded
+    /// and to when saved.
+    encoding: Encoding,
+    /// Whether the file's contents start with a byte order mark, which is kept when saving.
+    has_bom: bool,
+    /// Whether the line ending or byte order mark was changed since the buffer was last saved
+    /// or reloaded.
+    has_unsaved_format_change: bool,
+    indentation: BufferIndentation,
     preview_version: clock::Global,
     transaction_depth: usize,
     was_dirty_before_starting_transaction: Option<bool>,
@@ -111,6 +132,10 @@ pub struct Buffer {
     sync_parse_timeout: Duration,
     syntax_map: Mutex<SyntaxMap>,
     reparse: Option<Task<()>>,
+    /// The range of a large buffer that's being parsed ahead of the rest of it.
+    partial_parse: Option<(Range<usize>, Task<()>)>,
+    /// Whether a view has shown the buffer, so that it's parsed before hidden large buffers.
+    was_shown: Arc<AtomicBool>,
     parse_status: (watch::Sender<ParseStatus>, watch::Receiver<ParseStatus>),
     non_text_state_update_count: usize,
     diagnostics: SmallVec<[(LanguageServerId, DiagnosticSet); 2]>,
@@ -145,10 +170,14 @@ struct BufferBranchState {
 pub struct BufferSnapshot {
     pub text: text::BufferSnapshot,
     pub(crate) syntax: SyntaxSnapshot,
+    /// A parse of the visible range of a large buffer, used for highlighting until the whole
+    /// buffer has been parsed.
+    partial_syntax: Option<SyntaxSnapshot>,
     file: Option<Arc<dyn File>>,
     diagnostics: SmallVec<[(LanguageServerId, DiagnosticSet); 2]>,
     remote_selections: TreeMap<ReplicaId, SelectionSet>,
     language: Option<Arc<Language>>,
+    indentation: BufferIndentation,
     non_text_state_update_count: usize,
 }
 
@@ -936,6 +965,169 @@ impl Buffer {
         self
     }
 
+    /// Assign the encoding that the buffer's file was loaded with, returning the buffer.
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Returns the [`Encoding`] of the buffer's file on disk.
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Assign the encoding that the buffer is saved with.
+    pub fn set_encoding(&mut self, encoding: Encoding, cx: &mut Context<Self>) {
+        self.encoding = encoding;
+        cx.notify();
+    }
+
+    /// Assign whether the buffer's file starts with a byte order mark, returning the buffer.
+    pub fn with_byte_order_mark(mut self, has_bom: bool) -> Self {
+        self.has_bom = has_bom;
+        self
+    }
+
+    /// Assign the line ending that the buffer is saved with, returning the buffer.
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.text.set_line_ending(line_ending);
+        self
+    }
+
+    /// Whether the buffer is saved with a byte order mark.
+    pub fn has_bom(&self) -> bool {
+        self.has_bom
+    }
+
+    /// Assign whether the buffer is saved with a byte order mark. This makes the buffer dirty
+    /// until it's saved.
+    pub fn set_has_bom(&mut self, has_bom: bool, cx: &mut Context<Self>) {
+        if self.has_bom != has_bom {
+            self.has_bom = has_bom;
+            self.did_change_format(cx);
+        }
+    }
+
+    /// Assign the line ending that the buffer is saved with. This makes the buffer dirty until
+    /// it's saved.
+    pub fn set_line_ending(&mut self, line_ending: LineEnding, cx: &mut Context<Self>) {
+        if self.line_ending() != line_ending {
+            self.text.set_line_ending(line_ending);
+            self.did_change_format(cx);
+        }
+    }
+
+    /// Assign the indentation that was detected from the buffer's contents, returning the buffer.
+    pub fn with_detected_indentation(mut self, indent: Option<IndentSize>) -> Self {
+        self.indentation.detected = indent;
+        self
+    }
+
+    /// The indentation that the buffer uses in place of the one in its settings, if any.
+    pub fn indentation(&self) -> BufferIndentation {
+        self.indentation
+    }
+
+    /// Assign the indentation that the buffer uses regardless of its settings, or `None` to
+    /// go back to the detected one or the settings.
+    pub fn set_indentation_override(&mut self, indent: Option<IndentSize>, cx: &mut Context<Self>) {
+        if self.indentation.overridden != indent {
+            self.indentation.overridden = indent;
+            self.non_text_state_update_count += 1;
+            cx.notify();
+        }
+    }
+
+    /// Returns the settings for the buffer's language, with its indentation applied.
+    pub fn settings<'a>(&'a self, cx: &'a App) -> Cow<'a, LanguageSettings> {
+        self.indentation.apply(language_settings(
+            self.language().map(|language| language.name()),
+            self.file.as_ref(),
+            cx,
+        ))
+    }
+
+    /// Returns the settings for the language at the given location, with the buffer's
+    /// indentation applied.
+    pub fn settings_at<'a, D: ToOffset>(
+        &'a self,
+        position: D,
+        cx: &'a App,
+    ) -> Cow<'a, LanguageSettings> {
+        self.indentation.apply(language_settings(
+            self.language_at(position).map(|language| language.name()),
+            self.file.as_ref(),
+            cx,
+        ))
+    }
+
+    /// Rewrites the indentation of every line with the given kind of whitespace, keeping its
+    /// width, and indents with that kind from then on. Lines that start inside a string are
+    /// left alone, since their leading whitespace is part of the string.
+    pub fn convert_indentation(&mut self, kind: IndentKind, cx: &mut Context<Self>) {
+        let tab_size = self.settings(cx).tab_size.get();
+        let snapshot = self.snapshot();
+        let mut edits = Vec::new();
+        for row in 0..=snapshot.max_point().row {
+            let indent = snapshot.indent_size_for_line(row);
+            if indent.len == 0 {
+                continue;
+            }
+            let is_in_string = snapshot
+                .language_scope_at(Point::new(row, 0))
+                .is_some_and(|scope| scope.override_name() == Some("string"));
+            if is_in_string {
+                continue;
+            }
+
+            let mut indent_len = 0;
+            let mut columns = 0;
+            for c in snapshot.chars_at(Point::new(row, 0)) {
+                match c {
+                    ' ' => columns += 1,
+                    '\t' => columns += tab_size - columns % tab_size,
+                    _ => break,
+                }
+                indent_len += 1;
+            }
+            let new_indent = match kind {
+                IndentKind::Space => " ".repeat(columns as usize),
+                IndentKind::Tab => {
+                    let mut new_indent = "\t".repeat((columns / tab_size) as usize);
+                    new_indent.push_str(&" ".repeat((columns % tab_size) as usize));
+                    new_indent
+                }
+            };
+            let old_range = Point::new(row, 0)..Point::new(row, indent_len);
+            if snapshot
+                .text_for_range(old_range.clone())
+                .collect::<String>()
+                != new_indent
+            {
+                edits.push((old_range, new_indent));
+            }
+        }
+
+        self.edit(edits, None, cx);
+        self.set_indentation_override(
+            Some(match kind {
+                IndentKind::Space => IndentSize::spaces(tab_size),
+                IndentKind::Tab => IndentSize::tab(),
+            }),
+            cx,
+        );
+    }
+
+    fn did_change_format(&mut self, cx: &mut Context<Self>) {
+        let was_dirty = self.is_dirty();
+        self.has_unsaved_format_change = true;
+        self.non_text_state_update_count += 1;
+        if was_dirty != self.is_dirty() {
+            cx.emit(BufferEvent::DirtyChanged);
+        }
+        cx.notify();
+    }
+
     /// Returns the [`Capability`] of this buffer.
     pub fn capability(&self) -> Capability {
         self.capability
@@ -954,6 +1146,10 @@ impl Buffer {
         Self {
             saved_mtime,
             saved_version: buffer.version(),
+            encoding: Encoding::default(),
+            has_bom: false,
+            has_unsaved_format_change: false,
+            indentation: Default::default(),
             preview_version: buffer.version(),
             reload_task: None,
             transaction_depth: 0,
@@ -965,6 +1161,8 @@ impl Buffer {
             capability,
             syntax_map,
             reparse: None,
+            partial_parse: None,
+            was_shown: Default::default(),
             non_text_state_update_count: 0,
             sync_parse_timeout: Duration::from_millis(1),
             parse_status: async_watch::channel(ParseStatus::Idle),
@@ -1005,10 +1203,12 @@ impl Buffer {
             BufferSnapshot {
                 text,
                 syntax,
+                partial_syntax: None,
                 file: None,
                 diagnostics: Default::default(),
                 remote_selections: Default::default(),
                 language,
+                indentation: Default::default(),
                 non_text_state_update_count: 0,
             }
         }
@@ -1023,10 +1223,12 @@ impl Buffer {
         BufferSnapshot {
             text,
             syntax,
+            partial_syntax: None,
             file: None,
             diagnostics: Default::default(),
             remote_selections: Default::default(),
             language: None,
+            indentation: Default::default(),
             non_text_state_update_count: 0,
         }
     }
@@ -1051,10 +1253,12 @@ impl Buffer {
         BufferSnapshot {
             text,
             syntax,
+            partial_syntax: None,
             file: None,
             diagnostics: Default::default(),
             remote_selections: Default::default(),
             language,
+            indentation: Default::default(),
             non_text_state_update_count: 0,
         }
     }
@@ -1066,14 +1270,17 @@ impl Buffer {
         let mut syntax_map = self.syntax_map.lock();
         syntax_map.interpolate(&text);
         let syntax = syntax_map.snapshot();
+        let partial_syntax = syntax_map.partial_snapshot();
 
         BufferSnapshot {
             text,
             syntax,
+            partial_syntax,
             file: self.file.clone(),
             remote_selections: self.remote_selections.clone(),
             diagnostics: self.diagnostics.clone(),
             language: self.language.clone(),
+            indentation: self.indentation,
             non_text_state_update_count: self.non_text_state_update_count,
         }
     }
@@ -1139,11 +1346,16 @@ impl Buffer {
     /// given `ranges` to its base buffer.
     ///
     /// If `ranges` is empty, then all changes will be applied. This buffer must
-    /// be a branch buffer to call this method.
-    pub fn merge_into_base(&mut self, ranges: Vec<Range<usize>>, cx: &mut Context<Self>) {
+    /// be a branch buffer to call this method. Returns the transaction in the
+    /// base buffer that applied the changes, if there were any to apply.
+    pub fn merge_into_base(
+        &mut self,
+        ranges: Vec<Range<usize>>,
+        cx: &mut Context<Self>,
+    ) -> Option<TransactionId> {
         let Some(base_buffer) = self.base_buffer() else {
             debug_panic!("not a branch buffer");
-            return;
+            return None;
         };
 
         let mut ranges = if ranges.is_empty() {
@@ -1176,9 +1388,12 @@ impl Buffer {
             }
         }
 
-        let operation = base_buffer.update(cx, |base_buffer, cx| {
+        let (operation, transaction_id) = base_buffer.update(cx, |base_buffer, cx| {
             // cx.emit(BufferEvent::DiffBaseChanged);
-            base_buffer.edit(edits, None, cx)
+            let operation = base_buffer.edit(edits, None, cx);
+            let transaction_id =
+                operation.and_then(|_| Some(base_buffer.peek_undo_stack()?.transaction_id()));
+            (operation, transaction_id)
         });
 
         if let Some(operation) = operation {
@@ -1189,6 +1404,7 @@ impl Buffer {
                 merged_operations.push(operation);
             }
         }
+        transaction_id
     }
 
     fn on_base_buffer_event(
@@ -1253,6 +1469,7 @@ impl Buffer {
     pub fn set_language(&mut self, language: Option<Arc<Language>>, cx: &mut Context<Self>) {
         self.non_text_state_update_count += 1;
         self.syntax_map.lock().clear(&self.text);
+        self.partial_parse = None;
         self.language = language;
         self.was_changed();
         self.reparse(cx);
@@ -1287,6 +1504,7 @@ impl Buffer {
         self.saved_version = version;
         self.has_unsaved_edits
             .set((self.saved_version().clone(), false));
+        self.has_unsaved_format_change = false;
         self.has_conflict = false;
         self.saved_mtime = mtime;
         self.was_changed();
@@ -1302,22 +1520,40 @@ impl Buffer {
 
     /// Reloads the contents of the buffer from disk.
     pub fn reload(&mut self, cx: &Context<Self>) -> oneshot::Receiver<Option<Transaction>> {
+        self.reload_with_encoding(self.encoding, cx)
+    }
+
+    /// Reloads the contents of the buffer from disk, decoding them with the given encoding.
+    /// The buffer keeps its current encoding if the contents aren't valid in the given one.
+    pub fn reload_with_encoding(
+        &mut self,
+        encoding: Encoding,
+        cx: &Context<Self>,
+    ) -> oneshot::Receiver<Option<Transaction>> {
         let (tx, rx) = futures::channel::oneshot::channel();
         let prev_version = self.text.version();
         self.reload_task = Some(cx.spawn(async move |this, cx| {
-            let Some((new_mtime, new_text)) = this.update(cx, |this, cx| {
+            let Some((new_mtime, new_bytes)) = this.update(cx, |this, cx| {
                 let file = this.file.as_ref()?.as_local()?;
-                Some((file.disk_state().mtime(), file.load(cx)))
+                Some((file.disk_state().mtime(), file.load_bytes(cx)))
             })?
             else {
                 return Ok(());
             };
 
-            let new_text = new_text.await?;
+            let new_bytes = new_bytes.await?;
+            let (new_text, has_bom) = cx
+                .background_spawn(async move {
+                    let text = encoding.decode(&new_bytes)?;
+                    anyhow::Ok((text, encoding.has_bom(&new_bytes)))
+                })
+                .await?;
             let diff = this
                 .update(cx, |this, cx| this.diff(new_text.clone(), cx))?
                 .await;
             this.update(cx, |this, cx| {
+                this.encoding = encoding;
+                this.has_bom = has_bom;
                 if this.version() == diff.base_version {
                     this.finalize_last_transaction();
                     this.apply_diff(diff, true, cx);
@@ -1354,6 +1590,7 @@ impl Buffer {
         self.saved_version = version;
         self.has_unsaved_edits
             .set((self.saved_version.clone(), false));
+        self.has_unsaved_format_change = false;
         self.text.set_line_ending(line_ending);
         self.saved_mtime = mtime;
         cx.emit(BufferEvent::Reloaded);
@@ -1461,6 +1698,10 @@ impl Buffer {
     /// initiate an additional reparse recursively. To avoid concurrent parses
     /// for the same buffer, we only initiate a new parse if we are not already
     /// parsing in the background.
+    ///
+    /// Large buffers that haven't been parsed yet, see [`LARGE_BUFFER_PARSE_THRESHOLD`],
+    /// don't block the main thread at all, and take turns with other large buffers,
+    /// parsing the ones shown in a view first.
     pub fn reparse(&mut self, cx: &mut Context<Self>) {
         if self.reparse.is_some() {
             return;
@@ -1480,20 +1721,31 @@ impl Buffer {
         let mut syntax_snapshot = syntax_map.snapshot();
         drop(syntax_map);
 
+        let is_large_initial_parse =
+            syntax_snapshot.is_empty() && text.len() >= LARGE_BUFFER_PARSE_THRESHOLD;
         let parse_task = cx.background_spawn({
             let language = language.clone();
             let language_registry = language_registry.clone();
+            let was_shown = self.was_shown.clone();
             async move {
+                let _permit = if is_large_initial_parse {
+                    Some(LARGE_BUFFER_PARSES.acquire(was_shown).await)
+                } else {
+                    None
+                };
                 syntax_snapshot.reparse(&text, language_registry, language);
                 syntax_snapshot
             }
         });
 
         self.parse_status.0.send(ParseStatus::Parsing).unwrap();
-        match cx
-            .background_executor()
-            .block_with_timeout(self.sync_parse_timeout, parse_task)
-        {
+        let parse_result = if is_large_initial_parse {
+            Err(parse_task)
+        } else {
+            cx.background_executor()
+                .block_with_timeout(self.sync_parse_timeout, parse_task)
+        };
+        match parse_result {
             Ok(new_syntax_snapshot) => {
                 self.did_finish_parsing(new_syntax_snapshot, cx);
                 self.reparse = None;
@@ -1530,6 +1782,7 @@ impl Buffer {
         self.was_changed();
         self.non_text_state_update_count += 1;
         self.syntax_map.lock().did_parse(syntax_snapshot);
+        self.partial_parse = None;
         self.request_autoindent(cx);
         self.parse_status.0.send(ParseStatus::Idle).unwrap();
         cx.emit(BufferEvent::Reparsed);
@@ -1540,6 +1793,54 @@ impl Buffer {
         self.parse_status.1.clone()
     }
 
+    /// Called by views showing the given range of the buffer. Large buffers that
+    /// haven't been parsed yet parse the range, and some of the text around it,
+    /// ahead of the rest of the buffer, so that it's highlighted sooner.
+    pub fn parse_visible_range(&mut self, range: Range<usize>, cx: &mut Context<Self>) {
+        self.was_shown.store(true, atomic::Ordering::Release);
+        let Some(language) = self.language.clone() else {
+            return;
+        };
+        if self.len() < LARGE_BUFFER_PARSE_THRESHOLD || !self.syntax_map.lock().is_empty() {
+            return;
+        }
+        if let Some((parsed_range, _)) = &self.partial_parse {
+            if parsed_range.start <= range.start && parsed_range.end >= range.end {
+                return;
+            }
+        }
+
+        // Parse as much text again on either side, so that small scrolls don't need another parse.
+        let text = self.text_snapshot();
+        let margin = range.len();
+        let start_row = text.offset_to_point(range.start.saturating_sub(margin)).row;
+        let end_row = text
+            .offset_to_point((range.end + margin).min(text.len()))
+            .row;
+        let start = text.point_to_offset(Point::new(start_row, 0));
+        let end = text.point_to_offset(Point::new(end_row, text.line_len(end_row)));
+        let language_registry = self.syntax_map.lock().language_registry();
+        let parse_task =
+            cx.background_spawn({
+                let text = text.clone();
+                async move {
+                    SyntaxSnapshot::parse_range(&text, language_registry, language, start..end)
+                }
+            });
+        let task = cx.spawn(async move |this, cx| {
+            let partial_snapshot = parse_task.await;
+            this.update(cx, |this, cx| {
+                if this.syntax_map.lock().did_parse_range(partial_snapshot) {
+                    this.was_changed();
+                    this.non_text_state_update_count += 1;
+                    cx.notify();
+                }
+            })
+            .ok();
+        });
+        self.partial_parse = Some((start..end, task));
+    }
+
     /// Assign to the buffer a set of diagnostics created by a given language server.
     pub fn update_diagnostics(
         &mut self,
@@ -1957,7 +2258,7 @@ impl Buffer {
             Some(DiskState::New) | Some(DiskState::Deleted) => {
                 !self.is_empty() && self.has_unsaved_edits()
             }
-            _ => self.has_unsaved_edits(),
+            _ => self.has_unsaved_edits() || self.has_unsaved_format_change,
         }
     }
 
@@ -2794,11 +3095,7 @@ impl BufferSnapshot {
     /// Returns [`IndentSize`] for a given position that respects user settings
     /// and language preferences.
     pub fn language_indent_size_at<T: ToOffset>(&self, position: T, cx: &App) -> IndentSize {
-        let settings = language_settings(
-            self.language_at(position).map(|l| l.name()),
-            self.file(),
-            cx,
-        );
+        let settings = self.settings_at(position, cx);
         if settings.hard_tabs {
             IndentSize::tab()
         } else {
@@ -3062,7 +3359,8 @@ impl BufferSnapshot {
     }
 
     fn get_highlights(&self, range: Range<usize>) -> (SyntaxMapCaptures, Vec<HighlightMap>) {
-        let captures = self.syntax.captures(range, &self.text, |grammar| {
+        let syntax = self.partial_syntax.as_ref().unwrap_or(&self.syntax);
+        let captures = syntax.captures(range, &self.text, |grammar| {
             grammar.highlights_query.as_ref()
         });
         let highlight_maps = captures
@@ -3170,17 +3468,27 @@ impl BufferSnapshot {
             .or(self.language.as_ref())
     }
 
-    /// Returns the settings for the language at the given location.
+    /// Returns the settings for the language at the given location, with the buffer's
+    /// indentation applied.
     pub fn settings_at<'a, D: ToOffset>(
         &'a self,
         position: D,
         cx: &'a App,
     ) -> Cow<'a, LanguageSettings> {
-        language_settings(
+        self.indentation.apply(language_settings(
             self.language_at(position).map(|l| l.name()),
             self.file.as_ref(),
             cx,
-        )
+        ))
+    }
+
+    /// Returns the settings for the main language, with the buffer's indentation applied.
+    pub fn settings<'a>(&'a self, cx: &'a App) -> Cow<'a, LanguageSettings> {
+        self.indentation.apply(language_settings(
+            self.language().map(|l| l.name()),
+            self.file.as_ref(),
+            cx,
+        ))
     }
 
     pub fn char_classifier_at<T: ToOffset>(&self, point: T) -> CharClassifier {
@@ -4263,10 +4571,12 @@ impl Clone for BufferSnapshot {
         Self {
             text: self.text.clone(),
             syntax: self.syntax.clone(),
+            partial_syntax: self.partial_syntax.clone(),
             file: self.file.clone(),
             remote_selections: self.remote_selections.clone(),
             diagnostics: self.diagnostics.clone(),
             language: self.language.clone(),
+            indentation: self.indentation,
             non_text_state_update_count: self.non_text_state_update_count,
         }
     }
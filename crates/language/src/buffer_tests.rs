@@ -251,6 +251,76 @@ async fn test_language_for_file_with_custom_file_types(cx: &mut TestAppContext)
     assert_eq!(language.name(), "Dockerfile".into());
 }
 
+#[gpui::test]
+async fn test_language_for_file_with_shebang_and_modeline(cx: &mut TestAppContext) {
+    cx.update(|cx| {
+        init_settings(cx, |settings| {
+            settings
+                .file_types
+                .insert("Shell Script".into(), vec!["scripts/*".into()]);
+        })
+    });
+
+    let languages = Arc::new(LanguageRegistry::test(cx.executor()));
+    for (name, path_suffixes) in [
+        ("Python", vec!["py"]),
+        ("Shell Script", vec!["sh"]),
+        ("JavaScript", vec!["js"]),
+        ("C", vec!["c", "h"]),
+        ("C++", vec!["cpp", "h"]),
+    ] {
+        languages.register_test_language(LanguageConfig {
+            name: name.into(),
+            matcher: LanguageMatcher {
+                path_suffixes: path_suffixes.into_iter().map(Into::into).collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    }
+
+    let language_name = |path: &str, content: &str| {
+        cx.read(|cx| languages.language_for_file(&file(path), Some(&content.into()), cx))
+            .map(|language| language.name())
+    };
+
+    // A shebang or modeline decides the language of files without an extension.
+    assert_eq!(
+        language_name("the/script", "#!/usr/bin/env python3\nprint()\n"),
+        Some("Python".into())
+    );
+    assert_eq!(
+        language_name("the/script", "echo hi\n# vim: set ft=sh:\n"),
+        Some("Shell Script".into())
+    );
+    assert_eq!(
+        language_name("the/script", "#!/bin/sh\n# -*- mode: python -*-\n"),
+        Some("Python".into())
+    );
+    assert_eq!(language_name("the/script", "echo hi\n"), None);
+
+    // They decide between the languages that an extension is ambiguous between, but don't
+    // override an extension that's unambiguous.
+    assert_eq!(
+        language_name("src/lib.h", "// -*- mode: c++ -*-\n"),
+        Some("C++".into())
+    );
+    assert_eq!(
+        language_name("src/lib.h", "/* vim: set ft=c: */\n"),
+        Some("C".into())
+    );
+    assert_eq!(
+        language_name("src/main.js", "#!/usr/bin/env python3\n"),
+        Some("JavaScript".into())
+    );
+
+    // Associations in the settings can match paths within the worktree, and take precedence.
+    assert_eq!(
+        language_name("scripts/deploy", "#!/usr/bin/env python3\n"),
+        Some("Shell Script".into())
+    );
+}
+
 fn file(path: &str) -> Arc<dyn File> {
     Arc::new(TestFile {
         path: Path::new(path).into(),
@@ -11,10 +11,11 @@ pub struct HighlightId(pub u32);
 const DEFAULT_SYNTAX_HIGHLIGHT_ID: HighlightId = HighlightId(u32::MAX);
 
 impl HighlightMap {
-    pub(crate) fn new(capture_names: &[&str], theme: &SyntaxTheme) -> Self {
+    pub(crate) fn new(capture_names: &[&str], theme: &SyntaxTheme, language_name: &str) -> Self {
         // For each capture name in the highlight query, find the longest
         // key in the theme's syntax styles that matches all of the
-        // dot-separated components of the capture name.
+        // dot-separated components of the capture name. Keys that are
+        // specific to this language take precedence over the others.
         HighlightMap(
             capture_names
                 .iter()
@@ -24,6 +25,13 @@ impl HighlightMap {
                         .iter()
                         .enumerate()
                         .filter_map(|(i, (key, _))| {
+                            let (key, is_language_specific) = match key.split_once('@') {
+                                Some((key, key_language)) if key_language == language_name => {
+                                    (key, true)
+                                }
+                                Some(_) => return None,
+                                None => (key.as_str(), false),
+                            };
                             let mut len = 0;
                             let capture_parts = capture_name.split('.');
                             for key_part in key.split('.') {
@@ -33,9 +41,9 @@ impl HighlightMap {
                                     return None;
                                 }
                             }
-                            Some((i, len))
+                            Some((i, (is_language_specific, len)))
                         })
-                        .max_by_key(|(_, len)| *len)
+                        .max_by_key(|(_, precedence)| *precedence)
                         .map_or(DEFAULT_SYNTAX_HIGHLIGHT_ID, |(i, _)| HighlightId(i as u32))
                 })
                 .collect(),
@@ -103,9 +111,37 @@ mod tests {
             "variable.builtin.self",
         ];
 
-        let map = HighlightMap::new(capture_names, &theme);
+        let map = HighlightMap::new(capture_names, &theme, "Rust");
         assert_eq!(map.get(0).name(&theme), Some("function"));
         assert_eq!(map.get(1).name(&theme), Some("function.async"));
         assert_eq!(map.get(2).name(&theme), Some("variable.builtin"));
     }
+
+    #[test]
+    fn test_highlight_map_with_language_specific_styles() {
+        let theme = SyntaxTheme {
+            highlights: [
+                ("comment", rgba(0x100000ff)),
+                ("comment.doc", rgba(0x200000ff)),
+                ("string", rgba(0x300000ff)),
+                ("comment@Rust", rgba(0x400000ff)),
+                ("string@YAML", rgba(0x500000ff)),
+            ]
+            .iter()
+            .map(|(name, color)| (name.to_string(), (*color).into()))
+            .collect(),
+        };
+
+        let capture_names = &["comment", "comment.doc", "string"];
+
+        let map = HighlightMap::new(capture_names, &theme, "Rust");
+        assert_eq!(map.get(0).name(&theme), Some("comment@Rust"));
+        assert_eq!(map.get(1).name(&theme), Some("comment@Rust"));
+        assert_eq!(map.get(2).name(&theme), Some("string"));
+
+        let map = HighlightMap::new(capture_names, &theme, "YAML");
+        assert_eq!(map.get(0).name(&theme), Some("comment"));
+        assert_eq!(map.get(1).name(&theme), Some("comment.doc"));
+        assert_eq!(map.get(2).name(&theme), Some("string@YAML"));
+    }
 }
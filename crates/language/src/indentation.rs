@@ -0,0 +1,129 @@
+use std::{borrow::Cow, cmp::Reverse, num::NonZeroU32, ops::RangeInclusive};
+
+use collections::HashMap;
+
+use crate::{language_settings::LanguageSettings, IndentKind, IndentSize};
+
+/// The number of lines at the start of a file that indentation is detected from.
+const MAX_DETECTION_LINES: usize = 10_000;
+
+/// The widths that space indentation is detected with. Differences of a single space are more
+/// often alignment than indentation.
+const DETECTED_SPACE_WIDTHS: RangeInclusive<u32> = 2..=8;
+
+/// The indentation of a buffer, when it differs from its language's settings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BufferIndentation {
+    /// The indentation detected from the buffer's contents when it was opened, which is used
+    /// if the `detect_indentation` setting is enabled.
+    pub detected: Option<IndentSize>,
+    /// The indentation that was picked for the buffer by the user, which takes precedence over
+    /// the detected one and the settings.
+    pub overridden: Option<IndentSize>,
+}
+
+impl BufferIndentation {
+    /// Returns the indentation that the buffer uses in place of the one in the given settings.
+    pub fn effective(&self, settings: &LanguageSettings) -> Option<IndentSize> {
+        self.overridden
+            .or(self.detected.filter(|_| settings.detect_indentation))
+    }
+
+    /// Replaces the `hard_tabs` and `tab_size` settings with the buffer's indentation.
+    pub fn apply<'a>(&self, mut settings: Cow<'a, LanguageSettings>) -> Cow<'a, LanguageSettings> {
+        if let Some(indent) = self.effective(&settings) {
+            let hard_tabs = indent.kind == IndentKind::Tab;
+            let tab_size = match indent.kind {
+                IndentKind::Tab => settings.tab_size,
+                IndentKind::Space => NonZeroU32::new(indent.len).unwrap_or(settings.tab_size),
+            };
+            if settings.hard_tabs != hard_tabs || settings.tab_size != tab_size {
+                let settings = settings.to_mut();
+                settings.hard_tabs = hard_tabs;
+                settings.tab_size = tab_size;
+            }
+        }
+        settings
+    }
+}
+
+/// Detects how the given text is indented, from the lines at its start. Returns `None` if it
+/// isn't indented, or isn't consistently enough to tell.
+pub fn detect_indentation(text: &str) -> Option<IndentSize> {
+    let mut tab_lines = 0;
+    let mut space_lines = 0;
+    let mut space_width_counts = HashMap::<u32, usize>::default();
+    let mut previous_space_indent = None;
+
+    for line in text.lines().take(MAX_DETECTION_LINES) {
+        let content = line.trim_start_matches([' ', '\t']);
+        // Continuation lines of block comments are offset by a space to align their asterisks.
+        if content.is_empty() || content.starts_with('*') {
+            continue;
+        }
+
+        if line.starts_with('\t') {
+            tab_lines += 1;
+            previous_space_indent = None;
+            continue;
+        }
+
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        if line[indent..].starts_with('\t') {
+            // Mixed indentation doesn't tell much about either kind.
+            previous_space_indent = None;
+            continue;
+        }
+        let indent = indent as u32;
+        if indent > 0 {
+            space_lines += 1;
+        }
+        if let Some(previous_indent) = previous_space_indent {
+            let width = indent.abs_diff(previous_indent);
+            if DETECTED_SPACE_WIDTHS.contains(&width) {
+                *space_width_counts.entry(width).or_default() += 1;
+            }
+        }
+        previous_space_indent = Some(indent);
+    }
+
+    if tab_lines > space_lines {
+        return Some(IndentSize::tab());
+    }
+    space_width_counts
+        .into_iter()
+        .max_by_key(|&(width, count)| (count, Reverse(width)))
+        .map(|(width, _)| IndentSize::spaces(width))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_indentation() {
+        assert_eq!(
+            detect_indentation("fn a() {\n    if b {\n        c();\n    }\n}\n"),
+            Some(IndentSize::spaces(4))
+        );
+        assert_eq!(
+            detect_indentation("a:\n  b:\n    c: 1\n    d: 2\n  e: 3\nf: 4\n"),
+            Some(IndentSize::spaces(2))
+        );
+        assert_eq!(
+            detect_indentation("func a() {\n\tif b {\n\t\tc()\n\t}\n}\n"),
+            Some(IndentSize::tab())
+        );
+
+        // Block comments and blank lines don't affect the result.
+        assert_eq!(
+            detect_indentation(
+                "/**\n * Docs.\n */\nfunction a() {\n\n  return {\n    b: 1,\n  };\n}\n"
+            ),
+            Some(IndentSize::spaces(2))
+        );
+
+        assert_eq!(detect_indentation("a\nb\nc\n"), None);
+        assert_eq!(detect_indentation("a(b,\n c)\n"), None);
+    }
+}
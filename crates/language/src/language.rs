@@ -9,10 +9,13 @@
 mod buffer;
 mod diagnostic_set;
 mod highlight_map;
+mod indentation;
+mod language_hints;
 mod language_registry;
 pub mod language_settings;
 mod manifest;
 mod outline;
+mod parse_queue;
 pub mod proto;
 mod syntax_map;
 mod task_context;
@@ -74,6 +77,7 @@ use util::serde::default_true;
 pub use buffer::Operation;
 pub use buffer::*;
 pub use diagnostic_set::{DiagnosticEntry, DiagnosticGroup};
+pub use indentation::{detect_indentation, BufferIndentation};
 pub use language_registry::{
     AvailableLanguage, BinaryStatus, LanguageNotFound, LanguageQueries, LanguageRegistry,
     QUERY_FILENAME_PREFIXES,
@@ -81,7 +85,7 @@ pub use language_registry::{
 pub use lsp::{LanguageServerId, LanguageServerName};
 pub use outline::*;
 pub use syntax_map::{OwnedSyntaxLayer, SyntaxLayer, ToTreeSitterPoint, TreeSitterOptions};
-pub use text::{AnchorRangeExt, LineEnding};
+pub use text::{AnchorRangeExt, Encoding, LineEnding};
 pub use tree_sitter::{Node, Parser, Tree, TreeCursor};
 
 /// Initializes the `language` crate.
@@ -1677,8 +1681,11 @@ impl Language {
     pub fn set_theme(&self, theme: &SyntaxTheme) {
         if let Some(grammar) = self.grammar.as_ref() {
             if let Some(highlights_query) = &grammar.highlights_query {
-                *grammar.highlight_map.lock() =
-                    HighlightMap::new(highlights_query.capture_names(), theme);
+                *grammar.highlight_map.lock() = HighlightMap::new(
+                    highlights_query.capture_names(),
+                    theme,
+                    self.config.name.as_ref(),
+                );
             }
         }
     }
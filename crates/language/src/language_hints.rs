@@ -0,0 +1,231 @@
+//! The hints that files give about their language in their contents: shebang lines, such as
+//! `#!/usr/bin/env python3`, and Vim and Emacs modelines, such as `# vim: set ft=ruby:` and
+//! `-*- mode: python -*-`.
+
+use sum_tree::Bias;
+use text::{Point, Rope};
+
+use crate::LanguageMatcher;
+
+/// How many lines at the start and end of a file are searched for a Vim modeline, which is Vim's
+/// default `modelines` option.
+const VIM_MODELINE_LINES: usize = 5;
+
+/// How many bytes at the start and end of a file are searched for hints, so that huge lines don't
+/// need to be read.
+const MAX_SEARCHED_LEN: usize = 4096;
+
+/// Interpreters whose name isn't the name of their language, mapped to one of the language's
+/// path suffixes.
+const INTERPRETER_PATH_SUFFIXES: &[(&str, &str)] = &[
+    ("node", "js"),
+    ("nodejs", "js"),
+    ("deno", "ts"),
+    ("bun", "ts"),
+    ("ts-node", "ts"),
+    ("pwsh", "ps1"),
+];
+
+pub(crate) struct LanguageHints {
+    /// The start of the first line, which languages' first line patterns are matched against.
+    first_line: String,
+    /// The language named by a modeline, normalized with [`normalize_name`].
+    modeline_language: Option<String>,
+    /// The interpreter in the shebang line, without its version.
+    interpreter: Option<String>,
+}
+
+impl LanguageHints {
+    pub(crate) fn new(content: &Rope) -> Self {
+        let first_line_end = content.clip_point(Point::new(0, 256), Bias::Left);
+        let first_line_end = content.point_to_offset(first_line_end);
+        let first_line = content
+            .chunks_in_range(0..first_line_end)
+            .collect::<String>();
+
+        let head_end = content.clip_offset(content.len().min(MAX_SEARCHED_LEN), Bias::Left);
+        let head = content.chunks_in_range(0..head_end).collect::<String>();
+        let tail_start =
+            content.clip_offset(content.len().saturating_sub(MAX_SEARCHED_LEN), Bias::Right);
+        let tail = content
+            .chunks_in_range(tail_start.max(head_end)..content.len())
+            .collect::<String>();
+
+        // Emacs only reads the mode from the first line, or the second after a shebang.
+        let emacs_mode = head.lines().take(2).find_map(emacs_modeline_mode);
+        let vim_filetype = || {
+            let head_lines = head.lines().take(VIM_MODELINE_LINES);
+            let tail_lines = tail.lines().rev().take(VIM_MODELINE_LINES);
+            head_lines.chain(tail_lines).find_map(vim_modeline_filetype)
+        };
+        let modeline_language = emacs_mode.or_else(vim_filetype).map(normalize_name);
+
+        Self {
+            interpreter: shebang_interpreter(&first_line).map(ToOwned::to_owned),
+            first_line,
+            modeline_language,
+        }
+    }
+
+    /// Returns how strongly the hints suggest that the file is in the given language: 2 if a
+    /// modeline names it, 1 if the shebang or first line does, and 0 otherwise.
+    pub(crate) fn score(&self, language_name: &str, matcher: &LanguageMatcher) -> usize {
+        let is_language = |name: &str| {
+            normalize_name(language_name) == name
+                || matcher.path_suffixes.iter().any(|suffix| suffix == name)
+        };
+
+        if self
+            .modeline_language
+            .as_deref()
+            .is_some_and(|modeline_language| is_language(modeline_language))
+        {
+            return 2;
+        }
+
+        let interpreter_matches = self.interpreter.as_deref().is_some_and(|interpreter| {
+            is_language(interpreter)
+                || INTERPRETER_PATH_SUFFIXES
+                    .iter()
+                    .any(|(name, suffix)| *name == interpreter && is_language(suffix))
+        });
+        let first_line_matches = matcher
+            .first_line_pattern
+            .as_ref()
+            .is_some_and(|pattern| pattern.is_match(&self.first_line));
+        if interpreter_matches || first_line_matches {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// Normalizes a language name so that the names used by Vim and Emacs match Zed's, such as
+/// `shell-script` and "Shell Script".
+fn normalize_name(name: &str) -> String {
+    name.to_lowercase().replace(['-', '_'], " ")
+}
+
+/// Returns the interpreter that a shebang line runs, without its path or version, such as
+/// `python` for `#!/usr/bin/env python3`.
+fn shebang_interpreter(line: &str) -> Option<&str> {
+    let mut args = line.strip_prefix("#!")?.split_whitespace();
+    let mut program = args.next()?.rsplit('/').next()?;
+    if program == "env" {
+        // Skip env's options, like `-S`, and the variables that it sets.
+        program = args.find(|arg| !arg.starts_with('-') && !arg.contains('='))?;
+    }
+    let program = program.trim_end_matches(|char: char| char.is_ascii_digit() || char == '.');
+    (!program.is_empty()).then_some(program)
+}
+
+/// Returns the file type set by a Vim modeline, like `vim: set ft=python:` or `vi:ts=4:ft=ruby`.
+fn vim_modeline_filetype(line: &str) -> Option<&str> {
+    let options = ["vim:", "vi:", "ex:"].iter().find_map(|marker| {
+        let (before, after) = line.split_once(marker)?;
+        // The marker must start the line or follow whitespace, so that `xvi:` isn't a modeline.
+        (before.is_empty() || before.ends_with(char::is_whitespace)).then_some(after)
+    })?;
+    let options = options.trim_start();
+    let options = options
+        .strip_prefix("set ")
+        .or_else(|| options.strip_prefix("se "))
+        .unwrap_or(options);
+    options
+        .split(|char: char| char == ':' || char.is_whitespace())
+        .find_map(|option| {
+            let (name, value) = option.split_once('=')?;
+            matches!(name, "ft" | "filetype" | "syn" | "syntax")
+                .then_some(value)
+                .filter(|value| !value.is_empty())
+        })
+}
+
+/// Returns the mode set by an Emacs modeline, like `-*- mode: python -*-` or `-*- python -*-`.
+fn emacs_modeline_mode(line: &str) -> Option<&str> {
+    let (_, variables) = line.split_once("-*-")?;
+    let (variables, _) = variables.split_once("-*-")?;
+    let mode = if variables.contains(':') {
+        variables.split(';').find_map(|variable| {
+            let (name, value) = variable.split_once(':')?;
+            name.trim().eq_ignore_ascii_case("mode").then_some(value)
+        })?
+    } else {
+        variables
+    };
+    let mode = mode.trim();
+    (!mode.is_empty()).then_some(mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shebang_interpreter() {
+        assert_eq!(shebang_interpreter("#!/bin/bash"), Some("bash"));
+        assert_eq!(
+            shebang_interpreter("#!/usr/bin/env python3"),
+            Some("python")
+        );
+        assert_eq!(
+            shebang_interpreter("#! /usr/bin/python3.11 -u"),
+            Some("python")
+        );
+        assert_eq!(
+            shebang_interpreter("#!/usr/bin/env -S NODE_ENV=test node --inspect"),
+            Some("node")
+        );
+        assert_eq!(shebang_interpreter("#!/usr/bin/env"), None);
+        assert_eq!(shebang_interpreter("# not a shebang"), None);
+    }
+
+    #[test]
+    fn test_modelines() {
+        assert_eq!(vim_modeline_filetype("# vim: set ft=ruby :"), Some("ruby"));
+        assert_eq!(
+            vim_modeline_filetype("// vi:ts=4:sw=4:filetype=javascript"),
+            Some("javascript")
+        );
+        assert_eq!(vim_modeline_filetype("/* vim: syntax=c */"), Some("c"));
+        assert_eq!(vim_modeline_filetype("# vim: set ts=4:"), None);
+        assert_eq!(vim_modeline_filetype("let xvim: ft=ruby"), None);
+
+        assert_eq!(
+            emacs_modeline_mode("# -*- mode: python -*-"),
+            Some("python")
+        );
+        assert_eq!(
+            emacs_modeline_mode("# -*- coding: utf-8; Mode: shell-script -*-"),
+            Some("shell-script")
+        );
+        assert_eq!(emacs_modeline_mode(";; -*- c++ -*-"), Some("c++"));
+        assert_eq!(emacs_modeline_mode("# -*- coding: utf-8 -*-"), None);
+    }
+
+    #[test]
+    fn test_language_hints_score() {
+        let shell = LanguageMatcher {
+            path_suffixes: vec!["sh".into(), "bash".into()],
+            first_line_pattern: None,
+        };
+        let python = LanguageMatcher {
+            path_suffixes: vec!["py".into()],
+            first_line_pattern: None,
+        };
+
+        let hints = LanguageHints::new(&Rope::from("#!/usr/bin/env bash\necho hi\n"));
+        assert_eq!(hints.score("Shell Script", &shell), 1);
+        assert_eq!(hints.score("Python", &python), 0);
+
+        let hints = LanguageHints::new(&Rope::from(
+            "#!/bin/sh\nexec python3 \"$0\"\n\n# vim: set ft=python:\n",
+        ));
+        assert_eq!(hints.score("Shell Script", &shell), 1);
+        assert_eq!(hints.score("Python", &python), 2);
+
+        let hints = LanguageHints::new(&Rope::from("# -*- mode: shell-script -*-\n"));
+        assert_eq!(hints.score("Shell Script", &shell), 2);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    language_hints::LanguageHints,
     language_settings::{
         all_language_settings, AllLanguageSettingsContent, LanguageSettingsContent,
     },
@@ -27,8 +28,7 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
-use sum_tree::Bias;
-use text::{Point, Rope};
+use text::Rope;
 use theme::Theme;
 use unicase::UniCase;
 use util::{maybe, post_inc, ResultExt};
@@ -648,6 +648,7 @@ impl LanguageRegistry {
 
         self.language_for_file_internal(
             &file.full_path(cx),
+            Some(file.path()),
             content,
             Some(&user_file_types.file_types),
         )
@@ -657,7 +658,7 @@ impl LanguageRegistry {
         self: &Arc<Self>,
         path: &'a Path,
     ) -> impl Future<Output = Result<Arc<Language>>> + 'a {
-        let available_language = self.language_for_file_internal(path, None, None);
+        let available_language = self.language_for_file_internal(path, None, None, None);
 
         let this = self.clone();
         async move {
@@ -669,9 +670,14 @@ impl LanguageRegistry {
         }
     }
 
+    /// Finds the language of a file from its path and contents. Associations in the settings take
+    /// precedence, followed by the languages' path suffixes. Shebangs and modelines in the
+    /// contents only decide the language when no language's path suffixes match, or when several
+    /// do.
     fn language_for_file_internal(
         self: &Arc<Self>,
         path: &Path,
+        worktree_path: Option<&Path>,
         content: Option<&Rope>,
         user_file_types: Option<&HashMap<Arc<str>, GlobSet>>,
     ) -> Option<AvailableLanguage> {
@@ -680,8 +686,16 @@ impl LanguageRegistry {
         // and no other extension which is not the desired behavior here,
         // as we want `.zshrc` to result in extension being `Some("zshrc")`
         let extension = filename.and_then(|filename| filename.split('.').last());
-        let path_suffixes = [extension, filename, path.to_str()];
+        // Matching the path within the worktree allows projects to associate
+        // languages with files in their directories, such as `scripts/*`.
+        let path_suffixes = [
+            extension,
+            filename,
+            path.to_str(),
+            worktree_path.and_then(|path| path.to_str()),
+        ];
         let empty = GlobSet::empty();
+        let hints = content.map(LanguageHints::new);
 
         self.find_matching_language(move |language_name, config| {
             let path_matches_default_suffix = config
@@ -695,21 +709,15 @@ impl LanguageRegistry {
                 .iter()
                 .map(|suffix| suffix.unwrap_or(""))
                 .any(|suffix| custom_suffixes.is_match(suffix));
-            let content_matches = content.zip(config.first_line_pattern.as_ref()).map_or(
-                false,
-                |(content, pattern)| {
-                    let end = content.clip_point(Point::new(0, 256), Bias::Left);
-                    let end = content.point_to_offset(end);
-                    let text = content.chunks_in_range(0..end).collect::<String>();
-                    pattern.is_match(&text)
-                },
-            );
+            let hints_score = hints
+                .as_ref()
+                .map_or(0, |hints| hints.score(language_name.as_ref(), config));
             if path_matches_custom_suffix {
-                2
-            } else if path_matches_default_suffix || content_matches {
-                1
+                6
+            } else if path_matches_default_suffix {
+                3 + hints_score
             } else {
-                0
+                hints_score
             }
         })
     }
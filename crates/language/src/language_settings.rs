@@ -1,11 +1,13 @@
 //! Provides `language`-related settings.
 
-use crate::{File, Language, LanguageName, LanguageServerName};
+use crate::{File, Language, LanguageName, LanguageServerName, LineEnding};
 use anyhow::Result;
 use collections::{HashMap, HashSet};
 use core::slice;
 use ec4rs::{
-    property::{FinalNewline, IndentSize, IndentStyle, TabWidth, TrimTrailingWs},
+    property::{
+        Charset, EndOfLine, FinalNewline, IndentSize, IndentStyle, TabWidth, TrimTrailingWs,
+    },
     Properties as EditorconfigProperties,
 };
 use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
@@ -24,6 +26,7 @@ use settings::{
     add_references_to_properties, Settings, SettingsLocation, SettingsSources, SettingsStore,
 };
 use std::{borrow::Cow, num::NonZeroU32, path::Path, sync::Arc};
+use theme::FontFeaturesSetting;
 use util::serde::default_true;
 
 /// Initializes the language settings.
@@ -74,11 +77,23 @@ pub struct LanguageSettings {
     /// Whether to indent lines using tab characters, as opposed to multiple
     /// spaces.
     pub hard_tabs: bool,
+    /// Whether to detect how files are indented when opening them, and to
+    /// indent them that way instead of with `hard_tabs` and `tab_size`.
+    pub detect_indentation: bool,
     /// How to soft-wrap long lines of text.
     pub soft_wrap: SoftWrap,
     /// The column at which to soft-wrap lines, for buffers where soft-wrap
     /// is enabled.
     pub preferred_line_length: u32,
+    /// The column at which to soft-wrap lines when `soft_wrap` is `fixed`.
+    pub soft_wrap_column: u32,
+    /// Whether to soft-wrap lines between words, rather than at the first
+    /// character that doesn't fit.
+    pub soft_wrap_at_word_boundaries: bool,
+    /// Whether to indent soft-wrapped lines like the line they continue.
+    pub soft_wrap_hanging_indent: bool,
+    /// The text to show in the gutter next to soft-wrapped lines, if any.
+    pub soft_wrap_indicator: Option<String>,
     /// Whether to show wrap guides (vertical rulers) in the editor.
     /// Setting this to true will show a guide at the 'preferred_line_length' value
     /// if softwrap is set to 'preferred_line_length', and will show any
@@ -96,6 +111,13 @@ pub struct LanguageSettings {
     /// Whether or not to ensure there's a single newline at the end of a buffer
     /// when saving it.
     pub ensure_final_newline_on_save: bool,
+    /// The line ending that new files are created with.
+    pub line_ending: LineEndingSetting,
+    /// Whether new files are created with a byte order mark.
+    pub byte_order_mark: bool,
+    /// Whether to convert files to `line_ending` and `byte_order_mark` when
+    /// saving them, rather than keeping the ones they were opened with.
+    pub normalize_on_save: bool,
     /// How to perform a buffer format.
     pub formatter: SelectedFormatter,
     /// Zed's Prettier integration settings.
@@ -116,6 +138,8 @@ pub struct LanguageSettings {
     /// Note: This setting has no effect in Vim mode, as rewrap is already
     /// allowed everywhere.
     pub allow_rewrap: RewrapBehavior,
+    /// Which text is spell checked in buffers of this language.
+    pub spell_check: SpellCheckBehavior,
     /// Controls whether edit predictions are shown immediately (true)
     /// or manually by triggering `editor::ShowEditPrediction` (false).
     pub show_edit_predictions: bool,
@@ -153,6 +177,9 @@ pub struct LanguageSettings {
     pub show_completion_documentation: bool,
     /// Completion settings for this language.
     pub completions: CompletionSettings,
+    /// The font features for editors of this language, which replace the
+    /// `buffer_font_features`.
+    pub font_features: Option<FontFeaturesSetting>,
 }
 
 impl LanguageSettings {
@@ -213,6 +240,9 @@ pub enum EditPredictionProvider {
     Copilot,
     Supermaven,
     Zed,
+    /// A local or self-hosted model served through an OpenAI-compatible
+    /// completions endpoint, such as llama.cpp, Ollama or vLLM.
+    OpenAiCompatible,
 }
 
 impl EditPredictionProvider {
@@ -221,7 +251,8 @@ impl EditPredictionProvider {
             EditPredictionProvider::Zed => true,
             EditPredictionProvider::None
             | EditPredictionProvider::Copilot
-            | EditPredictionProvider::Supermaven => false,
+            | EditPredictionProvider::Supermaven
+            | EditPredictionProvider::OpenAiCompatible => false,
         }
     }
 }
@@ -240,6 +271,8 @@ pub struct EditPredictionSettings {
     pub mode: EditPredictionsMode,
     /// Settings specific to GitHub Copilot.
     pub copilot: CopilotSettings,
+    /// Settings specific to OpenAI-compatible completion endpoints.
+    pub open_ai_compatible: OpenAiCompatibleEditPredictionSettings,
     /// Whether edit predictions are enabled in the assistant panel.
     /// This setting has no effect if globally disabled.
     pub enabled_in_assistant: bool,
@@ -287,6 +320,16 @@ pub struct CopilotSettings {
     pub proxy_no_verify: Option<bool>,
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct OpenAiCompatibleEditPredictionSettings {
+    /// The base URL of the API, e.g. `http://localhost:8080/v1`.
+    pub api_url: Option<String>,
+    /// The model to request completions from.
+    pub model: Option<String>,
+    /// The maximum number of tokens to generate for a single prediction.
+    pub max_tokens: Option<u32>,
+}
+
 /// The settings for all languages.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct AllLanguageSettingsContent {
@@ -367,6 +410,12 @@ pub struct LanguageSettingsContent {
     /// Default: false
     #[serde(default)]
     pub hard_tabs: Option<bool>,
+    /// Whether to detect how files are indented when opening them, and to
+    /// indent them that way instead of with `hard_tabs` and `tab_size`.
+    ///
+    /// Default: true
+    #[serde(default)]
+    pub detect_indentation: Option<bool>,
     /// How to soft-wrap long lines of text.
     ///
     /// Default: none
@@ -378,6 +427,30 @@ pub struct LanguageSettingsContent {
     /// Default: 80
     #[serde(default)]
     pub preferred_line_length: Option<u32>,
+    /// The column at which to soft-wrap lines when `soft_wrap` is `fixed`,
+    /// regardless of the editor width and the preferred line length.
+    ///
+    /// Default: 80
+    #[serde(default)]
+    pub soft_wrap_column: Option<u32>,
+    /// Whether to soft-wrap lines between words, rather than at the first
+    /// character that doesn't fit.
+    ///
+    /// Default: true
+    #[serde(default)]
+    pub soft_wrap_at_word_boundaries: Option<bool>,
+    /// Whether to indent soft-wrapped lines to the first non-whitespace
+    /// character of the line they continue, so that they line up with it.
+    ///
+    /// Default: true
+    #[serde(default)]
+    pub soft_wrap_hanging_indent: Option<bool>,
+    /// The text to show in the gutter next to soft-wrapped lines, like "↪",
+    /// or null to show nothing.
+    ///
+    /// Default: null
+    #[serde(default)]
+    pub soft_wrap_indicator: Option<String>,
     /// Whether to show wrap guides in the editor. Setting this to true will
     /// show a guide at the 'preferred_line_length' value if softwrap is set to
     /// 'preferred_line_length', and will show any additional guides as specified
@@ -411,6 +484,22 @@ pub struct LanguageSettingsContent {
     /// Default: true
     #[serde(default)]
     pub ensure_final_newline_on_save: Option<bool>,
+    /// The line ending that new files are created with.
+    ///
+    /// Default: system
+    #[serde(default)]
+    pub line_ending: Option<LineEndingSetting>,
+    /// Whether new files are created with a byte order mark.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub byte_order_mark: Option<bool>,
+    /// Whether to convert files to `line_ending` and `byte_order_mark` when
+    /// saving them, rather than keeping the ones they were opened with.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub normalize_on_save: Option<bool>,
     /// How to perform a buffer format.
     ///
     /// Default: auto
@@ -449,6 +538,11 @@ pub struct LanguageSettingsContent {
     /// Default: "in_comments"
     #[serde(default)]
     pub allow_rewrap: Option<RewrapBehavior>,
+    /// Which text is spell checked in buffers of this language.
+    ///
+    /// Default: "off"
+    #[serde(default)]
+    pub spell_check: Option<SpellCheckBehavior>,
     /// Controls whether edit predictions are shown immediately (true)
     /// or manually by triggering `editor::ShowEditPrediction` (false).
     ///
@@ -527,6 +621,12 @@ pub struct LanguageSettingsContent {
     pub show_completion_documentation: Option<bool>,
     /// Controls how completions are processed for this language.
     pub completions: Option<CompletionSettings>,
+    /// The OpenType features to render this language's buffers with, either inline, like
+    /// `{"calt": false}`, or as the name of one of the `font_feature_profiles`. They replace
+    /// the `buffer_font_features`.
+    ///
+    /// Default: null
+    pub font_features: Option<FontFeaturesSetting>,
 }
 
 /// The behavior of `editor::Rewrap`.
@@ -542,6 +642,19 @@ pub enum RewrapBehavior {
     Anywhere,
 }
 
+/// Which text is spell checked.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SpellCheckBehavior {
+    /// Don't spell check.
+    #[default]
+    Off,
+    /// Only spell check comments and strings.
+    CommentsAndStrings,
+    /// Spell check all text except code, such as code spans and code blocks in Markdown.
+    Prose,
+}
+
 /// The contents of the edit prediction settings.
 #[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct EditPredictionSettingsContent {
@@ -557,6 +670,9 @@ pub struct EditPredictionSettingsContent {
     /// Settings specific to GitHub Copilot.
     #[serde(default)]
     pub copilot: CopilotSettingsContent,
+    /// Settings for using a model behind an OpenAI-compatible completions endpoint.
+    #[serde(default)]
+    pub open_ai_compatible: OpenAiCompatibleEditPredictionSettingsContent,
     /// Whether edit predictions are enabled in the assistant prompt editor.
     /// This has no effect if globally disabled.
     #[serde(default = "default_true")]
@@ -577,6 +693,25 @@ pub struct CopilotSettingsContent {
     pub proxy_no_verify: Option<bool>,
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct OpenAiCompatibleEditPredictionSettingsContent {
+    /// The base URL of the API. Requests are sent to `{api_url}/completions`.
+    ///
+    /// Default: none
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// The model to request completions from.
+    ///
+    /// Default: none
+    #[serde(default)]
+    pub model: Option<String>,
+    /// The maximum number of tokens to generate for a single prediction.
+    ///
+    /// Default: 128
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+}
+
 /// The settings for enabling/disabling features.
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -600,6 +735,8 @@ pub enum SoftWrap {
     PreferredLineLength,
     /// Soft wrap line at the preferred line length or the editor width (whichever is smaller).
     Bounded,
+    /// Soft wrap lines at the `soft_wrap_column`, regardless of the editor width.
+    Fixed,
 }
 
 /// Controls the behavior of formatting files when they are saved.
@@ -716,6 +853,30 @@ impl<'de> Deserialize<'de> for FormatOnSave {
     }
 }
 
+/// The line ending that files are created with.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LineEndingSetting {
+    /// Use the line ending of the current platform.
+    #[default]
+    System,
+    /// Use `\n`.
+    Lf,
+    /// Use `\r\n`.
+    Crlf,
+}
+
+impl LineEndingSetting {
+    /// Returns the [`LineEnding`] that this setting stands for.
+    pub fn line_ending(&self) -> LineEnding {
+        match self {
+            Self::System => LineEnding::default(),
+            Self::Lf => LineEnding::Unix,
+            Self::Crlf => LineEnding::Windows,
+        }
+    }
+}
+
 /// Controls how whitespace should be displayedin the editor.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -861,6 +1022,9 @@ impl AsRef<[Formatter]> for FormatterList {
 /// Controls which formatter should be used when formatting code. If there are multiple formatters, they are executed in the order of declaration.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
+// `SelectedFormatter`'s schema is named "Formatter", so this schema needs a different name for
+// the scoped formatters to refer to.
+#[schemars(rename = "FormatterKind")]
 pub enum Formatter {
     /// Format code using the current language server.
     LanguageServer { name: Option<String> },
@@ -875,6 +1039,36 @@ pub enum Formatter {
     },
     /// Files should be formatted using code actions executed by language servers.
     CodeActions(HashMap<String, bool>),
+    /// Run the formatter only when formatting on save, such as `{ "on_save": "prettier" }`.
+    OnSave(Box<Formatter>),
+    /// Run the formatter only when formatting manually with the `editor::Format` or
+    /// `editor::FormatSelections` actions.
+    Manual(Box<Formatter>),
+}
+
+impl Formatter {
+    /// Returns the formatter that's run, without the scope that it's run in.
+    pub fn unscoped(&self) -> &Formatter {
+        match self {
+            Formatter::OnSave(formatter) | Formatter::Manual(formatter) => formatter.unscoped(),
+            formatter => formatter,
+        }
+    }
+}
+
+impl std::fmt::Display for Formatter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Formatter::LanguageServer { name: Some(name) } => {
+                write!(f, "language server {name}")
+            }
+            Formatter::LanguageServer { name: None } => write!(f, "language server"),
+            Formatter::Prettier => write!(f, "Prettier"),
+            Formatter::External { command, .. } => write!(f, "external command `{command}`"),
+            Formatter::CodeActions(_) => write!(f, "code actions"),
+            Formatter::OnSave(formatter) | Formatter::Manual(formatter) => formatter.fmt(f),
+        }
+    }
 }
 
 /// The settings for indent guides.
@@ -1090,6 +1284,16 @@ fn merge_with_editorconfig(settings: &mut LanguageSettings, cfg: &EditorconfigPr
             TrimTrailingWs::Value(b) => b,
         })
         .ok();
+    let line_ending = cfg.get::<EndOfLine>().ok().and_then(|v| match v {
+        EndOfLine::Lf => Some(LineEndingSetting::Lf),
+        EndOfLine::CrLf => Some(LineEndingSetting::Crlf),
+        EndOfLine::Cr => None,
+    });
+    let byte_order_mark = cfg.get::<Charset>().ok().and_then(|v| match v {
+        Charset::Utf8 => Some(false),
+        Charset::Utf8Bom => Some(true),
+        _ => None,
+    });
     fn merge<T>(target: &mut T, value: Option<T>) {
         if let Some(value) = value {
             *target = value;
@@ -1105,6 +1309,8 @@ fn merge_with_editorconfig(settings: &mut LanguageSettings, cfg: &EditorconfigPr
         &mut settings.ensure_final_newline_on_save,
         ensure_final_newline_on_save,
     );
+    merge(&mut settings.line_ending, line_ending);
+    merge(&mut settings.byte_order_mark, byte_order_mark);
 }
 
 /// The kind of an inlay hint.
@@ -1184,6 +1390,17 @@ impl settings::Settings for AllLanguageSettings {
             })
             .unwrap_or_default();
 
+        let mut open_ai_compatible_settings = default_value
+            .edit_predictions
+            .as_ref()
+            .map(|settings| settings.open_ai_compatible.clone())
+            .map(|settings| OpenAiCompatibleEditPredictionSettings {
+                api_url: settings.api_url,
+                model: settings.model,
+                max_tokens: settings.max_tokens,
+            })
+            .unwrap_or_default();
+
         let mut edit_predictions_enabled_in_assistant = default_value
             .edit_predictions
             .as_ref()
@@ -1236,6 +1453,22 @@ impl settings::Settings for AllLanguageSettings {
                 copilot_settings.proxy_no_verify = Some(proxy_no_verify);
             }
 
+            if let Some(open_ai_compatible) = user_settings
+                .edit_predictions
+                .as_ref()
+                .map(|settings| &settings.open_ai_compatible)
+            {
+                if let Some(api_url) = open_ai_compatible.api_url.clone() {
+                    open_ai_compatible_settings.api_url = Some(api_url);
+                }
+                if let Some(model) = open_ai_compatible.model.clone() {
+                    open_ai_compatible_settings.model = Some(model);
+                }
+                if let Some(max_tokens) = open_ai_compatible.max_tokens {
+                    open_ai_compatible_settings.max_tokens = Some(max_tokens);
+                }
+            }
+
             // A user's global settings override the default global settings and
             // all default language-specific settings.
             merge_settings(&mut defaults, &user_settings.defaults);
@@ -1291,6 +1524,7 @@ impl settings::Settings for AllLanguageSettings {
                     .collect(),
                 mode: edit_predictions_mode,
                 copilot: copilot_settings,
+                open_ai_compatible: open_ai_compatible_settings,
                 enabled_in_assistant: edit_predictions_enabled_in_assistant,
             },
             defaults,
@@ -1356,6 +1590,7 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         .clamp(NonZeroU32::new(1).unwrap(), NonZeroU32::new(16).unwrap());
 
     merge(&mut settings.hard_tabs, src.hard_tabs);
+    merge(&mut settings.detect_indentation, src.detect_indentation);
     merge(&mut settings.soft_wrap, src.soft_wrap);
     merge(&mut settings.use_autoclose, src.use_autoclose);
     merge(&mut settings.use_auto_surround, src.use_auto_surround);
@@ -1379,6 +1614,19 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         &mut settings.preferred_line_length,
         src.preferred_line_length,
     );
+    merge(&mut settings.soft_wrap_column, src.soft_wrap_column);
+    merge(
+        &mut settings.soft_wrap_at_word_boundaries,
+        src.soft_wrap_at_word_boundaries,
+    );
+    merge(
+        &mut settings.soft_wrap_hanging_indent,
+        src.soft_wrap_hanging_indent,
+    );
+    merge(
+        &mut settings.soft_wrap_indicator,
+        src.soft_wrap_indicator.clone().map(Some),
+    );
     merge(&mut settings.formatter, src.formatter.clone());
     merge(&mut settings.prettier, src.prettier.clone());
     merge(
@@ -1394,12 +1642,16 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         &mut settings.ensure_final_newline_on_save,
         src.ensure_final_newline_on_save,
     );
+    merge(&mut settings.line_ending, src.line_ending);
+    merge(&mut settings.byte_order_mark, src.byte_order_mark);
+    merge(&mut settings.normalize_on_save, src.normalize_on_save);
     merge(
         &mut settings.enable_language_server,
         src.enable_language_server,
     );
     merge(&mut settings.language_servers, src.language_servers.clone());
     merge(&mut settings.allow_rewrap, src.allow_rewrap);
+    merge(&mut settings.spell_check, src.spell_check);
     merge(
         &mut settings.show_edit_predictions,
         src.show_edit_predictions,
@@ -1423,6 +1675,10 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         src.show_completion_documentation,
     );
     merge(&mut settings.completions, src.completions);
+    merge(
+        &mut settings.font_features,
+        src.font_features.clone().map(Some),
+    );
 }
 
 /// Allows to enable/disable formatting with Prettier
@@ -1495,6 +1751,39 @@ mod tests {
                 .into()
             )))
         );
+        let raw = r#"{"formatter": [{"on_save": "prettier"}, {"manual": {"external": {"command": "fmt"}}}]}"#;
+        let settings: LanguageSettingsContent = serde_json::from_str(raw).unwrap();
+        assert_eq!(
+            settings.formatter,
+            Some(SelectedFormatter::List(FormatterList(
+                vec![
+                    Formatter::OnSave(Box::new(Formatter::Prettier)),
+                    Formatter::Manual(Box::new(Formatter::External {
+                        command: "fmt".into(),
+                        arguments: None
+                    })),
+                ]
+                .into()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_font_features_deserialization() {
+        let raw = r#"{"font_features": "no-ligatures"}"#;
+        let settings: LanguageSettingsContent = serde_json::from_str(raw).unwrap();
+        assert_eq!(
+            settings.font_features,
+            Some(FontFeaturesSetting::Profile("no-ligatures".into()))
+        );
+        let raw = r#"{"font_features": {"calt": false}}"#;
+        let settings: LanguageSettingsContent = serde_json::from_str(raw).unwrap();
+        assert_eq!(
+            settings.font_features,
+            Some(FontFeaturesSetting::Features(
+                gpui::FontFeatures::disable_ligatures()
+            ))
+        );
     }
 
     #[test]
@@ -0,0 +1,124 @@
+use std::{
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use futures::channel::oneshot;
+use parking_lot::Mutex;
+
+/// The number of large buffers that are parsed at the same time. Parsing more of them at once
+/// would take up the background threads that other work, like searching, needs.
+const MAX_CONCURRENT_PARSES: usize = 2;
+
+/// Limits how many large buffers are parsed at the same time. Buffers that are shown in a view
+/// are parsed first, the others in the order they were queued.
+pub(crate) struct ParseQueue {
+    state: Mutex<ParseQueueState>,
+}
+
+struct ParseQueueState {
+    running: usize,
+    waiting: Vec<Waiter>,
+}
+
+struct Waiter {
+    is_visible: Arc<AtomicBool>,
+    ready: oneshot::Sender<ParsePermit>,
+}
+
+/// Allows parsing a large buffer until it's dropped.
+pub(crate) struct ParsePermit {
+    queue: &'static ParseQueue,
+}
+
+impl ParseQueue {
+    pub const fn new() -> Self {
+        Self {
+            state: Mutex::new(ParseQueueState {
+                running: 0,
+                waiting: Vec::new(),
+            }),
+        }
+    }
+
+    /// Waits until a buffer can be parsed. `is_visible` is checked whenever another parse
+    /// finishes, so that buffers shown after being queued still go first.
+    pub async fn acquire(&'static self, is_visible: Arc<AtomicBool>) -> ParsePermit {
+        let ready = {
+            let mut state = self.state.lock();
+            if state.running < MAX_CONCURRENT_PARSES {
+                state.running += 1;
+                return ParsePermit { queue: self };
+            }
+            let (tx, rx) = oneshot::channel();
+            state.waiting.push(Waiter {
+                is_visible,
+                ready: tx,
+            });
+            rx
+        };
+        match ready.await {
+            Ok(permit) => permit,
+            // The queue never drops a waiter without handing it a permit.
+            Err(_) => unreachable!(),
+        }
+    }
+
+    fn release(&'static self) {
+        let mut state = self.state.lock();
+        let mut permit = ParsePermit { queue: self };
+        while !state.waiting.is_empty() {
+            let ix = state
+                .waiting
+                .iter()
+                .position(|waiter| waiter.is_visible.load(Ordering::Acquire))
+                .unwrap_or(0);
+            let waiter = state.waiting.remove(ix);
+            match waiter.ready.send(permit) {
+                Ok(()) => return,
+                // The waiter was dropped, so hand the permit to the next one.
+                Err(returned) => permit = returned,
+            }
+        }
+        state.running -= 1;
+        mem::forget(permit);
+    }
+}
+
+impl Drop for ParsePermit {
+    fn drop(&mut self) {
+        self.queue.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt as _;
+
+    #[test]
+    fn test_visible_buffers_are_parsed_first() {
+        static QUEUE: ParseQueue = ParseQueue::new();
+        let hidden = Arc::new(AtomicBool::new(false));
+        let visible = Arc::new(AtomicBool::new(false));
+
+        let running = (0..MAX_CONCURRENT_PARSES)
+            .map(|_| QUEUE.acquire(hidden.clone()).now_or_never().unwrap())
+            .collect::<Vec<_>>();
+        let mut hidden_parse = QUEUE.acquire(hidden.clone()).boxed();
+        let mut visible_parse = QUEUE.acquire(visible.clone()).boxed();
+        assert!((&mut hidden_parse).now_or_never().is_none());
+        assert!((&mut visible_parse).now_or_never().is_none());
+
+        // The buffer is shown after being queued, and still goes first.
+        visible.store(true, Ordering::Release);
+        drop(running);
+        let visible_permit = visible_parse.now_or_never().unwrap();
+        let hidden_permit = hidden_parse.now_or_never().unwrap();
+        drop((visible_permit, hidden_permit));
+        assert_eq!(QUEUE.state.lock().running, 0);
+    }
+}
@@ -22,6 +22,9 @@ use tree_sitter::{Node, Query, QueryCapture, QueryCaptures, QueryCursor, QueryMa
 pub struct SyntaxMap {
     snapshot: SyntaxSnapshot,
     language_registry: Option<Arc<LanguageRegistry>>,
+    /// A parse of part of the text, used for highlighting that part until the whole text has
+    /// been parsed.
+    partial_snapshot: Option<SyntaxSnapshot>,
 }
 
 #[derive(Clone)]
@@ -216,6 +219,7 @@ impl SyntaxMap {
         Self {
             snapshot: SyntaxSnapshot::new(text),
             language_registry: None,
+            partial_snapshot: None,
         }
     }
 
@@ -227,12 +231,19 @@ impl SyntaxMap {
         self.snapshot.clone()
     }
 
+    pub fn partial_snapshot(&self) -> Option<SyntaxSnapshot> {
+        self.partial_snapshot.clone()
+    }
+
     pub fn language_registry(&self) -> Option<Arc<LanguageRegistry>> {
         self.language_registry.clone()
     }
 
     pub fn interpolate(&mut self, text: &BufferSnapshot) {
         self.snapshot.interpolate(text);
+        if let Some(partial_snapshot) = &mut self.partial_snapshot {
+            partial_snapshot.interpolate(text);
+        }
     }
 
     #[cfg(test)]
@@ -243,10 +254,23 @@ impl SyntaxMap {
 
     pub fn did_parse(&mut self, snapshot: SyntaxSnapshot) {
         self.snapshot = snapshot;
+        self.partial_snapshot = None;
+    }
+
+    /// Returns whether the parse of part of the text is used, which it isn't if the whole text
+    /// has been parsed in the meantime.
+    pub fn did_parse_range(&mut self, snapshot: SyntaxSnapshot) -> bool {
+        if self.snapshot.is_empty() {
+            self.partial_snapshot = Some(snapshot);
+            true
+        } else {
+            false
+        }
     }
 
     pub fn clear(&mut self, text: &BufferSnapshot) {
         self.snapshot = SyntaxSnapshot::new(text);
+        self.partial_snapshot = None;
     }
 }
 
@@ -393,7 +417,13 @@ impl SyntaxSnapshot {
             .edits_since::<usize>(&self.parsed_version)
             .map(|edit| edit.new)
             .collect::<Vec<_>>();
-        self.reparse_with_ranges(text, root_language.clone(), edit_ranges, registry.as_ref());
+        self.reparse_with_ranges(
+            text,
+            root_language.clone(),
+            0..text.len(),
+            edit_ranges,
+            registry.as_ref(),
+        );
 
         if let Some(registry) = registry {
             if registry.version() != self.language_registry_version {
@@ -423,6 +453,7 @@ impl SyntaxSnapshot {
                     self.reparse_with_ranges(
                         text,
                         root_language,
+                        0..text.len(),
                         resolved_injection_ranges,
                         Some(&registry),
                     );
@@ -432,10 +463,31 @@ impl SyntaxSnapshot {
         }
     }
 
+    /// Parses only the given range of the text, so that it can be highlighted before the whole
+    /// text has been parsed. Syntax that starts before the range, like an enclosing block
+    /// comment, isn't recognized.
+    pub fn parse_range(
+        text: &BufferSnapshot,
+        registry: Option<Arc<LanguageRegistry>>,
+        root_language: Arc<Language>,
+        range: Range<usize>,
+    ) -> Self {
+        let mut snapshot = Self::new(text);
+        snapshot.reparse_with_ranges(
+            text,
+            root_language,
+            range.clone(),
+            vec![range],
+            registry.as_ref(),
+        );
+        snapshot
+    }
+
     fn reparse_with_ranges(
         &mut self,
         text: &BufferSnapshot,
         root_language: Arc<Language>,
+        root_range: Range<usize>,
         invalidated_ranges: Vec<Range<usize>>,
         registry: Option<&Arc<LanguageRegistry>>,
     ) {
@@ -449,18 +501,23 @@ impl SyntaxSnapshot {
         let mut changed_regions = ChangeRegionSet::default();
         let mut queue = BinaryHeap::new();
         let mut combined_injection_ranges = HashMap::default();
+        let root_anchor_range = if root_range == (0..text.len()) {
+            Anchor::MIN..Anchor::MAX
+        } else {
+            text.anchor_before(root_range.start)..text.anchor_after(root_range.end)
+        };
         queue.push(ParseStep {
             depth: 0,
             language: ParseStepLanguage::Loaded {
                 language: root_language,
             },
             included_ranges: vec![tree_sitter::Range {
-                start_byte: 0,
-                end_byte: text.len(),
-                start_point: Point::zero().to_ts_point(),
-                end_point: text.max_point().to_ts_point(),
+                start_byte: root_range.start,
+                end_byte: root_range.end,
+                start_point: text.offset_to_point(root_range.start).to_ts_point(),
+                end_point: text.offset_to_point(root_range.end).to_ts_point(),
             }],
-            range: Anchor::MIN..Anchor::MAX,
+            range: root_anchor_range,
             mode: ParseMode::Single,
         });
 
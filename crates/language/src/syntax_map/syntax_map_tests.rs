@@ -179,6 +179,46 @@ fn test_syntax_map_layers_for_range(cx: &mut App) {
     );
 }
 
+#[gpui::test]
+fn test_parse_range(cx: &mut App) {
+    let registry = Arc::new(LanguageRegistry::test(cx.background_executor().clone()));
+    let language = Arc::new(rust_lang());
+    registry.add(language.clone());
+
+    let buffer = Buffer::new(
+        0,
+        BufferId::new(1).unwrap(),
+        r#"
+            /* fn a() {}
+            fn b() {
+                vec![c];
+            }
+            */
+        "#
+        .unindent(),
+    );
+
+    // Only the given range is parsed, so the comment surrounding it isn't recognized.
+    let range = range_for_text(&buffer, "fn b() {\n    vec![c];\n}");
+    let snapshot = SyntaxSnapshot::parse_range(&buffer, Some(registry), language, range.clone());
+    let layers = snapshot
+        .layers_for_range(range.clone(), &buffer, true)
+        .collect::<Vec<_>>();
+    assert_eq!(layers.len(), 2);
+    assert_eq!(layers[0].node().byte_range(), range);
+    assert!(layers[0]
+        .node()
+        .to_sexp()
+        .starts_with("(source_file (function_item"));
+    assert!(layers[1].node().to_sexp().contains("(identifier)"));
+    assert_eq!(
+        snapshot
+            .layers_for_range(0..range.start - 1, &buffer, true)
+            .count(),
+        0
+    );
+}
+
 #[gpui::test]
 fn test_dynamic_language_injection(cx: &mut App) {
     let registry = Arc::new(LanguageRegistry::test(cx.background_executor().clone()));
@@ -86,6 +86,15 @@ pub struct TokenUsage {
     pub cache_read_input_tokens: u32,
 }
 
+impl TokenUsage {
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens as u64
+            + self.output_tokens as u64
+            + self.cache_creation_input_tokens as u64
+            + self.cache_read_input_tokens as u64
+    }
+}
+
 impl Add<TokenUsage> for TokenUsage {
     type Output = Self;
 
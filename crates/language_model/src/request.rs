@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::io::{Cursor, Write};
 use std::sync::Arc;
 
@@ -8,7 +9,7 @@ use gpui::{
     point, px, size, App, AppContext as _, DevicePixels, Image, ObjectFit, RenderImage,
     SharedString, Size, Task,
 };
-use image::{codecs::png::PngEncoder, imageops::resize, DynamicImage, ImageDecoder};
+use image::{codecs::png::PngEncoder, imageops::resize};
 use serde::{Deserialize, Serialize};
 use util::ResultExt;
 
@@ -33,18 +34,66 @@ const ANTHROPIC_SIZE_LIMT: f32 = 1568.;
 
 impl LanguageModelImage {
     pub fn from_image(data: Image, cx: &mut App) -> Task<Option<Self>> {
+        Self::from_image_with_max_size(Arc::new(data), ANTHROPIC_SIZE_LIMT as u32, cx)
+    }
+
+    /// Resolves an image into an LLM-ready format (base64), scaling it down to fit within
+    /// `max_size` pixels in both dimensions. Sizes above what models accept are clamped.
+    pub fn from_image_with_max_size(
+        data: Arc<Image>,
+        max_size: u32,
+        cx: &mut App,
+    ) -> Task<Option<Self>> {
         cx.background_spawn(async move {
-            match data.format() {
-                gpui::ImageFormat::Png
-                | gpui::ImageFormat::Jpeg
-                | gpui::ImageFormat::Webp
-                | gpui::ImageFormat::Gif => {}
-                _ => return None,
+            let format = match data.format() {
+                gpui::ImageFormat::Png => image::ImageFormat::Png,
+                gpui::ImageFormat::Jpeg => image::ImageFormat::Jpeg,
+                gpui::ImageFormat::Webp => image::ImageFormat::WebP,
+                gpui::ImageFormat::Gif => image::ImageFormat::Gif,
+                gpui::ImageFormat::Bmp => image::ImageFormat::Bmp,
+                gpui::ImageFormat::Tiff => image::ImageFormat::Tiff,
+                gpui::ImageFormat::Svg => return None,
             };
 
-            let image = image::codecs::png::PngDecoder::new(Cursor::new(data.bytes())).log_err()?;
-            let (width, height) = image.dimensions();
-            let image_size = size(DevicePixels(width as i32), DevicePixels(height as i32));
+            let image = image::load_from_memory_with_format(data.bytes(), format).log_err()?;
+            let max_size = max_size.clamp(1, ANTHROPIC_SIZE_LIMT as u32);
+            let mut image_size = size(
+                DevicePixels(image.width() as i32),
+                DevicePixels(image.height() as i32),
+            );
+
+            let png = if image.width() > max_size || image.height() > max_size {
+                let new_bounds = ObjectFit::ScaleDown.get_bounds(
+                    gpui::Bounds {
+                        origin: point(px(0.0), px(0.0)),
+                        size: size(px(max_size as f32), px(max_size as f32)),
+                    },
+                    image_size,
+                );
+                let image = image.resize(
+                    new_bounds.size.width.0 as u32,
+                    new_bounds.size.height.0 as u32,
+                    image::imageops::FilterType::Triangle,
+                );
+                image_size = size(
+                    DevicePixels(image.width() as i32),
+                    DevicePixels(image.height() as i32),
+                );
+
+                let mut png = Vec::new();
+                image
+                    .write_with_encoder(PngEncoder::new(&mut png))
+                    .log_err()?;
+                Cow::Owned(png)
+            } else if format == image::ImageFormat::Png {
+                Cow::Borrowed(data.bytes())
+            } else {
+                let mut png = Vec::new();
+                image
+                    .write_with_encoder(PngEncoder::new(&mut png))
+                    .log_err()?;
+                Cow::Owned(png)
+            };
 
             let mut base64_image = Vec::new();
 
@@ -54,31 +103,7 @@ impl LanguageModelImage {
                     &base64::engine::general_purpose::STANDARD,
                 );
 
-                if image_size.width.0 > ANTHROPIC_SIZE_LIMT as i32
-                    || image_size.height.0 > ANTHROPIC_SIZE_LIMT as i32
-                {
-                    let new_bounds = ObjectFit::ScaleDown.get_bounds(
-                        gpui::Bounds {
-                            origin: point(px(0.0), px(0.0)),
-                            size: size(px(ANTHROPIC_SIZE_LIMT), px(ANTHROPIC_SIZE_LIMT)),
-                        },
-                        image_size,
-                    );
-                    let image = DynamicImage::from_decoder(image).log_err()?.resize(
-                        new_bounds.size.width.0 as u32,
-                        new_bounds.size.height.0 as u32,
-                        image::imageops::FilterType::Triangle,
-                    );
-
-                    let mut png = Vec::new();
-                    image
-                        .write_with_encoder(PngEncoder::new(&mut png))
-                        .log_err()?;
-
-                    base64_encoder.write_all(png.as_slice()).log_err()?;
-                } else {
-                    base64_encoder.write_all(data.bytes()).log_err()?;
-                }
+                base64_encoder.write_all(&png).log_err()?;
             }
 
             // SAFETY: The base64 encoder should not produce non-UTF8.
@@ -153,6 +178,11 @@ impl LanguageModelImage {
         })
     }
 
+    /// The size of the image as it's sent to the model.
+    pub fn size(&self) -> Size<DevicePixels> {
+        self.size
+    }
+
     pub fn estimate_tokens(&self) -> usize {
         let width = self.size.width.0.unsigned_abs() as usize;
         let height = self.size.height.0.unsigned_abs() as usize;
@@ -248,3 +278,76 @@ pub struct LanguageModelResponseMessage {
     pub role: Option<Role>,
     pub content: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine as _;
+    use gpui::{hash, ImageFormat, TestAppContext};
+    use image::{DynamicImage, RgbImage};
+
+    fn encode_image(width: u32, height: u32, format: ImageFormat) -> Arc<Image> {
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(width, height))
+            .write_to(
+                &mut Cursor::new(&mut bytes),
+                match format {
+                    ImageFormat::Png => image::ImageFormat::Png,
+                    ImageFormat::Jpeg => image::ImageFormat::Jpeg,
+                    _ => unimplemented!(),
+                },
+            )
+            .unwrap();
+        Arc::new(Image {
+            id: hash(&bytes),
+            format,
+            bytes,
+        })
+    }
+
+    async fn to_language_model_image(
+        image: Arc<Image>,
+        max_size: u32,
+        cx: &mut TestAppContext,
+    ) -> LanguageModelImage {
+        cx.update(|cx| LanguageModelImage::from_image_with_max_size(image, max_size, cx))
+            .await
+            .unwrap()
+    }
+
+    fn decode_source(image: &LanguageModelImage) -> Vec<u8> {
+        base64::engine::general_purpose::STANDARD
+            .decode(image.source.as_bytes())
+            .unwrap()
+    }
+
+    #[gpui::test]
+    async fn test_image_downscaling(cx: &mut TestAppContext) {
+        // Larger images are scaled down to fit, keeping their aspect ratio.
+        let image =
+            to_language_model_image(encode_image(2000, 1000, ImageFormat::Png), 1000, cx).await;
+        assert_eq!(image.size(), size(DevicePixels(1000), DevicePixels(500)));
+        let png = image::load_from_memory(&decode_source(&image)).unwrap();
+        assert_eq!((png.width(), png.height()), (1000, 500));
+
+        // Sizes above what models accept are clamped.
+        let image =
+            to_language_model_image(encode_image(500, 4000, ImageFormat::Png), 10_000, cx).await;
+        assert_eq!(image.size(), size(DevicePixels(196), DevicePixels(1568)));
+
+        // Smaller PNGs are sent as they are.
+        let original = encode_image(300, 200, ImageFormat::Png);
+        let image = to_language_model_image(original.clone(), 1000, cx).await;
+        assert_eq!(image.size(), size(DevicePixels(300), DevicePixels(200)));
+        assert_eq!(decode_source(&image), original.bytes);
+
+        // Other formats are converted to PNG.
+        let image =
+            to_language_model_image(encode_image(300, 200, ImageFormat::Jpeg), 1000, cx).await;
+        assert_eq!(image.size(), size(DevicePixels(300), DevicePixels(200)));
+        assert_eq!(
+            image::guess_format(&decode_source(&image)).unwrap(),
+            image::ImageFormat::Png
+        );
+    }
+}
@@ -19,6 +19,7 @@ use crate::provider::lmstudio::LmStudioLanguageModelProvider;
 use crate::provider::mistral::MistralLanguageModelProvider;
 use crate::provider::ollama::OllamaLanguageModelProvider;
 use crate::provider::open_ai::OpenAiLanguageModelProvider;
+use crate::provider::open_ai_compatible::OpenAiCompatibleLanguageModelProvider;
 pub use crate::settings::*;
 
 pub fn init(user_store: Entity<UserStore>, client: Arc<Client>, fs: Arc<dyn Fs>, cx: &mut App) {
@@ -53,6 +54,10 @@ fn register_language_model_providers(
         LmStudioLanguageModelProvider::new(client.http_client(), cx),
         cx,
     );
+    registry.register_provider(
+        OpenAiCompatibleLanguageModelProvider::new(client.http_client(), cx),
+        cx,
+    );
     registry.register_provider(
         DeepSeekLanguageModelProvider::new(client.http_client(), cx),
         cx,
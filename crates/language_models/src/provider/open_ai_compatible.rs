@@ -0,0 +1,928 @@
+use anyhow::{anyhow, Result};
+use collections::{BTreeMap, HashMap};
+use futures::{future::BoxFuture, stream::BoxStream, FutureExt, Stream, StreamExt};
+use gpui::{AnyView, App, AsyncApp, Context, Subscription, Task};
+use http_client::HttpClient;
+use language_model::{
+    AuthenticateError, LanguageModel, LanguageModelCompletionEvent, LanguageModelId,
+    LanguageModelName, LanguageModelProvider, LanguageModelProviderId, LanguageModelProviderName,
+    LanguageModelProviderState, LanguageModelRequest, LanguageModelToolUse, MessageContent,
+    RateLimiter, Role, StopReason, TokenUsage,
+};
+use open_ai::{
+    list_models, stream_completion, FunctionContent, FunctionDefinition, RequestMessage,
+    ResponseStreamEvent, ToolCall, ToolCallContent, ToolChoice, ToolDefinition,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use std::{pin::Pin, str::FromStr, sync::Arc};
+use ui::{prelude::*, ButtonLike, Indicator};
+use util::ResultExt;
+
+use crate::AllLanguageModelSettings;
+
+const PROVIDER_ID: &str = "openai_compatible";
+const PROVIDER_NAME: &str = "OpenAI Compatible";
+
+/// The API key sent to the server, for servers started with authentication enabled.
+const OPENAI_COMPATIBLE_API_KEY_VAR: &str = "OPENAI_COMPATIBLE_API_KEY";
+
+/// The context length used when neither the settings nor the server specify one.
+const DEFAULT_MAX_TOKENS: usize = 8192;
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct OpenAiCompatibleSettings {
+    pub api_url: String,
+    pub available_models: Vec<AvailableModel>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AvailableModel {
+    /// The model's id as reported by the server (e.g. "qwen2.5-coder:7b")
+    pub name: String,
+    /// The model's name in Zed's UI, such as in the model selector dropdown menu in the assistant panel.
+    pub display_name: Option<String>,
+    /// The model's context length. Overrides the value reported by the server.
+    pub max_tokens: usize,
+    /// The maximum number of tokens to generate per response.
+    pub max_output_tokens: Option<u32>,
+    /// Whether the server supports tool calls for this model.
+    #[serde(default)]
+    pub supports_tools: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Model {
+    pub name: String,
+    pub display_name: Option<String>,
+    pub max_tokens: usize,
+    pub max_output_tokens: Option<u32>,
+    pub supports_tools: bool,
+}
+
+impl Model {
+    pub fn display_name(&self) -> &str {
+        self.display_name.as_ref().unwrap_or(&self.name)
+    }
+}
+
+pub struct OpenAiCompatibleLanguageModelProvider {
+    http_client: Arc<dyn HttpClient>,
+    state: gpui::Entity<State>,
+}
+
+pub struct State {
+    http_client: Arc<dyn HttpClient>,
+    available_models: Vec<Model>,
+    fetch_model_task: Option<Task<Result<()>>>,
+    _subscription: Subscription,
+}
+
+fn api_key() -> Option<String> {
+    std::env::var(OPENAI_COMPATIBLE_API_KEY_VAR)
+        .ok()
+        .filter(|key| !key.is_empty())
+}
+
+impl State {
+    fn is_authenticated(&self) -> bool {
+        !self.available_models.is_empty()
+    }
+
+    fn fetch_models(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let settings = &AllLanguageModelSettings::get_global(cx).openai_compatible;
+        let http_client = self.http_client.clone();
+        let api_url = settings.api_url.clone();
+
+        // As with Ollama, we treat the server as "authenticated" once it lists its models.
+        cx.spawn(async move |this, cx| {
+            let api_key = api_key();
+            let models = list_models(http_client.as_ref(), &api_url, api_key.as_deref()).await?;
+
+            let mut models: Vec<Model> = models
+                .into_iter()
+                .filter(|model| !model.id.contains("embed"))
+                .map(|model| Model {
+                    max_tokens: model.context_length().unwrap_or(DEFAULT_MAX_TOKENS),
+                    name: model.id,
+                    display_name: None,
+                    max_output_tokens: None,
+                    supports_tools: false,
+                })
+                .collect();
+
+            models.sort_by(|a, b| a.name.cmp(&b.name));
+
+            this.update(cx, |this, cx| {
+                this.available_models = models;
+                cx.notify();
+            })
+        })
+    }
+
+    fn restart_fetch_models_task(&mut self, cx: &mut Context<Self>) {
+        let task = self.fetch_models(cx);
+        self.fetch_model_task.replace(task);
+    }
+
+    fn authenticate(&mut self, cx: &mut Context<Self>) -> Task<Result<(), AuthenticateError>> {
+        if self.is_authenticated() {
+            return Task::ready(Ok(()));
+        }
+
+        let fetch_models_task = self.fetch_models(cx);
+        cx.spawn(async move |_this, _cx| Ok(fetch_models_task.await?))
+    }
+}
+
+impl OpenAiCompatibleLanguageModelProvider {
+    pub fn new(http_client: Arc<dyn HttpClient>, cx: &mut App) -> Self {
+        let this = Self {
+            http_client: http_client.clone(),
+            state: cx.new(|cx| {
+                let subscription = cx.observe_global::<SettingsStore>({
+                    let mut settings = AllLanguageModelSettings::get_global(cx)
+                        .openai_compatible
+                        .clone();
+                    move |this: &mut State, cx| {
+                        let new_settings =
+                            &AllLanguageModelSettings::get_global(cx).openai_compatible;
+                        if &settings != new_settings {
+                            settings = new_settings.clone();
+                            this.restart_fetch_models_task(cx);
+                            cx.notify();
+                        }
+                    }
+                });
+
+                State {
+                    http_client,
+                    available_models: Default::default(),
+                    fetch_model_task: None,
+                    _subscription: subscription,
+                }
+            }),
+        };
+        this.state
+            .update(cx, |state, cx| state.restart_fetch_models_task(cx));
+        this
+    }
+}
+
+impl LanguageModelProviderState for OpenAiCompatibleLanguageModelProvider {
+    type ObservableEntity = State;
+
+    fn observable_entity(&self) -> Option<gpui::Entity<Self::ObservableEntity>> {
+        Some(self.state.clone())
+    }
+}
+
+impl LanguageModelProvider for OpenAiCompatibleLanguageModelProvider {
+    fn id(&self) -> LanguageModelProviderId {
+        LanguageModelProviderId(PROVIDER_ID.into())
+    }
+
+    fn name(&self) -> LanguageModelProviderName {
+        LanguageModelProviderName(PROVIDER_NAME.into())
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::AiOpenAi
+    }
+
+    fn default_model(&self, cx: &App) -> Option<Arc<dyn LanguageModel>> {
+        self.provided_models(cx).into_iter().next()
+    }
+
+    fn provided_models(&self, cx: &App) -> Vec<Arc<dyn LanguageModel>> {
+        let mut models: BTreeMap<String, Model> = BTreeMap::default();
+
+        // Add models reported by the server
+        for model in self.state.read(cx).available_models.iter() {
+            models.insert(model.name.clone(), model.clone());
+        }
+
+        // Override with available models from settings
+        for model in AllLanguageModelSettings::get_global(cx)
+            .openai_compatible
+            .available_models
+            .iter()
+        {
+            models.insert(
+                model.name.clone(),
+                Model {
+                    name: model.name.clone(),
+                    display_name: model.display_name.clone(),
+                    max_tokens: model.max_tokens,
+                    max_output_tokens: model.max_output_tokens,
+                    supports_tools: model.supports_tools,
+                },
+            );
+        }
+
+        models
+            .into_values()
+            .map(|model| {
+                Arc::new(OpenAiCompatibleLanguageModel {
+                    id: LanguageModelId::from(model.name.clone()),
+                    model,
+                    http_client: self.http_client.clone(),
+                    request_limiter: RateLimiter::new(4),
+                }) as Arc<dyn LanguageModel>
+            })
+            .collect()
+    }
+
+    fn is_authenticated(&self, cx: &App) -> bool {
+        self.state.read(cx).is_authenticated()
+    }
+
+    fn authenticate(&self, cx: &mut App) -> Task<Result<(), AuthenticateError>> {
+        self.state.update(cx, |state, cx| state.authenticate(cx))
+    }
+
+    fn configuration_view(&self, window: &mut Window, cx: &mut App) -> AnyView {
+        let state = self.state.clone();
+        cx.new(|cx| ConfigurationView::new(state, window, cx))
+            .into()
+    }
+
+    fn reset_credentials(&self, cx: &mut App) -> Task<Result<()>> {
+        self.state.update(cx, |state, cx| state.fetch_models(cx))
+    }
+}
+
+pub struct OpenAiCompatibleLanguageModel {
+    id: LanguageModelId,
+    model: Model,
+    http_client: Arc<dyn HttpClient>,
+    request_limiter: RateLimiter,
+}
+
+impl OpenAiCompatibleLanguageModel {
+    fn stream_completion(
+        &self,
+        request: open_ai::Request,
+        cx: &AsyncApp,
+    ) -> BoxFuture<'static, Result<BoxStream<'static, Result<ResponseStreamEvent>>>> {
+        let http_client = self.http_client.clone();
+        let Ok(api_url) = cx.update(|cx| {
+            let settings = &AllLanguageModelSettings::get_global(cx).openai_compatible;
+            settings.api_url.clone()
+        }) else {
+            return futures::future::ready(Err(anyhow!("App state dropped"))).boxed();
+        };
+
+        let future = self.request_limiter.stream(async move {
+            let api_key = api_key().unwrap_or_default();
+            let request = stream_completion(http_client.as_ref(), &api_url, &api_key, request);
+            let response = request.await?;
+            Ok(response)
+        });
+
+        async move { Ok(future.await?.boxed()) }.boxed()
+    }
+}
+
+impl LanguageModel for OpenAiCompatibleLanguageModel {
+    fn id(&self) -> LanguageModelId {
+        self.id.clone()
+    }
+
+    fn name(&self) -> LanguageModelName {
+        LanguageModelName::from(self.model.display_name().to_string())
+    }
+
+    fn provider_id(&self) -> LanguageModelProviderId {
+        LanguageModelProviderId(PROVIDER_ID.into())
+    }
+
+    fn provider_name(&self) -> LanguageModelProviderName {
+        LanguageModelProviderName(PROVIDER_NAME.into())
+    }
+
+    fn telemetry_id(&self) -> String {
+        format!("openai_compatible/{}", self.model.name)
+    }
+
+    fn max_token_count(&self) -> usize {
+        self.model.max_tokens
+    }
+
+    fn max_output_tokens(&self) -> Option<u32> {
+        self.model.max_output_tokens
+    }
+
+    fn count_tokens(
+        &self,
+        request: LanguageModelRequest,
+        _cx: &App,
+    ) -> BoxFuture<'static, Result<usize>> {
+        // Local servers use their own tokenizers, so this is only an estimate.
+        let token_count = request
+            .messages
+            .iter()
+            .map(|msg| msg.string_contents().chars().count())
+            .sum::<usize>()
+            / 4;
+
+        async move { Ok(token_count) }.boxed()
+    }
+
+    fn stream_completion(
+        &self,
+        request: LanguageModelRequest,
+        cx: &AsyncApp,
+    ) -> BoxFuture<'static, Result<BoxStream<'static, Result<LanguageModelCompletionEvent>>>> {
+        let request = into_open_ai_compatible(request, &self.model);
+        let completions = self.stream_completion(request, cx);
+        async move { Ok(map_to_language_model_completion_events(completions.await?).boxed()) }
+            .boxed()
+    }
+
+    fn use_any_tool(
+        &self,
+        request: LanguageModelRequest,
+        tool_name: String,
+        tool_description: String,
+        schema: serde_json::Value,
+        cx: &AsyncApp,
+    ) -> BoxFuture<'static, Result<BoxStream<'static, Result<String>>>> {
+        let mut request = into_open_ai_compatible(request, &self.model);
+        request.tool_choice = Some(ToolChoice::Other(ToolDefinition::Function {
+            function: FunctionDefinition {
+                name: tool_name.clone(),
+                description: None,
+                parameters: None,
+            },
+        }));
+        request.tools = vec![ToolDefinition::Function {
+            function: FunctionDefinition {
+                name: tool_name.clone(),
+                description: Some(tool_description),
+                parameters: Some(schema),
+            },
+        }];
+
+        let response = self.stream_completion(request, cx);
+        self.request_limiter
+            .run(async move {
+                let response = response.await?;
+                Ok(
+                    open_ai::extract_tool_args_from_events(tool_name, Box::pin(response))
+                        .await?
+                        .boxed(),
+                )
+            })
+            .boxed()
+    }
+}
+
+/// Converts a request into the OpenAI chat format, including tool calls and
+/// tool results when the model supports them.
+pub fn into_open_ai_compatible(request: LanguageModelRequest, model: &Model) -> open_ai::Request {
+    let mut messages = Vec::new();
+    for message in request.messages {
+        let mut text = String::new();
+        let mut tool_calls = Vec::new();
+        for content in message.content {
+            match content {
+                MessageContent::Text(content) => text.push_str(&content),
+                MessageContent::Image(_) => {}
+                MessageContent::ToolUse(tool_use) if model.supports_tools => {
+                    tool_calls.push(ToolCall {
+                        id: tool_use.id.to_string(),
+                        content: ToolCallContent::Function {
+                            function: FunctionContent {
+                                name: tool_use.name.to_string(),
+                                arguments: tool_use.input.to_string(),
+                            },
+                        },
+                    });
+                }
+                MessageContent::ToolResult(tool_result) if model.supports_tools => {
+                    messages.push(RequestMessage::Tool {
+                        content: tool_result.content.to_string(),
+                        tool_call_id: tool_result.tool_use_id.to_string(),
+                    });
+                }
+                MessageContent::ToolUse(_) | MessageContent::ToolResult(_) => {}
+            }
+        }
+
+        match message.role {
+            Role::User => {
+                if !text.is_empty() {
+                    messages.push(RequestMessage::User { content: text });
+                }
+            }
+            Role::Assistant => {
+                if !text.is_empty() || !tool_calls.is_empty() {
+                    messages.push(RequestMessage::Assistant {
+                        content: Some(text).filter(|text| !text.is_empty()),
+                        tool_calls,
+                    });
+                }
+            }
+            Role::System => messages.push(RequestMessage::System { content: text }),
+        }
+    }
+
+    let tools = if model.supports_tools {
+        request
+            .tools
+            .into_iter()
+            .map(|tool| ToolDefinition::Function {
+                function: FunctionDefinition {
+                    name: tool.name,
+                    description: Some(tool.description),
+                    parameters: Some(tool.input_schema),
+                },
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    open_ai::Request {
+        model: model.name.clone(),
+        messages,
+        stream: true,
+        stop: request.stop,
+        temperature: request.temperature.unwrap_or(1.0),
+        max_tokens: model.max_output_tokens,
+        tools,
+        tool_choice: None,
+    }
+}
+
+pub fn map_to_language_model_completion_events(
+    events: Pin<Box<dyn Send + Stream<Item = Result<ResponseStreamEvent>>>>,
+) -> impl Stream<Item = Result<LanguageModelCompletionEvent>> {
+    #[derive(Default)]
+    struct RawToolCall {
+        id: String,
+        name: String,
+        arguments: String,
+    }
+
+    struct State {
+        events: Pin<Box<dyn Send + Stream<Item = Result<ResponseStreamEvent>>>>,
+        tool_calls_by_index: HashMap<usize, RawToolCall>,
+    }
+
+    futures::stream::unfold(
+        State {
+            events,
+            tool_calls_by_index: HashMap::default(),
+        },
+        |mut state| async move {
+            let event = match state.events.next().await? {
+                Ok(event) => event,
+                Err(error) => return Some((vec![Err(error)], state)),
+            };
+
+            let mut results = Vec::new();
+            if let Some(usage) = event.usage {
+                results.push(Ok(LanguageModelCompletionEvent::UsageUpdate(TokenUsage {
+                    input_tokens: usage.prompt_tokens,
+                    output_tokens: usage.completion_tokens,
+                    cache_creation_input_tokens: 0,
+                    cache_read_input_tokens: 0,
+                })));
+            }
+
+            for choice in event.choices {
+                if let Some(content) = choice.delta.content.filter(|content| !content.is_empty()) {
+                    results.push(Ok(LanguageModelCompletionEvent::Text(content)));
+                }
+
+                for chunk in choice.delta.tool_calls.unwrap_or_default() {
+                    let tool_call = state.tool_calls_by_index.entry(chunk.index).or_default();
+                    if let Some(id) = chunk.id {
+                        tool_call.id = id;
+                    }
+                    if let Some(function) = chunk.function {
+                        if let Some(name) = function.name {
+                            tool_call.name = name;
+                        }
+                        if let Some(arguments) = function.arguments {
+                            tool_call.arguments.push_str(&arguments);
+                        }
+                    }
+                }
+
+                match choice.finish_reason.as_deref() {
+                    Some("tool_calls") => {
+                        let mut tool_calls = state.tool_calls_by_index.drain().collect::<Vec<_>>();
+                        tool_calls.sort_by_key(|(index, _)| *index);
+                        for (_, tool_call) in tool_calls {
+                            results.push(parse_tool_call(
+                                tool_call.id,
+                                tool_call.name,
+                                &tool_call.arguments,
+                            ));
+                        }
+                        results.push(Ok(LanguageModelCompletionEvent::Stop(StopReason::ToolUse)));
+                    }
+                    Some("length") => {
+                        results.push(Ok(LanguageModelCompletionEvent::Stop(
+                            StopReason::MaxTokens,
+                        )));
+                    }
+                    Some(_) => {
+                        results.push(Ok(LanguageModelCompletionEvent::Stop(StopReason::EndTurn)));
+                    }
+                    None => {}
+                }
+            }
+
+            Some((results, state))
+        },
+    )
+    .flat_map(futures::stream::iter)
+}
+
+fn parse_tool_call(
+    id: String,
+    name: String,
+    arguments: &str,
+) -> Result<LanguageModelCompletionEvent> {
+    let input = if arguments.trim().is_empty() {
+        serde_json::Value::Object(serde_json::Map::default())
+    } else {
+        serde_json::Value::from_str(arguments).map_err(|err| anyhow!(err))?
+    };
+    Ok(LanguageModelCompletionEvent::ToolUse(
+        LanguageModelToolUse {
+            id: id.into(),
+            name: name.into(),
+            input,
+        },
+    ))
+}
+
+struct ConfigurationView {
+    state: gpui::Entity<State>,
+    loading_models_task: Option<Task<()>>,
+}
+
+impl ConfigurationView {
+    pub fn new(state: gpui::Entity<State>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let loading_models_task = Some(cx.spawn_in(window, {
+            let state = state.clone();
+            async move |this, cx| {
+                if let Some(task) = state
+                    .update(cx, |state, cx| state.authenticate(cx))
+                    .log_err()
+                {
+                    task.await.log_err();
+                }
+                this.update(cx, |this, cx| {
+                    this.loading_models_task = None;
+                    cx.notify();
+                })
+                .log_err();
+            }
+        }));
+
+        Self {
+            state,
+            loading_models_task,
+        }
+    }
+
+    fn retry_connection(&self, cx: &mut App) {
+        self.state
+            .update(cx, |state, cx| state.fetch_models(cx))
+            .detach_and_log_err(cx);
+    }
+}
+
+impl Render for ConfigurationView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_authenticated = self.state.read(cx).is_authenticated();
+        let api_url = AllLanguageModelSettings::get_global(cx)
+            .openai_compatible
+            .api_url
+            .clone();
+
+        let intro = "Use models served locally by llama.cpp, vLLM, Ollama, or any other server that implements the OpenAI chat completions API.";
+        let reqs = format!(
+            "The server must be running at {api_url}. Change `language_models.openai_compatible.api_url` in your settings to use a different address."
+        );
+        let api_key_note = format!(
+            "If the server requires an API key, set the {OPENAI_COMPATIBLE_API_KEY_VAR} environment variable before starting Zed."
+        );
+
+        if self.loading_models_task.is_some() {
+            div().child(Label::new("Loading models...")).into_any()
+        } else {
+            v_flex()
+                .size_full()
+                .gap_3()
+                .child(
+                    v_flex()
+                        .size_full()
+                        .gap_2()
+                        .p_1()
+                        .child(Label::new(intro))
+                        .child(Label::new(reqs))
+                        .child(Label::new(api_key_note).color(Color::Muted)),
+                )
+                .child(
+                    h_flex()
+                        .w_full()
+                        .pt_2()
+                        .justify_end()
+                        .gap_2()
+                        .child(if is_authenticated {
+                            // This is only a button to ensure the spacing is correct
+                            // it should stay disabled
+                            ButtonLike::new("connected")
+                                .disabled(true)
+                                // Since this won't ever be clickable, we can use the arrow cursor
+                                .cursor_style(gpui::CursorStyle::Arrow)
+                                .child(
+                                    h_flex()
+                                        .gap_2()
+                                        .child(Indicator::dot().color(Color::Success))
+                                        .child(Label::new("Connected"))
+                                        .into_any_element(),
+                                )
+                                .into_any_element()
+                        } else {
+                            Button::new("retry_openai_compatible_models", "Connect")
+                                .icon_position(IconPosition::Start)
+                                .icon(IconName::ArrowCircle)
+                                .on_click(
+                                    cx.listener(move |this, _, _, cx| this.retry_connection(cx)),
+                                )
+                                .into_any_element()
+                        }),
+                )
+                .into_any()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use language_model::{
+        LanguageModelRequestMessage, LanguageModelRequestTool, LanguageModelToolResult,
+    };
+    use serde_json::json;
+
+    fn model(supports_tools: bool) -> Model {
+        Model {
+            name: "qwen2.5-coder:7b".into(),
+            display_name: None,
+            max_tokens: DEFAULT_MAX_TOKENS,
+            max_output_tokens: Some(1024),
+            supports_tools,
+        }
+    }
+
+    fn completion_events(events: Vec<serde_json::Value>) -> Vec<LanguageModelCompletionEvent> {
+        let events = events
+            .into_iter()
+            .map(|event| Ok(serde_json::from_value::<ResponseStreamEvent>(event).unwrap()))
+            .collect::<Vec<_>>();
+        let events = map_to_language_model_completion_events(futures::stream::iter(events).boxed());
+        futures::executor::block_on(events.collect::<Vec<_>>())
+            .into_iter()
+            .map(|event| event.unwrap())
+            .collect()
+    }
+
+    fn delta_event(delta: serde_json::Value, finish_reason: Option<&str>) -> serde_json::Value {
+        json!({
+            "created": 0,
+            "model": "qwen2.5-coder:7b",
+            "choices": [{ "index": 0, "delta": delta, "finish_reason": finish_reason }],
+        })
+    }
+
+    fn tool_use(id: &str, name: &str, input: serde_json::Value) -> LanguageModelCompletionEvent {
+        LanguageModelCompletionEvent::ToolUse(LanguageModelToolUse {
+            id: id.into(),
+            name: name.into(),
+            input,
+        })
+    }
+
+    #[test]
+    fn test_text_and_usage_events() {
+        let mut last_event = delta_event(json!({ "content": "world" }), Some("stop"));
+        last_event["usage"] =
+            json!({ "prompt_tokens": 10, "completion_tokens": 2, "total_tokens": 12 });
+        assert_eq!(
+            completion_events(vec![
+                delta_event(json!({ "role": "assistant", "content": "Hello " }), None),
+                delta_event(json!({ "content": "" }), None),
+                last_event,
+            ]),
+            [
+                LanguageModelCompletionEvent::Text("Hello ".into()),
+                LanguageModelCompletionEvent::UsageUpdate(TokenUsage {
+                    input_tokens: 10,
+                    output_tokens: 2,
+                    cache_creation_input_tokens: 0,
+                    cache_read_input_tokens: 0,
+                }),
+                LanguageModelCompletionEvent::Text("world".into()),
+                LanguageModelCompletionEvent::Stop(StopReason::EndTurn),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tool_call_split_across_deltas() {
+        assert_eq!(
+            completion_events(vec![
+                delta_event(
+                    json!({ "tool_calls": [{
+                        "index": 0,
+                        "id": "call_1",
+                        "function": { "name": "read_file", "arguments": "" },
+                    }] }),
+                    None,
+                ),
+                delta_event(
+                    json!({ "tool_calls": [{
+                        "index": 0,
+                        "function": { "arguments": "{\"path\": " },
+                    }] }),
+                    None,
+                ),
+                delta_event(
+                    json!({ "tool_calls": [{
+                        "index": 0,
+                        "function": { "arguments": "\"src/main.rs\"}" },
+                    }] }),
+                    None,
+                ),
+                delta_event(json!({}), Some("tool_calls")),
+            ]),
+            [
+                tool_use("call_1", "read_file", json!({ "path": "src/main.rs" })),
+                LanguageModelCompletionEvent::Stop(StopReason::ToolUse),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interleaved_tool_call_deltas() {
+        // The deltas of both calls arrive interleaved, and the second call's first.
+        assert_eq!(
+            completion_events(vec![
+                delta_event(
+                    json!({ "tool_calls": [
+                        { "index": 1, "id": "call_2", "function": { "name": "list_directory" } },
+                        { "index": 0, "id": "call_1", "function": { "name": "read_file" } },
+                    ] }),
+                    None,
+                ),
+                delta_event(
+                    json!({ "tool_calls": [
+                        { "index": 0, "function": { "arguments": "{\"path\":" } },
+                        { "index": 1, "function": { "arguments": "{\"path\":\"src\"}" } },
+                    ] }),
+                    None,
+                ),
+                delta_event(
+                    json!({ "tool_calls": [
+                        { "index": 0, "function": { "arguments": "\"a.rs\"}" } },
+                    ] }),
+                    Some("tool_calls"),
+                ),
+            ]),
+            [
+                tool_use("call_1", "read_file", json!({ "path": "a.rs" })),
+                tool_use("call_2", "list_directory", json!({ "path": "src" })),
+                LanguageModelCompletionEvent::Stop(StopReason::ToolUse),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_tool_call() {
+        assert_eq!(
+            parse_tool_call("call_1".into(), "now".into(), "  ").unwrap(),
+            tool_use("call_1", "now", json!({}))
+        );
+        assert_eq!(
+            parse_tool_call("call_1".into(), "read_file".into(), r#"{"path":"a.rs"}"#).unwrap(),
+            tool_use("call_1", "read_file", json!({ "path": "a.rs" }))
+        );
+        assert!(parse_tool_call("call_1".into(), "read_file".into(), r#"{"path":"#).is_err());
+    }
+
+    fn request_with_tool_use() -> LanguageModelRequest {
+        LanguageModelRequest {
+            messages: vec![
+                LanguageModelRequestMessage {
+                    role: Role::System,
+                    content: vec![MessageContent::Text("You are helpful.".into())],
+                    cache: false,
+                },
+                LanguageModelRequestMessage {
+                    role: Role::User,
+                    content: vec![MessageContent::Text("Read a.rs".into())],
+                    cache: false,
+                },
+                LanguageModelRequestMessage {
+                    role: Role::Assistant,
+                    content: vec![MessageContent::ToolUse(LanguageModelToolUse {
+                        id: "call_1".into(),
+                        name: "read_file".into(),
+                        input: json!({ "path": "a.rs" }),
+                    })],
+                    cache: false,
+                },
+                LanguageModelRequestMessage {
+                    role: Role::User,
+                    content: vec![MessageContent::ToolResult(LanguageModelToolResult {
+                        tool_use_id: "call_1".into(),
+                        is_error: false,
+                        content: "fn main() {}".into(),
+                    })],
+                    cache: false,
+                },
+            ],
+            tools: vec![LanguageModelRequestTool {
+                name: "read_file".into(),
+                description: "Reads a file".into(),
+                input_schema: json!({ "type": "object" }),
+            }],
+            stop: Vec::new(),
+            temperature: None,
+        }
+    }
+
+    #[test]
+    fn test_into_open_ai_compatible() {
+        let request = into_open_ai_compatible(request_with_tool_use(), &model(true));
+        assert_eq!(request.model, "qwen2.5-coder:7b");
+        assert_eq!(request.max_tokens, Some(1024));
+        assert_eq!(request.temperature, 1.0);
+        assert_eq!(
+            request.messages,
+            [
+                RequestMessage::System {
+                    content: "You are helpful.".into(),
+                },
+                RequestMessage::User {
+                    content: "Read a.rs".into(),
+                },
+                RequestMessage::Assistant {
+                    content: None,
+                    tool_calls: vec![ToolCall {
+                        id: "call_1".into(),
+                        content: ToolCallContent::Function {
+                            function: FunctionContent {
+                                name: "read_file".into(),
+                                arguments: json!({ "path": "a.rs" }).to_string(),
+                            },
+                        },
+                    }],
+                },
+                RequestMessage::Tool {
+                    content: "fn main() {}".into(),
+                    tool_call_id: "call_1".into(),
+                },
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&request.tools).unwrap(),
+            json!([{
+                "type": "function",
+                "function": {
+                    "name": "read_file",
+                    "description": "Reads a file",
+                    "parameters": { "type": "object" },
+                },
+            }])
+        );
+    }
+
+    #[test]
+    fn test_into_open_ai_compatible_without_tool_support() {
+        // Tool uses and results are left out for models that don't support tools.
+        let request = into_open_ai_compatible(request_with_tool_use(), &model(false));
+        assert_eq!(
+            request.messages,
+            [
+                RequestMessage::System {
+                    content: "You are helpful.".into(),
+                },
+                RequestMessage::User {
+                    content: "Read a.rs".into(),
+                },
+            ]
+        );
+        assert!(request.tools.is_empty());
+    }
+}
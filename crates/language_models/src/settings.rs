@@ -20,6 +20,7 @@ use crate::provider::{
     mistral::MistralSettings,
     ollama::OllamaSettings,
     open_ai::OpenAiSettings,
+    open_ai_compatible::OpenAiCompatibleSettings,
 };
 
 /// Initializes the language model settings.
@@ -61,6 +62,7 @@ pub struct AllLanguageModelSettings {
     pub bedrock: AmazonBedrockSettings,
     pub ollama: OllamaSettings,
     pub openai: OpenAiSettings,
+    pub openai_compatible: OpenAiCompatibleSettings,
     pub zed_dot_dev: ZedDotDevSettings,
     pub google: GoogleSettings,
     pub copilot_chat: CopilotChatSettings,
@@ -75,6 +77,7 @@ pub struct AllLanguageModelSettingsContent {
     pub ollama: Option<OllamaSettingsContent>,
     pub lmstudio: Option<LmStudioSettingsContent>,
     pub openai: Option<OpenAiSettingsContent>,
+    pub openai_compatible: Option<OpenAiCompatibleSettingsContent>,
     #[serde(rename = "zed.dev")]
     pub zed_dot_dev: Option<ZedDotDevSettingsContent>,
     pub google: Option<GoogleSettingsContent>,
@@ -172,6 +175,12 @@ pub struct LmStudioSettingsContent {
     pub available_models: Option<Vec<provider::lmstudio::AvailableModel>>,
 }
 
+#[derive(Default, Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct OpenAiCompatibleSettingsContent {
+    pub api_url: Option<String>,
+    pub available_models: Option<Vec<provider::open_ai_compatible::AvailableModel>>,
+}
+
 #[derive(Default, Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct DeepseekSettingsContent {
     pub api_url: Option<String>,
@@ -321,6 +330,20 @@ impl settings::Settings for AllLanguageModelSettings {
                 lmstudio.as_ref().and_then(|s| s.available_models.clone()),
             );
 
+            // OpenAI Compatible
+            let openai_compatible = value.openai_compatible.clone();
+
+            merge(
+                &mut settings.openai_compatible.api_url,
+                openai_compatible.as_ref().and_then(|s| s.api_url.clone()),
+            );
+            merge(
+                &mut settings.openai_compatible.available_models,
+                openai_compatible
+                    .as_ref()
+                    .and_then(|s| s.available_models.clone()),
+            );
+
             // DeepSeek
             let deepseek = value.deepseek.clone();
 
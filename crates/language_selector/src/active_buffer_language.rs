@@ -1,6 +1,7 @@
 use editor::Editor;
 use gpui::{
-    div, Context, Entity, IntoElement, ParentElement, Render, Subscription, WeakEntity, Window,
+    div, App, Context, Entity, IntoElement, ParentElement, Render, SharedString, Subscription,
+    WeakEntity, Window,
 };
 use language::LanguageName;
 use ui::{Button, ButtonCommon, Clickable, FluentBuilder, LabelSize, Tooltip};
@@ -82,4 +83,8 @@ impl StatusItemView for ActiveBufferLanguage {
 
         cx.notify();
     }
+
+    fn segment_name(&self, _: &App) -> Option<SharedString> {
+        Some("language".into())
+    }
 }
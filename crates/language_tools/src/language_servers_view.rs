@@ -0,0 +1,335 @@
+use collections::HashMap;
+use gpui::{actions, Entity, EventEmitter, FocusHandle, Focusable, Subscription, Task, WeakEntity};
+use language::LanguageServerId;
+use lsp::LanguageServerName;
+use project::Project;
+use std::time::Duration;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use ui::{prelude::*, Divider, Tooltip};
+use workspace::{item::Item, SplitDirection, Workspace};
+
+use crate::lsp_log::{LogStore, LspLogView};
+
+const RESOURCE_USAGE_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+actions!(debug, [OpenLanguageServers]);
+
+pub(crate) fn init(log_store: Entity<LogStore>, cx: &mut App) {
+    cx.observe_new(move |workspace: &mut Workspace, _, _| {
+        let log_store = log_store.clone();
+        workspace.register_action(move |workspace, _: &OpenLanguageServers, window, cx| {
+            let project = workspace.project().clone();
+            let workspace_handle = workspace.weak_handle();
+            let log_store = log_store.clone();
+            let view = cx.new(|cx| {
+                LanguageServersView::new(project, workspace_handle, log_store, window, cx)
+            });
+            workspace.split_item(SplitDirection::Right, Box::new(view), window, cx);
+        });
+    })
+    .detach();
+}
+
+/// Lists the language servers running for a project and allows managing them individually.
+pub struct LanguageServersView {
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    log_store: Entity<LogStore>,
+    focus_handle: FocusHandle,
+    system: System,
+    resource_usage: HashMap<LanguageServerId, ResourceUsage>,
+    _refresh_resource_usage: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ResourceUsage {
+    pid: u32,
+    memory: u64,
+    cpu_usage: f32,
+}
+
+struct ServerRow {
+    id: LanguageServerId,
+    name: LanguageServerName,
+    worktree_root_name: Option<SharedString>,
+    status: SharedString,
+    is_busy: bool,
+}
+
+impl LanguageServersView {
+    pub fn new(
+        project: Entity<Project>,
+        workspace: WeakEntity<Workspace>,
+        log_store: Entity<LogStore>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let lsp_store = project.read(cx).lsp_store();
+        let subscriptions = vec![
+            cx.observe(&lsp_store, |_, _, cx| cx.notify()),
+            cx.observe(&log_store, |_, _, cx| cx.notify()),
+        ];
+
+        let refresh_resource_usage = cx.spawn(async move |this, cx| loop {
+            let Ok(()) = this.update(cx, |this, cx| this.refresh_resource_usage(cx)) else {
+                break;
+            };
+            cx.background_executor()
+                .timer(RESOURCE_USAGE_REFRESH_INTERVAL)
+                .await;
+        });
+
+        Self {
+            project,
+            workspace,
+            log_store,
+            focus_handle: cx.focus_handle(),
+            system: System::new(),
+            resource_usage: HashMap::default(),
+            _refresh_resource_usage: refresh_resource_usage,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn refresh_resource_usage(&mut self, cx: &mut Context<Self>) {
+        let lsp_store = self.project.read(cx).lsp_store();
+        let lsp_store = lsp_store.read(cx);
+        let pids = lsp_store
+            .language_server_statuses()
+            .filter_map(|(id, _)| {
+                let pid = lsp_store.language_server_for_id(id)?.process_id()?;
+                Some((id, pid))
+            })
+            .collect::<Vec<_>>();
+
+        let sysinfo_pids = pids
+            .iter()
+            .map(|(_, pid)| Pid::from_u32(*pid))
+            .collect::<Vec<_>>();
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&sysinfo_pids),
+            ProcessRefreshKind::new().with_cpu().with_memory(),
+        );
+
+        self.resource_usage = pids
+            .into_iter()
+            .filter_map(|(id, pid)| {
+                let process = self.system.process(Pid::from_u32(pid))?;
+                Some((
+                    id,
+                    ResourceUsage {
+                        pid,
+                        memory: process.memory(),
+                        cpu_usage: process.cpu_usage(),
+                    },
+                ))
+            })
+            .collect();
+        cx.notify();
+    }
+
+    fn rows(&self, cx: &App) -> Vec<ServerRow> {
+        let project = self.project.read(cx);
+        let log_store = self.log_store.read(cx);
+        let mut rows = project
+            .lsp_store()
+            .read(cx)
+            .language_server_statuses()
+            .map(|(id, status)| {
+                let worktree_root_name = log_store
+                    .server_worktree_id(id)
+                    .and_then(|worktree_id| project.worktree_for_id(worktree_id, cx))
+                    .map(|worktree| SharedString::from(worktree.read(cx).root_name().to_string()));
+                let pending_work = status.pending_work.values().next();
+                let is_busy = pending_work.is_some() || status.has_pending_diagnostic_updates;
+                let status_text = if let Some(progress) = pending_work {
+                    match &progress.title {
+                        Some(title) => format!("Busy: {title}"),
+                        None => "Busy".to_string(),
+                    }
+                } else if status.has_pending_diagnostic_updates {
+                    "Updating diagnostics".to_string()
+                } else {
+                    "Running".to_string()
+                };
+                ServerRow {
+                    id,
+                    name: status.name.as_str().into(),
+                    worktree_root_name,
+                    status: status_text.into(),
+                    is_busy,
+                }
+            })
+            .collect::<Vec<_>>();
+        rows.sort_by(|a, b| a.name.0.cmp(&b.name.0).then(a.id.cmp(&b.id)));
+        rows
+    }
+
+    fn open_logs(
+        &mut self,
+        server_id: LanguageServerId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let project = self.project.clone();
+        let log_store = self.log_store.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let log_view = cx.new(|cx| {
+                    let mut log_view = LspLogView::new(project, log_store, window, cx);
+                    log_view.show_logs_for_server(server_id, window, cx);
+                    log_view
+                });
+                workspace.split_item(SplitDirection::Right, Box::new(log_view), window, cx);
+            })
+            .ok();
+    }
+
+    fn restart_server(&mut self, server_id: LanguageServerId, cx: &mut Context<Self>) {
+        self.project.update(cx, |project, cx| {
+            project.restart_language_servers(vec![server_id], cx);
+        });
+    }
+
+    fn stop_server(&mut self, server_id: LanguageServerId, cx: &mut Context<Self>) {
+        self.resource_usage.remove(&server_id);
+        self.project.update(cx, |project, cx| {
+            project.stop_language_servers(vec![server_id], cx);
+        });
+    }
+
+    fn render_row(&self, row: ServerRow, cx: &mut Context<Self>) -> impl IntoElement {
+        let server_id = row.id;
+        let usage = self.resource_usage.get(&server_id).copied();
+        let title = match &row.worktree_root_name {
+            Some(worktree_root_name) => format!("{} ({})", row.name.0, worktree_root_name),
+            None => row.name.0.to_string(),
+        };
+
+        h_flex()
+            .w_full()
+            .py_1()
+            .gap_4()
+            .justify_between()
+            .child(
+                v_flex().child(Label::new(title)).child(
+                    h_flex()
+                        .gap_2()
+                        .child(Label::new(row.status).size(LabelSize::Small).color(
+                            if row.is_busy {
+                                Color::Warning
+                            } else {
+                                Color::Success
+                            },
+                        ))
+                        .children(usage.map(|usage| {
+                            Label::new(format!(
+                                "PID {} · {:.1} MB · {:.1}% CPU",
+                                usage.pid,
+                                usage.memory as f64 / 1024.0 / 1024.0,
+                                usage.cpu_usage,
+                            ))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                        })),
+                ),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new(("logs", server_id.0), "Logs")
+                            .style(ButtonStyle::Subtle)
+                            .tooltip(Tooltip::text("Open server logs, including stderr"))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.open_logs(server_id, window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new(("restart", server_id.0), "Restart")
+                            .style(ButtonStyle::Subtle)
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.restart_server(server_id, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new(("stop", server_id.0), "Stop")
+                            .style(ButtonStyle::Subtle)
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.stop_server(server_id, cx);
+                            })),
+                    ),
+            )
+    }
+}
+
+impl Render for LanguageServersView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let rows = self.rows(cx);
+        let is_empty = rows.is_empty();
+        v_flex()
+            .id("language-servers-view")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_4()
+            .gap_2()
+            .overflow_y_scroll()
+            .bg(cx.theme().colors().editor_background)
+            .child(Label::new("Language Servers").size(LabelSize::Large))
+            .child(Divider::horizontal())
+            .when(is_empty, |this| {
+                this.child(
+                    Label::new("No language servers are running for this project.")
+                        .color(Color::Muted),
+                )
+            })
+            .children(
+                rows.into_iter()
+                    .map(|row| self.render_row(row, cx).into_any_element())
+                    .collect::<Vec<_>>(),
+            )
+    }
+}
+
+impl Focusable for LanguageServersView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<()> for LanguageServersView {}
+
+impl Item for LanguageServersView {
+    type Event = ();
+
+    fn to_item_events(_: &Self::Event, _: impl FnMut(workspace::item::ItemEvent)) {}
+
+    fn tab_content_text(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some("Language Servers".into())
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<workspace::WorkspaceId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<Self>>
+    where
+        Self: Sized,
+    {
+        Some(cx.new(|cx| {
+            Self::new(
+                self.project.clone(),
+                self.workspace.clone(),
+                self.log_store.clone(),
+                window,
+                cx,
+            )
+        }))
+    }
+}
@@ -1,5 +1,7 @@
 mod key_context_view;
+mod language_servers_view;
 mod lsp_log;
+mod prettier_eslint_info;
 mod syntax_tree_view;
 
 #[cfg(test)]
@@ -7,11 +9,14 @@ mod lsp_log_tests;
 
 use gpui::App;
 
+pub use language_servers_view::{LanguageServersView, OpenLanguageServers};
 pub use lsp_log::{LogStore, LspLogToolbarItemView, LspLogView};
 pub use syntax_tree_view::{SyntaxTreeToolbarItemView, SyntaxTreeView};
 
 pub fn init(cx: &mut App) {
-    lsp_log::init(cx);
+    let log_store = lsp_log::init(cx);
+    language_servers_view::init(log_store, cx);
     syntax_tree_view::init(cx);
     key_context_view::init(cx);
+    prettier_eslint_info::init(cx);
 }
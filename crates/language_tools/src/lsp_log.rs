@@ -3,8 +3,8 @@ use copilot::Copilot;
 use editor::{actions::MoveToEnd, scroll::Autoscroll, Editor, EditorEvent};
 use futures::{channel::mpsc, StreamExt};
 use gpui::{
-    actions, div, AnyView, App, Context, Corner, Entity, EventEmitter, FocusHandle, Focusable,
-    IntoElement, ParentElement, Render, Styled, Subscription, WeakEntity, Window,
+    div, AnyView, App, Context, Corner, Entity, EventEmitter, FocusHandle, Focusable, IntoElement,
+    ParentElement, Render, Styled, Subscription, WeakEntity, Window,
 };
 use language::{language_settings::SoftWrap, LanguageServerId};
 use lsp::{
@@ -19,6 +19,7 @@ use workspace::{
     searchable::{Direction, SearchEvent, SearchableItem, SearchableItemHandle},
     SplitDirection, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView, Workspace, WorkspaceId,
 };
+use zed_actions::debug::OpenLanguageServerLogs;
 
 const SEND_LINE: &str = "// Send:";
 const RECEIVE_LINE: &str = "// Receive:";
@@ -201,35 +202,43 @@ pub(crate) struct LogMenuItem {
     pub server_kind: LanguageServerKind,
 }
 
-actions!(debug, [OpenLanguageServerLogs]);
-
-pub fn init(cx: &mut App) {
+pub fn init(cx: &mut App) -> Entity<LogStore> {
     let log_store = cx.new(LogStore::new);
 
-    cx.observe_new(move |workspace: &mut Workspace, _, cx| {
-        let project = workspace.project();
-        if project.read(cx).is_local() || project.read(cx).is_via_ssh() {
-            log_store.update(cx, |store, cx| {
-                store.add_project(project, cx);
-            });
-        }
-
+    cx.observe_new({
         let log_store = log_store.clone();
-        workspace.register_action(move |workspace, _: &OpenLanguageServerLogs, window, cx| {
-            let project = workspace.project().read(cx);
-            if project.is_local() || project.is_via_ssh() {
-                workspace.split_item(
-                    SplitDirection::Right,
-                    Box::new(cx.new(|cx| {
-                        LspLogView::new(workspace.project().clone(), log_store.clone(), window, cx)
-                    })),
-                    window,
-                    cx,
-                );
+        move |workspace: &mut Workspace, _, cx| {
+            let project = workspace.project();
+            if project.read(cx).is_local() || project.read(cx).is_via_ssh() {
+                log_store.update(cx, |store, cx| {
+                    store.add_project(project, cx);
+                });
             }
-        });
+
+            let log_store = log_store.clone();
+            workspace.register_action(move |workspace, _: &OpenLanguageServerLogs, window, cx| {
+                let project = workspace.project().read(cx);
+                if project.is_local() || project.is_via_ssh() {
+                    workspace.split_item(
+                        SplitDirection::Right,
+                        Box::new(cx.new(|cx| {
+                            LspLogView::new(
+                                workspace.project().clone(),
+                                log_store.clone(),
+                                window,
+                                cx,
+                            )
+                        })),
+                        window,
+                        cx,
+                    );
+                }
+            });
+        }
     })
     .detach();
+
+    log_store
 }
 
 impl LogStore {
@@ -480,6 +489,10 @@ impl LogStore {
         cx.notify();
     }
 
+    pub(crate) fn server_worktree_id(&self, server_id: LanguageServerId) -> Option<WorktreeId> {
+        self.language_servers.get(&server_id)?.worktree_id
+    }
+
     fn server_logs(&self, server_id: LanguageServerId) -> Option<&VecDeque<LogMessage>> {
         Some(&self.language_servers.get(&server_id)?.log_messages)
     }
@@ -834,7 +847,7 @@ impl LspLogView {
         Some(rows)
     }
 
-    fn show_logs_for_server(
+    pub(crate) fn show_logs_for_server(
         &mut self,
         server_id: LanguageServerId,
         window: &mut Window,
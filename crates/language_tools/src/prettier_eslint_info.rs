@@ -0,0 +1,279 @@
+use std::{
+    fmt::Write as _,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context as _;
+use editor::{Editor, MultiBuffer};
+use fs::Fs;
+use gpui::{actions, App, AppContext as _, Context, Window};
+use project::prettier_store::PrettierResolution;
+use util::ResultExt as _;
+use workspace::{notifications::NotifyTaskExt as _, Workspace};
+
+actions!(debug, [OpenPrettierAndEslintInfo]);
+
+const ESLINT_FLAT_CONFIG_FILE_NAMES: &[&str] = &[
+    "eslint.config.js",
+    "eslint.config.mjs",
+    "eslint.config.cjs",
+    "eslint.config.ts",
+    "eslint.config.mts",
+    "eslint.config.cts",
+];
+
+const ESLINT_LEGACY_CONFIG_FILE_NAMES: &[&str] = &[
+    ".eslintrc.js",
+    ".eslintrc.cjs",
+    ".eslintrc.yaml",
+    ".eslintrc.yml",
+    ".eslintrc.json",
+    ".eslintrc",
+];
+
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(open_prettier_and_eslint_info);
+    })
+    .detach();
+}
+
+/// Opens a report of which Prettier and ESLint installations and configuration files apply to
+/// the active editor's file, to troubleshoot formatting and linting in monorepos.
+fn open_prettier_and_eslint_info(
+    workspace: &mut Workspace,
+    _: &OpenPrettierAndEslintInfo,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(buffer) = workspace
+        .active_item_as::<Editor>(cx)
+        .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+    else {
+        return;
+    };
+    let Some(file_path) = buffer
+        .read(cx)
+        .file()
+        .and_then(|file| file.as_local())
+        .map(|file| file.abs_path(cx))
+    else {
+        return;
+    };
+
+    let project = workspace.project().clone();
+    let fs = project.read(cx).fs().clone();
+    let prettier = project
+        .read(cx)
+        .lsp_store()
+        .read(cx)
+        .prettier_store()
+        .map(|prettier_store| {
+            prettier_store.update(cx, |prettier_store, cx| {
+                prettier_store.prettier_for_buffer(&buffer, cx)
+            })
+        });
+    let markdown = workspace
+        .app_state()
+        .languages
+        .language_for_name("Markdown");
+
+    let task = cx.spawn_in(window, async move |workspace, cx| {
+        let prettier = match prettier {
+            Some(prettier) => Some(prettier.await),
+            None => None,
+        };
+        let eslint = locate_eslint(fs.as_ref(), &file_path).await?;
+        let report = report(&file_path, prettier, eslint);
+        let markdown = markdown.await.log_err();
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let title = "Prettier and ESLint";
+            let buffer = project.update(cx, |project, cx| {
+                project.create_local_buffer(&report, markdown, cx)
+            });
+            let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx).with_title(title.into()));
+            workspace.add_item_to_active_pane(
+                Box::new(cx.new(|cx| {
+                    let mut editor =
+                        Editor::for_multibuffer(buffer, Some(project.clone()), window, cx);
+                    editor.set_read_only(true);
+                    editor.set_breadcrumb_header(title.into());
+                    editor
+                })),
+                None,
+                true,
+                window,
+                cx,
+            );
+        })
+    });
+    task.detach_and_notify_err(window, cx);
+}
+
+struct EslintResolution {
+    /// The `eslint` package that the ESLint language server loads, and its version.
+    installation: Option<(PathBuf, String)>,
+    /// The nearest configuration file, which ESLint uses along with the ones it cascades to.
+    config_file: Option<EslintConfigFile>,
+}
+
+enum EslintConfigFile {
+    Flat(PathBuf),
+    Legacy(PathBuf),
+    PackageJson(PathBuf),
+}
+
+/// Finds the ESLint installation and configuration for a file in the same way ESLint does,
+/// walking up from the file's directory.
+async fn locate_eslint(fs: &dyn Fs, file_path: &Path) -> anyhow::Result<EslintResolution> {
+    let mut installation = None;
+    let mut config_file = None;
+    for dir in file_path.ancestors().skip(1) {
+        if installation.is_none() {
+            let package_json_path = dir.join("node_modules/eslint/package.json");
+            if fs.is_file(&package_json_path).await {
+                let package_json = fs.load(&package_json_path).await?;
+                let version = serde_json::from_str::<serde_json::Value>(&package_json)
+                    .with_context(|| format!("parsing {package_json_path:?}"))?
+                    .get("version")
+                    .and_then(|version| version.as_str())
+                    .unwrap_or("unknown")
+                    .to_string();
+                installation = Some((dir.join("node_modules/eslint"), version));
+            }
+        }
+
+        if config_file.is_none() {
+            config_file = eslint_config_file_in(fs, dir).await;
+        }
+
+        if installation.is_some() && config_file.is_some() {
+            break;
+        }
+    }
+    Ok(EslintResolution {
+        installation,
+        config_file,
+    })
+}
+
+async fn eslint_config_file_in(fs: &dyn Fs, dir: &Path) -> Option<EslintConfigFile> {
+    for file_name in ESLINT_FLAT_CONFIG_FILE_NAMES {
+        let path = dir.join(file_name);
+        if fs.is_file(&path).await {
+            return Some(EslintConfigFile::Flat(path));
+        }
+    }
+    for file_name in ESLINT_LEGACY_CONFIG_FILE_NAMES {
+        let path = dir.join(file_name);
+        if fs.is_file(&path).await {
+            return Some(EslintConfigFile::Legacy(path));
+        }
+    }
+    let package_json_path = dir.join("package.json");
+    let package_json = fs.load(&package_json_path).await.ok()?;
+    serde_json::from_str::<serde_json::Value>(&package_json)
+        .ok()?
+        .get("eslintConfig")
+        .map(|_| EslintConfigFile::PackageJson(package_json_path))
+}
+
+fn report(
+    file_path: &Path,
+    prettier: Option<anyhow::Result<Option<PrettierResolution>>>,
+    eslint: EslintResolution,
+) -> String {
+    let mut report = String::new();
+    writeln!(
+        report,
+        "# Prettier and ESLint for `{}`\n",
+        file_path.display()
+    )
+    .ok();
+
+    report.push_str("## Prettier\n\n");
+    match prettier {
+        None => report.push_str("Prettier can only be inspected in local projects.\n"),
+        Some(Err(error)) => {
+            writeln!(report, "Failed to start Prettier: {error:#}").ok();
+        }
+        Some(Ok(None)) => report.push_str("Prettier doesn't format this file.\n"),
+        Some(Ok(Some(prettier))) => {
+            match &prettier.prettier_dir {
+                Some(dir) => writeln!(
+                    report,
+                    "- Binary: `{}` (from the project)",
+                    dir.join("node_modules/prettier").display()
+                ),
+                None => writeln!(
+                    report,
+                    "- Binary: `{}` (Zed's default, as the project doesn't install Prettier)",
+                    paths::default_prettier_dir()
+                        .join("node_modules/prettier")
+                        .display()
+                ),
+            }
+            .ok();
+            writeln!(report, "- Version: {}", prettier.file_info.version).ok();
+            match &prettier.file_info.config_file {
+                Some(config_file) => {
+                    writeln!(report, "- Config: `{}`", config_file.display()).ok();
+                }
+                None => report.push_str(
+                    "- Config: none, so the `prettier` options in Zed's settings are used\n",
+                ),
+            }
+            if !prettier.file_info.config_plugins.is_empty() {
+                report.push_str("- Plugins from the config:\n");
+                for plugin in &prettier.file_info.config_plugins {
+                    writeln!(report, "  - `{plugin}`").ok();
+                }
+            }
+            match &prettier.ignore_dir {
+                Some(ignore_dir) => writeln!(
+                    report,
+                    "- Ignore file: `{}` ({})",
+                    ignore_dir.join(".prettierignore").display(),
+                    if prettier.file_info.ignored {
+                        "this file is ignored"
+                    } else {
+                        "this file isn't ignored"
+                    }
+                ),
+                None => writeln!(report, "- Ignore file: none"),
+            }
+            .ok();
+        }
+    }
+
+    report.push_str("\n## ESLint\n\n");
+    match &eslint.installation {
+        Some((path, version)) => {
+            writeln!(report, "- Binary: `{}`", path.display()).ok();
+            writeln!(report, "- Version: {version}").ok();
+        }
+        None => report.push_str(
+            "- Binary: none, as ESLint isn't installed in the project or its workspace root\n",
+        ),
+    }
+    match &eslint.config_file {
+        Some(EslintConfigFile::Flat(path)) => {
+            writeln!(report, "- Config: `{}` (flat config)", path.display())
+        }
+        Some(EslintConfigFile::Legacy(path)) => writeln!(
+            report,
+            "- Config: `{}` (eslintrc, which cascades to the configs in parent directories)",
+            path.display()
+        ),
+        Some(EslintConfigFile::PackageJson(path)) => writeln!(
+            report,
+            "- Config: the `eslintConfig` field of `{}`",
+            path.display()
+        ),
+        None => writeln!(report, "- Config: none"),
+    }
+    .ok();
+
+    report
+}
@@ -0,0 +1,261 @@
+//! Translates UI strings, like the labels of menus and buttons, into the user's language.
+//!
+//! Strings are looked up by their English text in locale files, which are JSON objects that map
+//! English strings to their translations. Zed bundles locale files in `assets/locales`, named
+//! after their locales like `de.json`, and extensions can provide more. Strings that haven't
+//! been translated are shown in English.
+
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use extension::{ExtensionHostProxy, ExtensionLocaleProxy};
+use gpui::{App, AssetSource, Global, SharedString};
+use parking_lot::RwLock;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources, SettingsStore};
+use util::ResultExt as _;
+
+const BUNDLED_LOCALES_DIR: &str = "locales/";
+const SYSTEM_LANGUAGE: &str = "system";
+
+pub fn init(cx: &mut App) {
+    LocalizationSettings::register(cx);
+
+    let translations = Arc::new(Translations::default());
+    translations.load_bundled_locales(cx.asset_source().as_ref());
+    ExtensionHostProxy::default_global(cx).register_locale_proxy(LocaleProxy {
+        translations: translations.clone(),
+    });
+    cx.set_global(Localization {
+        translations,
+        active_locales: Vec::new(),
+    });
+
+    update_active_locales(cx);
+    cx.observe_global::<SettingsStore>(update_active_locales)
+        .detach();
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct LocalizationSettings {
+    /// The language to show the UI in, like "de" or "pt-BR", or "system" to use the system's
+    /// language. Strings that haven't been translated into it are shown in English.
+    ///
+    /// Default: "system"
+    #[serde(default)]
+    pub ui_language: String,
+}
+
+impl Settings for LocalizationSettings {
+    const KEY: Option<&'static str> = None;
+
+    type FileContent = Self;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        sources.json_merge()
+    }
+}
+
+/// Translates the given English UI string into the user's language.
+pub fn localize(text: &'static str, cx: &App) -> SharedString {
+    cx.try_global::<Localization>()
+        .and_then(|localization| localization.translate(text))
+        .unwrap_or(SharedString::new_static(text))
+}
+
+/// Translates the given English UI string into the user's language, then replaces each
+/// `{name}` placeholder in it with the value of the argument of that name.
+pub fn localize_with(text: &'static str, args: &[(&str, &str)], cx: &App) -> SharedString {
+    let mut localized = localize(text, cx).to_string();
+    for (name, value) in args {
+        localized = localized.replace(&format!("{{{name}}}"), value);
+    }
+    localized.into()
+}
+
+/// The translations of UI strings, and the locales they're currently shown in.
+///
+/// Observe this global to update UI that isn't re-rendered when the language changes, like the
+/// application menus.
+pub struct Localization {
+    translations: Arc<Translations>,
+    /// The locales to look translations up in, from the most specific, like "pt-BR", to the
+    /// least, like "pt".
+    active_locales: Vec<SharedString>,
+}
+
+impl Global for Localization {}
+
+impl Localization {
+    /// Returns the locales that UI strings are translated into, from the most specific to the
+    /// least. This is empty when the UI is shown in English.
+    pub fn active_locales(&self) -> &[SharedString] {
+        &self.active_locales
+    }
+
+    fn translate(&self, text: &str) -> Option<SharedString> {
+        let translations = self.translations.0.read();
+        self.active_locales.iter().find_map(|locale| {
+            translations
+                .get(locale)
+                .and_then(|locale_translations| locale_translations.get(text))
+                .cloned()
+        })
+    }
+}
+
+/// Translations by locale, and then by the English strings they translate.
+#[derive(Default)]
+struct Translations(RwLock<HashMap<SharedString, HashMap<SharedString, SharedString>>>);
+
+impl Translations {
+    fn load_bundled_locales(&self, assets: &dyn AssetSource) {
+        let Some(paths) = assets.list(BUNDLED_LOCALES_DIR).log_err() else {
+            return;
+        };
+        for path in paths {
+            let Some(locale) = path
+                .strip_prefix(BUNDLED_LOCALES_DIR)
+                .and_then(|file_name| file_name.strip_suffix(".json"))
+            else {
+                continue;
+            };
+            let Some(Some(contents)) = assets.load(&path).log_err() else {
+                continue;
+            };
+            let Some(contents) = std::str::from_utf8(&contents).log_err() else {
+                continue;
+            };
+            self.register_locale(locale, contents).log_err();
+        }
+    }
+
+    /// Adds the translations in a locale file to the ones of its locale, replacing existing
+    /// translations of the same strings.
+    fn register_locale(&self, locale: &str, contents: &str) -> Result<()> {
+        let locale_translations: HashMap<SharedString, SharedString> =
+            serde_json::from_str(contents)
+                .with_context(|| format!("failed to parse translations for locale {locale}"))?;
+        self.0
+            .write()
+            .entry(normalize_locale(locale).into())
+            .or_default()
+            .extend(locale_translations);
+        Ok(())
+    }
+}
+
+struct LocaleProxy {
+    translations: Arc<Translations>,
+}
+
+impl ExtensionLocaleProxy for LocaleProxy {
+    fn register_locale(&self, locale: &str, locale_contents: &str) -> Result<()> {
+        self.translations.register_locale(locale, locale_contents)
+    }
+}
+
+fn update_active_locales(cx: &mut App) {
+    let ui_language = &LocalizationSettings::get_global(cx).ui_language;
+    let locale = if ui_language.is_empty() || ui_language == SYSTEM_LANGUAGE {
+        sys_locale::get_locale().unwrap_or_default()
+    } else {
+        ui_language.clone()
+    };
+    let active_locales = fallback_locales(&locale);
+
+    if cx.global::<Localization>().active_locales != active_locales {
+        log::info!("showing the UI in locale {locale:?}");
+        cx.global_mut::<Localization>().active_locales = active_locales;
+        cx.refresh_windows();
+    }
+}
+
+/// Returns the locales to look translations up in for the given locale, from the most specific
+/// to the least, or none for English, which UI strings are written in.
+fn fallback_locales(locale: &str) -> Vec<SharedString> {
+    let locale = normalize_locale(locale);
+    let language = locale.split('-').next().unwrap_or_default();
+    if language.is_empty() || language == "en" {
+        return Vec::new();
+    }
+
+    let mut locales = vec![SharedString::from(locale.clone())];
+    if language != locale {
+        locales.push(language.to_string().into());
+    }
+    locales
+}
+
+/// Normalizes locales like "pt_BR.UTF-8" to "pt-BR".
+fn normalize_locale(locale: &str) -> String {
+    let locale = locale.split(['.', '@']).next().unwrap_or_default();
+    let mut parts = locale.split(['-', '_']);
+    let language = parts.next().unwrap_or_default().to_lowercase();
+    let region = parts.next().map(|region| region.to_uppercase());
+    match region {
+        Some(region) => format!("{language}-{region}"),
+        None => language,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_locales() {
+        assert_eq!(fallback_locales("pt_BR.UTF-8"), ["pt-BR", "pt"]);
+        assert_eq!(fallback_locales("de"), ["de"]);
+        assert_eq!(fallback_locales("en-US"), Vec::<SharedString>::new());
+        assert_eq!(fallback_locales(""), Vec::<SharedString>::new());
+    }
+
+    #[gpui::test]
+    fn test_localize(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            init(cx);
+
+            let translations = cx.global::<Localization>().translations.clone();
+            translations
+                .register_locale(
+                    "de",
+                    r#"{"Accept": "Annehmen", "Call from {name}": "Anruf von {name}"}"#,
+                )
+                .unwrap();
+            translations
+                .register_locale("de_AT", r#"{"Accept": "Annehma"}"#)
+                .unwrap();
+        });
+
+        set_ui_language("en", cx);
+        cx.update(|cx| {
+            assert!(cx.global::<Localization>().active_locales().is_empty());
+            assert_eq!(localize("Accept", cx), "Accept");
+        });
+
+        set_ui_language("de-AT", cx);
+        cx.update(|cx| {
+            assert_eq!(localize("Accept", cx), "Annehma");
+            assert_eq!(
+                localize_with("Call from {name}", &[("name", "nathan")], cx),
+                "Anruf von nathan"
+            );
+            assert_eq!(localize("Decline", cx), "Decline");
+        });
+    }
+
+    fn set_ui_language(ui_language: &str, cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings::<LocalizationSettings>(cx, |settings| {
+                    settings.ui_language = ui_language.into();
+                });
+            });
+        });
+    }
+}
@@ -1040,6 +1040,11 @@ impl LanguageServer {
         &self.process_name
     }
 
+    /// Get the OS process id of the running language server, if it was spawned as a child process.
+    pub fn process_id(&self) -> Option<u32> {
+        self.server.lock().as_ref().map(|child| child.id())
+    }
+
     /// Get the reported capabilities of the running language server.
     pub fn capabilities(&self) -> ServerCapabilities {
         self.capabilities.read().clone()
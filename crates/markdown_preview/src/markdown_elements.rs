@@ -2,6 +2,8 @@ use gpui::{
     px, FontStyle, FontWeight, HighlightStyle, SharedString, StrikethroughStyle, UnderlineStyle,
 };
 use language::HighlightId;
+
+use crate::markdown_mermaid::{DiagramImageCache, Flowchart};
 use std::{fmt::Display, ops::Range, path::PathBuf};
 
 #[derive(Debug)]
@@ -12,6 +14,7 @@ pub enum ParsedMarkdownElement {
     Table(ParsedMarkdownTable),
     BlockQuote(ParsedMarkdownBlockQuote),
     CodeBlock(ParsedMarkdownCodeBlock),
+    MermaidDiagram(ParsedMarkdownMermaidDiagram),
     /// A paragraph of text and other inline elements.
     Paragraph(MarkdownParagraph),
     HorizontalRule(Range<usize>),
@@ -25,9 +28,11 @@ impl ParsedMarkdownElement {
             Self::Table(table) => table.source_range.clone(),
             Self::BlockQuote(block_quote) => block_quote.source_range.clone(),
             Self::CodeBlock(code_block) => code_block.source_range.clone(),
+            Self::MermaidDiagram(diagram) => diagram.source_range.clone(),
             Self::Paragraph(text) => match text.get(0)? {
                 MarkdownParagraphChunk::Text(t) => t.source_range.clone(),
                 MarkdownParagraphChunk::Image(image) => image.source_range.clone(),
+                MarkdownParagraphChunk::DisplayMath(math) => math.source_range.clone(),
             },
             Self::HorizontalRule(range) => range.clone(),
         })
@@ -45,6 +50,8 @@ pub type MarkdownParagraph = Vec<MarkdownParagraphChunk>;
 pub enum MarkdownParagraphChunk {
     Text(ParsedMarkdownText),
     Image(Image),
+    /// A `$$...$$` formula, shown on a line of its own.
+    DisplayMath(ParsedMarkdownMath),
 }
 
 #[derive(Debug)]
@@ -80,6 +87,25 @@ pub struct ParsedMarkdownCodeBlock {
     pub highlights: Option<Vec<(Range<usize>, HighlightId)>>,
 }
 
+#[derive(Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct ParsedMarkdownMermaidDiagram {
+    pub source_range: Range<usize>,
+    pub contents: SharedString,
+    /// The diagram, or why it couldn't be parsed.
+    pub flowchart: Result<Flowchart, SharedString>,
+    pub image_cache: DiagramImageCache,
+}
+
+#[derive(Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct ParsedMarkdownMath {
+    pub source_range: Range<usize>,
+    pub contents: SharedString,
+    /// The formula as Unicode text, or why it couldn't be rendered.
+    pub rendered: Result<SharedString, SharedString>,
+}
+
 #[derive(Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub struct ParsedMarkdownHeading {
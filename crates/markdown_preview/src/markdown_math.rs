@@ -0,0 +1,497 @@
+//! Renders LaTeX math as plain Unicode text, which is enough for the formulas
+//! that usually show up in design documents: Greek letters, operators,
+//! fractions, roots, and sub- and superscripts.
+
+use anyhow::{anyhow, Result};
+
+/// Converts the LaTeX source of a `$...$` or `$$...$$` block into Unicode text.
+/// Fails on commands and environments that have no textual equivalent.
+pub fn render_latex(source: &str) -> Result<String> {
+    let mut parser = LatexParser {
+        chars: source.chars().collect(),
+        ix: 0,
+    };
+    let rendered = parser.parse_sequence(false)?;
+    Ok(rendered.trim().to_string())
+}
+
+/// Commands for functions, which are typeset as their names.
+const FUNCTION_NAMES: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan", "sinh", "cosh", "tanh",
+    "log", "ln", "lg", "exp", "lim", "max", "min", "sup", "inf", "det", "gcd", "deg", "dim", "ker",
+    "arg", "mod",
+];
+
+struct LatexParser {
+    chars: Vec<char>,
+    ix: usize,
+}
+
+impl LatexParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.ix).copied()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let next = self.peek();
+        self.ix += 1;
+        next
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().map_or(false, char::is_whitespace) {
+            self.ix += 1;
+        }
+    }
+
+    /// Parses until the end of the input, or until the closing brace of the
+    /// group that is being parsed.
+    fn parse_sequence(&mut self, in_group: bool) -> Result<String> {
+        let mut output = String::new();
+        loop {
+            match self.peek() {
+                None if in_group => return Err(anyhow!("missing closing brace")),
+                None => break,
+                Some('}') if in_group => {
+                    self.ix += 1;
+                    break;
+                }
+                Some('}') => return Err(anyhow!("unexpected closing brace")),
+                Some('{') => {
+                    self.ix += 1;
+                    output.push_str(&self.parse_sequence(true)?);
+                }
+                Some(marker @ ('^' | '_')) => {
+                    self.ix += 1;
+                    let argument = self.parse_argument()?;
+                    output.push_str(&script(&argument, marker == '^'));
+                }
+                Some('\\') => {
+                    self.ix += 1;
+                    output.push_str(&self.parse_command()?);
+                }
+                Some('\'') => {
+                    self.ix += 1;
+                    output.push('′');
+                }
+                Some('~' | '&') => {
+                    self.ix += 1;
+                    output.push(' ');
+                }
+                Some(character) if character.is_whitespace() => {
+                    self.skip_whitespace();
+                    if !output.is_empty() && !output.ends_with([' ', '\n']) {
+                        output.push(' ');
+                    }
+                }
+                Some(character) => {
+                    self.ix += 1;
+                    output.push(character);
+                }
+            }
+        }
+        Ok(output)
+    }
+
+    /// Parses the argument of a command or script: a group, a command or a
+    /// single character.
+    fn parse_argument(&mut self) -> Result<String> {
+        self.skip_whitespace();
+        match self.next_char() {
+            Some('{') => self.parse_sequence(true),
+            Some('\\') => self.parse_command(),
+            Some(character) => Ok(character.to_string()),
+            None => Err(anyhow!("missing argument")),
+        }
+    }
+
+    fn parse_command(&mut self) -> Result<String> {
+        let Some(first) = self.next_char() else {
+            return Err(anyhow!("unfinished command"));
+        };
+        if !first.is_ascii_alphabetic() {
+            return match first {
+                ',' | ';' | ':' | '>' | ' ' => Ok(" ".into()),
+                '!' => Ok(String::new()),
+                '\\' => Ok("\n".into()),
+                '|' => Ok("‖".into()),
+                '{' | '}' | '%' | '$' | '_' | '#' | '&' => Ok(first.to_string()),
+                _ => Err(anyhow!("unsupported command \\{first}")),
+            };
+        }
+
+        let mut name = first.to_string();
+        while let Some(character) = self.peek().filter(char::is_ascii_alphabetic) {
+            name.push(character);
+            self.ix += 1;
+        }
+
+        if let Some(symbol) = symbol(&name) {
+            return Ok(symbol.into());
+        }
+        if FUNCTION_NAMES.contains(&name.as_str()) {
+            return Ok(name);
+        }
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.parse_argument()?;
+                let denominator = self.parse_argument()?;
+                Ok(format!("{}/{}", wrap(&numerator), wrap(&denominator)))
+            }
+            "sqrt" => {
+                let mut degree = None;
+                self.skip_whitespace();
+                if self.peek() == Some('[') {
+                    self.ix += 1;
+                    let mut value = String::new();
+                    loop {
+                        match self.next_char() {
+                            Some(']') => break,
+                            Some(character) => value.push(character),
+                            None => return Err(anyhow!("missing closing bracket")),
+                        }
+                    }
+                    degree = Some(value.trim().to_string());
+                }
+                let radicand = self.parse_argument()?;
+                let root = match degree.as_deref() {
+                    None | Some("2") => "√".to_string(),
+                    Some("3") => "∛".to_string(),
+                    Some("4") => "∜".to_string(),
+                    Some(degree) => format!("{}√", script(degree, true)),
+                };
+                Ok(format!("{root}{}", wrap(&radicand)))
+            }
+            "text" | "textrm" | "textbf" | "textit" | "mathrm" | "mathbf" | "mathit" | "mathsf"
+            | "mathtt" | "mathcal" | "operatorname" | "boldsymbol" => self.parse_argument(),
+            "mathbb" => Ok(self.parse_argument()?.chars().map(double_struck).collect()),
+            "hat" | "widehat" => self.accent('\u{0302}'),
+            "tilde" | "widetilde" => self.accent('\u{0303}'),
+            "bar" | "overline" => self.accent('\u{0304}'),
+            "dot" => self.accent('\u{0307}'),
+            "ddot" => self.accent('\u{0308}'),
+            "vec" => self.accent('\u{20D7}'),
+            "left" | "right" => {
+                // `\left.` and `\right.` stand for an invisible delimiter.
+                self.skip_whitespace();
+                if self.peek() == Some('.') {
+                    self.ix += 1;
+                }
+                Ok(String::new())
+            }
+            "big" | "Big" | "bigg" | "Bigg" | "bigl" | "bigr" | "Bigl" | "Bigr"
+            | "displaystyle" | "textstyle" | "limits" | "nolimits" => Ok(String::new()),
+            "begin" | "end" => Err(anyhow!("environments are not supported")),
+            _ => Err(anyhow!("unsupported command \\{name}")),
+        }
+    }
+
+    fn accent(&mut self, mark: char) -> Result<String> {
+        let argument = self.parse_argument()?;
+        Ok(argument
+            .chars()
+            .flat_map(|character| [character, mark])
+            .collect())
+    }
+}
+
+/// Wraps a fraction's numerator or denominator in parentheses unless it is a
+/// single term.
+fn wrap(term: &str) -> String {
+    if term.chars().all(char::is_alphanumeric) || term.chars().count() == 1 {
+        term.to_string()
+    } else {
+        format!("({term})")
+    }
+}
+
+/// Turns the argument of `^` or `_` into Unicode super- or subscript
+/// characters, falling back to `^(...)` when a character has no such form.
+fn script(argument: &str, superscript: bool) -> String {
+    let converted = argument
+        .chars()
+        .map(|character| {
+            if superscript {
+                superscript_char(character)
+            } else {
+                subscript_char(character)
+            }
+        })
+        .collect::<Option<String>>();
+    match converted {
+        Some(converted) => converted,
+        None => {
+            let marker = if superscript { '^' } else { '_' };
+            if argument.chars().count() == 1 {
+                format!("{marker}{argument}")
+            } else {
+                format!("{marker}({argument})")
+            }
+        }
+    }
+}
+
+fn superscript_char(character: char) -> Option<char> {
+    Some(match character {
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        '9' => '⁹',
+        '+' => '⁺',
+        '-' | '−' => '⁻',
+        '=' => '⁼',
+        '(' => '⁽',
+        ')' => '⁾',
+        'a' => 'ᵃ',
+        'b' => 'ᵇ',
+        'c' => 'ᶜ',
+        'd' => 'ᵈ',
+        'e' => 'ᵉ',
+        'f' => 'ᶠ',
+        'g' => 'ᵍ',
+        'h' => 'ʰ',
+        'i' => 'ⁱ',
+        'j' => 'ʲ',
+        'k' => 'ᵏ',
+        'l' => 'ˡ',
+        'm' => 'ᵐ',
+        'n' => 'ⁿ',
+        'o' => 'ᵒ',
+        'p' => 'ᵖ',
+        'r' => 'ʳ',
+        's' => 'ˢ',
+        't' => 'ᵗ',
+        'u' => 'ᵘ',
+        'v' => 'ᵛ',
+        'w' => 'ʷ',
+        'x' => 'ˣ',
+        'y' => 'ʸ',
+        'z' => 'ᶻ',
+        'T' => 'ᵀ',
+        '′' | '∗' | '∘' => character,
+        _ => return None,
+    })
+}
+
+fn subscript_char(character: char) -> Option<char> {
+    Some(match character {
+        '0' => '₀',
+        '1' => '₁',
+        '2' => '₂',
+        '3' => '₃',
+        '4' => '₄',
+        '5' => '₅',
+        '6' => '₆',
+        '7' => '₇',
+        '8' => '₈',
+        '9' => '₉',
+        '+' => '₊',
+        '-' | '−' => '₋',
+        '=' => '₌',
+        '(' => '₍',
+        ')' => '₎',
+        'a' => 'ₐ',
+        'e' => 'ₑ',
+        'h' => 'ₕ',
+        'i' => 'ᵢ',
+        'j' => 'ⱼ',
+        'k' => 'ₖ',
+        'l' => 'ₗ',
+        'm' => 'ₘ',
+        'n' => 'ₙ',
+        'o' => 'ₒ',
+        'p' => 'ₚ',
+        'r' => 'ᵣ',
+        's' => 'ₛ',
+        't' => 'ₜ',
+        'u' => 'ᵤ',
+        'v' => 'ᵥ',
+        'x' => 'ₓ',
+        _ => return None,
+    })
+}
+
+fn double_struck(character: char) -> char {
+    match character {
+        'C' => 'ℂ',
+        'H' => 'ℍ',
+        'N' => 'ℕ',
+        'P' => 'ℙ',
+        'Q' => 'ℚ',
+        'R' => 'ℝ',
+        'Z' => 'ℤ',
+        'A'..='Z' => char::from_u32(0x1D538 + (character as u32 - 'A' as u32)).unwrap_or(character),
+        _ => character,
+    }
+}
+
+fn symbol(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" => "ϵ",
+        "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" => "θ",
+        "vartheta" => "ϑ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "rho" => "ρ",
+        "sigma" => "σ",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" => "ϕ",
+        "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Upsilon" => "Υ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        "times" => "×",
+        "cdot" => "⋅",
+        "div" => "÷",
+        "pm" => "±",
+        "mp" => "∓",
+        "ast" => "∗",
+        "star" => "⋆",
+        "circ" => "∘",
+        "bullet" => "∙",
+        "le" | "leq" => "≤",
+        "ge" | "geq" => "≥",
+        "ll" => "≪",
+        "gg" => "≫",
+        "ne" | "neq" => "≠",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "simeq" => "≃",
+        "cong" => "≅",
+        "propto" => "∝",
+        "infty" => "∞",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "sum" => "∑",
+        "prod" => "∏",
+        "coprod" => "∐",
+        "int" => "∫",
+        "iint" => "∬",
+        "iiint" => "∭",
+        "oint" => "∮",
+        "in" => "∈",
+        "notin" => "∉",
+        "ni" => "∋",
+        "subset" => "⊂",
+        "subseteq" => "⊆",
+        "supset" => "⊃",
+        "supseteq" => "⊇",
+        "cup" => "∪",
+        "cap" => "∩",
+        "setminus" => "∖",
+        "emptyset" | "varnothing" => "∅",
+        "forall" => "∀",
+        "exists" => "∃",
+        "nexists" => "∄",
+        "land" | "wedge" => "∧",
+        "lor" | "vee" => "∨",
+        "neg" | "lnot" => "¬",
+        "oplus" => "⊕",
+        "otimes" => "⊗",
+        "to" | "rightarrow" => "→",
+        "gets" | "leftarrow" => "←",
+        "leftrightarrow" => "↔",
+        "Rightarrow" => "⇒",
+        "Leftarrow" => "⇐",
+        "Leftrightarrow" | "iff" => "⇔",
+        "implies" => "⟹",
+        "mapsto" => "↦",
+        "uparrow" => "↑",
+        "downarrow" => "↓",
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "vdots" => "⋮",
+        "ddots" => "⋱",
+        "angle" => "∠",
+        "perp" => "⊥",
+        "parallel" => "∥",
+        "mid" => "∣",
+        "prime" => "′",
+        "degree" => "°",
+        "ell" => "ℓ",
+        "hbar" => "ℏ",
+        "Re" => "ℜ",
+        "Im" => "ℑ",
+        "aleph" => "ℵ",
+        "langle" => "⟨",
+        "rangle" => "⟩",
+        "lceil" => "⌈",
+        "rceil" => "⌉",
+        "lfloor" => "⌊",
+        "rfloor" => "⌋",
+        "lbrace" => "{",
+        "rbrace" => "}",
+        "vert" => "|",
+        "Vert" => "‖",
+        "quad" => "  ",
+        "qquad" => "    ",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_render_latex() {
+        let render = |source| render_latex(source).unwrap();
+
+        assert_eq!(render(r"E = mc^2"), "E = mc²");
+        assert_eq!(
+            render(r"x_{i+1} = x_i - \alpha \nabla f"),
+            "xᵢ₊₁ = xᵢ - α ∇ f"
+        );
+        assert_eq!(render(r"\frac{a+b}{2} \leq \sqrt{ab}"), "(a+b)/2 ≤ √ab");
+        assert_eq!(render(r"\sum_{k=1}^{n} k"), "∑ₖ₌₁ⁿ k");
+        assert_eq!(render(r"\sqrt[3]{x + 1}"), "∛(x + 1)");
+        assert_eq!(render(r"f'(x) \in \mathbb{R}"), "f′(x) ∈ ℝ");
+        assert_eq!(render(r"\text{if } x > 0"), "if x > 0");
+        assert_eq!(render(r"e^{\pi i} + 1 = 0"), "e^(π i) + 1 = 0");
+        assert_eq!(render(r"\left( \frac{1}{x} \right)"), "( 1/x )");
+        assert_eq!(
+            render(r"\sin^2 \theta + \cos^2 \theta = 1"),
+            "sin² θ + cos² θ = 1"
+        );
+    }
+
+    #[test]
+    fn test_render_latex_errors() {
+        assert!(render_latex(r"\frac{1}{").is_err());
+        assert!(render_latex(r"x}").is_err());
+        assert!(render_latex(r"\begin{matrix} 1 \end{matrix}").is_err());
+        assert!(render_latex(r"\unknowncommand{x}").is_err());
+    }
+}
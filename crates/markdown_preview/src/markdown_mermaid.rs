@@ -0,0 +1,682 @@
+//! Parses Mermaid flowcharts and draws them as SVG for the markdown preview.
+//! Other kinds of Mermaid diagrams are reported as errors, so that the preview
+//! can show their source instead.
+
+use anyhow::{anyhow, Result};
+use gpui::{Image, ImageFormat};
+use std::fmt::{self, Write as _};
+use std::sync::{Arc, Mutex};
+
+const NODE_HEIGHT: f32 = 40.;
+const CHAR_WIDTH: f32 = 8.;
+const NODE_PADDING: f32 = 16.;
+const RANK_GAP: f32 = 56.;
+const NODE_GAP: f32 = 32.;
+const MARGIN: f32 = 16.;
+const FONT_FAMILY: &str = "Helvetica, Arial, DejaVu Sans, Liberation Sans, sans-serif";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlowchartDirection {
+    TopToBottom,
+    BottomToTop,
+    LeftToRight,
+    RightToLeft,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeShape {
+    Rectangle,
+    Rounded,
+    Circle,
+    Diamond,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FlowchartNode {
+    pub id: String,
+    pub label: String,
+    pub shape: NodeShape,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FlowchartEdge {
+    pub from: usize,
+    pub to: usize,
+    pub label: Option<String>,
+    pub dashed: bool,
+    pub arrow: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Flowchart {
+    pub direction: FlowchartDirection,
+    pub nodes: Vec<FlowchartNode>,
+    pub edges: Vec<FlowchartEdge>,
+}
+
+/// The colors to draw a diagram with, as CSS color strings.
+#[derive(Clone, PartialEq)]
+pub struct DiagramColors {
+    pub node_background: String,
+    pub node_border: String,
+    pub text: String,
+    pub edge: String,
+    pub background: String,
+}
+
+pub struct DiagramSvg {
+    pub svg: String,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// The image of a flowchart, which is drawn again only when the colors change, rather than every
+/// time the preview renders.
+#[derive(Default)]
+pub struct DiagramImageCache(Mutex<Option<CachedDiagram>>);
+
+#[derive(Clone)]
+pub struct CachedDiagram {
+    colors: DiagramColors,
+    pub image: Arc<Image>,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl DiagramImageCache {
+    pub fn get_or_draw(&self, flowchart: &Flowchart, colors: &DiagramColors) -> CachedDiagram {
+        let mut cached = self.0.lock().unwrap();
+        if let Some(cached) = cached.as_ref().filter(|cached| cached.colors == *colors) {
+            return cached.clone();
+        }
+        let diagram = flowchart.to_svg(colors);
+        let drawn = CachedDiagram {
+            colors: colors.clone(),
+            image: Arc::new(Image {
+                id: gpui::hash(&diagram.svg),
+                format: ImageFormat::Svg,
+                bytes: diagram.svg.into_bytes(),
+            }),
+            width: diagram.width,
+            height: diagram.height,
+        };
+        *cached = Some(drawn.clone());
+        drawn
+    }
+}
+
+impl fmt::Debug for DiagramImageCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DiagramImageCache").finish_non_exhaustive()
+    }
+}
+
+/// Cached images don't affect what a diagram is.
+impl PartialEq for DiagramImageCache {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+/// Parses the contents of a ```` ```mermaid ```` block.
+pub fn parse_mermaid(source: &str) -> Result<Flowchart> {
+    let mut lines = source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("%%"));
+    let header = lines
+        .next()
+        .ok_or_else(|| anyhow!("the diagram is empty"))?;
+    let mut words = header.split_whitespace();
+    let kind = words.next().unwrap_or_default();
+    if kind != "graph" && kind != "flowchart" {
+        return Err(anyhow!(
+            "{kind} diagrams are not supported, only flowcharts are"
+        ));
+    }
+    let direction = match words.next().unwrap_or("TB") {
+        "TB" | "TD" => FlowchartDirection::TopToBottom,
+        "BT" => FlowchartDirection::BottomToTop,
+        "LR" => FlowchartDirection::LeftToRight,
+        "RL" => FlowchartDirection::RightToLeft,
+        other => return Err(anyhow!("unknown flowchart direction {other}")),
+    };
+
+    let mut flowchart = Flowchart {
+        direction,
+        nodes: Vec::new(),
+        edges: Vec::new(),
+    };
+    for line in lines {
+        for statement in line.split(';').map(str::trim) {
+            let keyword = statement.split_whitespace().next();
+            if matches!(
+                keyword,
+                None | Some(
+                    "subgraph"
+                        | "end"
+                        | "direction"
+                        | "style"
+                        | "classDef"
+                        | "class"
+                        | "click"
+                        | "linkStyle"
+                )
+            ) {
+                continue;
+            }
+            flowchart.parse_statement(statement)?;
+        }
+    }
+    Ok(flowchart)
+}
+
+impl Flowchart {
+    /// Parses a chain of nodes joined by links, such as `A[Start] --> B{Ok?} -->|yes| C`.
+    fn parse_statement(&mut self, statement: &str) -> Result<()> {
+        let mut rest = statement;
+        let mut previous = self.parse_node(&mut rest)?;
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                return Ok(());
+            }
+            let (label, dashed, arrow) = parse_link(&mut rest)?;
+            let next = self.parse_node(&mut rest)?;
+            self.edges.push(FlowchartEdge {
+                from: previous,
+                to: next,
+                label,
+                dashed,
+                arrow,
+            });
+            previous = next;
+        }
+    }
+
+    fn parse_node(&mut self, rest: &mut &str) -> Result<usize> {
+        const SHAPES: &[(&str, &str, NodeShape)] = &[
+            ("((", "))", NodeShape::Circle),
+            ("([", "])", NodeShape::Rounded),
+            ("[(", ")]", NodeShape::Rounded),
+            ("[[", "]]", NodeShape::Rectangle),
+            ("{{", "}}", NodeShape::Diamond),
+            ("[", "]", NodeShape::Rectangle),
+            ("(", ")", NodeShape::Rounded),
+            ("{", "}", NodeShape::Diamond),
+            (">", "]", NodeShape::Rectangle),
+        ];
+
+        let input = rest.trim_start();
+        let id_len = input
+            .find(|character: char| !(character.is_alphanumeric() || character == '_'))
+            .unwrap_or(input.len());
+        if id_len == 0 {
+            return Err(anyhow!("expected a node at \"{input}\""));
+        }
+        let id = &input[..id_len];
+        let mut after = &input[id_len..];
+
+        let mut shape_and_label = None;
+        for (open, close, shape) in SHAPES {
+            if let Some(body) = after.strip_prefix(open) {
+                let end = body
+                    .find(close)
+                    .ok_or_else(|| anyhow!("missing \"{close}\" after node {id}"))?;
+                let label = body[..end].trim().trim_matches('"').to_string();
+                shape_and_label = Some((*shape, label));
+                after = &body[end + close.len()..];
+                break;
+            }
+        }
+        *rest = after;
+
+        if let Some(ix) = self.nodes.iter().position(|node| node.id == id) {
+            if let Some((shape, label)) = shape_and_label {
+                self.nodes[ix].shape = shape;
+                self.nodes[ix].label = label;
+            }
+            return Ok(ix);
+        }
+        let (shape, label) = shape_and_label.unwrap_or((NodeShape::Rectangle, id.to_string()));
+        self.nodes.push(FlowchartNode {
+            id: id.to_string(),
+            label,
+            shape,
+        });
+        Ok(self.nodes.len() - 1)
+    }
+
+    /// Assigns every node to a rank, so that links point from lower to higher
+    /// ranks, except for the ones that close a cycle.
+    fn ranks(&self) -> Vec<usize> {
+        fn visit(
+            node: usize,
+            edges: &[FlowchartEdge],
+            state: &mut [u8],
+            back_edges: &mut Vec<usize>,
+        ) {
+            state[node] = 1;
+            for (ix, edge) in edges.iter().enumerate() {
+                if edge.from != node {
+                    continue;
+                }
+                match state[edge.to] {
+                    0 => visit(edge.to, edges, state, back_edges),
+                    1 => back_edges.push(ix),
+                    _ => {}
+                }
+            }
+            state[node] = 2;
+        }
+
+        let mut state = vec![0; self.nodes.len()];
+        let mut back_edges = Vec::new();
+        for node in 0..self.nodes.len() {
+            if state[node] == 0 {
+                visit(node, &self.edges, &mut state, &mut back_edges);
+            }
+        }
+
+        let mut ranks = vec![0; self.nodes.len()];
+        for _ in 0..self.nodes.len() {
+            let mut changed = false;
+            for (ix, edge) in self.edges.iter().enumerate() {
+                if !back_edges.contains(&ix) && ranks[edge.to] <= ranks[edge.from] {
+                    ranks[edge.to] = ranks[edge.from] + 1;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        ranks
+    }
+
+    fn node_size(node: &FlowchartNode) -> (f32, f32) {
+        let width = (node.label.chars().count() as f32 * CHAR_WIDTH + 2. * NODE_PADDING).max(48.);
+        match node.shape {
+            NodeShape::Rectangle | NodeShape::Rounded => (width, NODE_HEIGHT),
+            NodeShape::Circle => {
+                let diameter = width.max(NODE_HEIGHT);
+                (diameter, diameter)
+            }
+            NodeShape::Diamond => (width * 1.5, NODE_HEIGHT * 1.5),
+        }
+    }
+
+    /// Returns the center of every node, and the size of the whole diagram.
+    fn layout(&self) -> (Vec<(f32, f32)>, f32, f32) {
+        let vertical = matches!(
+            self.direction,
+            FlowchartDirection::TopToBottom | FlowchartDirection::BottomToTop
+        );
+        let reversed = matches!(
+            self.direction,
+            FlowchartDirection::BottomToTop | FlowchartDirection::RightToLeft
+        );
+        let mut ranks = self.ranks();
+        let rank_count = ranks.iter().max().map_or(0, |max| max + 1);
+        if reversed {
+            for rank in &mut ranks {
+                *rank = rank_count - 1 - *rank;
+            }
+        }
+
+        // Sizes along and across the direction of the flowchart.
+        let sizes = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let (width, height) = Self::node_size(node);
+                if vertical {
+                    (height, width)
+                } else {
+                    (width, height)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut thickness = vec![0f32; rank_count];
+        let mut span = vec![0f32; rank_count];
+        for (node, (main, cross)) in sizes.iter().enumerate() {
+            let rank = ranks[node];
+            thickness[rank] = thickness[rank].max(*main);
+            if span[rank] > 0. {
+                span[rank] += NODE_GAP;
+            }
+            span[rank] += cross;
+        }
+        let max_span = span.iter().copied().fold(0., f32::max);
+
+        let mut rank_offsets = Vec::with_capacity(rank_count);
+        let mut offset = MARGIN;
+        for rank_thickness in &thickness {
+            rank_offsets.push(offset);
+            offset += rank_thickness + RANK_GAP;
+        }
+        let main_total = offset - RANK_GAP + MARGIN;
+        let cross_total = max_span + 2. * MARGIN;
+
+        let mut cross_offsets = span
+            .iter()
+            .map(|span| MARGIN + (max_span - span) / 2.)
+            .collect::<Vec<_>>();
+        let centers = sizes
+            .iter()
+            .enumerate()
+            .map(|(node, (_, cross))| {
+                let rank = ranks[node];
+                let main = rank_offsets[rank] + thickness[rank] / 2.;
+                let cross_center = cross_offsets[rank] + cross / 2.;
+                cross_offsets[rank] += cross + NODE_GAP;
+                if vertical {
+                    (cross_center, main)
+                } else {
+                    (main, cross_center)
+                }
+            })
+            .collect();
+
+        if vertical {
+            (centers, cross_total, main_total)
+        } else {
+            (centers, main_total, cross_total)
+        }
+    }
+
+    /// Draws the flowchart. The SVG is twice the size of the diagram so that
+    /// it stays sharp on high density displays.
+    pub fn to_svg(&self, colors: &DiagramColors) -> DiagramSvg {
+        let (centers, width, height) = self.layout();
+        let mut svg = String::new();
+        write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {width} {height}">"#,
+            width * 2.,
+            height * 2.,
+        )
+        .unwrap();
+        write!(
+            svg,
+            r#"<defs><marker id="arrow" viewBox="0 0 10 10" refX="10" refY="5" markerUnits="userSpaceOnUse" markerWidth="10" markerHeight="10" orient="auto"><path d="M 0 0 L 10 5 L 0 10 z" fill="{}"/></marker></defs>"#,
+            colors.edge
+        )
+        .unwrap();
+
+        for edge in &self.edges {
+            if edge.from == edge.to {
+                continue;
+            }
+            let (start, end) = (centers[edge.from], centers[edge.to]);
+            let (x1, y1) = self.boundary_point(edge.from, start, end);
+            let (x2, y2) = self.boundary_point(edge.to, end, start);
+            write!(
+                svg,
+                r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{}" stroke-width="1.5"{}{}/>"#,
+                colors.edge,
+                if edge.dashed {
+                    r#" stroke-dasharray="5 4""#
+                } else {
+                    ""
+                },
+                if edge.arrow {
+                    r#" marker-end="url(#arrow)""#
+                } else {
+                    ""
+                },
+            )
+            .unwrap();
+            if let Some(label) = &edge.label {
+                let (x, y) = ((x1 + x2) / 2., (y1 + y2) / 2.);
+                let label_width = label.chars().count() as f32 * CHAR_WIDTH * 0.9 + 8.;
+                write!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{label_width}" height="18" fill="{}"/>"#,
+                    x - label_width / 2.,
+                    y - 9.,
+                    colors.background
+                )
+                .unwrap();
+                write_text(&mut svg, x, y, 12., label, &colors.text);
+            }
+        }
+
+        for (node, (x, y)) in self.nodes.iter().zip(&centers) {
+            let (width, height) = Self::node_size(node);
+            let (left, top) = (x - width / 2., y - height / 2.);
+            let fill = &colors.node_background;
+            let stroke = &colors.node_border;
+            match node.shape {
+                NodeShape::Rectangle | NodeShape::Rounded => {
+                    let radius = if node.shape == NodeShape::Rounded {
+                        height / 2.
+                    } else {
+                        4.
+                    };
+                    write!(
+                        svg,
+                        r#"<rect x="{left}" y="{top}" width="{width}" height="{height}" rx="{radius}" fill="{fill}" stroke="{stroke}" stroke-width="1.5"/>"#
+                    )
+                    .unwrap();
+                }
+                NodeShape::Circle => {
+                    write!(
+                        svg,
+                        r#"<circle cx="{x}" cy="{y}" r="{}" fill="{fill}" stroke="{stroke}" stroke-width="1.5"/>"#,
+                        width / 2.
+                    )
+                    .unwrap();
+                }
+                NodeShape::Diamond => {
+                    write!(
+                        svg,
+                        r#"<polygon points="{x},{top} {},{y} {x},{} {left},{y}" fill="{fill}" stroke="{stroke}" stroke-width="1.5"/>"#,
+                        left + width,
+                        top + height
+                    )
+                    .unwrap();
+                }
+            }
+            write_text(&mut svg, *x, *y, 14., &node.label, &colors.text);
+        }
+
+        svg.push_str("</svg>");
+        DiagramSvg { svg, width, height }
+    }
+
+    /// Returns where the line from `center` towards `target` leaves the node.
+    fn boundary_point(&self, node: usize, center: (f32, f32), target: (f32, f32)) -> (f32, f32) {
+        let (width, height) = Self::node_size(&self.nodes[node]);
+        let (half_width, half_height) = (width / 2., height / 2.);
+        let (dx, dy) = (target.0 - center.0, target.1 - center.1);
+        if dx == 0. && dy == 0. {
+            return center;
+        }
+        let t = match self.nodes[node].shape {
+            NodeShape::Rectangle | NodeShape::Rounded => {
+                let tx = if dx == 0. {
+                    f32::INFINITY
+                } else {
+                    half_width / dx.abs()
+                };
+                let ty = if dy == 0. {
+                    f32::INFINITY
+                } else {
+                    half_height / dy.abs()
+                };
+                tx.min(ty)
+            }
+            NodeShape::Circle => half_width / (dx * dx + dy * dy).sqrt(),
+            NodeShape::Diamond => 1. / (dx.abs() / half_width + dy.abs() / half_height),
+        };
+        (center.0 + dx * t, center.1 + dy * t)
+    }
+}
+
+/// Parses a link such as `-->`, `-.->`, `---|label|` or `-- label -->`.
+/// Returns its label, whether it is dashed and whether it ends in an arrow.
+fn parse_link(rest: &mut &str) -> Result<(Option<String>, bool, bool)> {
+    let is_link_char = |character: char| matches!(character, '-' | '=' | '.' | '>' | '<');
+    let input = *rest;
+    let link_len = input
+        .find(|character: char| !is_link_char(character))
+        .unwrap_or(input.len());
+    if link_len < 2 {
+        return Err(anyhow!("expected a link at \"{input}\""));
+    }
+    let mut link = input[..link_len].to_string();
+    let mut after = &input[link_len..];
+    let mut label = None;
+
+    if matches!(link.as_str(), "--" | "==" | "-.") {
+        const CLOSING_LINKS: &[&str] = &["-->", "---", "==>", "===", ".->", ".-"];
+        let (label_end, _) = CLOSING_LINKS
+            .iter()
+            .filter_map(|closing| after.find(closing).map(|ix| (ix, *closing)))
+            .min_by_key(|(ix, _)| *ix)
+            .ok_or_else(|| anyhow!("unfinished link at \"{input}\""))?;
+        label = Some(after[..label_end].trim().to_string());
+        let closing = &after[label_end..];
+        let closing_len = closing
+            .find(|character: char| !is_link_char(character))
+            .unwrap_or(closing.len());
+        link.push_str(&closing[..closing_len]);
+        after = &closing[closing_len..];
+    }
+
+    let trimmed = after.trim_start();
+    if let Some(text) = trimmed.strip_prefix('|') {
+        let end = text
+            .find('|')
+            .ok_or_else(|| anyhow!("missing \"|\" after link label"))?;
+        label = Some(text[..end].trim().to_string());
+        after = &text[end + 1..];
+    }
+
+    *rest = after;
+    Ok((label, link.contains('.'), link.ends_with('>')))
+}
+
+fn write_text(svg: &mut String, x: f32, y: f32, font_size: f32, text: &str, color: &str) {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(character),
+        }
+    }
+    write!(
+        svg,
+        r#"<text x="{x}" y="{}" text-anchor="middle" font-family="{FONT_FAMILY}" font-size="{font_size}" fill="{color}">{escaped}</text>"#,
+        y + font_size * 0.35
+    )
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_mermaid_flowchart() {
+        let flowchart = parse_mermaid(
+            "flowchart LR\n\
+             %% a comment\n\
+             A[Start] --> B{Is it ok?}\n\
+             B -->|yes| C((Done))\n\
+             B -- no --> D(Retry); D -.-> A\n\
+             C --- E",
+        )
+        .unwrap();
+
+        assert_eq!(flowchart.direction, FlowchartDirection::LeftToRight);
+        assert_eq!(
+            flowchart
+                .nodes
+                .iter()
+                .map(|node| (node.id.as_str(), node.label.as_str(), node.shape))
+                .collect::<Vec<_>>(),
+            vec![
+                ("A", "Start", NodeShape::Rectangle),
+                ("B", "Is it ok?", NodeShape::Diamond),
+                ("C", "Done", NodeShape::Circle),
+                ("D", "Retry", NodeShape::Rounded),
+                ("E", "E", NodeShape::Rectangle),
+            ]
+        );
+        assert_eq!(
+            flowchart
+                .edges
+                .iter()
+                .map(|edge| (
+                    edge.from,
+                    edge.to,
+                    edge.label.as_deref(),
+                    edge.dashed,
+                    edge.arrow
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, 1, None, false, true),
+                (1, 2, Some("yes"), false, true),
+                (1, 3, Some("no"), false, true),
+                (3, 0, None, true, true),
+                (2, 4, None, false, false),
+            ]
+        );
+        // The link from "Retry" back to "Start" closes a cycle, so it doesn't
+        // push "Start" further down.
+        assert_eq!(flowchart.ranks(), vec![0, 1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn test_parse_mermaid_errors() {
+        assert!(parse_mermaid("sequenceDiagram\nAlice->>Bob: Hi").is_err());
+        assert!(parse_mermaid("graph TD\nA[Start --> B").is_err());
+        assert!(parse_mermaid("graph TD\nA --> ").is_err());
+        assert!(parse_mermaid("").is_err());
+    }
+
+    #[test]
+    fn test_flowchart_to_svg() {
+        let flowchart = parse_mermaid("graph TD\nA[a < b] --> B").unwrap();
+        let colors = DiagramColors {
+            node_background: "#eeeeee".into(),
+            node_border: "#888888".into(),
+            text: "#000000".into(),
+            edge: "#444444".into(),
+            background: "#ffffff".into(),
+        };
+        let diagram = flowchart.to_svg(&colors);
+        assert!(diagram.svg.starts_with("<svg"));
+        assert!(diagram.svg.contains("a &lt; b"));
+        assert!(diagram.svg.contains(r#"marker-end="url(#arrow)""#));
+        assert!(diagram.height > diagram.width);
+    }
+
+    #[test]
+    fn test_diagram_image_cache() {
+        let flowchart = parse_mermaid("graph TD\nA --> B").unwrap();
+        let colors = |text: &str| DiagramColors {
+            node_background: "#eeeeee".into(),
+            node_border: "#888888".into(),
+            text: text.into(),
+            edge: "#444444".into(),
+            background: "#ffffff".into(),
+        };
+        let cache = DiagramImageCache::default();
+        let first = cache.get_or_draw(&flowchart, &colors("#000000"));
+        let second = cache.get_or_draw(&flowchart, &colors("#000000"));
+        assert!(Arc::ptr_eq(&first.image, &second.image));
+
+        let recolored = cache.get_or_draw(&flowchart, &colors("#ffffff"));
+        assert!(!Arc::ptr_eq(&first.image, &recolored.image));
+        assert_ne!(first.image.bytes, recolored.image.bytes);
+    }
+}
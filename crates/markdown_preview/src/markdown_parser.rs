@@ -1,4 +1,8 @@
-use crate::markdown_elements::*;
+use crate::{
+    markdown_elements::*,
+    markdown_math::render_latex,
+    markdown_mermaid::{parse_mermaid, DiagramImageCache},
+};
 use async_recursion::async_recursion;
 use collections::FxHashMap;
 use gpui::FontWeight;
@@ -102,6 +106,8 @@ impl<'a> MarkdownParser<'a> {
             | Event::Html(_)
             | Event::InlineHtml(_)
             | Event::FootnoteReference(_)
+            | Event::InlineMath(_)
+            | Event::DisplayMath(_)
             | Event::Start(Tag::Link { .. })
             | Event::Start(Tag::Emphasis)
             | Event::Start(Tag::Strong)
@@ -172,6 +178,20 @@ impl<'a> MarkdownParser<'a> {
 
                     self.cursor += 1;
 
+                    if language.as_deref() == Some("mermaid") {
+                        let code_block = self.parse_code_block(None).await;
+                        let flowchart = parse_mermaid(&code_block.contents)
+                            .map_err(|error| error.to_string().into());
+                        return Some(vec![ParsedMarkdownElement::MermaidDiagram(
+                            ParsedMarkdownMermaidDiagram {
+                                source_range: code_block.source_range,
+                                contents: code_block.contents,
+                                flowchart,
+                                image_cache: DiagramImageCache::default(),
+                            },
+                        )]);
+                    }
+
                     let code_block = self.parse_code_block(language).await;
                     Some(vec![ParsedMarkdownElement::CodeBlock(code_block)])
                 }
@@ -215,7 +235,7 @@ impl<'a> MarkdownParser<'a> {
                 break;
             }
 
-            let (current, _) = self.current().unwrap();
+            let (current, current_range) = self.current().unwrap();
             let prev_len = text.len();
             match current {
                 Event::SoftBreak => {
@@ -334,6 +354,49 @@ impl<'a> MarkdownParser<'a> {
                         link: link.clone(),
                     });
                 }
+                Event::InlineMath(t) => match render_latex(t) {
+                    Ok(rendered) => {
+                        text.push_str(&rendered);
+                        highlights.push((
+                            prev_len..text.len(),
+                            MarkdownHighlight::Style(MarkdownHighlightStyle {
+                                italic: true,
+                                ..Default::default()
+                            }),
+                        ));
+                    }
+                    // Formulas that can't be rendered are shown as they were written.
+                    Err(_) => {
+                        text.push('$');
+                        text.push_str(t);
+                        text.push('$');
+                        region_ranges.push(prev_len..text.len());
+                        regions.push(ParsedRegion {
+                            code: true,
+                            link: None,
+                        });
+                    }
+                },
+                Event::DisplayMath(t) => {
+                    if !text.is_empty() {
+                        markdown_text_like.push(MarkdownParagraphChunk::Text(ParsedMarkdownText {
+                            source_range: source_range.clone(),
+                            contents: std::mem::take(&mut text),
+                            highlights: std::mem::take(&mut highlights),
+                            region_ranges: std::mem::take(&mut region_ranges),
+                            regions: std::mem::take(&mut regions),
+                        }));
+                    }
+                    markdown_text_like.push(MarkdownParagraphChunk::DisplayMath(
+                        ParsedMarkdownMath {
+                            source_range: current_range.clone(),
+                            contents: t.to_string().into(),
+                            rendered: render_latex(t)
+                                .map(Into::into)
+                                .map_err(|error| error.to_string().into()),
+                        },
+                    ));
+                }
                 Event::Start(tag) => match tag {
                     Tag::Emphasis => italic_depth += 1,
                     Tag::Strong => bold_depth += 1,
@@ -1409,6 +1472,38 @@ fn main() {
         );
     }
 
+    #[gpui::test]
+    async fn test_math() {
+        let parsed = parse("Energy is $E = mc^2$.\n\n$$\\frac{1}{2}$$\n\n$\\unknown$\n").await;
+
+        assert_eq!(parsed.children.len(), 3);
+        assert_eq!(parsed.children[0], p("Energy is E = mc².", 0..22));
+        let ParsedMarkdownElement::Paragraph(chunks) = &parsed.children[1] else {
+            panic!("expected a paragraph, got {:?}", parsed.children[1]);
+        };
+        let [MarkdownParagraphChunk::DisplayMath(math)] = chunks.as_slice() else {
+            panic!("expected display math, got {:?}", chunks);
+        };
+        assert_eq!(math.contents.as_ref(), "\\frac{1}{2}");
+        assert_eq!(math.rendered, Ok("1/2".into()));
+        assert_eq!(parsed.children[2], p("$\\unknown$", 40..51));
+    }
+
+    #[gpui::test]
+    async fn test_mermaid_diagram() {
+        let parsed = parse("```mermaid\ngraph TD\nA --> B\n```\n\n```mermaid\npie\n```\n").await;
+
+        let [ParsedMarkdownElement::MermaidDiagram(flowchart), ParsedMarkdownElement::MermaidDiagram(pie)] =
+            parsed.children.as_slice()
+        else {
+            panic!("expected two diagrams, got {:?}", parsed.children);
+        };
+        assert_eq!(flowchart.contents.as_ref(), "graph TD\nA --> B");
+        assert_eq!(flowchart.flowchart.as_ref().unwrap().nodes.len(), 2);
+        assert_eq!(pie.contents.as_ref(), "pie");
+        assert!(pie.flowchart.is_err());
+    }
+
     fn rust_lang() -> Arc<Language> {
         Arc::new(Language::new(
             LanguageConfig {
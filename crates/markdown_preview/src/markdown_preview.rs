@@ -2,9 +2,12 @@ use gpui::{actions, App};
 use workspace::Workspace;
 
 pub mod markdown_elements;
+pub mod markdown_math;
+pub mod markdown_mermaid;
 pub mod markdown_parser;
 pub mod markdown_preview_view;
 pub mod markdown_renderer;
+pub mod markdown_table;
 
 actions!(markdown, [OpenPreview, OpenPreviewToTheSide]);
 
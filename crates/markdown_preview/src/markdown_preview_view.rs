@@ -4,18 +4,19 @@ use std::{ops::Range, path::PathBuf};
 
 use anyhow::Result;
 use editor::scroll::Autoscroll;
-use editor::{Editor, EditorEvent};
+use editor::{Anchor, Editor, EditorEvent, ToOffset as _};
 use gpui::{
-    list, App, ClickEvent, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    list, App, ClickEvent, Context, Entity, EventEmitter, FocusHandle, Focusable, Hsla,
     InteractiveElement, IntoElement, ListState, ParentElement, Render, Styled, Subscription, Task,
     WeakEntity, Window,
 };
 use language::LanguageRegistry;
-use ui::prelude::*;
+use ui::{prelude::*, Tooltip};
 use workspace::item::{Item, ItemHandle};
 use workspace::{Pane, Workspace};
 
 use crate::markdown_elements::ParsedMarkdownElement;
+use crate::markdown_table::format_markdown_table;
 use crate::OpenPreviewToTheSide;
 use crate::{
     markdown_elements::ParsedMarkdown,
@@ -37,6 +38,22 @@ pub struct MarkdownPreviewView {
     fallback_tab_description: SharedString,
     language_registry: Arc<LanguageRegistry>,
     parsing_markdown_task: Option<Task<Result<()>>>,
+    collaborator_cursors: Vec<CollaboratorCursor>,
+    /// The block containing the cursor of the collaborator being followed in the editor.
+    leader_block: Option<usize>,
+    /// Whether the buffer was edited since it was last parsed.
+    edited_since_parse: bool,
+    /// A table that was edited in the source, to be reformatted once the cursor leaves it.
+    unformatted_table: Option<Range<language::Anchor>>,
+}
+
+/// The cursor of a collaborator editing the previewed buffer.
+struct CollaboratorCursor {
+    block: usize,
+    offset: usize,
+    color: Hsla,
+    user_name: Option<SharedString>,
+    is_leader: bool,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -50,6 +67,7 @@ pub enum MarkdownPreviewMode {
 struct EditorState {
     editor: Entity<Editor>,
     _subscription: Subscription,
+    _observation: Subscription,
 }
 
 impl MarkdownPreviewView {
@@ -141,7 +159,7 @@ impl MarkdownPreviewView {
         language_registry: Arc<LanguageRegistry>,
         fallback_description: Option<SharedString>,
         window: &mut Window,
-        cx: &mut Context<Workspace>,
+        cx: &mut App,
     ) -> Entity<Self> {
         cx.new(|cx| {
             let view = cx.entity().downgrade();
@@ -184,6 +202,18 @@ impl MarkdownPreviewView {
                                                 }
                                             })
                                         }
+                                    })
+                                    .with_heading_clicked_callback({
+                                        let view = view.clone();
+                                        move |source_range, window, cx| {
+                                            view.update(cx, |view, cx| {
+                                                view.move_cursor_to_block(
+                                                    window,
+                                                    cx,
+                                                    source_range.start..source_range.start,
+                                                );
+                                            })
+                                        }
                                     });
                             let block = contents.children.get(ix).unwrap();
                             let rendered_block = render_markdown_block(block, &mut render_cx);
@@ -193,6 +223,26 @@ impl MarkdownPreviewView {
                                 contents.children.get(ix + 1),
                             );
 
+                            let collaborator_cursors = this
+                                .collaborator_cursors
+                                .iter()
+                                .filter(|cursor| cursor.block == ix)
+                                .map(|cursor| {
+                                    div()
+                                        .id(("collaborator-cursor", cursor.offset))
+                                        .w(px(6.0))
+                                        .h(px(6.0))
+                                        .rounded_full()
+                                        .bg(cursor.color)
+                                        .when(cursor.is_leader, |this| {
+                                            this.border_1().border_color(cx.theme().colors().text)
+                                        })
+                                        .when_some(cursor.user_name.clone(), |this, name| {
+                                            this.tooltip(Tooltip::text(name))
+                                        })
+                                })
+                                .collect::<Vec<_>>();
+
                             div()
                                 .id(ix)
                                 .when(should_apply_padding, |this| this.pb_3())
@@ -235,7 +285,15 @@ impl MarkdownPreviewView {
                                         div()
                                             .relative()
                                             .child(div().pl_4().child(rendered_block))
-                                            .child(indicator.absolute().left_0().top_0()),
+                                            .child(indicator.absolute().left_0().top_0())
+                                            .child(
+                                                h_flex()
+                                                    .absolute()
+                                                    .right_0()
+                                                    .top_1()
+                                                    .gap_0p5()
+                                                    .children(collaborator_cursors),
+                                            ),
                                     )
                                 })
                                 .into_any()
@@ -258,6 +316,10 @@ impl MarkdownPreviewView {
                 fallback_tab_description: fallback_description
                     .unwrap_or_else(|| "Markdown Preview".into()),
                 parsing_markdown_task: None,
+                collaborator_cursors: Vec::new(),
+                leader_block: None,
+                edited_since_parse: false,
+                unformatted_table: None,
             };
 
             this.set_editor(active_editor, window, cx);
@@ -317,14 +379,17 @@ impl MarkdownPreviewView {
             window,
             |this, editor, event: &EditorEvent, window, cx| {
                 match event {
-                    EditorEvent::Edited { .. }
-                    | EditorEvent::DirtyChanged
-                    | EditorEvent::ExcerptsEdited { .. } => {
+                    EditorEvent::Edited { .. } => {
+                        this.edited_since_parse = true;
+                        this.parse_markdown_from_active_editor(true, window, cx);
+                    }
+                    EditorEvent::DirtyChanged | EditorEvent::ExcerptsEdited { .. } => {
                         this.parse_markdown_from_active_editor(true, window, cx);
                     }
                     EditorEvent::SelectionsChanged { .. } => {
                         let selection_range = editor
                             .update(cx, |editor, cx| editor.selections.last::<usize>(cx).range());
+                        this.format_table_if_cursor_left(selection_range.start, cx);
                         this.selected_block = this.get_block_index_under_cursor(selection_range);
                         this.list_state.scroll_to_reveal_item(this.selected_block);
                         cx.notify();
@@ -334,6 +399,12 @@ impl MarkdownPreviewView {
             },
         );
 
+        // Collaborators' selections don't emit events, but the editor is notified when
+        // they change.
+        let observation = cx.observe_in(&editor, window, |this, editor, window, cx| {
+            this.update_collaborator_cursors(&editor, window, cx);
+        });
+
         self.tab_description = editor
             .read(cx)
             .tab_description(0, cx)
@@ -342,7 +413,10 @@ impl MarkdownPreviewView {
         self.active_editor = Some(EditorState {
             editor,
             _subscription: subscription,
+            _observation: observation,
         });
+        self.edited_since_parse = false;
+        self.unformatted_table = None;
 
         self.parse_markdown_from_active_editor(false, window, cx);
     }
@@ -392,6 +466,9 @@ impl MarkdownPreviewView {
             view.update(cx, move |view, cx| {
                 let markdown_blocks_count = contents.children.len();
                 view.contents = Some(contents);
+                if std::mem::take(&mut view.edited_since_parse) {
+                    view.track_edited_table(&editor, cx);
+                }
                 let scroll_top = view.list_state.logical_scroll_top();
                 view.list_state.reset(markdown_blocks_count);
                 view.list_state.scroll_to(scroll_top);
@@ -400,6 +477,140 @@ impl MarkdownPreviewView {
         })
     }
 
+    fn update_collaborator_cursors(
+        &mut self,
+        editor: &Entity<Editor>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let cursors = editor.update(cx, |editor, cx| {
+            let snapshot = editor.snapshot(window, cx);
+            let Some(collaboration_hub) = editor.collaboration_hub() else {
+                return Vec::new();
+            };
+            let leader_peer_id = editor.leader_peer_id();
+            let range = Anchor::min()..Anchor::max();
+            snapshot
+                .remote_selections_in_range(&range, collaboration_hub, cx)
+                .map(|selection| {
+                    let color = selection
+                        .participant_index
+                        .map(|index| cx.theme().players().color_for_participant(index.0))
+                        .unwrap_or_else(|| cx.theme().players().absent())
+                        .cursor;
+                    (
+                        selection
+                            .selection
+                            .head()
+                            .to_offset(&snapshot.buffer_snapshot),
+                        color,
+                        selection.user_name,
+                        Some(selection.peer_id) == leader_peer_id,
+                    )
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let collaborator_cursors = cursors
+            .into_iter()
+            .map(|(offset, color, user_name, is_leader)| CollaboratorCursor {
+                block: self.get_block_index_under_cursor(offset..offset),
+                offset,
+                color,
+                user_name,
+                is_leader,
+            })
+            .collect::<Vec<_>>();
+
+        // Keep the block that the followed collaborator is editing in view.
+        let leader_block = collaborator_cursors
+            .iter()
+            .find(|cursor| cursor.is_leader)
+            .map(|cursor| cursor.block);
+        if leader_block != self.leader_block {
+            if let Some(block) = leader_block {
+                self.list_state.scroll_to_reveal_item(block);
+            }
+            self.leader_block = leader_block;
+        }
+
+        let changed = collaborator_cursors.len() != self.collaborator_cursors.len()
+            || collaborator_cursors
+                .iter()
+                .zip(&self.collaborator_cursors)
+                .any(|(new, old)| new.block != old.block || new.color != old.color);
+        self.collaborator_cursors = collaborator_cursors;
+        if changed {
+            cx.notify();
+        }
+    }
+
+    /// Remembers the table containing the cursor after an edit, so that it can be
+    /// reformatted once the user is done editing it.
+    fn track_edited_table(&mut self, editor: &Entity<Editor>, cx: &mut Context<Self>) {
+        let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+            return;
+        };
+        let cursor = editor.update(cx, |editor, cx| {
+            editor.selections.newest::<usize>(cx).head()
+        });
+        let table_range = self.contents.as_ref().and_then(|contents| {
+            contents.children.iter().find_map(|block| match block {
+                ParsedMarkdownElement::Table(table) if table.source_range.contains(&cursor) => {
+                    Some(table.source_range.clone())
+                }
+                _ => None,
+            })
+        });
+        if let Some(range) = table_range {
+            let buffer = buffer.read(cx);
+            self.unformatted_table =
+                Some(buffer.anchor_before(range.start)..buffer.anchor_after(range.end));
+        }
+    }
+
+    fn format_table_if_cursor_left(&mut self, cursor: usize, cx: &mut Context<Self>) {
+        let Some(table_range) = self.unformatted_table.clone() else {
+            return;
+        };
+        let Some(buffer) = self
+            .active_editor
+            .as_ref()
+            .and_then(|state| state.editor.read(cx).buffer().read(cx).as_singleton())
+        else {
+            self.unformatted_table = None;
+            return;
+        };
+
+        let snapshot = buffer.read(cx).snapshot();
+        let range = snapshot.summary_for_anchor::<usize>(&table_range.start)
+            ..snapshot.summary_for_anchor::<usize>(&table_range.end);
+        if range.start <= cursor && cursor <= range.end {
+            return;
+        }
+        self.unformatted_table = None;
+
+        let table = snapshot.text_for_range(range.clone()).collect::<String>();
+        let Some(formatted) = format_markdown_table(&table) else {
+            return;
+        };
+        if formatted == table {
+            return;
+        }
+
+        // Apply the formatting as a diff, so that collaborators' cursors and
+        // anything anchored in the table keep their place.
+        let mut new_text = snapshot.text();
+        new_text.replace_range(range, &formatted);
+        let diff = buffer.read(cx).diff(new_text, cx);
+        cx.spawn(async move |_, cx| {
+            let diff = diff.await;
+            buffer.update(cx, |buffer, cx| buffer.apply_diff(diff, true, cx))?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn move_cursor_to_block(
         &self,
         window: &mut Window,
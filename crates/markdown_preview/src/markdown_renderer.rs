@@ -1,12 +1,14 @@
 use crate::markdown_elements::{
     HeadingLevel, Link, MarkdownParagraph, MarkdownParagraphChunk, ParsedMarkdown,
     ParsedMarkdownBlockQuote, ParsedMarkdownCodeBlock, ParsedMarkdownElement,
-    ParsedMarkdownHeading, ParsedMarkdownListItem, ParsedMarkdownListItemType, ParsedMarkdownTable,
-    ParsedMarkdownTableAlignment, ParsedMarkdownTableRow,
+    ParsedMarkdownHeading, ParsedMarkdownListItem, ParsedMarkdownListItemType, ParsedMarkdownMath,
+    ParsedMarkdownMermaidDiagram, ParsedMarkdownTable, ParsedMarkdownTableAlignment,
+    ParsedMarkdownTableRow,
 };
+use crate::markdown_mermaid::DiagramColors;
 use gpui::{
     div, img, px, rems, AbsoluteLength, AnyElement, App, AppContext as _, ClipboardItem, Context,
-    DefiniteLength, Div, Element, ElementId, Entity, HighlightStyle, Hsla, ImageSource,
+    DefiniteLength, Div, Element, ElementId, Entity, FontStyle, HighlightStyle, Hsla, ImageSource,
     InteractiveText, IntoElement, Keystroke, Length, Modifiers, ParentElement, Render, Resource,
     SharedString, Styled, StyledText, TextStyle, WeakEntity, Window,
 };
@@ -19,13 +21,14 @@ use std::{
 use theme::{ActiveTheme, SyntaxTheme, ThemeSettings};
 use ui::{
     h_flex, relative, tooltip_container, v_flex, ButtonCommon, Checkbox, Clickable, Color,
-    FluentBuilder, IconButton, IconName, IconSize, InteractiveElement, Label, LabelCommon,
+    FluentBuilder, Icon, IconButton, IconName, IconSize, InteractiveElement, Label, LabelCommon,
     LabelSize, LinkPreview, StatefulInteractiveElement, StyledExt, StyledImage, ToggleState,
     Tooltip, VisibleOnHover,
 };
 use workspace::{OpenOptions, OpenVisible, Workspace};
 
 type CheckboxClickedCallback = Arc<Box<dyn Fn(bool, Range<usize>, &mut Window, &mut App)>>;
+type HeadingClickedCallback = Arc<Box<dyn Fn(Range<usize>, &mut Window, &mut App)>>;
 
 #[derive(Clone)]
 pub struct RenderContext {
@@ -39,9 +42,11 @@ pub struct RenderContext {
     text_muted_color: Hsla,
     code_block_background_color: Hsla,
     code_span_background_color: Hsla,
+    diagram_colors: Arc<DiagramColors>,
     syntax_theme: Arc<SyntaxTheme>,
     indent: usize,
     checkbox_clicked_callback: Option<CheckboxClickedCallback>,
+    heading_clicked_callback: Option<HeadingClickedCallback>,
 }
 
 impl RenderContext {
@@ -70,7 +75,15 @@ impl RenderContext {
             text_muted_color: theme.colors().text_muted,
             code_block_background_color: theme.colors().surface_background,
             code_span_background_color: theme.colors().editor_document_highlight_read_background,
+            diagram_colors: Arc::new(DiagramColors {
+                node_background: css_color(theme.colors().element_background),
+                node_border: css_color(theme.colors().border),
+                text: css_color(theme.colors().text),
+                edge: css_color(theme.colors().text_muted),
+                background: css_color(theme.colors().editor_background),
+            }),
             checkbox_clicked_callback: None,
+            heading_clicked_callback: None,
         }
     }
 
@@ -82,6 +95,14 @@ impl RenderContext {
         self
     }
 
+    pub fn with_heading_clicked_callback(
+        mut self,
+        callback: impl Fn(Range<usize>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.heading_clicked_callback = Some(Arc::new(Box::new(callback)));
+        self
+    }
+
     fn next_id(&mut self, span: &Range<usize>) -> ElementId {
         let id = format!("markdown-{}-{}-{}", self.next_id, span.start, span.end);
         self.next_id += 1;
@@ -135,10 +156,21 @@ pub fn render_markdown_block(block: &ParsedMarkdownElement, cx: &mut RenderConte
         Table(table) => render_markdown_table(table, cx),
         BlockQuote(block_quote) => render_markdown_block_quote(block_quote, cx),
         CodeBlock(code_block) => render_markdown_code_block(code_block, cx),
+        MermaidDiagram(diagram) => render_markdown_mermaid_diagram(diagram, cx),
         HorizontalRule(_) => render_markdown_rule(cx),
     }
 }
 
+fn css_color(color: Hsla) -> String {
+    let color = color.to_rgb();
+    format!(
+        "rgb({},{},{})",
+        (color.r * 255.).round(),
+        (color.g * 255.).round(),
+        (color.b * 255.).round()
+    )
+}
+
 fn render_markdown_heading(parsed: &ParsedMarkdownHeading, cx: &mut RenderContext) -> AnyElement {
     let size = match parsed.level {
         HeadingLevel::H1 => rems(2.),
@@ -157,6 +189,7 @@ fn render_markdown_heading(parsed: &ParsedMarkdownHeading, cx: &mut RenderContex
     let line_height = DefiniteLength::from(size.mul(1.25));
 
     div()
+        .id(cx.next_id(&parsed.source_range))
         .line_height(line_height)
         .text_size(size)
         .text_color(color)
@@ -164,6 +197,11 @@ fn render_markdown_heading(parsed: &ParsedMarkdownHeading, cx: &mut RenderContex
         .pb_1()
         .children(render_markdown_text(&parsed.contents, cx))
         .whitespace_normal()
+        .when_some(cx.heading_clicked_callback.clone(), |this, callback| {
+            let source_range = parsed.source_range.clone();
+            this.cursor_pointer()
+                .on_click(move |_, window, cx| callback(source_range.clone(), window, cx))
+        })
         .into_any()
 }
 
@@ -202,18 +240,14 @@ fn render_markdown_list_item(
                                     _ => return,
                                 };
 
-                                if window.modifiers().secondary() {
-                                    callback(checked, range.clone(), window, cx);
-                                }
+                                callback(checked, range.clone(), window, cx);
                             }
                         })
                     },
                 ),
             )
             .hover(|s| s.cursor_pointer())
-            .tooltip(|_, cx| {
-                InteractiveMarkdownElementTooltip::new(None, "toggle checkbox", cx).into()
-            })
+            .tooltip(Tooltip::text("Toggle checkbox"))
             .into_any_element(),
     };
     let bullet = div().mr_2().child(bullet);
@@ -239,6 +273,10 @@ fn paragraph_len(paragraphs: &MarkdownParagraph) -> usize {
             MarkdownParagraphChunk::Text(text) => text.contents.len(),
             // TODO: Scale column width based on image size
             MarkdownParagraphChunk::Image(_) => 1,
+            MarkdownParagraphChunk::DisplayMath(math) => match &math.rendered {
+                Ok(rendered) => rendered.len(),
+                Err(_) => math.contents.len(),
+            },
         })
         .sum()
 }
@@ -411,6 +449,90 @@ fn render_markdown_code_block(
         .into_any()
 }
 
+fn render_markdown_mermaid_diagram(
+    parsed: &ParsedMarkdownMermaidDiagram,
+    cx: &mut RenderContext,
+) -> AnyElement {
+    let flowchart = match &parsed.flowchart {
+        Ok(flowchart) => flowchart,
+        Err(error) => {
+            return render_raw_block_fallback(
+                format!("```mermaid\n{}\n```", parsed.contents),
+                error.clone(),
+                cx,
+            )
+        }
+    };
+
+    let diagram = parsed
+        .image_cache
+        .get_or_draw(flowchart, &cx.diagram_colors);
+    cx.with_common_p(div())
+        .id(cx.next_id(&parsed.source_range))
+        .w_full()
+        .overflow_x_scroll()
+        .child(
+            img(ImageSource::Image(diagram.image))
+                .w(px(diagram.width))
+                .h(px(diagram.height)),
+        )
+        .into_any()
+}
+
+/// Shows the source of a block that couldn't be rendered, along with the reason.
+fn render_raw_block_fallback(
+    source: String,
+    error: SharedString,
+    cx: &mut RenderContext,
+) -> AnyElement {
+    cx.with_common_p(div())
+        .font_family(cx.buffer_font_family.clone())
+        .px_3()
+        .py_3()
+        .bg(cx.code_block_background_color)
+        .rounded_sm()
+        .child(
+            h_flex()
+                .gap_1()
+                .pb_1()
+                .child(
+                    Icon::new(IconName::Warning)
+                        .size(IconSize::Small)
+                        .color(Color::Warning),
+                )
+                .child(
+                    Label::new(format!("Couldn't render this block: {error}"))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+        )
+        .child(StyledText::new(source))
+        .into_any()
+}
+
+fn render_markdown_display_math(parsed: &ParsedMarkdownMath, cx: &mut RenderContext) -> AnyElement {
+    match &parsed.rendered {
+        Ok(rendered) => h_flex()
+            .w_full()
+            .justify_center()
+            .py_1()
+            .child(StyledText::new(rendered.clone()).with_default_highlights(
+                &cx.text_style,
+                [(
+                    0..rendered.len(),
+                    HighlightStyle {
+                        font_style: Some(FontStyle::Italic),
+                        ..Default::default()
+                    },
+                )],
+            ))
+            .into_any(),
+        Err(error) => {
+            render_raw_block_fallback(format!("$${}$$", parsed.contents), error.clone(), cx)
+        }
+    }
+}
+
 fn render_markdown_paragraph(parsed: &MarkdownParagraph, cx: &mut RenderContext) -> AnyElement {
     cx.with_common_p(div())
         .children(render_markdown_text(parsed, cx))
@@ -573,6 +695,10 @@ fn render_markdown_text(parsed_new: &MarkdownParagraph, cx: &mut RenderContext)
                     .into_any();
                 any_element.push(image_element);
             }
+
+            MarkdownParagraphChunk::DisplayMath(math) => {
+                any_element.push(render_markdown_display_math(math, cx));
+            }
         }
     }
 
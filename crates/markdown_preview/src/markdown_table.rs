@@ -0,0 +1,150 @@
+use crate::markdown_elements::ParsedMarkdownTableAlignment;
+
+/// Pads the cells of a markdown table so that its columns line up, keeping the
+/// alignment markers of its delimiter row. Returns `None` if `source` isn't a table.
+pub fn format_markdown_table(source: &str) -> Option<String> {
+    let indent = &source[..source.len() - source.trim_start_matches([' ', '\t']).len()];
+    let rows = source
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(split_row)
+        .collect::<Vec<_>>();
+    if rows.len() < 2 {
+        return None;
+    }
+    let alignments = rows[1]
+        .iter()
+        .map(|cell| parse_alignment(cell))
+        .collect::<Option<Vec<_>>>()?;
+    let column_count = rows.iter().map(Vec::len).max()?;
+
+    // Delimiter rows need at least three characters per column.
+    let mut widths = vec![3; column_count];
+    for (ix, row) in rows.iter().enumerate() {
+        if ix == 1 {
+            continue;
+        }
+        for (column, cell) in row.iter().enumerate() {
+            widths[column] = widths[column].max(cell.chars().count());
+        }
+    }
+
+    let mut output = String::new();
+    for (ix, row) in rows.iter().enumerate() {
+        output.push_str(indent);
+        output.push('|');
+        for (column, width) in widths.iter().copied().enumerate() {
+            let alignment = alignments
+                .get(column)
+                .copied()
+                .unwrap_or(ParsedMarkdownTableAlignment::None);
+            let cell = if ix == 1 {
+                delimiter_cell(alignment, width)
+            } else {
+                pad_cell(row.get(column).map_or("", String::as_str), alignment, width)
+            };
+            output.push(' ');
+            output.push_str(&cell);
+            output.push_str(" |");
+        }
+        output.push('\n');
+    }
+    if !source.ends_with('\n') {
+        output.pop();
+    }
+    Some(output)
+}
+
+/// Splits a table row into its trimmed cells, leaving escaped pipes alone.
+fn split_row(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = match line.strip_suffix('|') {
+        Some(stripped) if !stripped.ends_with('\\') => stripped,
+        _ => line,
+    };
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut escaped = false;
+    for character in line.chars() {
+        if character == '|' && !escaped {
+            cells.push(cell.trim().to_string());
+            cell.clear();
+        } else {
+            cell.push(character);
+        }
+        escaped = character == '\\' && !escaped;
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+fn parse_alignment(cell: &str) -> Option<ParsedMarkdownTableAlignment> {
+    let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+    if dashes.is_empty() || !dashes.chars().all(|character| character == '-') {
+        return None;
+    }
+    Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+        (true, true) => ParsedMarkdownTableAlignment::Center,
+        (true, false) => ParsedMarkdownTableAlignment::Left,
+        (false, true) => ParsedMarkdownTableAlignment::Right,
+        (false, false) => ParsedMarkdownTableAlignment::None,
+    })
+}
+
+fn delimiter_cell(alignment: ParsedMarkdownTableAlignment, width: usize) -> String {
+    match alignment {
+        ParsedMarkdownTableAlignment::None => "-".repeat(width),
+        ParsedMarkdownTableAlignment::Left => format!(":{}", "-".repeat(width - 1)),
+        ParsedMarkdownTableAlignment::Right => format!("{}:", "-".repeat(width - 1)),
+        ParsedMarkdownTableAlignment::Center => format!(":{}:", "-".repeat(width - 2)),
+    }
+}
+
+fn pad_cell(cell: &str, alignment: ParsedMarkdownTableAlignment, width: usize) -> String {
+    let padding = width - cell.chars().count();
+    let (left, right) = match alignment {
+        ParsedMarkdownTableAlignment::None | ParsedMarkdownTableAlignment::Left => (0, padding),
+        ParsedMarkdownTableAlignment::Right => (padding, 0),
+        ParsedMarkdownTableAlignment::Center => (padding / 2, padding - padding / 2),
+    };
+    format!("{}{}{}", " ".repeat(left), cell, " ".repeat(right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_format_markdown_table() {
+        let table =
+            "| Name | Count | Notes |\n|:--|:-:|--:|\n| apples | 3 | fresh |\n| kiwis | 12 |\n";
+        assert_eq!(
+            format_markdown_table(table).unwrap(),
+            "| Name   | Count | Notes |\n\
+             | :----- | :---: | ----: |\n\
+             | apples |   3   | fresh |\n\
+             | kiwis  |  12   |       |\n"
+        );
+    }
+
+    #[test]
+    fn test_format_markdown_table_keeps_escaped_pipes_and_indent() {
+        let table = "  a | b\n  --- | ---\n  x \\| y | z";
+        assert_eq!(
+            format_markdown_table(table).unwrap(),
+            "  | a      | b   |\n  | ------ | --- |\n  | x \\| y | z   |"
+        );
+    }
+
+    #[test]
+    fn test_format_markdown_table_rejects_other_text() {
+        assert_eq!(
+            format_markdown_table("| a | b |\n| not | a delimiter |"),
+            None
+        );
+        assert_eq!(format_markdown_table("| a | b |"), None);
+    }
+}
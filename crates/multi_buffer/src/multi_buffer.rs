@@ -2466,10 +2466,7 @@ impl MultiBuffer {
             .map(|excerpt| excerpt.buffer.remote_id());
         buffer_id
             .and_then(|buffer_id| self.buffer(buffer_id))
-            .map(|buffer| {
-                let buffer = buffer.read(cx);
-                language_settings(buffer.language().map(|l| l.name()), buffer.file(), cx)
-            })
+            .map(|buffer| buffer.read(cx).settings(cx))
             .unwrap_or_else(move || self.language_settings_at(0, cx))
     }
 
@@ -2478,14 +2475,10 @@ impl MultiBuffer {
         point: T,
         cx: &'a App,
     ) -> Cow<'a, LanguageSettings> {
-        let mut language = None;
-        let mut file = None;
         if let Some((buffer, offset)) = self.point_to_buffer_offset(point, cx) {
-            let buffer = buffer.read(cx);
-            language = buffer.language_at(offset);
-            file = buffer.file();
+            return buffer.read(cx).settings_at(offset, cx);
         }
-        language_settings(language.map(|l| l.name()), file, cx)
+        language_settings(None, None, cx)
     }
 
     pub fn for_each_buffer(&self, mut f: impl FnMut(&Entity<Buffer>)) {
@@ -5644,8 +5637,7 @@ impl MultiBufferSnapshot {
             }
             let current_depth = indent_stack.len() as u32;
 
-            let settings =
-                language_settings(buffer.language().map(|l| l.name()), buffer.file(), cx);
+            let settings = buffer.settings(cx);
             let tab_size = settings.tab_size.get() as u32;
 
             // When encountering empty, continue until found useful line indent
@@ -5738,14 +5730,7 @@ impl MultiBufferSnapshot {
     fn language_settings<'a>(&'a self, cx: &'a App) -> Cow<'a, LanguageSettings> {
         self.excerpts
             .first()
-            .map(|excerpt| &excerpt.buffer)
-            .map(|buffer| {
-                language_settings(
-                    buffer.language().map(|language| language.name()),
-                    buffer.file(),
-                    cx,
-                )
-            })
+            .map(|excerpt| excerpt.buffer.settings(cx))
             .unwrap_or_else(move || self.language_settings_at(0, cx))
     }
 
@@ -5754,13 +5739,10 @@ impl MultiBufferSnapshot {
         point: T,
         cx: &'a App,
     ) -> Cow<'a, LanguageSettings> {
-        let mut language = None;
-        let mut file = None;
         if let Some((buffer, offset)) = self.point_to_buffer_offset(point) {
-            language = buffer.language_at(offset);
-            file = buffer.file();
+            return buffer.settings_at(offset, cx);
         }
-        language_settings(language.map(|l| l.name()), file, cx)
+        language_settings(None, None, cx)
     }
 
     pub fn language_scope_at<T: ToOffset>(&self, point: T) -> Option<LanguageScope> {
@@ -5867,28 +5849,7 @@ impl MultiBufferSnapshot {
             outline
                 .items
                 .into_iter()
-                .flat_map(|item| {
-                    Some(OutlineItem {
-                        depth: item.depth,
-                        range: self.anchor_in_excerpt(*excerpt_id, item.range.start)?
-                            ..self.anchor_in_excerpt(*excerpt_id, item.range.end)?,
-                        text: item.text,
-                        highlight_ranges: item.highlight_ranges,
-                        name_ranges: item.name_ranges,
-                        body_range: item.body_range.and_then(|body_range| {
-                            Some(
-                                self.anchor_in_excerpt(*excerpt_id, body_range.start)?
-                                    ..self.anchor_in_excerpt(*excerpt_id, body_range.end)?,
-                            )
-                        }),
-                        annotation_range: item.annotation_range.and_then(|annotation_range| {
-                            Some(
-                                self.anchor_in_excerpt(*excerpt_id, annotation_range.start)?
-                                    ..self.anchor_in_excerpt(*excerpt_id, annotation_range.end)?,
-                            )
-                        }),
-                    })
-                })
+                .flat_map(|item| self.outline_item_in_excerpt(*excerpt_id, item))
                 .collect(),
         ))
     }
@@ -5908,32 +5869,72 @@ impl MultiBufferSnapshot {
                 .symbols_containing(anchor.text_anchor, theme)
                 .into_iter()
                 .flatten()
-                .flat_map(|item| {
-                    Some(OutlineItem {
-                        depth: item.depth,
-                        range: self.anchor_in_excerpt(excerpt_id, item.range.start)?
-                            ..self.anchor_in_excerpt(excerpt_id, item.range.end)?,
-                        text: item.text,
-                        highlight_ranges: item.highlight_ranges,
-                        name_ranges: item.name_ranges,
-                        body_range: item.body_range.and_then(|body_range| {
-                            Some(
-                                self.anchor_in_excerpt(excerpt_id, body_range.start)?
-                                    ..self.anchor_in_excerpt(excerpt_id, body_range.end)?,
-                            )
-                        }),
-                        annotation_range: item.annotation_range.and_then(|body_range| {
-                            Some(
-                                self.anchor_in_excerpt(excerpt_id, body_range.start)?
-                                    ..self.anchor_in_excerpt(excerpt_id, body_range.end)?,
-                            )
-                        }),
-                    })
-                })
+                .flat_map(|item| self.outline_item_in_excerpt(excerpt_id, item))
                 .collect(),
         ))
     }
 
+    /// The symbols in the same parent symbol as the one at the given index of the symbols
+    /// containing the offset, including that one, in the order they appear in the buffer.
+    pub fn sibling_symbols<T: ToOffset>(
+        &self,
+        offset: T,
+        symbol_ix: usize,
+        theme: Option<&SyntaxTheme>,
+    ) -> Option<Vec<OutlineItem<Anchor>>> {
+        let anchor = self.anchor_before(offset);
+        let excerpt_id = anchor.excerpt_id;
+        let buffer = &self.excerpt(excerpt_id)?.buffer;
+        let symbols = buffer.symbols_containing(anchor.text_anchor, None)?;
+        let symbol = symbols.get(symbol_ix)?;
+        let parent_range = symbol_ix
+            .checked_sub(1)
+            .and_then(|parent_ix| symbols.get(parent_ix))
+            .map(|parent| parent.range.to_offset(buffer));
+        Some(
+            buffer
+                .outline(theme)?
+                .items
+                .into_iter()
+                .filter(|item| {
+                    item.depth == symbol.depth
+                        && parent_range.as_ref().map_or(true, |parent_range| {
+                            let range = item.range.to_offset(buffer);
+                            parent_range.start <= range.start && range.end <= parent_range.end
+                        })
+                })
+                .flat_map(|item| self.outline_item_in_excerpt(excerpt_id, item))
+                .collect(),
+        )
+    }
+
+    fn outline_item_in_excerpt(
+        &self,
+        excerpt_id: ExcerptId,
+        item: OutlineItem<text::Anchor>,
+    ) -> Option<OutlineItem<Anchor>> {
+        Some(OutlineItem {
+            depth: item.depth,
+            range: self.anchor_in_excerpt(excerpt_id, item.range.start)?
+                ..self.anchor_in_excerpt(excerpt_id, item.range.end)?,
+            text: item.text,
+            highlight_ranges: item.highlight_ranges,
+            name_ranges: item.name_ranges,
+            body_range: item.body_range.and_then(|body_range| {
+                Some(
+                    self.anchor_in_excerpt(excerpt_id, body_range.start)?
+                        ..self.anchor_in_excerpt(excerpt_id, body_range.end)?,
+                )
+            }),
+            annotation_range: item.annotation_range.and_then(|annotation_range| {
+                Some(
+                    self.anchor_in_excerpt(excerpt_id, annotation_range.start)?
+                        ..self.anchor_in_excerpt(excerpt_id, annotation_range.end)?,
+                )
+            }),
+        })
+    }
+
     fn excerpt_locator_for_id(&self, id: ExcerptId) -> &Locator {
         if id == ExcerptId::min() {
             Locator::min_ref()
@@ -0,0 +1,488 @@
+use anyhow::{anyhow, Result};
+use db::kvp::KEY_VALUE_STORE;
+use fs::Fs;
+use gpui::{
+    actions, Action, App, AsyncWindowContext, Context, Corner, Entity, EventEmitter, FocusHandle,
+    Focusable, PromptLevel, Subscription, Task, WeakEntity, Window,
+};
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore};
+use std::sync::Arc;
+use ui::{prelude::*, ContextMenu, IconButton, PopoverMenu, Tab, Tooltip};
+use util::ResultExt;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    notifications::{NotificationHistory, NotificationRecord, NotificationSource},
+    NotificationCenterSettings, Workspace,
+};
+
+const NOTIFICATION_CENTER_KEY: &str = "NotificationCenter";
+
+actions!(
+    notification_center,
+    [ToggleFocus, ToggleDoNotDisturb, ClearNotifications]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace
+            .register_action(|workspace, _: &ToggleFocus, window, cx| {
+                workspace.toggle_panel_focus::<NotificationCenter>(window, cx);
+            })
+            .register_action(|workspace, _: &ToggleDoNotDisturb, _, cx| {
+                toggle_do_not_disturb(workspace.app_state().fs.clone(), cx);
+            })
+            .register_action(|_, _: &ClearNotifications, _, cx| {
+                if let Some(history) = NotificationHistory::global(cx) {
+                    history.update(cx, |history, cx| history.clear(cx));
+                }
+            });
+    })
+    .detach();
+}
+
+fn toggle_do_not_disturb(fs: Arc<dyn Fs>, cx: &App) {
+    let enabled = NotificationCenterSettings::get_global(cx)
+        .do_not_disturb
+        .enabled;
+    settings::update_settings_file::<NotificationCenterSettings>(fs, cx, move |settings, _| {
+        settings.do_not_disturb.get_or_insert_default().enabled = !enabled;
+    });
+}
+
+/// Lists the notifications shown in any workspace, including the ones that
+/// were held back because their source is muted or "do not disturb" was on.
+pub struct NotificationCenter {
+    history: Entity<NotificationHistory>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    active: bool,
+    source_filter: Option<NotificationSource>,
+    pending_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedNotificationCenter {
+    width: Option<Pixels>,
+}
+
+impl NotificationCenter {
+    fn new(
+        history: Entity<NotificationHistory>,
+        workspace: &mut Workspace,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        cx.new(|cx| {
+            let subscriptions = vec![
+                cx.observe(&history, |this, history, cx| {
+                    if this.active {
+                        history.update(cx, |history, cx| history.mark_all_read(cx));
+                    }
+                    cx.notify();
+                }),
+                cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+            ];
+            Self {
+                history,
+                fs,
+                focus_handle: cx.focus_handle(),
+                width: None,
+                active: false,
+                source_filter: None,
+                pending_serialization: Task::ready(None),
+                _subscriptions: subscriptions,
+            }
+        })
+    }
+
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let serialized_panel = if let Some(panel) = cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(NOTIFICATION_CENTER_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedNotificationCenter>(
+                    &panel,
+                )?)
+            } else {
+                None
+            };
+
+            workspace.update_in(cx, |workspace, _, cx| {
+                let history = NotificationHistory::global(cx)
+                    .ok_or_else(|| anyhow!("notification history is not initialized"))?;
+                let panel = Self::new(history, workspace, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|w| w.round());
+                        cx.notify();
+                    });
+                }
+                Ok(panel)
+            })?
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        NOTIFICATION_CENTER_KEY.into(),
+                        serde_json::to_string(&SerializedNotificationCenter { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn is_do_not_disturb_active(cx: &App) -> bool {
+        NotificationCenterSettings::get_global(cx)
+            .do_not_disturb
+            .is_active_at(chrono::Local::now().time())
+    }
+
+    fn toggle_muted(&mut self, source: NotificationSource, cx: &mut App) {
+        let mut muted_sources = NotificationCenterSettings::get_global(cx)
+            .muted_sources
+            .clone();
+        if let Some(ix) = muted_sources.iter().position(|muted| *muted == source) {
+            muted_sources.remove(ix);
+        } else {
+            muted_sources.push(source);
+        }
+        settings::update_settings_file::<NotificationCenterSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.muted_sources = Some(muted_sources),
+        );
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let do_not_disturb = Self::is_do_not_disturb_active(cx);
+        let this = cx.entity().downgrade();
+
+        h_flex()
+            .justify_between()
+            .px_2()
+            .py_1()
+            // Match the height of the tab bar so they line up.
+            .h(Tab::container_height(cx))
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(Label::new("Notifications"))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new(
+                            "toggle-do-not-disturb",
+                            if do_not_disturb {
+                                IconName::BellOff
+                            } else {
+                                IconName::Bell
+                            },
+                        )
+                        .icon_size(IconSize::Small)
+                        .toggle_state(do_not_disturb)
+                        .tooltip(move |window, cx| {
+                            Tooltip::for_action(
+                                if do_not_disturb {
+                                    "Turn Off Do Not Disturb"
+                                } else {
+                                    "Turn On Do Not Disturb"
+                                },
+                                &ToggleDoNotDisturb,
+                                window,
+                                cx,
+                            )
+                        })
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(ToggleDoNotDisturb.boxed_clone(), cx)
+                        }),
+                    )
+                    .child(
+                        PopoverMenu::new("mute-notification-sources")
+                            .trigger(
+                                IconButton::new("mute-sources", IconName::Filter)
+                                    .icon_size(IconSize::Small)
+                                    .tooltip(Tooltip::text("Mute Sources")),
+                            )
+                            .anchor(Corner::TopRight)
+                            .menu(move |window, cx| {
+                                let this = this.clone();
+                                let muted_sources = NotificationCenterSettings::get_global(cx)
+                                    .muted_sources
+                                    .clone();
+                                Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                                    menu = menu.header("Mute Popups From");
+                                    for source in NotificationSource::ALL {
+                                        let this = this.clone();
+                                        menu = menu.toggleable_entry(
+                                            source.label(),
+                                            muted_sources.contains(&source),
+                                            IconPosition::Start,
+                                            None,
+                                            move |_, cx| {
+                                                this.update(cx, |this, cx| {
+                                                    this.toggle_muted(source, cx)
+                                                })
+                                                .ok();
+                                            },
+                                        );
+                                    }
+                                    menu
+                                }))
+                            }),
+                    )
+                    .child(
+                        IconButton::new("clear-notifications", IconName::Trash)
+                            .icon_size(IconSize::Small)
+                            .tooltip(move |window, cx| {
+                                Tooltip::for_action(
+                                    "Clear Notifications",
+                                    &ClearNotifications,
+                                    window,
+                                    cx,
+                                )
+                            })
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(ClearNotifications.boxed_clone(), cx)
+                            }),
+                    ),
+            )
+    }
+
+    fn render_filters(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let muted_sources = &NotificationCenterSettings::get_global(cx).muted_sources;
+        h_flex()
+            .flex_wrap()
+            .gap_1()
+            .px_2()
+            .py_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                Button::new("filter-all", "All")
+                    .label_size(LabelSize::Small)
+                    .toggle_state(self.source_filter.is_none())
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.source_filter = None;
+                        cx.notify();
+                    })),
+            )
+            .children(NotificationSource::ALL.into_iter().map(|source| {
+                let is_muted = muted_sources.contains(&source);
+                Button::new(source.label(), source.label())
+                    .icon(if is_muted {
+                        IconName::BellOff
+                    } else {
+                        source.icon()
+                    })
+                    .icon_position(IconPosition::Start)
+                    .icon_size(IconSize::XSmall)
+                    .icon_color(Color::Muted)
+                    .label_size(LabelSize::Small)
+                    .toggle_state(self.source_filter == Some(source))
+                    .when(is_muted, |button| {
+                        button.tooltip(Tooltip::text("Popups from this source are muted"))
+                    })
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.source_filter = Some(source);
+                        cx.notify();
+                    }))
+            }))
+    }
+
+    fn render_record(&self, record: &NotificationRecord, cx: &Context<Self>) -> AnyElement {
+        let id = record.id;
+        let color = match record.level {
+            PromptLevel::Info => Color::Muted,
+            PromptLevel::Warning => Color::Warning,
+            PromptLevel::Critical => Color::Error,
+        };
+        let deep_link = record.deep_link.as_ref().map(|action| action.boxed_clone());
+
+        h_flex()
+            .id(("notification", id))
+            .group("notification")
+            .items_start()
+            .gap_2()
+            .px_2()
+            .py_1p5()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                Icon::new(record.source.icon())
+                    .size(IconSize::Small)
+                    .color(color),
+            )
+            .child(
+                v_flex()
+                    .flex_1()
+                    .overflow_hidden()
+                    .child(Label::new(record.message.clone()).size(LabelSize::Small))
+                    .child(
+                        Label::new(format!(
+                            "{} · {}",
+                            record.source.label(),
+                            record.timestamp.format("%b %-d, %H:%M")
+                        ))
+                        .size(LabelSize::XSmall)
+                        .color(Color::Muted),
+                    ),
+            )
+            .child(
+                div()
+                    .invisible()
+                    .group_hover("notification", |style| style.visible())
+                    .child(
+                        IconButton::new(("dismiss-notification", id), IconName::Close)
+                            .icon_size(IconSize::XSmall)
+                            .tooltip(Tooltip::text("Remove"))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.history
+                                    .update(cx, |history, cx| history.remove(id, cx));
+                            })),
+                    ),
+            )
+            .when_some(deep_link, |this, deep_link| {
+                this.cursor_pointer()
+                    .hover(|style| style.bg(cx.theme().colors().element_hover))
+                    .on_click(move |_, window, cx| {
+                        window.dispatch_action(deep_link.boxed_clone(), cx)
+                    })
+            })
+            .into_any_element()
+    }
+}
+
+impl Render for NotificationCenter {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let history = self.history.read(cx);
+        let records = history
+            .records()
+            .rev()
+            .filter(|record| {
+                self.source_filter
+                    .map_or(true, |source| record.source == source)
+            })
+            .map(|record| self.render_record(record, cx))
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .size_full()
+            .track_focus(&self.focus_handle)
+            .child(self.render_header(cx))
+            .child(self.render_filters(cx))
+            .map(|this| {
+                if records.is_empty() {
+                    this.child(
+                        v_flex().p_4().items_center().child(
+                            Label::new("No notifications")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        v_flex()
+                            .id("notification-center-records")
+                            .flex_1()
+                            .overflow_y_scroll()
+                            .children(records),
+                    )
+                }
+            })
+    }
+}
+
+impl Focusable for NotificationCenter {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for NotificationCenter {}
+
+impl Panel for NotificationCenter {
+    fn persistent_name() -> &'static str {
+        "NotificationCenter"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        NotificationCenterSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<NotificationCenterSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| NotificationCenterSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn set_active(&mut self, active: bool, _: &mut Window, cx: &mut Context<Self>) {
+        self.active = active;
+        if active {
+            self.history
+                .update(cx, |history, cx| history.mark_all_read(cx));
+        }
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        if !NotificationCenterSettings::get_global(cx).button {
+            return None;
+        }
+        Some(if Self::is_do_not_disturb_active(cx) {
+            IconName::BellOff
+        } else if self.history.read(cx).unread_count() > 0 {
+            IconName::BellDot
+        } else {
+            IconName::Bell
+        })
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Notification Center")
+    }
+
+    fn icon_label(&self, _: &Window, cx: &App) -> Option<String> {
+        let count = self.history.read(cx).unread_count();
+        (count > 0).then(|| count.to_string())
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        13
+    }
+}
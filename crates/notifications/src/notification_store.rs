@@ -302,6 +302,9 @@ impl NotificationStore {
                     user_ids.push(sender_id);
                     message_ids.push(message_id);
                 }
+                Notification::ReviewComment { author_id, .. } => {
+                    user_ids.push(author_id);
+                }
             }
         }
 
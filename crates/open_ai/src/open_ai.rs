@@ -185,8 +185,13 @@ pub struct Request {
 pub struct CompletionRequest {
     pub model: String,
     pub prompt: String,
+    /// Text following the insertion point, for fill-in-the-middle completions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
     pub max_tokens: u32,
     pub temperature: f32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub prediction: Option<Prediction>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -555,6 +560,75 @@ pub async fn stream_completion(
     }
 }
 
+#[derive(Deserialize, Debug)]
+pub struct ListModelsResponse {
+    pub data: Vec<ModelListing>,
+}
+
+/// A model reported by the `/models` endpoint of an OpenAI-compatible server.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ModelListing {
+    pub id: String,
+    /// The context length reported by vLLM.
+    #[serde(default)]
+    pub max_model_len: Option<usize>,
+    /// The context length reported by some other servers, such as LiteLLM.
+    #[serde(default)]
+    pub context_length: Option<usize>,
+    /// Model metadata reported by the llama.cpp server.
+    #[serde(default)]
+    pub meta: Option<ModelListingMeta>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ModelListingMeta {
+    #[serde(default)]
+    pub n_ctx_train: Option<usize>,
+}
+
+impl ModelListing {
+    /// Returns the context length advertised by the server, if any.
+    pub fn context_length(&self) -> Option<usize> {
+        self.max_model_len
+            .or(self.context_length)
+            .or_else(|| self.meta.as_ref()?.n_ctx_train)
+    }
+}
+
+/// Lists the models served by an OpenAI-compatible API.
+pub async fn list_models(
+    client: &dyn HttpClient,
+    api_url: &str,
+    api_key: Option<&str>,
+) -> Result<Vec<ModelListing>> {
+    let uri = format!("{api_url}/models");
+    let mut request_builder = HttpRequest::builder()
+        .method(Method::GET)
+        .uri(uri)
+        .header("Accept", "application/json");
+    if let Some(api_key) = api_key {
+        request_builder = request_builder.header("Authorization", format!("Bearer {}", api_key));
+    }
+
+    let request = request_builder.body(AsyncBody::default())?;
+    let mut response = client.send(request).await?;
+
+    let mut body = String::new();
+    response.body_mut().read_to_string(&mut body).await?;
+
+    if response.status().is_success() {
+        let response: ListModelsResponse =
+            serde_json::from_str(&body).context("Unable to parse model listing")?;
+        Ok(response.data)
+    } else {
+        Err(anyhow!(
+            "Failed to list models: {} {}",
+            response.status(),
+            body,
+        ))
+    }
+}
+
 #[derive(Copy, Clone, Serialize, Deserialize)]
 pub enum OpenAiEmbeddingModel {
     #[serde(rename = "text-embedding-3-small")]
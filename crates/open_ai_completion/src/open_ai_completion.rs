@@ -0,0 +1,308 @@
+use anyhow::Result;
+use gpui::{App, AppContext as _, Context, Entity, EntityId, Task};
+use http_client::HttpClient;
+use inline_completion::{Direction, EditPredictionProvider, InlineCompletion};
+use language::{
+    language_settings::{all_language_settings, language_settings},
+    Anchor, Bias, Buffer, ToOffset,
+};
+use project::Project;
+use std::{sync::Arc, time::Duration};
+
+pub const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(150);
+
+/// How much text before and after the cursor is sent along with each request, in bytes.
+const MAX_PREFIX_LEN: usize = 8 * 1024;
+const MAX_SUFFIX_LEN: usize = 2 * 1024;
+
+const DEFAULT_MAX_TOKENS: u32 = 128;
+
+/// Supplies edit predictions from a model served through an OpenAI-compatible
+/// `/completions` endpoint, using fill-in-the-middle prompts.
+pub struct OpenAiCompletionProvider {
+    http_client: Arc<dyn HttpClient>,
+    buffer_id: Option<EntityId>,
+    completion: Option<CurrentCompletion>,
+    pending_refresh: Option<Task<Result<()>>>,
+}
+
+struct CurrentCompletion {
+    /// Where the cursor was when the completion was requested.
+    position: Anchor,
+    text: String,
+}
+
+impl OpenAiCompletionProvider {
+    pub fn new(http_client: Arc<dyn HttpClient>) -> Self {
+        Self {
+            http_client,
+            buffer_id: None,
+            completion: None,
+            pending_refresh: None,
+        }
+    }
+}
+
+impl EditPredictionProvider for OpenAiCompletionProvider {
+    fn name() -> &'static str {
+        "open_ai_compatible"
+    }
+
+    fn display_name() -> &'static str {
+        "OpenAI-Compatible"
+    }
+
+    fn show_completions_in_menu() -> bool {
+        false
+    }
+
+    fn is_enabled(&self, buffer: &Entity<Buffer>, cursor_position: Anchor, cx: &App) -> bool {
+        let buffer = buffer.read(cx);
+        let file = buffer.file();
+        let language = buffer.language_at(cursor_position);
+        all_language_settings(file, cx)
+            .edit_predictions
+            .open_ai_compatible
+            .api_url
+            .is_some()
+            && language_settings(language.map(|language| language.name()), file, cx)
+                .show_edit_predictions
+    }
+
+    fn is_refreshing(&self) -> bool {
+        self.pending_refresh.is_some()
+    }
+
+    fn refresh(
+        &mut self,
+        _project: Option<Entity<Project>>,
+        buffer: Entity<Buffer>,
+        cursor_position: Anchor,
+        debounce: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let settings = all_language_settings(None, cx)
+            .edit_predictions
+            .open_ai_compatible
+            .clone();
+        let Some(api_url) = settings.api_url else {
+            return;
+        };
+
+        let snapshot = buffer.read(cx).snapshot();
+        let offset = cursor_position.to_offset(&snapshot);
+        let prefix_start = snapshot.clip_offset(offset.saturating_sub(MAX_PREFIX_LEN), Bias::Right);
+        let suffix_end =
+            snapshot.clip_offset((offset + MAX_SUFFIX_LEN).min(snapshot.len()), Bias::Left);
+        let request = open_ai::CompletionRequest {
+            model: settings.model.unwrap_or_default(),
+            prompt: snapshot.text_for_range(prefix_start..offset).collect(),
+            suffix: Some(snapshot.text_for_range(offset..suffix_end).collect()),
+            max_tokens: settings.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+            temperature: 0.,
+            stop: vec!["\n\n".into()],
+            prediction: None,
+            rewrite_speculation: None,
+        };
+        let position = snapshot.anchor_before(offset);
+
+        let http_client = self.http_client.clone();
+        self.pending_refresh = Some(cx.spawn(async move |this, cx| {
+            if debounce {
+                cx.background_executor().timer(DEBOUNCE_TIMEOUT).await;
+            }
+
+            let response = cx
+                .background_spawn(async move {
+                    open_ai::complete_text(http_client.as_ref(), &api_url, "", request).await
+                })
+                .await;
+
+            this.update(cx, |this, cx| {
+                this.pending_refresh = None;
+                let text = response?
+                    .choices
+                    .into_iter()
+                    .next()
+                    .map(|choice| choice.text)
+                    .unwrap_or_default();
+                this.buffer_id = Some(buffer.entity_id());
+                this.completion = if text.trim().is_empty() {
+                    None
+                } else {
+                    Some(CurrentCompletion { position, text })
+                };
+                cx.notify();
+                Ok(())
+            })?
+        }));
+    }
+
+    fn cycle(
+        &mut self,
+        _buffer: Entity<Buffer>,
+        _cursor_position: Anchor,
+        _direction: Direction,
+        _cx: &mut Context<Self>,
+    ) {
+        // Only a single completion is requested at a time, so there is nothing to cycle through.
+    }
+
+    fn accept(&mut self, _cx: &mut Context<Self>) {
+        self.completion = None;
+    }
+
+    fn discard(&mut self, _cx: &mut Context<Self>) {
+        self.pending_refresh = None;
+        self.completion = None;
+    }
+
+    fn suggest(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cursor_position: Anchor,
+        cx: &mut Context<Self>,
+    ) -> Option<InlineCompletion> {
+        if self.buffer_id != Some(buffer.entity_id()) {
+            return None;
+        }
+        let completion = self.completion.as_ref()?;
+        let buffer = buffer.read(cx);
+        if !completion.position.is_valid(buffer) {
+            return None;
+        }
+
+        // Keep showing the completion while the user types text that matches it.
+        let start = completion.position.to_offset(buffer);
+        let cursor = cursor_position.to_offset(buffer);
+        if cursor < start {
+            return None;
+        }
+        let typed = buffer.text_for_range(start..cursor).collect::<String>();
+        let remaining = completion.text.strip_prefix(typed.as_str())?;
+        if remaining.trim().is_empty() {
+            return None;
+        }
+
+        let position = cursor_position.bias_right(buffer);
+        Some(InlineCompletion {
+            id: None,
+            edits: vec![(position..position, remaining.to_string())],
+            edit_preview: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{AppContext as _, TestAppContext, UpdateGlobal};
+    use http_client::{AsyncBody, FakeHttpClient, Response};
+    use language::language_settings::AllLanguageSettings;
+    use serde_json::json;
+    use settings::SettingsStore;
+
+    #[gpui::test]
+    async fn test_open_ai_completion_provider(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            language::init(cx);
+            SettingsStore::update_global(cx, |store: &mut SettingsStore, cx| {
+                store.update_user_settings::<AllLanguageSettings>(cx, |settings| {
+                    settings
+                        .edit_predictions
+                        .get_or_insert_default()
+                        .open_ai_compatible
+                        .api_url = Some("http://localhost:8080/v1".into());
+                });
+            });
+        });
+
+        let http_client = FakeHttpClient::create(|request| async move {
+            assert_eq!(request.uri(), "http://localhost:8080/v1/completions");
+            let body = json!({
+                "id": "cmpl-1",
+                "object": "text_completion",
+                "created": 0,
+                "model": "test",
+                "choices": [{ "text": "42;" }],
+                "usage": { "prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2 },
+            });
+            Ok(Response::builder()
+                .status(200)
+                .body(AsyncBody::from(body.to_string()))
+                .unwrap())
+        });
+        let provider = cx.new(|_| OpenAiCompletionProvider::new(http_client));
+        let buffer = cx.new(|cx| Buffer::local("let x = \n", cx));
+        let cursor = buffer.read_with(cx, |buffer, _| buffer.anchor_after(8));
+
+        provider.update(cx, |provider, cx| {
+            provider.refresh(None, buffer.clone(), cursor, false, cx);
+        });
+        cx.run_until_parked();
+
+        let suggestion_text = |cx: &mut TestAppContext| {
+            provider.update(cx, |provider, cx| {
+                let cursor = buffer.read(cx).anchor_after(buffer.read(cx).len() - 1);
+                provider
+                    .suggest(&buffer, cursor, cx)
+                    .map(|completion| completion.edits[0].1.clone())
+            })
+        };
+        assert_eq!(suggestion_text(cx).as_deref(), Some("42;"));
+
+        // Typing text that matches the completion narrows it down.
+        buffer.update(cx, |buffer, cx| buffer.edit([(8..8, "4")], None, cx));
+        assert_eq!(suggestion_text(cx).as_deref(), Some("2;"));
+
+        // Typing something else invalidates it.
+        buffer.update(cx, |buffer, cx| buffer.edit([(9..9, "x")], None, cx));
+        assert_eq!(suggestion_text(cx), None);
+    }
+
+    #[gpui::test]
+    async fn test_open_ai_completion_provider_is_enabled(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            language::init(cx);
+        });
+        let provider =
+            cx.new(|_| OpenAiCompletionProvider::new(FakeHttpClient::with_404_response()));
+        let buffer = cx.new(|cx| Buffer::local("let x = \n", cx));
+        let is_enabled = |cx: &mut TestAppContext| {
+            provider.read_with(cx, |provider, cx| {
+                let cursor = buffer.read(cx).anchor_after(8);
+                provider.is_enabled(&buffer, cursor, cx)
+            })
+        };
+        let update_settings =
+            |cx: &mut TestAppContext,
+             f: fn(&mut language::language_settings::AllLanguageSettingsContent)| {
+                cx.update(|cx| {
+                    SettingsStore::update_global(cx, |store: &mut SettingsStore, cx| {
+                        store.update_user_settings::<AllLanguageSettings>(cx, f);
+                    });
+                });
+            };
+
+        // Predictions need an endpoint to be requested from.
+        assert!(!is_enabled(cx));
+        update_settings(cx, |settings| {
+            settings
+                .edit_predictions
+                .get_or_insert_default()
+                .open_ai_compatible
+                .api_url = Some("http://localhost:8080/v1".into());
+        });
+        assert!(is_enabled(cx));
+
+        // They follow the languages' `show_edit_predictions` setting, like other providers.
+        update_settings(cx, |settings| {
+            settings.defaults.show_edit_predictions = Some(false);
+        });
+        assert!(!is_enabled(cx));
+    }
+}
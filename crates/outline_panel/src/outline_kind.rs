@@ -0,0 +1,99 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+/// The kind of symbol an outline item stands for.
+///
+/// Outline queries don't capture the kind of their items, so it is guessed from the keywords
+/// that precede the item's name, e.g. `fn` in `pub fn main`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum OutlineKind {
+    Module,
+    Type,
+    Implementation,
+    Function,
+    Constant,
+    Other,
+}
+
+impl OutlineKind {
+    pub const ALL: [OutlineKind; 6] = [
+        OutlineKind::Module,
+        OutlineKind::Type,
+        OutlineKind::Implementation,
+        OutlineKind::Function,
+        OutlineKind::Constant,
+        OutlineKind::Other,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            OutlineKind::Module => "Modules",
+            OutlineKind::Type => "Types",
+            OutlineKind::Implementation => "Implementations",
+            OutlineKind::Function => "Functions",
+            OutlineKind::Constant => "Constants and Variables",
+            OutlineKind::Other => "Other",
+        }
+    }
+
+    pub fn for_outline(text: &str, name_ranges: &[Range<usize>]) -> Self {
+        let Some(context) = name_ranges
+            .first()
+            .and_then(|name_range| text.get(..name_range.start))
+        else {
+            return OutlineKind::Other;
+        };
+
+        // The keyword closest to the name wins, as in `const fn`.
+        context
+            .split(|character: char| !character.is_alphanumeric() && character != '_')
+            .rev()
+            .find_map(Self::for_keyword)
+            .unwrap_or(OutlineKind::Other)
+    }
+
+    fn for_keyword(keyword: &str) -> Option<Self> {
+        Some(match keyword {
+            "mod" | "module" | "namespace" | "package" => OutlineKind::Module,
+            "struct" | "enum" | "union" | "trait" | "class" | "interface" | "type" | "typedef"
+            | "protocol" | "record" | "object" => OutlineKind::Type,
+            "impl" | "extension" | "instance" => OutlineKind::Implementation,
+            "fn" | "def" | "function" | "func" | "fun" | "macro" | "macro_rules" => {
+                OutlineKind::Function
+            }
+            "const" | "static" | "let" | "var" | "val" => OutlineKind::Constant,
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(text: &str, name: &str) -> OutlineKind {
+        let start = text.find(name).unwrap();
+        OutlineKind::for_outline(text, &[start..start + name.len()])
+    }
+
+    #[test]
+    fn test_outline_kind() {
+        assert_eq!(kind("pub fn main", "main"), OutlineKind::Function);
+        assert_eq!(kind("pub const fn new", "new"), OutlineKind::Function);
+        assert_eq!(kind("pub(crate) struct Foo", "Foo"), OutlineKind::Type);
+        assert_eq!(
+            kind("impl Display for Foo", "Display"),
+            OutlineKind::Implementation
+        );
+        assert_eq!(kind("static mut COUNT", "COUNT"), OutlineKind::Constant);
+        assert_eq!(kind("mod tests", "tests"), OutlineKind::Module);
+        assert_eq!(kind("async def fetch", "fetch"), OutlineKind::Function);
+        assert_eq!(kind("export default class App", "App"), OutlineKind::Type);
+        assert_eq!(kind("field_name", "field_name"), OutlineKind::Other);
+        assert_eq!(
+            OutlineKind::for_outline("## Heading", &[]),
+            OutlineKind::Other
+        );
+    }
+}
@@ -1,3 +1,4 @@
+mod outline_kind;
 mod outline_panel_settings;
 
 use std::{
@@ -39,6 +40,7 @@ use itertools::Itertools;
 use language::{BufferId, BufferSnapshot, OffsetRangeExt, OutlineItem};
 use menu::{Cancel, SelectFirst, SelectLast, SelectNext, SelectPrevious};
 
+use outline_kind::OutlineKind;
 use outline_panel_settings::{OutlinePanelDockPosition, OutlinePanelSettings, ShowIndentGuides};
 use project::{File, Fs, GitEntry, GitTraversal, Project, ProjectItem};
 use search::{BufferSearchBar, ProjectSearchView};
@@ -46,7 +48,7 @@ use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsStore};
 use smol::channel;
 use theme::{SyntaxTheme, ThemeSettings};
-use ui::{DynamicSpacing, IndentGuideColors, IndentGuideLayout};
+use ui::{DynamicSpacing, IconPosition, IndentGuideColors, IndentGuideLayout, PopoverMenu};
 use util::{debug_panic, RangeExt, ResultExt, TryFutureExt};
 use workspace::{
     dock::{DockPosition, Panel, PanelEvent},
@@ -121,6 +123,7 @@ pub struct OutlinePanel {
     hide_scrollbar_task: Option<Task<()>>,
     max_width_item_index: Option<usize>,
     preserve_selection_on_buffer_fold_toggles: HashSet<BufferId>,
+    hidden_outline_kinds: BTreeSet<OutlineKind>,
 }
 
 #[derive(Debug)]
@@ -658,6 +661,8 @@ pub enum Event {
 struct SerializedOutlinePanel {
     width: Option<Pixels>,
     active: Option<bool>,
+    #[serde(default)]
+    hidden_outline_kinds: BTreeSet<OutlineKind>,
 }
 
 pub fn init_settings(cx: &mut App) {
@@ -697,6 +702,7 @@ impl OutlinePanel {
                 panel.update(cx, |panel, cx| {
                     panel.width = serialized_panel.width.map(|px| px.round());
                     panel.active = serialized_panel.active.unwrap_or(false);
+                    panel.hidden_outline_kinds = serialized_panel.hidden_outline_kinds;
                     cx.notify();
                 });
             }
@@ -732,6 +738,7 @@ impl OutlinePanel {
             let focus_out_subscription =
                 cx.on_focus_out(&focus_handle, window, |outline_panel, _, window, cx| {
                     outline_panel.hide_scrollbar(window, cx);
+                    outline_panel.update_selected_outline_highlight(window, cx);
                 });
             let workspace_subscription = cx.subscribe_in(
                 &workspace
@@ -821,6 +828,7 @@ impl OutlinePanel {
                 updating_cached_entries: false,
                 new_entries_for_fs_update: HashSet::default(),
                 preserve_selection_on_buffer_fold_toggles: HashSet::default(),
+                hidden_outline_kinds: BTreeSet::default(),
                 fs_entries_update_task: Task::ready(()),
                 cached_entries_update_task: Task::ready(()),
                 reveal_selection_task: Task::ready(Ok(())),
@@ -848,12 +856,17 @@ impl OutlinePanel {
     fn serialize(&mut self, cx: &mut Context<Self>) {
         let width = self.width;
         let active = Some(self.active);
+        let hidden_outline_kinds = self.hidden_outline_kinds.clone();
         self.pending_serialization = cx.background_spawn(
             async move {
                 KEY_VALUE_STORE
                     .write_kvp(
                         OUTLINE_PANEL_KEY.into(),
-                        serde_json::to_string(&SerializedOutlinePanel { width, active })?,
+                        serde_json::to_string(&SerializedOutlinePanel {
+                            width,
+                            active,
+                            hidden_outline_kinds,
+                        })?,
                     )
                     .await?;
                 anyhow::Ok(())
@@ -2151,6 +2164,7 @@ impl OutlinePanel {
             Some(empty_icon())
         };
 
+        let target = outline.clone();
         self.entry_element(
             PanelEntry::Outline(OutlineEntry::Outline(outline.clone())),
             item_id,
@@ -2161,6 +2175,26 @@ impl OutlinePanel {
             window,
             cx,
         )
+        .on_drag(
+            DraggedOutline {
+                outline: outline.clone(),
+                click_offset: Point::default(),
+            },
+            |dragged, click_offset, _, cx| {
+                cx.new(|_| DraggedOutline {
+                    outline: dragged.outline.clone(),
+                    click_offset,
+                })
+            },
+        )
+        .drag_over::<DraggedOutline>(|style, _, _, cx| {
+            style.bg(cx.theme().colors().drop_target_background)
+        })
+        .on_drop(
+            cx.listener(move |outline_panel, dragged: &DraggedOutline, _, cx| {
+                outline_panel.move_outline(&dragged.outline, &target, cx);
+            }),
+        )
     }
 
     fn render_entry(
@@ -3090,6 +3124,7 @@ impl OutlinePanel {
             .and_then(|excerpts| excerpts.get(&excerpt_id))
             .into_iter()
             .flat_map(|excerpt| excerpt.iter_outlines())
+            .filter(|outline| self.is_outline_visible(outline))
             .flat_map(|outline| {
                 let start = multi_buffer_snapshot
                     .anchor_in_excerpt(excerpt_id, outline.range.start)?
@@ -4100,7 +4135,10 @@ impl OutlinePanel {
                     continue;
                 }
 
-                for outline in excerpt.iter_outlines() {
+                for outline in excerpt
+                    .iter_outlines()
+                    .filter(|outline| self.is_outline_visible(outline))
+                {
                     self.push_entry(
                         state,
                         track_matches,
@@ -4247,9 +4285,131 @@ impl OutlinePanel {
         self.selected_entry = SelectedEntry::Valid(entry, ix);
 
         self.autoscroll(cx);
+        self.update_selected_outline_highlight(window, cx);
         cx.notify();
     }
 
+    /// Highlights the range of the selected outline in the editor while the panel is focused,
+    /// so that moving through the panel shows what each entry spans.
+    fn update_selected_outline_highlight(&self, window: &Window, cx: &mut Context<Self>) {
+        let Some(active_editor) = self.active_editor() else {
+            return;
+        };
+        let range = match self.selected_entry() {
+            Some(PanelEntry::Outline(OutlineEntry::Outline(outline)))
+                if self.focus_handle.contains_focused(window, cx) =>
+            {
+                let snapshot = active_editor.read(cx).buffer().read(cx).snapshot(cx);
+                snapshot
+                    .anchor_in_excerpt(outline.excerpt_id, outline.outline.range.start)
+                    .zip(snapshot.anchor_in_excerpt(outline.excerpt_id, outline.outline.range.end))
+                    .map(|(start, end)| start..end)
+            }
+            _ => None,
+        };
+        active_editor.update(cx, |editor, cx| match range {
+            Some(range) => editor.highlight_background::<SelectedOutlineHighlight>(
+                &[range],
+                |colors| colors.editor_document_highlight_read_background,
+                cx,
+            ),
+            None => {
+                editor.clear_background_highlights::<SelectedOutlineHighlight>(cx);
+            }
+        });
+    }
+
+    fn is_outline_visible(&self, outline: &Outline) -> bool {
+        self.hidden_outline_kinds.is_empty()
+            || !self
+                .hidden_outline_kinds
+                .contains(&OutlineKind::for_outline(
+                    &outline.text,
+                    &outline.name_ranges,
+                ))
+    }
+
+    fn toggle_outline_kind(
+        &mut self,
+        kind: OutlineKind,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.hidden_outline_kinds.remove(&kind) {
+            self.hidden_outline_kinds.insert(kind);
+        }
+        self.serialize(cx);
+        self.update_cached_entries(None, window, cx);
+    }
+
+    /// Whether two outlines are items of the same parent, with only their descendants and
+    /// other siblings between them.
+    fn are_sibling_outlines(&self, a: &OutlineEntryOutline, b: &OutlineEntryOutline) -> bool {
+        if (a.buffer_id, a.excerpt_id, a.outline.depth)
+            != (b.buffer_id, b.excerpt_id, b.outline.depth)
+        {
+            return false;
+        }
+        let Some(excerpt) = self
+            .excerpts
+            .get(&a.buffer_id)
+            .and_then(|excerpts| excerpts.get(&a.excerpt_id))
+        else {
+            return false;
+        };
+        let outlines = excerpt.iter_outlines().collect::<Vec<_>>();
+        let position = |entry: &OutlineEntryOutline| {
+            outlines
+                .iter()
+                .position(|outline| outline.range == entry.outline.range)
+        };
+        let (Some(a_ix), Some(b_ix)) = (position(a), position(b)) else {
+            return false;
+        };
+        outlines[a_ix.min(b_ix)..=a_ix.max(b_ix)]
+            .iter()
+            .all(|outline| outline.depth >= a.outline.depth)
+    }
+
+    /// Moves the text of a dragged outline before or after one of its siblings, e.g. to reorder
+    /// the methods of a class.
+    fn move_outline(
+        &mut self,
+        dragged: &OutlineEntryOutline,
+        target: &OutlineEntryOutline,
+        cx: &mut Context<Self>,
+    ) {
+        if dragged == target || !self.are_sibling_outlines(dragged, target) {
+            return;
+        }
+        let Some(buffer) = self.project.read(cx).buffer_for_id(dragged.buffer_id, cx) else {
+            return;
+        };
+        buffer.update(cx, |buffer, cx| {
+            let snapshot = buffer.snapshot();
+            let (Some(source), Some(destination)) = (
+                outline_line_range(&dragged.outline, &snapshot),
+                outline_line_range(&target.outline, &snapshot),
+            ) else {
+                return;
+            };
+            let text = snapshot.text_for_range(source.clone()).collect::<String>();
+            let insertion_point = if destination.start < source.start {
+                destination.start
+            } else {
+                destination.end
+            };
+            buffer.edit(
+                [
+                    (source, String::new()),
+                    (insertion_point..insertion_point, text),
+                ],
+                None,
+                cx,
+            );
+        });
+    }
+
     fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Option<Stateful<Div>> {
         if !Self::should_show_scrollbar(cx)
             || !(self.show_scrollbar || self.vertical_scrollbar_state.is_dragging())
@@ -4655,6 +4815,7 @@ impl OutlinePanel {
                 .p_2()
                 .w_full()
                 .child(self.filter_editor.clone())
+                .child(self.render_outline_kind_filter(cx))
                 .child(
                     div().child(
                         IconButton::new(
@@ -4685,6 +4846,44 @@ impl OutlinePanel {
         )
     }
 
+    fn render_outline_kind_filter(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let outline_panel = cx.entity().downgrade();
+        PopoverMenu::new("outline-kind-filter")
+            .trigger_with_tooltip(
+                IconButton::new("outline-kind-filter-button", IconName::Filter)
+                    .shape(IconButtonShape::Square)
+                    .toggle_state(!self.hidden_outline_kinds.is_empty()),
+                Tooltip::text("Filter by Kind"),
+            )
+            .menu(move |window, cx| {
+                let hidden_outline_kinds = outline_panel
+                    .upgrade()?
+                    .read(cx)
+                    .hidden_outline_kinds
+                    .clone();
+                let outline_panel = outline_panel.clone();
+                Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                    for kind in OutlineKind::ALL {
+                        let outline_panel = outline_panel.clone();
+                        menu = menu.toggleable_entry(
+                            kind.label(),
+                            !hidden_outline_kinds.contains(&kind),
+                            IconPosition::Start,
+                            None,
+                            move |window, cx| {
+                                outline_panel
+                                    .update(cx, |outline_panel, cx| {
+                                        outline_panel.toggle_outline_kind(kind, window, cx)
+                                    })
+                                    .ok();
+                            },
+                        );
+                    }
+                    menu
+                }))
+            })
+    }
+
     fn buffers_inside_directory(
         &self,
         dir_worktree: WorktreeId,
@@ -4722,6 +4921,53 @@ impl OutlinePanel {
     }
 }
 
+enum SelectedOutlineHighlight {}
+
+/// An outline entry being dragged onto one of its siblings, to move its text there.
+#[derive(Clone)]
+struct DraggedOutline {
+    outline: OutlineEntryOutline,
+    click_offset: Point<Pixels>,
+}
+
+impl Render for DraggedOutline {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let ui_font = ThemeSettings::get_global(cx).ui_font.clone();
+        div()
+            .font(ui_font)
+            .pl(self.click_offset.x + px(12.))
+            .pt(self.click_offset.y + px(12.))
+            .child(
+                div()
+                    .px_1()
+                    .rounded_sm()
+                    .bg(cx.theme().colors().background)
+                    .child(Label::new(self.outline.outline.text.clone())),
+            )
+    }
+}
+
+/// The whole lines spanned by an outline and the annotations before it, or `None` if the
+/// outline shares its lines with other code, in which case it can't be moved line-wise.
+fn outline_line_range(
+    outline: &Outline,
+    buffer: &BufferSnapshot,
+) -> Option<Range<language::Point>> {
+    let range = outline.range.to_point(buffer);
+    let start = outline
+        .annotation_range
+        .as_ref()
+        .map_or(range.start, |annotation_range| {
+            annotation_range.to_point(buffer).start
+        });
+    let end = range.end;
+    let spans_whole_lines = start.column <= buffer.indent_size_for_line(start.row).len
+        && end.column == buffer.line_len(end.row)
+        && end.row < buffer.max_point().row;
+    spans_whole_lines
+        .then(|| language::Point::new(start.row, 0)..language::Point::new(end.row + 1, 0))
+}
+
 fn workspace_active_editor(
     workspace: &Workspace,
     cx: &App,
@@ -125,6 +125,14 @@ pub fn database_dir() -> &'static PathBuf {
     DATABASE_DIR.get_or_init(|| support_dir().join("db"))
 }
 
+/// Returns the path to the worktree cache directory.
+///
+/// This is where the entries of worktrees are stored between sessions, to speed up scanning them.
+pub fn worktree_cache_dir() -> &'static PathBuf {
+    static WORKTREE_CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+    WORKTREE_CACHE_DIR.get_or_init(|| support_dir().join("worktree_cache"))
+}
+
 /// Returns the path to the crashes directory, if it exists for the current platform.
 pub fn crashes_dir() -> &'static Option<PathBuf> {
     static CRASHES_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
@@ -213,6 +221,18 @@ pub fn snippets_dir() -> &'static PathBuf {
     SNIPPETS_DIR.get_or_init(|| config_dir().join("snippets"))
 }
 
+/// Returns the path to the directory of the user's Hunspell dictionaries for spell checking.
+pub fn dictionaries_dir() -> &'static PathBuf {
+    static DICTIONARIES_DIR: OnceLock<PathBuf> = OnceLock::new();
+    DICTIONARIES_DIR.get_or_init(|| config_dir().join("dictionaries"))
+}
+
+/// Returns the path to the `dictionary.txt` file of words that the user added to spell checking.
+pub fn dictionary_file() -> &'static PathBuf {
+    static DICTIONARY_FILE: OnceLock<PathBuf> = OnceLock::new();
+    DICTIONARY_FILE.get_or_init(|| config_dir().join("dictionary.txt"))
+}
+
 /// Returns the path to the contexts directory.
 ///
 /// This is where the saved contexts from the Assistant are stored.
@@ -332,6 +352,16 @@ pub fn local_settings_file_relative_path() -> &'static Path {
     Path::new(".zed/settings.json")
 }
 
+/// Returns the relative path to a `dictionary.txt` file of spell checking words within a project.
+pub fn local_dictionary_file_relative_path() -> &'static Path {
+    Path::new(".zed/dictionary.txt")
+}
+
+/// Returns the relative path to an `env` file of environment variables within a project.
+pub fn local_env_file_relative_path() -> &'static Path {
+    Path::new(".zed/env")
+}
+
 /// Returns the relative path to a `tasks.json` file within a project.
 pub fn local_tasks_file_relative_path() -> &'static Path {
     Path::new(".zed/tasks.json")
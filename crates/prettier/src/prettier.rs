@@ -100,19 +100,10 @@ impl Prettier {
                     match &closest_package_json_path {
                         None => closest_package_json_path = Some(path_to_check.clone()),
                         Some(closest_package_json_path) => {
-                            match package_json_contents.get("workspaces") {
-                                Some(serde_json::Value::Array(workspaces)) => {
+                            match workspace_definitions(fs, &path_to_check, &package_json_contents).await? {
+                                Some(workspaces) => {
                                     let subproject_path = closest_package_json_path.strip_prefix(&path_to_check).expect("traversing path parents, should be able to strip prefix");
-                                    if workspaces.iter().filter_map(|value| {
-                                        if let serde_json::Value::String(s) = value {
-                                            Some(s.clone())
-                                        } else {
-                                            log::warn!("Skipping non-string 'workspaces' value: {value:?}");
-                                            None
-                                        }
-                                    }).any(|workspace_definition| {
-                                        workspace_definition == subproject_path.to_string_lossy() || PathMatcher::new(&[workspace_definition]).ok().map_or(false, |path_matcher| path_matcher.is_match(subproject_path))
-                                    }) {
+                                    if is_workspace_member(subproject_path, &workspaces) {
                                         anyhow::ensure!(has_prettier_in_node_modules(fs, &path_to_check).await?, "Path {path_to_check:?} is the workspace root for project in {closest_package_json_path:?}, but it has no prettier installed");
                                         log::info!("Found prettier path {path_to_check:?} in the workspace root for project in {closest_package_json_path:?}");
                                         return Ok(ControlFlow::Continue(Some(path_to_check)));
@@ -120,7 +111,6 @@ impl Prettier {
                                         log::warn!("Skipping path {path_to_check:?} workspace root with workspaces {workspaces:?} that have no prettier installed");
                                     }
                                 },
-                                Some(unknown) => log::error!("Failed to parse workspaces for {path_to_check:?} from package.json, got {unknown:?}. Skipping."),
                                 None => log::warn!("Skipping path {path_to_check:?} that has no prettier dependency and no workspaces section in its package.json"),
                             }
                         }
@@ -182,34 +172,15 @@ impl Prettier {
                 match &closest_package_json_path {
                     None => closest_package_json_path = Some(path_to_check.clone()),
                     Some(closest_package_json_path) => {
-                        if let Some(serde_json::Value::Array(workspaces)) =
-                            package_json_contents.get("workspaces")
+                        if let Some(workspaces) =
+                            workspace_definitions(fs, &path_to_check, &package_json_contents)
+                                .await?
                         {
                             let subproject_path = closest_package_json_path
                                 .strip_prefix(&path_to_check)
                                 .expect("traversing path parents, should be able to strip prefix");
 
-                            if workspaces
-                                .iter()
-                                .filter_map(|value| {
-                                    if let serde_json::Value::String(s) = value {
-                                        Some(s.clone())
-                                    } else {
-                                        log::warn!(
-                                            "Skipping non-string 'workspaces' value: {value:?}"
-                                        );
-                                        None
-                                    }
-                                })
-                                .any(|workspace_definition| {
-                                    workspace_definition == subproject_path.to_string_lossy()
-                                        || PathMatcher::new(&[workspace_definition])
-                                            .ok()
-                                            .map_or(false, |path_matcher| {
-                                                path_matcher.is_match(subproject_path)
-                                            })
-                                })
-                            {
+                            if is_workspace_member(subproject_path, &workspaces) {
                                 let workspace_ignore = path_to_check.join(".prettierignore");
                                 if let Some(metadata) = fs.metadata(&workspace_ignore).await? {
                                     if !metadata.is_dir {
@@ -470,6 +441,37 @@ impl Prettier {
         }
     }
 
+    /// Returns which version of Prettier formats the file, and with which configuration.
+    pub async fn file_info(
+        &self,
+        path: &Path,
+        ignore_dir: Option<PathBuf>,
+    ) -> anyhow::Result<PrettierFileInfo> {
+        match self {
+            Self::Real(local) => {
+                let ignore_path = ignore_dir.and_then(|dir| {
+                    let ignore_file = dir.join(".prettierignore");
+                    ignore_file.is_file().then_some(ignore_file)
+                });
+                local
+                    .server
+                    .request::<Info>(InfoParams {
+                        path: path.to_path_buf(),
+                        ignore_path,
+                    })
+                    .await
+                    .context("prettier file info")
+            }
+            #[cfg(any(test, feature = "test-support"))]
+            Self::Test(_) => Ok(PrettierFileInfo {
+                version: "test".to_string(),
+                config_file: None,
+                config_plugins: Vec::new(),
+                ignored: false,
+            }),
+        }
+    }
+
     pub async fn clear_cache(&self) -> anyhow::Result<()> {
         match self {
             Self::Real(local) => local
@@ -519,6 +521,102 @@ async fn has_prettier_in_node_modules(fs: &dyn Fs, path: &Path) -> anyhow::Resul
     Ok(false)
 }
 
+/// Returns the workspace definitions of the project in the directory, from either the
+/// `workspaces` field of its `package.json`, in npm's or Yarn's format, or its
+/// `pnpm-workspace.yaml`.
+async fn workspace_definitions(
+    fs: &dyn Fs,
+    path: &Path,
+    package_json_contents: &HashMap<String, serde_json::Value>,
+) -> anyhow::Result<Option<Vec<String>>> {
+    let workspaces = match package_json_contents.get("workspaces") {
+        Some(serde_json::Value::Array(workspaces)) => Some(workspaces),
+        // Yarn also allows `"workspaces": { "packages": [...], "nohoist": [...] }`.
+        Some(serde_json::Value::Object(workspaces)) => match workspaces.get("packages") {
+            Some(serde_json::Value::Array(packages)) => Some(packages),
+            _ => None,
+        },
+        Some(unknown) => {
+            log::error!("Failed to parse workspaces for {path:?} from package.json, got {unknown:?}. Skipping.");
+            None
+        }
+        None => None,
+    };
+    if let Some(workspaces) = workspaces {
+        return Ok(Some(
+            workspaces
+                .iter()
+                .filter_map(|value| {
+                    if let serde_json::Value::String(s) = value {
+                        Some(s.clone())
+                    } else {
+                        log::warn!("Skipping non-string 'workspaces' value: {value:?}");
+                        None
+                    }
+                })
+                .collect(),
+        ));
+    }
+
+    let pnpm_workspace_path = path.join("pnpm-workspace.yaml");
+    if fs.is_file(&pnpm_workspace_path).await {
+        let contents = fs
+            .load(&pnpm_workspace_path)
+            .await
+            .with_context(|| format!("reading {pnpm_workspace_path:?} file contents"))?;
+        return Ok(Some(parse_pnpm_workspace_packages(&contents)));
+    }
+    Ok(None)
+}
+
+/// Parses the `packages` list of a `pnpm-workspace.yaml` file, which is the only part of it
+/// that's needed to find workspace roots.
+fn parse_pnpm_workspace_packages(contents: &str) -> Vec<String> {
+    let mut packages = Vec::new();
+    let mut in_packages = false;
+    for line in contents.lines() {
+        let line = line.split(" #").next().unwrap_or_default().trim_end();
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) && !line.starts_with('-') {
+            in_packages = line.trim() == "packages:";
+            continue;
+        }
+        if in_packages {
+            if let Some(package) = line.trim().strip_prefix('-') {
+                let package = package.trim().trim_matches(|c| c == '"' || c == '\'');
+                if !package.is_empty() {
+                    packages.push(package.to_owned());
+                }
+            }
+        }
+    }
+    packages
+}
+
+/// Whether the subproject at the path, relative to the workspace root, is one of the
+/// workspace's projects. Negated definitions, like `!**/test/**`, exclude projects.
+fn is_workspace_member(subproject_path: &Path, workspace_definitions: &[String]) -> bool {
+    let matches = |definition: &str| {
+        definition == subproject_path.to_string_lossy()
+            || PathMatcher::new(&[definition.to_owned()])
+                .ok()
+                .map_or(false, |path_matcher| path_matcher.is_match(subproject_path))
+    };
+    let mut is_member = false;
+    for definition in workspace_definitions {
+        if let Some(excluded) = definition.strip_prefix('!') {
+            if matches(excluded) {
+                return false;
+            }
+        } else if matches(definition) {
+            is_member = true;
+        }
+    }
+    is_member
+}
+
 async fn read_package_json(
     fs: &dyn Fs,
     path: &Path,
@@ -576,6 +674,36 @@ impl lsp::request::Request for Format {
     const METHOD: &'static str = "prettier/format";
 }
 
+enum Info {}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InfoParams {
+    #[serde(rename = "filepath")]
+    path: PathBuf,
+    ignore_path: Option<PathBuf>,
+}
+
+/// The Prettier version and configuration that a file is formatted with.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrettierFileInfo {
+    pub version: String,
+    /// The configuration file that applies to the file, if any.
+    pub config_file: Option<PathBuf>,
+    /// The plugins listed in the configuration, which are used instead of the ones in the
+    /// settings.
+    pub config_plugins: Vec<String>,
+    /// Whether the file is ignored by a `.prettierignore` file.
+    pub ignored: bool,
+}
+
+impl lsp::request::Request for Info {
+    type Params = InfoParams;
+    type Result = PrettierFileInfo;
+    const METHOD: &'static str = "prettier/info";
+}
+
 enum ClearCache {}
 
 impl lsp::request::Request for ClearCache {
@@ -899,6 +1027,100 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_prettier_lookup_in_pnpm_and_yarn_workspaces(cx: &mut gpui::TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "pnpm-monorepo": {
+                    "packages": {
+                        "web": {
+                            "src": {
+                                "index.ts": "// index.ts file contents",
+                            },
+                            "package.json": r#"{
+                                "devDependencies": {
+                                    "prettier": "^3.0.3"
+                                }
+                            }"#
+                        },
+                    },
+                    "pnpm-workspace.yaml": "packages:\n  - 'packages/*'\n  - '!packages/legacy'\n",
+                    "package.json": r#"{ "name": "pnpm-monorepo" }"#,
+                    "node_modules": {
+                        "prettier": {
+                            "index.js": "// Dummy prettier package file",
+                        },
+                    },
+                },
+                "yarn-monorepo": {
+                    "apps": {
+                        "site": {
+                            "index.js": "// index.js file contents",
+                            "package.json": r#"{
+                                "devDependencies": {
+                                    "prettier": "^3.0.3"
+                                }
+                            }"#
+                        },
+                    },
+                    "package.json": r#"{
+                        "workspaces": {
+                            "packages": ["apps/*"],
+                            "nohoist": ["**/react-native"]
+                        }
+                    }"#,
+                    "node_modules": {
+                        "prettier": {
+                            "index.js": "// Dummy prettier package file",
+                        },
+                    },
+                },
+            }),
+        )
+        .await;
+
+        assert_eq!(
+            Prettier::locate_prettier_installation(
+                fs.as_ref(),
+                &HashSet::default(),
+                Path::new("/root/pnpm-monorepo/packages/web/src/index.ts"),
+            )
+            .await
+            .unwrap(),
+            ControlFlow::Continue(Some(PathBuf::from("/root/pnpm-monorepo"))),
+            "Should ascend to the pnpm workspace root and find the prettier there",
+        );
+        assert_eq!(
+            Prettier::locate_prettier_installation(
+                fs.as_ref(),
+                &HashSet::default(),
+                Path::new("/root/yarn-monorepo/apps/site/index.js"),
+            )
+            .await
+            .unwrap(),
+            ControlFlow::Continue(Some(PathBuf::from("/root/yarn-monorepo"))),
+            "Should support the object form of yarn workspaces",
+        );
+    }
+
+    #[test]
+    fn test_workspace_definitions() {
+        let packages = parse_pnpm_workspace_packages(
+            "# Workspace packages\npackages:\n  - 'packages/*'\n  - \"apps/*\" # the apps\n  - '!**/test/**'\ncatalog:\n  react: ^18.0.0\n",
+        );
+        assert_eq!(packages, vec!["packages/*", "apps/*", "!**/test/**"]);
+
+        assert!(is_workspace_member(Path::new("packages/web"), &packages));
+        assert!(is_workspace_member(Path::new("apps/site"), &packages));
+        assert!(!is_workspace_member(Path::new("tools/lint"), &packages));
+        assert!(!is_workspace_member(
+            Path::new("packages/web/test/fixture"),
+            &packages
+        ));
+    }
+
     #[gpui::test]
     async fn test_prettier_lookup_in_npm_workspaces_for_not_installed(
         cx: &mut gpui::TestAppContext,
@@ -220,7 +220,11 @@ async function handleMessage(message, prettier) {
     const plugins =
       Array.isArray(resolvedConfig?.plugins) &&
       resolvedConfig.plugins.length > 0
-        ? resolvedConfig.plugins
+        ? await resolveConfigPlugins(
+            prettier,
+            resolvedConfig.plugins,
+            params.options.filepath,
+          )
         : params.options.plugins;
 
     const options = {
@@ -237,6 +241,32 @@ async function handleMessage(message, prettier) {
     );
     const formattedText = await prettier.prettier.format(params.text, options);
     sendResponse({ id, result: { text: formattedText } });
+  } else if (method === "prettier/info") {
+    const configFile = await resolveConfigFile(prettier, params.filepath);
+    const resolvedConfig =
+      (await prettier.prettier.resolveConfig(params.filepath)) || {};
+    let ignored = false;
+    if (params.ignorePath) {
+      const fileInfo = await prettier.prettier.getFileInfo(params.filepath, {
+        ignorePath: params.ignorePath,
+      });
+      ignored = fileInfo.ignored;
+    }
+    sendResponse({
+      id,
+      result: {
+        version: prettier.prettier.version,
+        configFile,
+        configPlugins: await resolveConfigPlugins(
+          prettier,
+          (resolvedConfig.plugins || []).filter(
+            (plugin) => typeof plugin === "string",
+          ),
+          params.filepath,
+        ),
+        ignored,
+      },
+    });
   } else if (method === "prettier/clear_cache") {
     prettier.prettier.clearConfigCache();
     prettier.config =
@@ -274,6 +304,37 @@ function sendResponse(response) {
   process.stdout.write(headers + responsePayloadString);
 }
 
+async function resolveConfigFile(prettier, filepath) {
+  return (await prettier.prettier.resolveConfigFile(filepath)) || null;
+}
+
+// Plugins named in a project's config are installed in that project, which may be a
+// different package of a monorepo than the one prettier was found in, so they are
+// resolved from the config file's directory before prettier's own.
+async function resolveConfigPlugins(prettier, plugins, filepath) {
+  const configFile = filepath
+    ? await resolveConfigFile(prettier, filepath)
+    : null;
+  if (!configFile) {
+    return plugins;
+  }
+  return plugins.map((plugin) => {
+    if (typeof plugin !== "string") {
+      return plugin;
+    }
+    try {
+      return require.resolve(plugin, {
+        paths: [path.dirname(configFile), prettierContainerPath],
+      });
+    } catch (error) {
+      process.stderr.write(
+        `Failed to resolve prettier plugin '${plugin}' from '${configFile}': ${error}\n`,
+      );
+      return plugin;
+    }
+  });
+}
+
 function loadPrettier(prettierPath) {
   return new Promise((resolve, reject) => {
     fs.access(prettierPath, fs.constants.F_OK, (err) => {
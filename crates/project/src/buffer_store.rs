@@ -13,6 +13,7 @@ use gpui::{
     App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Subscription, Task, WeakEntity,
 };
 use language::{
+    language_settings::language_settings,
     proto::{
         deserialize_line_ending, deserialize_version, serialize_line_ending, serialize_version,
         split_operations,
@@ -364,7 +365,17 @@ impl LocalBufferStore {
         let buffer = buffer_handle.read(cx);
 
         let text = buffer.as_rope().clone();
-        let line_ending = buffer.line_ending();
+        let settings = language_settings(
+            buffer.language().map(|language| language.name()),
+            buffer.file(),
+            cx,
+        );
+        let (line_ending, has_bom) = if settings.normalize_on_save {
+            (settings.line_ending.line_ending(), settings.byte_order_mark)
+        } else {
+            (buffer.line_ending(), buffer.has_bom())
+        };
+        let encoding = buffer.encoding();
         let version = buffer.version();
         let buffer_id = buffer.remote_id();
         if buffer
@@ -375,7 +386,7 @@ impl LocalBufferStore {
         }
 
         let save = worktree.update(cx, |worktree, cx| {
-            worktree.write_file(path.as_ref(), text, line_ending, cx)
+            worktree.write_file(path.as_ref(), text, line_ending, encoding, has_bom, cx)
         });
 
         cx.spawn(async move |this, cx| {
@@ -406,6 +417,8 @@ impl LocalBufferStore {
                 if has_changed_file {
                     buffer.file_updated(new_file, cx);
                 }
+                buffer.set_line_ending(line_ending, cx);
+                buffer.set_has_bom(has_bom, cx);
                 buffer.did_save(version.clone(), mtime, cx);
             })
         })
@@ -642,11 +655,17 @@ impl LocalBufferStore {
             let buffer_id = BufferId::from(reservation.entity_id().as_non_zero_u64());
             cx.spawn(async move |_, cx| {
                 let loaded = load_file.await?;
-                let text_buffer = cx
-                    .background_spawn(async move { text::Buffer::new(0, buffer_id, loaded.text) })
+                let (text_buffer, indentation) = cx
+                    .background_spawn(async move {
+                        let indentation = language::detect_indentation(&loaded.text);
+                        (text::Buffer::new(0, buffer_id, loaded.text), indentation)
+                    })
                     .await;
                 cx.insert_entity(reservation, |_| {
                     Buffer::build(text_buffer, Some(loaded.file), Capability::ReadWrite)
+                        .with_encoding(loaded.encoding)
+                        .with_byte_order_mark(loaded.has_bom)
+                        .with_detected_indentation(indentation)
                 })
             })
         });
@@ -657,18 +676,20 @@ impl LocalBufferStore {
                 Err(error) if is_not_found_error(&error) => cx.new(|cx| {
                     let buffer_id = BufferId::from(cx.entity_id().as_non_zero_u64());
                     let text_buffer = text::Buffer::new(0, buffer_id, "".into());
-                    Buffer::build(
-                        text_buffer,
-                        Some(Arc::new(File {
-                            worktree,
-                            path,
-                            disk_state: DiskState::New,
-                            entry_id: None,
-                            is_local: true,
-                            is_private: false,
-                        })),
-                        Capability::ReadWrite,
-                    )
+                    let file: Arc<dyn language::File> = Arc::new(File {
+                        worktree,
+                        path,
+                        disk_state: DiskState::New,
+                        entry_id: None,
+                        is_local: true,
+                        is_private: false,
+                    });
+                    let settings = language_settings(None, Some(&file), cx);
+                    let (line_ending, has_bom) =
+                        (settings.line_ending.line_ending(), settings.byte_order_mark);
+                    Buffer::build(text_buffer, Some(file), Capability::ReadWrite)
+                        .with_line_ending(line_ending)
+                        .with_byte_order_mark(has_bom)
                 }),
                 Err(e) => Err(e),
             }?;
@@ -700,8 +721,15 @@ impl LocalBufferStore {
 
     fn create_buffer(&self, cx: &mut Context<BufferStore>) -> Task<Result<Entity<Buffer>>> {
         cx.spawn(async move |buffer_store, cx| {
-            let buffer =
-                cx.new(|cx| Buffer::local("", cx).with_language(language::PLAIN_TEXT.clone(), cx))?;
+            let buffer = cx.new(|cx| {
+                let settings = language_settings(None, None, cx);
+                let (line_ending, has_bom) =
+                    (settings.line_ending.line_ending(), settings.byte_order_mark);
+                Buffer::local("", cx)
+                    .with_language(language::PLAIN_TEXT.clone(), cx)
+                    .with_line_ending(line_ending)
+                    .with_byte_order_mark(has_bom)
+            })?;
             buffer_store.update(cx, |buffer_store, cx| {
                 buffer_store.add_buffer(buffer.clone(), cx).log_err();
             })?;
@@ -1076,7 +1104,10 @@ impl BufferStore {
         fs: Arc<dyn Fs>,
         cx: &mut Context<Self>,
     ) -> Receiver<Entity<Buffer>> {
-        let (tx, rx) = smol::channel::unbounded();
+        const MAX_CONCURRENT_BUFFER_OPENS: usize = 64;
+        // Buffers are only opened as fast as they're searched, so that searches with many
+        // candidates don't hold all of them in memory at once.
+        let (tx, rx) = smol::channel::bounded(MAX_CONCURRENT_BUFFER_OPENS);
         let mut open_buffers = HashSet::default();
         let mut unnamed_buffers = Vec::new();
         for handle in self.buffers() {
@@ -1089,7 +1120,6 @@ impl BufferStore {
             };
         }
 
-        const MAX_CONCURRENT_BUFFER_OPENS: usize = 64;
         let project_paths_rx = self
             .worktree_store
             .update(cx, |worktree_store, cx| {
@@ -1313,6 +1313,11 @@ impl Session {
         self.thread_states.thread_status(thread_id)
     }
 
+    /// Returns whether any of the session's threads are stopped, such as at a breakpoint.
+    pub fn any_stopped_thread(&self) -> bool {
+        self.thread_states.any_stopped_thread()
+    }
+
     pub fn threads(&mut self, cx: &mut Context<Self>) -> Vec<(dap::Thread, ThreadStatus)> {
         self.fetch(
             dap_command::ThreadsCommand,
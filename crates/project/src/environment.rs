@@ -1,11 +1,13 @@
 use futures::{future::Shared, FutureExt};
+use paths::local_env_file_relative_path;
 use std::{path::Path, sync::Arc};
+use terminal::terminal_settings::TerminalSettings;
 use util::ResultExt;
 
-use collections::HashMap;
-use gpui::{App, AppContext as _, Context, Entity, EventEmitter, Task};
-use settings::Settings as _;
-use worktree::WorktreeId;
+use collections::{HashMap, HashSet};
+use gpui::{App, AppContext as _, Context, Entity, EventEmitter, SharedString, Task};
+use settings::{Settings as _, SettingsLocation};
+use worktree::{Worktree, WorktreeId};
 
 use crate::{
     project_settings::{DirenvSettings, ProjectSettings},
@@ -16,9 +18,20 @@ pub struct ProjectEnvironment {
     worktree_store: Entity<WorktreeStore>,
     cli_environment: Option<HashMap<String, String>>,
     environments: HashMap<WorktreeId, Shared<Task<Option<HashMap<String, String>>>>>,
+    /// The variables of the worktrees' `.zed/env` files.
+    env_files: HashMap<WorktreeId, HashMap<String, String>>,
+    /// The worktrees in restricted mode, whose `.zed/env` files are ignored until they're trusted.
+    restricted_worktrees: HashSet<WorktreeId>,
     environment_error_messages: HashMap<WorktreeId, EnvironmentErrorMessage>,
 }
 
+/// The environment that a kind of process spawned in a worktree starts with.
+pub struct SubsystemEnvironment {
+    /// The kind of process, like "Terminals".
+    pub subsystem: SharedString,
+    pub env: HashMap<String, String>,
+}
+
 pub enum ProjectEnvironmentEvent {
     ErrorsUpdated,
 }
@@ -32,17 +45,40 @@ impl ProjectEnvironment {
         cx: &mut App,
     ) -> Entity<Self> {
         cx.new(|cx| {
-            cx.subscribe(worktree_store, |this: &mut Self, _, event, _| {
-                if let WorktreeStoreEvent::WorktreeRemoved(_, id) = event {
-                    this.remove_worktree_environment(*id);
-                }
-            })
+            cx.subscribe(
+                worktree_store,
+                |this: &mut Self, _, event, cx| match event {
+                    WorktreeStoreEvent::WorktreeAdded(worktree) => {
+                        this.load_env_file(worktree, cx);
+                    }
+                    WorktreeStoreEvent::WorktreeUpdatedEntries(worktree_id, changes) => {
+                        let env_file_changed = changes
+                            .iter()
+                            .any(|(path, _, _)| path.as_ref() == local_env_file_relative_path());
+                        if env_file_changed {
+                            let worktree = this
+                                .worktree_store
+                                .read(cx)
+                                .worktree_for_id(*worktree_id, cx);
+                            if let Some(worktree) = worktree {
+                                this.load_env_file(&worktree, cx);
+                            }
+                        }
+                    }
+                    WorktreeStoreEvent::WorktreeRemoved(_, id) => {
+                        this.remove_worktree_environment(*id);
+                    }
+                    _ => {}
+                },
+            )
             .detach();
 
             Self {
                 worktree_store: worktree_store.clone(),
                 cli_environment,
                 environments: Default::default(),
+                env_files: Default::default(),
+                restricted_worktrees: Default::default(),
                 environment_error_messages: Default::default(),
             }
         })
@@ -51,6 +87,64 @@ impl ProjectEnvironment {
     pub(crate) fn remove_worktree_environment(&mut self, worktree_id: WorktreeId) {
         self.environment_error_messages.remove(&worktree_id);
         self.environments.remove(&worktree_id);
+        self.env_files.remove(&worktree_id);
+        self.restricted_worktrees.remove(&worktree_id);
+    }
+
+    pub(crate) fn set_restricted_worktrees(&mut self, worktree_ids: HashSet<WorktreeId>) {
+        self.restricted_worktrees = worktree_ids;
+    }
+
+    fn load_env_file(&mut self, worktree: &Entity<Worktree>, cx: &mut Context<Self>) {
+        let worktree = worktree.read(cx);
+        let Some(local_worktree) = worktree.as_local() else {
+            return;
+        };
+        let worktree_id = worktree.id();
+        let fs = local_worktree.fs().clone();
+        let env_file_path = worktree.abs_path().join(local_env_file_relative_path());
+        cx.spawn(async move |this, cx| {
+            let env = if fs.is_file(&env_file_path).await {
+                fs.load(&env_file_path)
+                    .await
+                    .log_err()
+                    .map(|contents| parse_env_file(&contents))
+            } else {
+                None
+            };
+            this.update(cx, |this, _| match env {
+                Some(env) => {
+                    this.env_files.insert(worktree_id, env);
+                }
+                None => {
+                    this.env_files.remove(&worktree_id);
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Returns the variables that the project adds to the environment of the processes spawned
+    /// in the given worktree: the ones of its `.zed/env` file, and then the ones of the `env`
+    /// setting, which take precedence. The `.zed/env` file of a worktree in restricted mode is
+    /// ignored.
+    pub(crate) fn project_env(
+        &self,
+        worktree_id: Option<WorktreeId>,
+        cx: &App,
+    ) -> HashMap<String, String> {
+        let mut env = worktree_id
+            .filter(|worktree_id| !self.restricted_worktrees.contains(worktree_id))
+            .and_then(|worktree_id| self.env_files.get(&worktree_id))
+            .cloned()
+            .unwrap_or_default();
+        let settings_location = worktree_id.map(|worktree_id| SettingsLocation {
+            worktree_id,
+            path: Path::new(""),
+        });
+        env.extend(ProjectSettings::get(settings_location, cx).env.clone());
+        env
     }
 
     /// Returns the inherited CLI environment, if this project was opened from the Zed CLI.
@@ -85,11 +179,97 @@ impl ProjectEnvironment {
     /// If it wasn't opened from the CLI, and a worktree is given, then a shell is spawned in
     /// the worktree's path, to get environment variables as if the user has `cd`'d into
     /// the worktrees path.
+    /// Either way, the variables of the project's `.zed/env` file and `env` setting are added.
     pub(crate) fn get_environment(
         &mut self,
         worktree_id: Option<WorktreeId>,
         worktree_abs_path: Option<Arc<Path>>,
         cx: &Context<Self>,
+    ) -> Shared<Task<Option<HashMap<String, String>>>> {
+        let environment = self.get_base_environment(worktree_id, worktree_abs_path, cx);
+        let project_env = self.project_env(worktree_id, cx);
+        if project_env.is_empty() {
+            return environment;
+        }
+        cx.background_spawn(async move {
+            let mut environment = environment.await?;
+            environment.extend(project_env);
+            Some(environment)
+        })
+        .shared()
+    }
+
+    /// Returns the environments that terminals, tasks, debug sessions and language servers
+    /// spawned in the given worktree start with.
+    pub fn subsystem_environments(
+        &mut self,
+        worktree: &Entity<Worktree>,
+        cx: &mut Context<Self>,
+    ) -> Task<Vec<SubsystemEnvironment>> {
+        let (worktree_id, worktree_abs_path) = {
+            let worktree = worktree.read(cx);
+            if !worktree.is_local() {
+                return Task::ready(Vec::new());
+            }
+            (worktree.id(), worktree.abs_path())
+        };
+        let settings_location = SettingsLocation {
+            worktree_id,
+            path: Path::new(""),
+        };
+
+        // Terminals run a login shell, which loads the shell environment itself.
+        let mut terminal_env = self.get_cli_environment().unwrap_or_default();
+        terminal_env.extend(self.project_env(Some(worktree_id), cx));
+        terminal_env.extend(
+            TerminalSettings::get(Some(settings_location), cx)
+                .env
+                .clone(),
+        );
+
+        let language_server_envs = ProjectSettings::get(Some(settings_location), cx)
+            .lsp
+            .iter()
+            .filter_map(|(name, settings)| {
+                let env = settings.binary.as_ref()?.env.clone()?;
+                Some((name.clone(), env))
+            })
+            .collect::<Vec<_>>();
+
+        let environment = self.get_environment(Some(worktree_id), Some(worktree_abs_path), cx);
+        cx.background_spawn(async move {
+            let environment = environment.await.unwrap_or_default();
+            let mut environments = vec![
+                SubsystemEnvironment {
+                    subsystem: "Terminals".into(),
+                    env: terminal_env,
+                },
+                SubsystemEnvironment {
+                    subsystem: "Tasks and debug sessions".into(),
+                    env: environment.clone(),
+                },
+                SubsystemEnvironment {
+                    subsystem: "Language servers".into(),
+                    env: environment.clone(),
+                },
+            ];
+            for (name, language_server_env) in language_server_envs {
+                let mut env = environment.clone();
+                env.extend(language_server_env);
+                environments.push(SubsystemEnvironment {
+                    subsystem: format!("Language server {name}").into(),
+                    env,
+                });
+            }
+            environments
+        })
+    }
+
+    fn get_base_environment(
+        &mut self,
+        worktree_id: Option<WorktreeId>,
+        worktree_abs_path: Option<Arc<Path>>,
+        cx: &Context<Self>,
     ) -> Shared<Task<Option<HashMap<String, String>>>> {
         if cfg!(any(test, feature = "test-support")) {
             return Task::ready(Some(HashMap::default())).shared();
@@ -201,6 +381,35 @@ impl From<EnvironmentOrigin> for String {
     }
 }
 
+/// Parses the variables of an env file, whose lines look like `KEY=value`, optionally with an
+/// `export` prefix and quotes around the value. Empty lines and lines starting with `#` are
+/// skipped.
+fn parse_env_file(contents: &str) -> HashMap<String, String> {
+    let mut env = HashMap::default();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            log::warn!("skipping line without a value in env file: {line:?}");
+            continue;
+        };
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|quote| {
+                value
+                    .strip_prefix(*quote)
+                    .and_then(|value| value.strip_suffix(*quote))
+            })
+            .unwrap_or(value);
+        env.insert(key.trim().to_string(), value.to_string());
+    }
+    env
+}
+
 pub struct EnvironmentErrorMessage(pub String);
 
 impl EnvironmentErrorMessage {
@@ -11,7 +11,6 @@ use anyhow::{anyhow, Context as _, Result};
 use async_trait::async_trait;
 use client::proto::{self, PeerId};
 use clock::Global;
-use collections::HashSet;
 use futures::future;
 use gpui::{App, AsyncApp, Entity};
 use language::{
@@ -69,6 +68,18 @@ pub(crate) fn make_lsp_text_document_position(
     })
 }
 
+/// Whether a code action kind is the given kind or one of its sub-kinds, as code action kinds
+/// are hierarchical: `refactor.extract.function` is a `refactor.extract` and a `refactor`.
+pub fn code_action_kind_contains(
+    kind: &lsp::CodeActionKind,
+    sub_kind: &lsp::CodeActionKind,
+) -> bool {
+    sub_kind
+        .as_str()
+        .strip_prefix(kind.as_str())
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
 #[async_trait(?Send)]
 pub trait LspCommand: 'static + Sized + Send + std::fmt::Debug {
     type Response: 'static + Default + Send + std::fmt::Debug;
@@ -2360,8 +2371,9 @@ impl LspCommand for GetCodeActions {
                     .as_ref()
                     .zip(Self::supported_code_action_kinds(capabilities))
                 {
-                    let server_supported = supported.into_iter().collect::<HashSet<_>>();
-                    requested.iter().any(|kind| server_supported.contains(kind))
+                    requested
+                        .iter()
+                        .any(|kind| Self::is_kind_supported(kind, &supported))
                 } else {
                     true
                 }
@@ -2389,11 +2401,9 @@ impl LspCommand for GetCodeActions {
 
         let only = if let Some(requested) = &self.kinds {
             if let Some(supported_kinds) = supported {
-                let server_supported = supported_kinds.into_iter().collect::<HashSet<_>>();
-
                 let filtered = requested
                     .iter()
-                    .filter(|kind| server_supported.contains(kind))
+                    .filter(|kind| Self::is_kind_supported(kind, &supported_kinds))
                     .cloned()
                     .collect();
                 Some(filtered)
@@ -2425,12 +2435,6 @@ impl LspCommand for GetCodeActions {
         server_id: LanguageServerId,
         cx: AsyncApp,
     ) -> Result<Vec<CodeAction>> {
-        let requested_kinds_set = if let Some(kinds) = self.kinds {
-            Some(kinds.into_iter().collect::<HashSet<_>>())
-        } else {
-            None
-        };
-
         let language_server = cx.update(|cx| {
             lsp_store
                 .read(cx)
@@ -2469,9 +2473,12 @@ impl LspCommand for GetCodeActions {
                 };
 
                 if let Some((requested_kinds, kind)) =
-                    requested_kinds_set.as_ref().zip(lsp_action.action_kind())
+                    self.kinds.as_ref().zip(lsp_action.action_kind())
                 {
-                    if !requested_kinds.contains(&kind) {
+                    if !requested_kinds
+                        .iter()
+                        .any(|requested_kind| code_action_kind_contains(requested_kind, &kind))
+                    {
                         return None;
                     }
                 }
@@ -2575,6 +2582,16 @@ impl GetCodeActions {
         }
     }
 
+    /// Whether a server that supports the given kinds of code actions can return actions of the
+    /// requested kind, which is the case when it supports the kind, one of its sub-kinds, or a
+    /// kind that it's a sub-kind of.
+    fn is_kind_supported(kind: &CodeActionKind, supported_kinds: &[CodeActionKind]) -> bool {
+        supported_kinds.iter().any(|supported_kind| {
+            code_action_kind_contains(kind, supported_kind)
+                || code_action_kind_contains(supported_kind, kind)
+        })
+    }
+
     pub fn can_resolve_actions(capabilities: &ServerCapabilities) -> bool {
         capabilities
             .code_action_provider
@@ -383,7 +383,10 @@ impl LocalLspStore {
         .get(&adapter.name)
         .and_then(|s| s.binary.clone());
 
-        if settings.as_ref().is_some_and(|b| b.path.is_some()) {
+        if let Some(path) = settings
+            .as_ref()
+            .and_then(|settings| settings.resolved_path(delegate.worktree_root_path()))
+        {
             let settings = settings.unwrap();
 
             return cx.spawn(async move |_| {
@@ -391,7 +394,7 @@ impl LocalLspStore {
                 env.extend(settings.env.unwrap_or_default());
 
                 Ok(LanguageServerBinary {
-                    path: PathBuf::from(&settings.path.unwrap()),
+                    path,
                     env: Some(env),
                     arguments: settings
                         .arguments
@@ -1328,9 +1331,21 @@ impl LocalLspStore {
             // but that need to not be returned right away (with `?` for example) because we
             // still need to clean up the transaction history and update the project transaction
             let mut result = anyhow::Ok(());
+            // the formatter that's running, so that failures can be reported with the formatter
+            // that caused them
+            let mut current_formatter = None;
 
             'formatters: for formatter in formatters {
+                let Some(formatter) = formatter_for_trigger(formatter, trigger) else {
+                    zlog::trace!(logger => "Skipping {} as it's not run on {:?}", formatter, trigger);
+                    continue 'formatters;
+                };
+                current_formatter = Some(formatter);
                 match formatter {
+                    Formatter::OnSave(_) | Formatter::Manual(_) => {
+                        // scoped formatters are unwrapped by `formatter_for_trigger`
+                        continue 'formatters;
+                    }
                     Formatter::Prettier => {
                         let logger = zlog::scoped!(logger => "prettier");
                         zlog::trace!(logger => "formatting");
@@ -1348,10 +1363,16 @@ impl LocalLspStore {
                             zlog::error!(logger => "failed, reason: {:?}", result.as_ref());
                             break 'formatters;
                         };
-                        let Some(diff) = diff else {
+                        let Some(mut diff) = diff else {
                             zlog::trace!(logger => "No changes");
                             continue 'formatters;
                         };
+                        if let Some(ranges) = buffer.ranges.as_ref() {
+                            zlog::trace!(logger => "Restricting changes to ranges");
+                            buffer.handle.read_with(cx, |buffer, _| {
+                                restrict_diff_to_ranges(&mut diff, ranges, &buffer.snapshot())
+                            })?;
+                        }
                         if let Some(err) =
                             err_if_buffer_edited_since_start(buffer, transaction_id_format, &cx)
                         {
@@ -1391,10 +1412,16 @@ impl LocalLspStore {
                             zlog::error!(logger => "failed, reason: {:?}", result.as_ref());
                             break 'formatters;
                         };
-                        let Some(diff) = diff else {
+                        let Some(mut diff) = diff else {
                             zlog::trace!(logger => "No changes");
                             continue 'formatters;
                         };
+                        if let Some(ranges) = buffer.ranges.as_ref() {
+                            zlog::trace!(logger => "Restricting changes to ranges");
+                            buffer.handle.read_with(cx, |buffer, _| {
+                                restrict_diff_to_ranges(&mut diff, ranges, &buffer.snapshot())
+                            })?;
+                        }
                         if let Some(err) =
                             err_if_buffer_edited_since_start(buffer, transaction_id_format, &cx)
                         {
@@ -1780,8 +1807,12 @@ impl LocalLspStore {
                 }
             }
 
+            if let Some(formatter) = current_formatter {
+                result = result.with_context(|| format!("failed to format with {formatter}"));
+            }
+
             let buffer_handle = buffer.handle.clone();
-            buffer.handle.update(cx, |buffer, _| {
+            buffer.handle.update(cx, |buffer, cx| {
                 let Some(transaction_id) = transaction_id_format else {
                     zlog::trace!(logger => "No formatting transaction id");
                     return result;
@@ -1799,6 +1830,14 @@ impl LocalLspStore {
                     zlog::trace!(logger => "Last transaction on undo stack is not the formatting transaction, skipping finalization & update of project transaction");
                     return result;
                 }
+                if result.is_err() {
+                    // abort the chain cleanly by reverting the changes of the formatters that
+                    // ran before the one that failed
+                    zlog::trace!(logger => "reverting format transaction after failure");
+                    buffer.undo_transaction(transaction_id, cx);
+                    buffer.forget_transaction(transaction_id);
+                    return result;
+                }
                 let transaction = buffer
                     .finalize_last_transaction()
                     .cloned()
@@ -3406,6 +3445,7 @@ impl LspStore {
     pub fn init(client: &AnyProtoClient) {
         client.add_entity_request_handler(Self::handle_multi_lsp_query);
         client.add_entity_request_handler(Self::handle_restart_language_servers);
+        client.add_entity_request_handler(Self::handle_stop_language_servers);
         client.add_entity_request_handler(Self::handle_cancel_language_server_work);
         client.add_entity_message_handler(Self::handle_start_language_server);
         client.add_entity_message_handler(Self::handle_update_language_server);
@@ -3741,6 +3781,20 @@ impl LspStore {
             }
 
             language::BufferEvent::Saved => {
+                // A shebang or modeline may have been added to a file without a language.
+                let language = buffer.read(cx).language();
+                let is_plain_text = language.is_none() || language == Some(&*language::PLAIN_TEXT);
+                if is_plain_text && self.detect_language_for_buffer(&buffer, cx).is_some() {
+                    if let Some(local) = self.as_local_mut() {
+                        local.initialize_buffer(&buffer, cx);
+                        if local
+                            .registered_buffers
+                            .contains_key(&buffer.read(cx).remote_id())
+                        {
+                            local.register_buffer_with_language_servers(&buffer, cx);
+                        }
+                    }
+                }
                 self.on_buffer_saved(buffer, cx);
             }
 
@@ -4431,6 +4485,31 @@ impl LspStore {
         }
     }
 
+    /// Resolves a code action without applying it. Code actions of remote projects are resolved
+    /// by the host when they're applied, so they're returned as they are.
+    pub fn resolve_code_action(
+        &self,
+        buffer_handle: &Entity<Buffer>,
+        mut action: CodeAction,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<CodeAction>> {
+        if action.resolved || !self.mode.is_local() {
+            return Task::ready(Ok(action));
+        }
+        let Some(lang_server) = buffer_handle.update(cx, |buffer, cx| {
+            self.language_server_for_local_buffer(buffer, action.server_id, cx)
+                .map(|(_, server)| server.clone())
+        }) else {
+            return Task::ready(Ok(action));
+        };
+        cx.spawn(async move |_, _| {
+            LocalLspStore::try_resolve_code_action(&lang_server, &mut action)
+                .await
+                .context("resolving a code action")?;
+            Ok(action)
+        })
+    }
+
     pub fn apply_code_action(
         &self,
         buffer_handle: Entity<Buffer>,
@@ -7925,8 +8004,37 @@ impl LspStore {
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         this.update(&mut cx, |this, cx| {
-            let buffers = this.buffer_ids_to_buffers(envelope.payload.buffer_ids.into_iter(), cx);
-            this.restart_language_servers_for_buffers(buffers, cx);
+            if envelope.payload.language_server_ids.is_empty() {
+                let buffers =
+                    this.buffer_ids_to_buffers(envelope.payload.buffer_ids.into_iter(), cx);
+                this.restart_language_servers_for_buffers(buffers, cx);
+            } else {
+                let server_ids = envelope
+                    .payload
+                    .language_server_ids
+                    .into_iter()
+                    .map(LanguageServerId::from_proto)
+                    .collect();
+                this.restart_language_servers(server_ids, cx);
+            }
+        })?;
+
+        Ok(proto::Ack {})
+    }
+
+    pub async fn handle_stop_language_servers(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::StopLanguageServers>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        this.update(&mut cx, |this, cx| {
+            let server_ids = envelope
+                .payload
+                .language_server_ids
+                .into_iter()
+                .map(LanguageServerId::from_proto)
+                .collect();
+            this.stop_language_servers(server_ids, cx);
         })?;
 
         Ok(proto::Ack {})
@@ -8379,6 +8487,7 @@ impl LspStore {
                     .into_iter()
                     .map(|b| b.read(cx).remote_id().to_proto())
                     .collect(),
+                language_server_ids: Vec::new(),
             });
             cx.background_spawn(request).detach_and_log_err(cx);
         } else {
@@ -8393,23 +8502,11 @@ impl LspStore {
                     })
                 })
                 .collect::<BTreeSet<_>>();
-            local.lsp_tree.update(cx, |this, _| {
-                this.remove_nodes(&language_servers_to_stop);
-            });
-            let tasks = language_servers_to_stop
-                .into_iter()
-                .map(|server| {
-                    let name = self
-                        .language_server_statuses
-                        .get(&server)
-                        .map(|state| state.name.as_str().into())
-                        .unwrap_or_else(|| LanguageServerName::from("Unknown"));
-                    self.stop_local_language_server(server, name, cx)
-                })
-                .collect::<Vec<_>>();
+            let stop = self
+                .stop_local_language_servers(language_servers_to_stop.into_iter().collect(), cx);
 
             cx.spawn(async move |this, cx| {
-                cx.background_spawn(futures::future::join_all(tasks)).await;
+                stop.await;
                 this.update(cx, |this, cx| {
                     for buffer in buffers {
                         this.register_buffer_with_language_servers(&buffer, true, cx);
@@ -8421,6 +8518,110 @@ impl LspStore {
         }
     }
 
+    /// Restarts the given language servers, re-registering every buffer that was using them.
+    pub fn restart_language_servers(
+        &mut self,
+        server_ids: Vec<LanguageServerId>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((client, project_id)) = self.upstream_client() {
+            let request = client.request(proto::RestartLanguageServers {
+                project_id,
+                buffer_ids: Vec::new(),
+                language_server_ids: server_ids
+                    .into_iter()
+                    .map(|server_id| server_id.to_proto())
+                    .collect(),
+            });
+            cx.background_spawn(request).detach_and_log_err(cx);
+            return;
+        }
+
+        let buffers = self.buffers_using_language_servers(&server_ids, cx);
+        let stop = self.stop_local_language_servers(server_ids, cx);
+        cx.spawn(async move |this, cx| {
+            stop.await;
+            this.update(cx, |this, cx| {
+                for buffer in buffers {
+                    this.register_buffer_with_language_servers(&buffer, true, cx);
+                }
+            })
+            .ok()
+        })
+        .detach();
+    }
+
+    /// Stops the given language servers. They will be started again the next time a buffer that needs them is opened.
+    pub fn stop_language_servers(
+        &mut self,
+        server_ids: Vec<LanguageServerId>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((client, project_id)) = self.upstream_client() {
+            let request = client.request(proto::StopLanguageServers {
+                project_id,
+                language_server_ids: server_ids
+                    .into_iter()
+                    .map(|server_id| server_id.to_proto())
+                    .collect(),
+            });
+            cx.background_spawn(request).detach_and_log_err(cx);
+            return;
+        }
+
+        self.stop_local_language_servers(server_ids, cx).detach();
+    }
+
+    fn buffers_using_language_servers(
+        &mut self,
+        server_ids: &[LanguageServerId],
+        cx: &mut Context<Self>,
+    ) -> Vec<Entity<Buffer>> {
+        let buffers = self.buffer_store.read(cx).buffers().collect::<Vec<_>>();
+        let Some(local) = self.as_local() else {
+            return Vec::new();
+        };
+        buffers
+            .into_iter()
+            .filter(|buffer| {
+                buffer.update(cx, |buffer, cx| {
+                    local
+                        .language_server_ids_for_buffer(buffer, cx)
+                        .iter()
+                        .any(|server_id| server_ids.contains(server_id))
+                })
+            })
+            .collect()
+    }
+
+    fn stop_local_language_servers(
+        &mut self,
+        server_ids: Vec<LanguageServerId>,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        let Some(local) = self.as_local_mut() else {
+            return Task::ready(());
+        };
+        let server_ids = server_ids.into_iter().collect::<BTreeSet<_>>();
+        local.lsp_tree.update(cx, |this, _| {
+            this.remove_nodes(&server_ids);
+        });
+        let tasks = server_ids
+            .into_iter()
+            .map(|server| {
+                let name = self
+                    .language_server_statuses
+                    .get(&server)
+                    .map(|state| state.name.as_str().into())
+                    .unwrap_or_else(|| LanguageServerName::from("Unknown"));
+                self.stop_local_language_server(server, name, cx)
+            })
+            .collect::<Vec<_>>();
+        cx.background_spawn(async move {
+            futures::future::join_all(tasks).await;
+        })
+    }
+
     fn get_buffer<'a>(&self, abs_path: &Path, cx: &'a App) -> Option<&'a Buffer> {
         let (worktree, relative_path) =
             self.worktree_store.read(cx).find_worktree(&abs_path, cx)?;
@@ -10088,6 +10289,34 @@ async fn populate_labels_for_symbols(
     }
 }
 
+/// Returns the formatter to run for the trigger, unwrapping the formatters that are scoped to
+/// it, or `None` if the formatter is scoped to the other trigger.
+fn formatter_for_trigger(formatter: &Formatter, trigger: FormatTrigger) -> Option<&Formatter> {
+    match (formatter, trigger) {
+        (Formatter::OnSave(formatter), FormatTrigger::Save)
+        | (Formatter::Manual(formatter), FormatTrigger::Manual) => {
+            formatter_for_trigger(formatter, trigger)
+        }
+        (Formatter::OnSave(_), FormatTrigger::Manual)
+        | (Formatter::Manual(_), FormatTrigger::Save) => None,
+        (formatter, _) => Some(formatter),
+    }
+}
+
+/// Keeps only the hunks of the diff that intersect the ranges, so that formatters that can only
+/// format whole files can be used to format selections.
+fn restrict_diff_to_ranges(diff: &mut Diff, ranges: &[Range<Anchor>], snapshot: &BufferSnapshot) {
+    let ranges = ranges
+        .iter()
+        .map(|range| range.to_offset(snapshot))
+        .collect::<Vec<_>>();
+    diff.edits.retain(|(edit_range, _)| {
+        ranges
+            .iter()
+            .any(|range| edit_range.start <= range.end && edit_range.end >= range.start)
+    });
+}
+
 fn include_text(server: &lsp::LanguageServer) -> Option<bool> {
     match server.capabilities().text_document_sync.as_ref()? {
         lsp::TextDocumentSyncCapability::Kind(kind) => match *kind {
@@ -420,7 +420,7 @@ impl<'tree> ServerTreeRebase<'tree> {
                     .and_then(|roots| roots.get(&disposition.name))
                     .filter(|(old_node, _)| {
                         disposition.attach == old_node.attach
-                            && disposition.settings == old_node.settings
+                            && !disposition.settings.requires_restart(&old_node.settings)
                     })
                 else {
                     return Some(node);
@@ -20,7 +20,7 @@ use language::{
 use lsp::{LanguageServer, LanguageServerId, LanguageServerName};
 use node_runtime::NodeRuntime;
 use paths::default_prettier_dir;
-use prettier::Prettier;
+use prettier::{Prettier, PrettierFileInfo};
 use smol::stream::StreamExt;
 use util::{ResultExt, TryFutureExt};
 
@@ -271,6 +271,38 @@ impl PrettierStore {
         }
     }
 
+    /// Returns which Prettier formats the buffer and with which configuration, for
+    /// troubleshooting. Returns `None` if the buffer has no file or language, or is in
+    /// `node_modules`.
+    pub fn prettier_for_buffer(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<PrettierResolution>>> {
+        let Some(buffer_path) =
+            File::from_dyn(buffer.read(cx).file()).map(|file| file.abs_path(cx))
+        else {
+            return Task::ready(Ok(None));
+        };
+        let prettier_instance = self.prettier_instance_for_buffer(buffer, cx);
+        let ignore_dir = self.prettier_ignore_for_buffer(buffer, cx);
+        cx.spawn(async move |_, _| {
+            let Some((prettier_dir, prettier_task)) = prettier_instance.await else {
+                return Ok(None);
+            };
+            let ignore_dir = ignore_dir.await;
+            let prettier = prettier_task
+                .await
+                .map_err(|error| anyhow!("prettier failed to spawn: {error:#}"))?;
+            let file_info = prettier.file_info(&buffer_path, ignore_dir.clone()).await?;
+            Ok(Some(PrettierResolution {
+                prettier_dir,
+                ignore_dir,
+                file_info,
+            }))
+        })
+    }
+
     fn start_prettier(
         node: NodeRuntime,
         prettier_dir: PathBuf,
@@ -695,7 +727,8 @@ pub fn prettier_plugins_for_language(
 
         SelectedFormatter::List(list) => list
             .as_ref()
-            .contains(&Formatter::Prettier)
+            .iter()
+            .any(|formatter| formatter.unscoped() == &Formatter::Prettier)
             .then_some(&language_settings.prettier.plugins),
     }
 }
@@ -768,6 +801,17 @@ pub(super) async fn format_with_prettier(
     }
 }
 
+/// The Prettier that formats a buffer, and the configuration that it uses.
+#[derive(Debug)]
+pub struct PrettierResolution {
+    /// The directory of the project or workspace root that Prettier is installed in, or
+    /// `None` if Zed's default Prettier is used.
+    pub prettier_dir: Option<PathBuf>,
+    /// The directory of the `.prettierignore` file that applies to the buffer.
+    pub ignore_dir: Option<PathBuf>,
+    pub file_info: PrettierFileInfo,
+}
+
 pub struct DefaultPrettier {
     prettier: PrettierInstallation,
     installed_plugins: HashSet<Arc<str>>,
@@ -15,6 +15,7 @@ mod task_inventory;
 pub mod task_store;
 pub mod terminals;
 pub mod toolchain_store;
+pub mod workspace_trust;
 pub mod worktree_store;
 
 #[cfg(test)]
@@ -23,7 +24,7 @@ mod project_tests;
 mod direnv;
 mod environment;
 use buffer_diff::BufferDiff;
-pub use environment::{EnvironmentErrorMessage, ProjectEnvironmentEvent};
+pub use environment::{EnvironmentErrorMessage, ProjectEnvironmentEvent, SubsystemEnvironment};
 use git_store::{GitEvent, Repository};
 pub mod search_history;
 mod yarn;
@@ -98,9 +99,10 @@ use std::{
     sync::Arc,
     time::Duration,
 };
+use workspace_trust::WorkspaceTrustSettings;
 
 use task_store::TaskStore;
-use terminals::Terminals;
+use terminals::{RemoteTerminal, Terminals};
 use text::{Anchor, BufferId};
 use toolchain_store::EmptyToolchainStore;
 use util::{
@@ -133,6 +135,8 @@ pub use toolchain_store::ToolchainStore;
 const MAX_PROJECT_SEARCH_HISTORY_SIZE: usize = 500;
 const MAX_SEARCH_RESULT_FILES: usize = 5_000;
 const MAX_SEARCH_RESULT_RANGES: usize = 10_000;
+/// How many search results are found ahead of being received, after which searching waits.
+const SEARCH_RESULT_BUFFER_LEN: usize = 256;
 
 pub trait ProjectItem {
     fn try_open(
@@ -247,6 +251,11 @@ enum ProjectClientState {
     Remote {
         sharing_has_stopped: bool,
         capability: Capability,
+        /// The local user's role in the call, which limits what they can do in
+        /// the project regardless of the host's permissions.
+        role: proto::ChannelRole,
+        /// What the host of the project allows the local user to do.
+        permissions: proto::ProjectPermissions,
         remote_id: u64,
         replica_id: ReplicaId,
     },
@@ -264,6 +273,10 @@ pub enum Event {
     HideToast {
         notification_id: SharedString,
     },
+    /// The host changed what the local user is allowed to do in the project.
+    PermissionsChanged,
+    /// The host started sharing a terminal with the local user.
+    RemoteTerminalShared(Entity<RemoteTerminal>),
     LanguageServerPrompt(LanguageServerPromptRequest),
     LanguageNotFound(Entity<Buffer>),
     ActiveEntryChanged(Option<ProjectEntryId>),
@@ -272,6 +285,9 @@ pub enum Event {
     WorktreeOrderChanged,
     WorktreeRemoved(WorktreeId),
     WorktreeUpdatedEntries(WorktreeId, UpdatedEntriesSet),
+    /// Worktrees entered or left restricted mode, which ignores their tasks and their settings
+    /// that run programs until they're trusted.
+    RestrictedWorktreesChanged,
     DiskBasedDiagnosticsStarted {
         language_server_id: LanguageServerId,
     },
@@ -782,6 +798,7 @@ impl Project {
     pub fn init_settings(cx: &mut App) {
         WorktreeSettings::register(cx);
         ProjectSettings::register(cx);
+        WorkspaceTrustSettings::register(cx);
     }
 
     pub fn init(client: &Arc<Client>, cx: &mut App) {
@@ -791,6 +808,10 @@ impl Project {
         let client: AnyProtoClient = client.clone().into();
         client.add_entity_message_handler(Self::handle_add_collaborator);
         client.add_entity_message_handler(Self::handle_update_project_collaborator);
+        client.add_entity_message_handler(Self::handle_update_project_permissions);
+        client.add_entity_message_handler(Self::handle_update_shared_terminal);
+        client.add_entity_message_handler(Self::handle_unshare_terminal);
+        client.add_entity_request_handler(Self::handle_shared_terminal_input);
         client.add_entity_message_handler(Self::handle_remove_collaborator);
         client.add_entity_message_handler(Self::handle_update_project);
         client.add_entity_message_handler(Self::handle_unshare_project);
@@ -892,12 +913,18 @@ impl Project {
             });
 
             let settings_observer = cx.new(|cx| {
-                SettingsObserver::new_local(
+                let mut observer = SettingsObserver::new_local(
                     fs.clone(),
                     worktree_store.clone(),
                     task_store.clone(),
                     cx,
-                )
+                );
+                // Tests open folders that are trusted, unless they enforce workspace trust
+                // themselves.
+                if !cfg!(any(test, feature = "test-support")) {
+                    observer.enforce_workspace_trust(cx);
+                }
+                observer
             });
             cx.subscribe(&settings_observer, Self::on_settings_observer_event)
                 .detach();
@@ -956,9 +983,7 @@ impl Project {
 
                 buffers_needing_diff: Default::default(),
                 git_diff_debouncer: DebouncedDelay::new(),
-                terminals: Terminals {
-                    local_handles: Vec::new(),
-                },
+                terminals: Terminals::new(),
                 node: Some(node),
                 search_history: Self::new_search_history(),
                 environment,
@@ -1117,9 +1142,7 @@ impl Project {
                 ssh_client: Some(ssh.clone()),
                 buffers_needing_diff: Default::default(),
                 git_diff_debouncer: DebouncedDelay::new(),
-                terminals: Terminals {
-                    local_handles: Vec::new(),
-                },
+                terminals: Terminals::new(),
                 node: Some(node),
                 search_history: Self::new_search_history(),
                 environment,
@@ -1231,6 +1254,13 @@ impl Project {
     ) -> Result<Entity<Self>> {
         let remote_id = response.payload.project_id;
         let role = response.payload.role();
+        let permissions = response
+            .payload
+            .permissions
+            .unwrap_or(proto::ProjectPermissions {
+                can_edit: true,
+                ..Default::default()
+            });
 
         let worktree_store = cx.new(|_| {
             WorktreeStore::remote(true, client.clone().into(), response.payload.project_id)
@@ -1345,6 +1375,8 @@ impl Project {
                 client_state: ProjectClientState::Remote {
                     sharing_has_stopped: false,
                     capability: Capability::ReadWrite,
+                    role,
+                    permissions,
                     remote_id,
                     replica_id,
                 },
@@ -1353,9 +1385,7 @@ impl Project {
                 git_store: git_store.clone(),
                 buffers_needing_diff: Default::default(),
                 git_diff_debouncer: DebouncedDelay::new(),
-                terminals: Terminals {
-                    local_handles: Vec::new(),
-                },
+                terminals: Terminals::new(),
                 node: None,
                 search_history: Self::new_search_history(),
                 search_included_history: Self::new_search_history(),
@@ -1364,7 +1394,7 @@ impl Project {
                 remotely_created_models: Arc::new(Mutex::new(RemotelyCreatedModels::default())),
                 toolchain_store: None,
             };
-            this.set_role(role, cx);
+            this.update_capability(cx);
             for worktree in worktrees {
                 this.add_worktree(&worktree, cx);
             }
@@ -1649,6 +1679,25 @@ impl Project {
         &self.environment
     }
 
+    /// Returns whether the worktree is in restricted mode, because its folder isn't trusted.
+    /// Sets the settings of the workspace file that the project was opened from, which apply to
+    /// its local worktrees beneath the settings files at their roots.
+    pub fn set_workspace_settings(
+        &mut self,
+        workspace_settings: Option<serde_json::Value>,
+        cx: &mut Context<Self>,
+    ) {
+        self.settings_observer.update(cx, |settings_observer, cx| {
+            settings_observer.set_workspace_settings(workspace_settings, cx)
+        });
+    }
+
+    pub fn is_worktree_restricted(&self, worktree_id: WorktreeId, cx: &App) -> bool {
+        self.settings_observer
+            .read(cx)
+            .is_worktree_restricted(worktree_id)
+    }
+
     pub fn cli_environment(&self, cx: &App) -> Option<HashMap<String, String>> {
         self.environment.read(cx).get_cli_environment()
     }
@@ -2141,6 +2190,7 @@ impl Project {
             self.client_state = ProjectClientState::Local;
             self.collaborators.clear();
             self.client_subscriptions.clear();
+            self.terminals.shared.clear();
             self.worktree_store.update(cx, |store, cx| {
                 store.unshared(cx);
             });
@@ -2184,13 +2234,45 @@ impl Project {
     }
 
     pub fn set_role(&mut self, role: proto::ChannelRole, cx: &mut Context<Self>) {
-        let new_capability =
-            if role == proto::ChannelRole::Member || role == proto::ChannelRole::Admin {
+        if let ProjectClientState::Remote {
+            role: current_role, ..
+        } = &mut self.client_state
+        {
+            *current_role = role;
+        }
+        self.update_capability(cx);
+    }
+
+    fn set_permissions(&mut self, permissions: proto::ProjectPermissions, cx: &mut Context<Self>) {
+        if let ProjectClientState::Remote {
+            permissions: current_permissions,
+            ..
+        } = &mut self.client_state
+        {
+            if *current_permissions == permissions {
+                return;
+            }
+            *current_permissions = permissions;
+        }
+        self.update_capability(cx);
+        cx.emit(Event::PermissionsChanged);
+    }
+
+    fn update_capability(&mut self, cx: &mut Context<Self>) {
+        if let ProjectClientState::Remote {
+            capability,
+            role,
+            permissions,
+            ..
+        } = &mut self.client_state
+        {
+            let can_edit =
+                *role == proto::ChannelRole::Member || *role == proto::ChannelRole::Admin;
+            let new_capability = if can_edit && permissions.can_edit {
                 Capability::ReadWrite
             } else {
                 Capability::ReadOnly
             };
-        if let ProjectClientState::Remote { capability, .. } = &mut self.client_state {
             if *capability == new_capability {
                 return;
             }
@@ -2218,6 +2300,7 @@ impl Project {
             });
             self.lsp_store
                 .update(cx, |lsp_store, _cx| lsp_store.disconnected_from_host());
+            self.close_remote_terminals(cx);
         }
     }
 
@@ -2250,6 +2333,58 @@ impl Project {
         }
     }
 
+    /// Returns what the host of the project allows the local user to do.
+    pub fn permissions(&self) -> proto::ProjectPermissions {
+        match &self.client_state {
+            ProjectClientState::Remote { permissions, .. } => *permissions,
+            ProjectClientState::Shared { .. } | ProjectClientState::Local => {
+                proto::ProjectPermissions {
+                    can_edit: true,
+                    can_use_terminal: true,
+                    can_run_tasks: true,
+                }
+            }
+        }
+    }
+
+    /// Changes what the given collaborator is allowed to do in this project,
+    /// which must be shared by the local user.
+    pub fn set_collaborator_permissions(
+        &mut self,
+        user_id: u64,
+        permissions: proto::ProjectPermissions,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let ProjectClientState::Shared { remote_id } = self.client_state else {
+            return Task::ready(Err(anyhow!("project is not shared")));
+        };
+        let request = self.client.request(proto::UpdateProjectPermissions {
+            project_id: remote_id,
+            user_id,
+            permissions: Some(permissions),
+        });
+        cx.spawn(async move |this, cx| {
+            request.await?;
+            this.update(cx, |this, cx| {
+                this.update_collaborator_permissions(user_id, permissions, cx)
+            })
+        })
+    }
+
+    fn update_collaborator_permissions(
+        &mut self,
+        user_id: u64,
+        permissions: proto::ProjectPermissions,
+        cx: &mut Context<Self>,
+    ) {
+        for collaborator in self.collaborators.values_mut() {
+            if collaborator.user_id == user_id && !collaborator.is_host {
+                collaborator.permissions = permissions;
+            }
+        }
+        cx.notify();
+    }
+
     pub fn is_read_only(&self, cx: &App) -> bool {
         self.is_disconnected(cx) || self.capability() == Capability::ReadOnly
     }
@@ -2818,6 +2953,13 @@ impl Project {
                 }),
                 Err(_) => {}
             },
+            SettingsObserverEvent::RestrictedWorktreesChanged => {
+                let restricted_worktrees = self.settings_observer.read(cx).restricted_worktrees();
+                self.environment.update(cx, |environment, _| {
+                    environment.set_restricted_worktrees(restricted_worktrees)
+                });
+                cx.emit(Event::RestrictedWorktreesChanged)
+            }
             SettingsObserverEvent::LocalTasksUpdated(result) => match result {
                 Err(InvalidSettingsError::Tasks { message, path }) => {
                     let message = format!("Failed to set local tasks in {path:?}:\n{message}");
@@ -3036,6 +3178,26 @@ impl Project {
         })
     }
 
+    pub fn restart_language_servers(
+        &mut self,
+        server_ids: Vec<LanguageServerId>,
+        cx: &mut Context<Self>,
+    ) {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.restart_language_servers(server_ids, cx)
+        })
+    }
+
+    pub fn stop_language_servers(
+        &mut self,
+        server_ids: Vec<LanguageServerId>,
+        cx: &mut Context<Self>,
+    ) {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.stop_language_servers(server_ids, cx)
+        })
+    }
+
     pub fn cancel_language_server_work_for_buffers(
         &mut self,
         buffers: impl IntoIterator<Item = Entity<Buffer>>,
@@ -3473,6 +3635,19 @@ impl Project {
         })
     }
 
+    /// Fills in the parts of a code action that its language server left out when listing it,
+    /// like its edit, without applying it.
+    pub fn resolve_code_action(
+        &self,
+        buffer_handle: &Entity<Buffer>,
+        action: CodeAction,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<CodeAction>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.resolve_code_action(buffer_handle, action, cx)
+        })
+    }
+
     pub fn apply_code_action_kind(
         &self,
         buffers: HashSet<Entity<Buffer>>,
@@ -3568,12 +3743,43 @@ impl Project {
     }
 
     pub fn search(&mut self, query: SearchQuery, cx: &mut Context<Self>) -> Receiver<SearchResult> {
-        let (result_tx, result_rx) = smol::channel::unbounded();
+        self.search_with_limits(
+            query,
+            Some(MAX_SEARCH_RESULT_FILES),
+            Some(MAX_SEARCH_RESULT_RANGES),
+            cx,
+        )
+    }
+
+    /// Searches the project like [`Self::search`], but without limiting the number of matches.
+    /// Matches are only searched for as fast as they are received, so the caller decides how many
+    /// of them are held in memory by how far ahead it reads.
+    ///
+    /// Remote projects still limit the number of files searched, as their candidates are opened
+    /// by the host up front.
+    pub fn search_streaming(
+        &mut self,
+        query: SearchQuery,
+        cx: &mut Context<Self>,
+    ) -> Receiver<SearchResult> {
+        let max_files = (!self.is_local()).then_some(MAX_SEARCH_RESULT_FILES);
+        self.search_with_limits(query, max_files, None, cx)
+    }
+
+    fn search_with_limits(
+        &mut self,
+        query: SearchQuery,
+        max_files: Option<usize>,
+        max_ranges: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> Receiver<SearchResult> {
+        let (result_tx, result_rx) = smol::channel::bounded(SEARCH_RESULT_BUFFER_LEN);
 
         let matching_buffers_rx = if query.is_opened_only() {
             self.sort_search_candidates(&query, cx)
         } else {
-            self.find_search_candidate_buffers(&query, MAX_SEARCH_RESULT_FILES + 1, cx)
+            let limit = max_files.map_or(usize::MAX, |max_files| max_files + 1);
+            self.find_search_candidate_buffers(&query, limit, cx)
         };
 
         cx.spawn(async move |_, cx| {
@@ -3616,8 +3822,8 @@ impl Project {
                         result_tx
                             .send(SearchResult::Buffer { buffer, ranges })
                             .await?;
-                        if buffer_count > MAX_SEARCH_RESULT_FILES
-                            || range_count > MAX_SEARCH_RESULT_RANGES
+                        if max_files.map_or(false, |max_files| buffer_count > max_files)
+                            || max_ranges.map_or(false, |max_ranges| range_count > max_ranges)
                         {
                             limit_reached = true;
                             break 'outer;
@@ -4196,6 +4402,7 @@ impl Project {
                 buffer_store.forget_shared_buffers_for(&collaborator.peer_id);
             });
             this.breakpoint_store.read(cx).broadcast();
+            this.resend_shared_terminals(cx);
             cx.emit(Event::CollaboratorJoined(collaborator.peer_id));
             this.collaborators
                 .insert(collaborator.peer_id, collaborator);
@@ -4204,6 +4411,24 @@ impl Project {
         Ok(())
     }
 
+    async fn handle_update_project_permissions(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UpdateProjectPermissions>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let user_id = envelope.payload.user_id;
+        let permissions = envelope
+            .payload
+            .permissions
+            .ok_or_else(|| anyhow!("missing permissions"))?;
+        this.update(&mut cx, |this, cx| {
+            this.update_collaborator_permissions(user_id, permissions, cx);
+            if this.client.user_id() == Some(user_id) {
+                this.set_permissions(permissions, cx);
+            }
+        })
+    }
+
     async fn handle_update_project_collaborator(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::UpdateProjectCollaborator>,
@@ -4270,6 +4495,7 @@ impl Project {
             this.git_store.update(cx, |git_store, _| {
                 git_store.forget_shared_diffs_for(&peer_id);
             });
+            this.forget_shared_terminal_input_controller(peer_id, cx);
 
             cx.emit(Event::CollaboratorLeft(peer_id));
             Ok(())
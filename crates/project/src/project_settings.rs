@@ -1,9 +1,9 @@
 use anyhow::Context as _;
-use collections::HashMap;
+use collections::{HashMap, HashSet};
 use dap::adapters::DebugAdapterName;
 use fs::Fs;
 use futures::StreamExt as _;
-use gpui::{App, AsyncApp, BorrowAppContext, Context, Entity, EventEmitter, Task};
+use gpui::{App, AsyncApp, BorrowAppContext, Context, Entity, EventEmitter, Subscription, Task};
 use lsp::LanguageServerName;
 use paths::{
     local_debug_file_relative_path, local_settings_file_relative_path,
@@ -30,6 +30,7 @@ use worktree::{PathChange, UpdatedEntriesSet, Worktree, WorktreeId};
 
 use crate::{
     task_store::{TaskSettingsLocation, TaskStore},
+    workspace_trust::{remove_restricted_settings, WorkspaceTrustSettings},
     worktree_store::{WorktreeStore, WorktreeStoreEvent},
 };
 
@@ -66,6 +67,14 @@ pub struct ProjectSettings {
     #[serde(default)]
     pub load_direnv: DirenvSettings,
 
+    /// Environment variables to set for the terminals, tasks, debug sessions and language
+    /// servers spawned in the project. These take precedence over the variables of the
+    /// project's `.zed/env` file and its shell environment.
+    ///
+    /// Default: {}
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+
     /// Configuration for session-related features
     #[serde(default)]
     pub session: SessionSettings,
@@ -271,6 +280,12 @@ const fn true_value() -> bool {
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 pub struct BinarySettings {
+    /// The path to the language server binary.
+    ///
+    /// A leading `~` is expanded to the home directory, and relative paths
+    /// such as `./node_modules/.bin/server` are resolved against the worktree
+    /// root, which allows project settings to point at project-local binaries.
+    /// Bare command names are looked up on the `PATH`.
     pub path: Option<String>,
     pub arguments: Option<Vec<String>>,
     // this can't be an FxHashMap because the extension APIs require the default SipHash
@@ -278,6 +293,19 @@ pub struct BinarySettings {
     pub ignore_system_version: Option<bool>,
 }
 
+impl BinarySettings {
+    /// Returns the configured binary path, resolved relative to the given worktree root.
+    pub fn resolved_path(&self, worktree_root: &Path) -> Option<PathBuf> {
+        let path = self.path.as_deref()?;
+        let path = PathBuf::from(shellexpand::tilde(path).as_ref());
+        if path.is_relative() && path.components().count() > 1 {
+            Some(worktree_root.join(path))
+        } else {
+            Some(path)
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct LspSettings {
@@ -286,6 +314,16 @@ pub struct LspSettings {
     pub settings: Option<serde_json::Value>,
 }
 
+impl LspSettings {
+    /// Whether switching from `self` to `other` requires restarting the language server.
+    ///
+    /// Changes to `settings` are delivered to running servers via `workspace/didChangeConfiguration`,
+    /// but the binary and the initialization options only take effect when the server starts.
+    pub fn requires_restart(&self, other: &Self) -> bool {
+        self.binary != other.binary || self.initialization_options != other.initialization_options
+    }
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SessionSettings {
     /// Whether or not to restore unsaved buffers on restart.
@@ -324,6 +362,7 @@ pub enum SettingsObserverMode {
 pub enum SettingsObserverEvent {
     LocalSettingsUpdated(Result<PathBuf, InvalidSettingsError>),
     LocalTasksUpdated(Result<PathBuf, InvalidSettingsError>),
+    RestrictedWorktreesChanged,
 }
 
 impl EventEmitter<SettingsObserverEvent> for SettingsObserver {}
@@ -334,7 +373,17 @@ pub struct SettingsObserver {
     worktree_store: Entity<WorktreeStore>,
     project_id: u64,
     task_store: Entity<TaskStore>,
+    /// The worktrees in restricted mode, or `None` when restricted mode isn't enforced.
+    restricted_worktrees: Option<HashSet<WorktreeId>>,
+    /// The contents of the local worktrees' settings and tasks files, to apply again when the
+    /// worktrees enter or leave restricted mode.
+    local_settings_files:
+        HashMap<WorktreeId, HashMap<(Arc<Path>, LocalSettingsKind), Option<String>>>,
+    /// The settings of the workspace file that the project was opened from, which apply to the
+    /// local worktrees beneath the settings files at their roots.
+    workspace_settings: Option<serde_json::Value>,
     _global_task_config_watchers: (Task<()>, Task<()>),
+    _workspace_trust_subscription: Option<Subscription>,
 }
 
 /// SettingsObserver observers changes to .zed/{settings, task}.json files in local worktrees
@@ -362,6 +411,10 @@ impl SettingsObserver {
             mode: SettingsObserverMode::Local(fs.clone()),
             downstream_client: None,
             project_id: 0,
+            restricted_worktrees: None,
+            local_settings_files: HashMap::default(),
+            workspace_settings: None,
+            _workspace_trust_subscription: None,
             _global_task_config_watchers: (
                 Self::subscribe_to_global_task_file_changes(
                     fs.clone(),
@@ -391,6 +444,10 @@ impl SettingsObserver {
             mode: SettingsObserverMode::Remote,
             downstream_client: None,
             project_id: 0,
+            restricted_worktrees: None,
+            local_settings_files: HashMap::default(),
+            workspace_settings: None,
+            _workspace_trust_subscription: None,
             _global_task_config_watchers: (
                 Self::subscribe_to_global_task_file_changes(
                     fs.clone(),
@@ -453,6 +510,112 @@ impl SettingsObserver {
         self.downstream_client = None;
     }
 
+    /// Opens the local worktrees that aren't trusted in restricted mode, which ignores their
+    /// tasks and their settings that run programs until they're trusted.
+    pub fn enforce_workspace_trust(&mut self, cx: &mut Context<Self>) {
+        if self.restricted_worktrees.is_some() {
+            return;
+        }
+        self.restricted_worktrees = Some(HashSet::default());
+        self._workspace_trust_subscription = Some(
+            cx.observe_global::<SettingsStore>(|this, cx| this.update_restricted_worktrees(cx)),
+        );
+        self.update_restricted_worktrees(cx);
+    }
+
+    /// Sets the settings of the workspace file that the project was opened from, which apply to
+    /// the local worktrees beneath the settings files at their roots.
+    pub fn set_workspace_settings(
+        &mut self,
+        workspace_settings: Option<serde_json::Value>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.workspace_settings == workspace_settings {
+            return;
+        }
+        self.workspace_settings = workspace_settings;
+        let worktrees = self.worktree_store.read(cx).worktrees().collect::<Vec<_>>();
+        for worktree in worktrees {
+            self.update_root_settings(worktree, cx);
+        }
+    }
+
+    /// Applies the settings file at the root of a local worktree again, along with the workspace
+    /// settings.
+    fn update_root_settings(&mut self, worktree: Entity<Worktree>, cx: &mut Context<Self>) {
+        if !matches!(self.mode, SettingsObserverMode::Local(_)) {
+            return;
+        }
+        let root = Arc::<Path>::from(Path::new(""));
+        let content = self
+            .local_settings_files
+            .get(&worktree.read(cx).id())
+            .and_then(|settings_files| {
+                settings_files
+                    .get(&(root.clone(), LocalSettingsKind::Settings))
+                    .cloned()
+            })
+            .flatten();
+        self.update_settings(worktree, [(root, LocalSettingsKind::Settings, content)], cx);
+    }
+
+    /// Returns the worktrees in restricted mode.
+    pub fn restricted_worktrees(&self) -> HashSet<WorktreeId> {
+        self.restricted_worktrees.clone().unwrap_or_default()
+    }
+
+    pub fn is_worktree_restricted(&self, worktree_id: WorktreeId) -> bool {
+        self.restricted_worktrees
+            .as_ref()
+            .is_some_and(|restricted_worktrees| restricted_worktrees.contains(&worktree_id))
+    }
+
+    fn update_restricted_worktrees(&mut self, cx: &mut Context<Self>) {
+        let Some(restricted_worktrees) = self.restricted_worktrees.as_ref() else {
+            return;
+        };
+        let trust_settings = WorkspaceTrustSettings::get_global(cx);
+        let changed_worktrees = self
+            .worktree_store
+            .read(cx)
+            .worktrees()
+            .filter_map(|worktree| {
+                let worktree_id = worktree.read(cx).id();
+                let restricted = worktree.read(cx).is_local()
+                    && !trust_settings.is_trusted(&worktree.read(cx).abs_path());
+                (restricted != restricted_worktrees.contains(&worktree_id))
+                    .then_some((worktree, restricted))
+            })
+            .collect::<Vec<_>>();
+        if changed_worktrees.is_empty() {
+            return;
+        }
+
+        for (worktree, restricted) in changed_worktrees {
+            let worktree_id = worktree.read(cx).id();
+            if let Some(restricted_worktrees) = self.restricted_worktrees.as_mut() {
+                if restricted {
+                    restricted_worktrees.insert(worktree_id);
+                } else {
+                    restricted_worktrees.remove(&worktree_id);
+                }
+            }
+            let settings_files = self
+                .local_settings_files
+                .get(&worktree_id)
+                .cloned()
+                .unwrap_or_default();
+            self.update_settings(
+                worktree,
+                settings_files
+                    .into_iter()
+                    .map(|((directory, kind), content)| (directory, kind, content)),
+                cx,
+            );
+        }
+        cx.emit(SettingsObserverEvent::RestrictedWorktreesChanged);
+    }
+
     async fn handle_update_worktree_settings(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::UpdateWorktreeSettings>,
@@ -492,13 +655,29 @@ impl SettingsObserver {
         event: &WorktreeStoreEvent,
         cx: &mut Context<Self>,
     ) {
-        if let WorktreeStoreEvent::WorktreeAdded(worktree) = event {
-            cx.subscribe(worktree, |this, worktree, event, cx| {
-                if let worktree::Event::UpdatedEntries(changes) = event {
-                    this.update_local_worktree_settings(&worktree, changes, cx)
+        match event {
+            WorktreeStoreEvent::WorktreeAdded(worktree) => {
+                // Worktrees enter restricted mode before their settings files are loaded.
+                self.update_restricted_worktrees(cx);
+                if self.workspace_settings.is_some() {
+                    self.update_root_settings(worktree.clone(), cx);
                 }
-            })
-            .detach()
+                cx.subscribe(worktree, |this, worktree, event, cx| {
+                    if let worktree::Event::UpdatedEntries(changes) = event {
+                        this.update_local_worktree_settings(&worktree, changes, cx)
+                    }
+                })
+                .detach()
+            }
+            WorktreeStoreEvent::WorktreeRemoved(_, worktree_id) => {
+                self.local_settings_files.remove(worktree_id);
+                if let Some(restricted_worktrees) = self.restricted_worktrees.as_mut() {
+                    if restricted_worktrees.remove(worktree_id) {
+                        cx.emit(SettingsObserverEvent::RestrictedWorktreesChanged);
+                    }
+                }
+            }
+            _ => {}
         }
     }
 
@@ -647,6 +826,42 @@ impl SettingsObserver {
         let task_store = self.task_store.clone();
 
         for (directory, kind, file_content) in settings_contents {
+            if let SettingsObserverMode::Local(_) = self.mode {
+                let settings_files = self.local_settings_files.entry(worktree_id).or_default();
+                match &file_content {
+                    Some(content) => {
+                        settings_files.insert((directory.clone(), kind), Some(content.clone()));
+                    }
+                    None => {
+                        settings_files.remove(&(directory.clone(), kind));
+                    }
+                }
+            }
+            let file_content = match &self.workspace_settings {
+                Some(workspace_settings)
+                    if kind == LocalSettingsKind::Settings
+                        && directory.as_os_str().is_empty()
+                        && matches!(self.mode, SettingsObserverMode::Local(_)) =>
+                {
+                    Some(merge_workspace_settings(
+                        workspace_settings,
+                        file_content.as_deref(),
+                    ))
+                }
+                _ => file_content,
+            };
+            let file_content = if self.is_worktree_restricted(worktree_id) {
+                match kind {
+                    LocalSettingsKind::Settings => {
+                        file_content.map(|content| remove_restricted_settings(&content))
+                    }
+                    LocalSettingsKind::Tasks(_) => None,
+                    LocalSettingsKind::Editorconfig => file_content,
+                }
+            } else {
+                file_content
+            };
+
             match kind {
                 LocalSettingsKind::Settings | LocalSettingsKind::Editorconfig => cx
                     .update_global::<SettingsStore, _>(|store, cx| {
@@ -781,6 +996,23 @@ impl SettingsObserver {
     }
 }
 
+/// Merges the contents of the settings file at the root of a worktree over the settings of the
+/// workspace file. Contents that can't be parsed are returned as they are, since they're rejected
+/// when they're applied.
+fn merge_workspace_settings(
+    workspace_settings: &serde_json::Value,
+    content: Option<&str>,
+) -> String {
+    let mut settings = workspace_settings.clone();
+    if let Some(content) = content {
+        let Ok(file_settings) = parse_json_with_comments::<serde_json::Value>(content) else {
+            return content.to_string();
+        };
+        util::merge_non_null_json_value_into(file_settings, &mut settings);
+    }
+    settings.to_string()
+}
+
 pub fn local_settings_kind_from_proto(kind: proto::LocalSettingsKind) -> LocalSettingsKind {
     match kind {
         proto::LocalSettingsKind::Settings => LocalSettingsKind::Settings,
@@ -10,7 +10,10 @@ use git::repository::RepoPath;
 use gpui::{App, BackgroundExecutor, SemanticVersion, UpdateGlobal};
 use http_client::Url;
 use language::{
-    language_settings::{language_settings, AllLanguageSettings, LanguageSettingsContent},
+    language_settings::{
+        language_settings, AllLanguageSettings, FormatOnSave, LanguageSettingsContent,
+        LineEndingSetting,
+    },
     tree_sitter_rust, tree_sitter_typescript, Diagnostic, DiagnosticEntry, DiagnosticSet,
     DiskState, FakeLspAdapter, LanguageConfig, LanguageMatcher, LanguageName, LineEnding,
     OffsetRangeExt, Point, ToPoint,
@@ -206,6 +209,65 @@ async fn test_editorconfig_support(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_workspace_settings(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/code"),
+        json!({
+            "app": {
+                ".zed": {
+                    "settings.json": r#"{ "tab_size": 8 }"#,
+                },
+                "app.rs": "fn app() {}",
+            },
+            "lib": {
+                "lib.rs": "fn lib() {}",
+            },
+        }),
+    )
+    .await;
+
+    let project = Project::test(
+        fs,
+        [path!("/code/app").as_ref(), path!("/code/lib").as_ref()],
+        cx,
+    )
+    .await;
+    cx.executor().run_until_parked();
+
+    let file_settings = |cx: &mut gpui::TestAppContext| {
+        cx.update(|cx| {
+            project
+                .read(cx)
+                .worktrees(cx)
+                .zip(["app.rs", "lib.rs"])
+                .map(|(worktree, path)| {
+                    let file = File::for_entry(
+                        worktree.read(cx).entry_for_path(path).unwrap().clone(),
+                        worktree.clone(),
+                    ) as _;
+                    let settings = language_settings(None, Some(&file), cx);
+                    (settings.tab_size.get(), settings.hard_tabs)
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    project.update(cx, |project, cx| {
+        project.set_workspace_settings(Some(json!({ "tab_size": 3, "hard_tabs": true })), cx)
+    });
+    cx.executor().run_until_parked();
+    // The settings files in the folders take precedence over the workspace settings.
+    assert_eq!(file_settings(cx), [(8, true), (3, true)]);
+
+    project.update(cx, |project, cx| project.set_workspace_settings(None, cx));
+    cx.executor().run_until_parked();
+    assert_eq!(file_settings(cx), [(8, false), (4, false)]);
+}
+
 #[gpui::test]
 async fn test_managing_project_specific_settings(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -1872,6 +1934,181 @@ async fn test_toggling_enable_language_server(cx: &mut gpui::TestAppContext) {
         .await;
 }
 
+#[gpui::test]
+async fn test_restarting_and_stopping_a_single_language_server(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/dir"), json!({ "a.rs": "", "b.js": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+
+    let mut fake_rust_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            name: "rust-lsp",
+            ..Default::default()
+        },
+    );
+    let mut fake_js_servers = language_registry.register_fake_lsp(
+        "JavaScript",
+        FakeLspAdapter {
+            name: "js-lsp",
+            ..Default::default()
+        },
+    );
+    language_registry.add(rust_lang());
+    language_registry.add(js_lang());
+
+    let _rs_buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let _js_buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/b.js"), cx)
+        })
+        .await
+        .unwrap();
+
+    let mut fake_rust_server_1 = fake_rust_servers.next().await.unwrap();
+    fake_rust_server_1
+        .receive_notification::<lsp::notification::DidOpenTextDocument>()
+        .await;
+    let mut fake_js_server = fake_js_servers.next().await.unwrap();
+    fake_js_server
+        .receive_notification::<lsp::notification::DidOpenTextDocument>()
+        .await;
+    let rust_server_id = fake_rust_server_1.server.server_id();
+    let js_server_id = fake_js_server.server.server_id();
+
+    // Restarting the Rust language server restarts only that server, and reopens its buffers
+    // in the new one.
+    project.update(cx, |project, cx| {
+        project.restart_language_servers(vec![rust_server_id], cx);
+    });
+    fake_rust_server_1
+        .receive_notification::<lsp::notification::Exit>()
+        .await;
+    let mut fake_rust_server_2 = fake_rust_servers.next().await.unwrap();
+    assert_eq!(
+        fake_rust_server_2
+            .receive_notification::<lsp::notification::DidOpenTextDocument>()
+            .await
+            .text_document
+            .uri
+            .as_str(),
+        uri!("file:///dir/a.rs")
+    );
+    cx.executor().run_until_parked();
+    assert!(fake_js_servers.try_next().is_err());
+    project.read_with(cx, |project, cx| {
+        let server_ids = project
+            .language_server_statuses(cx)
+            .map(|(server_id, _)| server_id)
+            .collect::<Vec<_>>();
+        assert!(server_ids.contains(&js_server_id));
+        assert!(!server_ids.contains(&rust_server_id));
+        assert!(server_ids.contains(&fake_rust_server_2.server.server_id()));
+    });
+
+    // Stopping the JavaScript language server stops only that server.
+    project.update(cx, |project, cx| {
+        project.stop_language_servers(vec![js_server_id], cx);
+    });
+    fake_js_server
+        .receive_notification::<lsp::notification::Exit>()
+        .await;
+    cx.executor().run_until_parked();
+    assert!(fake_rust_servers.try_next().is_err());
+    project.read_with(cx, |project, cx| {
+        let server_ids = project
+            .language_server_statuses(cx)
+            .map(|(server_id, _)| server_id)
+            .collect::<Vec<_>>();
+        assert_eq!(server_ids, [fake_rust_server_2.server.server_id()]);
+    });
+}
+
+#[gpui::test]
+async fn test_language_server_binary_from_project_settings(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            ".zed": {
+                "settings.json": r#"{
+                    "lsp": {
+                        "rust-lsp": {
+                            "binary": {
+                                "path": "./bin/rust-lsp",
+                                "arguments": ["--stdio"]
+                            }
+                        }
+                    }
+                }"#
+            },
+            "a.rs": "",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    let mut fake_rust_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            name: "rust-lsp",
+            ..Default::default()
+        },
+    );
+    language_registry.add(rust_lang());
+    cx.executor().run_until_parked();
+
+    let _buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let mut fake_server_1 = fake_rust_servers.next().await.unwrap();
+    assert_eq!(
+        fake_server_1.binary.path,
+        Path::new(path!("/dir")).join("./bin/rust-lsp")
+    );
+    assert_eq!(fake_server_1.binary.arguments, vec!["--stdio"]);
+
+    // Changing the binary in the project settings restarts the server with the new binary.
+    fs.insert_file(
+        path!("/dir/.zed/settings.json"),
+        r#"{
+            "lsp": {
+                "rust-lsp": {
+                    "binary": {
+                        "path": "./bin/rust-lsp",
+                        "arguments": ["--stdio", "--verbose"]
+                    }
+                }
+            }
+        }"#
+        .as_bytes()
+        .to_vec(),
+    )
+    .await;
+    fake_server_1
+        .receive_notification::<lsp::notification::Exit>()
+        .await;
+    let fake_server_2 = fake_rust_servers.next().await.unwrap();
+    assert_eq!(fake_server_2.binary.arguments, vec!["--stdio", "--verbose"]);
+}
+
 #[gpui::test(iterations = 3)]
 async fn test_transforming_diagnostics(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -4113,6 +4350,68 @@ async fn test_buffer_line_endings(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_changing_buffer_line_ending_and_bom(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/dir"), json!({})).await;
+    fs.insert_file(path!("/dir/file1"), b"\xef\xbb\xbfa\nb\n".to_vec())
+        .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/file1"), cx))
+        .await
+        .unwrap();
+    buffer.update(cx, |buffer, _| {
+        assert_eq!(buffer.text(), "a\nb\n");
+        assert!(buffer.has_bom());
+        assert!(!buffer.is_dirty());
+    });
+
+    // Changing the line ending or the byte order mark makes the buffer dirty
+    // until it's saved.
+    buffer.update(cx, |buffer, cx| {
+        buffer.set_line_ending(LineEnding::Windows, cx);
+        buffer.set_has_bom(false, cx);
+        assert!(buffer.is_dirty());
+    });
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        fs.load_bytes(path!("/dir/file1").as_ref()).await.unwrap(),
+        b"a\r\nb\r\n"
+    );
+    buffer.update(cx, |buffer, _| assert!(!buffer.is_dirty()));
+
+    // Files are converted to the configured defaults when normalizing on save.
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |settings, cx| {
+            settings.update_user_settings::<AllLanguageSettings>(cx, |settings| {
+                settings.defaults.line_ending = Some(LineEndingSetting::Lf);
+                settings.defaults.byte_order_mark = Some(true);
+                settings.defaults.normalize_on_save = Some(true);
+            });
+        })
+    });
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        fs.load_bytes(path!("/dir/file1").as_ref()).await.unwrap(),
+        b"\xef\xbb\xbfa\nb\n"
+    );
+    buffer.update(cx, |buffer, _| {
+        assert_eq!(buffer.line_ending(), LineEnding::Unix);
+        assert!(buffer.has_bom());
+        assert!(!buffer.is_dirty());
+    });
+}
+
 #[gpui::test]
 async fn test_grouped_diagnostics(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -5628,6 +5927,113 @@ async fn test_code_actions_only_kinds(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_code_actions_sub_kinds(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.ts": "a",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(typescript_lang());
+    let mut fake_language_servers = language_registry.register_fake_lsp(
+        "TypeScript",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                code_action_provider: Some(lsp::CodeActionProviderCapability::Options(
+                    lsp::CodeActionOptions {
+                        code_action_kinds: Some(vec![
+                            CodeActionKind::QUICKFIX,
+                            CodeActionKind::REFACTOR_EXTRACT,
+                            CodeActionKind::REFACTOR_INLINE,
+                        ]),
+                        ..lsp::CodeActionOptions::default()
+                    },
+                )),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |p, cx| {
+            p.open_local_buffer_with_lsp(path!("/dir/a.ts"), cx)
+        })
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+
+    let fake_server = fake_language_servers
+        .next()
+        .await
+        .expect("failed to get the language server");
+
+    let mut request_handled = fake_server
+        .set_request_handler::<lsp::request::CodeActionRequest, _, _>(
+            move |params, _| async move {
+                assert_eq!(
+                    params.context.only,
+                    Some(vec![CodeActionKind::REFACTOR]),
+                    "a kind that contains the supported kinds should be requested"
+                );
+                Ok(Some(vec![
+                    lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+                        title: "extract function".to_string(),
+                        kind: Some(CodeActionKind::new("refactor.extract.function")),
+                        ..lsp::CodeAction::default()
+                    }),
+                    lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+                        title: "inline variable".to_string(),
+                        kind: Some(CodeActionKind::REFACTOR_INLINE),
+                        ..lsp::CodeAction::default()
+                    }),
+                    lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+                        title: "fix typo".to_string(),
+                        kind: Some(CodeActionKind::QUICKFIX),
+                        ..lsp::CodeAction::default()
+                    }),
+                    lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+                        title: "refactoring rewrite".to_string(),
+                        kind: Some(CodeActionKind::new("refactoring.rewrite")),
+                        ..lsp::CodeAction::default()
+                    }),
+                ]))
+            },
+        );
+
+    let code_actions_task = project.update(cx, |project, cx| {
+        project.code_actions(
+            &buffer,
+            0..buffer.read(cx).len(),
+            Some(vec![CodeActionKind::REFACTOR]),
+            cx,
+        )
+    });
+
+    let () = request_handled
+        .next()
+        .await
+        .expect("The code action request should have been triggered");
+
+    let code_actions = code_actions_task.await.unwrap();
+    assert_eq!(
+        code_actions
+            .iter()
+            .map(|action| action.lsp_action.title().to_string())
+            .collect::<Vec<_>>(),
+        vec!["extract function", "inline variable"]
+    );
+}
+
 #[gpui::test]
 async fn test_multiple_language_server_actions(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -7200,6 +7606,263 @@ async fn search(
         .collect())
 }
 
+#[gpui::test]
+async fn test_project_environment(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    cx.update(terminal::terminal_settings::TerminalSettings::register);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            ".zed": {
+                "env": r#"
+                    # Comments and empty lines are skipped.
+
+                    export DATABASE_URL="postgres://localhost/dev"
+                    LOG_LEVEL='debug'
+                    PROFILE=dev
+                "#.unindent(),
+                "settings.json": r#"{ "env": { "PROFILE": "release" } }"#,
+            },
+            "main.rs": "fn main() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    cx.executor().run_until_parked();
+    let worktree = project.update(cx, |project, cx| project.worktrees(cx).next().unwrap());
+    let (worktree_id, worktree_abs_path) =
+        worktree.read_with(cx, |worktree, _| (worktree.id(), worktree.abs_path()));
+
+    let env = project
+        .update(cx, |project, cx| {
+            project.environment().update(cx, |environment, cx| {
+                environment.get_environment(Some(worktree_id), Some(worktree_abs_path), cx)
+            })
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        env.get("DATABASE_URL").map(String::as_str),
+        Some("postgres://localhost/dev")
+    );
+    assert_eq!(env.get("LOG_LEVEL").map(String::as_str), Some("debug"));
+    // The `env` setting takes precedence over the `.zed/env` file.
+    assert_eq!(env.get("PROFILE").map(String::as_str), Some("release"));
+
+    fs.save(
+        path!("/dir/.zed/env").as_ref(),
+        &"LOG_LEVEL=info\n".into(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    cx.executor().run_until_parked();
+
+    let environments = project
+        .update(cx, |project, cx| {
+            project.environment().update(cx, |environment, cx| {
+                environment.subsystem_environments(&worktree, cx)
+            })
+        })
+        .await;
+    for environment in environments {
+        assert_eq!(
+            environment.env.get("LOG_LEVEL").map(String::as_str),
+            Some("info"),
+            "{}",
+            environment.subsystem
+        );
+        assert!(!environment.env.contains_key("DATABASE_URL"));
+    }
+}
+
+#[gpui::test]
+async fn test_restricted_mode(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    TaskStore::init(None);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            ".zed": {
+                "settings.json": r#"{
+                    "tab_size": 8,
+                    "lsp": { "rust-analyzer": { "binary": { "path": "./rust-analyzer" } } }
+                }"#,
+                "tasks.json": r#"[{ "label": "build", "command": "./build.sh" }]"#,
+            },
+            "a.rs": "fn a() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    project.update(cx, |project, cx| {
+        project
+            .settings_observer
+            .update(cx, |observer, cx| observer.enforce_workspace_trust(cx))
+    });
+    cx.executor().run_until_parked();
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let mut task_contexts = TaskContexts::default();
+    task_contexts.active_worktree_context = Some((worktree_id, TaskContext::default()));
+
+    let lsp_binary_path = |cx: &mut gpui::TestAppContext| {
+        cx.update(|cx| {
+            let settings = ProjectSettings::get(
+                Some(SettingsLocation {
+                    worktree_id,
+                    path: Path::new("a.rs"),
+                }),
+                cx,
+            );
+            settings
+                .lsp
+                .get(&LanguageServerName::new_static("rust-analyzer"))
+                .and_then(|settings| settings.binary.as_ref()?.path.clone())
+        })
+    };
+
+    // The settings that don't run programs still apply in restricted mode.
+    assert!(project.update(cx, |project, cx| project
+        .is_worktree_restricted(worktree_id, cx)));
+    assert_eq!(lsp_binary_path(cx), None);
+    cx.update(|cx| {
+        let file = project
+            .read(cx)
+            .worktrees(cx)
+            .next()
+            .unwrap()
+            .read(cx)
+            .entry_for_path("a.rs")
+            .cloned()
+            .unwrap();
+        let worktree = project.read(cx).worktrees(cx).next().unwrap();
+        let file = File::for_entry(file, worktree) as _;
+        assert_eq!(language_settings(None, Some(&file), cx).tab_size.get(), 8);
+        assert!(get_all_tasks(&project, &task_contexts, cx).is_empty());
+    });
+
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |settings, cx| {
+            settings.update_user_settings::<WorkspaceTrustSettings>(cx, |settings| {
+                settings.trusted_folders.push(PathBuf::from(path!("/dir")));
+            });
+        })
+    });
+    cx.executor().run_until_parked();
+
+    assert!(!project.update(cx, |project, cx| project
+        .is_worktree_restricted(worktree_id, cx)));
+    assert_eq!(lsp_binary_path(cx), Some("./rust-analyzer".to_string()));
+    cx.update(|cx| {
+        let labels = get_all_tasks(&project, &task_contexts, cx)
+            .into_iter()
+            .map(|(_, task)| task.resolved_label)
+            .collect::<Vec<_>>();
+        assert_eq!(labels, ["build"]);
+    });
+}
+
+#[gpui::test]
+async fn test_restricted_mode_environment_and_formatters(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    cx.update(terminal::terminal_settings::TerminalSettings::register);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            ".zed": {
+                "env": "PATH=./bin\n",
+                "settings.json": r#"{
+                    "env": { "EDITOR": "./editor" },
+                    "format_on_save": [{ "external": { "command": "./format.sh" } }],
+                    "languages": {
+                        "Rust": {
+                            "format_on_save": [{ "external": { "command": "./format.sh" } }],
+                            "prettier": { "plugins": ["./plugin.js"] }
+                        }
+                    }
+                }"#,
+            },
+            "a.rs": "fn a() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    project.update(cx, |project, cx| {
+        project
+            .settings_observer
+            .update(cx, |observer, cx| observer.enforce_workspace_trust(cx))
+    });
+    cx.executor().run_until_parked();
+    let worktree = project.update(cx, |project, cx| project.worktrees(cx).next().unwrap());
+    let (worktree_id, worktree_abs_path) =
+        worktree.read_with(cx, |worktree, _| (worktree.id(), worktree.abs_path()));
+
+    let project_env = |cx: &mut gpui::TestAppContext| {
+        project.update(cx, |project, cx| {
+            project
+                .environment()
+                .read(cx)
+                .project_env(Some(worktree_id), cx)
+        })
+    };
+    let format_on_save = |cx: &mut gpui::TestAppContext| {
+        cx.update(|cx| {
+            let entry = worktree.read(cx).entry_for_path("a.rs").cloned().unwrap();
+            let file = File::for_entry(entry, worktree.clone()) as _;
+            let settings = language_settings(Some("Rust".into()), Some(&file), cx);
+            (
+                settings.format_on_save.clone(),
+                settings.prettier.plugins.clone(),
+            )
+        })
+    };
+
+    // A restricted folder can't put its own programs on the `PATH` or run formatters on save.
+    assert!(project.update(cx, |project, cx| project
+        .is_worktree_restricted(worktree_id, cx)));
+    assert!(project_env(cx).is_empty());
+    let (format_on_save_setting, prettier_plugins) = format_on_save(cx);
+    assert!(!matches!(format_on_save_setting, FormatOnSave::List(_)));
+    assert!(prettier_plugins.is_empty());
+    let env = project
+        .update(cx, |project, cx| {
+            project.environment().update(cx, |environment, cx| {
+                environment.get_environment(Some(worktree_id), Some(worktree_abs_path.clone()), cx)
+            })
+        })
+        .await
+        .unwrap_or_default();
+    assert_ne!(env.get("PATH").map(String::as_str), Some("./bin"));
+    assert!(!env.contains_key("EDITOR"));
+
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |settings, cx| {
+            settings.update_user_settings::<WorkspaceTrustSettings>(cx, |settings| {
+                settings.trusted_folders.push(PathBuf::from(path!("/dir")));
+            });
+        })
+    });
+    cx.executor().run_until_parked();
+
+    let env = project_env(cx);
+    assert_eq!(env.get("PATH").map(String::as_str), Some("./bin"));
+    assert_eq!(env.get("EDITOR").map(String::as_str), Some("./editor"));
+    let (format_on_save_setting, prettier_plugins) = format_on_save(cx);
+    assert!(matches!(format_on_save_setting, FormatOnSave::List(_)));
+    assert!(prettier_plugins.contains("./plugin.js"));
+}
+
 pub fn init_test(cx: &mut gpui::TestAppContext) {
     if std::env::var("RUST_LOG").is_ok() {
         env_logger::try_init().ok();
@@ -1,7 +1,11 @@
-use crate::Project;
-use anyhow::{Context as _, Result};
+use crate::{Event, Project};
+use anyhow::{anyhow, Context as _, Result};
+use client::{proto, Client, TypedEnvelope};
 use collections::HashMap;
-use gpui::{AnyWindowHandle, App, AppContext as _, Context, Entity, Task, WeakEntity};
+use gpui::{
+    AnyWindowHandle, App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Subscription,
+    Task, WeakEntity,
+};
 use itertools::Itertools;
 use language::LanguageName;
 use settings::{Settings, SettingsLocation};
@@ -12,19 +16,105 @@ use std::{
     iter,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 use task::{Shell, ShellBuilder, SpawnInTerminal};
 use terminal::{
     terminal_settings::{self, TerminalSettings, VenvSettings},
-    TaskState, TaskStatus, Terminal, TerminalBuilder,
+    Event as TerminalEvent, TaskState, TaskStatus, Terminal, TerminalBuilder,
 };
 use util::ResultExt;
 
 // #[cfg(target_os = "macos")]
 // use std::os::unix::ffi::OsStrExt;
 
+/// How many lines of a shared terminal's output are sent to guests.
+const SHARED_TERMINAL_LINES: usize = 200;
+/// How long to wait for more output before sending a shared terminal's output to guests.
+const SHARED_TERMINAL_UPDATE_DEBOUNCE: Duration = Duration::from_millis(50);
+
 pub struct Terminals {
     pub(crate) local_handles: Vec<WeakEntity<terminal::Terminal>>,
+    /// Terminals that the host is sharing with the guests of the project.
+    pub(crate) shared: HashMap<u64, SharedTerminal>,
+    /// Terminals that the host of the project is sharing with us.
+    pub(crate) remote: HashMap<u64, Entity<RemoteTerminal>>,
+}
+
+impl Terminals {
+    pub(crate) fn new() -> Self {
+        Self {
+            local_handles: Vec::new(),
+            shared: HashMap::default(),
+            remote: HashMap::default(),
+        }
+    }
+}
+
+pub(crate) struct SharedTerminal {
+    terminal: WeakEntity<Terminal>,
+    /// The guest that is currently allowed to type into the terminal.
+    input_controller: Option<proto::PeerId>,
+    pending_update: Option<Task<()>>,
+    _subscription: Subscription,
+}
+
+/// A guest's read-only view of a terminal that the host is sharing, which the
+/// guest can type into while the host has handed input control to them.
+pub struct RemoteTerminal {
+    project_id: u64,
+    terminal_id: u64,
+    title: String,
+    lines: Vec<String>,
+    input_controller: Option<proto::PeerId>,
+    client: Arc<Client>,
+}
+
+pub enum RemoteTerminalEvent {
+    Unshared,
+}
+
+impl EventEmitter<RemoteTerminalEvent> for RemoteTerminal {}
+
+impl RemoteTerminal {
+    /// Returns the id that the host uses for this terminal.
+    pub fn id(&self) -> u64 {
+        self.terminal_id
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Returns the collaborator who can currently type into the terminal,
+    /// other than the host.
+    pub fn input_controller(&self) -> Option<proto::PeerId> {
+        self.input_controller
+    }
+
+    pub fn has_input_control(&self) -> bool {
+        self.input_controller.is_some() && self.input_controller == self.client.peer_id()
+    }
+
+    /// Sends keystrokes to the host's terminal.
+    pub fn input(&self, input: Vec<u8>, cx: &App) -> Task<Result<()>> {
+        if !self.has_input_control() {
+            return Task::ready(Err(anyhow!("the host hasn't given you input control")));
+        }
+        let request = self.client.request(proto::SharedTerminalInput {
+            project_id: self.project_id,
+            terminal_id: self.terminal_id,
+            input,
+        });
+        cx.background_spawn(async move {
+            request.await?;
+            Ok(())
+        })
+    }
 }
 
 /// Terminals are opened either for the users shell, or to run a task.
@@ -49,6 +139,8 @@ pub enum TerminalKind {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SshCommand {
     arguments: Vec<String>,
+    /// Whether the arguments are for `docker` rather than `ssh`.
+    docker: bool,
 }
 
 impl Project {
@@ -76,9 +168,13 @@ impl Project {
         if let Some(ssh_client) = &self.ssh_client {
             let ssh_client = ssh_client.read(cx);
             if let Some(args) = ssh_client.ssh_args() {
+                let connection_options = ssh_client.connection_options();
                 return Some((
-                    ssh_client.connection_options().host.clone(),
-                    SshCommand { arguments: args },
+                    connection_options.host,
+                    SshCommand {
+                        arguments: args,
+                        docker: connection_options.docker,
+                    },
                 ));
             }
         }
@@ -89,7 +185,7 @@ impl Project {
     pub fn create_terminal(
         &mut self,
         kind: TerminalKind,
-        window: AnyWindowHandle,
+        window: Option<AnyWindowHandle>,
         cx: &mut Context<Self>,
     ) -> Task<Result<Entity<Terminal>>> {
         let path: Option<Arc<Path>> = match &kind {
@@ -156,11 +252,13 @@ impl Project {
         let builder = ShellBuilder::new(ssh_details.is_none(), &settings.shell);
         let (command, args) = builder.build(command, &Vec::new());
 
-        let mut env = self
-            .environment
-            .read(cx)
-            .get_cli_environment()
-            .unwrap_or_default();
+        let worktree_id = path
+            .as_ref()
+            .and_then(|path| self.find_worktree(path, cx))
+            .map(|(worktree, _)| worktree.read(cx).id());
+        let environment = self.environment.read(cx);
+        let mut env = environment.get_cli_environment().unwrap_or_default();
+        env.extend(environment.project_env(worktree_id, cx));
         env.extend(settings.env.clone());
 
         match &self.ssh_details(cx) {
@@ -192,7 +290,7 @@ impl Project {
         &mut self,
         kind: TerminalKind,
         python_venv_directory: Option<PathBuf>,
-        window: AnyWindowHandle,
+        window: Option<AnyWindowHandle>,
         cx: &mut Context<Self>,
     ) -> Result<Entity<Terminal>> {
         let this = &mut *self;
@@ -223,11 +321,12 @@ impl Project {
         let (completion_tx, completion_rx) = bounded(1);
 
         // Start with the environment that we might have inherited from the Zed CLI.
-        let mut env = this
-            .environment
-            .read(cx)
-            .get_cli_environment()
-            .unwrap_or_default();
+        let environment = this.environment.read(cx);
+        let mut env = environment.get_cli_environment().unwrap_or_default();
+        // Then add the project's variables, from its `.zed/env` file and `env` setting.
+        env.extend(
+            environment.project_env(settings_location.map(|location| location.worktree_id), cx),
+        );
         // Then extend it with the explicit env variables from the settings, so they take
         // precedence.
         env.extend(settings.env.clone());
@@ -548,6 +647,256 @@ impl Project {
     }
 }
 
+impl Project {
+    /// Shares a local terminal with the guests of this project, which must be
+    /// shared by the local user. Guests see the terminal's output, and can type
+    /// into it only while the host has handed input control to them.
+    pub fn share_terminal(
+        &mut self,
+        terminal: &Entity<Terminal>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        if !self.is_shared() || self.is_via_collab() {
+            return Err(anyhow!(
+                "only the host of a shared project can share terminals"
+            ));
+        }
+
+        let terminal_id = terminal.entity_id().as_u64();
+        if self.terminals.shared.contains_key(&terminal_id) {
+            return Ok(());
+        }
+        let subscription = cx.subscribe(terminal, move |this, _, event, cx| match event {
+            TerminalEvent::Wakeup | TerminalEvent::TitleChanged => {
+                this.schedule_shared_terminal_update(terminal_id, cx)
+            }
+            TerminalEvent::CloseTerminal => this.unshare_terminal_by_id(terminal_id, cx),
+            _ => {}
+        });
+        self.terminals.shared.insert(
+            terminal_id,
+            SharedTerminal {
+                terminal: terminal.downgrade(),
+                input_controller: None,
+                pending_update: None,
+                _subscription: subscription,
+            },
+        );
+        self.send_shared_terminal_update(terminal_id, cx);
+        cx.notify();
+        Ok(())
+    }
+
+    pub fn unshare_terminal(&mut self, terminal: &Entity<Terminal>, cx: &mut Context<Self>) {
+        self.unshare_terminal_by_id(terminal.entity_id().as_u64(), cx);
+    }
+
+    pub fn is_terminal_shared(&self, terminal: &Entity<Terminal>) -> bool {
+        self.terminals
+            .shared
+            .contains_key(&terminal.entity_id().as_u64())
+    }
+
+    /// Returns the guest that can currently type into the given shared terminal.
+    pub fn shared_terminal_input_controller(
+        &self,
+        terminal: &Entity<Terminal>,
+    ) -> Option<proto::PeerId> {
+        self.terminals
+            .shared
+            .get(&terminal.entity_id().as_u64())?
+            .input_controller
+    }
+
+    /// Hands input control of a shared terminal to the given guest, or takes
+    /// it back when `controller` is `None`. Only one guest can type at a time.
+    pub fn set_shared_terminal_input_controller(
+        &mut self,
+        terminal: &Entity<Terminal>,
+        controller: Option<proto::PeerId>,
+        cx: &mut Context<Self>,
+    ) {
+        let terminal_id = terminal.entity_id().as_u64();
+        let Some(shared) = self.terminals.shared.get_mut(&terminal_id) else {
+            return;
+        };
+        if shared.input_controller == controller {
+            return;
+        }
+        shared.input_controller = controller;
+        self.send_shared_terminal_update(terminal_id, cx);
+        cx.notify();
+    }
+
+    /// Returns the terminals that the host of this project is sharing with us.
+    pub fn remote_terminals(&self) -> impl Iterator<Item = &Entity<RemoteTerminal>> {
+        self.terminals.remote.values()
+    }
+
+    fn unshare_terminal_by_id(&mut self, terminal_id: u64, cx: &mut Context<Self>) {
+        if self.terminals.shared.remove(&terminal_id).is_none() {
+            return;
+        }
+        if let Some(project_id) = self.remote_id() {
+            self.client
+                .send(proto::UnshareTerminal {
+                    project_id,
+                    terminal_id,
+                })
+                .log_err();
+        }
+        cx.notify();
+    }
+
+    fn schedule_shared_terminal_update(&mut self, terminal_id: u64, cx: &mut Context<Self>) {
+        let Some(shared) = self.terminals.shared.get_mut(&terminal_id) else {
+            return;
+        };
+        if shared.pending_update.is_some() {
+            return;
+        }
+        shared.pending_update = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(SHARED_TERMINAL_UPDATE_DEBOUNCE)
+                .await;
+            this.update(cx, |this, cx| {
+                if let Some(shared) = this.terminals.shared.get_mut(&terminal_id) {
+                    shared.pending_update = None;
+                }
+                this.send_shared_terminal_update(terminal_id, cx);
+            })
+            .ok();
+        }));
+    }
+
+    fn send_shared_terminal_update(&self, terminal_id: u64, cx: &App) {
+        let Some(project_id) = self.remote_id() else {
+            return;
+        };
+        let Some(shared) = self.terminals.shared.get(&terminal_id) else {
+            return;
+        };
+        let Some(terminal) = shared.terminal.upgrade() else {
+            return;
+        };
+        let terminal = terminal.read(cx);
+        self.client
+            .send(proto::UpdateSharedTerminal {
+                project_id,
+                terminal_id,
+                title: terminal.title(true),
+                lines: terminal.last_n_non_empty_lines(SHARED_TERMINAL_LINES),
+                input_controller: shared.input_controller,
+            })
+            .log_err();
+    }
+
+    /// Sends every shared terminal to the guests again, so that guests who
+    /// just joined the project can see them.
+    pub(crate) fn resend_shared_terminals(&self, cx: &App) {
+        for terminal_id in self.terminals.shared.keys() {
+            self.send_shared_terminal_update(*terminal_id, cx);
+        }
+    }
+
+    /// Takes input control back from a guest who left the project.
+    pub(crate) fn forget_shared_terminal_input_controller(
+        &mut self,
+        peer_id: proto::PeerId,
+        cx: &App,
+    ) {
+        let terminal_ids = self
+            .terminals
+            .shared
+            .iter_mut()
+            .filter(|(_, shared)| shared.input_controller == Some(peer_id))
+            .map(|(terminal_id, shared)| {
+                shared.input_controller = None;
+                *terminal_id
+            })
+            .collect::<Vec<_>>();
+        for terminal_id in terminal_ids {
+            self.send_shared_terminal_update(terminal_id, cx);
+        }
+    }
+
+    pub(crate) fn close_remote_terminals(&mut self, cx: &mut App) {
+        for (_, terminal) in self.terminals.remote.drain() {
+            terminal.update(cx, |_, cx| cx.emit(RemoteTerminalEvent::Unshared));
+        }
+    }
+
+    pub(crate) async fn handle_update_shared_terminal(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UpdateSharedTerminal>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let payload = envelope.payload;
+        this.update(&mut cx, |this, cx| {
+            if let Some(terminal) = this.terminals.remote.get(&payload.terminal_id) {
+                terminal.update(cx, |terminal, cx| {
+                    terminal.title = payload.title;
+                    terminal.lines = payload.lines;
+                    terminal.input_controller = payload.input_controller;
+                    cx.notify();
+                });
+            } else {
+                let terminal = cx.new(|_| RemoteTerminal {
+                    project_id: payload.project_id,
+                    terminal_id: payload.terminal_id,
+                    title: payload.title,
+                    lines: payload.lines,
+                    input_controller: payload.input_controller,
+                    client: this.client.clone(),
+                });
+                this.terminals
+                    .remote
+                    .insert(payload.terminal_id, terminal.clone());
+                cx.emit(Event::RemoteTerminalShared(terminal));
+            }
+        })
+    }
+
+    pub(crate) async fn handle_unshare_terminal(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UnshareTerminal>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            if let Some(terminal) = this.terminals.remote.remove(&envelope.payload.terminal_id) {
+                terminal.update(cx, |_, cx| cx.emit(RemoteTerminalEvent::Unshared));
+            }
+        })
+    }
+
+    pub(crate) async fn handle_shared_terminal_input(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::SharedTerminalInput>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let sender_id = envelope.original_sender_id()?;
+        let terminal_id = envelope.payload.terminal_id;
+        this.update(&mut cx, |this, cx| {
+            let shared = this
+                .terminals
+                .shared
+                .get(&terminal_id)
+                .ok_or_else(|| anyhow!("terminal {terminal_id} is not shared"))?;
+            if shared.input_controller != Some(sender_id) {
+                return Err(anyhow!("the host hasn't given you input control"));
+            }
+            let terminal = shared
+                .terminal
+                .upgrade()
+                .ok_or_else(|| anyhow!("terminal {terminal_id} was closed"))?;
+            terminal.update(cx, |terminal, _| {
+                terminal.input_bytes(envelope.payload.input)
+            });
+            Ok(proto::Ack {})
+        })?
+    }
+}
+
 fn wrap_for_ssh(
     ssh_command: &SshCommand,
     command: Option<(&String, &Vec<String>)>,
@@ -593,10 +942,16 @@ fn wrap_for_ssh(
     } else {
         format!("cd; {env_changes} {to_run}")
     };
+    let mut args = ssh_command.arguments.clone();
+    if ssh_command.docker {
+        // `docker exec` runs its arguments directly, rather than passing them to a shell.
+        args.extend(["sh".to_string(), "-c".to_string(), commands]);
+        return ("docker".to_string(), args);
+    }
+
     let shell_invocation = format!("sh -c {}", shlex::try_quote(&commands).unwrap());
 
     let program = "ssh".to_string();
-    let mut args = ssh_command.arguments.clone();
 
     args.push("-t".to_string());
     args.push(shell_invocation);
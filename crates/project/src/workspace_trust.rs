@@ -0,0 +1,201 @@
+//! Restricted mode for folders that the user hasn't trusted yet.
+//!
+//! Project settings and tasks are written by whoever wrote the project, so opening a folder
+//! from an unknown source shouldn't run the programs they point to. Until a folder is trusted,
+//! its settings that run programs, like language server binaries, are ignored, and so are its
+//! tasks and the variables of its `.zed/env` file.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Result;
+use fs::Fs;
+use gpui::App;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use settings::{parse_json_with_comments, update_settings_file, Settings, SettingsSources};
+
+/// The key paths of the project settings that run programs, where `*` matches any key.
+const RESTRICTED_SETTINGS: &[&[&str]] = &[
+    &["lsp", "*", "binary"],
+    &["dap"],
+    &["node"],
+    &["context_servers"],
+    &["terminal", "shell"],
+    &["formatter"],
+    &["languages", "*", "formatter"],
+    &["format_on_save"],
+    &["languages", "*", "format_on_save"],
+    &["prettier", "plugins"],
+    &["languages", "*", "prettier", "plugins"],
+    &["env"],
+    &["load_direnv"],
+];
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct WorkspaceTrustSettings {
+    /// Whether folders are opened in restricted mode until they're trusted.
+    ///
+    /// Default: true
+    #[serde(default)]
+    pub enabled: bool,
+    /// The folders that are trusted, along with the folders inside them. This can only be set in
+    /// the user settings.
+    ///
+    /// Default: []
+    #[serde(default)]
+    pub trusted_folders: Vec<PathBuf>,
+}
+
+impl Settings for WorkspaceTrustSettings {
+    const KEY: Option<&'static str> = Some("workspace_trust");
+
+    type FileContent = Self;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        sources.json_merge()
+    }
+}
+
+impl WorkspaceTrustSettings {
+    /// Returns whether the folder at the given path is trusted.
+    pub fn is_trusted(&self, abs_path: &Path) -> bool {
+        !self.enabled
+            || self
+                .trusted_folders
+                .iter()
+                .any(|folder| abs_path.starts_with(expand_tilde(folder)))
+    }
+}
+
+/// Trusts the folders at the given paths, and the folders inside them.
+pub fn trust_folders(abs_paths: Vec<PathBuf>, fs: Arc<dyn Fs>, cx: &App) {
+    update_settings_file::<WorkspaceTrustSettings>(fs, cx, move |settings, _| {
+        for abs_path in abs_paths {
+            if !settings.trusted_folders.contains(&abs_path) {
+                settings.trusted_folders.push(abs_path);
+            }
+        }
+    });
+}
+
+/// Stops trusting the given folder, which is one of the trusted folders in the user settings.
+pub fn distrust_folder(folder: PathBuf, fs: Arc<dyn Fs>, cx: &App) {
+    update_settings_file::<WorkspaceTrustSettings>(fs, cx, move |settings, _| {
+        settings
+            .trusted_folders
+            .retain(|trusted_folder| trusted_folder != &folder);
+    });
+}
+
+fn expand_tilde(path: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(relative_path) => util::paths::home_dir().join(relative_path),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Removes the settings that run programs from the contents of a project settings file. Contents
+/// that can't be parsed are returned as they are, since they're rejected when they're applied.
+pub(crate) fn remove_restricted_settings(content: &str) -> String {
+    let Ok(mut settings) = parse_json_with_comments::<Value>(content) else {
+        return content.to_string();
+    };
+    for key_path in RESTRICTED_SETTINGS {
+        remove_key_path(&mut settings, key_path);
+    }
+    settings.to_string()
+}
+
+fn remove_key_path(value: &mut Value, key_path: &[&str]) {
+    let Some(object) = value.as_object_mut() else {
+        return;
+    };
+    match key_path {
+        [] => {}
+        [key] => {
+            object.remove(*key);
+        }
+        ["*", rest @ ..] => {
+            for value in object.values_mut() {
+                remove_key_path(value, rest);
+            }
+        }
+        [key, rest @ ..] => {
+            if let Some(value) = object.get_mut(*key) {
+                remove_key_path(value, rest);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_remove_restricted_settings() {
+        let content = r#"{
+            // Comments are allowed in settings files.
+            "tab_size": 2,
+            "formatter": { "external": { "command": "./format.sh" } },
+            "format_on_save": [{ "external": { "command": "./format.sh" } }],
+            "env": { "PATH": "./bin:/usr/bin" },
+            "prettier": { "plugins": ["./plugin.js"], "singleQuote": true },
+            "lsp": {
+                "rust-analyzer": {
+                    "binary": { "path": "./rust-analyzer" },
+                    "initialization_options": { "check": { "command": "clippy" } }
+                }
+            },
+            "languages": {
+                "Rust": { "formatter": "language_server", "hard_tabs": true },
+                "TypeScript": {
+                    "format_on_save": [{ "external": { "command": "./format.sh" } }],
+                    "prettier": { "allowed": true, "plugins": ["./plugin.js"] }
+                }
+            },
+            "terminal": { "shell": "system", "font_size": 12 }
+        }"#;
+        let settings: Value = serde_json::from_str(&remove_restricted_settings(content)).unwrap();
+        assert_eq!(
+            settings,
+            json!({
+                "tab_size": 2,
+                "lsp": {
+                    "rust-analyzer": {
+                        "initialization_options": { "check": { "command": "clippy" } }
+                    }
+                },
+                "prettier": { "singleQuote": true },
+                "languages": {
+                    "Rust": { "hard_tabs": true },
+                    "TypeScript": { "prettier": { "allowed": true } }
+                },
+                "terminal": { "font_size": 12 }
+            })
+        );
+    }
+
+    #[test]
+    fn test_trusted_folders() {
+        let settings = WorkspaceTrustSettings {
+            enabled: true,
+            trusted_folders: vec![PathBuf::from("/code/trusted")],
+        };
+        assert!(settings.is_trusted(Path::new("/code/trusted")));
+        assert!(settings.is_trusted(Path::new("/code/trusted/app")));
+        assert!(!settings.is_trusted(Path::new("/code/trusted-not")));
+        assert!(!settings.is_trusted(Path::new("/code")));
+
+        let settings = WorkspaceTrustSettings {
+            enabled: false,
+            trusted_folders: Vec::new(),
+        };
+        assert!(settings.is_trusted(Path::new("/code")));
+    }
+}
@@ -19,10 +19,10 @@ use git::status::GitSummary;
 use gpui::{
     actions, anchored, deferred, div, impl_actions, point, px, size, uniform_list, Action,
     AnyElement, App, ArcCow, AsyncWindowContext, Bounds, ClipboardItem, Context, DismissEvent, Div,
-    DragMoveEvent, Entity, EventEmitter, ExternalPaths, FocusHandle, Focusable, Hsla,
-    InteractiveElement, KeyContext, ListHorizontalSizingBehavior, ListSizingBehavior, MouseButton,
-    MouseDownEvent, ParentElement, Pixels, Point, PromptLevel, Render, ScrollStrategy, Stateful,
-    Styled, Subscription, Task, UniformListScrollHandle, WeakEntity, Window,
+    DragMoveEvent, Entity, EventEmitter, ExternalDragItem, ExternalPaths, FocusHandle, Focusable,
+    Hsla, InteractiveElement, KeyContext, ListHorizontalSizingBehavior, ListSizingBehavior,
+    MouseButton, MouseDownEvent, ParentElement, Pixels, Point, PromptLevel, Render, ScrollStrategy,
+    Stateful, Styled, Subscription, Task, UniformListScrollHandle, WeakEntity, Window,
 };
 use indexmap::IndexMap;
 use language::DiagnosticSeverity;
@@ -58,13 +58,20 @@ use ui::{
 use util::{maybe, paths::compare_paths, ResultExt, TakeUntilExt, TryFutureExt};
 use workspace::{
     dock::{DockPosition, Panel, PanelEvent},
-    notifications::{DetachAndPromptErr, NotifyTaskExt},
+    notifications::{DetachAndPromptErr, NotificationId, NotifyTaskExt},
     DraggedSelection, OpenInTerminal, OpenOptions, OpenVisible, PreviewTabsSettings, SelectedEntry,
-    Workspace,
+    Toast, Workspace,
 };
 use worktree::CreatedEntry;
 
 const PROJECT_PANEL_KEY: &str = "ProjectPanel";
+const REVEAL_IN_FILE_MANAGER_LABEL: &str = if cfg!(target_os = "macos") {
+    "Reveal in Finder"
+} else if cfg!(target_os = "windows") {
+    "Reveal in File Explorer"
+} else {
+    "Reveal in File Manager"
+};
 const NEW_ENTRY_ID: ProjectEntryId = ProjectEntryId::MAX;
 
 pub struct ProjectPanel {
@@ -107,6 +114,9 @@ pub struct ProjectPanel {
     // in case a user clicks to open a file.
     mouse_down: bool,
     hover_expand_task: Option<Task<()>>,
+    /// The absolute paths of the entries that were moved to the trash, by the operations that
+    /// moved them there, so that the operations can be undone.
+    trash_history: Vec<Vec<PathBuf>>,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -190,6 +200,7 @@ actions!(
         Duplicate,
         RevealInFileManager,
         RemoveFromProject,
+        UndoTrash,
         OpenWithSystem,
         Cut,
         Paste,
@@ -468,6 +479,7 @@ impl ProjectPanel {
                 scroll_handle,
                 mouse_down: false,
                 hover_expand_task: None,
+                trash_history: Vec::new(),
             };
             this.update_visible_entries(None, cx);
 
@@ -713,11 +725,11 @@ impl ProjectPanel {
                         menu.action("New File", Box::new(NewFile))
                             .action("New Folder", Box::new(NewDirectory))
                             .separator()
-                            .when(is_local && cfg!(target_os = "macos"), |menu| {
-                                menu.action("Reveal in Finder", Box::new(RevealInFileManager))
-                            })
-                            .when(is_local && cfg!(not(target_os = "macos")), |menu| {
-                                menu.action("Reveal in File Manager", Box::new(RevealInFileManager))
+                            .when(is_local, |menu| {
+                                menu.action(
+                                    REVEAL_IN_FILE_MANAGER_LABEL,
+                                    Box::new(RevealInFileManager),
+                                )
                             })
                             .when(is_local, |menu| {
                                 menu.action("Open in Default App", Box::new(OpenWithSystem))
@@ -1457,6 +1469,19 @@ impl ProjectPanel {
             if file_paths.is_empty() {
                 return None;
             }
+            // Entries in the trash can only be restored by the file system that they're in.
+            let trashed_abs_paths =
+                if trash && project.is_local() && self.fs.can_restore_from_trash() {
+                    items_to_delete
+                        .iter()
+                        .filter_map(|selection| {
+                            project
+                                .absolute_path(&project.path_for_entry(selection.entry_id, cx)?, cx)
+                        })
+                        .collect::<Vec<_>>()
+                } else {
+                    Vec::new()
+                };
             let answer = if !skip_prompt {
                 let operation = if trash { "Trash" } else { "Delete" };
                 let prompt = match file_paths.first() {
@@ -1532,6 +1557,9 @@ impl ProjectPanel {
                     } else {
                         panel.select_last(&SelectLast {}, window, cx);
                     }
+                    if !trashed_abs_paths.is_empty() {
+                        panel.record_trash(trashed_abs_paths, cx);
+                    }
                 })?;
                 Ok(())
             })
@@ -1540,6 +1568,58 @@ impl ProjectPanel {
         });
     }
 
+    /// Remembers the entries that were moved to the trash, offering to undo it.
+    fn record_trash(&mut self, abs_paths: Vec<PathBuf>, cx: &mut Context<Self>) {
+        let message = match abs_paths.as_slice() {
+            [abs_path] => format!(
+                "Moved {} to the trash",
+                abs_path
+                    .file_name()
+                    .unwrap_or(abs_path.as_os_str())
+                    .to_string_lossy()
+            ),
+            _ => format!("Moved {} items to the trash", abs_paths.len()),
+        };
+        self.trash_history.push(abs_paths);
+
+        let panel = cx.entity().downgrade();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(NotificationId::unique::<UndoTrash>(), message)
+                        .on_click("Undo", move |window, cx| {
+                            panel
+                                .update(cx, |panel, cx| panel.undo_trash(&UndoTrash, window, cx))
+                                .ok();
+                        })
+                        .autohide(),
+                    cx,
+                )
+            })
+            .ok();
+    }
+
+    fn undo_trash(&mut self, _: &UndoTrash, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(abs_paths) = self.trash_history.pop() else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.dismiss_toast(&NotificationId::unique::<UndoTrash>(), cx)
+            })
+            .ok();
+        let fs = self.fs.clone();
+        cx.spawn(async move |_, _| {
+            for abs_path in abs_paths {
+                fs.restore_from_trash(&abs_path)
+                    .await
+                    .with_context(|| format!("restoring {abs_path:?} from the trash"))?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_notify_err(window, cx);
+    }
+
     fn find_next_selection_after_deletion(
         &self,
         sanitized_entries: BTreeSet<SelectedEntry>,
@@ -2906,8 +2986,61 @@ impl ProjectPanel {
             }
         }
 
+        let message = match paths.as_slice() {
+            [path] => format!(
+                "Copy {} into {}?",
+                path.file_name()
+                    .unwrap_or(path.as_os_str())
+                    .to_string_lossy(),
+                target_directory
+                    .file_name()
+                    .unwrap_or(target_directory.as_os_str())
+                    .to_string_lossy()
+            ),
+            _ => format!(
+                "Copy {} items into {}?",
+                paths.len(),
+                target_directory
+                    .file_name()
+                    .unwrap_or(target_directory.as_os_str())
+                    .to_string_lossy()
+            ),
+        };
+        let answer = window.prompt(
+            PromptLevel::Info,
+            &message,
+            Some("They can be opened in the project instead, without copying them."),
+            &["Copy", "Open", "Cancel"],
+            cx,
+        );
+
         cx.spawn_in(window, async move |this, cx| {
             async move {
+                match answer.await? {
+                    0 => {}
+                    1 => {
+                        return this.update_in(cx, |this, window, cx| {
+                            this.workspace
+                                .update(cx, |workspace, cx| {
+                                    workspace
+                                        .open_paths(
+                                            paths.iter().map(|path| path.to_path_buf()).collect(),
+                                            OpenOptions {
+                                                visible: Some(OpenVisible::OnlyDirectories),
+                                                ..Default::default()
+                                            },
+                                            None,
+                                            window,
+                                            cx,
+                                        )
+                                        .detach();
+                                })
+                                .ok();
+                        });
+                    }
+                    _ => return Ok(()),
+                }
+
                 for (filename, original_path) in &paths_to_replace {
                     let answer = cx.update(|window, cx| {
                         window
@@ -2947,6 +3080,35 @@ impl ProjectPanel {
         .detach();
     }
 
+    /// Drags the dragged entries out of the window as files when the mouse leaves it, so that
+    /// they can be dropped in other applications.
+    fn drag_selection_out_of_window(
+        &mut self,
+        event: &DragMoveEvent<DraggedSelection>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let viewport = Bounds::new(Point::default(), window.viewport_size());
+        if viewport.contains(&event.event.position) || !self.project.read(cx).is_local() {
+            return;
+        }
+
+        let project = self.project.read(cx);
+        let paths = event
+            .drag(cx)
+            .items()
+            .filter_map(|selection| {
+                let project_path = project.path_for_entry(selection.entry_id, cx)?;
+                project.absolute_path(&project_path, cx)
+            })
+            .collect::<Vec<_>>();
+        if !paths.is_empty() && window.start_external_drag(ExternalDragItem::Paths(paths)) {
+            self.hover_scroll_task.take();
+            self.hover_expand_task.take();
+            cx.stop_active_drag(window);
+        }
+    }
+
     fn drag_onto(
         &mut self,
         selections: &DraggedSelection,
@@ -4449,6 +4611,7 @@ impl Render for ProjectPanel {
                 .group("project-panel")
                 .on_drag_move(cx.listener(handle_drag_move_scroll::<ExternalPaths>))
                 .on_drag_move(cx.listener(handle_drag_move_scroll::<DraggedSelection>))
+                .on_drag_move(cx.listener(Self::drag_selection_out_of_window))
                 .size_full()
                 .relative()
                 .on_hover(cx.listener(|this, hovered, window, cx| {
@@ -4496,6 +4659,7 @@ impl Render for ProjectPanel {
                         .on_action(cx.listener(Self::rename))
                         .on_action(cx.listener(Self::delete))
                         .on_action(cx.listener(Self::trash))
+                        .on_action(cx.listener(Self::undo_trash))
                         .on_action(cx.listener(Self::cut))
                         .on_action(cx.listener(Self::copy))
                         .on_action(cx.listener(Self::paste))
@@ -24,13 +24,32 @@ pub struct AssistantSystemPromptContext {
 
 impl AssistantSystemPromptContext {
     pub fn new(worktrees: Vec<WorktreeInfoForSystemPrompt>) -> Self {
-        let has_rules = worktrees
-            .iter()
-            .any(|worktree| worktree.rules_file.is_some());
-        Self {
+        let mut this = Self {
             worktrees,
-            has_rules,
+            has_rules: false,
+        };
+        this.has_rules = this.any_rules_file_enabled();
+        this
+    }
+
+    /// Enables or disables the rules file at the given path.
+    pub fn set_rules_file_enabled(&mut self, abs_path: &Path, enabled: bool) {
+        for rules_file in self
+            .worktrees
+            .iter_mut()
+            .flat_map(|worktree| worktree.rules_files.iter_mut())
+            .filter(|rules_file| rules_file.abs_path.as_ref() == abs_path)
+        {
+            rules_file.enabled = enabled;
         }
+        self.has_rules = self.any_rules_file_enabled();
+    }
+
+    fn any_rules_file_enabled(&self) -> bool {
+        self.worktrees
+            .iter()
+            .flat_map(|worktree| &worktree.rules_files)
+            .any(|rules_file| rules_file.enabled)
     }
 }
 
@@ -38,7 +57,8 @@ impl AssistantSystemPromptContext {
 pub struct WorktreeInfoForSystemPrompt {
     pub root_name: String,
     pub abs_path: Arc<Path>,
-    pub rules_file: Option<RulesFile>,
+    /// The rules files in the worktree, starting with the one at its root.
+    pub rules_files: Vec<RulesFile>,
 }
 
 #[derive(Serialize)]
@@ -46,6 +66,9 @@ pub struct RulesFile {
     pub rel_path: Arc<Path>,
     pub abs_path: Arc<Path>,
     pub text: String,
+    /// Whether the rules are included in the system prompt. Users can disable rules files
+    /// that don't apply to what they're working on.
+    pub enabled: bool,
 }
 
 #[derive(Serialize)]
@@ -5,6 +5,7 @@ fn main() {
         .type_attribute("ProjectPath", "#[derive(Hash, Eq)]")
         .type_attribute("Breakpoint", "#[derive(Hash, Eq)]")
         .type_attribute("Anchor", "#[derive(Hash, Eq)]")
+        .type_attribute("ProjectPermissions", "#[derive(Eq, Copy)]")
         .compile_protos(&["proto/zed.proto"], &["proto"])
         .unwrap();
 }
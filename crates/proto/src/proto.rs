@@ -396,6 +396,17 @@ messages!(
     (RespondToChannelInvite, Foreground),
     (RespondToContactRequest, Foreground),
     (RestartLanguageServers, Foreground),
+    (StopLanguageServers, Foreground),
+    (AddReviewComment, Foreground),
+    (AddReviewCommentResponse, Foreground),
+    (GetReviewComments, Foreground),
+    (GetReviewCommentsResponse, Foreground),
+    (ResolveReviewComment, Foreground),
+    (UpdateReviewComment, Foreground),
+    (UpdateProjectPermissions, Foreground),
+    (UpdateSharedTerminal, Foreground),
+    (UnshareTerminal, Foreground),
+    (SharedTerminalInput, Foreground),
     (RoomUpdated, Foreground),
     (SaveBuffer, Foreground),
     (SendChannelMessage, Background),
@@ -587,6 +598,12 @@ request_messages!(
     (RejoinRemoteProjects, RejoinRemoteProjectsResponse),
     (MultiLspQuery, MultiLspQueryResponse),
     (RestartLanguageServers, Ack),
+    (StopLanguageServers, Ack),
+    (AddReviewComment, AddReviewCommentResponse),
+    (GetReviewComments, GetReviewCommentsResponse),
+    (ResolveReviewComment, Ack),
+    (UpdateProjectPermissions, Ack),
+    (SharedTerminalInput, Ack),
     (OpenContext, OpenContextResponse),
     (CreateContext, CreateContextResponse),
     (SynchronizeContexts, SynchronizeContextsResponse),
@@ -667,6 +684,15 @@ entity_messages!(
     LinkedEditingRange,
     MultiLspQuery,
     RestartLanguageServers,
+    StopLanguageServers,
+    AddReviewComment,
+    GetReviewComments,
+    ResolveReviewComment,
+    UpdateReviewComment,
+    UpdateProjectPermissions,
+    UpdateSharedTerminal,
+    UnshareTerminal,
+    SharedTerminalInput,
     OnTypeFormatting,
     OpenNewBuffer,
     OpenBufferById,
@@ -0,0 +1,211 @@
+use std::{collections::BTreeSet, path::PathBuf};
+
+use anyhow::Result;
+use gpui::{actions, App, Context, Window};
+use remote::DevContainer;
+use settings::{update_settings_file, Settings};
+use workspace::{notifications::NotificationId, OpenOptions, Toast, Workspace};
+
+use crate::{
+    open_ssh_project,
+    ssh_connections::{SshConnection, SshProject, SshSettings},
+};
+
+actions!(
+    dev_container,
+    [
+        ReopenInContainer,
+        BuildContainer,
+        RebuildContainer,
+        StopContainer
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace
+            .register_action(|workspace, _: &ReopenInContainer, window, cx| {
+                run_lifecycle_command(workspace, LifecycleCommand::Start, window, cx)
+            })
+            .register_action(|workspace, _: &BuildContainer, window, cx| {
+                run_lifecycle_command(workspace, LifecycleCommand::Build, window, cx)
+            })
+            .register_action(|workspace, _: &RebuildContainer, window, cx| {
+                run_lifecycle_command(workspace, LifecycleCommand::Rebuild, window, cx)
+            })
+            .register_action(|workspace, _: &StopContainer, window, cx| {
+                run_lifecycle_command(workspace, LifecycleCommand::Stop, window, cx)
+            });
+    })
+    .detach();
+}
+
+struct DevContainerStatus;
+
+#[derive(Clone, Copy)]
+enum LifecycleCommand {
+    Build,
+    Start,
+    Rebuild,
+    Stop,
+}
+
+impl LifecycleCommand {
+    fn status_message(&self) -> &'static str {
+        match self {
+            Self::Build => "Building dev container…",
+            Self::Start => "Starting dev container…",
+            Self::Rebuild => "Rebuilding dev container…",
+            Self::Stop => "Stopping dev container…",
+        }
+    }
+
+    fn finished_message(&self) -> &'static str {
+        match self {
+            Self::Build => "Built dev container",
+            Self::Start => "Started dev container",
+            Self::Rebuild => "Rebuilt dev container",
+            Self::Stop => "Stopped dev container",
+        }
+    }
+
+    /// Whether the project should be reopened in the container once the command is done.
+    fn connects(&self) -> bool {
+        matches!(self, Self::Start | Self::Rebuild)
+    }
+
+    async fn run(&self, dev_container: &DevContainer) -> Result<()> {
+        match self {
+            Self::Build => dev_container.build().await.map(|_| ()),
+            Self::Start => dev_container.start().await,
+            Self::Rebuild => dev_container.rebuild().await,
+            Self::Stop => dev_container.stop().await,
+        }
+    }
+}
+
+/// Returns the local folder whose dev container the workspace's project belongs to: either
+/// a local folder with a `devcontainer.json`, or the folder that the container the project
+/// is connected to was created from.
+fn dev_container_folder(workspace: &Workspace, cx: &App) -> Option<PathBuf> {
+    let project = workspace.project().read(cx);
+    if let Some(ssh_client) = project.ssh_client() {
+        let connection_options = ssh_client.read(cx).connection_options();
+        if !connection_options.docker {
+            return None;
+        }
+        return SshSettings::get_global(cx)
+            .ssh_connections()
+            .find(|connection| {
+                connection.docker == Some(true) && connection.host == connection_options.host
+            })
+            .and_then(|connection| connection.dev_container);
+    }
+
+    project
+        .visible_worktrees(cx)
+        .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+        .find(|path| DevContainer::config_path(path).is_some())
+}
+
+fn run_lifecycle_command(
+    workspace: &mut Workspace,
+    command: LifecycleCommand,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let status_id = NotificationId::unique::<DevContainerStatus>();
+    let Some(local_folder) = dev_container_folder(workspace, cx) else {
+        workspace.show_toast(
+            Toast::new(status_id, "No devcontainer.json was found in this project").autohide(),
+            cx,
+        );
+        return;
+    };
+
+    let app_state = workspace.app_state().clone();
+    let replace_window = window.window_handle().downcast::<Workspace>();
+    workspace.show_toast(Toast::new(status_id.clone(), command.status_message()), cx);
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let result = cx
+            .background_spawn(async move {
+                let dev_container = DevContainer::load(&local_folder).await?;
+                command.run(&dev_container).await?;
+                anyhow::Ok(dev_container)
+            })
+            .await;
+
+        let dev_container = match result {
+            Ok(dev_container) => dev_container,
+            Err(error) => {
+                log::error!("dev container command failed: {error:?}");
+                workspace.update(cx, |workspace, cx| {
+                    workspace.dismiss_toast(&status_id, cx);
+                    workspace.show_error(&error, cx);
+                })?;
+                return anyhow::Ok(());
+            }
+        };
+
+        workspace.update(cx, |workspace, cx| {
+            workspace.dismiss_toast(&status_id, cx);
+            if !command.connects() {
+                workspace.show_toast(
+                    Toast::new(status_id, command.finished_message()).autohide(),
+                    cx,
+                );
+            }
+        })?;
+        if !command.connects() {
+            return Ok(());
+        }
+
+        let connection_options = dev_container.connection_options();
+        let workspace_folder = dev_container.workspace_folder();
+        cx.update(|_, cx| {
+            update_settings_file::<SshSettings>(app_state.fs.clone(), cx, {
+                let connection_options = connection_options.clone();
+                let workspace_folder = workspace_folder.clone();
+                let local_folder = dev_container.local_folder.clone();
+                move |settings, _| {
+                    let connections = settings.ssh_connections.get_or_insert(Default::default());
+                    let project = SshProject {
+                        paths: vec![workspace_folder],
+                    };
+                    if let Some(connection) = connections.iter_mut().find(|connection| {
+                        connection.docker == Some(true)
+                            && connection.host == connection_options.host
+                    }) {
+                        connection.username = connection_options.username;
+                        connection.dev_container = Some(local_folder);
+                        connection.projects.insert(project);
+                    } else {
+                        connections.push(SshConnection {
+                            host: connection_options.host.into(),
+                            username: connection_options.username,
+                            nickname: connection_options.nickname,
+                            projects: BTreeSet::from([project]),
+                            docker: Some(true),
+                            dev_container: Some(local_folder),
+                            ..Default::default()
+                        });
+                    }
+                }
+            });
+        })?;
+
+        open_ssh_project(
+            connection_options,
+            vec![PathBuf::from(workspace_folder)],
+            app_state,
+            OpenOptions {
+                replace_window,
+                ..Default::default()
+            },
+            cx,
+        )
+        .await
+    })
+    .detach_and_log_err(cx);
+}
@@ -0,0 +1,648 @@
+use std::collections::BTreeSet;
+use std::net::TcpListener;
+use std::time::Duration;
+
+use editor::Editor;
+use gpui::{
+    actions, px, App, ClipboardItem, Context, Entity, EventEmitter, FocusHandle, Focusable, Pixels,
+    Subscription, Task, WeakEntity, Window,
+};
+use remote::ssh_session::SshPortForwardOption;
+use remote::{ConnectionState, SshRemoteClient};
+use ui::{prelude::*, Indicator, ListItem, ListItemSpacing, Tooltip};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    notifications::{simple_message_notification::MessageNotification, NotificationId},
+    Workspace,
+};
+
+actions!(ports_panel, [ToggleFocus]);
+
+const DEFAULT_SIZE: Pixels = px(320.);
+const LISTENING_PORTS_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<PortsPanel>(window, cx);
+        });
+
+        let Some(ssh_client) = workspace.project().read(cx).ssh_client() else {
+            return;
+        };
+        // Ports of containers are published when the container is created, rather than
+        // forwarded over the connection.
+        if ssh_client.read(cx).connection_options().docker {
+            return;
+        }
+        let workspace_handle = cx.entity().downgrade();
+        let panel = cx.new(|cx| PortsPanel::new(ssh_client, workspace_handle, window, cx));
+        workspace.add_panel(panel, window, cx);
+    })
+    .detach();
+}
+
+/// A local port that is forwarded to the server.
+struct ForwardedPort {
+    forward: SshPortForwardOption,
+    label: Option<SharedString>,
+    status: ForwardStatus,
+    /// Whether the forward is configured in the settings for the server, in which case it is
+    /// established along with the connection.
+    configured: bool,
+}
+
+enum ForwardStatus {
+    Starting,
+    Active,
+    Failed(SharedString),
+}
+
+/// The dock panel that lists the ports forwarded to the server of a remote project, and
+/// offers to forward the ports that processes on the server start listening on.
+pub struct PortsPanel {
+    ssh_client: Entity<SshRemoteClient>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    position: DockPosition,
+    size: Option<Pixels>,
+    forwards: Vec<ForwardedPort>,
+    /// Whether the ports that were already being listened on have been recorded, so that
+    /// only ports opened after that are offered.
+    has_listed_ports: bool,
+    /// Ports being listened on that shouldn't be offered for forwarding.
+    ignored_ports: BTreeSet<u16>,
+    /// Ports that processes started listening on, and that haven't been forwarded yet.
+    detected_ports: BTreeSet<u16>,
+    new_port_editor: Entity<Editor>,
+    new_port_error: Option<SharedString>,
+    editing_label: Option<(usize, Entity<Editor>)>,
+    was_connected: bool,
+    _poll_listening_ports: Task<()>,
+    _ssh_client_subscription: Subscription,
+}
+
+impl PortsPanel {
+    fn new(
+        ssh_client: Entity<SshRemoteClient>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let forwards = ssh_client
+            .read(cx)
+            .connection_options()
+            .port_forwards
+            .unwrap_or_default()
+            .into_iter()
+            .map(|forward| ForwardedPort {
+                forward,
+                label: None,
+                status: ForwardStatus::Active,
+                configured: true,
+            })
+            .collect();
+
+        let new_port_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Forward a port or host:port", cx);
+            editor
+        });
+
+        let poll_listening_ports = cx.spawn(async move |this, cx| loop {
+            let Ok(listening_ports) =
+                this.update(cx, |this, cx| this.ssh_client.read(cx).listening_ports(cx))
+            else {
+                break;
+            };
+            match listening_ports.await {
+                Ok(ports) => {
+                    if this
+                        .update(cx, |this, cx| this.listening_ports_changed(ports, cx))
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+                Err(error) => log::debug!("failed to list listening ports: {error:#}"),
+            }
+            cx.background_executor()
+                .timer(LISTENING_PORTS_POLL_INTERVAL)
+                .await;
+        });
+
+        let ssh_client_subscription =
+            cx.observe(&ssh_client, |this, _, cx| this.connection_state_changed(cx));
+        let was_connected = is_connected(ssh_client.read(cx).connection_state());
+
+        Self {
+            ssh_client,
+            workspace,
+            focus_handle: cx.focus_handle(),
+            position: DockPosition::Bottom,
+            size: None,
+            forwards,
+            has_listed_ports: false,
+            ignored_ports: BTreeSet::new(),
+            detected_ports: BTreeSet::new(),
+            new_port_editor,
+            new_port_error: None,
+            editing_label: None,
+            was_connected,
+            _poll_listening_ports: poll_listening_ports,
+            _ssh_client_subscription: ssh_client_subscription,
+        }
+    }
+
+    fn listening_ports_changed(&mut self, ports: BTreeSet<u16>, cx: &mut Context<Self>) {
+        if !self.has_listed_ports {
+            self.has_listed_ports = true;
+            self.ignored_ports = ports;
+            return;
+        }
+
+        // Once a port is closed, it can be offered again when it's reopened.
+        self.ignored_ports.retain(|port| ports.contains(port));
+        self.detected_ports.retain(|port| ports.contains(port));
+
+        let new_ports = ports
+            .into_iter()
+            .filter(|port| {
+                !self.ignored_ports.contains(port)
+                    && !self.detected_ports.contains(port)
+                    && !self.is_forwarded(*port)
+            })
+            .collect::<Vec<_>>();
+        for port in new_ports {
+            self.detected_ports.insert(port);
+            self.show_detected_port_notification(port, cx);
+        }
+        cx.notify();
+    }
+
+    fn is_forwarded(&self, remote_port: u16) -> bool {
+        self.forwards.iter().any(|forwarded| {
+            forwarded.forward.remote_port == remote_port
+                && forwarded
+                    .forward
+                    .remote_host
+                    .as_deref()
+                    .map_or(true, |host| host == "localhost")
+        })
+    }
+
+    fn show_detected_port_notification(&self, port: u16, cx: &mut Context<Self>) {
+        struct DetectedPort;
+
+        let panel = cx.entity().downgrade();
+        let host = self.ssh_client.read(cx).connection_string();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_notification(
+                    NotificationId::composite::<DetectedPort>(SharedString::from(port.to_string())),
+                    cx,
+                    |cx| {
+                        cx.new(|cx| {
+                            MessageNotification::new(
+                                format!("A process on {host} is listening on port {port}."),
+                                cx,
+                            )
+                            .primary_message("Forward Port")
+                            .primary_on_click({
+                                let panel = panel.clone();
+                                move |_, cx| {
+                                    panel
+                                        .update(cx, |panel, cx| panel.forward_port(None, port, cx))
+                                        .ok();
+                                }
+                            })
+                            .secondary_message("Ignore")
+                            .secondary_on_click(move |_, cx| {
+                                panel
+                                    .update(cx, |panel, cx| panel.ignore_port(port, cx))
+                                    .ok();
+                            })
+                        })
+                    },
+                );
+            })
+            .ok();
+    }
+
+    fn ignore_port(&mut self, port: u16, cx: &mut Context<Self>) {
+        self.detected_ports.remove(&port);
+        self.ignored_ports.insert(port);
+        cx.notify();
+    }
+
+    fn forward_port(
+        &mut self,
+        remote_host: Option<String>,
+        remote_port: u16,
+        cx: &mut Context<Self>,
+    ) {
+        if remote_host.is_none() {
+            self.detected_ports.remove(&remote_port);
+        }
+
+        let forward = SshPortForwardOption {
+            local_host: None,
+            local_port: available_local_port(remote_port),
+            remote_host,
+            remote_port,
+        };
+        self.forwards.push(ForwardedPort {
+            forward: forward.clone(),
+            label: None,
+            status: ForwardStatus::Starting,
+            configured: false,
+        });
+        self.start_forward(forward, cx);
+        cx.notify();
+    }
+
+    fn start_forward(&mut self, forward: SshPortForwardOption, cx: &mut Context<Self>) {
+        let task = self.ssh_client.read(cx).forward_port(forward.clone(), cx);
+        cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                if let Some(forwarded) = this
+                    .forwards
+                    .iter_mut()
+                    .find(|forwarded| forwarded.forward == forward)
+                {
+                    forwarded.status = match result {
+                        Ok(()) => ForwardStatus::Active,
+                        Err(error) => ForwardStatus::Failed(format!("{error:#}").into()),
+                    };
+                    cx.notify();
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn stop_forward(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if ix >= self.forwards.len() {
+            return;
+        }
+        let forwarded = self.forwards.remove(ix);
+        self.editing_label = None;
+
+        // Don't offer to forward the port again while it's still being listened on.
+        if forwarded.forward.remote_host.is_none() {
+            self.ignored_ports.insert(forwarded.forward.remote_port);
+        }
+        if !matches!(forwarded.status, ForwardStatus::Failed(_)) {
+            self.ssh_client
+                .read(cx)
+                .cancel_port_forward(forwarded.forward, cx)
+                .detach_and_log_err(cx);
+        }
+        cx.notify();
+    }
+
+    fn connection_state_changed(&mut self, cx: &mut Context<Self>) {
+        let is_connected = is_connected(self.ssh_client.read(cx).connection_state());
+        if is_connected && !self.was_connected {
+            // Forwards are added to the running connection, so they have to be added again
+            // once it has been re-established.
+            let forwards = self
+                .forwards
+                .iter_mut()
+                .filter(|forwarded| !forwarded.configured)
+                .map(|forwarded| {
+                    forwarded.status = ForwardStatus::Starting;
+                    forwarded.forward.clone()
+                })
+                .collect::<Vec<_>>();
+            for forward in forwards {
+                self.start_forward(forward, cx);
+            }
+        }
+        self.was_connected = is_connected;
+        cx.notify();
+    }
+
+    fn edit_label(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(forwarded) = self.forwards.get(ix) else {
+            return;
+        };
+        let label = forwarded.label.clone();
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Label", cx);
+            if let Some(label) = label {
+                editor.set_text(label.to_string(), window, cx);
+            }
+            editor
+        });
+        editor.focus_handle(cx).focus(window);
+        self.editing_label = Some((ix, editor));
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some((ix, editor)) = self.editing_label.take() {
+            let label = editor.read(cx).text(cx).trim().to_string();
+            if let Some(forwarded) = self.forwards.get_mut(ix) {
+                forwarded.label = (!label.is_empty()).then(|| label.into());
+            }
+            self.focus_handle.focus(window);
+            cx.notify();
+        } else if self.new_port_editor.focus_handle(cx).is_focused(window) {
+            let text = self.new_port_editor.read(cx).text(cx).trim().to_string();
+            if text.is_empty() {
+                return;
+            }
+            match parse_forward_target(&text) {
+                Some((remote_host, remote_port)) => {
+                    self.new_port_error = None;
+                    self.new_port_editor
+                        .update(cx, |editor, cx| editor.set_text("", window, cx));
+                    self.forward_port(remote_host, remote_port, cx);
+                }
+                None => {
+                    self.new_port_error = Some(format!("Invalid port: {text}").into());
+                    cx.notify();
+                }
+            }
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing_label.take().is_some() {
+            self.focus_handle.focus(window);
+            cx.notify();
+        }
+    }
+
+    fn render_forwarded_port(
+        &self,
+        ix: usize,
+        forwarded: &ForwardedPort,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let forward = &forwarded.forward;
+        let local_url = format!("http://localhost:{}", forward.local_port);
+        let remote_address = format!(
+            "{}:{}",
+            forward.remote_host.as_deref().unwrap_or("localhost"),
+            forward.remote_port
+        );
+        let indicator_color = match &forwarded.status {
+            ForwardStatus::Starting => Color::Info,
+            ForwardStatus::Active => Color::Success,
+            ForwardStatus::Failed(_) => Color::Error,
+        };
+        let title = match self.editing_label.as_ref() {
+            Some((editing_ix, editor)) if *editing_ix == ix => editor.clone().into_any_element(),
+            _ => Label::new(
+                forwarded
+                    .label
+                    .clone()
+                    .unwrap_or_else(|| remote_address.clone().into()),
+            )
+            .truncate()
+            .into_any_element(),
+        };
+
+        ListItem::new(("forwarded-port", ix))
+            .spacing(ListItemSpacing::Sparse)
+            .start_slot(Indicator::dot().color(indicator_color))
+            .child(
+                v_flex()
+                    .min_w_0()
+                    .child(title)
+                    .child(
+                        Label::new(format!(
+                            "{remote_address} → localhost:{}",
+                            forward.local_port
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    )
+                    .children(match &forwarded.status {
+                        ForwardStatus::Failed(error) => Some(
+                            Label::new(error.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Error),
+                        ),
+                        _ => None,
+                    }),
+            )
+            .end_slot(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new(("label-port", ix), IconName::Pencil)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Label Port"))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.edit_label(ix, window, cx)
+                            })),
+                    )
+                    .child(
+                        IconButton::new(("copy-local-url", ix), IconName::Copy)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Copy Local URL"))
+                            .on_click({
+                                let local_url = local_url.clone();
+                                move |_, _, cx| {
+                                    cx.write_to_clipboard(ClipboardItem::new_string(
+                                        local_url.clone(),
+                                    ))
+                                }
+                            }),
+                    )
+                    .child(
+                        IconButton::new(("open-local-url", ix), IconName::ArrowUpRight)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Open in Browser"))
+                            .on_click(move |_, _, cx| cx.open_url(&local_url)),
+                    )
+                    .child(
+                        IconButton::new(("stop-forwarding", ix), IconName::Close)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Stop Forwarding"))
+                            .on_click(cx.listener(move |this, _, _, cx| this.stop_forward(ix, cx))),
+                    ),
+            )
+    }
+
+    fn render_detected_port(&self, port: u16, cx: &mut Context<Self>) -> impl IntoElement {
+        ListItem::new(("detected-port", port as usize))
+            .spacing(ListItemSpacing::Sparse)
+            .start_slot(Indicator::dot().color(Color::Muted))
+            .child(Label::new(format!("localhost:{port}")))
+            .end_slot(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new(("forward-detected-port", port as usize), "Forward")
+                            .label_size(LabelSize::Small)
+                            .on_click(
+                                cx.listener(move |this, _, _, cx| {
+                                    this.forward_port(None, port, cx)
+                                }),
+                            ),
+                    )
+                    .child(
+                        IconButton::new(("ignore-detected-port", port as usize), IconName::Close)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Ignore"))
+                            .on_click(
+                                cx.listener(move |this, _, _, cx| this.ignore_port(port, cx)),
+                            ),
+                    ),
+            )
+    }
+}
+
+fn is_connected(state: ConnectionState) -> bool {
+    matches!(
+        state,
+        ConnectionState::Connected | ConnectionState::HeartbeatMissed
+    )
+}
+
+/// Parses a port, or a `host:port` reachable from the server.
+fn parse_forward_target(text: &str) -> Option<(Option<String>, u16)> {
+    let (remote_host, port) = match text.rsplit_once(':') {
+        Some((host, port)) => (Some(host.to_string()).filter(|host| !host.is_empty()), port),
+        None => (None, text),
+    };
+    let port = port.parse().ok().filter(|port| *port != 0)?;
+    Some((remote_host, port))
+}
+
+/// Returns the given port if it's free on this machine, or else a free port chosen by the OS.
+fn available_local_port(preferred: u16) -> u16 {
+    TcpListener::bind(("127.0.0.1", preferred))
+        .or_else(|_| TcpListener::bind(("127.0.0.1", 0)))
+        .and_then(|listener| listener.local_addr())
+        .map(|address| address.port())
+        .unwrap_or(preferred)
+}
+
+impl Render for PortsPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let forwarded_ports = self
+            .forwards
+            .iter()
+            .enumerate()
+            .map(|(ix, forwarded)| self.render_forwarded_port(ix, forwarded, cx))
+            .collect::<Vec<_>>();
+        let detected_ports = self
+            .detected_ports
+            .iter()
+            .map(|port| self.render_detected_port(*port, cx))
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .key_context("PortsPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .size_full()
+            .bg(cx.theme().colors().panel_background)
+            .child(
+                v_flex()
+                    .p_2()
+                    .gap_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(self.new_port_editor.clone())
+                    .children(
+                        self.new_port_error.clone().map(|error| {
+                            Label::new(error).size(LabelSize::Small).color(Color::Error)
+                        }),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .id("ports")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .p_1()
+                    .when(
+                        forwarded_ports.is_empty() && detected_ports.is_empty(),
+                        |this| {
+                            this.child(
+                                div()
+                                    .p_2()
+                                    .child(Label::new("No forwarded ports.").color(Color::Muted)),
+                            )
+                        },
+                    )
+                    .children(forwarded_ports)
+                    .when(!detected_ports.is_empty(), |this| {
+                        this.child(
+                            div().px_2().pt_2().child(
+                                Label::new("Detected")
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                        )
+                        .children(detected_ports)
+                    }),
+            )
+    }
+}
+
+impl Focusable for PortsPanel {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for PortsPanel {}
+
+impl Panel for PortsPanel {
+    fn persistent_name() -> &'static str {
+        "PortsPanel"
+    }
+
+    fn position(&self, _window: &Window, _cx: &App) -> DockPosition {
+        self.position
+    }
+
+    fn position_is_valid(&self, _position: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        self.position = position;
+        cx.notify();
+    }
+
+    fn size(&self, _window: &Window, _cx: &App) -> Pixels {
+        self.size.unwrap_or(DEFAULT_SIZE)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.size = size;
+        cx.notify();
+    }
+
+    fn icon(&self, _window: &Window, _cx: &App) -> Option<IconName> {
+        Some(IconName::ArrowRightLeft)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Ports")
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        11
+    }
+}
@@ -1,13 +1,17 @@
+mod dev_containers;
 pub mod disconnected_overlay;
+mod ports_panel;
+mod recent_projects_settings;
 mod remote_servers;
 mod ssh_connections;
+pub use recent_projects_settings::{RecentProjectsGrouping, RecentProjectsSettings};
 pub use ssh_connections::{is_connecting_over_ssh, open_ssh_project};
 
 use disconnected_overlay::DisconnectedOverlay;
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{
-    Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
-    Subscription, Task, WeakEntity, Window,
+    actions, impl_actions, Action, AnyElement, App, Context, DismissEvent, Div, Entity,
+    EventEmitter, FocusHandle, Focusable, Subscription, Task, WeakEntity, Window,
 };
 use ordered_float::OrderedFloat;
 use picker::{
@@ -15,6 +19,8 @@ use picker::{
     Picker, PickerDelegate,
 };
 pub use remote_servers::RemoteServerProjects;
+use schemars::JsonSchema;
+use serde::Deserialize;
 use settings::Settings;
 pub use ssh_connections::SshSettings;
 use std::{
@@ -24,16 +30,27 @@ use std::{
 use ui::{prelude::*, tooltip_container, KeyBinding, ListItem, ListItemSpacing, Tooltip};
 use util::{paths::PathExt, ResultExt};
 use workspace::{
-    CloseIntent, ModalView, OpenOptions, SerializedWorkspaceLocation, Workspace, WorkspaceId,
-    WORKSPACE_DB,
+    notifications::NotificationId, CloseIntent, ModalView, OpenOptions, SerializedSshProject,
+    SerializedWorkspaceLocation, Toast, Workspace, WorkspaceId, WORKSPACE_DB,
 };
 use zed_actions::{OpenRecent, OpenRemote};
 
+actions!(recent_projects, [TogglePin, RemoveStaleProjects]);
+
+/// Opens the recent project at the given position in the list.
+#[derive(Clone, Deserialize, PartialEq, JsonSchema)]
+pub struct OpenNth(pub usize);
+
+impl_actions!(recent_projects, [OpenNth]);
+
 pub fn init(cx: &mut App) {
     SshSettings::register(cx);
+    RecentProjectsSettings::register(cx);
     cx.observe_new(RecentProjects::register).detach();
     cx.observe_new(RemoteServerProjects::register).detach();
     cx.observe_new(DisconnectedOverlay::register).detach();
+    ports_panel::init(cx);
+    dev_containers::init(cx);
 }
 
 pub struct RecentProjects {
@@ -89,6 +106,9 @@ impl RecentProjects {
         _window: Option<&mut Window>,
         _cx: &mut Context<Workspace>,
     ) {
+        workspace.register_action(|workspace, _: &RemoveStaleProjects, _, cx| {
+            remove_stale_projects(workspace, cx);
+        });
         workspace.register_action(|workspace, open_recent: &OpenRecent, window, cx| {
             let Some(recent_projects) = workspace.active_modal::<Self>(cx) else {
                 Self::open(workspace, open_recent.create_new_window, window, cx);
@@ -129,7 +149,22 @@ impl Focusable for RecentProjects {
 impl Render for RecentProjects {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         v_flex()
+            .key_context("RecentProjects")
             .w(rems(self.rem_width))
+            .on_action(cx.listener(|this, action: &OpenNth, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    if action.0 < picker.delegate.match_count() {
+                        picker.set_selected_index(action.0, false, window, cx);
+                        picker.delegate.confirm(false, window, cx);
+                    }
+                })
+            }))
+            .on_action(cx.listener(|this, _: &TogglePin, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    let ix = picker.delegate.selected_index();
+                    picker.delegate.toggle_pin(ix, window, cx);
+                })
+            }))
             .child(self.picker.clone())
             .on_mouse_down_out(cx.listener(|this, _, window, cx| {
                 this.picker.update(cx, |this, cx| {
@@ -142,6 +177,12 @@ impl Render for RecentProjects {
 pub struct RecentProjectsDelegate {
     workspace: WeakEntity<Workspace>,
     workspaces: Vec<(WorkspaceId, SerializedWorkspaceLocation)>,
+    pinned_workspaces: Vec<WorkspaceId>,
+    /// The index of the first match in each group of matches, with the group's header.
+    groups: Vec<(usize, Option<SharedString>)>,
+    /// The workspace files that the workspaces were opened from, which are opened instead of
+    /// their folders.
+    workspace_files: Vec<(WorkspaceId, PathBuf)>,
     selected_match_index: usize,
     matches: Vec<StringMatch>,
     render_paths: bool,
@@ -156,6 +197,9 @@ impl RecentProjectsDelegate {
         Self {
             workspace,
             workspaces: Vec::new(),
+            pinned_workspaces: Vec::new(),
+            groups: Vec::new(),
+            workspace_files: Vec::new(),
             selected_match_index: 0,
             matches: Default::default(),
             create_new_window,
@@ -167,15 +211,55 @@ impl RecentProjectsDelegate {
 
     pub fn set_workspaces(&mut self, workspaces: Vec<(WorkspaceId, SerializedWorkspaceLocation)>) {
         self.workspaces = workspaces;
+        self.pinned_workspaces = WORKSPACE_DB
+            .pinned_workspaces()
+            .log_err()
+            .unwrap_or_default();
+        self.workspace_files = WORKSPACE_DB.workspace_files().log_err().unwrap_or_default();
         self.has_any_non_local_projects = !self
             .workspaces
             .iter()
             .all(|(_, location)| matches!(location, SerializedWorkspaceLocation::Local(_, _)));
     }
+
+    fn is_pinned(&self, workspace_id: WorkspaceId) -> bool {
+        self.pinned_workspaces.contains(&workspace_id)
+    }
+
+    /// Returns the header of the group that the workspace is in, if the group has one.
+    fn group_header(
+        &self,
+        workspace_id: WorkspaceId,
+        location: &SerializedWorkspaceLocation,
+        cx: &App,
+    ) -> Option<SharedString> {
+        if self.is_pinned(workspace_id) {
+            return Some("Pinned".into());
+        }
+        match RecentProjectsSettings::get_global(cx).group_by {
+            RecentProjectsGrouping::None => None,
+            RecentProjectsGrouping::Directory => {
+                let paths = location.sorted_paths();
+                let directory = paths.first()?.parent()?;
+                Some(directory.compact().to_string_lossy().into_owned().into())
+            }
+            RecentProjectsGrouping::Host => Some(match location {
+                SerializedWorkspaceLocation::Local(_, _) => "Local".into(),
+                SerializedWorkspaceLocation::Ssh(ssh_project) => host_name(ssh_project).into(),
+            }),
+        }
+    }
+
+    fn workspace_file(&self, workspace_id: WorkspaceId) -> Option<&Path> {
+        self.workspace_files
+            .iter()
+            .find(|(id, _)| *id == workspace_id)
+            .map(|(_, path)| path.as_path())
+    }
 }
 impl EventEmitter<DismissEvent> for RecentProjectsDelegate {}
 impl PickerDelegate for RecentProjectsDelegate {
-    type ListItem = ListItem;
+    type ListItem = Div;
 
     fn placeholder_text(&self, window: &mut Window, _: &mut App) -> Arc<str> {
         let (create_window, reuse_window) = if self.create_new_window {
@@ -202,6 +286,14 @@ impl PickerDelegate for RecentProjectsDelegate {
         self.selected_match_index
     }
 
+    fn separators_after_indices(&self) -> Vec<usize> {
+        self.groups
+            .iter()
+            .skip(1)
+            .map(|(start, _)| start - 1)
+            .collect()
+    }
+
     fn set_selected_index(
         &mut self,
         ix: usize,
@@ -244,6 +336,7 @@ impl PickerDelegate for RecentProjectsDelegate {
             cx.background_executor().clone(),
         ));
         self.matches.sort_unstable_by_key(|m| m.candidate_id);
+        self.group_matches(cx);
 
         if self.reset_selected_match_index {
             self.selected_match_index = self
@@ -279,7 +372,10 @@ impl PickerDelegate for RecentProjectsDelegate {
                     } else {
                         match candidate_workspace_location {
                             SerializedWorkspaceLocation::Local(paths, _) => {
-                                let paths = paths.paths().to_vec();
+                                let paths = match self.workspace_file(*candidate_workspace_id) {
+                                    Some(workspace_file) => vec![workspace_file.to_path_buf()],
+                                    None => paths.paths().to_vec(),
+                                };
                                 if replace_current_window {
                                     cx.spawn_in(window, async move |workspace, cx| {
                                         let continue_replacing = workspace
@@ -369,7 +465,23 @@ impl PickerDelegate for RecentProjectsDelegate {
     ) -> Option<Self::ListItem> {
         let hit = self.matches.get(ix)?;
 
-        let (_, location) = self.workspaces.get(hit.candidate_id)?;
+        let (workspace_id, location) = self.workspaces.get(hit.candidate_id)?;
+        let workspace_file_name = self
+            .workspace_file(*workspace_id)
+            .and_then(|path| path.file_name())
+            .map(|file_name| file_name.to_string_lossy().into_owned());
+        let is_pinned = self.is_pinned(*workspace_id);
+        let group_header = self
+            .groups
+            .iter()
+            .find(|(start, _)| *start == ix)
+            .and_then(|(_, header)| header.clone());
+        let remote_badge = match location {
+            SerializedWorkspaceLocation::Local(_, _) => None,
+            SerializedWorkspaceLocation::Ssh(ssh_project) => {
+                Some(render_remote_badge(ssh_project, cx))
+            }
+        };
 
         let mut path_start_offset = 0;
 
@@ -391,63 +503,105 @@ impl PickerDelegate for RecentProjectsDelegate {
             paths,
         };
 
-        Some(
-            ListItem::new(ix)
-                .toggle_state(selected)
-                .inset(true)
-                .spacing(ListItemSpacing::Sparse)
-                .child(
-                    h_flex()
-                        .flex_grow()
-                        .gap_3()
-                        .when(self.has_any_non_local_projects, |this| {
-                            this.child(match location {
-                                SerializedWorkspaceLocation::Local(_, _) => {
-                                    Icon::new(IconName::Screen)
-                                        .color(Color::Muted)
-                                        .into_any_element()
-                                }
-                                SerializedWorkspaceLocation::Ssh(_) => Icon::new(IconName::Server)
-                                    .color(Color::Muted)
-                                    .into_any_element(),
-                            })
+        let list_item = ListItem::new(ix)
+            .toggle_state(selected)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .child(
+                h_flex()
+                    .flex_grow()
+                    .gap_3()
+                    .when(self.has_any_non_local_projects, |this| {
+                        this.child(match location {
+                            SerializedWorkspaceLocation::Local(_, _) => Icon::new(IconName::Screen)
+                                .color(Color::Muted)
+                                .into_any_element(),
+                            SerializedWorkspaceLocation::Ssh(_) => Icon::new(IconName::Server)
+                                .color(Color::Muted)
+                                .into_any_element(),
                         })
-                        .child({
-                            let mut highlighted = highlighted_match.clone();
-                            if !self.render_paths {
-                                highlighted.paths.clear();
-                            }
-                            highlighted.render(window, cx)
-                        }),
-                )
-                .map(|el| {
-                    let delete_button = div()
-                        .child(
-                            IconButton::new("delete", IconName::Close)
-                                .icon_size(IconSize::Small)
-                                .on_click(cx.listener(move |this, _event, window, cx| {
-                                    cx.stop_propagation();
-                                    window.prevent_default();
-
-                                    this.delegate.delete_recent_project(ix, window, cx)
-                                }))
-                                .tooltip(Tooltip::text("Delete from Recent Projects...")),
+                    })
+                    .child({
+                        let mut highlighted = highlighted_match.clone();
+                        if !self.render_paths {
+                            highlighted.paths.clear();
+                        }
+                        highlighted.render(window, cx)
+                    })
+                    .children(workspace_file_name.map(|file_name| {
+                        Label::new(file_name)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                    }))
+                    .children(remote_badge),
+            )
+            .map(|el| {
+                let buttons = h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new(
+                            "pin",
+                            if is_pinned {
+                                IconName::Unpin
+                            } else {
+                                IconName::Pin
+                            },
                         )
-                        .into_any_element();
+                        .icon_size(IconSize::Small)
+                        .on_click(cx.listener(move |this, _event, window, cx| {
+                            cx.stop_propagation();
+                            window.prevent_default();
 
-                    if self.selected_index() == ix {
-                        el.end_slot::<AnyElement>(delete_button)
-                    } else {
-                        el.end_hover_slot::<AnyElement>(delete_button)
-                    }
+                            this.delegate.toggle_pin(ix, window, cx)
+                        }))
+                        .tooltip(move |window, cx| {
+                            Tooltip::for_action(
+                                if is_pinned { "Unpin" } else { "Pin" },
+                                &TogglePin,
+                                window,
+                                cx,
+                            )
+                        }),
+                    )
+                    .child(
+                        IconButton::new("delete", IconName::Close)
+                            .icon_size(IconSize::Small)
+                            .on_click(cx.listener(move |this, _event, window, cx| {
+                                cx.stop_propagation();
+                                window.prevent_default();
+
+                                this.delegate.delete_recent_project(ix, window, cx)
+                            }))
+                            .tooltip(Tooltip::text("Delete from Recent Projects...")),
+                    )
+                    .into_any_element();
+
+                if self.selected_index() == ix || is_pinned {
+                    el.end_slot::<AnyElement>(buttons)
+                } else {
+                    el.end_hover_slot::<AnyElement>(buttons)
+                }
+            })
+            .tooltip(move |_, cx| {
+                let tooltip_highlighted_location = highlighted_match.clone();
+                cx.new(|_| MatchTooltip {
+                    highlighted_location: tooltip_highlighted_location,
                 })
-                .tooltip(move |_, cx| {
-                    let tooltip_highlighted_location = highlighted_match.clone();
-                    cx.new(|_| MatchTooltip {
-                        highlighted_location: tooltip_highlighted_location,
-                    })
-                    .into()
-                }),
+                .into()
+            });
+
+        Some(
+            v_flex()
+                .when_some(group_header, |this, header| {
+                    this.child(
+                        h_flex().px_2().pt_1().child(
+                            Label::new(header)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                    )
+                })
+                .child(list_item),
         )
     }
 
@@ -532,7 +686,139 @@ fn highlights_for_path(
         },
     )
 }
+/// Returns the name of the host that the SSH project is on, as it's written in `ssh` commands.
+fn host_name(ssh_project: &SerializedSshProject) -> String {
+    let mut host_name = String::new();
+    if let Some(user) = &ssh_project.user {
+        host_name.push_str(user);
+        host_name.push('@');
+    }
+    host_name.push_str(&ssh_project.host);
+    if let Some(port) = ssh_project.port {
+        host_name.push(':');
+        host_name.push_str(&port.to_string());
+    }
+    host_name
+}
+
+fn render_remote_badge(ssh_project: &SerializedSshProject, cx: &App) -> impl IntoElement {
+    let is_docker = SshSettings::get_global(cx)
+        .ssh_connections()
+        .any(|connection| {
+            connection.host.as_ref() == ssh_project.host && connection.docker.unwrap_or_default()
+        });
+    h_flex()
+        .flex_none()
+        .px_1()
+        .rounded_sm()
+        .border_1()
+        .border_color(cx.theme().colors().border_variant)
+        .child(
+            Label::new(if is_docker { "Docker" } else { "SSH" })
+                .size(LabelSize::XSmall)
+                .color(Color::Muted),
+        )
+}
+
+/// Removes the recent projects that haven't been opened for the number of days in the settings,
+/// unless they're pinned.
+fn remove_stale_projects(workspace: &mut Workspace, cx: &mut Context<Workspace>) {
+    let stale_after_days = RecentProjectsSettings::get_global(cx).stale_after_days;
+    let current_workspace_id = workspace.database_id();
+    cx.spawn(async move |workspace, cx| {
+        let stale_workspaces = WORKSPACE_DB
+            .stale_workspaces(stale_after_days)?
+            .into_iter()
+            .filter(|workspace_id| Some(*workspace_id) != current_workspace_id)
+            .collect::<Vec<_>>();
+        for workspace_id in &stale_workspaces {
+            WORKSPACE_DB.delete_workspace_by_id(*workspace_id).await?;
+        }
+
+        let message = match stale_workspaces.len() {
+            0 => "No recent projects are stale".to_string(),
+            1 => "Removed 1 stale recent project".to_string(),
+            count => format!("Removed {count} stale recent projects"),
+        };
+        workspace.update(cx, |workspace, cx| {
+            workspace.show_toast(
+                Toast::new(NotificationId::unique::<RemoveStaleProjects>(), message),
+                cx,
+            )
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
 impl RecentProjectsDelegate {
+    /// Moves the pinned matches to the top, and groups the rest by the grouping in the settings,
+    /// keeping the groups and the matches in them in order of recency.
+    fn group_matches(&mut self, cx: &App) {
+        let mut headers = Vec::new();
+        let mut matches = std::mem::take(&mut self.matches)
+            .into_iter()
+            .map(|string_match| {
+                let (workspace_id, location) = &self.workspaces[string_match.candidate_id];
+                let header = self.group_header(*workspace_id, location, cx);
+                let group_ix = match headers.iter().position(|other| *other == header) {
+                    Some(group_ix) => group_ix,
+                    None => {
+                        headers.push(header.clone());
+                        headers.len() - 1
+                    }
+                };
+                (
+                    !self.is_pinned(*workspace_id),
+                    group_ix,
+                    string_match,
+                    header,
+                )
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by_key(|(is_unpinned, group_ix, _, _)| (*is_unpinned, *group_ix));
+
+        self.groups.clear();
+        for (ix, (_, _, string_match, header)) in matches.into_iter().enumerate() {
+            if self
+                .groups
+                .last()
+                .map_or(true, |(_, last_header)| *last_header != header)
+            {
+                self.groups.push((ix, header));
+            }
+            self.matches.push(string_match);
+        }
+    }
+
+    fn toggle_pin(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(selected_match) = self.matches.get(ix) {
+            let (workspace_id, _) = self.workspaces[selected_match.candidate_id];
+            let is_pinned = self.is_pinned(workspace_id);
+            cx.spawn_in(window, async move |this, cx| {
+                if is_pinned {
+                    WORKSPACE_DB.unpin_workspace(workspace_id).await.log_err();
+                } else {
+                    WORKSPACE_DB.pin_workspace(workspace_id).await.log_err();
+                }
+                this.update_in(cx, move |picker, window, cx| {
+                    picker.delegate.pinned_workspaces = WORKSPACE_DB
+                        .pinned_workspaces()
+                        .log_err()
+                        .unwrap_or_default();
+                    picker.delegate.reset_selected_match_index = false;
+                    picker.update_matches(picker.query(cx), window, cx);
+                    // Keep the project selected where it moved to.
+                    if let Some(ix) = picker.delegate.matches.iter().position(|string_match| {
+                        picker.delegate.workspaces[string_match.candidate_id].0 == workspace_id
+                    }) {
+                        picker.set_selected_index(ix, true, window, cx);
+                    }
+                })
+            })
+            .detach();
+        }
+    }
+
     fn delete_recent_project(
         &self,
         ix: usize,
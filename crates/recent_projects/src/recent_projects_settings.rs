@@ -0,0 +1,47 @@
+use anyhow::Result;
+use gpui::App;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Debug)]
+pub struct RecentProjectsSettings {
+    pub group_by: RecentProjectsGrouping,
+    pub stale_after_days: u64,
+}
+
+/// How the recent projects are grouped, below the pinned ones.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RecentProjectsGrouping {
+    /// Don't group the recent projects.
+    #[default]
+    None,
+    /// Group the recent projects by the directory that they're in.
+    Directory,
+    /// Group the recent projects by the host that they're on.
+    Host,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct RecentProjectsSettingsContent {
+    /// How to group the recent projects, below the pinned ones.
+    ///
+    /// Default: none
+    pub group_by: Option<RecentProjectsGrouping>,
+    /// The number of days after which `recent projects: remove stale projects` removes the recent
+    /// projects that haven't been opened, unless they're pinned.
+    ///
+    /// Default: 90
+    pub stale_after_days: Option<u64>,
+}
+
+impl Settings for RecentProjectsSettings {
+    const KEY: Option<&'static str> = Some("recent_projects");
+
+    type FileContent = RecentProjectsSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::BTreeSet;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use editor::Editor;
 use file_finder::OpenPathDelegate;
@@ -18,6 +19,7 @@ use gpui::{
 use picker::Picker;
 use project::Project;
 use remote::ssh_session::ConnectionIdentifier;
+use remote::ConnectionState;
 use remote::SshConnectionOptions;
 use remote::SshRemoteClient;
 use settings::update_settings_file;
@@ -25,8 +27,8 @@ use settings::Settings;
 use ui::Navigable;
 use ui::NavigableEntry;
 use ui::{
-    prelude::*, IconButtonShape, List, ListItem, ListSeparator, Modal, ModalHeader, Scrollbar,
-    ScrollbarState, Section, Tooltip,
+    prelude::*, IconButtonShape, Indicator, List, ListItem, ListSeparator, Modal, ModalHeader,
+    Scrollbar, ScrollbarState, Section, Tooltip,
 };
 use util::ResultExt;
 use workspace::notifications::NotificationId;
@@ -45,12 +47,15 @@ use crate::ssh_connections::SshPrompt;
 use crate::ssh_connections::SshSettings;
 use crate::OpenRemote;
 
+const CONNECTION_HEALTH_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
 mod navigation_base {}
 pub struct RemoteServerProjects {
     mode: Mode,
     focus_handle: FocusHandle,
     workspace: WeakEntity<Workspace>,
     retained_connections: Vec<Entity<SshRemoteClient>>,
+    _refresh_connection_health: Task<()>,
 }
 
 struct CreateRemoteServer {
@@ -109,6 +114,33 @@ impl EditNicknameState {
     }
 }
 
+struct EditJumpHostState {
+    index: usize,
+    editor: Entity<Editor>,
+}
+
+impl EditJumpHostState {
+    fn new(index: usize, window: &mut Window, cx: &mut App) -> Self {
+        let this = Self {
+            index,
+            editor: cx.new(|cx| Editor::single_line(window, cx)),
+        };
+        let starting_text = SshSettings::get_global(cx)
+            .ssh_connections()
+            .nth(index)
+            .and_then(|state| state.jump_host.clone())
+            .filter(|text| !text.is_empty());
+        this.editor.update(cx, |this, cx| {
+            this.set_placeholder_text("user@jump-host:2222", cx);
+            if let Some(starting_text) = starting_text {
+                this.set_text(starting_text, window, cx);
+            }
+        });
+        this.editor.focus_handle(cx).focus(window);
+        this
+    }
+}
+
 impl Focusable for ProjectPicker {
     fn focus_handle(&self, cx: &App) -> FocusHandle {
         self.picker.focus_handle(cx)
@@ -292,12 +324,13 @@ impl DefaultState {
 struct ViewServerOptionsState {
     server_index: usize,
     connection: SshConnection,
-    entries: [NavigableEntry; 4],
+    entries: [NavigableEntry; 6],
 }
 enum Mode {
     Default(DefaultState),
     ViewServerOptions(ViewServerOptionsState),
     EditNickname(EditNicknameState),
+    EditJumpHost(EditJumpHostState),
     ProjectPicker(Entity<ProjectPicker>),
     CreateRemoteServer(CreateRemoteServer),
 }
@@ -339,11 +372,22 @@ impl RemoteServerProjects {
             ..Default::default()
         });
 
+        // Keep the state and latency of open connections up to date.
+        let refresh_connection_health = cx.spawn(async move |this, cx| loop {
+            cx.background_executor()
+                .timer(CONNECTION_HEALTH_REFRESH_INTERVAL)
+                .await;
+            if this.update(cx, |_, cx| cx.notify()).is_err() {
+                break;
+            }
+        });
+
         Self {
             mode: Mode::default_mode(cx),
             focus_handle,
             workspace,
             retained_connections: Vec::new(),
+            _refresh_connection_health: refresh_connection_health,
         }
     }
 
@@ -573,6 +617,19 @@ impl RemoteServerProjects {
                 self.mode = Mode::default_mode(cx);
                 self.focus_handle.focus(window);
             }
+            Mode::EditJumpHost(state) => {
+                let text = Some(get_text(&state.editor, cx)).filter(|text| !text.is_empty());
+                let index = state.index;
+                self.update_settings_file(cx, move |setting, _| {
+                    if let Some(connections) = setting.ssh_connections.as_mut() {
+                        if let Some(connection) = connections.get_mut(index) {
+                            connection.jump_host = text;
+                        }
+                    }
+                });
+                self.mode = Mode::default_mode(cx);
+                self.focus_handle.focus(window);
+            }
         }
     }
 
@@ -633,7 +690,8 @@ impl RemoteServerProjects {
                         aux_label.map(|label| {
                             Label::new(label).size(LabelSize::Small).color(Color::Muted)
                         }),
-                    ),
+                    )
+                    .children(self.render_connection_health(&ssh_server.connection, cx)),
             )
             .child(
                 List::new()
@@ -731,6 +789,70 @@ impl RemoteServerProjects {
             )
     }
 
+    /// Returns the state and latency of an open connection to the server, if there is one.
+    fn connection_health(
+        &self,
+        connection: &SshConnection,
+        cx: &App,
+    ) -> Option<(ConnectionState, Option<Duration>)> {
+        // The workspace that this modal belongs to is being rendered, so it can't be read
+        // through its window.
+        let active_workspace = self.workspace.upgrade();
+        let workspaces = active_workspace
+            .iter()
+            .map(|workspace| workspace.read(cx))
+            .chain(
+                cx.windows()
+                    .into_iter()
+                    .filter_map(|window| window.downcast::<Workspace>())
+                    .filter_map(|window| window.read(cx).ok()),
+            );
+
+        workspaces
+            .filter_map(|workspace| workspace.project().read(cx).ssh_client())
+            .find_map(|client| {
+                let client = client.read(cx);
+                let options = client.connection_options();
+                let is_same_server = options.host == connection.host.as_ref()
+                    && options.username == connection.username
+                    && options.port == connection.port;
+                is_same_server.then(|| (client.connection_state(), client.latency()))
+            })
+    }
+
+    fn render_connection_health(
+        &self,
+        connection: &SshConnection,
+        cx: &App,
+    ) -> Option<impl IntoElement> {
+        let (state, latency) = self.connection_health(connection, cx)?;
+        let (color, status) = match state {
+            ConnectionState::Connecting => (Color::Info, "Connecting…".to_string()),
+            ConnectionState::Connected => (
+                Color::Success,
+                match latency {
+                    Some(latency) => format!("Connected ({} ms)", latency.as_millis()),
+                    None => "Connected".to_string(),
+                },
+            ),
+            ConnectionState::HeartbeatMissed => (Color::Warning, "Not responding".to_string()),
+            ConnectionState::Reconnecting => (Color::Warning, "Reconnecting…".to_string()),
+            ConnectionState::Disconnected => (Color::Error, "Disconnected".to_string()),
+        };
+
+        Some(
+            h_flex()
+                .ml_auto()
+                .gap_1()
+                .child(Indicator::dot().color(color))
+                .child(
+                    Label::new(status)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+        )
+    }
+
     fn render_ssh_project(
         &mut self,
         server_ix: usize,
@@ -741,6 +863,7 @@ impl RemoteServerProjects {
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
         let server = server.clone();
+        let is_default = server.connection.default_project.as_ref() == Some(project);
         let element_id_base = SharedString::from(format!("remote-project-{server_ix}"));
         let container_element_id_base =
             SharedString::from(format!("remote-project-container-{element_id_base}"));
@@ -805,9 +928,43 @@ impl RemoteServerProjects {
                     )
                     .child(Label::new(project.paths.join(", ")))
                     .on_click(cx.listener(move |this, _, window, cx| callback(this, window, cx)))
-                    .end_hover_slot::<AnyElement>(Some(
+                    .end_slot::<AnyElement>(is_default.then(|| {
                         div()
                             .mr_2()
+                            .child(
+                                Icon::new(IconName::StarFilled)
+                                    .size(IconSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .into_any_element()
+                    }))
+                    .end_hover_slot::<AnyElement>(Some(
+                        h_flex()
+                            .mr_2()
+                            .child({
+                                let project = project.clone();
+                                IconButton::new(
+                                    "default-remote-project",
+                                    if is_default {
+                                        IconName::StarFilled
+                                    } else {
+                                        IconName::Star
+                                    },
+                                )
+                                .icon_size(IconSize::Small)
+                                .shape(IconButtonShape::Square)
+                                .size(ButtonSize::Large)
+                                .tooltip(Tooltip::text(if is_default {
+                                    "Unset Default Project"
+                                } else {
+                                    "Set as Default Project"
+                                }))
+                                .on_click(cx.listener(
+                                    move |this, _, _, cx| {
+                                        this.toggle_default_ssh_project(server_ix, &project, cx)
+                                    },
+                                ))
+                            })
                             .child({
                                 let project = project.clone();
                                 // Right-margin to offset it from the Scrollbar
@@ -857,6 +1014,44 @@ impl RemoteServerProjects {
                 .and_then(|connections| connections.get_mut(server))
             {
                 server.projects.remove(&project);
+                if server.default_project.as_ref() == Some(&project) {
+                    server.default_project = None;
+                }
+            }
+        });
+    }
+
+    fn toggle_default_ssh_project(
+        &mut self,
+        server: usize,
+        project: &SshProject,
+        cx: &mut Context<Self>,
+    ) {
+        let project = project.clone();
+        self.update_settings_file(cx, move |setting, _| {
+            if let Some(server) = setting
+                .ssh_connections
+                .as_mut()
+                .and_then(|connections| connections.get_mut(server))
+            {
+                if server.default_project.as_ref() == Some(&project) {
+                    server.default_project = None;
+                } else {
+                    server.default_project = Some(project);
+                }
+            }
+        });
+    }
+
+    fn toggle_forward_agent(&mut self, server: usize, cx: &mut Context<Self>) {
+        self.update_settings_file(cx, move |setting, _| {
+            if let Some(server) = setting
+                .ssh_connections
+                .as_mut()
+                .and_then(|connections| connections.get_mut(server))
+            {
+                let forward_agent = !server.forward_agent.unwrap_or_default();
+                server.forward_agent = forward_agent.then_some(true);
             }
         });
     }
@@ -879,6 +1074,11 @@ impl RemoteServerProjects {
                     args: connection_options.args.unwrap_or_default(),
                     upload_binary_over_ssh: None,
                     port_forwards: connection_options.port_forwards,
+                    jump_host: connection_options.jump_host,
+                    forward_agent: connection_options.forward_agent.then_some(true),
+                    default_project: None,
+                    docker: connection_options.docker.then_some(true),
+                    dev_container: None,
                 })
         });
     }
@@ -892,7 +1092,7 @@ impl RemoteServerProjects {
 
         state.address_editor.update(cx, |editor, cx| {
             if editor.text(cx).is_empty() {
-                editor.set_placeholder_text("ssh user@example -p 2222", cx);
+                editor.set_placeholder_text("ssh user@example -p 2222 or docker://container", cx);
             }
         });
 
@@ -935,7 +1135,7 @@ impl RemoteServerProjects {
                                     .gap_1()
                                     .child(
                                         Label::new(
-                                            "Enter the command you use to SSH into this server.",
+                                            "Enter the command you use to SSH into this server, or docker:// followed by a container name.",
                                         )
                                         .color(Color::Muted)
                                         .size(LabelSize::Small),
@@ -969,6 +1169,13 @@ impl RemoteServerProjects {
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
         let connection_string = connection.host.clone();
+        // The options can be changed from this view, so read them from the settings rather than
+        // from the snapshot taken when the view was opened.
+        let current_connection = SshSettings::get_global(cx)
+            .ssh_connections()
+            .nth(server_index)
+            .unwrap_or_else(|| connection.clone());
+        let forward_agent = current_connection.forward_agent.unwrap_or_default();
 
         let mut view = Navigable::new(
             div()
@@ -1024,6 +1231,77 @@ impl RemoteServerProjects {
                                         })),
                                 )
                         })
+                        .child({
+                            let label = if current_connection.jump_host.is_some() {
+                                "Edit Jump Host"
+                            } else {
+                                "Connect Through a Jump Host"
+                            };
+                            div()
+                                .id("ssh-options-jump-host")
+                                .track_focus(&entries[1].focus_handle)
+                                .on_action(cx.listener(
+                                    move |this, _: &menu::Confirm, window, cx| {
+                                        this.mode = Mode::EditJumpHost(EditJumpHostState::new(
+                                            server_index,
+                                            window,
+                                            cx,
+                                        ));
+                                        cx.notify();
+                                    },
+                                ))
+                                .child(
+                                    ListItem::new("jump-host")
+                                        .toggle_state(
+                                            entries[1].focus_handle.contains_focused(window, cx),
+                                        )
+                                        .inset(true)
+                                        .spacing(ui::ListItemSpacing::Sparse)
+                                        .start_slot(Icon::new(IconName::Route).color(Color::Muted))
+                                        .child(Label::new(label))
+                                        .end_slot(current_connection.jump_host.clone().map(
+                                            |jump_host| Label::new(jump_host).color(Color::Muted),
+                                        ))
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            this.mode = Mode::EditJumpHost(EditJumpHostState::new(
+                                                server_index,
+                                                window,
+                                                cx,
+                                            ));
+                                            cx.notify();
+                                        })),
+                                )
+                        })
+                        .child(
+                            div()
+                                .id("ssh-options-forward-agent")
+                                .track_focus(&entries[2].focus_handle)
+                                .on_action(cx.listener(move |this, _: &menu::Confirm, _, cx| {
+                                    this.toggle_forward_agent(server_index, cx);
+                                }))
+                                .child(
+                                    ListItem::new("forward-agent")
+                                        .toggle_state(
+                                            entries[2].focus_handle.contains_focused(window, cx),
+                                        )
+                                        .inset(true)
+                                        .spacing(ui::ListItemSpacing::Sparse)
+                                        .start_slot(
+                                            Icon::new(IconName::LockOutlined).color(Color::Muted),
+                                        )
+                                        .child(Label::new("Forward SSH Agent"))
+                                        .end_slot(
+                                            Label::new(if forward_agent { "On" } else { "Off" })
+                                                .color(Color::Muted),
+                                        )
+                                        .tooltip(Tooltip::text(
+                                            "Takes effect the next time you connect",
+                                        ))
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.toggle_forward_agent(server_index, cx);
+                                        })),
+                                ),
+                        )
                         .child({
                             let workspace = self.workspace.clone();
                             fn callback(
@@ -1059,7 +1337,7 @@ impl RemoteServerProjects {
                             }
                             div()
                                 .id("ssh-options-copy-server-address")
-                                .track_focus(&entries[1].focus_handle)
+                                .track_focus(&entries[3].focus_handle)
                                 .on_action({
                                     let connection_string = connection_string.clone();
                                     let workspace = self.workspace.clone();
@@ -1070,7 +1348,7 @@ impl RemoteServerProjects {
                                 .child(
                                     ListItem::new("copy-server-address")
                                         .toggle_state(
-                                            entries[1].focus_handle.contains_focused(window, cx),
+                                            entries[3].focus_handle.contains_focused(window, cx),
                                         )
                                         .inset(true)
                                         .spacing(ui::ListItemSpacing::Sparse)
@@ -1131,7 +1409,7 @@ impl RemoteServerProjects {
                             }
                             div()
                                 .id("ssh-options-copy-server-address")
-                                .track_focus(&entries[2].focus_handle)
+                                .track_focus(&entries[4].focus_handle)
                                 .on_action(cx.listener({
                                     let connection_string = connection_string.clone();
                                     move |_, _: &menu::Confirm, window, cx| {
@@ -1148,7 +1426,7 @@ impl RemoteServerProjects {
                                 .child(
                                     ListItem::new("remove-server")
                                         .toggle_state(
-                                            entries[2].focus_handle.contains_focused(window, cx),
+                                            entries[4].focus_handle.contains_focused(window, cx),
                                         )
                                         .inset(true)
                                         .spacing(ui::ListItemSpacing::Sparse)
@@ -1170,7 +1448,7 @@ impl RemoteServerProjects {
                         .child({
                             div()
                                 .id("ssh-options-copy-server-address")
-                                .track_focus(&entries[3].focus_handle)
+                                .track_focus(&entries[5].focus_handle)
                                 .on_action(cx.listener(|this, _: &menu::Confirm, window, cx| {
                                     this.mode = Mode::default_mode(cx);
                                     cx.focus_self(window);
@@ -1179,7 +1457,7 @@ impl RemoteServerProjects {
                                 .child(
                                     ListItem::new("go-back")
                                         .toggle_state(
-                                            entries[3].focus_handle.contains_focused(window, cx),
+                                            entries[5].focus_handle.contains_focused(window, cx),
                                         )
                                         .inset(true)
                                         .spacing(ui::ListItemSpacing::Sparse)
@@ -1242,6 +1520,54 @@ impl RemoteServerProjects {
             )
     }
 
+    fn render_edit_jump_host(
+        &self,
+        state: &EditJumpHostState,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let Some(connection) = SshSettings::get_global(cx)
+            .ssh_connections()
+            .nth(state.index)
+        else {
+            return v_flex()
+                .id("ssh-edit-jump-host")
+                .track_focus(&self.focus_handle(cx));
+        };
+
+        let connection_string = connection.host.clone();
+        let nickname = connection.nickname.clone().map(|s| s.into());
+
+        v_flex()
+            .id("ssh-edit-jump-host")
+            .track_focus(&self.focus_handle(cx))
+            .child(
+                SshConnectionHeader {
+                    connection_string,
+                    paths: Default::default(),
+                    nickname,
+                }
+                .render(window, cx),
+            )
+            .child(
+                h_flex()
+                    .p_2()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(state.editor.clone()),
+            )
+            .child(
+                h_flex().p_2().child(
+                    Label::new(
+                        "Connect to this server through another host, as with `ssh -J`. \
+                         Leave empty to connect directly.",
+                    )
+                    .color(Color::Muted)
+                    .size(LabelSize::Small),
+                ),
+            )
+    }
+
     fn render_default(
         &mut self,
         mut state: DefaultState,
@@ -1435,6 +1761,9 @@ impl Render for RemoteServerProjects {
                 Mode::EditNickname(state) => self
                     .render_edit_nickname(state, window, cx)
                     .into_any_element(),
+                Mode::EditJumpHost(state) => self
+                    .render_edit_jump_host(state, window, cx)
+                    .into_any_element(),
             })
     }
 }
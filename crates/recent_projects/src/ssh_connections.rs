@@ -53,6 +53,9 @@ impl SshSettings {
                     port,
                     username,
                     port_forwards: conn.port_forwards,
+                    jump_host: conn.jump_host,
+                    forward_agent: conn.forward_agent.unwrap_or_default(),
+                    docker: conn.docker.unwrap_or_default(),
                     password: None,
                 };
             }
@@ -64,6 +67,20 @@ impl SshSettings {
             ..Default::default()
         }
     }
+
+    /// Returns the project to open when connecting to the server without specifying any paths.
+    pub fn default_project_for(
+        &self,
+        host: &str,
+        port: Option<u16>,
+        username: Option<&str>,
+    ) -> Option<SshProject> {
+        self.ssh_connections()
+            .find(|conn| {
+                conn.host == host && conn.username.as_deref() == username && conn.port == port
+            })
+            .and_then(|conn| conn.default_project)
+    }
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
@@ -90,6 +107,23 @@ pub struct SshConnection {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub port_forwards: Option<Vec<SshPortForwardOption>>,
+
+    /// A host to connect through, as accepted by `ssh -J`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jump_host: Option<String>,
+    /// Whether to forward your local SSH agent to the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forward_agent: Option<bool>,
+    /// The project to open when connecting to this server without specifying any paths.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_project: Option<SshProject>,
+    /// Whether `host` is the name of a local Docker container, rather than an SSH host.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub docker: Option<bool>,
+    /// The local folder whose `devcontainer.json` describes this container, if any.
+    /// Used to rebuild the container.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dev_container: Option<PathBuf>,
 }
 
 impl From<SshConnection> for SshConnectionOptions {
@@ -103,6 +137,9 @@ impl From<SshConnection> for SshConnectionOptions {
             nickname: val.nickname,
             upload_binary_over_ssh: val.upload_binary_over_ssh.unwrap_or_default(),
             port_forwards: val.port_forwards,
+            jump_host: val.jump_host,
+            forward_agent: val.forward_agent.unwrap_or_default(),
+            docker: val.docker.unwrap_or_default(),
         }
     }
 }
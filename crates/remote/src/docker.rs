@@ -0,0 +1,688 @@
+use crate::{
+    shell_script,
+    ssh_session::{
+        parse_listening_ports, parse_platform, remote_server_binary_path, RemoteConnection,
+        SshClientDelegate, SshPortForwardOption, SshRemoteConnection,
+    },
+    SshConnectionOptions, SshPlatform,
+};
+use anyhow::{anyhow, Context as _, Result};
+use async_trait::async_trait;
+use collections::HashMap;
+use futures::channel::mpsc::{Sender, UnboundedReceiver, UnboundedSender};
+use gpui::{App, AsyncApp, Task};
+use release_channel::{AppCommitSha, AppVersion, ReleaseChannel};
+use rpc::proto::Envelope;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use smol::process::{self, Stdio};
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering::SeqCst},
+        Arc,
+    },
+};
+
+/// Runs the remote server inside a Docker container on this machine.
+pub(crate) struct DockerRemoteConnection {
+    connection_options: SshConnectionOptions,
+    remote_binary_path: Option<PathBuf>,
+    killed: AtomicBool,
+}
+
+impl DockerRemoteConnection {
+    pub(crate) async fn new(
+        connection_options: SshConnectionOptions,
+        delegate: Arc<dyn SshClientDelegate>,
+        cx: &mut AsyncApp,
+    ) -> Result<Self> {
+        delegate.set_status(Some("Starting container"), cx);
+        run_docker(&["start", &connection_options.host])
+            .await
+            .with_context(|| format!("failed to start container {}", connection_options.host))?;
+
+        let mut this = Self {
+            connection_options,
+            remote_binary_path: None,
+            killed: AtomicBool::new(false),
+        };
+
+        let (release_channel, version, commit) = cx.update(|cx| {
+            (
+                ReleaseChannel::global(cx),
+                AppVersion::global(cx),
+                AppCommitSha::try_global(cx),
+            )
+        })?;
+        this.remote_binary_path = Some(
+            this.ensure_server_binary(&delegate, release_channel, version, commit, cx)
+                .await?,
+        );
+
+        Ok(this)
+    }
+
+    /// Builds a `docker exec` invocation that runs the given script in the container,
+    /// from the home directory of the container's user.
+    fn exec_command(&self, script: &str) -> process::Command {
+        let mut command = util::command::new_smol_command("docker");
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .args(self.exec_args(false))
+            .args(["sh", "-c", &format!("cd; {script}")]);
+        command
+    }
+
+    fn exec_args(&self, tty: bool) -> Vec<String> {
+        let mut args = vec![
+            "exec".to_string(),
+            if tty { "-it" } else { "-i" }.to_string(),
+        ];
+        if let Some(username) = &self.connection_options.username {
+            args.push("-u".to_string());
+            args.push(username.clone());
+        }
+        args.push(self.connection_options.host.clone());
+        args
+    }
+
+    async fn run_script(&self, script: &str) -> Result<String> {
+        let output = self.exec_command(script).output().await?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            Err(anyhow!(
+                "failed to run command in container: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ))
+        }
+    }
+
+    async fn platform(&self) -> Result<SshPlatform> {
+        parse_platform(&self.run_script("uname -sm").await?)
+    }
+
+    async fn ensure_server_binary(
+        &self,
+        delegate: &Arc<dyn SshClientDelegate>,
+        release_channel: ReleaseChannel,
+        version: gpui::SemanticVersion,
+        commit: Option<AppCommitSha>,
+        cx: &mut AsyncApp,
+    ) -> Result<PathBuf> {
+        let dst_path = remote_server_binary_path(release_channel, version, commit);
+
+        #[cfg(debug_assertions)]
+        if std::env::var("ZED_BUILD_REMOTE_SERVER").is_ok() {
+            let src_path =
+                SshRemoteConnection::build_local(self.platform().await?, delegate, cx).await?;
+            self.upload_server_binary(&src_path, &dst_path, delegate, cx)
+                .await?;
+            return Ok(dst_path);
+        }
+
+        if self
+            .run_script(&shell_script!(
+                "{binary_path} version",
+                binary_path = &dst_path.to_string_lossy()
+            ))
+            .await
+            .is_ok()
+        {
+            return Ok(dst_path);
+        }
+
+        let wanted_version = cx.update(|cx| match release_channel {
+            ReleaseChannel::Nightly => Ok(None),
+            ReleaseChannel::Dev => {
+                anyhow::bail!(
+                    "ZED_BUILD_REMOTE_SERVER is not set and no remote server exists in the container at ({:?})",
+                    dst_path
+                )
+            }
+            _ => Ok(Some(AppVersion::global(cx))),
+        })??;
+
+        let platform = self.platform().await?;
+        let src_path = delegate
+            .download_server_binary_locally(platform, release_channel, wanted_version, cx)
+            .await?;
+        self.upload_server_binary(&src_path, &dst_path, delegate, cx)
+            .await?;
+        Ok(dst_path)
+    }
+
+    /// Copies a gzipped server binary into the container and extracts it to `dst_path`.
+    async fn upload_server_binary(
+        &self,
+        src_path: &Path,
+        dst_path: &Path,
+        delegate: &Arc<dyn SshClientDelegate>,
+        cx: &mut AsyncApp,
+    ) -> Result<()> {
+        delegate.set_status(Some("Copying remote development server into container"), cx);
+        // `docker cp` does not expand `~`, so the binary is copied to a fixed location
+        // and moved into the home directory from inside the container.
+        let tmp_path_gz = format!("/tmp/zed-remote-server-{}.gz", std::process::id());
+        run_docker(&[
+            "cp",
+            &src_path.to_string_lossy(),
+            &format!("{}:{}", self.connection_options.host, tmp_path_gz),
+        ])
+        .await
+        .context("failed to copy server binary into container")?;
+
+        delegate.set_status(Some("Extracting remote development server"), cx);
+        let parent = dst_path.parent().unwrap_or(Path::new("."));
+        self.run_script(&shell_script!(
+            "mkdir -p {parent} && gunzip -f {tmp_path_gz} && chmod 755 {tmp_path} && mv {tmp_path} {dst_path}",
+            parent = &parent.to_string_lossy(),
+            tmp_path_gz = &tmp_path_gz,
+            tmp_path = tmp_path_gz.strip_suffix(".gz").unwrap(),
+            dst_path = &dst_path.to_string_lossy(),
+        ))
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl RemoteConnection for DockerRemoteConnection {
+    fn start_proxy(
+        &self,
+        unique_identifier: String,
+        reconnect: bool,
+        incoming_tx: UnboundedSender<Envelope>,
+        outgoing_rx: UnboundedReceiver<Envelope>,
+        connection_activity_tx: Sender<()>,
+        delegate: Arc<dyn SshClientDelegate>,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<i32>> {
+        delegate.set_status(Some("Starting proxy"), cx);
+
+        let Some(remote_binary_path) = self.remote_binary_path.clone() else {
+            return Task::ready(Err(anyhow!("Remote binary path not set")));
+        };
+
+        let mut start_proxy_command = shell_script!(
+            "exec {binary_path} proxy --identifier {identifier}",
+            binary_path = &remote_binary_path.to_string_lossy(),
+            identifier = &unique_identifier,
+        );
+        if let Some(rust_log) = std::env::var("RUST_LOG").ok() {
+            start_proxy_command = format!(
+                "RUST_LOG={} {}",
+                shlex::try_quote(&rust_log).unwrap(),
+                start_proxy_command
+            )
+        }
+        if reconnect {
+            start_proxy_command.push_str(" --reconnect");
+        }
+
+        let proxy_process = match self
+            .exec_command(&start_proxy_command)
+            // IMPORTANT: we kill this process when we drop the task that uses it.
+            .kill_on_drop(true)
+            .spawn()
+        {
+            Ok(process) => process,
+            Err(error) => {
+                return Task::ready(Err(anyhow!("failed to spawn remote server: {}", error)))
+            }
+        };
+
+        SshRemoteConnection::multiplex(
+            proxy_process,
+            incoming_tx,
+            outgoing_rx,
+            connection_activity_tx,
+            &cx,
+        )
+    }
+
+    fn upload_directory(
+        &self,
+        src_path: PathBuf,
+        dest_path: PathBuf,
+        cx: &App,
+    ) -> Task<Result<()>> {
+        let container = self.connection_options.host.clone();
+        cx.background_spawn(async move {
+            run_docker(&[
+                "cp",
+                &src_path.to_string_lossy(),
+                &format!("{}:{}", container, dest_path.display()),
+            ])
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to upload directory {} -> {}",
+                    src_path.display(),
+                    dest_path.display()
+                )
+            })?;
+            Ok(())
+        })
+    }
+
+    fn forward_port(&self, forward: SshPortForwardOption, _cx: &App) -> Task<Result<()>> {
+        Task::ready(Err(anyhow!(
+            "cannot forward port {} from a container; publish it with `runArgs` instead",
+            forward.remote_port
+        )))
+    }
+
+    fn cancel_port_forward(&self, _forward: SshPortForwardOption, _cx: &App) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn listening_ports(&self, cx: &App) -> Task<Result<BTreeSet<u16>>> {
+        let command = self
+            .exec_command("cat /proc/net/tcp /proc/net/tcp6 2>/dev/null || true")
+            .output();
+        cx.background_spawn(async move {
+            let output = command.await?;
+            Ok(parse_listening_ports(&String::from_utf8_lossy(
+                &output.stdout,
+            )))
+        })
+    }
+
+    async fn kill(&self) -> Result<()> {
+        // The container outlives the connection, so that it can be reconnected to.
+        self.killed.store(true, SeqCst);
+        Ok(())
+    }
+
+    fn has_been_killed(&self) -> bool {
+        self.killed.load(SeqCst)
+    }
+
+    fn ssh_args(&self) -> Vec<String> {
+        self.exec_args(true)
+    }
+
+    fn connection_options(&self) -> SshConnectionOptions {
+        self.connection_options.clone()
+    }
+}
+
+async fn run_docker(args: &[&str]) -> Result<String> {
+    log::debug!("docker {:?}", args);
+    let output = util::command::new_smol_command("docker")
+        .args(args)
+        .output()
+        .await
+        .context("failed to run docker. Is it installed and on your PATH?")?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(anyhow!(
+            "docker {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// The parts of a `devcontainer.json` file that Zed understands.
+///
+/// See https://containers.dev/implementors/json_reference/ for the full format.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevContainerConfig {
+    pub name: Option<String>,
+    /// The image to run, if the container isn't built from a Dockerfile.
+    pub image: Option<String>,
+    pub build: Option<DevContainerBuild>,
+    /// Where the project is mounted in the container. Defaults to `/workspaces/<folder name>`.
+    pub workspace_folder: Option<String>,
+    #[serde(default)]
+    pub container_env: HashMap<String, String>,
+    /// The user to run the server, terminals and tasks as.
+    pub remote_user: Option<String>,
+    /// Extra arguments to pass to `docker run`.
+    #[serde(default)]
+    pub run_args: Vec<String>,
+    /// A command to run once, after the container has been created.
+    pub post_create_command: Option<DevContainerCommand>,
+    pub docker_compose_file: Option<serde_json::Value>,
+}
+
+impl DevContainerConfig {
+    /// Parses the contents of a `devcontainer.json` file, which may contain comments
+    /// and trailing commas.
+    pub fn parse(contents: &str) -> Result<Self> {
+        let config: Self = serde_json_lenient::from_str(contents)?;
+        if config.docker_compose_file.is_some() {
+            anyhow::bail!("dev containers that use Docker Compose are not supported yet");
+        }
+        if config.image.is_none() && config.build.is_none() {
+            anyhow::bail!("devcontainer.json must specify either `image` or `build`");
+        }
+        Ok(config)
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevContainerBuild {
+    /// The Dockerfile to build, relative to `devcontainer.json`.
+    pub dockerfile: Option<String>,
+    /// The build context, relative to `devcontainer.json`. Defaults to its directory.
+    pub context: Option<String>,
+    #[serde(default)]
+    pub args: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum DevContainerCommand {
+    /// A command that is run through a shell.
+    Shell(String),
+    /// A program and its arguments, run without a shell.
+    Args(Vec<String>),
+}
+
+/// A development container for a local folder, described by its `devcontainer.json`.
+#[derive(Debug, Clone)]
+pub struct DevContainer {
+    pub local_folder: PathBuf,
+    pub config_path: PathBuf,
+    pub config: DevContainerConfig,
+}
+
+impl DevContainer {
+    /// Returns the `devcontainer.json` file for the given folder, if it has one.
+    pub fn config_path(local_folder: &Path) -> Option<PathBuf> {
+        [
+            local_folder.join(".devcontainer").join("devcontainer.json"),
+            local_folder.join(".devcontainer.json"),
+        ]
+        .into_iter()
+        .find(|path| path.is_file())
+    }
+
+    pub async fn load(local_folder: &Path) -> Result<Self> {
+        let config_path = Self::config_path(local_folder)
+            .ok_or_else(|| anyhow!("no devcontainer.json found in {}", local_folder.display()))?;
+        let contents = smol::fs::read_to_string(&config_path).await?;
+        let config = DevContainerConfig::parse(&contents)
+            .with_context(|| format!("failed to parse {}", config_path.display()))?;
+        Ok(Self {
+            local_folder: local_folder.to_path_buf(),
+            config_path,
+            config,
+        })
+    }
+
+    /// The name of the container, which is stable for a given local folder.
+    pub fn container_name(&self) -> String {
+        let folder_name = self
+            .local_folder
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect::<String>();
+        let hash = Sha256::digest(self.local_folder.to_string_lossy().as_bytes())
+            .iter()
+            .take(4)
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        match folder_name.trim_matches('-') {
+            "" => format!("zed-{hash}"),
+            folder_name => format!("zed-{folder_name}-{hash}"),
+        }
+    }
+
+    pub fn display_name(&self) -> String {
+        self.config.name.clone().unwrap_or_else(|| {
+            self.local_folder
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| self.container_name())
+        })
+    }
+
+    /// The folder that the project is mounted at inside the container.
+    pub fn workspace_folder(&self) -> String {
+        self.config.workspace_folder.clone().unwrap_or_else(|| {
+            format!(
+                "/workspaces/{}",
+                self.local_folder
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            )
+        })
+    }
+
+    pub fn connection_options(&self) -> SshConnectionOptions {
+        SshConnectionOptions {
+            host: self.container_name(),
+            username: self.config.remote_user.clone(),
+            nickname: Some(self.display_name()),
+            docker: true,
+            ..Default::default()
+        }
+    }
+
+    /// Builds or pulls the image the container runs, returning its name.
+    pub async fn build(&self) -> Result<String> {
+        let Some(build) = &self.config.build else {
+            let image = self.config.image.clone().unwrap_or_default();
+            run_docker(&["pull", &image]).await?;
+            return Ok(image);
+        };
+
+        let config_dir = self.config_path.parent().unwrap_or(&self.local_folder);
+        let context = config_dir.join(build.context.as_deref().unwrap_or("."));
+        let dockerfile = config_dir.join(build.dockerfile.as_deref().unwrap_or("Dockerfile"));
+        let image = format!("{}-image", self.container_name());
+
+        let mut args = vec![
+            "build".to_string(),
+            "--tag".to_string(),
+            image.clone(),
+            "--file".to_string(),
+            dockerfile.to_string_lossy().to_string(),
+        ];
+        for (key, value) in &build.args {
+            args.push("--build-arg".to_string());
+            args.push(format!("{key}={value}"));
+        }
+        args.push(context.to_string_lossy().to_string());
+
+        run_docker(&args.iter().map(String::as_str).collect::<Vec<_>>()).await?;
+        Ok(image)
+    }
+
+    /// Starts the container, building and creating it first if it doesn't exist yet.
+    pub async fn start(&self) -> Result<()> {
+        let container_name = self.container_name();
+        if run_docker(&["container", "inspect", &container_name])
+            .await
+            .is_ok()
+        {
+            run_docker(&["start", &container_name]).await?;
+            return Ok(());
+        }
+
+        let image = self.build().await?;
+        let workspace_folder = self.workspace_folder();
+        let mut args = vec![
+            "run".to_string(),
+            "--detach".to_string(),
+            "--name".to_string(),
+            container_name.clone(),
+            "--label".to_string(),
+            format!("dev.zed.local_folder={}", self.local_folder.display()),
+            "--volume".to_string(),
+            format!("{}:{}", self.local_folder.display(), workspace_folder),
+        ];
+        for (key, value) in &self.config.container_env {
+            args.push("--env".to_string());
+            args.push(format!("{key}={value}"));
+        }
+        args.extend(self.config.run_args.iter().cloned());
+        args.extend([
+            image,
+            "sh".to_string(),
+            "-c".to_string(),
+            // Keep the container running until it is stopped.
+            "trap 'exit 0' TERM; while sleep 1000 & wait $!; do :; done".to_string(),
+        ]);
+        run_docker(&args.iter().map(String::as_str).collect::<Vec<_>>()).await?;
+
+        if let Some(command) = &self.config.post_create_command {
+            let mut args = vec!["exec", "--workdir", workspace_folder.as_str()];
+            if let Some(user) = &self.config.remote_user {
+                args.extend(["--user", user.as_str()]);
+            }
+            args.push(&container_name);
+            match command {
+                DevContainerCommand::Shell(command) => args.extend(["sh", "-c", command.as_str()]),
+                DevContainerCommand::Args(command) => {
+                    args.extend(command.iter().map(String::as_str))
+                }
+            }
+            run_docker(&args)
+                .await
+                .context("postCreateCommand failed")?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        run_docker(&["stop", &self.container_name()]).await?;
+        Ok(())
+    }
+
+    /// Removes the container and creates it again from a freshly built image.
+    pub async fn rebuild(&self) -> Result<()> {
+        let container_name = self.container_name();
+        if run_docker(&["container", "inspect", &container_name])
+            .await
+            .is_ok()
+        {
+            run_docker(&["rm", "--force", &container_name]).await?;
+        }
+        self.start().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dev_container_config() {
+        let config = DevContainerConfig::parse(
+            r#"{
+                // Comments and trailing commas are allowed.
+                "name": "Rust",
+                "image": "mcr.microsoft.com/devcontainers/rust:1",
+                "workspaceFolder": "/src",
+                "containerEnv": { "RUST_LOG": "info" },
+                "remoteUser": "vscode",
+                "runArgs": ["--cap-add=SYS_PTRACE"],
+                "postCreateCommand": "cargo fetch",
+                "customizations": { "vscode": { "extensions": [] } },
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.name.as_deref(), Some("Rust"));
+        assert_eq!(
+            config.image.as_deref(),
+            Some("mcr.microsoft.com/devcontainers/rust:1")
+        );
+        assert!(config.build.is_none());
+        assert_eq!(config.workspace_folder.as_deref(), Some("/src"));
+        assert_eq!(
+            config.container_env.get("RUST_LOG").map(String::as_str),
+            Some("info")
+        );
+        assert_eq!(config.remote_user.as_deref(), Some("vscode"));
+        assert_eq!(config.run_args, ["--cap-add=SYS_PTRACE"]);
+        assert!(matches!(
+            config.post_create_command,
+            Some(DevContainerCommand::Shell(command)) if command == "cargo fetch"
+        ));
+
+        let config = DevContainerConfig::parse(
+            r#"{
+                "build": {
+                    "dockerfile": "Dockerfile.dev",
+                    "context": "..",
+                    "args": { "VARIANT": "bookworm" }
+                },
+                "postCreateCommand": ["npm", "install"]
+            }"#,
+        )
+        .unwrap();
+        let build = config.build.unwrap();
+        assert_eq!(build.dockerfile.as_deref(), Some("Dockerfile.dev"));
+        assert_eq!(build.context.as_deref(), Some(".."));
+        assert_eq!(
+            build.args.get("VARIANT").map(String::as_str),
+            Some("bookworm")
+        );
+        assert!(config.image.is_none());
+        assert!(config.container_env.is_empty());
+        assert!(config.run_args.is_empty());
+        assert!(matches!(
+            config.post_create_command,
+            Some(DevContainerCommand::Args(args)) if args == ["npm", "install"]
+        ));
+
+        // Configurations that can't be started are rejected.
+        assert!(DevContainerConfig::parse(r#"{ "name": "Empty" }"#).is_err());
+        assert!(DevContainerConfig::parse(
+            r#"{ "dockerComposeFile": "docker-compose.yml", "service": "app" }"#
+        )
+        .is_err());
+        assert!(DevContainerConfig::parse("not json").is_err());
+    }
+
+    #[test]
+    fn test_dev_container_names() {
+        let dev_container = |local_folder: &str, name: Option<&str>| DevContainer {
+            local_folder: PathBuf::from(local_folder),
+            config_path: PathBuf::from(local_folder).join(".devcontainer.json"),
+            config: DevContainerConfig {
+                name: name.map(ToString::to_string),
+                image: Some("alpine".to_string()),
+                ..Default::default()
+            },
+        };
+
+        // Names are derived from the folder name and a hash of its full path, so that
+        // folders with the same name get different containers.
+        let container = dev_container("/home/user/My Project", None);
+        assert_eq!(container.container_name(), "zed-my-project-78ce1d89");
+        assert_eq!(container.display_name(), "My Project");
+        assert_eq!(container.workspace_folder(), "/workspaces/My Project");
+        assert_eq!(
+            dev_container("/tmp/My Project", None).container_name(),
+            "zed-my-project-dab36f6a"
+        );
+        assert_eq!(dev_container("/", None).container_name(), "zed-8a5edab2");
+
+        let container = dev_container("/home/user/My Project", Some("Rust"));
+        assert_eq!(container.container_name(), "zed-my-project-78ce1d89");
+        assert_eq!(container.display_name(), "Rust");
+
+        let options = container.connection_options();
+        assert_eq!(options.host, "zed-my-project-78ce1d89");
+        assert_eq!(options.nickname.as_deref(), Some("Rust"));
+        assert!(options.docker);
+    }
+}
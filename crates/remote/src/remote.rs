@@ -1,8 +1,10 @@
+mod docker;
 pub mod json_log;
 pub mod protocol;
 pub mod proxy;
 pub mod ssh_session;
 
+pub use docker::{DevContainer, DevContainerConfig};
 pub use ssh_session::{
     ConnectionState, SshClientDelegate, SshConnectionOptions, SshPlatform, SshRemoteClient,
     SshRemoteEvent,
@@ -1,4 +1,5 @@
 use crate::{
+    docker::DockerRemoteConnection,
     json_log::LogRecord,
     protocol::{
         message_len_from_buffer, read_message_with_len, write_message, MessageId, MESSAGE_LEN_SIZE,
@@ -37,7 +38,7 @@ use smol::{
 };
 use std::{
     any::TypeId,
-    collections::VecDeque,
+    collections::{BTreeSet, VecDeque},
     fmt, iter,
     ops::ControlFlow,
     path::{Path, PathBuf},
@@ -71,6 +72,39 @@ pub struct SshPortForwardOption {
     pub remote_port: u16,
 }
 
+impl SshPortForwardOption {
+    /// Returns the forward in the form accepted by `ssh -L`.
+    fn local_forward_spec(&self) -> String {
+        format!(
+            "{}:{}:{}:{}",
+            self.local_host.as_deref().unwrap_or("localhost"),
+            self.local_port,
+            self.remote_host.as_deref().unwrap_or("localhost"),
+            self.remote_port
+        )
+    }
+}
+
+/// Parses the contents of `/proc/net/tcp` and `/proc/net/tcp6`, returning the ports that
+/// are being listened on.
+pub(crate) fn parse_listening_ports(proc_net_tcp: &str) -> BTreeSet<u16> {
+    const TCP_LISTEN: &str = "0A";
+
+    proc_net_tcp
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let local_address = fields.nth(1)?;
+            let state = fields.nth(1)?;
+            if state != TCP_LISTEN {
+                return None;
+            }
+            let (_, port) = local_address.rsplit_once(':')?;
+            u16::from_str_radix(port, 16).ok()
+        })
+        .collect()
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct SshConnectionOptions {
     pub host: String,
@@ -79,6 +113,13 @@ pub struct SshConnectionOptions {
     pub password: Option<String>,
     pub args: Option<Vec<String>>,
     pub port_forwards: Option<Vec<SshPortForwardOption>>,
+    /// A host to connect through, as accepted by `ssh -J`.
+    pub jump_host: Option<String>,
+    /// Whether to forward the local SSH agent to the server.
+    pub forward_agent: bool,
+    /// Whether `host` is the name of a local Docker container to run the server in,
+    /// rather than a host to connect to over SSH.
+    pub docker: bool,
 
     pub nickname: Option<String>,
     pub upload_binary_over_ssh: bool,
@@ -134,6 +175,10 @@ fn parse_port_forward_spec(spec: &str) -> Result<SshPortForwardOption> {
 
 impl SshConnectionOptions {
     pub fn parse_command_line(input: &str) -> Result<Self> {
+        if let Some(container) = input.trim().strip_prefix("docker://") {
+            return Self::parse_docker_url(container);
+        }
+
         let input = input.trim_start_matches("ssh ");
         let mut hostname: Option<String> = None;
         let mut username: Option<String> = None;
@@ -231,13 +276,35 @@ impl SshConnectionOptions {
             port_forwards,
             args: Some(args),
             password: None,
+            jump_host: None,
+            forward_agent: false,
+            docker: false,
             nickname: None,
             upload_binary_over_ssh: false,
         })
     }
 
+    /// Parses a container reference of the form `[user@]container`.
+    fn parse_docker_url(input: &str) -> Result<Self> {
+        let input = input.trim_end_matches('/');
+        let (username, container) = match input.rsplit_once('@') {
+            Some((username, container)) => (Some(username.to_string()), container),
+            None => (None, input),
+        };
+        if container.is_empty() || container.contains(char::is_whitespace) {
+            anyhow::bail!("invalid container name: {:?}", container);
+        }
+
+        Ok(Self {
+            host: container.to_string(),
+            username,
+            docker: true,
+            ..Default::default()
+        })
+    }
+
     pub fn ssh_url(&self) -> String {
-        let mut result = String::from("ssh://");
+        let mut result = String::from(if self.docker { "docker://" } else { "ssh://" });
         if let Some(username) = &self.username {
             // Username might be: username1@username2@ip2
             let username = urlencoding::encode(username);
@@ -255,22 +322,20 @@ impl SshConnectionOptions {
     pub fn additional_args(&self) -> Vec<String> {
         let mut args = self.args.iter().flatten().cloned().collect::<Vec<String>>();
 
-        if let Some(forwards) = &self.port_forwards {
-            args.extend(forwards.iter().map(|pf| {
-                let local_host = match &pf.local_host {
-                    Some(host) => host,
-                    None => "localhost",
-                };
-                let remote_host = match &pf.remote_host {
-                    Some(host) => host,
-                    None => "localhost",
-                };
+        if let Some(jump_host) = &self.jump_host {
+            args.push("-J".to_string());
+            args.push(jump_host.clone());
+        }
+        if self.forward_agent {
+            args.push("-A".to_string());
+        }
 
-                format!(
-                    "-L{}:{}:{}:{}",
-                    local_host, pf.local_port, remote_host, pf.remote_port
-                )
-            }));
+        if let Some(forwards) = &self.port_forwards {
+            args.extend(
+                forwards
+                    .iter()
+                    .map(|forward| format!("-L{}", forward.local_forward_spec())),
+            );
         }
 
         args
@@ -387,6 +452,31 @@ impl SshSocket {
             .arg(format!("ControlPath={}", self.socket_path.display()))
     }
 
+    /// Asks the master connection to start or stop a port forward, without restarting it.
+    async fn control_port_forward(
+        &self,
+        operation: &str,
+        forward: &SshPortForwardOption,
+    ) -> Result<()> {
+        let mut command = util::command::new_smol_command("ssh");
+        let output = self
+            .ssh_options(&mut command)
+            .args(["-O", operation])
+            .arg(format!("-L{}", forward.local_forward_spec()))
+            .arg(self.connection_options.ssh_url())
+            .output()
+            .await?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "failed to {operation} port {}: {}",
+                forward.remote_port,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+
     fn ssh_args(&self) -> Vec<String> {
         vec![
             "-o".to_string(),
@@ -402,7 +492,20 @@ const MAX_MISSED_HEARTBEATS: usize = 5;
 const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
 const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(5);
 
-const MAX_RECONNECT_ATTEMPTS: usize = 3;
+const MAX_RECONNECT_ATTEMPTS: usize = 5;
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+/// Returns how long to wait before the given reconnect attempt. The first attempt is made
+/// immediately, and the delay doubles with every attempt after that.
+fn reconnect_delay(attempt: usize) -> Duration {
+    if attempt <= 1 {
+        return Duration::ZERO;
+    }
+    INITIAL_RECONNECT_DELAY
+        .saturating_mul(1 << (attempt - 2).min(16) as u32)
+        .min(MAX_RECONNECT_DELAY)
+}
 
 enum State {
     Connecting,
@@ -564,6 +667,7 @@ pub struct SshRemoteClient {
     unique_identifier: String,
     connection_options: SshConnectionOptions,
     state: Arc<Mutex<Option<State>>>,
+    latency: Option<Duration>,
 }
 
 #[derive(Debug)]
@@ -628,6 +732,7 @@ impl SshRemoteClient {
                     unique_identifier: unique_identifier.clone(),
                     connection_options: connection_options.clone(),
                     state: Arc::new(Mutex::new(Some(State::Connecting))),
+                    latency: None,
                 })?;
 
                 let ssh_connection = cx
@@ -651,14 +756,17 @@ impl SshRemoteClient {
 
                 let multiplex_task = Self::monitor(this.downgrade(), io_task, &cx);
 
+                let ping_start = Instant::now();
                 if let Err(error) = client.ping(HEARTBEAT_TIMEOUT).await {
                     log::error!("failed to establish connection: {}", error);
                     return Err(error);
                 }
+                let latency = ping_start.elapsed();
 
                 let heartbeat_task = Self::heartbeat(this.downgrade(), connection_activity_rx, cx);
 
                 this.update(cx, |this, _| {
+                    this.latency = Some(latency);
                     *this.state.lock() = Some(State::Connected {
                         ssh_connection,
                         delegate,
@@ -779,11 +887,20 @@ impl SshRemoteClient {
 
         self.set_state(State::Reconnecting, cx);
 
-        log::info!("Trying to reconnect to ssh server... Attempt {}", attempts);
+        let delay = reconnect_delay(attempts);
+        log::info!(
+            "Trying to reconnect to ssh server in {:?}... Attempt {}",
+            delay,
+            attempts
+        );
 
         let unique_identifier = self.unique_identifier.clone();
         let client = self.client.clone();
         let reconnect_task = cx.spawn(async move |this, cx| {
+            if !delay.is_zero() {
+                cx.background_executor().timer(delay).await;
+            }
+
             macro_rules! failed {
                 ($error:expr, $attempts:expr, $ssh_connection:expr, $delegate:expr) => {
                     return State::ReconnectFailed {
@@ -931,15 +1048,23 @@ impl SshRemoteClient {
                         _ = keepalive_timer => {
                             log::debug!("Sending heartbeat to server...");
 
+                            let ping_start = Instant::now();
                             let result = select_biased! {
                                 _ = connection_activity_rx.next().fuse() => {
-                                    Ok(())
+                                    Ok(None)
                                 }
                                 ping_result = client.ping(HEARTBEAT_TIMEOUT).fuse() => {
-                                    ping_result
+                                    ping_result.map(|_| Some(ping_start.elapsed()))
                                 }
                             };
 
+                            if let Ok(Some(latency)) = result {
+                                this.update(cx, |this, cx| {
+                                    this.latency = Some(latency);
+                                    cx.notify();
+                                })?;
+                            }
+
                             if result.is_err() {
                                 missed_heartbeats += 1;
                                 log::warn!(
@@ -1088,6 +1213,35 @@ impl SshRemoteClient {
         connection.upload_directory(src_path, dest_path, cx)
     }
 
+    /// Forwards a local port to a port on the server, or on a host reachable from it.
+    ///
+    /// Forwards are added to the running connection, so they are lost if it has to be
+    /// re-established.
+    pub fn forward_port(&self, forward: SshPortForwardOption, cx: &App) -> Task<Result<()>> {
+        let state = self.state.lock();
+        let Some(connection) = state.as_ref().and_then(|state| state.ssh_connection()) else {
+            return Task::ready(Err(anyhow!("no ssh connection")));
+        };
+        connection.forward_port(forward, cx)
+    }
+
+    pub fn cancel_port_forward(&self, forward: SshPortForwardOption, cx: &App) -> Task<Result<()>> {
+        let state = self.state.lock();
+        let Some(connection) = state.as_ref().and_then(|state| state.ssh_connection()) else {
+            return Task::ready(Err(anyhow!("no ssh connection")));
+        };
+        connection.cancel_port_forward(forward, cx)
+    }
+
+    /// Returns the TCP ports that are being listened on by processes on the server.
+    pub fn listening_ports(&self, cx: &App) -> Task<Result<BTreeSet<u16>>> {
+        let state = self.state.lock();
+        let Some(connection) = state.as_ref().and_then(|state| state.ssh_connection()) else {
+            return Task::ready(Err(anyhow!("no ssh connection")));
+        };
+        connection.listening_ports(cx)
+    }
+
     pub fn proto_client(&self) -> AnyProtoClient {
         self.client.clone().into()
     }
@@ -1112,6 +1266,11 @@ impl SshRemoteClient {
         self.connection_state() == ConnectionState::Disconnected
     }
 
+    /// The round-trip time of the most recent heartbeat, if the server has responded to one.
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub fn simulate_disconnect(&self, client_cx: &mut App) -> Task<()> {
         let opts = self.connection_options();
@@ -1239,9 +1398,15 @@ impl ConnectionPool {
                 let opts = opts.clone();
                 let delegate = delegate.clone();
                 async move |cx| {
-                    let connection = SshRemoteConnection::new(opts.clone(), delegate, cx)
-                        .await
-                        .map(|connection| Arc::new(connection) as Arc<dyn RemoteConnection>);
+                    let connection = if opts.docker {
+                        DockerRemoteConnection::new(opts.clone(), delegate, cx)
+                            .await
+                            .map(|connection| Arc::new(connection) as Arc<dyn RemoteConnection>)
+                    } else {
+                        SshRemoteConnection::new(opts.clone(), delegate, cx)
+                            .await
+                            .map(|connection| Arc::new(connection) as Arc<dyn RemoteConnection>)
+                    };
 
                     cx.update_global(|pool: &mut Self, _| {
                         debug_assert!(matches!(
@@ -1278,8 +1443,63 @@ impl From<SshRemoteClient> for AnyProtoClient {
     }
 }
 
+/// Parses the output of `uname -sm` into the platform to download the server for.
+pub(crate) fn parse_platform(uname: &str) -> Result<SshPlatform> {
+    let Some((os, arch)) = uname.split_once(" ") else {
+        Err(anyhow!("unknown uname: {uname:?}"))?
+    };
+
+    let os = match os.trim() {
+        "Darwin" => "macos",
+        "Linux" => "linux",
+        _ => Err(anyhow!(
+            "Prebuilt remote servers are not yet available for {os:?}. See https://zed.dev/docs/remote-development"
+        ))?,
+    };
+    // exclude armv5,6,7 as they are 32-bit.
+    let arch = if arch.starts_with("armv8")
+        || arch.starts_with("armv9")
+        || arch.starts_with("arm64")
+        || arch.starts_with("aarch64")
+    {
+        "aarch64"
+    } else if arch.starts_with("x86") {
+        "x86_64"
+    } else {
+        Err(anyhow!(
+            "Prebuilt remote servers are not yet available for {arch:?}. See https://zed.dev/docs/remote-development"
+        ))?
+    };
+
+    Ok(SshPlatform { os, arch })
+}
+
+/// Returns the path, relative to the home directory, that the server binary for this
+/// version of Zed is installed to.
+pub(crate) fn remote_server_binary_path(
+    release_channel: ReleaseChannel,
+    version: SemanticVersion,
+    commit: Option<AppCommitSha>,
+) -> PathBuf {
+    let version_str = match release_channel {
+        ReleaseChannel::Nightly => {
+            let commit = commit.map(|s| s.0.to_string()).unwrap_or_default();
+
+            format!("{}-{}", version, commit)
+        }
+        ReleaseChannel::Dev => "build".to_string(),
+        _ => version.to_string(),
+    };
+    let binary_name = format!(
+        "zed-remote-server-{}-{}",
+        release_channel.dev_name(),
+        version_str
+    );
+    paths::remote_server_dir_relative().join(binary_name)
+}
+
 #[async_trait(?Send)]
-trait RemoteConnection: Send + Sync {
+pub(crate) trait RemoteConnection: Send + Sync {
     fn start_proxy(
         &self,
         unique_identifier: String,
@@ -1292,6 +1512,9 @@ trait RemoteConnection: Send + Sync {
     ) -> Task<Result<i32>>;
     fn upload_directory(&self, src_path: PathBuf, dest_path: PathBuf, cx: &App)
         -> Task<Result<()>>;
+    fn forward_port(&self, forward: SshPortForwardOption, cx: &App) -> Task<Result<()>>;
+    fn cancel_port_forward(&self, forward: SshPortForwardOption, cx: &App) -> Task<Result<()>>;
+    fn listening_ports(&self, cx: &App) -> Task<Result<BTreeSet<u16>>>;
     async fn kill(&self) -> Result<()>;
     fn has_been_killed(&self) -> bool;
     fn ssh_args(&self) -> Vec<String>;
@@ -1301,7 +1524,7 @@ trait RemoteConnection: Send + Sync {
     fn simulate_disconnect(&self, _: &AsyncApp) {}
 }
 
-struct SshRemoteConnection {
+pub(crate) struct SshRemoteConnection {
     socket: SshSocket,
     master_process: Mutex<Option<Child>>,
     remote_binary_path: Option<PathBuf>,
@@ -1374,6 +1597,29 @@ impl RemoteConnection for SshRemoteConnection {
         })
     }
 
+    fn forward_port(&self, forward: SshPortForwardOption, cx: &App) -> Task<Result<()>> {
+        let socket = self.socket.clone();
+        cx.background_spawn(async move { socket.control_port_forward("forward", &forward).await })
+    }
+
+    fn cancel_port_forward(&self, forward: SshPortForwardOption, cx: &App) -> Task<Result<()>> {
+        let socket = self.socket.clone();
+        cx.background_spawn(async move { socket.control_port_forward("cancel", &forward).await })
+    }
+
+    fn listening_ports(&self, cx: &App) -> Task<Result<BTreeSet<u16>>> {
+        let socket = self.socket.clone();
+        cx.background_spawn(async move {
+            let output = socket
+                .run_command(
+                    "sh",
+                    &["-c", "cat /proc/net/tcp /proc/net/tcp6 2>/dev/null || true"],
+                )
+                .await?;
+            Ok(parse_listening_ports(&output))
+        })
+    }
+
     fn start_proxy(
         &self,
         unique_identifier: String,
@@ -1563,36 +1809,10 @@ impl SshRemoteConnection {
 
     async fn platform(&self) -> Result<SshPlatform> {
         let uname = self.socket.run_command("sh", &["-c", "uname -sm"]).await?;
-        let Some((os, arch)) = uname.split_once(" ") else {
-            Err(anyhow!("unknown uname: {uname:?}"))?
-        };
-
-        let os = match os.trim() {
-            "Darwin" => "macos",
-            "Linux" => "linux",
-            _ => Err(anyhow!(
-                "Prebuilt remote servers are not yet available for {os:?}. See https://zed.dev/docs/remote-development"
-            ))?,
-        };
-        // exclude armv5,6,7 as they are 32-bit.
-        let arch = if arch.starts_with("armv8")
-            || arch.starts_with("armv9")
-            || arch.starts_with("arm64")
-            || arch.starts_with("aarch64")
-        {
-            "aarch64"
-        } else if arch.starts_with("x86") {
-            "x86_64"
-        } else {
-            Err(anyhow!(
-                "Prebuilt remote servers are not yet available for {arch:?}. See https://zed.dev/docs/remote-development"
-            ))?
-        };
-
-        Ok(SshPlatform { os, arch })
+        parse_platform(&uname)
     }
 
-    fn multiplex(
+    pub(crate) fn multiplex(
         mut ssh_proxy_process: Child,
         incoming_tx: UnboundedSender<Envelope>,
         mut outgoing_rx: UnboundedReceiver<Envelope>,
@@ -1703,21 +1923,7 @@ impl SshRemoteConnection {
         commit: Option<AppCommitSha>,
         cx: &mut AsyncApp,
     ) -> Result<PathBuf> {
-        let version_str = match release_channel {
-            ReleaseChannel::Nightly => {
-                let commit = commit.map(|s| s.0.to_string()).unwrap_or_default();
-
-                format!("{}-{}", version, commit)
-            }
-            ReleaseChannel::Dev => "build".to_string(),
-            _ => version.to_string(),
-        };
-        let binary_name = format!(
-            "zed-remote-server-{}-{}",
-            release_channel.dev_name(),
-            version_str
-        );
-        let dst_path = paths::remote_server_dir_relative().join(binary_name);
+        let dst_path = remote_server_binary_path(release_channel, version, commit);
         let tmp_path_gz = PathBuf::from(format!(
             "{}-download-{}.gz",
             dst_path.to_string_lossy(),
@@ -1726,9 +1932,7 @@ impl SshRemoteConnection {
 
         #[cfg(debug_assertions)]
         if std::env::var("ZED_BUILD_REMOTE_SERVER").is_ok() {
-            let src_path = self
-                .build_local(self.platform().await?, delegate, cx)
-                .await?;
+            let src_path = Self::build_local(self.platform().await?, delegate, cx).await?;
             self.upload_local_server_binary(&src_path, &tmp_path_gz, delegate, cx)
                 .await?;
             self.extract_server_binary(&dst_path, &tmp_path_gz, delegate, cx)
@@ -1950,9 +2154,10 @@ impl SshRemoteConnection {
         }
     }
 
+    /// Builds the remote server from source, cross-compiling it if the remote
+    /// platform differs from this one, and returns the path to the gzipped binary.
     #[cfg(debug_assertions)]
-    async fn build_local(
-        &self,
+    pub(crate) async fn build_local(
         platform: SshPlatform,
         delegate: &Arc<dyn SshClientDelegate>,
         cx: &mut AsyncApp,
@@ -2366,7 +2571,7 @@ impl ProtoClient for ChannelClient {
 
 #[cfg(any(test, feature = "test-support"))]
 mod fake {
-    use std::{path::PathBuf, sync::Arc};
+    use std::{collections::BTreeSet, path::PathBuf, sync::Arc};
 
     use anyhow::Result;
     use async_trait::async_trait;
@@ -2383,6 +2588,7 @@ mod fake {
 
     use super::{
         ChannelClient, RemoteConnection, SshClientDelegate, SshConnectionOptions, SshPlatform,
+        SshPortForwardOption,
     };
 
     pub(super) struct FakeRemoteConnection {
@@ -2430,6 +2636,22 @@ mod fake {
             unreachable!()
         }
 
+        fn forward_port(&self, _forward: SshPortForwardOption, _cx: &App) -> Task<Result<()>> {
+            Task::ready(Ok(()))
+        }
+
+        fn cancel_port_forward(
+            &self,
+            _forward: SshPortForwardOption,
+            _cx: &App,
+        ) -> Task<Result<()>> {
+            Task::ready(Ok(()))
+        }
+
+        fn listening_ports(&self, _cx: &App) -> Task<Result<BTreeSet<u16>>> {
+            Task::ready(Ok(BTreeSet::default()))
+        }
+
         fn connection_options(&self) -> SshConnectionOptions {
             self.connection_options.clone()
         }
@@ -11,7 +11,7 @@ use futures::{select, select_biased, AsyncRead, AsyncWrite, AsyncWriteExt, Futur
 use git::GitHostingProviderRegistry;
 use gpui::{App, AppContext as _, Context, Entity, SemanticVersion, UpdateGlobal as _};
 use gpui_tokio::Tokio;
-use http_client::{read_proxy_from_env, Uri};
+use http_client::ProxyConfig;
 use language::LanguageRegistry;
 use node_runtime::{NodeBinaryOptions, NodeRuntime};
 use paths::logs_dir;
@@ -449,13 +449,13 @@ pub fn execute_run(
             let fs = Arc::new(RealFs::new(None, cx.background_executor().clone()));
             let node_settings_rx = initialize_settings(session.clone(), fs.clone(), cx);
 
-            let proxy_url = read_proxy_settings(cx);
+            let proxy_config = read_proxy_settings(cx);
 
             let http_client = {
                 let _guard = Tokio::handle(cx).enter();
                 Arc::new(
-                    ReqwestClient::proxy_and_user_agent(
-                        proxy_url,
+                    ReqwestClient::proxy_config_and_user_agent(
+                        proxy_config,
                         &format!(
                             "Zed-Server/{} ({}; {})",
                             env!("CARGO_PKG_VERSION"),
@@ -848,18 +848,10 @@ pub fn handle_settings_file_changes(
     .detach();
 }
 
-fn read_proxy_settings(cx: &mut Context<HeadlessProject>) -> Option<Uri> {
-    let proxy_str = ProxySettings::get_global(cx).proxy.to_owned();
-    let proxy_url = proxy_str
-        .as_ref()
-        .and_then(|input: &String| {
-            input
-                .parse::<Uri>()
-                .inspect_err(|e| log::error!("Error parsing proxy settings: {}", e))
-                .ok()
-        })
-        .or_else(read_proxy_from_env);
-    proxy_url
+fn read_proxy_settings(cx: &mut Context<HeadlessProject>) -> ProxyConfig {
+    let proxy_settings = ProxySettings::get_global(cx);
+    http_client_tls::set_custom_ca_certificates(&proxy_settings.ca_certificates).log_err();
+    proxy_settings.proxy_config()
 }
 
 fn daemonize() -> Result<ControlFlow<()>> {
@@ -337,6 +337,13 @@ impl ExecutionView {
         }
     }
 
+    /// Whether the execution raised an error.
+    pub fn has_error(&self) -> bool {
+        self.outputs
+            .iter()
+            .any(|output| matches!(output, Output::ErrorOutput(_)))
+    }
+
     /// Accept a Jupyter message belonging to this execution
     pub fn push_message(
         &mut self,
@@ -4,9 +4,11 @@ pub mod kernels;
 pub mod notebook;
 mod outputs;
 mod repl_editor;
+mod repl_panel;
 mod repl_sessions_ui;
 mod repl_store;
 mod session;
+mod variables;
 
 use std::{sync::Arc, time::Duration};
 
@@ -19,8 +21,10 @@ use settings::Settings as _;
 pub use crate::jupyter_settings::JupyterSettings;
 pub use crate::kernels::{Kernel, KernelSpecification, KernelStatus};
 pub use crate::repl_editor::*;
+pub use crate::repl_panel::{ClearOutputHistory, RefreshVariables, ReplPanel, ToggleReplPanel};
 pub use crate::repl_sessions_ui::{
-    ClearOutputs, Interrupt, ReplSessionsPage, Restart, Run, Sessions, Shutdown,
+    ClearOutputs, Interrupt, ReplSessionsPage, Restart, RestartAndRunAll, Run, RunAll,
+    RunCellsAbove, RunCellsBelow, Sessions, Shutdown,
 };
 use crate::repl_store::ReplStore;
 pub use crate::session::Session;
@@ -32,6 +36,7 @@ pub fn init(fs: Arc<dyn Fs>, cx: &mut App) {
     JupyterSettings::register(cx);
     ::editor::init_settings(cx);
     repl_sessions_ui::init(cx);
+    repl_panel::init(cx);
     ReplStore::init(fs, cx);
 }
 
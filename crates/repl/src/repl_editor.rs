@@ -55,8 +55,8 @@ pub fn assign_kernelspec(
                 let store = store.clone();
                 move |_this, _session, event, cx| match event {
                     SessionEvent::Shutdown(shutdown_event) => {
-                        store.update(cx, |store, _cx| {
-                            store.remove_session(shutdown_event.entity_id());
+                        store.update(cx, |store, cx| {
+                            store.remove_session(shutdown_event.entity_id(), cx);
                         });
                     }
                 }
@@ -65,8 +65,8 @@ pub fn assign_kernelspec(
         })
         .ok();
 
-    store.update(cx, |store, _cx| {
-        store.insert_session(weak_editor.entity_id(), session.clone());
+    store.update(cx, |store, cx| {
+        store.insert_session(weak_editor.entity_id(), session.clone(), cx);
     });
 
     Ok(())
@@ -87,6 +87,74 @@ pub fn run(
     let selected_range = editor
         .update(cx, |editor, cx| editor.selections.newest_adjusted(cx))
         .range();
+    let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+        return Ok(());
+    };
+
+    let (runnable_ranges, next_cell_point) =
+        runnable_ranges(&buffer.read(cx).snapshot(), selected_range);
+
+    execute_ranges(
+        editor,
+        runnable_ranges,
+        next_cell_point,
+        move_down,
+        window,
+        cx,
+    )
+}
+
+/// Which cells of a file to run, relative to the cell containing the cursor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellsToRun {
+    All,
+    /// The cells before the one containing the cursor.
+    Above,
+    /// The cell containing the cursor and the ones after it.
+    Below,
+}
+
+pub fn run_cells(
+    editor: WeakEntity<Editor>,
+    cells: CellsToRun,
+    window: &mut Window,
+    cx: &mut App,
+) -> Result<()> {
+    let store = ReplStore::global(cx);
+    if !store.read(cx).is_enabled() {
+        return Ok(());
+    }
+
+    let editor = editor.upgrade().context("editor was dropped")?;
+    let cursor = editor
+        .update(cx, |editor, cx| editor.selections.newest_adjusted(cx))
+        .head();
+    let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+        return Ok(());
+    };
+
+    let runnable_ranges = cells_to_run(&buffer.read(cx).snapshot(), cursor.row, cells);
+    execute_ranges(editor, runnable_ranges, None, false, window, cx)
+}
+
+pub fn restart_and_run_all(
+    editor: WeakEntity<Editor>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Result<()> {
+    restart(editor.clone(), window, cx);
+    run_cells(editor, CellsToRun::All, window, cx)
+}
+
+fn execute_ranges(
+    editor: Entity<Editor>,
+    runnable_ranges: Vec<Range<Point>>,
+    next_cell_point: Option<Point>,
+    move_down: bool,
+    window: &mut Window,
+    cx: &mut App,
+) -> Result<()> {
+    let store = ReplStore::global(cx);
     let multibuffer = editor.read(cx).buffer().clone();
     let Some(buffer) = multibuffer.read(cx).as_singleton() else {
         return Ok(());
@@ -96,9 +164,6 @@ pub fn run(
         return Ok(());
     };
 
-    let (runnable_ranges, next_cell_point) =
-        runnable_ranges(&buffer.read(cx).snapshot(), selected_range);
-
     for runnable_range in runnable_ranges {
         let Some(language) = multibuffer.read(cx).language_at(runnable_range.start, cx) else {
             continue;
@@ -126,8 +191,8 @@ pub fn run(
                     let store = store.clone();
                     move |_this, _session, event, cx| match event {
                         SessionEvent::Shutdown(shutdown_event) => {
-                            store.update(cx, |store, _cx| {
-                                store.remove_session(shutdown_event.entity_id());
+                            store.update(cx, |store, cx| {
+                                store.remove_session(shutdown_event.entity_id(), cx);
                             });
                         }
                     }
@@ -135,8 +200,8 @@ pub fn run(
                 .detach();
             });
 
-            store.update(cx, |store, _cx| {
-                store.insert_session(editor.entity_id(), session.clone());
+            store.update(cx, |store, cx| {
+                store.insert_session(editor.entity_id(), session.clone(), cx);
             });
 
             session
@@ -346,6 +411,20 @@ fn cell_range(buffer: &BufferSnapshot, start_row: u32, end_row: u32) -> Range<Po
     Point::new(start_row, 0)..Point::new(snippet_end_row, buffer.line_len(snippet_end_row))
 }
 
+/// Returns the markers that start a Jupytext cell in the buffer's language, e.g. `# %%`.
+fn jupytext_prefixes(buffer: &BufferSnapshot) -> Vec<String> {
+    let Some(language) = buffer.language() else {
+        return Vec::new();
+    };
+
+    let default_scope = language.default_scope();
+    default_scope
+        .line_comment_prefixes()
+        .iter()
+        .map(|comment_prefix| format!("{comment_prefix}%%"))
+        .collect()
+}
+
 // Returns the ranges of the snippets in the buffer and the next point for moving the cursor to
 fn jupytext_cells(
     buffer: &BufferSnapshot,
@@ -353,21 +432,11 @@ fn jupytext_cells(
 ) -> (Vec<Range<Point>>, Option<Point>) {
     let mut current_row = range.start.row;
 
-    let Some(language) = buffer.language() else {
-        return (Vec::new(), None);
-    };
-
-    let default_scope = language.default_scope();
-    let comment_prefixes = default_scope.line_comment_prefixes();
-    if comment_prefixes.is_empty() {
+    let jupytext_prefixes = jupytext_prefixes(buffer);
+    if jupytext_prefixes.is_empty() {
         return (Vec::new(), None);
     }
 
-    let jupytext_prefixes = comment_prefixes
-        .iter()
-        .map(|comment_prefix| format!("{comment_prefix}%%"))
-        .collect::<Vec<_>>();
-
     let mut snippet_start_row = None;
     loop {
         if jupytext_prefixes
@@ -412,6 +481,58 @@ fn jupytext_cells(
     (snippets, None)
 }
 
+/// Returns the ranges of all the cells in the buffer: the code blocks of Markdown files, or
+/// the Jupytext cells of other files, where a file without cells is a single cell.
+fn all_cells(buffer: &BufferSnapshot) -> Vec<Range<Point>> {
+    let max_row = buffer.max_point().row;
+    if let Some(language) = buffer.language() {
+        if language.name() == "Markdown".into() {
+            return markdown_code_blocks(buffer, Point::zero()..buffer.max_point());
+        }
+    }
+
+    let jupytext_prefixes = jupytext_prefixes(buffer);
+    let cell_starts = (0..=max_row)
+        .filter(|row| {
+            jupytext_prefixes
+                .iter()
+                .any(|prefix| buffer.contains_str_at(Point::new(*row, 0), prefix))
+        })
+        .collect::<Vec<_>>();
+
+    let mut cells = Vec::new();
+    // Code before the first cell marker is a cell of its own.
+    let first_cell_start = cell_starts.first().copied().unwrap_or(max_row + 1);
+    if (0..first_cell_start).any(|row| !buffer.is_line_blank(row)) {
+        cells.push(cell_range(
+            buffer,
+            0,
+            first_cell_start.saturating_sub(1).min(max_row),
+        ));
+    }
+    for (ix, cell_start) in cell_starts.iter().enumerate() {
+        let cell_end = cell_starts.get(ix + 1).map_or(max_row, |next| next - 1);
+        cells.push(cell_range(buffer, *cell_start, cell_end));
+    }
+    cells
+}
+
+fn cells_to_run(buffer: &BufferSnapshot, cursor_row: u32, cells: CellsToRun) -> Vec<Range<Point>> {
+    let mut all_cells = all_cells(buffer);
+    let current_cell = all_cells
+        .iter()
+        .rposition(|cell| cell.start.row <= cursor_row);
+
+    match cells {
+        CellsToRun::All => all_cells,
+        CellsToRun::Above => {
+            all_cells.truncate(current_cell.unwrap_or(0));
+            all_cells
+        }
+        CellsToRun::Below => all_cells.split_off(current_cell.unwrap_or(0)),
+    }
+}
+
 fn runnable_ranges(
     buffer: &BufferSnapshot,
     range: Range<Point>,
@@ -622,6 +743,72 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_cells_to_run(cx: &mut App) {
+        let test_language = Arc::new(Language::new(
+            LanguageConfig {
+                name: "TestLang".into(),
+                line_comments: vec!["# ".into()],
+                ..Default::default()
+            },
+            None,
+        ));
+
+        let buffer = cx.new(|cx| {
+            Buffer::local(
+                indoc! { r#"
+                    import math
+
+                    # %%
+                    print(1 + 1)
+
+                    # %%
+                    print(2 + 2)
+                    # %%
+                    print(3 + 3)
+                "# },
+                cx,
+            )
+            .with_language(test_language.clone(), cx)
+        });
+        let snapshot = buffer.read(cx).snapshot();
+        let cell_texts = |cursor_row, cells| {
+            cells_to_run(&snapshot, cursor_row, cells)
+                .into_iter()
+                .map(|range| snapshot.text_for_range(range).collect::<String>())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            cell_texts(4, CellsToRun::All),
+            vec![
+                "import math",
+                "# %%\nprint(1 + 1)",
+                "# %%\nprint(2 + 2)",
+                "# %%\nprint(3 + 3)"
+            ]
+        );
+        assert_eq!(
+            cell_texts(6, CellsToRun::Above),
+            vec!["import math", "# %%\nprint(1 + 1)"]
+        );
+        assert_eq!(
+            cell_texts(6, CellsToRun::Below),
+            vec!["# %%\nprint(2 + 2)", "# %%\nprint(3 + 3)"]
+        );
+
+        // A file without cells is run as a whole
+        let buffer = cx
+            .new(|cx| Buffer::local("x = 1\n\nprint(x)\n\n", cx).with_language(test_language, cx));
+        let snapshot = buffer.read(cx).snapshot();
+        let cells = cells_to_run(&snapshot, 0, CellsToRun::All)
+            .into_iter()
+            .map(|range| snapshot.text_for_range(range).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(cells, vec!["x = 1\n\nprint(x)"]);
+        assert!(cells_to_run(&snapshot, 2, CellsToRun::Above).is_empty());
+    }
+
     #[gpui::test]
     fn test_markdown_code_blocks(cx: &mut App) {
         let markdown = languages::language("markdown", tree_sitter_md::LANGUAGE.into());
@@ -0,0 +1,376 @@
+use editor::Editor;
+use gpui::{
+    actions, px, App, Context, Entity, EventEmitter, FocusHandle, Focusable, Pixels, Subscription,
+    WeakEntity, Window,
+};
+use settings::Settings as _;
+use theme::ThemeSettings;
+use ui::{prelude::*, Tooltip};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    Workspace,
+};
+
+use crate::repl_store::ReplStore;
+use crate::variables::KernelVariable;
+use crate::{JupyterSettings, Session};
+
+actions!(
+    repl,
+    [ToggleReplPanel, RefreshVariables, ClearOutputHistory]
+);
+
+const DEFAULT_SIZE: Pixels = px(400.);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+
+        workspace.register_action(|workspace, _: &ToggleReplPanel, window, cx| {
+            workspace.toggle_panel_focus::<ReplPanel>(window, cx);
+        });
+
+        let workspace_handle = cx.entity();
+        let panel = cx.new(|cx| ReplPanel::new(&workspace_handle, window, cx));
+        workspace.add_panel(panel, window, cx);
+    })
+    .detach();
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReplPanelTab {
+    Outputs,
+    Variables,
+}
+
+/// The dock panel that shows the outputs of past executions and the variables of the kernel
+/// for the REPL session of the active editor.
+pub struct ReplPanel {
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    position: DockPosition,
+    size: Option<Pixels>,
+    active_tab: ReplPanelTab,
+    session: Option<Entity<Session>>,
+    _session_subscription: Option<Subscription>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ReplPanel {
+    fn new(workspace: &Entity<Workspace>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let subscriptions = vec![
+            cx.subscribe_in(
+                workspace,
+                window,
+                |this, _, event: &workspace::Event, _, cx| {
+                    if let workspace::Event::ActiveItemChanged = event {
+                        this.update_session(cx);
+                    }
+                },
+            ),
+            cx.observe(&ReplStore::global(cx), |this, _, cx| {
+                this.update_session(cx)
+            }),
+        ];
+
+        let mut this = Self {
+            workspace: workspace.downgrade(),
+            focus_handle: cx.focus_handle(),
+            position: DockPosition::Right,
+            size: None,
+            active_tab: ReplPanelTab::Outputs,
+            session: None,
+            _session_subscription: None,
+            _subscriptions: subscriptions,
+        };
+        this.update_session(cx);
+        this
+    }
+
+    /// Shows the session of the active editor, keeping the current one while the active item
+    /// isn't an editor.
+    fn update_session(&mut self, cx: &mut Context<Self>) {
+        let store = ReplStore::global(cx);
+        let active_editor = self
+            .workspace
+            .upgrade()
+            .and_then(|workspace| workspace.read(cx).active_item_as::<Editor>(cx));
+
+        let session = match active_editor {
+            Some(editor) => store.read(cx).get_session(editor.entity_id()).cloned(),
+            None => self.session.clone().filter(|session| {
+                store
+                    .read(cx)
+                    .sessions()
+                    .any(|stored| stored.entity_id() == session.entity_id())
+            }),
+        };
+
+        if session.as_ref().map(|session| session.entity_id())
+            == self.session.as_ref().map(|session| session.entity_id())
+        {
+            return;
+        }
+
+        self._session_subscription = session
+            .as_ref()
+            .map(|session| cx.observe(session, |_, _, cx| cx.notify()));
+        self.session = session;
+        cx.notify();
+    }
+
+    fn refresh_variables(&mut self, _: &RefreshVariables, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(session) = self.session.as_ref() {
+            session.update(cx, |session, cx| session.refresh_variables(cx));
+        }
+    }
+
+    fn clear_output_history(
+        &mut self,
+        _: &ClearOutputHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(session) = self.session.as_ref() {
+            session.update(cx, |session, cx| session.clear_output_history(cx));
+        }
+    }
+
+    fn render_tab_button(
+        &self,
+        tab: ReplPanelTab,
+        label: &'static str,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        Button::new(label, label)
+            .style(ButtonStyle::Subtle)
+            .label_size(LabelSize::Small)
+            .toggle_state(self.active_tab == tab)
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.active_tab = tab;
+                cx.notify();
+            }))
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        let tool_button = match self.active_tab {
+            ReplPanelTab::Outputs => IconButton::new("clear-output-history", IconName::Trash)
+                .icon_size(IconSize::Small)
+                .tooltip(move |window, cx| {
+                    Tooltip::for_action_in(
+                        "Clear Output History",
+                        &ClearOutputHistory,
+                        &focus_handle,
+                        window,
+                        cx,
+                    )
+                })
+                .on_click(|_, window, cx| window.dispatch_action(Box::new(ClearOutputHistory), cx)),
+            ReplPanelTab::Variables => IconButton::new("refresh-variables", IconName::RotateCw)
+                .icon_size(IconSize::Small)
+                .tooltip(move |window, cx| {
+                    Tooltip::for_action_in(
+                        "Refresh Variables",
+                        &RefreshVariables,
+                        &focus_handle,
+                        window,
+                        cx,
+                    )
+                })
+                .on_click(|_, window, cx| window.dispatch_action(Box::new(RefreshVariables), cx)),
+        };
+
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(self.render_tab_button(ReplPanelTab::Outputs, "Outputs", cx))
+            .child(self.render_tab_button(ReplPanelTab::Variables, "Variables", cx))
+            .child(div().flex_1())
+            .when(self.session.is_some(), |this| this.child(tool_button))
+    }
+
+    fn render_outputs(&self, session: &Session, cx: &App) -> AnyElement {
+        let history = session.output_history();
+        if history.is_empty() {
+            return render_placeholder("Outputs of the code you run will be collected here.");
+        }
+
+        let buffer_font = ThemeSettings::get_global(cx).buffer_font.family.clone();
+        v_flex()
+            .id("output-history")
+            .size_full()
+            .overflow_y_scroll()
+            .children(history.iter().rev().enumerate().map(|(ix, entry)| {
+                let first_line = entry.code.lines().next().unwrap_or_default().to_string();
+                v_flex()
+                    .id(ix)
+                    .w_full()
+                    .p_2()
+                    .gap_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        div().font_family(buffer_font.clone()).child(
+                            Label::new(first_line)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .single_line(),
+                        ),
+                    )
+                    .child(entry.execution_view.clone())
+            }))
+            .into_any_element()
+    }
+
+    fn render_variables(&self, session: &Session, cx: &App) -> AnyElement {
+        if !session.supports_variable_inspection() {
+            return render_placeholder("Variables can only be listed for Python kernels.");
+        }
+        let Some(variables) = session.variables() else {
+            return render_placeholder("Variables will be listed once code has been run.");
+        };
+        if variables.is_empty() {
+            return render_placeholder("No variables are defined.");
+        }
+
+        let buffer_font = ThemeSettings::get_global(cx).buffer_font.family.clone();
+        v_flex()
+            .id("variables")
+            .size_full()
+            .overflow_y_scroll()
+            .font_family(buffer_font)
+            .children(
+                variables
+                    .iter()
+                    .map(|variable| render_variable(variable, cx)),
+            )
+            .into_any_element()
+    }
+}
+
+fn render_variable(variable: &KernelVariable, cx: &App) -> impl IntoElement {
+    let type_name = match &variable.shape {
+        Some(shape) => format!("{} ({shape})", variable.type_name),
+        None => variable.type_name.to_string(),
+    };
+
+    v_flex()
+        .id(ElementId::Name(variable.name.clone()))
+        .w_full()
+        .px_2()
+        .py_1()
+        .border_b_1()
+        .border_color(cx.theme().colors().border_variant)
+        .child(
+            h_flex()
+                .gap_2()
+                .child(Label::new(variable.name.clone()).size(LabelSize::Small))
+                .child(
+                    Label::new(type_name)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+        )
+        .child(
+            Label::new(variable.value.clone())
+                .size(LabelSize::Small)
+                .color(Color::Muted)
+                .single_line(),
+        )
+        .tooltip(Tooltip::text(variable.value.clone()))
+}
+
+fn render_placeholder(text: &'static str) -> AnyElement {
+    v_flex()
+        .size_full()
+        .p_4()
+        .items_center()
+        .justify_center()
+        .child(Label::new(text).color(Color::Muted))
+        .into_any_element()
+}
+
+impl Render for ReplPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = match self.session.clone() {
+            Some(session) => {
+                let session = session.read(cx);
+                match self.active_tab {
+                    ReplPanelTab::Outputs => self.render_outputs(session, cx),
+                    ReplPanelTab::Variables => self.render_variables(session, cx),
+                }
+            }
+            None => render_placeholder("Run code in a kernel to see its outputs and variables."),
+        };
+
+        v_flex()
+            .key_context("ReplPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::refresh_variables))
+            .on_action(cx.listener(Self::clear_output_history))
+            .size_full()
+            .bg(cx.theme().colors().panel_background)
+            .child(self.render_header(cx))
+            .child(div().flex_1().min_h_0().child(content))
+    }
+}
+
+impl Focusable for ReplPanel {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for ReplPanel {}
+
+impl Panel for ReplPanel {
+    fn persistent_name() -> &'static str {
+        "ReplPanel"
+    }
+
+    fn position(&self, _window: &Window, _cx: &App) -> DockPosition {
+        self.position
+    }
+
+    fn position_is_valid(&self, _position: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        self.position = position;
+        cx.notify();
+    }
+
+    fn size(&self, _window: &Window, _cx: &App) -> Pixels {
+        self.size.unwrap_or(DEFAULT_SIZE)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.size = size;
+        cx.notify();
+    }
+
+    fn icon(&self, _window: &Window, cx: &App) -> Option<IconName> {
+        // Only offer the panel once a kernel has been started.
+        let has_sessions = ReplStore::global(cx).read(cx).sessions().next().is_some();
+        (JupyterSettings::enabled(cx) && has_sessions).then_some(IconName::ReplNeutral)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("REPL")
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleReplPanel)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        14
+    }
+}
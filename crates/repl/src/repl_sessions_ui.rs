@@ -12,12 +12,17 @@ use workspace::{item::Item, Workspace};
 
 use crate::jupyter_settings::JupyterSettings;
 use crate::repl_store::ReplStore;
+use crate::CellsToRun;
 
 actions!(
     repl,
     [
         Run,
         RunInPlace,
+        RunAll,
+        RunCellsAbove,
+        RunCellsBelow,
+        RestartAndRunAll,
         ClearOutputs,
         Sessions,
         Interrupt,
@@ -136,6 +141,61 @@ pub fn init(cx: &mut App) {
                         }
                     })
                     .detach();
+
+                editor
+                    .register_action({
+                        let editor_handle = editor_handle.clone();
+                        move |_: &RunAll, window, cx| {
+                            if !JupyterSettings::enabled(cx) {
+                                return;
+                            }
+
+                            crate::run_cells(editor_handle.clone(), CellsToRun::All, window, cx)
+                                .log_err();
+                        }
+                    })
+                    .detach();
+
+                editor
+                    .register_action({
+                        let editor_handle = editor_handle.clone();
+                        move |_: &RunCellsAbove, window, cx| {
+                            if !JupyterSettings::enabled(cx) {
+                                return;
+                            }
+
+                            crate::run_cells(editor_handle.clone(), CellsToRun::Above, window, cx)
+                                .log_err();
+                        }
+                    })
+                    .detach();
+
+                editor
+                    .register_action({
+                        let editor_handle = editor_handle.clone();
+                        move |_: &RunCellsBelow, window, cx| {
+                            if !JupyterSettings::enabled(cx) {
+                                return;
+                            }
+
+                            crate::run_cells(editor_handle.clone(), CellsToRun::Below, window, cx)
+                                .log_err();
+                        }
+                    })
+                    .detach();
+
+                editor
+                    .register_action({
+                        let editor_handle = editor_handle.clone();
+                        move |_: &RestartAndRunAll, window, cx| {
+                            if !JupyterSettings::enabled(cx) {
+                                return;
+                            }
+
+                            crate::restart_and_run_all(editor_handle.clone(), window, cx).log_err();
+                        }
+                    })
+                    .detach();
             });
         },
     )
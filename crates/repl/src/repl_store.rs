@@ -272,11 +272,18 @@ impl ReplStore {
         self.sessions.get(&entity_id)
     }
 
-    pub fn insert_session(&mut self, entity_id: EntityId, session: Entity<Session>) {
+    pub fn insert_session(
+        &mut self,
+        entity_id: EntityId,
+        session: Entity<Session>,
+        cx: &mut Context<Self>,
+    ) {
         self.sessions.insert(entity_id, session);
+        cx.notify();
     }
 
-    pub fn remove_session(&mut self, entity_id: EntityId) {
+    pub fn remove_session(&mut self, entity_id: EntityId, cx: &mut Context<Self>) {
         self.sessions.remove(&entity_id);
+        cx.notify();
     }
 }
@@ -1,6 +1,7 @@
 use crate::components::KernelListItem;
 use crate::kernels::RemoteRunningKernel;
 use crate::setup_editor_session_actions;
+use crate::variables::{list_variables_code, parse_variables, KernelVariable};
 use crate::{
     kernels::{Kernel, KernelSpecification, NativeRunningKernel},
     outputs::{ExecutionStatus, ExecutionView},
@@ -36,9 +37,35 @@ pub struct Session {
     pub kernel: Kernel,
     blocks: HashMap<String, EditorBlock>,
     pub kernel_specification: KernelSpecification,
+    output_history: Vec<OutputHistoryEntry>,
+    variables: Option<Vec<KernelVariable>>,
+    variables_request: Option<VariablesRequest>,
+    /// Executions requested while the kernel restarts, sent once it has started again.
+    messages_awaiting_restart: Vec<JupyterMessage>,
     _buffer_subscription: Subscription,
 }
 
+enum RunningCellHighlight {}
+enum SucceededCellHighlight {}
+enum FailedCellHighlight {}
+
+/// The most executions whose outputs are kept for the REPL panel.
+const MAX_OUTPUT_HISTORY: usize = 100;
+
+/// The outputs of an execution, which are kept after its output area is removed from the
+/// editor so that they can be reviewed in the REPL panel.
+pub struct OutputHistoryEntry {
+    message_id: String,
+    pub code: SharedString,
+    pub execution_view: Entity<ExecutionView>,
+}
+
+/// A request for the variables of the kernel, collecting the output it prints.
+struct VariablesRequest {
+    message_id: String,
+    output: String,
+}
+
 struct EditorBlock {
     code_range: Range<Anchor>,
     invalidation_anchor: Anchor,
@@ -222,6 +249,10 @@ impl Session {
             kernel: Kernel::StartingKernel(Task::ready(()).shared()),
             blocks: HashMap::default(),
             kernel_specification,
+            output_history: Vec::new(),
+            variables: None,
+            variables_request: None,
+            messages_awaiting_restart: Vec::new(),
             _buffer_subscription: subscription,
         };
 
@@ -247,6 +278,9 @@ impl Session {
 
         let session_view = cx.entity().clone();
 
+        self.variables = None;
+        self.variables_request = None;
+
         let kernel = match self.kernel_specification.clone() {
             KernelSpecification::Jupyter(kernel_specification)
             | KernelSpecification::PythonEnv(kernel_specification) => NativeRunningKernel::new(
@@ -288,6 +322,21 @@ impl Session {
             })
             .shared();
 
+        let messages_awaiting_restart = std::mem::take(&mut self.messages_awaiting_restart);
+        if !messages_awaiting_restart.is_empty() {
+            let pending_kernel = pending_kernel.clone();
+            cx.spawn(async move |this, cx| {
+                pending_kernel.await;
+                this.update(cx, |session, cx| {
+                    for message in messages_awaiting_restart {
+                        session.send(message, cx).ok();
+                    }
+                })
+                .ok();
+            })
+            .detach();
+        }
+
         self.kernel(Kernel::StartingKernel(pending_kernel), cx);
         cx.notify();
     }
@@ -295,8 +344,17 @@ impl Session {
     pub fn kernel_errored(&mut self, error_message: String, cx: &mut Context<Self>) {
         self.kernel(Kernel::ErroredLaunch(error_message.clone()), cx);
 
-        self.blocks.values().for_each(|block| {
-            block.execution_view.update(cx, |execution_view, cx| {
+        let execution_views = self
+            .blocks
+            .values()
+            .map(|block| &block.execution_view)
+            .chain(
+                self.output_history
+                    .iter()
+                    .map(|entry| &entry.execution_view),
+            );
+        execution_views.for_each(|execution_view| {
+            execution_view.update(cx, |execution_view, cx| {
                 match execution_view.status {
                     ExecutionStatus::Finished => {
                         // Do nothing when the output was good
@@ -310,6 +368,7 @@ impl Session {
                 cx.notify();
             });
         });
+        self.update_gutter_status(cx);
     }
 
     fn on_buffer_event(
@@ -338,6 +397,7 @@ impl Session {
                         editor.remove_blocks(blocks_to_remove, None, cx);
                     })
                     .ok();
+                self.update_gutter_status(cx);
                 cx.notify();
             }
         }
@@ -362,6 +422,51 @@ impl Session {
             .ok();
 
         self.blocks.clear();
+        self.update_gutter_status(cx);
+    }
+
+    /// Highlights the gutter next to each cell that has an output area with the status of its
+    /// execution.
+    fn update_gutter_status(&self, cx: &mut Context<Self>) {
+        let mut running = Vec::new();
+        let mut succeeded = Vec::new();
+        let mut failed = Vec::new();
+        for block in self.blocks.values() {
+            let execution_view = block.execution_view.read(cx);
+            let ranges = match &execution_view.status {
+                ExecutionStatus::Finished if execution_view.has_error() => &mut failed,
+                ExecutionStatus::Finished => &mut succeeded,
+                ExecutionStatus::KernelErrored(_) => &mut failed,
+                ExecutionStatus::ConnectingToKernel
+                | ExecutionStatus::Queued
+                | ExecutionStatus::Executing
+                | ExecutionStatus::Restarting => &mut running,
+                ExecutionStatus::Unknown
+                | ExecutionStatus::ShuttingDown
+                | ExecutionStatus::Shutdown => continue,
+            };
+            ranges.push(block.code_range.clone());
+        }
+
+        self.editor
+            .update(cx, |editor, cx| {
+                editor.highlight_gutter::<RunningCellHighlight>(
+                    &running,
+                    |cx| cx.theme().status().info,
+                    cx,
+                );
+                editor.highlight_gutter::<SucceededCellHighlight>(
+                    &succeeded,
+                    |cx| cx.theme().status().success,
+                    cx,
+                );
+                editor.highlight_gutter::<FailedCellHighlight>(
+                    &failed,
+                    |cx| cx.theme().status().error,
+                    cx,
+                );
+            })
+            .ok();
     }
 
     pub fn execute(
@@ -381,6 +486,7 @@ impl Session {
             return;
         }
 
+        let execute_request_code = code.clone();
         let execute_request = ExecuteRequest {
             code,
             ..ExecuteRequest::default()
@@ -425,6 +531,7 @@ impl Session {
                 if let Some(session) = session_view.upgrade() {
                     session.update(cx, |session, cx| {
                         session.blocks.remove(&parent_message_id);
+                        session.update_gutter_status(cx);
                         cx.notify();
                     });
                 }
@@ -439,9 +546,13 @@ impl Session {
             },
         );
 
-        let Ok(editor_block) =
-            EditorBlock::new(self.editor.clone(), anchor_range, status, on_close, cx)
-        else {
+        let Ok(editor_block) = EditorBlock::new(
+            self.editor.clone(),
+            anchor_range,
+            status.clone(),
+            on_close,
+            cx,
+        ) else {
             return;
         };
 
@@ -454,6 +565,19 @@ impl Session {
         self.blocks
             .insert(message.header.msg_id.clone(), editor_block);
 
+        if let Some(workspace) = editor.read(cx).workspace() {
+            let execution_view = cx.new(|cx| ExecutionView::new(status, workspace.downgrade(), cx));
+            if self.output_history.len() >= MAX_OUTPUT_HISTORY {
+                self.output_history.remove(0);
+            }
+            self.output_history.push(OutputHistoryEntry {
+                message_id: message.header.msg_id.clone(),
+                code: execute_request_code.into(),
+                execution_view,
+            });
+            cx.notify();
+        }
+
         match &self.kernel {
             Kernel::RunningKernel(_) => {
                 self.send(message, cx).ok();
@@ -472,8 +596,10 @@ impl Session {
                 })
                 .detach();
             }
+            Kernel::Restarting => self.messages_awaiting_restart.push(message),
             _ => {}
         }
+        self.update_gutter_status(cx);
 
         if move_down {
             editor.update(cx, move |editor, cx| {
@@ -495,6 +621,15 @@ impl Session {
             None => return,
         };
 
+        if self
+            .variables_request
+            .as_ref()
+            .map_or(false, |request| &request.message_id == parent_message_id)
+        {
+            self.handle_variables_message(&message.content, cx);
+            return;
+        }
+
         match &message.content {
             JupyterMessageContent::Status(status) => {
                 self.kernel.set_execution_state(&status.execution_state);
@@ -524,6 +659,11 @@ impl Session {
                         execution_view.update_display_data(&update.data, &display_id, window, cx);
                     });
                 });
+                for entry in &self.output_history {
+                    entry.execution_view.update(cx, |execution_view, cx| {
+                        execution_view.update_display_data(&update.data, &display_id, window, cx);
+                    });
+                }
                 return;
             }
             _ => {}
@@ -531,6 +671,97 @@ impl Session {
 
         if let Some(block) = self.blocks.get_mut(parent_message_id) {
             block.handle_message(message, window, cx);
+            if let JupyterMessageContent::Status(_) | JupyterMessageContent::ErrorOutput(_) =
+                &message.content
+            {
+                self.update_gutter_status(cx);
+            }
+        }
+
+        if let Some(entry) = self
+            .output_history
+            .iter()
+            .find(|entry| &entry.message_id == parent_message_id)
+        {
+            entry.execution_view.update(cx, |execution_view, cx| {
+                execution_view.push_message(&message.content, window, cx);
+            });
+
+            // The execution may have defined or changed variables.
+            if let JupyterMessageContent::Status(status) = &message.content {
+                if matches!(status.execution_state, ExecutionState::Idle) {
+                    self.refresh_variables(cx);
+                }
+            }
+        }
+    }
+
+    /// The outputs of the most recent executions, oldest first.
+    pub fn output_history(&self) -> &[OutputHistoryEntry] {
+        &self.output_history
+    }
+
+    pub fn clear_output_history(&mut self, cx: &mut Context<Self>) {
+        self.output_history.clear();
+        cx.notify();
+    }
+
+    /// Whether the variables of this session's kernel can be listed.
+    pub fn supports_variable_inspection(&self) -> bool {
+        list_variables_code(&self.kernel_specification.language()).is_some()
+    }
+
+    /// The global variables of the kernel, or `None` if they haven't been listed yet.
+    pub fn variables(&self) -> Option<&[KernelVariable]> {
+        self.variables.as_deref()
+    }
+
+    /// Asks the kernel for its global variables, replacing [`Self::variables`] once it replies.
+    pub fn refresh_variables(&mut self, cx: &mut Context<Self>) {
+        let Some(code) = list_variables_code(&self.kernel_specification.language()) else {
+            return;
+        };
+        if !matches!(self.kernel, Kernel::RunningKernel(_)) {
+            return;
+        }
+
+        let message: JupyterMessage = ExecuteRequest {
+            code: code.to_string(),
+            store_history: false,
+            ..ExecuteRequest::default()
+        }
+        .into();
+        self.variables_request = Some(VariablesRequest {
+            message_id: message.header.msg_id.clone(),
+            output: String::new(),
+        });
+        self.send(message, cx).log_err();
+    }
+
+    fn handle_variables_message(
+        &mut self,
+        content: &JupyterMessageContent,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(request) = self.variables_request.as_mut() else {
+            return;
+        };
+
+        match content {
+            JupyterMessageContent::StreamContent(stream) => {
+                request.output.push_str(&stream.text);
+            }
+            JupyterMessageContent::Status(status)
+                if matches!(status.execution_state, ExecutionState::Idle) =>
+            {
+                match parse_variables(&request.output) {
+                    Some(variables) => self.variables = Some(variables),
+                    None => log::error!("failed to list the variables of the kernel"),
+                }
+                self.variables_request = None;
+                cx.notify();
+            }
+            _ => {}
         }
     }
 
@@ -610,6 +841,11 @@ impl Session {
 
                 let forced = kernel.force_shutdown(window, cx);
 
+                // Clear the outputs now rather than once the kernel has restarted, so that cells
+                // run while it restarts keep theirs.
+                // TODO: Differentiate between restart and restart+clear-outputs
+                self.clear_outputs(cx);
+
                 cx.spawn_in(window, async move |this, cx| {
                     // Send shutdown request with restart flag
                     log::debug!("restarting kernel");
@@ -624,8 +860,6 @@ impl Session {
 
                     // Start a new kernel
                     this.update_in(cx, |session, window, cx| {
-                        // TODO: Differentiate between restart and restart+clear-outputs
-                        session.clear_outputs(cx);
                         session.start_kernel(window, cx);
                     })
                     .ok();
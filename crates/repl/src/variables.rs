@@ -0,0 +1,106 @@
+//! Listing the global variables of a kernel, for the variable explorer in the REPL panel.
+//!
+//! Jupyter has no message for inspecting a kernel's namespace, so the variables are listed by
+//! running a short snippet in the kernel that prints them as JSON on a line starting with
+//! [`VARIABLES_MARKER`]. Only Python kernels are supported.
+
+use serde::Deserialize;
+use ui::SharedString;
+
+/// Prefixes the line of output that holds the listed variables.
+const VARIABLES_MARKER: &str = "__zed_variables__:";
+
+const PYTHON_LIST_VARIABLES: &str = r#"def __zed_list_variables():
+    import json, types
+    variables = []
+    for name, value in list(globals().items()):
+        if name.startswith("_") or name in ("In", "Out", "exit", "quit", "get_ipython"):
+            continue
+        if isinstance(value, (types.ModuleType, types.FunctionType, types.BuiltinFunctionType, type)):
+            continue
+        shape = getattr(value, "shape", None)
+        if shape is None and isinstance(value, (list, tuple, dict, set, str, bytes)):
+            shape = (len(value),)
+        try:
+            text = repr(value)
+        except Exception:
+            text = "<unavailable>"
+        if len(text) > 200:
+            text = text[:199] + "…"
+        variables.append({
+            "name": name,
+            "type": type(value).__name__,
+            "shape": " × ".join(str(d) for d in shape) if isinstance(shape, tuple) else None,
+            "value": " ".join(text.split()),
+        })
+    print("__zed_variables__:" + json.dumps(variables))
+__zed_list_variables()
+del __zed_list_variables
+"#;
+
+/// A global variable of a kernel.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KernelVariable {
+    pub name: SharedString,
+    #[serde(rename = "type")]
+    pub type_name: SharedString,
+    /// The dimensions of arrays and data frames, or the length of collections.
+    pub shape: Option<SharedString>,
+    /// A single-line, truncated representation of the value.
+    pub value: SharedString,
+}
+
+/// Returns the code that lists the variables of a kernel for the given language, if
+/// inspecting its variables is supported.
+pub(crate) fn list_variables_code(language: &str) -> Option<&'static str> {
+    language
+        .eq_ignore_ascii_case("python")
+        .then_some(PYTHON_LIST_VARIABLES)
+}
+
+/// Finds the variables in the output of the code returned by [`list_variables_code`].
+pub(crate) fn parse_variables(output: &str) -> Option<Vec<KernelVariable>> {
+    let json = output
+        .lines()
+        .rev()
+        .find_map(|line| line.strip_prefix(VARIABLES_MARKER))?;
+    serde_json::from_str(json).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_variables() {
+        let output = "some earlier print\n__zed_variables__:[{\"name\": \"df\", \"type\": \"DataFrame\", \"shape\": \"3 \\u00d7 2\", \"value\": \"a b 0 1 2\"}, {\"name\": \"x\", \"type\": \"int\", \"shape\": null, \"value\": \"1\"}]\n";
+        assert_eq!(
+            parse_variables(output),
+            Some(vec![
+                KernelVariable {
+                    name: "df".into(),
+                    type_name: "DataFrame".into(),
+                    shape: Some("3 × 2".into()),
+                    value: "a b 0 1 2".into(),
+                },
+                KernelVariable {
+                    name: "x".into(),
+                    type_name: "int".into(),
+                    shape: None,
+                    value: "1".into(),
+                },
+            ])
+        );
+
+        assert_eq!(
+            parse_variables("Traceback (most recent call last):\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_list_variables_code() {
+        assert!(list_variables_code("Python").is_some());
+        assert!(list_variables_code("typescript").is_none());
+    }
+}
@@ -38,6 +38,13 @@ pub enum Notification {
         sender_id: u64,
         channel_id: u64,
     },
+    ReviewComment {
+        #[serde(rename = "entity_id")]
+        comment_id: u64,
+        author_id: u64,
+        worktree_root_name: String,
+        path: String,
+    },
 }
 
 impl Notification {
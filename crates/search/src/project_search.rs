@@ -3,33 +3,33 @@ use crate::{
     ReplaceAll, ReplaceNext, SearchOptions, SelectNextMatch, SelectPreviousMatch,
     ToggleCaseSensitive, ToggleIncludeIgnored, ToggleRegex, ToggleReplace, ToggleWholeWord,
 };
-use anyhow::Context as _;
+use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet};
 use editor::{
     actions::SelectAll, items::active_match_index, scroll::Autoscroll, Anchor, Editor,
     EditorElement, EditorEvent, EditorSettings, EditorStyle, MultiBuffer, MAX_TAB_TITLE_LEN,
 };
-use futures::StreamExt;
+use futures::{stream::BoxStream, StreamExt};
 use gpui::{
-    actions, div, Action, AnyElement, AnyView, App, Axis, Context, Entity, EntityId, EventEmitter,
-    FocusHandle, Focusable, Global, Hsla, InteractiveElement, IntoElement, KeyContext,
-    ParentElement, Point, Render, SharedString, Styled, Subscription, Task, TextStyle,
+    actions, div, Action, AnyElement, AnyView, App, AsyncApp, Axis, Context, Entity, EntityId,
+    EventEmitter, FocusHandle, Focusable, Global, Hsla, InteractiveElement, IntoElement,
+    KeyContext, ParentElement, Point, Render, SharedString, Styled, Subscription, Task, TextStyle,
     UpdateGlobal, WeakEntity, Window,
 };
 use language::{Buffer, Language};
 use menu::Confirm;
 use project::{
-    search::{SearchInputKind, SearchQuery},
+    search::{SearchInputKind, SearchQuery, SearchResult},
     search_history::SearchHistoryCursor,
     Project, ProjectPath,
 };
+use rpc::proto::{self, PeerId};
 use settings::Settings;
 use std::{
     any::{Any, TypeId},
     mem,
     ops::{Not, Range},
     path::Path,
-    pin::pin,
     sync::Arc,
 };
 use theme::ThemeSettings;
@@ -39,17 +39,27 @@ use ui::{
 };
 use util::paths::PathMatcher;
 use workspace::{
-    item::{BreadcrumbText, Item, ItemEvent, ItemHandle},
+    item::{BreadcrumbText, Dedup, FollowEvent, FollowableItem, Item, ItemEvent, ItemHandle},
     searchable::{Direction, SearchableItem, SearchableItemHandle},
     DeploySearch, ItemNavHistory, NewSearch, ToolbarItemEvent, ToolbarItemLocation,
-    ToolbarItemView, Workspace, WorkspaceId,
+    ToolbarItemView, ViewId, Workspace, WorkspaceId,
 };
 
 actions!(
     project_search,
-    [SearchInNew, ToggleFocus, NextField, ToggleFilters]
+    [
+        SearchInNew,
+        ToggleFocus,
+        NextField,
+        ToggleFilters,
+        LoadMoreMatches
+    ]
 );
 
+/// The number of matches that are shown before the search pauses until more are requested, so
+/// that searches with a huge number of matches don't hold all of them in memory.
+const MATCHES_PER_PAGE: usize = 10_000;
+
 #[derive(Default)]
 struct ActiveSettings(HashMap<WeakEntity<Project>, ProjectSearchSettings>);
 
@@ -57,6 +67,7 @@ impl Global for ActiveSettings {}
 
 pub fn init(cx: &mut App) {
     cx.set_global(ActiveSettings::default());
+    workspace::FollowableViewRegistry::register::<ProjectSearchView>(cx);
     cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
         register_workspace_action(workspace, move |search_bar, _: &Deploy, window, cx| {
             search_bar.focus_search(window, cx);
@@ -70,6 +81,9 @@ pub fn init(cx: &mut App) {
                 search_bar.toggle_filters(window, cx);
             },
         );
+        register_workspace_action(workspace, move |search_bar, _: &LoadMoreMatches, _, cx| {
+            search_bar.load_more_matches(cx);
+        });
         register_workspace_action(
             workspace,
             move |search_bar, _: &ToggleCaseSensitive, _, cx| {
@@ -141,6 +155,8 @@ pub struct ProjectSearch {
     search_id: usize,
     no_results: Option<bool>,
     limit_reached: bool,
+    /// The rest of the search's results, when it was paused after a page of matches.
+    remaining_results: Option<BoxStream<'static, SearchResult>>,
     search_history_cursor: SearchHistoryCursor,
     search_included_history_cursor: SearchHistoryCursor,
     search_excluded_history_cursor: SearchHistoryCursor,
@@ -170,6 +186,9 @@ pub struct ProjectSearchView {
     replace_enabled: bool,
     included_opened_only: bool,
     regex_language: Option<Arc<Language>>,
+    remote_id: Option<ViewId>,
+    /// The search that we last ran on behalf of the collaborator we're following.
+    followed_search: Option<proto::view::ProjectSearch>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -198,6 +217,7 @@ impl ProjectSearch {
             search_id: 0,
             no_results: None,
             limit_reached: false,
+            remaining_results: None,
             search_history_cursor: Default::default(),
             search_included_history_cursor: Default::default(),
             search_excluded_history_cursor: Default::default(),
@@ -217,6 +237,7 @@ impl ProjectSearch {
             search_id: self.search_id,
             no_results: self.no_results,
             limit_reached: self.limit_reached,
+            remaining_results: None,
             search_history_cursor: self.search_history_cursor.clone(),
             search_included_history_cursor: self.search_included_history_cursor.clone(),
             search_excluded_history_cursor: self.search_excluded_history_cursor.clone(),
@@ -254,15 +275,14 @@ impl ProjectSearch {
                     .search_history_mut(SearchInputKind::Exclude)
                     .add(&mut self.search_excluded_history_cursor, excluded);
             }
-            project.search(query.clone(), cx)
+            project.search_streaming(query.clone(), cx)
         });
         self.last_search_query_text = Some(query.as_str().to_string());
         self.search_id += 1;
         self.active_query = Some(query);
         self.match_ranges.clear();
+        self.remaining_results = None;
         self.pending_search = Some(cx.spawn(async move |this, cx| {
-            let mut matches = pin!(search.ready_chunks(1024));
-            let this = this.upgrade()?;
             this.update(cx, |this, cx| {
                 this.match_ranges.clear();
                 this.excerpts.update(cx, |this, cx| this.clear(cx));
@@ -270,54 +290,100 @@ impl ProjectSearch {
                 this.limit_reached = false;
             })
             .ok()?;
+            Self::receive_results(this, search.boxed(), cx).await
+        }));
+        cx.notify();
+    }
 
-            let mut limit_reached = false;
-            while let Some(results) = matches.next().await {
-                let mut buffers_with_ranges = Vec::with_capacity(results.len());
-                for result in results {
-                    match result {
-                        project::search::SearchResult::Buffer { buffer, ranges } => {
-                            buffers_with_ranges.push((buffer, ranges));
-                        }
-                        project::search::SearchResult::LimitReached => {
-                            limit_reached = true;
-                        }
+    pub fn has_more_matches(&self) -> bool {
+        self.remaining_results.is_some()
+    }
+
+    /// Resumes a search that was paused after a page of matches.
+    fn load_more_matches(&mut self, cx: &mut Context<Self>) {
+        let Some(results) = self.remaining_results.take() else {
+            return;
+        };
+        self.pending_search =
+            Some(cx.spawn(async move |this, cx| Self::receive_results(this, results, cx).await));
+        cx.notify();
+    }
+
+    /// Adds the search's results to the excerpts until they run out, or until another page of
+    /// matches was added, in which case the rest of the results are kept for later.
+    async fn receive_results(
+        this: WeakEntity<Self>,
+        mut results: BoxStream<'static, SearchResult>,
+        cx: &mut AsyncApp,
+    ) -> Option<()> {
+        let this = this.upgrade()?;
+        let mut match_count = this.update(cx, |this, _| this.match_ranges.len()).ok()?;
+        let page_end = match_count + MATCHES_PER_PAGE;
+        let mut matches = (&mut results).ready_chunks(1024);
+
+        let mut limit_reached = false;
+        let mut unreceived_results = Vec::new();
+        while let Some(chunk) = matches.next().await {
+            let mut chunk = chunk.into_iter();
+            let mut buffers_with_ranges = Vec::with_capacity(chunk.len());
+            for result in chunk.by_ref() {
+                match result {
+                    SearchResult::Buffer { buffer, ranges } => {
+                        match_count += ranges.len();
+                        buffers_with_ranges.push((buffer, ranges));
+                    }
+                    SearchResult::LimitReached => {
+                        limit_reached = true;
                     }
                 }
-
-                let match_ranges = this
-                    .update(cx, |this, cx| {
-                        this.excerpts.update(cx, |excerpts, cx| {
-                            excerpts.push_multiple_excerpts_with_context_lines(
-                                buffers_with_ranges,
-                                editor::DEFAULT_MULTIBUFFER_CONTEXT,
-                                cx,
-                            )
-                        })
+                if match_count >= page_end {
+                    break;
+                }
+            }
+            unreceived_results.extend(chunk);
+
+            let match_ranges = this
+                .update(cx, |this, cx| {
+                    this.excerpts.update(cx, |excerpts, cx| {
+                        excerpts.push_multiple_excerpts_with_context_lines(
+                            buffers_with_ranges,
+                            editor::DEFAULT_MULTIBUFFER_CONTEXT,
+                            cx,
+                        )
                     })
-                    .ok()?
-                    .await;
-
-                this.update(cx, |this, cx| {
-                    this.match_ranges.extend(match_ranges);
-                    cx.notify();
                 })
-                .ok()?;
-            }
+                .ok()?
+                .await;
 
             this.update(cx, |this, cx| {
-                if !this.match_ranges.is_empty() {
-                    this.no_results = Some(false);
-                }
-                this.limit_reached = limit_reached;
-                this.pending_search.take();
+                this.match_ranges.extend(match_ranges);
                 cx.notify();
             })
             .ok()?;
+            if match_count >= page_end {
+                break;
+            }
+        }
+        drop(matches);
 
-            None
-        }));
-        cx.notify();
+        let remaining_results = (match_count >= page_end).then(|| {
+            futures::stream::iter(unreceived_results)
+                .chain(results)
+                .boxed()
+        });
+
+        this.update(cx, |this, cx| {
+            if !this.match_ranges.is_empty() {
+                this.no_results = Some(false);
+            }
+            this.limit_reached = limit_reached;
+            this.remaining_results = remaining_results;
+            this.pending_search.take();
+            cx.notify();
+        })
+        .ok()?;
+
+        None
     }
 }
 
@@ -829,12 +895,61 @@ impl ProjectSearchView {
             replace_enabled: false,
             included_opened_only: false,
             regex_language: None,
+            remote_id: None,
+            followed_search: None,
             _subscriptions: subscriptions,
         };
         this.entity_changed(window, cx);
         this
     }
 
+    /// Describes the search whose results are shown, for collaborators following us.
+    fn follow_state(&self, cx: &App) -> proto::view::ProjectSearch {
+        let Some(query) = self.entity.read(cx).active_query.as_ref() else {
+            return Default::default();
+        };
+        proto::view::ProjectSearch {
+            query: query.as_str().to_string(),
+            options: SearchOptions::from_query(query).bits() as u32,
+            files_to_include: query.as_inner().files_to_include().sources().join(","),
+            files_to_exclude: query.as_inner().files_to_exclude().sources().join(","),
+        }
+    }
+
+    /// Runs the search that the collaborator we're following is looking at.
+    fn apply_follow_state(
+        &mut self,
+        state: proto::view::ProjectSearch,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.followed_search.as_ref() == Some(&state) {
+            return;
+        }
+        self.search_options = SearchOptions::from_bits_truncate(state.options as u8);
+        self.set_search_editor(SearchInputKind::Query, &state.query, window, cx);
+        self.set_search_editor(
+            SearchInputKind::Include,
+            &state.files_to_include,
+            window,
+            cx,
+        );
+        self.set_search_editor(
+            SearchInputKind::Exclude,
+            &state.files_to_exclude,
+            window,
+            cx,
+        );
+        self.filters_enabled |=
+            !state.files_to_include.is_empty() || !state.files_to_exclude.is_empty();
+        let has_query = !state.query.is_empty();
+        self.followed_search = Some(state);
+        if has_query {
+            self.search(cx);
+        }
+        cx.notify();
+    }
+
     pub fn new_search_in_directory(
         workspace: &mut Workspace,
         dir_path: &Path,
@@ -1414,6 +1529,93 @@ impl Default for ProjectSearchBar {
     }
 }
 
+impl FollowableItem for ProjectSearchView {
+    fn remote_id(&self) -> Option<ViewId> {
+        self.remote_id
+    }
+
+    fn to_state_proto(&self, _window: &Window, cx: &App) -> Option<proto::view::Variant> {
+        Some(proto::view::Variant::ProjectSearch(self.follow_state(cx)))
+    }
+
+    fn from_state_proto(
+        workspace: Entity<Workspace>,
+        remote_id: ViewId,
+        state: &mut Option<proto::view::Variant>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<Task<Result<Entity<Self>>>> {
+        let proto::view::Variant::ProjectSearch(_) = state.as_ref()? else {
+            return None;
+        };
+        let Some(proto::view::Variant::ProjectSearch(state)) = state.take() else {
+            unreachable!()
+        };
+
+        let project = workspace.read(cx).project().clone();
+        let weak_workspace = workspace.downgrade();
+        let entity = cx.new(|cx| ProjectSearch::new(project, cx));
+        let view = cx.new(|cx| {
+            let mut view = ProjectSearchView::new(weak_workspace, entity, window, cx, None);
+            view.remote_id = Some(remote_id);
+            view.apply_follow_state(state, window, cx);
+            view
+        });
+        Some(Task::ready(Ok(view)))
+    }
+
+    fn to_follow_event(_event: &Self::Event) -> Option<FollowEvent> {
+        None
+    }
+
+    fn add_event_to_update_proto(
+        &self,
+        event: &Self::Event,
+        update: &mut Option<proto::update_view::Variant>,
+        _window: &Window,
+        cx: &App,
+    ) -> bool {
+        match event {
+            ViewEvent::UpdateTab => {
+                *update = Some(proto::update_view::Variant::ProjectSearch(
+                    self.follow_state(cx),
+                ));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn apply_update_proto(
+        &mut self,
+        _project: &Entity<Project>,
+        message: proto::update_view::Variant,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if let proto::update_view::Variant::ProjectSearch(state) = message {
+            self.apply_follow_state(state, window, cx);
+        }
+        Task::ready(Ok(()))
+    }
+
+    fn is_project_item(&self, _window: &Window, _cx: &App) -> bool {
+        true
+    }
+
+    fn set_leader_peer_id(
+        &mut self,
+        _leader_peer_id: Option<PeerId>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+
+    fn dedup(&self, _existing: &Self, _window: &Window, _cx: &App) -> Option<Dedup> {
+        None
+    }
+}
+
 impl ProjectSearchBar {
     pub fn new() -> Self {
         Self {
@@ -1548,6 +1750,17 @@ impl ProjectSearchBar {
         }
     }
 
+    fn load_more_matches(&mut self, cx: &mut Context<Self>) {
+        if let Some(search_view) = self.active_project_search.as_ref() {
+            search_view.update(cx, |search_view, cx| {
+                search_view
+                    .entity
+                    .update(cx, |search, cx| search.load_more_matches(cx));
+            });
+            cx.notify();
+        }
+    }
+
     fn toggle_opened_only(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
         if let Some(search_view) = self.active_project_search.as_ref() {
             search_view.update(cx, |search_view, cx| {
@@ -1857,6 +2070,7 @@ impl Render for ProjectSearchBar {
             );
 
         let limit_reached = search.entity.read(cx).limit_reached;
+        let has_more_matches = search.entity.read(cx).has_more_matches();
 
         let match_text = search
             .active_match_index
@@ -1865,7 +2079,7 @@ impl Render for ProjectSearchBar {
                 let match_quantity = search.entity.read(cx).match_ranges.len();
                 if match_quantity > 0 {
                     debug_assert!(match_quantity >= index);
-                    if limit_reached {
+                    if limit_reached || has_more_matches {
                         Some(format!("{index}/{match_quantity}+").to_string())
                     } else {
                         Some(format!("{index}/{match_quantity}").to_string())
@@ -1945,7 +2159,26 @@ impl Render for ProjectSearchBar {
                             "Search limits reached.\nTry narrowing your search.",
                         ))
                     }),
-            );
+            )
+            .when(has_more_matches, |el| {
+                el.child(
+                    IconButton::new("project-search-load-more", IconName::Ellipsis)
+                        .shape(IconButtonShape::Square)
+                        .on_click(cx.listener(|this, _, _, cx| this.load_more_matches(cx)))
+                        .tooltip({
+                            let focus_handle = focus_handle.clone();
+                            move |window, cx| {
+                                Tooltip::for_action_in(
+                                    "Load More Matches",
+                                    &LoadMoreMatches,
+                                    &focus_handle,
+                                    window,
+                                    cx,
+                                )
+                            }
+                        }),
+                )
+            });
 
         let search_line = h_flex()
             .w_full()
@@ -3897,6 +4130,50 @@ pub mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_project_search_pagination(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let matches_per_file = MATCHES_PER_PAGE * 3 / 8;
+        let file_text = "x\n".repeat(matches_per_file);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "one.txt": file_text,
+                "two.txt": file_text,
+                "three.txt": file_text,
+                "four.txt": file_text,
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let search = cx.new(|cx| ProjectSearch::new(project.clone(), cx));
+        let search_view = cx.add_window(|window, cx| {
+            ProjectSearchView::new(workspace.downgrade(), search.clone(), window, cx, None)
+        });
+
+        perform_search(search_view, "x", cx);
+        search.update(cx, |search, _| {
+            assert_eq!(
+                search.match_ranges.len(),
+                matches_per_file * 3,
+                "the search should pause after the file that fills the first page"
+            );
+            assert!(search.has_more_matches());
+        });
+
+        search.update(cx, |search, cx| search.load_more_matches(cx));
+        cx.background_executor.run_until_parked();
+        search.update(cx, |search, _| {
+            assert_eq!(search.match_ranges.len(), matches_per_file * 4);
+            assert!(!search.has_more_matches());
+            assert!(!search.limit_reached);
+        });
+    }
+
     fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings = SettingsStore::test(cx);
@@ -4,16 +4,19 @@ mod embedding_index;
 mod indexing;
 mod project_index;
 mod project_index_debug_view;
+mod semantic_index_settings;
 mod summary_backlog;
 mod summary_index;
 mod worktree_index;
 
 use anyhow::{Context as _, Result};
+use client::Client;
 use collections::HashMap;
 use fs::Fs;
 use gpui::{App, AppContext as _, AsyncApp, BorrowAppContext, Context, Entity, Global, WeakEntity};
 use language::LineEnding;
 use project::{Project, Worktree};
+use settings::{Settings as _, SettingsStore};
 use std::{
     cmp::Ordering,
     path::{Path, PathBuf},
@@ -25,8 +28,56 @@ use workspace::Workspace;
 pub use embedding::*;
 pub use project_index::{LoadedSearchResult, ProjectIndex, SearchResult, Status};
 pub use project_index_debug_view::ProjectIndexDebugView;
+pub use semantic_index_settings::*;
 pub use summary_index::FileSummary;
 
+/// Opens the semantic index when it's enabled in the settings, and reopens it
+/// whenever its embedding provider changes.
+pub fn init(client: Arc<Client>, cx: &mut App) {
+    SemanticIndexSettings::register(cx);
+
+    let mut current_settings = None;
+    update_semantic_db(&client, &mut current_settings, cx);
+    cx.observe_global::<SettingsStore>(move |cx| {
+        update_semantic_db(&client, &mut current_settings, cx);
+    })
+    .detach();
+}
+
+fn update_semantic_db(
+    client: &Arc<Client>,
+    current_settings: &mut Option<SemanticIndexSettings>,
+    cx: &mut App,
+) {
+    let settings = SemanticIndexSettings::get_global(cx).clone();
+    if current_settings.as_ref() == Some(&settings) {
+        return;
+    }
+    *current_settings = Some(settings.clone());
+
+    if cx.has_global::<SemanticDb>() {
+        cx.remove_global::<SemanticDb>();
+    }
+    if !settings.enabled {
+        return;
+    }
+
+    let embedding_provider = settings.embedding_provider.build(client);
+    let db_path = paths::embeddings_dir()
+        .join("semantic-index")
+        .join(settings.embedding_provider.id());
+    cx.spawn(async move |cx| {
+        let semantic_db = SemanticDb::new(db_path, embedding_provider, cx).await?;
+        cx.update(|cx| {
+            // The settings may have changed again while the database was being opened.
+            if SemanticIndexSettings::get_global(cx) == &settings {
+                cx.set_global(semantic_db);
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
 pub struct SemanticDb {
     embedding_provider: Arc<dyn EmbeddingProvider>,
     db_connection: Option<heed::Env>,
@@ -61,7 +112,9 @@ impl SemanticDb {
 
                     if cx.has_global::<SemanticDb>() {
                         cx.update_global::<SemanticDb, _>(|this, cx| {
-                            this.create_project_index(project, cx);
+                            if this.project_index(project.clone(), cx).is_none() {
+                                this.create_project_index(project, cx);
+                            }
                         })
                     } else {
                         log::info!("No SemanticDb, skipping project index")
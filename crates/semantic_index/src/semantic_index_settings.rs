@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use client::Client;
+use gpui::App;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+use crate::{
+    CloudEmbeddingProvider, EmbeddingProvider, LmStudioEmbeddingModel, LmStudioEmbeddingProvider,
+    OllamaEmbeddingModel, OllamaEmbeddingProvider, OpenAiEmbeddingModel, OpenAiEmbeddingProvider,
+};
+
+/// The settings for the semantic index of each project, which the agent's
+/// `semantic-search` tool searches.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SemanticIndexSettings {
+    /// Whether to index projects for semantic search. Projects are indexed
+    /// when they're opened, and their files are re-indexed when they change.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub enabled: bool,
+    /// Which service computes the embeddings the index is made of.
+    ///
+    /// Default: {"provider": "zed_cloud"}
+    #[serde(default)]
+    pub embedding_provider: EmbeddingProviderSettings,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "provider", rename_all = "snake_case")]
+pub enum EmbeddingProviderSettings {
+    /// Zed's hosted embeddings, which require being signed in.
+    #[default]
+    ZedCloud,
+    /// OpenAI's embeddings API, authenticated with the `OPENAI_API_KEY` environment variable.
+    OpenAi {
+        #[serde(default)]
+        model: OpenAiEmbeddingModelSetting,
+        #[serde(default = "default_open_ai_api_url")]
+        api_url: String,
+    },
+    /// A local Ollama server.
+    Ollama {
+        #[serde(default)]
+        model: OllamaEmbeddingModelSetting,
+    },
+    /// A local LM Studio server, using `nomic-embed-text`.
+    LmStudio,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum OpenAiEmbeddingModelSetting {
+    #[default]
+    #[serde(rename = "text-embedding-3-small")]
+    TextEmbedding3Small,
+    #[serde(rename = "text-embedding-3-large")]
+    TextEmbedding3Large,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum OllamaEmbeddingModelSetting {
+    #[default]
+    #[serde(rename = "nomic-embed-text")]
+    NomicEmbedText,
+    #[serde(rename = "mxbai-embed-large")]
+    MxbaiEmbedLarge,
+}
+
+fn default_open_ai_api_url() -> String {
+    open_ai::OPEN_AI_API_URL.to_string()
+}
+
+impl EmbeddingProviderSettings {
+    /// Identifies the provider and model, so that embeddings computed by
+    /// different models are never stored in the same index.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::ZedCloud => "zed_cloud",
+            Self::OpenAi { model, .. } => match model {
+                OpenAiEmbeddingModelSetting::TextEmbedding3Small => {
+                    "open_ai-text-embedding-3-small"
+                }
+                OpenAiEmbeddingModelSetting::TextEmbedding3Large => {
+                    "open_ai-text-embedding-3-large"
+                }
+            },
+            Self::Ollama { model } => match model {
+                OllamaEmbeddingModelSetting::NomicEmbedText => "ollama-nomic-embed-text",
+                OllamaEmbeddingModelSetting::MxbaiEmbedLarge => "ollama-mxbai-embed-large",
+            },
+            Self::LmStudio => "lmstudio-nomic-embed-text",
+        }
+    }
+
+    pub fn build(&self, client: &Arc<Client>) -> Arc<dyn EmbeddingProvider> {
+        match self {
+            Self::ZedCloud => Arc::new(CloudEmbeddingProvider::new(client.clone())),
+            Self::OpenAi { model, api_url } => Arc::new(OpenAiEmbeddingProvider::new(
+                client.http_client(),
+                match model {
+                    OpenAiEmbeddingModelSetting::TextEmbedding3Small => {
+                        OpenAiEmbeddingModel::TextEmbedding3Small
+                    }
+                    OpenAiEmbeddingModelSetting::TextEmbedding3Large => {
+                        OpenAiEmbeddingModel::TextEmbedding3Large
+                    }
+                },
+                api_url.clone(),
+                std::env::var("OPENAI_API_KEY").unwrap_or_default(),
+            )),
+            Self::Ollama { model } => Arc::new(OllamaEmbeddingProvider::new(
+                client.http_client(),
+                match model {
+                    OllamaEmbeddingModelSetting::NomicEmbedText => {
+                        OllamaEmbeddingModel::NomicEmbedText
+                    }
+                    OllamaEmbeddingModelSetting::MxbaiEmbedLarge => {
+                        OllamaEmbeddingModel::MxbaiEmbedLarge
+                    }
+                },
+            )),
+            Self::LmStudio => Arc::new(LmStudioEmbeddingProvider::new(
+                client.http_client(),
+                LmStudioEmbeddingModel::NomicEmbedText,
+            )),
+        }
+    }
+}
+
+impl Settings for SemanticIndexSettings {
+    const KEY: Option<&'static str> = Some("semantic_index");
+
+    type FileContent = Self;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
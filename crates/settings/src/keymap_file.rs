@@ -2,8 +2,8 @@ use anyhow::{anyhow, Result};
 use collections::{BTreeMap, HashMap, IndexMap};
 use fs::Fs;
 use gpui::{
-    Action, ActionBuildError, App, InvalidKeystrokeError, KeyBinding, KeyBindingContextPredicate,
-    NoAction, SharedString, KEYSTROKE_PARSE_EXPECTED_MESSAGE,
+    Action, ActionBuildError, App, Global, InvalidKeystrokeError, KeyBinding,
+    KeyBindingContextPredicate, NoAction, SharedString, KEYSTROKE_PARSE_EXPECTED_MESSAGE,
 };
 use schemars::{
     gen::{SchemaGenerator, SchemaSettings},
@@ -12,7 +12,7 @@ use schemars::{
 };
 use serde::Deserialize;
 use serde_json::Value;
-use std::{any::TypeId, fmt::Write, rc::Rc, sync::Arc, sync::LazyLock};
+use std::{any::TypeId, borrow::Cow, fmt::Write, rc::Rc, sync::Arc, sync::LazyLock};
 use util::{asset_str, markdown::MarkdownString};
 
 use crate::{settings_store::parse_json_with_comments, SettingsAssets};
@@ -36,6 +36,15 @@ pub(crate) static KEY_BINDING_VALIDATORS: LazyLock<BTreeMap<TypeId, Box<dyn KeyB
         validators
     });
 
+/// The placeholder that stands for the user's leader key in binding keystrokes.
+const LEADER_PLACEHOLDER: &str = "leader";
+
+/// The keystrokes that the `leader` placeholder expands to when keymaps are loaded.
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct KeymapLeader(pub Option<SharedString>);
+
+impl Global for KeymapLeader {}
+
 // Note that the doc comments on these are shown by json-language-server when editing the keymap, so
 // they should be considered user-facing documentation. Documentation is not handled well with
 // schemars-0.8 - when there are newlines, it is rendered as plaintext (see
@@ -70,7 +79,7 @@ pub struct KeymapSection {
     /// keystrokes key is a string representing a sequence of keystrokes to type, where the
     /// keystrokes are separated by whitespace. Each keystroke is a sequence of modifiers (`ctrl`,
     /// `alt`, `shift`, `fn`, `cmd`, `super`, or `win`) followed by a key, separated by `-`. The
-    /// order of bindings does matter. When the same keystrokes are bound at the same context depth,
+    /// keystroke `leader` stands for the `leader_key` setting. The order of bindings does matter. When the same keystrokes are bound at the same context depth,
     /// the binding that occurs later in the file is preferred. For displaying keystrokes in the UI,
     /// the later binding for the same action is preferred.
     #[serde(default)]
@@ -384,7 +393,8 @@ impl KeymapFile {
             },
         };
 
-        let key_binding = match KeyBinding::load(keystrokes, action, context, key_equivalents) {
+        let keystrokes = Self::expand_leader(keystrokes, cx)?;
+        let key_binding = match KeyBinding::load(&keystrokes, action, context, key_equivalents) {
             Ok(key_binding) => key_binding,
             Err(InvalidKeystrokeError { keystroke }) => {
                 return Err(format!(
@@ -405,6 +415,40 @@ impl KeymapFile {
         }
     }
 
+    fn expand_leader<'a>(keystrokes: &'a str, cx: &App) -> Result<Cow<'a, str>, String> {
+        if !keystrokes
+            .split_whitespace()
+            .any(|keystroke| keystroke == LEADER_PLACEHOLDER)
+        {
+            return Ok(Cow::Borrowed(keystrokes));
+        }
+
+        let Some(leader) = cx
+            .try_global::<KeymapLeader>()
+            .and_then(|leader| leader.0.clone())
+        else {
+            return Err(format!(
+                "{} is used, but no {} is set in the settings.",
+                inline_code_string(LEADER_PLACEHOLDER),
+                MarkdownString::inline_code("leader_key"),
+            ));
+        };
+
+        Ok(Cow::Owned(
+            keystrokes
+                .split_whitespace()
+                .map(|keystroke| {
+                    if keystroke == LEADER_PLACEHOLDER {
+                        leader.as_ref()
+                    } else {
+                        keystroke
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+        ))
+    }
+
     pub fn generate_json_schema_for_registered_actions(cx: &mut App) -> Value {
         let mut generator = SchemaSettings::draft07()
             .with(|settings| settings.option_add_null_type = false)
@@ -603,6 +647,80 @@ impl KeymapFile {
             }
         }
     }
+
+    /// Appends a section to the user's keymap file that binds `keystrokes` to `action` in the
+    /// given context. An action of `null` disables the keystrokes in that context.
+    pub async fn append_binding_to_keymap_file(
+        fs: &Arc<dyn Fs>,
+        context: Option<&str>,
+        keystrokes: &str,
+        action: Value,
+    ) -> Result<()> {
+        let text = Self::load_keymap_file(fs).await?;
+        let text = Self::append_binding_to_text(&text, context, keystrokes, &action)?;
+        fs.atomic_write(paths::keymap_file().clone(), text).await
+    }
+
+    /// Appends a section with a single binding to the given keymap file contents, preserving
+    /// everything that is already there, including comments.
+    pub fn append_binding_to_text(
+        text: &str,
+        context: Option<&str>,
+        keystrokes: &str,
+        action: &Value,
+    ) -> Result<String> {
+        if !text.trim().is_empty() {
+            Self::parse(text)?;
+        }
+
+        let mut section = String::from("  {\n");
+        if let Some(context) = context {
+            writeln!(
+                section,
+                "    \"context\": {},",
+                serde_json::to_string(context)?
+            )?;
+        }
+        writeln!(section, "    \"bindings\": {{")?;
+        writeln!(
+            section,
+            "      {}: {}",
+            serde_json::to_string(keystrokes)?,
+            serde_json::to_string(action)?
+        )?;
+        write!(section, "    }}\n  }}")?;
+
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&tree_sitter_json::LANGUAGE.into())?;
+        let syntax_tree = parser
+            .parse(text, None)
+            .ok_or_else(|| anyhow!("failed to parse keymap file"))?;
+        let root = syntax_tree.root_node();
+        let mut root_cursor = root.walk();
+        let Some(array) = root
+            .named_children(&mut root_cursor)
+            .find(|node| node.kind() == "array")
+        else {
+            return Ok(format!("{}[\n{section}\n]\n", text.trim_end()));
+        };
+
+        let mut array_cursor = array.walk();
+        let last_element = array
+            .named_children(&mut array_cursor)
+            .filter(|node| node.kind() != "comment")
+            .last();
+
+        let mut new_text = text.to_string();
+        match last_element {
+            Some(last_element) => {
+                new_text.insert_str(last_element.end_byte(), &format!(",\n{section}"));
+            }
+            None => {
+                new_text.insert_str(array.start_byte() + 1, &format!("\n{section}\n"));
+            }
+        }
+        Ok(new_text)
+    }
 }
 
 // Double quotes a string and wraps it in backticks for markdown inline code..
@@ -628,4 +746,52 @@ mod tests {
         };
         KeymapFile::parse(json).unwrap();
     }
+
+    #[test]
+    fn test_append_binding_to_text() {
+        let keymap = indoc::indoc! {r#"
+            [
+              // Comments are preserved
+              {
+                "bindings": {
+                  "up": "menu::SelectPrevious"
+                }
+              }
+            ]
+        "#};
+        let new_keymap = KeymapFile::append_binding_to_text(
+            keymap,
+            Some("Editor && mode == full"),
+            "ctrl-k ctrl-d",
+            &serde_json::json!(["editor::DeleteLine", { "count": 2 }]),
+        )
+        .unwrap();
+        assert_eq!(
+            new_keymap,
+            indoc::indoc! {r#"
+                [
+                  // Comments are preserved
+                  {
+                    "bindings": {
+                      "up": "menu::SelectPrevious"
+                    }
+                  },
+                  {
+                    "context": "Editor && mode == full",
+                    "bindings": {
+                      "ctrl-k ctrl-d": ["editor::DeleteLine",{"count":2}]
+                    }
+                  }
+                ]
+            "#}
+        );
+
+        let new_keymap =
+            KeymapFile::append_binding_to_text("[]", None, "ctrl-a", &serde_json::Value::Null)
+                .unwrap();
+        assert_eq!(
+            new_keymap,
+            "[\n  {\n    \"bindings\": {\n      \"ctrl-a\": null\n    }\n  }\n]"
+        );
+    }
 }
@@ -15,6 +15,7 @@ pub use json_schema::*;
 pub use key_equivalents::*;
 pub use keymap_file::{
     KeyBindingValidator, KeyBindingValidatorRegistration, KeymapFile, KeymapFileLoadResult,
+    KeymapLeader,
 };
 pub use settings_file::*;
 pub use settings_store::{
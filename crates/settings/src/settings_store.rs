@@ -121,6 +121,8 @@ pub struct SettingsSources<'a, T> {
     pub user: Option<&'a T>,
     /// The user settings for the current release channel.
     pub release_channel: Option<&'a T>,
+    /// The user settings from the active settings profile.
+    pub profile: Option<&'a T>,
     /// The server's settings.
     pub server: Option<&'a T>,
     /// The project settings, ordered from least specific to most specific.
@@ -139,6 +141,7 @@ impl<'a, T: Serialize> SettingsSources<'a, T> {
             .into_iter()
             .chain(self.user)
             .chain(self.release_channel)
+            .chain(self.profile)
             .chain(self.server)
             .chain(self.project.iter().copied())
     }
@@ -176,6 +179,7 @@ pub struct SettingsStore {
     setting_values: HashMap<TypeId, Box<dyn AnySettingValue>>,
     raw_default_settings: serde_json::Value,
     raw_user_settings: serde_json::Value,
+    active_profile: Option<String>,
     raw_server_settings: Option<serde_json::Value>,
     raw_extension_settings: serde_json::Value,
     raw_local_settings: BTreeMap<(WorktreeId, Arc<Path>), serde_json::Value>,
@@ -269,6 +273,7 @@ impl SettingsStore {
             setting_values: Default::default(),
             raw_default_settings: serde_json::json!({}),
             raw_user_settings: serde_json::json!({}),
+            active_profile: None,
             raw_server_settings: None,
             raw_extension_settings: serde_json::json!({}),
             raw_local_settings: Default::default(),
@@ -322,6 +327,13 @@ impl SettingsStore {
                     .log_err();
             }
 
+            let profile_value = raw_profile_settings(&self.raw_user_settings, &self.active_profile)
+                .and_then(|profile_settings| {
+                    setting_value
+                        .deserialize_setting(profile_settings)
+                        .log_err()
+                });
+
             let server_value = self
                 .raw_server_settings
                 .as_ref()
@@ -340,6 +352,7 @@ impl SettingsStore {
                         extensions: extension_value.as_ref(),
                         user: user_value.as_ref(),
                         release_channel: release_channel_value.as_ref(),
+                        profile: profile_value.as_ref(),
                         server: server_value.as_ref(),
                         project: &[],
                     },
@@ -384,6 +397,36 @@ impl SettingsStore {
         &self.raw_user_settings
     }
 
+    /// Returns the name of the active settings profile, if any.
+    pub fn active_profile(&self) -> Option<&str> {
+        self.active_profile.as_deref()
+    }
+
+    /// Returns the names of the settings profiles defined in the user's settings.
+    pub fn profile_names(&self) -> Vec<String> {
+        self.raw_user_settings
+            .get("profiles")
+            .and_then(serde_json::Value::as_object)
+            .map(|profiles| profiles.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Activates the settings profile with the given name, whose settings take precedence over the
+    /// rest of the user's settings, or deactivates the active profile if `profile` is `None`.
+    pub fn set_active_profile(&mut self, profile: Option<String>, cx: &mut App) -> Result<()> {
+        if self.active_profile == profile {
+            return Ok(());
+        }
+        self.active_profile = profile;
+        self.recompute_values(None, cx)?;
+        Ok(())
+    }
+
+    /// Access the raw JSON value of the default settings.
+    pub fn raw_default_settings(&self) -> &serde_json::Value {
+        &self.raw_default_settings
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub fn test(cx: &mut App) -> Self {
         let mut this = Self::new(cx);
@@ -434,33 +477,84 @@ impl SettingsStore {
                     let new_text = cx.read_global(|store: &SettingsStore, cx| {
                         store.new_text_for_update::<T>(old_text, |content| update(content, cx))
                     })?;
-                    let settings_path = paths::settings_file().as_path();
-                    if fs.is_file(settings_path).await {
-                        let resolved_path =
-                            fs.canonicalize(settings_path).await.with_context(|| {
-                                format!("Failed to canonicalize settings path {:?}", settings_path)
-                            })?;
-
-                        fs.atomic_write(resolved_path.clone(), new_text)
-                            .await
-                            .with_context(|| {
-                                format!("Failed to write settings to file {:?}", resolved_path)
-                            })?;
-                    } else {
-                        fs.atomic_write(settings_path.to_path_buf(), new_text)
-                            .await
-                            .with_context(|| {
-                                format!("Failed to write settings to file {:?}", settings_path)
-                            })?;
-                    }
+                    Self::write_settings_file(&fs, new_text).await
+                }
+                .boxed_local()
+            }))
+            .ok();
+    }
 
-                    anyhow::Ok(())
+    /// Sets the value at the given key path in the user's settings file, or removes it if `value`
+    /// is `None`.
+    ///
+    /// Unlike [`Self::update_settings_file`], this works on the raw JSON, for callers that don't
+    /// know the type of the setting they're editing.
+    pub fn update_settings_file_at_key_path(
+        &self,
+        fs: Arc<dyn Fs>,
+        key_path: Vec<String>,
+        value: Option<serde_json::Value>,
+    ) {
+        self.setting_file_updates_tx
+            .unbounded_send(Box::new(move |cx: AsyncApp| {
+                async move {
+                    let old_text = Self::load_settings(&fs).await?;
+                    let new_text = cx.read_global(|store: &SettingsStore, _| {
+                        let key_path = key_path.iter().map(String::as_str).collect::<Vec<_>>();
+                        store.new_text_for_key_path(&old_text, &key_path, value.as_ref())
+                    })?;
+                    Self::write_settings_file(&fs, new_text).await
                 }
                 .boxed_local()
             }))
             .ok();
     }
 
+    async fn write_settings_file(fs: &Arc<dyn Fs>, new_text: String) -> Result<()> {
+        let settings_path = paths::settings_file().as_path();
+        if fs.is_file(settings_path).await {
+            let resolved_path = fs.canonicalize(settings_path).await.with_context(|| {
+                format!("Failed to canonicalize settings path {:?}", settings_path)
+            })?;
+
+            fs.atomic_write(resolved_path.clone(), new_text)
+                .await
+                .with_context(|| format!("Failed to write settings to file {:?}", resolved_path))?;
+        } else {
+            fs.atomic_write(settings_path.to_path_buf(), new_text)
+                .await
+                .with_context(|| format!("Failed to write settings to file {:?}", settings_path))?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets the value at the given key path in a JSON file, or removes it if `value` is `None`,
+    /// returning the new text for that JSON file.
+    pub fn new_text_for_key_path(
+        &self,
+        old_text: &str,
+        key_path: &[&str],
+        value: Option<&serde_json::Value>,
+    ) -> String {
+        let mut new_text = old_text.to_string();
+        let edit = match value {
+            Some(value) => Some(replace_value_in_json_text(
+                old_text,
+                key_path,
+                self.json_tab_size(),
+                value,
+            )),
+            None => {
+                remove_value_in_json_text(old_text, key_path).map(|range| (range, String::new()))
+            }
+        };
+        if let Some((range, replacement)) = edit {
+            new_text.replace_range(range, &replacement);
+        }
+        new_text
+    }
+
     /// Updates the value of a setting in a JSON file, returning the new text
     /// for that JSON file.
     pub fn new_text_for_update<T: Settings>(
@@ -761,7 +855,7 @@ impl SettingsStore {
     ) -> serde_json::Value {
         use schemars::{
             gen::SchemaSettings,
-            schema::{Schema, SchemaObject},
+            schema::{InstanceType, ObjectValidation, Schema, SchemaObject},
         };
 
         let settings = SchemaSettings::draft07().with(|settings| {
@@ -849,6 +943,20 @@ impl SettingsStore {
             }
         }
 
+        let profile_schema = combined_schema.schema.clone();
+        combined_schema.schema.object().properties.insert(
+            "profiles".to_string(),
+            SchemaObject {
+                instance_type: Some(InstanceType::Object.into()),
+                object: Some(Box::new(ObjectValidation {
+                    additional_properties: Some(Box::new(profile_schema.into())),
+                    ..Default::default()
+                })),
+                ..Default::default()
+            }
+            .into(),
+        );
+
         for release_stage in ["dev", "nightly", "stable", "preview"] {
             let schema = combined_schema.schema.clone();
             combined_schema
@@ -907,6 +1015,15 @@ impl SettingsStore {
                 }
             }
 
+            let profile_settings =
+                raw_profile_settings(&self.raw_user_settings, &self.active_profile).and_then(
+                    |profile_settings| {
+                        setting_value
+                            .deserialize_setting(profile_settings)
+                            .log_err()
+                    },
+                );
+
             // If the global settings file changed, reload the global value for the field.
             if changed_local_path.is_none() {
                 if let Some(value) = setting_value
@@ -916,6 +1033,7 @@ impl SettingsStore {
                             extensions: extension_settings.as_ref(),
                             user: user_settings.as_ref(),
                             release_channel: release_channel_settings.as_ref(),
+                            profile: profile_settings.as_ref(),
                             server: server_settings.as_ref(),
                             project: &[],
                         },
@@ -967,6 +1085,7 @@ impl SettingsStore {
                                     extensions: extension_settings.as_ref(),
                                     user: user_settings.as_ref(),
                                     release_channel: release_channel_settings.as_ref(),
+                                    profile: profile_settings.as_ref(),
                                     server: server_settings.as_ref(),
                                     project: &project_settings_stack.iter().collect::<Vec<_>>(),
                                 },
@@ -1211,6 +1330,58 @@ fn update_value_in_json_text<'a>(
     }
 }
 
+/// Returns the user settings of the given profile.
+fn raw_profile_settings<'a>(
+    raw_user_settings: &'a serde_json::Value,
+    profile: &Option<String>,
+) -> Option<&'a serde_json::Value> {
+    raw_user_settings.get("profiles")?.get(profile.as_ref()?)
+}
+
+/// Returns the range to delete in order to remove the key at the given path, along with the
+/// comma separating it from its siblings.
+fn remove_value_in_json_text(text: &str, key_path: &[&str]) -> Option<Range<usize>> {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_json::LANGUAGE.into())
+        .unwrap();
+    let syntax_tree = parser.parse(text, None)?;
+
+    let mut object = syntax_tree.root_node().named_child(0)?;
+    let mut pair = None;
+    for key in key_path {
+        if object.kind() != "object" {
+            return None;
+        }
+        let quoted_key = format!("\"{key}\"");
+        let mut cursor = object.walk();
+        let found_pair = object.named_children(&mut cursor).find(|child| {
+            child.kind() == "pair"
+                && child
+                    .child_by_field_name("key")
+                    .and_then(|key| text.get(key.byte_range()))
+                    == Some(quoted_key.as_str())
+        })?;
+        pair = Some((object, found_pair));
+        object = found_pair.child_by_field_name("value")?;
+    }
+
+    let (object, pair) = pair?;
+    let next_pair =
+        std::iter::successors(pair.next_named_sibling(), |node| node.next_named_sibling())
+            .find(|node| node.kind() == "pair");
+    let previous_pair =
+        std::iter::successors(pair.prev_named_sibling(), |node| node.prev_named_sibling())
+            .find(|node| node.kind() == "pair");
+
+    match (previous_pair, next_pair) {
+        (_, Some(next_pair)) => Some(pair.start_byte()..next_pair.start_byte()),
+        (Some(previous_pair), None) => Some(previous_pair.end_byte()..pair.end_byte()),
+        // Removing the only key leaves an empty object.
+        (None, None) => Some(object.start_byte() + 1..object.end_byte() - 1),
+    }
+}
+
 fn replace_value_in_json_text(
     text: &str,
     key_path: &[&str],
@@ -1504,6 +1675,56 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_settings_profiles(cx: &mut App) {
+        let mut store = SettingsStore::new(cx);
+        store.register_setting::<UserSettings>(cx);
+        store
+            .set_default_settings(
+                r#"{
+                    "user": {
+                        "name": "John Doe",
+                        "age": 30,
+                        "staff": false
+                    }
+                }"#,
+                cx,
+            )
+            .unwrap();
+        store
+            .set_user_settings(
+                r#"{
+                    "user": { "age": 31 },
+                    "profiles": {
+                        "staff": {
+                            "user": { "staff": true }
+                        },
+                        "older": {
+                            "user": { "age": 50 }
+                        }
+                    }
+                }"#,
+                cx,
+            )
+            .unwrap();
+
+        assert_eq!(store.profile_names(), vec!["staff", "older"]);
+        assert_eq!(store.get::<UserSettings>(None).age, 31);
+        assert!(!store.get::<UserSettings>(None).staff);
+
+        store.set_active_profile(Some("staff".into()), cx).unwrap();
+        assert_eq!(store.active_profile(), Some("staff"));
+        assert_eq!(store.get::<UserSettings>(None).age, 31);
+        assert!(store.get::<UserSettings>(None).staff);
+
+        store.set_active_profile(Some("older".into()), cx).unwrap();
+        assert_eq!(store.get::<UserSettings>(None).age, 50);
+        assert!(!store.get::<UserSettings>(None).staff);
+
+        store.set_active_profile(None, cx).unwrap();
+        assert_eq!(store.get::<UserSettings>(None).age, 31);
+    }
+
     #[gpui::test]
     fn test_setting_store_assign_json_before_register(cx: &mut App) {
         let mut store = SettingsStore::new(cx);
@@ -1667,6 +1888,81 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_new_text_for_key_path(cx: &mut App) {
+        let store = SettingsStore::new(cx);
+        let text = r#"{
+            // The user's name.
+            "name": "Max",
+            "languages": {
+                "Rust": {
+                    "tab_size": 4
+                }
+            }
+        }"#
+        .unindent();
+
+        // setting a nested value that doesn't exist yet
+        let text = store.new_text_for_key_path(
+            &text,
+            &["languages", "Rust", "hard_tabs"],
+            Some(&serde_json::json!(true)),
+        );
+        pretty_assertions::assert_eq!(
+            text,
+            r#"{
+                // The user's name.
+                "name": "Max",
+                "languages": {
+                    "Rust": {
+                        "hard_tabs": true,
+                        "tab_size": 4
+                    }
+                }
+            }"#
+            .unindent()
+        );
+
+        // replacing an existing value
+        let text =
+            store.new_text_for_key_path(&text, &["name"], Some(&serde_json::json!("Nathan")));
+        assert!(text.contains(r#""name": "Nathan","#));
+
+        // removing the first key of an object
+        let text = store.new_text_for_key_path(&text, &["languages", "Rust", "hard_tabs"], None);
+        pretty_assertions::assert_eq!(
+            text,
+            r#"{
+                // The user's name.
+                "name": "Nathan",
+                "languages": {
+                    "Rust": {
+                        "tab_size": 4
+                    }
+                }
+            }"#
+            .unindent()
+        );
+
+        // removing the only key of an object
+        let text = store.new_text_for_key_path(&text, &["languages", "Rust", "tab_size"], None);
+        assert!(text.contains(r#""Rust": {}"#));
+
+        // removing the last key of an object
+        let text = store.new_text_for_key_path(&text, &["languages"], None);
+        pretty_assertions::assert_eq!(
+            text,
+            r#"{
+                // The user's name.
+                "name": "Nathan"
+            }"#
+            .unindent()
+        );
+
+        // removing a key that doesn't exist
+        assert_eq!(store.new_text_for_key_path(&text, &["age"], None), text);
+    }
+
     fn check_settings_update<T: Settings>(
         store: &mut SettingsStore,
         old_json: String,
@@ -61,7 +61,11 @@ impl EditableSettingControl for ThemeControl {
         settings
             .theme_selection
             .as_ref()
-            .map(|selection| selection.theme(appearance.0).to_string())
+            .map(|selection| {
+                selection
+                    .theme(settings.followed_appearance(selection.mode(), appearance.0))
+                    .to_string()
+            })
             .unwrap_or_else(|| ThemeSettings::default_theme(*appearance).to_string())
     }
 
@@ -70,8 +74,15 @@ impl EditableSettingControl for ThemeControl {
         value: Self::Value,
         cx: &App,
     ) {
-        let appearance = SystemAppearance::global(cx);
-        settings.set_theme(value, appearance.0);
+        let theme_settings = ThemeSettings::get_global(cx);
+        let appearance = theme_settings.followed_appearance(
+            settings
+                .theme
+                .as_ref()
+                .and_then(|selection| selection.mode()),
+            SystemAppearance::global(cx).0,
+        );
+        settings.set_theme(value, appearance);
     }
 }
 
@@ -157,6 +168,14 @@ impl RenderOnce for ThemeModeControl {
                     .on_click(|_, _, cx| Self::write(ThemeMode::System, cx))
                     .middle(),
             )
+            .child(
+                ToggleButton::new("schedule", "Schedule")
+                    .style(ButtonStyle::Filled)
+                    .size(ButtonSize::Large)
+                    .toggle_state(value == ThemeMode::Schedule)
+                    .on_click(|_, _, cx| Self::write(ThemeMode::Schedule, cx))
+                    .middle(),
+            )
             .child(
                 ToggleButton::new("dark", "Dark")
                     .style(ButtonStyle::Filled)
@@ -0,0 +1,579 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use editor::{Editor, EditorEvent};
+use fs::Fs;
+use gpui::{
+    is_no_action, uniform_list, App, Entity, EventEmitter, FocusHandle, Focusable, KeyBinding,
+    KeymapVersion, Keystroke, Subscription, UniformListScrollHandle,
+};
+use serde_json::Value;
+use settings::KeymapFile;
+use ui::{prelude::*, text_for_keystrokes, KeyBinding as KeyBindingLabel, Tooltip};
+use workspace::item::{Item, ItemEvent};
+use workspace::Workspace;
+
+/// A row of the keymap editor, describing one key binding.
+struct BindingRow {
+    binding: KeyBinding,
+    action_name: SharedString,
+    context: Option<SharedString>,
+    status: BindingStatus,
+    /// Whether the action can be written back to the keymap by name alone. Actions that take
+    /// input have to be edited in the keymap file directly.
+    editable: bool,
+}
+
+enum BindingStatus {
+    Active,
+    Disabled,
+    Shadowed { by: SharedString },
+}
+
+enum Recording {
+    /// Recording keystrokes to search for.
+    Search(Vec<Keystroke>),
+    /// Recording new keystrokes for the binding with the given row index.
+    Binding {
+        row_ix: usize,
+        keystrokes: Vec<Keystroke>,
+    },
+}
+
+/// Lists every key binding along with its context, highlighting bindings that are shadowed by
+/// later ones, and lets bindings be rebound or removed by writing to the user's keymap file.
+pub struct KeymapEditor {
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    search_editor: Entity<Editor>,
+    keymap_version: Option<KeymapVersion>,
+    rows: Vec<BindingRow>,
+    matches: Vec<usize>,
+    selected_row: Option<usize>,
+    recording: Option<Recording>,
+    show_only_conflicts: bool,
+    scroll_handle: UniformListScrollHandle,
+    _subscriptions: [Subscription; 2],
+}
+
+impl KeymapEditor {
+    pub fn new(
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        cx.new(|cx| {
+            let search_editor = cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text("Search by action or context…", cx);
+                editor
+            });
+            let search_subscription =
+                cx.subscribe(&search_editor, |this: &mut Self, _, event, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        this.update_matches(cx);
+                    }
+                });
+
+            let this = cx.weak_entity();
+            let interceptor = cx.intercept_keystrokes(move |event, window, cx| {
+                this.update(cx, |this, cx| {
+                    if this.recording.is_some() && this.focus_handle.contains_focused(window, cx) {
+                        this.record_keystroke(&event.keystroke, cx);
+                        cx.stop_propagation();
+                    }
+                })
+                .ok();
+            });
+
+            let mut this = Self {
+                fs,
+                focus_handle: cx.focus_handle(),
+                search_editor,
+                keymap_version: None,
+                rows: Vec::new(),
+                matches: Vec::new(),
+                selected_row: None,
+                recording: None,
+                show_only_conflicts: false,
+                scroll_handle: UniformListScrollHandle::new(),
+                _subscriptions: [search_subscription, interceptor],
+            };
+            this.reload_bindings(cx);
+            this
+        })
+    }
+
+    fn reload_bindings(&mut self, cx: &mut Context<Self>) {
+        let keymap = cx.key_bindings();
+        let keymap = keymap.borrow();
+        if self.keymap_version == Some(keymap.version()) {
+            return;
+        }
+        self.keymap_version = Some(keymap.version());
+
+        let bindings = keymap.bindings().collect::<Vec<_>>();
+        let shadowing = keymap.shadowing_bindings();
+        self.rows = bindings
+            .iter()
+            .zip(shadowing)
+            .map(|(binding, shadowed_by)| {
+                let action_name = binding.action().name();
+                let disabled = is_no_action(binding.action());
+                let status = match shadowed_by {
+                    _ if disabled => BindingStatus::Disabled,
+                    Some(ix) if is_no_action(bindings[ix].action()) => BindingStatus::Disabled,
+                    Some(ix) => BindingStatus::Shadowed {
+                        by: shadowed_by_label(bindings[ix]),
+                    },
+                    None => BindingStatus::Active,
+                };
+                let editable = !disabled
+                    && cx
+                        .build_action(action_name, None)
+                        .is_ok_and(|action| action.partial_eq(binding.action()));
+
+                BindingRow {
+                    binding: (*binding).clone(),
+                    action_name: action_name.to_string().into(),
+                    context: binding
+                        .predicate()
+                        .map(|predicate| predicate.to_string().into()),
+                    status,
+                    editable,
+                }
+            })
+            .collect();
+        drop(keymap);
+
+        self.selected_row = None;
+        self.recording = None;
+        self.update_matches(cx);
+    }
+
+    fn update_matches(&mut self, cx: &mut Context<Self>) {
+        let query = self.search_editor.read(cx).text(cx).to_lowercase();
+        let keystrokes = match &self.recording {
+            Some(Recording::Search(keystrokes)) => keystrokes.as_slice(),
+            _ => &[],
+        };
+
+        self.matches = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| {
+                if self.show_only_conflicts && !matches!(row.status, BindingStatus::Shadowed { .. })
+                {
+                    return false;
+                }
+                if !row.binding.keystrokes().starts_with(keystrokes) {
+                    return false;
+                }
+                query.is_empty()
+                    || row.action_name.to_lowercase().contains(&query)
+                    || row
+                        .context
+                        .as_ref()
+                        .is_some_and(|context| context.to_lowercase().contains(&query))
+            })
+            .map(|(ix, _)| ix)
+            .rev()
+            .collect();
+        cx.notify();
+    }
+
+    fn record_keystroke(&mut self, keystroke: &Keystroke, cx: &mut Context<Self>) {
+        match keystroke.key.as_str() {
+            "escape" => {
+                self.stop_recording(cx);
+                return;
+            }
+            "enter" => {
+                if let Some(Recording::Binding { row_ix, keystrokes }) = self.recording.take() {
+                    self.rebind(row_ix, keystrokes, cx);
+                }
+                self.update_matches(cx);
+                return;
+            }
+            _ => {}
+        }
+
+        match &mut self.recording {
+            Some(Recording::Search(keystrokes)) => {
+                keystrokes.push(keystroke.clone());
+                self.update_matches(cx);
+            }
+            Some(Recording::Binding { keystrokes, .. }) => {
+                keystrokes.push(keystroke.clone());
+                cx.notify();
+            }
+            None => {}
+        }
+    }
+
+    fn toggle_search_recording(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if matches!(self.recording, Some(Recording::Search(_))) {
+            self.stop_recording(cx);
+        } else {
+            self.recording = Some(Recording::Search(Vec::new()));
+            window.focus(&self.focus_handle);
+            self.update_matches(cx);
+        }
+    }
+
+    fn start_rebinding(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.selected_row = Some(row_ix);
+        self.recording = Some(Recording::Binding {
+            row_ix,
+            keystrokes: Vec::new(),
+        });
+        window.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    fn stop_recording(&mut self, cx: &mut Context<Self>) {
+        self.recording = None;
+        self.update_matches(cx);
+    }
+
+    /// Disables the binding's current keystrokes in its context and binds the action to the new
+    /// keystrokes instead.
+    fn rebind(&mut self, row_ix: usize, keystrokes: Vec<Keystroke>, cx: &mut Context<Self>) {
+        let Some(row) = self.rows.get(row_ix) else {
+            return;
+        };
+        if keystrokes.is_empty() || !row.editable {
+            return;
+        }
+
+        let context = row.context.clone();
+        let old_keystrokes = unparse_keystrokes(row.binding.keystrokes());
+        let new_keystrokes = unparse_keystrokes(&keystrokes);
+        let action = Value::String(row.action_name.to_string());
+        let fs = self.fs.clone();
+        cx.background_spawn(async move {
+            let context = context.as_deref();
+            KeymapFile::append_binding_to_keymap_file(&fs, context, &old_keystrokes, Value::Null)
+                .await?;
+            KeymapFile::append_binding_to_keymap_file(&fs, context, &new_keystrokes, action).await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn remove_binding(&mut self, row_ix: usize, cx: &mut Context<Self>) {
+        let Some(row) = self.rows.get(row_ix) else {
+            return;
+        };
+        let context = row.context.clone();
+        let keystrokes = unparse_keystrokes(row.binding.keystrokes());
+        let fs = self.fs.clone();
+        cx.background_spawn(async move {
+            KeymapFile::append_binding_to_keymap_file(
+                &fs,
+                context.as_deref(),
+                &keystrokes,
+                Value::Null,
+            )
+            .await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn render_row(&self, row_ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let row = &self.rows[row_ix];
+        let is_selected = self.selected_row == Some(row_ix);
+        let recorded_keystrokes = match &self.recording {
+            Some(Recording::Binding {
+                row_ix: recording_ix,
+                keystrokes,
+            }) if *recording_ix == row_ix => Some(keystrokes),
+            _ => None,
+        };
+
+        let keystrokes = match recorded_keystrokes {
+            Some(keystrokes) if keystrokes.is_empty() => Label::new("Type keystrokes, then enter…")
+                .size(LabelSize::Small)
+                .color(Color::Accent)
+                .into_any_element(),
+            Some(keystrokes) => Label::new(text_for_keystrokes(keystrokes, cx))
+                .size(LabelSize::Small)
+                .color(Color::Accent)
+                .into_any_element(),
+            None => KeyBindingLabel::new(row.binding.clone(), cx).into_any_element(),
+        };
+
+        let status = match &row.status {
+            BindingStatus::Active => None,
+            BindingStatus::Disabled => Some(
+                Label::new("Disabled")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .into_any_element(),
+            ),
+            BindingStatus::Shadowed { by } => Some(
+                div()
+                    .id(("shadowed", row_ix))
+                    .child(
+                        Label::new("Shadowed")
+                            .size(LabelSize::Small)
+                            .color(Color::Warning),
+                    )
+                    .tooltip(Tooltip::text(format!("Overridden by {by}")))
+                    .into_any_element(),
+            ),
+        };
+
+        h_flex()
+            .id(("binding", row_ix))
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_4()
+            .when(is_selected, |this| {
+                this.bg(cx.theme().colors().element_selected)
+            })
+            .when(
+                matches!(row.status, BindingStatus::Shadowed { .. }),
+                |this| {
+                    this.border_l_2()
+                        .border_color(cx.theme().status().warning_border)
+                },
+            )
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.selected_row = Some(row_ix);
+                cx.notify();
+            }))
+            .child(div().w(rems(14.)).child(keystrokes))
+            .child(
+                div()
+                    .flex_1()
+                    .child(Label::new(row.action_name.clone()).truncate()),
+            )
+            .child(
+                div().w(rems(18.)).child(
+                    Label::new(row.context.clone().unwrap_or_else(|| "(global)".into()))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .truncate(),
+                ),
+            )
+            .child(div().w(rems(5.)).children(status))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new(("rebind", row_ix), IconName::Pencil)
+                            .icon_size(IconSize::Small)
+                            .disabled(!row.editable)
+                            .tooltip(Tooltip::text(if row.editable {
+                                "Record new keystrokes"
+                            } else {
+                                "This action takes input, edit it in keymap.json"
+                            }))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.start_rebinding(row_ix, window, cx)
+                            })),
+                    )
+                    .child(
+                        IconButton::new(("remove", row_ix), IconName::Trash)
+                            .icon_size(IconSize::Small)
+                            .disabled(matches!(row.status, BindingStatus::Disabled))
+                            .tooltip(Tooltip::text("Disable this binding"))
+                            .on_click(
+                                cx.listener(move |this, _, _, cx| this.remove_binding(row_ix, cx)),
+                            ),
+                    ),
+            )
+            .into_any_element()
+    }
+
+    fn render_rows(&mut self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        self.matches[range]
+            .to_vec()
+            .into_iter()
+            .map(|row_ix| self.render_row(row_ix, cx))
+            .collect()
+    }
+}
+
+fn shadowed_by_label(binding: &KeyBinding) -> SharedString {
+    match binding.predicate() {
+        Some(predicate) => format!("{} in {}", binding.action().name(), predicate).into(),
+        None => binding.action().name().to_string().into(),
+    }
+}
+
+fn unparse_keystrokes(keystrokes: &[Keystroke]) -> String {
+    keystrokes
+        .iter()
+        .map(|keystroke| keystroke.unparse())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl EventEmitter<ItemEvent> for KeymapEditor {}
+
+impl Focusable for KeymapEditor {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for KeymapEditor {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Keyboard))
+    }
+
+    fn tab_content_text(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some("Key Bindings".into())
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+impl Render for KeymapEditor {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.reload_bindings(cx);
+
+        let conflict_count = self
+            .rows
+            .iter()
+            .filter(|row| matches!(row.status, BindingStatus::Shadowed { .. }))
+            .count();
+        let search_keystrokes = match &self.recording {
+            Some(Recording::Search(keystrokes)) => Some(keystrokes),
+            _ => None,
+        };
+
+        v_flex()
+            .key_context("KeymapEditor")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_4()
+            .gap_2()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex_1()
+                            .px_2()
+                            .py_1()
+                            .border_1()
+                            .rounded_md()
+                            .border_color(cx.theme().colors().border)
+                            .child(self.search_editor.clone()),
+                    )
+                    .child(
+                        Button::new(
+                            "search-by-keystroke",
+                            match search_keystrokes {
+                                Some(keystrokes) if keystrokes.is_empty() => {
+                                    "Type keystrokes…".to_string()
+                                }
+                                Some(keystrokes) => text_for_keystrokes(keystrokes, cx),
+                                None => "Search by Keystroke".to_string(),
+                            },
+                        )
+                        .toggle_state(search_keystrokes.is_some())
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.toggle_search_recording(window, cx)
+                        })),
+                    )
+                    .child(
+                        Button::new(
+                            "show-conflicts",
+                            format!("Shadowed Bindings ({conflict_count})"),
+                        )
+                        .toggle_state(self.show_only_conflicts)
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.show_only_conflicts = !this.show_only_conflicts;
+                            this.update_matches(cx);
+                        })),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .px_2()
+                    .gap_4()
+                    .child(
+                        div().w(rems(14.)).child(
+                            Label::new("Keystrokes")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                    )
+                    .child(
+                        div().flex_1().child(
+                            Label::new("Action")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                    )
+                    .child(
+                        div().w(rems(18.)).child(
+                            Label::new("Context")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                    )
+                    .child(div().w(rems(5.))),
+            )
+            .child(
+                uniform_list(
+                    cx.entity(),
+                    "keymap-editor-bindings",
+                    self.matches.len(),
+                    |this, range, _, cx| this.render_rows(range, cx),
+                )
+                .track_scroll(self.scroll_handle.clone())
+                .flex_1(),
+            )
+            .when_some(self.selected_row, |this, row_ix| {
+                let Some(row) = self.rows.get(row_ix) else {
+                    return this;
+                };
+                let keystrokes = unparse_keystrokes(row.binding.keystrokes());
+                this.child(
+                    Label::new(format!(
+                        "\"{keystrokes}\": \"{}\"{}",
+                        row.action_name,
+                        row.context
+                            .as_ref()
+                            .map(|context| format!(" in \"{context}\""))
+                            .unwrap_or_default()
+                    ))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+                )
+            })
+    }
+}
+
+impl KeymapEditor {
+    /// Opens the keymap editor, or activates it if it's already open in the active pane.
+    pub fn open(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .find_map(|item| item.downcast::<KeymapEditor>());
+
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+        } else {
+            let keymap_editor = KeymapEditor::new(workspace, window, cx);
+            workspace.add_item_to_active_pane(Box::new(keymap_editor), None, true, window, cx);
+        }
+    }
+}
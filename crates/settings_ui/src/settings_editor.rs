@@ -0,0 +1,768 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use editor::{Editor, EditorEvent, EditorSettingsControls};
+use fs::Fs;
+use gpui::{
+    uniform_list, App, Entity, EventEmitter, FocusHandle, Focusable, Subscription,
+    UniformListScrollHandle, WeakEntity,
+};
+use serde_json::Value;
+use settings::{initial_project_settings_content, SettingsJsonSchemaParams, SettingsStore};
+use ui::{prelude::*, ContextMenu, DropdownMenu, Switch, ToggleState, Tooltip};
+use workspace::item::{Item, ItemEvent};
+use workspace::Workspace;
+
+use crate::appearance_settings_controls::AppearanceSettingsControls;
+
+/// Settings nested deeper than this are edited as a whole object.
+const MAX_KEY_PATH_DEPTH: usize = 3;
+
+/// A setting described by the settings schema.
+struct SettingEntry {
+    /// The path of the setting, relative to the root of the settings file or, for language
+    /// settings, to the language's entry in `languages`.
+    key_path: Vec<String>,
+    key: SharedString,
+    description: Option<SharedString>,
+    kind: SettingKind,
+}
+
+enum SettingKind {
+    Boolean,
+    Enum(Vec<Value>),
+    Other,
+}
+
+#[derive(Clone, PartialEq)]
+enum SettingsTab {
+    Common,
+    All,
+    Language(SharedString),
+}
+
+/// A graphical editor for the settings files, generated from the settings schema.
+///
+/// The JSON files remain the source of truth: every change is written straight to them, and the
+/// editor re-renders whenever they are reloaded.
+pub struct SettingsEditor {
+    fs: Arc<dyn Fs>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    search_editor: Entity<Editor>,
+    value_editor: Entity<Editor>,
+    global_entries: Vec<SettingEntry>,
+    language_entries: Vec<SettingEntry>,
+    language_names: Vec<SharedString>,
+    active_tab: SettingsTab,
+    matches: Vec<usize>,
+    /// The index of the entry whose value is being edited as JSON.
+    editing_entry: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl SettingsEditor {
+    pub fn new(
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let language_names = workspace.app_state().languages.language_names();
+        let workspace = cx.weak_entity();
+        cx.new(|cx| {
+            let schema = cx.global::<SettingsStore>().json_schema(
+                &SettingsJsonSchemaParams {
+                    language_names: &language_names,
+                    font_names: &cx.text_system().all_font_names(),
+                },
+                cx,
+            );
+            let definitions = schema.get("definitions").unwrap_or(&Value::Null);
+            // Language settings get their own tabs, and release channel overrides repeat the
+            // whole schema.
+            let global_entries = setting_entries(
+                &schema,
+                definitions,
+                &["languages", "dev", "nightly", "stable", "preview"],
+            );
+            let language_entries = definitions
+                .get("LanguageSettingsContent")
+                .map(|schema| setting_entries(schema, definitions, &[]))
+                .unwrap_or_default();
+
+            let search_editor = cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text("Search settings…", cx);
+                editor
+            });
+            let value_editor = cx.new(|cx| Editor::single_line(window, cx));
+            let subscriptions = vec![
+                cx.subscribe(&search_editor, |this: &mut Self, _, event, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        if this.active_tab == SettingsTab::Common {
+                            this.active_tab = SettingsTab::All;
+                        }
+                        this.update_matches(cx);
+                    }
+                }),
+                cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+            ];
+
+            let mut this = Self {
+                fs,
+                workspace,
+                focus_handle: cx.focus_handle(),
+                search_editor,
+                value_editor,
+                global_entries,
+                language_entries,
+                language_names: language_names.into_iter().map(Into::into).collect(),
+                active_tab: SettingsTab::Common,
+                matches: Vec::new(),
+                editing_entry: None,
+                scroll_handle: UniformListScrollHandle::new(),
+                _subscriptions: subscriptions,
+            };
+            this.update_matches(cx);
+            this
+        })
+    }
+
+    /// Opens the settings editor, or activates it if it's already open in the active pane.
+    pub fn open(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .find_map(|item| item.downcast::<SettingsEditor>());
+
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+        } else {
+            let settings_editor = SettingsEditor::new(workspace, window, cx);
+            workspace.add_item_to_active_pane(Box::new(settings_editor), None, true, window, cx);
+        }
+    }
+
+    fn entries(&self) -> &[SettingEntry] {
+        match self.active_tab {
+            SettingsTab::Common => &[],
+            SettingsTab::All => &self.global_entries,
+            SettingsTab::Language(_) => &self.language_entries,
+        }
+    }
+
+    fn update_matches(&mut self, cx: &mut Context<Self>) {
+        let query = self.search_editor.read(cx).text(cx).to_lowercase();
+        self.matches = self
+            .entries()
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                query.is_empty()
+                    || entry.key.to_lowercase().contains(&query)
+                    || entry
+                        .description
+                        .as_ref()
+                        .is_some_and(|description| description.to_lowercase().contains(&query))
+            })
+            .map(|(ix, _)| ix)
+            .collect();
+        self.editing_entry = None;
+        cx.notify();
+    }
+
+    fn activate_tab(&mut self, tab: SettingsTab, cx: &mut Context<Self>) {
+        self.active_tab = tab;
+        self.update_matches(cx);
+    }
+
+    /// The path of the entry within the settings file, taking the active language into account.
+    fn full_key_path(&self, entry: &SettingEntry) -> Vec<String> {
+        match &self.active_tab {
+            SettingsTab::Language(language) => ["languages".to_string(), language.to_string()]
+                .into_iter()
+                .chain(entry.key_path.iter().cloned())
+                .collect(),
+            _ => entry.key_path.clone(),
+        }
+    }
+
+    fn user_value(&self, entry: &SettingEntry, cx: &App) -> Option<Value> {
+        let settings = cx.global::<SettingsStore>().raw_user_settings();
+        value_at_key_path(settings, &self.full_key_path(entry)).cloned()
+    }
+
+    /// The default value of the entry. Language settings that have no language-specific default
+    /// fall back to the global default.
+    fn default_value(&self, entry: &SettingEntry, cx: &App) -> Option<Value> {
+        let settings = cx.global::<SettingsStore>().raw_default_settings();
+        value_at_key_path(settings, &self.full_key_path(entry))
+            .or_else(|| value_at_key_path(settings, &entry.key_path))
+            .cloned()
+    }
+
+    fn set_value(&mut self, entry_ix: usize, value: Option<Value>, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries().get(entry_ix) else {
+            return;
+        };
+        let key_path = self.full_key_path(entry);
+        cx.global::<SettingsStore>()
+            .update_settings_file_at_key_path(self.fs.clone(), key_path, value);
+    }
+
+    fn start_editing(&mut self, entry_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries().get(entry_ix) else {
+            return;
+        };
+        let value = self
+            .user_value(entry, cx)
+            .or_else(|| self.default_value(entry, cx))
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+        self.value_editor.update(cx, |editor, cx| {
+            editor.set_text(value, window, cx);
+            editor.select_all(&editor::actions::SelectAll, window, cx);
+        });
+        self.editing_entry = Some(entry_ix);
+        window.focus(&self.value_editor.focus_handle(cx));
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry_ix) = self.editing_entry.take() else {
+            return;
+        };
+        let text = self.value_editor.read(cx).text(cx);
+        // Anything that isn't valid JSON is written as a string, so that string settings can be
+        // typed without quotes.
+        let value = serde_json::from_str(&text).unwrap_or(Value::String(text));
+        self.set_value(entry_ix, Some(value), cx);
+        window.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing_entry.take().is_some() {
+            window.focus(&self.focus_handle);
+            cx.notify();
+        }
+    }
+
+    /// Writes the entry's current value to the settings file of the first folder in the project,
+    /// so that it applies to everyone working on it.
+    fn copy_to_project_settings(&mut self, entry_ix: usize, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries().get(entry_ix) else {
+            return;
+        };
+        let Some(value) = self
+            .user_value(entry, cx)
+            .or_else(|| self.default_value(entry, cx))
+        else {
+            return;
+        };
+        let Some(settings_path) = self.project_settings_path(cx) else {
+            return;
+        };
+        let key_path = self.full_key_path(entry);
+        let fs = self.fs.clone();
+        cx.spawn(async move |_, cx| {
+            let old_text = if fs.is_file(&settings_path).await {
+                fs.load(&settings_path).await?
+            } else {
+                initial_project_settings_content().to_string()
+            };
+            let new_text = cx.read_global(|store: &SettingsStore, _| {
+                let key_path = key_path.iter().map(String::as_str).collect::<Vec<_>>();
+                store.new_text_for_key_path(&old_text, &key_path, Some(&value))
+            })?;
+            if let Some(settings_dir) = settings_path.parent() {
+                fs.create_dir(settings_dir).await?;
+            }
+            fs.atomic_write(settings_path, new_text).await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn project_settings_path(&self, cx: &App) -> Option<std::path::PathBuf> {
+        let workspace = self.workspace.upgrade()?;
+        let project = workspace.read(cx).project().read(cx);
+        let worktree = project.visible_worktrees(cx).find(|worktree| {
+            let worktree = worktree.read(cx);
+            worktree.is_local() && worktree.root_entry().is_some_and(|entry| entry.is_dir())
+        })?;
+        Some(
+            worktree
+                .read(cx)
+                .abs_path()
+                .join(paths::local_settings_file_relative_path()),
+        )
+    }
+
+    fn render_value_control(
+        &self,
+        entry_ix: usize,
+        entry: &SettingEntry,
+        value: Option<&Value>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        if self.editing_entry == Some(entry_ix) {
+            return div()
+                .w(rems(16.))
+                .px_1()
+                .border_1()
+                .rounded_sm()
+                .border_color(cx.theme().colors().border_focused)
+                .child(self.value_editor.clone())
+                .into_any_element();
+        }
+
+        match &entry.kind {
+            SettingKind::Boolean => {
+                let is_enabled = value.and_then(Value::as_bool).unwrap_or(false);
+                Switch::new(("setting-switch", entry_ix), is_enabled.into())
+                    .on_click(cx.listener(move |this, state: &ToggleState, _, cx| {
+                        let is_enabled = *state == ToggleState::Selected;
+                        this.set_value(entry_ix, Some(Value::Bool(is_enabled)), cx);
+                    }))
+                    .into_any_element()
+            }
+            SettingKind::Enum(values) => {
+                let this = cx.weak_entity();
+                let values = values.clone();
+                DropdownMenu::new(
+                    ("setting-dropdown", entry_ix),
+                    value.map(value_label).unwrap_or_default(),
+                    ContextMenu::build(window, cx, move |mut menu, _, _| {
+                        for value in values {
+                            let this = this.clone();
+                            menu = menu.entry(value_label(&value), None, move |_, cx| {
+                                this.update(cx, |this, cx| {
+                                    this.set_value(entry_ix, Some(value.clone()), cx)
+                                })
+                                .ok();
+                            });
+                        }
+                        menu
+                    }),
+                )
+                .into_any_element()
+            }
+            SettingKind::Other => {
+                h_flex()
+                    .id(("setting-value", entry_ix))
+                    .w(rems(16.))
+                    .gap_1()
+                    .cursor_pointer()
+                    .child(
+                        Label::new(value.map(value_label).unwrap_or_default())
+                            .size(LabelSize::Small)
+                            .truncate(),
+                    )
+                    .tooltip(Tooltip::text("Edit as JSON"))
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.start_editing(entry_ix, window, cx)
+                    }))
+                    .into_any_element()
+            }
+        }
+    }
+
+    fn render_entry(
+        &self,
+        entry_ix: usize,
+        has_project: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let entry = &self.entries()[entry_ix];
+        let user_value = self.user_value(entry, cx);
+        let default_value = self.default_value(entry, cx);
+        let is_modified = user_value.is_some();
+        let value = user_value.as_ref().or(default_value.as_ref());
+
+        let status = if is_modified {
+            div()
+                .id(("setting-modified", entry_ix))
+                .child(
+                    Label::new("Modified")
+                        .size(LabelSize::Small)
+                        .color(Color::Accent),
+                )
+                .tooltip(Tooltip::text(format!(
+                    "Default: {}",
+                    default_value.as_ref().map(value_label).unwrap_or_default()
+                )))
+        } else {
+            div().id(("setting-modified", entry_ix)).child(
+                Label::new("Default")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+        };
+
+        h_flex()
+            .id(("setting", entry_ix))
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_4()
+            .when(is_modified, |this| {
+                this.border_l_2()
+                    .border_color(cx.theme().colors().text_accent)
+            })
+            .child(
+                v_flex()
+                    .flex_1()
+                    .overflow_hidden()
+                    .child(Label::new(entry.key.clone()).truncate())
+                    .child(
+                        div()
+                            .id(("setting-description", entry_ix))
+                            .child(
+                                Label::new(entry.description.clone().unwrap_or_default())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .truncate(),
+                            )
+                            .when_some(entry.description.clone(), |this, description| {
+                                this.tooltip(Tooltip::text(description))
+                            }),
+                    ),
+            )
+            .child(self.render_value_control(entry_ix, entry, value, window, cx))
+            .child(div().w(rems(5.)).child(status))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new(("reset-setting", entry_ix), IconName::RotateCcw)
+                            .icon_size(IconSize::Small)
+                            .disabled(!is_modified)
+                            .tooltip(Tooltip::text("Reset to Default"))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.set_value(entry_ix, None, cx)
+                            })),
+                    )
+                    .child(
+                        IconButton::new(("copy-to-project", entry_ix), IconName::Copy)
+                            .icon_size(IconSize::Small)
+                            .disabled(!has_project)
+                            .tooltip(Tooltip::text(if has_project {
+                                "Copy to Project Settings"
+                            } else {
+                                "Open a folder to copy settings to its project settings"
+                            }))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.copy_to_project_settings(entry_ix, cx)
+                            })),
+                    ),
+            )
+            .into_any_element()
+    }
+
+    fn render_entries(
+        &mut self,
+        range: Range<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        let has_project = self.project_settings_path(cx).is_some();
+        self.matches[range]
+            .to_vec()
+            .into_iter()
+            .map(|entry_ix| self.render_entry(entry_ix, has_project, window, cx))
+            .collect()
+    }
+
+    fn render_tabs(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let mut tab_button = |id: &'static str, label: &'static str, tab: SettingsTab| {
+            Button::new(id, label)
+                .toggle_state(self.active_tab == tab)
+                .on_click(cx.listener(move |this, _, _, cx| this.activate_tab(tab.clone(), cx)))
+        };
+        let language_tab = match &self.active_tab {
+            SettingsTab::Language(language) => language.clone(),
+            _ => self.language_names.first().cloned().unwrap_or_default(),
+        };
+
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(tab_button("common-settings", "Common", SettingsTab::Common))
+                    .child(tab_button("all-settings", "All Settings", SettingsTab::All))
+                    .child(tab_button(
+                        "language-settings",
+                        "Languages",
+                        SettingsTab::Language(language_tab),
+                    )),
+            )
+            .when(
+                matches!(self.active_tab, SettingsTab::Language(_)),
+                |this| {
+                    this.child(
+                        h_flex()
+                            .id("language-tabs")
+                            .gap_1()
+                            .overflow_x_scroll()
+                            .children(self.language_names.iter().enumerate().map(
+                                |(ix, language)| {
+                                    let tab = SettingsTab::Language(language.clone());
+                                    Button::new(("language-tab", ix), language.clone())
+                                        .label_size(LabelSize::Small)
+                                        .toggle_state(self.active_tab == tab)
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.activate_tab(tab.clone(), cx)
+                                        }))
+                                },
+                            )),
+                    )
+                },
+            )
+    }
+}
+
+/// Flattens the properties of a settings schema into a list of settings, sorted by key.
+fn setting_entries(
+    schema: &Value,
+    definitions: &Value,
+    excluded_keys: &[&str],
+) -> Vec<SettingEntry> {
+    fn collect(
+        schema: &Value,
+        definitions: &Value,
+        key_path: &mut Vec<String>,
+        entries: &mut Vec<SettingEntry>,
+    ) {
+        let resolved = resolve_schema(schema, definitions);
+        match resolved.get("properties").and_then(Value::as_object) {
+            Some(properties) if !properties.is_empty() && key_path.len() < MAX_KEY_PATH_DEPTH => {
+                for (key, property) in properties {
+                    key_path.push(key.clone());
+                    collect(property, definitions, key_path, entries);
+                    key_path.pop();
+                }
+            }
+            _ if !key_path.is_empty() => entries.push(SettingEntry {
+                key_path: key_path.clone(),
+                key: key_path.join(".").into(),
+                description: schema
+                    .get("description")
+                    .or_else(|| resolved.get("description"))
+                    .and_then(Value::as_str)
+                    .map(|description| description.trim().to_string().into()),
+                kind: setting_kind(resolved, definitions),
+            }),
+            _ => {}
+        }
+    }
+
+    let mut entries = Vec::new();
+    let resolved = resolve_schema(schema, definitions);
+    if let Some(properties) = resolved.get("properties").and_then(Value::as_object) {
+        for (key, property) in properties {
+            if !excluded_keys.contains(&key.as_str()) {
+                collect(property, definitions, &mut vec![key.clone()], &mut entries);
+            }
+        }
+    }
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    entries
+}
+
+/// Follows references, single-element `allOf`s and nullable `anyOf`s to the schema that
+/// describes the value itself.
+fn resolve_schema<'a>(mut schema: &'a Value, definitions: &'a Value) -> &'a Value {
+    // Guards against recursive definitions.
+    for _ in 0..16 {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            match reference
+                .strip_prefix("#/definitions/")
+                .and_then(|name| definitions.get(name))
+            {
+                Some(definition) => {
+                    schema = definition;
+                    continue;
+                }
+                None => break,
+            }
+        }
+
+        let subschemas = ["allOf", "anyOf"]
+            .iter()
+            .find_map(|key| schema.get(key)?.as_array());
+        if let Some(subschemas) = subschemas {
+            let mut non_null = subschemas.iter().filter(|schema| !is_null_schema(schema));
+            if let (Some(subschema), None) = (non_null.next(), non_null.next()) {
+                schema = subschema;
+                continue;
+            }
+        }
+        break;
+    }
+    schema
+}
+
+fn is_null_schema(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+}
+
+fn setting_kind(schema: &Value, definitions: &Value) -> SettingKind {
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return SettingKind::Enum(values.iter().filter(|v| !v.is_null()).cloned().collect());
+    }
+
+    // Enums with documented variants are described as one schema per variant.
+    let variants = ["oneOf", "anyOf"]
+        .iter()
+        .find_map(|key| schema.get(key)?.as_array());
+    if let Some(variants) = variants {
+        let values = variants
+            .iter()
+            .filter(|variant| !is_null_schema(variant))
+            .map(|variant| {
+                let variant = resolve_schema(variant, definitions);
+                match variant.get("enum").and_then(Value::as_array)?.as_slice() {
+                    [value] => Some(value.clone()),
+                    _ => None,
+                }
+                .or_else(|| variant.get("const").cloned())
+            })
+            .collect::<Option<Vec<_>>>();
+        if let Some(values) = values.filter(|values| !values.is_empty()) {
+            return SettingKind::Enum(values);
+        }
+    }
+
+    let mut types = match schema.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    types.retain(|ty| *ty != "null");
+    if types == ["boolean"] {
+        SettingKind::Boolean
+    } else {
+        SettingKind::Other
+    }
+}
+
+fn value_at_key_path<'a>(mut value: &'a Value, key_path: &[String]) -> Option<&'a Value> {
+    for key in key_path {
+        value = value.get(key)?;
+    }
+    Some(value).filter(|value| !value.is_null())
+}
+
+fn value_label(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+impl EventEmitter<ItemEvent> for SettingsEditor {}
+
+impl Focusable for SettingsEditor {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for SettingsEditor {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Settings))
+    }
+
+    fn tab_content_text(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some("Settings".into())
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+impl Render for SettingsEditor {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = if self.active_tab == SettingsTab::Common {
+            v_flex()
+                .gap_4()
+                .child(
+                    v_flex().gap_1().child(Label::new("Appearance")).child(
+                        v_flex()
+                            .elevation_2(cx)
+                            .child(AppearanceSettingsControls::new()),
+                    ),
+                )
+                .child(
+                    v_flex().gap_1().child(Label::new("Editor")).child(
+                        v_flex()
+                            .elevation_2(cx)
+                            .child(EditorSettingsControls::new()),
+                    ),
+                )
+                .into_any_element()
+        } else if self.matches.is_empty() {
+            Label::new("No matching settings")
+                .color(Color::Muted)
+                .into_any_element()
+        } else {
+            uniform_list(
+                cx.entity(),
+                "settings-editor-entries",
+                self.matches.len(),
+                |this, range, window, cx| this.render_entries(range, window, cx),
+            )
+            .track_scroll(self.scroll_handle.clone())
+            .flex_1()
+            .into_any_element()
+        };
+
+        v_flex()
+            .key_context("SettingsEditor")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .size_full()
+            .p_4()
+            .gap_2()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new("Settings").size(LabelSize::Large))
+                    .child(div().flex_1())
+                    .child(
+                        Button::new("open-settings-json", "Open settings.json")
+                            .icon(IconName::FileCode)
+                            .icon_position(IconPosition::Start)
+                            .icon_size(IconSize::Small)
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(Box::new(zed_actions::OpenSettings), cx)
+                            }),
+                    ),
+            )
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .border_1()
+                    .rounded_md()
+                    .border_color(cx.theme().colors().border)
+                    .child(self.search_editor.clone()),
+            )
+            .child(self.render_tabs(cx))
+            .child(content)
+    }
+}
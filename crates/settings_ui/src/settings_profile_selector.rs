@@ -0,0 +1,225 @@
+use std::sync::Arc;
+
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{App, DismissEvent, Entity, EventEmitter, Focusable, Task, WeakEntity};
+use picker::{Picker, PickerDelegate};
+use settings::SettingsStore;
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::{ModalView, Workspace};
+
+const NO_PROFILE_LABEL: &str = "No Profile";
+
+pub struct SettingsProfileSelector {
+    picker: Entity<Picker<SettingsProfileSelectorDelegate>>,
+}
+
+impl SettingsProfileSelector {
+    pub fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let workspace_handle = cx.entity().downgrade();
+        let active_profile = workspace.settings_profile(cx);
+        workspace.toggle_modal(window, cx, |window, cx| {
+            let delegate = SettingsProfileSelectorDelegate::new(
+                cx.entity().downgrade(),
+                workspace_handle,
+                active_profile,
+                cx,
+            );
+            let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+            Self { picker }
+        });
+    }
+}
+
+impl EventEmitter<DismissEvent> for SettingsProfileSelector {}
+
+impl Focusable for SettingsProfileSelector {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl ModalView for SettingsProfileSelector {}
+
+impl Render for SettingsProfileSelector {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(24.)).child(self.picker.clone())
+    }
+}
+
+struct SettingsProfileSelectorDelegate {
+    selector: WeakEntity<SettingsProfileSelector>,
+    workspace: WeakEntity<Workspace>,
+    /// The profiles to choose from, starting with `None` for no profile.
+    profiles: Vec<Option<String>>,
+    active_profile: Option<String>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl SettingsProfileSelectorDelegate {
+    fn new(
+        selector: WeakEntity<SettingsProfileSelector>,
+        workspace: WeakEntity<Workspace>,
+        active_profile: Option<String>,
+        cx: &App,
+    ) -> Self {
+        let profiles = std::iter::once(None)
+            .chain(
+                cx.global::<SettingsStore>()
+                    .profile_names()
+                    .into_iter()
+                    .map(Some),
+            )
+            .collect::<Vec<_>>();
+        let matches = profiles
+            .iter()
+            .enumerate()
+            .map(|(candidate_id, profile)| StringMatch {
+                candidate_id,
+                score: 0.0,
+                positions: Vec::new(),
+                string: profile_label(profile).to_string(),
+            })
+            .collect();
+        let selected_index = profiles
+            .iter()
+            .position(|profile| *profile == active_profile)
+            .unwrap_or(0);
+
+        Self {
+            selector,
+            workspace,
+            profiles,
+            active_profile,
+            matches,
+            selected_index,
+        }
+    }
+}
+
+fn profile_label(profile: &Option<String>) -> &str {
+    profile.as_deref().unwrap_or(NO_PROFILE_LABEL)
+}
+
+impl PickerDelegate for SettingsProfileSelectorDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select Settings Profile...".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut Window, _: &mut Context<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .profiles
+            .iter()
+            .enumerate()
+            .map(|(id, profile)| StringMatchCandidate::new(id, profile_label(profile)))
+            .collect::<Vec<_>>();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, _| {
+                this.delegate.matches = matches;
+                this.delegate.selected_index = this
+                    .delegate
+                    .selected_index
+                    .min(this.delegate.matches.len().saturating_sub(1));
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(profile) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|mat| self.profiles.get(mat.candidate_id))
+        else {
+            return;
+        };
+        let profile = profile.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.set_settings_profile(profile, cx)
+            })
+            .log_err();
+        self.selector
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.selector
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let profile_match = &self.matches[ix];
+        let is_active = self.profiles[profile_match.candidate_id] == self.active_profile;
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(HighlightedLabel::new(
+                    profile_match.string.clone(),
+                    profile_match.positions.clone(),
+                ))
+                .when(is_active, |this| {
+                    this.end_slot(
+                        Icon::new(IconName::Check)
+                            .size(IconSize::Small)
+                            .color(Color::Accent),
+                    )
+                }),
+        )
+    }
+}
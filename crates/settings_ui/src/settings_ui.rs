@@ -1,131 +1,33 @@
 mod appearance_settings_controls;
+mod keymap_editor;
+mod settings_editor;
+mod settings_profile_selector;
 
-use std::any::TypeId;
-
-use command_palette_hooks::CommandPaletteFilter;
-use editor::EditorSettingsControls;
-use feature_flags::{FeatureFlag, FeatureFlagViewExt};
-use gpui::{actions, App, Entity, EventEmitter, FocusHandle, Focusable};
-use ui::prelude::*;
-use workspace::item::{Item, ItemEvent};
+use gpui::{actions, App};
 use workspace::Workspace;
 
-use crate::appearance_settings_controls::AppearanceSettingsControls;
-
-pub struct SettingsUiFeatureFlag;
-
-impl FeatureFlag for SettingsUiFeatureFlag {
-    const NAME: &'static str = "settings-ui";
-}
+pub use crate::keymap_editor::KeymapEditor;
+pub use crate::settings_editor::SettingsEditor;
+pub use crate::settings_profile_selector::SettingsProfileSelector;
 
-actions!(zed, [OpenSettingsEditor]);
+actions!(
+    zed,
+    [OpenSettingsEditor, OpenKeymapEditor, SelectSettingsProfile]
+);
 
 pub fn init(cx: &mut App) {
-    cx.observe_new(|workspace: &mut Workspace, window, cx| {
-        let Some(window) = window else {
-            return;
-        };
-
+    cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
         workspace.register_action(|workspace, _: &OpenSettingsEditor, window, cx| {
-            let existing = workspace
-                .active_pane()
-                .read(cx)
-                .items()
-                .find_map(|item| item.downcast::<SettingsPage>());
-
-            if let Some(existing) = existing {
-                workspace.activate_item(&existing, true, true, window, cx);
-            } else {
-                let settings_page = SettingsPage::new(workspace, cx);
-                workspace.add_item_to_active_pane(Box::new(settings_page), None, true, window, cx)
-            }
+            SettingsEditor::open(workspace, window, cx);
         });
 
-        let settings_ui_actions = [TypeId::of::<OpenSettingsEditor>()];
-
-        CommandPaletteFilter::update_global(cx, |filter, _cx| {
-            filter.hide_action_types(&settings_ui_actions);
+        workspace.register_action(|workspace, _: &OpenKeymapEditor, window, cx| {
+            KeymapEditor::open(workspace, window, cx);
         });
 
-        cx.observe_flag::<SettingsUiFeatureFlag, _>(
-            window,
-            move |is_enabled, _workspace, _, cx| {
-                if is_enabled {
-                    CommandPaletteFilter::update_global(cx, |filter, _cx| {
-                        filter.show_action_types(settings_ui_actions.iter());
-                    });
-                } else {
-                    CommandPaletteFilter::update_global(cx, |filter, _cx| {
-                        filter.hide_action_types(&settings_ui_actions);
-                    });
-                }
-            },
-        )
-        .detach();
+        workspace.register_action(|workspace, _: &SelectSettingsProfile, window, cx| {
+            SettingsProfileSelector::toggle(workspace, window, cx);
+        });
     })
     .detach();
 }
-
-pub struct SettingsPage {
-    focus_handle: FocusHandle,
-}
-
-impl SettingsPage {
-    pub fn new(_workspace: &Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
-        cx.new(|cx| Self {
-            focus_handle: cx.focus_handle(),
-        })
-    }
-}
-
-impl EventEmitter<ItemEvent> for SettingsPage {}
-
-impl Focusable for SettingsPage {
-    fn focus_handle(&self, _cx: &App) -> FocusHandle {
-        self.focus_handle.clone()
-    }
-}
-
-impl Item for SettingsPage {
-    type Event = ItemEvent;
-
-    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
-        Some(Icon::new(IconName::Settings))
-    }
-
-    fn tab_content_text(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
-        Some("Settings".into())
-    }
-
-    fn show_toolbar(&self) -> bool {
-        false
-    }
-
-    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
-        f(*event)
-    }
-}
-
-impl Render for SettingsPage {
-    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        v_flex()
-            .p_4()
-            .size_full()
-            .gap_4()
-            .child(Label::new("Settings").size(LabelSize::Large))
-            .child(
-                v_flex().gap_1().child(Label::new("Appearance")).child(
-                    v_flex()
-                        .elevation_2(cx)
-                        .child(AppearanceSettingsControls::new()),
-                ),
-            )
-            .child(
-                v_flex().gap_1().child(Label::new("Editor")).child(
-                    v_flex()
-                        .elevation_2(cx)
-                        .child(EditorSettingsControls::new()),
-                ),
-            )
-    }
-}
@@ -4,7 +4,7 @@ use schemars::{
     schema::{ObjectValidation, Schema, SchemaObject},
     JsonSchema,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json_lenient::Value;
 
 #[derive(Deserialize)]
@@ -41,7 +41,7 @@ impl JsonSchema for VSSnippetsFile {
     }
 }
 
-#[derive(Deserialize, JsonSchema)]
+#[derive(Deserialize, Serialize, JsonSchema)]
 #[serde(untagged)]
 pub(crate) enum ListOrDirect {
     Single(String),
@@ -70,14 +70,16 @@ impl std::fmt::Display for ListOrDirect {
     }
 }
 
-#[derive(Deserialize, JsonSchema)]
+#[derive(Deserialize, Serialize, JsonSchema)]
 pub(crate) struct VSCodeSnippet {
     /// The snippet prefix used to decide whether a completion menu should be shown.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) prefix: Option<ListOrDirect>,
 
     /// The snippet content. Use `$1` and `${1:defaultText}` to define cursor positions and `$0` for final cursor position.
     pub(crate) body: ListOrDirect,
 
     /// The snippet description displayed inside the completion menu.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) description: Option<ListOrDirect>,
 }
@@ -1,6 +1,7 @@
 mod extension_snippet;
 pub mod format;
 mod registry;
+pub mod user_snippets;
 
 use std::{
     path::{Path, PathBuf},
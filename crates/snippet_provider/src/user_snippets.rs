@@ -0,0 +1,246 @@
+//! Reading and writing the snippet files in the user's snippets directory, which is what the
+//! snippets editor edits instead of the JSON being edited by hand.
+
+use std::path::PathBuf;
+
+use anyhow::{Context as _, Result};
+use collections::BTreeMap;
+use serde::Deserialize;
+
+use crate::format::{ListOrDirect, VSCodeSnippet, VSSnippetsFile};
+
+/// The file stem of the snippets file for snippets that apply to every language.
+pub const GLOBAL_SNIPPETS_FILE_STEM: &str = "snippets";
+
+/// A snippet in one of the user's snippet files, in the form that's shown in the snippets editor.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserSnippet {
+    /// The key of the snippet in its file, which is also the prefix if it has none.
+    pub name: String,
+    pub prefixes: Vec<String>,
+    pub body: String,
+    pub description: Option<String>,
+}
+
+impl UserSnippet {
+    /// The prefixes that trigger the snippet, which is its name if it has no prefixes.
+    pub fn effective_prefixes(&self) -> Vec<&str> {
+        if self.prefixes.is_empty() {
+            vec![self.name.as_str()]
+        } else {
+            self.prefixes.iter().map(String::as_str).collect()
+        }
+    }
+
+    /// Returns the prefixes that both this snippet and the other snippet are triggered by.
+    pub fn conflicting_prefixes(&self, other: &UserSnippet) -> Vec<String> {
+        let other_prefixes = other.effective_prefixes();
+        self.effective_prefixes()
+            .into_iter()
+            .filter(|prefix| other_prefixes.contains(prefix))
+            .map(ToOwned::to_owned)
+            .collect()
+    }
+}
+
+/// Returns the path of the snippets file for the given language scope, or for the global scope
+/// if it's `None`.
+pub fn snippets_file_path(scope: Option<&str>) -> PathBuf {
+    paths::snippets_dir().join(format!(
+        "{}.json",
+        scope.unwrap_or(GLOBAL_SNIPPETS_FILE_STEM)
+    ))
+}
+
+/// Parses the contents of a snippets file, returning its snippets sorted by name. Snippets whose
+/// bodies aren't valid are still returned, so that they can be fixed.
+pub fn parse_snippets_file(contents: &str) -> Result<Vec<UserSnippet>> {
+    if contents.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let file: VSSnippetsFile = serde_json_lenient::from_str(contents)?;
+    let mut snippets = file
+        .snippets
+        .into_iter()
+        .map(|(name, snippet)| user_snippet(name, snippet))
+        .collect::<Vec<_>>();
+    snippets.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(snippets)
+}
+
+/// Serializes snippets in the format of a snippets file, with multi-line bodies written as lists
+/// of lines as VS Code does.
+pub fn serialize_snippets_file(snippets: &[UserSnippet]) -> Result<String> {
+    let file = snippets
+        .iter()
+        .map(|snippet| {
+            let snippet_json = VSCodeSnippet {
+                prefix: match snippet.prefixes.as_slice() {
+                    [] => None,
+                    [prefix] => Some(ListOrDirect::Single(prefix.clone())),
+                    prefixes => Some(ListOrDirect::List(prefixes.to_vec())),
+                },
+                body: if snippet.body.contains('\n') {
+                    ListOrDirect::List(snippet.body.split('\n').map(ToOwned::to_owned).collect())
+                } else {
+                    ListOrDirect::Single(snippet.body.clone())
+                },
+                description: snippet.description.clone().map(ListOrDirect::Single),
+            };
+            (snippet.name.as_str(), snippet_json)
+        })
+        .collect::<BTreeMap<_, _>>();
+    let mut contents = serde_json::to_string_pretty(&file)?;
+    contents.push('\n');
+    Ok(contents)
+}
+
+#[derive(Deserialize)]
+struct VSCodeSnippetWithScope {
+    /// The comma-separated language IDs that the snippet applies to in a `.code-snippets` file.
+    scope: Option<String>,
+    #[serde(flatten)]
+    snippet: VSCodeSnippet,
+}
+
+/// Parses a VS Code snippets file, which is either a `<language>.json` file or a
+/// `.code-snippets` file whose snippets can list the languages that they apply to. Only the
+/// snippets that apply to the given language scope are returned, or all of them for the global
+/// scope.
+pub fn import_vscode_snippets(contents: &str, scope: Option<&str>) -> Result<Vec<UserSnippet>> {
+    let file: BTreeMap<String, VSCodeSnippetWithScope> =
+        serde_json_lenient::from_str(contents).context("parsing VS Code snippets")?;
+    Ok(file
+        .into_iter()
+        .filter(|(_, snippet)| match (scope, &snippet.scope) {
+            (Some(scope), Some(snippet_scope)) => snippet_scope
+                .split(',')
+                .any(|language_id| language_id.trim().eq_ignore_ascii_case(scope)),
+            _ => true,
+        })
+        .map(|(name, snippet)| user_snippet(name, snippet.snippet))
+        .collect())
+}
+
+fn user_snippet(name: String, snippet: VSCodeSnippet) -> UserSnippet {
+    UserSnippet {
+        name,
+        prefixes: snippet.prefix.map(Into::into).unwrap_or_default(),
+        body: snippet.body.to_string(),
+        description: snippet
+            .description
+            .map(|description| description.to_string())
+            .filter(|description| !description.is_empty()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippets_file_round_trip() {
+        let snippets = parse_snippets_file(
+            r#"{
+                // Comments are allowed.
+                "Log": {
+                    "prefix": ["log", "cl"],
+                    "body": ["console.log($1);", "$0"],
+                    "description": "Logs to the console",
+                },
+                "Arrow function": { "body": "() => $0" },
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            snippets,
+            vec![
+                UserSnippet {
+                    name: "Arrow function".into(),
+                    prefixes: vec![],
+                    body: "() => $0".into(),
+                    description: None,
+                },
+                UserSnippet {
+                    name: "Log".into(),
+                    prefixes: vec!["log".into(), "cl".into()],
+                    body: "console.log($1);\n$0".into(),
+                    description: Some("Logs to the console".into()),
+                },
+            ]
+        );
+
+        let contents = serialize_snippets_file(&snippets).unwrap();
+        assert_eq!(
+            contents,
+            r#"{
+  "Arrow function": {
+    "body": "() => $0"
+  },
+  "Log": {
+    "prefix": [
+      "log",
+      "cl"
+    ],
+    "body": [
+      "console.log($1);",
+      "$0"
+    ],
+    "description": "Logs to the console"
+  }
+}
+"#
+        );
+        assert_eq!(parse_snippets_file(&contents).unwrap(), snippets);
+        assert_eq!(parse_snippets_file("").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_import_vscode_snippets() {
+        let contents = r#"{
+            "For loop": { "scope": "javascript,typescript", "prefix": "for", "body": "for (;;) {}" },
+            "Main": { "scope": "rust", "prefix": "main", "body": "fn main() {}" },
+            "Todo": { "prefix": "todo", "body": "TODO: $0" },
+        }"#;
+
+        let names = |snippets: Vec<UserSnippet>| {
+            snippets
+                .into_iter()
+                .map(|snippet| snippet.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(import_vscode_snippets(contents, Some("typescript")).unwrap()),
+            vec!["For loop", "Todo"]
+        );
+        assert_eq!(
+            names(import_vscode_snippets(contents, Some("rust")).unwrap()),
+            vec!["Main", "Todo"]
+        );
+        assert_eq!(
+            names(import_vscode_snippets(contents, None).unwrap()),
+            vec!["For loop", "Main", "Todo"]
+        );
+    }
+
+    #[test]
+    fn test_conflicting_prefixes() {
+        let log = UserSnippet {
+            name: "Log".into(),
+            prefixes: vec!["log".into(), "cl".into()],
+            ..Default::default()
+        };
+        let console_log = UserSnippet {
+            name: "cl".into(),
+            ..Default::default()
+        };
+        let debug = UserSnippet {
+            name: "Debug".into(),
+            prefixes: vec!["dbg".into()],
+            ..Default::default()
+        };
+        assert_eq!(log.conflicting_prefixes(&console_log), vec!["cl"]);
+        assert_eq!(console_log.conflicting_prefixes(&log), vec!["cl"]);
+        assert!(log.conflicting_prefixes(&debug).is_empty());
+    }
+}
@@ -0,0 +1,794 @@
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{anyhow, Context as _, Result};
+use editor::{Editor, EditorEvent};
+use fs::Fs;
+use gpui::{
+    App, Entity, EventEmitter, FocusHandle, Focusable, HighlightStyle, PathPromptOptions,
+    StyledText, Subscription, Task, WeakEntity,
+};
+use language::Language;
+use project::{DirectoryLister, Project, ProjectPath};
+use snippet::Snippet;
+use snippet_provider::user_snippets::{
+    import_vscode_snippets, parse_snippets_file, serialize_snippets_file, snippets_file_path,
+    UserSnippet,
+};
+use ui::{prelude::*, ListItem, ListItemSpacing, Tooltip};
+use workspace::{
+    item::{Item, ItemEvent},
+    notifications::DetachAndPromptErr,
+    OpenOptions, OpenVisible, Workspace, WorkspaceId,
+};
+
+/// Edits the snippets of one scope, which is either a language or the global scope, and writes
+/// them to the scope's snippets file when saved.
+pub struct SnippetsEditor {
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    /// The file stem of the language's snippets file, or `None` for the global scope.
+    scope: Option<String>,
+    scope_name: SharedString,
+    path: PathBuf,
+    snippets: Vec<UserSnippet>,
+    /// The global snippets, which are also offered in the language's scope and so can conflict
+    /// with its snippets.
+    global_snippets: Vec<UserSnippet>,
+    selected_index: Option<usize>,
+    name_editor: Entity<Editor>,
+    prefixes_editor: Entity<Editor>,
+    description_editor: Entity<Editor>,
+    body_editor: Entity<Editor>,
+    is_dirty: bool,
+    error: Option<SharedString>,
+    notice: Option<SharedString>,
+    focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl SnippetsEditor {
+    /// Opens the snippets editor for the given language, or for the global scope if it's `None`,
+    /// activating it if it's already open.
+    pub fn open(
+        language: Option<Arc<Language>>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<()>> {
+        let scope = language.as_ref().map(|language| language.lsp_id());
+        let scope_name: SharedString = language
+            .as_ref()
+            .map_or("Global".into(), |language| language.name().into());
+        let path = snippets_file_path(scope.as_deref());
+
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .filter_map(|item| item.downcast::<SnippetsEditor>())
+            .find(|editor| editor.read(cx).path == path);
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return Task::ready(Ok(()));
+        }
+
+        let fs = workspace.app_state().fs.clone();
+        cx.spawn_in(window, async move |workspace, cx| {
+            let snippets = parse_snippets_file(&load_file(fs.as_ref(), &path).await?)
+                .with_context(|| format!("parsing {path:?}"))?;
+            let global_snippets = if scope.is_some() {
+                let global_path = snippets_file_path(None);
+                match load_file(fs.as_ref(), &global_path).await {
+                    Ok(contents) => parse_snippets_file(&contents).unwrap_or_default(),
+                    Err(_) => Vec::new(),
+                }
+            } else {
+                Vec::new()
+            };
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let workspace_handle = cx.entity().downgrade();
+                let snippets_editor = cx.new(|cx| {
+                    Self::new(
+                        workspace_handle,
+                        fs,
+                        scope,
+                        scope_name,
+                        path,
+                        snippets,
+                        global_snippets,
+                        window,
+                        cx,
+                    )
+                });
+                workspace.add_item_to_active_pane(
+                    Box::new(snippets_editor),
+                    None,
+                    true,
+                    window,
+                    cx,
+                );
+            })
+        })
+    }
+
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        fs: Arc<dyn Fs>,
+        scope: Option<String>,
+        scope_name: SharedString,
+        path: PathBuf,
+        snippets: Vec<UserSnippet>,
+        global_snippets: Vec<UserSnippet>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let single_line_editor = |placeholder: &str, window: &mut Window, cx: &mut App| {
+            cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text(placeholder, cx);
+                editor
+            })
+        };
+        let name_editor = single_line_editor("Snippet name", window, cx);
+        let prefixes_editor = single_line_editor("Prefixes, separated by commas", window, cx);
+        let description_editor = single_line_editor("Description", window, cx);
+        let body_editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(16, window, cx);
+            editor.set_placeholder_text("Body, such as: console.log(${1:value});$0", cx);
+            editor.set_show_gutter(false, cx);
+            editor
+        });
+
+        let subscriptions = [
+            &name_editor,
+            &prefixes_editor,
+            &description_editor,
+            &body_editor,
+        ]
+        .into_iter()
+        .map(|editor| cx.subscribe(editor, Self::on_field_edited))
+        .collect();
+
+        let mut this = Self {
+            workspace,
+            fs,
+            scope,
+            scope_name,
+            path,
+            snippets,
+            global_snippets,
+            selected_index: None,
+            name_editor,
+            prefixes_editor,
+            description_editor,
+            body_editor,
+            is_dirty: false,
+            error: None,
+            notice: None,
+            focus_handle: cx.focus_handle(),
+            _subscriptions: subscriptions,
+        };
+        if !this.snippets.is_empty() {
+            this.select(0, window, cx);
+        }
+        this
+    }
+
+    fn select(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(snippet) = self.snippets.get(index).cloned() else {
+            return;
+        };
+        self.selected_index = Some(index);
+        self.name_editor.update(cx, |editor, cx| {
+            editor.set_text(snippet.name, window, cx);
+        });
+        self.prefixes_editor.update(cx, |editor, cx| {
+            editor.set_text(snippet.prefixes.join(", "), window, cx);
+        });
+        self.description_editor.update(cx, |editor, cx| {
+            editor.set_text(snippet.description.unwrap_or_default(), window, cx);
+        });
+        self.body_editor.update(cx, |editor, cx| {
+            editor.set_text(snippet.body, window, cx);
+        });
+        cx.notify();
+    }
+
+    /// Copies the fields into the selected snippet as they're edited, so that the list and the
+    /// preview stay up to date.
+    fn on_field_edited(&mut self, _: Entity<Editor>, event: &EditorEvent, cx: &mut Context<Self>) {
+        if !matches!(event, EditorEvent::Edited { .. }) {
+            return;
+        }
+        let Some(index) = self.selected_index else {
+            return;
+        };
+        let description = self.description_editor.read(cx).text(cx);
+        let edited = UserSnippet {
+            name: self.name_editor.read(cx).text(cx).trim().to_string(),
+            prefixes: self
+                .prefixes_editor
+                .read(cx)
+                .text(cx)
+                .split(',')
+                .map(str::trim)
+                .filter(|prefix| !prefix.is_empty())
+                .map(ToOwned::to_owned)
+                .collect(),
+            body: self.body_editor.read(cx).text(cx),
+            description: Some(description.trim().to_string())
+                .filter(|description| !description.is_empty()),
+        };
+        // Setting the fields when selecting a snippet also reports edits.
+        if self.snippets[index] != edited {
+            self.snippets[index] = edited;
+            self.error = None;
+            self.set_dirty(true, cx);
+        }
+        cx.notify();
+    }
+
+    fn set_dirty(&mut self, is_dirty: bool, cx: &mut Context<Self>) {
+        if self.is_dirty != is_dirty {
+            self.is_dirty = is_dirty;
+            cx.emit(ItemEvent::UpdateTab);
+        }
+    }
+
+    fn new_snippet(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let mut name = "New Snippet".to_string();
+        let mut suffix = 1;
+        while self.snippets.iter().any(|snippet| snippet.name == name) {
+            suffix += 1;
+            name = format!("New Snippet {suffix}");
+        }
+        self.snippets.push(UserSnippet {
+            name,
+            ..Default::default()
+        });
+        self.notice = None;
+        self.set_dirty(true, cx);
+        self.select(self.snippets.len() - 1, window, cx);
+        let focus_handle = self.name_editor.focus_handle(cx);
+        window.focus(&focus_handle);
+        self.name_editor.update(cx, |editor, cx| {
+            editor.select_all(&Default::default(), window, cx);
+        });
+    }
+
+    fn delete_selected(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(index) = self.selected_index.take() else {
+            return;
+        };
+        self.snippets.remove(index);
+        self.set_dirty(true, cx);
+        if self.snippets.is_empty() {
+            for editor in [
+                &self.name_editor,
+                &self.prefixes_editor,
+                &self.description_editor,
+                &self.body_editor,
+            ] {
+                editor.update(cx, |editor, cx| editor.set_text("", window, cx));
+            }
+            cx.notify();
+        } else {
+            self.select(index.min(self.snippets.len() - 1), window, cx);
+        }
+    }
+
+    /// Adds the snippets from VS Code snippet files that apply to this scope, replacing the
+    /// snippets with the same names.
+    fn import_from_vscode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let paths = workspace.update(cx, |workspace, cx| {
+            workspace.prompt_for_open_path(
+                PathPromptOptions {
+                    files: true,
+                    directories: false,
+                    multiple: true,
+                },
+                DirectoryLister::Local(workspace.app_state().fs.clone()),
+                window,
+                cx,
+            )
+        });
+        let fs = self.fs.clone();
+        let scope = self.scope.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let Some(paths) = paths.await.ok().flatten() else {
+                return Ok(());
+            };
+            let mut imported = Vec::new();
+            for path in paths {
+                let contents = fs.load(&path).await?;
+                imported.extend(
+                    import_vscode_snippets(&contents, scope.as_deref())
+                        .with_context(|| format!("importing {path:?}"))?,
+                );
+            }
+
+            this.update_in(cx, |this, window, cx| {
+                let count = imported.len();
+                for snippet in imported {
+                    match this
+                        .snippets
+                        .iter_mut()
+                        .find(|existing| existing.name == snippet.name)
+                    {
+                        Some(existing) => *existing = snippet,
+                        None => this.snippets.push(snippet),
+                    }
+                }
+                this.snippets.sort_by(|a, b| a.name.cmp(&b.name));
+                this.notice = Some(
+                    format!(
+                        "Imported {count} snippet{}. Save to keep them.",
+                        if count == 1 { "" } else { "s" }
+                    )
+                    .into(),
+                );
+                if count > 0 {
+                    this.set_dirty(true, cx);
+                    this.select(0, window, cx);
+                }
+                cx.notify();
+            })
+        })
+        .detach_and_prompt_err("Failed to import snippets", window, cx, |_, _, _| None);
+    }
+
+    fn open_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let path = self.path.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace
+                    .open_abs_path(
+                        path,
+                        OpenOptions {
+                            visible: Some(OpenVisible::None),
+                            ..Default::default()
+                        },
+                        window,
+                        cx,
+                    )
+                    .detach_and_log_err(cx);
+            })
+            .ok();
+    }
+
+    fn validate(&self) -> Result<()> {
+        for (index, snippet) in self.snippets.iter().enumerate() {
+            if snippet.name.is_empty() {
+                return Err(anyhow!("Every snippet needs a name."));
+            }
+            if self.snippets[..index]
+                .iter()
+                .any(|other| other.name == snippet.name)
+            {
+                return Err(anyhow!(
+                    "There is more than one snippet named \"{}\".",
+                    snippet.name
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the prefixes that the snippet at the given index shares with other snippets that
+    /// are offered in this scope, along with those snippets' names and scopes.
+    fn prefix_conflicts(&self, index: usize) -> Vec<(String, String, SharedString)> {
+        let snippet = &self.snippets[index];
+        let in_scope = self
+            .snippets
+            .iter()
+            .enumerate()
+            .filter(|(other_index, _)| *other_index != index)
+            .map(|(_, other)| (other, self.scope_name.clone()));
+        let global = self
+            .global_snippets
+            .iter()
+            .map(|other| (other, SharedString::from("Global")));
+        in_scope
+            .chain(global)
+            .flat_map(|(other, scope_name)| {
+                snippet
+                    .conflicting_prefixes(other)
+                    .into_iter()
+                    .map(move |prefix| (prefix, other.name.clone(), scope_name.clone()))
+            })
+            .collect()
+    }
+
+    fn render_list(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("snippets")
+            .w(rems(16.))
+            .h_full()
+            .p_1()
+            .overflow_y_scroll()
+            .border_r_1()
+            .border_color(cx.theme().colors().border)
+            .when(self.snippets.is_empty(), |this| {
+                this.child(
+                    div()
+                        .p_2()
+                        .child(Label::new("No snippets yet").color(Color::Muted)),
+                )
+            })
+            .children(self.snippets.iter().enumerate().map(|(index, snippet)| {
+                let has_conflicts = !self.prefix_conflicts(index).is_empty();
+                ListItem::new(index)
+                    .inset(true)
+                    .spacing(ListItemSpacing::Sparse)
+                    .toggle_state(self.selected_index == Some(index))
+                    .on_click(
+                        cx.listener(move |this, _, window, cx| this.select(index, window, cx)),
+                    )
+                    .child(
+                        v_flex()
+                            .child(Label::new(if snippet.name.is_empty() {
+                                "Untitled".to_string()
+                            } else {
+                                snippet.name.clone()
+                            }))
+                            .child(
+                                Label::new(snippet.effective_prefixes().join(", "))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .buffer_font(cx),
+                            ),
+                    )
+                    .when(has_conflicts, |this| {
+                        this.end_slot(
+                            Icon::new(IconName::Warning)
+                                .size(IconSize::Small)
+                                .color(Color::Warning),
+                        )
+                    })
+            }))
+    }
+
+    fn render_field(
+        &self,
+        label: &'static str,
+        editor: &Entity<Editor>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        v_flex()
+            .gap_1()
+            .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .border_1()
+                    .border_color(cx.theme().colors().border)
+                    .rounded_md()
+                    .bg(cx.theme().colors().editor_background)
+                    .child(editor.clone()),
+            )
+    }
+
+    fn render_preview(&self, snippet: &UserSnippet, cx: &mut Context<Self>) -> impl IntoElement {
+        let preview = match Snippet::parse(&snippet.body) {
+            Ok(parsed) => {
+                let highlight = HighlightStyle {
+                    background_color: Some(cx.theme().colors().element_selected),
+                    ..Default::default()
+                };
+                let tab_stop_count = parsed.tabstops.len();
+                let highlights = tab_stop_ranges(&parsed)
+                    .into_iter()
+                    .map(|range| (range, highlight))
+                    .collect::<Vec<_>>();
+                v_flex()
+                    .gap_1()
+                    .child(
+                        div()
+                            .p_2()
+                            .rounded_md()
+                            .bg(cx.theme().colors().editor_background)
+                            .font_buffer(cx)
+                            .child(StyledText::new(parsed.text).with_highlights(highlights)),
+                    )
+                    .child(
+                        Label::new(format!(
+                            "{tab_stop_count} tab stop{}",
+                            if tab_stop_count == 1 { "" } else { "s" }
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    )
+            }
+            Err(error) => v_flex().child(
+                Label::new(format!("Invalid snippet body: {error:#}"))
+                    .size(LabelSize::Small)
+                    .color(Color::Error),
+            ),
+        };
+        v_flex()
+            .gap_1()
+            .child(
+                Label::new("Preview")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(preview)
+    }
+
+    fn render_details(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(index) = self.selected_index else {
+            return v_flex().id("snippet-details").flex_1().p_4().child(
+                Label::new("Create a snippet or import snippets from VS Code.").color(Color::Muted),
+            );
+        };
+        let snippet = self.snippets[index].clone();
+        let conflicts = self.prefix_conflicts(index);
+
+        v_flex()
+            .id("snippet-details")
+            .flex_1()
+            .p_4()
+            .gap_3()
+            .overflow_y_scroll()
+            .child(self.render_field("Name", &self.name_editor, cx))
+            .child(self.render_field("Prefixes", &self.prefixes_editor, cx))
+            .children(conflicts.into_iter().map(|(prefix, name, scope_name)| {
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Icon::new(IconName::Warning)
+                            .size(IconSize::Small)
+                            .color(Color::Warning),
+                    )
+                    .child(
+                        Label::new(format!(
+                            "\"{prefix}\" also triggers \"{name}\" ({scope_name})"
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Warning),
+                    )
+            }))
+            .child(self.render_field("Description", &self.description_editor, cx))
+            .child(self.render_field("Body", &self.body_editor, cx))
+            .child(self.render_preview(&snippet, cx))
+            .child(
+                h_flex().child(
+                    Button::new("delete-snippet", "Delete Snippet")
+                        .icon(IconName::Trash)
+                        .icon_position(IconPosition::Start)
+                        .icon_size(IconSize::Small)
+                        .on_click(
+                            cx.listener(|this, _, window, cx| this.delete_selected(window, cx)),
+                        ),
+                ),
+            )
+    }
+}
+
+/// Returns the ranges of the tab stops' placeholders in the snippet's text, excluding the ones
+/// that are only cursor positions.
+fn tab_stop_ranges(snippet: &Snippet) -> Vec<Range<usize>> {
+    let mut ranges = snippet
+        .tabstops
+        .iter()
+        .flat_map(|tab_stop| tab_stop.ranges.iter())
+        .filter(|range| range.start < range.end)
+        .map(|range| range.start as usize..range.end as usize)
+        .collect::<Vec<_>>();
+    ranges.sort_by_key(|range| (range.start, range.end));
+    // Nested placeholders overlap, which highlights can't.
+    ranges.dedup_by(|range, previous| range.start < previous.end);
+    ranges
+}
+
+async fn load_file(fs: &dyn Fs, path: &Path) -> Result<String> {
+    if fs.is_file(path).await {
+        fs.load(path).await
+    } else {
+        Ok(String::new())
+    }
+}
+
+impl EventEmitter<ItemEvent> for SnippetsEditor {}
+
+impl Focusable for SnippetsEditor {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for SnippetsEditor {
+    type Event = ItemEvent;
+
+    fn tab_content_text(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some(format!("{} Snippets", self.scope_name).into())
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some(self.path.to_string_lossy().into_owned().into())
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Snippets Editor Opened")
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<WorkspaceId>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<Entity<Self>> {
+        None
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn is_dirty(&self, _: &App) -> bool {
+        self.is_dirty
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        true
+    }
+
+    fn save(
+        &mut self,
+        _format: bool,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if let Err(error) = self.validate() {
+            self.error = Some(error.to_string().into());
+            cx.notify();
+            return Task::ready(Err(error));
+        }
+        let contents = match serialize_snippets_file(&self.snippets) {
+            Ok(contents) => contents,
+            Err(error) => return Task::ready(Err(error)),
+        };
+        let fs = self.fs.clone();
+        let path = self.path.clone();
+        cx.spawn(async move |this, cx| {
+            if let Some(dir) = path.parent() {
+                fs.create_dir(dir).await?;
+            }
+            fs.atomic_write(path, contents).await?;
+            this.update(cx, |this, cx| {
+                this.error = None;
+                this.notice = None;
+                this.set_dirty(false, cx);
+                cx.notify();
+            })
+        })
+    }
+
+    fn save_as(
+        &mut self,
+        _: Entity<Project>,
+        _: ProjectPath,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        unreachable!()
+    }
+
+    fn reload(
+        &mut self,
+        _project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let fs = self.fs.clone();
+        let path = self.path.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let snippets = parse_snippets_file(&load_file(fs.as_ref(), &path).await?)?;
+            this.update_in(cx, |this, window, cx| {
+                this.snippets = snippets;
+                this.selected_index = None;
+                if !this.snippets.is_empty() {
+                    this.select(0, window, cx);
+                }
+                this.set_dirty(false, cx);
+                cx.notify();
+            })
+        })
+    }
+}
+
+impl Render for SnippetsEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("SnippetsEditor")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .w_full()
+                    .p_4()
+                    .gap_2()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        v_flex()
+                            .child(
+                                Headline::new(format!("{} Snippets", self.scope_name))
+                                    .size(HeadlineSize::Large),
+                            )
+                            .child(
+                                Label::new(self.path.to_string_lossy().into_owned())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(
+                                Button::new("open-snippets-file", "Open JSON")
+                                    .tooltip(Tooltip::text("Edit the snippets file by hand"))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.open_file(window, cx)
+                                    })),
+                            )
+                            .child(
+                                Button::new("import-vscode-snippets", "Import from VS Code…")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.import_from_vscode(window, cx)
+                                    })),
+                            )
+                            .child(
+                                Button::new("new-snippet", "New Snippet")
+                                    .style(ButtonStyle::Filled)
+                                    .icon(IconName::Plus)
+                                    .icon_position(IconPosition::Start)
+                                    .icon_size(IconSize::Small)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.new_snippet(window, cx)
+                                    })),
+                            ),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .px_4()
+                        .py_1()
+                        .child(Label::new(error).color(Color::Error)),
+                )
+            })
+            .when_some(self.notice.clone(), |this, notice| {
+                this.child(
+                    div()
+                        .px_4()
+                        .py_1()
+                        .child(Label::new(notice).color(Color::Muted)),
+                )
+            })
+            .child(
+                h_flex()
+                    .flex_1()
+                    .min_h_0()
+                    .items_start()
+                    .child(self.render_list(cx))
+                    .child(self.render_details(cx)),
+            )
+    }
+}
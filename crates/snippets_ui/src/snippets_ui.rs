@@ -1,3 +1,5 @@
+mod snippets_editor;
+
 use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
 use gpui::{
     actions, App, Context, DismissEvent, Entity, EventEmitter, Focusable, ParentElement, Render,
@@ -9,7 +11,9 @@ use picker::{Picker, PickerDelegate};
 use std::{borrow::Borrow, fs, sync::Arc};
 use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
 use util::ResultExt;
-use workspace::{notifications::NotifyResultExt, ModalView, OpenOptions, OpenVisible, Workspace};
+use workspace::{notifications::NotifyResultExt, ModalView, Workspace};
+
+pub use snippets_editor::SnippetsEditor;
 
 actions!(snippets, [ConfigureSnippets, OpenFolder]);
 
@@ -135,24 +139,16 @@ impl PickerDelegate for ScopeSelectorDelegate {
 
             if let Some(workspace) = self.workspace.upgrade() {
                 cx.spawn_in(window, async move |_, cx| {
-                    let scope = match scope_name.as_str() {
-                        "Global" => "snippets".to_string(),
-                        _ => language.await?.lsp_id(),
+                    let language = match scope_name.as_str() {
+                        "Global" => None,
+                        _ => Some(language.await?),
                     };
 
-                    workspace.update_in(cx, |workspace, window, cx| {
-                        workspace
-                            .open_abs_path(
-                                config_dir().join("snippets").join(scope + ".json"),
-                                OpenOptions {
-                                    visible: Some(OpenVisible::None),
-                                    ..Default::default()
-                                },
-                                window,
-                                cx,
-                            )
-                            .detach();
-                    })
+                    workspace
+                        .update_in(cx, |workspace, window, cx| {
+                            SnippetsEditor::open(language, workspace, window, cx)
+                        })?
+                        .await
                 })
                 .detach_and_log_err(cx);
             };
@@ -0,0 +1,560 @@
+use std::{ops::Range, path::PathBuf, rc::Rc, sync::Arc, time::Duration};
+
+use anyhow::{anyhow, Context as _, Result};
+use collections::HashSet;
+use editor::{
+    Addon, CodeActionProvider, Editor, EditorMode, ExcerptId, TextDecoration, TextDecorationKind,
+};
+use fs::Fs;
+use gpui::{App, AppContext as _, Context, Entity, Subscription, Task, WeakEntity, Window};
+use language::{
+    language_settings::{language_settings, SpellCheckBehavior},
+    Buffer, BufferEvent, BufferSnapshot,
+};
+use project::{CodeAction, LspAction, ProjectTransaction};
+use serde::{Deserialize, Serialize};
+use settings::SettingsStore;
+use ui::Color;
+use util::ResultExt as _;
+
+use crate::{
+    words::{parse_word_list, words},
+    Dictionaries, SpellChecker, Toggle,
+};
+
+/// The source of the misspelling decorations in editors.
+const DECORATION_SOURCE: &str = "spell_check";
+
+const CODE_ACTION_PROVIDER_ID: &str = "spell_check";
+
+/// How long to wait after an edit before checking the buffer again.
+const CHECK_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Buffers longer than this aren't spell checked, as checking them would take too long.
+const MAX_BUFFER_LEN: usize = 2 * 1024 * 1024;
+
+const MAX_SUGGESTIONS: usize = 5;
+
+/// The syntax nodes whose text isn't checked in prose, as it's code or markup.
+const NON_PROSE_NODE_KINDS: &[&str] = &[
+    "code_span",
+    "fenced_code_block",
+    "indented_code_block",
+    "html_block",
+    "html_tag",
+    "link_destination",
+    "uri_autolink",
+    "email_autolink",
+    "comment",
+];
+
+pub(crate) fn register(editor: &mut Editor, window: Option<&mut Window>, cx: &mut Context<Editor>) {
+    let Some(window) = window else {
+        return;
+    };
+    if !matches!(editor.mode(), EditorMode::Full) {
+        return;
+    }
+    let Some(buffer) = editor.buffer().read(cx).as_singleton() else {
+        return;
+    };
+    let Some(spell_checker) = SpellChecker::global(cx) else {
+        return;
+    };
+
+    let editor_handle = cx.entity().downgrade();
+    let spell_check = cx.new(|cx| EditorSpellCheck::new(editor_handle, buffer, spell_checker, cx));
+    let toggle_action = editor.register_action({
+        let spell_check = spell_check.downgrade();
+        move |_: &Toggle, _, cx| {
+            spell_check
+                .update(cx, |spell_check, cx| spell_check.toggle(cx))
+                .ok();
+        }
+    });
+    editor.add_code_action_provider(
+        Rc::new(SpellCheckCodeActionProvider {
+            spell_check: spell_check.downgrade(),
+        }),
+        window,
+        cx,
+    );
+    editor.register_addon(SpellCheckAddon {
+        _spell_check: spell_check,
+        _toggle_action: toggle_action,
+    });
+}
+
+/// Keeps an editor's spell checking alive for as long as the editor.
+struct SpellCheckAddon {
+    _spell_check: Entity<EditorSpellCheck>,
+    _toggle_action: Subscription,
+}
+
+impl Addon for SpellCheckAddon {
+    fn to_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Checks the spelling of the buffer in an editor whenever it changes, and underlines the
+/// misspelled words.
+struct EditorSpellCheck {
+    editor: WeakEntity<Editor>,
+    buffer: Entity<Buffer>,
+    spell_checker: Entity<SpellChecker>,
+    /// Whether spell checking was toggled on or off in this editor, overriding the language's
+    /// `spell_check` setting.
+    enabled_override: Option<bool>,
+    /// The dictionaries that the buffer was last checked with, which corrections are suggested
+    /// from.
+    dictionaries: Dictionaries,
+    misspellings: Vec<Range<text::Anchor>>,
+    check_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EditorSpellCheck {
+    fn new(
+        editor: WeakEntity<Editor>,
+        buffer: Entity<Buffer>,
+        spell_checker: Entity<SpellChecker>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let subscriptions = vec![
+            cx.subscribe(&buffer, |this, _, event, cx| match event {
+                BufferEvent::Edited | BufferEvent::Reparsed => this.check(CHECK_DEBOUNCE, cx),
+                BufferEvent::LanguageChanged
+                | BufferEvent::Reloaded
+                | BufferEvent::FileHandleChanged => this.check(Duration::ZERO, cx),
+                _ => {}
+            }),
+            cx.observe(&spell_checker, |this, _, cx| this.check(Duration::ZERO, cx)),
+            cx.observe_global::<SettingsStore>(|this, cx| this.check(Duration::ZERO, cx)),
+        ];
+        let mut this = Self {
+            editor,
+            buffer,
+            spell_checker,
+            enabled_override: None,
+            dictionaries: Dictionaries::default(),
+            misspellings: Vec::new(),
+            check_task: Task::ready(()),
+            _subscriptions: subscriptions,
+        };
+        this.check(Duration::ZERO, cx);
+        this
+    }
+
+    fn behavior(&self, cx: &App) -> SpellCheckBehavior {
+        let buffer = self.buffer.read(cx);
+        let configured = language_settings(
+            buffer.language().map(|language| language.name()),
+            buffer.file(),
+            cx,
+        )
+        .spell_check;
+        match self.enabled_override {
+            None => configured,
+            Some(false) => SpellCheckBehavior::Off,
+            Some(true) if configured == SpellCheckBehavior::Off => {
+                let has_grammar = buffer
+                    .language()
+                    .is_some_and(|language| language.grammar().is_some());
+                if has_grammar {
+                    SpellCheckBehavior::CommentsAndStrings
+                } else {
+                    SpellCheckBehavior::Prose
+                }
+            }
+            Some(true) => configured,
+        }
+    }
+
+    fn toggle(&mut self, cx: &mut Context<Self>) {
+        let is_enabled = self.behavior(cx) != SpellCheckBehavior::Off;
+        self.enabled_override = Some(!is_enabled);
+        self.check(Duration::ZERO, cx);
+    }
+
+    fn check(&mut self, delay: Duration, cx: &mut Context<Self>) {
+        self.check_task = cx.spawn(async move |this, cx| {
+            if !delay.is_zero() {
+                cx.background_executor().timer(delay).await;
+            }
+            // The buffer is read after the delay, so that it's been reparsed after edits.
+            let Some((behavior, snapshot, fs, dictionary_files)) = this
+                .update(cx, |this, cx| {
+                    let buffer = this.buffer.read(cx);
+                    let fs = this.spell_checker.read(cx).fs.clone();
+                    let dictionary_files = [Some(paths::dictionary_file().clone())]
+                        .into_iter()
+                        .chain([project_dictionary_file(buffer, cx)])
+                        .flatten()
+                        .collect::<Vec<_>>();
+                    (this.behavior(cx), buffer.snapshot(), fs, dictionary_files)
+                })
+                .ok()
+            else {
+                return;
+            };
+            if behavior == SpellCheckBehavior::Off || snapshot.len() > MAX_BUFFER_LEN {
+                this.update(cx, |this, cx| this.set_misspellings(Vec::new(), cx))
+                    .ok();
+                return;
+            }
+
+            let added_words = load_added_words(fs.as_ref(), dictionary_files).await;
+            let Ok(dictionaries) = this.update(cx, |this, cx| Dictionaries {
+                dictionaries: this
+                    .spell_checker
+                    .update(cx, |spell_checker, cx| spell_checker.dictionaries(cx)),
+                added_words: Arc::new(added_words),
+            }) else {
+                return;
+            };
+            let misspellings = cx
+                .background_spawn({
+                    let dictionaries = dictionaries.clone();
+                    async move { find_misspellings(&snapshot, behavior, &dictionaries) }
+                })
+                .await;
+            this.update(cx, |this, cx| {
+                this.dictionaries = dictionaries;
+                this.set_misspellings(misspellings, cx);
+            })
+            .ok();
+        });
+    }
+
+    fn set_misspellings(&mut self, misspellings: Vec<Range<text::Anchor>>, cx: &mut Context<Self>) {
+        self.misspellings = misspellings;
+        let misspellings = &self.misspellings;
+        self.editor
+            .update(cx, |editor, cx| {
+                let multibuffer = editor.buffer().read(cx).snapshot(cx);
+                let Some(excerpt_id) = multibuffer
+                    .as_singleton()
+                    .map(|(excerpt_id, _, _)| *excerpt_id)
+                else {
+                    return;
+                };
+                let decorations = misspellings
+                    .iter()
+                    .filter_map(|range| {
+                        Some(TextDecoration {
+                            range: multibuffer.anchor_in_excerpt(excerpt_id, range.start)?
+                                ..multibuffer.anchor_in_excerpt(excerpt_id, range.end)?,
+                            kind: TextDecorationKind::Underline,
+                            color: Color::Info,
+                        })
+                    })
+                    .collect();
+                editor.set_text_decorations(DECORATION_SOURCE, decorations, cx);
+            })
+            .ok();
+    }
+
+    fn misspelling_at(
+        &self,
+        range: &Range<text::Anchor>,
+        snapshot: &BufferSnapshot,
+    ) -> Option<Range<text::Anchor>> {
+        self.misspellings
+            .iter()
+            .find(|misspelling| {
+                misspelling.start.cmp(&range.end, snapshot).is_le()
+                    && misspelling.end.cmp(&range.start, snapshot).is_ge()
+            })
+            .cloned()
+    }
+}
+
+/// Returns the path of the dictionary of the project that the buffer's file is in.
+fn project_dictionary_file(buffer: &Buffer, cx: &App) -> Option<PathBuf> {
+    let file = buffer.file()?.as_local()?;
+    // Files that are opened on their own aren't in a project directory.
+    if file.path().as_os_str().is_empty() {
+        return None;
+    }
+    let abs_path = file.abs_path(cx);
+    let worktree_root = abs_path.ancestors().nth(file.path().components().count())?;
+    Some(worktree_root.join(paths::local_dictionary_file_relative_path()))
+}
+
+async fn load_added_words(fs: &dyn Fs, dictionary_files: Vec<PathBuf>) -> HashSet<String> {
+    let mut words = HashSet::default();
+    for path in dictionary_files {
+        if fs.is_file(&path).await {
+            if let Some(contents) = fs.load(&path).await.log_err() {
+                words.extend(parse_word_list(&contents));
+            }
+        }
+    }
+    words
+}
+
+fn find_misspellings(
+    snapshot: &BufferSnapshot,
+    behavior: SpellCheckBehavior,
+    dictionaries: &Dictionaries,
+) -> Vec<Range<text::Anchor>> {
+    let mut misspellings = Vec::new();
+    for range in checked_ranges(snapshot, behavior) {
+        let text = snapshot.text_for_range(range.clone()).collect::<String>();
+        for word_range in words(&text) {
+            if !dictionaries.is_correct(&text[word_range.clone()]) {
+                misspellings.push(
+                    snapshot.anchor_after(range.start + word_range.start)
+                        ..snapshot.anchor_before(range.start + word_range.end),
+                );
+            }
+        }
+    }
+    misspellings
+}
+
+/// Returns the sorted, disjoint ranges of the buffer that are spell checked.
+fn checked_ranges(snapshot: &BufferSnapshot, behavior: SpellCheckBehavior) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    match behavior {
+        SpellCheckBehavior::Off => {}
+        SpellCheckBehavior::CommentsAndStrings => {
+            for layer in snapshot.syntax_layers() {
+                let mut stack = vec![layer.node()];
+                while let Some(node) = stack.pop() {
+                    let kind = node.kind();
+                    let mut cursor = node.walk();
+                    if kind.contains("comment") {
+                        ranges.push(node.byte_range());
+                    } else if kind.contains("string") {
+                        // Check the string's text around its children, like escape sequences
+                        // and interpolations, which may contain strings of their own.
+                        let mut start = node.start_byte();
+                        for child in node.named_children(&mut cursor) {
+                            ranges.push(start..child.start_byte());
+                            stack.push(child);
+                            start = child.end_byte();
+                        }
+                        ranges.push(start..node.end_byte());
+                    } else {
+                        stack.extend(node.children(&mut cursor));
+                    }
+                }
+            }
+        }
+        SpellCheckBehavior::Prose => {
+            let mut excluded = Vec::new();
+            for layer in snapshot.syntax_layers() {
+                let mut stack = vec![layer.node()];
+                while let Some(node) = stack.pop() {
+                    if NON_PROSE_NODE_KINDS.contains(&node.kind()) {
+                        excluded.push(node.byte_range());
+                    } else {
+                        let mut cursor = node.walk();
+                        stack.extend(node.children(&mut cursor));
+                    }
+                }
+            }
+            excluded.sort_by_key(|range| range.start);
+            let mut start = 0;
+            for range in excluded {
+                if start < range.start {
+                    ranges.push(start..range.start);
+                }
+                start = start.max(range.end);
+            }
+            ranges.push(start..snapshot.len());
+        }
+    }
+
+    ranges.retain(|range| range.start < range.end);
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// The fix that a spell checking code action applies.
+#[derive(Serialize, Deserialize)]
+enum SpellCheckFix {
+    Replace(String),
+    AddToProjectDictionary,
+    AddToUserDictionary,
+}
+
+struct SpellCheckCodeActionProvider {
+    spell_check: WeakEntity<EditorSpellCheck>,
+}
+
+impl CodeActionProvider for SpellCheckCodeActionProvider {
+    fn id(&self) -> Arc<str> {
+        CODE_ACTION_PROVIDER_ID.into()
+    }
+
+    fn code_actions(
+        &self,
+        buffer: &Entity<Buffer>,
+        range: Range<text::Anchor>,
+        _: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Vec<CodeAction>>> {
+        let Some(spell_check) = self.spell_check.upgrade() else {
+            return Task::ready(Ok(Vec::new()));
+        };
+        let spell_check = spell_check.read(cx);
+        let buffer = buffer.read(cx);
+        let snapshot = buffer.snapshot();
+        let Some(misspelling) = spell_check.misspelling_at(&range, &snapshot) else {
+            return Task::ready(Ok(Vec::new()));
+        };
+        let word = snapshot
+            .text_for_range(misspelling.clone())
+            .collect::<String>();
+        let dictionaries = spell_check.dictionaries.clone();
+        let has_project_dictionary = project_dictionary_file(buffer, cx).is_some();
+
+        cx.background_spawn(async move {
+            let mut actions = dictionaries
+                .suggestions(&word, MAX_SUGGESTIONS)
+                .into_iter()
+                .map(|suggestion| {
+                    code_action(
+                        misspelling.clone(),
+                        format!("Change to \"{suggestion}\""),
+                        SpellCheckFix::Replace(suggestion),
+                    )
+                })
+                .collect::<Vec<_>>();
+            if has_project_dictionary {
+                actions.push(code_action(
+                    misspelling.clone(),
+                    format!("Add \"{word}\" to project dictionary"),
+                    SpellCheckFix::AddToProjectDictionary,
+                ));
+            }
+            actions.push(code_action(
+                misspelling,
+                format!("Add \"{word}\" to user dictionary"),
+                SpellCheckFix::AddToUserDictionary,
+            ));
+            Ok(actions)
+        })
+    }
+
+    fn apply_code_action(
+        &self,
+        buffer: Entity<Buffer>,
+        action: CodeAction,
+        _excerpt_id: ExcerptId,
+        _push_to_history: bool,
+        _window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<ProjectTransaction>> {
+        let fix = match &action.lsp_action {
+            LspAction::Action(lsp_action) => lsp_action
+                .data
+                .clone()
+                .and_then(|data| serde_json::from_value::<SpellCheckFix>(data).ok()),
+            _ => None,
+        };
+        let Some(fix) = fix else {
+            return Task::ready(Err(anyhow!("not a spell checking code action")));
+        };
+
+        let dictionary_file = match fix {
+            SpellCheckFix::Replace(replacement) => {
+                buffer.update(cx, |buffer, cx| {
+                    buffer.edit([(action.range, replacement)], None, cx);
+                });
+                return Task::ready(Ok(ProjectTransaction::default()));
+            }
+            SpellCheckFix::AddToProjectDictionary => {
+                match project_dictionary_file(buffer.read(cx), cx) {
+                    Some(path) => path,
+                    None => return Task::ready(Err(anyhow!("the file isn't in a project"))),
+                }
+            }
+            SpellCheckFix::AddToUserDictionary => paths::dictionary_file().clone(),
+        };
+        let Some(spell_checker) = SpellChecker::global(cx) else {
+            return Task::ready(Ok(ProjectTransaction::default()));
+        };
+        let word = buffer
+            .read(cx)
+            .text_for_range(action.range)
+            .collect::<String>();
+        let add_word = spell_checker.update(cx, |spell_checker, cx| {
+            spell_checker.add_word(word, dictionary_file, cx)
+        });
+        cx.background_spawn(async move {
+            add_word.await.context("adding word to dictionary")?;
+            Ok(ProjectTransaction::default())
+        })
+    }
+}
+
+fn code_action(range: Range<text::Anchor>, title: String, fix: SpellCheckFix) -> CodeAction {
+    CodeAction {
+        server_id: language::LanguageServerId(0),
+        range,
+        lsp_action: LspAction::Action(Box::new(lsp::CodeAction {
+            title,
+            kind: Some(lsp::CodeActionKind::QUICKFIX),
+            data: serde_json::to_value(fix).log_err(),
+            ..Default::default()
+        })),
+        resolved: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use language::{Language, LanguageConfig, LanguageMatcher};
+
+    fn rust_lang() -> Arc<Language> {
+        Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            Some(tree_sitter_rust::LANGUAGE.into()),
+        ))
+    }
+
+    #[gpui::test]
+    fn test_checked_ranges(cx: &mut TestAppContext) {
+        let text = r#"/* A comment. */
+fn main() {
+    let greeting = "Hello\nworld";
+    println!("{greeting}");
+}
+"#;
+        let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(rust_lang(), cx));
+        cx.run_until_parked();
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+
+        let checked_text = |behavior| {
+            checked_ranges(&snapshot, behavior)
+                .into_iter()
+                .map(|range| snapshot.text_for_range(range).collect::<String>())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            checked_text(SpellCheckBehavior::CommentsAndStrings),
+            vec!["/* A comment. */", "\"Hello", "world\"", "\"{greeting}\""]
+        );
+        assert_eq!(checked_text(SpellCheckBehavior::Off), Vec::<String>::new());
+    }
+}
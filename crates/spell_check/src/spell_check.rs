@@ -0,0 +1,215 @@
+//! Spell checking of comments, strings and prose in editors, with Hunspell dictionaries and
+//! words that users add to their own dictionary or to a project's.
+
+mod editor_spell_check;
+mod words;
+
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::{anyhow, Context as _, Result};
+use collections::HashSet;
+use fs::Fs;
+use gpui::{actions, App, AppContext as _, AssetSource, AsyncApp, Context, Entity, Global, Task};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources, SettingsStore};
+use spellbook::Dictionary;
+
+actions!(spell_check, [Toggle]);
+
+pub fn init(fs: Arc<dyn Fs>, cx: &mut App) {
+    SpellCheckSettings::register(cx);
+    let spell_checker = cx.new(|cx| SpellChecker::new(fs, cx));
+    cx.set_global(GlobalSpellChecker(spell_checker));
+    cx.observe_new(editor_spell_check::register).detach();
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SpellCheckSettings {
+    /// The Hunspell dictionaries to spell check with, such as "en_US". Dictionaries in the
+    /// `dictionaries` directory of Zed's config directory are used before the bundled ones.
+    ///
+    /// Default: ["en_US"]
+    #[serde(default)]
+    pub spell_check_dictionaries: Vec<String>,
+}
+
+impl Settings for SpellCheckSettings {
+    const KEY: Option<&'static str> = None;
+
+    type FileContent = Self;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        sources.json_merge()
+    }
+}
+
+struct GlobalSpellChecker(Entity<SpellChecker>);
+
+impl Global for GlobalSpellChecker {}
+
+/// Loads the dictionaries in the settings, which are shared by all editors, and adds words to
+/// the user's and projects' dictionaries.
+pub struct SpellChecker {
+    fs: Arc<dyn Fs>,
+    /// Whether an editor has spell checked with the dictionaries, which aren't loaded until then.
+    is_needed: bool,
+    dictionary_names: Vec<String>,
+    dictionaries: Vec<Arc<Dictionary>>,
+    _load_dictionaries: Task<()>,
+    _settings_subscription: gpui::Subscription,
+}
+
+impl SpellChecker {
+    pub fn global(cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalSpellChecker>()
+            .map(|global| global.0.clone())
+    }
+
+    fn new(fs: Arc<dyn Fs>, cx: &mut Context<Self>) -> Self {
+        Self {
+            fs,
+            is_needed: false,
+            dictionary_names: Vec::new(),
+            dictionaries: Vec::new(),
+            _load_dictionaries: Task::ready(()),
+            _settings_subscription: cx
+                .observe_global::<SettingsStore>(|this, cx| this.load_dictionaries(cx)),
+        }
+    }
+
+    /// Returns the loaded dictionaries, and starts loading them the first time they're needed.
+    pub(crate) fn dictionaries(&mut self, cx: &mut Context<Self>) -> Vec<Arc<Dictionary>> {
+        if !self.is_needed {
+            self.is_needed = true;
+            self.load_dictionaries(cx);
+        }
+        self.dictionaries.clone()
+    }
+
+    fn load_dictionaries(&mut self, cx: &mut Context<Self>) {
+        if !self.is_needed {
+            return;
+        }
+        let names = SpellCheckSettings::get_global(cx)
+            .spell_check_dictionaries
+            .clone();
+        if names == self.dictionary_names {
+            return;
+        }
+        self.dictionary_names = names.clone();
+
+        let fs = self.fs.clone();
+        let asset_source = cx.asset_source().clone();
+        self._load_dictionaries = cx.spawn(async move |this, cx| {
+            let mut dictionaries = Vec::new();
+            for name in names {
+                match load_dictionary(&name, fs.as_ref(), asset_source.as_ref(), cx).await {
+                    Ok(dictionary) => dictionaries.push(Arc::new(dictionary)),
+                    Err(error) => log::error!("failed to load the {name} dictionary: {error:#}"),
+                }
+            }
+            this.update(cx, |this, cx| {
+                this.dictionaries = dictionaries;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    /// Adds a word to the dictionary file at the given path, which is either the user's or a
+    /// project's, so that it's no longer reported as misspelled.
+    pub fn add_word(
+        &self,
+        word: String,
+        dictionary_file: PathBuf,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let fs = self.fs.clone();
+        cx.spawn(async move |this, cx| {
+            let mut contents = if fs.is_file(&dictionary_file).await {
+                fs.load(&dictionary_file).await?
+            } else {
+                String::new()
+            };
+            if !contents.is_empty() && !contents.ends_with('\n') {
+                contents.push('\n');
+            }
+            contents.push_str(&word);
+            contents.push('\n');
+            if let Some(dir) = dictionary_file.parent() {
+                fs.create_dir(dir).await?;
+            }
+            fs.atomic_write(dictionary_file, contents).await?;
+            // Editors check their buffers again when notified, reloading the added words.
+            this.update(cx, |_, cx| cx.notify())
+        })
+    }
+}
+
+async fn load_dictionary(
+    name: &str,
+    fs: &dyn Fs,
+    asset_source: &dyn AssetSource,
+    cx: &AsyncApp,
+) -> Result<Dictionary> {
+    let user_aff_path = paths::dictionaries_dir().join(format!("{name}.aff"));
+    let user_dic_path = paths::dictionaries_dir().join(format!("{name}.dic"));
+    let (aff, dic) = if fs.is_file(&user_aff_path).await && fs.is_file(&user_dic_path).await {
+        (
+            fs.load(&user_aff_path).await?,
+            fs.load(&user_dic_path).await?,
+        )
+    } else {
+        let load_asset = |extension: &str| -> Result<String> {
+            let path = format!("dictionaries/{name}.{extension}");
+            let data = asset_source
+                .load(&path)?
+                .with_context(|| format!("no dictionary at {path}"))?;
+            Ok(String::from_utf8(data.into_owned())?)
+        };
+        (load_asset("aff")?, load_asset("dic")?)
+    };
+    cx.background_spawn(async move {
+        Dictionary::new(&aff, &dic).map_err(|error| anyhow!("parsing dictionary: {error}"))
+    })
+    .await
+}
+
+/// The dictionaries and added words that a buffer is checked with.
+#[derive(Clone, Default)]
+pub(crate) struct Dictionaries {
+    dictionaries: Vec<Arc<Dictionary>>,
+    /// The words added to the user's and the project's dictionaries, in lowercase.
+    added_words: Arc<HashSet<String>>,
+}
+
+impl Dictionaries {
+    /// Whether the word is spelled correctly, which it's assumed to be if there are no
+    /// dictionaries to check it with.
+    pub(crate) fn is_correct(&self, word: &str) -> bool {
+        if self.dictionaries.is_empty() || self.added_words.contains(&word.to_lowercase()) {
+            return true;
+        }
+        let word = word.replace('’', "'");
+        self.dictionaries
+            .iter()
+            .any(|dictionary| dictionary.check(&word))
+    }
+
+    pub(crate) fn suggestions(&self, word: &str, limit: usize) -> Vec<String> {
+        let word = word.replace('’', "'");
+        let mut suggestions = Vec::new();
+        for dictionary in &self.dictionaries {
+            let mut dictionary_suggestions = Vec::new();
+            dictionary.suggest(&word, &mut dictionary_suggestions);
+            for suggestion in dictionary_suggestions {
+                if !suggestions.contains(&suggestion) {
+                    suggestions.push(suggestion);
+                }
+            }
+        }
+        suggestions.truncate(limit);
+        suggestions
+    }
+}
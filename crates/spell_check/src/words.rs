@@ -0,0 +1,123 @@
+use std::ops::Range;
+
+use collections::HashSet;
+
+/// Returns the ranges of the words in the text that should be spell checked, skipping the
+/// tokens that look like code, such as identifiers, paths, URLs and numbers.
+pub(crate) fn words(text: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut token_start = None;
+    for (ix, char) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        if char.is_whitespace() {
+            if let Some(start) = token_start.take() {
+                push_token_words(text, start..ix, &mut words);
+            }
+        } else if token_start.is_none() {
+            token_start = Some(ix);
+        }
+    }
+    words
+}
+
+fn push_token_words(text: &str, token_range: Range<usize>, words: &mut Vec<Range<usize>>) {
+    let token = &text[token_range.clone()];
+    if token.contains('`') {
+        return;
+    }
+    // Trim the punctuation around words, like quotes and the end of a sentence.
+    let Some(start) = token.find(char::is_alphabetic) else {
+        return;
+    };
+    let Some((last_ix, last_char)) = token
+        .char_indices()
+        .rev()
+        .find(|(_, char)| char.is_alphabetic())
+    else {
+        return;
+    };
+    let end = last_ix + last_char.len_utf8();
+    let core = &token[start..end];
+    if core
+        .chars()
+        .any(|char| !(char.is_alphabetic() || is_apostrophe(char) || char == '-'))
+    {
+        return;
+    }
+
+    let mut part_start = token_range.start + start;
+    for part in core.split('-') {
+        if is_checkable(part) {
+            words.push(part_start..part_start + part.len());
+        }
+        part_start += part.len() + 1;
+    }
+}
+
+/// Whether the word should be checked. Words in capitals are usually acronyms, and words in
+/// mixed case are usually identifiers.
+fn is_checkable(word: &str) -> bool {
+    let mut chars = word.chars().filter(|char| char.is_alphabetic());
+    let Some(_) = chars.next() else {
+        return false;
+    };
+    let mut has_lowercase = false;
+    for char in chars {
+        if char.is_uppercase() {
+            return false;
+        }
+        has_lowercase = true;
+    }
+    has_lowercase
+}
+
+pub(crate) fn is_apostrophe(char: char) -> bool {
+    char == '\'' || char == '’'
+}
+
+/// Parses a file of words to accept, with one word per line and lines starting with `#` being
+/// comments. The words are lowercased, as they're accepted in any case.
+pub(crate) fn parse_word_list(contents: &str) -> HashSet<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word_texts(text: &str) -> Vec<&str> {
+        words(text).into_iter().map(|range| &text[range]).collect()
+    }
+
+    #[test]
+    fn test_words() {
+        assert_eq!(
+            word_texts("Teh quick, \"brown\" fox's well-known jump."),
+            vec!["Teh", "quick", "brown", "fox's", "well", "known", "jump"]
+        );
+        assert_eq!(
+            word_texts("Call foo_bar() or fooBar, see https://zed.dev and src/main.rs for v2."),
+            vec!["Call", "or", "see", "and", "for"]
+        );
+        assert_eq!(
+            word_texts("The HTTP API uses `recieve` and a b"),
+            vec!["The", "uses", "and"]
+        );
+        assert_eq!(word_texts("  naïve  café\n"), vec!["naïve", "café"]);
+    }
+
+    #[test]
+    fn test_parse_word_list() {
+        assert_eq!(
+            parse_word_list("# Words for this project\nZed\n\n  gpui \n"),
+            HashSet::from_iter(["zed".to_string(), "gpui".to_string()])
+        );
+    }
+}
@@ -351,7 +351,7 @@ impl TerminalBuilder {
         alternate_scroll: AlternateScroll,
         max_scroll_history_lines: Option<usize>,
         is_ssh_terminal: bool,
-        window: AnyWindowHandle,
+        window: Option<AnyWindowHandle>,
         completion_tx: Sender<()>,
         debug_terminal: bool,
         cx: &App,
@@ -454,7 +454,9 @@ impl TerminalBuilder {
         let pty = match tty::new(
             &pty_options,
             TerminalBounds::default().into(),
-            window.window_id().as_u64(),
+            // Terminals that aren't opened from a window, like those of the assistant's tools,
+            // have no window ID to pass on.
+            window.map_or(0, |window| window.window_id().as_u64()),
         ) {
             Ok(pty) => pty,
             Err(error) => {
@@ -2,14 +2,13 @@ use alacritty_terminal::vte::ansi::{
     CursorShape as AlacCursorShape, CursorStyle as AlacCursorStyle,
 };
 use collections::HashMap;
-use gpui::{
-    px, AbsoluteLength, App, FontFallbacks, FontFeatures, FontWeight, Pixels, SharedString,
-};
+use gpui::{px, AbsoluteLength, App, FontFallbacks, FontWeight, Pixels, SharedString};
 use schemars::{gen::SchemaGenerator, schema::RootSchema, JsonSchema};
 use serde_derive::{Deserialize, Serialize};
 use settings::{add_references_to_properties, SettingsJsonSchemaParams, SettingsSources};
 use std::path::PathBuf;
 use task::Shell;
+use theme::FontFeaturesSetting;
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -31,7 +30,7 @@ pub struct TerminalSettings {
     pub font_size: Option<Pixels>,
     pub font_family: Option<SharedString>,
     pub font_fallbacks: Option<FontFallbacks>,
-    pub font_features: Option<FontFeatures>,
+    pub font_features: Option<FontFeaturesSetting>,
     pub font_weight: Option<FontWeight>,
     pub line_height: TerminalLineHeight,
     pub env: HashMap<String, String>,
@@ -159,7 +158,12 @@ pub struct TerminalSettingsContent {
     ///
     /// Default: comfortable
     pub line_height: Option<TerminalLineHeight>,
-    pub font_features: Option<FontFeatures>,
+    /// Sets the terminal's OpenType features, either inline or as the name of one of the
+    /// `font_feature_profiles`.
+    ///
+    /// If this option is not included,
+    /// the terminal will default to matching the buffer's font features.
+    pub font_features: Option<FontFeaturesSetting>,
     /// Sets the terminal's font weight in CSS weight units 0-900.
     pub font_weight: Option<f32>,
     /// Any key-value pairs added to this list will be added to the terminal's
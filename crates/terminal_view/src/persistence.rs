@@ -244,8 +244,9 @@ async fn deserialize_pane_group(
                             working_directory.as_deref().map(Path::to_path_buf),
                         );
                         let window = window.window_handle();
-                        let terminal = project
-                            .update(cx, |project, cx| project.create_terminal(kind, window, cx));
+                        let terminal = project.update(cx, |project, cx| {
+                            project.create_terminal(kind, Some(window), cx)
+                        });
                         Some(Some(terminal))
                     } else {
                         Some(None)
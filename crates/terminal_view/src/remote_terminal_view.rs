@@ -0,0 +1,281 @@
+use anyhow::Result;
+use gpui::{
+    div, App, Entity, EventEmitter, FocusHandle, Focusable, KeyDownEvent, Render, ScrollHandle,
+    Subscription, Task, WeakEntity,
+};
+use project::{
+    terminals::{RemoteTerminal, RemoteTerminalEvent},
+    Project,
+};
+use rpc::proto::{self, PeerId};
+use settings::Settings;
+use terminal::{alacritty_terminal::term::TermMode, mappings::keys::to_esc_str};
+use theme::ThemeSettings;
+use ui::{prelude::*, Icon, IconName, Label};
+use workspace::{
+    item::{Dedup, FollowEvent, FollowableItem, Item, ItemEvent},
+    ViewId, Workspace,
+};
+
+/// Opens a tab for each terminal that the host of the project starts sharing.
+pub(crate) fn init(cx: &mut App) {
+    workspace::FollowableViewRegistry::register::<RemoteTerminalView>(cx);
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+        let project = workspace.project().clone();
+        cx.subscribe_in(&project, window, |workspace, project, event, window, cx| {
+            if let project::Event::RemoteTerminalShared(terminal) = event {
+                let view = cx.new(|cx| RemoteTerminalView::new(terminal.clone(), project, cx));
+                workspace.add_item_to_active_pane(Box::new(view), None, false, window, cx);
+            }
+        })
+        .detach();
+    })
+    .detach();
+}
+
+/// Shows the output of a terminal that the host of the project is sharing, and
+/// forwards keystrokes to it while the host has handed input control to us.
+pub struct RemoteTerminalView {
+    terminal: Entity<RemoteTerminal>,
+    project: WeakEntity<Project>,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+    remote_id: Option<ViewId>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl RemoteTerminalView {
+    fn new(
+        terminal: Entity<RemoteTerminal>,
+        project: &Entity<Project>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let scroll_handle = ScrollHandle::new();
+        let subscriptions = vec![
+            cx.observe(&terminal, |this, _, cx| {
+                this.scroll_handle.scroll_to_bottom();
+                cx.notify();
+            }),
+            cx.subscribe(&terminal, |_, _, event, cx| match event {
+                RemoteTerminalEvent::Unshared => cx.emit(ItemEvent::CloseItem),
+            }),
+        ];
+        Self {
+            terminal,
+            project: project.downgrade(),
+            focus_handle: cx.focus_handle(),
+            scroll_handle,
+            remote_id: None,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn key_down(&mut self, event: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let terminal = self.terminal.read(cx);
+        if !terminal.has_input_control() {
+            return;
+        }
+        let keystroke = &event.keystroke;
+        let Some(input) = to_esc_str(keystroke, &TermMode::default(), false)
+            .or_else(|| keystroke.key_char.clone())
+        else {
+            return;
+        };
+        terminal
+            .input(input.into_bytes(), cx)
+            .detach_and_log_err(cx);
+        cx.stop_propagation();
+    }
+
+    /// Describes who is currently typing into the terminal.
+    fn input_status(&self, cx: &App) -> SharedString {
+        let terminal = self.terminal.read(cx);
+        if terminal.has_input_control() {
+            return "You have input control".into();
+        }
+        let typist = terminal.input_controller().and_then(|peer_id| {
+            let project = self.project.upgrade()?;
+            let project = project.read(cx);
+            let collaborator = project.collaborators().get(&peer_id)?;
+            project
+                .user_store()
+                .read(cx)
+                .get_cached_user(collaborator.user_id)
+        });
+        match typist {
+            Some(user) => format!("@{} is typing", user.github_login).into(),
+            None => "Only the host can type".into(),
+        }
+    }
+}
+
+impl EventEmitter<ItemEvent> for RemoteTerminalView {}
+
+impl Focusable for RemoteTerminalView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for RemoteTerminalView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _window: &Window, cx: &App) -> Option<SharedString> {
+        Some(self.terminal.read(cx).title().to_string().into())
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Terminal))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl FollowableItem for RemoteTerminalView {
+    fn remote_id(&self) -> Option<ViewId> {
+        self.remote_id
+    }
+
+    fn to_state_proto(&self, _window: &Window, _cx: &App) -> Option<proto::view::Variant> {
+        None
+    }
+
+    fn from_state_proto(
+        workspace: Entity<Workspace>,
+        remote_id: ViewId,
+        state: &mut Option<proto::view::Variant>,
+        _window: &mut Window,
+        cx: &mut App,
+    ) -> Option<Task<Result<Entity<Self>>>> {
+        let proto::view::Variant::Terminal(terminal_state) = state.as_ref()? else {
+            return None;
+        };
+        let terminal_id = terminal_state.terminal_id;
+        state.take();
+
+        let project = workspace.read(cx).project().clone();
+        let terminal = project
+            .read(cx)
+            .remote_terminals()
+            .find(|terminal| terminal.read(cx).id() == terminal_id)
+            .cloned();
+        let Some(terminal) = terminal else {
+            return Some(Task::ready(Err(anyhow::anyhow!(
+                "terminal {terminal_id} is not shared"
+            ))));
+        };
+        let view = cx.new(|cx| {
+            let mut view = RemoteTerminalView::new(terminal, &project, cx);
+            view.remote_id = Some(remote_id);
+            view
+        });
+        Some(Task::ready(Ok(view)))
+    }
+
+    fn to_follow_event(_event: &Self::Event) -> Option<FollowEvent> {
+        None
+    }
+
+    fn add_event_to_update_proto(
+        &self,
+        _event: &Self::Event,
+        _update: &mut Option<proto::update_view::Variant>,
+        _window: &Window,
+        _cx: &App,
+    ) -> bool {
+        false
+    }
+
+    fn apply_update_proto(
+        &mut self,
+        _project: &Entity<Project>,
+        _message: proto::update_view::Variant,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn is_project_item(&self, _window: &Window, _cx: &App) -> bool {
+        true
+    }
+
+    fn set_leader_peer_id(
+        &mut self,
+        _leader_peer_id: Option<PeerId>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+
+    fn dedup(&self, existing: &Self, _window: &Window, _cx: &App) -> Option<Dedup> {
+        if existing.terminal == self.terminal {
+            Some(Dedup::KeepExisting)
+        } else {
+            None
+        }
+    }
+}
+
+impl Render for RemoteTerminalView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let settings = ThemeSettings::get_global(cx);
+        let has_input_control = self.terminal.read(cx).has_input_control();
+        let lines = self
+            .terminal
+            .read(cx)
+            .lines()
+            .iter()
+            .map(|line| div().child(line.clone()))
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .key_context("RemoteTerminal")
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(Self::key_down))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .gap_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Icon::new(if has_input_control {
+                            IconName::Pencil
+                        } else {
+                            IconName::Eye
+                        })
+                        .size(IconSize::Small)
+                        .color(Color::Muted),
+                    )
+                    .child(
+                        Label::new(self.input_status(cx))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .id("remote-terminal-output")
+                    .track_scroll(&self.scroll_handle)
+                    .overflow_y_scroll()
+                    .flex_1()
+                    .p_2()
+                    .font_family(settings.buffer_font.family.clone())
+                    .text_size(settings.buffer_font_size(cx))
+                    .children(lines),
+            )
+    }
+}
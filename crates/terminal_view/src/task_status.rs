@@ -0,0 +1,78 @@
+use gpui::{App, Global, WeakEntity};
+use terminal::{Event, TaskStatus, Terminal};
+use ui::IconName;
+use workspace::{StatusItem, StatusItemRegistry, StatusItemSide};
+
+use crate::terminal_panel;
+
+const STATUS_ITEM_NAME: &str = "tasks";
+
+#[derive(Default)]
+struct TaskTerminals(Vec<WeakEntity<Terminal>>);
+
+impl Global for TaskTerminals {}
+
+/// Shows the number of running tasks in the status bar.
+pub(crate) fn init(cx: &mut App) {
+    cx.set_global(TaskTerminals::default());
+    cx.observe_new(|terminal: &mut Terminal, _window, cx| {
+        if terminal.task().is_none() {
+            return;
+        }
+        let handle = cx.entity().downgrade();
+        cx.global_mut::<TaskTerminals>().0.push(handle);
+        // The terminal is being updated in these callbacks, so it can only be
+        // read once they return.
+        cx.subscribe_self(|_, event, cx| {
+            if let Event::TaskLocatorReady { .. } = event {
+                cx.defer(update_status_item);
+            }
+        })
+        .detach();
+        cx.on_release(|_, cx| cx.defer(update_status_item)).detach();
+        cx.defer(update_status_item);
+    })
+    .detach();
+}
+
+fn update_status_item(cx: &mut App) {
+    let Some(registry) = StatusItemRegistry::global(cx) else {
+        return;
+    };
+    let mut terminals = std::mem::take(&mut cx.global_mut::<TaskTerminals>().0);
+    terminals.retain(|terminal| terminal.upgrade().is_some());
+    let running = terminals
+        .iter()
+        .filter_map(|terminal| terminal.upgrade())
+        .filter(|terminal| {
+            terminal
+                .read(cx)
+                .task()
+                .map_or(false, |task| task.status == TaskStatus::Running)
+        })
+        .count();
+    cx.global_mut::<TaskTerminals>().0 = terminals;
+
+    registry.update(cx, |registry, cx| {
+        if running == 0 {
+            registry.remove_item(STATUS_ITEM_NAME, cx);
+            return;
+        }
+        let tooltip = if running == 1 {
+            "1 task running".to_string()
+        } else {
+            format!("{running} tasks running")
+        };
+        registry.set_item(
+            StatusItem {
+                name: STATUS_ITEM_NAME.into(),
+                label: running.to_string().into(),
+                icon: Some(IconName::Play),
+                tooltip: Some(tooltip.into()),
+                action: Some(Box::new(terminal_panel::ToggleFocus)),
+                side: StatusItemSide::Left,
+            },
+            cx,
+        );
+    });
+}
@@ -613,8 +613,8 @@ impl Element for TerminalElement {
                 let font_features = terminal_settings
                     .font_features
                     .as_ref()
-                    .unwrap_or(&settings.buffer_font.features)
-                    .clone();
+                    .and_then(|features| settings.resolve_font_features(features))
+                    .unwrap_or_else(|| settings.buffer_font.features.clone());
 
                 let font_weight = terminal_settings.font_weight.unwrap_or_default();
 
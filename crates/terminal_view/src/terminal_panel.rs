@@ -436,7 +436,12 @@ impl TerminalPanel {
         let window_handle = window.window_handle();
         let terminal = project
             .update(cx, |project, cx| {
-                project.create_terminal_with_venv(kind, python_venv_directory, window_handle, cx)
+                project.create_terminal_with_venv(
+                    kind,
+                    python_venv_directory,
+                    Some(window_handle),
+                    cx,
+                )
             })
             .ok()?;
 
@@ -672,7 +677,7 @@ impl TerminalPanel {
         cx.spawn_in(window, async move |workspace, cx| {
             let terminal = project
                 .update(cx, |project, cx| {
-                    project.create_terminal(kind, window_handle, cx)
+                    project.create_terminal(kind, Some(window_handle), cx)
                 })?
                 .await?;
 
@@ -713,7 +718,7 @@ impl TerminalPanel {
             let window_handle = cx.window_handle();
             let terminal = project
                 .update(cx, |project, cx| {
-                    project.create_terminal(kind, window_handle, cx)
+                    project.create_terminal(kind, Some(window_handle), cx)
                 })?
                 .await?;
             let result = workspace.update_in(cx, |workspace, window, cx| {
@@ -827,7 +832,7 @@ impl TerminalPanel {
                 .flatten()?;
             let new_terminal = project
                 .update(cx, |project, cx| {
-                    project.create_terminal(TerminalKind::Task(spawn_task), window_handle, cx)
+                    project.create_terminal(TerminalKind::Task(spawn_task), Some(window_handle), cx)
                 })
                 .ok()?
                 .await
@@ -1,4 +1,6 @@
 mod persistence;
+pub mod remote_terminal_view;
+mod task_status;
 pub mod terminal_element;
 pub mod terminal_panel;
 pub mod terminal_scrollbar;
@@ -6,13 +8,15 @@ pub mod terminal_tab_tooltip;
 
 use editor::{actions::SelectAll, scroll::ScrollbarAutoHide, Editor, EditorSettings};
 use gpui::{
-    anchored, deferred, div, impl_actions, AnyElement, App, DismissEvent, Entity, EventEmitter,
-    FocusHandle, Focusable, KeyContext, KeyDownEvent, Keystroke, MouseButton, MouseDownEvent,
-    Pixels, Render, ScrollWheelEvent, Stateful, Styled, Subscription, Task, WeakEntity,
+    actions, anchored, deferred, div, impl_actions, AnyElement, App, DismissEvent, Entity,
+    EventEmitter, FocusHandle, Focusable, KeyContext, KeyDownEvent, Keystroke, MouseButton,
+    MouseDownEvent, Pixels, PromptLevel, Render, ScrollWheelEvent, Stateful, Styled, Subscription,
+    Task, WeakEntity,
 };
 use itertools::Itertools;
 use persistence::TERMINAL_DB;
 use project::{search::SearchQuery, terminals::TerminalKind, Entry, Metadata, Project};
+use rpc::proto::{self, PeerId};
 use schemars::JsonSchema;
 use terminal::{
     alacritty_terminal::{
@@ -34,12 +38,14 @@ use ui::{
 use util::{debug_panic, paths::PathWithPosition, ResultExt};
 use workspace::{
     item::{
-        BreadcrumbText, Item, ItemEvent, SerializableItem, TabContentParams, TabTooltipContent,
+        BreadcrumbText, Dedup, FollowEvent, FollowableItem, Item, ItemEvent, SerializableItem,
+        TabContentParams, TabTooltipContent,
     },
+    notifications::{self, NotificationSource},
     register_serializable_item,
     searchable::{Direction, SearchEvent, SearchOptions, SearchableItem, SearchableItemHandle},
     CloseActiveItem, NewCenterTerminal, NewTerminal, OpenOptions, OpenVisible, ToolbarItemLocation,
-    Workspace, WorkspaceId,
+    ViewId, Workspace, WorkspaceId,
 };
 
 use anyhow::Context as _;
@@ -65,6 +71,13 @@ const CURSOR_BLINK_INTERVAL: Duration = Duration::from_millis(500);
 
 const GIT_DIFF_PATH_PREFIXES: &[&str] = &["a", "b"];
 
+/// How a terminal is shared with the guests of the project.
+struct TerminalSharing {
+    is_shared: bool,
+    input_controller: Option<PeerId>,
+    guests: Vec<(PeerId, String)>,
+}
+
 /// Event to transmit the scroll from the element to the view
 #[derive(Clone, Debug, PartialEq)]
 pub struct ScrollTerminal(pub i32);
@@ -77,11 +90,16 @@ pub struct SendKeystroke(String);
 
 impl_actions!(terminal, [SendText, SendKeystroke]);
 
+actions!(terminal, [ToggleShareTerminal]);
+
 pub fn init(cx: &mut App) {
     terminal_panel::init(cx);
+    remote_terminal_view::init(cx);
+    task_status::init(cx);
     terminal::init(cx);
 
     register_serializable_item::<TerminalView>(cx);
+    workspace::FollowableViewRegistry::register::<TerminalView>(cx);
 
     cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
         workspace.register_action(TerminalView::deploy);
@@ -240,6 +258,8 @@ impl TerminalView {
             .map_or(false, |terminal_panel| {
                 terminal_panel.read(cx).assistant_enabled()
             });
+        let sharing = self.sharing_state(cx);
+        let this = cx.entity().downgrade();
         let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
             menu.context(self.focus_handle.clone())
                 .action("New Terminal", Box::new(NewTerminal))
@@ -252,6 +272,43 @@ impl TerminalView {
                     menu.separator()
                         .action("Inline Assist", Box::new(InlineAssist::default()))
                 })
+                .when_some(sharing, |mut menu, sharing| {
+                    menu = menu.separator().action(
+                        if sharing.is_shared {
+                            "Stop Sharing Terminal"
+                        } else {
+                            "Share Terminal"
+                        },
+                        Box::new(ToggleShareTerminal),
+                    );
+                    if !sharing.is_shared {
+                        return menu;
+                    }
+                    for (peer_id, github_login) in sharing.guests {
+                        if sharing.input_controller == Some(peer_id) {
+                            continue;
+                        }
+                        let this = this.clone();
+                        menu = menu.entry(
+                            format!("Give Input Control to @{github_login}"),
+                            None,
+                            move |_, cx| {
+                                this.update(cx, |this, cx| {
+                                    this.set_input_controller(Some(peer_id), cx)
+                                })
+                                .ok();
+                            },
+                        );
+                    }
+                    if sharing.input_controller.is_some() {
+                        let this = this.clone();
+                        menu = menu.entry("Take Back Input Control", None, move |_, cx| {
+                            this.update(cx, |this, cx| this.set_input_controller(None, cx))
+                                .ok();
+                        });
+                    }
+                    menu
+                })
                 .separator()
                 .action(
                     "Close Terminal Tab",
@@ -458,6 +515,85 @@ impl TerminalView {
         cx.notify();
     }
 
+    /// Returns how this terminal is shared, if the local user is the host of a
+    /// shared project and could share it.
+    fn sharing_state(&self, cx: &App) -> Option<TerminalSharing> {
+        let project = self.project.upgrade()?;
+        let project = project.read(cx);
+        if !project.is_shared() || project.is_via_collab() {
+            return None;
+        }
+        let user_store = project.user_store().read(cx);
+        let guests = project
+            .collaborators()
+            .values()
+            .filter(|collaborator| {
+                !collaborator.is_host && collaborator.permissions.can_use_terminal
+            })
+            .filter_map(|collaborator| {
+                let user = user_store.get_cached_user(collaborator.user_id)?;
+                Some((collaborator.peer_id, user.github_login.clone()))
+            })
+            .collect();
+        Some(TerminalSharing {
+            is_shared: project.is_terminal_shared(&self.terminal),
+            input_controller: project.shared_terminal_input_controller(&self.terminal),
+            guests,
+        })
+    }
+
+    fn toggle_share_terminal(
+        &mut self,
+        _: &ToggleShareTerminal,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project) = self.project.upgrade() else {
+            return;
+        };
+        let result = project.update(cx, |project, cx| {
+            if project.is_terminal_shared(&self.terminal) {
+                project.unshare_terminal(&self.terminal, cx);
+                Ok(())
+            } else {
+                project.share_terminal(&self.terminal, cx)
+            }
+        });
+        if let Err(error) = result {
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    workspace.show_error(&error, cx);
+                })
+                .ok();
+        }
+        window.refresh();
+        cx.notify();
+    }
+
+    fn set_input_controller(&mut self, controller: Option<PeerId>, cx: &mut Context<Self>) {
+        let Some(project) = self.project.upgrade() else {
+            return;
+        };
+        project.update(cx, |project, cx| {
+            project.set_shared_terminal_input_controller(&self.terminal, controller, cx)
+        });
+        cx.notify();
+    }
+
+    /// Returns the login of the guest who can currently type into this
+    /// terminal, if it is shared.
+    fn input_controller_login(&self, cx: &App) -> Option<String> {
+        let project = self.project.upgrade()?;
+        let project = project.read(cx);
+        let peer_id = project.shared_terminal_input_controller(&self.terminal)?;
+        let collaborator = project.collaborators().get(&peer_id)?;
+        let user = project
+            .user_store()
+            .read(cx)
+            .get_cached_user(collaborator.user_id)?;
+        Some(user.github_login.clone())
+    }
+
     fn toggle_vi_mode(&mut self, _: &ToggleViMode, _: &mut Window, cx: &mut Context<Self>) {
         self.terminal.update(cx, |term, _| term.toggle_vi_mode());
         cx.notify();
@@ -467,6 +603,7 @@ impl TerminalView {
         //Don't blink the cursor when not focused, blinking is disabled, or paused
         if !focused
             || self.blinking_paused
+            || cx.reduce_motion()
             || self
                 .terminal
                 .read(cx)
@@ -983,6 +1120,26 @@ fn subscribe_for_terminal_events(
                 cx.emit(SearchEvent::ActiveMatchChanged)
             }
             Event::TaskLocatorReady { task_id, success } => {
+                if let Some(task) = this.terminal.read(cx).task() {
+                    let (level, message) = if *success {
+                        (
+                            PromptLevel::Info,
+                            format!("Task `{}` finished successfully", task.full_label),
+                        )
+                    } else {
+                        (
+                            PromptLevel::Warning,
+                            format!("Task `{}` failed", task.full_label),
+                        )
+                    };
+                    notifications::record_notification(
+                        NotificationSource::Task,
+                        level,
+                        message,
+                        Some(Box::new(terminal_panel::ToggleFocus)),
+                        cx,
+                    );
+                }
                 if *success {
                     workspace
                         .update(cx, |workspace, cx| {
@@ -1264,6 +1421,7 @@ impl Render for TerminalView {
             .on_action(cx.listener(TerminalView::copy))
             .on_action(cx.listener(TerminalView::paste))
             .on_action(cx.listener(TerminalView::clear))
+            .on_action(cx.listener(TerminalView::toggle_share_terminal))
             .on_action(cx.listener(TerminalView::scroll_line_up))
             .on_action(cx.listener(TerminalView::scroll_line_down))
             .on_action(cx.listener(TerminalView::scroll_page_up))
@@ -1389,6 +1547,13 @@ impl Item for TerminalView {
                     }),
             )
             .child(Label::new(title).color(params.text_color()))
+            .when_some(self.input_controller_login(cx), |this, github_login| {
+                this.child(
+                    Label::new(format!("@{github_login} typing"))
+                        .color(Color::Accent)
+                        .size(LabelSize::Small),
+                )
+            })
             .into_any()
     }
 
@@ -1414,7 +1579,7 @@ impl Item for TerminalView {
                 project.create_terminal_with_venv(
                     TerminalKind::Shell(working_directory),
                     python_venv_directory,
-                    window_handle,
+                    Some(window_handle),
                     cx,
                 )
             })
@@ -1496,6 +1661,74 @@ impl Item for TerminalView {
     }
 }
 
+/// Collaborators follow the host into a terminal only while it is shared, in
+/// which case they see it as a [`RemoteTerminalView`](remote_terminal_view::RemoteTerminalView).
+impl FollowableItem for TerminalView {
+    fn remote_id(&self) -> Option<ViewId> {
+        None
+    }
+
+    fn to_state_proto(&self, _window: &Window, cx: &App) -> Option<proto::view::Variant> {
+        let project = self.project.upgrade()?;
+        if !project.read(cx).is_terminal_shared(&self.terminal) {
+            return None;
+        }
+        Some(proto::view::Variant::Terminal(proto::view::Terminal {
+            terminal_id: self.terminal.entity_id().as_u64(),
+        }))
+    }
+
+    fn from_state_proto(
+        _workspace: Entity<Workspace>,
+        _remote_id: ViewId,
+        _state: &mut Option<proto::view::Variant>,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) -> Option<Task<anyhow::Result<Entity<Self>>>> {
+        None
+    }
+
+    fn to_follow_event(_event: &Self::Event) -> Option<FollowEvent> {
+        None
+    }
+
+    fn add_event_to_update_proto(
+        &self,
+        _event: &Self::Event,
+        _update: &mut Option<proto::update_view::Variant>,
+        _window: &Window,
+        _cx: &App,
+    ) -> bool {
+        false
+    }
+
+    fn apply_update_proto(
+        &mut self,
+        _project: &Entity<Project>,
+        _message: proto::update_view::Variant,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn is_project_item(&self, _window: &Window, _cx: &App) -> bool {
+        true
+    }
+
+    fn set_leader_peer_id(
+        &mut self,
+        _leader_peer_id: Option<PeerId>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+
+    fn dedup(&self, _existing: &Self, _window: &Window, _cx: &App) -> Option<Dedup> {
+        None
+    }
+}
+
 impl SerializableItem for TerminalView {
     fn serialized_item_kind() -> &'static str {
         "Terminal"
@@ -1574,7 +1807,7 @@ impl SerializableItem for TerminalView {
 
             let terminal = project
                 .update(cx, |project, cx| {
-                    project.create_terminal(TerminalKind::Shell(cwd), window_handle, cx)
+                    project.create_terminal(TerminalKind::Shell(cwd), Some(window_handle), cx)
                 })?
                 .await?;
             cx.update(|window, cx| {
@@ -0,0 +1,996 @@
+//! Parsing of the output of test frameworks into the outcome of each test, and building the
+//! commands that run only some of the tests again.
+
+use std::collections::HashMap;
+
+/// The test frameworks whose output is understood, which are detected from the test command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestFramework {
+    /// Rust's built-in test harness, run with `cargo test`.
+    Libtest,
+    GoTest,
+    Pytest,
+    Unittest,
+    /// Jest and Vitest, whose output is similar.
+    Jest,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestResult {
+    /// The name that the framework identifies the test by, such as `module::tests::test_name`
+    /// for Rust or `tests/test_file.py::test_name` for pytest.
+    pub name: String,
+    pub outcome: TestOutcome,
+    /// The output of a failed test, such as its panic message or assertion error.
+    pub message: Option<String>,
+    pub diff: Option<FailureDiff>,
+}
+
+/// The values that a failed equality assertion compared.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FailureDiff {
+    pub expected: String,
+    pub actual: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    Unchanged(&'a str),
+    Expected(&'a str),
+    Actual(&'a str),
+}
+
+impl TestFramework {
+    /// Detects the framework from a test command and its arguments.
+    pub fn detect(command: &str, args: &[String]) -> Option<Self> {
+        let words = command
+            .split_whitespace()
+            .chain(args.iter().map(String::as_str))
+            .map(|word| word.rsplit(['/', '\\']).next().unwrap_or(word))
+            .collect::<Vec<_>>();
+        let has = |word: &str| words.iter().any(|candidate| *candidate == word);
+        if has("cargo") && (has("test") || has("nextest")) {
+            Some(Self::Libtest)
+        } else if has("go") && has("test") {
+            Some(Self::GoTest)
+        } else if has("pytest") || has("py.test") {
+            Some(Self::Pytest)
+        } else if has("unittest") {
+            Some(Self::Unittest)
+        } else if has("jest") || has("vitest") {
+            Some(Self::Jest)
+        } else {
+            None
+        }
+    }
+
+    /// The arguments that make the framework report the outcome of each test, rather than just
+    /// the failures.
+    pub fn verbose_args(&self) -> &'static [&'static str] {
+        match self {
+            Self::Libtest => &[],
+            Self::GoTest => &["-v"],
+            Self::Pytest | Self::Unittest => &["-v"],
+            Self::Jest => &["--verbose"],
+        }
+    }
+
+    /// Returns the path of the file that a test is in, relative to the directory the tests ran
+    /// in, for frameworks that name tests by their file.
+    pub fn test_file<'a>(&self, name: &'a str) -> Option<&'a str> {
+        match self {
+            Self::Pytest => name.split_once("::").map(|(file, _)| file),
+            Self::Libtest | Self::GoTest | Self::Unittest | Self::Jest => None,
+        }
+    }
+
+    /// Returns the name of the test's function or case, which is what its runnable in an
+    /// editor is named.
+    pub fn short_name<'a>(&self, name: &'a str) -> &'a str {
+        match self {
+            Self::Libtest => name.rsplit("::").next().unwrap_or(name),
+            Self::GoTest => name,
+            Self::Pytest => {
+                let name = name.rsplit("::").next().unwrap_or(name);
+                name.split('[').next().unwrap_or(name)
+            }
+            Self::Unittest => name.rsplit('.').next().unwrap_or(name),
+            Self::Jest => {
+                let name = name.rsplit(" › ").next().unwrap_or(name);
+                name.rsplit(" > ").next().unwrap_or(name).trim()
+            }
+        }
+    }
+
+    pub fn parse(&self, output: &str) -> Vec<TestResult> {
+        let mut results = match self {
+            Self::Libtest => parse_libtest(output),
+            Self::GoTest => parse_go_test(output),
+            Self::Pytest => parse_pytest(output),
+            Self::Unittest => parse_unittest(output),
+            Self::Jest => parse_jest(output),
+        };
+        for result in &mut results {
+            if let Some(message) = &result.message {
+                result.diff = failure_diff(message);
+            }
+        }
+        results
+    }
+
+    /// Returns the arguments that run only the given tests with the same command, replacing
+    /// the filters in the original arguments. The arguments are passed to a shell, so the names
+    /// are quoted.
+    pub fn rerun_args(&self, args: &[String], test_names: &[&str]) -> Vec<String> {
+        match self {
+            Self::Libtest => {
+                let (cargo_args, harness_args) = match args.iter().position(|arg| arg == "--") {
+                    Some(ix) => (&args[..ix], &args[ix + 1..]),
+                    None => (args, &[][..]),
+                };
+                let mut new_args = without_positional_args(
+                    cargo_args,
+                    &[
+                        "-p",
+                        "--package",
+                        "--bin",
+                        "--test",
+                        "--example",
+                        "--bench",
+                        "-F",
+                        "--features",
+                        "--target",
+                        "--target-dir",
+                        "--manifest-path",
+                        "--profile",
+                        "-j",
+                        "--jobs",
+                        "--color",
+                        "--message-format",
+                        "--config",
+                        "-Z",
+                    ],
+                    1,
+                );
+                new_args.push("--".to_string());
+                new_args.extend(
+                    without_positional_args(harness_args, &["--skip", "--test-threads"], 0)
+                        .into_iter()
+                        .filter(|arg| arg != "--exact"),
+                );
+                new_args.push("--exact".to_string());
+                new_args.extend(test_names.iter().map(|name| shell_quote(name)));
+                new_args
+            }
+            Self::GoTest => {
+                let mut new_args = without_flags(args, &["-run"]);
+                let mut top_level_names = Vec::new();
+                for name in test_names {
+                    let name = name.split('/').next().unwrap_or(name);
+                    if !top_level_names.contains(&name) {
+                        top_level_names.push(name);
+                    }
+                }
+                new_args.push("-run".to_string());
+                new_args.push(shell_quote(&format!("^({})$", top_level_names.join("|"))));
+                new_args
+            }
+            Self::Pytest | Self::Unittest => {
+                let module = if *self == Self::Pytest {
+                    "pytest"
+                } else {
+                    "unittest"
+                };
+                let mut new_args = match args.iter().position(|arg| arg == module) {
+                    Some(ix) => {
+                        let mut new_args = args[..=ix].to_vec();
+                        new_args.extend(without_positional_args(
+                            &without_flags(&args[ix + 1..], &["-k"]),
+                            &[],
+                            0,
+                        ));
+                        new_args
+                    }
+                    None => without_positional_args(args, &[], 0),
+                };
+                new_args.extend(test_names.iter().map(|name| shell_quote(name)));
+                new_args
+            }
+            Self::Jest => {
+                let mut new_args = without_flags(args, &["-t", "--testNamePattern"]);
+                let names = test_names
+                    .iter()
+                    .map(|name| regex_escape(&name.replace(" › ", " ").replace(" > ", " ")))
+                    .collect::<Vec<_>>();
+                new_args.push("--testNamePattern".to_string());
+                new_args.push(shell_quote(&format!("^({})$", names.join("|"))));
+                new_args
+            }
+        }
+    }
+}
+
+/// Removes the positional arguments after the first `keep_leading` ones, keeping the flags and
+/// the values of the given flags that take one.
+fn without_positional_args(
+    args: &[String],
+    flags_with_values: &[&str],
+    keep_leading: usize,
+) -> Vec<String> {
+    let mut new_args = Vec::new();
+    let mut positional_count = 0;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg.starts_with('-') {
+            new_args.push(arg.clone());
+            if flags_with_values.contains(&arg.as_str()) {
+                new_args.extend(args.next().cloned());
+            }
+        } else {
+            if positional_count < keep_leading {
+                new_args.push(arg.clone());
+            }
+            positional_count += 1;
+        }
+    }
+    new_args
+}
+
+/// Removes the given flags, along with their values.
+fn without_flags(args: &[String], flags: &[&str]) -> Vec<String> {
+    let mut new_args = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if flags.contains(&arg.as_str()) {
+            args.next();
+        } else if !flags
+            .iter()
+            .any(|flag| arg.starts_with(&format!("{flag}=")))
+        {
+            new_args.push(arg.clone());
+        }
+    }
+    new_args
+}
+
+fn shell_quote(arg: &str) -> String {
+    shlex::try_quote(arg)
+        .map(|arg| arg.into_owned())
+        .unwrap_or_else(|_| arg.to_string())
+}
+
+fn regex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        if "\\.+*?()|[]{}^$".contains(char) {
+            escaped.push('\\');
+        }
+        escaped.push(char);
+    }
+    escaped
+}
+
+fn parse_libtest(output: &str) -> Vec<TestResult> {
+    let mut results = Vec::new();
+    let mut failure_messages = HashMap::<&str, Vec<&str>>::new();
+    let mut current_failure = None;
+    for line in output.lines() {
+        if let Some(rest) = line.strip_prefix("test ") {
+            if let Some((name, status)) = rest.rsplit_once(" ... ") {
+                let outcome = if status == "ok" {
+                    TestOutcome::Passed
+                } else if status == "FAILED" {
+                    TestOutcome::Failed
+                } else if status.starts_with("ignored") {
+                    TestOutcome::Skipped
+                } else {
+                    continue;
+                };
+                results.push(test_result(name, outcome));
+                continue;
+            }
+        }
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|line| line.strip_suffix(" stdout ----"))
+        {
+            current_failure = Some(name);
+            continue;
+        }
+        if line == "failures:" || line.starts_with("test result:") {
+            current_failure = None;
+            continue;
+        }
+        if let Some(name) = current_failure {
+            failure_messages.entry(name).or_default().push(line);
+        }
+    }
+    set_failure_messages(&mut results, failure_messages, |result_name, name| {
+        result_name == name
+    });
+    results
+}
+
+fn parse_go_test(output: &str) -> Vec<TestResult> {
+    let mut results = Vec::new();
+    let mut test_output = HashMap::<&str, Vec<&str>>::new();
+    let mut current_test = None;
+    for line in output.lines() {
+        let trimmed = line.trim_start();
+        if let Some(name) = trimmed
+            .strip_prefix("=== RUN")
+            .or_else(|| trimmed.strip_prefix("=== CONT"))
+            .or_else(|| trimmed.strip_prefix("=== PAUSE"))
+        {
+            current_test = Some(name.trim());
+            continue;
+        }
+        let status = [
+            ("--- PASS: ", TestOutcome::Passed),
+            ("--- FAIL: ", TestOutcome::Failed),
+            ("--- SKIP: ", TestOutcome::Skipped),
+        ]
+        .into_iter()
+        .find_map(|(prefix, outcome)| Some((trimmed.strip_prefix(prefix)?, outcome)));
+        if let Some((rest, outcome)) = status {
+            let name = rest.split(" (").next().unwrap_or(rest).trim();
+            results.push(test_result(name, outcome));
+            // Older versions of Go print the output of tests after their outcome.
+            current_test = Some(name);
+            continue;
+        }
+        if trimmed.starts_with("PASS")
+            || trimmed.starts_with("FAIL")
+            || trimmed.starts_with("ok ")
+            || trimmed.starts_with("=== NAME")
+        {
+            current_test = None;
+            continue;
+        }
+        if let Some(name) = current_test {
+            if line.starts_with(char::is_whitespace) {
+                test_output.entry(name).or_default().push(line);
+            }
+        }
+    }
+    set_failure_messages(&mut results, test_output, |result_name, name| {
+        result_name == name
+    });
+    results
+}
+
+fn parse_pytest(output: &str) -> Vec<TestResult> {
+    let mut results = Vec::new();
+    let mut failure_messages = HashMap::<&str, Vec<&str>>::new();
+    let mut current_failure = None;
+    for line in output.lines() {
+        if let Some((name, rest)) = line.split_once(' ') {
+            if name.contains("::") {
+                let status = rest.split_whitespace().next().unwrap_or_default();
+                let outcome = match status {
+                    "PASSED" | "XFAIL" => Some(TestOutcome::Passed),
+                    "FAILED" | "ERROR" | "XPASS" => Some(TestOutcome::Failed),
+                    "SKIPPED" => Some(TestOutcome::Skipped),
+                    _ => None,
+                };
+                if let Some(outcome) = outcome {
+                    results.push(test_result(name, outcome));
+                    continue;
+                }
+            }
+        }
+        if line.starts_with('_') && line.ends_with('_') {
+            let name = line.trim_matches(|char| char == '_' || char == ' ');
+            current_failure = (!name.is_empty()).then_some(name);
+            continue;
+        }
+        if line.starts_with("===") {
+            current_failure = None;
+            continue;
+        }
+        if let Some(name) = current_failure {
+            failure_messages.entry(name).or_default().push(line);
+        }
+    }
+    // Failure headers name tests by their class and function, like `TestClass.test_name`.
+    set_failure_messages(&mut results, failure_messages, |result_name, name| {
+        let test_path = result_name
+            .split_once("::")
+            .map_or(result_name, |(_, path)| path);
+        test_path.replace("::", ".") == name
+    });
+    results
+}
+
+fn parse_unittest(output: &str) -> Vec<TestResult> {
+    let mut results = Vec::new();
+    let mut failure_messages = HashMap::<String, Vec<&str>>::new();
+    let mut current_failure = None;
+    let mut lines = output.lines().peekable();
+    while let Some(line) = lines.next() {
+        if let Some((test, status)) = line.split_once(" ... ") {
+            if let Some(name) = unittest_id(test) {
+                let outcome = if status == "ok" {
+                    Some(TestOutcome::Passed)
+                } else if status == "FAIL" || status == "ERROR" {
+                    Some(TestOutcome::Failed)
+                } else if status.starts_with("skipped") || status == "expected failure" {
+                    Some(TestOutcome::Skipped)
+                } else {
+                    None
+                };
+                if let Some(outcome) = outcome {
+                    results.push(test_result(&name, outcome));
+                    continue;
+                }
+            }
+        }
+        if let Some(test) = line
+            .strip_prefix("FAIL: ")
+            .or_else(|| line.strip_prefix("ERROR: "))
+        {
+            current_failure = unittest_id(test);
+            // Skip the line of dashes under the header.
+            lines.next_if(|line| line.starts_with("---"));
+            continue;
+        }
+        if line.starts_with("=====") || line.starts_with("-----") {
+            current_failure = None;
+            continue;
+        }
+        if let Some(name) = &current_failure {
+            failure_messages.entry(name.clone()).or_default().push(line);
+        }
+    }
+    set_failure_messages(&mut results, failure_messages, |result_name, name| {
+        result_name == name
+    });
+    results
+}
+
+/// Returns the id of a test from how unittest describes it, which is `test_name (module.Class)`
+/// or, since Python 3.11, `test_name (module.Class.test_name)`.
+fn unittest_id(description: &str) -> Option<String> {
+    let (method, rest) = description.split_once(" (")?;
+    let class_path = rest.split(')').next()?;
+    if class_path.ends_with(&format!(".{method}")) {
+        Some(class_path.to_string())
+    } else {
+        Some(format!("{class_path}.{method}"))
+    }
+}
+
+fn parse_jest(output: &str) -> Vec<TestResult> {
+    let mut results = Vec::new();
+    let mut failure_messages = HashMap::<&str, Vec<&str>>::new();
+    let mut current_failure = None;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        let status = [
+            ("✓ ", TestOutcome::Passed),
+            ("√ ", TestOutcome::Passed),
+            ("✕ ", TestOutcome::Failed),
+            ("× ", TestOutcome::Failed),
+            ("○ ", TestOutcome::Skipped),
+            ("↓ ", TestOutcome::Skipped),
+        ]
+        .into_iter()
+        .find_map(|(prefix, outcome)| Some((trimmed.strip_prefix(prefix)?, outcome)));
+        if let Some((rest, outcome)) = status {
+            // Strip the duration, like `(3 ms)`, and Vitest's file path.
+            let name = match rest.rsplit_once(" (") {
+                Some((name, duration)) if duration.ends_with("ms)") || duration.ends_with("s)") => {
+                    name
+                }
+                _ => rest,
+            };
+            let name = name.strip_prefix("skipped ").unwrap_or(name);
+            let name = match name.split_once(" > ") {
+                Some((file, test)) if file.contains('.') => test,
+                _ => name,
+            };
+            results.push(test_result(name.trim(), outcome));
+            current_failure = None;
+            continue;
+        }
+        if let Some(name) = trimmed
+            .strip_prefix("● ")
+            .or_else(|| trimmed.strip_prefix("FAIL "))
+        {
+            if !name.is_empty() && !name.starts_with("Test suite failed") {
+                current_failure = Some(name.trim());
+                continue;
+            }
+        }
+        if trimmed.starts_with("Test Suites:") || trimmed.starts_with("Test Files ") {
+            current_failure = None;
+            continue;
+        }
+        if let Some(name) = current_failure {
+            failure_messages.entry(name).or_default().push(line);
+        }
+    }
+
+    // Failures are headed by the test's full name, with its `describe` blocks, which is what
+    // reruns are filtered by.
+    for (full_name, lines) in failure_messages {
+        let title = TestFramework::Jest.short_name(full_name);
+        let Some(result) = results.iter_mut().find(|result| {
+            result.outcome == TestOutcome::Failed
+                && result.message.is_none()
+                && (result.name == title || result.name == full_name)
+        }) else {
+            continue;
+        };
+        result.name = full_name.to_string();
+        result.message = message(&lines);
+    }
+    results
+}
+
+fn test_result(name: &str, outcome: TestOutcome) -> TestResult {
+    TestResult {
+        name: name.to_string(),
+        outcome,
+        message: None,
+        diff: None,
+    }
+}
+
+fn set_failure_messages<K: AsRef<str>>(
+    results: &mut [TestResult],
+    messages: HashMap<K, Vec<&str>>,
+    matches: impl Fn(&str, &str) -> bool,
+) {
+    for result in results {
+        if result.outcome != TestOutcome::Failed {
+            continue;
+        }
+        if let Some((_, lines)) = messages
+            .iter()
+            .find(|(name, _)| matches(&result.name, name.as_ref()))
+        {
+            result.message = message(lines);
+        }
+    }
+}
+
+/// Joins the lines of a failure message, removing their common indentation and the blank
+/// lines around them.
+fn message(lines: &[&str]) -> Option<String> {
+    let start = lines.iter().position(|line| !line.trim().is_empty())?;
+    let end = lines.iter().rposition(|line| !line.trim().is_empty())? + 1;
+    let lines = &lines[start..end];
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    Some(
+        lines
+            .iter()
+            .map(|line| line.get(indent..).unwrap_or("").trim_end())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+/// Finds the values compared by a failed equality assertion in a failure message.
+fn failure_diff(message: &str) -> Option<FailureDiff> {
+    let mut expected = None;
+    let mut actual = None;
+    for line in message.lines() {
+        // pytest prefixes the lines of assertion errors with `E`.
+        let line = line.strip_prefix("E ").unwrap_or(line).trim();
+        let value = |value: &str| {
+            value
+                .trim()
+                .trim_end_matches(',')
+                .trim_matches('`')
+                .to_string()
+        };
+        // Rust's `assert_eq!(actual, expected)`.
+        if let Some(left) = line.strip_prefix("left:") {
+            actual.get_or_insert_with(|| value(left));
+        } else if let Some(right) = line.strip_prefix("right:") {
+            expected.get_or_insert_with(|| value(right));
+        // Jest, Vitest and Go's testify.
+        } else if let Some(value_text) = line
+            .strip_prefix("Expected:")
+            .or_else(|| line.strip_prefix("expected:"))
+        {
+            expected.get_or_insert_with(|| value(value_text));
+        } else if let Some(value_text) = line
+            .strip_prefix("Received:")
+            .or_else(|| line.strip_prefix("actual  :"))
+            .or_else(|| line.strip_prefix("actual:"))
+        {
+            actual.get_or_insert_with(|| value(value_text));
+        // pytest's `assert actual == expected`.
+        } else if let Some(comparison) = line.strip_prefix("assert ") {
+            if let Some((left, right)) = comparison.split_once(" == ") {
+                actual.get_or_insert_with(|| value(left));
+                expected.get_or_insert_with(|| value(right));
+            }
+        // unittest's `assertEqual(actual, expected)`.
+        } else if let Some(comparison) = line.strip_prefix("AssertionError: ") {
+            if let Some((left, right)) = comparison.split_once(" != ") {
+                actual.get_or_insert_with(|| value(left));
+                expected.get_or_insert_with(|| value(right));
+            } else if let Some((left, right)) = comparison
+                .strip_prefix("assert ")
+                .and_then(|comparison| comparison.split_once(" == "))
+            {
+                actual.get_or_insert_with(|| value(left));
+                expected.get_or_insert_with(|| value(right));
+            }
+        }
+    }
+    Some(FailureDiff {
+        expected: expected?,
+        actual: actual?,
+    })
+}
+
+impl FailureDiff {
+    /// Compares the expected and actual values line by line, with the longest common
+    /// subsequence of their lines unchanged.
+    pub fn lines(&self) -> Vec<DiffLine<'_>> {
+        let expected = split_value_lines(&self.expected);
+        let actual = split_value_lines(&self.actual);
+
+        let mut common = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+        for (i, expected_line) in expected.iter().enumerate().rev() {
+            for (j, actual_line) in actual.iter().enumerate().rev() {
+                common[i][j] = if expected_line == actual_line {
+                    common[i + 1][j + 1] + 1
+                } else {
+                    common[i + 1][j].max(common[i][j + 1])
+                };
+            }
+        }
+
+        let mut lines = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < expected.len() || j < actual.len() {
+            if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+                lines.push(DiffLine::Unchanged(expected[i]));
+                i += 1;
+                j += 1;
+            } else if j == actual.len()
+                || (i < expected.len() && common[i + 1][j] >= common[i][j + 1])
+            {
+                lines.push(DiffLine::Expected(expected[i]));
+                i += 1;
+            } else {
+                lines.push(DiffLine::Actual(actual[j]));
+                j += 1;
+            }
+        }
+        lines
+    }
+}
+
+/// Splits a value into lines, including the `\n` escapes of values printed with `{:?}`.
+fn split_value_lines(value: &str) -> Vec<&str> {
+    if value.contains('\n') {
+        value.lines().collect()
+    } else {
+        value.split("\\n").collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn outcomes(results: &[TestResult]) -> Vec<(&str, TestOutcome)> {
+        results
+            .iter()
+            .map(|result| (result.name.as_str(), result.outcome))
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_framework() {
+        assert_eq!(
+            TestFramework::detect("cargo", &args(&["test", "-p", "editor"])),
+            Some(TestFramework::Libtest)
+        );
+        assert_eq!(
+            TestFramework::detect("go", &args(&["test", "./..."])),
+            Some(TestFramework::GoTest)
+        );
+        assert_eq!(
+            TestFramework::detect("/usr/bin/python3", &args(&["-m", "pytest", "tests"])),
+            Some(TestFramework::Pytest)
+        );
+        assert_eq!(
+            TestFramework::detect("python", &args(&["-m", "unittest", "tests.test_a"])),
+            Some(TestFramework::Unittest)
+        );
+        assert_eq!(
+            TestFramework::detect("npx jest", &args(&["src/a.test.ts"])),
+            Some(TestFramework::Jest)
+        );
+        assert_eq!(TestFramework::detect("cargo", &args(&["run"])), None);
+    }
+
+    #[test]
+    fn test_parse_libtest() {
+        let output = "
+running 3 tests
+test parser::tests::test_parse ... ok
+test parser::tests::test_ignored ... ignored, slow
+test parser::tests::test_fail ... FAILED
+
+failures:
+
+---- parser::tests::test_fail stdout ----
+
+thread 'parser::tests::test_fail' panicked at src/parser.rs:10:5:
+assertion `left == right` failed
+  left: \"a\\nb\"
+ right: \"a\\nc\"
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    parser::tests::test_fail
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out
+";
+        let results = TestFramework::Libtest.parse(output);
+        assert_eq!(
+            outcomes(&results),
+            vec![
+                ("parser::tests::test_parse", TestOutcome::Passed),
+                ("parser::tests::test_ignored", TestOutcome::Skipped),
+                ("parser::tests::test_fail", TestOutcome::Failed),
+            ]
+        );
+        assert!(results[2]
+            .message
+            .as_deref()
+            .unwrap()
+            .starts_with("thread 'parser::tests::test_fail' panicked"));
+        let diff = results[2].diff.as_ref().unwrap();
+        assert_eq!(
+            diff.lines(),
+            vec![
+                DiffLine::Unchanged("\"a"),
+                DiffLine::Expected("c\""),
+                DiffLine::Actual("b\""),
+            ]
+        );
+        assert_eq!(
+            TestFramework::Libtest.short_name("parser::tests::test_fail"),
+            "test_fail"
+        );
+    }
+
+    #[test]
+    fn test_parse_go_test() {
+        let output = "=== RUN   TestAdd
+--- PASS: TestAdd (0.00s)
+=== RUN   TestSub
+    math_test.go:12: got 1, want 2
+--- FAIL: TestSub (0.00s)
+=== RUN   TestTable
+=== RUN   TestTable/empty
+    table_test.go:20:
+        \tError Trace:\ttable_test.go:20
+        \tError:      \tNot equal:
+        \t            \texpected: 2
+        \t            \tactual  : 3
+--- FAIL: TestTable (0.00s)
+    --- FAIL: TestTable/empty (0.00s)
+FAIL
+exit status 1
+FAIL\texample.com/math\t0.002s
+";
+        let results = TestFramework::GoTest.parse(output);
+        assert_eq!(
+            outcomes(&results),
+            vec![
+                ("TestAdd", TestOutcome::Passed),
+                ("TestSub", TestOutcome::Failed),
+                ("TestTable", TestOutcome::Failed),
+                ("TestTable/empty", TestOutcome::Failed),
+            ]
+        );
+        assert_eq!(
+            results[1].message.as_deref(),
+            Some("math_test.go:12: got 1, want 2")
+        );
+        assert_eq!(
+            results[3].diff,
+            Some(FailureDiff {
+                expected: "2".into(),
+                actual: "3".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_pytest() {
+        let output =
+            "============================= test session starts ==============================
+collected 3 items
+
+tests/test_math.py::test_add PASSED                                      [ 33%]
+tests/test_math.py::TestOps::test_mul FAILED                             [ 66%]
+tests/test_math.py::test_skip SKIPPED (no reason)                        [100%]
+
+=================================== FAILURES ===================================
+_______________________________ TestOps.test_mul _______________________________
+
+    def test_mul(self):
+>       assert mul(2, 3) == 5
+E       assert 6 == 5
+
+tests/test_math.py:10: AssertionError
+=========================== short test summary info ============================
+FAILED tests/test_math.py::TestOps::test_mul - assert 6 == 5
+";
+        let results = TestFramework::Pytest.parse(output);
+        assert_eq!(
+            outcomes(&results),
+            vec![
+                ("tests/test_math.py::test_add", TestOutcome::Passed),
+                ("tests/test_math.py::TestOps::test_mul", TestOutcome::Failed),
+                ("tests/test_math.py::test_skip", TestOutcome::Skipped),
+            ]
+        );
+        assert_eq!(
+            results[1].diff,
+            Some(FailureDiff {
+                expected: "5".into(),
+                actual: "6".into()
+            })
+        );
+        assert_eq!(
+            TestFramework::Pytest.short_name("tests/test_math.py::test_add[1-2]"),
+            "test_add"
+        );
+        assert_eq!(
+            TestFramework::Pytest.test_file("tests/test_math.py::TestOps::test_mul"),
+            Some("tests/test_math.py")
+        );
+        assert_eq!(
+            TestFramework::Libtest.test_file("math::tests::test_mul"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_unittest() {
+        let output = "test_add (tests.test_math.MathTest.test_add) ... ok
+test_sub (tests.test_math.MathTest) ... FAIL
+
+======================================================================
+FAIL: test_sub (tests.test_math.MathTest)
+----------------------------------------------------------------------
+Traceback (most recent call last):
+  File \"tests/test_math.py\", line 9, in test_sub
+    self.assertEqual(sub(3, 1), 1)
+AssertionError: 2 != 1
+
+----------------------------------------------------------------------
+Ran 2 tests in 0.001s
+";
+        let results = TestFramework::Unittest.parse(output);
+        assert_eq!(
+            outcomes(&results),
+            vec![
+                ("tests.test_math.MathTest.test_add", TestOutcome::Passed),
+                ("tests.test_math.MathTest.test_sub", TestOutcome::Failed),
+            ]
+        );
+        assert_eq!(
+            results[1].diff,
+            Some(FailureDiff {
+                expected: "1".into(),
+                actual: "2".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_jest() {
+        let output = " FAIL  src/math.test.js
+  math
+    ✓ adds numbers (3 ms)
+    ✕ subtracts numbers (5 ms)
+    ○ skipped divides numbers
+
+  ● math › subtracts numbers
+
+    expect(received).toBe(expected) // Object.is equality
+
+    Expected: 1
+    Received: 2
+
+      4 |   test('subtracts numbers', () => {
+
+Test Suites: 1 failed, 1 total
+";
+        let results = TestFramework::Jest.parse(output);
+        assert_eq!(
+            outcomes(&results),
+            vec![
+                ("adds numbers", TestOutcome::Passed),
+                ("math › subtracts numbers", TestOutcome::Failed),
+                ("divides numbers", TestOutcome::Skipped),
+            ]
+        );
+        assert_eq!(
+            results[1].diff,
+            Some(FailureDiff {
+                expected: "1".into(),
+                actual: "2".into()
+            })
+        );
+        assert_eq!(
+            TestFramework::Jest.short_name("math › subtracts numbers"),
+            "subtracts numbers"
+        );
+    }
+
+    #[test]
+    fn test_rerun_args() {
+        assert_eq!(
+            TestFramework::Libtest.rerun_args(
+                &args(&["test", "-p", "editor", "test_foo", "--", "--nocapture"]),
+                &["tests::test_foo", "tests::test_foo_bar"],
+            ),
+            args(&[
+                "test",
+                "-p",
+                "editor",
+                "--",
+                "--nocapture",
+                "--exact",
+                "tests::test_foo",
+                "tests::test_foo_bar",
+            ])
+        );
+        assert_eq!(
+            TestFramework::GoTest.rerun_args(
+                &args(&["test", "-v", "-run", "'^TestA$'", "./..."]),
+                &["TestA/case", "TestB"],
+            ),
+            args(&["test", "-v", "./...", "-run", "'^(TestA|TestB)$'"])
+        );
+        assert_eq!(
+            TestFramework::Pytest.rerun_args(
+                &args(&["-m", "pytest", "-v", "tests/test_math.py"]),
+                &["tests/test_math.py::test_add"],
+            ),
+            args(&["-m", "pytest", "-v", "tests/test_math.py::test_add"])
+        );
+        assert_eq!(
+            TestFramework::Jest.rerun_args(
+                &args(&["--testNamePattern", "\"adds\"", "src/math.test.js"]),
+                &["math › adds (numbers)"],
+            ),
+            args(&[
+                "src/math.test.js",
+                "--testNamePattern",
+                "'^(math adds \\(numbers\\))$'",
+            ])
+        );
+    }
+}
@@ -0,0 +1,775 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use collections::HashSet;
+use editor::{Editor, RunnableKey, RunnableStatus};
+use gpui::{
+    px, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable, Pixels,
+    SharedString, Task, Window,
+};
+use project::{Project, ProjectPath};
+use task::{SpawnInTerminal, TaskType, VariableName};
+use ui::{prelude::*, Disclosure, ListItem, ListItemSpacing, Tooltip};
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    Workspace,
+};
+
+use crate::{
+    is_test_task,
+    test_output::{DiffLine, TestFramework, TestOutcome, TestResult},
+    DebugNearestTest, RerunAllTests, RerunFailedTests, RunNearestTest, TestStatuses, ToggleFocus,
+};
+
+const DEFAULT_SIZE: Pixels = px(360.);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+        });
+        workspace.register_action(run_nearest_test);
+        workspace.register_action(debug_nearest_test);
+        workspace.register_action(|workspace, _: &RerunFailedTests, window, cx| {
+            if let Some(panel) = workspace.panel::<TestPanel>(cx) {
+                panel.update(cx, |panel, cx| panel.rerun(true, window, cx));
+            }
+        });
+        workspace.register_action(|workspace, _: &RerunAllTests, window, cx| {
+            if let Some(panel) = workspace.panel::<TestPanel>(cx) {
+                panel.update(cx, |panel, cx| panel.rerun(false, window, cx));
+            }
+        });
+
+        let project = workspace.project().clone();
+        let panel = cx.new(|cx| TestPanel::new(project, cx));
+        workspace.add_panel(panel, window, cx);
+    })
+    .detach();
+}
+
+/// Runs the test that encloses the cursor in the active editor, or the closest one, and shows
+/// its outcome in the test panel.
+fn run_nearest_test(
+    workspace: &mut Workspace,
+    _: &RunNearestTest,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some(panel) = workspace.panel::<TestPanel>(cx) else {
+        return;
+    };
+    let task = editor.update(cx, |editor, cx| {
+        editor.resolve_nearest_runnable_task(
+            |template| is_test_task(template) && template.task_type == TaskType::Script,
+            cx,
+        )
+    });
+    workspace.focus_panel::<TestPanel>(window, cx);
+    cx.spawn_in(window, async move |_, cx| {
+        let Some((_, mut resolved_task)) = task.await else {
+            return;
+        };
+        let Some(spawn_in_terminal) = resolved_task.resolved.take() else {
+            return;
+        };
+        panel
+            .update_in(cx, |panel, window, cx| {
+                panel.run(spawn_in_terminal, Vec::new(), window, cx)
+            })
+            .ok();
+    })
+    .detach();
+}
+
+/// Starts debugging the test that encloses the cursor in the active editor, or the closest one,
+/// with the language's debug task for tests.
+fn debug_nearest_test(
+    workspace: &mut Workspace,
+    _: &DebugNearestTest,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let task = editor.update(cx, |editor, cx| {
+        editor.resolve_nearest_runnable_task(
+            |template| is_test_task(template) && matches!(template.task_type, TaskType::Debug(_)),
+            cx,
+        )
+    });
+    cx.spawn_in(window, async move |workspace, cx| {
+        let Some((task_source_kind, resolved_task)) = task.await else {
+            return;
+        };
+        workspace
+            .update(cx, |workspace, cx| {
+                workspace::tasks::schedule_resolved_task(
+                    workspace,
+                    task_source_kind,
+                    resolved_task,
+                    false,
+                    cx,
+                );
+            })
+            .ok();
+    })
+    .detach();
+}
+
+/// A run of a test command, whose output is parsed into the outcomes of its tests once it
+/// finishes.
+struct TestRun {
+    /// The command that was run, which is run again with different filters to rerun tests.
+    task: SpawnInTerminal,
+    framework: Option<TestFramework>,
+    /// The directory the command runs in.
+    cwd: Option<PathBuf>,
+    /// The file of the runnable that the command was started from.
+    source: Option<ProjectPath>,
+    status: RunStatus,
+    results: Vec<TestResult>,
+    output: String,
+}
+
+enum RunStatus {
+    Running,
+    Finished { success: bool },
+    Failed(SharedString),
+}
+
+/// The dock panel that runs tests and lists their outcomes, with the output and the expected
+/// and actual values of failed tests.
+pub struct TestPanel {
+    project: Entity<Project>,
+    focus_handle: FocusHandle,
+    position: DockPosition,
+    size: Option<Pixels>,
+    run: Option<TestRun>,
+    /// The names of the tests whose failures are expanded.
+    expanded: HashSet<String>,
+    show_output: bool,
+    _run_task: Task<()>,
+}
+
+impl TestPanel {
+    fn new(project: Entity<Project>, cx: &mut Context<Self>) -> Self {
+        Self {
+            project,
+            focus_handle: cx.focus_handle(),
+            position: DockPosition::Bottom,
+            size: None,
+            run: None,
+            expanded: HashSet::default(),
+            show_output: false,
+            _run_task: Task::ready(()),
+        }
+    }
+
+    /// Runs a test command in the background, rather than in a terminal, to collect its output.
+    /// `rerun_names` are the tests that a rerun runs, which are shown as running until it ends.
+    fn run(
+        &mut self,
+        mut task: SpawnInTerminal,
+        rerun_names: Vec<String>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let framework = TestFramework::detect(&task.command, &task.args);
+        let cwd = task.cwd.clone().or_else(|| {
+            self.project
+                .read(cx)
+                .visible_worktrees(cx)
+                .next()
+                .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+        });
+        let source = task
+            .env
+            .get(&VariableName::File.to_string())
+            .and_then(|file| {
+                self.project
+                    .read(cx)
+                    .project_path_for_absolute_path(Path::new(file), cx)
+            });
+        let mut run = TestRun {
+            task: task.clone(),
+            framework,
+            cwd: cwd.clone(),
+            source,
+            status: RunStatus::Running,
+            results: Vec::new(),
+            output: String::new(),
+        };
+        if !self.project.read(cx).is_local() {
+            run.status = RunStatus::Failed("Tests can only be run in local projects.".into());
+            self.run = Some(run);
+            cx.notify();
+            return;
+        }
+
+        if let Some(framework) = framework {
+            for arg in framework.verbose_args() {
+                if !task.args.iter().any(|existing| existing == arg) {
+                    task.args.push(arg.to_string());
+                }
+            }
+            let statuses = rerun_names
+                .iter()
+                .filter_map(|name| {
+                    let key = runnable_key(self.project.read(cx), &run, framework, name, cx)?;
+                    Some((key, RunnableStatus::Running))
+                })
+                .collect::<Vec<_>>();
+            self.set_statuses(statuses, cx);
+        }
+
+        self.run = Some(run);
+        self.expanded.clear();
+        cx.notify();
+
+        let output = cx.background_spawn(run_command(task, cwd));
+        self._run_task = cx.spawn(async move |this, cx| {
+            let output = output.await;
+            this.update(cx, |this, cx| this.finish_run(output, cx)).ok();
+        });
+    }
+
+    fn finish_run(&mut self, output: anyhow::Result<(bool, String)>, cx: &mut Context<Self>) {
+        let Some(run) = self.run.as_mut() else {
+            return;
+        };
+        match output {
+            Ok((success, output)) => {
+                run.results = run
+                    .framework
+                    .map(|framework| framework.parse(&output))
+                    .unwrap_or_default();
+                run.output = output;
+                run.status = RunStatus::Finished { success };
+            }
+            Err(error) => {
+                run.status = RunStatus::Failed(format!("{error:#}").into());
+            }
+        }
+        // Show the output when it couldn't be parsed into tests, which is all there is to see.
+        self.show_output = run.results.is_empty();
+
+        if let Some(framework) = run.framework {
+            let mut statuses = collections::HashMap::default();
+            for result in &run.results {
+                let status = match result.outcome {
+                    TestOutcome::Passed => RunnableStatus::Passed,
+                    TestOutcome::Failed => RunnableStatus::Failed,
+                    TestOutcome::Skipped => continue,
+                };
+                let Some(key) =
+                    runnable_key(self.project.read(cx), run, framework, &result.name, cx)
+                else {
+                    continue;
+                };
+                // Tests with the same name in different modules of a file fail together.
+                if statuses.get(&key) != Some(&RunnableStatus::Failed) {
+                    statuses.insert(key, status);
+                }
+            }
+            self.set_statuses(statuses, cx);
+        }
+        cx.notify();
+    }
+
+    fn set_statuses(
+        &self,
+        statuses: impl IntoIterator<Item = (RunnableKey, RunnableStatus)>,
+        cx: &mut Context<Self>,
+    ) {
+        let project_id = self.project.entity_id();
+        if let Some(test_statuses) = TestStatuses::global(cx) {
+            test_statuses.update(cx, |test_statuses, cx| {
+                test_statuses.set_statuses(project_id, statuses, cx)
+            });
+        }
+    }
+
+    /// Runs the last test command again, with only the tests that failed if `failed_only` is
+    /// true.
+    fn rerun(&mut self, failed_only: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(run) = self.run.as_ref() else {
+            return;
+        };
+        if matches!(run.status, RunStatus::Running) {
+            return;
+        }
+        let mut task = run.task.clone();
+        let mut rerun_names = Vec::new();
+        if failed_only {
+            let Some(framework) = run.framework else {
+                return;
+            };
+            rerun_names = run
+                .results
+                .iter()
+                .filter(|result| result.outcome == TestOutcome::Failed)
+                .map(|result| result.name.clone())
+                .collect::<Vec<_>>();
+            if rerun_names.is_empty() {
+                return;
+            }
+            let names = rerun_names.iter().map(String::as_str).collect::<Vec<_>>();
+            task.args = framework.rerun_args(&task.args, &names);
+        }
+        self.run(task, rerun_names, window, cx);
+    }
+
+    fn toggle_expanded(&mut self, name: String, cx: &mut Context<Self>) {
+        if !self.expanded.remove(&name) {
+            self.expanded.insert(name);
+        }
+        cx.notify();
+    }
+
+    fn render_header(&self, run: &TestRun, cx: &mut Context<Self>) -> impl IntoElement {
+        let count = |outcome| {
+            run.results
+                .iter()
+                .filter(|result| result.outcome == outcome)
+                .count()
+        };
+        let summary = match &run.status {
+            RunStatus::Running => "Running…".to_string(),
+            RunStatus::Failed(_) => "Failed to run".to_string(),
+            RunStatus::Finished { success } if run.results.is_empty() => {
+                if *success {
+                    "Finished".to_string()
+                } else {
+                    "Failed".to_string()
+                }
+            }
+            RunStatus::Finished { .. } => format!(
+                "{} passed, {} failed, {} skipped",
+                count(TestOutcome::Passed),
+                count(TestOutcome::Failed),
+                count(TestOutcome::Skipped)
+            ),
+        };
+        let is_running = matches!(run.status, RunStatus::Running);
+        let has_failures = count(TestOutcome::Failed) > 0;
+
+        h_flex()
+            .p_2()
+            .gap_2()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                v_flex()
+                    .min_w_0()
+                    .child(Label::new(run.task.label.clone()).truncate())
+                    .child(
+                        Label::new(summary)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new("rerun-failed-tests", IconName::Rerun)
+                            .icon_size(IconSize::Small)
+                            .disabled(is_running || !has_failures || run.framework.is_none())
+                            .tooltip(Tooltip::text("Rerun Failed Tests"))
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.rerun(true, window, cx)),
+                            ),
+                    )
+                    .child(
+                        IconButton::new("rerun-all-tests", IconName::RotateCw)
+                            .icon_size(IconSize::Small)
+                            .disabled(is_running)
+                            .tooltip(Tooltip::text("Rerun All Tests"))
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.rerun(false, window, cx)),
+                            ),
+                    )
+                    .child(
+                        IconButton::new("show-test-output", IconName::Terminal)
+                            .icon_size(IconSize::Small)
+                            .toggle_state(self.show_output)
+                            .tooltip(Tooltip::text("Show Output"))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.show_output = !this.show_output;
+                                cx.notify();
+                            })),
+                    ),
+            )
+    }
+
+    fn render_result(
+        &self,
+        ix: usize,
+        result: &TestResult,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let (icon, color) = match result.outcome {
+            TestOutcome::Passed => (IconName::Check, Color::Success),
+            TestOutcome::Failed => (IconName::XCircle, Color::Error),
+            TestOutcome::Skipped => (IconName::Dash, Color::Muted),
+        };
+        let has_details = result.message.is_some();
+        let is_expanded = has_details && self.expanded.contains(&result.name);
+        let name = result.name.clone();
+
+        v_flex()
+            .child(
+                ListItem::new(("test-result", ix))
+                    .spacing(ListItemSpacing::Sparse)
+                    .start_slot(
+                        h_flex()
+                            .gap_1()
+                            .when(has_details, |this| {
+                                this.child(Disclosure::new(
+                                    ("test-result-disclosure", ix),
+                                    is_expanded,
+                                ))
+                            })
+                            .child(Icon::new(icon).size(IconSize::Small).color(color)),
+                    )
+                    .child(Label::new(result.name.clone()).truncate())
+                    .when(has_details, |item| {
+                        item.on_click(
+                            cx.listener(move |this, _, _, cx| {
+                                this.toggle_expanded(name.clone(), cx)
+                            }),
+                        )
+                    }),
+            )
+            .when(is_expanded, |this| {
+                this.child(self.render_failure(result, cx))
+            })
+    }
+
+    fn render_failure(&self, result: &TestResult, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors();
+        let status_colors = cx.theme().status();
+        v_flex()
+            .ml_8()
+            .mr_2()
+            .mb_2()
+            .p_2()
+            .gap_2()
+            .rounded_md()
+            .bg(colors.editor_background)
+            .font_buffer(cx)
+            .text_buffer(cx)
+            .when_some(result.diff.as_ref(), |this, diff| {
+                this.child(
+                    v_flex()
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Label::new("- Expected")
+                                        .size(LabelSize::Small)
+                                        .color(Color::Created),
+                                )
+                                .child(
+                                    Label::new("+ Actual")
+                                        .size(LabelSize::Small)
+                                        .color(Color::Deleted),
+                                ),
+                        )
+                        .children(diff.lines().into_iter().map(|line| {
+                            let (prefix, text, background) = match line {
+                                DiffLine::Unchanged(text) => (" ", text, None),
+                                DiffLine::Expected(text) => {
+                                    ("-", text, Some(status_colors.created_background))
+                                }
+                                DiffLine::Actual(text) => {
+                                    ("+", text, Some(status_colors.deleted_background))
+                                }
+                            };
+                            div()
+                                .when_some(background, |this, background| this.bg(background))
+                                .child(format!("{prefix} {text}"))
+                        })),
+                )
+            })
+            .children(
+                result
+                    .message
+                    .clone()
+                    .map(|message| div().text_color(colors.text_muted).child(message)),
+            )
+    }
+}
+
+/// Returns the runnable of a test in a run, which is in the file that the framework names, or
+/// else in the file that the run was started from.
+fn runnable_key(
+    project: &Project,
+    run: &TestRun,
+    framework: TestFramework,
+    name: &str,
+    cx: &App,
+) -> Option<RunnableKey> {
+    let path = framework
+        .test_file(name)
+        .and_then(|file| {
+            let abs_path = run.cwd.as_ref()?.join(file);
+            project.project_path_for_absolute_path(&abs_path, cx)
+        })
+        .or_else(|| run.source.clone())?;
+    Some(RunnableKey {
+        path,
+        name: framework.short_name(name).to_string().into(),
+    })
+}
+
+/// Runs the command of a task through the shell, as a terminal would, and returns whether it
+/// succeeded and its combined output.
+async fn run_command(
+    task: SpawnInTerminal,
+    cwd: Option<PathBuf>,
+) -> anyhow::Result<(bool, String)> {
+    let script = std::iter::once(task.command.as_str())
+        .chain(task.args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ");
+    #[cfg(not(target_os = "windows"))]
+    let mut command = {
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+        let mut command = util::command::new_smol_command(shell);
+        command.arg("-c").arg(&script);
+        command
+    };
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = util::command::new_smol_command("cmd");
+        command.arg("/C").arg(&script);
+        command
+    };
+    if let Some(cwd) = cwd {
+        command.current_dir(cwd);
+    }
+    let output = command
+        .envs(task.env)
+        .output()
+        .await
+        .with_context(|| format!("running `{script}`"))?;
+
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.is_empty() {
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(&stderr);
+    }
+    Ok((output.status.success(), text))
+}
+
+impl Render for TestPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = match self.run.as_ref() {
+            None => v_flex().p_2().child(
+                Label::new("Run a test with test runner: run nearest test.").color(Color::Muted),
+            ),
+            Some(run) => {
+                let results = run
+                    .results
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, result)| self.render_result(ix, result, cx).into_any_element())
+                    .collect::<Vec<_>>();
+                v_flex()
+                    .size_full()
+                    .child(self.render_header(run, cx))
+                    .child(
+                        v_flex()
+                            .id("test-results")
+                            .flex_1()
+                            .overflow_y_scroll()
+                            .p_1()
+                            .when_some(
+                                match &run.status {
+                                    RunStatus::Failed(error) => Some(error.clone()),
+                                    _ => None,
+                                },
+                                |this, error| {
+                                    this.child(
+                                        div().p_2().child(Label::new(error).color(Color::Error)),
+                                    )
+                                },
+                            )
+                            .children(results)
+                            .when(self.show_output && !run.output.is_empty(), |this| {
+                                this.child(
+                                    div()
+                                        .m_1()
+                                        .p_2()
+                                        .rounded_md()
+                                        .bg(cx.theme().colors().editor_background)
+                                        .font_buffer(cx)
+                                        .text_buffer(cx)
+                                        .child(run.output.clone()),
+                                )
+                            }),
+                    )
+            }
+        };
+
+        v_flex()
+            .key_context("TestPanel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().panel_background)
+            .child(content)
+    }
+}
+
+impl Focusable for TestPanel {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for TestPanel {}
+
+impl Panel for TestPanel {
+    fn persistent_name() -> &'static str {
+        "TestPanel"
+    }
+
+    fn position(&self, _window: &Window, _cx: &App) -> DockPosition {
+        self.position
+    }
+
+    fn position_is_valid(&self, _position: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        self.position = position;
+        cx.notify();
+    }
+
+    fn size(&self, _window: &Window, _cx: &App) -> Pixels {
+        self.size.unwrap_or(DEFAULT_SIZE)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.size = size;
+        cx.notify();
+    }
+
+    fn icon(&self, _window: &Window, _cx: &App) -> Option<IconName> {
+        Some(IconName::Play)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Tests")
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        15
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GlobalTestStatuses;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use task::{TaskContext, TaskTemplate};
+    use util::path;
+
+    #[gpui::test]
+    async fn test_statuses_of_same_named_tests_in_different_files(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "tests": {
+                    "test_a.py": "def test_same():\n    pass\n",
+                    "test_b.py": "def test_same():\n    assert False\n",
+                }
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let test_statuses = cx.new(|_| TestStatuses::default());
+        cx.update(|cx| cx.set_global(GlobalTestStatuses(test_statuses.clone())));
+        let panel = cx.new(|cx| TestPanel::new(project.clone(), cx));
+
+        let task = TaskTemplate {
+            label: "pytest".into(),
+            command: "pytest".into(),
+            ..TaskTemplate::default()
+        }
+        .resolve_task("test", &TaskContext::default())
+        .and_then(|task| task.resolved)
+        .unwrap();
+        panel.update(cx, |panel, cx| {
+            panel.run = Some(TestRun {
+                task,
+                framework: Some(TestFramework::Pytest),
+                cwd: Some(PathBuf::from(path!("/project"))),
+                source: None,
+                status: RunStatus::Running,
+                results: Vec::new(),
+                output: String::new(),
+            });
+            panel.finish_run(
+                Ok((
+                    false,
+                    "tests/test_a.py::test_same PASSED\ntests/test_b.py::test_same FAILED\n".into(),
+                )),
+                cx,
+            );
+        });
+
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        let key = |path: &str| RunnableKey {
+            path: ProjectPath {
+                worktree_id,
+                path: Path::new(path).into(),
+            },
+            name: "test_same".into(),
+        };
+        let statuses = test_statuses.read_with(cx, |test_statuses, _| {
+            test_statuses.statuses(project.entity_id())
+        });
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(
+            statuses.get(&key("tests/test_a.py")),
+            Some(&RunnableStatus::Passed)
+        );
+        assert_eq!(
+            statuses.get(&key("tests/test_b.py")),
+            Some(&RunnableStatus::Failed)
+        );
+    }
+}
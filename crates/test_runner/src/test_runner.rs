@@ -0,0 +1,108 @@
+//! Runs the tests that languages' runnables find, shows their outcomes and failures in the test
+//! panel, and colors the run indicators of tests in editors by how their last run went.
+
+mod test_output;
+mod test_panel;
+
+use std::sync::Arc;
+
+use collections::HashMap;
+use editor::{Addon, Editor, EditorMode, RunnableKey, RunnableStatus};
+use gpui::{
+    actions, App, AppContext as _, Context, Entity, EntityId, Global, Subscription, Window,
+};
+use task::TaskTemplate;
+
+pub use test_output::{DiffLine, FailureDiff, TestFramework, TestOutcome, TestResult};
+pub use test_panel::TestPanel;
+
+actions!(
+    test_runner,
+    [
+        ToggleFocus,
+        RunNearestTest,
+        DebugNearestTest,
+        RerunFailedTests,
+        RerunAllTests
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    let statuses = cx.new(|_| TestStatuses::default());
+    cx.set_global(GlobalTestStatuses(statuses));
+    cx.observe_new(register_editor).detach();
+    test_panel::init(cx);
+}
+
+/// Whether a task runs tests, which is the case for the tasks of runnables tagged as tests,
+/// like `rust-test` or `python-pytest-method`.
+pub fn is_test_task(template: &TaskTemplate) -> bool {
+    template.tags.iter().any(|tag| tag.contains("test"))
+}
+
+/// The outcomes of the last runs of tests in each project, by their runnables.
+#[derive(Default)]
+struct TestStatuses {
+    statuses_by_project: HashMap<EntityId, Arc<HashMap<RunnableKey, RunnableStatus>>>,
+}
+
+struct GlobalTestStatuses(Entity<TestStatuses>);
+
+impl Global for GlobalTestStatuses {}
+
+impl TestStatuses {
+    fn global(cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalTestStatuses>()
+            .map(|global| global.0.clone())
+    }
+
+    fn statuses(&self, project_id: EntityId) -> Arc<HashMap<RunnableKey, RunnableStatus>> {
+        self.statuses_by_project
+            .get(&project_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn set_statuses(
+        &mut self,
+        project_id: EntityId,
+        statuses: impl IntoIterator<Item = (RunnableKey, RunnableStatus)>,
+        cx: &mut Context<Self>,
+    ) {
+        let project_statuses = self.statuses_by_project.entry(project_id).or_default();
+        let mut new_statuses = project_statuses.as_ref().clone();
+        new_statuses.extend(statuses);
+        *project_statuses = Arc::new(new_statuses);
+        cx.notify();
+    }
+}
+
+fn register_editor(editor: &mut Editor, _: Option<&mut Window>, cx: &mut Context<Editor>) {
+    if !matches!(editor.mode(), EditorMode::Full) {
+        return;
+    }
+    let Some(statuses) = TestStatuses::global(cx) else {
+        return;
+    };
+    let Some(project_id) = editor.project.as_ref().map(|project| project.entity_id()) else {
+        return;
+    };
+    editor.set_runnable_statuses(statuses.read(cx).statuses(project_id), cx);
+    let subscription = cx.observe(&statuses, move |editor, statuses, cx| {
+        editor.set_runnable_statuses(statuses.read(cx).statuses(project_id), cx)
+    });
+    editor.register_addon(TestStatusesAddon {
+        _subscription: subscription,
+    });
+}
+
+/// Keeps an editor's run indicators up to date with the outcomes of tests.
+struct TestStatusesAddon {
+    _subscription: Subscription,
+}
+
+impl Addon for TestStatusesAddon {
+    fn to_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,246 @@
+use std::{borrow::Cow, fmt, io};
+
+use anyhow::{anyhow, Result};
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16_BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// The character encoding of a file's contents on disk. Buffers are always UTF-8 in memory and
+/// are converted when they are loaded and saved.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// ISO-8859-1, decoded as its Windows-1252 superset like browsers do.
+    Latin1,
+    ShiftJis,
+    Gbk,
+}
+
+impl Encoding {
+    pub const ALL: [Self; 6] = [
+        Self::Utf8,
+        Self::Utf16Le,
+        Self::Utf16Be,
+        Self::Latin1,
+        Self::ShiftJis,
+        Self::Gbk,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16Le => "UTF-16 LE",
+            Self::Utf16Be => "UTF-16 BE",
+            Self::Latin1 => "Latin-1",
+            Self::ShiftJis => "Shift-JIS",
+            Self::Gbk => "GBK",
+        }
+    }
+
+    /// The byte order mark that contents in this encoding can start with.
+    pub fn bom(&self) -> Option<&'static [u8]> {
+        match self {
+            Self::Utf8 => Some(UTF8_BOM),
+            Self::Utf16Le => Some(UTF16_LE_BOM),
+            Self::Utf16Be => Some(UTF16_BE_BOM),
+            Self::Latin1 | Self::ShiftJis | Self::Gbk => None,
+        }
+    }
+
+    /// Whether the given contents start with this encoding's byte order mark.
+    pub fn has_bom(&self, bytes: &[u8]) -> bool {
+        self.bom().is_some_and(|bom| bytes.starts_with(bom))
+    }
+
+    /// Detects the encoding of the given contents and decodes them, without their byte order
+    /// mark. Contents that are neither UTF-8 nor marked as UTF-16 are guessed to be in one of
+    /// the legacy encodings, falling back to Latin-1. Contents that look binary fail to decode
+    /// with [`io::ErrorKind::InvalidData`].
+    pub fn detect_and_decode(bytes: Vec<u8>) -> Result<(String, Self)> {
+        let bytes = match String::from_utf8(bytes) {
+            Ok(mut text) => {
+                if text.starts_with('\u{feff}') {
+                    text.drain(..'\u{feff}'.len_utf8());
+                }
+                return Ok((text, Self::Utf8));
+            }
+            Err(error) => error.into_bytes(),
+        };
+
+        let encoding = if bytes.starts_with(UTF16_LE_BOM) {
+            Self::Utf16Le
+        } else if bytes.starts_with(UTF16_BE_BOM) {
+            Self::Utf16Be
+        } else if bytes.contains(&0) {
+            return Err(invalid_data("contents look binary"));
+        } else {
+            let mut detector = chardetng::EncodingDetector::new();
+            detector.feed(&bytes, true);
+            let guess = detector.guess(None, false);
+            if guess == encoding_rs::SHIFT_JIS {
+                Self::ShiftJis
+            } else if guess == encoding_rs::GBK || guess == encoding_rs::GB18030 {
+                Self::Gbk
+            } else {
+                Self::Latin1
+            }
+        };
+
+        match encoding.decode(&bytes) {
+            Ok(text) => Ok((text, encoding)),
+            Err(_) if encoding != Self::Latin1 => Ok((Self::Latin1.decode(&bytes)?, Self::Latin1)),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Decodes contents in this encoding without their byte order mark, failing with
+    /// [`io::ErrorKind::InvalidData`] if they aren't valid in it rather than replacing the
+    /// invalid parts.
+    pub fn decode(&self, bytes: &[u8]) -> Result<String> {
+        let encoding = match self {
+            Self::Utf8 => encoding_rs::UTF_8,
+            Self::Utf16Le => encoding_rs::UTF_16LE,
+            Self::Utf16Be => encoding_rs::UTF_16BE,
+            Self::Latin1 => encoding_rs::WINDOWS_1252,
+            Self::ShiftJis => encoding_rs::SHIFT_JIS,
+            Self::Gbk => encoding_rs::GBK,
+        };
+        let bytes = match self.bom() {
+            Some(bom) => bytes.strip_prefix(bom).unwrap_or(bytes),
+            None => bytes,
+        };
+        encoding
+            .decode_without_bom_handling_and_without_replacement(bytes)
+            .map(Cow::into_owned)
+            .ok_or_else(|| invalid_data(format!("contents aren't valid {self}")))
+    }
+
+    /// Encodes text in this encoding, failing if it contains characters that can't be
+    /// represented in it. The byte order mark is only written for encodings that have one.
+    pub fn encode<'a>(&self, text: &'a str, with_bom: bool) -> Result<Cow<'a, [u8]>> {
+        let bom = self.bom().filter(|_| with_bom).unwrap_or_default();
+        let encoding = match self {
+            Self::Utf8 if bom.is_empty() => return Ok(Cow::Borrowed(text.as_bytes())),
+            Self::Utf8 => {
+                return Ok(Cow::Owned([bom, text.as_bytes()].concat()));
+            }
+            Self::Utf16Le => {
+                let mut bytes = bom.to_vec();
+                bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+                return Ok(Cow::Owned(bytes));
+            }
+            Self::Utf16Be => {
+                let mut bytes = bom.to_vec();
+                bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+                return Ok(Cow::Owned(bytes));
+            }
+            Self::Latin1 => encoding_rs::WINDOWS_1252,
+            Self::ShiftJis => encoding_rs::SHIFT_JIS,
+            Self::Gbk => encoding_rs::GBK,
+        };
+        let (bytes, _, had_unmappable_characters) = encoding.encode(text);
+        if had_unmappable_characters {
+            return Err(anyhow!(
+                "text contains characters that can't be saved as {self}"
+            ));
+        }
+        Ok(bytes)
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+fn invalid_data(message: impl Into<String>) -> anyhow::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_and_decode() {
+        let (text, encoding) = Encoding::detect_and_decode("héllo".into()).unwrap();
+        assert_eq!((text.as_str(), encoding), ("héllo", Encoding::Utf8));
+
+        let (text, encoding) = Encoding::detect_and_decode("\u{feff}héllo".into()).unwrap();
+        assert_eq!((text.as_str(), encoding), ("héllo", Encoding::Utf8));
+
+        let utf16 = Encoding::Utf16Le
+            .encode("héllo\n", true)
+            .unwrap()
+            .into_owned();
+        let (text, encoding) = Encoding::detect_and_decode(utf16).unwrap();
+        assert_eq!((text.as_str(), encoding), ("héllo\n", Encoding::Utf16Le));
+
+        let utf16 = Encoding::Utf16Be
+            .encode("héllo\n", true)
+            .unwrap()
+            .into_owned();
+        let (text, encoding) = Encoding::detect_and_decode(utf16).unwrap();
+        assert_eq!((text.as_str(), encoding), ("héllo\n", Encoding::Utf16Be));
+
+        let latin1 = b"caf\xe9 cr\xe8me br\xfbl\xe9e\n".to_vec();
+        let (text, encoding) = Encoding::detect_and_decode(latin1).unwrap();
+        assert_eq!(
+            (text.as_str(), encoding),
+            ("café crème brûlée\n", Encoding::Latin1)
+        );
+
+        let shift_jis = Encoding::ShiftJis
+            .encode(
+                "これは日本語のテキストです。ファイルの文字コードを判定します。\n",
+                false,
+            )
+            .unwrap()
+            .into_owned();
+        let (_, encoding) = Encoding::detect_and_decode(shift_jis).unwrap();
+        assert_eq!(encoding, Encoding::ShiftJis);
+
+        let gbk = Encoding::Gbk
+            .encode("这是一段中文文本，用来检测文件的字符编码。\n", false)
+            .unwrap()
+            .into_owned();
+        let (_, encoding) = Encoding::detect_and_decode(gbk).unwrap();
+        assert_eq!(encoding, Encoding::Gbk);
+
+        let error = Encoding::detect_and_decode(vec![0x7f, 0x45, 0x4c, 0x46, 0x02, 0x00, 0xff])
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<io::Error>().map(io::Error::kind),
+            Some(io::ErrorKind::InvalidData)
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        for encoding in Encoding::ALL {
+            for with_bom in [false, true] {
+                let bytes = encoding.encode("plain text\n", with_bom).unwrap();
+                assert_eq!(
+                    encoding.has_bom(&bytes),
+                    with_bom && encoding.bom().is_some()
+                );
+                assert_eq!(encoding.decode(&bytes).unwrap(), "plain text\n");
+            }
+        }
+        assert_eq!(
+            Encoding::Utf8.encode("hi", true).unwrap().as_ref(),
+            b"\xef\xbb\xbfhi"
+        );
+        assert_eq!(
+            Encoding::Latin1.encode("café", true).unwrap().as_ref(),
+            b"caf\xe9"
+        );
+        assert!(Encoding::Latin1.encode("日本語", false).is_err());
+        assert!(Encoding::ShiftJis.decode(b"\x82").is_err());
+    }
+}
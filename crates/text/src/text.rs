@@ -1,4 +1,5 @@
 mod anchor;
+mod encoding;
 pub mod locator;
 #[cfg(any(test, feature = "test-support"))]
 pub mod network;
@@ -15,6 +16,7 @@ use anyhow::{anyhow, Context as _, Result};
 pub use clock::ReplicaId;
 use clock::LOCAL_BRANCH_REPLICA_ID;
 use collections::{HashMap, HashSet};
+pub use encoding::Encoding;
 use locator::Locator;
 use operation_queue::OperationQueue;
 pub use patch::Patch;
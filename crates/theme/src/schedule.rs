@@ -0,0 +1,354 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use gpui::App;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use std::time::Duration;
+
+use crate::{Appearance, ThemeMode, ThemeSettings};
+
+/// The format used for times of day in a [`ThemeSchedule`].
+const TIME_OF_DAY_FORMAT: &str = "%H:%M";
+
+/// A schedule that decides whether the light or the dark theme is used when the
+/// theme's mode is [`ThemeMode::Schedule`].
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeSchedule {
+    /// Switch between the light and the dark theme at fixed times of day.
+    FixedHours {
+        /// The local time of day at which to switch to the light theme, as `"HH:MM"`.
+        light: String,
+        /// The local time of day at which to switch to the dark theme, as `"HH:MM"`.
+        dark: String,
+    },
+    /// Use the light theme between sunrise and sunset at the given location, and the dark
+    /// theme otherwise.
+    SunriseSunset {
+        /// The latitude of the location, in degrees north of the equator.
+        latitude: f64,
+        /// The longitude of the location, in degrees east of Greenwich.
+        longitude: f64,
+    },
+}
+
+impl ThemeSchedule {
+    /// Returns the [`Appearance`] this schedule prescribes at the given time.
+    ///
+    /// Returns `None` if the schedule contains a time of day that can't be parsed.
+    pub fn appearance_at<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<Appearance> {
+        match self {
+            Self::FixedHours { light, dark } => {
+                let light = NaiveTime::parse_from_str(light, TIME_OF_DAY_FORMAT).ok()?;
+                let dark = NaiveTime::parse_from_str(dark, TIME_OF_DAY_FORMAT).ok()?;
+                let time = now.time();
+                let is_light = if light <= dark {
+                    light <= time && time < dark
+                } else {
+                    // The light period wraps around midnight.
+                    time >= light || time < dark
+                };
+                Some(if is_light {
+                    Appearance::Light
+                } else {
+                    Appearance::Dark
+                })
+            }
+            Self::SunriseSunset {
+                latitude,
+                longitude,
+            } => {
+                let appearance = match sun_times(*latitude, *longitude, now.date_naive()) {
+                    SunTimes::Daily { sunrise, sunset } => {
+                        let now = now.with_timezone(&Utc);
+                        if sunrise <= now && now < sunset {
+                            Appearance::Light
+                        } else {
+                            Appearance::Dark
+                        }
+                    }
+                    SunTimes::PolarDay => Appearance::Light,
+                    SunTimes::PolarNight => Appearance::Dark,
+                };
+                Some(appearance)
+            }
+        }
+    }
+
+    /// Returns an error if the schedule can't be evaluated.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Self::FixedHours { light, dark } => {
+                for time in [light, dark] {
+                    if NaiveTime::parse_from_str(time, TIME_OF_DAY_FORMAT).is_err() {
+                        return Err(anyhow!(
+                            "invalid time of day {time:?} in theme schedule, expected \"HH:MM\""
+                        ));
+                    }
+                }
+                Ok(())
+            }
+            Self::SunriseSunset {
+                latitude,
+                longitude,
+            } => {
+                if !(-90.0..=90.0).contains(latitude) {
+                    return Err(anyhow!("invalid latitude {latitude} in theme schedule"));
+                }
+                if !(-180.0..=180.0).contains(longitude) {
+                    return Err(anyhow!("invalid longitude {longitude} in theme schedule"));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Returns the appearance that a theme selection in the given [`ThemeMode`] follows.
+///
+/// A selection in [`ThemeMode::Schedule`] follows the given schedule, falling back to the
+/// system's appearance when there is no valid schedule. Any other selection follows the
+/// system's appearance.
+pub fn followed_appearance<Tz: TimeZone>(
+    mode: Option<ThemeMode>,
+    schedule: Option<&ThemeSchedule>,
+    system_appearance: Appearance,
+    now: &DateTime<Tz>,
+) -> Appearance {
+    match (mode, schedule) {
+        (Some(ThemeMode::Schedule), Some(schedule)) => {
+            schedule.appearance_at(now).unwrap_or(system_appearance)
+        }
+        _ => system_appearance,
+    }
+}
+
+/// How often the `theme_schedule` is checked for a change of appearance.
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Periodically checks the `theme_schedule`, cross-fading to the other theme whenever the
+/// scheduled appearance changes.
+pub(crate) fn observe_theme_schedule(cx: &mut App) {
+    cx.spawn(async move |cx| {
+        let mut scheduled_appearance = None;
+        loop {
+            let Ok(appearance) = cx.update(|cx| {
+                ThemeSettings::get_global(cx)
+                    .theme_schedule
+                    .as_ref()
+                    .and_then(|schedule| schedule.appearance_at(&Local::now()))
+            }) else {
+                break;
+            };
+
+            // Changes to the schedule itself are applied when the settings are loaded.
+            if scheduled_appearance.is_some() && appearance != scheduled_appearance {
+                cx.update(ThemeSettings::reload_current_theme_with_crossfade)
+                    .ok();
+            }
+            scheduled_appearance = appearance;
+
+            cx.background_executor()
+                .timer(SCHEDULE_CHECK_INTERVAL)
+                .await;
+        }
+    })
+    .detach();
+}
+
+#[derive(Debug, PartialEq)]
+enum SunTimes {
+    Daily {
+        sunrise: DateTime<Utc>,
+        sunset: DateTime<Utc>,
+    },
+    /// The sun doesn't set on this day.
+    PolarDay,
+    /// The sun doesn't rise on this day.
+    PolarNight,
+}
+
+/// Computes the times of sunrise and sunset on the given date, using the sunrise equation.
+///
+/// See https://en.wikipedia.org/wiki/Sunrise_equation.
+fn sun_times(latitude: f64, longitude: f64, date: NaiveDate) -> SunTimes {
+    const J2000: f64 = 2451545.0;
+    const UNIX_EPOCH_JULIAN_DAY: f64 = 2440587.5;
+    const EARTH_AXIAL_TILT: f64 = 23.4397;
+    // Accounts for atmospheric refraction and the radius of the sun's disc.
+    const SUN_ALTITUDE_AT_HORIZON: f64 = -0.833;
+
+    let noon = date.and_hms_opt(12, 0, 0).unwrap().and_utc();
+    let julian_day = noon.timestamp() as f64 / 86400.0 + UNIX_EPOCH_JULIAN_DAY;
+    let day = (julian_day - J2000 + 0.0008).round();
+
+    let mean_solar_time = day - longitude / 360.0;
+    let mean_anomaly = (357.5291 + 0.98560028 * mean_solar_time).rem_euclid(360.0);
+    let mean_anomaly_rad = mean_anomaly.to_radians();
+    let equation_of_center = 1.9148 * mean_anomaly_rad.sin()
+        + 0.02 * (2.0 * mean_anomaly_rad).sin()
+        + 0.0003 * (3.0 * mean_anomaly_rad).sin();
+    let ecliptic_longitude =
+        (mean_anomaly + equation_of_center + 180.0 + 102.9372).rem_euclid(360.0);
+    let ecliptic_longitude_rad = ecliptic_longitude.to_radians();
+    let solar_transit = J2000 + mean_solar_time + 0.0053 * mean_anomaly_rad.sin()
+        - 0.0069 * (2.0 * ecliptic_longitude_rad).sin();
+
+    let declination_sin = ecliptic_longitude_rad.sin() * EARTH_AXIAL_TILT.to_radians().sin();
+    let declination_cos = (1.0 - declination_sin * declination_sin).sqrt();
+    let latitude_rad = latitude.to_radians();
+    let hour_angle_cos = (SUN_ALTITUDE_AT_HORIZON.to_radians().sin()
+        - latitude_rad.sin() * declination_sin)
+        / (latitude_rad.cos() * declination_cos);
+
+    if hour_angle_cos < -1.0 {
+        return SunTimes::PolarDay;
+    }
+    if hour_angle_cos > 1.0 {
+        return SunTimes::PolarNight;
+    }
+
+    let hour_angle = hour_angle_cos.acos().to_degrees();
+    let to_utc = |julian_day: f64| {
+        let seconds = (julian_day - UNIX_EPOCH_JULIAN_DAY) * 86400.0;
+        Utc.timestamp_millis_opt((seconds * 1000.0) as i64).unwrap()
+    };
+
+    SunTimes::Daily {
+        sunrise: to_utc(solar_transit - hour_angle / 360.0),
+        sunset: to_utc(solar_transit + hour_angle / 360.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::FixedOffset;
+
+    use super::*;
+
+    fn at(date: &str, time: &str, offset_hours: i32) -> DateTime<FixedOffset> {
+        let offset = FixedOffset::east_opt(offset_hours * 3600).unwrap();
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+        let time = NaiveTime::parse_from_str(time, "%H:%M").unwrap();
+        offset.from_local_datetime(&date.and_time(time)).unwrap()
+    }
+
+    #[test]
+    fn test_fixed_hours_schedule() {
+        let schedule = ThemeSchedule::FixedHours {
+            light: "07:00".into(),
+            dark: "19:30".into(),
+        };
+        assert_eq!(
+            schedule.appearance_at(&at("2024-03-01", "06:59", 0)),
+            Some(Appearance::Dark)
+        );
+        assert_eq!(
+            schedule.appearance_at(&at("2024-03-01", "07:00", 0)),
+            Some(Appearance::Light)
+        );
+        assert_eq!(
+            schedule.appearance_at(&at("2024-03-01", "19:30", 0)),
+            Some(Appearance::Dark)
+        );
+
+        // The light period can wrap around midnight.
+        let schedule = ThemeSchedule::FixedHours {
+            light: "22:00".into(),
+            dark: "04:00".into(),
+        };
+        assert_eq!(
+            schedule.appearance_at(&at("2024-03-01", "23:00", 0)),
+            Some(Appearance::Light)
+        );
+        assert_eq!(
+            schedule.appearance_at(&at("2024-03-01", "03:00", 0)),
+            Some(Appearance::Light)
+        );
+        assert_eq!(
+            schedule.appearance_at(&at("2024-03-01", "12:00", 0)),
+            Some(Appearance::Dark)
+        );
+
+        let schedule = ThemeSchedule::FixedHours {
+            light: "7am".into(),
+            dark: "19:30".into(),
+        };
+        assert_eq!(schedule.appearance_at(&at("2024-03-01", "12:00", 0)), None);
+        assert!(schedule.validate().is_err());
+    }
+
+    #[test]
+    fn test_sunrise_sunset_schedule() {
+        // London on the summer solstice: sunrise is around 03:43 UTC and sunset around
+        // 20:21 UTC.
+        let SunTimes::Daily { sunrise, sunset } =
+            sun_times(51.5, -0.13, NaiveDate::from_ymd_opt(2024, 6, 21).unwrap())
+        else {
+            panic!("expected the sun to rise and set in London");
+        };
+        let expected_sunrise = at("2024-06-21", "03:43", 0).with_timezone(&Utc);
+        let expected_sunset = at("2024-06-21", "20:21", 0).with_timezone(&Utc);
+        assert!((sunrise - expected_sunrise).num_minutes().abs() <= 5);
+        assert!((sunset - expected_sunset).num_minutes().abs() <= 5);
+
+        let schedule = ThemeSchedule::SunriseSunset {
+            latitude: 51.5,
+            longitude: -0.13,
+        };
+        assert_eq!(
+            schedule.appearance_at(&at("2024-06-21", "12:00", 1)),
+            Some(Appearance::Light)
+        );
+        assert_eq!(
+            schedule.appearance_at(&at("2024-06-21", "23:00", 1)),
+            Some(Appearance::Dark)
+        );
+
+        // Above the arctic circle the sun neither rises in winter nor sets in summer.
+        let tromso = ThemeSchedule::SunriseSunset {
+            latitude: 69.65,
+            longitude: 18.96,
+        };
+        assert_eq!(
+            tromso.appearance_at(&at("2024-06-21", "01:00", 2)),
+            Some(Appearance::Light)
+        );
+        assert_eq!(
+            tromso.appearance_at(&at("2024-12-21", "12:00", 1)),
+            Some(Appearance::Dark)
+        );
+    }
+
+    #[test]
+    fn test_followed_appearance() {
+        let schedule = ThemeSchedule::FixedHours {
+            light: "07:00".into(),
+            dark: "19:00".into(),
+        };
+        let night = at("2024-03-01", "23:00", 0);
+        assert_eq!(
+            followed_appearance(
+                Some(ThemeMode::Schedule),
+                Some(&schedule),
+                Appearance::Light,
+                &night
+            ),
+            Appearance::Dark
+        );
+        assert_eq!(
+            followed_appearance(
+                Some(ThemeMode::System),
+                Some(&schedule),
+                Appearance::Light,
+                &night
+            ),
+            Appearance::Light
+        );
+        assert_eq!(
+            followed_appearance(Some(ThemeMode::Schedule), None, Appearance::Light, &night),
+            Appearance::Light
+        );
+    }
+}
@@ -110,30 +110,37 @@ impl ThemeStyleContent {
 
     /// Returns the syntax style overrides in the [`ThemeContent`].
     pub fn syntax_overrides(&self) -> Vec<(String, HighlightStyle)> {
-        self.syntax
-            .iter()
-            .map(|(key, style)| {
-                (
-                    key.clone(),
-                    HighlightStyle {
-                        color: style
-                            .color
-                            .as_ref()
-                            .and_then(|color| try_parse_color(color).ok()),
-                        background_color: style
-                            .background_color
-                            .as_ref()
-                            .and_then(|color| try_parse_color(color).ok()),
-                        font_style: style.font_style.map(FontStyle::from),
-                        font_weight: style.font_weight.map(FontWeight::from),
-                        ..Default::default()
-                    },
-                )
-            })
-            .collect()
+        syntax_styles(&self.syntax)
     }
 }
 
+/// Returns the [`HighlightStyle`]s for the given syntax styles, keyed by scope.
+pub(crate) fn syntax_styles(
+    syntax: &IndexMap<String, HighlightStyleContent>,
+) -> Vec<(String, HighlightStyle)> {
+    syntax
+        .iter()
+        .map(|(key, style)| {
+            (
+                key.clone(),
+                HighlightStyle {
+                    color: style
+                        .color
+                        .as_ref()
+                        .and_then(|color| try_parse_color(color).ok()),
+                    background_color: style
+                        .background_color
+                        .as_ref()
+                        .and_then(|color| try_parse_color(color).ok()),
+                    font_style: style.font_style.map(FontStyle::from),
+                    font_weight: style.font_weight.map(FontWeight::from),
+                    ..Default::default()
+                },
+            )
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(default)]
 pub struct ThemeColorsContent {
@@ -1,14 +1,18 @@
 use crate::fallback_themes::zed_default_dark;
 use crate::{
-    Appearance, IconTheme, IconThemeNotFoundError, SyntaxTheme, Theme, ThemeNotFoundError,
-    ThemeRegistry, ThemeStyleContent, DEFAULT_ICON_THEME_NAME,
+    followed_appearance, syntax_styles, Appearance, HighlightStyleContent, IconTheme,
+    IconThemeNotFoundError, SyntaxTheme, Theme, ThemeNotFoundError, ThemeRegistry, ThemeSchedule,
+    ThemeStyleContent, DEFAULT_ICON_THEME_NAME,
 };
 use anyhow::Result;
+use chrono::Local;
+use collections::HashMap;
 use derive_more::{Deref, DerefMut};
 use gpui::{
     px, App, Context, Font, FontFallbacks, FontFeatures, FontStyle, FontWeight, Global, Pixels,
-    Subscription, Window,
+    Subscription, Task, Window,
 };
+use indexmap::IndexMap;
 use refineable::Refineable;
 use schemars::{
     gen::SchemaGenerator,
@@ -18,10 +22,12 @@ use schemars::{
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use settings::{add_references_to_properties, Settings, SettingsJsonSchemaParams, SettingsSources};
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use util::ResultExt as _;
 
 const MIN_FONT_SIZE: Pixels = px(6.0);
+const MIN_UI_SCALE: f32 = 0.5;
+const MAX_UI_SCALE: f32 = 3.0;
 const MIN_LINE_HEIGHT: f32 = 1.0;
 
 #[derive(
@@ -67,6 +73,30 @@ impl UiDensity {
     }
 }
 
+/// Whether an accessibility mode, like high contrast or reduced motion, is enabled.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessibilityModeSetting {
+    /// Follow the preference set in the system's accessibility settings.
+    #[default]
+    System,
+    /// Always enable the mode.
+    On,
+    /// Never enable the mode.
+    Off,
+}
+
+impl AccessibilityModeSetting {
+    /// Returns whether the mode is enabled, given the system's preference.
+    pub fn is_enabled(self, system_preference: bool) -> bool {
+        match self {
+            AccessibilityModeSetting::System => system_preference,
+            AccessibilityModeSetting::On => true,
+            AccessibilityModeSetting::Off => false,
+        }
+    }
+}
+
 impl From<String> for UiDensity {
     fn from(s: String) -> Self {
         match s.as_str() {
@@ -96,6 +126,12 @@ pub struct ThemeSettings {
     ///
     /// Changing this will impact the size of all UI elements.
     ui_font_size: Pixels,
+    /// How much to scale the UI by, independently of the buffer font size.
+    ui_scale: f32,
+    /// How much to scale the UI by on particular displays, by their UUIDs.
+    ///
+    /// Takes precedence over [`Self::ui_scale`].
+    display_ui_scales: HashMap<String, f32>,
     /// The font used for UI elements.
     pub ui_font: Font,
     /// The font size used for buffers, and the terminal.
@@ -114,12 +150,14 @@ pub struct ThemeSettings {
     pub buffer_line_height: BufferLineHeight,
     /// The current theme selection.
     pub theme_selection: Option<ThemeSelection>,
+    /// The schedule followed by theme selections in [`ThemeMode::Schedule`].
+    pub theme_schedule: Option<ThemeSchedule>,
     /// The active theme.
     pub active_theme: Arc<Theme>,
     /// Manual overrides for the active theme.
     ///
     /// Note: This setting is still experimental. See [this tracking issue](https://github.com/zed-industries/zed/issues/18078)
-    pub theme_overrides: Option<ThemeStyleContent>,
+    pub theme_overrides: Option<ThemeOverridesContent>,
     /// The current icon theme selection.
     pub icon_theme_selection: Option<IconThemeSelection>,
     /// The active icon theme.
@@ -129,6 +167,12 @@ pub struct ThemeSettings {
     pub ui_density: UiDensity,
     /// The amount of fading applied to unnecessary code.
     pub unnecessary_code_fade: f32,
+    /// Whether to increase the contrast of the active theme's text, icons and borders.
+    pub high_contrast: AccessibilityModeSetting,
+    /// Whether to skip animations, cursor blinking and theme cross-fades.
+    pub reduce_motion: AccessibilityModeSetting,
+    /// Named sets of font features, which language and terminal settings can refer to.
+    pub font_feature_profiles: HashMap<String, FontFeatures>,
 }
 
 impl ThemeSettings {
@@ -143,16 +187,42 @@ impl ThemeSettings {
         }
     }
 
+    /// Returns the font features that the given setting stands for, or `None` when it names a
+    /// profile that doesn't exist.
+    pub fn resolve_font_features(&self, setting: &FontFeaturesSetting) -> Option<FontFeatures> {
+        match setting {
+            FontFeaturesSetting::Profile(name) => self.font_feature_profiles.get(name).cloned(),
+            FontFeaturesSetting::Features(features) => Some(features.clone()),
+        }
+    }
+
+    /// Returns the [`Appearance`] followed by a theme selection with the given [`ThemeMode`],
+    /// taking into account the `theme_schedule` and the current time.
+    pub fn followed_appearance(
+        &self,
+        mode: Option<ThemeMode>,
+        system_appearance: Appearance,
+    ) -> Appearance {
+        followed_appearance(
+            mode,
+            self.theme_schedule.as_ref(),
+            system_appearance,
+            &Local::now(),
+        )
+    }
+
     /// Reloads the current theme.
     ///
     /// Reads the [`ThemeSettings`] to know which theme should be loaded,
-    /// taking into account the current [`SystemAppearance`].
+    /// taking into account the current [`SystemAppearance`] and `theme_schedule`.
     pub fn reload_current_theme(cx: &mut App) {
         let mut theme_settings = ThemeSettings::get_global(cx).clone();
         let system_appearance = SystemAppearance::global(cx);
 
         if let Some(theme_selection) = theme_settings.theme_selection.clone() {
-            let mut theme_name = theme_selection.theme(*system_appearance);
+            let appearance =
+                theme_settings.followed_appearance(theme_selection.mode(), *system_appearance);
+            let mut theme_name = theme_selection.theme(appearance);
 
             // If the selected theme doesn't exist, fall back to a default theme
             // based on the system appearance.
@@ -162,7 +232,7 @@ impl ThemeSettings {
                     log::error!("{err}");
                 }
 
-                theme_name = Self::default_theme(*system_appearance);
+                theme_name = Self::default_theme(appearance);
             };
 
             if let Some(_theme) = theme_settings.switch_theme(theme_name, cx) {
@@ -174,13 +244,15 @@ impl ThemeSettings {
     /// Reloads the current icon theme.
     ///
     /// Reads the [`ThemeSettings`] to know which icon theme should be loaded,
-    /// taking into account the current [`SystemAppearance`].
+    /// taking into account the current [`SystemAppearance`] and `theme_schedule`.
     pub fn reload_current_icon_theme(cx: &mut App) {
         let mut theme_settings = ThemeSettings::get_global(cx).clone();
         let system_appearance = SystemAppearance::global(cx);
 
         if let Some(icon_theme_selection) = theme_settings.icon_theme_selection.clone() {
-            let mut icon_theme_name = icon_theme_selection.icon_theme(*system_appearance);
+            let appearance =
+                theme_settings.followed_appearance(icon_theme_selection.mode(), *system_appearance);
+            let mut icon_theme_name = icon_theme_selection.icon_theme(appearance);
 
             // If the selected icon theme doesn't exist, fall back to the default theme.
             let theme_registry = ThemeRegistry::global(cx);
@@ -199,8 +271,68 @@ impl ThemeSettings {
             }
         }
     }
+
+    /// Reloads the current theme and icon theme, cross-fading the UI colors from the
+    /// previously active theme to the new one.
+    ///
+    /// This is used when the theme changes without the user's direct involvement, such as
+    /// when the system's appearance changes or the `theme_schedule` switches themes.
+    pub fn reload_current_theme_with_crossfade(cx: &mut App) {
+        let previous_theme = ThemeSettings::get_global(cx).active_theme.clone();
+        Self::reload_current_theme(cx);
+        Self::reload_current_icon_theme(cx);
+        update_reduce_motion(cx);
+
+        let target_theme = ThemeSettings::get_global(cx).active_theme.clone();
+        if target_theme.name == previous_theme.name || cx.reduce_motion() {
+            return;
+        }
+
+        // Start from the previous theme's colors right away, so the new theme never flashes.
+        let mut theme_settings = ThemeSettings::get_global(cx).clone();
+        theme_settings.active_theme = Arc::new(target_theme.crossfaded_from(&previous_theme, 0.));
+        ThemeSettings::override_global(theme_settings, cx);
+
+        let task = cx.spawn(async move |cx| {
+            for step in 1..=CROSSFADE_STEPS {
+                cx.background_executor()
+                    .timer(CROSSFADE_DURATION / CROSSFADE_STEPS)
+                    .await;
+
+                let theme = if step == CROSSFADE_STEPS {
+                    target_theme.clone()
+                } else {
+                    let progress = step as f32 / CROSSFADE_STEPS as f32;
+                    Arc::new(target_theme.crossfaded_from(&previous_theme, progress))
+                };
+                let updated = cx.update(|cx| {
+                    let mut theme_settings = ThemeSettings::get_global(cx).clone();
+                    // Stop if another theme was selected in the meantime.
+                    if theme_settings.active_theme.name != target_theme.name {
+                        return false;
+                    }
+                    theme_settings.active_theme = theme;
+                    ThemeSettings::override_global(theme_settings, cx);
+                    cx.refresh_windows();
+                    true
+                });
+                if !matches!(updated, Ok(true)) {
+                    break;
+                }
+            }
+        });
+        cx.set_global(ThemeCrossfade(task));
+    }
 }
 
+const CROSSFADE_DURATION: Duration = Duration::from_millis(300);
+const CROSSFADE_STEPS: u32 = 15;
+
+/// The cross-fade in progress, if any. Replacing it cancels the previous cross-fade.
+struct ThemeCrossfade(Task<()>);
+
+impl Global for ThemeCrossfade {}
+
 /// The appearance of the system.
 #[derive(Debug, Clone, Copy, Deref)]
 pub struct SystemAppearance(pub Appearance);
@@ -281,6 +413,8 @@ fn theme_name_ref(_: &mut SchemaGenerator) -> Schema {
 /// `Light` and `Dark` will select their respective themes.
 ///
 /// `System` will select the theme based on the system's appearance.
+///
+/// `Schedule` will select the theme based on the time of day, following the `theme_schedule`.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ThemeMode {
@@ -293,17 +427,23 @@ pub enum ThemeMode {
     /// Use the theme based on the system's appearance.
     #[default]
     System,
+
+    /// Use the theme based on the time of day, as configured by the `theme_schedule`.
+    Schedule,
 }
 
 impl ThemeSelection {
     /// Returns the theme name for the selected [ThemeMode].
-    pub fn theme(&self, system_appearance: Appearance) -> &str {
+    ///
+    /// The given [`Appearance`] is the one the selection follows, as returned by
+    /// [`ThemeSettings::followed_appearance`].
+    pub fn theme(&self, appearance: Appearance) -> &str {
         match self {
             Self::Static(theme) => theme,
             Self::Dynamic { mode, light, dark } => match mode {
                 ThemeMode::Light => light,
                 ThemeMode::Dark => dark,
-                ThemeMode::System => match system_appearance {
+                ThemeMode::System | ThemeMode::Schedule => match appearance {
                     Appearance::Light => light,
                     Appearance::Dark => dark,
                 },
@@ -346,13 +486,16 @@ pub enum IconThemeSelection {
 
 impl IconThemeSelection {
     /// Returns the icon theme name based on the given [`Appearance`].
-    pub fn icon_theme(&self, system_appearance: Appearance) -> &str {
+    ///
+    /// The given [`Appearance`] is the one the selection follows, as returned by
+    /// [`ThemeSettings::followed_appearance`].
+    pub fn icon_theme(&self, appearance: Appearance) -> &str {
         match self {
             Self::Static(theme) => theme,
             Self::Dynamic { mode, light, dark } => match mode {
                 ThemeMode::Light => light,
                 ThemeMode::Dark => dark,
-                ThemeMode::System => match system_appearance {
+                ThemeMode::System | ThemeMode::Schedule => match appearance {
                     Appearance::Light => light,
                     Appearance::Dark => dark,
                 },
@@ -375,6 +518,14 @@ pub struct ThemeSettingsContent {
     /// The default font size for text in the UI.
     #[serde(default)]
     pub ui_font_size: Option<f32>,
+    /// How much to scale panels, tabs, menus and notifications by, independently of the
+    /// buffer font size.
+    #[serde(default)]
+    pub ui_scale: Option<f32>,
+    /// How much to scale the UI by on particular displays, by their UUIDs. These are set by the
+    /// `zed: zoom ui in` and `zed: zoom ui out` actions, for the display the window is on.
+    #[serde(default)]
+    pub display_ui_scales: Option<HashMap<String, f32>>,
     /// The name of a font to use for rendering in the UI.
     #[serde(default)]
     pub ui_font_family: Option<String>,
@@ -415,6 +566,12 @@ pub struct ThemeSettingsContent {
     /// The name of the icon theme to use.
     #[serde(default)]
     pub icon_theme: Option<IconThemeSelection>,
+    /// The schedule used to switch between the light and the dark theme when the mode of
+    /// `theme` or `icon_theme` is `"schedule"`.
+    ///
+    /// Default: null
+    #[serde(default)]
+    pub theme_schedule: Option<ThemeSchedule>,
 
     /// UNSTABLE: Expect many elements to be broken.
     ///
@@ -426,11 +583,60 @@ pub struct ThemeSettingsContent {
     #[serde(default)]
     pub unnecessary_code_fade: Option<f32>,
 
+    /// Whether to increase the contrast of the theme's text, icons and borders.
+    ///
+    /// Default: system
+    #[serde(default)]
+    pub high_contrast: Option<AccessibilityModeSetting>,
+
+    /// Whether to skip animations, cursor blinking and theme cross-fades.
+    ///
+    /// Default: system
+    #[serde(default)]
+    pub reduce_motion: Option<AccessibilityModeSetting>,
+
+    /// Named sets of OpenType features, like `{"no-ligatures": {"calt": false}}`, that the
+    /// `font_features` of languages and of the terminal can refer to by name.
+    ///
+    /// Default: {}
+    #[serde(default)]
+    pub font_feature_profiles: Option<HashMap<String, FontFeatures>>,
+
     /// EXPERIMENTAL: Overrides for the current theme.
     ///
     /// These values will override the ones on the current theme specified in `theme`.
     #[serde(rename = "experimental.theme_overrides", default)]
-    pub theme_overrides: Option<ThemeStyleContent>,
+    pub theme_overrides: Option<ThemeOverridesContent>,
+}
+
+/// Overrides for the current theme.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ThemeOverridesContent {
+    #[serde(flatten)]
+    pub style: ThemeStyleContent,
+
+    /// Overrides that only apply to the languages with the given names.
+    #[serde(default)]
+    pub languages: IndexMap<String, LanguageThemeOverridesContent>,
+}
+
+/// Overrides for the current theme that only apply to a single language.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct LanguageThemeOverridesContent {
+    /// The styles for syntax nodes in this language, merged over the styles for the same syntax
+    /// nodes in the current theme.
+    #[serde(default)]
+    pub syntax: IndexMap<String, HighlightStyleContent>,
+}
+
+/// OpenType features given either inline, or as the name of one of the `font_feature_profiles`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(untagged)]
+pub enum FontFeaturesSetting {
+    /// The name of one of the `font_feature_profiles`.
+    Profile(String),
+    /// The features themselves.
+    Features(FontFeatures),
 }
 
 fn default_font_features() -> Option<FontFeatures> {
@@ -450,7 +656,7 @@ impl ThemeSettingsContent {
                 ThemeSelection::Dynamic { mode, light, dark } => match mode {
                     ThemeMode::Light => light,
                     ThemeMode::Dark => dark,
-                    ThemeMode::System => match appearance {
+                    ThemeMode::System | ThemeMode::Schedule => match appearance {
                         Appearance::Light => light,
                         Appearance::Dark => dark,
                     },
@@ -471,7 +677,7 @@ impl ThemeSettingsContent {
                 IconThemeSelection::Dynamic { mode, light, dark } => match mode {
                     ThemeMode::Light => light,
                     ThemeMode::Dark => dark,
-                    ThemeMode::System => match appearance {
+                    ThemeMode::System | ThemeMode::Schedule => match appearance {
                         Appearance::Light => light,
                         Appearance::Dark => dark,
                     },
@@ -579,6 +785,14 @@ impl ThemeSettings {
         clamp_font_size(font_size)
     }
 
+    /// Returns how much to scale the UI by on the display with the given UUID.
+    pub fn ui_scale(&self, display_uuid: Option<&str>) -> f32 {
+        display_uuid
+            .and_then(|uuid| self.display_ui_scales.get(uuid))
+            .copied()
+            .unwrap_or(self.ui_scale)
+    }
+
     /// Returns the buffer font size, read from the settings.
     ///
     /// The real buffer font size is stored in-memory, to support temporary font size changes.
@@ -621,13 +835,37 @@ impl ThemeSettings {
         }
 
         self.apply_theme_overrides();
+        self.apply_high_contrast(cx);
 
         new_theme
     }
 
+    /// Increases the contrast of the current theme, if high contrast is enabled.
+    pub fn apply_high_contrast(&mut self, cx: &App) {
+        if self.is_high_contrast(cx) {
+            self.active_theme = Arc::new(self.active_theme.with_increased_contrast());
+        }
+    }
+
+    /// Returns whether the contrast of the theme is increased.
+    pub fn is_high_contrast(&self, cx: &App) -> bool {
+        self.high_contrast
+            .is_enabled(cx.system_prefers_high_contrast())
+    }
+
+    /// Returns whether animations, cursor blinking and theme cross-fades are skipped.
+    pub fn is_reduced_motion(&self, cx: &App) -> bool {
+        self.reduce_motion
+            .is_enabled(cx.system_prefers_reduced_motion())
+    }
+
     /// Applies the theme overrides, if there are any, to the current theme.
     pub fn apply_theme_overrides(&mut self) {
-        if let Some(theme_overrides) = &self.theme_overrides {
+        if let Some(ThemeOverridesContent {
+            style: theme_overrides,
+            languages,
+        }) = &self.theme_overrides
+        {
             let mut base_theme = (*self.active_theme).clone();
 
             if let Some(window_background_appearance) = theme_overrides.window_background_appearance
@@ -648,6 +886,13 @@ impl ThemeSettings {
             base_theme.styles.accents.merge(&theme_overrides.accents);
             base_theme.styles.syntax =
                 SyntaxTheme::merge(base_theme.styles.syntax, theme_overrides.syntax_overrides());
+            for (language_name, language_overrides) in languages {
+                base_theme.styles.syntax = SyntaxTheme::merge_for_language(
+                    base_theme.styles.syntax,
+                    language_name,
+                    syntax_styles(&language_overrides.syntax),
+                );
+            }
 
             self.active_theme = Arc::new(base_theme);
         }
@@ -716,16 +961,32 @@ pub fn reset_buffer_font_size(cx: &mut App) {
 // TODO: Make private, change usages to use `get_ui_font_size` instead.
 #[allow(missing_docs)]
 pub fn setup_ui_font(window: &mut Window, cx: &mut App) -> gpui::Font {
-    let (ui_font, ui_font_size) = {
-        let theme_settings = ThemeSettings::get_global(cx);
-        let font = theme_settings.ui_font.clone();
-        (font, theme_settings.ui_font_size(cx))
-    };
-
-    window.set_rem_size(ui_font_size);
+    let ui_font = ThemeSettings::get_global(cx).ui_font.clone();
+    window.set_rem_size(ui_rem_size(window, cx));
     ui_font
 }
 
+/// Returns the UUID of the display the window is on, which its UI scale is persisted under.
+pub fn display_uuid(window: &Window, cx: &App) -> Option<String> {
+    let uuid = window.display(cx)?.uuid().ok()?;
+    Some(uuid.to_string())
+}
+
+/// Returns the size of a rem in the UI of the given window, which is the UI font size scaled by
+/// the UI scale of the display the window is on.
+pub fn ui_rem_size(window: &Window, cx: &App) -> Pixels {
+    let theme_settings = ThemeSettings::get_global(cx);
+    let ui_scale = theme_settings.ui_scale(display_uuid(window, cx).as_deref());
+    theme_settings.ui_font_size(cx) * ui_scale
+}
+
+/// Tells GPUI whether to reduce motion, following the `reduce_motion` setting and, by default,
+/// the system's preference.
+pub fn update_reduce_motion(cx: &mut App) {
+    let reduce_motion = ThemeSettings::get_global(cx).is_reduced_motion(cx);
+    cx.set_reduce_motion(reduce_motion);
+}
+
 /// Sets the adjusted UI font size.
 pub fn adjust_ui_font_size(cx: &mut App, mut f: impl FnMut(&mut Pixels)) {
     let ui_font_size = ThemeSettings::get_global(cx).ui_font_size(cx);
@@ -746,6 +1007,11 @@ pub fn reset_ui_font_size(cx: &mut App) {
     }
 }
 
+/// Ensures the UI scale is within the valid range.
+pub fn clamp_ui_scale(scale: f32) -> f32 {
+    scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE)
+}
+
 /// Ensures font size is within the valid range.
 pub fn clamp_font_size(size: Pixels) -> Pixels {
     size.max(MIN_FONT_SIZE)
@@ -765,8 +1031,35 @@ impl settings::Settings for ThemeSettings {
         let system_appearance = SystemAppearance::default_global(cx);
 
         let defaults = sources.default;
+        let theme_schedule = [
+            sources.server,
+            sources.profile,
+            sources.release_channel,
+            sources.user,
+            Some(defaults),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|value| value.theme_schedule.clone());
+        if let Some(err) = theme_schedule
+            .as_ref()
+            .and_then(|schedule| schedule.validate().err())
+        {
+            log::error!("{err}");
+        }
+        let appearance_for = |mode: Option<ThemeMode>| {
+            followed_appearance(
+                mode,
+                theme_schedule.as_ref(),
+                *system_appearance,
+                &Local::now(),
+            )
+        };
+
         let mut this = Self {
             ui_font_size: defaults.ui_font_size.unwrap().into(),
+            ui_scale: defaults.ui_scale.unwrap_or(1.0),
+            display_ui_scales: HashMap::default(),
             ui_font: Font {
                 family: defaults.ui_font_family.as_ref().unwrap().clone().into(),
                 features: defaults.ui_font_features.clone().unwrap(),
@@ -790,8 +1083,12 @@ impl settings::Settings for ThemeSettings {
             buffer_font_size: defaults.buffer_font_size.unwrap().into(),
             buffer_line_height: defaults.buffer_line_height.unwrap(),
             theme_selection: defaults.theme.clone(),
+            theme_schedule: theme_schedule.clone(),
             active_theme: themes
-                .get(defaults.theme.as_ref().unwrap().theme(*system_appearance))
+                .get({
+                    let selection = defaults.theme.as_ref().unwrap();
+                    selection.theme(appearance_for(selection.mode()))
+                })
                 .or(themes.get(&zed_default_dark().name))
                 .unwrap(),
             theme_overrides: None,
@@ -801,18 +1098,22 @@ impl settings::Settings for ThemeSettings {
                 .as_ref()
                 .and_then(|selection| {
                     themes
-                        .get_icon_theme(selection.icon_theme(*system_appearance))
+                        .get_icon_theme(selection.icon_theme(appearance_for(selection.mode())))
                         .ok()
                 })
                 .unwrap_or_else(|| themes.get_icon_theme(DEFAULT_ICON_THEME_NAME).unwrap()),
             ui_density: defaults.ui_density.unwrap_or(UiDensity::Default),
             unnecessary_code_fade: defaults.unnecessary_code_fade.unwrap_or(0.0),
+            high_contrast: defaults.high_contrast.unwrap_or_default(),
+            reduce_motion: defaults.reduce_motion.unwrap_or_default(),
+            font_feature_profiles: defaults.font_feature_profiles.clone().unwrap_or_default(),
         };
 
         for value in sources
             .user
             .into_iter()
             .chain(sources.release_channel)
+            .chain(sources.profile)
             .chain(sources.server)
         {
             if let Some(value) = value.ui_density {
@@ -848,7 +1149,7 @@ impl settings::Settings for ThemeSettings {
             if let Some(value) = &value.theme {
                 this.theme_selection = Some(value.clone());
 
-                let theme_name = value.theme(*system_appearance);
+                let theme_name = value.theme(appearance_for(value.mode()));
 
                 match themes.get(theme_name) {
                     Ok(theme) => {
@@ -868,7 +1169,7 @@ impl settings::Settings for ThemeSettings {
             if let Some(value) = &value.icon_theme {
                 this.icon_theme_selection = Some(value.clone());
 
-                let icon_theme_name = value.icon_theme(*system_appearance);
+                let icon_theme_name = value.icon_theme(appearance_for(value.mode()));
 
                 match themes.get_icon_theme(icon_theme_name) {
                     Ok(icon_theme) => {
@@ -885,6 +1186,16 @@ impl settings::Settings for ThemeSettings {
             merge(&mut this.ui_font_size, value.ui_font_size.map(Into::into));
             this.ui_font_size = this.ui_font_size.clamp(px(6.), px(100.));
 
+            merge(&mut this.ui_scale, value.ui_scale);
+            this.ui_scale = clamp_ui_scale(this.ui_scale);
+            if let Some(display_ui_scales) = &value.display_ui_scales {
+                this.display_ui_scales.extend(
+                    display_ui_scales
+                        .iter()
+                        .map(|(uuid, scale)| (uuid.clone(), clamp_ui_scale(*scale))),
+                );
+            }
+
             merge(
                 &mut this.buffer_font_size,
                 value.buffer_font_size.map(Into::into),
@@ -896,7 +1207,18 @@ impl settings::Settings for ThemeSettings {
             // Clamp the `unnecessary_code_fade` to ensure text can't disappear entirely.
             merge(&mut this.unnecessary_code_fade, value.unnecessary_code_fade);
             this.unnecessary_code_fade = this.unnecessary_code_fade.clamp(0.0, 0.9);
+
+            merge(&mut this.high_contrast, value.high_contrast);
+            merge(&mut this.reduce_motion, value.reduce_motion);
+            if let Some(font_feature_profiles) = &value.font_feature_profiles {
+                this.font_feature_profiles.extend(
+                    font_feature_profiles
+                        .iter()
+                        .map(|(name, features)| (name.clone(), features.clone())),
+                );
+            }
         }
+        this.apply_high_contrast(cx);
 
         Ok(this)
     }
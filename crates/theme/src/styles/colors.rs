@@ -6,8 +6,8 @@ use std::sync::Arc;
 use strum::{AsRefStr, EnumIter, IntoEnumIterator};
 
 use crate::{
-    AccentColors, ActiveTheme, PlayerColors, StatusColors, StatusColorsRefinement, SyntaxTheme,
-    SystemColors,
+    AccentColors, ActiveTheme, Appearance, PlayerColors, StatusColors, StatusColorsRefinement,
+    SyntaxTheme, SystemColors,
 };
 
 #[derive(Refineable, Clone, Debug, PartialEq)]
@@ -486,6 +486,159 @@ impl ThemeColors {
         }
     }
 
+    pub fn color_mut(&mut self, field: ThemeColorField) -> &mut Hsla {
+        match field {
+            ThemeColorField::Border => &mut self.border,
+            ThemeColorField::BorderVariant => &mut self.border_variant,
+            ThemeColorField::BorderFocused => &mut self.border_focused,
+            ThemeColorField::BorderSelected => &mut self.border_selected,
+            ThemeColorField::BorderTransparent => &mut self.border_transparent,
+            ThemeColorField::BorderDisabled => &mut self.border_disabled,
+            ThemeColorField::ElevatedSurfaceBackground => &mut self.elevated_surface_background,
+            ThemeColorField::SurfaceBackground => &mut self.surface_background,
+            ThemeColorField::Background => &mut self.background,
+            ThemeColorField::ElementBackground => &mut self.element_background,
+            ThemeColorField::ElementHover => &mut self.element_hover,
+            ThemeColorField::ElementActive => &mut self.element_active,
+            ThemeColorField::ElementSelected => &mut self.element_selected,
+            ThemeColorField::ElementDisabled => &mut self.element_disabled,
+            ThemeColorField::DropTargetBackground => &mut self.drop_target_background,
+            ThemeColorField::GhostElementBackground => &mut self.ghost_element_background,
+            ThemeColorField::GhostElementHover => &mut self.ghost_element_hover,
+            ThemeColorField::GhostElementActive => &mut self.ghost_element_active,
+            ThemeColorField::GhostElementSelected => &mut self.ghost_element_selected,
+            ThemeColorField::GhostElementDisabled => &mut self.ghost_element_disabled,
+            ThemeColorField::Text => &mut self.text,
+            ThemeColorField::TextMuted => &mut self.text_muted,
+            ThemeColorField::TextPlaceholder => &mut self.text_placeholder,
+            ThemeColorField::TextDisabled => &mut self.text_disabled,
+            ThemeColorField::TextAccent => &mut self.text_accent,
+            ThemeColorField::Icon => &mut self.icon,
+            ThemeColorField::IconMuted => &mut self.icon_muted,
+            ThemeColorField::IconDisabled => &mut self.icon_disabled,
+            ThemeColorField::IconPlaceholder => &mut self.icon_placeholder,
+            ThemeColorField::IconAccent => &mut self.icon_accent,
+            ThemeColorField::StatusBarBackground => &mut self.status_bar_background,
+            ThemeColorField::TitleBarBackground => &mut self.title_bar_background,
+            ThemeColorField::TitleBarInactiveBackground => &mut self.title_bar_inactive_background,
+            ThemeColorField::ToolbarBackground => &mut self.toolbar_background,
+            ThemeColorField::TabBarBackground => &mut self.tab_bar_background,
+            ThemeColorField::TabInactiveBackground => &mut self.tab_inactive_background,
+            ThemeColorField::TabActiveBackground => &mut self.tab_active_background,
+            ThemeColorField::SearchMatchBackground => &mut self.search_match_background,
+            ThemeColorField::PanelBackground => &mut self.panel_background,
+            ThemeColorField::PanelFocusedBorder => &mut self.panel_focused_border,
+            ThemeColorField::PanelIndentGuide => &mut self.panel_indent_guide,
+            ThemeColorField::PanelIndentGuideHover => &mut self.panel_indent_guide_hover,
+            ThemeColorField::PanelIndentGuideActive => &mut self.panel_indent_guide_active,
+            ThemeColorField::PaneFocusedBorder => &mut self.pane_focused_border,
+            ThemeColorField::PaneGroupBorder => &mut self.pane_group_border,
+            ThemeColorField::ScrollbarThumbBackground => &mut self.scrollbar_thumb_background,
+            ThemeColorField::ScrollbarThumbHoverBackground => {
+                &mut self.scrollbar_thumb_hover_background
+            }
+            ThemeColorField::ScrollbarThumbBorder => &mut self.scrollbar_thumb_border,
+            ThemeColorField::ScrollbarTrackBackground => &mut self.scrollbar_track_background,
+            ThemeColorField::ScrollbarTrackBorder => &mut self.scrollbar_track_border,
+            ThemeColorField::EditorForeground => &mut self.editor_foreground,
+            ThemeColorField::EditorBackground => &mut self.editor_background,
+            ThemeColorField::EditorGutterBackground => &mut self.editor_gutter_background,
+            ThemeColorField::EditorSubheaderBackground => &mut self.editor_subheader_background,
+            ThemeColorField::EditorActiveLineBackground => &mut self.editor_active_line_background,
+            ThemeColorField::EditorHighlightedLineBackground => {
+                &mut self.editor_highlighted_line_background
+            }
+            ThemeColorField::EditorLineNumber => &mut self.editor_line_number,
+            ThemeColorField::EditorActiveLineNumber => &mut self.editor_active_line_number,
+            ThemeColorField::EditorInvisible => &mut self.editor_invisible,
+            ThemeColorField::EditorWrapGuide => &mut self.editor_wrap_guide,
+            ThemeColorField::EditorActiveWrapGuide => &mut self.editor_active_wrap_guide,
+            ThemeColorField::EditorIndentGuide => &mut self.editor_indent_guide,
+            ThemeColorField::EditorIndentGuideActive => &mut self.editor_indent_guide_active,
+            ThemeColorField::EditorDocumentHighlightReadBackground => {
+                &mut self.editor_document_highlight_read_background
+            }
+            ThemeColorField::EditorDocumentHighlightWriteBackground => {
+                &mut self.editor_document_highlight_write_background
+            }
+            ThemeColorField::EditorDocumentHighlightBracketBackground => {
+                &mut self.editor_document_highlight_bracket_background
+            }
+            ThemeColorField::TerminalBackground => &mut self.terminal_background,
+            ThemeColorField::TerminalForeground => &mut self.terminal_foreground,
+            ThemeColorField::TerminalBrightForeground => &mut self.terminal_bright_foreground,
+            ThemeColorField::TerminalDimForeground => &mut self.terminal_dim_foreground,
+            ThemeColorField::TerminalAnsiBackground => &mut self.terminal_ansi_background,
+            ThemeColorField::TerminalAnsiBlack => &mut self.terminal_ansi_black,
+            ThemeColorField::TerminalAnsiBrightBlack => &mut self.terminal_ansi_bright_black,
+            ThemeColorField::TerminalAnsiDimBlack => &mut self.terminal_ansi_dim_black,
+            ThemeColorField::TerminalAnsiRed => &mut self.terminal_ansi_red,
+            ThemeColorField::TerminalAnsiBrightRed => &mut self.terminal_ansi_bright_red,
+            ThemeColorField::TerminalAnsiDimRed => &mut self.terminal_ansi_dim_red,
+            ThemeColorField::TerminalAnsiGreen => &mut self.terminal_ansi_green,
+            ThemeColorField::TerminalAnsiBrightGreen => &mut self.terminal_ansi_bright_green,
+            ThemeColorField::TerminalAnsiDimGreen => &mut self.terminal_ansi_dim_green,
+            ThemeColorField::TerminalAnsiYellow => &mut self.terminal_ansi_yellow,
+            ThemeColorField::TerminalAnsiBrightYellow => &mut self.terminal_ansi_bright_yellow,
+            ThemeColorField::TerminalAnsiDimYellow => &mut self.terminal_ansi_dim_yellow,
+            ThemeColorField::TerminalAnsiBlue => &mut self.terminal_ansi_blue,
+            ThemeColorField::TerminalAnsiBrightBlue => &mut self.terminal_ansi_bright_blue,
+            ThemeColorField::TerminalAnsiDimBlue => &mut self.terminal_ansi_dim_blue,
+            ThemeColorField::TerminalAnsiMagenta => &mut self.terminal_ansi_magenta,
+            ThemeColorField::TerminalAnsiBrightMagenta => &mut self.terminal_ansi_bright_magenta,
+            ThemeColorField::TerminalAnsiDimMagenta => &mut self.terminal_ansi_dim_magenta,
+            ThemeColorField::TerminalAnsiCyan => &mut self.terminal_ansi_cyan,
+            ThemeColorField::TerminalAnsiBrightCyan => &mut self.terminal_ansi_bright_cyan,
+            ThemeColorField::TerminalAnsiDimCyan => &mut self.terminal_ansi_dim_cyan,
+            ThemeColorField::TerminalAnsiWhite => &mut self.terminal_ansi_white,
+            ThemeColorField::TerminalAnsiBrightWhite => &mut self.terminal_ansi_bright_white,
+            ThemeColorField::TerminalAnsiDimWhite => &mut self.terminal_ansi_dim_white,
+            ThemeColorField::LinkTextHover => &mut self.link_text_hover,
+            ThemeColorField::VersionControlAdded => &mut self.version_control_added,
+            ThemeColorField::VersionControlDeleted => &mut self.version_control_deleted,
+            ThemeColorField::VersionControlModified => &mut self.version_control_modified,
+            ThemeColorField::VersionControlRenamed => &mut self.version_control_renamed,
+            ThemeColorField::VersionControlConflict => &mut self.version_control_conflict,
+            ThemeColorField::VersionControlIgnored => &mut self.version_control_ignored,
+        }
+    }
+
+    /// Returns these colors blended towards `other` by the given amount, where `0.0`
+    /// returns these colors and `1.0` returns `other`.
+    pub fn blend_towards(&self, other: &ThemeColors, amount: f32) -> ThemeColors {
+        let mut blended = self.clone();
+        for (field, color) in other.iter() {
+            let blended_color = blended.color_mut(field);
+            *blended_color = blended_color.blend(color.opacity(amount));
+        }
+        blended
+    }
+
+    /// Returns these colors with their text, icons and borders pushed away from the
+    /// backgrounds of a theme with the given appearance, and made opaque.
+    pub fn with_increased_contrast(&self, appearance: Appearance) -> ThemeColors {
+        const FOREGROUND_AMOUNT: f32 = 0.6;
+        const BORDER_AMOUNT: f32 = 0.4;
+
+        let target_lightness = if appearance.is_light() { 0. } else { 1. };
+        let mut colors = self.clone();
+        for field in ThemeColorField::iter() {
+            let name = field.as_ref();
+            let amount =
+                if name.contains("text") || name.contains("icon") || name.contains("line_number") {
+                    FOREGROUND_AMOUNT
+                } else if name.contains("border") && !name.contains("transparent") {
+                    BORDER_AMOUNT
+                } else {
+                    continue;
+                };
+            let color = colors.color_mut(field);
+            color.l += (target_lightness - color.l) * amount;
+            color.a = 1.;
+        }
+        colors
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (ThemeColorField, Hsla)> + '_ {
         ThemeColorField::iter().map(move |field| (field, self.color(field)))
     }
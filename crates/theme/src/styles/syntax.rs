@@ -64,18 +64,7 @@ impl SyntaxTheme {
                 .iter_mut()
                 .find(|(existing_name, _)| existing_name == &name)
             {
-                existing_highlight.color = highlight.color.or(existing_highlight.color);
-                existing_highlight.font_weight =
-                    highlight.font_weight.or(existing_highlight.font_weight);
-                existing_highlight.font_style =
-                    highlight.font_style.or(existing_highlight.font_style);
-                existing_highlight.background_color = highlight
-                    .background_color
-                    .or(existing_highlight.background_color);
-                existing_highlight.underline = highlight.underline.or(existing_highlight.underline);
-                existing_highlight.strikethrough =
-                    highlight.strikethrough.or(existing_highlight.strikethrough);
-                existing_highlight.fade_out = highlight.fade_out.or(existing_highlight.fade_out);
+                merge_highlight_style(existing_highlight, highlight);
             } else {
                 merged_highlights.push((name, highlight));
             }
@@ -85,6 +74,64 @@ impl SyntaxTheme {
             highlights: merged_highlights,
         })
     }
+
+    /// Returns a new [`Arc<SyntaxTheme>`] with the given syntax styles merged in for the given
+    /// language only.
+    ///
+    /// Each style is merged over the theme's style for the same scope, and stored under a
+    /// [language-specific key](language_specific_syntax_key) that only that language's highlights
+    /// are matched against.
+    pub fn merge_for_language(
+        base: Arc<Self>,
+        language_name: &str,
+        syntax_styles: Vec<(String, HighlightStyle)>,
+    ) -> Arc<Self> {
+        if syntax_styles.is_empty() {
+            return base;
+        }
+
+        let language_styles = syntax_styles
+            .into_iter()
+            .map(|(scope, highlight)| {
+                let mut style = base.style_for_scope(&scope);
+                merge_highlight_style(&mut style, highlight);
+                (language_specific_syntax_key(&scope, language_name), style)
+            })
+            .collect();
+        Self::merge(base, language_styles)
+    }
+
+    /// Returns the style for the given scope, falling back to its less specific parents (e.g.
+    /// `comment` for `comment.doc`) if the theme doesn't style it.
+    fn style_for_scope(&self, scope: &str) -> HighlightStyle {
+        let mut scope = scope;
+        loop {
+            if let Some(ix) = self.highlight_id(scope) {
+                return self.highlights[ix as usize].1;
+            }
+            match scope.rsplit_once('.') {
+                Some((parent, _)) => scope = parent,
+                None => return HighlightStyle::default(),
+            }
+        }
+    }
+}
+
+/// Returns the key under which a syntax style that only applies to the given language is stored.
+pub fn language_specific_syntax_key(scope: &str, language_name: &str) -> String {
+    format!("{scope}@{language_name}")
+}
+
+fn merge_highlight_style(existing_highlight: &mut HighlightStyle, highlight: HighlightStyle) {
+    existing_highlight.color = highlight.color.or(existing_highlight.color);
+    existing_highlight.font_weight = highlight.font_weight.or(existing_highlight.font_weight);
+    existing_highlight.font_style = highlight.font_style.or(existing_highlight.font_style);
+    existing_highlight.background_color = highlight
+        .background_color
+        .or(existing_highlight.background_color);
+    existing_highlight.underline = highlight.underline.or(existing_highlight.underline);
+    existing_highlight.strikethrough = highlight.strikethrough.or(existing_highlight.strikethrough);
+    existing_highlight.fade_out = highlight.fade_out.or(existing_highlight.fade_out);
 }
 
 #[cfg(test)]
@@ -194,4 +241,73 @@ mod tests {
             ]))
         );
     }
+
+    #[test]
+    fn test_syntax_theme_merge_for_language() {
+        let base = Arc::new(SyntaxTheme::new_test([
+            ("comment", gpui::red()),
+            ("string", gpui::green()),
+        ]));
+
+        let syntax_theme = SyntaxTheme::merge_for_language(
+            base.clone(),
+            "Rust",
+            vec![
+                (
+                    "comment.doc".to_string(),
+                    HighlightStyle {
+                        font_style: Some(FontStyle::Italic),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "string".to_string(),
+                    HighlightStyle {
+                        color: Some(gpui::blue()),
+                        ..Default::default()
+                    },
+                ),
+            ],
+        );
+        assert_eq!(
+            syntax_theme,
+            Arc::new(SyntaxTheme::new_test_styles([
+                (
+                    "comment",
+                    HighlightStyle {
+                        color: Some(gpui::red()),
+                        ..Default::default()
+                    }
+                ),
+                (
+                    "string",
+                    HighlightStyle {
+                        color: Some(gpui::green()),
+                        ..Default::default()
+                    }
+                ),
+                (
+                    "comment.doc@Rust",
+                    HighlightStyle {
+                        color: Some(gpui::red()),
+                        font_style: Some(FontStyle::Italic),
+                        ..Default::default()
+                    }
+                ),
+                (
+                    "string@Rust",
+                    HighlightStyle {
+                        color: Some(gpui::blue()),
+                        ..Default::default()
+                    }
+                ),
+            ]))
+        );
+
+        // Merging no styles leaves the theme untouched.
+        assert!(Arc::ptr_eq(
+            &SyntaxTheme::merge_for_language(base.clone(), "Rust", Vec::new()),
+            &base
+        ));
+    }
 }
@@ -15,6 +15,7 @@ mod icon_theme;
 mod icon_theme_schema;
 mod registry;
 mod scale;
+mod schedule;
 mod schema;
 mod settings;
 mod styles;
@@ -40,6 +41,7 @@ pub use crate::icon_theme::*;
 pub use crate::icon_theme_schema::*;
 pub use crate::registry::*;
 pub use crate::scale::*;
+pub use crate::schedule::*;
 pub use crate::schema::*;
 pub use crate::settings::*;
 pub use crate::styles::*;
@@ -102,7 +104,9 @@ pub fn init(themes_to_load: LoadThemes, cx: &mut App) {
 
     ThemeSettings::register(cx);
     FontFamilyCache::init_global(cx);
+    schedule::observe_theme_schedule(cx);
 
+    update_reduce_motion(cx);
     let mut prev_buffer_font_size_settings =
         ThemeSettings::get_global(cx).buffer_font_size_settings();
     let mut prev_ui_font_size_settings = ThemeSettings::get_global(cx).ui_font_size_settings();
@@ -118,6 +122,8 @@ pub fn init(themes_to_load: LoadThemes, cx: &mut App) {
             prev_ui_font_size_settings = ui_font_size_settings;
             reset_ui_font_size(cx);
         }
+
+        update_reduce_motion(cx);
     })
     .detach();
 }
@@ -278,6 +284,25 @@ pub struct Theme {
 }
 
 impl Theme {
+    /// Returns a copy of this theme whose UI colors are blended from the colors of `from`,
+    /// where a `progress` of `0.0` shows the colors of `from` and `1.0` shows this theme's.
+    pub fn crossfaded_from(&self, from: &Theme, progress: f32) -> Theme {
+        let mut theme = self.clone();
+        theme.styles.colors = from
+            .styles
+            .colors
+            .blend_towards(&self.styles.colors, progress);
+        theme
+    }
+
+    /// Returns a copy of this theme whose text, icons and borders stand out more from the
+    /// backgrounds behind them, for when high contrast is enabled.
+    pub fn with_increased_contrast(&self) -> Theme {
+        let mut theme = self.clone();
+        theme.styles.colors = self.styles.colors.with_increased_contrast(self.appearance);
+        theme
+    }
+
     /// Returns the [`SystemColors`] for the theme.
     #[inline(always)]
     pub fn system(&self) -> &SystemColors {
@@ -166,7 +166,13 @@ impl PickerDelegate for IconThemeSelectorDelegate {
             value = theme_name
         );
 
-        let appearance = Appearance::from(window.appearance());
+        let appearance = theme_settings.followed_appearance(
+            theme_settings
+                .icon_theme_selection
+                .as_ref()
+                .and_then(|selection| selection.mode()),
+            Appearance::from(window.appearance()),
+        );
 
         update_settings_file::<ThemeSettings>(self.fs.clone(), cx, move |settings, _| {
             settings.set_icon_theme(theme_name.to_string(), appearance);
@@ -304,6 +310,7 @@ impl PickerDelegate for IconThemeSelectorDelegate {
                             window.dispatch_action(
                                 Box::new(Extensions {
                                     category_filter: Some(ExtensionCategoryFilter::IconThemes),
+                                    id: None,
                                 }),
                                 cx,
                             );
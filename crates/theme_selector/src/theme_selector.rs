@@ -214,7 +214,14 @@ impl PickerDelegate for ThemeSelectorDelegate {
 
         telemetry::event!("Settings Changed", setting = "theme", value = theme_name);
 
-        let appearance = Appearance::from(window.appearance());
+        let theme_settings = ThemeSettings::get_global(cx);
+        let appearance = theme_settings.followed_appearance(
+            theme_settings
+                .theme_selection
+                .as_ref()
+                .and_then(|selection| selection.mode()),
+            Appearance::from(window.appearance()),
+        );
 
         update_settings_file::<ThemeSettings>(self.fs.clone(), cx, move |settings, _| {
             settings.set_theme(theme_name.to_string(), appearance);
@@ -352,6 +359,7 @@ impl PickerDelegate for ThemeSelectorDelegate {
                             window.dispatch_action(
                                 Box::new(Extensions {
                                     category_filter: Some(ExtensionCategoryFilter::Themes),
+                                    id: None,
                                 }),
                                 cx,
                             );
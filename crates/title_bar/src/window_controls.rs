@@ -1,5 +1,5 @@
 use gpui::{svg, Action, Hsla};
-use ui::prelude::*;
+use ui::{prelude::*, Tooltip};
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub enum WindowControlType {
@@ -22,6 +22,18 @@ impl WindowControlType {
             WindowControlType::Close => IconName::GenericClose,
         }
     }
+
+    /// Returns the tooltip of the control and a hint about snapping the window, for the
+    /// maximize and restore controls. Client-side decorations don't get the hints that system
+    /// title bars show, so this points out that the window snaps when its title bar is dragged.
+    fn snap_hint(&self) -> Option<(&'static str, &'static str)> {
+        let hint = "Drag the title bar to a screen edge to snap the window";
+        match self {
+            WindowControlType::Maximize => Some(("Maximize", hint)),
+            WindowControlType::Restore => Some(("Restore", hint)),
+            WindowControlType::Minimize | WindowControlType::Close => None,
+        }
+    }
 }
 
 #[allow(unused)]
@@ -145,6 +157,9 @@ impl RenderOnce for WindowControl {
             .hover(|this| this.bg(self.style.background_hover))
             .active(|this| this.bg(self.style.background_hover))
             .child(icon)
+            .when_some(self.icon.snap_hint(), |this, (title, hint)| {
+                this.tooltip(move |window, cx| Tooltip::with_meta(title, None, hint, window, cx))
+            })
             .on_mouse_move(|_, _, cx| cx.stop_propagation())
             .on_click(move |_, window, cx| {
                 cx.stop_propagation();
@@ -1,7 +1,7 @@
 use editor::Editor;
 use gpui::{
-    div, AsyncWindowContext, Context, Entity, IntoElement, ParentElement, Render, Subscription,
-    Task, WeakEntity, Window,
+    div, App, AsyncWindowContext, Context, Entity, IntoElement, ParentElement, Render,
+    Subscription, Task, WeakEntity, Window,
 };
 use language::{Buffer, BufferEvent, LanguageName, Toolchain};
 use project::{Project, WorktreeId};
@@ -182,4 +182,8 @@ impl StatusItemView for ActiveToolchain {
         }
         cx.notify();
     }
+
+    fn segment_name(&self, _: &App) -> Option<SharedString> {
+        Some("toolchain".into())
+    }
 }
@@ -402,7 +402,7 @@ impl ButtonCommon for Button {
 
 impl RenderOnce for Button {
     #[allow(refining_impl_trait)]
-    fn render(self, _window: &mut Window, cx: &mut App) -> ButtonLike {
+    fn render(mut self, _window: &mut Window, cx: &mut App) -> ButtonLike {
         let is_disabled = self.base.disabled;
         let is_selected = self.base.selected;
 
@@ -410,6 +410,7 @@ impl RenderOnce for Button {
             .selected_label
             .filter(|_| is_selected)
             .unwrap_or(self.label);
+        self.base.accessibility_label = Some(label.clone());
 
         let label_color = if is_disabled {
             Color::Disabled
@@ -1,5 +1,8 @@
 use gpui::{relative, CursorStyle, DefiniteLength, MouseButton, MouseDownEvent, MouseUpEvent};
-use gpui::{transparent_black, AnyElement, AnyView, ClickEvent, Hsla, Rems};
+use gpui::{
+    transparent_black, Accessibility, AccessibilityRole, AnyElement, AnyView, ClickEvent, Hsla,
+    Rems,
+};
 use smallvec::SmallVec;
 
 use crate::{prelude::*, DynamicSpacing, ElevationIndex};
@@ -360,6 +363,8 @@ pub struct ButtonLike {
     cursor_style: CursorStyle,
     on_click: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
     on_right_click: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
+    /// The name screen readers give the button, like the text of its label.
+    pub(super) accessibility_label: Option<SharedString>,
     children: SmallVec<[AnyElement; 2]>,
 }
 
@@ -381,6 +386,7 @@ impl ButtonLike {
             cursor_style: CursorStyle::PointingHand,
             on_click: None,
             on_right_click: None,
+            accessibility_label: None,
             layer: None,
         }
     }
@@ -508,9 +514,13 @@ impl RenderOnce for ButtonLike {
             .filter(|_| self.selected)
             .unwrap_or(self.style);
 
+        let mut accessibility = Accessibility::new(AccessibilityRole::Button);
+        accessibility.label = self.accessibility_label;
+
         self.base
             .h_flex()
             .id(self.id.clone())
+            .accessibility(accessibility)
             .font_ui(cx)
             .group("")
             .flex_none()
@@ -3,13 +3,11 @@ use crate::{
     List, ListItem, ListSeparator, ListSubHeader,
 };
 use gpui::{
-    px, Action, AnyElement, App, AppContext as _, DismissEvent, Entity, EventEmitter, FocusHandle,
-    Focusable, IntoElement, Render, Subscription,
+    px, Accessibility, AccessibilityRole, Action, AnyElement, App, AppContext as _, DismissEvent,
+    Entity, EventEmitter, FocusHandle, Focusable, IntoElement, Render, Subscription,
 };
 use menu::{SelectFirst, SelectLast, SelectNext, SelectPrevious};
-use settings::Settings;
 use std::{rc::Rc, time::Duration};
-use theme::ThemeSettings;
 
 pub enum ContextMenuItem {
     Separator,
@@ -697,8 +695,16 @@ impl ContextMenu {
 
         let documentation_aside_callback = documentation_aside.clone();
 
+        let mut accessibility = Accessibility::new(AccessibilityRole::MenuItem)
+            .label(label.clone())
+            .selected(Some(ix) == self.selected_index);
+        if let Some((_, toggled)) = toggle {
+            accessibility = accessibility.toggled(*toggled);
+        }
+
         div()
             .id(("context-menu-child", ix))
+            .accessibility(accessibility)
             .when_some(
                 documentation_aside_callback.clone(),
                 |this, documentation_aside_callback| {
@@ -804,7 +810,7 @@ impl ContextMenuItem {
 
 impl Render for ContextMenu {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let ui_font_size = ThemeSettings::get_global(cx).ui_font_size(cx);
+        let ui_font_size = theme::ui_rem_size(window, cx);
         let window_size = window.viewport_size();
         let rem_size = window.rem_size();
         let is_wide_window = window_size.width / rem_size > rems_from_px(800.).0;
@@ -839,6 +845,7 @@ impl Render for ContextMenu {
                     .child(
                         v_flex()
                             .id("context-menu")
+                            .accessibility(Accessibility::new(AccessibilityRole::Menu))
                             .min_w(px(200.))
                             .max_h(vh(0.75, window))
                             .flex_1()
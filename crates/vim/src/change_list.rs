@@ -1,7 +1,8 @@
 use editor::{
     display_map::ToDisplayPoint, movement, scroll::Autoscroll, Anchor, Bias, Direction, Editor,
+    MultiBuffer,
 };
-use gpui::{actions, Context, Window};
+use gpui::{actions, Context, Entity, Window};
 
 use crate::{state::Mode, Vim};
 
@@ -24,6 +25,7 @@ impl Vim {
         cx: &mut Context<Self>,
     ) {
         let count = Vim::take_count(cx).unwrap_or(1);
+        self.restore_change_list(window, cx);
         if self.change_list.is_empty() {
             return;
         }
@@ -50,6 +52,7 @@ impl Vim {
     }
 
     pub(crate) fn push_to_change_list(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.restore_change_list(window, cx);
         let Some((map, selections, buffer)) = self.update_editor(window, cx, |_, editor, _, cx| {
             let (map, selections) = editor.selections.all_adjusted_display(cx);
             let buffer = editor.buffer().clone();
@@ -86,8 +89,37 @@ impl Vim {
             self.change_list.pop();
         }
         self.change_list.push(new_positions.clone());
+        self.save_change_list(&buffer, window, cx);
         self.set_mark(".".to_string(), new_positions, &buffer, window, cx)
     }
+
+    /// Picks up the changes made to the file in other editors, or in previous sessions.
+    fn restore_change_list(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.change_list.is_empty() {
+            return;
+        }
+        let Some((marks_state, editor)) = self.marks_state(window, cx).zip(self.editor()) else {
+            return;
+        };
+        let buffer = editor.read(cx).buffer().clone();
+        if let Some(change_list) = marks_state.read(cx).change_list(&buffer, cx) {
+            self.change_list = change_list;
+        }
+    }
+
+    fn save_change_list(
+        &mut self,
+        buffer: &Entity<MultiBuffer>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(marks_state) = self.marks_state(window, cx) else {
+            return;
+        };
+        marks_state.update(cx, |marks_state, cx| {
+            marks_state.set_change_list(buffer, &self.change_list, cx)
+        });
+    }
 }
 
 #[cfg(test)]
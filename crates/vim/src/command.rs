@@ -445,7 +445,6 @@ impl VimCommand {
         }
     }
 
-    // TODO: ranges with search queries
     fn parse_range(query: &str) -> (Option<CommandRange>, String) {
         let mut chars = query.chars().peekable();
 
@@ -531,6 +530,35 @@ impl VimCommand {
                     offset: Self::parse_offset(chars),
                 })
             }
+            '/' | '?' => {
+                // An unterminated pattern is a search (`:/foo`), not a range.
+                let mut lookahead = chars.clone();
+                let delimiter = lookahead.next()?;
+                let mut pattern = String::new();
+                let mut escaped = false;
+                loop {
+                    let c = lookahead.next()?;
+                    if escaped {
+                        escaped = false;
+                        if c != delimiter || c == '?' {
+                            pattern.push('\\');
+                        }
+                        pattern.push(c);
+                    } else if c == '\\' {
+                        escaped = true;
+                    } else if c == delimiter {
+                        break;
+                    } else {
+                        pattern.push(c);
+                    }
+                }
+                *chars = lookahead;
+                Some(Position::Pattern {
+                    pattern,
+                    backwards: delimiter == '?',
+                    offset: Self::parse_offset(chars),
+                })
+            }
             _ => None,
         }
     }
@@ -562,10 +590,25 @@ impl VimCommand {
 
 #[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq)]
 enum Position {
-    Line { row: u32, offset: i32 },
-    Mark { name: char, offset: i32 },
-    LastLine { offset: i32 },
-    CurrentLine { offset: i32 },
+    Line {
+        row: u32,
+        offset: i32,
+    },
+    Mark {
+        name: char,
+        offset: i32,
+    },
+    LastLine {
+        offset: i32,
+    },
+    CurrentLine {
+        offset: i32,
+    },
+    Pattern {
+        pattern: String,
+        backwards: bool,
+        offset: i32,
+    },
 }
 
 impl Position {
@@ -619,6 +662,42 @@ impl Position {
                 .to_point(&snapshot.buffer_snapshot)
                 .row
                 .saturating_add_signed(*offset),
+            Position::Pattern {
+                pattern,
+                backwards,
+                offset,
+            } => {
+                let regex = Regex::new(pattern)?;
+                let buffer = &snapshot.buffer_snapshot;
+                let current_row = editor
+                    .selections
+                    .newest_anchor()
+                    .head()
+                    .to_point(buffer)
+                    .row;
+                let row_count = buffer.max_row().0 + 1;
+                // Like `/` and `?`, start searching on the line after (or before) the cursor,
+                // wrapping around the end of the buffer.
+                (1..=row_count)
+                    .map(|distance| {
+                        if *backwards {
+                            (current_row + row_count - distance) % row_count
+                        } else {
+                            (current_row + distance) % row_count
+                        }
+                    })
+                    .find(|row| {
+                        let line = buffer
+                            .text_for_range(
+                                Point::new(*row, 0)
+                                    ..Point::new(*row, buffer.line_len(MultiBufferRow(*row))),
+                            )
+                            .collect::<String>();
+                        regex.is_match(&line)
+                    })
+                    .ok_or_else(|| anyhow!("pattern not found: {}", pattern))?
+                    .saturating_add_signed(*offset)
+            }
         };
 
         Ok(MultiBufferRow(target).min(snapshot.buffer_snapshot.max_row()))
@@ -1904,4 +1983,17 @@ mod test {
             a
             ˇa"});
     }
+
+    #[gpui::test]
+    async fn test_command_pattern_ranges(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇa\nfoo\nb\nbar\nc", Mode::Normal);
+        cx.simulate_keystrokes(": / f o o / , / b a r / d enter");
+        cx.assert_state("a\nˇc", Mode::Normal);
+
+        cx.set_state("a\nfoo\nb\nˇc", Mode::Normal);
+        cx.simulate_keystrokes(": ? f o o ? + 1 enter");
+        cx.assert_state("a\nfoo\nˇb\nc", Mode::Normal);
+    }
 }
@@ -129,4 +129,8 @@ impl StatusItemView for ModeIndicator {
         _cx: &mut Context<Self>,
     ) {
     }
+
+    fn segment_name(&self, _: &App) -> Option<SharedString> {
+        Some("vim_mode".into())
+    }
 }
@@ -4,7 +4,7 @@ use editor::{
     display_map::{DisplaySnapshot, ToDisplayPoint},
     movement,
     scroll::Autoscroll,
-    Anchor, Bias, DisplayPoint, Editor, MultiBuffer,
+    Anchor, Bias, DisplayPoint, Editor, MultiBuffer, ScrollbarMarkCategory,
 };
 use gpui::{Context, Entity, EntityId, UpdateGlobal, Window};
 use language::SelectionGoal;
@@ -14,10 +14,12 @@ use workspace::OpenOptions;
 
 use crate::{
     motion::{self, Motion},
-    state::{Mark, Mode, VimGlobals},
+    state::{Mark, MarksState, Mode, VimGlobals},
     Vim,
 };
 
+struct VimMarks;
+
 impl Vim {
     pub fn create_mark(&mut self, text: Arc<str>, window: &mut Window, cx: &mut Context<Self>) {
         self.update_editor(window, cx, |vim, editor, window, cx| {
@@ -29,9 +31,34 @@ impl Vim {
                 .collect::<Vec<_>>();
             vim.set_mark(text.to_string(), anchors, editor.buffer(), window, cx);
         });
+        self.refresh_scrollbar_marks(window, cx);
         self.clear_operator(window, cx);
     }
 
+    /// Shows the letter marks of the editor's buffer in the scrollbar.
+    pub(crate) fn refresh_scrollbar_marks(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace(window) else {
+            return;
+        };
+        let Some(marks_state) = Vim::globals(cx).marks.get(&workspace.entity_id()).cloned() else {
+            return;
+        };
+        self.update_editor(window, cx, |_, editor, _, cx| {
+            let ranges = marks_state
+                .read(cx)
+                .letter_mark_anchors(editor.buffer(), cx)
+                .into_iter()
+                .map(|anchor| anchor..anchor)
+                .collect::<Vec<_>>();
+            editor.set_scrollbar_marks::<VimMarks>(
+                ScrollbarMarkCategory::VimMarks,
+                &ranges,
+                |colors| colors.text_accent,
+                cx,
+            );
+        });
+    }
+
     // When handling an action, you must create visual marks if you will switch to normal
     // mode without the default selection behavior.
     pub(crate) fn store_visual_marks(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -265,6 +292,31 @@ impl Vim {
         }
     }
 
+    pub(crate) fn marks_state(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<MarksState>> {
+        let workspace_id = self.workspace(window)?.entity_id();
+        Vim::globals(cx).marks.get(&workspace_id).cloned()
+    }
+
+    pub(crate) fn save_jump_list(&self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(marks_state) = self.marks_state(window, cx) {
+            marks_state.update(cx, |marks_state, cx| marks_state.save_jump_list(cx));
+        }
+    }
+
+    pub(crate) fn restore_jump_list(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(marks_state) = self.marks_state(window, cx) else {
+            return;
+        };
+        // The workspace may still be updating the pane that the editor was focused in.
+        cx.defer(move |cx| {
+            marks_state.update(cx, |marks_state, cx| marks_state.restore_jump_list(cx))
+        });
+    }
+
     pub fn set_mark(
         &mut self,
         mut name: String,
@@ -4,11 +4,11 @@ use crate::{
     insert::NormalBefore,
     motion::Motion,
     normal::InsertBefore,
-    state::{Mode, Operator, RecordedSelection, ReplayableAction, VimGlobals},
+    state::{Mode, Operator, RecordedSelection, ReplayableAction, VimGlobals, DB},
     Vim,
 };
 use editor::Editor;
-use gpui::{actions, Action, App, Context, Window};
+use gpui::{actions, Action, App, AsyncApp, Context, Keystroke, Modifiers, Window};
 use workspace::Workspace;
 
 actions!(vim, [Repeat, EndRepeat, ToggleRecord, ReplayLastRecording]);
@@ -57,7 +57,14 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, |vim, _: &ToggleRecord, window, cx| {
         let globals = Vim::globals(cx);
         if let Some(char) = globals.recording_register.take() {
-            globals.last_recorded_register = Some(char)
+            globals.last_recorded_register = Some(char);
+            // Leave out the keystrokes that stopped the recording.
+            let binding_start = globals.recording_binding_start;
+            globals.recording_keystrokes.truncate(binding_start);
+            let keystrokes = keystrokes_to_text(&std::mem::take(&mut globals.recording_keystrokes));
+            globals.registers.insert(char, keystrokes.clone().into());
+            cx.background_spawn(async move { DB.set_macro(char, keystrokes).await })
+                .detach_and_log_err(cx);
         } else {
             vim.push_operator(Operator::RecordRegister, window, cx);
         }
@@ -159,6 +166,7 @@ impl Vim {
         let globals = Vim::globals(cx);
         globals.recording_register = Some(register);
         globals.recordings.remove(&register);
+        globals.recording_keystrokes.clear();
         globals.ignore_current_insertion = true;
         self.clear_operator(window, cx)
     }
@@ -180,6 +188,17 @@ impl Vim {
             register = last;
         }
         let Some(actions) = globals.recordings.get(&register) else {
+            // Macros from previous sessions, and text yanked into a register, are
+            // replayed by typing their keystrokes.
+            let Some(text) = globals.registers.get(&register) else {
+                return;
+            };
+            let keystrokes = text_to_keystrokes(&text.text);
+            globals.last_replayed_register = Some(register);
+            let keystrokes = (0..count)
+                .flat_map(|_| keystrokes.iter().cloned())
+                .collect();
+            self.replay_keystrokes(keystrokes, window, cx);
             return;
         };
 
@@ -194,6 +213,37 @@ impl Vim {
         replayer.replay(repeated_actions, window, cx);
     }
 
+    fn replay_keystrokes(
+        &mut self,
+        keystrokes: Vec<Keystroke>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if keystrokes.is_empty() || Vim::globals(cx).replaying_keystrokes {
+            return;
+        }
+        Vim::globals(cx).replaying_keystrokes = true;
+        cx.spawn_in(window, async move |_, cx| {
+            for keystroke in keystrokes.into_iter().take(10000) {
+                let dispatched = cx.update(|window, cx| {
+                    let focused = window.focused(cx);
+                    window.dispatch_keystroke(keystroke, cx);
+                    if window.focused(cx) != focused {
+                        // Like `workspace::SendKeystrokes`, render before the next keystroke so
+                        // that it's dispatched to the newly focused view.
+                        window.draw(cx);
+                    }
+                });
+                if dispatched.is_err() {
+                    break;
+                }
+            }
+            // The window may have closed while the macro was running.
+            AsyncApp::update(cx, |cx| Vim::globals(cx).replaying_keystrokes = false).ok();
+        })
+        .detach();
+    }
+
     pub(crate) fn repeat(
         &mut self,
         from_insert_mode: bool,
@@ -343,17 +393,87 @@ impl Vim {
     }
 }
 
+/// Writes keystrokes the way vim shows macros in registers: characters are written as
+/// they're typed, and other keys in angle brackets, like `<escape>` or `<ctrl-w>`.
+pub(crate) fn keystrokes_to_text(keystrokes: &[Keystroke]) -> String {
+    let mut text = String::new();
+    for keystroke in keystrokes {
+        let keystroke = keystroke.clone().with_simulated_ime();
+        let modifiers = &keystroke.modifiers;
+        let has_modifiers =
+            modifiers.control || modifiers.alt || modifiers.platform || modifiers.function;
+        let is_character = keystroke.key.chars().count() == 1 || keystroke.key == "space";
+        match keystroke.key_char.as_deref() {
+            Some("<") if !has_modifiers => text.push_str("<lt>"),
+            Some(key_char) if !has_modifiers && is_character && key_char.chars().count() == 1 => {
+                text.push_str(key_char)
+            }
+            _ => {
+                text.push('<');
+                text.push_str(&keystroke.unparse());
+                text.push('>');
+            }
+        }
+    }
+    text
+}
+
+/// Parses the text of a register into the keystrokes that type it.
+pub(crate) fn text_to_keystrokes(text: &str) -> Vec<Keystroke> {
+    let mut keystrokes = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c == '<' {
+            if let Some(end) = rest.find('>') {
+                let keystroke = match &rest[1..end] {
+                    "lt" => Some(character_keystroke('<')),
+                    name => Keystroke::parse(name).ok(),
+                };
+                if let Some(keystroke) = keystroke {
+                    keystrokes.push(keystroke);
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+        keystrokes.push(character_keystroke(c));
+        rest = &rest[c.len_utf8()..];
+    }
+    keystrokes
+}
+
+fn character_keystroke(c: char) -> Keystroke {
+    let (key, key_char) = match c {
+        ' ' => ("space".to_string(), Some(" ".to_string())),
+        '\n' => ("enter".to_string(), None),
+        '\t' => ("tab".to_string(), None),
+        '\x1b' => ("escape".to_string(), None),
+        c => (c.to_lowercase().to_string(), Some(c.to_string())),
+    };
+    Keystroke {
+        modifiers: Modifiers {
+            shift: c.is_uppercase(),
+            ..Default::default()
+        },
+        key,
+        key_char,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use editor::test::editor_lsp_test_context::EditorLspTestContext;
     use futures::StreamExt;
     use indoc::indoc;
 
-    use gpui::EntityInputHandler;
+    use gpui::{EntityInputHandler, KeyBinding};
 
+    use super::{keystrokes_to_text, text_to_keystrokes};
     use crate::{
+        insert::NormalBefore,
         state::Mode,
         test::{NeovimBackedTestContext, VimTestContext},
+        Vim,
     };
 
     #[gpui::test]
@@ -721,4 +841,80 @@ mod test {
         cx.simulate_shared_keystrokes("@ b").await;
         cx.shared_state().await.assert_eq("aaaaaaabbbˇd");
     }
+
+    #[gpui::test]
+    async fn test_record_stores_keystrokes(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇhello world\nfoo bar\n", Mode::Normal);
+        cx.simulate_keystrokes("q a c w j escape g g q");
+        cx.assert_state("ˇj world\nfoo bar\n", Mode::Normal);
+        let text = cx.update(|_, cx| {
+            Vim::globals(cx)
+                .registers
+                .get(&'a')
+                .map(|register| register.text.to_string())
+        });
+        assert_eq!(text.as_deref(), Some("cwj<escape>gg"));
+    }
+
+    #[gpui::test]
+    async fn test_record_multi_key_binding(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.update(|_, cx| {
+            cx.bind_keys([KeyBinding::new(
+                "j k",
+                NormalBefore,
+                Some("vim_mode == insert"),
+            )])
+        });
+
+        // `j o` doesn't complete the binding, so its `j` is typed as text, while `j k` does.
+        cx.set_state("ˇhello\nhello\nhello\n", Mode::Normal);
+        cx.simulate_keystrokes("q a i j o j k j q");
+        cx.assert_state("johello\nhˇello\nhello\n", Mode::Normal);
+        let text = cx.update(|_, cx| {
+            Vim::globals(cx)
+                .registers
+                .get(&'a')
+                .map(|register| register.text.to_string())
+        });
+        assert_eq!(text.as_deref(), Some("ijojkj"));
+
+        cx.simulate_keystrokes("0 @ a");
+        cx.run_until_parked();
+        cx.assert_state("johello\njohello\nhˇello\n", Mode::Normal);
+    }
+
+    #[gpui::test]
+    async fn test_replay_register_text(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇone two three four\n", Mode::Normal);
+        cx.update(|_, cx| {
+            Vim::globals(cx)
+                .registers
+                .insert('b', "dw".to_string().into())
+        });
+        cx.simulate_keystrokes("2 @ b");
+        cx.run_until_parked();
+        cx.assert_state("ˇthree four\n", Mode::Normal);
+        cx.simulate_keystrokes("@ @");
+        cx.run_until_parked();
+        cx.assert_state("ˇfour\n", Mode::Normal);
+
+        // Yanking into a register replaces the macro recorded there.
+        cx.set_state("ˇx\nabc\n", Mode::Normal);
+        cx.simulate_keystrokes("q c d d q u");
+        cx.simulate_keystrokes("\" c y i w j @ c");
+        cx.run_until_parked();
+        cx.assert_state("x\nˇbc\n", Mode::Normal);
+    }
+
+    #[test]
+    fn test_keystroke_text() {
+        for text in ["cwj<escape>gg", "A <lt>b><ctrl-w>:"] {
+            assert_eq!(keystrokes_to_text(&text_to_keystrokes(text)), text);
+        }
+    }
 }
@@ -1,18 +1,23 @@
-use editor::Editor;
-use gpui::{actions, impl_actions, impl_internal_actions, Context, Window};
+use editor::{scroll::Autoscroll, Anchor, Editor, MultiBufferSnapshot, ToPoint};
+use gpui::{actions, impl_actions, impl_internal_actions, App, Context, Entity, Window};
 use language::Point;
+use project::search::SearchQuery;
 use schemars::JsonSchema;
 use search::{buffer_search, BufferSearchBar, SearchOptions};
 use serde_derive::Deserialize;
-use std::{iter::Peekable, str::Chars, time::Duration};
+use settings::Settings;
+use std::{iter::Peekable, ops::Range, str::Chars, sync::Arc, time::Duration};
 use util::serde::default_true;
-use workspace::{notifications::NotifyResultExt, searchable::Direction};
+use workspace::{
+    notifications::NotifyResultExt,
+    searchable::{Direction, SearchableItem},
+};
 
 use crate::{
     command::CommandRange,
     motion::Motion,
-    state::{Mode, SearchState},
-    Vim,
+    state::{Mode, Operator, SearchState},
+    Vim, VimSettings,
 };
 
 #[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq)]
@@ -63,7 +68,11 @@ pub struct ReplaceCommand {
 pub(crate) struct Replacement {
     search: String,
     replacement: String,
-    should_replace_all: bool,
+    /// Whether the `g` flag was given, which toggles between replacing every match on a line
+    /// and only the first one, depending on the `gdefault` setting.
+    g_flag: bool,
+    count_only: bool,
+    confirm: bool,
     is_case_sensitive: bool,
 }
 
@@ -460,7 +469,7 @@ impl Vim {
                 let search = if replacement.search.is_empty() {
                     search_bar.query(cx)
                 } else {
-                    replacement.search
+                    replacement.search.clone()
                 };
                 if search_bar.should_use_smartcase_search(cx) {
                     options.set(
@@ -469,42 +478,252 @@ impl Vim {
                     );
                 }
                 search_bar.set_replacement(Some(&replacement.replacement), cx);
-                Some(search_bar.search(&search, Some(options), window, cx))
+                let task = search_bar.search(&search, Some(options), window, cx);
+                Some((task, search, options))
             });
-            let Some(search) = search else { return };
-            let search_bar = search_bar.downgrade();
+            let Some((search, query, options)) = search else {
+                return;
+            };
             cx.spawn_in(window, async move |_, cx| {
                 search.await?;
-                search_bar.update_in(cx, |search_bar, window, cx| {
-                    if replacement.should_replace_all {
-                        search_bar.select_last_match(window, cx);
-                        search_bar.replace_all(&Default::default(), window, cx);
-                        cx.spawn(async move |_, cx| {
-                            cx.background_executor()
-                                .timer(Duration::from_millis(200))
-                                .await;
-                            editor
-                                .update(cx, |editor, cx| editor.clear_search_within_ranges(cx))
-                                .ok();
-                        })
-                        .detach();
-                        vim.update(cx, |vim, cx| {
-                            vim.move_cursor(
-                                Motion::StartOfLine {
-                                    display_lines: false,
-                                },
-                                None,
-                                window,
-                                cx,
-                            )
-                        });
-                    }
+                let query = Arc::new(
+                    SearchQuery::regex(
+                        query,
+                        false,
+                        options.contains(SearchOptions::CASE_SENSITIVE),
+                        false,
+                        Default::default(),
+                        Default::default(),
+                        None,
+                    )?
+                    .with_replacement(replacement.replacement.clone()),
+                );
+                let matches = editor
+                    .update_in(cx, |editor, window, cx| {
+                        editor.find_matches(query.clone(), window, cx)
+                    })?
+                    .await;
+                vim.update_in(cx, |vim, window, cx| {
+                    vim.substitute_matches(matches, query, &replacement, editor, window, cx)
                 })?;
                 anyhow::Ok(())
             })
             .detach_and_log_err(cx);
         })
     }
+
+    fn substitute_matches(
+        &mut self,
+        mut matches: Vec<Range<Anchor>>,
+        query: Arc<SearchQuery>,
+        replacement: &Replacement,
+        editor: Entity<Editor>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = editor.read(cx).buffer().read(cx).snapshot(cx);
+        let replace_all_on_line = replacement.g_flag != VimSettings::get_global(cx).gdefault;
+        if !replace_all_on_line {
+            matches.dedup_by_key(|range| range.start.to_point(&snapshot).row);
+        }
+        let line_count = line_count(&matches, &snapshot);
+
+        if replacement.count_only {
+            editor.update(cx, |editor, cx| editor.clear_search_within_ranges(cx));
+            self.status_label = Some(
+                format!(
+                    "{} on {}",
+                    pluralize(matches.len(), "match", "matches"),
+                    pluralize(line_count, "line", "lines")
+                )
+                .into(),
+            );
+            cx.notify();
+            return;
+        }
+
+        let Some(last_match) = matches.last().cloned() else {
+            editor.update(cx, |editor, cx| editor.clear_search_within_ranges(cx));
+            return;
+        };
+
+        if replacement.confirm {
+            self.pending_substitution = Some(PendingSubstitution {
+                matches,
+                query,
+                editor,
+                match_ix: 0,
+                replaced: Vec::new(),
+            });
+            self.push_operator(Operator::ConfirmSubstitution, window, cx);
+            self.show_substitution_match(window, cx);
+            return;
+        }
+
+        editor.update(cx, |editor, cx| {
+            editor.change_selections(None, window, cx, |s| {
+                s.select_ranges([last_match.start..last_match.start])
+            });
+            editor.replace_all(&mut matches.iter(), &query, window, cx);
+        });
+        clear_search_within_ranges_later(editor, cx);
+        self.move_cursor(
+            Motion::StartOfLine {
+                display_lines: false,
+            },
+            None,
+            window,
+            cx,
+        );
+        if line_count > 2 {
+            self.status_label = Some(
+                format!(
+                    "{} on {}",
+                    pluralize(matches.len(), "substitution", "substitutions"),
+                    pluralize(line_count, "line", "lines")
+                )
+                .into(),
+            );
+            cx.notify();
+        }
+    }
+
+    /// Handles a key typed while `:s///c` asks whether to replace the current match.
+    pub(crate) fn confirm_substitution(
+        &mut self,
+        text: Arc<str>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(pending) = self.pending_substitution.as_mut() else {
+            self.clear_operator(window, cx);
+            return;
+        };
+        let current = pending.matches[pending.match_ix].clone();
+        match text.as_ref() {
+            "y" | "l" => {
+                pending.editor.update(cx, |editor, cx| {
+                    editor.replace(&current, &pending.query, window, cx)
+                });
+                pending.replaced.push(current);
+                pending.match_ix += 1;
+                if text.as_ref() == "l" {
+                    pending.match_ix = pending.matches.len();
+                }
+            }
+            "n" => pending.match_ix += 1,
+            "a" => {
+                let remaining = &pending.matches[pending.match_ix..];
+                pending.editor.update(cx, |editor, cx| {
+                    editor.replace_all(&mut remaining.iter(), &pending.query, window, cx)
+                });
+                pending.replaced.extend_from_slice(remaining);
+                pending.match_ix = pending.matches.len();
+            }
+            "q" => pending.match_ix = pending.matches.len(),
+            _ => return,
+        }
+        self.show_substitution_match(window, cx);
+    }
+
+    /// Moves the cursor to the match awaiting confirmation, or wraps up the substitution
+    /// once there are no matches left.
+    fn show_substitution_match(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(pending) = self.pending_substitution.as_ref() else {
+            return;
+        };
+        if let Some(current) = pending.matches.get(pending.match_ix) {
+            let position = current.start;
+            pending.editor.update(cx, |editor, cx| {
+                editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                    s.select_ranges([position..position])
+                });
+            });
+            let replacement = pending.query.replacement().unwrap_or_default();
+            self.status_label = Some(format!("replace with {replacement} (y/n/a/q/l)?").into());
+            cx.notify();
+            return;
+        }
+
+        let Some(pending) = self.pending_substitution.take() else {
+            return;
+        };
+        self.clear_operator(window, cx);
+        clear_search_within_ranges_later(pending.editor.clone(), cx);
+        let Some(last_replaced) = pending.replaced.last().cloned() else {
+            self.status_label.take();
+            cx.notify();
+            return;
+        };
+        pending.editor.update(cx, |editor, cx| {
+            editor.change_selections(None, window, cx, |s| {
+                s.select_ranges([last_replaced.start..last_replaced.start])
+            });
+        });
+        self.move_cursor(
+            Motion::StartOfLine {
+                display_lines: false,
+            },
+            None,
+            window,
+            cx,
+        );
+        let snapshot = pending.editor.read(cx).buffer().read(cx).snapshot(cx);
+        self.status_label = Some(
+            format!(
+                "{} on {}",
+                pluralize(pending.replaced.len(), "substitution", "substitutions"),
+                pluralize(line_count(&pending.replaced, &snapshot), "line", "lines")
+            )
+            .into(),
+        );
+        cx.notify();
+    }
+}
+
+/// The matches of a `:s///c` command that are waiting to be confirmed, one at a time.
+pub(crate) struct PendingSubstitution {
+    matches: Vec<Range<Anchor>>,
+    query: Arc<SearchQuery>,
+    editor: Entity<Editor>,
+    match_ix: usize,
+    replaced: Vec<Range<Anchor>>,
+}
+
+impl PendingSubstitution {
+    pub(crate) fn cancel(self, cx: &mut App) {
+        self.editor
+            .update(cx, |editor, cx| editor.clear_search_within_ranges(cx));
+    }
+}
+
+fn clear_search_within_ranges_later(editor: Entity<Editor>, cx: &mut Context<Vim>) {
+    cx.spawn(async move |_, cx| {
+        cx.background_executor()
+            .timer(Duration::from_millis(200))
+            .await;
+        editor
+            .update(cx, |editor, cx| editor.clear_search_within_ranges(cx))
+            .ok();
+    })
+    .detach();
+}
+
+fn line_count(matches: &[Range<Anchor>], snapshot: &MultiBufferSnapshot) -> usize {
+    let mut rows = matches
+        .iter()
+        .map(|range| range.start.to_point(snapshot).row)
+        .collect::<Vec<_>>();
+    rows.dedup();
+    rows.len()
+}
+
+fn pluralize(count: usize, singular: &str, plural: &str) -> String {
+    if count == 1 {
+        format!("1 {singular}")
+    } else {
+        format!("{count} {plural}")
+    }
 }
 
 impl Replacement {
@@ -564,14 +783,18 @@ impl Replacement {
         let mut replacement = Replacement {
             search,
             replacement,
-            should_replace_all: true,
+            g_flag: false,
+            count_only: false,
+            confirm: false,
             is_case_sensitive: true,
         };
 
         for c in flags.chars() {
             match c {
-                'g' | 'I' => {}
-                'c' | 'n' => replacement.should_replace_all = false,
+                'g' => replacement.g_flag = !replacement.g_flag,
+                'n' => replacement.count_only = true,
+                'c' => replacement.confirm = true,
+                'I' => replacement.is_case_sensitive = true,
                 'i' => replacement.is_case_sensitive = false,
                 _ => {}
             }
@@ -586,8 +809,9 @@ mod test {
     use std::time::Duration;
 
     use crate::{
-        state::Mode,
+        state::{Mode, Operator},
         test::{NeovimBackedTestContext, VimTestContext},
+        VimAddon, VimSettings,
     };
     use editor::EditorSettings;
     use editor::{display_map::DisplayRow, DisplayPoint};
@@ -990,4 +1214,94 @@ mod test {
                  "
         });
     }
+
+    #[gpui::test]
+    async fn test_replace_gdefault(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        // By default, every match on a line is replaced, and `g` replaces only the first one.
+        cx.set_state("ˇaa\naa\naa\n", Mode::Normal);
+        cx.simulate_keystrokes(": % s / a / b enter");
+        cx.run_until_parked();
+        cx.assert_state("bb\nbb\nˇbb\n", Mode::Normal);
+
+        cx.set_state("ˇaa\naa\naa\n", Mode::Normal);
+        cx.simulate_keystrokes(": % s / a / b / g enter");
+        cx.run_until_parked();
+        cx.assert_state("ba\nba\nˇba\n", Mode::Normal);
+
+        cx.update_global(|store: &mut SettingsStore, cx| {
+            store.update_user_settings::<VimSettings>(cx, |s| {
+                s.gdefault = Some(false);
+            });
+        });
+
+        cx.set_state("ˇaa\naa\naa\n", Mode::Normal);
+        cx.simulate_keystrokes(": % s / a / b enter");
+        cx.run_until_parked();
+        cx.assert_state("ba\nba\nˇba\n", Mode::Normal);
+
+        cx.set_state("ˇaa\naa\naa\n", Mode::Normal);
+        cx.simulate_keystrokes(": % s / a / b / g enter");
+        cx.run_until_parked();
+        cx.assert_state("bb\nbb\nˇbb\n", Mode::Normal);
+
+        cx.set_state("ˇaa\naa\naa\n", Mode::Normal);
+        cx.simulate_keystrokes(": % s / a / b / g g enter");
+        cx.run_until_parked();
+        cx.assert_state("ba\nba\nˇba\n", Mode::Normal);
+    }
+
+    #[gpui::test]
+    async fn test_replace_flags(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇaa\naa\naa\n", Mode::Normal);
+        cx.simulate_keystrokes(": % s / a / b / n enter");
+        cx.run_until_parked();
+        cx.assert_state("ˇaa\naa\naa\n", Mode::Normal);
+        let status_label = cx.update_editor(|editor, _, cx| {
+            editor
+                .addon::<VimAddon>()
+                .unwrap()
+                .entity
+                .read(cx)
+                .status_label
+                .clone()
+        });
+        assert_eq!(status_label.as_deref(), Some("6 matches on 3 lines"));
+    }
+
+    #[gpui::test]
+    async fn test_replace_with_confirmation(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇa\na\na\na\n", Mode::Normal);
+        cx.simulate_keystrokes(": % s / a / b / c enter");
+        cx.run_until_parked();
+        assert_eq!(cx.active_operator(), Some(Operator::ConfirmSubstitution));
+        cx.assert_state("ˇa\na\na\na\n", Mode::Normal);
+
+        cx.simulate_keystrokes("y");
+        cx.assert_state("b\nˇa\na\na\n", Mode::Normal);
+        cx.simulate_keystrokes("n");
+        cx.assert_state("b\na\nˇa\na\n", Mode::Normal);
+        cx.simulate_keystrokes("a");
+        assert_eq!(cx.active_operator(), None);
+        cx.assert_state("b\na\nb\nˇb\n", Mode::Normal);
+
+        cx.set_state("ˇa\na\na\n", Mode::Normal);
+        cx.simulate_keystrokes(": % s / a / b / c enter");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("n l");
+        assert_eq!(cx.active_operator(), None);
+        cx.assert_state("a\nˇb\na\n", Mode::Normal);
+
+        cx.set_state("ˇa\na\n", Mode::Normal);
+        cx.simulate_keystrokes(": % s / a / b / c enter");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("escape");
+        assert_eq!(cx.active_operator(), None);
+        cx.assert_state("ˇa\na\n", Mode::Normal);
+    }
 }
@@ -8,11 +8,13 @@ use collections::HashMap;
 use command_palette_hooks::{CommandPaletteFilter, CommandPaletteInterceptor};
 use db::define_connection;
 use db::sqlez_macros::sql;
+use editor::clipboard_history::ClipboardHistory;
 use editor::display_map::{is_invisible, replacement};
 use editor::{Anchor, ClipboardSelection, Editor, MultiBuffer, ToPoint as EditorToPoint};
 use gpui::{
     Action, App, AppContext, BorrowAppContext, ClipboardEntry, ClipboardItem, DismissEvent, Entity,
-    EntityId, Global, HighlightStyle, StyledText, Subscription, Task, TextStyle, WeakEntity,
+    EntityId, Global, HighlightStyle, Keystroke, KeystrokeEvent, StyledText, Subscription, Task,
+    TextStyle, WeakEntity,
 };
 use language::{Buffer, BufferEvent, BufferId, Chunk, Point};
 use multi_buffer::MultiBufferRow;
@@ -22,8 +24,9 @@ use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsStore};
 use std::borrow::BorrowMut;
 use std::collections::HashSet;
-use std::path::Path;
-use std::{fmt::Display, ops::Range, sync::Arc};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{any::Any, fmt::Display, ops::Range, sync::Arc};
 use text::{Bias, ToPoint};
 use theme::ThemeSettings;
 use ui::{
@@ -127,6 +130,7 @@ pub enum Operator {
     ToggleComments,
     ReplaceWithRegister,
     Exchange,
+    ConfirmSubstitution,
 }
 
 #[derive(Default, Clone, Debug)]
@@ -208,6 +212,11 @@ pub struct VimGlobals {
     pub recorded_selection: RecordedSelection,
 
     pub recording_register: Option<char>,
+    pub recording_keystrokes: Vec<Keystroke>,
+    /// The index in `recording_keystrokes` of the first keystroke of the binding being typed,
+    /// which is left out of the macro if it stops the recording.
+    pub recording_binding_start: usize,
+    pub replaying_keystrokes: bool,
     pub last_recorded_register: Option<char>,
     pub last_replayed_register: Option<char>,
     pub replayer: Option<Replayer>,
@@ -231,9 +240,23 @@ pub struct MarksState {
     serialized_marks: HashMap<Arc<Path>, HashMap<String, Vec<Point>>>,
     global_marks: HashMap<String, MarkLocation>,
 
+    change_lists: HashMap<Arc<Path>, Vec<Vec<Point>>>,
+
+    /// The jump list of a previous session, until it's restored into the active pane.
+    restored_jump_list: Option<Vec<(Arc<Path>, Point)>>,
+    save_jump_list_task: Option<Task<()>>,
+
     _subscription: Subscription,
 }
 
+/// How many changes are remembered for `g;` and `g,` in each file, like vim.
+const MAX_CHANGE_LIST_LEN: usize = 100;
+
+/// How many jumps are remembered for `ctrl-o` and `ctrl-i`, like vim.
+const MAX_JUMP_LIST_LEN: usize = 100;
+
+const JUMP_LIST_SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum MarkLocation {
     Buffer(EntityId),
@@ -268,6 +291,9 @@ impl MarksState {
                 watched_buffers: HashMap::default(),
                 serialized_marks: HashMap::default(),
                 global_marks: HashMap::default(),
+                change_lists: HashMap::default(),
+                restored_jump_list: None,
+                save_jump_list_task: None,
                 _subscription: subscription,
             };
 
@@ -294,14 +320,22 @@ impl MarksState {
             let Some(workspace_id) = this.update(cx, |this, cx| this.workspace_id(cx))? else {
                 return Ok(());
             };
-            let (marks, paths) = cx
+            let (marks, paths, change_lists, jump_list) = cx
                 .background_spawn(async move {
                     let marks = DB.get_marks(workspace_id)?;
                     let paths = DB.get_global_marks_paths(workspace_id)?;
-                    anyhow::Ok((marks, paths))
+                    let change_lists = DB.get_change_lists(workspace_id)?;
+                    let jump_list = DB.get_jump_list(workspace_id)?;
+                    anyhow::Ok((marks, paths, change_lists, jump_list))
                 })
                 .await?;
-            this.update(cx, |this, cx| this.loaded(marks, paths, cx))
+            this.update(cx, |this, cx| {
+                this.change_lists.extend(change_lists);
+                if !jump_list.is_empty() {
+                    this.restored_jump_list = Some(jump_list);
+                }
+                this.loaded(marks, paths, cx)
+            })
         })
         .detach_and_log_err(cx);
     }
@@ -570,6 +604,183 @@ impl MarksState {
         self.serialize_buffer_marks(abs_path, &buffer, cx)
     }
 
+    /// Returns the positions of the marks named with a single letter, like the ones set with
+    /// `ma`, in the multibuffer.
+    pub fn letter_mark_anchors(&self, multi_buffer: &Entity<MultiBuffer>, cx: &App) -> Vec<Anchor> {
+        let is_letter_mark = |name: &String| {
+            let mut chars = name.chars();
+            chars.next().is_some_and(|c| c.is_ascii_alphabetic()) && chars.next().is_none()
+        };
+
+        if let Some(marks) = self.multibuffer_marks.get(&multi_buffer.entity_id()) {
+            return marks
+                .iter()
+                .filter(|(name, _)| is_letter_mark(name))
+                .flat_map(|(_, anchors)| anchors.iter().copied())
+                .collect();
+        }
+
+        let Some(singleton) = multi_buffer.read(cx).as_singleton() else {
+            return Vec::new();
+        };
+        let Some(excerpt_id) = multi_buffer.read(cx).excerpt_ids().first().copied() else {
+            return Vec::new();
+        };
+        let buffer_id = singleton.read(cx).remote_id();
+        let Some(marks) = self.buffer_marks.get(&buffer_id) else {
+            return Vec::new();
+        };
+        marks
+            .iter()
+            .filter(|(name, _)| is_letter_mark(name))
+            .flat_map(|(_, anchors)| anchors.iter())
+            .map(|anchor| Anchor::in_buffer(excerpt_id, buffer_id, *anchor))
+            .collect()
+    }
+
+    /// Returns the change list saved for the file open in the multibuffer, such as
+    /// in a previous session.
+    pub fn change_list(
+        &self,
+        multi_buffer: &Entity<MultiBuffer>,
+        cx: &App,
+    ) -> Option<Vec<Vec<Anchor>>> {
+        let singleton = multi_buffer.read(cx).as_singleton()?;
+        let path = self.path_for_buffer(&singleton, cx)?;
+        let change_list = self.change_lists.get(&path)?;
+        let excerpt_id = *multi_buffer.read(cx).excerpt_ids().first()?;
+        let buffer = singleton.read(cx);
+        let buffer_id = buffer.remote_id();
+        Some(
+            change_list
+                .iter()
+                .map(|points| {
+                    points
+                        .iter()
+                        .map(|point| {
+                            let anchor =
+                                buffer.anchor_before(buffer.clip_point(*point, Bias::Left));
+                            Anchor::in_buffer(excerpt_id, buffer_id, anchor)
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// Saves the change list of the file open in the multibuffer, so that `g;` and `g,`
+    /// work after it's reopened.
+    pub fn set_change_list(
+        &mut self,
+        multi_buffer: &Entity<MultiBuffer>,
+        change_list: &[Vec<Anchor>],
+        cx: &mut Context<Self>,
+    ) {
+        let Some(path) = multi_buffer
+            .read(cx)
+            .as_singleton()
+            .and_then(|singleton| self.path_for_buffer(&singleton, cx))
+        else {
+            return;
+        };
+        let snapshot = multi_buffer.read(cx).snapshot(cx);
+        let change_list: Vec<Vec<Point>> = change_list
+            [change_list.len().saturating_sub(MAX_CHANGE_LIST_LEN)..]
+            .iter()
+            .map(|anchors| {
+                anchors
+                    .iter()
+                    .map(|anchor| anchor.to_point(&snapshot))
+                    .collect()
+            })
+            .collect();
+        if self.change_lists.get(&path) == Some(&change_list) {
+            return;
+        }
+        self.change_lists.insert(path.clone(), change_list.clone());
+
+        if let Some(workspace_id) = self.workspace_id(cx) {
+            cx.background_spawn(async move {
+                DB.set_change_list(workspace_id, path, change_list).await
+            })
+            .detach_and_log_err(cx);
+        }
+    }
+
+    /// Puts the jump list of the previous session before the active pane's navigation
+    /// history, so that `ctrl-o` goes back through it.
+    pub fn restore_jump_list(&mut self, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let Some(jump_list) = self.restored_jump_list.take() else {
+            return;
+        };
+        let workspace = workspace.read(cx);
+        let project = workspace.project().read(cx);
+        let pane = workspace.active_pane().clone();
+        let entries = jump_list
+            .into_iter()
+            .filter_map(|(abs_path, point)| {
+                let project_path = project.find_project_path(&abs_path, cx)?;
+                Some((
+                    project_path,
+                    Some(abs_path.to_path_buf()),
+                    Box::new(point) as Box<dyn Any + Send>,
+                ))
+            })
+            .collect();
+        pane.update(cx, |pane, cx| {
+            pane.nav_history_mut().restore_backward_entries(entries, cx)
+        });
+    }
+
+    /// Saves the active pane's navigation history as the workspace's jump list, once it
+    /// stops changing.
+    pub fn save_jump_list(&mut self, cx: &mut Context<Self>) {
+        self.save_jump_list_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(JUMP_LIST_SAVE_DEBOUNCE)
+                .await;
+            this.update(cx, |this, cx| this.serialize_jump_list(cx))
+                .log_err();
+        }));
+    }
+
+    fn serialize_jump_list(&mut self, cx: &mut Context<Self>) {
+        // Keep the previous session's jump list until it's restored.
+        if self.restored_jump_list.is_some() {
+            return;
+        }
+        let Some(workspace_id) = self.workspace_id(cx) else {
+            return;
+        };
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let workspace = workspace.read(cx);
+        let project = workspace.project().read(cx);
+        let mut jump_list: Vec<(Arc<Path>, Point)> = Vec::new();
+        workspace
+            .active_pane()
+            .read(cx)
+            .nav_history()
+            .for_each_backward_entry(cx, |entry, (project_path, abs_path)| {
+                let Some(point) = entry.data.as_deref().and_then(Editor::navigation_position)
+                else {
+                    return;
+                };
+                let Some(abs_path) = abs_path.or_else(|| project.absolute_path(&project_path, cx))
+                else {
+                    return;
+                };
+                jump_list.push((Arc::from(abs_path), point));
+            });
+        let jump_list = jump_list[jump_list.len().saturating_sub(MAX_JUMP_LIST_LEN)..].to_vec();
+        cx.background_spawn(async move { DB.set_jump_list(workspace_id, jump_list).await })
+            .detach_and_log_err(cx);
+    }
+
     pub fn get_mark(
         &self,
         name: &str,
@@ -615,6 +826,12 @@ impl Global for VimGlobals {}
 impl VimGlobals {
     pub(crate) fn register(cx: &mut App) {
         cx.set_global(VimGlobals::default());
+        Self::load_macros(cx);
+
+        cx.intercept_keystrokes(|event, window, cx| {
+            Vim::globals(cx).intercept_keystroke(event, window);
+        })
+        .detach();
 
         cx.observe_keystrokes(|event, _, cx| {
             let Some(action) = event.action.as_ref().map(|action| action.boxed_clone()) else {
@@ -644,6 +861,7 @@ impl VimGlobals {
             was_enabled = Some(is_enabled);
             if is_enabled {
                 KeyBinding::set_vim_mode(cx, true);
+                Self::load_macros(cx);
                 CommandPaletteFilter::update_global(cx, |filter, _| {
                     filter.show_namespace(Vim::NAMESPACE);
                 });
@@ -679,6 +897,25 @@ impl VimGlobals {
         .detach()
     }
 
+    /// Restores the macros recorded in previous sessions into their registers.
+    fn load_macros(cx: &mut App) {
+        cx.spawn(async move |cx| {
+            let macros = cx.background_spawn(async move { DB.get_macros() }).await?;
+            cx.update(|cx| {
+                let globals = Vim::globals(cx);
+                for (name, keystrokes) in macros {
+                    if let Some(register) = name.chars().next() {
+                        globals
+                            .registers
+                            .entry(register)
+                            .or_insert_with(|| keystrokes.into());
+                    }
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn register_workspace(&mut self, workspace: &Workspace, cx: &mut Context<Workspace>) {
         let entity_id = cx.entity_id();
         self.marks.insert(entity_id, MarksState::new(workspace, cx));
@@ -698,8 +935,14 @@ impl VimGlobals {
         linewise: bool,
         cx: &mut Context<Editor>,
     ) {
+        // Yanks and deletes are pasted from the clipboard history too, unless they're thrown away.
+        if register != Some('_') {
+            ClipboardHistory::record(&content.clone().into(), cx);
+        }
         if let Some(register) = register {
             let lower = register.to_lowercase().next().unwrap_or(register);
+            // The register no longer holds the recorded macro, so `@` replays its text instead.
+            self.recordings.remove(&lower);
             if lower != register {
                 let current = self.registers.entry(lower).or_default();
                 current.text = (current.text.to_string() + &content.text).into();
@@ -836,7 +1079,7 @@ impl VimGlobals {
                 self.stop_recording_after_next_action = false;
             }
         }
-        if self.replayer.is_none() {
+        if self.replayer.is_none() && !self.replaying_keystrokes {
             if let Some(recording_register) = self.recording_register {
                 self.recordings
                     .entry(recording_register)
@@ -846,6 +1089,20 @@ impl VimGlobals {
         }
     }
 
+    /// Records the keystrokes of a macro as they're typed, so that it can be stored as text in
+    /// its register.
+    fn intercept_keystroke(&mut self, event: &KeystrokeEvent, window: &Window) {
+        if self.recording_register.is_none() || self.replayer.is_some() || self.replaying_keystrokes
+        {
+            return;
+        }
+        // The keystrokes pending in the window, like the first `g` of `g g`, were recorded as
+        // they were typed, and start the binding that this keystroke may complete.
+        let pending_len = window.pending_input_keystrokes().map_or(0, <[_]>::len);
+        self.recording_binding_start = self.recording_keystrokes.len().saturating_sub(pending_len);
+        self.recording_keystrokes.push(event.keystroke.clone());
+    }
+
     pub fn observe_insertion(&mut self, text: &Arc<str>, range_to_replace: Option<Range<isize>>) {
         if self.ignore_current_insertion {
             self.ignore_current_insertion = false;
@@ -961,6 +1218,7 @@ impl Operator {
             Operator::RecordRegister => "q",
             Operator::ReplayRegister => "@",
             Operator::ToggleComments => "gc",
+            Operator::ConfirmSubstitution => ":s",
         }
     }
 
@@ -994,6 +1252,7 @@ impl Operator {
             | Operator::Digraph { .. }
             | Operator::Literal { .. }
             | Operator::ChangeSurrounds { target: Some(_) }
+            | Operator::ConfirmSubstitution
             | Operator::DeleteSurrounds => true,
             Operator::Change
             | Operator::Delete
@@ -1047,7 +1306,8 @@ impl Operator {
             | Operator::Jump { .. }
             | Operator::Register
             | Operator::RecordRegister
-            | Operator::ReplayRegister => false,
+            | Operator::ReplayRegister
+            | Operator::ConfirmSubstitution => false,
         }
     }
 }
@@ -1619,6 +1879,26 @@ define_connection! (
             CREATE UNIQUE INDEX idx_vim_global_marks_paths
             ON vim_global_marks_paths(workspace_id, mark_name);
         ),
+        sql! (
+            CREATE TABLE vim_macros(
+                register_name TEXT PRIMARY KEY,
+                keystrokes TEXT NOT NULL
+            );
+        ),
+        sql! (
+            CREATE TABLE vim_change_lists(
+                workspace_id INTEGER,
+                path BLOB,
+                value TEXT
+            );
+            CREATE UNIQUE INDEX idx_vim_change_lists ON vim_change_lists (workspace_id, path);
+        ),
+        sql! (
+            CREATE TABLE vim_jump_lists(
+                workspace_id INTEGER PRIMARY KEY,
+                value TEXT
+            );
+        ),
     ];
 );
 
@@ -1705,4 +1985,113 @@ impl VimDb {
             WHERE workspace_id = ?
         ))?(workspace_id)
     }
+
+    pub(crate) async fn set_macro(&self, register: char, keystrokes: String) -> Result<()> {
+        self.write(move |conn| {
+            conn.exec_bound(sql!(
+                INSERT OR REPLACE INTO vim_macros
+                    (register_name, keystrokes)
+                VALUES
+                    (?, ?)
+            ))?((register.to_string(), keystrokes))
+        })
+        .await
+    }
+
+    fn get_macros(&self) -> Result<Vec<(String, String)>> {
+        self.select(sql!(
+            SELECT register_name, keystrokes FROM vim_macros
+        ))?()
+    }
+
+    pub(crate) async fn set_change_list(
+        &self,
+        workspace_id: WorkspaceId,
+        path: Arc<Path>,
+        change_list: Vec<Vec<Point>>,
+    ) -> Result<()> {
+        let value: Vec<Vec<(u32, u32)>> = change_list
+            .into_iter()
+            .map(|points| {
+                points
+                    .into_iter()
+                    .map(|point| (point.row, point.column))
+                    .collect()
+            })
+            .collect();
+        let value = serde_json::to_string(&value)?;
+        self.write(move |conn| {
+            conn.exec_bound(sql!(
+                INSERT OR REPLACE INTO vim_change_lists
+                    (workspace_id, path, value)
+                VALUES
+                    (?, ?, ?)
+            ))?((workspace_id, path, value))
+        })
+        .await
+    }
+
+    fn get_change_lists(
+        &self,
+        workspace_id: WorkspaceId,
+    ) -> Result<Vec<(Arc<Path>, Vec<Vec<Point>>)>> {
+        let result: Vec<(Arc<Path>, String)> = self.select_bound(sql!(
+            SELECT path, value FROM vim_change_lists
+                WHERE workspace_id = ?
+        ))?(workspace_id)?;
+
+        Ok(result
+            .into_iter()
+            .filter_map(|(path, value)| {
+                let value: Vec<Vec<(u32, u32)>> = serde_json::from_str(&value).log_err()?;
+                let change_list = value
+                    .into_iter()
+                    .map(|points| {
+                        points
+                            .into_iter()
+                            .map(|(row, column)| Point { row, column })
+                            .collect()
+                    })
+                    .collect();
+                Some((path, change_list))
+            })
+            .collect())
+    }
+
+    pub(crate) async fn set_jump_list(
+        &self,
+        workspace_id: WorkspaceId,
+        jump_list: Vec<(Arc<Path>, Point)>,
+    ) -> Result<()> {
+        let value: Vec<(&Path, u32, u32)> = jump_list
+            .iter()
+            .map(|(path, point)| (path.as_ref(), point.row, point.column))
+            .collect();
+        let value = serde_json::to_string(&value)?;
+        self.write(move |conn| {
+            conn.exec_bound(sql!(
+                INSERT OR REPLACE INTO vim_jump_lists
+                    (workspace_id, value)
+                VALUES
+                    (?, ?)
+            ))?((workspace_id, value))
+        })
+        .await
+    }
+
+    fn get_jump_list(&self, workspace_id: WorkspaceId) -> Result<Vec<(Arc<Path>, Point)>> {
+        let value: Option<String> = self.select_row_bound(sql!(
+            SELECT value FROM vim_jump_lists
+                WHERE workspace_id = ?
+        ))?(workspace_id)?;
+        let Some(value) = value else {
+            return Ok(Vec::new());
+        };
+
+        let value: Vec<(PathBuf, u32, u32)> = serde_json::from_str(&value)?;
+        Ok(value
+            .into_iter()
+            .map(|(path, row, column)| (Arc::from(path), Point { row, column }))
+            .collect())
+    }
 }
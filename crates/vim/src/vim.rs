@@ -33,7 +33,7 @@ use insert::{NormalBefore, TemporaryNormal};
 use language::{CharKind, CursorShape, Point, Selection, SelectionGoal, TransactionId};
 pub use mode_indicator::ModeIndicator;
 use motion::Motion;
-use normal::search::SearchSubmit;
+use normal::search::{PendingSubstitution, SearchSubmit};
 use object::Object;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -325,6 +325,7 @@ pub(crate) struct Vim {
 
     selected_register: Option<char>,
     pub search: SearchState,
+    pub(crate) pending_substitution: Option<PendingSubstitution>,
 
     editor: WeakEntity<Editor>,
 
@@ -372,6 +373,7 @@ impl Vim {
             status_label: None,
             selected_register: None,
             search: SearchState::default(),
+            pending_substitution: None,
 
             last_command: None,
             running_command: None,
@@ -800,7 +802,10 @@ impl Vim {
         cx: &mut Context<Self>,
     ) {
         match event {
-            EditorEvent::Focused => self.focused(true, window, cx),
+            EditorEvent::Focused => {
+                self.focused(true, window, cx);
+                self.restore_jump_list(window, cx);
+            }
             EditorEvent::Blurred => self.blurred(window, cx),
             EditorEvent::SelectionsChanged { local: true } => {
                 self.local_selections_changed(window, cx);
@@ -834,6 +839,7 @@ impl Vim {
                     };
                     vim.set_mark(mark, vec![*anchor], editor.buffer(), window, cx);
                 });
+                self.save_jump_list(window, cx);
             }
             _ => {}
         }
@@ -1131,6 +1137,7 @@ impl Vim {
 
         cx.emit(VimEvent::Focused);
         self.sync_vim_settings(window, cx);
+        self.refresh_scrollbar_marks(window, cx);
 
         if VimSettings::get_global(cx).toggle_relative_line_numbers {
             if let Some(old_vim) = Vim::globals(cx).focused_vim() {
@@ -1356,6 +1363,10 @@ impl Vim {
         Vim::take_count(cx);
         self.selected_register.take();
         self.operator_stack.clear();
+        if let Some(pending) = self.pending_substitution.take() {
+            pending.cancel(cx);
+            self.status_label.take();
+        }
         self.sync_vim_settings(window, cx);
     }
 
@@ -1586,6 +1597,7 @@ impl Vim {
                 _ => self.clear_operator(window, cx),
             },
             Some(Operator::Mark) => self.create_mark(text, window, cx),
+            Some(Operator::ConfirmSubstitution) => self.confirm_substitution(text, window, cx),
             Some(Operator::RecordRegister) => {
                 self.record_register(text.chars().next().unwrap(), window, cx)
             }
@@ -1672,6 +1684,7 @@ struct VimSettings {
     pub use_smartcase_find: bool,
     pub custom_digraphs: HashMap<String, Arc<str>>,
     pub highlight_on_yank_duration: u64,
+    pub gdefault: bool,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -1683,6 +1696,7 @@ struct VimSettingsContent {
     pub use_smartcase_find: Option<bool>,
     pub custom_digraphs: Option<HashMap<String, Arc<str>>>,
     pub highlight_on_yank_duration: Option<u64>,
+    pub gdefault: Option<bool>,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -1741,6 +1755,7 @@ impl Settings for VimSettings {
             highlight_on_yank_duration: settings
                 .highlight_on_yank_duration
                 .ok_or_else(Self::missing_default)?,
+            gdefault: settings.gdefault.ok_or_else(Self::missing_default)?,
         })
     }
 }
@@ -25,11 +25,9 @@ impl Settings for VimModeSetting {
 
     fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
         Ok(Self(
-            sources
-                .user
-                .or(sources.server)
-                .copied()
-                .flatten()
+            [sources.profile, sources.user, sources.server]
+                .into_iter()
+                .find_map(|value| value.copied().flatten())
                 .unwrap_or(sources.default.ok_or_else(Self::missing_default)?),
         ))
     }
@@ -925,6 +925,15 @@ impl StatusItemView for PanelButtons {
     ) {
         // Nothing to do, panel buttons don't depend on the active center item
     }
+
+    fn segment_name(&self, cx: &App) -> Option<SharedString> {
+        let name = match self.dock.read(cx).position() {
+            DockPosition::Left => "left_dock",
+            DockPosition::Bottom => "bottom_dock",
+            DockPosition::Right => "right_dock",
+        };
+        Some(name.into())
+    }
 }
 
 #[cfg(any(test, feature = "test-support"))]
@@ -348,6 +348,10 @@ pub trait Item: Focusable + EventEmitter<Self::Event> + Render + Sized {
     ) {
     }
 
+    /// Called when the pane the item is in enters or leaves zen mode, and when the item is added
+    /// to a pane, so that it can hide its own distractions, like gutters.
+    fn set_zen_mode(&mut self, _enabled: bool, _window: &mut Window, _cx: &mut Context<Self>) {}
+
     fn show_toolbar(&self) -> bool {
         true
     }
@@ -480,6 +484,7 @@ pub trait ItemHandle: 'static + Send {
         cx: &mut Context<Workspace>,
     );
     fn deactivated(&self, window: &mut Window, cx: &mut App);
+    fn set_zen_mode(&self, enabled: bool, window: &mut Window, cx: &mut App);
     fn discarded(&self, project: Entity<Project>, window: &mut Window, cx: &mut App);
     fn workspace_deactivated(&self, window: &mut Window, cx: &mut App);
     fn navigate(&self, data: Box<dyn Any>, window: &mut Window, cx: &mut App) -> bool;
@@ -691,9 +696,11 @@ impl<T: Item> ItemHandle for Entity<T> {
     ) {
         let weak_item = self.downgrade();
         let history = pane.read(cx).nav_history_for_item(self);
+        let zen_mode = pane.read(cx).is_zen_mode();
         self.update(cx, |this, cx| {
             this.set_nav_history(history, window, cx);
             this.added_to_workspace(workspace, window, cx);
+            this.set_zen_mode(zen_mode, window, cx);
         });
 
         if let Some(serializable_item) = self.to_serializable_item_handle(cx) {
@@ -874,6 +881,10 @@ impl<T: Item> ItemHandle for Entity<T> {
         self.update(cx, |this, cx| this.deactivated(window, cx));
     }
 
+    fn set_zen_mode(&self, enabled: bool, window: &mut Window, cx: &mut App) {
+        self.update(cx, |this, cx| this.set_zen_mode(enabled, window, cx));
+    }
+
     fn workspace_deactivated(&self, window: &mut Window, cx: &mut App) {
         self.update(cx, |this, cx| this.workspace_deactivated(window, cx));
     }
@@ -1,14 +1,20 @@
-use crate::{Toast, Workspace};
+use crate::{NotificationCenterSettings, Toast, Workspace};
+use chrono::{DateTime, Local};
 use gpui::{
-    svg, AnyView, App, AppContext as _, AsyncWindowContext, ClipboardItem, Context, DismissEvent,
-    Entity, EventEmitter, FocusHandle, Focusable, PromptLevel, Render, ScrollHandle, Task,
+    svg, Accessibility, AccessibilityRole, Action, AnyView, App, AppContext as _,
+    AsyncWindowContext, ClipboardItem, Context, DismissEvent, Entity, EventEmitter, FocusHandle,
+    Focusable, Global, PromptLevel, Render, ScrollHandle, Task,
 };
 use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use std::collections::VecDeque;
 use std::ops::Deref;
 use std::sync::{Arc, LazyLock};
 use std::{any::TypeId, time::Duration};
 use ui::{prelude::*, Tooltip};
-use util::ResultExt;
+use util::{post_inc, ResultExt};
 
 #[derive(Default)]
 pub struct Notifications {
@@ -54,6 +60,158 @@ impl NotificationId {
     }
 }
 
+/// Where a notification in the [`NotificationHistory`] came from. Each source
+/// can be muted separately in the notification center.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSource {
+    LanguageServer,
+    Task,
+    Collab,
+    Call,
+    Workspace,
+}
+
+impl NotificationSource {
+    pub const ALL: [Self; 5] = [
+        Self::LanguageServer,
+        Self::Task,
+        Self::Collab,
+        Self::Call,
+        Self::Workspace,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::LanguageServer => "Language Servers",
+            Self::Task => "Tasks",
+            Self::Collab => "Collaboration",
+            Self::Call => "Calls",
+            Self::Workspace => "Workspace",
+        }
+    }
+
+    pub fn icon(&self) -> IconName {
+        match self {
+            Self::LanguageServer => IconName::Bolt,
+            Self::Task => IconName::Play,
+            Self::Collab => IconName::UserGroup,
+            Self::Call => IconName::AudioOn,
+            Self::Workspace => IconName::Info,
+        }
+    }
+}
+
+/// A notification kept in the [`NotificationHistory`] after its popup is gone.
+pub struct NotificationRecord {
+    pub id: usize,
+    pub source: NotificationSource,
+    pub level: PromptLevel,
+    pub message: SharedString,
+    pub timestamp: DateTime<Local>,
+    /// An action that takes the user back to where the notification came from.
+    pub deep_link: Option<Box<dyn Action>>,
+}
+
+/// The notifications shown in any workspace, newest last, so that they can be
+/// reviewed in the notification center.
+#[derive(Default)]
+pub struct NotificationHistory {
+    records: VecDeque<NotificationRecord>,
+    next_record_id: usize,
+    unread_count: usize,
+}
+
+struct GlobalNotificationHistory(Entity<NotificationHistory>);
+
+impl Global for GlobalNotificationHistory {}
+
+impl NotificationHistory {
+    pub fn init_global(cx: &mut App) {
+        let history = cx.new(|_| Self::default());
+        cx.set_global(GlobalNotificationHistory(history));
+    }
+
+    pub fn global(cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalNotificationHistory>()
+            .map(|history| history.0.clone())
+    }
+
+    pub fn records(&self) -> impl DoubleEndedIterator<Item = &NotificationRecord> {
+        self.records.iter()
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.unread_count
+    }
+
+    pub fn mark_all_read(&mut self, cx: &mut Context<Self>) {
+        if self.unread_count > 0 {
+            self.unread_count = 0;
+            cx.notify();
+        }
+    }
+
+    pub fn remove(&mut self, id: usize, cx: &mut Context<Self>) {
+        self.records.retain(|record| record.id != id);
+        self.unread_count = self.unread_count.min(self.records.len());
+        cx.notify();
+    }
+
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        self.records.clear();
+        self.unread_count = 0;
+        cx.notify();
+    }
+
+    fn push(
+        &mut self,
+        source: NotificationSource,
+        level: PromptLevel,
+        message: SharedString,
+        deep_link: Option<Box<dyn Action>>,
+        max_history: usize,
+        cx: &mut Context<Self>,
+    ) {
+        self.records.push_back(NotificationRecord {
+            id: post_inc(&mut self.next_record_id),
+            source,
+            level,
+            message,
+            timestamp: Local::now(),
+            deep_link,
+        });
+        while self.records.len() > max_history {
+            self.records.pop_front();
+        }
+        self.unread_count = (self.unread_count + 1).min(self.records.len());
+        cx.notify();
+    }
+}
+
+/// Records a notification in the [`NotificationHistory`]. Returns whether it
+/// should also pop up, which isn't the case while its source is muted or
+/// "do not disturb" is on.
+pub fn record_notification(
+    source: NotificationSource,
+    level: PromptLevel,
+    message: impl Into<SharedString>,
+    deep_link: Option<Box<dyn Action>>,
+    cx: &mut App,
+) -> bool {
+    let settings = NotificationCenterSettings::get_global(cx);
+    let is_quiet = settings.muted_sources.contains(&source)
+        || settings.do_not_disturb.is_active_at(Local::now().time());
+    let max_history = settings.max_history;
+    if let Some(history) = NotificationHistory::global(cx) {
+        let message = message.into();
+        history.update(cx, |history, cx| {
+            history.push(source, level, message, deep_link, max_history, cx)
+        });
+    }
+    !is_quiet
+}
+
 pub trait Notification: EventEmitter<DismissEvent> + Focusable + Render {}
 
 impl Workspace {
@@ -106,8 +264,18 @@ impl Workspace {
     where
         E: std::fmt::Debug + std::fmt::Display,
     {
+        let message = format!("Error: {err}");
+        if !record_notification(
+            NotificationSource::Workspace,
+            PromptLevel::Critical,
+            message.clone(),
+            None,
+            cx,
+        ) {
+            return;
+        }
         self.show_notification(workspace_error_notification_id(), cx, |cx| {
-            cx.new(|cx| ErrorMessagePrompt::new(format!("Error: {err}"), cx))
+            cx.new(|cx| ErrorMessagePrompt::new(message, cx))
         });
     }
 
@@ -245,6 +413,10 @@ impl Render for LanguageServerPrompt {
 
         div()
             .id("language_server_prompt_notification")
+            .accessibility(
+                Accessibility::new(AccessibilityRole::Alert)
+                    .label(format!("{}: {}", request.lsp_name, request.message)),
+            )
             .group("language_server_prompt_notification")
             .occlude()
             .w_full()
@@ -343,6 +515,7 @@ impl Render for ErrorMessagePrompt {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         h_flex()
             .id("error_message_prompt_notification")
+            .accessibility(Accessibility::new(AccessibilityRole::Alert).label(self.message.clone()))
             .occlude()
             .elevation_3(cx)
             .items_start()
@@ -408,8 +581,8 @@ pub mod simple_message_notification {
     use std::sync::Arc;
 
     use gpui::{
-        div, AnyElement, DismissEvent, EventEmitter, FocusHandle, Focusable, ParentElement, Render,
-        SharedString, Styled,
+        div, Accessibility, AccessibilityRole, AnyElement, DismissEvent, EventEmitter, FocusHandle,
+        Focusable, ParentElement, Render, SharedString, Styled,
     };
     use ui::prelude::*;
 
@@ -579,7 +752,11 @@ pub mod simple_message_notification {
 
     impl Render for MessageNotification {
         fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+            let mut accessibility = Accessibility::new(AccessibilityRole::Alert);
+            accessibility.label = self.title.clone();
+
             v_flex()
+                .accessibility(accessibility)
                 .occlude()
                 .p_3()
                 .gap_2()
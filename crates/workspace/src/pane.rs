@@ -323,6 +323,7 @@ pub struct Pane {
     pinned_tab_count: usize,
     diagnostics: HashMap<ProjectPath, DiagnosticSeverity>,
     zoom_out_on_close: bool,
+    zen_mode: bool,
     /// If a certain project item wants to get recreated with specific data, it can persist its data before the recreation here.
     pub project_item_restoration_data: HashMap<ProjectItemKind, Box<dyn Any + Send>>,
 }
@@ -374,6 +375,24 @@ pub struct NavigationEntry {
     pub is_preview: bool,
 }
 
+/// Stands in for an item that was open in a previous session, so that navigating to its
+/// history entries opens it again by its path.
+struct RestoredItem(EntityId);
+
+impl WeakItemHandle for RestoredItem {
+    fn id(&self) -> EntityId {
+        self.0
+    }
+
+    fn boxed_clone(&self) -> Box<dyn WeakItemHandle> {
+        Box::new(RestoredItem(self.0))
+    }
+
+    fn upgrade(&self) -> Option<Box<dyn ItemHandle>> {
+        None
+    }
+}
+
 #[derive(Clone)]
 pub struct DraggedTab {
     pub pane: Entity<Pane>,
@@ -530,6 +549,7 @@ impl Pane {
             pinned_tab_count: 0,
             diagnostics: Default::default(),
             zoom_out_on_close: true,
+            zen_mode: false,
             project_item_restoration_data: HashMap::default(),
         }
     }
@@ -2810,6 +2830,19 @@ impl Pane {
         self.zoomed
     }
 
+    /// Hides the tab bar while in zen mode, and lets the pane's items know about it.
+    pub fn set_zen_mode(&mut self, enabled: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.zen_mode = enabled;
+        for item in &self.items {
+            item.set_zen_mode(enabled, window, cx);
+        }
+        cx.notify();
+    }
+
+    pub fn is_zen_mode(&self) -> bool {
+        self.zen_mode
+    }
+
     fn handle_drag_move<T: 'static>(
         &mut self,
         event: &DragMoveEvent<T>,
@@ -3044,7 +3077,7 @@ impl Pane {
         }
         let mut to_pane = cx.entity().clone();
         let mut split_direction = self.drag_split_direction;
-        let paths = paths.paths().to_vec();
+        let mut paths = paths.paths().to_vec();
         let is_remote = self
             .workspace
             .update(cx, |workspace, cx| {
@@ -3083,6 +3116,68 @@ impl Pane {
                         split_direction = None;
                     }
 
+                    // Ask whether to add the folders that aren't in the project yet to it, or to
+                    // open them in a new window.
+                    let mut folders = Vec::new();
+                    for path in &paths {
+                        if fs.is_dir(path).await {
+                            folders.push(path.clone());
+                        }
+                    }
+                    let Ok(new_folders) = workspace.update(cx, |workspace, cx| {
+                        let project = workspace.project().read(cx);
+                        folders
+                            .into_iter()
+                            .filter(|folder| project.find_worktree(folder, cx).is_none())
+                            .collect::<Vec<_>>()
+                    }) else {
+                        return;
+                    };
+                    if !new_folders.is_empty() {
+                        let Ok(answer) = workspace.update_in(cx, |_, window, cx| {
+                            let message = match new_folders.as_slice() {
+                                [folder] => format!(
+                                    "Add {} to the project?",
+                                    folder
+                                        .file_name()
+                                        .unwrap_or(folder.as_os_str())
+                                        .to_string_lossy()
+                                ),
+                                _ => format!("Add {} folders to the project?", new_folders.len()),
+                            };
+                            window.prompt(
+                                PromptLevel::Info,
+                                &message,
+                                None,
+                                &["Add to Project", "Open in New Window", "Cancel"],
+                                cx,
+                            )
+                        }) else {
+                            return;
+                        };
+                        match answer.await {
+                            Ok(0) => {}
+                            Ok(1) => {
+                                workspace
+                                    .update(cx, |workspace, cx| {
+                                        crate::open_paths(
+                                            &new_folders,
+                                            workspace.app_state().clone(),
+                                            OpenOptions {
+                                                open_new_workspace: Some(true),
+                                                ..Default::default()
+                                            },
+                                            cx,
+                                        )
+                                        .detach_and_log_err(cx);
+                                    })
+                                    .ok();
+                                paths.retain(|path| !new_folders.contains(path));
+                            }
+                            _ => return,
+                        }
+                    }
+
                     if let Ok(open_task) = workspace.update_in(cx, |workspace, window, cx| {
                         if let Some(split_direction) = split_direction {
                             to_pane = workspace.split_pane(to_pane, split_direction, window, cx);
@@ -3154,7 +3249,7 @@ impl Render for Pane {
         }
 
         let should_display_tab_bar = self.should_display_tab_bar.clone();
-        let display_tab_bar = should_display_tab_bar(window, cx);
+        let display_tab_bar = !self.zen_mode && should_display_tab_bar(window, cx);
         let Some(project) = self.project.upgrade() else {
             return div().track_focus(&self.focus_handle(cx));
         };
@@ -3467,18 +3562,53 @@ impl NavHistory {
             .chain(borrowed_history.backward_stack.iter())
             .chain(borrowed_history.closed_stack.iter())
             .for_each(|entry| {
-                if let Some(project_and_abs_path) =
-                    borrowed_history.paths_by_item.get(&entry.item.id())
-                {
-                    f(entry, project_and_abs_path.clone());
-                } else if let Some(item) = entry.item.upgrade() {
-                    if let Some(path) = item.project_path(cx) {
-                        f(entry, (path, None));
-                    }
+                if let Some(project_and_abs_path) = borrowed_history.path_for_entry(entry, cx) {
+                    f(entry, project_and_abs_path);
                 }
             })
     }
 
+    /// Calls `f` with the entries that going back navigates through, oldest first.
+    pub fn for_each_backward_entry(
+        &self,
+        cx: &App,
+        mut f: impl FnMut(&NavigationEntry, (ProjectPath, Option<PathBuf>)),
+    ) {
+        let borrowed_history = self.0.lock();
+        for entry in &borrowed_history.backward_stack {
+            if let Some(project_and_abs_path) = borrowed_history.path_for_entry(entry, cx) {
+                f(entry, project_and_abs_path);
+            }
+        }
+    }
+
+    /// Puts entries from a previous session, oldest first, before the ones that going back
+    /// navigates through. Going back to them opens their files again.
+    pub fn restore_backward_entries(
+        &mut self,
+        entries: Vec<(ProjectPath, Option<PathBuf>, Box<dyn Any + Send>)>,
+        cx: &mut App,
+    ) {
+        let mut state = self.0.lock();
+        let mut item_ids = HashMap::default();
+        let capacity = MAX_NAVIGATION_HISTORY_LEN.saturating_sub(state.backward_stack.len());
+        for (project_path, abs_path, data) in entries.into_iter().rev().take(capacity) {
+            let item_id = *item_ids
+                .entry(project_path.clone())
+                .or_insert_with(|| cx.reserve_entity::<()>().entity_id());
+            state
+                .paths_by_item
+                .insert(item_id, (project_path, abs_path));
+            state.backward_stack.push_front(NavigationEntry {
+                item: Arc::new(RestoredItem(item_id)),
+                data: Some(data),
+                timestamp: 0,
+                is_preview: false,
+            });
+        }
+        state.did_update(cx);
+    }
+
     pub fn set_mode(&mut self, mode: NavigationMode) {
         self.0.lock().mode = mode;
     }
@@ -3591,6 +3721,19 @@ impl NavHistory {
 }
 
 impl NavHistoryState {
+    fn path_for_entry(
+        &self,
+        entry: &NavigationEntry,
+        cx: &App,
+    ) -> Option<(ProjectPath, Option<PathBuf>)> {
+        if let Some(project_and_abs_path) = self.paths_by_item.get(&entry.item.id()) {
+            Some(project_and_abs_path.clone())
+        } else {
+            let path = entry.item.upgrade()?.project_path(cx)?;
+            Some((path, None))
+        }
+    }
+
     pub fn did_update(&self, cx: &mut App) {
         if let Some(pane) = self.pane.upgrade() {
             cx.defer(move |cx| {
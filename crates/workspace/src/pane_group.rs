@@ -122,6 +122,47 @@ impl PaneGroup {
         };
     }
 
+    /// Moves the given pane to the far edge of the group in the given direction,
+    /// spanning the full height or width of the group.
+    pub fn move_to_edge(&mut self, pane: &Entity<Pane>, direction: SplitDirection) -> Result<()> {
+        if !self.remove(pane)? {
+            return Ok(());
+        }
+
+        let new_member = Member::Pane(pane.clone());
+        match &mut self.root {
+            Member::Axis(axis) if axis.axis == direction.axis() => {
+                if direction.increasing() {
+                    axis.members.push(new_member);
+                } else {
+                    axis.members.insert(0, new_member);
+                }
+                *axis.flexes.lock() = vec![1.; axis.members.len()];
+            }
+            root => {
+                let old_root = root.clone();
+                let members = if direction.increasing() {
+                    vec![old_root, new_member]
+                } else {
+                    vec![new_member, old_root]
+                };
+                *root = Member::Axis(PaneAxis::new(direction.axis(), members));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rotates the panes that share an axis with the given pane, moving each one
+    /// to the next (or previous) position.
+    pub fn rotate(&mut self, pane: &Entity<Pane>, forward: bool) {
+        match &mut self.root {
+            Member::Pane(_) => {}
+            Member::Axis(axis) => {
+                axis.rotate(pane, forward);
+            }
+        };
+    }
+
     pub fn render(
         &self,
         project: &Entity<Project>,
@@ -631,6 +672,29 @@ impl PaneAxis {
         }
     }
 
+    fn rotate(&mut self, pane: &Entity<Pane>, forward: bool) -> bool {
+        let contains_pane = self
+            .members
+            .iter()
+            .any(|member| matches!(member, Member::Pane(found) if found == pane));
+        if contains_pane {
+            let mut flexes = self.flexes.lock();
+            if forward {
+                self.members.rotate_right(1);
+                flexes.rotate_right(1);
+            } else {
+                self.members.rotate_left(1);
+                flexes.rotate_left(1);
+            }
+            return true;
+        }
+
+        self.members.iter_mut().any(|member| match member {
+            Member::Axis(axis) => axis.rotate(pane, forward),
+            Member::Pane(_) => false,
+        })
+    }
+
     fn bounding_box_for_pane(&self, pane: &Entity<Pane>) -> Option<Bounds<Pixels>> {
         debug_assert!(self.members.len() == self.bounding_boxes.lock().len());
 
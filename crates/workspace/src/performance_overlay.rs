@@ -0,0 +1,266 @@
+use std::{fmt::Write as _, time::Duration};
+
+use gpui::{ClipboardItem, FrameTimings, Hsla, Task};
+use ui::{prelude::*, Tooltip};
+
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+/// The frame time that keeps up with a 60Hz display.
+const FRAME_BUDGET: Duration = Duration::from_micros(16_667);
+const CHART_FRAMES: usize = 60;
+const CHART_HEIGHT: Pixels = px(40.);
+const MAX_VISIBLE_VIEWS: usize = 8;
+const MAX_VISIBLE_SPANS: usize = 8;
+
+/// Shows how long the window's frames take to draw, which views that time is spent on and the
+/// slowest recent spans, to help with reporting performance problems.
+#[derive(Default)]
+pub struct PerformanceOverlay {
+    visible: bool,
+    _refresh_task: Option<Task<()>>,
+}
+
+impl PerformanceOverlay {
+    pub fn toggle(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.visible = !self.visible;
+        window.set_frame_timings_enabled(self.visible);
+        self._refresh_task = self.visible.then(|| {
+            cx.spawn(async move |this, cx| loop {
+                cx.background_executor().timer(REFRESH_INTERVAL).await;
+                if this.update(cx, |_, cx| cx.notify()).is_err() {
+                    break;
+                }
+            })
+        });
+        cx.notify();
+    }
+
+    fn frame_color(duration: Duration, cx: &App) -> Hsla {
+        let status = cx.theme().status();
+        if duration <= FRAME_BUDGET {
+            status.success
+        } else if duration <= FRAME_BUDGET * 2 {
+            status.warning
+        } else {
+            status.error
+        }
+    }
+
+    fn render_frame_chart(frame_timings: &FrameTimings, cx: &App) -> impl IntoElement {
+        // Frames taking twice the budget or longer fill the chart.
+        let scale = CHART_HEIGHT.0 / (FRAME_BUDGET * 2).as_secs_f32();
+        h_flex()
+            .h(CHART_HEIGHT)
+            .items_end()
+            .gap_px()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .children(
+                frame_timings
+                    .frames()
+                    .rev()
+                    .take(CHART_FRAMES)
+                    .rev()
+                    .map(|frame| {
+                        let height = (frame.total.as_secs_f32() * scale).clamp(1., CHART_HEIGHT.0);
+                        div()
+                            .w(px(3.))
+                            .h(px(height))
+                            .bg(Self::frame_color(frame.total, cx))
+                    }),
+            )
+    }
+
+    fn render_section_header(title: &'static str) -> impl IntoElement {
+        Label::new(title)
+            .size(LabelSize::XSmall)
+            .color(Color::Muted)
+    }
+
+    fn render_row(name: SharedString, columns: Vec<String>) -> impl IntoElement {
+        h_flex()
+            .gap_2()
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .child(Label::new(name).size(LabelSize::Small).truncate()),
+            )
+            .children(columns.into_iter().map(|column| {
+                div().w(rems(4.)).flex().justify_end().child(
+                    Label::new(column)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            }))
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.)
+}
+
+/// Returns the duration that the given fraction of durations is shorter than or equal to.
+fn percentile(durations: impl Iterator<Item = Duration>, fraction: f64) -> Duration {
+    let mut durations = durations.collect::<Vec<_>>();
+    if durations.is_empty() {
+        return Duration::ZERO;
+    }
+    durations.sort();
+    let ix = ((durations.len() as f64 * fraction).ceil() as usize).clamp(1, durations.len()) - 1;
+    durations[ix]
+}
+
+/// Summarizes the frame timings as text that can be pasted into a performance report.
+fn report(frame_timings: &FrameTimings) -> String {
+    let frame_times = || frame_timings.frames().map(|frame| frame.total);
+    let mut report = String::new();
+    writeln!(
+        report,
+        "Frames: {} drawn, {} in the last second",
+        frame_timings.frames().len(),
+        frame_timings.frames_per_second()
+    )
+    .ok();
+    writeln!(
+        report,
+        "Frame time: median {}, p95 {}, worst {}",
+        format_duration(percentile(frame_times(), 0.5)),
+        format_duration(percentile(frame_times(), 0.95)),
+        format_duration(frame_times().max().unwrap_or_default())
+    )
+    .ok();
+
+    writeln!(report, "\nViews in the last frame (count, layout, paint):").ok();
+    for view in frame_timings.views() {
+        writeln!(
+            report,
+            "  {} ×{}: {}, {}",
+            view.name,
+            view.count,
+            format_duration(view.layout),
+            format_duration(view.paint)
+        )
+        .ok();
+    }
+
+    writeln!(report, "\nSlowest recent spans:").ok();
+    for span in frame_timings.slowest_spans() {
+        writeln!(
+            report,
+            "  {}: {}",
+            span.name,
+            format_duration(span.duration)
+        )
+        .ok();
+    }
+    report
+}
+
+impl Render for PerformanceOverlay {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(frame_timings) = window.frame_timings().filter(|_| self.visible) else {
+            return div();
+        };
+
+        let frame_times = || frame_timings.frames().map(|frame| frame.total);
+        let last_frame = frame_timings.frames().last().map(|frame| frame.total);
+        let summary = format!(
+            "{} fps · last {} · p95 {}",
+            frame_timings.frames_per_second(),
+            format_duration(last_frame.unwrap_or_default()),
+            format_duration(percentile(frame_times(), 0.95)),
+        );
+        let report = report(frame_timings);
+        let now = std::time::Instant::now();
+
+        div().absolute().top_2().right_2().child(
+            v_flex()
+                .id("performance-overlay")
+                .occlude()
+                .elevation_2(cx)
+                .w(rems(24.))
+                .p_2()
+                .gap_2()
+                .child(
+                    h_flex()
+                        .justify_between()
+                        .child(Label::new("Performance").size(LabelSize::Small))
+                        .child(
+                            h_flex()
+                                .child(
+                                    IconButton::new("copy-performance-report", IconName::Copy)
+                                        .icon_size(IconSize::Small)
+                                        .tooltip(Tooltip::text("Copy Report"))
+                                        .on_click(move |_, _, cx| {
+                                            cx.write_to_clipboard(ClipboardItem::new_string(
+                                                report.clone(),
+                                            ))
+                                        }),
+                                )
+                                .child(
+                                    IconButton::new("close-performance-overlay", IconName::Close)
+                                        .icon_size(IconSize::Small)
+                                        .tooltip(Tooltip::text("Close"))
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.toggle(window, cx)
+                                        })),
+                                ),
+                        ),
+                )
+                .child(Label::new(summary).size(LabelSize::Small).color(
+                    last_frame.map_or(Color::Muted, |frame| {
+                        Color::Custom(Self::frame_color(frame, cx))
+                    }),
+                ))
+                .child(Self::render_frame_chart(frame_timings, cx))
+                .child(
+                    v_flex()
+                        .child(Self::render_section_header("Views in the Last Frame"))
+                        .children(frame_timings.views().iter().take(MAX_VISIBLE_VIEWS).map(
+                            |view| {
+                                Self::render_row(
+                                    format!("{} ×{}", view.name, view.count).into(),
+                                    vec![format_duration(view.layout), format_duration(view.paint)],
+                                )
+                            },
+                        )),
+                )
+                .child(
+                    v_flex()
+                        .child(Self::render_section_header("Slowest Recent Spans"))
+                        .children(
+                            frame_timings
+                                .slowest_spans()
+                                .iter()
+                                .take(MAX_VISIBLE_SPANS)
+                                .map(|span| {
+                                    let age = now.saturating_duration_since(span.recorded_at);
+                                    Self::render_row(
+                                        span.name.clone(),
+                                        vec![
+                                            format_duration(span.duration),
+                                            format!("{}s ago", age.as_secs()),
+                                        ],
+                                    )
+                                }),
+                        ),
+                ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::percentile;
+
+    #[test]
+    fn test_percentile() {
+        let durations = || (1..=20).map(Duration::from_millis);
+        assert_eq!(percentile(durations(), 0.5), Duration::from_millis(10));
+        assert_eq!(percentile(durations(), 0.95), Duration::from_millis(19));
+        assert_eq!(percentile(durations(), 1.), Duration::from_millis(20));
+        assert_eq!(percentile(std::iter::empty(), 0.5), Duration::ZERO);
+    }
+}
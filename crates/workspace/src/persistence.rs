@@ -526,6 +526,38 @@ define_connection! {
     ),
     sql!(
         ALTER TABLE breakpoints DROP COLUMN kind
+    ),
+    sql!(
+        CREATE TABLE window_frames (
+            workspace_id INTEGER NOT NULL,
+            display BLOB NOT NULL,
+            window_state TEXT NOT NULL,
+            window_x REAL,
+            window_y REAL,
+            window_width REAL,
+            window_height REAL,
+            PRIMARY KEY (workspace_id, display),
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        );
+    ),
+    sql!(
+        CREATE TABLE workspace_files (
+            workspace_id INTEGER PRIMARY KEY,
+            path TEXT NOT NULL,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        );
+    ),
+    sql!(
+        CREATE TABLE pinned_workspaces (
+            workspace_id INTEGER PRIMARY KEY,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        );
     )
     ];
 }
@@ -984,6 +1016,9 @@ impl WorkspaceDb {
     query! {
         pub async fn delete_workspace_by_id(id: WorkspaceId) -> Result<()> {
             DELETE FROM toolchains WHERE workspace_id = ?1;
+            DELETE FROM window_frames WHERE workspace_id = ?1;
+            DELETE FROM workspace_files WHERE workspace_id = ?1;
+            DELETE FROM pinned_workspaces WHERE workspace_id = ?1;
             DELETE FROM workspaces
             WHERE workspace_id IS ?
         }
@@ -1290,7 +1325,77 @@ impl WorkspaceDb {
                 window_width = ?5,
                 window_height = ?6,
                 display = ?7
-            WHERE workspace_id = ?1
+            WHERE workspace_id = ?1;
+            INSERT OR REPLACE INTO window_frames(workspace_id, display, window_state, window_x, window_y, window_width, window_height)
+            VALUES (?1, ?7, ?2, ?3, ?4, ?5, ?6)
+        }
+    }
+
+    query! {
+        /// Returns the bounds that the workspace's window last had on each of the displays it
+        /// was on.
+        pub(crate) fn window_frames(workspace_id: WorkspaceId) -> Result<Vec<(Uuid, SerializedWindowBounds)>> {
+            SELECT display, window_state, window_x, window_y, window_width, window_height
+            FROM window_frames
+            WHERE workspace_id = ?
+        }
+    }
+
+    query! {
+        /// Remembers the workspace file that the workspace was opened from, or saved to.
+        pub(crate) async fn set_workspace_file(workspace_id: WorkspaceId, path: PathBuf) -> Result<()> {
+            INSERT OR REPLACE INTO workspace_files(workspace_id, path)
+            VALUES (?1, ?2)
+        }
+    }
+
+    query! {
+        pub(crate) fn workspace_file(workspace_id: WorkspaceId) -> Result<Option<PathBuf>> {
+            SELECT path
+            FROM workspace_files
+            WHERE workspace_id = ?
+        }
+    }
+
+    query! {
+        /// Returns the workspace files that workspaces were opened from, or saved to.
+        pub fn workspace_files() -> Result<Vec<(WorkspaceId, PathBuf)>> {
+            SELECT workspace_id, path
+            FROM workspace_files
+        }
+    }
+
+    query! {
+        /// Pins the workspace to the top of the recent projects.
+        pub async fn pin_workspace(workspace_id: WorkspaceId) -> Result<()> {
+            INSERT OR IGNORE INTO pinned_workspaces(workspace_id)
+            VALUES (?)
+        }
+    }
+
+    query! {
+        pub async fn unpin_workspace(workspace_id: WorkspaceId) -> Result<()> {
+            DELETE FROM pinned_workspaces
+            WHERE workspace_id = ?
+        }
+    }
+
+    query! {
+        pub fn pinned_workspaces() -> Result<Vec<WorkspaceId>> {
+            SELECT workspace_id
+            FROM pinned_workspaces
+        }
+    }
+
+    query! {
+        /// Returns the workspaces that aren't pinned and haven't been used for the given number of
+        /// days.
+        pub fn stale_workspaces(days: u64) -> Result<Vec<WorkspaceId>> {
+            SELECT workspace_id
+            FROM workspaces
+            WHERE julianday(timestamp) < julianday() - ?
+                AND workspace_id NOT IN (SELECT workspace_id FROM pinned_workspaces)
+            ORDER BY workspace_id
         }
     }
 
@@ -1530,6 +1635,131 @@ mod tests {
         assert_eq!(test_text_1, "test-text-1");
     }
 
+    #[gpui::test]
+    async fn test_window_frames() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_window_frames").await);
+        let workspace = SerializedWorkspace {
+            id: WorkspaceId(1),
+            location: SerializedWorkspaceLocation::from_local_paths(["/tmp"]),
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            breakpoints: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            session_id: None,
+            window_id: Some(1),
+        };
+        db.save_workspace(workspace).await;
+
+        let laptop_display = Uuid::from_u128(1);
+        let external_display = Uuid::from_u128(2);
+        let laptop_bounds = SerializedWindowBounds(WindowBounds::Maximized(Bounds {
+            origin: point(px(0.), px(0.)),
+            size: size(px(1440.), px(900.)),
+        }));
+        let external_bounds = SerializedWindowBounds(WindowBounds::Windowed(Bounds {
+            origin: point(px(100.), px(50.)),
+            size: size(px(1200.), px(800.)),
+        }));
+        db.set_window_open_status(WorkspaceId(1), laptop_bounds, laptop_display)
+            .await
+            .unwrap();
+        db.set_window_open_status(WorkspaceId(1), external_bounds, external_display)
+            .await
+            .unwrap();
+
+        // The workspace remembers the display its window was on last, along with a frame for
+        // each display.
+        let workspace = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(workspace.display, Some(external_display));
+        assert_eq!(workspace.window_bounds, Some(external_bounds));
+        let mut frames = db.window_frames(WorkspaceId(1)).unwrap();
+        frames.sort_by_key(|(display, _)| *display);
+        assert_eq!(
+            frames,
+            [
+                (laptop_display, laptop_bounds),
+                (external_display, external_bounds)
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_workspace_files() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_workspace_files").await);
+        let workspace = SerializedWorkspace {
+            id: WorkspaceId(1),
+            location: SerializedWorkspaceLocation::from_local_paths(["/code/app", "/code/lib"]),
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            breakpoints: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            session_id: None,
+            window_id: None,
+        };
+        db.save_workspace(workspace).await;
+
+        let path = PathBuf::from("/code/app/app.zed-workspace");
+        db.set_workspace_file(WorkspaceId(1), path.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            db.workspace_file(WorkspaceId(1)).unwrap(),
+            Some(path.clone())
+        );
+        assert_eq!(db.workspace_files().unwrap(), [(WorkspaceId(1), path)]);
+
+        db.delete_workspace_by_id(WorkspaceId(1)).await.unwrap();
+        assert!(db.workspace_files().unwrap().is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_pinned_workspaces() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_pinned_workspaces").await);
+        for (id, path) in [(1, "/code/app"), (2, "/code/lib")] {
+            db.save_workspace(SerializedWorkspace {
+                id: WorkspaceId(id),
+                location: SerializedWorkspaceLocation::from_local_paths([path]),
+                center_group: Default::default(),
+                window_bounds: Default::default(),
+                breakpoints: Default::default(),
+                display: Default::default(),
+                docks: Default::default(),
+                centered_layout: false,
+                session_id: None,
+                window_id: None,
+            })
+            .await;
+        }
+        db.write(|conn| {
+            conn.exec(sql!(UPDATE workspaces SET timestamp = datetime(julianday() - 10)))
+                .unwrap()()
+            .unwrap();
+        })
+        .await;
+
+        db.pin_workspace(WorkspaceId(2)).await.unwrap();
+        assert_eq!(db.pinned_workspaces().unwrap(), [WorkspaceId(2)]);
+        assert_eq!(db.stale_workspaces(5).unwrap(), [WorkspaceId(1)]);
+        assert!(db.stale_workspaces(20).unwrap().is_empty());
+
+        db.unpin_workspace(WorkspaceId(2)).await.unwrap();
+        assert!(db.pinned_workspaces().unwrap().is_empty());
+        assert_eq!(
+            db.stale_workspaces(5).unwrap(),
+            [WorkspaceId(1), WorkspaceId(2)]
+        );
+    }
+
     #[gpui::test]
     async fn test_workspace_id_stability() {
         env_logger::try_init().ok();
@@ -373,15 +373,22 @@ impl SerializedPaneGroup {
                 let mut current_active_pane = None;
                 let mut members = Vec::new();
                 let mut items = Vec::new();
-                for child in children {
-                    if let Some((new_member, active_pane, new_items)) = child
-                        .deserialize(project, workspace_id, workspace.clone(), cx)
-                        .await
-                    {
-                        members.push(new_member);
-                        items.extend(new_items);
-                        current_active_pane = current_active_pane.or(active_pane);
+                // Deserialize the children concurrently, so that loading the items of one pane
+                // doesn't wait for those of the panes before it.
+                let children = futures::future::join_all(children.into_iter().map(|child| {
+                    let workspace = workspace.clone();
+                    let mut cx = cx.clone();
+                    async move {
+                        child
+                            .deserialize(project, workspace_id, workspace, &mut cx)
+                            .await
                     }
+                }))
+                .await;
+                for (new_member, active_pane, new_items) in children.into_iter().flatten() {
+                    members.push(new_member);
+                    items.extend(new_items);
+                    current_active_pane = current_active_pane.or(active_pane);
                 }
 
                 if members.is_empty() {
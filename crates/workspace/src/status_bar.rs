@@ -1,11 +1,12 @@
-use crate::{ItemHandle, Pane};
+use crate::{workspace_settings::StatusBarSettings, ItemHandle, Pane};
 use gpui::{
-    AnyView, App, Context, Decorations, Entity, IntoElement, ParentElement, Render, Styled,
-    Subscription, Window,
+    Action, AnyElement, AnyView, App, Context, Decorations, Entity, Global, IntoElement,
+    ParentElement, Render, Styled, Subscription, Window,
 };
+use settings::{Settings, SettingsStore};
 use std::any::TypeId;
 use theme::CLIENT_SIDE_DECORATION_ROUNDING;
-use ui::{h_flex, prelude::*};
+use ui::{h_flex, prelude::*, Tooltip};
 use util::ResultExt;
 
 pub trait StatusItemView: Render {
@@ -15,6 +16,12 @@ pub trait StatusItemView: Render {
         window: &mut Window,
         cx: &mut Context<Self>,
     );
+
+    /// The name under which this item can be reordered or hidden with the
+    /// `status_bar` setting. Items without a name always keep their place.
+    fn segment_name(&self, _cx: &App) -> Option<SharedString> {
+        None
+    }
 }
 
 trait StatusItemViewHandle: Send {
@@ -26,6 +33,73 @@ trait StatusItemViewHandle: Send {
         cx: &mut App,
     );
     fn item_type(&self) -> TypeId;
+    fn segment_name(&self, cx: &App) -> Option<SharedString>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatusItemSide {
+    Left,
+    Right,
+}
+
+/// A status bar segment contributed by a subsystem that doesn't own a view,
+/// such as the number of running tasks.
+pub struct StatusItem {
+    pub name: SharedString,
+    pub label: SharedString,
+    pub icon: Option<IconName>,
+    pub tooltip: Option<SharedString>,
+    pub action: Option<Box<dyn Action>>,
+    pub side: StatusItemSide,
+}
+
+/// The segments shown in every status bar in addition to the views each
+/// workspace adds itself.
+#[derive(Default)]
+pub struct StatusItemRegistry {
+    items: Vec<StatusItem>,
+}
+
+struct GlobalStatusItemRegistry(Entity<StatusItemRegistry>);
+
+impl Global for GlobalStatusItemRegistry {}
+
+impl StatusItemRegistry {
+    pub fn init_global(cx: &mut App) {
+        let registry = cx.new(|_| Self::default());
+        cx.set_global(GlobalStatusItemRegistry(registry));
+    }
+
+    pub fn global(cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalStatusItemRegistry>()
+            .map(|registry| registry.0.clone())
+    }
+
+    /// Adds the item, replacing any previously registered item with the same name.
+    pub fn set_item(&mut self, item: StatusItem, cx: &mut Context<Self>) {
+        if let Some(existing) = self
+            .items
+            .iter_mut()
+            .find(|existing| existing.name == item.name)
+        {
+            *existing = item;
+        } else {
+            self.items.push(item);
+        }
+        cx.notify();
+    }
+
+    pub fn remove_item(&mut self, name: &str, cx: &mut Context<Self>) {
+        let len = self.items.len();
+        self.items.retain(|item| item.name != name);
+        if self.items.len() != len {
+            cx.notify();
+        }
+    }
+
+    pub fn items(&self) -> &[StatusItem] {
+        &self.items
+    }
 }
 
 pub struct StatusBar {
@@ -33,6 +107,7 @@ pub struct StatusBar {
     right_items: Vec<Box<dyn StatusItemViewHandle>>,
     active_pane: Entity<Pane>,
     _observe_active_pane: Subscription,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl Render for StatusBar {
@@ -65,17 +140,105 @@ impl Render for StatusBar {
 
 impl StatusBar {
     fn render_left_tools(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let items = self.left_items.iter();
         h_flex()
             .gap(DynamicSpacing::Base04.rems(cx))
             .overflow_x_hidden()
-            .children(self.left_items.iter().map(|item| item.to_any()))
+            .children(self.arranged_segments(items, StatusItemSide::Left, cx))
     }
 
     fn render_right_tools(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let items = self.right_items.iter().rev();
         h_flex()
             .gap(DynamicSpacing::Base04.rems(cx))
-            .children(self.right_items.iter().rev().map(|item| item.to_any()))
+            .children(self.arranged_segments(items, StatusItemSide::Right, cx))
     }
+
+    /// Returns the segments of one side of the status bar from left to right,
+    /// ordered and filtered according to the `status_bar` setting.
+    fn arranged_segments<'a>(
+        &self,
+        items: impl Iterator<Item = &'a Box<dyn StatusItemViewHandle>>,
+        side: StatusItemSide,
+        cx: &App,
+    ) -> Vec<AnyElement> {
+        let mut names = Vec::new();
+        let mut segments = Vec::new();
+        for item in items {
+            names.push(item.segment_name(cx));
+            segments.push(Some(item.to_any().into_any_element()));
+        }
+        if let Some(registry) = StatusItemRegistry::global(cx) {
+            for item in registry.read(cx).items() {
+                if item.side == side {
+                    names.push(Some(item.name.clone()));
+                    segments.push(Some(render_status_item(item)));
+                }
+            }
+        }
+
+        let settings = StatusBarSettings::get_global(cx);
+        let order = match side {
+            StatusItemSide::Left => &settings.left,
+            StatusItemSide::Right => &settings.right,
+        };
+        arrange_segments(&names, order, &settings.hidden)
+            .into_iter()
+            .filter_map(|ix| segments[ix].take())
+            .collect()
+    }
+}
+
+fn render_status_item(item: &StatusItem) -> AnyElement {
+    let action = item.action.as_ref().map(|action| action.boxed_clone());
+    Button::new(ElementId::Name(item.name.clone()), item.label.clone())
+        .label_size(LabelSize::Small)
+        .when_some(item.icon, |button, icon| {
+            button
+                .icon(icon)
+                .icon_size(IconSize::Small)
+                .icon_color(Color::Muted)
+                .icon_position(IconPosition::Start)
+        })
+        .when_some(item.tooltip.clone(), |button, tooltip| {
+            button.tooltip(Tooltip::text(tooltip))
+        })
+        .when_some(action, |button, action| {
+            button.on_click(move |_, window, cx| window.dispatch_action(action.boxed_clone(), cx))
+        })
+        .into_any_element()
+}
+
+/// Returns the indices of the segments to show, in display order. Segments
+/// listed in `order` come first, followed by the remaining ones in their
+/// default order, while `hidden` ones are left out.
+fn arrange_segments(
+    names: &[Option<SharedString>],
+    order: &[String],
+    hidden: &[String],
+) -> Vec<usize> {
+    let is_hidden = |name: &Option<SharedString>| {
+        name.as_ref().map_or(false, |name| {
+            hidden.iter().any(|hidden| hidden == name.as_ref())
+        })
+    };
+    let mut arranged = Vec::with_capacity(names.len());
+    for name in order {
+        if let Some(ix) = names
+            .iter()
+            .position(|candidate| candidate.as_deref() == Some(name.as_str()))
+        {
+            if !is_hidden(&names[ix]) && !arranged.contains(&ix) {
+                arranged.push(ix);
+            }
+        }
+    }
+    for (ix, name) in names.iter().enumerate() {
+        if !is_hidden(name) && !arranged.contains(&ix) {
+            arranged.push(ix);
+        }
+    }
+    arranged
 }
 
 impl StatusBar {
@@ -87,7 +250,14 @@ impl StatusBar {
             _observe_active_pane: cx.observe_in(active_pane, window, |this, _, window, cx| {
                 this.update_active_pane_item(window, cx)
             }),
+            _subscriptions: Vec::new(),
         };
+        this._subscriptions
+            .push(cx.observe_global::<SettingsStore>(|_, cx| cx.notify()));
+        if let Some(registry) = StatusItemRegistry::global(cx) {
+            this._subscriptions
+                .push(cx.observe(&registry, |_, _, cx| cx.notify()));
+        }
         this.update_active_pane_item(window, cx);
         this
     }
@@ -212,6 +382,10 @@ impl<T: StatusItemView> StatusItemViewHandle for Entity<T> {
     fn item_type(&self) -> TypeId {
         TypeId::of::<T>()
     }
+
+    fn segment_name(&self, cx: &App) -> Option<SharedString> {
+        self.read(cx).segment_name(cx)
+    }
 }
 
 impl From<&dyn StatusItemViewHandle> for AnyView {
@@ -219,3 +393,38 @@ impl From<&dyn StatusItemViewHandle> for AnyView {
         val.to_any().clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrange_segments() {
+        let names = [
+            Some("diagnostics".into()),
+            None,
+            Some("language".into()),
+            Some("cursor_position".into()),
+        ];
+        let strings = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(arrange_segments(&names, &[], &[]), vec![0, 1, 2, 3]);
+        assert_eq!(
+            arrange_segments(&names, &strings(&["cursor_position", "unknown"]), &[]),
+            vec![3, 0, 1, 2]
+        );
+        assert_eq!(
+            arrange_segments(
+                &names,
+                &strings(&["language"]),
+                &strings(&["diagnostics", "language"])
+            ),
+            vec![1, 3]
+        );
+    }
+}
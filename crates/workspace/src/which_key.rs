@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use gpui::{Subscription, Task};
+use settings::Settings;
+use ui::{prelude::*, text_for_keystrokes};
+
+use crate::WorkspaceSettings;
+
+/// How long a multi-stroke key binding has to be pending before its continuations are shown.
+const SHOW_DELAY: Duration = Duration::from_millis(300);
+const MAX_VISIBLE_CONTINUATIONS: usize = 24;
+
+struct Continuation {
+    keystrokes: SharedString,
+    label: SharedString,
+}
+
+/// Shows the key bindings that can complete the keystrokes typed so far, while a multi-stroke key
+/// binding is pending.
+pub struct WhichKey {
+    pending_keystrokes: SharedString,
+    continuations: Vec<Continuation>,
+    visible: bool,
+    show_task: Option<Task<()>>,
+    _subscription: Subscription,
+}
+
+impl WhichKey {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let subscription = cx.observe_pending_input(window, |this: &mut Self, window, cx| {
+            this.pending_input_changed(window, cx);
+        });
+
+        Self {
+            pending_keystrokes: SharedString::default(),
+            continuations: Vec::new(),
+            visible: false,
+            show_task: None,
+            _subscription: subscription,
+        }
+    }
+
+    fn pending_input_changed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let pending_keystrokes = window
+            .pending_input_keystrokes()
+            .filter(|_| WorkspaceSettings::get_global(cx).show_which_key)
+            .map(|keystrokes| keystrokes.to_vec());
+        let Some(pending_keystrokes) = pending_keystrokes else {
+            self.hide(cx);
+            return;
+        };
+
+        self.pending_keystrokes = text_for_keystrokes(&pending_keystrokes, cx).into();
+        self.continuations = window
+            .pending_input_continuations(cx)
+            .into_iter()
+            .map(|binding| Continuation {
+                keystrokes: text_for_keystrokes(
+                    &binding.keystrokes()[pending_keystrokes.len()..],
+                    cx,
+                )
+                .into(),
+                label: action_label(binding.action().name()).into(),
+            })
+            .collect();
+        self.continuations
+            .sort_by(|a, b| a.keystrokes.cmp(&b.keystrokes));
+
+        if self.continuations.is_empty() {
+            self.hide(cx);
+        } else if self.visible {
+            cx.notify();
+        } else if self.show_task.is_none() {
+            self.show_task = Some(cx.spawn(async move |this, cx| {
+                cx.background_executor().timer(SHOW_DELAY).await;
+                this.update(cx, |this, cx| {
+                    this.visible = true;
+                    cx.notify();
+                })
+                .ok();
+            }));
+        }
+    }
+
+    fn hide(&mut self, cx: &mut Context<Self>) {
+        self.show_task = None;
+        self.continuations.clear();
+        if self.visible {
+            self.visible = false;
+            cx.notify();
+        }
+    }
+}
+
+/// Turns an action name like `editor::GoToDefinition` into `go to definition`.
+fn action_label(name: &str) -> String {
+    let name = name.rsplit("::").next().unwrap_or(name);
+    let mut label = String::with_capacity(name.len() + 4);
+    for char in name.chars() {
+        if char.is_uppercase() {
+            if !label.is_empty() {
+                label.push(' ');
+            }
+            label.extend(char.to_lowercase());
+        } else if char == '_' {
+            label.push(' ');
+        } else {
+            label.push(char);
+        }
+    }
+    label
+}
+
+impl Render for WhichKey {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if !self.visible || self.continuations.is_empty() {
+            return div();
+        }
+
+        let hidden_count = self
+            .continuations
+            .len()
+            .saturating_sub(MAX_VISIBLE_CONTINUATIONS);
+
+        div().absolute().bottom_8().right_4().child(
+            v_flex()
+                .id("which-key")
+                .occlude()
+                .elevation_2(cx)
+                .p_2()
+                .gap_1()
+                .max_w(rems(32.))
+                .child(
+                    Label::new(self.pending_keystrokes.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .children(
+                    self.continuations
+                        .iter()
+                        .take(MAX_VISIBLE_CONTINUATIONS)
+                        .map(|continuation| {
+                            h_flex()
+                                .gap_3()
+                                .child(
+                                    div().min_w(rems(4.)).child(
+                                        Label::new(continuation.keystrokes.clone())
+                                            .size(LabelSize::Small)
+                                            .color(Color::Accent),
+                                    ),
+                                )
+                                .child(
+                                    Label::new(continuation.label.clone())
+                                        .size(LabelSize::Small)
+                                        .truncate(),
+                                )
+                        }),
+                )
+                .when(hidden_count > 0, |this| {
+                    this.child(
+                        Label::new(format!("{hidden_count} more…"))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::action_label;
+
+    #[test]
+    fn test_action_label() {
+        assert_eq!(action_label("editor::GoToDefinition"), "go to definition");
+        assert_eq!(action_label("workspace::SplitLeft"), "split left");
+        assert_eq!(action_label("go_to_line::Toggle"), "toggle");
+    }
+}
@@ -4,6 +4,7 @@ mod modal_layer;
 pub mod notifications;
 pub mod pane;
 pub mod pane_group;
+mod performance_overlay;
 mod persistence;
 pub mod searchable;
 pub mod shared_screen;
@@ -12,10 +13,14 @@ pub mod tasks;
 mod theme_preview;
 mod toast_layer;
 mod toolbar;
+mod which_key;
+mod workspace_file;
 mod workspace_settings;
 
 use dap::DapRegistry;
+use performance_overlay::PerformanceOverlay;
 pub use toast_layer::{RunAction, ToastAction, ToastLayer, ToastView};
+use which_key::WhichKey;
 
 use anyhow::{anyhow, Context as _, Result};
 use call::{call_settings::CallSettings, ActiveCall};
@@ -39,9 +44,9 @@ use gpui::{
     action_as, actions, canvas, impl_action_as, impl_actions, point, relative, size,
     transparent_black, Action, AnyView, AnyWeakView, App, AsyncApp, AsyncWindowContext, Bounds,
     Context, CursorStyle, Decorations, DragMoveEvent, Entity, EntityId, EventEmitter, FocusHandle,
-    Focusable, Global, Hsla, KeyContext, Keystroke, ManagedView, MouseButton, PathPromptOptions,
-    Point, PromptLevel, Render, ResizeEdge, Size, Stateful, Subscription, Task, Tiling, WeakEntity,
-    WindowBounds, WindowHandle, WindowId, WindowOptions,
+    Focusable, Global, Hsla, KeyContext, Keystroke, ManagedView, MouseButton, MouseUpEvent,
+    PathPromptOptions, Point, PromptLevel, Render, ResizeEdge, Size, Stateful, Subscription, Task,
+    Tiling, WeakEntity, WindowBounds, WindowHandle, WindowId, WindowOptions,
 };
 pub use item::{
     FollowableItem, FollowableItemHandle, Item, ItemHandle, ItemSettings, PreviewTabsSettings,
@@ -52,18 +57,16 @@ use language::{LanguageRegistry, Rope};
 pub use modal_layer::*;
 use node_runtime::NodeRuntime;
 use notifications::{
-    simple_message_notification::MessageNotification, DetachAndPromptErr, Notifications,
+    simple_message_notification::MessageNotification, DetachAndPromptErr, NotificationSource,
+    Notifications,
 };
 pub use pane::*;
 pub use pane_group::*;
+use persistence::{model::SerializedWorkspace, SerializedWindowBounds, DB};
 pub use persistence::{
-    model::{ItemId, LocalPaths, SerializedWorkspaceLocation},
+    model::{ItemId, LocalPaths, SerializedSshProject, SerializedWorkspaceLocation},
     WorkspaceDb, DB as WORKSPACE_DB,
 };
-use persistence::{
-    model::{SerializedSshProject, SerializedWorkspace},
-    SerializedWindowBounds, DB,
-};
 use postage::stream::Stream;
 use project::{
     debugger::breakpoint_store::BreakpointStoreEvent, DirectoryLister, Project, ProjectEntryId,
@@ -73,14 +76,14 @@ use remote::{ssh_session::ConnectionIdentifier, SshClientDelegate, SshConnection
 use schemars::JsonSchema;
 use serde::Deserialize;
 use session::AppSession;
-use settings::Settings;
+use settings::{Settings, SettingsLocation, SettingsStore};
 use shared_screen::SharedScreen;
 use sqlez::{
     bindable::{Bind, Column, StaticColumnCount},
     statement::Statement,
 };
 use status_bar::StatusBar;
-pub use status_bar::StatusItemView;
+pub use status_bar::{StatusItem, StatusItemRegistry, StatusItemSide, StatusItemView};
 use std::{
     any::TypeId,
     borrow::Cow,
@@ -101,8 +104,10 @@ pub use ui;
 use ui::prelude::*;
 use util::{paths::SanitizedPath, serde::default_true, ResultExt, TryFutureExt};
 use uuid::Uuid;
+pub use workspace_file::{is_workspace_file, WorkspaceFile, WORKSPACE_FILE_EXTENSION};
 pub use workspace_settings::{
-    AutosaveSetting, RestoreOnStartupBehavior, TabBarSettings, WorkspaceSettings,
+    AutosaveSetting, DoNotDisturbSettings, NotificationCenterSettings, RestoreOnStartupBehavior,
+    StatusBarSettings, TabBarSettings, WorkspaceSettings,
 };
 
 use crate::notifications::NotificationId;
@@ -112,6 +117,8 @@ use crate::persistence::{
 };
 
 pub const SERIALIZATION_THROTTLE_TIME: Duration = Duration::from_millis(200);
+/// How long after being added deferred panels are loaded if nothing needed them sooner.
+const DEFERRED_PANEL_LOAD_DELAY: Duration = Duration::from_secs(2);
 
 static ZED_WINDOW_SIZE: LazyLock<Option<Size<Pixels>>> = LazyLock::new(|| {
     env::var("ZED_WINDOW_SIZE")
@@ -175,11 +182,13 @@ actions!(
         ReloadActiveItem,
         SaveAs,
         SaveWithoutFormat,
+        SaveWorkspaceAs,
         ShutdownDebugAdapters,
         ToggleBottomDock,
         ToggleCenteredLayout,
         ToggleLeftDock,
         ToggleRightDock,
+        ToggleZenMode,
         ToggleZoom,
         Unfollow,
         Welcome,
@@ -238,6 +247,14 @@ pub struct CloseInactiveTabsAndPanes {
 #[derive(Clone, Deserialize, PartialEq, JsonSchema)]
 pub struct SendKeystrokes(pub String);
 
+/// Activates the settings profile with the given name in the current window, or deactivates the
+/// active profile if no name is given.
+#[derive(Clone, PartialEq, Debug, Deserialize, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ActivateSettingsProfile {
+    pub name: Option<String>,
+}
+
 #[derive(Clone, Deserialize, PartialEq, Default, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Reload {
@@ -258,6 +275,7 @@ impl_actions!(
     workspace,
     [
         ActivatePane,
+        ActivateSettingsProfile,
         CloseAllItemsAndPanes,
         CloseInactiveTabsAndPanes,
         MoveItemToPane,
@@ -281,6 +299,12 @@ actions!(
         SwapPaneRight,
         SwapPaneUp,
         SwapPaneDown,
+        MovePaneLeft,
+        MovePaneRight,
+        MovePaneUp,
+        MovePaneDown,
+        RotatePanesForward,
+        RotatePanesBackward,
     ]
 );
 
@@ -368,6 +392,8 @@ pub fn init_settings(cx: &mut App) {
     ItemSettings::register(cx);
     PreviewTabsSettings::register(cx);
     TabBarSettings::register(cx);
+    NotificationCenterSettings::register(cx);
+    StatusBarSettings::register(cx);
 }
 
 fn prompt_and_open_paths(app_state: Arc<AppState>, options: PathPromptOptions, cx: &mut App) {
@@ -407,6 +433,8 @@ pub fn init(app_state: Arc<AppState>, cx: &mut App) {
     component::init();
     theme_preview::init(cx);
     toast_layer::init(cx);
+    notifications::NotificationHistory::init_global(cx);
+    StatusItemRegistry::init_global(cx);
 
     cx.on_action(Workspace::close_global);
     cx.on_action(reload);
@@ -462,28 +490,58 @@ type WorkspaceItemBuilder =
 
 impl Global for ProjectItemOpeners {}
 
+/// Openers for files that the [ProjectItemOpeners] failed to open because their
+/// contents can't be decoded as text.
+#[derive(Clone, Default, Deref, DerefMut)]
+struct BinaryFileOpeners(Vec<ProjectItemOpener>);
+
+impl Global for BinaryFileOpeners {}
+
 /// Registers a [ProjectItem] for the app. When opening a file, all the registered
 /// items will get a chance to open the file, starting from the project item that
 /// was added last.
 pub fn register_project_item<I: ProjectItem>(cx: &mut App) {
     let builders = cx.default_global::<ProjectItemOpeners>();
-    builders.push(|project, project_path, window, cx| {
-        let project_item = <I::Item as project::ProjectItem>::try_open(project, project_path, cx)?;
-        let project = project.clone();
-        Some(window.spawn(cx, async move |cx| {
-            let project_item = project_item.await?;
-            let project_entry_id: Option<ProjectEntryId> =
-                project_item.read_with(cx, project::ProjectItem::entry_id)?;
-            let build_workspace_item = Box::new(
-                |pane: &mut Pane, window: &mut Window, cx: &mut Context<Pane>| {
-                    Box::new(
-                        cx.new(|cx| I::for_project_item(project, pane, project_item, window, cx)),
-                    ) as Box<dyn ItemHandle>
-                },
-            ) as Box<_>;
-            Ok((project_entry_id, build_workspace_item))
-        }))
-    });
+    builders.push(open_project_item::<I>);
+}
+
+/// Registers a [ProjectItem] that opens the files that couldn't be opened as text,
+/// such as executables and other binary files.
+pub fn register_binary_project_item<I: ProjectItem>(cx: &mut App) {
+    let builders = cx.default_global::<BinaryFileOpeners>();
+    builders.push(open_project_item::<I>);
+}
+
+fn open_project_item<I: ProjectItem>(
+    project: &Entity<Project>,
+    project_path: &ProjectPath,
+    window: &mut Window,
+    cx: &mut App,
+) -> Option<Task<Result<(Option<ProjectEntryId>, WorkspaceItemBuilder)>>> {
+    let project_item = <I::Item as project::ProjectItem>::try_open(project, project_path, cx)?;
+    let project = project.clone();
+    Some(window.spawn(cx, async move |cx| {
+        let project_item = project_item.await?;
+        let project_entry_id: Option<ProjectEntryId> =
+            project_item.read_with(cx, project::ProjectItem::entry_id)?;
+        let build_workspace_item = Box::new(
+            |pane: &mut Pane, window: &mut Window, cx: &mut Context<Pane>| {
+                Box::new(cx.new(|cx| I::for_project_item(project, pane, project_item, window, cx)))
+                    as Box<dyn ItemHandle>
+            },
+        ) as Box<_>;
+        Ok((project_entry_id, build_workspace_item))
+    }))
+}
+
+/// Whether a file failed to open because its contents can't be decoded as text.
+fn is_binary_file_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause.downcast_ref::<std::string::FromUtf8Error>().is_some()
+            || cause
+                .downcast_ref::<std::io::Error>()
+                .is_some_and(|error| error.kind() == std::io::ErrorKind::InvalidData)
+    })
 }
 
 #[derive(Default)]
@@ -824,6 +882,17 @@ type PromptForOpenPath = Box<
     ) -> oneshot::Receiver<Option<Vec<PathBuf>>>,
 >;
 
+/// A panel whose loading was put off until it's first needed, see [`Workspace::add_deferred_panel`].
+struct DeferredPanel {
+    persistent_name: &'static str,
+    load: Box<dyn FnOnce(&mut Window, &mut Context<Workspace>) -> Task<Result<()>>>,
+}
+
+/// The layout to restore when leaving zen mode.
+struct ZenMode {
+    open_docks: Vec<DockPosition>,
+}
+
 /// Collects everything project-related for a certain window opened.
 /// In some way, is a counterpart of a window, as the [`WindowHandle`] could be downcast into `Workspace`.
 ///
@@ -835,6 +904,9 @@ pub struct Workspace {
     workspace_actions: Vec<Box<dyn Fn(Div, &mut Window, &mut Context<Self>) -> Div>>,
     zoomed: Option<AnyWeakView>,
     previous_dock_drag_coordinates: Option<Point<Pixels>>,
+    /// The project folders being dragged from the project panel, which are moved to
+    /// another window if they are dropped onto it.
+    dragged_worktrees: Option<Vec<WorktreeId>>,
     zoomed_position: Option<DockPosition>,
     center: PaneGroup,
     left_dock: Entity<Dock>,
@@ -848,6 +920,8 @@ pub struct Workspace {
     status_bar: Entity<StatusBar>,
     modal_layer: Entity<ModalLayer>,
     toast_layer: Entity<ToastLayer>,
+    which_key: Entity<WhichKey>,
+    performance_overlay: Entity<PerformanceOverlay>,
     titlebar_item: Option<AnyView>,
     notifications: Notifications,
     project: Entity<Project>,
@@ -860,6 +934,9 @@ pub struct Workspace {
     database_id: Option<WorkspaceId>,
     app_state: Arc<AppState>,
     dispatching_keystrokes: Rc<RefCell<(HashSet<String>, Vec<Keystroke>)>>,
+    /// The settings profile activated in this window with [`ActivateSettingsProfile`], which takes
+    /// precedence over the profile bound to the project. `Some(None)` means no profile at all.
+    selected_settings_profile: Option<Option<String>>,
     _subscriptions: Vec<Subscription>,
     _apply_leader_updates: Task<Result<()>>,
     _observe_current_user: Task<Result<()>>,
@@ -867,7 +944,11 @@ pub struct Workspace {
     pane_history_timestamp: Arc<AtomicUsize>,
     bounds: Bounds<Pixels>,
     centered_layout: bool,
+    zen_mode: Option<ZenMode>,
     bounds_save_task_queued: Option<Task<()>>,
+    /// The workspace file that the workspace was opened from, or saved to.
+    workspace_file: Option<Arc<Path>>,
+    _workspace_file_watcher: Option<Task<()>>,
     on_prompt_for_new_path: Option<PromptForNewPath>,
     on_prompt_for_open_path: Option<PromptForOpenPath>,
     serializable_items_tx: UnboundedSender<Box<dyn SerializableItemHandle>>,
@@ -875,6 +956,7 @@ pub struct Workspace {
     _items_serializer: Task<Result<()>>,
     session_id: Option<String>,
     debug_task_queue: HashMap<task::TaskId, DebugAdapterConfig>,
+    deferred_panels: HashMap<TypeId, DeferredPanel>,
 }
 
 impl EventEmitter<Event> for Workspace {}
@@ -951,11 +1033,46 @@ impl Workspace {
                 project::Event::Toast {
                     notification_id,
                     message,
-                } => this.show_notification(
-                    NotificationId::named(notification_id.clone()),
-                    cx,
-                    |cx| cx.new(|cx| MessageNotification::new(message.clone(), cx)),
-                ),
+                } => {
+                    if notifications::record_notification(
+                        NotificationSource::Workspace,
+                        PromptLevel::Info,
+                        message.clone(),
+                        None,
+                        cx,
+                    ) {
+                        this.show_notification(
+                            NotificationId::named(notification_id.clone()),
+                            cx,
+                            |cx| cx.new(|cx| MessageNotification::new(message.clone(), cx)),
+                        )
+                    }
+                }
+
+                project::Event::PermissionsChanged => {
+                    struct PermissionsChanged;
+
+                    let permissions = this.project.read(cx).permissions();
+                    let allowed = [
+                        (permissions.can_edit, "edit files"),
+                        (permissions.can_use_terminal, "use terminals"),
+                        (permissions.can_run_tasks, "run tasks"),
+                    ]
+                    .into_iter()
+                    .filter_map(|(allowed, description)| allowed.then_some(description))
+                    .collect::<Vec<_>>();
+                    let message = if allowed.is_empty() {
+                        "The host made this project read-only for you".to_string()
+                    } else {
+                        format!("The host now allows you to {}", allowed.join(", "))
+                    };
+                    this.update_window_edited(window, cx);
+                    this.show_notification(
+                        NotificationId::unique::<PermissionsChanged>(),
+                        cx,
+                        |cx| cx.new(|cx| MessageNotification::new(message.clone(), cx)),
+                    );
+                }
 
                 project::Event::HideToast { notification_id } => {
                     this.dismiss_notification(&NotificationId::named(notification_id.clone()), cx)
@@ -964,6 +1081,19 @@ impl Workspace {
                 project::Event::LanguageServerPrompt(request) => {
                     struct LanguageServerPrompt;
 
+                    let should_show = notifications::record_notification(
+                        NotificationSource::LanguageServer,
+                        request.level,
+                        format!("{}: {}", request.lsp_name, request.message),
+                        Some(Box::new(zed_actions::debug::OpenLanguageServerLogs)),
+                        cx,
+                    );
+                    // Prompts that ask the user to pick an action still pop up,
+                    // since the language server is waiting for the answer.
+                    if !should_show && request.actions.is_empty() {
+                        return;
+                    }
+
                     let mut hasher = DefaultHasher::new();
                     request.lsp_name.as_str().hash(&mut hasher);
                     let id = hasher.finish();
@@ -1061,6 +1191,8 @@ impl Workspace {
         cx.emit(Event::WorkspaceCreated(weak_handle.clone()));
         let modal_layer = cx.new(|_| ModalLayer::new());
         let toast_layer = cx.new(|_| ToastLayer::new());
+        let which_key = cx.new(|cx| WhichKey::new(window, cx));
+        let performance_overlay = cx.new(|_| PerformanceOverlay::default());
 
         let left_dock = Dock::new(DockPosition::Left, modal_layer.clone(), window, cx);
         let bottom_dock = Dock::new(DockPosition::Bottom, modal_layer.clone(), window, cx);
@@ -1093,6 +1225,11 @@ impl Workspace {
 
         let subscriptions = vec![
             cx.observe_window_activation(window, Self::on_window_activation_changed),
+            cx.observe_global_in::<SettingsStore>(window, |this, window, cx| {
+                if window.is_window_active() {
+                    this.update_settings_profile(cx);
+                }
+            }),
             cx.observe_window_bounds(window, move |this, window, cx| {
                 if this.bounds_save_task_queued.is_some() {
                     return;
@@ -1127,8 +1264,7 @@ impl Workspace {
 
                 *SystemAppearance::global_mut(cx) = SystemAppearance(window_appearance.into());
 
-                ThemeSettings::reload_current_theme(cx);
-                ThemeSettings::reload_current_icon_theme(cx);
+                ThemeSettings::reload_current_theme_with_crossfade(cx);
             }),
             cx.on_release(move |this, cx| {
                 this.app_state.workspace_store.update(cx, move |store, _| {
@@ -1140,12 +1276,23 @@ impl Workspace {
         cx.defer_in(window, |this, window, cx| {
             this.update_window_title(window, cx);
             this.show_initial_notifications(cx);
+            // Workspaces that are restored keep the settings of the workspace file they were
+            // opened from.
+            if this.project.read(cx).is_local() {
+                if let Some(workspace_file) = this
+                    .database_id
+                    .and_then(|database_id| DB.workspace_file(database_id).log_err().flatten())
+                {
+                    this.set_workspace_file(workspace_file.into(), cx);
+                }
+            }
         });
         Workspace {
             weak_self: weak_handle.clone(),
             zoomed: None,
             zoomed_position: None,
             previous_dock_drag_coordinates: None,
+            dragged_worktrees: None,
             center: PaneGroup::new(center_pane.clone()),
             panes: vec![center_pane.clone()],
             panes_by_item: Default::default(),
@@ -1155,6 +1302,8 @@ impl Workspace {
             status_bar,
             modal_layer,
             toast_layer,
+            which_key,
+            performance_overlay,
             titlebar_item: None,
             notifications: Default::default(),
             left_dock,
@@ -1164,6 +1313,7 @@ impl Workspace {
             follower_states: Default::default(),
             last_leaders_by_pane: Default::default(),
             dispatching_keystrokes: Default::default(),
+            selected_settings_profile: None,
             window_edited: false,
             dirty_items: Default::default(),
             active_call,
@@ -1179,7 +1329,10 @@ impl Workspace {
             // This data will be incorrect, but it will be overwritten by the time it needs to be used.
             bounds: Default::default(),
             centered_layout: false,
+            zen_mode: None,
             bounds_save_task_queued: None,
+            workspace_file: None,
+            _workspace_file_watcher: None,
             on_prompt_for_new_path: None,
             on_prompt_for_open_path: None,
             serializable_items_tx,
@@ -1187,6 +1340,7 @@ impl Workspace {
             session_id: Some(session_id),
             serialized_ssh_project: None,
             debug_task_queue: Default::default(),
+            deferred_panels: Default::default(),
         }
     }
 
@@ -1299,9 +1453,17 @@ impl Workspace {
                 let (window_bounds, display) = if let Some(bounds) = window_bounds_override {
                     (Some(WindowBounds::Windowed(bounds)), None)
                 } else {
+                    let connected_displays = cx.update(|cx| {
+                        cx.displays()
+                            .into_iter()
+                            .filter_map(|display| display.uuid().ok())
+                            .collect::<Vec<_>>()
+                    })?;
                     let restorable_bounds = serialized_workspace
                         .as_ref()
-                        .and_then(|workspace| Some((workspace.display?, workspace.window_bounds?)))
+                        .and_then(|workspace| {
+                            restorable_window_frame(workspace, &connected_displays)
+                        })
                         .or_else(|| {
                             let (display, window_bounds) = DB.last_window().log_err()?;
                             Some((display?, window_bounds?))
@@ -1405,6 +1567,107 @@ impl Workspace {
         });
     }
 
+    /// Adds a panel that is only loaded once it's first focused or opened, once a restored dock
+    /// shows it, or otherwise shortly after startup, so that loading it doesn't delay opening the
+    /// window.
+    pub fn add_deferred_panel<T: Panel, F>(
+        &mut self,
+        load: impl FnOnce(WeakEntity<Self>, AsyncWindowContext) -> F + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) where
+        F: Future<Output = Result<Entity<T>>> + 'static,
+    {
+        if self.panel::<T>(cx).is_some() {
+            return;
+        }
+
+        self.deferred_panels.insert(
+            TypeId::of::<T>(),
+            DeferredPanel {
+                persistent_name: T::persistent_name(),
+                load: Box::new(move |window, cx| {
+                    cx.spawn_in(window, async move |workspace, cx| {
+                        let panel = load(workspace.clone(), cx.clone()).await?;
+                        workspace.update_in(cx, |workspace, window, cx| {
+                            workspace.add_panel(panel, window, cx);
+                        })
+                    })
+                }),
+            },
+        );
+
+        if self.dock_restores_deferred_panel(T::persistent_name(), cx) {
+            self.load_deferred_panel(TypeId::of::<T>(), window, cx)
+                .detach_and_log_err(cx);
+        } else if self.deferred_panels.len() == 1 {
+            cx.spawn_in(window, async move |workspace, cx| {
+                cx.background_executor()
+                    .timer(DEFERRED_PANEL_LOAD_DELAY)
+                    .await;
+                workspace.update_in(cx, |workspace, window, cx| {
+                    workspace.load_deferred_panels(window, cx)
+                })
+            })
+            .detach_and_log_err(cx);
+        }
+    }
+
+    /// Loads all of the panels whose loading was deferred.
+    pub fn load_deferred_panels(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let panel_types = self.deferred_panels.keys().copied().collect::<Vec<_>>();
+        for panel_type in panel_types {
+            self.load_deferred_panel(panel_type, window, cx)
+                .detach_and_log_err(cx);
+        }
+    }
+
+    fn load_deferred_panel(
+        &mut self,
+        panel_type: TypeId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        match self.deferred_panels.remove(&panel_type) {
+            Some(deferred_panel) => (deferred_panel.load)(window, cx),
+            None => Task::ready(Ok(())),
+        }
+    }
+
+    /// If the panel of the given type hasn't been loaded yet, loads it and then runs the given
+    /// callback. Returns whether the panel was loaded.
+    fn load_deferred_panel_then<T: Panel>(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        then: impl FnOnce(&mut Self, &mut Window, &mut Context<Self>) + 'static,
+    ) -> bool {
+        if !self.deferred_panels.contains_key(&TypeId::of::<T>()) {
+            return false;
+        }
+
+        let load = self.load_deferred_panel(TypeId::of::<T>(), window, cx);
+        cx.spawn_in(window, async move |workspace, cx| {
+            load.await?;
+            workspace.update_in(cx, |workspace, window, cx| then(workspace, window, cx))
+        })
+        .detach_and_log_err(cx);
+        true
+    }
+
+    /// Whether a dock restored from the database is open on the panel with the given name.
+    fn dock_restores_deferred_panel(&self, persistent_name: &str, cx: &App) -> bool {
+        self.all_docks().iter().any(|dock| {
+            dock.read(cx)
+                .serialized_dock
+                .as_ref()
+                .map_or(false, |serialized| {
+                    serialized.visible
+                        && serialized.active_panel.as_deref() == Some(persistent_name)
+                })
+        })
+    }
+
     pub fn status_bar(&self) -> &Entity<StatusBar> {
         &self.status_bar
     }
@@ -1875,6 +2138,116 @@ impl Workspace {
         .detach_and_log_err(cx)
     }
 
+    fn track_dragged_worktrees(
+        &mut self,
+        event: &DragMoveEvent<DraggedSelection>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let project = self.project.read(cx);
+        if !project.is_local() {
+            return;
+        }
+        // Only whole folders can be moved, not the entries inside them.
+        self.dragged_worktrees = event
+            .drag(cx)
+            .items()
+            .map(|entry| {
+                let worktree = project.worktree_for_id(entry.worktree_id, cx)?;
+                let root_entry_id = worktree.read(cx).root_entry()?.id;
+                (root_entry_id == entry.entry_id).then_some(entry.worktree_id)
+            })
+            .collect();
+    }
+
+    fn drop_dragged_worktrees_outside_window(
+        &mut self,
+        event: &MouseUpEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(worktree_ids) = self.dragged_worktrees.take() else {
+            return;
+        };
+        let window_bounds = window.bounds();
+        let position = window_bounds.origin + event.position;
+        if !cx.has_active_drag() || window_bounds.contains(&position) {
+            return;
+        }
+
+        let this_window = window.window_handle();
+        let target = cx
+            .window_stack()
+            .unwrap_or_else(|| cx.windows())
+            .into_iter()
+            .filter(|handle| *handle != this_window)
+            .filter_map(|handle| handle.downcast::<Workspace>())
+            .find(|handle| {
+                handle
+                    .update(cx, |workspace, window, cx| {
+                        workspace.project.read(cx).is_local() && window.bounds().contains(&position)
+                    })
+                    .unwrap_or(false)
+            });
+        if let Some(target) = target {
+            self.move_worktrees_to_window(worktree_ids, target, window, cx);
+        }
+    }
+
+    /// Moves folders of this project to the project in another window, closing this window
+    /// if it has no folders left.
+    pub fn move_worktrees_to_window(
+        &mut self,
+        worktree_ids: Vec<WorktreeId>,
+        target: WindowHandle<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let abs_paths = worktree_ids
+            .iter()
+            .filter_map(|worktree_id| {
+                let worktree = self.project.read(cx).worktree_for_id(*worktree_id, cx)?;
+                Some(worktree.read(cx).abs_path().to_path_buf())
+            })
+            .collect::<Vec<_>>();
+        if abs_paths.is_empty() {
+            return;
+        }
+        let Some(open) = target
+            .update(cx, |workspace, window, cx| {
+                window.activate_window();
+                workspace.open_paths(
+                    abs_paths,
+                    OpenOptions {
+                        visible: Some(OpenVisible::All),
+                        ..Default::default()
+                    },
+                    None,
+                    window,
+                    cx,
+                )
+            })
+            .log_err()
+        else {
+            return;
+        };
+
+        cx.spawn_in(window, async move |this, cx| {
+            open.await;
+            this.update_in(cx, |this, window, cx| {
+                this.project.update(cx, |project, cx| {
+                    for worktree_id in worktree_ids {
+                        project.remove_worktree(worktree_id, cx);
+                    }
+                });
+                if this.project.read(cx).visible_worktrees(cx).next().is_none() {
+                    this.close_window(&CloseWindow, window, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn move_focused_panel_to_next_position(
         &mut self,
         _: &MoveFocusedPanelToNextPosition,
@@ -1975,6 +2348,46 @@ impl Workspace {
         .detach_and_log_err(cx);
     }
 
+    fn activate_settings_profile(
+        &mut self,
+        action: &ActivateSettingsProfile,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_settings_profile(action.name.clone(), cx);
+    }
+
+    /// Activates the settings profile with the given name while this workspace's window is active,
+    /// regardless of the profile bound to its project.
+    pub fn set_settings_profile(&mut self, profile: Option<String>, cx: &mut Context<Self>) {
+        self.selected_settings_profile = Some(profile);
+        self.update_settings_profile(cx);
+    }
+
+    /// Returns the settings profile that applies while this workspace's window is active: the one
+    /// activated in it explicitly, or else the one bound to its project by `settings_profile`.
+    pub fn settings_profile(&self, cx: &App) -> Option<String> {
+        if let Some(profile) = &self.selected_settings_profile {
+            return profile.clone();
+        }
+        let worktree = self.project.read(cx).visible_worktrees(cx).next();
+        let location = worktree.map(|worktree| SettingsLocation {
+            worktree_id: worktree.read(cx).id(),
+            path: Path::new(""),
+        });
+        WorkspaceSettings::get(location, cx)
+            .settings_profile
+            .clone()
+    }
+
+    fn update_settings_profile(&self, cx: &mut App) {
+        let profile = self.settings_profile(cx);
+        if cx.global::<SettingsStore>().active_profile() != profile.as_deref() {
+            SettingsStore::update_global(cx, |store, cx| store.set_active_profile(profile, cx))
+                .log_err();
+        }
+    }
+
     fn send_keystrokes(
         &mut self,
         action: &SendKeystrokes,
@@ -2275,6 +2688,97 @@ impl Workspace {
         }
     }
 
+    /// Returns the path of the workspace file that the workspace was opened from, or saved to.
+    pub fn workspace_file(&self) -> Option<&Arc<Path>> {
+        self.workspace_file.as_ref()
+    }
+
+    /// Associates the workspace with the workspace file at the given path, applying its settings
+    /// to the project and applying them again whenever the file changes.
+    pub fn set_workspace_file(&mut self, abs_path: Arc<Path>, cx: &mut Context<Self>) {
+        let mut file_contents = settings::watch_config_file(
+            cx.background_executor(),
+            self.app_state.fs.clone(),
+            abs_path.to_path_buf(),
+        );
+        self._workspace_file_watcher = Some(cx.spawn(async move |this, cx| {
+            while let Some(content) = file_contents.next().await {
+                let settings = match WorkspaceFile::parse(&content) {
+                    Ok(workspace_file) => Some(workspace_file.settings).filter(|s| !s.is_null()),
+                    Err(error) => {
+                        log::error!("{error:?}");
+                        continue;
+                    }
+                };
+                let updated = this.update(cx, |this, cx| {
+                    this.project.update(cx, |project, cx| {
+                        project.set_workspace_settings(settings, cx)
+                    })
+                });
+                if updated.is_err() {
+                    break;
+                }
+            }
+        }));
+
+        if let Some(database_id) = self.database_id {
+            cx.background_executor()
+                .spawn(DB.set_workspace_file(database_id, abs_path.to_path_buf()))
+                .detach_and_log_err(cx);
+        }
+        self.workspace_file = Some(abs_path);
+    }
+
+    fn save_workspace_as(
+        &mut self,
+        _: &SaveWorkspaceAs,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let project = self.project.read(cx);
+        if !project.is_local() {
+            self.show_error(
+                &anyhow!("Only local projects can be saved as workspace files"),
+                cx,
+            );
+            return;
+        }
+        let folders = project
+            .visible_worktrees(cx)
+            .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+            .collect::<Vec<_>>();
+        let directory = match &self.workspace_file {
+            Some(workspace_file) => workspace_file.parent().map(Path::to_path_buf),
+            None => folders.first().cloned(),
+        };
+        let current_workspace_file = self.workspace_file.clone();
+        let fs = self.app_state.fs.clone();
+        let abs_path = cx.prompt_for_new_path(&directory.unwrap_or_default());
+        cx.spawn_in(window, async move |this, cx| {
+            let Some(mut abs_path) = abs_path.await?? else {
+                return anyhow::Ok(());
+            };
+            if abs_path.extension().is_none() {
+                abs_path.set_extension(WORKSPACE_FILE_EXTENSION);
+            }
+
+            let mut workspace_file = WorkspaceFile::new(&abs_path, &folders);
+            // Keep the settings of the workspace file that's being saved as another one.
+            if let Some(current_workspace_file) = current_workspace_file {
+                if let Ok(content) = fs.load(&current_workspace_file).await {
+                    workspace_file.settings = WorkspaceFile::parse(&content)?.settings;
+                }
+            }
+            fs.atomic_write(abs_path.clone(), workspace_file.to_json())
+                .await?;
+
+            this.update(cx, |this, cx| {
+                this.set_workspace_file(abs_path.into(), cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn absolute_path_of_worktree(
         &self,
         worktree_id: WorktreeId,
@@ -2590,6 +3094,12 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Option<Entity<T>> {
+        if self.load_deferred_panel_then::<T>(window, cx, |workspace, window, cx| {
+            workspace.focus_panel::<T>(window, cx);
+        }) {
+            return None;
+        }
+
         let panel = self.focus_or_unfocus_panel::<T>(window, cx, |_, _, _| true)?;
         panel.to_any().downcast().ok()
     }
@@ -2597,6 +3107,12 @@ impl Workspace {
     /// Focus the panel of the given type if it isn't already focused. If it is
     /// already focused, then transfer focus back to the workspace center.
     pub fn toggle_panel_focus<T: Panel>(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.load_deferred_panel_then::<T>(window, cx, |workspace, window, cx| {
+            workspace.focus_panel::<T>(window, cx);
+        }) {
+            return;
+        }
+
         self.focus_or_unfocus_panel::<T>(window, cx, |panel, window, cx| {
             !panel.panel_focus_handle(cx).contains_focused(window, cx)
         });
@@ -2676,6 +3192,12 @@ impl Workspace {
 
     /// Open the panel of the given type
     pub fn open_panel<T: Panel>(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.load_deferred_panel_then::<T>(window, cx, |workspace, window, cx| {
+            workspace.open_panel::<T>(window, cx);
+        }) {
+            return;
+        }
+
         for dock in self.all_docks() {
             if let Some(panel_index) = dock.read(cx).panel_index_for_type::<T>() {
                 dock.update(cx, |dock, cx| {
@@ -2749,6 +3271,9 @@ impl Workspace {
             pane.set_can_split(Some(Arc::new(|_, _, _, _| true)));
             pane
         });
+        if self.zen_mode.is_some() {
+            pane.update(cx, |pane, cx| pane.set_zen_mode(true, window, cx));
+        }
         cx.subscribe_in(&pane, window, Self::handle_pane_event)
             .detach();
         self.panes.push(pane.clone());
@@ -3006,7 +3531,28 @@ impl Workspace {
         else {
             return Task::ready(Err(anyhow!("cannot open file {:?}", path.path)));
         };
-        open_project_item
+
+        let binary_file_openers = cx.default_global::<BinaryFileOpeners>().clone();
+        if binary_file_openers.is_empty() {
+            return open_project_item;
+        }
+        window.spawn(cx, async move |cx| {
+            let error = match open_project_item.await {
+                Ok(opened_item) => return Ok(opened_item),
+                Err(error) if is_binary_file_error(&error) => error,
+                Err(error) => return Err(error),
+            };
+            let open_binary_file = cx.update(|window, cx| {
+                binary_file_openers
+                    .iter()
+                    .rev()
+                    .find_map(|open_binary_file| open_binary_file(&project, &path, window, cx))
+            })?;
+            match open_binary_file {
+                Some(open_binary_file) => open_binary_file.await,
+                None => Err(error),
+            }
+        })
     }
 
     pub fn find_project_item<T>(
@@ -3329,6 +3875,22 @@ impl Workspace {
         }
     }
 
+    pub fn move_pane_to_edge(&mut self, direction: SplitDirection, cx: &mut Context<Self>) {
+        if self
+            .center
+            .move_to_edge(&self.active_pane, direction)
+            .log_err()
+            .is_some()
+        {
+            cx.notify();
+        }
+    }
+
+    pub fn rotate_panes(&mut self, forward: bool, cx: &mut Context<Self>) {
+        self.center.rotate(&self.active_pane, forward);
+        cx.notify();
+    }
+
     pub fn resize_pane(
         &mut self,
         axis: gpui::Axis,
@@ -4152,6 +4714,9 @@ impl Workspace {
         };
         let id = ViewId::from_proto(id)?;
         let panel_id = view.panel_id.and_then(proto::PanelId::from_i32);
+        if !cx.update(|_, cx| WorkspaceSettings::get_global(cx).follow.should_follow(view))? {
+            return Ok(());
+        }
 
         let pane = this.update(cx, |this, _cx| {
             let state = this
@@ -4459,6 +5024,7 @@ impl Workspace {
     pub fn on_window_activation_changed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if window.is_window_active() {
             self.update_active_view_for_followers(window, cx);
+            self.update_settings_profile(cx);
 
             if let Some(database_id) = self.database_id {
                 cx.background_spawn(persistence::DB.update_timestamp(database_id))
@@ -4861,6 +5427,21 @@ impl Workspace {
                     });
                 }
 
+                // Deferred panels that the restored docks show can't wait to be loaded.
+                let restored_panels = workspace
+                    .deferred_panels
+                    .iter()
+                    .filter(|(_, deferred_panel)| {
+                        workspace.dock_restores_deferred_panel(deferred_panel.persistent_name, cx)
+                    })
+                    .map(|(panel_type, _)| *panel_type)
+                    .collect::<Vec<_>>();
+                for panel_type in restored_panels {
+                    workspace
+                        .load_deferred_panel(panel_type, window, cx)
+                        .detach_and_log_err(cx);
+                }
+
                 cx.notify();
             })?;
 
@@ -4918,7 +5499,9 @@ impl Workspace {
             .on_action(cx.listener(Self::close_all_items_and_panes))
             .on_action(cx.listener(Self::save_all))
             .on_action(cx.listener(Self::send_keystrokes))
+            .on_action(cx.listener(Self::activate_settings_profile))
             .on_action(cx.listener(Self::add_folder_to_project))
+            .on_action(cx.listener(Self::save_workspace_as))
             .on_action(cx.listener(Self::follow_next_collaborator))
             .on_action(cx.listener(Self::close_window))
             .on_action(cx.listener(Self::activate_pane_at_index))
@@ -4993,6 +5576,24 @@ impl Workspace {
             .on_action(cx.listener(|workspace, _: &SwapPaneDown, _, cx| {
                 workspace.swap_pane_in_direction(SplitDirection::Down, cx)
             }))
+            .on_action(cx.listener(|workspace, _: &MovePaneLeft, _, cx| {
+                workspace.move_pane_to_edge(SplitDirection::Left, cx)
+            }))
+            .on_action(cx.listener(|workspace, _: &MovePaneRight, _, cx| {
+                workspace.move_pane_to_edge(SplitDirection::Right, cx)
+            }))
+            .on_action(cx.listener(|workspace, _: &MovePaneUp, _, cx| {
+                workspace.move_pane_to_edge(SplitDirection::Up, cx)
+            }))
+            .on_action(cx.listener(|workspace, _: &MovePaneDown, _, cx| {
+                workspace.move_pane_to_edge(SplitDirection::Down, cx)
+            }))
+            .on_action(cx.listener(|workspace, _: &RotatePanesForward, _, cx| {
+                workspace.rotate_panes(true, cx)
+            }))
+            .on_action(cx.listener(|workspace, _: &RotatePanesBackward, _, cx| {
+                workspace.rotate_panes(false, cx)
+            }))
             .on_action(cx.listener(|this, _: &ToggleLeftDock, window, cx| {
                 this.toggle_dock(DockPosition::Left, window, cx);
             }))
@@ -5022,6 +5623,17 @@ impl Workspace {
                 },
             ))
             .on_action(cx.listener(Workspace::toggle_centered_layout))
+            .on_action(cx.listener(Workspace::toggle_zen_mode))
+            .on_action(cx.listener(
+                |workspace: &mut Workspace,
+                 _: &zed_actions::TogglePerformanceOverlay,
+                 window,
+                 cx| {
+                    workspace
+                        .performance_overlay
+                        .update(cx, |overlay, cx| overlay.toggle(window, cx));
+                },
+            ))
     }
 
     #[cfg(any(test, feature = "test-support"))]
@@ -5116,6 +5728,64 @@ impl Workspace {
         cx.notify();
     }
 
+    /// Enters zen mode by closing the docks and hiding the title bar, the status bar and the
+    /// center panes' tab bars, or leaves it by reopening the docks that were open before.
+    pub fn toggle_zen_mode(
+        &mut self,
+        _: &ToggleZenMode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(zen_mode) = self.zen_mode.take() {
+            for position in zen_mode.open_docks {
+                self.dock_at_position(position).update(cx, |dock, cx| {
+                    dock.set_open(true, window, cx);
+                });
+            }
+        } else {
+            let mut open_docks = Vec::new();
+            for dock in self.all_docks() {
+                dock.update(cx, |dock, cx| {
+                    if dock.is_open() {
+                        open_docks.push(dock.position());
+                        dock.set_open(false, window, cx);
+                    }
+                });
+            }
+            self.zen_mode = Some(ZenMode { open_docks });
+            window.focus(&self.active_pane.focus_handle(cx));
+        }
+
+        let enabled = self.zen_mode.is_some();
+        for pane in self.center.panes() {
+            pane.update(cx, |pane, cx| pane.set_zen_mode(enabled, window, cx));
+        }
+        cx.notify();
+        self.serialize_workspace(window, cx);
+    }
+
+    pub fn is_zen_mode(&self) -> bool {
+        self.zen_mode.is_some()
+    }
+
+    /// The width of the centered buffer in zen mode, when there's only one center pane.
+    fn zen_mode_width(&self, window: &mut Window, cx: &mut App) -> Option<Pixels> {
+        if self.zen_mode.is_none() || self.center.panes().len() != 1 {
+            return None;
+        }
+        self.active_item(cx)?;
+        let theme_settings = ThemeSettings::get_global(cx);
+        let font_id = window
+            .text_system()
+            .resolve_font(&theme_settings.buffer_font);
+        let font_size = theme_settings.buffer_font_size(cx);
+        let em_width = window
+            .text_system()
+            .em_width(font_id, font_size)
+            .log_err()?;
+        Some(em_width * WorkspaceSettings::get_global(cx).zen_mode.column_width as f32)
+    }
+
     fn adjust_padding(padding: Option<f32>) -> f32 {
         padding
             .unwrap_or(Self::DEFAULT_PADDING)
@@ -5239,6 +5909,28 @@ fn leader_border_for_pane(
     )
 }
 
+/// Returns the display and bounds to open a workspace's window with. When the display that the
+/// window was last on isn't connected, like after undocking a laptop, the window is opened with
+/// the frame it last had on one of the connected displays.
+fn restorable_window_frame(
+    workspace: &SerializedWorkspace,
+    connected_displays: &[Uuid],
+) -> Option<(Uuid, SerializedWindowBounds)> {
+    let last_frame = workspace.display.zip(workspace.window_bounds);
+    if let Some((display, _)) = last_frame {
+        if connected_displays.contains(&display) {
+            return last_frame;
+        }
+    }
+
+    DB.window_frames(workspace.id)
+        .log_err()
+        .into_iter()
+        .flatten()
+        .find(|(display, _)| connected_displays.contains(display))
+        .or(last_frame)
+}
+
 fn window_bounds_env_override() -> Option<Bounds<Pixels>> {
     ZED_WINDOW_POSITION
         .zip(*ZED_WINDOW_SIZE)
@@ -5403,7 +6095,9 @@ impl Render for Workspace {
         let mut context = KeyContext::new_with_defaults();
         context.add("Workspace");
         context.set("keyboard_layout", cx.keyboard_layout().clone());
+        let zen_mode_width = self.zen_mode_width(window, cx);
         let centered_layout = self.centered_layout
+            && self.zen_mode.is_none()
             && self.center.panes().len() == 1
             && self.active_item(cx).is_some();
         let render_padding = |size| {
@@ -5442,7 +6136,15 @@ impl Render for Workspace {
                 .items_start()
                 .text_color(colors.text)
                 .overflow_hidden()
-                .children(self.titlebar_item.clone())
+                .on_drag_move(cx.listener(Self::track_dragged_worktrees))
+                .capture_any_mouse_up(cx.listener(|this, _, _, _| this.dragged_worktrees = None))
+                .on_mouse_up_out(
+                    MouseButton::Left,
+                    cx.listener(Self::drop_dragged_worktrees_outside_window),
+                )
+                .when(self.zen_mode.is_none(), |this| {
+                    this.children(self.titlebar_item.clone())
+                })
                 .child(
                     div()
                         .size_full()
@@ -5570,19 +6272,32 @@ impl Render for Workspace {
                                                 .child(
                                                     h_flex()
                                                         .flex_1()
+                                                        .when(zen_mode_width.is_some(), |this| {
+                                                            this.justify_center()
+                                                                .bg(colors.editor_background)
+                                                        })
                                                         .when_some(paddings.0, |this, p| {
                                                             this.child(p.border_r_1())
                                                         })
-                                                        .child(self.center.render(
-                                                            &self.project,
-                                                            &self.follower_states,
-                                                            self.active_call(),
-                                                            &self.active_pane,
-                                                            self.zoomed.as_ref(),
-                                                            &self.app_state,
-                                                            window,
-                                                            cx,
-                                                        ))
+                                                        .child(
+                                                            div()
+                                                                .h_full()
+                                                                .flex_1()
+                                                                .when_some(
+                                                                    zen_mode_width,
+                                                                    |this, width| this.max_w(width),
+                                                                )
+                                                                .child(self.center.render(
+                                                                    &self.project,
+                                                                    &self.follower_states,
+                                                                    self.active_call(),
+                                                                    &self.active_pane,
+                                                                    self.zoomed.as_ref(),
+                                                                    &self.app_state,
+                                                                    window,
+                                                                    cx,
+                                                                )),
+                                                        )
                                                         .when_some(paddings.1, |this, p| {
                                                             this.child(p.border_l_1())
                                                         }),
@@ -5625,9 +6340,13 @@ impl Render for Workspace {
                                 }))
                                 .children(self.render_notifications(window, cx)),
                         )
-                        .child(self.status_bar.clone())
+                        .when(self.zen_mode.is_none(), |this| {
+                            this.child(self.status_bar.clone())
+                        })
                         .child(self.modal_layer.clone())
-                        .child(self.toast_layer.clone()),
+                        .child(self.toast_layer.clone())
+                        .child(self.which_key.clone())
+                        .child(self.performance_overlay.clone()),
                 ),
             window,
             cx,
@@ -6114,12 +6833,23 @@ pub fn open_paths(
         Vec<Option<Result<Box<dyn ItemHandle>, anyhow::Error>>>,
     )>,
 > {
-    let abs_paths = abs_paths.to_vec();
+    let mut abs_paths = abs_paths.to_vec();
     let mut existing = None;
     let mut best_match = None;
     let mut open_visible = OpenVisible::All;
 
     cx.spawn(async move |cx| {
+        // Workspace files open the folders that are saved in them.
+        let mut workspace_file_path = None;
+        if let [path] = abs_paths.as_slice() {
+            if is_workspace_file(path) {
+                let content = app_state.fs.load(path).await?;
+                let workspace_file = WorkspaceFile::parse(&content)?;
+                workspace_file_path = Some(Arc::<Path>::from(path.as_path()));
+                abs_paths = workspace_file.folder_abs_paths(path);
+            }
+        }
+
         if open_options.open_new_workspace != Some(true) {
             let all_paths = abs_paths.iter().map(|path| app_state.fs.metadata(path));
             let all_metadatas = futures::future::join_all(all_paths)
@@ -6181,7 +6911,7 @@ pub fn open_paths(
             }
         }
 
-        if let Some(existing) = existing {
+        let (window, items) = if let Some(existing) = existing {
             let open_task = existing
                 .update(cx, |workspace, window, cx| {
                     window.activate_window();
@@ -6206,7 +6936,7 @@ pub fn open_paths(
                 }
             });
 
-            Ok((existing, open_task))
+            (existing, open_task)
         } else {
             cx.update(move |cx| {
                 Workspace::new_local(
@@ -6217,8 +6947,17 @@ pub fn open_paths(
                     cx,
                 )
             })?
-            .await
+            .await?
+        };
+
+        if let Some(workspace_file_path) = workspace_file_path {
+            window
+                .update(cx, |workspace, _, cx| {
+                    workspace.set_workspace_file(workspace_file_path, cx)
+                })
+                .log_err();
         }
+        Ok((window, items))
     })
 }
 
@@ -7077,6 +7816,73 @@ mod tests {
         assert_eq!(cx.window_title().as_deref(), Some("root2 — one.txt"));
     }
 
+    #[gpui::test]
+    async fn test_move_worktrees_to_window(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/a", json!({ "one.txt": "" })).await;
+        fs.insert_tree("/b", json!({ "two.txt": "" })).await;
+        fs.insert_tree("/c", json!({ "three.txt": "" })).await;
+
+        let project_1 = Project::test(fs.clone(), ["/a".as_ref(), "/b".as_ref()], cx).await;
+        let project_2 = Project::test(fs, ["/c".as_ref()], cx).await;
+        let window_1 =
+            cx.add_window(|window, cx| Workspace::test_new(project_1.clone(), window, cx));
+        let window_2 =
+            cx.add_window(|window, cx| Workspace::test_new(project_2.clone(), window, cx));
+
+        let worktree_paths = |project: &Entity<Project>, cx: &mut TestAppContext| {
+            project.read_with(cx, |project, cx| {
+                project
+                    .visible_worktrees(cx)
+                    .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+                    .collect::<Vec<_>>()
+            })
+        };
+        let worktree_id = |root_name: &str, cx: &mut TestAppContext| {
+            project_1.read_with(cx, |project, cx| {
+                project
+                    .worktree_for_root_name(root_name, cx)
+                    .unwrap()
+                    .read(cx)
+                    .id()
+            })
+        };
+
+        // Moving one of the folders leaves the other one in the original window.
+        let worktree_b = worktree_id("b", cx);
+        window_1
+            .update(cx, |workspace, window, cx| {
+                workspace.move_worktrees_to_window(vec![worktree_b], window_2, window, cx)
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(worktree_paths(&project_1, cx), [PathBuf::from("/a")]);
+        assert_eq!(
+            worktree_paths(&project_2, cx),
+            [PathBuf::from("/c"), PathBuf::from("/b")]
+        );
+
+        // Moving the last folder closes the original window.
+        let worktree_a = worktree_id("a", cx);
+        window_1
+            .update(cx, |workspace, window, cx| {
+                workspace.move_worktrees_to_window(vec![worktree_a], window_2, window, cx)
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert!(window_1.update(cx, |_, _, _| {}).is_err());
+        assert_eq!(
+            worktree_paths(&project_2, cx),
+            [
+                PathBuf::from("/c"),
+                PathBuf::from("/b"),
+                PathBuf::from("/a")
+            ]
+        );
+    }
+
     #[gpui::test]
     async fn test_close_window(cx: &mut TestAppContext) {
         init_test(cx);
@@ -7589,6 +8395,57 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_toggle_zen_mode(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let left_panel = cx.new(|cx| TestPanel::new(DockPosition::Left, cx));
+            workspace.add_panel(left_panel, window, cx);
+            let right_panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(right_panel, window, cx);
+            workspace
+                .left_dock()
+                .update(cx, |dock, cx| dock.set_open(true, window, cx));
+        });
+
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        pane.update_in(cx, |pane, window, cx| {
+            let item = cx.new(TestItem::new);
+            pane.add_item(Box::new(item), true, true, None, window, cx);
+        });
+
+        // Entering zen mode closes the docks and hides the tab bars.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_zen_mode(&ToggleZenMode, window, cx);
+            assert!(workspace.is_zen_mode());
+            assert!(!workspace.left_dock().read(cx).is_open());
+            assert!(!workspace.right_dock().read(cx).is_open());
+            assert!(pane.read(cx).is_zen_mode());
+        });
+
+        // Panes split off while in zen mode are in it too.
+        let new_pane = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.split_pane(pane.clone(), SplitDirection::Right, window, cx)
+        });
+        assert!(new_pane.read_with(cx, |pane, _| pane.is_zen_mode()));
+
+        // Leaving it reopens only the docks that were open before.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_zen_mode(&ToggleZenMode, window, cx);
+            assert!(!workspace.is_zen_mode());
+            assert!(workspace.left_dock().read(cx).is_open());
+            assert!(!workspace.right_dock().read(cx).is_open());
+            assert!(!pane.read(cx).is_zen_mode());
+            assert!(!new_pane.read(cx).is_zen_mode());
+        });
+    }
+
     #[gpui::test]
     async fn test_toggle_docks_and_panels(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -7739,6 +8596,52 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_move_and_rotate_panes(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        // Start with `a` on the left, and `b` above `c` on the right.
+        let (a, b, c) = workspace.update_in(cx, |workspace, window, cx| {
+            let a = workspace.active_pane().clone();
+            let b = workspace.split_pane(a.clone(), SplitDirection::Right, window, cx);
+            let c = workspace.split_pane(b.clone(), SplitDirection::Down, window, cx);
+            (a, b, c)
+        });
+
+        workspace.update(cx, |workspace, _| {
+            workspace
+                .center
+                .move_to_edge(&c, SplitDirection::Left)
+                .unwrap();
+            let Member::Axis(axis) = &workspace.center.root else {
+                panic!("expected an axis");
+            };
+            assert_eq!(axis.axis, gpui::Axis::Horizontal);
+            assert_eq!(workspace.center.panes(), [&c, &a, &b]);
+
+            workspace.center.rotate(&a, true);
+            assert_eq!(workspace.center.panes(), [&b, &c, &a]);
+
+            workspace.center.rotate(&a, false);
+            assert_eq!(workspace.center.panes(), [&c, &a, &b]);
+
+            workspace
+                .center
+                .move_to_edge(&c, SplitDirection::Down)
+                .unwrap();
+            let Member::Axis(axis) = &workspace.center.root else {
+                panic!("expected an axis");
+            };
+            assert_eq!(axis.axis, gpui::Axis::Vertical);
+            assert_eq!(workspace.center.panes(), [&a, &b, &c]);
+        });
+    }
+
     #[gpui::test]
     async fn test_join_pane_into_next(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -0,0 +1,108 @@
+//! Workspace files save a set of folders to open together, along with settings for them. They
+//! have the `.zed-workspace` extension and can be opened like folders. The folders in them are
+//! relative to the workspace file, so that they can be shared in repositories.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, Result};
+use serde::{Deserialize, Serialize};
+use settings::parse_json_with_comments;
+
+pub const WORKSPACE_FILE_EXTENSION: &str = "zed-workspace";
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceFile {
+    /// The folders to open, relative to the directory of the workspace file.
+    pub folders: Vec<PathBuf>,
+    /// The settings for the folders, which the settings files in the folders take precedence
+    /// over.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub settings: serde_json::Value,
+}
+
+/// Returns whether the file at the given path is a workspace file.
+pub fn is_workspace_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == WORKSPACE_FILE_EXTENSION)
+}
+
+impl WorkspaceFile {
+    /// Creates a workspace file to save at `path` for the folders at the given absolute paths.
+    pub fn new(path: &Path, folder_abs_paths: &[PathBuf]) -> Self {
+        let directory = path.parent().unwrap_or(Path::new(""));
+        Self {
+            folders: folder_abs_paths
+                .iter()
+                .map(|folder| match pathdiff::diff_paths(folder, directory) {
+                    Some(relative_path) if relative_path.as_os_str().is_empty() => {
+                        PathBuf::from(".")
+                    }
+                    Some(relative_path) => relative_path,
+                    None => folder.clone(),
+                })
+                .collect(),
+            settings: serde_json::Value::Null,
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        parse_json_with_comments(content).context("failed to parse workspace file")
+    }
+
+    /// Returns the absolute paths of the folders, given the path of the workspace file.
+    pub fn folder_abs_paths(&self, path: &Path) -> Vec<PathBuf> {
+        let directory = path.parent().unwrap_or(Path::new(""));
+        self.folders
+            .iter()
+            .map(|folder| fs::normalize_path(&directory.join(folder)))
+            .collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_workspace_file_folders() {
+        let path = Path::new("/code/app/app.zed-workspace");
+        let workspace_file = WorkspaceFile::new(
+            path,
+            &[PathBuf::from("/code/app"), PathBuf::from("/code/lib")],
+        );
+        assert_eq!(
+            workspace_file.folders,
+            [PathBuf::from("."), PathBuf::from("../lib")]
+        );
+        assert_eq!(
+            workspace_file.folder_abs_paths(path),
+            [PathBuf::from("/code/app"), PathBuf::from("/code/lib")]
+        );
+    }
+
+    #[test]
+    fn test_parse_workspace_file() {
+        let workspace_file = WorkspaceFile::parse(
+            r#"{
+                // Comments are allowed in workspace files.
+                "folders": [".", "../lib"],
+                "settings": { "tab_size": 2 },
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            workspace_file,
+            WorkspaceFile {
+                folders: vec![PathBuf::from("."), PathBuf::from("../lib")],
+                settings: json!({ "tab_size": 2 }),
+            }
+        );
+        assert!(is_workspace_file(Path::new("/code/app.zed-workspace")));
+        assert!(!is_workspace_file(Path::new("/code/app.json")));
+    }
+}
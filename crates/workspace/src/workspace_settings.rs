@@ -1,8 +1,11 @@
 use std::num::NonZeroUsize;
 
+use crate::{dock::DockPosition, notifications::NotificationSource};
 use anyhow::Result;
+use chrono::NaiveTime;
+use client::proto;
 use collections::HashMap;
-use gpui::App;
+use gpui::{App, Pixels};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources};
@@ -13,6 +16,8 @@ pub struct WorkspaceSettings {
     pub pane_split_direction_horizontal: PaneSplitDirectionHorizontal,
     pub pane_split_direction_vertical: PaneSplitDirectionVertical,
     pub centered_layout: CenteredLayoutSettings,
+    pub zen_mode: ZenModeSettings,
+    pub follow: FollowSettings,
     pub confirm_quit: bool,
     pub show_call_status_icon: bool,
     pub autosave: AutosaveSetting,
@@ -21,11 +26,16 @@ pub struct WorkspaceSettings {
     pub drop_target_size: f32,
     pub use_system_path_prompts: bool,
     pub use_system_prompts: bool,
+    pub use_system_window_tabs: bool,
     pub command_aliases: HashMap<String, String>,
     pub show_user_picture: bool,
     pub max_tabs: Option<NonZeroUsize>,
     pub when_closing_with_no_tabs: CloseWindowWhenNoItems,
     pub on_last_window_closed: OnLastWindowClosed,
+    pub leader_key: Option<String>,
+    pub key_sequence_timeout: u64,
+    pub show_which_key: bool,
+    pub settings_profile: Option<String>,
 }
 
 #[derive(Copy, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -119,6 +129,10 @@ pub struct WorkspaceSettingsContent {
     pub pane_split_direction_vertical: Option<PaneSplitDirectionVertical>,
     /// Centered layout related settings.
     pub centered_layout: Option<CenteredLayoutSettings>,
+    /// Zen mode related settings.
+    pub zen_mode: Option<ZenModeSettings>,
+    /// Which surfaces to follow a collaborator into, besides editors.
+    pub follow: Option<FollowSettings>,
     /// Whether or not to prompt the user to confirm before closing the application.
     ///
     /// Default: false
@@ -165,6 +179,12 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: true
     pub use_system_prompts: Option<bool>,
+    /// Whether windows can be merged into the system's native window tabs, and dragged between
+    /// them, with the `zed: merge all windows` action. This only has an effect on macOS, and
+    /// applies to the windows that are opened after it's changed.
+    ///
+    /// Default: false
+    pub use_system_window_tabs: Option<bool>,
     /// Aliases for the command palette. When you type a key in this map,
     /// it will be assumed to equal the value.
     ///
@@ -183,6 +203,24 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: auto (nothing on macOS, "app quit" otherwise)
     pub on_last_window_closed: Option<OnLastWindowClosed>,
+    /// The keystroke that `leader` stands for in key bindings, e.g. `"leader f f"`.
+    ///
+    /// Default: null
+    pub leader_key: Option<String>,
+    /// How long to wait, in milliseconds, for the next keystroke of a multi-stroke key binding.
+    ///
+    /// Default: 1000
+    pub key_sequence_timeout: Option<u64>,
+    /// Whether to show a popup listing the possible continuations of a
+    /// partially typed multi-stroke key binding.
+    ///
+    /// Default: true
+    pub show_which_key: Option<bool>,
+    /// The name of the settings profile, defined under `profiles`, to activate while this
+    /// project's window is active. Usually set in a project's `.zed/settings.json`.
+    ///
+    /// Default: null
+    pub settings_profile: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -250,6 +288,176 @@ pub struct CenteredLayoutSettings {
     pub right_padding: Option<f32>,
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ZenModeSettings {
+    /// The number of columns of the buffer font that the centered buffer is wide in zen mode.
+    ///
+    /// Default: 100
+    pub column_width: u32,
+    /// Whether to keep the line with the cursor vertically centered while in zen mode.
+    ///
+    /// Default: false
+    pub typewriter_scrolling: bool,
+}
+
+impl Default for ZenModeSettings {
+    fn default() -> Self {
+        Self {
+            column_width: 100,
+            typewriter_scrolling: false,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct FollowSettings {
+    /// Whether to follow a collaborator into terminals that they're sharing.
+    ///
+    /// Default: true
+    pub terminals: bool,
+    /// Whether to follow a collaborator into the assistant panel.
+    ///
+    /// Default: true
+    pub assistant_panel: bool,
+    /// Whether to follow a collaborator into the project search results they're viewing.
+    ///
+    /// Default: true
+    pub project_search: bool,
+}
+
+impl Default for FollowSettings {
+    fn default() -> Self {
+        Self {
+            terminals: true,
+            assistant_panel: true,
+            project_search: true,
+        }
+    }
+}
+
+impl FollowSettings {
+    /// Returns whether to follow a leader into the given view.
+    pub fn should_follow(&self, view: &proto::View) -> bool {
+        if view.panel_id == Some(proto::PanelId::AssistantPanel as i32) {
+            return self.assistant_panel;
+        }
+        match &view.variant {
+            Some(proto::view::Variant::Terminal(_)) => self.terminals,
+            Some(proto::view::Variant::ProjectSearch(_)) => self.project_search,
+            Some(proto::view::Variant::ContextEditor(_)) => self.assistant_panel,
+            _ => true,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct NotificationCenterSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+    pub max_history: usize,
+    pub muted_sources: Vec<NotificationSource>,
+    pub do_not_disturb: DoNotDisturbSettings,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct NotificationCenterSettingsContent {
+    /// Whether to show the notification center button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the notification center.
+    ///
+    /// Default: right
+    pub dock: Option<DockPosition>,
+    /// Default width of the notification center in pixels.
+    ///
+    /// Default: 380
+    pub default_width: Option<f32>,
+    /// How many notifications to keep in the history.
+    ///
+    /// Default: 200
+    pub max_history: Option<usize>,
+    /// Sources whose notifications are recorded in the history without popping up.
+    ///
+    /// Default: []
+    pub muted_sources: Option<Vec<NotificationSource>>,
+    /// When to hold back notification popups from every source.
+    pub do_not_disturb: Option<DoNotDisturbSettings>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DoNotDisturbSettings {
+    /// Whether "do not disturb" is turned on, regardless of the schedule.
+    ///
+    /// Default: false
+    pub enabled: bool,
+    /// A daily period of local time during which "do not disturb" turns on by itself.
+    ///
+    /// Default: null
+    pub schedule: Option<DoNotDisturbSchedule>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DoNotDisturbSchedule {
+    /// The time at which "do not disturb" turns on, such as "22:00".
+    pub start: String,
+    /// The time at which "do not disturb" turns off, such as "08:00".
+    pub end: String,
+}
+
+impl DoNotDisturbSettings {
+    /// Returns whether popups should be held back at the given local time.
+    pub fn is_active_at(&self, time: NaiveTime) -> bool {
+        if self.enabled {
+            return true;
+        }
+        let Some(schedule) = &self.schedule else {
+            return false;
+        };
+        let (Ok(start), Ok(end)) = (
+            NaiveTime::parse_from_str(&schedule.start, "%H:%M"),
+            NaiveTime::parse_from_str(&schedule.end, "%H:%M"),
+        ) else {
+            return false;
+        };
+        if start <= end {
+            start <= time && time < end
+        } else {
+            // The schedule wraps around midnight.
+            start <= time || time < end
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct StatusBarSettings {
+    pub left: Vec<String>,
+    pub right: Vec<String>,
+    pub hidden: Vec<String>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct StatusBarSettingsContent {
+    /// The order of the segments on the left side of the status bar. Segments
+    /// that aren't listed follow in their default order.
+    ///
+    /// Default: []
+    pub left: Option<Vec<String>>,
+    /// The order of the segments on the right side of the status bar. Segments
+    /// that aren't listed follow in their default order.
+    ///
+    /// Default: []
+    pub right: Option<Vec<String>>,
+    /// Segments that aren't shown in the status bar.
+    ///
+    /// Default: []
+    pub hidden: Option<Vec<String>>,
+}
+
 impl Settings for WorkspaceSettings {
     const KEY: Option<&'static str> = None;
 
@@ -269,3 +477,57 @@ impl Settings for TabBarSettings {
         sources.json_merge()
     }
 }
+
+impl Settings for NotificationCenterSettings {
+    const KEY: Option<&'static str> = Some("notification_center");
+
+    type FileContent = NotificationCenterSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        sources.json_merge()
+    }
+}
+
+impl Settings for StatusBarSettings {
+    const KEY: Option<&'static str> = Some("status_bar");
+
+    type FileContent = StatusBarSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        sources.json_merge()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_do_not_disturb_schedule() {
+        let time = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+        let schedule = |start: &str, end: &str| DoNotDisturbSettings {
+            enabled: false,
+            schedule: Some(DoNotDisturbSchedule {
+                start: start.into(),
+                end: end.into(),
+            }),
+        };
+
+        let overnight = schedule("22:00", "08:00");
+        assert!(overnight.is_active_at(time(23, 30)));
+        assert!(overnight.is_active_at(time(7, 59)));
+        assert!(!overnight.is_active_at(time(8, 0)));
+        assert!(!overnight.is_active_at(time(12, 0)));
+
+        let lunch = schedule("12:00", "13:00");
+        assert!(lunch.is_active_at(time(12, 30)));
+        assert!(!lunch.is_active_at(time(13, 0)));
+
+        assert!(!schedule("noon", "13:00").is_active_at(time(12, 30)));
+        assert!(DoNotDisturbSettings {
+            enabled: true,
+            schedule: None,
+        }
+        .is_active_at(time(12, 0)));
+    }
+}
@@ -0,0 +1,329 @@
+//! The prompt to trust folders that are opened in restricted mode, and the modal to manage the
+//! trusted folders.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use collections::HashSet;
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    actions, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    ParentElement, Render, Styled, WeakEntity, Window,
+};
+use picker::{Picker, PickerDelegate};
+use project::{
+    workspace_trust::{distrust_folder, trust_folders, WorkspaceTrustSettings},
+    Project,
+};
+use settings::Settings as _;
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::{paths::PathExt as _, ResultExt as _};
+use workspace::{
+    notifications::{simple_message_notification::MessageNotification, NotificationId},
+    ModalView, Workspace,
+};
+
+actions!(workspace_trust, [TrustProjectFolders, ManageTrustedFolders]);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(register).detach();
+}
+
+fn register(workspace: &mut Workspace, _: Option<&mut Window>, cx: &mut Context<Workspace>) {
+    workspace.register_action(|workspace, _: &TrustProjectFolders, _, cx| {
+        trust_restricted_folders(workspace.project(), cx);
+    });
+    workspace.register_action(|workspace, _: &ManageTrustedFolders, window, cx| {
+        let project = workspace.project().clone();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            TrustedFoldersModal::new(project, window, cx)
+        });
+    });
+
+    // The folders that were already in restricted mode the last time the prompt was shown, so
+    // that it's only shown again for folders that weren't.
+    let mut prompted_folders = HashSet::default();
+    update_restricted_mode_prompt(workspace, &mut prompted_folders, cx);
+    cx.subscribe(workspace.project(), move |workspace, _, event, cx| {
+        if let project::Event::RestrictedWorktreesChanged = event {
+            update_restricted_mode_prompt(workspace, &mut prompted_folders, cx);
+        }
+    })
+    .detach();
+}
+
+struct RestrictedModePrompt;
+
+fn update_restricted_mode_prompt(
+    workspace: &mut Workspace,
+    prompted_folders: &mut HashSet<Arc<Path>>,
+    cx: &mut Context<Workspace>,
+) {
+    let notification_id = NotificationId::unique::<RestrictedModePrompt>();
+    let folders = restricted_folders(workspace.project(), cx);
+    if folders.is_empty() {
+        workspace.dismiss_notification(&notification_id, cx);
+    }
+    let has_new_folders = folders
+        .iter()
+        .any(|folder| !prompted_folders.contains(folder));
+    *prompted_folders = folders.iter().cloned().collect();
+    if !has_new_folders {
+        return;
+    }
+
+    let message = match folders.as_slice() {
+        [folder] => format!(
+            "{} is open in restricted mode, so its tasks and the project settings that run \
+             programs, like language server binaries, are ignored.",
+            folder.compact().display()
+        ),
+        _ => format!(
+            "{} folders are open in restricted mode, so their tasks and the project settings \
+             that run programs, like language server binaries, are ignored.",
+            folders.len()
+        ),
+    };
+    let project = workspace.project().downgrade();
+    workspace.show_notification(notification_id, cx, move |cx| {
+        cx.new(|cx| {
+            MessageNotification::new(message, cx)
+                .with_title("Do you trust the authors of this project?")
+                .primary_message("Trust")
+                .primary_icon(IconName::Check)
+                .primary_on_click(move |_, cx| {
+                    if let Some(project) = project.upgrade() {
+                        trust_restricted_folders(&project, cx);
+                    }
+                    cx.emit(DismissEvent);
+                })
+                .secondary_message("Manage Trusted Folders")
+                .secondary_on_click(|window, cx| {
+                    window.dispatch_action(Box::new(ManageTrustedFolders), cx);
+                    cx.emit(DismissEvent);
+                })
+        })
+    });
+}
+
+/// Returns the paths of the project's folders that are in restricted mode.
+fn restricted_folders(project: &Entity<Project>, cx: &App) -> Vec<Arc<Path>> {
+    let project = project.read(cx);
+    project
+        .visible_worktrees(cx)
+        .filter(|worktree| project.is_worktree_restricted(worktree.read(cx).id(), cx))
+        .map(|worktree| worktree.read(cx).abs_path())
+        .collect()
+}
+
+fn trust_restricted_folders(project: &Entity<Project>, cx: &App) {
+    let folders = restricted_folders(project, cx);
+    if !folders.is_empty() {
+        let fs = project.read(cx).fs().clone();
+        trust_folders(
+            folders.iter().map(|folder| folder.to_path_buf()).collect(),
+            fs,
+            cx,
+        );
+    }
+}
+
+pub struct TrustedFoldersModal {
+    picker: Entity<Picker<TrustedFoldersDelegate>>,
+}
+
+impl TrustedFoldersModal {
+    fn new(project: Entity<Project>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let delegate = TrustedFoldersDelegate::new(cx.entity().downgrade(), project, cx);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for TrustedFoldersModal {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for TrustedFoldersModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for TrustedFoldersModal {}
+impl ModalView for TrustedFoldersModal {}
+
+enum Folder {
+    /// A folder of the project that's in restricted mode, which is trusted when confirmed.
+    Restricted(Arc<Path>),
+    /// A trusted folder from the settings, which stops being trusted when confirmed.
+    Trusted(PathBuf),
+}
+
+pub struct TrustedFoldersDelegate {
+    modal: WeakEntity<TrustedFoldersModal>,
+    project: Entity<Project>,
+    folders: Vec<Folder>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl TrustedFoldersDelegate {
+    fn new(
+        modal: WeakEntity<TrustedFoldersModal>,
+        project: Entity<Project>,
+        cx: &mut Context<TrustedFoldersModal>,
+    ) -> Self {
+        let folders = restricted_folders(&project, cx)
+            .into_iter()
+            .map(Folder::Restricted)
+            .chain(
+                WorkspaceTrustSettings::get_global(cx)
+                    .trusted_folders
+                    .iter()
+                    .cloned()
+                    .map(Folder::Trusted),
+            )
+            .collect();
+        Self {
+            modal,
+            project,
+            folders,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+
+    fn candidates(&self) -> Vec<StringMatchCandidate> {
+        self.folders
+            .iter()
+            .enumerate()
+            .map(|(id, folder)| {
+                let path = match folder {
+                    Folder::Restricted(path) => path.compact(),
+                    Folder::Trusted(path) => path.compact(),
+                };
+                StringMatchCandidate::new(id, &path.to_string_lossy())
+            })
+            .collect()
+    }
+}
+
+impl PickerDelegate for TrustedFoldersDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Trust a restricted folder, or stop trusting a folder…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No trusted or restricted folders".into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(mat) = self.matches.get(self.selected_index) {
+            let fs = self.project.read(cx).fs().clone();
+            match &self.folders[mat.candidate_id] {
+                Folder::Restricted(path) => trust_folders(vec![path.to_path_buf()], fs, cx),
+                Folder::Trusted(path) => distrust_folder(path.clone(), fs, cx),
+            }
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.modal
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> gpui::Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = &self.matches[ix];
+        let (icon, status) = match &self.folders[mat.candidate_id] {
+            Folder::Restricted(_) => (IconName::LockOutlined, "Restricted"),
+            Folder::Trusted(_) => (IconName::Check, "Trusted"),
+        };
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(Icon::new(icon).color(Color::Muted))
+                .child(HighlightedLabel::new(
+                    mat.string.clone(),
+                    mat.positions.clone(),
+                ))
+                .end_slot(Label::new(status).color(Color::Muted)),
+        )
+    }
+}
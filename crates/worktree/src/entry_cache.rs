@@ -0,0 +1,115 @@
+use std::{
+    ffi::OsStr,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{anyhow, Result};
+use collections::HashMap;
+use fs::{Fs, MTime, Metadata};
+use serde::{Deserialize, Serialize};
+
+use crate::WorktreeSettings;
+
+/// Bumped whenever the format of the cache changes, so that older caches are ignored.
+const ENTRY_CACHE_VERSION: u32 = 1;
+
+/// The children of a directory as they were when it was last scanned. If the directory's mtime
+/// hasn't changed since, its children can be reused instead of reading the directory again.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct KnownDir {
+    pub mtime: MTime,
+    pub children: Vec<KnownChild>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct KnownChild {
+    pub name: String,
+    pub inode: u64,
+    pub mtime: MTime,
+    pub is_symlink: bool,
+    pub is_dir: bool,
+    pub len: u64,
+    pub is_fifo: bool,
+}
+
+impl KnownChild {
+    pub fn name(&self) -> &OsStr {
+        OsStr::new(&self.name)
+    }
+
+    pub fn metadata(&self) -> Metadata {
+        Metadata {
+            inode: self.inode,
+            mtime: self.mtime,
+            is_symlink: self.is_symlink,
+            is_dir: self.is_dir,
+            len: self.len,
+            is_fifo: self.is_fifo,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct EntryCache {
+    version: u32,
+    root_abs_path: PathBuf,
+    /// The `file_scan_exclusions` the entries were scanned with. Directories can't be reused when
+    /// these change, as they would lack the children that are no longer excluded.
+    file_scan_exclusions: Vec<String>,
+    dirs: Vec<(PathBuf, KnownDir)>,
+}
+
+/// Returns where the entries of the worktree with the given root are cached.
+pub(crate) fn entry_cache_path(root_abs_path: &Path) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    root_abs_path.hash(&mut hasher);
+    paths::worktree_cache_dir().join(format!("{:016x}.json", hasher.finish()))
+}
+
+pub(crate) async fn load_entry_cache(
+    fs: &dyn Fs,
+    cache_path: &Path,
+    root_abs_path: &Path,
+    settings: &WorktreeSettings,
+) -> Result<HashMap<Arc<Path>, KnownDir>> {
+    let cache: EntryCache = serde_json::from_str(&fs.load(cache_path).await?)?;
+    if cache.version != ENTRY_CACHE_VERSION {
+        return Err(anyhow!("entry cache has version {}", cache.version));
+    }
+    if cache.root_abs_path != root_abs_path {
+        return Err(anyhow!("entry cache is for {:?}", cache.root_abs_path));
+    }
+    if cache.file_scan_exclusions != settings.file_scan_exclusions.sources() {
+        return Err(anyhow!("entry cache was scanned with other exclusions"));
+    }
+    Ok(cache
+        .dirs
+        .into_iter()
+        .map(|(path, dir)| (path.into(), dir))
+        .collect())
+}
+
+pub(crate) async fn save_entry_cache(
+    fs: &dyn Fs,
+    cache_path: &Path,
+    root_abs_path: &Path,
+    settings: &WorktreeSettings,
+    dirs: Vec<(Arc<Path>, KnownDir)>,
+) -> Result<()> {
+    let cache = EntryCache {
+        version: ENTRY_CACHE_VERSION,
+        root_abs_path: root_abs_path.to_path_buf(),
+        file_scan_exclusions: settings.file_scan_exclusions.sources().to_vec(),
+        dirs: dirs
+            .into_iter()
+            .map(|(path, dir)| (path.to_path_buf(), dir))
+            .collect(),
+    };
+    if let Some(cache_dir) = cache_path.parent() {
+        fs.create_dir(cache_dir).await?;
+    }
+    fs.atomic_write(cache_path.to_path_buf(), serde_json::to_string(&cache)?)
+        .await
+}
@@ -1,3 +1,4 @@
+mod entry_cache;
 mod ignore;
 mod worktree_settings;
 #[cfg(test)]
@@ -7,6 +8,7 @@ use ::ignore::gitignore::{Gitignore, GitignoreBuilder};
 use anyhow::{anyhow, Context as _, Result};
 use clock::ReplicaId;
 use collections::{HashMap, HashSet, VecDeque};
+use entry_cache::{entry_cache_path, load_entry_cache, save_entry_cache, KnownChild, KnownDir};
 use fs::{copy_recursive, Fs, MTime, PathEvent, RemoveOptions, Watcher};
 use futures::{
     channel::{
@@ -67,7 +69,7 @@ use std::{
     time::{Duration, Instant},
 };
 use sum_tree::{Bias, Edit, KeyedItem, SeekTarget, SumTree, Summary, TreeMap, TreeSet, Unit};
-use text::{LineEnding, Rope};
+use text::{Encoding, LineEnding, Rope};
 use util::{
     paths::{home_dir, PathMatcher, SanitizedPath},
     ResultExt,
@@ -75,6 +77,13 @@ use util::{
 pub use worktree_settings::WorktreeSettings;
 
 pub const FS_WATCH_LATENCY: Duration = Duration::from_millis(100);
+/// Batches of FS events at least this large are followed by more events for a while, for
+/// example when switching branches, so the scanner waits for them before rescanning.
+const LARGE_EVENT_BATCH_LEN: usize = 500;
+/// The longest the scanner waits for a burst of FS events to settle.
+const MAX_EVENT_BATCH_DURATION: Duration = Duration::from_secs(1);
+/// How often the scanned entries are persisted while FS events keep arriving.
+const ENTRY_CACHE_SAVE_INTERVAL: Duration = Duration::from_secs(60);
 
 /// A set of local or remote files that are being opened as part of a project.
 /// Responsible for tracking related FS (for local)/collab (for remote) events and corresponding updates.
@@ -104,6 +113,10 @@ pub enum CreatedEntry {
 pub struct LoadedFile {
     pub file: Arc<File>,
     pub text: String,
+    /// The encoding the file's contents were detected to be in.
+    pub encoding: Encoding,
+    /// Whether the file's contents start with a byte order mark.
+    pub has_bom: bool,
 }
 
 pub struct LoadedBinaryFile {
@@ -600,6 +613,12 @@ struct BackgroundScannerState {
     prev_snapshot: Snapshot,
     git_hosting_provider_registry: Option<Arc<GitHostingProviderRegistry>>,
     repository_scans: HashMap<PathKey, Task<()>>,
+    /// Listings of directories from a previous scan, which are reused instead of reading the
+    /// directories again if their mtime hasn't changed.
+    known_dirs: HashMap<Arc<Path>, KnownDir>,
+    /// Directories that FS events were received for in the current update. Their listings are
+    /// never reused, as a change may not have updated their mtime yet.
+    dirs_with_events: HashSet<Arc<Path>>,
 }
 
 #[derive(Debug, Clone)]
@@ -1096,10 +1115,14 @@ impl Worktree {
         path: &Path,
         text: Rope,
         line_ending: LineEnding,
+        encoding: Encoding,
+        has_bom: bool,
         cx: &Context<Worktree>,
     ) -> Task<Result<Arc<File>>> {
         match self {
-            Worktree::Local(this) => this.write_file(path, text, line_ending, cx),
+            Worktree::Local(this) => {
+                this.write_file(path, text, line_ending, encoding, has_bom, cx)
+            }
             Worktree::Remote(_) => {
                 Task::ready(Err(anyhow!("remote worktree can't yet write files")))
             }
@@ -1487,6 +1510,9 @@ impl LocalWorktree {
         let fs = self.fs.clone();
         let git_hosting_provider_registry = GitHostingProviderRegistry::try_global(cx);
         let settings = self.settings.clone();
+        // Tests don't persist entries, so that they don't depend on each other.
+        let entry_cache_path = (self.visible && !cfg!(any(test, feature = "test-support")))
+            .then(|| entry_cache_path(snapshot.abs_path.as_path()));
         let (scan_states_tx, mut scan_states_rx) = mpsc::unbounded();
         let background_scanner = cx.background_spawn({
             let abs_path = snapshot.abs_path.as_path().to_path_buf();
@@ -1517,11 +1543,14 @@ impl LocalWorktree {
                         changed_paths: Default::default(),
                         repository_scans: HashMap::default(),
                         git_hosting_provider_registry,
+                        known_dirs: HashMap::default(),
+                        dirs_with_events: HashSet::default(),
                     })),
                     phase: BackgroundScannerPhase::InitialScan,
                     share_private_files,
                     settings,
                     watcher,
+                    entry_cache_path,
                 };
 
                 scanner
@@ -1755,7 +1784,7 @@ impl LocalWorktree {
         let entry = self.refresh_entry(path.clone(), None, cx);
         let is_private = self.is_path_private(path.as_ref());
 
-        cx.spawn(async move |this, _cx| {
+        cx.spawn(async move |this, cx| {
             let abs_path = abs_path?;
             // WARN: Temporary workaround for #27283.
             //       We are not efficient with our memory usage per file, and use in excess of 64GB for a 10GB file
@@ -1771,7 +1800,16 @@ impl LocalWorktree {
                     }
                 }
             }
-            let text = fs.load(&abs_path).await?;
+            let bytes = fs.load_bytes(&abs_path).await?;
+            let (text, encoding, has_bom) = cx
+                .background_spawn(async move {
+                    let has_bom = Encoding::ALL
+                        .iter()
+                        .any(|encoding| encoding.has_bom(&bytes));
+                    let (text, encoding) = Encoding::detect_and_decode(bytes)?;
+                    anyhow::Ok((text, encoding, has_bom && encoding.bom().is_some()))
+                })
+                .await?;
 
             let worktree = this
                 .upgrade()
@@ -1801,7 +1839,12 @@ impl LocalWorktree {
                 }
             };
 
-            Ok(LoadedFile { file, text })
+            Ok(LoadedFile {
+                file,
+                text,
+                encoding,
+                has_bom,
+            })
         })
     }
 
@@ -1887,6 +1930,8 @@ impl LocalWorktree {
         path: impl Into<Arc<Path>>,
         text: Rope,
         line_ending: LineEnding,
+        encoding: Encoding,
+        has_bom: bool,
         cx: &Context<Worktree>,
     ) -> Task<Result<Arc<File>>> {
         let path = path.into();
@@ -1899,7 +1944,17 @@ impl LocalWorktree {
         let write = cx.background_spawn({
             let fs = fs.clone();
             let abs_path = abs_path.clone();
-            async move { fs.save(&abs_path, &text, line_ending).await }
+            async move {
+                if encoding == Encoding::Utf8 && !has_bom {
+                    return fs.save(&abs_path, &text, line_ending).await;
+                }
+                let mut content = text.to_string();
+                if line_ending != LineEnding::Unix {
+                    content = content.replace('\n', line_ending.as_str());
+                }
+                fs.write(&abs_path, &encoding.encode(&content, has_bom)?)
+                    .await
+            }
         });
 
         cx.spawn(async move |this, cx| {
@@ -2902,6 +2957,60 @@ impl Snapshot {
 }
 
 impl LocalSnapshot {
+    /// Returns the listings of the loaded directories at or below the given path, so that they
+    /// can be reused when those directories haven't changed.
+    fn known_dirs(&self, path: &Path) -> Vec<(Arc<Path>, KnownDir)> {
+        // `.git` directories aren't in the snapshot, so the directories containing them are
+        // always read again to find their repositories.
+        let git_parent_abs_paths = self
+            .git_repositories
+            .values()
+            .flat_map(|repo| {
+                [
+                    Some(&repo.dot_git_dir_abs_path),
+                    repo.dot_git_worktree_abs_path.as_ref(),
+                ]
+            })
+            .flatten()
+            .filter_map(|abs_path| abs_path.parent())
+            .collect::<HashSet<_>>();
+
+        self.traverse_from_path(false, true, true, path)
+            .take_while(|entry| entry.path.starts_with(path))
+            .filter_map(|entry| {
+                if entry.kind != EntryKind::Dir || entry.is_external {
+                    return None;
+                }
+                let mtime = entry.mtime?;
+                let abs_path = self.abs_path.as_path().join(&entry.path);
+                if git_parent_abs_paths.contains(abs_path.as_path()) {
+                    return None;
+                }
+                // Directories with a `.gitignore` are read again too, in case it was edited
+                // in place without changing the directory's mtime.
+                let children = self
+                    .child_entries(&entry.path)
+                    .map(|child| {
+                        let name = child.path.file_name()?;
+                        if name == *GITIGNORE {
+                            return None;
+                        }
+                        Some(KnownChild {
+                            name: name.to_str()?.to_string(),
+                            inode: child.inode,
+                            mtime: child.mtime?,
+                            is_symlink: child.canonical_path.is_some(),
+                            is_dir: child.is_dir(),
+                            len: child.size,
+                            is_fifo: child.is_fifo,
+                        })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some((entry.path.clone(), KnownDir { mtime, children }))
+            })
+            .collect()
+    }
+
     pub fn local_repo_for_work_directory_path(&self, path: &Path) -> Option<&LocalRepositoryEntry> {
         self.git_repositories
             .iter()
@@ -4217,6 +4326,8 @@ struct BackgroundScanner {
     watcher: Arc<dyn Watcher>,
     settings: WorktreeSettings,
     share_private_files: bool,
+    /// Where the scanned entries are persisted, if they are.
+    entry_cache_path: Option<PathBuf>,
 }
 
 #[derive(Copy, Clone, PartialEq)]
@@ -4298,6 +4409,24 @@ impl BackgroundScanner {
 
         log::info!("containing git repository: {containing_git_repository:?}");
 
+        // Reuse the listings of directories that haven't changed since the worktree was last
+        // scanned, so that reopening a large worktree doesn't have to read all of it again.
+        if let Some(cache_path) = &self.entry_cache_path {
+            match load_entry_cache(
+                self.fs.as_ref(),
+                cache_path,
+                root_abs_path.as_path(),
+                &self.settings,
+            )
+            .await
+            {
+                Ok(known_dirs) => self.state.lock().known_dirs = known_dirs,
+                Err(error) => {
+                    log::debug!("not reusing cached entries of {root_abs_path:?}: {error:#}")
+                }
+            }
+        }
+
         let (scan_job_tx, scan_job_rx) = channel::unbounded();
         {
             let mut state = self.state.lock();
@@ -4320,7 +4449,10 @@ impl BackgroundScanner {
         {
             let mut state = self.state.lock();
             state.snapshot.completed_scan_id = state.snapshot.scan_id;
+            state.known_dirs.clear();
         }
+        self.save_entry_cache();
+        let mut entry_cache_saved_at = self.executor.now();
 
         let scanning = self.scans_running.load(atomic::Ordering::Acquire) > 0;
         self.send_status_update(scanning, SmallVec::new());
@@ -4381,7 +4513,28 @@ impl BackgroundScanner {
                     while let Poll::Ready(Some(more_paths)) = futures::poll!(fs_events_rx.next()) {
                         paths.extend(more_paths);
                     }
+
+                    // Wait for bursts of events to settle, so that the affected directories
+                    // are rescanned once rather than after every batch.
+                    if paths.len() >= LARGE_EVENT_BATCH_LEN {
+                        let batch_started_at = self.executor.now();
+                        while self.executor.now() - batch_started_at < MAX_EVENT_BATCH_DURATION {
+                            let mut quiet_timer = self.executor.timer(FS_WATCH_LATENCY).fuse();
+                            select_biased! {
+                                more_paths = fs_events_rx.next().fuse() => {
+                                    let Some(more_paths) = more_paths else { break };
+                                    paths.extend(more_paths);
+                                }
+                                _ = quiet_timer => break,
+                            }
+                        }
+                    }
+
                     self.process_events(paths.into_iter().map(Into::into).collect()).await;
+                    if self.executor.now() - entry_cache_saved_at >= ENTRY_CACHE_SAVE_INTERVAL {
+                        self.save_entry_cache();
+                        entry_cache_saved_at = self.executor.now();
+                    }
                 }
             }
         }
@@ -4472,6 +4625,17 @@ impl BackgroundScanner {
 
         let mut relative_paths = Vec::with_capacity(abs_paths.len());
         let mut dot_git_abs_paths = Vec::new();
+        {
+            let mut state = self.state.lock();
+            for abs_path in &abs_paths {
+                if let Ok(path) = abs_path.strip_prefix(root_canonical_path.as_path()) {
+                    state.dirs_with_events.insert(path.into());
+                    if let Some(parent) = path.parent() {
+                        state.dirs_with_events.insert(parent.into());
+                    }
+                }
+            }
+        }
         abs_paths.sort_unstable();
         abs_paths.dedup_by(|a, b| a.starts_with(b));
         abs_paths.retain(|abs_path| {
@@ -4549,6 +4713,7 @@ impl BackgroundScanner {
         });
 
         if relative_paths.is_empty() && dot_git_abs_paths.is_empty() {
+            self.state.lock().dirs_with_events.clear();
             return;
         }
 
@@ -4567,6 +4732,11 @@ impl BackgroundScanner {
 
         self.update_ignore_statuses(scan_job_tx).await;
         self.scan_dirs(false, scan_job_rx).await;
+        {
+            let mut state = self.state.lock();
+            state.known_dirs.clear();
+            state.dirs_with_events.clear();
+        }
 
         let status_update = if !dot_git_abs_paths.is_empty() {
             Some(self.update_git_repositories(dot_git_abs_paths))
@@ -4699,6 +4869,31 @@ impl BackgroundScanner {
         dec_scans_running(&self.scans_running, 1);
     }
 
+    /// Persists the listings of the scanned directories in the background, so that the next
+    /// initial scan of this worktree can reuse the ones that haven't changed.
+    fn save_entry_cache(&self) {
+        let Some(cache_path) = self.entry_cache_path.clone() else {
+            return;
+        };
+        let snapshot = self.state.lock().snapshot.clone();
+        let fs = self.fs.clone();
+        let settings = self.settings.clone();
+        self.executor
+            .spawn(async move {
+                let known_dirs = snapshot.known_dirs(Path::new(""));
+                save_entry_cache(
+                    fs.as_ref(),
+                    &cache_path,
+                    snapshot.abs_path.as_path(),
+                    &settings,
+                    known_dirs,
+                )
+                .await
+                .log_err();
+            })
+            .detach();
+    }
+
     fn send_status_update(&self, scanning: bool, barrier: SmallVec<[barrier::Sender; 1]>) -> bool {
         send_status_update_inner(
             self.phase,
@@ -4712,15 +4907,21 @@ impl BackgroundScanner {
     async fn scan_dir(&self, job: &ScanJob) -> Result<()> {
         let root_abs_path;
         let root_char_bag;
+        let known_dir;
         {
-            let snapshot = &self.state.lock().snapshot;
+            let mut state = self.state.lock();
             if self.settings.is_path_excluded(&job.path) {
                 log::error!("skipping excluded directory {:?}", job.path);
                 return Ok(());
             }
             log::info!("scanning directory {:?}", job.path);
-            root_abs_path = snapshot.abs_path().clone();
-            root_char_bag = snapshot.root_char_bag;
+            root_abs_path = state.snapshot.abs_path().clone();
+            root_char_bag = state.snapshot.root_char_bag;
+            known_dir = if state.dirs_with_events.contains(&job.path) {
+                None
+            } else {
+                state.known_dirs.remove(&job.path)
+            };
         }
 
         let next_entry_id = self.next_entry_id.clone();
@@ -4729,21 +4930,44 @@ impl BackgroundScanner {
         let mut root_canonical_path = None;
         let mut new_entries: Vec<Entry> = Vec::new();
         let mut new_jobs: Vec<Option<ScanJob>> = Vec::new();
-        let mut child_paths = self
-            .fs
-            .read_dir(&job.abs_path)
-            .await?
-            .filter_map(|entry| async {
-                match entry {
-                    Ok(entry) => Some(entry),
-                    Err(error) => {
-                        log::error!("error processing entry {:?}", error);
-                        None
+        let known_children = match known_dir {
+            Some(known_dir) => self
+                .fs
+                .metadata(&job.abs_path)
+                .await
+                .ok()
+                .flatten()
+                .filter(|metadata| metadata.mtime == known_dir.mtime)
+                .map(|_| known_dir.children),
+            None => None,
+        };
+        let mut known_metadata = HashMap::default();
+        let mut child_paths = if let Some(known_children) = known_children {
+            log::debug!("reusing the unchanged listing of directory {:?}", job.path);
+            known_children
+                .into_iter()
+                .map(|child| {
+                    let child_abs_path = job.abs_path.join(child.name());
+                    known_metadata.insert(child_abs_path.clone(), child.metadata());
+                    child_abs_path
+                })
+                .collect()
+        } else {
+            self.fs
+                .read_dir(&job.abs_path)
+                .await?
+                .filter_map(|entry| async {
+                    match entry {
+                        Ok(entry) => Some(entry),
+                        Err(error) => {
+                            log::error!("error processing entry {:?}", error);
+                            None
+                        }
                     }
-                }
-            })
-            .collect::<Vec<_>>()
-            .await;
+                })
+                .collect::<Vec<_>>()
+                .await
+        };
 
         // Ensure that .git and .gitignore are processed first.
         swap_to_front(&mut child_paths, *GITIGNORE);
@@ -4792,14 +5016,19 @@ impl BackgroundScanner {
                 continue;
             }
 
-            let child_metadata = match self.fs.metadata(&child_abs_path).await {
-                Ok(Some(metadata)) => metadata,
-                Ok(None) => continue,
-                Err(err) => {
-                    log::error!("error processing {child_abs_path:?}: {err:?}");
-                    continue;
-                }
-            };
+            let child_metadata =
+                if let Some(metadata) = known_metadata.remove(child_abs_path.as_ref()) {
+                    metadata
+                } else {
+                    match self.fs.metadata(&child_abs_path).await {
+                        Ok(Some(metadata)) => metadata,
+                        Ok(None) => continue,
+                        Err(err) => {
+                            log::error!("error processing {child_abs_path:?}: {err:?}");
+                            continue;
+                        }
+                    }
+                };
 
             let mut child_entry = Entry::new(
                 child_path.clone(),
@@ -4994,6 +5223,14 @@ impl BackgroundScanner {
         // detected regardless of the order of the paths.
         for (path, metadata) in relative_paths.iter().zip(metadata.iter()) {
             if matches!(metadata, Ok(None)) || doing_recursive_update {
+                // Remember the directories below the path, so that the ones that haven't
+                // changed don't have to be read again when it is rescanned.
+                if doing_recursive_update
+                    && matches!(metadata, Ok(Some((metadata, _))) if metadata.is_dir)
+                {
+                    let known_dirs = state.snapshot.known_dirs(path);
+                    state.known_dirs.extend(known_dirs);
+                }
                 log::trace!("remove path {:?}", path);
                 state.remove_path(path);
             }
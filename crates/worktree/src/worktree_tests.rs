@@ -1,6 +1,7 @@
 use crate::{
-    worktree_settings::WorktreeSettings, Entry, EntryKind, Event, PathChange, StatusEntry,
-    WorkDirectory, Worktree, WorktreeModelHandle,
+    entry_cache::{load_entry_cache, save_entry_cache},
+    worktree_settings::WorktreeSettings,
+    Entry, EntryKind, Event, PathChange, StatusEntry, WorkDirectory, Worktree, WorktreeModelHandle,
 };
 use anyhow::Result;
 use fs::{FakeFs, Fs, RealFs, RemoveOptions};
@@ -25,7 +26,8 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
-use util::{path, test::TempTree, ResultExt};
+use text::{Encoding, LineEnding};
+use util::{path, paths::PathMatcher, test::TempTree, ResultExt};
 
 #[gpui::test]
 async fn test_traversal(cx: &mut TestAppContext) {
@@ -896,6 +898,8 @@ async fn test_write_file(cx: &mut TestAppContext) {
                 Path::new("tracked-dir/file.txt"),
                 "hello".into(),
                 Default::default(),
+                Default::default(),
+                false,
                 cx,
             )
         })
@@ -907,6 +911,8 @@ async fn test_write_file(cx: &mut TestAppContext) {
                 Path::new("ignored-dir/file.txt"),
                 "world".into(),
                 Default::default(),
+                Default::default(),
+                false,
                 cx,
             )
         })
@@ -921,6 +927,83 @@ async fn test_write_file(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_load_and_write_file_with_encoding(cx: &mut TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree("/root", json!({})).await;
+    fs.insert_file("/root/latin1.txt", b"caf\xe9 cr\xe8me\r\n".to_vec())
+        .await;
+    fs.insert_file("/root/binary.bin", vec![0x7f, 0x45, 0x4c, 0x46, 0x00, 0xff])
+        .await;
+
+    let tree = Worktree::local(
+        Path::new("/root"),
+        true,
+        fs.clone(),
+        Default::default(),
+        &mut cx.to_async(),
+    )
+    .await
+    .unwrap();
+    cx.read(|cx| tree.read(cx).as_local().unwrap().scan_complete())
+        .await;
+
+    let loaded = tree
+        .update(cx, |tree, cx| tree.load_file("latin1.txt".as_ref(), cx))
+        .await
+        .unwrap();
+    assert_eq!(loaded.text, "café crème\r\n");
+    assert_eq!(loaded.encoding, Encoding::Latin1);
+    assert!(!loaded.has_bom);
+
+    // Binary files still fail to load as text.
+    tree.update(cx, |tree, cx| tree.load_file("binary.bin".as_ref(), cx))
+        .await
+        .unwrap_err();
+
+    tree.update(cx, |tree, cx| {
+        tree.write_file(
+            Path::new("latin1.txt"),
+            "crème brûlée\n".into(),
+            LineEnding::Windows,
+            Encoding::Latin1,
+            false,
+            cx,
+        )
+    })
+    .await
+    .unwrap();
+    assert_eq!(
+        fs.read_file_sync("/root/latin1.txt").unwrap(),
+        b"cr\xe8me br\xfbl\xe9e\r\n"
+    );
+
+    tree.update(cx, |tree, cx| {
+        tree.write_file(
+            Path::new("utf16.txt"),
+            "hi".into(),
+            Default::default(),
+            Encoding::Utf16Le,
+            true,
+            cx,
+        )
+    })
+    .await
+    .unwrap();
+    assert_eq!(
+        fs.read_file_sync("/root/utf16.txt").unwrap(),
+        [0xff, 0xfe, b'h', 0, b'i', 0]
+    );
+    let loaded = tree
+        .update(cx, |tree, cx| tree.load_file("utf16.txt".as_ref(), cx))
+        .await
+        .unwrap();
+    assert_eq!(loaded.text, "hi");
+    assert_eq!(loaded.encoding, Encoding::Utf16Le);
+    assert!(loaded.has_bom);
+}
+
 #[gpui::test]
 async fn test_file_scan_inclusions(cx: &mut TestAppContext) {
     init_test(cx);
@@ -1403,6 +1486,104 @@ async fn test_fs_events_in_dot_git_worktree(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_entry_cache(cx: &mut TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        "/root",
+        json!({
+            ".git": {},
+            "a": {
+                ".gitignore": "*.log\n",
+                "b.txt": "",
+            },
+            "c": {
+                "d": {
+                    "e.txt": "",
+                },
+                "f.txt": "",
+            },
+        }),
+    )
+    .await;
+
+    let tree = Worktree::local(
+        Path::new("/root"),
+        true,
+        fs.clone(),
+        Default::default(),
+        &mut cx.to_async(),
+    )
+    .await
+    .unwrap();
+    cx.read(|cx| tree.read(cx).as_local().unwrap().scan_complete())
+        .await;
+
+    // Directories containing a `.git` or a `.gitignore` are always read again.
+    let (known_dirs, settings) = tree.read_with(cx, |tree, _| {
+        let tree = tree.as_local().unwrap();
+        (
+            tree.snapshot.known_dirs(Path::new("")),
+            tree.settings.clone(),
+        )
+    });
+    let listings = known_dirs
+        .iter()
+        .map(|(path, dir)| {
+            let children = dir
+                .children
+                .iter()
+                .map(|child| child.name.as_str())
+                .collect::<Vec<_>>();
+            (path.as_ref(), children)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        listings,
+        [
+            (Path::new("c"), vec!["d", "f.txt"]),
+            (Path::new("c/d"), vec!["e.txt"]),
+        ]
+    );
+
+    let cache_path = Path::new("/cache/root.json");
+    save_entry_cache(
+        fs.as_ref(),
+        cache_path,
+        Path::new("/root"),
+        &settings,
+        known_dirs.clone(),
+    )
+    .await
+    .unwrap();
+    let loaded = load_entry_cache(fs.as_ref(), cache_path, Path::new("/root"), &settings)
+        .await
+        .unwrap();
+    assert_eq!(
+        loaded,
+        known_dirs
+            .into_iter()
+            .collect::<collections::HashMap<_, _>>()
+    );
+
+    // Caches of other worktrees, or scanned with other exclusions, aren't used.
+    assert!(
+        load_entry_cache(fs.as_ref(), cache_path, Path::new("/other"), &settings)
+            .await
+            .is_err()
+    );
+    let other_settings = WorktreeSettings {
+        file_scan_exclusions: PathMatcher::new(["**/c"]).unwrap(),
+        ..settings
+    };
+    assert!(
+        load_entry_cache(fs.as_ref(), cache_path, Path::new("/root"), &other_settings)
+            .await
+            .is_err()
+    );
+}
+
 #[gpui::test(iterations = 30)]
 async fn test_create_directory_during_initial_scan(cx: &mut TestAppContext) {
     init_test(cx);
@@ -1924,8 +2105,14 @@ fn randomly_mutate_worktree(
                 })
             } else {
                 log::info!("overwriting file {:?} ({})", entry.path, entry.id.0);
-                let task =
-                    worktree.write_file(entry.path.clone(), "".into(), Default::default(), cx);
+                let task = worktree.write_file(
+                    entry.path.clone(),
+                    "".into(),
+                    Default::default(),
+                    Default::default(),
+                    false,
+                    cx,
+                );
                 cx.background_spawn(async move {
                     task.await?;
                     Ok(())
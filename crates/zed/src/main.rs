@@ -22,7 +22,6 @@ use git::GitHostingProviderRegistry;
 use gpui::{App, AppContext as _, Application, AsyncApp, UpdateGlobal as _};
 
 use gpui_tokio::Tokio;
-use http_client::{read_proxy_from_env, Uri};
 use language::LanguageRegistry;
 use prompt_store::PromptBuilder;
 use reqwest_client::ReqwestClient;
@@ -54,8 +53,8 @@ use workspace::{AppState, SerializedWorkspaceLocation, WorkspaceSettings, Worksp
 use zed::{
     app_menus, build_window_options, derive_paths_with_position, handle_cli_connection,
     handle_keymap_file_changes, handle_settings_changed, handle_settings_file_changes,
-    initialize_workspace, inline_completion_registry, open_paths_with_positions, OpenListener,
-    OpenRequest,
+    initialize_workspace, inline_completion_registry, open_paths_with_positions, startup_timings,
+    OpenListener, OpenRequest,
 };
 
 #[cfg(unix)]
@@ -170,6 +169,7 @@ fn fail_to_open_window(e: anyhow::Error, _cx: &mut App) {
 }
 
 fn main() {
+    startup_timings::start();
     let args = Args::parse();
 
     #[cfg(all(not(debug_assertions), target_os = "windows"))]
@@ -207,6 +207,7 @@ fn main() {
     let app_version = AppVersion::init(env!("CARGO_PKG_VERSION"));
     let app_commit_sha =
         option_env!("ZED_COMMIT_SHA").map(|commit_sha| AppCommitSha(commit_sha.to_string()));
+    startup_timings::record_phase("Load session");
 
     reliability::init_panic_hook(
         app_version,
@@ -306,6 +307,8 @@ fn main() {
         }
     });
 
+    startup_timings::record_phase("Set up application");
+
     app.run(move |cx| {
         release_channel::init(app_version, cx);
         gpui_tokio::init(cx);
@@ -323,20 +326,13 @@ fn main() {
             std::env::consts::OS,
             std::env::consts::ARCH
         );
-        let proxy_str = ProxySettings::get_global(cx).proxy.to_owned();
-        let proxy_url = proxy_str
-            .as_ref()
-            .and_then(|input| {
-                input
-                    .parse::<Uri>()
-                    .inspect_err(|e| log::error!("Error parsing proxy settings: {}", e))
-                    .ok()
-            })
-            .or_else(read_proxy_from_env);
+        let proxy_settings = ProxySettings::get_global(cx);
+        http_client_tls::set_custom_ca_certificates(&proxy_settings.ca_certificates).log_err();
+        let proxy_config = proxy_settings.proxy_config();
         let http = {
             let _guard = Tokio::handle(cx).enter();
 
-            ReqwestClient::proxy_and_user_agent(proxy_url, &user_agent)
+            ReqwestClient::proxy_config_and_user_agent(proxy_config, &user_agent)
                 .expect("could not start HTTP client")
         };
         cx.set_http_client(Arc::new(http));
@@ -386,6 +382,7 @@ fn main() {
         language::init(cx);
         language_extension::init(extension_host_proxy.clone(), languages.clone());
         languages::init(languages.clone(), node_runtime.clone(), cx);
+        startup_timings::record_phase("Register languages");
         let user_store = cx.new(|cx| UserStore::new(client.clone(), cx));
         let workspace_store = cx.new(|cx| WorkspaceStore::new(client.clone(), cx));
 
@@ -453,6 +450,7 @@ fn main() {
             cx.background_executor().clone(),
         );
         command_palette::init(cx);
+        clipboard_history_ui::init(cx);
         let copilot_language_server_id = app_state.languages.next_language_server_id();
         copilot::init(
             copilot_language_server_id,
@@ -470,6 +468,7 @@ fn main() {
             cx,
         );
         snippet_provider::init(cx);
+        localization::init(cx);
         inline_completion_registry::init(
             app_state.client.clone(),
             app_state.user_store.clone(),
@@ -488,6 +487,7 @@ fn main() {
             prompt_builder.clone(),
             cx,
         );
+        semantic_index::init(app_state.client.clone(), cx);
         assistant_tools::init(app_state.client.http_client(), cx);
         repl::init(app_state.fs.clone(), cx);
         extension_host::init(
@@ -497,6 +497,7 @@ fn main() {
             app_state.node_runtime.clone(),
             cx,
         );
+        startup_timings::record_phase("Start extension host");
         recent_projects::init(cx);
 
         load_embedded_fonts(cx);
@@ -504,6 +505,7 @@ fn main() {
         app_state.languages.set_theme(cx.theme().clone());
         editor::init(cx);
         image_viewer::init(cx);
+        hex_editor::init(cx);
         repl::notebook::init(cx);
         diagnostics::init(cx);
 
@@ -519,26 +521,33 @@ fn main() {
         project_panel::init(cx);
         outline_panel::init(cx);
         tasks_ui::init(cx);
+        test_runner::init(cx);
         snippets_ui::init(cx);
+        spell_check::init(app_state.fs.clone(), cx);
         channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
         search::init(cx);
         vim::init(cx);
         terminal_view::init(cx);
         journal::init(app_state.clone(), cx);
         language_selector::init(cx);
+        encoding_selector::init(cx);
+        workspace_trust_ui::init(cx);
         toolchain_selector::init(cx);
         theme_selector::init(cx);
         language_tools::init(cx);
         call::init(app_state.client.clone(), app_state.user_store.clone(), cx);
         notifications::init(app_state.client.clone(), app_state.user_store.clone(), cx);
+        notification_center::init(cx);
         collab_ui::init(&app_state, cx);
         git_ui::init(cx);
         feedback::init(cx);
         markdown_preview::init(cx);
+        csv_preview::init(cx);
         welcome::init(cx);
         settings_ui::init(cx);
         extensions_ui::init(cx);
         zeta::init(cx);
+        startup_timings::record_phase("Initialize features");
 
         cx.observe_global::<SettingsStore>({
             let fs = fs.clone();
@@ -585,7 +594,9 @@ fn main() {
         watch_themes(fs.clone(), cx);
         watch_languages(fs.clone(), app_state.languages.clone(), cx);
 
-        cx.set_menus(app_menus());
+        cx.set_menus(app_menus(cx));
+        cx.observe_global::<localization::Localization>(|cx| cx.set_menus(app_menus(cx)))
+            .detach();
         initialize_workspace(app_state.clone(), prompt_builder, cx);
 
         cx.activate(true);
@@ -698,6 +709,28 @@ fn handle_open_request(request: OpenRequest, app_state: Arc<AppState>, cx: &mut
         }));
     }
 
+    if let Some(extension_id) = request.open_extension {
+        cx.spawn(async move |cx| {
+            if let Some(task) = task {
+                task.await?;
+            }
+            let workspace_window =
+                workspace::get_any_active_workspace(app_state, cx.clone()).await?;
+            workspace_window.update(cx, |_, window, cx| {
+                window.activate_window();
+                window.dispatch_action(
+                    Box::new(zed_actions::Extensions {
+                        category_filter: None,
+                        id: Some(extension_id),
+                    }),
+                    cx,
+                );
+            })
+        })
+        .detach_and_log_err(cx);
+        return;
+    }
+
     if !request.open_channel_notes.is_empty() || request.join_channel.is_some() {
         cx.spawn(async move |mut cx| {
             let result = maybe!(async {
@@ -814,18 +847,20 @@ async fn installation_id() -> Result<IdType> {
 
 async fn restore_or_create_workspace(app_state: Arc<AppState>, cx: &mut AsyncApp) -> Result<()> {
     if let Some(locations) = restorable_workspace_locations(cx, &app_state).await {
+        // Local workspaces are opened concurrently, so that restoring several large workspaces
+        // doesn't take as long as restoring each of them in turn.
+        let mut local_workspace_tasks = Vec::new();
         for location in locations {
             match location {
                 SerializedWorkspaceLocation::Local(location, _) => {
-                    let task = cx.update(|cx| {
+                    local_workspace_tasks.push(cx.update(|cx| {
                         workspace::open_paths(
                             location.paths().as_ref(),
                             app_state.clone(),
                             workspace::OpenOptions::default(),
                             cx,
                         )
-                    })?;
-                    task.await?;
+                    })?);
                 }
                 SerializedWorkspaceLocation::Ssh(ssh) => {
                     let connection_options = cx.update(|cx| {
@@ -848,6 +883,15 @@ async fn restore_or_create_workspace(app_state: Arc<AppState>, cx: &mut AsyncApp
                 }
             }
         }
+
+        // Activate the windows in the order they were stacked in, regardless of which finished
+        // opening first.
+        for result in future::join_all(local_workspace_tasks).await {
+            let (window, _) = result?;
+            window
+                .update(cx, |_, window, _| window.activate_window())
+                .log_err();
+        }
     } else if matches!(KEY_VALUE_STORE.read_kvp(FIRST_OPEN), Ok(None)) {
         cx.update(|cx| show_welcome_view(app_state, cx))?.await?;
     } else {
@@ -864,6 +908,7 @@ async fn restore_or_create_workspace(app_state: Arc<AppState>, cx: &mut AsyncApp
         .await?;
     }
 
+    startup_timings::record_phase("Restore workspaces");
     Ok(())
 }
 
@@ -1051,7 +1096,8 @@ fn eager_load_active_theme_and_icon_theme(fs: Arc<dyn Fs>, cx: &App) {
     let appearance = SystemAppearance::global(cx).0;
 
     if let Some(theme_selection) = theme_settings.theme_selection.as_ref() {
-        let theme_name = theme_selection.theme(appearance);
+        let theme_name = theme_selection
+            .theme(theme_settings.followed_appearance(theme_selection.mode(), appearance));
         if matches!(theme_registry.get(theme_name), Err(ThemeNotFoundError(_))) {
             if let Some(theme_path) = extension_store.read(cx).path_to_extension_theme(theme_name) {
                 cx.spawn({
@@ -1071,7 +1117,9 @@ fn eager_load_active_theme_and_icon_theme(fs: Arc<dyn Fs>, cx: &App) {
     }
 
     if let Some(icon_theme_selection) = theme_settings.icon_theme_selection.as_ref() {
-        let icon_theme_name = icon_theme_selection.icon_theme(appearance);
+        let icon_theme_name = icon_theme_selection.icon_theme(
+            theme_settings.followed_appearance(icon_theme_selection.mode(), appearance),
+        );
         if matches!(
             theme_registry.get_icon_theme(icon_theme_name),
             Err(IconThemeNotFoundError(_))
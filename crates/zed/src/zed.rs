@@ -1,10 +1,13 @@
 mod app_menus;
+pub(crate) mod cli_batch;
+mod connection_diagnostics;
 pub mod inline_completion_registry;
 #[cfg(target_os = "macos")]
 pub(crate) mod mac_only_instance;
 mod migrate;
 mod open_listener;
 mod quick_action_bar;
+pub mod startup_timings;
 #[cfg(target_os = "windows")]
 pub(crate) mod windows_only_instance;
 
@@ -14,7 +17,7 @@ use assets::Assets;
 use assistant2::AssistantDiffToolbar;
 use assistant_context_editor::AssistantPanelDelegate;
 use breadcrumbs::Breadcrumbs;
-use client::{zed_urls, ZED_URL_SCHEME};
+use client::{zed_urls, ProxySettings, ZED_URL_SCHEME};
 use collections::VecDeque;
 use command_palette_hooks::CommandPaletteFilter;
 use debugger_ui::debugger_panel::DebugPanel;
@@ -36,10 +39,10 @@ use migrator::{migrate_keymap, migrate_settings};
 pub use open_listener::*;
 use outline_panel::OutlinePanel;
 use paths::{
-    local_debug_file_relative_path, local_settings_file_relative_path,
-    local_tasks_file_relative_path,
+    local_debug_file_relative_path, local_env_file_relative_path,
+    local_settings_file_relative_path, local_tasks_file_relative_path,
 };
-use project::{DirectoryLister, ProjectItem};
+use project::{DirectoryLister, ProjectItem, SubsystemEnvironment};
 use project_panel::ProjectPanel;
 use prompt_store::PromptBuilder;
 use quick_action_bar::QuickActionBar;
@@ -49,8 +52,8 @@ use rope::Rope;
 use search::project_search::ProjectSearchBar;
 use settings::{
     initial_debug_tasks_content, initial_project_settings_content, initial_tasks_content,
-    update_settings_file, InvalidSettingsError, KeymapFile, KeymapFileLoadResult, Settings,
-    SettingsStore, DEFAULT_KEYMAP_PATH, VIM_KEYMAP_PATH,
+    update_settings_file, InvalidSettingsError, KeymapFile, KeymapFileLoadResult, KeymapLeader,
+    Settings, SettingsStore, DEFAULT_KEYMAP_PATH, VIM_KEYMAP_PATH,
 };
 use std::any::TypeId;
 use std::path::PathBuf;
@@ -80,10 +83,15 @@ actions!(
     zed,
     [
         DebugElements,
+        DiagnoseConnection,
         Hide,
         HideOthers,
+        MergeAllWindows,
         Minimize,
+        MoveTabToNewWindow,
         OpenDefaultSettings,
+        OpenProjectEnvFile,
+        OpenProjectEnvironment,
         OpenProjectSettings,
         OpenProjectTasks,
         OpenProjectDebugTasks,
@@ -91,6 +99,7 @@ actions!(
         OpenDebugTasks,
         ResetDatabase,
         ShowAll,
+        StartupTimings,
         ToggleFullScreen,
         Zoom,
         TestPanic,
@@ -158,6 +167,9 @@ pub fn build_window_options(display_uuid: Option<Uuid>, cx: &mut App) -> WindowO
             width: px(360.0),
             height: px(240.0),
         }),
+        tabbing_identifier: WorkspaceSettings::get_global(cx)
+            .use_system_window_tabs
+            .then(|| app_id.to_owned()),
     }
 }
 
@@ -231,6 +243,12 @@ pub fn initialize_workspace(
         );
         let active_buffer_language =
             cx.new(|_| language_selector::ActiveBufferLanguage::new(workspace));
+        let active_buffer_encoding =
+            cx.new(|_| encoding_selector::ActiveBufferEncoding::new(workspace));
+        let active_buffer_line_ending =
+            cx.new(|_| encoding_selector::ActiveBufferLineEnding::new(workspace));
+        let active_buffer_indentation =
+            cx.new(|_| encoding_selector::ActiveBufferIndentation::new(workspace));
         let active_toolchain_language =
             cx.new(|cx| toolchain_selector::ActiveToolchain::new(workspace, window, cx));
         let vim_mode_indicator = cx.new(|cx| vim::ModeIndicator::new(window, cx));
@@ -241,6 +259,9 @@ pub fn initialize_workspace(
             status_bar.add_left_item(diagnostic_summary, window, cx);
             status_bar.add_left_item(activity_indicator, window, cx);
             status_bar.add_right_item(inline_completion_button, window, cx);
+            status_bar.add_right_item(active_buffer_indentation, window, cx);
+            status_bar.add_right_item(active_buffer_line_ending, window, cx);
+            status_bar.add_right_item(active_buffer_encoding, window, cx);
             status_bar.add_right_item(active_buffer_language, window, cx);
             status_bar.add_right_item(active_toolchain_language, window, cx);
             status_bar.add_right_item(vim_mode_indicator, window, cx);
@@ -259,7 +280,7 @@ pub fn initialize_workspace(
                 .unwrap_or(true)
         });
 
-        initialize_panels(prompt_builder.clone(), window, cx);
+        initialize_panels(prompt_builder.clone(), workspace, window, cx);
         register_actions(app_state.clone(), workspace, window, cx);
 
         workspace.focus_handle(cx).focus(window);
@@ -392,6 +413,7 @@ fn show_software_emulation_warning_if_needed(
 
 fn initialize_panels(
     prompt_builder: Arc<PromptBuilder>,
+    workspace: &mut Workspace,
     window: &mut Window,
     cx: &mut Context<Workspace>,
 ) {
@@ -400,42 +422,29 @@ fn initialize_panels(
 
     let prompt_builder = prompt_builder.clone();
 
+    // Panels that aren't usually visible when a window opens are loaded once they are needed, so
+    // that they don't delay opening it.
+    workspace.add_deferred_panel(OutlinePanel::load, window, cx);
+    workspace.add_deferred_panel(collab_ui::collab_panel::CollabPanel::load, window, cx);
+    workspace.add_deferred_panel(collab_ui::chat_panel::ChatPanel::load, window, cx);
+    workspace.add_deferred_panel(
+        collab_ui::notification_panel::NotificationPanel::load,
+        window,
+        cx,
+    );
+    workspace.add_deferred_panel(collab_ui::review_panel::ReviewPanel::load, window, cx);
+    workspace.add_deferred_panel(notification_center::NotificationCenter::load, window, cx);
+
     cx.spawn_in(window, async move |workspace_handle, cx| {
         let project_panel = ProjectPanel::load(workspace_handle.clone(), cx.clone());
-        let outline_panel = OutlinePanel::load(workspace_handle.clone(), cx.clone());
         let terminal_panel = TerminalPanel::load(workspace_handle.clone(), cx.clone());
-        let channels_panel =
-            collab_ui::collab_panel::CollabPanel::load(workspace_handle.clone(), cx.clone());
-        let chat_panel =
-            collab_ui::chat_panel::ChatPanel::load(workspace_handle.clone(), cx.clone());
-        let notification_panel = collab_ui::notification_panel::NotificationPanel::load(
-            workspace_handle.clone(),
-            cx.clone(),
-        );
+        let (project_panel, terminal_panel) = futures::try_join!(project_panel, terminal_panel)?;
 
-        let (
-            project_panel,
-            outline_panel,
-            terminal_panel,
-            channels_panel,
-            chat_panel,
-            notification_panel,
-        ) = futures::try_join!(
-            project_panel,
-            outline_panel,
-            terminal_panel,
-            channels_panel,
-            chat_panel,
-            notification_panel,
-        )?;
+        startup_timings::record_phase("Load panels");
 
         workspace_handle.update_in(cx, |workspace, window, cx| {
             workspace.add_panel(project_panel, window, cx);
-            workspace.add_panel(outline_panel, window, cx);
             workspace.add_panel(terminal_panel, window, cx);
-            workspace.add_panel(channels_panel, window, cx);
-            workspace.add_panel(chat_panel, window, cx);
-            workspace.add_panel(notification_panel, window, cx);
             cx.when_flag_enabled::<Debugger>(window, |_, window, cx| {
                 cx.spawn_in(
                     window,
@@ -507,7 +516,8 @@ fn initialize_panels(
 
                 workspace
                     .register_action(assistant2::AssistantPanel::toggle_focus)
-                    .register_action(assistant2::InlineAssistant::inline_assist);
+                    .register_action(assistant2::InlineAssistant::inline_assist)
+                    .register_action(assistant2::InlineAssistant::edit_selection);
             } else {
                 <dyn AssistantPanelDelegate>::set_global(
                     Arc::new(assistant::assistant_panel::ConcreteAssistantPanelDelegate),
@@ -539,6 +549,12 @@ fn register_actions(
         .register_action(|_, _: &Zoom, window, _| {
             window.zoom_window();
         })
+        .register_action(|_, _: &MergeAllWindows, window, _| {
+            window.merge_all_windows();
+        })
+        .register_action(|_, _: &MoveTabToNewWindow, window, _| {
+            window.move_tab_to_new_window();
+        })
         .register_action(|_, _: &ToggleFullScreen, window, _| {
             window.toggle_fullscreen();
         })
@@ -625,6 +641,24 @@ fn register_actions(
                 }
             }
         })
+        .register_action({
+            let fs = app_state.fs.clone();
+            move |_, _: &zed_actions::ZoomUiIn, window, cx| {
+                adjust_ui_scale(fs.clone(), window, cx, |scale| Some(scale + UI_SCALE_STEP));
+            }
+        })
+        .register_action({
+            let fs = app_state.fs.clone();
+            move |_, _: &zed_actions::ZoomUiOut, window, cx| {
+                adjust_ui_scale(fs.clone(), window, cx, |scale| Some(scale - UI_SCALE_STEP));
+            }
+        })
+        .register_action({
+            let fs = app_state.fs.clone();
+            move |_, _: &zed_actions::ResetUiZoom, window, cx| {
+                adjust_ui_scale(fs.clone(), window, cx, |_| None);
+            }
+        })
         .register_action({
             let fs = app_state.fs.clone();
             move |_, action: &zed_actions::IncreaseBufferFontSize, _window, cx| {
@@ -713,6 +747,39 @@ fn register_actions(
                 cx,
             );
         })
+        .register_action(|workspace, _: &OpenProjectEnvironment, window, cx| {
+            open_project_environment(workspace, window, cx);
+        })
+        .register_action(|workspace, _: &StartupTimings, window, cx| {
+            open_bundled_file(
+                workspace,
+                startup_timings::report().into(),
+                "Startup Timings",
+                "Markdown",
+                window,
+                cx,
+            );
+        })
+        .register_action(|workspace, _: &DiagnoseConnection, window, cx| {
+            let http = workspace.client().http_client();
+            let ca_certificates = ProxySettings::get_global(cx).ca_certificates.clone();
+            let report =
+                cx.background_spawn(connection_diagnostics::diagnose(http, ca_certificates));
+            cx.spawn_in(window, async move |workspace, cx| {
+                let report = report.await;
+                workspace.update_in(cx, |workspace, window, cx| {
+                    open_bundled_file(
+                        workspace,
+                        report.into(),
+                        "Connection Diagnostics",
+                        "Markdown",
+                        window,
+                        cx,
+                    );
+                })
+            })
+            .detach_and_log_err(cx);
+        })
         .register_action(
             move |workspace: &mut Workspace,
                   _: &zed_actions::OpenTelemetryLog,
@@ -763,6 +830,7 @@ fn register_actions(
         .register_action(open_project_settings_file)
         .register_action(open_project_tasks_file)
         .register_action(open_project_debug_tasks_file)
+        .register_action(open_project_env_file)
         .register_action(
             move |workspace, _: &zed_actions::OpenDefaultKeymap, window, cx| {
                 open_bundled_file(
@@ -1019,6 +1087,40 @@ fn install_cli(
 }
 
 static WAITING_QUIT_CONFIRMATION: AtomicBool = AtomicBool::new(false);
+const UI_SCALE_STEP: f32 = 0.1;
+
+/// Persists a new UI scale for the display the window is on, or clears it when `f` returns
+/// `None`. Windows on displays that can't be identified share the `ui_scale` setting.
+fn adjust_ui_scale(
+    fs: Arc<dyn fs::Fs>,
+    window: &Window,
+    cx: &mut App,
+    f: impl 'static + Send + FnOnce(f32) -> Option<f32>,
+) {
+    let display_uuid = theme::display_uuid(window, cx);
+    update_settings_file::<ThemeSettings>(fs, cx, move |settings, cx| {
+        let scale = ThemeSettings::get_global(cx).ui_scale(display_uuid.as_deref());
+        let new_scale = f(scale).map(|scale| {
+            // Round away the error that repeated steps accumulate.
+            theme::clamp_ui_scale((scale * 100.).round() / 100.)
+        });
+        match display_uuid {
+            Some(display_uuid) => {
+                let display_ui_scales = settings.display_ui_scales.get_or_insert_default();
+                match new_scale {
+                    Some(scale) => {
+                        display_ui_scales.insert(display_uuid, scale);
+                    }
+                    None => {
+                        display_ui_scales.remove(&display_uuid);
+                    }
+                }
+            }
+            None => settings.ui_scale = new_scale,
+        }
+    });
+}
+
 fn quit(_: &Quit, cx: &mut App) {
     if WAITING_QUIT_CONFIRMATION.load(atomic::Ordering::Acquire) {
         return;
@@ -1242,16 +1344,22 @@ pub fn handle_keymap_file_changes(
 ) {
     BaseKeymap::register(cx);
     VimModeSetting::register(cx);
+    WorkspaceSettings::register(cx);
 
     let (base_keymap_tx, mut base_keymap_rx) = mpsc::unbounded();
     let (keyboard_layout_tx, mut keyboard_layout_rx) = mpsc::unbounded();
     let mut old_base_keymap = *BaseKeymap::get_global(cx);
     let mut old_vim_enabled = VimModeSetting::get_global(cx).0;
+    update_key_sequence_settings(cx);
     cx.observe_global::<SettingsStore>(move |cx| {
         let new_base_keymap = *BaseKeymap::get_global(cx);
         let new_vim_enabled = VimModeSetting::get_global(cx).0;
+        let leader_changed = update_key_sequence_settings(cx);
 
-        if new_base_keymap != old_base_keymap || new_vim_enabled != old_vim_enabled {
+        if new_base_keymap != old_base_keymap
+            || new_vim_enabled != old_vim_enabled
+            || leader_changed
+        {
             old_base_keymap = new_base_keymap;
             old_vim_enabled = new_vim_enabled;
             base_keymap_tx.unbounded_send(()).unwrap();
@@ -1328,6 +1436,21 @@ pub fn handle_keymap_file_changes(
     .detach();
 }
 
+/// Applies the key sequence timeout and leader key settings, returning whether the leader key
+/// changed, in which case the keymaps need to be reloaded.
+fn update_key_sequence_settings(cx: &mut App) -> bool {
+    let settings = WorkspaceSettings::get_global(cx);
+    let timeout = Duration::from_millis(settings.key_sequence_timeout);
+    let leader = KeymapLeader(settings.leader_key.clone().map(SharedString::from));
+
+    cx.set_key_sequence_timeout(timeout);
+    if cx.try_global::<KeymapLeader>() == Some(&leader) {
+        return false;
+    }
+    cx.set_global(leader);
+    true
+}
+
 fn show_keymap_file_json_error(
     notification_id: NotificationId,
     error: &anyhow::Error,
@@ -1420,7 +1543,7 @@ fn reload_keymaps(cx: &mut App, user_key_bindings: Vec<KeyBinding>) {
     cx.clear_key_bindings();
     load_default_keymap(cx);
     cx.bind_keys(user_key_bindings);
-    cx.set_menus(app_menus());
+    cx.set_menus(app_menus(cx));
     cx.set_dock_menu(vec![MenuItem::action("New Window", workspace::NewWindow)]);
 }
 
@@ -1527,6 +1650,23 @@ fn open_project_tasks_file(
     )
 }
 
+fn open_project_env_file(
+    workspace: &mut Workspace,
+    _: &OpenProjectEnvFile,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    open_local_file(
+        workspace,
+        local_env_file_relative_path(),
+        "# Environment variables for the terminals, tasks, debug sessions and language servers\n\
+         # spawned in this project, one `KEY=value` per line.\n"
+            .into(),
+        window,
+        cx,
+    )
+}
+
 fn open_project_debug_tasks_file(
     workspace: &mut Workspace,
     _: &OpenProjectDebugTasks,
@@ -1666,6 +1806,68 @@ fn open_telemetry_log_file(
     }).detach();
 }
 
+/// Opens a report of the environment variables that each kind of process spawned in the project's
+/// folders starts with.
+fn open_project_environment(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let project = workspace.project().read(cx);
+    let environment = project.environment().clone();
+    let worktrees = project.visible_worktrees(cx).collect::<Vec<_>>();
+    let tasks = worktrees
+        .into_iter()
+        .map(|worktree| {
+            let root_name = worktree.read(cx).root_name().to_string();
+            let environments = environment.update(cx, |environment, cx| {
+                environment.subsystem_environments(&worktree, cx)
+            });
+            async move { (root_name, environments.await) }
+        })
+        .collect::<Vec<_>>();
+    cx.spawn_in(window, async move |workspace, cx| {
+        let worktree_environments = futures::future::join_all(tasks).await;
+        let report = project_environment_report(worktree_environments);
+        workspace.update_in(cx, |workspace, window, cx| {
+            open_bundled_file(
+                workspace,
+                report.into(),
+                "Project Environment",
+                "Markdown",
+                window,
+                cx,
+            );
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+fn project_environment_report(
+    worktree_environments: Vec<(String, Vec<SubsystemEnvironment>)>,
+) -> String {
+    let mut report = String::from("# Project Environment\n");
+    if worktree_environments.is_empty() {
+        report.push_str("\nThis project has no folders open.\n");
+    }
+    for (root_name, environments) in worktree_environments {
+        report.push_str(&format!("\n## {root_name}\n"));
+        if environments.is_empty() {
+            report.push_str("\nThe environment of remote folders isn't available.\n");
+        }
+        for SubsystemEnvironment { subsystem, env } in environments {
+            report.push_str(&format!("\n### {subsystem}\n\n```sh\n"));
+            let mut env = env.into_iter().collect::<Vec<_>>();
+            env.sort();
+            for (key, value) in env {
+                report.push_str(&format!("{key}={value}\n"));
+            }
+            report.push_str("```\n");
+        }
+    }
+    report
+}
+
 fn open_bundled_file(
     workspace: &Workspace,
     text: Cow<'static, str>,
@@ -4273,6 +4475,7 @@ mod tests {
             language::init(cx);
             editor::init(cx);
             collab_ui::init(&app_state, cx);
+            notification_center::init(cx);
             git_ui::init(cx);
             project_panel::init(cx);
             outline_panel::init(cx);
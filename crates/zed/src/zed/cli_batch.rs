@@ -0,0 +1,332 @@
+//! The `zed format` and `zed check` commands, which run the formatters and language servers
+//! configured in Zed on files without opening them in a window, so that the same configuration
+//! can be used in scripts and pre-commit hooks.
+
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use anyhow::{Context as _, Result};
+use cli::{ipc::IpcSender, CliResponse};
+use collections::{HashMap, HashSet};
+use futures::{channel::mpsc, FutureExt as _, StreamExt as _};
+use gpui::{AppContext as _, AsyncApp, Entity};
+use language::{Buffer, DiagnosticSeverity, Point};
+use project::{
+    lsp_store::{FormatTrigger, LspFormatTarget, OpenLspBufferHandle},
+    Project, ProjectPath,
+};
+use util::ResultExt as _;
+use workspace::AppState;
+
+/// How long language servers can take to start and report diagnostics before the results are
+/// reported as they are.
+const LANGUAGE_SERVER_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long no language server has to report anything for them to be considered done.
+const LANGUAGE_SERVER_QUIET_PERIOD: Duration = Duration::from_secs(2);
+
+/// Formats the files at the given paths and the files in the given directories, or with
+/// `check`, reports the ones that aren't formatted without changing them. Returns the status
+/// that the CLI exits with.
+pub async fn format_paths(
+    paths: Vec<String>,
+    check: bool,
+    env: Option<HashMap<String, String>>,
+    responses: &IpcSender<CliResponse>,
+    app_state: Arc<AppState>,
+    cx: &mut AsyncApp,
+) -> Result<i32> {
+    let project = create_project(&app_state, env, cx)?;
+    let buffers = open_buffers(&project, paths, responses, cx).await?;
+    let _lsp_handles = register_with_language_servers(&project, &buffers, cx)?;
+    wait_for_language_servers(&project, cx).await;
+
+    let transaction = project
+        .update(cx, |project, cx| {
+            project.format(
+                buffers.iter().map(|(buffer, _)| buffer.clone()).collect(),
+                LspFormatTarget::Buffers,
+                false,
+                FormatTrigger::Manual,
+                cx,
+            )
+        })?
+        .await?;
+
+    let mut status = 0;
+    for (buffer, path) in &buffers {
+        let is_formatted = !transaction.0.contains_key(buffer)
+            || !buffer.read_with(cx, |buffer, _| buffer.is_dirty())?;
+        if is_formatted {
+            continue;
+        }
+        if check {
+            send(
+                responses,
+                CliResponse::Stdout {
+                    message: format!("{} is not formatted", path.display()),
+                },
+            );
+            status = 1;
+        } else {
+            project
+                .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))?
+                .await
+                .with_context(|| format!("saving {}", path.display()))?;
+            send(
+                responses,
+                CliResponse::Stdout {
+                    message: format!("formatted {}", path.display()),
+                },
+            );
+        }
+    }
+    Ok(status)
+}
+
+/// Reports the diagnostics that language servers publish for the files at the given paths and
+/// the files in the given directories. Returns the status that the CLI exits with, which is
+/// non-zero if there were any errors.
+pub async fn check_paths(
+    paths: Vec<String>,
+    env: Option<HashMap<String, String>>,
+    responses: &IpcSender<CliResponse>,
+    app_state: Arc<AppState>,
+    cx: &mut AsyncApp,
+) -> Result<i32> {
+    let project = create_project(&app_state, env, cx)?;
+    let buffers = open_buffers(&project, paths, responses, cx).await?;
+    let _lsp_handles = register_with_language_servers(&project, &buffers, cx)?;
+    wait_for_language_servers(&project, cx).await;
+
+    let mut error_count = 0;
+    let mut warning_count = 0;
+    for (buffer, path) in &buffers {
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot())?;
+        for entry in snapshot.diagnostics_in_range::<_, Point>(0..snapshot.len(), false) {
+            let severity = match entry.diagnostic.severity {
+                DiagnosticSeverity::ERROR => {
+                    error_count += 1;
+                    "error"
+                }
+                DiagnosticSeverity::WARNING => {
+                    warning_count += 1;
+                    "warning"
+                }
+                _ => continue,
+            };
+            let source = entry
+                .diagnostic
+                .source
+                .as_ref()
+                .map(|source| format!(" [{source}]"))
+                .unwrap_or_default();
+            send(
+                responses,
+                CliResponse::Stdout {
+                    message: format!(
+                        "{}:{}:{}: {severity}: {}{source}",
+                        path.display(),
+                        entry.range.start.row + 1,
+                        entry.range.start.column + 1,
+                        entry.diagnostic.message,
+                    ),
+                },
+            );
+        }
+    }
+
+    send(
+        responses,
+        CliResponse::Stderr {
+            message: format!(
+                "{error_count} error{}, {warning_count} warning{} in {} file{}",
+                if error_count == 1 { "" } else { "s" },
+                if warning_count == 1 { "" } else { "s" },
+                buffers.len(),
+                if buffers.len() == 1 { "" } else { "s" },
+            ),
+        },
+    );
+    Ok(if error_count > 0 { 1 } else { 0 })
+}
+
+fn create_project(
+    app_state: &AppState,
+    env: Option<HashMap<String, String>>,
+    cx: &mut AsyncApp,
+) -> Result<Entity<Project>> {
+    cx.update(|cx| {
+        Project::local(
+            app_state.client.clone(),
+            app_state.node_runtime.clone(),
+            app_state.user_store.clone(),
+            app_state.languages.clone(),
+            app_state.debug_adapters.clone(),
+            app_state.fs.clone(),
+            env,
+            cx,
+        )
+    })
+}
+
+/// Opens the files at the given paths, and the files in the given directories that aren't
+/// ignored and are in a known language, along with their absolute paths.
+async fn open_buffers(
+    project: &Entity<Project>,
+    paths: Vec<String>,
+    responses: &IpcSender<CliResponse>,
+    cx: &mut AsyncApp,
+) -> Result<Vec<(Entity<Buffer>, PathBuf)>> {
+    let mut project_paths = Vec::new();
+    for path in paths {
+        let (worktree, relative_path) = project
+            .update(cx, |project, cx| {
+                project.find_or_create_worktree(&path, true, cx)
+            })?
+            .await
+            .with_context(|| format!("opening {path}"))?;
+        if let Some(scan_complete) = worktree.read_with(cx, |worktree, _| {
+            worktree.as_local().map(|w| w.scan_complete())
+        })? {
+            scan_complete.await;
+        }
+        worktree.read_with(cx, |worktree, _| {
+            let worktree_id = worktree.id();
+            let is_dir = worktree
+                .entry_for_path(&relative_path)
+                .is_some_and(|entry| entry.is_dir());
+            if is_dir {
+                for entry in worktree.files(false, 0) {
+                    if entry.path.starts_with(&relative_path) {
+                        project_paths.push((
+                            ProjectPath {
+                                worktree_id,
+                                path: entry.path.clone(),
+                            },
+                            worktree.absolutize(&entry.path),
+                            false,
+                        ));
+                    }
+                }
+            } else {
+                project_paths.push((
+                    ProjectPath {
+                        worktree_id,
+                        path: relative_path.clone().into(),
+                    },
+                    worktree.absolutize(&relative_path),
+                    true,
+                ));
+            }
+        })?;
+    }
+
+    let mut buffers = Vec::new();
+    let mut opened = HashSet::default();
+    for (project_path, abs_path, is_explicit) in project_paths {
+        let Some(abs_path) = abs_path.log_err() else {
+            continue;
+        };
+        if !opened.insert(project_path.clone()) {
+            continue;
+        }
+        let buffer = match project
+            .update(cx, |project, cx| project.open_buffer(project_path, cx))?
+            .await
+        {
+            Ok(buffer) => buffer,
+            Err(error) => {
+                // Files that were found in a directory may not be text, and are skipped.
+                if is_explicit {
+                    send(
+                        responses,
+                        CliResponse::Stderr {
+                            message: format!("failed to open {}: {error:#}", abs_path.display()),
+                        },
+                    );
+                }
+                continue;
+            }
+        };
+        if is_explicit || buffer.read_with(cx, |buffer, _| buffer.language().is_some())? {
+            buffers.push((buffer, abs_path));
+        }
+    }
+    Ok(buffers)
+}
+
+fn register_with_language_servers(
+    project: &Entity<Project>,
+    buffers: &[(Entity<Buffer>, PathBuf)],
+    cx: &mut AsyncApp,
+) -> Result<Vec<OpenLspBufferHandle>> {
+    cx.update(|cx| {
+        buffers
+            .iter()
+            .map(|(buffer, _)| {
+                project.update(cx, |project, cx| {
+                    project.register_buffer_with_language_servers(buffer, cx)
+                })
+            })
+            .collect()
+    })
+}
+
+/// Waits for the language servers to start and publish their diagnostics, which is assumed to
+/// be done once none of them have reported anything for a while.
+async fn wait_for_language_servers(project: &Entity<Project>, cx: &mut AsyncApp) {
+    let (tx, mut rx) = mpsc::unbounded();
+    let Some(_subscription) = cx
+        .update(|cx| {
+            cx.subscribe(project, move |_, event, _| {
+                if matches!(
+                    event,
+                    project::Event::LanguageServerAdded(..)
+                        | project::Event::DiskBasedDiagnosticsStarted { .. }
+                        | project::Event::DiskBasedDiagnosticsFinished { .. }
+                        | project::Event::DiagnosticsUpdated { .. }
+                ) {
+                    tx.unbounded_send(()).ok();
+                }
+            })
+        })
+        .log_err()
+    else {
+        return;
+    };
+
+    let mut timeout = cx
+        .background_executor()
+        .timer(LANGUAGE_SERVER_TIMEOUT)
+        .fuse();
+    loop {
+        let mut quiet_period = cx
+            .background_executor()
+            .timer(LANGUAGE_SERVER_QUIET_PERIOD)
+            .fuse();
+        futures::select_biased! {
+            _ = timeout => {
+                log::warn!("timed out waiting for language servers");
+                break;
+            }
+            _ = rx.next() => {}
+            _ = quiet_period => {
+                let is_running = project
+                    .read_with(cx, |project, cx| {
+                        project
+                            .language_servers_running_disk_based_diagnostics(cx)
+                            .next()
+                            .is_some()
+                    })
+                    .unwrap_or(false);
+                if !is_running {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn send(responses: &IpcSender<CliResponse>, response: CliResponse) {
+    responses.send(response).log_err();
+}
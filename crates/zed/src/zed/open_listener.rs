@@ -1,5 +1,6 @@
 use crate::handle_open_request;
 use crate::restorable_workspace_locations;
+use crate::zed::cli_batch;
 use anyhow::{anyhow, Context as _, Result};
 use cli::{ipc, IpcHandshake};
 use cli::{ipc::IpcSender, CliRequest, CliResponse};
@@ -12,11 +13,13 @@ use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender};
 use futures::channel::{mpsc, oneshot};
 use futures::future::join_all;
 use futures::{FutureExt, SinkExt, StreamExt};
+use git_ui::file_diff_view::FileDiffView;
 use gpui::{App, AsyncApp, Global, WindowHandle};
 use language::Point;
 use recent_projects::{open_ssh_project, SshSettings};
 use remote::SshConnectionOptions;
 use settings::Settings;
+use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
@@ -35,6 +38,7 @@ pub struct OpenRequest {
     pub join_channel: Option<u64>,
     pub ssh_connection: Option<SshConnectionOptions>,
     pub dock_menu_action: Option<usize>,
+    pub open_extension: Option<String>,
 }
 
 impl OpenRequest {
@@ -95,11 +99,31 @@ impl OpenRequest {
             }
         }
         self.ssh_connection = Some(connection_options);
+        if url.path().is_empty() {
+            if let Some(project) =
+                SshSettings::get_global(cx).default_project_for(&host, port, username.as_deref())
+            {
+                self.open_paths.extend(project.paths);
+                return Ok(());
+            }
+        }
         self.parse_file_path(url.path());
         Ok(())
     }
 
     fn parse_request_path(&mut self, request_path: &str) -> Result<()> {
+        if let Some(query) = request_path.strip_prefix("open?") {
+            return self.parse_open_query(query);
+        }
+        if let Some(extension_id) = request_path.strip_prefix("extension/") {
+            let extension_id = urlencoding::decode(extension_id.trim_end_matches('/'))?;
+            if extension_id.is_empty() || extension_id.contains('/') {
+                return Err(anyhow!("invalid zed url: {}", request_path));
+            }
+            self.open_extension = Some(extension_id.into_owned());
+            return Ok(());
+        }
+
         let mut parts = request_path.split('/');
         if parts.next() == Some("channel") {
             if let Some(slug) = parts.next() {
@@ -125,6 +149,29 @@ impl OpenRequest {
         }
         Err(anyhow!("invalid zed url: {}", request_path))
     }
+
+    /// Parses the query of a `zed://open?path=<path>&line=<line>&column=<column>` link, where
+    /// the line and column are optional.
+    fn parse_open_query(&mut self, query: &str) -> Result<()> {
+        let mut path = None;
+        let mut line = None;
+        let mut column = None;
+        for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+            match key.as_ref() {
+                "path" => path = Some(value.into_owned()),
+                "line" => line = Some(value.parse::<u32>().context("invalid line")?),
+                "column" => column = Some(value.parse::<u32>().context("invalid column")?),
+                _ => {}
+            }
+        }
+        let path = path.ok_or_else(|| anyhow!("missing path in zed url: open?{query}"))?;
+        self.open_paths.push(match (line, column) {
+            (Some(line), Some(column)) => format!("{path}:{line}:{column}"),
+            (Some(line), None) => format!("{path}:{line}"),
+            _ => path,
+        });
+        Ok(())
+    }
 }
 
 #[derive(Clone)]
@@ -258,6 +305,7 @@ pub async fn handle_cli_connection(
             CliRequest::Open {
                 urls,
                 paths,
+                diff_paths,
                 wait,
                 open_new_workspace,
                 env,
@@ -283,6 +331,20 @@ pub async fn handle_cli_connection(
                     return;
                 }
 
+                if !diff_paths.is_empty() {
+                    let result = open_diffs(diff_paths, wait, &responses, app_state, cx).await;
+                    let status = if result.is_err() { 1 } else { 0 };
+                    if let Err(error) = result {
+                        responses
+                            .send(CliResponse::Stderr {
+                                message: format!("{error:#}"),
+                            })
+                            .log_err();
+                    }
+                    responses.send(CliResponse::Exit { status }).log_err();
+                    return;
+                }
+
                 let open_workspace_result = open_workspaces(
                     paths,
                     open_new_workspace,
@@ -297,10 +359,108 @@ pub async fn handle_cli_connection(
                 let status = if open_workspace_result.is_err() { 1 } else { 0 };
                 responses.send(CliResponse::Exit { status }).log_err();
             }
+            CliRequest::Format { paths, check, env } => {
+                let result =
+                    cli_batch::format_paths(paths, check, env, &responses, app_state, cx).await;
+                finish_batch_command(result, &responses, cx);
+            }
+            CliRequest::Check { paths, env } => {
+                let result = cli_batch::check_paths(paths, env, &responses, app_state, cx).await;
+                finish_batch_command(result, &responses, cx);
+            }
         }
     }
 }
 
+/// Opens a diff view for each pair of old and new files in the active workspace, bringing its
+/// window to the front. With `wait`, returns once all of them have been closed.
+async fn open_diffs(
+    diff_paths: Vec<[String; 2]>,
+    wait: bool,
+    responses: &IpcSender<CliResponse>,
+    app_state: Arc<AppState>,
+    cx: &mut AsyncApp,
+) -> Result<()> {
+    let workspace = workspace::get_any_active_workspace(app_state, cx.clone()).await?;
+    let mut item_release_futures = Vec::new();
+    for [old_path, new_path] in diff_paths {
+        let diff_view = workspace
+            .update(cx, |workspace, window, cx| {
+                window.activate_window();
+                FileDiffView::open(old_path.into(), new_path.into(), workspace, window, cx)
+            })?
+            .await?;
+        cx.update(|cx| {
+            let released = oneshot::channel();
+            diff_view
+                .on_release(
+                    cx,
+                    Box::new(move |_| {
+                        let _ = released.0.send(());
+                    }),
+                )
+                .detach();
+            item_release_futures.push(released.1);
+        })?;
+    }
+
+    if wait {
+        let released = futures::future::try_join_all(item_release_futures).map(|_| ());
+        wait_until_released(released, responses, cx).await;
+    }
+    Ok(())
+}
+
+/// Waits for the given future, which resolves when the items opened by the CLI are closed, as
+/// long as the CLI is still running.
+async fn wait_until_released(
+    released: impl Future<Output = ()>,
+    responses: &IpcSender<CliResponse>,
+    cx: &AsyncApp,
+) {
+    let background = cx.background_executor().clone();
+    let released = released.fuse();
+    futures::pin_mut!(released);
+
+    loop {
+        // Repeatedly check if CLI is still open to avoid wasting resources
+        // waiting for files or workspaces to close.
+        let mut timer = background.timer(Duration::from_secs(1)).fuse();
+        futures::select_biased! {
+            _ = released => break,
+            _ = timer => {
+                if responses.send(CliResponse::Ping).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn finish_batch_command(
+    result: Result<i32>,
+    responses: &IpcSender<CliResponse>,
+    cx: &mut AsyncApp,
+) {
+    let status = result.unwrap_or_else(|error| {
+        responses
+            .send(CliResponse::Stderr {
+                message: format!("{error:#}"),
+            })
+            .log_err();
+        1
+    });
+    responses.send(CliResponse::Exit { status }).log_err();
+
+    // Don't keep Zed running if it was only started to run the command.
+    cx.update(|cx| {
+        if cx.windows().is_empty() {
+            cx.quit();
+        }
+    })
+    .log_err();
+}
+
 async fn open_workspaces(
     paths: Vec<String>,
     open_new_workspace: Option<bool>,
@@ -465,11 +625,11 @@ async fn open_local_workspace(
             }
 
             if wait {
-                let background = cx.background_executor().clone();
+                let mut wait_cx = cx.clone();
                 let wait = async move {
                     if paths_with_position.is_empty() {
                         let (done_tx, done_rx) = oneshot::channel();
-                        let _subscription = workspace.update(cx, |_, _, cx| {
+                        let _subscription = workspace.update(&mut wait_cx, |_, _, cx| {
                             cx.on_release(move |_, _| {
                                 let _ = done_tx.send(());
                             })
@@ -478,24 +638,8 @@ async fn open_local_workspace(
                     } else {
                         let _ = futures::future::try_join_all(item_release_futures).await;
                     };
-                }
-                .fuse();
-
-                futures::pin_mut!(wait);
-
-                loop {
-                    // Repeatedly check if CLI is still open to avoid wasting resources
-                    // waiting for files or workspaces to close.
-                    let mut timer = background.timer(Duration::from_secs(1)).fuse();
-                    futures::select_biased! {
-                        _ = wait => break,
-                        _ = timer => {
-                            if responses.send(CliResponse::Ping).is_err() {
-                                break;
-                            }
-                        }
-                    }
-                }
+                };
+                wait_until_released(wait, responses, cx).await;
             }
         }
         Err(error) => {
@@ -541,7 +685,55 @@ mod tests {
     use util::path;
     use workspace::{AppState, Workspace};
 
-    use crate::zed::{open_listener::open_local_workspace, tests::init_test};
+    use crate::zed::{
+        open_listener::{open_local_workspace, OpenRequest},
+        tests::init_test,
+    };
+
+    #[gpui::test]
+    fn test_parse_deep_links(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let request = cx
+            .update(|cx| {
+                OpenRequest::parse(
+                    vec![
+                        "zed://open?path=%2Froot%2Fsrc%2Fmain.rs&line=12&column=3".into(),
+                        "zed://open?path=/root/README.md&line=4".into(),
+                        "zed://open?path=/root/Cargo.toml".into(),
+                        "zed://channel/2".into(),
+                        "zed://extension/html".into(),
+                    ],
+                    cx,
+                )
+            })
+            .unwrap();
+        assert_eq!(
+            request.open_paths,
+            vec![
+                "/root/src/main.rs:12:3",
+                "/root/README.md:4",
+                "/root/Cargo.toml"
+            ]
+        );
+        assert_eq!(request.join_channel, Some(2));
+        assert_eq!(request.open_extension.as_deref(), Some("html"));
+
+        let request = cx
+            .update(|cx| {
+                OpenRequest::parse(
+                    vec![
+                        "zed://open?line=4".into(),
+                        "zed://open?path=/root/README.md&line=four".into(),
+                        "zed://extension/".into(),
+                    ],
+                    cx,
+                )
+            })
+            .unwrap();
+        assert!(request.open_paths.is_empty());
+        assert_eq!(request.open_extension, None);
+    }
 
     #[gpui::test]
     async fn test_open_workspace_with_directory(cx: &mut TestAppContext) {
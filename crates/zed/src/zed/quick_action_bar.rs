@@ -1,3 +1,4 @@
+mod csv_preview;
 mod markdown_preview;
 mod repl_menu;
 
@@ -415,6 +416,7 @@ impl Render for QuickActionBar {
             .gap(DynamicSpacing::Base04.rems(cx))
             .children(self.render_repl_menu(cx))
             .children(self.render_toggle_markdown_preview(self.workspace.clone(), cx))
+            .children(self.render_toggle_csv_table_view(self.workspace.clone(), cx))
             .children(search_button)
             .when(
                 AssistantSettings::get_global(cx).enabled
@@ -0,0 +1,42 @@
+use csv_preview::{csv_table_view::CsvTableView, ToggleTableView};
+use gpui::{AnyElement, WeakEntity};
+use ui::{prelude::*, IconButtonShape, Tooltip};
+use workspace::Workspace;
+
+use super::QuickActionBar;
+
+impl QuickActionBar {
+    pub fn render_toggle_csv_table_view(
+        &self,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<Self>,
+    ) -> Option<AnyElement> {
+        let mut active_editor_is_csv = false;
+
+        if let Some(workspace) = self.workspace.upgrade() {
+            workspace.update(cx, |workspace, cx| {
+                active_editor_is_csv =
+                    CsvTableView::resolve_active_item_as_csv_editor(workspace, cx).is_some();
+            });
+        }
+
+        if !active_editor_is_csv {
+            return None;
+        }
+
+        let button = IconButton::new("toggle-csv-table-view", IconName::Blocks)
+            .shape(IconButtonShape::Square)
+            .icon_size(IconSize::Small)
+            .style(ButtonStyle::Subtle)
+            .tooltip(Tooltip::for_action_title("Show as Table", &ToggleTableView))
+            .on_click(move |_, window, cx| {
+                if let Some(workspace) = workspace.upgrade() {
+                    workspace.update(cx, |_, cx| {
+                        window.dispatch_action(Box::new(ToggleTableView), cx);
+                    });
+                }
+            });
+
+        Some(button.into_any_element())
+    }
+}
@@ -5,8 +5,8 @@ use gpui::{percentage, Animation, AnimationExt, AnyElement, Entity, Transformati
 use picker::Picker;
 use repl::{
     components::{KernelPickerDelegate, KernelSelector},
-    worktree_id_for_editor, ExecutionState, JupyterSettings, Kernel, KernelSpecification,
-    KernelStatus, Session, SessionSupport,
+    worktree_id_for_editor, CellsToRun, ExecutionState, JupyterSettings, Kernel,
+    KernelSpecification, KernelStatus, Session, SessionSupport,
 };
 use ui::{
     prelude::*, ButtonLike, ContextMenu, IconWithIndicator, Indicator, IntoElement, PopoverMenu,
@@ -145,6 +145,36 @@ impl QuickActionBar {
                             }
                         },
                     )
+                    .custom_entry(
+                        move |_window, _cx| Label::new("Run All Cells").into_any_element(),
+                        {
+                            let editor = editor.clone();
+                            move |window, cx| {
+                                repl::run_cells(editor.clone(), CellsToRun::All, window, cx)
+                                    .log_err();
+                            }
+                        },
+                    )
+                    .custom_entry(
+                        move |_window, _cx| Label::new("Run Cells Above").into_any_element(),
+                        {
+                            let editor = editor.clone();
+                            move |window, cx| {
+                                repl::run_cells(editor.clone(), CellsToRun::Above, window, cx)
+                                    .log_err();
+                            }
+                        },
+                    )
+                    .custom_entry(
+                        move |_window, _cx| Label::new("Run Cells Below").into_any_element(),
+                        {
+                            let editor = editor.clone();
+                            move |window, cx| {
+                                repl::run_cells(editor.clone(), CellsToRun::Below, window, cx)
+                                    .log_err();
+                            }
+                        },
+                    )
                     .custom_entry(
                         move |_window, _cx| {
                             Label::new("Interrupt")
@@ -202,6 +232,20 @@ impl QuickActionBar {
                             }
                         },
                     )
+                    .custom_entry(
+                        move |_window, _cx| {
+                            Label::new("Restart Kernel and Run All Cells")
+                                .size(LabelSize::Small)
+                                .color(Color::Error)
+                                .into_any_element()
+                        },
+                        {
+                            let editor = editor.clone();
+                            move |window, cx| {
+                                repl::restart_and_run_all(editor.clone(), window, cx).log_err();
+                            }
+                        },
+                    )
                     .separator()
                     .action("View Sessions", Box::new(repl::Sessions))
                     // TODO: Add shut down all kernels action
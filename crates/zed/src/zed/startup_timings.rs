@@ -0,0 +1,88 @@
+use std::{
+    fmt::Write as _,
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+static STARTED_AT: LazyLock<Instant> = LazyLock::new(Instant::now);
+static PHASES: Mutex<Vec<(&'static str, Instant)>> = Mutex::new(Vec::new());
+
+/// Starts measuring how long starting up takes. Called first thing in `main`.
+pub fn start() {
+    LazyLock::force(&STARTED_AT);
+}
+
+/// Records that the given phase of starting up finished now. Phases that already finished, for
+/// example because a second window was opened, aren't recorded again.
+pub fn record_phase(name: &'static str) {
+    let finished_at = Instant::now();
+    let mut phases = PHASES.lock();
+    if phases.iter().all(|(phase, _)| *phase != name) {
+        phases.push((name, finished_at));
+    }
+}
+
+/// Describes how long each phase of starting up took, as Markdown.
+pub fn report() -> String {
+    let started_at = *STARTED_AT;
+    let mut phases = PHASES
+        .lock()
+        .iter()
+        .map(|(name, finished_at)| (*name, finished_at.saturating_duration_since(started_at)))
+        .collect::<Vec<_>>();
+    phases.sort_by_key(|(_, finished_at)| *finished_at);
+    format_report(&phases)
+}
+
+fn format_report(phases: &[(&str, Duration)]) -> String {
+    let mut report = String::from("# Startup Timings\n\n");
+    if phases.is_empty() {
+        report.push_str("No startup phases were recorded.\n");
+        return report;
+    }
+
+    report.push_str("| Phase | Duration | Finished At |\n");
+    report.push_str("| --- | ---: | ---: |\n");
+    let mut previous = Duration::ZERO;
+    for (name, finished_at) in phases {
+        writeln!(
+            report,
+            "| {name} | {} | {} |",
+            format_duration(finished_at.saturating_sub(previous)),
+            format_duration(*finished_at)
+        )
+        .ok();
+        previous = *finished_at;
+    }
+    report
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::format_report;
+
+    #[test]
+    fn test_format_report() {
+        let report = format_report(&[
+            ("Load settings", Duration::from_millis(12)),
+            ("Restore workspaces", Duration::from_micros(40_500)),
+        ]);
+        assert_eq!(
+            report,
+            "# Startup Timings\n\n\
+             | Phase | Duration | Finished At |\n\
+             | --- | ---: | ---: |\n\
+             | Load settings | 12.0ms | 12.0ms |\n\
+             | Restore workspaces | 28.5ms | 40.5ms |\n"
+        );
+        assert!(format_report(&[]).contains("No startup phases"));
+    }
+}
@@ -127,6 +127,7 @@ fn send_args_to_instance(args: &Args) -> anyhow::Result<()> {
         CliRequest::Open {
             paths,
             urls,
+            diff_paths: Vec::new(),
             wait: false,
             open_new_workspace: None,
             env: None,
@@ -37,6 +37,10 @@ actions!(
         About,
         OpenLicenses,
         OpenTelemetryLog,
+        TogglePerformanceOverlay,
+        ZoomUiIn,
+        ZoomUiOut,
+        ResetUiZoom,
     ]
 );
 
@@ -59,6 +63,9 @@ pub struct Extensions {
     /// Filters the extensions page down to extensions that are in the specified category.
     #[serde(default)]
     pub category_filter: Option<ExtensionCategoryFilter>,
+    /// Searches the extensions page for the extension with the given ID.
+    #[serde(default)]
+    pub id: Option<String>,
 }
 
 #[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema)]
@@ -134,6 +141,12 @@ pub mod workspace {
     );
 }
 
+pub mod debug {
+    use gpui::actions;
+
+    actions!(debug, [OpenLanguageServerLogs]);
+}
+
 pub mod git {
     use gpui::{action_with_deprecated_aliases, actions};
 